@@ -0,0 +1,348 @@
+use crate::browser::BrowserApp;
+use crate::dom::{Document, Element, Node};
+use crate::image::RgbImage;
+use crate::layout::ElementGeometry;
+use crate::platform::SoftwarePainter;
+use crate::render::Viewport;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(15);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Drives a [`BrowserApp`] from code, with no window or event loop: each call ticks and paints
+/// the page in memory with the software backend until what it waits for has happened.
+/// Selectors are CSS selector lists and act on the first matching element.
+pub struct Agent {
+    app: BrowserApp,
+    painter: SoftwarePainter,
+    viewport: Viewport,
+    /// How long a call waits for the page before failing.
+    timeout: Duration,
+    /// Whether something changed since the page was last painted.
+    stale: bool,
+}
+
+impl Agent {
+    /// Starts on an empty page shown at `width_px` by `height_px`.
+    pub fn new(width_px: i32, height_px: i32) -> Result<Agent, String> {
+        let viewport = Viewport {
+            width_px,
+            height_px,
+        };
+        crate::css_media::set_device_pixel_ratio(1.0);
+        let mut app = BrowserApp::from_html("", "")?;
+        app.set_chrome_visible(false);
+        app.set_element_boxes(true);
+        Ok(Agent {
+            app,
+            painter: SoftwarePainter::new(viewport)?,
+            viewport,
+            timeout: DEFAULT_TIMEOUT,
+            stale: true,
+        })
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Agent {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn app(&self) -> &BrowserApp {
+        &self.app
+    }
+
+    /// Shows pages as the article found in them, as [`BrowserApp::set_reader_mode`] does. The
+    /// document selectors act on stays the page's own.
+    pub fn set_reader_mode(&mut self, enabled: bool) {
+        self.app.set_reader_mode(enabled);
+        self.stale = true;
+    }
+
+    /// Opens `location`, a URL or file path, and waits until it and its stylesheets and images
+    /// have loaded.
+    pub fn navigate(&mut self, location: &str) -> Result<(), String> {
+        self.app.navigate(location)?;
+        self.stale = true;
+        self.wait_for_load()
+    }
+
+    pub fn wait_for_load(&mut self) -> Result<(), String> {
+        self.wait_until("the page to load", |_, ready| ready)
+    }
+
+    /// Waits until an element matches `selector`, as one added by a script or a page still
+    /// loading would.
+    pub fn wait_for_selector(&mut self, selector: &str) -> Result<(), String> {
+        self.wait_until(selector, |document, _| {
+            document.query_selector(selector).is_some()
+        })
+    }
+
+    /// Follows the link the element is or sits inside, then waits for the new page to load.
+    pub fn click(&mut self, selector: &str) -> Result<(), String> {
+        let path = self.find(selector)?;
+        let document = self.app.document();
+        let link = (0..=path.len())
+            .rev()
+            .filter_map(|depth| document.element_at(&path[..depth]))
+            .find_map(link_target)
+            .ok_or_else(|| format!("Nothing to click at {selector}: it is not inside a link"))?;
+        self.app.follow_link(&link.0, link.1)?;
+        self.stale = true;
+        self.wait_for_load()
+    }
+
+    /// Appends `text` to the value of an `<input>` or the contents of a `<textarea>`.
+    pub fn type_text(&mut self, selector: &str, text: &str) -> Result<(), String> {
+        let path = self.find(selector)?;
+        let document = self.app.document_mut();
+        let Some(element) = document.element_at(&path) else {
+            return Err(format!("No element matches {selector}"));
+        };
+        match element.name.as_str() {
+            "input" => {
+                let value = element.attributes.get("value").unwrap_or_default();
+                let value = format!("{value}{text}");
+                document.set_attribute(&path, "value", value);
+            }
+            "textarea" => {
+                let contents = format!("{}{text}", raw_text(element));
+                document.set_text_content(&path, contents);
+            }
+            name => return Err(format!("Cannot type into <{name}> at {selector}")),
+        }
+        self.stale = true;
+        self.settle()
+    }
+
+    /// Scrolls so the top of the element is at the top of the viewport, as far as the page
+    /// allows. An element without a box of its own, such as an empty `<span>`, scrolls to the
+    /// nearest box around it.
+    pub fn scroll_to(&mut self, selector: &str) -> Result<(), String> {
+        let path = self.find(selector)?;
+        self.settle()?;
+        let document = self.app.document();
+        let (is_fixed, y_px) = (1..=path.len())
+            .rev()
+            .filter_map(|depth| document.element_at(&path[..depth]))
+            .find_map(|element| self.app.element_box(element))
+            .map(|inspected| (inspected.is_fixed, inspected.border_box.y))
+            .ok_or_else(|| format!("{selector} is not rendered"))?;
+        if !is_fixed {
+            self.app.set_scroll_y_px(y_px);
+            self.stale = true;
+        }
+        self.settle()
+    }
+
+    /// Where the element was laid out, or `None` if it is hidden. The middle of its
+    /// `viewport_box` is a point to click when `in_viewport`.
+    pub fn geometry(&mut self, selector: &str) -> Result<Option<ElementGeometry>, String> {
+        self.find(selector)?;
+        self.settle()?;
+        Ok(self
+            .app
+            .element_geometry(selector)
+            .into_iter()
+            .next()
+            .flatten())
+    }
+
+    /// Writes what the viewport shows as a PNG.
+    pub fn screenshot(&mut self, path: &Path) -> Result<(), String> {
+        crate::png::write_rgb_png(path, &self.capture()?)
+    }
+
+    /// Writes the page's icon as a PNG, keeping its transparency. Returns the icon's size, or
+    /// `None` without writing anything when the page has no icon.
+    pub fn save_favicon(&mut self, path: &Path) -> Result<Option<(u32, u32)>, String> {
+        self.settle()?;
+        let Some(icon) = self.app.favicon() else {
+            return Ok(None);
+        };
+        crate::png::write_rgba_png(path, &icon, crate::png::PngOptions::default())?;
+        Ok(Some((icon.width, icon.height)))
+    }
+
+    /// What the viewport shows.
+    pub fn capture(&mut self) -> Result<RgbImage, String> {
+        self.settle()?;
+        self.painter.capture_back_buffer_rgb()
+    }
+
+    /// The element's text with runs of whitespace collapsed, leaving out scripts and styles.
+    pub fn read_text(&self, selector: &str) -> Result<String, String> {
+        let element = self
+            .app
+            .document()
+            .query_selector(selector)
+            .ok_or_else(|| format!("No element matches {selector}"))?;
+        Ok(visible_text(element))
+    }
+
+    fn find(&self, selector: &str) -> Result<Vec<usize>, String> {
+        self.app
+            .document()
+            .query_selector_path(selector)
+            .ok_or_else(|| format!("No element matches {selector}"))
+    }
+
+    /// Lets the page take in what just changed and paints it.
+    fn settle(&mut self) -> Result<(), String> {
+        let tick = self.app.tick()?;
+        self.stale |= tick.needs_redraw;
+        self.paint()
+    }
+
+    fn paint(&mut self) -> Result<(), String> {
+        if self.stale {
+            self.painter.ensure_back_buffer(self.viewport)?;
+            self.app.render(&mut self.painter, self.viewport)?;
+            self.stale = false;
+        }
+        Ok(())
+    }
+
+    /// Ticks and paints until `done`, given the document and whether the page and everything
+    /// it references have loaded, returns true.
+    fn wait_until(
+        &mut self,
+        what: &str,
+        mut done: impl FnMut(&Document, bool) -> bool,
+    ) -> Result<(), String> {
+        let started = Instant::now();
+        loop {
+            let tick = self.app.tick()?;
+            self.stale |= tick.needs_redraw;
+            // Images are only asked for once layout finds them, so a tick that came before a
+            // paint cannot tell whether everything has loaded.
+            let painting = self.stale;
+            self.paint()?;
+            let loaded = tick.ready_for_screenshot && tick.pending_resources == 0;
+            if !painting && done(self.app.document(), loaded) {
+                return Ok(());
+            }
+            if started.elapsed() >= self.timeout {
+                return Err(format!(
+                    "Timed out after {} ms waiting for {what}",
+                    self.timeout.as_millis()
+                ));
+            }
+            if !painting {
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// The `href` of a link element, and whether it opens in a new tab.
+fn link_target(element: &Element) -> Option<(String, bool)> {
+    if element.name != "a" {
+        return None;
+    }
+    let href = element.attributes.get("href")?.trim();
+    if href.is_empty() {
+        return None;
+    }
+    let new_tab = element
+        .attributes
+        .get("target")
+        .is_some_and(|target| target.trim().eq_ignore_ascii_case("_blank"));
+    Some((href.to_owned(), new_tab))
+}
+
+fn raw_text(element: &Element) -> String {
+    element
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Node::Text(text) => Some(text.as_str()),
+            Node::Element(_) => None,
+        })
+        .collect()
+}
+
+/// `element`'s text as [`Agent::read_text`] gives it.
+pub fn visible_text(element: &Element) -> String {
+    let mut text = String::new();
+    collect_visible_text(element, &mut text);
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn collect_visible_text(element: &Element, out: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Text(text) => {
+                out.push_str(text);
+                out.push(' ');
+            }
+            Node::Element(child) if matches!(child.name.as_str(), "script" | "style") => {}
+            Node::Element(child) => collect_visible_text(child, out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Agent;
+
+    #[test]
+    fn drives_pages_from_files_without_a_window() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("one-agent-one-browser-agent-{nanos}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("index.html"),
+            r#"<html><head><link rel="icon" href="icon.png"></head><body>
+                <h1>Start</h1>
+                <input id="q" value="one">
+                <div style="height: 2000px"></div>
+                <p id="far">Far <b>down</b><script>ignored()</script></p>
+                <p><a id="next" href="next.html"><span>Next</span></a></p>
+            </body></html>"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("next.html"), "<p id=done>Arrived</p>").unwrap();
+        let icon = crate::image::RgbImage::new(2, 3, vec![200; 18]).unwrap();
+        crate::png::write_rgb_png(&dir.join("icon.png"), &icon).unwrap();
+
+        let mut agent = Agent::new(400, 300).unwrap();
+        agent
+            .navigate(dir.join("index.html").to_str().unwrap())
+            .unwrap();
+        agent.wait_for_selector("h1").unwrap();
+        assert_eq!(agent.read_text("#far").unwrap(), "Far down");
+        assert_eq!(
+            agent.save_favicon(&dir.join("favicon.png")).unwrap(),
+            Some((2, 3))
+        );
+
+        agent.type_text("#q", " two").unwrap();
+        assert_eq!(
+            agent
+                .app()
+                .document()
+                .query_selector("#q")
+                .and_then(|input| input.attributes.get("value")),
+            Some("one two")
+        );
+        assert!(agent.type_text("h1", "x").is_err());
+
+        agent.scroll_to("#far").unwrap();
+        assert!(agent.app().scroll_y_px() > 1000);
+
+        let shot = dir.join("shot.png");
+        agent.screenshot(&shot).unwrap();
+        assert!(std::fs::metadata(&shot).unwrap().len() > 0);
+
+        assert!(agent.click("h1").is_err());
+        agent.click("#next span").unwrap();
+        assert_eq!(agent.read_text("#done").unwrap(), "Arrived");
+        assert_eq!(agent.save_favicon(&dir.join("none.png")).unwrap(), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,4 +1,6 @@
+use crate::image::Argb32Image;
 use crate::render::{Painter, Viewport};
+use std::sync::Arc;
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct TickResult {
@@ -7,6 +9,88 @@ pub struct TickResult {
     pub pending_resources: usize,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Backspace,
+    Delete,
+    Enter,
+    Escape,
+    Tab,
+    Left,
+    Right,
+    Home,
+    End,
+    F12,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyInput {
+    pub key: Key,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl KeyInput {
+    pub fn plain(key: Key) -> KeyInput {
+        KeyInput {
+            key,
+            ctrl: false,
+            alt: false,
+        }
+    }
+
+    /// Ctrl+V (Cmd+V on macOS). Platform backends check this before offering the key to the app,
+    /// and paste the clipboard's text through `App::paste_text` instead.
+    pub fn is_paste(self) -> bool {
+        self.ctrl && !self.alt && matches!(self.key, Key::Char('v' | 'V'))
+    }
+}
+
+/// Zoom steps offered by the keyboard shortcuts, in percent of the platform scale.
+pub const ZOOM_LEVELS_PERCENT: [u32; 13] =
+    [30, 50, 67, 80, 90, 100, 110, 125, 150, 175, 200, 250, 300];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZoomCommand {
+    In,
+    Out,
+    Reset,
+}
+
+impl ZoomCommand {
+    /// Ctrl+= (or Ctrl++), Ctrl+- and Ctrl+0. Platform backends check this before offering the
+    /// key to the app.
+    pub fn from_key(input: KeyInput) -> Option<ZoomCommand> {
+        if !input.ctrl || input.alt {
+            return None;
+        }
+        match input.key {
+            Key::Char('=' | '+') => Some(ZoomCommand::In),
+            Key::Char('-' | '_') => Some(ZoomCommand::Out),
+            Key::Char('0') => Some(ZoomCommand::Reset),
+            _ => None,
+        }
+    }
+
+    pub fn apply(self, percent: u32) -> u32 {
+        match self {
+            ZoomCommand::In => ZOOM_LEVELS_PERCENT
+                .iter()
+                .copied()
+                .find(|&level| level > percent)
+                .unwrap_or(percent),
+            ZoomCommand::Out => ZOOM_LEVELS_PERCENT
+                .iter()
+                .rev()
+                .copied()
+                .find(|&level| level < percent)
+                .unwrap_or(percent),
+            ZoomCommand::Reset => 100,
+        }
+    }
+}
+
 pub trait App {
     fn tick(&mut self) -> Result<TickResult, String>;
     fn render(&mut self, painter: &mut dyn Painter, viewport: Viewport) -> Result<(), String>;
@@ -15,6 +99,10 @@ pub trait App {
         Ok(TickResult::default())
     }
 
+    fn navigate_forward(&mut self) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
     fn mouse_down(
         &mut self,
         _x_px: i32,
@@ -24,7 +112,146 @@ pub trait App {
         Ok(TickResult::default())
     }
 
+    /// The primary button was released, after a `mouse_down`.
+    fn mouse_up(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
+    /// Scrolls by `delta_y_px` at once, as for the pixel-exact deltas of a touchpad.
     fn mouse_wheel(&mut self, _delta_y_px: i32, _viewport: Viewport) -> Result<TickResult, String> {
         Ok(TickResult::default())
     }
+
+    /// Scrolls sideways by `delta_x_px`, positive to the right, for a tilted wheel, a touchpad's
+    /// horizontal motion, or Shift with the wheel.
+    fn horizontal_wheel(
+        &mut self,
+        _delta_x_px: i32,
+        _viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
+    /// A mouse wheel notch worth `delta_y_px`, which the app may glide through over a few ticks
+    /// rather than jump.
+    fn wheel_notch(&mut self, delta_y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
+        self.mouse_wheel(delta_y_px, viewport)
+    }
+
+    /// The fingers left the touchpad while scrolling at `velocity_y_px_per_s`, so the page may
+    /// coast on and slow to a stop.
+    fn scroll_fling(
+        &mut self,
+        _velocity_y_px_per_s: f64,
+        _viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
+    /// Pointer motion over the window, in the same coordinates as `mouse_down`.
+    fn mouse_move(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
+    /// Returns `None` when the key is not consumed, leaving the platform's default binding
+    /// (Backspace navigates back, Escape closes the window) in effect.
+    fn key_down(
+        &mut self,
+        _input: KeyInput,
+        _viewport: Viewport,
+    ) -> Result<Option<TickResult>, String> {
+        Ok(None)
+    }
+
+    /// Text pasted from the clipboard, or on Linux from the primary selection by a middle click,
+    /// for whatever has the keyboard focus.
+    fn paste_text(&mut self, _text: &str, _viewport: Viewport) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
+    /// Data dropped onto the window: a `text/uri-list` of files or links, or plain text.
+    fn open_dropped(&mut self, _dropped: &str, _viewport: Viewport) -> Result<TickResult, String> {
+        Ok(TickResult::default())
+    }
+
+    /// Page zoom in percent. Backends multiply their device scale by it, so the page is laid out
+    /// again against a smaller or larger CSS viewport rather than having its pixels stretched.
+    fn zoom_percent(&self) -> u32 {
+        100
+    }
+
+    fn set_zoom_percent(&mut self, _percent: u32) {}
+
+    /// Height of the laid-out page, or `None` before the first render. Full-page screenshots
+    /// scroll through this much of it.
+    fn document_height_px(&self) -> Option<i32> {
+        None
+    }
+
+    fn scroll_y_px(&self) -> i32 {
+        0
+    }
+
+    /// Takes effect on the next render, which clamps it to the page.
+    fn set_scroll_y_px(&mut self, _y_px: i32) {}
+
+    /// What the window's title bar should read, polled after each tick. `None` keeps the title
+    /// the window was opened with.
+    fn window_title(&self) -> Option<String> {
+        None
+    }
+
+    /// The page's icon for the title bar and taskbar, once it has loaded.
+    fn window_icon(&self) -> Option<Arc<Argb32Image>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zoom_steps_through_levels_and_stops_at_the_ends() {
+        let ctrl = |ch| KeyInput {
+            key: Key::Char(ch),
+            ctrl: true,
+            alt: false,
+        };
+        assert_eq!(ZoomCommand::from_key(ctrl('=')), Some(ZoomCommand::In));
+        assert_eq!(ZoomCommand::from_key(KeyInput::plain(Key::Char('='))), None);
+        assert_eq!(ZoomCommand::In.apply(100), 110);
+        assert_eq!(ZoomCommand::Out.apply(100), 90);
+        assert_eq!(ZoomCommand::In.apply(300), 300);
+        assert_eq!(ZoomCommand::Out.apply(30), 30);
+        assert_eq!(ZoomCommand::Reset.apply(250), 100);
+    }
+
+    #[test]
+    fn ctrl_v_pastes() {
+        let ctrl_v = KeyInput {
+            key: Key::Char('v'),
+            ctrl: true,
+            alt: false,
+        };
+        assert!(ctrl_v.is_paste());
+        assert!(!KeyInput::plain(Key::Char('v')).is_paste());
+        assert!(
+            !KeyInput {
+                alt: true,
+                ..ctrl_v
+            }
+            .is_paste()
+        );
+    }
 }
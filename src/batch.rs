@@ -0,0 +1,49 @@
+/// Longest part of an output file name taken from the URL.
+const MAX_NAME_CHARS: usize = 80;
+
+/// The URLs of a `--batch` list: one per line, skipping blank lines and `#` comments.
+pub fn parse_url_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_owned)
+        .collect()
+}
+
+/// The file name, without extension, that the `index`th URL of a batch is written under: the
+/// position keeps names unique and in list order, and the URL without its scheme says what the
+/// file shows.
+pub fn output_stem(index: usize, url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let mut name = String::new();
+    for ch in rest.chars() {
+        if name.chars().count() == MAX_NAME_CHARS {
+            break;
+        }
+        if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_') {
+            name.push(ch);
+        } else if !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    let name = name.trim_matches('_');
+    format!("{:03}-{name}", index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{output_stem, parse_url_list};
+
+    #[test]
+    fn url_lists_skip_comments_and_names_stay_file_safe() {
+        assert_eq!(
+            parse_url_list("# pages\nhttps://example.com/\n\n  http://a.test/x?y=1  \n"),
+            ["https://example.com/", "http://a.test/x?y=1"]
+        );
+        assert_eq!(output_stem(0, "https://example.com/"), "001-example.com");
+        assert_eq!(
+            output_stem(11, "http://a.test:8080/x/../y?q=a b#top"),
+            "012-a.test_8080_x_.._y_q_a_b_top"
+        );
+    }
+}
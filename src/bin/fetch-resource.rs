@@ -9,7 +9,7 @@ fn main() -> ExitCode {
         Err(err) => {
             eprintln!("{err}\n");
             eprintln!("Usage: fetch-resource <url> [--out <path>]");
-            eprintln!("Fetches a URL using the browser's libcurl wrapper, prints basic info,");
+            eprintln!("Fetches a URL using the browser's HTTP backend, prints basic info,");
             eprintln!("and optionally writes the response bytes to disk.");
             return ExitCode::from(2);
         }
@@ -0,0 +1,60 @@
+use one_agent_one_browser::fuzz;
+use std::path::{Path, PathBuf};
+
+fn main() -> std::process::ExitCode {
+    match run() {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::ExitCode::from(1)
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let names = fuzz::TARGETS
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join("|");
+    let [target_name, paths @ ..] = args.as_slice() else {
+        return Err(format!("Usage: fuzz-replay <{names}> <file-or-dir>..."));
+    };
+    if paths.is_empty() {
+        return Err(format!("Usage: fuzz-replay <{names}> <file-or-dir>..."));
+    }
+    let target = fuzz::target(target_name)
+        .ok_or_else(|| format!("Unknown fuzz target {target_name:?} (expected {names})"))?;
+
+    let mut inputs = Vec::new();
+    for path in paths {
+        collect_inputs(Path::new(path), &mut inputs)?;
+    }
+    inputs.sort();
+
+    for input in &inputs {
+        let data = std::fs::read(input)
+            .map_err(|err| format!("Failed to read {}: {err}", input.display()))?;
+        // The default panic hook has already printed the message; name the input that caused it.
+        if std::panic::catch_unwind(|| target(&data)).is_err() {
+            return Err(format!("{target_name}: panicked on {}", input.display()));
+        }
+    }
+    println!("{target_name}: {} input(s) ok", inputs.len());
+    Ok(())
+}
+
+fn collect_inputs(path: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !path.is_dir() {
+        out.push(path.to_owned());
+        return Ok(());
+    }
+    let entries = std::fs::read_dir(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        collect_inputs(&entry.path(), out)?;
+    }
+    Ok(())
+}
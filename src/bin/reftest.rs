@@ -0,0 +1,336 @@
+use one_agent_one_browser::agent::Agent;
+use one_agent_one_browser::html;
+use one_agent_one_browser::image::RgbImage;
+use one_agent_one_browser::reftest::{self, ImageDiff, Relation};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+  reftest <test.html> <ref.html> [--mismatch] [options]
+  reftest --suite <dir> [options]
+
+Renders pages headlessly with the software backend and compares their pixels. With --suite,
+every HTML file under <dir> with a <link rel=\"match\" href=...> or <link rel=\"mismatch\"
+href=...> is a test, compared with each reference it names.
+
+Options:
+  --width <px>        viewport width (default 800)
+  --height <px>       viewport height (default 600)
+  --tolerance <0-255> how far apart a channel may be before its pixel differs (default 0)
+  --max-pixels <n>    how many pixels may differ for the pages to match (default 0)";
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args_os().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n");
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let output_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("target")
+        .join("reftest");
+    let mut agent = match Agent::new(args.width_px, args.height_px) {
+        Ok(agent) => agent,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let cases = match &args.mode {
+        Mode::Pair {
+            test,
+            reference,
+            relation,
+        } => vec![Case {
+            test: test.clone(),
+            references: vec![(*relation, reference.clone())],
+        }],
+        Mode::Suite(dir) => match find_suite(dir) {
+            Ok(cases) => cases,
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::from(2);
+            }
+        },
+    };
+    if cases.is_empty() {
+        eprintln!("No reftests found");
+        return ExitCode::from(2);
+    }
+
+    let mut passed = 0usize;
+    let mut failed = 0usize;
+    for case in &cases {
+        for (relation, reference) in &case.references {
+            match run_case(
+                &mut agent,
+                &args,
+                &output_dir,
+                &case.test,
+                reference,
+                *relation,
+            ) {
+                Ok(()) => passed += 1,
+                Err(err) => {
+                    failed += 1;
+                    eprintln!("{err}");
+                }
+            }
+        }
+    }
+
+    println!("Summary: {passed} passed, {failed} failed");
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+#[derive(Debug)]
+enum Mode {
+    Pair {
+        test: PathBuf,
+        reference: PathBuf,
+        relation: Relation,
+    },
+    Suite(PathBuf),
+}
+
+#[derive(Debug)]
+struct Args {
+    mode: Mode,
+    width_px: i32,
+    height_px: i32,
+    tolerance: u8,
+    max_pixels: u64,
+}
+
+/// A test page and the references it is compared with.
+struct Case {
+    test: PathBuf,
+    references: Vec<(Relation, PathBuf)>,
+}
+
+fn parse_args(args: impl Iterator<Item = OsString>) -> Result<Args, String> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    let mut suite: Option<PathBuf> = None;
+    let mut mismatch = false;
+    let mut width_px = 800i32;
+    let mut height_px = 600i32;
+    let mut tolerance = 0u8;
+    let mut max_pixels = 0u64;
+
+    let mut it = args;
+    while let Some(arg) = it.next() {
+        let Some(flag) = arg.to_str() else {
+            paths.push(PathBuf::from(arg));
+            continue;
+        };
+        match flag {
+            "--suite" => {
+                let dir = it
+                    .next()
+                    .ok_or_else(|| "Missing value for --suite".to_owned())?;
+                suite = Some(PathBuf::from(dir));
+            }
+            "--mismatch" => mismatch = true,
+            "--width" => width_px = parse_value(it.next(), "--width")?,
+            "--height" => height_px = parse_value(it.next(), "--height")?,
+            "--tolerance" => tolerance = parse_value(it.next(), "--tolerance")?,
+            "--max-pixels" => max_pixels = parse_value(it.next(), "--max-pixels")?,
+            _ if flag.starts_with('-') => return Err(format!("Unknown flag: {flag}")),
+            _ => paths.push(PathBuf::from(flag)),
+        }
+    }
+
+    if width_px <= 0 || height_px <= 0 {
+        return Err(format!("Invalid viewport size: {width_px}x{height_px}"));
+    }
+    let mode = match suite {
+        Some(_) if mismatch => {
+            return Err("--mismatch is read from the tests of a --suite".to_owned());
+        }
+        Some(dir) if paths.is_empty() => Mode::Suite(dir),
+        None if paths.len() == 2 => {
+            let reference = paths.pop().unwrap_or_default();
+            let test = paths.pop().unwrap_or_default();
+            Mode::Pair {
+                test,
+                reference,
+                relation: if mismatch {
+                    Relation::Mismatch
+                } else {
+                    Relation::Match
+                },
+            }
+        }
+        _ => return Err("Expected <test.html> <ref.html>, or --suite <dir>".to_owned()),
+    };
+    Ok(Args {
+        mode,
+        width_px,
+        height_px,
+        tolerance,
+        max_pixels,
+    })
+}
+
+fn parse_value<T: std::str::FromStr>(value: Option<OsString>, flag: &str) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("Missing value for {flag}"))?;
+    let value = value.to_string_lossy();
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid {flag} value: {value}"))
+}
+
+/// The tests under `dir`, in path order, with their references resolved against each test's
+/// directory.
+fn find_suite(dir: &Path) -> Result<Vec<Case>, String> {
+    let mut pages = Vec::new();
+    collect_html_files(dir, &mut pages)?;
+    pages.sort();
+
+    let mut cases = Vec::new();
+    for page in pages {
+        let bytes = std::fs::read(&page)
+            .map_err(|err| format!("Failed to read {}: {err}", page.display()))?;
+        let document = html::parse_document_bytes(&bytes);
+        let references: Vec<(Relation, PathBuf)> = reftest::references(&document)
+            .into_iter()
+            .map(|reference| {
+                let href = reference.href.split(['#', '?']).next().unwrap_or_default();
+                let base = page.parent().unwrap_or_else(|| Path::new("."));
+                (reference.relation, base.join(href))
+            })
+            .collect();
+        if !references.is_empty() {
+            cases.push(Case {
+                test: page,
+                references,
+            });
+        }
+    }
+    Ok(cases)
+}
+
+fn collect_html_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| format!("Failed to read {}: {err}", dir.display()))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("Failed to read {}: {err}", dir.display()))?
+            .path();
+        if path.is_dir() {
+            collect_html_files(&path, out)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"))
+        {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn capture(agent: &mut Agent, page: &Path) -> Result<RgbImage, String> {
+    agent
+        .navigate(&page.to_string_lossy())
+        .and_then(|()| agent.capture())
+        .map_err(|err| format!("Failed to render {}: {err}", page.display()))
+}
+
+fn run_case(
+    agent: &mut Agent,
+    args: &Args,
+    output_dir: &Path,
+    test: &Path,
+    reference: &Path,
+    relation: Relation,
+) -> Result<(), String> {
+    let (sign, label) = match relation {
+        Relation::Match => ("==", "match"),
+        Relation::Mismatch => ("!=", "mismatch"),
+    };
+    let name = format!("{} {sign} {}", test.display(), reference.display());
+    let fail = |detail: String| format!("FAIL {name}\n{detail}");
+
+    let test_image = capture(agent, test).map_err(fail)?;
+    let reference_image = capture(agent, reference).map_err(fail)?;
+    let diff = reftest::compare(&test_image, &reference_image, args.tolerance).map_err(fail)?;
+    let matches = diff.differing_pixels <= args.max_pixels;
+    if matches == (relation == Relation::Match) {
+        println!("PASS {name}");
+        return Ok(());
+    }
+
+    let mut detail = format!(
+        "Expected a {label}: {} / {} pixels differ (max {}), largest channel difference {} (tolerance {})\nBBox:       {}\n",
+        diff.differing_pixels,
+        diff.total_pixels,
+        args.max_pixels,
+        diff.max_channel_difference,
+        args.tolerance,
+        format_bounds(&diff),
+    );
+    match write_failure(
+        output_dir,
+        test,
+        &test_image,
+        &reference_image,
+        args.tolerance,
+    ) {
+        Ok(paths) => {
+            for (what, path) in paths {
+                detail.push_str(&format!("{what:<12}{}\n", path.display()));
+            }
+        }
+        Err(err) => detail.push_str(&format!("{err}\n")),
+    }
+    Err(fail(detail))
+}
+
+fn format_bounds(diff: &ImageDiff) -> String {
+    match diff.bounds {
+        Some((min_x, min_y, max_x, max_y)) => format!(
+            "x={min_x}..{max_x} y={min_y}..{max_y} ({}x{})",
+            max_x - min_x + 1,
+            max_y - min_y + 1
+        ),
+        None => "none".to_owned(),
+    }
+}
+
+/// Writes both captures and their difference next to each other for a look at what went wrong.
+fn write_failure(
+    output_dir: &Path,
+    test: &Path,
+    test_image: &RgbImage,
+    reference_image: &RgbImage,
+    tolerance: u8,
+) -> Result<Vec<(&'static str, PathBuf)>, String> {
+    std::fs::create_dir_all(output_dir)
+        .map_err(|err| format!("Failed to create {}: {err}", output_dir.display()))?;
+    let stem = test
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "test".to_owned());
+    let diff = reftest::diff_image(test_image, reference_image, tolerance)?;
+    let mut written = Vec::new();
+    for (what, suffix, image) in [
+        ("Test PNG:", "test", test_image),
+        ("Ref PNG:", "ref", reference_image),
+        ("Diff PNG:", "diff", &diff),
+    ] {
+        let path = output_dir.join(format!("{stem}.{suffix}.png"));
+        one_agent_one_browser::png::write_rgb_png(&path, image)?;
+        written.push((what, path));
+    }
+    Ok(written)
+}
@@ -1,45 +1,126 @@
-use crate::app::TickResult;
+use crate::app::{KeyInput, TickResult};
+use crate::budget::{PageMetrics, PhaseTimings};
 use crate::css::Stylesheet;
+use crate::css_media::MediaPreferences;
 use crate::debug;
+use crate::debug::inspector::Inspector;
 use crate::dom::Document;
+use crate::extract;
+use crate::geom::Rect;
+use crate::image::Argb32Image;
+use crate::layout::{ElementGeometry, InspectedBox, OutlineHeading, VirtualWindow};
+use crate::net::{TlsInfo, Waterfall, WaterfallEntry};
+use crate::pdf::PageSetup;
 use crate::render::{DisplayCommand, DisplayList, LinkHitRegion, Painter, Viewport};
 use crate::resources::{NoResources, ResourceLoader, ResourceManager};
 use crate::style::StyleComputer;
-use crate::url::Url;
+use crate::url::{Scheme, Url};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+mod chrome;
+mod file_loader;
+mod history;
+mod live_reload;
+mod page_head;
+mod panic_page;
+mod parse_worker;
 mod render_helpers;
+mod scroll;
+mod scrollbar;
+mod tabs;
 mod url_loader;
 
+use self::chrome::{
+    CHROME_HEIGHT_PX, Chrome, ChromeAction, ChromeHit, SecurityIndicator, TabLabels,
+};
+use self::history::{HistoryEntry, PageSnapshot};
+use self::live_reload::LiveReload;
+use self::page_head::PageHead;
+use self::parse_worker::Parsed;
 use self::render_helpers::{clip_rect_to_viewport, fill_linear_gradient_rect_clipped};
+use self::scroll::ScrollMotion;
+use self::scrollbar::{Axis, Scrollbar};
+use self::tabs::TabState;
 use self::url_loader::{StylesheetSlot, UrlLoader, stylesheet_sources_from_loader};
+use crate::app::Key;
 
 const STYLES_DEBOUNCE: Duration = Duration::from_millis(80);
+const ANIMATION_FRAME_INTERVAL: Duration = Duration::from_millis(16);
+// With layout virtualization, content this many viewport heights above and below the
+// visible area is laid out exactly.
+const VIRTUAL_WINDOW_MARGIN_VIEWPORTS: i32 = 2;
 
 pub struct BrowserApp {
-    title: String,
-    document: Document,
-    styles: StyleComputer,
-    style_sources: Vec<StylesheetSource>,
-    styles_viewport: Option<Viewport>,
-    cached_layout: Option<CachedLayout>,
+    /// The dark-mode and resolution settings the current styles were picked for.
+    styles_media: MediaPreferences,
+    /// A wheel glide or touchpad fling still playing out, advanced on each tick.
+    scroll_motion: Option<ScrollMotion>,
+    layout_virtualization: bool,
+    chrome: Option<Chrome>,
+    /// Tabs in strip order, never empty; the one at `active_tab` is shown.
+    tabs: Vec<TabState>,
+    active_tab: usize,
+    zoom_percent: u32,
+    watch_files: bool,
+    /// Record element boxes on every layout, for [`BrowserApp::element_box`].
+    element_boxes: bool,
+    /// Show pages as the article [`crate::reader`] finds in them.
+    reader_mode: bool,
+    /// The reader view of the shown page, built once the page has loaded.
+    reader: Option<ReaderView>,
+    report_timing: bool,
+}
+
+struct PaintedFrame {
+    window_viewport: Viewport,
     scroll_y_px: i32,
-    url_loader: Option<UrlLoader>,
-    base: Option<PageBase>,
-    location: Option<PageLocation>,
-    history: Vec<PageLocation>,
-    resources: Option<ResourceManager>,
-    styles_dirty: bool,
-    last_stylesheet_change: Option<Instant>,
+    scroll_x_px: i32,
+    chrome: bool,
+    inspecting: bool,
+    /// The display list and canvas background painted, when there was a layout.
+    layout: Option<(Arc<DisplayList>, Option<crate::geom::Color>)>,
 }
 
 struct CachedLayout {
     viewport: Viewport,
-    display_list: DisplayList,
+    virtual_window: Option<VirtualWindow>,
+    display_list: Arc<DisplayList>,
     link_regions: Vec<LinkHitRegion>,
     document_height_px: i32,
+    document_width_px: i32,
     canvas_background_color: Option<crate::geom::Color>,
+    outline: Vec<OutlineHeading>,
+    inspected_boxes: Vec<InspectedBox>,
+}
+
+/// The article of the shown page and the stylesheet it is read with, or `None` when the page has
+/// no article and is shown as it is.
+struct ReaderView {
+    article: Option<(Document, StyleComputer)>,
+}
+
+/// Restarted on every navigation, for [`BrowserApp::page_metrics`].
+struct LoadTiming {
+    started_at: Instant,
+    /// When the page and all of its subresources had finished loading.
+    loaded_at: Option<Instant>,
+    slowest_layout: Duration,
+    phases: PhaseTimings,
+    /// Whether `--timing` has printed `phases` for this page.
+    phases_reported: bool,
+}
+
+impl LoadTiming {
+    fn new() -> LoadTiming {
+        LoadTiming {
+            started_at: Instant::now(),
+            loaded_at: None,
+            slowest_layout: Duration::ZERO,
+            phases: PhaseTimings::default(),
+            phases_reported: false,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -48,6 +129,37 @@ enum PageBase {
     FileDir(std::path::PathBuf),
 }
 
+impl PageBase {
+    /// What `document`'s relative URLs resolve against: its `<base href>` resolved against this
+    /// base, or this base when it has none. A local `href` not ending in `/` names a file, which
+    /// resolves against its directory.
+    fn for_document(&self, document: &Document) -> PageBase {
+        let Some(href) = document
+            .base_href
+            .as_deref()
+            .filter(|href| !href.is_empty())
+        else {
+            return self.clone();
+        };
+        match self {
+            PageBase::Url(base) => base
+                .resolve(href)
+                .map_or_else(|| self.clone(), PageBase::Url),
+            PageBase::FileDir(_) if href.starts_with("http://") || href.starts_with("https://") => {
+                Url::parse(href).map_or_else(|_| self.clone(), PageBase::Url)
+            }
+            PageBase::FileDir(dir) => {
+                let path = file_loader::resolve_link_path(dir, href);
+                if href.ends_with('/') {
+                    PageBase::FileDir(path)
+                } else {
+                    PageBase::FileDir(file_loader::local_base_dir(&path))
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum PageLocation {
     Url(Url),
@@ -56,31 +168,22 @@ enum PageLocation {
 
 impl BrowserApp {
     pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
-        let source = std::fs::read_to_string(path)
-            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        let page = file_loader::read_local_page(path)?;
         if debug::enabled(debug::Target::Nav, debug::Level::Info) {
             let path_display = path.display().to_string();
             let path_display = debug::shorten(&path_display, 64);
             debug::log(
                 debug::Target::Nav,
                 debug::Level::Info,
-                format_args!("open file={path_display} bytes={}", source.len()),
+                format_args!("open file={path_display} bytes={}", page.source.len()),
             );
         }
-        let title = path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("Browser")
-            .to_owned();
-        let base_dir = path
-            .parent()
-            .map(std::path::Path::to_owned)
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
-        let resource_base = ResourceBase::FileDir(base_dir.clone());
-        let mut app = Self::from_html_with_base(&title, &source, Some(resource_base))?;
-        app.base = Some(PageBase::FileDir(base_dir.clone()));
-        app.location = Some(PageLocation::File(path.to_owned()));
-        app.resources = Some(ResourceManager::from_file_dir(base_dir));
+        let base_dir = page.base_dir;
+        let mut app = Self::from_html_with_base(&page.title, &page.source, Some(&base_dir))?;
+        app.tab_mut().base = Some(PageBase::FileDir(base_dir.clone()));
+        app.tab_mut().location = Some(PageLocation::File(path.to_owned()));
+        app.tab_mut().resources = Some(ResourceManager::from_file_dir(base_dir));
+        app.apply_document_base();
         Ok(app)
     }
 
@@ -100,28 +203,299 @@ impl BrowserApp {
         }
         let title = base_url.as_str().to_owned();
         let loading_document = crate::html::parse_document("<p>Loading...</p>");
-        let styles = StyleComputer::empty();
         let loader = UrlLoader::new(base_url.clone())?;
         Ok(Self {
-            title,
-            document: loading_document,
-            styles,
-            style_sources: Vec::new(),
-            styles_viewport: None,
-            cached_layout: None,
-            scroll_y_px: 0,
-            url_loader: Some(loader),
-            base: Some(PageBase::Url(base_url.clone())),
-            location: Some(PageLocation::Url(base_url.clone())),
-            history: Vec::new(),
-            resources: Some(ResourceManager::from_url(base_url)),
-            styles_dirty: false,
-            last_stylesheet_change: None,
+            tabs: vec![TabState {
+                title,
+                document: loading_document,
+                url_loader: Some(loader),
+                base: Some(PageBase::Url(base_url.clone())),
+                location: Some(PageLocation::Url(base_url.clone())),
+                resources: Some(ResourceManager::from_url(base_url)),
+                ..TabState::blank()
+            }],
+            styles_media: MediaPreferences::default(),
+            scroll_motion: None,
+            layout_virtualization: false,
+            chrome: None,
+            active_tab: 0,
+            zoom_percent: 100,
+            watch_files: false,
+            element_boxes: false,
+            reader_mode: false,
+            reader: None,
+            report_timing: false,
         })
     }
 
     pub fn title(&self) -> &str {
-        &self.title
+        &self.tab().title
+    }
+
+    /// The document's `<title>`, or the location while it has none.
+    pub fn window_title(&self) -> &str {
+        self.tab().head.title().unwrap_or(&self.tab().title)
+    }
+
+    /// The page's icon, from its first `<link rel="icon">` or else `/favicon.ico` on a page
+    /// served over HTTP, once it has been fetched and decoded.
+    pub fn favicon(&self) -> Option<Arc<Argb32Image>> {
+        self.tab().head.icon()
+    }
+
+    /// The protocol and certificate of the connection the shown page came over, once its
+    /// `https://` response has arrived.
+    pub fn tls_info(&self) -> Option<&TlsInfo> {
+        self.tab().tls.as_ref()
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.tab().history.can_go_back()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.tab().history.can_go_forward()
+    }
+
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn active_tab(&self) -> usize {
+        self.active_tab
+    }
+
+    fn tab(&self) -> &TabState {
+        &self.tabs[self.active_tab]
+    }
+
+    fn tab_mut(&mut self) -> &mut TabState {
+        &mut self.tabs[self.active_tab]
+    }
+
+    /// The heading hierarchy of the shown page as of the last render; empty before the first
+    /// render.
+    pub fn outline(&self) -> &[OutlineHeading] {
+        self.tab()
+            .cached_layout
+            .as_ref()
+            .map_or(&[], |cached| cached.outline.as_slice())
+    }
+
+    /// What the last render of the shown page drew, in document coordinates; `None` before the
+    /// first render.
+    pub fn display_list(&self) -> Option<&DisplayList> {
+        self.tab()
+            .cached_layout
+            .as_ref()
+            .map(|cached| cached.display_list.as_ref())
+    }
+
+    /// Each element's computed style against the viewport of the last render, as written by
+    /// [`StyleComputer::dump_styles`].
+    pub fn computed_styles_text(&self) -> String {
+        let viewport = self.tab().styles_viewport.unwrap_or(Viewport {
+            width_px: 0,
+            height_px: 0,
+        });
+        self.tab()
+            .styles
+            .dump_styles(&self.tab().document, viewport.width_px, viewport.height_px)
+    }
+
+    /// The visible text of the shown page as styled for the last render, as blocks from
+    /// [`extract::extract_blocks`], with links and images resolved against the page.
+    pub fn text_blocks(&self) -> Vec<extract::Block> {
+        let viewport = self.tab().styles_viewport.unwrap_or(Viewport {
+            width_px: 0,
+            height_px: 0,
+        });
+        let resolve = |href: &str| match self.resolve_href(href) {
+            Some(PageLocation::Url(url)) => url.as_str().to_owned(),
+            Some(PageLocation::File(path)) => path.display().to_string(),
+            None => href.to_owned(),
+        };
+        let (document, styles) = self.shown_page();
+        extract::extract_blocks(document, styles, viewport, &resolve)
+    }
+
+    /// Height of the shown page as of the last render.
+    pub fn document_height_px(&self) -> Option<i32> {
+        self.tab()
+            .cached_layout
+            .as_ref()
+            .map(|cached| cached.document_height_px)
+    }
+
+    pub fn scroll_y_px(&self) -> i32 {
+        self.tab().scroll_y_px
+    }
+
+    /// How far the shown page is scrolled to the right.
+    pub fn scroll_x_px(&self) -> i32 {
+        self.tab().scroll_x_px
+    }
+
+    /// Scrolls the shown page; the next render clamps the offset to the page.
+    pub fn set_scroll_y_px(&mut self, y_px: i32) {
+        self.tab_mut().scroll_restore = None;
+        self.scroll_motion = None;
+        self.tab_mut().scroll_y_px = y_px.max(0);
+    }
+
+    /// Writes the last render of the shown page to `path` as a PDF, split into pages of
+    /// `setup`'s paper size. The page should have been rendered at `setup`'s content viewport.
+    pub fn write_pdf(&self, path: &std::path::Path, setup: PageSetup) -> Result<(), String> {
+        let cached = self
+            .tab()
+            .cached_layout
+            .as_ref()
+            .ok_or_else(|| "Nothing has been rendered to print".to_owned())?;
+        crate::pdf::write_pdf(path, &cached.display_list, cached.document_height_px, setup)
+    }
+
+    /// Load time, bytes and layout time of the shown page. The load time keeps growing until
+    /// the page and all of its subresources have arrived.
+    /// Time spent in each phase of showing the current page so far.
+    pub fn phase_timings(&self) -> PhaseTimings {
+        PhaseTimings {
+            bytes_fetched: self.tab().waterfall.total_bytes() + self.local_bytes(),
+            ..self.tab().load_timing.phases
+        }
+    }
+
+    pub fn page_metrics(&self) -> PageMetrics {
+        let load_time = self
+            .tab()
+            .load_timing
+            .loaded_at
+            .unwrap_or_else(Instant::now)
+            .saturating_duration_since(self.tab().load_timing.started_at);
+        PageMetrics {
+            load_time,
+            bytes: self.tab().waterfall.total_bytes() + self.local_bytes(),
+            layout_time: self.tab().load_timing.slowest_layout,
+        }
+    }
+
+    pub fn zoom_percent(&self) -> u32 {
+        self.zoom_percent
+    }
+
+    /// Text metrics depend on the device scale, so every tab is laid out again, not only those
+    /// whose CSS viewport changes.
+    pub fn set_zoom_percent(&mut self, percent: u32) {
+        if self.zoom_percent != percent {
+            self.zoom_percent = percent;
+            for tab in &mut self.tabs {
+                tab.cached_layout = None;
+            }
+        }
+    }
+
+    /// The shown page's document. Changes made through its mutation API are laid out on the
+    /// next tick.
+    pub fn document(&self) -> &Document {
+        &self.tab().document
+    }
+
+    pub fn document_mut(&mut self) -> &mut Document {
+        &mut self.tab_mut().document
+    }
+
+    pub fn resource_waterfall(&self) -> &Waterfall {
+        &self.tab().waterfall
+    }
+
+    /// Shows or hides the location strip drawn above the page.
+    pub fn set_chrome_visible(&mut self, visible: bool) {
+        if visible != self.chrome.is_some() {
+            self.chrome = visible.then(Chrome::default);
+            self.tab_mut().cached_layout = None;
+        }
+    }
+
+    /// Prints [`BrowserApp::phase_timings`] to stderr once each page has loaded and been painted.
+    pub fn set_timing_report(&mut self, enabled: bool) {
+        self.report_timing = enabled;
+    }
+
+    /// Reloads a local page, keeping its scroll position, whenever it or a local stylesheet or
+    /// image it references changes on disk.
+    pub fn set_watch_files(&mut self, enabled: bool) {
+        self.watch_files = enabled;
+        if !enabled {
+            self.tab_mut().live_reload = None;
+        }
+    }
+
+    /// Records every element's box when laying out, as the layout inspector does, so
+    /// [`BrowserApp::element_box`] can find them.
+    pub fn set_element_boxes(&mut self, enabled: bool) {
+        if self.element_boxes != enabled {
+            self.element_boxes = enabled;
+            self.tab_mut().cached_layout = None;
+        }
+    }
+
+    /// The box `element` of [`BrowserApp::document`] got in the last layout; for an inline
+    /// element, the one on its first line. Boxes are only recorded with
+    /// [`BrowserApp::set_element_boxes`] on or the inspector open.
+    pub fn element_box(&self, element: &crate::dom::Element) -> Option<&InspectedBox> {
+        self.tab()
+            .cached_layout
+            .as_ref()?
+            .inspected_boxes
+            .iter()
+            .find(|inspected| inspected.is_box_of(element))
+    }
+
+    /// Where each element matching `selectors` was laid out last, in document order: `None` for
+    /// one that got no box, as when it or an ancestor has `display: none` or
+    /// `visibility: hidden`. Viewport boxes are in the page's viewport, below any browser
+    /// chrome. An inline element broken across lines gives its first line. Boxes are only
+    /// recorded with [`BrowserApp::set_element_boxes`] on or the inspector open.
+    pub fn element_geometry(&self, selectors: &str) -> Vec<Option<ElementGeometry>> {
+        self.tab()
+            .document
+            .query_selector_all(selectors)
+            .into_iter()
+            .map(|element| self.geometry_of(element))
+            .collect()
+    }
+
+    /// Like [`BrowserApp::element_geometry`], for the element with the `id` attribute `id`.
+    pub fn element_geometry_by_id(&self, id: &str) -> Option<ElementGeometry> {
+        self.geometry_of(self.tab().document.find_first_element_by_id(id)?)
+    }
+
+    fn geometry_of(&self, element: &crate::dom::Element) -> Option<ElementGeometry> {
+        let viewport = self.tab().cached_layout.as_ref()?.viewport;
+        Some(
+            self.element_box(element)?
+                .geometry(viewport, self.tab().scroll_y_px),
+        )
+    }
+
+    /// Opens `location` as if typed into the location bar: a URL, a file path or a bare host.
+    pub fn navigate(&mut self, location: &str) -> Result<(), String> {
+        self.navigate_typed_location(location)
+    }
+
+    /// Follows `href`, resolved against the page, as a click on a link to it would.
+    pub fn follow_link(&mut self, href: &str, new_tab: bool) -> Result<(), String> {
+        if new_tab {
+            self.open_href_in_new_tab(href)
+        } else {
+            self.navigate_href(href)
+        }
+    }
+
+    pub fn set_layout_virtualization(&mut self, enabled: bool) {
+        if self.layout_virtualization != enabled {
+            self.layout_virtualization = enabled;
+            self.tab_mut().cached_layout = None;
+        }
     }
 
     pub fn tick(&mut self) -> Result<TickResult, String> {
@@ -129,12 +503,27 @@ impl BrowserApp {
         let mut ready_for_screenshot = true;
         let mut pending_resources = 0usize;
 
-        if let Some(mut loader) = self.url_loader.take() {
+        if let Some(mut loader) = self.tab_mut().url_loader.take() {
             while let Some(event) = loader.pool.try_recv() {
-                if event.id == loader.html_request_id && !loader.html_loaded {
+                let kind = if event.id == loader.html_request_id {
+                    "html"
+                } else {
+                    "css"
+                };
+                let entry = WaterfallEntry {
+                    kind,
+                    url: event.url.clone(),
+                    ok: false,
+                    bytes: event.result.as_ref().map_or(0, Vec::len),
+                    timing: event.timing,
+                    decoded_at: None,
+                };
+
+                if event.id == loader.html_request_id {
                     let bytes = match event.result {
                         Ok(bytes) => bytes,
                         Err(err) => {
+                            self.tab_mut().waterfall.push(entry);
                             if debug::enabled(debug::Target::Nav, debug::Level::Error) {
                                 let url = debug::shorten(loader.base_url.as_str(), 64);
                                 let err = debug::shorten(&err, 48);
@@ -150,60 +539,49 @@ impl BrowserApp {
                             ));
                         }
                     };
-                    let html_source = String::from_utf8_lossy(&bytes).into_owned();
-                    let mut document = crate::html::parse_document(&html_source);
-                    crate::js::execute_inline_scripts(&mut document);
-
-                    loader.stylesheets = loader.fetch_stylesheets(&document)?;
-                    loader.html_loaded = true;
-
-                    self.document = document;
-                    self.style_sources = stylesheet_sources_from_loader(&loader.stylesheets);
-                    self.styles = StyleComputer::empty();
-                    self.styles_viewport = None;
-                    self.cached_layout = None;
-                    self.scroll_y_px = 0;
-                    needs_redraw = true;
-                    if debug::enabled(debug::Target::Nav, debug::Level::Info) {
-                        let css_total = loader.stylesheets.len();
-                        let css_external = loader
-                            .stylesheets
-                            .iter()
-                            .filter(|slot| matches!(slot, StylesheetSlot::External { .. }))
-                            .count();
+                    if debug::enabled(debug::Target::Nav, debug::Level::Debug) {
                         let url = debug::shorten(loader.base_url.as_str(), 64);
                         debug::log(
                             debug::Target::Nav,
-                            debug::Level::Info,
-                            format_args!(
-                                "html+ url={url} bytes={} css={}/{}",
-                                bytes.len(),
-                                css_external,
-                                css_total
-                            ),
+                            debug::Level::Debug,
+                            format_args!("html~ url={url} bytes={}", bytes.len()),
+                        );
+                    }
+                    if let Some(tls) = &event.tls
+                        && debug::enabled(debug::Target::Nav, debug::Level::Debug)
+                    {
+                        let unknown = "?";
+                        let protocol = tls.protocol.as_deref().unwrap_or(unknown);
+                        let issuer = debug::shorten(tls.issuer.as_deref().unwrap_or(unknown), 48);
+                        let until = tls.valid_until.as_deref().unwrap_or(unknown);
+                        debug::log(
+                            debug::Target::Nav,
+                            debug::Level::Debug,
+                            format_args!("tls {protocol} issuer={issuer} until={until}"),
                         );
                     }
+                    loader.tls = event.tls;
+                    loader.parsing.insert(event.id, entry);
+                    let styled_for = self
+                        .tab()
+                        .styles_viewport
+                        .map(|viewport| (viewport, self.styles_media));
+                    loader
+                        .parser
+                        .parse_page(bytes, loader.base_url.clone(), styled_for)?;
                     continue;
                 }
 
-                let slot = loader
+                let Some(slot) = loader
                     .stylesheets
                     .iter_mut()
-                    .find(|slot| slot.request_id() == Some(event.id));
-                let Some(slot) = slot else {
+                    .find(|slot| slot.request_id() == Some(event.id))
+                else {
                     continue;
                 };
 
                 match event.result {
                     Ok(bytes) => {
-                        let css = String::from_utf8_lossy(&bytes).into_owned();
-                        slot.set_stylesheet(Arc::new(Stylesheet::parse(&css)));
-                        self.style_sources = stylesheet_sources_from_loader(&loader.stylesheets);
-                        self.styles = StyleComputer::empty();
-                        self.styles_viewport = None;
-                        self.cached_layout = None;
-                        self.styles_dirty = true;
-                        self.last_stylesheet_change = Some(Instant::now());
                         if debug::enabled(debug::Target::Css, debug::Level::Debug) {
                             let url = debug::shorten(&event.url, 64);
                             debug::log(
@@ -216,9 +594,12 @@ impl BrowserApp {
                                 ),
                             );
                         }
+                        loader.parsing.insert(event.id, entry);
+                        loader.parser.parse_stylesheet(event.id, bytes)?;
                     }
                     Err(err) => {
                         slot.set_stylesheet(Arc::new(Stylesheet::parse("")));
+                        self.tab_mut().waterfall.push(entry);
                         if debug::enabled(debug::Target::Css, debug::Level::Warn) {
                             let url = debug::shorten(&event.url, 64);
                             let err = debug::shorten(&err, 48);
@@ -232,17 +613,119 @@ impl BrowserApp {
                 }
             }
 
+            while let Some(parsed) = loader.parser.try_recv() {
+                match parsed {
+                    Parsed::Page(page) => {
+                        let mut entry = loader.parsing.remove(&loader.html_request_id);
+                        let refs = match page.stylesheets {
+                            Ok(refs) => refs,
+                            Err(err) => {
+                                self.tab_mut().waterfall.extend(entry);
+                                return Err(err);
+                            }
+                        };
+                        loader.stylesheets = loader.fetch_stylesheets(refs)?;
+                        loader.html_loaded = true;
+                        if let Some(entry) = entry.as_mut() {
+                            entry.ok = true;
+                            entry.decoded_at = Some(Instant::now());
+                        }
+
+                        self.reader = None;
+                        self.scroll_motion = None;
+                        let tab = self.tab_mut();
+                        tab.waterfall.extend(entry);
+                        tab.document = page.document;
+                        tab.head = PageHead::unresolved();
+                        tab.base = Some(PageBase::Url(loader.base_url.clone()));
+                        tab.resources = Some(ResourceManager::from_url(loader.base_url.clone()));
+                        tab.tls = loader.tls.take();
+                        tab.insecure_content = false;
+                        tab.load_timing.phases.parse += page.parse_time;
+                        tab.style_sources = stylesheet_sources_from_loader(&loader.stylesheets);
+                        match page.styles {
+                            Some(styled) => {
+                                tab.styles = styled.styles;
+                                tab.styles_viewport = Some(styled.viewport);
+                                tab.styles_sheet_media = styled.sheet_media;
+                                tab.load_timing.phases.style += styled.style_time;
+                            }
+                            None => {
+                                tab.styles = StyleComputer::empty();
+                                tab.styles_viewport = None;
+                            }
+                        }
+                        tab.styles_dirty = false;
+                        tab.last_stylesheet_change = None;
+                        tab.cached_layout = None;
+                        tab.scroll_y_px = 0;
+                        tab.scroll_x_px = 0;
+                        self.apply_document_base();
+                        needs_redraw = true;
+                        if debug::enabled(debug::Target::Nav, debug::Level::Info) {
+                            let css_total = loader.stylesheets.len();
+                            let css_external = loader
+                                .stylesheets
+                                .iter()
+                                .filter(|slot| matches!(slot, StylesheetSlot::External { .. }))
+                                .count();
+                            let url = debug::shorten(loader.base_url.as_str(), 64);
+                            debug::log(
+                                debug::Target::Nav,
+                                debug::Level::Info,
+                                format_args!("html+ url={url} css={css_external}/{css_total}"),
+                            );
+                        }
+                    }
+                    Parsed::Stylesheet {
+                        request_id,
+                        stylesheet,
+                    } => {
+                        let Some(slot) = loader
+                            .stylesheets
+                            .iter_mut()
+                            .find(|slot| slot.request_id() == Some(request_id))
+                        else {
+                            continue;
+                        };
+                        slot.set_stylesheet(stylesheet);
+                        // Debounce from when the response arrived, not from when parsing finished.
+                        let mut arrived_at = Instant::now();
+                        if let Some(mut entry) = loader.parsing.remove(&request_id) {
+                            arrived_at = entry.timing.finished_at;
+                            entry.ok = true;
+                            entry.decoded_at = Some(Instant::now());
+                            self.tab_mut().waterfall.push(entry);
+                        }
+                        self.tab_mut().style_sources =
+                            stylesheet_sources_from_loader(&loader.stylesheets);
+                        let tab = self.tab_mut();
+                        tab.styles = StyleComputer::empty();
+                        tab.styles_viewport = None;
+                        tab.cached_layout = None;
+                        tab.styles_dirty = true;
+                        self.tab_mut().last_stylesheet_change = Some(
+                            self.tab()
+                                .last_stylesheet_change
+                                .map_or(arrived_at, |last| last.max(arrived_at)),
+                        );
+                    }
+                }
+            }
+
+            self.tab_mut().insecure_content |= loader.insecure_content;
             ready_for_screenshot = loader.ready_for_screenshot();
-            self.url_loader = if ready_for_screenshot {
+            self.tab_mut().url_loader = if ready_for_screenshot {
                 None
             } else {
                 Some(loader)
             };
         }
 
-        if self.styles_dirty {
+        if self.tab().styles_dirty {
             let should_redraw = ready_for_screenshot
                 || self
+                    .tab()
                     .last_stylesheet_change
                     .is_some_and(|instant| instant.elapsed() >= STYLES_DEBOUNCE);
             if should_redraw {
@@ -250,10 +733,10 @@ impl BrowserApp {
             }
         }
 
-        if let Some(resources) = &self.resources {
+        if let Some(resources) = &self.tab().resources {
             let tick = resources.tick();
             if tick.new_successes > 0 {
-                self.cached_layout = None;
+                self.tab_mut().cached_layout = None;
                 needs_redraw = true;
                 if debug::enabled(debug::Target::Res, debug::Level::Debug) {
                     debug::log(
@@ -263,12 +746,78 @@ impl BrowserApp {
                     );
                 }
             }
+        }
+        // The placeholder shown while the HTML is on its way has no head to read.
+        if self
+            .tab()
+            .url_loader
+            .as_ref()
+            .is_none_or(|loader| loader.html_loaded)
+        {
+            let tab = self.tab_mut();
+            let serves_favicon = matches!(tab.base, Some(PageBase::Url(_)));
+            tab.head
+                .tick(&tab.document, tab.resources.as_ref(), serves_favicon);
+        }
+        let tab = self.tab_mut();
+        if let Some(resources) = &tab.resources {
             pending_resources = resources.pending_count();
+            tab.waterfall.extend(resources.take_waterfall_entries());
+            if tab.waterfall.mark_decoded(|url| resources.decoded_at(url))
+                && tab.inspector.is_some()
+            {
+                needs_redraw = true;
+            }
+        }
+
+        if let Some(mut motion) = self.scroll_motion.take() {
+            let (y_px, going) = motion.step(Instant::now(), self.max_scroll_y_px(None));
+            if y_px != self.tab().scroll_y_px {
+                self.tab_mut().scroll_y_px = y_px;
+                needs_redraw = true;
+            }
+            if going {
+                self.scroll_motion = Some(motion);
+            }
+        }
+
+        if self.shown_page().1.is_animating()
+            && self
+                .tab()
+                .last_animation_frame
+                .is_none_or(|frame| frame.elapsed() >= ANIMATION_FRAME_INTERVAL)
+        {
+            self.tab_mut().last_animation_frame = Some(Instant::now());
+            self.tab_mut().cached_layout = None;
+            needs_redraw = true;
+        }
+
+        if self.watch_files && self.tick_live_reload()? {
+            needs_redraw = true;
+        }
+
+        if self.apply_document_mutations()? {
+            needs_redraw = true;
+        }
+
+        if self.reader_mode && self.reader.is_none() && self.tab().url_loader.is_none() {
+            self.build_reader_view();
+            needs_redraw = true;
+        }
+
+        if ready_for_screenshot && pending_resources == 0 {
+            self.tab_mut()
+                .load_timing
+                .loaded_at
+                .get_or_insert_with(Instant::now);
+            if !self.tab().waterfall_reported {
+                self.report_waterfall();
+            }
         }
 
         if needs_redraw {
-            self.styles_dirty = false;
-            self.last_stylesheet_change = None;
+            self.tab_mut().styles_dirty = false;
+            self.tab_mut().last_stylesheet_change = None;
         }
 
         Ok(TickResult {
@@ -278,78 +827,314 @@ impl BrowserApp {
         })
     }
 
-    pub fn render(&mut self, painter: &mut dyn Painter, viewport: Viewport) -> Result<(), String> {
-        self.ensure_styles_for_viewport(viewport)?;
-        if !self
-            .cached_layout
+    /// Styles, lays out and paints the page. A panic on the way replaces the page with one
+    /// saying it crashed, which is painted instead once the opacity, clip and transform pushes
+    /// the broken frame left open are popped. Agent and control queries read the layout made
+    /// here, so they are covered too.
+    pub fn render(
+        &mut self,
+        painter: &mut dyn Painter,
+        window_viewport: Viewport,
+    ) -> Result<(), String> {
+        let mut painter = panic_page::BalancedPainter::new(painter);
+        let message = match panic_page::catch_page_panic(|| {
+            self.render_page(&mut painter, window_viewport)
+        }) {
+            Ok(result) => return result,
+            Err(message) => message,
+        };
+        painter.unwind()?;
+        self.show_panic_page(&message);
+        match panic_page::catch_page_panic(|| self.render_page(&mut painter, window_viewport)) {
+            Ok(result) => result,
+            Err(crash_page_message) => {
+                painter.unwind()?;
+                Err(format!(
+                    "Painting the crash page panicked: {crash_page_message} (after: {message})"
+                ))
+            }
+        }
+    }
+
+    /// Shows what a page panicked with in its place, keeping its location so reloading tries it
+    /// again.
+    fn show_panic_page(&mut self, message: &str) {
+        self.tab_mut().document = panic_page::panic_document(message);
+        self.tab_mut().head = PageHead::unresolved();
+        self.reader = None;
+        let tab = self.tab_mut();
+        tab.style_sources = Vec::new();
+        tab.styles = StyleComputer::empty();
+        tab.styles_viewport = None;
+        tab.cached_layout = None;
+        tab.url_loader = None;
+        tab.resources = None;
+        tab.scroll_y_px = 0;
+        tab.scroll_x_px = 0;
+        tab.scroll_restore = None;
+        self.scroll_motion = None;
+    }
+
+    fn render_page(
+        &mut self,
+        painter: &mut dyn Painter,
+        window_viewport: Viewport,
+    ) -> Result<(), String> {
+        let viewport = self.page_viewport(window_viewport);
+        let page_arrived = self
+            .tab()
+            .url_loader
             .as_ref()
-            .is_some_and(|cached| cached.viewport == viewport)
-        {
+            .is_none_or(|loader| loader.html_loaded);
+        if let Some(target) = self.tab().scroll_restore.filter(|_| page_arrived) {
+            self.tab_mut().scroll_y_px = target;
+            // Keep re-applying the offset while late stylesheets and images can still grow the page.
+            let settled = self.tab().url_loader.is_none()
+                && self
+                    .tab()
+                    .resources
+                    .as_ref()
+                    .is_none_or(|resources| resources.pending_count() == 0);
+            if settled {
+                self.tab_mut().scroll_restore = None;
+            }
+        }
+        self.ensure_styles_for_viewport(viewport)?;
+        let visible_top_px = self.tab().scroll_y_px.max(0);
+        let visible_bottom_px = visible_top_px.saturating_add(viewport.height_px.max(0));
+        if !self.tab().cached_layout.as_ref().is_some_and(|cached| {
+            cached.viewport == viewport
+                && cached
+                    .virtual_window
+                    .is_none_or(|window| window.contains_range(visible_top_px, visible_bottom_px))
+        }) {
+            let virtual_window = self.layout_virtualization.then(|| {
+                let margin_px = viewport
+                    .height_px
+                    .max(0)
+                    .saturating_mul(VIRTUAL_WINDOW_MARGIN_VIEWPORTS);
+                VirtualWindow {
+                    top_px: visible_top_px.saturating_sub(margin_px),
+                    bottom_px: visible_bottom_px.saturating_add(margin_px),
+                }
+            });
             let no_resources = NoResources;
             let resources: &dyn ResourceLoader = self
+                .tab()
                 .resources
                 .as_ref()
                 .map(|resources| resources as &dyn ResourceLoader)
                 .unwrap_or(&no_resources);
 
-            let layout_start = debug::enabled(debug::Target::Layout, debug::Level::Debug)
-                .then(std::time::Instant::now);
-            let output = crate::layout::layout_document(
-                &self.document,
-                &self.styles,
+            let (document, styles) = self.shown_page();
+            styles.set_animation_time(Instant::now());
+            let (styled_before, styling_before) = styles.styling_stats();
+            let span = debug::span(debug::Target::Layout, "layout");
+            let output = crate::layout::layout_document_in_window(
+                document,
+                styles,
                 painter,
                 viewport,
                 resources,
+                virtual_window,
+                self.tab().inspector.is_some() || self.element_boxes,
             )?;
-            if let Some(start) = layout_start {
-                let ms: u64 = start.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
-                debug::log(
-                    debug::Target::Layout,
-                    debug::Level::Debug,
-                    format_args!(
-                        "layout+ ms={ms} vw={} vh={} cmds={} links={} h={}",
-                        viewport.width_px,
-                        viewport.height_px,
-                        output.display_list.commands.len(),
-                        output.link_regions.len(),
-                        output.document_height_px
-                    ),
-                );
-            }
-            self.cached_layout = Some(CachedLayout {
+            let layout_time = span.elapsed();
+            let (styled, styling) = styles.styling_stats();
+            let styling = styling.saturating_sub(styling_before);
+            self.tab_mut().load_timing.slowest_layout =
+                self.tab().load_timing.slowest_layout.max(layout_time);
+            let phases = &mut self.tab_mut().load_timing.phases;
+            phases.style += styling;
+            phases.elements_styled += styled.saturating_sub(styled_before);
+            phases.layout += layout_time.saturating_sub(styling);
+            phases.layout_passes += 1;
+            phases.display_commands = output.display_list.commands.len();
+            span.end(format_args!(
+                "vw={} vh={} cmds={} links={} h={}",
+                viewport.width_px,
+                viewport.height_px,
+                output.display_list.commands.len(),
+                output.link_regions.len(),
+                output.document_height_px
+            ));
+            self.tab_mut().cached_layout = Some(CachedLayout {
                 viewport,
-                display_list: output.display_list,
+                virtual_window,
+                display_list: Arc::new(output.display_list),
                 link_regions: output.link_regions,
                 document_height_px: output.document_height_px,
+                document_width_px: output.document_width_px,
                 canvas_background_color: output.canvas_background_color,
+                outline: output.outline,
+                inspected_boxes: output.inspected_boxes,
             });
         }
 
-        painter.clear()?;
-
-        if let Some(cached) = &self.cached_layout {
-            let viewport_width_px = viewport.width_px.max(0);
-            let viewport_height_px = viewport.height_px.max(0);
-
+        let paint = debug::span(debug::Target::Render, "paint");
+        let tab = self.tab_mut();
+        if let Some(cached) = &tab.cached_layout {
             let max_scroll_y_px = cached
                 .document_height_px
-                .saturating_sub(viewport_height_px)
+                .saturating_sub(viewport.height_px.max(0))
+                .max(0);
+            tab.scroll_y_px = tab.scroll_y_px.min(max_scroll_y_px).max(0);
+            let max_scroll_x_px = cached
+                .document_width_px
+                .saturating_sub(viewport.width_px.max(0))
                 .max(0);
-            if self.scroll_y_px > max_scroll_y_px {
-                self.scroll_y_px = max_scroll_y_px;
+            tab.scroll_x_px = tab.scroll_x_px.min(max_scroll_x_px).max(0);
+        }
+
+        match self.frame_damage(painter, window_viewport) {
+            Some(damage) => {
+                for &clip in &damage {
+                    painter.set_clip(Some(clip))?;
+                    self.paint_frame(painter, window_viewport, Some(clip))?;
+                }
+                painter.set_clip(None)?;
             }
-            if self.scroll_y_px < 0 {
-                self.scroll_y_px = 0;
+            None => self.paint_frame(painter, window_viewport, None)?,
+        }
+        self.tab_mut().painted = Some(PaintedFrame {
+            window_viewport,
+            scroll_y_px: self.tab().scroll_y_px,
+            scroll_x_px: self.tab().scroll_x_px,
+            chrome: self.chrome.is_some(),
+            inspecting: self.tab().inspector.is_some(),
+            layout: self.tab().cached_layout.as_ref().map(|cached| {
+                (
+                    Arc::clone(&cached.display_list),
+                    cached.canvas_background_color,
+                )
+            }),
+        });
+
+        painter.flush()?;
+        self.tab_mut().load_timing.phases.paint += paint.elapsed();
+        self.tab_mut().load_timing.phases.frames_painted += 1;
+        if self.report_timing
+            && self.tab().load_timing.loaded_at.is_some()
+            && !self.tab().load_timing.phases_reported
+        {
+            self.tab_mut().load_timing.phases_reported = true;
+            eprint!("{}", self.phase_timings().to_text());
+        }
+        Ok(())
+    }
+
+    /// The window areas that changed since the last frame, or `None` when the whole window has
+    /// to be repainted.
+    fn frame_damage(
+        &mut self,
+        painter: &dyn Painter,
+        window_viewport: Viewport,
+    ) -> Option<Vec<Rect>> {
+        let painted = self.tab_mut().painted.take()?;
+        if !painter.retains_frame()
+            || painted.window_viewport != window_viewport
+            || painted.scroll_y_px != self.tab().scroll_y_px
+            || painted.scroll_x_px != self.tab().scroll_x_px
+            || painted.chrome != self.chrome.is_some()
+            || painted.inspecting
+            || self.tab().inspector.is_some()
+        {
+            return None;
+        }
+        let page_top_px = self.page_top_px();
+        let scroll_y_px = self.tab().scroll_y_px.saturating_sub(page_top_px);
+        let mut damage = match (&painted.layout, &self.tab().cached_layout) {
+            (Some((display_list, background)), Some(cached)) => {
+                if *background != cached.canvas_background_color {
+                    return None;
+                }
+                if Arc::ptr_eq(display_list, &cached.display_list) {
+                    Vec::new()
+                } else {
+                    crate::render::damage::display_list_damage(
+                        display_list,
+                        &cached.display_list,
+                        painter,
+                        |rect, fixed| Rect {
+                            y: if fixed {
+                                rect.y.saturating_add(page_top_px)
+                            } else {
+                                rect.y.saturating_sub(scroll_y_px)
+                            },
+                            ..rect
+                        },
+                    )?
+                }
             }
-            let scroll_y_px = self.scroll_y_px;
+            (None, None) => Vec::new(),
+            _ => return None,
+        };
+        if self.chrome.is_some() {
+            // The location bar's caret and status change without a new layout.
+            damage.push(Rect {
+                x: 0,
+                y: 0,
+                width: window_viewport.width_px,
+                height: CHROME_HEIGHT_PX,
+            });
+        }
+        for scrollbar in self.scrollbars(window_viewport) {
+            damage.push(scrollbar.track);
+        }
+        let window = Rect {
+            x: 0,
+            y: 0,
+            width: window_viewport.width_px.max(0),
+            height: window_viewport.height_px.max(0),
+        };
+        let damage = damage
+            .into_iter()
+            .map(|rect| rect.intersection(window))
+            .collect();
+        Some(crate::render::damage::merge_damage(damage))
+    }
+
+    /// Paints the page and the chrome. With a `clip`, commands entirely outside it are skipped.
+    fn paint_frame(
+        &mut self,
+        painter: &mut dyn Painter,
+        window_viewport: Viewport,
+        clip: Option<Rect>,
+    ) -> Result<(), String> {
+        let page_top_px = self.page_top_px();
+        painter.clear()?;
+
+        let tab = self.tab_mut();
+        if let Some(cached) = &tab.cached_layout {
+            let viewport_width_px = window_viewport.width_px.max(0);
+            let viewport_height_px = window_viewport.height_px.max(0);
+            let scroll_y_px = tab.scroll_y_px.saturating_sub(page_top_px);
 
             if let Some(color) = cached.canvas_background_color {
                 painter.fill_rect(0, 0, viewport_width_px, viewport_height_px, color)?;
             }
 
             let mut fixed_depth = 0usize;
+            let mut transform_depth = 0usize;
 
             for cmd in &cached.display_list.commands {
+                if let Some(clip) = clip
+                    && transform_depth == 0
+                    && let Some((top, bottom)) =
+                        crate::render::damage::vertical_extent(cmd, &*painter)
+                {
+                    let offset_y_px = if fixed_depth > 0 {
+                        page_top_px
+                    } else {
+                        -scroll_y_px
+                    };
+                    if bottom.saturating_add(offset_y_px) <= clip.y
+                        || top.saturating_add(offset_y_px) >= clip.bottom()
+                    {
+                        continue;
+                    }
+                }
+                let shift_x_px = if fixed_depth > 0 { 0 } else { tab.scroll_x_px };
                 match cmd {
                     DisplayCommand::PushFixed => {
                         fixed_depth = fixed_depth.saturating_add(1);
@@ -357,16 +1142,31 @@ impl BrowserApp {
                     DisplayCommand::PopFixed => {
                         fixed_depth = fixed_depth.saturating_sub(1);
                     }
+                    DisplayCommand::PushTransform(transform) => {
+                        let offset_y_px = if fixed_depth > 0 {
+                            page_top_px
+                        } else {
+                            -scroll_y_px
+                        };
+                        painter.push_transform(
+                            transform.about(-f64::from(shift_x_px), f64::from(offset_y_px)),
+                        )?;
+                        transform_depth = transform_depth.saturating_add(1);
+                    }
+                    DisplayCommand::PopTransform => {
+                        painter.pop_transform()?;
+                        transform_depth = transform_depth.saturating_sub(1);
+                    }
                     DisplayCommand::PushOpacity(opacity) => painter.push_opacity(*opacity)?,
                     DisplayCommand::PopOpacity(opacity) => painter.pop_opacity(*opacity)?,
                     DisplayCommand::Rect(rect) => {
                         let y_px = if fixed_depth > 0 {
-                            rect.y_px
+                            rect.y_px.saturating_add(page_top_px)
                         } else {
                             rect.y_px.saturating_sub(scroll_y_px)
                         };
                         if let Some((x, y, w, h)) = clip_rect_to_viewport(
-                            rect.x_px,
+                            rect.x_px.saturating_sub(shift_x_px),
                             y_px,
                             rect.width_px,
                             rect.height_px,
@@ -378,12 +1178,12 @@ impl BrowserApp {
                     }
                     DisplayCommand::LinearGradientRect(rect) => {
                         let y_px = if fixed_depth > 0 {
-                            rect.y_px
+                            rect.y_px.saturating_add(page_top_px)
                         } else {
                             rect.y_px.saturating_sub(scroll_y_px)
                         };
                         let translated = crate::render::DrawLinearGradientRect {
-                            x_px: rect.x_px,
+                            x_px: rect.x_px.saturating_sub(shift_x_px),
                             y_px,
                             width_px: rect.width_px,
                             height_px: rect.height_px,
@@ -404,7 +1204,7 @@ impl BrowserApp {
                     }
                     DisplayCommand::RoundedRect(rect) => {
                         let y_px = if fixed_depth > 0 {
-                            rect.y_px
+                            rect.y_px.saturating_add(page_top_px)
                         } else {
                             rect.y_px.saturating_sub(scroll_y_px)
                         };
@@ -414,7 +1214,7 @@ impl BrowserApp {
                             && y_px.saturating_add(rect.height_px) > 0
                         {
                             painter.fill_rounded_rect(
-                                rect.x_px,
+                                rect.x_px.saturating_sub(shift_x_px),
                                 y_px,
                                 rect.width_px,
                                 rect.height_px,
@@ -425,7 +1225,7 @@ impl BrowserApp {
                     }
                     DisplayCommand::RoundedRectBorder(rect) => {
                         let y_px = if fixed_depth > 0 {
-                            rect.y_px
+                            rect.y_px.saturating_add(page_top_px)
                         } else {
                             rect.y_px.saturating_sub(scroll_y_px)
                         };
@@ -435,7 +1235,7 @@ impl BrowserApp {
                             && y_px.saturating_add(rect.height_px) > 0
                         {
                             painter.stroke_rounded_rect(
-                                rect.x_px,
+                                rect.x_px.saturating_sub(shift_x_px),
                                 y_px,
                                 rect.width_px,
                                 rect.height_px,
@@ -447,7 +1247,7 @@ impl BrowserApp {
                     }
                     DisplayCommand::Text(text) => {
                         let baseline_y_px = if fixed_depth > 0 {
-                            text.y_px
+                            text.y_px.saturating_add(page_top_px)
                         } else {
                             text.y_px.saturating_sub(scroll_y_px)
                         };
@@ -461,7 +1261,7 @@ impl BrowserApp {
                             let bottom = baseline_y_px.saturating_add(metrics.descent_px);
                             if bottom > 0 && top < viewport_height_px {
                                 painter.draw_text(
-                                    text.x_px,
+                                    text.x_px.saturating_sub(shift_x_px),
                                     baseline_y_px,
                                     &text.text,
                                     text.style,
@@ -471,7 +1271,7 @@ impl BrowserApp {
                     }
                     DisplayCommand::Image(image) => {
                         let y_px = if fixed_depth > 0 {
-                            image.y_px
+                            image.y_px.saturating_add(page_top_px)
                         } else {
                             image.y_px.saturating_sub(scroll_y_px)
                         };
@@ -481,7 +1281,7 @@ impl BrowserApp {
                             && y_px.saturating_add(image.height_px) > 0
                         {
                             painter.draw_image(
-                                image.x_px,
+                                image.x_px.saturating_sub(shift_x_px),
                                 y_px,
                                 image.width_px,
                                 image.height_px,
@@ -492,7 +1292,7 @@ impl BrowserApp {
                     }
                     DisplayCommand::Svg(svg) => {
                         let y_px = if fixed_depth > 0 {
-                            svg.y_px
+                            svg.y_px.saturating_add(page_top_px)
                         } else {
                             svg.y_px.saturating_sub(scroll_y_px)
                         };
@@ -502,7 +1302,7 @@ impl BrowserApp {
                             && y_px.saturating_add(svg.height_px) > 0
                         {
                             painter.draw_svg(
-                                svg.x_px,
+                                svg.x_px.saturating_sub(shift_x_px),
                                 y_px,
                                 svg.width_px,
                                 svg.height_px,
@@ -513,9 +1313,55 @@ impl BrowserApp {
                     }
                 }
             }
+
+            if let Some(inspector) = tab.inspector.as_mut() {
+                inspector.report_hovered(&cached.inspected_boxes, tab.scroll_y_px);
+                if let Some(inspected) = inspector.hovered(&cached.inspected_boxes, tab.scroll_y_px)
+                {
+                    let offset_y_px = if inspected.is_fixed {
+                        page_top_px
+                    } else {
+                        -scroll_y_px
+                    };
+                    debug::inspector::paint_overlay(
+                        painter,
+                        inspected,
+                        offset_y_px,
+                        window_viewport,
+                    )?;
+                }
+            }
         }
 
-        painter.flush()?;
+        if self.tab().inspector.is_some() {
+            debug::inspector::paint_waterfall(painter, &self.tab().waterfall, window_viewport)?;
+        }
+
+        for scrollbar in self.scrollbars(window_viewport) {
+            let dragged = self
+                .tab()
+                .scrollbar_grab
+                .is_some_and(|(axis, _)| axis == scrollbar.axis);
+            scrollbar.paint(painter, dragged)?;
+        }
+
+        if let Some(chrome) = &self.chrome {
+            let titles: Vec<String> = self.tabs.iter().map(|tab| tab.title.clone()).collect();
+            let tabs = TabLabels {
+                titles: &titles,
+                active: self.active_tab,
+            };
+            let location = self.location_display();
+            let status = self.loading_status();
+            chrome.paint(
+                painter,
+                window_viewport.width_px,
+                tabs,
+                &location,
+                self.security_indicator(),
+                &status,
+            )?;
+        }
         Ok(())
     }
 
@@ -523,34 +1369,88 @@ impl BrowserApp {
         &mut self,
         x_px: i32,
         y_px: i32,
-        viewport: Viewport,
+        window_viewport: Viewport,
     ) -> Result<TickResult, String> {
-        let Some(cached) = self
-            .cached_layout
-            .as_ref()
-            .filter(|cached| cached.viewport == viewport)
-        else {
-            return Ok(TickResult::default());
-        };
-
-        let Some(href) = cached
-            .link_regions
-            .iter()
-            .rev()
-            .find(|region| {
-                let hit_y_px = if region.is_fixed {
-                    y_px
-                } else {
-                    y_px.saturating_add(self.scroll_y_px)
+        if let Some(chrome) = &self.chrome
+            && y_px < CHROME_HEIGHT_PX
+        {
+            match chrome.hit_test(x_px, y_px, window_viewport.width_px, self.tabs.len()) {
+                Some(ChromeHit::Location) => {
+                    let location = self.location_display();
+                    if let Some(chrome) = self.chrome.as_mut() {
+                        chrome.begin_editing(&location);
+                    }
+                }
+                Some(ChromeHit::Tab(index)) => self.switch_tab(index),
+                Some(ChromeHit::CloseTab(index)) => self.close_tab(index),
+                Some(ChromeHit::NewTab) => self.open_blank_tab(),
+                None => return Ok(TickResult::default()),
+            }
+            return Ok(TickResult {
+                needs_redraw: true,
+                ready_for_screenshot: false,
+                pending_resources: 0,
+            });
+        }
+
+        if let Some(scrollbar) = self
+            .scrollbars(window_viewport)
+            .into_iter()
+            .find(|scrollbar| scrollbar.track_contains(x_px, y_px))
+        {
+            self.tab_mut().scroll_restore = None;
+            self.scroll_motion = None;
+            let position_px = scrollbar.axis.pick(x_px, y_px);
+            if scrollbar.thumb_contains(x_px, y_px) {
+                self.tab_mut().scrollbar_grab =
+                    Some((scrollbar.axis, position_px - scrollbar.thumb_start_px()));
+            } else {
+                let scroll_px = self.scroll_along(scrollbar.axis);
+                *scroll_px = scrollbar.page_towards(position_px, *scroll_px);
+            }
+            return Ok(TickResult {
+                needs_redraw: true,
+                ready_for_screenshot: true,
+                pending_resources: 0,
+            });
+        }
+
+        let y_px = y_px.saturating_sub(self.page_top_px());
+        let viewport = self.page_viewport(window_viewport);
+        let Some(cached) = self
+            .tab()
+            .cached_layout
+            .as_ref()
+            .filter(|cached| cached.viewport == viewport)
+        else {
+            return Ok(TickResult::default());
+        };
+
+        let Some((href, opens_new_tab)) = cached
+            .link_regions
+            .iter()
+            .rev()
+            .find(|region| {
+                let (hit_x_px, hit_y_px) = if region.is_fixed {
+                    (x_px, y_px)
+                } else {
+                    (
+                        x_px.saturating_add(self.tab().scroll_x_px),
+                        y_px.saturating_add(self.tab().scroll_y_px),
+                    )
                 };
-                region.contains_point(x_px, hit_y_px)
+                region.contains_point(hit_x_px, hit_y_px)
             })
-            .map(|region| region.href.clone())
+            .map(|region| (region.href.clone(), region.opens_new_tab))
         else {
             return Ok(TickResult::default());
         };
 
-        self.navigate_href(href.as_ref())?;
+        if opens_new_tab {
+            self.open_href_in_new_tab(href.as_ref())?;
+        } else {
+            self.navigate_href(href.as_ref())?;
+        }
         Ok(TickResult {
             needs_redraw: true,
             ready_for_screenshot: false,
@@ -558,7 +1458,12 @@ impl BrowserApp {
         })
     }
 
-    fn mouse_wheel(&mut self, delta_y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
+    fn mouse_wheel(
+        &mut self,
+        delta_y_px: i32,
+        window_viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        let viewport = self.page_viewport(window_viewport);
         if delta_y_px == 0 {
             return Ok(TickResult {
                 needs_redraw: false,
@@ -567,42 +1472,502 @@ impl BrowserApp {
             });
         }
 
-        let next_unclamped = self.scroll_y_px.saturating_add(delta_y_px).max(0);
-        let max_scroll_y_px = self
+        self.tab_mut().scroll_restore = None;
+        self.scroll_motion = None;
+        let next_unclamped = self.tab().scroll_y_px.saturating_add(delta_y_px).max(0);
+        let next = next_unclamped.min(self.max_scroll_y_px(Some(viewport)));
+        let changed = next != self.tab().scroll_y_px;
+        self.tab_mut().scroll_y_px = next;
+        Ok(TickResult {
+            needs_redraw: changed,
+            ready_for_screenshot: true,
+            pending_resources: 0,
+        })
+    }
+
+    fn horizontal_wheel(&mut self, delta_x_px: i32, window_viewport: Viewport) -> TickResult {
+        let viewport = self.page_viewport(window_viewport);
+        let max_scroll_x_px = self
+            .tab()
             .cached_layout
             .as_ref()
             .filter(|cached| cached.viewport == viewport)
+            .map_or(0, |cached| {
+                cached
+                    .document_width_px
+                    .saturating_sub(viewport.width_px.max(0))
+                    .max(0)
+            });
+        let next = self
+            .tab()
+            .scroll_x_px
+            .saturating_add(delta_x_px)
+            .clamp(0, max_scroll_x_px);
+        let changed = next != self.tab().scroll_x_px;
+        self.tab_mut().scroll_x_px = next;
+        TickResult {
+            needs_redraw: changed,
+            ready_for_screenshot: true,
+            pending_resources: 0,
+        }
+    }
+
+    /// Starts a glide that the following ticks play out.
+    fn wheel_notch(&mut self, delta_y_px: i32, window_viewport: Viewport) -> TickResult {
+        let viewport = self.page_viewport(window_viewport);
+        if delta_y_px != 0 {
+            self.tab_mut().scroll_restore = None;
+            self.scroll_motion = Some(ScrollMotion::glide(
+                self.scroll_motion,
+                self.tab().scroll_y_px,
+                delta_y_px,
+                self.max_scroll_y_px(Some(viewport)),
+                Instant::now(),
+            ));
+        }
+        TickResult {
+            needs_redraw: false,
+            ready_for_screenshot: true,
+            pending_resources: 0,
+        }
+    }
+
+    fn scroll_fling(&mut self, velocity_y_px_per_s: f64) -> TickResult {
+        self.tab_mut().scroll_restore = None;
+        self.scroll_motion = Some(ScrollMotion::fling(
+            self.tab().scroll_y_px,
+            velocity_y_px_per_s,
+            Instant::now(),
+        ));
+        TickResult {
+            needs_redraw: false,
+            ready_for_screenshot: true,
+            pending_resources: 0,
+        }
+    }
+
+    /// How far the page scrolls, from the last layout; unbounded until the page has been laid
+    /// out, or laid out at `viewport` when one is given.
+    fn max_scroll_y_px(&self, viewport: Option<Viewport>) -> i32 {
+        self.tab()
+            .cached_layout
+            .as_ref()
+            .filter(|cached| viewport.is_none_or(|viewport| cached.viewport == viewport))
             .map(|cached| {
                 cached
                     .document_height_px
-                    .saturating_sub(viewport.height_px.max(0))
+                    .saturating_sub(cached.viewport.height_px.max(0))
                     .max(0)
             })
-            .unwrap_or(i32::MAX);
-        let next = next_unclamped.min(max_scroll_y_px);
-        let changed = next != self.scroll_y_px;
-        self.scroll_y_px = next;
+            .unwrap_or(i32::MAX)
+    }
+
+    /// Drags the scrollbar thumb once it has been grabbed. Otherwise only tracked while the
+    /// layout inspector is open, to highlight the box under the pointer.
+    pub fn mouse_move(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        window_viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        if let Some((axis, grab_px)) = self.tab().scrollbar_grab {
+            let Some(scrollbar) = self
+                .scrollbars(window_viewport)
+                .into_iter()
+                .find(|scrollbar| scrollbar.axis == axis)
+            else {
+                self.tab_mut().scrollbar_grab = None;
+                return Ok(TickResult::default());
+            };
+            let next = scrollbar.scroll_for_thumb_at(axis.pick(x_px, y_px).saturating_sub(grab_px));
+            let scroll_px = self.scroll_along(axis);
+            let changed = next != *scroll_px;
+            *scroll_px = next;
+            return Ok(TickResult {
+                needs_redraw: changed,
+                ready_for_screenshot: true,
+                pending_resources: 0,
+            });
+        }
+        let page_top_px = self.page_top_px();
+        let viewport = self.page_viewport(window_viewport);
+        let tab = self.tab_mut();
+        let Some(inspector) = tab.inspector.as_mut() else {
+            return Ok(TickResult::default());
+        };
+        inspector.set_pointer((y_px >= page_top_px).then(|| (x_px, y_px - page_top_px)));
+        let Some(cached) = tab
+            .cached_layout
+            .as_ref()
+            .filter(|cached| cached.viewport == viewport)
+        else {
+            return Ok(TickResult::default());
+        };
         Ok(TickResult {
-            needs_redraw: changed,
-            ready_for_screenshot: true,
+            needs_redraw: inspector.report_hovered(&cached.inspected_boxes, tab.scroll_y_px),
+            ready_for_screenshot: false,
             pending_resources: 0,
         })
     }
+
+    /// Lets go of the scrollbar thumb.
+    pub fn mouse_up(&mut self) -> TickResult {
+        TickResult {
+            needs_redraw: self.tab_mut().scrollbar_grab.take().is_some(),
+            ready_for_screenshot: true,
+            pending_resources: 0,
+        }
+    }
+
+    pub fn reader_mode(&self) -> bool {
+        self.reader_mode
+    }
+
+    /// Shows pages, from the next tick on, as the article found in them with a plain built-in
+    /// stylesheet, or as they are. A page without an article is shown as it is either way.
+    pub fn set_reader_mode(&mut self, enabled: bool) {
+        if enabled == self.reader_mode {
+            return;
+        }
+        self.reader_mode = enabled;
+        self.reader = None;
+        let tab = self.tab_mut();
+        tab.cached_layout = None;
+        tab.scroll_y_px = 0;
+        tab.scroll_x_px = 0;
+        tab.scroll_restore = None;
+        self.scroll_motion = None;
+        debug::log(
+            debug::Target::Layout,
+            debug::Level::Info,
+            format_args!("reader {}", if enabled { "on" } else { "off" }),
+        );
+    }
+
+    /// Opens or closes the layout inspector. The page is laid out again, since element boxes are
+    /// only recorded while it is open.
+    pub fn toggle_inspector(&mut self) {
+        self.tab_mut().inspector = match self.tab_mut().inspector.take() {
+            Some(_) => None,
+            None => Some(Inspector::default()),
+        };
+        self.tab_mut().cached_layout = None;
+        debug::log(
+            debug::Target::Layout,
+            debug::Level::Info,
+            format_args!(
+                "inspector {}",
+                if self.tab().inspector.is_some() {
+                    "on"
+                } else {
+                    "off"
+                }
+            ),
+        );
+    }
 }
 
 impl BrowserApp {
-    fn maybe_push_history(&mut self, previous: Option<PageLocation>) {
+    /// The document laid out and how it is styled: the page's own, or its reader view.
+    fn shown_page(&self) -> (&Document, &StyleComputer) {
+        match &self.reader {
+            Some(ReaderView {
+                article: Some((document, styles)),
+            }) => (document, styles),
+            _ => (&self.tab().document, &self.tab().styles),
+        }
+    }
+
+    fn build_reader_view(&mut self) {
+        let title = self
+            .tab()
+            .document
+            .title()
+            .unwrap_or_else(|| self.tab().title.clone());
+        let article = crate::reader::extract_article(&self.tab().document, &title)
+            .map(|article| (article, StyleComputer::from_css(crate::reader::STYLESHEET)));
+        debug::log(
+            debug::Target::Layout,
+            debug::Level::Info,
+            format_args!("reader article={}", article.is_some()),
+        );
+        self.reader = Some(ReaderView { article });
+        self.tab_mut().cached_layout = None;
+    }
+
+    /// The scrollbars shown with the chrome: a vertical one when the laid out page is taller than
+    /// the window, then a horizontal one when it is wider.
+    fn scrollbars(&self, window_viewport: Viewport) -> Vec<Scrollbar> {
+        let viewport = self.page_viewport(window_viewport);
+        let Some(cached) = self
+            .tab()
+            .cached_layout
+            .as_ref()
+            .filter(|cached| self.chrome.is_some() && cached.viewport == viewport)
+        else {
+            return Vec::new();
+        };
+        let page_area = Rect {
+            x: 0,
+            y: self.page_top_px(),
+            width: viewport.width_px,
+            height: viewport.height_px,
+        };
+        let vertical =
+            Scrollbar::vertical(page_area, cached.document_height_px, self.tab().scroll_y_px);
+        let horizontal = Scrollbar::horizontal(
+            page_area,
+            cached.document_width_px,
+            self.tab().scroll_x_px,
+            vertical.is_some(),
+        );
+        vertical.into_iter().chain(horizontal).collect()
+    }
+
+    fn scroll_along(&mut self, axis: Axis) -> &mut i32 {
+        match axis {
+            Axis::Vertical => &mut self.tab_mut().scroll_y_px,
+            Axis::Horizontal => &mut self.tab_mut().scroll_x_px,
+        }
+    }
+
+    fn page_top_px(&self) -> i32 {
+        if self.chrome.is_some() {
+            CHROME_HEIGHT_PX
+        } else {
+            0
+        }
+    }
+
+    fn page_viewport(&self, window_viewport: Viewport) -> Viewport {
+        Viewport {
+            width_px: window_viewport.width_px,
+            height_px: window_viewport
+                .height_px
+                .saturating_sub(self.page_top_px())
+                .max(0),
+        }
+    }
+
+    fn location_display(&self) -> String {
+        location_display(self.tab().location.as_ref(), &self.tab().title)
+    }
+
+    fn security_indicator(&self) -> SecurityIndicator {
+        match &self.tab().location {
+            Some(PageLocation::Url(url)) if url.scheme() == Scheme::Https => {
+                let insecure_images = self
+                    .tab()
+                    .resources
+                    .as_ref()
+                    .is_some_and(ResourceManager::loaded_insecure_content);
+                if self.tab().insecure_content || insecure_images {
+                    SecurityIndicator::NotSecure
+                } else {
+                    SecurityIndicator::Secure
+                }
+            }
+            Some(PageLocation::Url(_)) => SecurityIndicator::NotSecure,
+            Some(PageLocation::File(_)) | None => SecurityIndicator::None,
+        }
+    }
+
+    fn loading_status(&self) -> String {
+        if self.tab().url_loader.is_some() {
+            return "Loading…".to_owned();
+        }
+        let pending = self
+            .tab()
+            .resources
+            .as_ref()
+            .map_or(0, ResourceManager::pending_count);
+        if pending > 0 {
+            return format!("{pending} pending");
+        }
+        String::new()
+    }
+
+    fn key_down(&mut self, input: KeyInput) -> Result<Option<TickResult>, String> {
+        let location = self.location_display();
+        let Some(action) = self
+            .chrome
+            .as_mut()
+            .and_then(|chrome| chrome.key_down(input, &location))
+        else {
+            return match (input.key, input.ctrl, input.alt) {
+                (Key::F12, false, false) => {
+                    self.toggle_inspector();
+                    Ok(Some(TickResult {
+                        needs_redraw: true,
+                        ready_for_screenshot: false,
+                        pending_resources: 0,
+                    }))
+                }
+                (Key::Char('r' | 'R'), true, true) => {
+                    self.set_reader_mode(!self.reader_mode);
+                    Ok(Some(TickResult {
+                        needs_redraw: true,
+                        ready_for_screenshot: false,
+                        pending_resources: 0,
+                    }))
+                }
+                (Key::Left, false, true) | (Key::Char('['), true, false) => {
+                    self.go_back().map(Some)
+                }
+                (Key::Right, false, true) | (Key::Char(']'), true, false) => {
+                    self.go_forward().map(Some)
+                }
+                (Key::Char('t' | 'T'), true, false) => {
+                    self.open_blank_tab();
+                    Ok(Some(TickResult {
+                        needs_redraw: true,
+                        ready_for_screenshot: false,
+                        pending_resources: 0,
+                    }))
+                }
+                (Key::Char('w' | 'W'), true, false) => {
+                    self.close_tab(self.active_tab);
+                    Ok(Some(TickResult {
+                        needs_redraw: true,
+                        ready_for_screenshot: false,
+                        pending_resources: 0,
+                    }))
+                }
+                (Key::Tab, true, false) => {
+                    self.switch_tab((self.active_tab + 1) % self.tabs.len());
+                    Ok(Some(TickResult {
+                        needs_redraw: true,
+                        ready_for_screenshot: false,
+                        pending_resources: 0,
+                    }))
+                }
+                _ => Ok(None),
+            };
+        };
+        if let ChromeAction::Navigate(input) = action
+            && let Err(err) = self.navigate_typed_location(&input)
+            && debug::enabled(debug::Target::Nav, debug::Level::Warn)
+        {
+            let input = debug::shorten(&input, 64);
+            let err = debug::shorten(&err, 48);
+            debug::log(
+                debug::Target::Nav,
+                debug::Level::Warn,
+                format_args!("typed! input={input} err={err}"),
+            );
+        }
+        Ok(Some(TickResult {
+            needs_redraw: true,
+            ready_for_screenshot: false,
+            pending_resources: 0,
+        }))
+    }
+
+    fn paste_text(&mut self, text: &str) -> TickResult {
+        let pasted = self.chrome.as_mut().and_then(|chrome| chrome.paste(text));
+        TickResult {
+            needs_redraw: pasted.is_some(),
+            ready_for_screenshot: false,
+            pending_resources: 0,
+        }
+    }
+
+    fn open_dropped(&mut self, dropped: &str) -> TickResult {
+        let Some(location) = dropped_location(dropped) else {
+            return TickResult::default();
+        };
+        if let Some(chrome) = self.chrome.as_mut() {
+            chrome.stop_editing();
+        }
+        if let Err(err) = self.navigate_typed_location(location)
+            && debug::enabled(debug::Target::Nav, debug::Level::Warn)
+        {
+            let location = debug::shorten(location, 64);
+            let err = debug::shorten(&err, 48);
+            debug::log(
+                debug::Target::Nav,
+                debug::Level::Warn,
+                format_args!("dropped! location={location} err={err}"),
+            );
+        }
+        TickResult {
+            needs_redraw: true,
+            ready_for_screenshot: false,
+            pending_resources: 0,
+        }
+    }
+
+    fn navigate_typed_location(&mut self, input: &str) -> Result<(), String> {
+        let previous = self.history_entry();
+        if let Some(path) = file_loader::file_url_path(input) {
+            self.load_file(&path)?;
+        } else if input.contains("://") {
+            self.begin_url_navigation(Url::parse(input)?)?;
+        } else if std::path::Path::new(input).exists() {
+            self.load_file(std::path::Path::new(input))?;
+        } else {
+            self.begin_url_navigation(Url::parse(&format!("https://{input}"))?)?;
+        }
+        self.maybe_push_history(previous);
+        Ok(())
+    }
+
+    fn report_waterfall(&mut self) {
+        self.tab_mut().waterfall_reported = true;
+        if self.tab().waterfall.is_empty()
+            || !debug::enabled(debug::Target::Net, debug::Level::Debug)
+        {
+            return;
+        }
+
+        if let Some(critical) = self.tab().waterfall.critical_entry() {
+            let url = debug::shorten(&critical.url, 64);
+            debug::log(
+                debug::Target::Net,
+                debug::Level::Debug,
+                format_args!(
+                    "waterfall n={} critical={} url={url}",
+                    self.tab().waterfall.entries().len(),
+                    critical.kind
+                ),
+            );
+        }
+        for line in self.tab().waterfall.render_lines() {
+            debug::log(
+                debug::Target::Net,
+                debug::Level::Debug,
+                format_args!("wf {line}"),
+            );
+        }
+    }
+
+    fn history_entry(&self) -> Option<HistoryEntry> {
+        let location = self.tab().location.clone()?;
+        let snapshot = self.tab().url_loader.is_none().then(|| PageSnapshot {
+            title: self.tab().title.clone(),
+            document: self.tab().document.clone(),
+            style_sources: self.tab().style_sources.clone(),
+        });
+        Some(HistoryEntry {
+            location,
+            scroll_y_px: self.tab().scroll_y_px,
+            snapshot,
+        })
+    }
+
+    fn maybe_push_history(&mut self, previous: Option<HistoryEntry>) {
         let Some(previous) = previous else {
             return;
         };
         if self
+            .tab()
             .location
             .as_ref()
-            .is_some_and(|current| current == &previous)
+            .is_some_and(|current| current == &previous.location)
         {
             return;
         }
-        self.history.push(previous);
+        self.tab_mut().history.push(previous);
     }
 
     fn navigate_to_location(&mut self, location: PageLocation) -> Result<(), String> {
@@ -612,9 +1977,72 @@ impl BrowserApp {
         }
     }
 
+    fn restore_history_entry(&mut self, entry: HistoryEntry) -> Result<(), String> {
+        match entry.snapshot {
+            Some(snapshot) => self.restore_snapshot(entry.location, snapshot),
+            None => self.navigate_to_location(entry.location)?,
+        }
+        if entry.scroll_y_px > 0 {
+            self.tab_mut().scroll_restore = Some(entry.scroll_y_px);
+        }
+        Ok(())
+    }
+
+    fn restore_snapshot(&mut self, location: PageLocation, snapshot: PageSnapshot) {
+        if debug::enabled(debug::Target::Nav, debug::Level::Info) {
+            let location = location_display(Some(&location), &snapshot.title);
+            let location = debug::shorten(&location, 72);
+            debug::log(
+                debug::Target::Nav,
+                debug::Level::Info,
+                format_args!("restore {location}"),
+            );
+        }
+        let (base, resources) = match &location {
+            PageLocation::Url(url) => (
+                PageBase::Url(url.clone()),
+                ResourceManager::from_url(url.clone()),
+            ),
+            PageLocation::File(path) => {
+                let dir = file_loader::local_base_dir(path);
+                (
+                    PageBase::FileDir(dir.clone()),
+                    ResourceManager::from_file_dir(dir),
+                )
+            }
+        };
+        self.reader = None;
+        self.scroll_motion = None;
+        let tab = self.tab_mut();
+        tab.title = snapshot.title;
+        tab.document = snapshot.document;
+        tab.head = PageHead::unresolved();
+        tab.styles = StyleComputer::empty();
+        tab.style_sources = snapshot.style_sources;
+        tab.styles_viewport = None;
+        tab.cached_layout = None;
+        tab.scroll_y_px = 0;
+        tab.scroll_x_px = 0;
+        tab.scroll_restore = None;
+        tab.url_loader = None;
+        tab.base = Some(base);
+        tab.location = Some(location);
+        tab.tls = None;
+        tab.insecure_content = false;
+        tab.resources = Some(resources);
+        self.apply_document_base();
+        let tab = self.tab_mut();
+        tab.styles_dirty = false;
+        tab.last_stylesheet_change = None;
+        tab.waterfall.clear();
+        tab.waterfall_reported = false;
+        tab.load_timing = LoadTiming::new();
+    }
+
     fn go_back(&mut self) -> Result<TickResult, String> {
-        while let Some(location) = self.history.pop() {
-            if self.navigate_to_location(location).is_ok() {
+        let mut current = self.history_entry();
+        while let Some(entry) = self.tab_mut().history.go_back(current.take()) {
+            if self.restore_history_entry(entry).is_ok() {
                 return Ok(TickResult {
                     needs_redraw: true,
                     ready_for_screenshot: false,
@@ -625,17 +2053,41 @@ impl BrowserApp {
         Ok(TickResult::default())
     }
 
-    fn navigate_href(&mut self, href: &str) -> Result<(), String> {
+    fn go_forward(&mut self) -> Result<TickResult, String> {
+        let mut current = self.history_entry();
+        while let Some(entry) = self.tab_mut().history.go_forward(current.take()) {
+            if self.restore_history_entry(entry).is_ok() {
+                return Ok(TickResult {
+                    needs_redraw: true,
+                    ready_for_screenshot: false,
+                    pending_resources: 0,
+                });
+            }
+        }
+        Ok(TickResult::default())
+    }
+
+    /// Points the page's resources at the base its `<base href>` gives, once the document is in.
+    fn apply_document_base(&mut self) {
+        let tab = self.tab_mut();
+        let (Some(base), Some(resources)) = (&tab.base, tab.resources.as_mut()) else {
+            return;
+        };
+        resources.set_base(match base.for_document(&tab.document) {
+            PageBase::Url(url) => crate::resources::ResourceBase::Url(url),
+            PageBase::FileDir(dir) => crate::resources::ResourceBase::FileDir(dir),
+        });
+    }
+
+    fn resolve_href(&self, href: &str) -> Option<PageLocation> {
         let href = href.trim();
         if href.is_empty() {
-            return Ok(());
+            return None;
         }
 
-        let previous = self.location.clone();
-
         if href.starts_with("http://") || href.starts_with("https://") {
-            let url = match Url::parse(href) {
-                Ok(url) => url,
+            return match Url::parse(href) {
+                Ok(url) => Some(PageLocation::Url(url)),
                 Err(_) => {
                     if debug::enabled(debug::Target::Nav, debug::Level::Debug) {
                         let href = debug::shorten(href, 64);
@@ -645,35 +2097,110 @@ impl BrowserApp {
                             format_args!("href? {href}"),
                         );
                     }
-                    return Ok(());
+                    None
                 }
             };
-            self.begin_url_navigation(url)?;
-            self.maybe_push_history(previous);
+        }
+
+        match self.tab().base.as_ref()?.for_document(&self.tab().document) {
+            PageBase::Url(base) => base.resolve(href).map(PageLocation::Url),
+            PageBase::FileDir(dir) => Some(PageLocation::File(file_loader::resolve_link_path(
+                &dir, href,
+            ))),
+        }
+    }
+
+    fn navigate_href(&mut self, href: &str) -> Result<(), String> {
+        let Some(location) = self.resolve_href(href) else {
             return Ok(());
+        };
+
+        let previous = self.history_entry();
+        match location {
+            PageLocation::Url(url) => self.begin_url_navigation(url)?,
+            PageLocation::File(path) => {
+                if self.load_file(&path).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        self.maybe_push_history(previous);
+        Ok(())
+    }
+
+    fn open_href_in_new_tab(&mut self, href: &str) -> Result<(), String> {
+        let Some(location) = self.resolve_href(href) else {
+            return Ok(());
+        };
+
+        let opener = self.active_tab;
+        let is_file = matches!(location, PageLocation::File(_));
+        self.open_tab();
+        if let Err(err) = self.navigate_to_location(location) {
+            self.close_tab(self.active_tab);
+            self.switch_tab(opener);
+            // Unreadable files are ignored, as for links followed in the same tab.
+            if !is_file {
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Opens an empty tab with the location field focused, as for Ctrl+T.
+    fn open_blank_tab(&mut self) {
+        self.open_tab();
+        if let Some(chrome) = self.chrome.as_mut() {
+            chrome.begin_editing("");
+        }
+    }
+
+    /// Inserts a blank tab after the shown one and switches to it.
+    fn open_tab(&mut self) {
+        self.tabs.insert(self.active_tab + 1, TabState::blank());
+        self.show_tab(self.active_tab + 1);
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index != self.active_tab && index < self.tabs.len() {
+            self.show_tab(index);
+        }
+    }
+
+    /// Closing the last tab leaves a blank one in its place.
+    fn close_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        if self.tabs.len() == 1 {
+            self.tabs[0] = TabState::blank();
+            self.show_tab(0);
+            return;
+        }
+        let closing_shown = index == self.active_tab;
+        self.tabs.remove(index);
+        if index < self.active_tab || self.active_tab == self.tabs.len() {
+            self.active_tab -= 1;
         }
-
-        match (self.base.clone(), previous) {
-            (Some(PageBase::Url(base)), previous) => {
-                let Some(url) = base.resolve(href) else {
-                    return Ok(());
-                };
-                self.begin_url_navigation(url)?;
-                self.maybe_push_history(previous);
-            }
-            (Some(PageBase::FileDir(dir)), previous) => {
-                let path = resolve_link_file_path(&dir, href);
-                if let Err(_) = self.load_file(&path) {
-                    return Ok(());
-                }
-                self.maybe_push_history(previous);
-            }
-            (None, _) => {}
+        if closing_shown {
+            self.show_tab(self.active_tab);
         }
+    }
 
-        Ok(())
+    fn show_tab(&mut self, index: usize) {
+        self.active_tab = index;
+        // The window still shows the previous tab, and a scrollbar drag doesn't carry over.
+        let tab = self.tab_mut();
+        tab.painted = None;
+        tab.scrollbar_grab = None;
+        self.scroll_motion = None;
+        self.reader = None;
+        if let Some(chrome) = self.chrome.as_mut() {
+            chrome.stop_editing();
+        }
     }
 
+    /// The shown page stays up, with its base and resources, until the new one has been parsed.
     fn begin_url_navigation(&mut self, url: Url) -> Result<(), String> {
         if debug::enabled(debug::Target::Nav, debug::Level::Info) {
             let url = debug::shorten(url.as_str(), 72);
@@ -684,103 +2211,243 @@ impl BrowserApp {
             );
         }
         let loader = UrlLoader::new(url.clone())?;
-        self.title = url.as_str().to_owned();
-        self.base = Some(PageBase::Url(url.clone()));
-        self.location = Some(PageLocation::Url(url.clone()));
-        self.resources = Some(ResourceManager::from_url(url.clone()));
-        self.document = crate::html::parse_document("<p>Loading...</p>");
-        self.styles = StyleComputer::empty();
-        self.style_sources = Vec::new();
-        self.styles_viewport = None;
-        self.cached_layout = None;
-        self.scroll_y_px = 0;
-        self.url_loader = Some(loader);
-        self.styles_dirty = false;
-        self.last_stylesheet_change = None;
+        let tab = self.tab_mut();
+        tab.title = url.as_str().to_owned();
+        tab.location = Some(PageLocation::Url(url));
+        tab.scroll_restore = None;
+        tab.url_loader = Some(loader);
+        tab.waterfall.clear();
+        tab.waterfall_reported = false;
+        tab.load_timing = LoadTiming::new();
+        Ok(())
+    }
+
+    fn tick_live_reload(&mut self) -> Result<bool, String> {
+        let Some(PageLocation::File(path)) = &self.tab().location else {
+            self.tab_mut().live_reload = None;
+            return Ok(false);
+        };
+        let path = path.clone();
+        if self
+            .tab()
+            .live_reload
+            .as_ref()
+            .is_none_or(|live| live.page() != path)
+        {
+            self.tab_mut().live_reload = Some(LiveReload::new(&path)?);
+            self.watch_stylesheet_files()?;
+        }
+        let tab = self.tab_mut();
+        let Some(live) = tab.live_reload.as_mut() else {
+            return Ok(false);
+        };
+        if let Some(resources) = &tab.resources {
+            for file in resources.local_files() {
+                live.watch(&file)?;
+            }
+        }
+        if !live.poll() {
+            return Ok(false);
+        }
+
+        let scroll_y_px = self.tab().scroll_y_px;
+        if let Err(err) = self.load_file(&path) {
+            if debug::enabled(debug::Target::Nav, debug::Level::Warn) {
+                let err = debug::shorten(&err, 64);
+                debug::log(
+                    debug::Target::Nav,
+                    debug::Level::Warn,
+                    format_args!("reload! err={err}"),
+                );
+            }
+            return Ok(false);
+        }
+        self.tab_mut().scroll_y_px = scroll_y_px;
+        self.watch_stylesheet_files()?;
+        Ok(true)
+    }
+
+    /// Drops the cached layout after the document was mutated. Styles are rebuilt only when a
+    /// stylesheet changed; a fetched page keeps the stylesheets its loader collected.
+    fn apply_document_mutations(&mut self) -> Result<bool, String> {
+        let mutations = self.tab_mut().document.take_mutations();
+        if mutations.is_empty() {
+            return Ok(false);
+        }
+        if mutations.stylesheets {
+            let base = match &self.tab().base {
+                Some(PageBase::Url(_)) => None,
+                Some(PageBase::FileDir(dir)) => {
+                    Some(Some(local_stylesheet_base(dir, &self.tab().document)))
+                }
+                None => Some(None),
+            };
+            if let Some(base) = base {
+                self.tab_mut().style_sources =
+                    collect_page_stylesheet_sources(&self.tab().document, base.as_ref())?;
+                self.tab_mut().styles_viewport = None;
+            }
+        }
+        self.tab_mut().cached_layout = None;
+        if debug::enabled(debug::Target::Layout, debug::Level::Debug) {
+            debug::log(
+                debug::Target::Layout,
+                debug::Level::Debug,
+                format_args!(
+                    "dirty n={} css={}",
+                    mutations.dirty.len(),
+                    u8::from(mutations.stylesheets)
+                ),
+            );
+        }
+        Ok(true)
+    }
+
+    fn watch_stylesheet_files(&mut self) -> Result<(), String> {
+        let tab = self.tab_mut();
+        let (Some(live), Some(PageBase::FileDir(dir))) = (tab.live_reload.as_mut(), &tab.base)
+        else {
+            return Ok(());
+        };
+        let ResourceBase::FileDir(dir) = local_stylesheet_base(dir, &tab.document) else {
+            return Ok(());
+        };
+        let mut paths = Vec::new();
+        collect_local_stylesheet_paths(&tab.document.root, &dir, &mut paths);
+        for path in paths {
+            live.watch(&path)?;
+        }
         Ok(())
     }
 
+    /// Bytes of the local page file, its local stylesheets and its local images.
+    fn local_bytes(&self) -> u64 {
+        let file_len =
+            |path: &std::path::Path| std::fs::metadata(path).map_or(0, |meta| meta.len());
+        let mut bytes = self
+            .tab()
+            .resources
+            .as_ref()
+            .map_or(0, ResourceManager::local_bytes);
+        if let Some(PageLocation::File(path)) = &self.tab().location
+            && path.is_file()
+        {
+            bytes += file_len(path);
+        }
+        if let Some(PageBase::FileDir(dir)) = &self.tab().base
+            && let ResourceBase::FileDir(dir) = local_stylesheet_base(dir, &self.tab().document)
+        {
+            let mut paths = Vec::new();
+            collect_local_stylesheet_paths(&self.tab().document.root, &dir, &mut paths);
+            bytes += paths.iter().map(|path| file_len(path)).sum::<u64>();
+        }
+        bytes
+    }
+
     fn load_file(&mut self, path: &std::path::Path) -> Result<(), String> {
-        let source = std::fs::read_to_string(path)
-            .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+        let page = file_loader::read_local_page(path)?;
         if debug::enabled(debug::Target::Nav, debug::Level::Info) {
             let path_display = path.display().to_string();
             let path_display = debug::shorten(&path_display, 64);
             debug::log(
                 debug::Target::Nav,
                 debug::Level::Info,
-                format_args!("nav file={path_display} bytes={}", source.len()),
+                format_args!("nav file={path_display} bytes={}", page.source.len()),
             );
         }
-        let title = path
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("Browser")
-            .to_owned();
-        let base_dir = path
-            .parent()
-            .map(std::path::Path::to_owned)
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
-        let mut document = crate::html::parse_document(&source);
-        crate::js::execute_inline_scripts(&mut document);
-        let resource_base = ResourceBase::FileDir(base_dir.clone());
-        let style_sources = collect_page_stylesheet_sources(&document, Some(&resource_base))?;
-
-        self.title = title;
-        self.document = document;
-        self.styles = StyleComputer::empty();
-        self.style_sources = style_sources;
-        self.styles_viewport = None;
-        self.cached_layout = None;
-        self.scroll_y_px = 0;
-        self.url_loader = None;
-        self.base = Some(PageBase::FileDir(base_dir));
-        self.location = Some(PageLocation::File(path.to_owned()));
-        self.resources = match &self.base {
+        let title = page.title;
+        let base_dir = page.base_dir;
+        let parsed = panic_page::catch_page_panic(|| {
+            let parse = debug::span(debug::Target::Html, "parse");
+            let mut document = crate::html::parse_document(&page.source);
+            let parse_time = parse.elapsed();
+            parse.end(format_args!("bytes={}", page.source.len()));
+            crate::js::execute_inline_scripts(&mut document);
+            let resource_base = local_stylesheet_base(&base_dir, &document);
+            collect_page_stylesheet_sources(&document, Some(&resource_base))
+                .map(|style_sources| (document, style_sources, parse_time))
+        });
+        let (document, style_sources, parse_time) = match parsed {
+            Ok(parsed) => parsed?,
+            Err(message) => (
+                panic_page::panic_document(&message),
+                Vec::new(),
+                Duration::ZERO,
+            ),
+        };
+
+        self.reader = None;
+        self.scroll_motion = None;
+        let tab = self.tab_mut();
+        tab.title = title;
+        tab.document = document;
+        tab.head = PageHead::unresolved();
+        tab.styles = StyleComputer::empty();
+        tab.style_sources = style_sources;
+        tab.styles_viewport = None;
+        tab.cached_layout = None;
+        tab.scroll_y_px = 0;
+        tab.scroll_x_px = 0;
+        tab.scroll_restore = None;
+        tab.url_loader = None;
+        tab.base = Some(PageBase::FileDir(base_dir));
+        tab.location = Some(PageLocation::File(path.to_owned()));
+        tab.tls = None;
+        tab.insecure_content = false;
+        tab.resources = match &tab.base {
             Some(PageBase::Url(url)) => Some(ResourceManager::from_url(url.clone())),
             Some(PageBase::FileDir(dir)) => Some(ResourceManager::from_file_dir(dir.clone())),
             None => None,
         };
-        self.styles_dirty = false;
-        self.last_stylesheet_change = None;
+        self.apply_document_base();
+        let tab = self.tab_mut();
+        tab.styles_dirty = false;
+        tab.last_stylesheet_change = None;
+        tab.waterfall.clear();
+        tab.waterfall_reported = false;
+        tab.load_timing = LoadTiming::new();
+        tab.load_timing.phases.parse = parse_time;
         Ok(())
     }
 
     fn ensure_styles_for_viewport(&mut self, viewport: Viewport) -> Result<(), String> {
-        if self.styles_viewport == Some(viewport) {
+        let media = crate::css_media::current_preferences();
+        if self.tab().styles_viewport == Some(viewport) && self.styles_media == media {
             return Ok(());
         }
 
-        let mut stylesheets = Vec::new();
-        for source in &self.style_sources {
-            if let Some(media) = source.media.as_deref() {
-                if !crate::css_media::media_query_matches(media, viewport) {
-                    continue;
-                }
-            }
-            stylesheets.push(source.stylesheet.clone());
+        // `@media` blocks are matched against the viewport while styling each element, so
+        // only a sheet whose own `media` attribute flipped needs a new `StyleComputer`.
+        let sheet_media = sheet_media(&self.tab().style_sources, viewport, media);
+        let rebuild =
+            self.tab().styles_viewport.is_none() || sheet_media != self.tab().styles_sheet_media;
+        self.tab_mut().styles_viewport = Some(viewport);
+        self.styles_media = media;
+        self.tab_mut().cached_layout = None;
+        if !rebuild {
+            return Ok(());
         }
 
-        self.styles = StyleComputer::from_stylesheets(stylesheets);
-        self.styles_viewport = Some(viewport);
-        self.cached_layout = None;
-        if debug::enabled(debug::Target::Css, debug::Level::Debug) {
-            debug::log(
-                debug::Target::Css,
-                debug::Level::Debug,
-                format_args!(
-                    "styles+ vw={} vh={} sheets={}",
-                    viewport.width_px,
-                    viewport.height_px,
-                    self.style_sources.len()
-                ),
-            );
-        }
+        let span = debug::span(debug::Target::Css, "style");
+        let styles = styles_for(&self.tab().style_sources, &sheet_media);
+        styles.continue_animations_from(&self.tab().styles);
+        let tab = self.tab_mut();
+        tab.styles = styles;
+        tab.styles_sheet_media = sheet_media;
+        tab.load_timing.phases.style += span.elapsed();
+        span.end(format_args!(
+            "vw={} vh={} sheets={}",
+            viewport.width_px,
+            viewport.height_px,
+            self.tab().style_sources.len()
+        ));
         Ok(())
     }
 }
 
 enum ResourceBase {
+    /// A local page whose `<base href>` is a web address.
+    Url(Url),
     FileDir(std::path::PathBuf),
 }
 
@@ -788,35 +2455,43 @@ impl BrowserApp {
     fn from_html_with_base(
         title: &str,
         html_source: &str,
-        base: Option<ResourceBase>,
+        base_dir: Option<&std::path::Path>,
     ) -> Result<Self, String> {
+        let parse = debug::span(debug::Target::Html, "parse");
         let mut document = crate::html::parse_document(html_source);
+        let parse_time = parse.elapsed();
+        parse.end(format_args!("bytes={}", html_source.len()));
         crate::js::execute_inline_scripts(&mut document);
-        Self::from_document_with_base(title, document, base)
+        let mut app = Self::from_document_with_base(title, document, base_dir)?;
+        app.tab_mut().load_timing.phases.parse = parse_time;
+        Ok(app)
     }
 
     fn from_document_with_base(
         title: &str,
         document: Document,
-        base: Option<ResourceBase>,
+        base_dir: Option<&std::path::Path>,
     ) -> Result<Self, String> {
+        let base = base_dir.map(|dir| local_stylesheet_base(dir, &document));
         let style_sources = collect_page_stylesheet_sources(&document, base.as_ref())?;
-        let styles = StyleComputer::empty();
         Ok(Self {
-            title: title.to_owned(),
-            document,
-            styles,
-            style_sources,
-            styles_viewport: None,
-            cached_layout: None,
-            scroll_y_px: 0,
-            url_loader: None,
-            base: None,
-            location: None,
-            history: Vec::new(),
-            resources: None,
-            styles_dirty: false,
-            last_stylesheet_change: None,
+            tabs: vec![TabState {
+                title: title.to_owned(),
+                document,
+                style_sources,
+                ..TabState::blank()
+            }],
+            styles_media: MediaPreferences::default(),
+            scroll_motion: None,
+            layout_virtualization: false,
+            chrome: None,
+            active_tab: 0,
+            zoom_percent: 100,
+            watch_files: false,
+            element_boxes: false,
+            reader_mode: false,
+            reader: None,
+            report_timing: false,
         })
     }
 }
@@ -827,6 +2502,32 @@ struct StylesheetSource {
     media: Option<String>,
 }
 
+/// Which of `sources` apply under their `media` attribute.
+fn sheet_media(
+    sources: &[StylesheetSource],
+    viewport: Viewport,
+    media: MediaPreferences,
+) -> Vec<bool> {
+    sources
+        .iter()
+        .map(|source| {
+            source.media.as_deref().is_none_or(|query| {
+                crate::css_media::media_query_matches_with(query, viewport, media)
+            })
+        })
+        .collect()
+}
+
+fn styles_for(sources: &[StylesheetSource], sheet_media: &[bool]) -> StyleComputer {
+    let stylesheets = sources
+        .iter()
+        .zip(sheet_media)
+        .filter(|(_, applies)| **applies)
+        .map(|(source, _)| source.stylesheet.clone())
+        .collect();
+    StyleComputer::from_stylesheets(stylesheets)
+}
+
 fn collect_page_stylesheet_sources(
     document: &Document,
     base: Option<&ResourceBase>,
@@ -875,6 +2576,23 @@ fn collect_page_stylesheet_sources_from_element(
     Ok(())
 }
 
+/// The first entry of a `text/uri-list`, which skips `#` comment lines, or the first line of
+/// dropped plain text.
+fn dropped_location(dropped: &str) -> Option<&str> {
+    dropped
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn location_display(location: Option<&PageLocation>, title: &str) -> String {
+    match location {
+        Some(PageLocation::Url(url)) => url.as_str().to_owned(),
+        Some(PageLocation::File(path)) => path.display().to_string(),
+        None => title.to_owned(),
+    }
+}
+
 fn is_stylesheet_link(element: &crate::dom::Element) -> bool {
     if element.name != "link" {
         return false;
@@ -892,8 +2610,32 @@ fn load_stylesheet_text(href: &str, base: Option<&ResourceBase>) -> Result<Optio
         return Ok(None);
     }
 
-    if href.starts_with("http://") || href.starts_with("https://") {
-        return Ok(Some(crate::net::fetch_url_text(href)?));
+    if let Some(css) = data_url_stylesheet_text(href) {
+        return Ok(Some(css));
+    }
+
+    let url = if href.starts_with("http://") || href.starts_with("https://") {
+        Url::parse(href).ok()
+    } else {
+        match base {
+            Some(ResourceBase::Url(base)) => base.resolve(href),
+            _ => None,
+        }
+    };
+    if let Some(url) = url {
+        if !crate::resources::resource_policy().allows_url(None, &url) {
+            if debug::enabled(debug::Target::Css, debug::Level::Warn) {
+                let url = debug::shorten(url.as_str(), 64);
+                debug::log(
+                    debug::Target::Css,
+                    debug::Level::Warn,
+                    format_args!("css! url={url} err=origin_not_allowed"),
+                );
+            }
+            return Ok(None);
+        }
+        let css = crate::net::fetch_url_text(url.as_str())?;
+        return Ok(stylesheet_fits(css.len()).then_some(css));
     }
 
     let Some(base) = base else {
@@ -901,16 +2643,54 @@ fn load_stylesheet_text(href: &str, base: Option<&ResourceBase>) -> Result<Optio
     };
 
     match base {
+        ResourceBase::Url(_) => Ok(None),
         ResourceBase::FileDir(dir) => {
             let path = resolve_stylesheet_file_path(dir, href);
             match std::fs::read_to_string(&path) {
-                Ok(css) => Ok(Some(css)),
+                Ok(css) => Ok(stylesheet_fits(css.len()).then_some(css)),
                 Err(_) => Ok(None),
             }
         }
     }
 }
 
+/// Whether a stylesheet `len` bytes long is applied; larger ones than
+/// [`crate::net::ResourceLimits::max_css_bytes`] are left out of the page.
+fn stylesheet_fits(len: usize) -> bool {
+    let limit = crate::net::resource_limits().max_css_bytes;
+    if len <= limit {
+        return true;
+    }
+    if debug::enabled(debug::Target::Css, debug::Level::Warn) {
+        debug::log(
+            debug::Target::Css,
+            debug::Level::Warn,
+            format_args!("css! bytes={len} err=too_large limit={limit}"),
+        );
+    }
+    false
+}
+
+/// The CSS carried by a `data:` stylesheet href, or `None` for other hrefs and other MIME
+/// types.
+fn data_url_stylesheet_text(href: &str) -> Option<String> {
+    let data = crate::url::DataUrl::parse(href)?;
+    let essence = data.essence();
+    if !essence.eq_ignore_ascii_case("text/css") && !essence.eq_ignore_ascii_case("text/plain") {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&data.body).into_owned())
+}
+
+/// What a local page's stylesheet links resolve against once `document`'s `<base href>`
+/// applies to `base_dir`.
+fn local_stylesheet_base(base_dir: &std::path::Path, document: &Document) -> ResourceBase {
+    match PageBase::FileDir(base_dir.to_owned()).for_document(document) {
+        PageBase::Url(url) => ResourceBase::Url(url),
+        PageBase::FileDir(dir) => ResourceBase::FileDir(dir),
+    }
+}
+
 fn resolve_stylesheet_file_path(base_dir: &std::path::Path, href: &str) -> std::path::PathBuf {
     let href = href
         .split('#')
@@ -926,8 +2706,28 @@ fn resolve_stylesheet_file_path(base_dir: &std::path::Path, href: &str) -> std::
     base_dir.join(href)
 }
 
-fn resolve_link_file_path(base_dir: &std::path::Path, href: &str) -> std::path::PathBuf {
-    resolve_stylesheet_file_path(base_dir, href)
+fn collect_local_stylesheet_paths(
+    element: &crate::dom::Element,
+    base_dir: &std::path::Path,
+    out: &mut Vec<std::path::PathBuf>,
+) {
+    if is_stylesheet_link(element)
+        && let Some(href) = element.attributes.get("href")
+    {
+        let href = href.trim();
+        if !href.is_empty()
+            && !href.starts_with("http://")
+            && !href.starts_with("https://")
+            && !crate::url::DataUrl::is_data_url(href)
+        {
+            out.push(resolve_stylesheet_file_path(base_dir, href));
+        }
+    }
+    for child in &element.children {
+        if let crate::dom::Node::Element(el) = child {
+            collect_local_stylesheet_paths(el, base_dir, out);
+        }
+    }
 }
 
 impl crate::app::App for BrowserApp {
@@ -943,6 +2743,10 @@ impl crate::app::App for BrowserApp {
         BrowserApp::go_back(self)
     }
 
+    fn navigate_forward(&mut self) -> Result<TickResult, String> {
+        BrowserApp::go_forward(self)
+    }
+
     fn mouse_down(
         &mut self,
         x_px: i32,
@@ -952,15 +2756,505 @@ impl crate::app::App for BrowserApp {
         BrowserApp::mouse_down(self, x_px, y_px, viewport)
     }
 
+    fn mouse_up(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(BrowserApp::mouse_up(self))
+    }
+
     fn mouse_wheel(&mut self, delta_y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
         BrowserApp::mouse_wheel(self, delta_y_px, viewport)
     }
+
+    fn horizontal_wheel(
+        &mut self,
+        delta_x_px: i32,
+        viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(BrowserApp::horizontal_wheel(self, delta_x_px, viewport))
+    }
+
+    fn wheel_notch(&mut self, delta_y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
+        Ok(BrowserApp::wheel_notch(self, delta_y_px, viewport))
+    }
+
+    fn scroll_fling(
+        &mut self,
+        velocity_y_px_per_s: f64,
+        _viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        Ok(BrowserApp::scroll_fling(self, velocity_y_px_per_s))
+    }
+
+    fn mouse_move(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        BrowserApp::mouse_move(self, x_px, y_px, viewport)
+    }
+
+    fn key_down(
+        &mut self,
+        input: KeyInput,
+        _viewport: Viewport,
+    ) -> Result<Option<TickResult>, String> {
+        BrowserApp::key_down(self, input)
+    }
+
+    fn paste_text(&mut self, text: &str, _viewport: Viewport) -> Result<TickResult, String> {
+        Ok(BrowserApp::paste_text(self, text))
+    }
+
+    fn open_dropped(&mut self, dropped: &str, _viewport: Viewport) -> Result<TickResult, String> {
+        Ok(BrowserApp::open_dropped(self, dropped))
+    }
+
+    fn zoom_percent(&self) -> u32 {
+        BrowserApp::zoom_percent(self)
+    }
+
+    fn set_zoom_percent(&mut self, percent: u32) {
+        BrowserApp::set_zoom_percent(self, percent)
+    }
+
+    fn document_height_px(&self) -> Option<i32> {
+        BrowserApp::document_height_px(self)
+    }
+
+    fn scroll_y_px(&self) -> i32 {
+        BrowserApp::scroll_y_px(self)
+    }
+
+    fn set_scroll_y_px(&mut self, y_px: i32) {
+        BrowserApp::set_scroll_y_px(self, y_px)
+    }
+
+    fn window_title(&self) -> Option<String> {
+        Some(BrowserApp::window_title(self).to_owned())
+    }
+
+    fn window_icon(&self) -> Option<Arc<Argb32Image>> {
+        self.favicon()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn drops_open_the_first_uri_in_the_list() {
+        assert_eq!(
+            dropped_location(
+                "# from a file manager\r\nfile:///tmp/a%20b.html\r\nfile:///tmp/c.html\r\n"
+            ),
+            Some("file:///tmp/a%20b.html")
+        );
+        assert_eq!(dropped_location("  example.com \n"), Some("example.com"));
+        assert_eq!(dropped_location("\n# nothing\n"), None);
+    }
+
+    #[test]
+    fn https_pages_lose_the_padlock_to_http_stylesheets() {
+        let mut app = BrowserApp::from_html("page.html", "<p>Hi</p>").unwrap();
+        assert_eq!(app.security_indicator(), SecurityIndicator::None);
+
+        let secure = Url::parse("https://example.com/").unwrap();
+        app.tab_mut().location = Some(PageLocation::Url(secure));
+        assert_eq!(app.security_indicator(), SecurityIndicator::Secure);
+        app.tab_mut().insecure_content = true;
+        assert_eq!(app.security_indicator(), SecurityIndicator::NotSecure);
+
+        let plain = Url::parse("http://example.com/").unwrap();
+        app.tab_mut().location = Some(PageLocation::Url(plain));
+        app.tab_mut().insecure_content = false;
+        assert_eq!(app.security_indicator(), SecurityIndicator::NotSecure);
+    }
+
+    #[test]
+    fn closing_a_tab_shows_its_neighbour() {
+        let mut app = BrowserApp::from_html("one.html", "<p>1</p>").unwrap();
+        app.open_tab();
+        app.tab_mut().title = "two".to_owned();
+        app.open_tab();
+        app.tab_mut().title = "three".to_owned();
+        app.switch_tab(0);
+        assert_eq!(app.title(), "one.html");
+
+        app.close_tab(0);
+        assert_eq!((app.active_tab, app.title()), (0, "two"));
+        app.close_tab(1);
+        assert_eq!((app.tab_count(), app.title()), (1, "two"));
+        app.open_tab();
+        app.close_tab(1);
+        assert_eq!((app.active_tab, app.title()), (0, "two"));
+        app.close_tab(0);
+        assert_eq!((app.tab_count(), app.title()), (1, tabs::NEW_TAB_TITLE));
+    }
+
+    /// Panics measuring `"explode"`, as a layout bug would, and drawing `"detonate"`, as a paint
+    /// bug would, counting the opacity groups left open.
+    struct PanickingPainter {
+        inner: crate::platform::SoftwarePainter,
+        open_opacity: usize,
+    }
+
+    impl crate::render::TextMeasurer for PanickingPainter {
+        fn font_metrics_px(&self, style: crate::render::TextStyle) -> crate::render::FontMetricsPx {
+            self.inner.font_metrics_px(style)
+        }
+
+        fn text_width_px(
+            &self,
+            text: &str,
+            style: crate::render::TextStyle,
+        ) -> Result<i32, String> {
+            if text == "explode" {
+                panic!("measuring failed");
+            }
+            self.inner.text_width_px(text, style)
+        }
+    }
+
+    impl Painter for PanickingPainter {
+        fn clear(&mut self) -> Result<(), String> {
+            self.inner.clear()
+        }
+
+        fn push_opacity(&mut self, opacity: u8) -> Result<(), String> {
+            self.open_opacity += 1;
+            self.inner.push_opacity(opacity)
+        }
+
+        fn pop_opacity(&mut self, opacity: u8) -> Result<(), String> {
+            self.open_opacity -= 1;
+            self.inner.pop_opacity(opacity)
+        }
+
+        fn fill_rect(
+            &mut self,
+            x_px: i32,
+            y_px: i32,
+            width_px: i32,
+            height_px: i32,
+            color: crate::geom::Color,
+        ) -> Result<(), String> {
+            self.inner.fill_rect(x_px, y_px, width_px, height_px, color)
+        }
+
+        fn fill_rounded_rect(
+            &mut self,
+            x_px: i32,
+            y_px: i32,
+            width_px: i32,
+            height_px: i32,
+            radius_px: i32,
+            color: crate::geom::Color,
+        ) -> Result<(), String> {
+            self.inner
+                .fill_rounded_rect(x_px, y_px, width_px, height_px, radius_px, color)
+        }
+
+        fn stroke_rounded_rect(
+            &mut self,
+            x_px: i32,
+            y_px: i32,
+            width_px: i32,
+            height_px: i32,
+            radius_px: i32,
+            border_width_px: i32,
+            color: crate::geom::Color,
+        ) -> Result<(), String> {
+            self.inner.stroke_rounded_rect(
+                x_px,
+                y_px,
+                width_px,
+                height_px,
+                radius_px,
+                border_width_px,
+                color,
+            )
+        }
+
+        fn draw_text(
+            &mut self,
+            x_px: i32,
+            y_px: i32,
+            text: &str,
+            style: crate::render::TextStyle,
+        ) -> Result<(), String> {
+            if text == "detonate" {
+                panic!("drawing failed");
+            }
+            self.inner.draw_text(x_px, y_px, text, style)
+        }
+
+        fn draw_image(
+            &mut self,
+            x_px: i32,
+            y_px: i32,
+            width_px: i32,
+            height_px: i32,
+            image: &Argb32Image,
+            opacity: u8,
+        ) -> Result<(), String> {
+            self.inner
+                .draw_image(x_px, y_px, width_px, height_px, image, opacity)
+        }
+
+        fn flush(&mut self) -> Result<(), String> {
+            self.inner.flush()
+        }
+    }
+
+    #[test]
+    fn a_panicking_layout_or_paint_shows_the_crash_page() {
+        let viewport = Viewport {
+            width_px: 400,
+            height_px: 300,
+        };
+        for html in [
+            "<p>explode</p>",
+            "<div style='opacity: 0.5'><p>detonate</p></div>",
+        ] {
+            let mut app = BrowserApp::from_html("page.html", html).unwrap();
+            let mut painter = PanickingPainter {
+                inner: crate::platform::SoftwarePainter::new(viewport).unwrap(),
+                open_opacity: 0,
+            };
+            app.tick().unwrap();
+            app.render(&mut painter, viewport).unwrap();
+
+            assert_eq!(app.document().title().as_deref(), Some("Page crashed"));
+            let shown = extract::blocks_to_text(&app.text_blocks());
+            assert!(shown.contains("This page crashed the browser"), "{shown}");
+            assert_eq!(painter.open_opacity, 0, "{html}");
+        }
+    }
+
+    #[test]
+    fn page_data_can_be_built_off_the_platform_thread() {
+        fn assert_send<T: Send>() {}
+        assert_send::<Document>();
+        assert_send::<StyleComputer>();
+        assert_send::<crate::layout::LayoutOutput>();
+        assert_send::<crate::style::ComputedStyle>();
+    }
+
+    #[test]
+    fn window_title_follows_the_document_title() {
+        let mut app = BrowserApp::from_html("page.html", "<p>Untitled</p>").unwrap();
+        app.tick().unwrap();
+        assert_eq!(app.window_title(), "page.html");
+
+        let mut app =
+            BrowserApp::from_html("page.html", "<title> Home  page </title><p>Hi</p>").unwrap();
+        assert_eq!(app.window_title(), "page.html");
+        app.tick().unwrap();
+        assert_eq!(app.window_title(), "Home page");
+    }
+
+    #[test]
+    fn element_geometry_follows_layout_and_scrolling() {
+        let html = "<body style='margin:0'><div id=top style='height:50px'></div>\
+                    <p style='margin:0'>go <a id=link href=x>here</a></p><p style='display:none'>gone</p>\
+                    <div style='height:500px'></div><div id=far style='height:20px'></div></body>";
+        let viewport = Viewport {
+            width_px: 200,
+            height_px: 100,
+        };
+        let mut app = BrowserApp::from_html("test", html).unwrap();
+        app.set_element_boxes(true);
+        let mut painter = crate::platform::SoftwarePainter::new(viewport).unwrap();
+        app.render(&mut painter, viewport).unwrap();
+
+        let top = app.element_geometry_by_id("top").unwrap();
+        assert_eq!(
+            top.border_box,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 200,
+                height: 50,
+            }
+        );
+        assert!(top.in_viewport);
+        let link = app.element_geometry_by_id("link").unwrap();
+        assert_eq!(link.border_box.y, 50);
+        assert!(link.border_box.x > 0 && link.border_box.width > 0);
+        let paragraphs = app.element_geometry("p");
+        assert!(paragraphs[0].is_some() && paragraphs[1].is_none());
+
+        let far = app.element_geometry_by_id("far").unwrap();
+        assert!(!far.in_viewport);
+        app.set_scroll_y_px(far.border_box.y - 40);
+        app.render(&mut painter, viewport).unwrap();
+        let far = app.element_geometry_by_id("far").unwrap();
+        assert!(far.in_viewport);
+        assert!(app.scroll_y_px() > 0);
+        assert_eq!(far.viewport_box.y, far.border_box.y - app.scroll_y_px());
+    }
+
+    #[test]
+    fn text_dumps_of_tall_pages_reach_the_last_paragraph() {
+        let args = crate::cli::parse_args(
+            ["page.html", "--virtualize-layout", "--dump-text"]
+                .into_iter()
+                .map(std::ffi::OsString::from),
+        )
+        .unwrap();
+        let html = format!(
+            "<body>{}<p>The last paragraph.</p></body>",
+            "<p style='height:400px'>Filler.</p>".repeat(50)
+        );
+        let viewport = Viewport {
+            width_px: 200,
+            height_px: 100,
+        };
+        let mut app = BrowserApp::from_html("test", &html).unwrap();
+        app.set_layout_virtualization(args.virtualizes_layout());
+        let mut painter = crate::platform::SoftwarePainter::new(viewport).unwrap();
+        app.render(&mut painter, viewport).unwrap();
+
+        let text = extract::blocks_to_text(&app.text_blocks());
+        assert!(text.trim_end().ends_with("The last paragraph."), "{text}");
+    }
+
+    #[test]
+    fn reader_mode_shows_the_article_until_turned_off() {
+        let paragraph = "A paragraph of the story, long enough and with a comma, to count";
+        let html = format!(
+            "<nav><a href=/>Home</a></nav><div class=content><p>{paragraph}.</p>\
+             <p>{paragraph} again.</p></div><footer><p>{paragraph} in the footer.</p></footer>"
+        );
+        let viewport = Viewport {
+            width_px: 400,
+            height_px: 300,
+        };
+        let mut app = BrowserApp::from_html("Story", &html).unwrap();
+        let mut painter = crate::platform::SoftwarePainter::new(viewport).unwrap();
+        let text = |app: &BrowserApp| extract::blocks_to_text(&app.text_blocks());
+
+        app.set_reader_mode(true);
+        assert!(app.tick().unwrap().needs_redraw);
+        app.render(&mut painter, viewport).unwrap();
+        let shown = text(&app);
+        assert!(shown.starts_with("# Story"), "{shown}");
+        assert!(shown.contains("again.") && !shown.contains("Home") && !shown.contains("footer"));
+
+        app.set_reader_mode(false);
+        app.tick().unwrap();
+        app.render(&mut painter, viewport).unwrap();
+        let shown = text(&app);
+        assert!(
+            shown.contains("Home") && shown.contains("footer"),
+            "{shown}"
+        );
+    }
+
+    #[test]
+    fn scrollbar_thumb_drags_and_the_track_pages() {
+        let window = Viewport {
+            width_px: 400,
+            height_px: CHROME_HEIGHT_PX + 300,
+        };
+        let html = "<body style='margin: 0'><div style='height: 1200px'></div></body>";
+        let mut app = BrowserApp::from_html("page.html", html).unwrap();
+        app.set_chrome_visible(true);
+        let mut painter = crate::platform::SoftwarePainter::new(window).unwrap();
+        app.tick().unwrap();
+        app.render(&mut painter, window).unwrap();
+
+        let [scrollbar] = app.scrollbars(window)[..] else {
+            panic!("one scrollbar");
+        };
+        assert_eq!(scrollbar.track.y, CHROME_HEIGHT_PX);
+        assert_eq!(scrollbar.thumb.height, 75);
+        let x_px = scrollbar.thumb.x + 5;
+        let grab_y_px = scrollbar.thumb.y + 10;
+        app.mouse_down(x_px, grab_y_px, window).unwrap();
+        assert!(
+            app.mouse_move(x_px, grab_y_px + 45, window)
+                .unwrap()
+                .needs_redraw
+        );
+        assert_eq!(app.scroll_y_px(), 180);
+        assert!(app.mouse_up().needs_redraw);
+        app.mouse_move(x_px, grab_y_px + 100, window).unwrap();
+        assert_eq!(app.scroll_y_px(), 180);
+
+        app.mouse_down(x_px, window.height_px - 1, window).unwrap();
+        assert_eq!(app.scroll_y_px(), 180 + 300 * 7 / 8);
+        app.mouse_down(x_px, CHROME_HEIGHT_PX, window).unwrap();
+        assert_eq!(app.scroll_y_px(), 180);
+
+        app.set_chrome_visible(false);
+        assert!(app.scrollbars(window).is_empty());
+    }
+
+    #[test]
+    fn wide_pages_scroll_sideways() {
+        let window = Viewport {
+            width_px: 400,
+            height_px: CHROME_HEIGHT_PX + 300,
+        };
+        let html = "<body style='margin: 0'>\
+                    <div style='width: 1200px; height: 10px; background: #ff0000'></div>\
+                    <div style='position: fixed; top: 0; width: 2000px; height: 10px; \
+                    background: #0000ff'></div></body>";
+        let mut app = BrowserApp::from_html("page.html", html).unwrap();
+        app.set_chrome_visible(true);
+        let mut painter = crate::platform::SoftwarePainter::new(window).unwrap();
+        app.tick().unwrap();
+        app.render(&mut painter, window).unwrap();
+
+        assert!(app.horizontal_wheel(300, window).needs_redraw);
+        assert_eq!(app.scroll_x_px(), 300);
+        app.horizontal_wheel(5000, window);
+        assert_eq!(app.scroll_x_px(), 800);
+        assert_eq!(app.scroll_y_px(), 0);
+
+        let [scrollbar] = app.scrollbars(window)[..] else {
+            panic!("one scrollbar");
+        };
+        assert_eq!(scrollbar.axis, Axis::Horizontal);
+        assert_eq!(scrollbar.track.bottom(), window.height_px);
+        assert_eq!(scrollbar.thumb.right(), window.width_px);
+        let y_px = scrollbar.thumb.y + 5;
+        app.mouse_down(scrollbar.thumb.x + 10, y_px, window)
+            .unwrap();
+        app.mouse_move(10, y_px, window).unwrap();
+        app.mouse_up();
+        assert_eq!(app.scroll_x_px(), 0);
+    }
+
+    #[test]
+    fn wheel_notches_glide_while_touchpad_deltas_apply_at_once() {
+        let viewport = Viewport {
+            width_px: 320,
+            height_px: 200,
+        };
+        let mut app = BrowserApp::from_html("page.html", "<p>Tall</p>").unwrap();
+        app.wheel_notch(48, viewport);
+        app.wheel_notch(48, viewport);
+        assert_eq!(app.scroll_y_px(), 0);
+
+        std::thread::sleep(Duration::from_millis(200));
+        app.tick().unwrap();
+        assert_eq!(app.scroll_y_px(), 96);
+
+        app.wheel_notch(48, viewport);
+        app.mouse_wheel(-6, viewport).unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+        app.tick().unwrap();
+        assert_eq!(app.scroll_y_px(), 90);
+    }
+
     #[test]
     fn stylesheets_are_parsed_once_and_reused_across_viewports() {
         crate::css::reset_stylesheet_parse_call_count();
@@ -983,4 +3277,99 @@ mod tests {
 
         assert_eq!(crate::css::stylesheet_parse_call_count(), parsed);
     }
+
+    #[test]
+    fn resizing_across_breakpoints_switches_media_dependent_styles() {
+        let html = "<style>p { color: #000001; } @media (max-width: 600px) { p { color: #000002; } }</style>\
+            <style media=\"(max-width: 400px)\">p { color: #000003; }</style><p>t</p>";
+        let mut app = BrowserApp::from_html("test", html).unwrap();
+        let color_at = |app: &mut BrowserApp, width_px: i32| {
+            let viewport = Viewport {
+                width_px,
+                height_px: 200,
+            };
+            app.ensure_styles_for_viewport(viewport).unwrap();
+            let p = app.tab().document.find_first_element_by_name("p").unwrap();
+            let root = crate::style::ComputedStyle::root_defaults();
+            let color = app
+                .tab()
+                .styles
+                .compute_style_in_viewport(p, &root, &[], width_px, 200)
+                .color;
+            (color.r, color.g, color.b)
+        };
+
+        assert_eq!(color_at(&mut app, 800), (0, 0, 1));
+        assert_eq!(color_at(&mut app, 500), (0, 0, 2));
+        assert_eq!(app.tab().styles_sheet_media, vec![true, false]);
+        assert_eq!(color_at(&mut app, 300), (0, 0, 3));
+        assert_eq!(app.tab().styles_sheet_media, vec![true, true]);
+        assert_eq!(color_at(&mut app, 900), (0, 0, 1));
+        assert!(app.tab().cached_layout.is_none());
+    }
+
+    #[test]
+    fn mutations_between_ticks_invalidate_layout_and_restyle_only_for_stylesheets() {
+        let viewport = Viewport {
+            width_px: 320,
+            height_px: 200,
+        };
+        let html =
+            "<html><head><style>p { color: red; }</style></head><body><p>t</p></body></html>";
+        let mut app = BrowserApp::from_html("test", html).unwrap();
+        app.ensure_styles_for_viewport(viewport).unwrap();
+        assert!(!app.tick().unwrap().needs_redraw);
+
+        crate::css::reset_stylesheet_parse_call_count();
+        let body = [0, 1];
+        assert!(
+            app.document_mut()
+                .set_attribute(&body, "class", "dark".to_owned())
+        );
+        assert!(app.tick().unwrap().needs_redraw);
+        assert_eq!(app.tab().styles_viewport, Some(viewport));
+        assert!(!app.tick().unwrap().needs_redraw);
+
+        let style = [0, 0, 0];
+        assert!(
+            app.document_mut()
+                .set_text_content(&style, "p { color: blue; }".to_owned())
+        );
+        assert!(app.tick().unwrap().needs_redraw);
+        assert_eq!(crate::css::stylesheet_parse_call_count(), 1);
+        assert_eq!(app.tab().styles_viewport, None);
+    }
+
+    #[test]
+    fn base_href_moves_where_links_and_stylesheets_resolve() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("one-agent-one-browser-base-{nanos}"));
+        std::fs::create_dir_all(dir.join("assets")).unwrap();
+        std::fs::write(dir.join("assets/site.css"), "p { color: red; }").unwrap();
+        let html = "<head><base href='assets/'><link rel=stylesheet href=site.css></head><p>Hi</p>";
+
+        let mut app = BrowserApp::from_html_with_base("page.html", html, Some(&dir)).unwrap();
+        app.tab_mut().base = Some(PageBase::FileDir(dir.clone()));
+        assert_eq!(app.tab().style_sources.len(), 1);
+        assert_eq!(
+            app.resolve_href("next.html"),
+            Some(PageLocation::File(dir.join("assets/next.html")))
+        );
+
+        app.tab_mut().base = Some(PageBase::Url(
+            Url::parse("https://example.com/a/page.html").unwrap(),
+        ));
+        app.tab_mut().document.base_href = Some("https://cdn.example.com/b/".to_owned());
+        assert_eq!(
+            app.resolve_href("c.html"),
+            Some(PageLocation::Url(
+                Url::parse("https://cdn.example.com/b/c.html").unwrap()
+            ))
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
@@ -0,0 +1,513 @@
+use crate::app::{Key, KeyInput};
+use crate::geom::Color;
+use crate::render::{Painter, TextStyle};
+use crate::style::FontFamily;
+
+const TAB_STRIP_HEIGHT_PX: i32 = 30;
+const LOCATION_BAR_HEIGHT_PX: i32 = 36;
+pub(super) const CHROME_HEIGHT_PX: i32 = TAB_STRIP_HEIGHT_PX + LOCATION_BAR_HEIGHT_PX;
+
+const PADDING_PX: i32 = 6;
+const TAB_MAX_WIDTH_PX: i32 = 200;
+const TAB_CLOSE_WIDTH_PX: i32 = 20;
+const NEW_TAB_BUTTON_WIDTH_PX: i32 = 28;
+const FIELD_RADIUS_PX: i32 = 4;
+const STATUS_WIDTH_PX: i32 = 96;
+const FONT_SIZE_PX: i32 = 14;
+
+const BACKGROUND: Color = Color {
+    r: 0xf1,
+    g: 0xf3,
+    b: 0xf4,
+    a: 255,
+};
+const TAB_STRIP_BACKGROUND: Color = Color {
+    r: 0xde,
+    g: 0xe1,
+    b: 0xe6,
+    a: 255,
+};
+const DIVIDER: Color = Color {
+    r: 0xc8,
+    g: 0xc8,
+    b: 0xc8,
+    a: 255,
+};
+const FIELD_BORDER: Color = Color {
+    r: 0x9a,
+    g: 0xa0,
+    b: 0xa6,
+    a: 255,
+};
+const FIELD_BORDER_FOCUSED: Color = Color {
+    r: 0x1a,
+    g: 0x73,
+    b: 0xe8,
+    a: 255,
+};
+const MUTED_TEXT: Color = Color {
+    r: 0x5f,
+    g: 0x63,
+    b: 0x68,
+    a: 255,
+};
+const SECURE: Color = Color {
+    r: 0x18,
+    g: 0x80,
+    b: 0x38,
+    a: 255,
+};
+const NOT_SECURE: Color = Color {
+    r: 0xc5,
+    g: 0x22,
+    b: 0x1f,
+    a: 255,
+};
+const PADLOCK_WIDTH_PX: i32 = 10;
+const PADLOCK_BODY_HEIGHT_PX: i32 = 8;
+const NOT_SECURE_LABEL: &str = "Not secure";
+
+pub(super) enum ChromeAction {
+    Redraw,
+    Navigate(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ChromeHit {
+    Tab(usize),
+    CloseTab(usize),
+    NewTab,
+    Location,
+}
+
+/// What the location field shows ahead of the location about how the page was fetched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SecurityIndicator {
+    /// A local file or a page still without a location.
+    None,
+    /// An `https://` page whose subresources all came over `https://` too.
+    Secure,
+    /// An `http://` page, or an `https://` one that loaded `http://` subresources.
+    NotSecure,
+}
+
+pub(super) struct TabLabels<'a> {
+    pub(super) titles: &'a [String],
+    pub(super) active: usize,
+}
+
+#[derive(Default)]
+pub(super) struct Chrome {
+    edit: Option<LocationEdit>,
+}
+
+struct LocationEdit {
+    text: Vec<char>,
+    cursor: usize,
+}
+
+impl Chrome {
+    pub(super) fn begin_editing(&mut self, location: &str) {
+        let text: Vec<char> = location.chars().collect();
+        let cursor = text.len();
+        self.edit = Some(LocationEdit { text, cursor });
+    }
+
+    pub(super) fn stop_editing(&mut self) {
+        self.edit = None;
+    }
+
+    /// Returns what a click at a point above `CHROME_HEIGHT_PX` landed on, or `None` for the
+    /// strip's empty space.
+    pub(super) fn hit_test(
+        &self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        tab_count: usize,
+    ) -> Option<ChromeHit> {
+        if y_px >= TAB_STRIP_HEIGHT_PX {
+            return Some(ChromeHit::Location);
+        }
+        let strip = TabStrip::new(width_px, tab_count);
+        if x_px >= strip.new_tab_x() && x_px < strip.new_tab_x() + NEW_TAB_BUTTON_WIDTH_PX {
+            return Some(ChromeHit::NewTab);
+        }
+        if x_px < PADDING_PX || strip.tab_width_px <= 0 {
+            return None;
+        }
+        let index = usize::try_from((x_px - PADDING_PX) / strip.tab_width_px).ok()?;
+        if index >= tab_count {
+            return None;
+        }
+        let tab_right = strip.tab_x(index) + strip.tab_width_px;
+        if x_px >= tab_right - TAB_CLOSE_WIDTH_PX {
+            return Some(ChromeHit::CloseTab(index));
+        }
+        Some(ChromeHit::Tab(index))
+    }
+
+    /// Returns `None` when the key is not meant for the chrome.
+    pub(super) fn key_down(&mut self, input: KeyInput, location: &str) -> Option<ChromeAction> {
+        if input.alt {
+            return None;
+        }
+        if input.ctrl {
+            if matches!(input.key, Key::Char('l' | 'L')) {
+                self.begin_editing(location);
+                return Some(ChromeAction::Redraw);
+            }
+            return None;
+        }
+
+        let edit = self.edit.as_mut()?;
+        match input.key {
+            Key::Char(ch) if !ch.is_control() => {
+                edit.text.insert(edit.cursor, ch);
+                edit.cursor += 1;
+            }
+            Key::Char(_) => return Some(ChromeAction::Redraw),
+            Key::Backspace => {
+                if edit.cursor > 0 {
+                    edit.cursor -= 1;
+                    edit.text.remove(edit.cursor);
+                }
+            }
+            Key::Delete => {
+                if edit.cursor < edit.text.len() {
+                    edit.text.remove(edit.cursor);
+                }
+            }
+            Key::Left => edit.cursor = edit.cursor.saturating_sub(1),
+            Key::Right => edit.cursor = (edit.cursor + 1).min(edit.text.len()),
+            Key::Home => edit.cursor = 0,
+            Key::End => edit.cursor = edit.text.len(),
+            Key::Escape => self.edit = None,
+            Key::Tab | Key::F12 => {}
+            Key::Enter => {
+                let text: String = edit.text.iter().collect();
+                self.edit = None;
+                let text = text.trim();
+                if text.is_empty() {
+                    return Some(ChromeAction::Redraw);
+                }
+                return Some(ChromeAction::Navigate(text.to_owned()));
+            }
+        }
+        Some(ChromeAction::Redraw)
+    }
+
+    /// Inserts pasted text at the cursor, dropping line breaks; `None` when the location field
+    /// isn't being edited.
+    pub(super) fn paste(&mut self, text: &str) -> Option<ChromeAction> {
+        let edit = self.edit.as_mut()?;
+        let pasted: Vec<char> = text.chars().filter(|ch| !ch.is_control()).collect();
+        let count = pasted.len();
+        edit.text.splice(edit.cursor..edit.cursor, pasted);
+        edit.cursor += count;
+        Some(ChromeAction::Redraw)
+    }
+
+    pub(super) fn paint(
+        &self,
+        painter: &mut dyn Painter,
+        width_px: i32,
+        tabs: TabLabels<'_>,
+        location: &str,
+        security: SecurityIndicator,
+        status: &str,
+    ) -> Result<(), String> {
+        let width_px = width_px.max(0);
+        self.paint_tab_strip(painter, width_px, tabs)?;
+        painter.fill_rect(
+            0,
+            TAB_STRIP_HEIGHT_PX,
+            width_px,
+            LOCATION_BAR_HEIGHT_PX - 1,
+            BACKGROUND,
+        )?;
+        painter.fill_rect(0, CHROME_HEIGHT_PX - 1, width_px, 1, DIVIDER)?;
+
+        let field_x = PADDING_PX;
+        let field_y = TAB_STRIP_HEIGHT_PX + PADDING_PX;
+        let field_width = width_px
+            .saturating_sub(PADDING_PX.saturating_mul(3))
+            .saturating_sub(STATUS_WIDTH_PX)
+            .max(0);
+        let field_height = LOCATION_BAR_HEIGHT_PX - PADDING_PX * 2;
+        if field_width > 0 {
+            painter.fill_rounded_rect(
+                field_x,
+                field_y,
+                field_width,
+                field_height,
+                FIELD_RADIUS_PX,
+                Color::WHITE,
+            )?;
+            let border = if self.edit.is_some() {
+                FIELD_BORDER_FOCUSED
+            } else {
+                FIELD_BORDER
+            };
+            painter.stroke_rounded_rect(
+                field_x,
+                field_y,
+                field_width,
+                field_height,
+                FIELD_RADIUS_PX,
+                1,
+                border,
+            )?;
+        }
+
+        let style = text_style(Color::BLACK);
+        let metrics = painter.font_metrics_px(style);
+        let baseline_y =
+            field_y + (field_height - metrics.line_height_px()) / 2 + metrics.ascent_px;
+        let text_x = field_x + PADDING_PX;
+        let text_width = field_width.saturating_sub(PADDING_PX * 2).max(0);
+
+        match &self.edit {
+            Some(edit) => {
+                let before_cursor: String = edit.text[..edit.cursor].iter().collect();
+                let shown_from =
+                    visible_start(&*painter, &edit.text, edit.cursor, text_width, style)?;
+                let shown: String = edit.text[shown_from..].iter().collect();
+                let shown = fit_to_width(&*painter, &shown, text_width, style)?;
+                painter.draw_text(text_x, baseline_y, &shown, style)?;
+
+                let skipped: String = edit.text[..shown_from].iter().collect();
+                let cursor_x = text_x
+                    + painter
+                        .text_width_px(&before_cursor, style)?
+                        .saturating_sub(painter.text_width_px(&skipped, style)?);
+                painter.fill_rect(
+                    cursor_x,
+                    baseline_y - metrics.ascent_px,
+                    1,
+                    metrics.line_height_px(),
+                    Color::BLACK,
+                )?;
+            }
+            None => {
+                let indicator_width = paint_security_indicator(
+                    painter,
+                    text_x,
+                    baseline_y,
+                    metrics.ascent_px,
+                    security,
+                )?;
+                let text_x = text_x + indicator_width;
+                let text_width = text_width.saturating_sub(indicator_width).max(0);
+                let shown = fit_to_width(&*painter, location, text_width, style)?;
+                painter.draw_text(text_x, baseline_y, &shown, style)?;
+            }
+        }
+
+        if !status.is_empty() {
+            let status_style = text_style(MUTED_TEXT);
+            let status_x = field_x + field_width + PADDING_PX;
+            let shown = fit_to_width(&*painter, status, STATUS_WIDTH_PX, status_style)?;
+            painter.draw_text(status_x, baseline_y, &shown, status_style)?;
+        }
+
+        Ok(())
+    }
+
+    fn paint_tab_strip(
+        &self,
+        painter: &mut dyn Painter,
+        width_px: i32,
+        tabs: TabLabels<'_>,
+    ) -> Result<(), String> {
+        painter.fill_rect(0, 0, width_px, TAB_STRIP_HEIGHT_PX, TAB_STRIP_BACKGROUND)?;
+        let strip = TabStrip::new(width_px, tabs.titles.len());
+        let tab_top = PADDING_PX / 2;
+        let tab_height = TAB_STRIP_HEIGHT_PX - tab_top;
+        let style = text_style(Color::BLACK);
+        let metrics = painter.font_metrics_px(style);
+        let baseline_y = tab_top + (tab_height - metrics.line_height_px()) / 2 + metrics.ascent_px;
+
+        for (index, title) in tabs.titles.iter().enumerate() {
+            let tab_x = strip.tab_x(index);
+            let tab_width = strip.tab_width_px - 1;
+            if index == tabs.active {
+                painter.fill_rounded_rect(
+                    tab_x,
+                    tab_top,
+                    tab_width,
+                    tab_height + FIELD_RADIUS_PX,
+                    FIELD_RADIUS_PX,
+                    BACKGROUND,
+                )?;
+            } else if index + 1 != tabs.active && index + 1 < tabs.titles.len() {
+                painter.fill_rect(tab_x + tab_width, tab_top + 6, 1, tab_height - 12, DIVIDER)?;
+            }
+
+            let title_width = tab_width
+                .saturating_sub(PADDING_PX * 2)
+                .saturating_sub(TAB_CLOSE_WIDTH_PX)
+                .max(0);
+            let shown = fit_to_width(&*painter, title, title_width, style)?;
+            painter.draw_text(tab_x + PADDING_PX, baseline_y, &shown, style)?;
+
+            let close_style = text_style(MUTED_TEXT);
+            let close_x = tab_x + tab_width - TAB_CLOSE_WIDTH_PX
+                + (TAB_CLOSE_WIDTH_PX - painter.text_width_px("×", close_style)?) / 2;
+            painter.draw_text(close_x, baseline_y, "×", close_style)?;
+        }
+
+        let plus_style = text_style(MUTED_TEXT);
+        let plus_x = strip.new_tab_x()
+            + (NEW_TAB_BUTTON_WIDTH_PX - painter.text_width_px("+", plus_style)?) / 2;
+        painter.draw_text(plus_x, baseline_y, "+", plus_style)?;
+        Ok(())
+    }
+}
+
+/// Tabs share the strip's width equally, up to `TAB_MAX_WIDTH_PX` each, followed by the
+/// new-tab button.
+struct TabStrip {
+    tab_width_px: i32,
+    tab_count: i32,
+}
+
+impl TabStrip {
+    fn new(width_px: i32, tab_count: usize) -> TabStrip {
+        let tab_count = i32::try_from(tab_count).unwrap_or(i32::MAX).max(1);
+        let available = width_px
+            .saturating_sub(PADDING_PX * 2)
+            .saturating_sub(NEW_TAB_BUTTON_WIDTH_PX)
+            .max(0);
+        TabStrip {
+            tab_width_px: (available / tab_count).min(TAB_MAX_WIDTH_PX),
+            tab_count,
+        }
+    }
+
+    fn tab_x(&self, index: usize) -> i32 {
+        let index = i32::try_from(index).unwrap_or(i32::MAX);
+        PADDING_PX.saturating_add(index.saturating_mul(self.tab_width_px))
+    }
+
+    fn new_tab_x(&self) -> i32 {
+        PADDING_PX.saturating_add(self.tab_count.saturating_mul(self.tab_width_px))
+    }
+}
+
+fn text_style(color: Color) -> TextStyle {
+    TextStyle {
+        color,
+        font_family: FontFamily::SansSerif,
+        font_size_px: FONT_SIZE_PX,
+        ..TextStyle::default()
+    }
+}
+
+/// Draws `security` at `x_px` on the location's baseline, returning how much of the field it
+/// took up.
+fn paint_security_indicator(
+    painter: &mut dyn Painter,
+    x_px: i32,
+    baseline_y: i32,
+    ascent_px: i32,
+    security: SecurityIndicator,
+) -> Result<i32, String> {
+    match security {
+        SecurityIndicator::None => Ok(0),
+        SecurityIndicator::Secure => {
+            let body_y = baseline_y - PADLOCK_BODY_HEIGHT_PX;
+            let shackle_height = (ascent_px - PADLOCK_BODY_HEIGHT_PX).max(4);
+            painter.stroke_rounded_rect(
+                x_px + 2,
+                body_y - shackle_height + 1,
+                PADLOCK_WIDTH_PX - 4,
+                shackle_height + 2,
+                3,
+                2,
+                SECURE,
+            )?;
+            painter.fill_rounded_rect(
+                x_px,
+                body_y,
+                PADLOCK_WIDTH_PX,
+                PADLOCK_BODY_HEIGHT_PX,
+                1,
+                SECURE,
+            )?;
+            Ok(PADLOCK_WIDTH_PX + PADDING_PX)
+        }
+        SecurityIndicator::NotSecure => {
+            let style = text_style(NOT_SECURE);
+            painter.draw_text(x_px, baseline_y, NOT_SECURE_LABEL, style)?;
+            let width = painter.text_width_px(NOT_SECURE_LABEL, style)?;
+            let divider_x = x_px + width + PADDING_PX;
+            painter.fill_rect(divider_x, baseline_y - ascent_px, 1, ascent_px, DIVIDER)?;
+            Ok(width + PADDING_PX * 2 + 1)
+        }
+    }
+}
+
+/// First character index to draw so that the cursor stays inside the field.
+fn visible_start(
+    painter: &dyn Painter,
+    text: &[char],
+    cursor: usize,
+    max_width_px: i32,
+    style: TextStyle,
+) -> Result<usize, String> {
+    let mut start = 0usize;
+    while start < cursor {
+        let prefix: String = text[start..cursor].iter().collect();
+        if painter.text_width_px(&prefix, style)? <= max_width_px {
+            break;
+        }
+        start += 1;
+    }
+    Ok(start)
+}
+
+fn fit_to_width(
+    painter: &dyn Painter,
+    text: &str,
+    max_width_px: i32,
+    style: TextStyle,
+) -> Result<String, String> {
+    if painter.text_width_px(text, style)? <= max_width_px {
+        return Ok(text.to_owned());
+    }
+    let mut chars: Vec<char> = text.chars().collect();
+    while !chars.is_empty() {
+        chars.pop();
+        let mut candidate: String = chars.iter().collect();
+        candidate.push('…');
+        if painter.text_width_px(&candidate, style)? <= max_width_px {
+            return Ok(candidate);
+        }
+    }
+    Ok(String::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Chrome, ChromeAction};
+    use crate::app::{Key, KeyInput};
+
+    #[test]
+    fn pasting_inserts_at_the_cursor_without_line_breaks() {
+        let mut chrome = Chrome::default();
+        assert!(chrome.paste("ignored").is_none());
+
+        chrome.begin_editing("example.com");
+        chrome.key_down(KeyInput::plain(Key::Home), "");
+        assert!(chrome.paste("https://\n").is_some());
+        chrome.key_down(KeyInput::plain(Key::End), "");
+        chrome.paste("/a\r\nb");
+        match chrome.key_down(KeyInput::plain(Key::Enter), "") {
+            Some(ChromeAction::Navigate(location)) => {
+                assert_eq!(location, "https://example.com/ab");
+            }
+            _ => panic!("Enter should navigate"),
+        }
+    }
+}
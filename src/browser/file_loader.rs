@@ -0,0 +1,192 @@
+use crate::url::percent_decode_str;
+use std::path::{Path, PathBuf};
+
+/// A local page ready to parse: a file's contents or a generated directory index.
+pub(super) struct LocalPage {
+    pub(super) title: String,
+    pub(super) source: String,
+    /// Relative links and resources on the page resolve against this directory.
+    pub(super) base_dir: PathBuf,
+}
+
+pub(super) fn read_local_page(path: &Path) -> Result<LocalPage, String> {
+    if path.is_dir() {
+        return Ok(LocalPage {
+            title: format!("Index of {}", path.display()),
+            source: directory_listing_html(path)?,
+            base_dir: path.to_owned(),
+        });
+    }
+
+    let mut source = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    source.truncate(crate::html::within_size_limit(&source).len());
+    Ok(LocalPage {
+        title: path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("Browser")
+            .to_owned(),
+        source,
+        base_dir: local_base_dir(path),
+    })
+}
+
+/// A directory is its own base; a file resolves against the directory containing it.
+pub(super) fn local_base_dir(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        return path.to_owned();
+    }
+    path.parent()
+        .map(Path::to_owned)
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// Maps a link on a local page to a path. `file://` URLs are absolute; other hrefs are joined
+/// to `base_dir` after dropping any query or fragment and undoing percent-encoding.
+pub(super) fn resolve_link_path(base_dir: &Path, href: &str) -> PathBuf {
+    let href = href.trim();
+    if let Some(path) = file_url_path(href) {
+        return path;
+    }
+    let href = href.split(['#', '?']).next().unwrap_or(href);
+    let href = percent_decode_str(href);
+    if href.starts_with('/') {
+        return PathBuf::from(href);
+    }
+    base_dir.join(href)
+}
+
+/// The path named by a `file://` URL, or `None` for anything else.
+pub(super) fn file_url_path(input: &str) -> Option<PathBuf> {
+    let rest = input
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("file://"))
+        .map(|_| &input[7..])?;
+    let rest = rest.split(['#', '?']).next().unwrap_or(rest);
+    // `file://localhost/x` and `file:///x` name the same file.
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+    let path = percent_decode_str(rest);
+    // `file:///C:/x` names `C:/x` on Windows.
+    if cfg!(windows) && path.as_bytes().get(2) == Some(&b':') {
+        return Some(PathBuf::from(&path[1..]));
+    }
+    Some(PathBuf::from(path))
+}
+
+fn directory_listing_html(dir: &Path) -> Result<String, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| format!("Failed to read {}: {err}", dir.display()))?;
+    let mut listed: Vec<(bool, String)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+            (is_dir, entry.file_name().to_string_lossy().into_owned())
+        })
+        .collect();
+    listed.sort_by(|(a_dir, a_name), (b_dir, b_name)| {
+        b_dir
+            .cmp(a_dir)
+            .then_with(|| a_name.to_lowercase().cmp(&b_name.to_lowercase()))
+            .then_with(|| a_name.cmp(b_name))
+    });
+
+    let title = escape_html(&dir.display().to_string());
+    let mut html = format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>Index of {title}</title>\n\
+         <style>body {{ font-family: sans-serif; margin: 16px; }} \
+         ul {{ list-style: none; padding: 0; }} li {{ margin: 4px 0; }}</style>\n\
+         </head><body>\n<h1>Index of {title}</h1>\n<ul>\n"
+    );
+    if dir.parent().is_some() {
+        html.push_str("<li><a href=\"../\">../</a></li>\n");
+    }
+    for (is_dir, name) in listed {
+        let suffix = if is_dir { "/" } else { "" };
+        html.push_str(&format!(
+            "<li><a href=\"{}{suffix}\">{}{suffix}</a></li>\n",
+            escape_html(&encode_path_segment(&name)),
+            escape_html(&name)
+        ));
+    }
+    html.push_str("</ul>\n</body></html>\n");
+    Ok(html)
+}
+
+fn encode_path_segment(name: &str) -> String {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    let mut out = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            out.push(char::from(byte));
+        } else {
+            out.push('%');
+            out.push(char::from(HEX[usize::from(byte >> 4)]));
+            out.push(char::from(HEX[usize::from(byte & 0x0f)]));
+        }
+    }
+    out
+}
+
+pub(super) fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listing_links_resolve_back_to_the_entries() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("one-agent-one-browser-listing-{nanos}"));
+        std::fs::create_dir_all(dir.join("sub dir")).unwrap();
+        std::fs::write(dir.join("a&b #1.html"), "<p>x</p>").unwrap();
+
+        let page = read_local_page(&dir).unwrap();
+        assert_eq!(page.base_dir, dir);
+        assert!(page.title.starts_with("Index of "));
+        let sub = page.source.find("href=\"sub%20dir/\"").unwrap();
+        let file = page.source.find("href=\"a&amp;b%20%231.html\"").unwrap();
+        assert!(sub < file, "directories are listed first");
+        assert!(page.source.contains(">a&amp;b #1.html</a>"));
+
+        assert_eq!(
+            resolve_link_path(&dir, "a&b%20%231.html"),
+            dir.join("a&b #1.html")
+        );
+        assert_eq!(resolve_link_path(&dir, "sub%20dir/"), dir.join("sub dir/"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parses_file_urls() {
+        assert_eq!(
+            file_url_path("file:///tmp/a%20b.html#top"),
+            Some(PathBuf::from("/tmp/a b.html"))
+        );
+        assert_eq!(
+            file_url_path("FILE://localhost/tmp/x"),
+            Some(PathBuf::from("/tmp/x"))
+        );
+        assert_eq!(file_url_path("http://example.com/"), None);
+        assert_eq!(
+            resolve_link_path(Path::new("/srv"), "file:///etc/hosts"),
+            PathBuf::from("/etc/hosts")
+        );
+    }
+}
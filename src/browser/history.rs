@@ -0,0 +1,77 @@
+use super::{PageLocation, StylesheetSource};
+use crate::dom::Document;
+
+// Parsed documents are kept for the entries nearest to the current page only; the rest are
+// reloaded from their location when revisited.
+const MAX_SNAPSHOTS: usize = 8;
+
+pub(super) struct HistoryEntry {
+    pub(super) location: PageLocation,
+    pub(super) scroll_y_px: i32,
+    pub(super) snapshot: Option<PageSnapshot>,
+}
+
+pub(super) struct PageSnapshot {
+    pub(super) title: String,
+    pub(super) document: Document,
+    pub(super) style_sources: Vec<StylesheetSource>,
+}
+
+#[derive(Default)]
+pub(super) struct SessionHistory {
+    back: Vec<HistoryEntry>,
+    forward: Vec<HistoryEntry>,
+}
+
+impl SessionHistory {
+    /// Records the page being left for a new navigation, which discards the forward entries.
+    pub(super) fn push(&mut self, entry: HistoryEntry) {
+        self.forward.clear();
+        self.back.push(entry);
+        self.trim_snapshots();
+    }
+
+    pub(super) fn go_back(&mut self, current: Option<HistoryEntry>) -> Option<HistoryEntry> {
+        let target = self.back.pop()?;
+        self.forward.extend(current);
+        self.trim_snapshots();
+        Some(target)
+    }
+
+    pub(super) fn go_forward(&mut self, current: Option<HistoryEntry>) -> Option<HistoryEntry> {
+        let target = self.forward.pop()?;
+        self.back.extend(current);
+        self.trim_snapshots();
+        Some(target)
+    }
+
+    pub(super) fn can_go_back(&self) -> bool {
+        !self.back.is_empty()
+    }
+
+    pub(super) fn can_go_forward(&self) -> bool {
+        !self.forward.is_empty()
+    }
+
+    fn trim_snapshots(&mut self) {
+        let mut by_distance: Vec<(usize, &mut HistoryEntry)> = self
+            .back
+            .iter_mut()
+            .rev()
+            .enumerate()
+            .chain(self.forward.iter_mut().rev().enumerate())
+            .collect();
+        by_distance.sort_by_key(|(distance, _)| *distance);
+        let mut kept = 0usize;
+        for (_, entry) in by_distance {
+            if entry.snapshot.is_none() {
+                continue;
+            }
+            if kept < MAX_SNAPSHOTS {
+                kept += 1;
+            } else {
+                entry.snapshot = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::watch::FileWatcher;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Editors often save in several steps (truncate, write, rename), so the page is reloaded only
+/// once the watched files have been quiet this long.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Watches the shown local page and the stylesheets and images it references.
+pub(super) struct LiveReload {
+    page: PathBuf,
+    watcher: FileWatcher,
+    changed_at: Option<Instant>,
+}
+
+impl LiveReload {
+    pub(super) fn new(page: &Path) -> Result<LiveReload, String> {
+        let mut watcher = FileWatcher::new()?;
+        watcher.watch(page)?;
+        Ok(LiveReload {
+            page: page.to_owned(),
+            watcher,
+            changed_at: None,
+        })
+    }
+
+    pub(super) fn page(&self) -> &Path {
+        &self.page
+    }
+
+    pub(super) fn watch(&mut self, path: &Path) -> Result<(), String> {
+        self.watcher.watch(path)
+    }
+
+    /// Returns true once a change has settled and the page should be reloaded.
+    pub(super) fn poll(&mut self) -> bool {
+        if self.watcher.poll_changed() {
+            self.changed_at = Some(Instant::now());
+        }
+        if self
+            .changed_at
+            .is_some_and(|changed_at| changed_at.elapsed() >= SETTLE_DELAY)
+        {
+            self.changed_at = None;
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,80 @@
+use crate::dom::Document;
+use crate::image::Argb32Image;
+use crate::resources::{ResourceLoader, ResourceManager};
+use std::sync::Arc;
+
+/// Where browsers look for an icon when the page doesn't link one.
+const DEFAULT_FAVICON: &str = "/favicon.ico";
+
+/// The title and icon a document gives the window, looked up once per document.
+pub(super) struct PageHead {
+    resolved: bool,
+    title: Option<String>,
+    icon: Favicon,
+}
+
+enum Favicon {
+    None,
+    /// Waiting on this reference from the resource manager.
+    Loading(String),
+    Loaded(Arc<Argb32Image>),
+}
+
+impl PageHead {
+    pub(super) fn unresolved() -> PageHead {
+        PageHead {
+            resolved: false,
+            title: None,
+            icon: Favicon::None,
+        }
+    }
+
+    pub(super) fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub(super) fn icon(&self) -> Option<Arc<Argb32Image>> {
+        match &self.icon {
+            Favicon::Loaded(image) => Some(Arc::clone(image)),
+            Favicon::None | Favicon::Loading(_) => None,
+        }
+    }
+
+    /// Reads the title and icon link from `document` on the first call, then polls `resources`
+    /// for the icon. `default_icon` falls back to `/favicon.ico` when no icon is linked, which
+    /// only makes sense for pages served over HTTP.
+    pub(super) fn tick(
+        &mut self,
+        document: &Document,
+        resources: Option<&ResourceManager>,
+        default_icon: bool,
+    ) {
+        if !self.resolved {
+            self.resolved = true;
+            self.title = document.title();
+            let href = document
+                .favicon_href()
+                .or(default_icon.then_some(DEFAULT_FAVICON));
+            if let Some(href) = href
+                && resources.is_some()
+            {
+                self.icon = Favicon::Loading(href.to_owned());
+            }
+        }
+
+        let (Favicon::Loading(reference), Some(resources)) = (&self.icon, resources) else {
+            return;
+        };
+        self.icon = match resources.load_bytes(reference) {
+            Ok(Some(bytes)) => match crate::image::decode_image(&bytes) {
+                Ok(image) => {
+                    resources.image_decoded(reference);
+                    Favicon::Loaded(Arc::new(image))
+                }
+                Err(_) => Favicon::None,
+            },
+            Ok(None) if resources.is_pending(reference) => return,
+            Ok(None) | Err(_) => Favicon::None,
+        };
+    }
+}
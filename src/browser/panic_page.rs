@@ -0,0 +1,271 @@
+use super::file_loader::escape_html;
+use crate::debug;
+use crate::dom::Document;
+use crate::geom::{Color, Rect};
+use crate::image::Argb32Image;
+use crate::render::{
+    FillRule, FontMetricsPx, Painter, PathCommand, StrokeStyle, TextMeasurer, TextStyle, Transform,
+};
+use std::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+/// Runs `work`, returning what it panicked with instead of unwinding out of the browser, so one
+/// broken page doesn't end the window or the agent session.
+pub(super) fn catch_page_panic<T>(work: impl FnOnce() -> T) -> Result<T, String> {
+    catch_unwind(AssertUnwindSafe(work)).map_err(|payload| {
+        let message = panic_message(payload.as_ref());
+        if debug::enabled(debug::Target::Nav, debug::Level::Error) {
+            let err = debug::shorten(&message, 96);
+            debug::log(
+                debug::Target::Nav,
+                debug::Level::Error,
+                format_args!("panic! err={err}"),
+            );
+        }
+        message
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return (*message).to_owned();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    "unknown panic".to_owned()
+}
+
+/// What is shown in place of a page whose parsing, styling or layout panicked.
+pub(super) fn panic_document(message: &str) -> Document {
+    crate::html::parse_document(&format!(
+        "<title>Page crashed</title><h1>This page crashed the browser</h1>\
+         <p>Reload to try again, or go back.</p><pre>{}</pre>",
+        escape_html(message)
+    ))
+}
+
+/// An opacity, clip or transform push not yet matched by its pop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OpenPush {
+    Opacity(u8),
+    Clip,
+    Transform,
+}
+
+/// Forwards to a painter, remembering the pushes still open so that those a panicking frame
+/// left behind can be popped before anything else is painted.
+pub(super) struct BalancedPainter<'a> {
+    inner: &'a mut dyn Painter,
+    open: Vec<OpenPush>,
+}
+
+impl<'a> BalancedPainter<'a> {
+    pub(super) fn new(inner: &'a mut dyn Painter) -> Self {
+        Self {
+            inner,
+            open: Vec::new(),
+        }
+    }
+
+    /// Pops every push still open, innermost first.
+    pub(super) fn unwind(&mut self) -> Result<(), String> {
+        while let Some(push) = self.open.pop() {
+            match push {
+                OpenPush::Opacity(opacity) => self.inner.pop_opacity(opacity)?,
+                OpenPush::Clip => self.inner.pop_clip()?,
+                OpenPush::Transform => self.inner.pop_transform()?,
+            }
+        }
+        Ok(())
+    }
+
+    fn close(&mut self, push: OpenPush) {
+        if let Some(index) = self.open.iter().rposition(|open| *open == push) {
+            self.open.remove(index);
+        }
+    }
+}
+
+impl TextMeasurer for BalancedPainter<'_> {
+    fn font_metrics_px(&self, style: TextStyle) -> FontMetricsPx {
+        self.inner.font_metrics_px(style)
+    }
+
+    fn text_width_px(&self, text: &str, style: TextStyle) -> Result<i32, String> {
+        self.inner.text_width_px(text, style)
+    }
+}
+
+impl Painter for BalancedPainter<'_> {
+    fn clear(&mut self) -> Result<(), String> {
+        self.inner.clear()
+    }
+
+    fn push_opacity(&mut self, opacity: u8) -> Result<(), String> {
+        self.inner.push_opacity(opacity)?;
+        self.open.push(OpenPush::Opacity(opacity));
+        Ok(())
+    }
+
+    fn pop_opacity(&mut self, opacity: u8) -> Result<(), String> {
+        self.close(OpenPush::Opacity(opacity));
+        self.inner.pop_opacity(opacity)
+    }
+
+    fn fill_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        color: Color,
+    ) -> Result<(), String> {
+        self.inner.fill_rect(x_px, y_px, width_px, height_px, color)
+    }
+
+    fn fill_rounded_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        radius_px: i32,
+        color: Color,
+    ) -> Result<(), String> {
+        self.inner
+            .fill_rounded_rect(x_px, y_px, width_px, height_px, radius_px, color)
+    }
+
+    fn stroke_rounded_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        radius_px: i32,
+        border_width_px: i32,
+        color: Color,
+    ) -> Result<(), String> {
+        self.inner.stroke_rounded_rect(
+            x_px,
+            y_px,
+            width_px,
+            height_px,
+            radius_px,
+            border_width_px,
+            color,
+        )
+    }
+
+    fn draw_text(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        text: &str,
+        style: TextStyle,
+    ) -> Result<(), String> {
+        self.inner.draw_text(x_px, y_px, text, style)
+    }
+
+    fn draw_image(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        image: &Argb32Image,
+        opacity: u8,
+    ) -> Result<(), String> {
+        self.inner
+            .draw_image(x_px, y_px, width_px, height_px, image, opacity)
+    }
+
+    fn push_clip_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+    ) -> Result<(), String> {
+        self.inner.push_clip_rect(x_px, y_px, width_px, height_px)?;
+        self.open.push(OpenPush::Clip);
+        Ok(())
+    }
+
+    fn pop_clip(&mut self) -> Result<(), String> {
+        self.close(OpenPush::Clip);
+        self.inner.pop_clip()
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        self.inner.push_transform(transform)?;
+        self.open.push(OpenPush::Transform);
+        Ok(())
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        self.close(OpenPush::Transform);
+        self.inner.pop_transform()
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        self.inner.fill_path(path, rule, color)
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        self.inner.stroke_path(path, style, color)
+    }
+
+    fn draw_svg(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        svg_xml: &str,
+        opacity: u8,
+    ) -> Result<(), String> {
+        self.inner
+            .draw_svg(x_px, y_px, width_px, height_px, svg_xml, opacity)
+    }
+
+    fn retains_frame(&self) -> bool {
+        self.inner.retains_frame()
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), String> {
+        self.inner.set_clip(clip)
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_become_their_message() {
+        let caught = catch_page_panic(|| -> i32 { panic!("bad <td> at {}", 3) });
+        assert_eq!(caught, Err("bad <td> at 3".to_owned()));
+        assert_eq!(catch_page_panic(|| 7), Ok(7));
+
+        let document = panic_document("bad <td>");
+        assert_eq!(document.title().as_deref(), Some("Page crashed"));
+        let pre = document.find_first_element_by_name("pre").unwrap();
+        assert!(matches!(&pre.children[..], [crate::dom::Node::Text(text)] if text == "bad <td>"));
+    }
+}
@@ -0,0 +1,224 @@
+use super::panic_page::{catch_page_panic, panic_document};
+use super::url_loader::{StylesheetRef, collect_stylesheet_refs};
+use super::{StylesheetSource, sheet_media, styles_for};
+use crate::css::Stylesheet;
+use crate::css_media::MediaPreferences;
+use crate::debug;
+use crate::dom::Document;
+use crate::net::RequestId;
+use crate::render::Viewport;
+use crate::style::StyleComputer;
+use crate::url::Url;
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+
+/// Parses fetched HTML and CSS, and styles a fetched page, away from the platform thread so
+/// the window keeps handling input while a large page is being built.
+pub(super) struct ParseWorker {
+    job_tx: mpsc::Sender<ParseJob>,
+    result_rx: mpsc::Receiver<Parsed>,
+}
+
+pub(super) struct ParsedPage {
+    pub(super) document: Document,
+    pub(super) stylesheets: Result<Vec<StylesheetRef>, String>,
+    pub(super) parse_time: Duration,
+    /// The page styled with its inline stylesheets, when the job said what for.
+    pub(super) styles: Option<PageStyles>,
+}
+
+/// A `StyleComputer` built for a viewport, as `BrowserApp::ensure_styles_for_viewport` builds
+/// one.
+pub(super) struct PageStyles {
+    pub(super) viewport: Viewport,
+    pub(super) sheet_media: Vec<bool>,
+    pub(super) styles: StyleComputer,
+    pub(super) style_time: Duration,
+}
+
+pub(super) enum Parsed {
+    Page(Box<ParsedPage>),
+    Stylesheet {
+        request_id: RequestId,
+        stylesheet: Arc<Stylesheet>,
+    },
+}
+
+enum ParseJob {
+    Page {
+        bytes: Vec<u8>,
+        base_url: Url,
+        styled_for: Option<(Viewport, MediaPreferences)>,
+    },
+    Stylesheet {
+        request_id: RequestId,
+        bytes: Vec<u8>,
+    },
+}
+
+impl ParseWorker {
+    pub(super) fn new() -> ParseWorker {
+        let (job_tx, job_rx) = mpsc::channel::<ParseJob>();
+        let (result_tx, result_rx) = mpsc::channel::<Parsed>();
+        std::thread::spawn(move || worker_loop(job_rx, result_tx));
+        ParseWorker { job_tx, result_rx }
+    }
+
+    /// Parses a page, and styles it too when `styled_for` gives the viewport and preferences.
+    pub(super) fn parse_page(
+        &self,
+        bytes: Vec<u8>,
+        base_url: Url,
+        styled_for: Option<(Viewport, MediaPreferences)>,
+    ) -> Result<(), String> {
+        self.send(ParseJob::Page {
+            bytes,
+            base_url,
+            styled_for,
+        })
+    }
+
+    pub(super) fn parse_stylesheet(
+        &self,
+        request_id: RequestId,
+        bytes: Vec<u8>,
+    ) -> Result<(), String> {
+        self.send(ParseJob::Stylesheet { request_id, bytes })
+    }
+
+    pub(super) fn try_recv(&self) -> Option<Parsed> {
+        self.result_rx.try_recv().ok()
+    }
+
+    fn send(&self, job: ParseJob) -> Result<(), String> {
+        self.job_tx
+            .send(job)
+            .map_err(|_| "Failed to enqueue parse: worker is shut down".to_owned())
+    }
+}
+
+fn worker_loop(job_rx: mpsc::Receiver<ParseJob>, result_tx: mpsc::Sender<Parsed>) {
+    while let Ok(job) = job_rx.recv() {
+        let parsed = match job {
+            ParseJob::Page {
+                bytes,
+                base_url,
+                styled_for,
+            } => Parsed::Page(Box::new(
+                catch_page_panic(|| parse_page(&bytes, &base_url, styled_for)).unwrap_or_else(
+                    |message| ParsedPage {
+                        document: panic_document(&message),
+                        stylesheets: Ok(Vec::new()),
+                        parse_time: Duration::ZERO,
+                        styles: None,
+                    },
+                ),
+            )),
+            ParseJob::Stylesheet { request_id, bytes } => {
+                let css = if super::stylesheet_fits(bytes.len()) {
+                    String::from_utf8_lossy(&bytes)
+                } else {
+                    "".into()
+                };
+                let stylesheet = catch_page_panic(|| Stylesheet::parse(&css))
+                    .unwrap_or_else(|_| Stylesheet::parse(""));
+                Parsed::Stylesheet {
+                    request_id,
+                    stylesheet: Arc::new(stylesheet),
+                }
+            }
+        };
+        if result_tx.send(parsed).is_err() {
+            return;
+        }
+    }
+}
+
+fn parse_page(
+    bytes: &[u8],
+    base_url: &Url,
+    styled_for: Option<(Viewport, MediaPreferences)>,
+) -> ParsedPage {
+    let parse = debug::span(debug::Target::Html, "parse");
+    let mut document = crate::html::parse_document_bytes(bytes);
+    let parse_time = parse.elapsed();
+    parse.end(format_args!("bytes={}", bytes.len()));
+    crate::js::execute_inline_scripts(&mut document);
+    let base_url = document
+        .base_href
+        .as_deref()
+        .and_then(|href| base_url.resolve(href))
+        .unwrap_or_else(|| base_url.clone());
+    let mut refs = Vec::new();
+    let stylesheets = collect_stylesheet_refs(&document.root, &base_url, &mut refs).map(|()| refs);
+    let styles = match (&stylesheets, styled_for) {
+        (Ok(refs), Some((viewport, media))) => Some(style_page(refs, viewport, media)),
+        _ => None,
+    };
+    ParsedPage {
+        document,
+        stylesheets,
+        parse_time,
+        styles,
+    }
+}
+
+/// External stylesheets are only requested once the page is back, so the styles start from the
+/// inline ones, which are all the page has until then.
+fn style_page(refs: &[StylesheetRef], viewport: Viewport, media: MediaPreferences) -> PageStyles {
+    let span = debug::span(debug::Target::Css, "style");
+    let sources: Vec<StylesheetSource> = refs
+        .iter()
+        .filter_map(|reference| match reference {
+            StylesheetRef::Inline { stylesheet, media } => Some(StylesheetSource {
+                stylesheet: stylesheet.clone(),
+                media: media.clone(),
+            }),
+            StylesheetRef::External { .. } => None,
+        })
+        .collect();
+    let sheet_media = sheet_media(&sources, viewport, media);
+    let styles = styles_for(&sources, &sheet_media);
+    let style_time = span.elapsed();
+    span.end(format_args!(
+        "vw={} vh={} sheets={}",
+        viewport.width_px,
+        viewport.height_px,
+        sources.len()
+    ));
+    PageStyles {
+        viewport,
+        sheet_media,
+        styles,
+        style_time,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn styles_the_page_with_its_inline_sheets_when_asked() {
+        let html = b"<style>p{color:red}</style><style media=print>p{color:blue}</style>\
+            <link rel=stylesheet href=late.css><p>x</p>";
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let viewport = Viewport {
+            width_px: 800,
+            height_px: 600,
+        };
+
+        let page = parse_page(html, &base_url, None);
+        assert_eq!(page.stylesheets.map(|refs| refs.len()), Ok(3));
+        assert!(page.styles.is_none());
+
+        let page = parse_page(
+            html,
+            &base_url,
+            Some((viewport, MediaPreferences::default())),
+        );
+        let styled = page.styles.expect("styled");
+        assert_eq!(styled.viewport, viewport);
+        assert_eq!(styled.sheet_media, [true, false]);
+    }
+}
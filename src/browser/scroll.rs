@@ -0,0 +1,135 @@
+use std::time::{Duration, Instant};
+
+/// How long a wheel notch takes to glide to where it scrolls.
+const GLIDE_DURATION: Duration = Duration::from_millis(150);
+/// Time constant of a fling's exponential slowdown.
+const FLING_TIME_CONSTANT_S: f64 = 0.325;
+/// A fling stops once it is slower than this.
+const FLING_MIN_SPEED_PX_PER_S: f64 = 20.0;
+
+/// Scrolling that plays out over several frames: a glide to where wheel notches lead, or a
+/// touchpad fling coasting to a stop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum ScrollMotion {
+    Glide {
+        from_px: f64,
+        to_px: i32,
+        started_at: Instant,
+    },
+    Fling {
+        position_px: f64,
+        velocity_px_per_s: f64,
+        stepped_at: Instant,
+    },
+}
+
+impl ScrollMotion {
+    /// Glides `delta_px` on from `current_px`, or from the target of a glide already under way
+    /// so quick notches add up. The target is clamped to `0..=max_px`.
+    pub(super) fn glide(
+        previous: Option<ScrollMotion>,
+        current_px: i32,
+        delta_px: i32,
+        max_px: i32,
+        now: Instant,
+    ) -> ScrollMotion {
+        let base_px = match previous {
+            Some(ScrollMotion::Glide { to_px, .. }) => to_px,
+            _ => current_px,
+        };
+        ScrollMotion::Glide {
+            from_px: f64::from(current_px),
+            to_px: base_px.saturating_add(delta_px).clamp(0, max_px.max(0)),
+            started_at: now,
+        }
+    }
+
+    pub(super) fn fling(current_px: i32, velocity_px_per_s: f64, now: Instant) -> ScrollMotion {
+        ScrollMotion::Fling {
+            position_px: f64::from(current_px),
+            velocity_px_per_s,
+            stepped_at: now,
+        }
+    }
+
+    /// Advances to `now`. Returns the scroll offset, clamped to `0..=max_px`, and whether the
+    /// motion goes on past it.
+    pub(super) fn step(&mut self, now: Instant, max_px: i32) -> (i32, bool) {
+        let max_px = max_px.max(0);
+        match self {
+            ScrollMotion::Glide {
+                from_px,
+                to_px,
+                started_at,
+            } => {
+                let t = (now.saturating_duration_since(*started_at).as_secs_f64()
+                    / GLIDE_DURATION.as_secs_f64())
+                .min(1.0);
+                let eased = 1.0 - (1.0 - t).powi(3);
+                let position_px = *from_px + (f64::from(*to_px) - *from_px) * eased;
+                (clamp_px(position_px, max_px), t < 1.0)
+            }
+            ScrollMotion::Fling {
+                position_px,
+                velocity_px_per_s,
+                stepped_at,
+            } => {
+                let dt = now.saturating_duration_since(*stepped_at).as_secs_f64();
+                let decay = (-dt / FLING_TIME_CONSTANT_S).exp();
+                // The distance covered while the velocity decays exponentially over `dt`.
+                *position_px += *velocity_px_per_s * FLING_TIME_CONSTANT_S * (1.0 - decay);
+                *velocity_px_per_s *= decay;
+                *stepped_at = now;
+                let clamped = clamp_px(*position_px, max_px);
+                let at_edge = *position_px <= 0.0 || *position_px >= f64::from(max_px);
+                let moving = velocity_px_per_s.abs() >= FLING_MIN_SPEED_PX_PER_S;
+                (clamped, moving && !at_edge)
+            }
+        }
+    }
+}
+
+fn clamp_px(position_px: f64, max_px: i32) -> i32 {
+    position_px.round().clamp(0.0, f64::from(max_px)) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glides_ease_out_to_the_combined_target_of_quick_notches() {
+        let start = Instant::now();
+        let mut glide = ScrollMotion::glide(None, 0, 100, 1000, start);
+        let (halfway_px, going) = glide.step(start + GLIDE_DURATION / 2, 1000);
+        assert!(going);
+        // Ease-out covers most of the distance in the first half.
+        assert!((80..100).contains(&halfway_px), "{halfway_px}");
+
+        let mut glide = ScrollMotion::glide(Some(glide), halfway_px, 100, 1000, start);
+        assert_eq!(glide.step(start + GLIDE_DURATION, 1000), (200, false));
+
+        let mut clamped = ScrollMotion::glide(None, 950, 100, 1000, start);
+        assert_eq!(clamped.step(start + GLIDE_DURATION, 1000), (1000, false));
+    }
+
+    #[test]
+    fn flings_slow_down_and_stop_at_the_page_ends() {
+        let start = Instant::now();
+        let mut fling = ScrollMotion::fling(100, 2000.0, start);
+        let (first_px, going) = fling.step(start + Duration::from_millis(16), 10_000);
+        assert!(going);
+        let (second_px, _) = fling.step(start + Duration::from_millis(32), 10_000);
+        assert!(second_px - first_px < first_px - 100);
+
+        // It coasts velocity × time constant in all: 650px.
+        let (rest_px, going) = fling.step(start + Duration::from_secs(10), 10_000);
+        assert_eq!((rest_px, going), (750, false));
+
+        let mut upwards = ScrollMotion::fling(100, -2000.0, start);
+        assert_eq!(
+            upwards.step(start + Duration::from_secs(1), 10_000),
+            (0, false)
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::geom::{Color, Rect};
+use crate::render::Painter;
+
+const WIDTH_PX: i32 = 10;
+const MIN_THUMB_LENGTH_PX: i32 = 24;
+
+const TRACK: Color = Color {
+    r: 0xf1,
+    g: 0xf3,
+    b: 0xf4,
+    a: 255,
+};
+const THUMB: Color = Color {
+    r: 0xbd,
+    g: 0xc1,
+    b: 0xc6,
+    a: 255,
+};
+const THUMB_DRAGGED: Color = Color {
+    r: 0x80,
+    g: 0x86,
+    b: 0x8b,
+    a: 255,
+};
+
+/// Which way a scrollbar scrolls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Axis {
+    Vertical,
+    Horizontal,
+}
+
+impl Axis {
+    /// Where `rect` starts and how long it is along this axis.
+    fn span(self, rect: Rect) -> (i32, i32) {
+        match self {
+            Axis::Vertical => (rect.y, rect.height),
+            Axis::Horizontal => (rect.x, rect.width),
+        }
+    }
+
+    /// The coordinate of a point along this axis.
+    pub(super) fn pick(self, x_px: i32, y_px: i32) -> i32 {
+        match self {
+            Axis::Vertical => y_px,
+            Axis::Horizontal => x_px,
+        }
+    }
+}
+
+/// A scrollbar along the right or bottom edge of the page area, whose thumb shows how much of
+/// the document is in view and where.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Scrollbar {
+    pub(super) axis: Axis,
+    pub(super) track: Rect,
+    pub(super) thumb: Rect,
+    max_scroll_px: i32,
+}
+
+impl Scrollbar {
+    /// `None` when the whole document height fits in `page_area`, which the page is shown in.
+    pub(super) fn vertical(
+        page_area: Rect,
+        document_height_px: i32,
+        scroll_y_px: i32,
+    ) -> Option<Scrollbar> {
+        let track = Rect {
+            x: page_area.right() - WIDTH_PX,
+            y: page_area.y,
+            width: WIDTH_PX,
+            height: page_area.height,
+        };
+        Scrollbar::new(
+            Axis::Vertical,
+            track,
+            page_area.height,
+            document_height_px,
+            scroll_y_px,
+        )
+    }
+
+    /// `None` when the whole document width fits in `page_area`. With `corner`, the track stops
+    /// short of the bottom right corner, where the vertical scrollbar ends.
+    pub(super) fn horizontal(
+        page_area: Rect,
+        document_width_px: i32,
+        scroll_x_px: i32,
+        corner: bool,
+    ) -> Option<Scrollbar> {
+        let track = Rect {
+            x: page_area.x,
+            y: page_area.bottom() - WIDTH_PX,
+            width: page_area.width - if corner { WIDTH_PX } else { 0 },
+            height: WIDTH_PX,
+        };
+        Scrollbar::new(
+            Axis::Horizontal,
+            track,
+            page_area.width,
+            document_width_px,
+            scroll_x_px,
+        )
+    }
+
+    fn new(
+        axis: Axis,
+        track: Rect,
+        viewport_px: i32,
+        document_px: i32,
+        scroll_px: i32,
+    ) -> Option<Scrollbar> {
+        let max_scroll_px = document_px.saturating_sub(viewport_px);
+        let (track_start_px, track_px) = axis.span(track);
+        if max_scroll_px <= 0 || track.width < WIDTH_PX || track.height < WIDTH_PX {
+            return None;
+        }
+        let thumb_px =
+            (i64::from(track_px) * i64::from(viewport_px) / i64::from(document_px)) as i32;
+        let thumb_px = thumb_px.max(MIN_THUMB_LENGTH_PX).min(track_px);
+        let travel_px = track_px - thumb_px;
+        let offset_px = (i64::from(travel_px) * i64::from(scroll_px.clamp(0, max_scroll_px))
+            / i64::from(max_scroll_px)) as i32;
+        let thumb_start_px = track_start_px + offset_px;
+        let thumb = match axis {
+            Axis::Vertical => Rect {
+                y: thumb_start_px,
+                height: thumb_px,
+                ..track
+            },
+            Axis::Horizontal => Rect {
+                x: thumb_start_px,
+                width: thumb_px,
+                ..track
+            },
+        };
+        Some(Scrollbar {
+            axis,
+            track,
+            thumb,
+            max_scroll_px,
+        })
+    }
+
+    pub(super) fn track_contains(&self, x_px: i32, y_px: i32) -> bool {
+        contains(self.track, x_px, y_px)
+    }
+
+    pub(super) fn thumb_contains(&self, x_px: i32, y_px: i32) -> bool {
+        contains(self.thumb, x_px, y_px)
+    }
+
+    /// Where the thumb starts along the track.
+    pub(super) fn thumb_start_px(&self) -> i32 {
+        self.axis.span(self.thumb).0
+    }
+
+    /// The scroll offset that puts the start of the thumb at `thumb_start_px`.
+    pub(super) fn scroll_for_thumb_at(&self, thumb_start_px: i32) -> i32 {
+        let (track_start_px, track_px) = self.axis.span(self.track);
+        let travel_px = track_px - self.axis.span(self.thumb).1;
+        if travel_px <= 0 {
+            return 0;
+        }
+        let offset_px = thumb_start_px
+            .saturating_sub(track_start_px)
+            .clamp(0, travel_px);
+        (i64::from(offset_px) * i64::from(self.max_scroll_px) / i64::from(travel_px)) as i32
+    }
+
+    /// Where a click on the track at `position_px` along it scrolls: a page further towards it,
+    /// stopping once the thumb is centred on it.
+    pub(super) fn page_towards(&self, position_px: i32, scroll_px: i32) -> i32 {
+        let (_, track_px) = self.axis.span(self.track);
+        let (thumb_start_px, thumb_px) = self.axis.span(self.thumb);
+        let page_px = (track_px * 7 / 8).max(1);
+        let under_pointer_px = self.scroll_for_thumb_at(position_px - thumb_px / 2);
+        if position_px < thumb_start_px {
+            scroll_px.saturating_sub(page_px).max(under_pointer_px)
+        } else {
+            scroll_px.saturating_add(page_px).min(under_pointer_px)
+        }
+        .clamp(0, self.max_scroll_px)
+    }
+
+    pub(super) fn paint(&self, painter: &mut dyn Painter, dragged: bool) -> Result<(), String> {
+        painter.fill_rect(
+            self.track.x,
+            self.track.y,
+            self.track.width,
+            self.track.height,
+            TRACK,
+        )?;
+        painter.fill_rounded_rect(
+            self.thumb.x + 2,
+            self.thumb.y + 2,
+            self.thumb.width - 4,
+            self.thumb.height - 4,
+            (WIDTH_PX - 4) / 2,
+            if dragged { THUMB_DRAGGED } else { THUMB },
+        )
+    }
+}
+
+fn contains(rect: Rect, x_px: i32, y_px: i32) -> bool {
+    x_px >= rect.x && x_px < rect.right() && y_px >= rect.y && y_px < rect.bottom()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumb_follows_the_scroll_offset_and_the_track_pages() {
+        let page_area = Rect {
+            x: 0,
+            y: 50,
+            width: 400,
+            height: 200,
+        };
+        assert_eq!(Scrollbar::vertical(page_area, 200, 0), None);
+
+        let top = Scrollbar::vertical(page_area, 800, 0).unwrap();
+        assert_eq!(top.track.x, 390);
+        assert_eq!((top.thumb.y, top.thumb.height), (50, 50));
+        let bottom = Scrollbar::vertical(page_area, 800, 600).unwrap();
+        assert_eq!(bottom.thumb.bottom(), 250);
+        let middle = Scrollbar::vertical(page_area, 800, 300).unwrap();
+        assert_eq!(middle.thumb.y, 125);
+
+        assert_eq!(top.scroll_for_thumb_at(125), 300);
+        assert_eq!(top.scroll_for_thumb_at(0), 0);
+        assert_eq!(top.scroll_for_thumb_at(1000), 600);
+
+        assert_eq!(top.page_towards(240, 0), 175);
+        assert_eq!(top.page_towards(110, 0), 140);
+        assert!(top.thumb_contains(395, 60) && !top.thumb_contains(395, 110));
+        assert_eq!(middle.page_towards(60, 300), 125);
+
+        assert_eq!(Scrollbar::horizontal(page_area, 400, 0, true), None);
+        let wide = Scrollbar::horizontal(page_area, 800, 400, true).unwrap();
+        assert_eq!(
+            wide.track,
+            Rect {
+                x: 0,
+                y: 240,
+                width: 390,
+                height: 10
+            }
+        );
+        assert_eq!((wide.thumb.x, wide.thumb.width), (195, 195));
+        assert_eq!(wide.scroll_for_thumb_at(0), 0);
+        assert_eq!(wide.scroll_for_thumb_at(wide.thumb_start_px()), 400);
+    }
+}
@@ -0,0 +1,91 @@
+use super::history::SessionHistory;
+use super::live_reload::LiveReload;
+use super::page_head::PageHead;
+use super::scrollbar::Axis;
+use super::url_loader::UrlLoader;
+use super::{CachedLayout, LoadTiming, PageBase, PageLocation, PaintedFrame, StylesheetSource};
+use crate::debug::inspector::Inspector;
+use crate::dom::Document;
+use crate::net::{TlsInfo, Waterfall};
+use crate::render::Viewport;
+use crate::resources::ResourceManager;
+use crate::style::StyleComputer;
+use std::time::Instant;
+
+pub(super) const NEW_TAB_TITLE: &str = "New Tab";
+
+/// The per-document state of a tab. Only the shown tab is ticked, so the network responses of
+/// background tabs wait in their loader until the tab is shown again.
+pub(super) struct TabState {
+    pub(super) title: String,
+    pub(super) document: Document,
+    /// The title and favicon `document` gives the window.
+    pub(super) head: PageHead,
+    pub(super) styles: StyleComputer,
+    pub(super) style_sources: Vec<StylesheetSource>,
+    pub(super) styles_viewport: Option<Viewport>,
+    /// Which of `style_sources` passed their `media` query when `styles` was built.
+    pub(super) styles_sheet_media: Vec<bool>,
+    pub(super) cached_layout: Option<CachedLayout>,
+    pub(super) scroll_y_px: i32,
+    pub(super) scroll_x_px: i32,
+    pub(super) url_loader: Option<UrlLoader>,
+    pub(super) base: Option<PageBase>,
+    pub(super) location: Option<PageLocation>,
+    /// What the connection the page came over reported, for an `https://` page.
+    pub(super) tls: Option<TlsInfo>,
+    /// Whether a stylesheet of an `https://` page was fetched over `http://`.
+    pub(super) insecure_content: bool,
+    pub(super) history: SessionHistory,
+    pub(super) scroll_restore: Option<i32>,
+    pub(super) resources: Option<ResourceManager>,
+    pub(super) styles_dirty: bool,
+    pub(super) last_stylesheet_change: Option<Instant>,
+    pub(super) waterfall: Waterfall,
+    pub(super) waterfall_reported: bool,
+    pub(super) load_timing: LoadTiming,
+    /// When the last frame of a running CSS transition or animation was asked for.
+    pub(super) last_animation_frame: Option<Instant>,
+    pub(super) live_reload: Option<LiveReload>,
+    /// Set while the F12 layout inspector is open.
+    pub(super) inspector: Option<Inspector>,
+    /// The scrollbar whose thumb is dragged, and how far along the thumb the pointer grabbed it.
+    pub(super) scrollbar_grab: Option<(Axis, i32)>,
+    /// What the last frame showed, so the next can repaint only what changed.
+    pub(super) painted: Option<PaintedFrame>,
+}
+
+impl TabState {
+    pub(super) fn blank() -> TabState {
+        TabState {
+            title: NEW_TAB_TITLE.to_owned(),
+            document: crate::html::parse_document(""),
+            head: PageHead::unresolved(),
+            styles: StyleComputer::empty(),
+            style_sources: Vec::new(),
+            styles_viewport: None,
+            styles_sheet_media: Vec::new(),
+            cached_layout: None,
+            scroll_y_px: 0,
+            scroll_x_px: 0,
+            url_loader: None,
+            base: None,
+            location: None,
+            tls: None,
+            insecure_content: false,
+            history: SessionHistory::default(),
+            scroll_restore: None,
+            resources: None,
+            styles_dirty: false,
+            last_stylesheet_change: None,
+            waterfall: Waterfall::new(),
+            waterfall_reported: false,
+            load_timing: LoadTiming::new(),
+            last_animation_frame: None,
+            live_reload: None,
+            inspector: None,
+            scrollbar_grab: None,
+            painted: None,
+        }
+    }
+}
@@ -1,45 +1,58 @@
+use super::parse_worker::ParseWorker;
 use crate::css::Stylesheet;
-use crate::dom::Document;
+use crate::debug;
+use crate::net::WaterfallEntry;
 use crate::url::Url;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 pub(super) struct UrlLoader {
     pub(super) base_url: Url,
     pub(super) pool: crate::net::FetchPool,
+    pub(super) parser: ParseWorker,
     pub(super) html_request_id: crate::net::RequestId,
     pub(super) html_loaded: bool,
     pub(super) stylesheets: Vec<StylesheetSlot>,
+    /// Waterfall entries of fetched responses still waiting on the parse worker.
+    pub(super) parsing: HashMap<crate::net::RequestId, WaterfallEntry>,
+    /// Set once an `http://` stylesheet of an `https://` page is fetched as written.
+    pub(super) insecure_content: bool,
+    /// What the connection the HTML came over reported, kept until the page is parsed.
+    pub(super) tls: Option<crate::net::TlsInfo>,
 }
 
 impl UrlLoader {
     pub(super) fn new(base_url: Url) -> Result<UrlLoader, String> {
         let mut pool = crate::net::FetchPool::new(8).with_label("page");
-        let html_request_id = pool.fetch_bytes(base_url.as_str().to_owned())?;
+        let html_request_id = pool.fetch_bytes(base_url.without_fragment().to_owned())?;
         Ok(UrlLoader {
             base_url,
             pool,
+            parser: ParseWorker::new(),
             html_request_id,
             html_loaded: false,
             stylesheets: Vec::new(),
+            parsing: HashMap::new(),
+            insecure_content: false,
+            tls: None,
         })
     }
 
     pub(super) fn fetch_stylesheets(
         &mut self,
-        document: &Document,
+        refs: Vec<StylesheetRef>,
     ) -> Result<Vec<StylesheetSlot>, String> {
-        let mut refs = Vec::new();
-        collect_stylesheet_refs(&document.root, &self.base_url, &mut refs)?;
-
         let mut slots = Vec::with_capacity(refs.len());
         for reference in refs {
             match reference {
-                StylesheetRef::Inline { css, media } => slots.push(StylesheetSlot::Inline {
-                    stylesheet: Arc::new(Stylesheet::parse(&css)),
-                    media,
-                }),
+                StylesheetRef::Inline { stylesheet, media } => {
+                    slots.push(StylesheetSlot::Inline { stylesheet, media })
+                }
                 StylesheetRef::External { url, media } => {
-                    let id = self.pool.fetch_bytes(url.clone())?;
+                    let Some(url) = self.mixed_content_url(url) else {
+                        continue;
+                    };
+                    let id = self.pool.fetch_bytes(url)?;
                     slots.push(StylesheetSlot::External {
                         request_id: id,
                         stylesheet: None,
@@ -52,6 +65,40 @@ impl UrlLoader {
         Ok(slots)
     }
 
+    /// Where a stylesheet of the page is fetched from under the mixed content policy, or `None`
+    /// when it or the resource policy blocks it.
+    fn mixed_content_url(&mut self, url: String) -> Option<String> {
+        let Ok(parsed) = Url::parse(&url) else {
+            return Some(url);
+        };
+        let Some(fetched) = crate::net::mixed_content_policy().apply(&self.base_url, parsed) else {
+            if debug::enabled(debug::Target::Css, debug::Level::Warn) {
+                let url = debug::shorten(&url, 64);
+                debug::log(
+                    debug::Target::Css,
+                    debug::Level::Warn,
+                    format_args!("css! url={url} err=mixed_content"),
+                );
+            }
+            return None;
+        };
+        if !crate::resources::resource_policy().allows_url(Some(&self.base_url), &fetched) {
+            if debug::enabled(debug::Target::Css, debug::Level::Warn) {
+                let url = debug::shorten(&url, 64);
+                debug::log(
+                    debug::Target::Css,
+                    debug::Level::Warn,
+                    format_args!("css! url={url} err=origin_not_allowed"),
+                );
+            }
+            return None;
+        }
+        if crate::net::is_mixed_content(&self.base_url, &fetched) {
+            self.insecure_content = true;
+        }
+        Some(fetched.without_fragment().to_owned())
+    }
+
     pub(super) fn ready_for_screenshot(&self) -> bool {
         if !self.html_loaded {
             return false;
@@ -126,12 +173,18 @@ pub(super) fn stylesheet_sources_from_loader(
     out
 }
 
-enum StylesheetRef {
-    Inline { css: String, media: Option<String> },
-    External { url: String, media: Option<String> },
+pub(super) enum StylesheetRef {
+    Inline {
+        stylesheet: Arc<Stylesheet>,
+        media: Option<String>,
+    },
+    External {
+        url: String,
+        media: Option<String>,
+    },
 }
 
-fn collect_stylesheet_refs(
+pub(super) fn collect_stylesheet_refs(
     element: &crate::dom::Element,
     base_url: &Url,
     out: &mut Vec<StylesheetRef>,
@@ -145,29 +198,23 @@ fn collect_stylesheet_refs(
             }
         }
         out.push(StylesheetRef::Inline {
-            css,
+            stylesheet: Arc::new(Stylesheet::parse(&css)),
             media: element.attributes.get("media").map(str::to_owned),
         });
     }
 
     if super::is_stylesheet_link(element) {
-        if let Some(href) = element.attributes.get("href") {
-            let href = href.trim();
-            if !href.is_empty() {
-                let url = if href.starts_with("http://") || href.starts_with("https://") {
-                    href.to_owned()
-                } else {
-                    base_url
-                        .resolve(href)
-                        .ok_or_else(|| format!("Failed to resolve stylesheet URL: {href}"))?
-                        .as_str()
-                        .to_owned()
-                };
-                out.push(StylesheetRef::External {
-                    url,
-                    media: element.attributes.get("media").map(str::to_owned),
-                });
-            }
+        let href = element.attributes.get("href");
+        if let Some(css) = href.and_then(super::data_url_stylesheet_text) {
+            out.push(StylesheetRef::Inline {
+                stylesheet: Arc::new(Stylesheet::parse(&css)),
+                media: element.attributes.get("media").map(str::to_owned),
+            });
+        } else if let Some(url) = href.and_then(|href| base_url.resolve(href)) {
+            out.push(StylesheetRef::External {
+                url: url.without_fragment().to_owned(),
+                media: element.attributes.get("media").map(str::to_owned),
+            });
         }
     }
 
@@ -0,0 +1,271 @@
+use std::fmt::Write as _;
+use std::time::Duration;
+
+/// What loading a page cost, as measured by `BrowserApp::page_metrics`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PageMetrics {
+    /// From opening the page until it and all of its stylesheets and images have loaded.
+    pub load_time: Duration,
+    /// HTML, stylesheet and image bytes, whether fetched over the network or read from disk.
+    pub bytes: u64,
+    /// The slowest single layout pass.
+    pub layout_time: Duration,
+}
+
+/// Where the time went from opening a page until it was first painted after loading, for
+/// `--timing`. Layout time leaves out the styling done during layout, which counts as style.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PhaseTimings {
+    pub parse: Duration,
+    pub style: Duration,
+    pub layout: Duration,
+    pub paint: Duration,
+    pub elements_styled: u64,
+    pub layout_passes: u32,
+    /// In the display list of the last layout.
+    pub display_commands: usize,
+    pub frames_painted: u32,
+    /// HTML, stylesheet and image bytes, as in [`PageMetrics::bytes`].
+    pub bytes_fetched: u64,
+}
+
+impl PhaseTimings {
+    /// One line per phase with its time in milliseconds and counts of what it processed, then the
+    /// bytes fetched.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let phases = [
+            ("parse", self.parse, String::new()),
+            (
+                "style",
+                self.style,
+                format!("elements={}", self.elements_styled),
+            ),
+            (
+                "layout",
+                self.layout,
+                format!(
+                    "passes={} commands={}",
+                    self.layout_passes, self.display_commands
+                ),
+            ),
+            (
+                "paint",
+                self.paint,
+                format!("frames={}", self.frames_painted),
+            ),
+        ];
+        for (name, time, details) in phases {
+            let ms = time.as_secs_f64() * 1000.0;
+            let line = format!("{name:<7}{ms:>9.2} ms  {details}");
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        let _ = writeln!(out, "fetched  bytes={}", self.bytes_fetched);
+        out
+    }
+}
+
+/// Limits set with `--budget load-ms=<ms>,bytes=<n>,layout-ms=<ms>`; any subset may be given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub max_load_ms: Option<u64>,
+    pub max_bytes: Option<u64>,
+    pub max_layout_ms: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Overrun {
+    pub metric: &'static str,
+    pub limit: u64,
+    pub actual: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BudgetReport {
+    pub load_ms: u64,
+    pub bytes: u64,
+    pub layout_ms: u64,
+    pub budget: Budget,
+    pub overruns: Vec<Overrun>,
+}
+
+impl Budget {
+    pub fn parse(spec: &str) -> Result<Budget, String> {
+        let mut budget = Budget::default();
+        for item in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid --budget entry {item:?}: expected key=value"))?;
+            let value: u64 = value.trim().parse().map_err(|_| {
+                format!("Invalid --budget value for {key}: expected an integer, got {value:?}")
+            })?;
+            let slot = match key.trim() {
+                "load-ms" => &mut budget.max_load_ms,
+                "bytes" => &mut budget.max_bytes,
+                "layout-ms" => &mut budget.max_layout_ms,
+                other => {
+                    return Err(format!(
+                        "Unknown --budget key {other:?} (expected load-ms, bytes or layout-ms)"
+                    ));
+                }
+            };
+            if slot.replace(value).is_some() {
+                return Err(format!("Duplicate --budget key {}", key.trim()));
+            }
+        }
+        if budget == Budget::default() {
+            return Err("--budget needs at least one of load-ms, bytes or layout-ms".to_owned());
+        }
+        Ok(budget)
+    }
+
+    pub fn check(&self, metrics: &PageMetrics) -> BudgetReport {
+        let load_ms = duration_ms(metrics.load_time);
+        let layout_ms = duration_ms(metrics.layout_time);
+        let overruns = [
+            ("load_ms", self.max_load_ms, load_ms),
+            ("bytes", self.max_bytes, metrics.bytes),
+            ("layout_ms", self.max_layout_ms, layout_ms),
+        ]
+        .into_iter()
+        .filter_map(|(metric, limit, actual)| {
+            let limit = limit?;
+            (actual > limit).then_some(Overrun {
+                metric,
+                limit,
+                actual,
+            })
+        })
+        .collect();
+        BudgetReport {
+            load_ms,
+            bytes: metrics.bytes,
+            layout_ms,
+            budget: *self,
+            overruns,
+        }
+    }
+}
+
+impl BudgetReport {
+    pub fn exceeded(&self) -> bool {
+        !self.overruns.is_empty()
+    }
+
+    /// One line of JSON: the measured metrics, the limits, and each limit that was exceeded.
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "{{\"ok\":{},\"metrics\":{{\"load_ms\":{},\"bytes\":{},\"layout_ms\":{}}},\"budget\":{{",
+            !self.exceeded(),
+            self.load_ms,
+            self.bytes,
+            self.layout_ms
+        );
+        let limits = [
+            ("load_ms", self.budget.max_load_ms),
+            ("bytes", self.budget.max_bytes),
+            ("layout_ms", self.budget.max_layout_ms),
+        ];
+        let mut first = true;
+        for (name, limit) in limits {
+            let Some(limit) = limit else {
+                continue;
+            };
+            if !first {
+                out.push(',');
+            }
+            first = false;
+            let _ = write!(out, "\"{name}\":{limit}");
+        }
+        out.push_str("},\"exceeded\":[");
+        for (index, overrun) in self.overruns.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            let _ = write!(
+                out,
+                "{{\"metric\":\"{}\",\"limit\":{},\"actual\":{}}}",
+                overrun.metric, overrun.limit, overrun.actual
+            );
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_budget_specs() {
+        assert_eq!(
+            Budget::parse("load-ms=1500, bytes=200000").unwrap(),
+            Budget {
+                max_load_ms: Some(1500),
+                max_bytes: Some(200_000),
+                max_layout_ms: None,
+            }
+        );
+        assert!(Budget::parse("").is_err());
+        assert!(Budget::parse("paint-ms=3").is_err());
+        assert!(Budget::parse("bytes=1,bytes=2").is_err());
+        assert!(Budget::parse("bytes=lots").is_err());
+    }
+
+    #[test]
+    fn writes_phase_timings_as_text() {
+        let timings = PhaseTimings {
+            parse: Duration::from_micros(1500),
+            style: Duration::from_millis(2),
+            layout: Duration::from_micros(12_346),
+            paint: Duration::from_millis(40),
+            elements_styled: 37,
+            layout_passes: 2,
+            display_commands: 22,
+            frames_painted: 3,
+            bytes_fetched: 4096,
+        };
+        assert_eq!(
+            timings.to_text(),
+            "parse       1.50 ms\n\
+             style       2.00 ms  elements=37\n\
+             layout     12.35 ms  passes=2 commands=22\n\
+             paint      40.00 ms  frames=3\n\
+             fetched  bytes=4096\n"
+        );
+    }
+
+    #[test]
+    fn reports_exceeded_limits_as_json() {
+        let budget = Budget::parse("load-ms=100,layout-ms=20").unwrap();
+        let metrics = PageMetrics {
+            load_time: Duration::from_millis(250),
+            bytes: 1234,
+            layout_time: Duration::from_millis(5),
+        };
+        let report = budget.check(&metrics);
+        assert!(report.exceeded());
+        assert_eq!(
+            report.to_json(),
+            "{\"ok\":false,\"metrics\":{\"load_ms\":250,\"bytes\":1234,\"layout_ms\":5},\
+             \"budget\":{\"load_ms\":100,\"layout_ms\":20},\
+             \"exceeded\":[{\"metric\":\"load_ms\",\"limit\":100,\"actual\":250}]}"
+        );
+
+        let relaxed = Budget::parse("load-ms=1000").unwrap().check(&metrics);
+        assert!(!relaxed.exceeded());
+        assert!(relaxed.to_json().starts_with("{\"ok\":true,"));
+    }
+}
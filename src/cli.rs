@@ -1,13 +1,77 @@
+use crate::budget::Budget;
+use crate::control::Channel;
+use crate::css_media::ColorScheme;
+use crate::net::{MixedContentPolicy, RequestHeaders, parse_no_proxy};
+use crate::pdf::PaperSize;
+use crate::platform::{Backend, ScreenshotTarget};
 use std::ffi::OsString;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Debug, Default)]
 pub struct Args {
     pub target: Option<Target>,
-    pub screenshot_path: Option<PathBuf>,
+    pub backend: Option<Backend>,
+    pub screenshot: Option<ScreenshotTarget>,
+    pub full_page_screenshot: bool,
     pub headless: bool,
     pub width_px: Option<i32>,
     pub height_px: Option<i32>,
+    pub virtualize_layout: bool,
+    pub dump_outline: bool,
+    pub dump_display_list: bool,
+    pub dump_dom: bool,
+    pub dump_styles: bool,
+    pub dump_text: bool,
+    pub timing: bool,
+    pub watch: bool,
+    /// Show the page as the article found in it.
+    pub reader: bool,
+    pub budget: Option<Budget>,
+    pub color_scheme: Option<ColorScheme>,
+    /// Prefer dark styles, and invert the colors of pages without them.
+    pub dark: bool,
+    /// Hide `<noscript>` content, as a browser running the page's scripts does.
+    pub no_noscript: bool,
+    pub mixed_content: Option<MixedContentPolicy>,
+    pub block_list: Option<PathBuf>,
+    /// `--allow-origin` origins; when any are given, subresources from other origins than the
+    /// page's own are not loaded.
+    pub allow_origins: Vec<String>,
+    pub user_agent: Option<String>,
+    /// `--header` values in the order given.
+    pub headers: Vec<(String, String)>,
+    /// Replaces `http_proxy` and `https_proxy` for every request.
+    pub proxy: Option<String>,
+    /// `--no-proxy` hosts; `None` keeps `no_proxy` from the environment.
+    pub no_proxy: Option<Vec<String>>,
+    pub connect_timeout: Option<Duration>,
+    pub read_timeout: Option<Duration>,
+    pub retries: Option<u32>,
+    pub pdf: Option<PathBuf>,
+    pub paper: Option<PaperSize>,
+    /// A file of URLs to screenshot one after another into `out_dir`.
+    pub batch: Option<PathBuf>,
+    pub out_dir: Option<PathBuf>,
+    /// Take commands from the channel instead of opening a window.
+    pub control: Option<Channel>,
+    /// Write the window's input events to this file.
+    pub record: Option<PathBuf>,
+    /// Feed the input events of a `--record` file to the page headlessly.
+    pub replay: Option<PathBuf>,
+}
+
+impl Args {
+    /// Whether to virtualize layout: asked for, and not writing output that needs the whole
+    /// page laid out, since a virtualized layout leaves out content far from the top.
+    pub fn virtualizes_layout(&self) -> bool {
+        self.virtualize_layout
+            && !self.dump_outline
+            && !self.dump_display_list
+            && !self.dump_text
+            && !self.full_page_screenshot
+            && self.pdf.is_none()
+    }
 }
 
 #[derive(Debug)]
@@ -65,10 +129,10 @@ pub fn parse_args(mut args: impl Iterator<Item = OsString>) -> Result<Args, Stri
                 if path.is_empty() {
                     return Err("Invalid --screenshot=... value: path is empty".to_owned());
                 }
-                if parsed.screenshot_path.is_some() {
+                if parsed.screenshot.is_some() {
                     return Err("Duplicate --screenshot flag".to_owned());
                 }
-                parsed.screenshot_path = Some(PathBuf::from(path));
+                parsed.screenshot = Some(screenshot_target(path.into()));
                 continue;
             }
 
@@ -76,10 +140,37 @@ pub fn parse_args(mut args: impl Iterator<Item = OsString>) -> Result<Args, Stri
                 let path = args
                     .next()
                     .ok_or_else(|| "Missing value for --screenshot".to_owned())?;
-                if parsed.screenshot_path.is_some() {
+                if parsed.screenshot.is_some() {
                     return Err("Duplicate --screenshot flag".to_owned());
                 }
-                parsed.screenshot_path = Some(PathBuf::from(path));
+                parsed.screenshot = Some(screenshot_target(path));
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--backend=") {
+                if parsed.backend.is_some() {
+                    return Err("Duplicate --backend flag".to_owned());
+                }
+                parsed.backend = Some(parse_backend(value)?);
+                continue;
+            }
+
+            if flag == "--backend" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --backend".to_owned())?;
+                if parsed.backend.is_some() {
+                    return Err("Duplicate --backend flag".to_owned());
+                }
+                parsed.backend = Some(parse_backend(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if flag == "--screenshot-full-page" {
+                if parsed.full_page_screenshot {
+                    return Err("Duplicate --screenshot-full-page flag".to_owned());
+                }
+                parsed.full_page_screenshot = true;
                 continue;
             }
 
@@ -91,6 +182,468 @@ pub fn parse_args(mut args: impl Iterator<Item = OsString>) -> Result<Args, Stri
                 continue;
             }
 
+            if flag == "--virtualize-layout" {
+                if parsed.virtualize_layout {
+                    return Err("Duplicate --virtualize-layout flag".to_owned());
+                }
+                parsed.virtualize_layout = true;
+                continue;
+            }
+
+            if flag == "--dump-outline" {
+                if parsed.dump_outline {
+                    return Err("Duplicate --dump-outline flag".to_owned());
+                }
+                parsed.dump_outline = true;
+                continue;
+            }
+
+            if flag == "--dump-display-list" {
+                if parsed.dump_display_list {
+                    return Err("Duplicate --dump-display-list flag".to_owned());
+                }
+                parsed.dump_display_list = true;
+                continue;
+            }
+
+            if flag == "--dump-dom" {
+                if parsed.dump_dom {
+                    return Err("Duplicate --dump-dom flag".to_owned());
+                }
+                parsed.dump_dom = true;
+                continue;
+            }
+
+            if flag == "--dump-styles" {
+                if parsed.dump_styles {
+                    return Err("Duplicate --dump-styles flag".to_owned());
+                }
+                parsed.dump_styles = true;
+                continue;
+            }
+
+            if flag == "--dump-text" {
+                if parsed.dump_text {
+                    return Err("Duplicate --dump-text flag".to_owned());
+                }
+                parsed.dump_text = true;
+                continue;
+            }
+
+            if flag == "--reader" {
+                if parsed.reader {
+                    return Err("Duplicate --reader flag".to_owned());
+                }
+                parsed.reader = true;
+                continue;
+            }
+
+            if flag == "--timing" {
+                if parsed.timing {
+                    return Err("Duplicate --timing flag".to_owned());
+                }
+                parsed.timing = true;
+                continue;
+            }
+
+            if let Some(spec) = flag.strip_prefix("--budget=") {
+                if parsed.budget.is_some() {
+                    return Err("Duplicate --budget flag".to_owned());
+                }
+                parsed.budget = Some(Budget::parse(spec)?);
+                continue;
+            }
+
+            if flag == "--budget" {
+                let spec = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --budget".to_owned())?;
+                if parsed.budget.is_some() {
+                    return Err("Duplicate --budget flag".to_owned());
+                }
+                parsed.budget = Some(Budget::parse(&spec.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--color-scheme=") {
+                if parsed.color_scheme.is_some() {
+                    return Err("Duplicate --color-scheme flag".to_owned());
+                }
+                parsed.color_scheme = Some(parse_color_scheme(value)?);
+                continue;
+            }
+
+            if flag == "--color-scheme" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --color-scheme".to_owned())?;
+                if parsed.color_scheme.is_some() {
+                    return Err("Duplicate --color-scheme flag".to_owned());
+                }
+                parsed.color_scheme = Some(parse_color_scheme(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if flag == "--dark" {
+                if parsed.dark {
+                    return Err("Duplicate --dark flag".to_owned());
+                }
+                parsed.dark = true;
+                continue;
+            }
+
+            if cfg!(feature = "js-engine") && flag == "--no-noscript" {
+                if parsed.no_noscript {
+                    return Err("Duplicate --no-noscript flag".to_owned());
+                }
+                parsed.no_noscript = true;
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--mixed-content=") {
+                if parsed.mixed_content.is_some() {
+                    return Err("Duplicate --mixed-content flag".to_owned());
+                }
+                parsed.mixed_content = Some(parse_mixed_content(value)?);
+                continue;
+            }
+
+            if flag == "--mixed-content" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --mixed-content".to_owned())?;
+                if parsed.mixed_content.is_some() {
+                    return Err("Duplicate --mixed-content flag".to_owned());
+                }
+                parsed.mixed_content = Some(parse_mixed_content(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--user-agent=") {
+                if parsed.user_agent.is_some() {
+                    return Err("Duplicate --user-agent flag".to_owned());
+                }
+                parsed.user_agent = Some(parse_user_agent(value)?);
+                continue;
+            }
+
+            if flag == "--user-agent" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --user-agent".to_owned())?;
+                if parsed.user_agent.is_some() {
+                    return Err("Duplicate --user-agent flag".to_owned());
+                }
+                parsed.user_agent = Some(parse_user_agent(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--proxy=") {
+                if parsed.proxy.is_some() {
+                    return Err("Duplicate --proxy flag".to_owned());
+                }
+                parsed.proxy = Some(parse_proxy(value)?);
+                continue;
+            }
+
+            if flag == "--proxy" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --proxy".to_owned())?;
+                if parsed.proxy.is_some() {
+                    return Err("Duplicate --proxy flag".to_owned());
+                }
+                parsed.proxy = Some(parse_proxy(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--no-proxy=") {
+                if parsed.no_proxy.is_some() {
+                    return Err("Duplicate --no-proxy flag".to_owned());
+                }
+                parsed.no_proxy = Some(parse_no_proxy(value));
+                continue;
+            }
+
+            if flag == "--no-proxy" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --no-proxy".to_owned())?;
+                if parsed.no_proxy.is_some() {
+                    return Err("Duplicate --no-proxy flag".to_owned());
+                }
+                parsed.no_proxy = Some(parse_no_proxy(&value.to_string_lossy()));
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--connect-timeout=") {
+                if parsed.connect_timeout.is_some() {
+                    return Err("Duplicate --connect-timeout flag".to_owned());
+                }
+                parsed.connect_timeout = Some(parse_timeout_ms(value, "--connect-timeout")?);
+                continue;
+            }
+
+            if flag == "--connect-timeout" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --connect-timeout".to_owned())?;
+                let value = value.to_string_lossy();
+                if parsed.connect_timeout.is_some() {
+                    return Err("Duplicate --connect-timeout flag".to_owned());
+                }
+                parsed.connect_timeout = Some(parse_timeout_ms(&value, "--connect-timeout")?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--read-timeout=") {
+                if parsed.read_timeout.is_some() {
+                    return Err("Duplicate --read-timeout flag".to_owned());
+                }
+                parsed.read_timeout = Some(parse_timeout_ms(value, "--read-timeout")?);
+                continue;
+            }
+
+            if flag == "--read-timeout" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --read-timeout".to_owned())?;
+                let value = value.to_string_lossy();
+                if parsed.read_timeout.is_some() {
+                    return Err("Duplicate --read-timeout flag".to_owned());
+                }
+                parsed.read_timeout = Some(parse_timeout_ms(&value, "--read-timeout")?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--retries=") {
+                if parsed.retries.is_some() {
+                    return Err("Duplicate --retries flag".to_owned());
+                }
+                parsed.retries = Some(parse_retries(value)?);
+                continue;
+            }
+
+            if flag == "--retries" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --retries".to_owned())?;
+                let value = value.to_string_lossy();
+                if parsed.retries.is_some() {
+                    return Err("Duplicate --retries flag".to_owned());
+                }
+                parsed.retries = Some(parse_retries(&value)?);
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--header=") {
+                parsed.headers.push(RequestHeaders::parse_header(value)?);
+                continue;
+            }
+
+            if flag == "--header" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --header".to_owned())?;
+                parsed
+                    .headers
+                    .push(RequestHeaders::parse_header(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(path) = flag.strip_prefix("--block-list=") {
+                if path.is_empty() {
+                    return Err("Invalid --block-list=... value: path is empty".to_owned());
+                }
+                if parsed.block_list.is_some() {
+                    return Err("Duplicate --block-list flag".to_owned());
+                }
+                parsed.block_list = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if flag == "--block-list" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --block-list".to_owned())?;
+                if parsed.block_list.is_some() {
+                    return Err("Duplicate --block-list flag".to_owned());
+                }
+                parsed.block_list = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--allow-origin=") {
+                parsed.allow_origins.push(parse_allow_origin(value)?);
+                continue;
+            }
+
+            if flag == "--allow-origin" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --allow-origin".to_owned())?;
+                parsed
+                    .allow_origins
+                    .push(parse_allow_origin(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(path) = flag.strip_prefix("--batch=") {
+                if path.is_empty() {
+                    return Err("Invalid --batch=... value: path is empty".to_owned());
+                }
+                if parsed.batch.is_some() {
+                    return Err("Duplicate --batch flag".to_owned());
+                }
+                parsed.batch = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if flag == "--batch" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --batch".to_owned())?;
+                if parsed.batch.is_some() {
+                    return Err("Duplicate --batch flag".to_owned());
+                }
+                parsed.batch = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if let Some(path) = flag.strip_prefix("--out-dir=") {
+                if path.is_empty() {
+                    return Err("Invalid --out-dir=... value: path is empty".to_owned());
+                }
+                if parsed.out_dir.is_some() {
+                    return Err("Duplicate --out-dir flag".to_owned());
+                }
+                parsed.out_dir = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if flag == "--out-dir" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --out-dir".to_owned())?;
+                if parsed.out_dir.is_some() {
+                    return Err("Duplicate --out-dir flag".to_owned());
+                }
+                parsed.out_dir = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--control=") {
+                if parsed.control.is_some() {
+                    return Err("Duplicate --control flag".to_owned());
+                }
+                parsed.control = Some(parse_control(value)?);
+                continue;
+            }
+
+            if flag == "--control" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --control".to_owned())?;
+                if parsed.control.is_some() {
+                    return Err("Duplicate --control flag".to_owned());
+                }
+                parsed.control = Some(parse_control(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if let Some(path) = flag.strip_prefix("--record=") {
+                if path.is_empty() {
+                    return Err("Invalid --record=... value: path is empty".to_owned());
+                }
+                if parsed.record.is_some() {
+                    return Err("Duplicate --record flag".to_owned());
+                }
+                parsed.record = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if flag == "--record" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --record".to_owned())?;
+                if parsed.record.is_some() {
+                    return Err("Duplicate --record flag".to_owned());
+                }
+                parsed.record = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if let Some(path) = flag.strip_prefix("--replay=") {
+                if path.is_empty() {
+                    return Err("Invalid --replay=... value: path is empty".to_owned());
+                }
+                if parsed.replay.is_some() {
+                    return Err("Duplicate --replay flag".to_owned());
+                }
+                parsed.replay = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if flag == "--replay" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --replay".to_owned())?;
+                if parsed.replay.is_some() {
+                    return Err("Duplicate --replay flag".to_owned());
+                }
+                parsed.replay = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if let Some(path) = flag.strip_prefix("--pdf=") {
+                if path.is_empty() {
+                    return Err("Invalid --pdf=... value: path is empty".to_owned());
+                }
+                if parsed.pdf.is_some() {
+                    return Err("Duplicate --pdf flag".to_owned());
+                }
+                parsed.pdf = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if flag == "--pdf" {
+                let path = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --pdf".to_owned())?;
+                if parsed.pdf.is_some() {
+                    return Err("Duplicate --pdf flag".to_owned());
+                }
+                parsed.pdf = Some(PathBuf::from(path));
+                continue;
+            }
+
+            if let Some(value) = flag.strip_prefix("--paper=") {
+                if parsed.paper.is_some() {
+                    return Err("Duplicate --paper flag".to_owned());
+                }
+                parsed.paper = Some(parse_paper(value)?);
+                continue;
+            }
+
+            if flag == "--paper" {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "Missing value for --paper".to_owned())?;
+                if parsed.paper.is_some() {
+                    return Err("Duplicate --paper flag".to_owned());
+                }
+                parsed.paper = Some(parse_paper(&value.to_string_lossy())?);
+                continue;
+            }
+
+            if flag == "--watch" {
+                if parsed.watch {
+                    return Err("Duplicate --watch flag".to_owned());
+                }
+                parsed.watch = true;
+                continue;
+            }
+
             if flag.starts_with('-') {
                 return Err(format!("Unknown flag: {flag}"));
             }
@@ -109,9 +662,228 @@ pub fn parse_args(mut args: impl Iterator<Item = OsString>) -> Result<Args, Stri
         parsed.target = Some(Target::File(PathBuf::from(arg)));
     }
 
+    let stdout_flags: Vec<&str> = [
+        (parsed.dump_outline, "--dump-outline"),
+        (parsed.dump_display_list, "--dump-display-list"),
+        (parsed.dump_dom, "--dump-dom"),
+        (parsed.dump_styles, "--dump-styles"),
+        (parsed.dump_text, "--dump-text"),
+        (parsed.budget.is_some(), "--budget"),
+        (
+            parsed.screenshot == Some(ScreenshotTarget::Stdout),
+            "--screenshot -",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(given, flag)| given.then_some(flag))
+    .collect();
+    if let [first, second, ..] = stdout_flags.as_slice() {
+        return Err(format!(
+            "{first} and {second} both print to stdout, so they cannot be combined"
+        ));
+    }
+
+    if parsed.full_page_screenshot && parsed.screenshot.is_none() && parsed.batch.is_none() {
+        return Err("--screenshot-full-page needs --screenshot or --batch".to_owned());
+    }
+
+    if parsed.batch.is_some() != parsed.out_dir.is_some() {
+        return Err("--batch and --out-dir need each other".to_owned());
+    }
+
+    if parsed.batch.is_some()
+        && (parsed.target.is_some()
+            || parsed.screenshot.is_some()
+            || parsed.pdf.is_some()
+            || parsed.watch
+            || parsed.dump_outline
+            || parsed.dump_dom
+            || parsed.dump_styles
+            || parsed.dump_text
+            || parsed.budget.is_some())
+    {
+        return Err(
+            "--batch writes one screenshot per listed URL, so it cannot be combined with a page to open, --screenshot, --pdf, --watch, --dump-outline, --dump-dom, --dump-styles, --dump-text or --budget"
+                .to_owned(),
+        );
+    }
+
+    if parsed.control.is_some()
+        && (parsed.backend.is_some()
+            || parsed.screenshot.is_some()
+            || parsed.full_page_screenshot
+            || parsed.pdf.is_some()
+            || parsed.watch
+            || parsed.batch.is_some()
+            || parsed.dump_outline
+            || parsed.dump_display_list
+            || parsed.dump_dom
+            || parsed.dump_styles
+            || parsed.dump_text
+            || parsed.budget.is_some())
+    {
+        return Err(
+            "--control answers commands on stdout with the software backend, so it cannot be combined with --backend, --screenshot, --screenshot-full-page, --pdf, --watch, --batch, --dump-outline, --dump-display-list, --dump-dom, --dump-styles, --dump-text or --budget"
+                .to_owned(),
+        );
+    }
+
+    if parsed.record.is_some()
+        && (parsed.replay.is_some()
+            || parsed.headless
+            || parsed.backend == Some(Backend::Software)
+            || parsed.screenshot.is_some()
+            || parsed.pdf.is_some()
+            || parsed.batch.is_some()
+            || parsed.control.is_some()
+            || parsed.dump_outline
+            || parsed.dump_display_list
+            || parsed.dump_dom
+            || parsed.dump_styles
+            || parsed.dump_text
+            || parsed.budget.is_some())
+    {
+        return Err(
+            "--record captures input to a window, so it cannot be combined with --replay, --headless, --backend software, --screenshot, --pdf, --batch, --control, --dump-outline, --dump-display-list, --dump-dom, --dump-styles, --dump-text or --budget"
+                .to_owned(),
+        );
+    }
+
+    if parsed.replay.is_some()
+        && (parsed.backend == Some(Backend::Native)
+            || parsed.width_px.is_some()
+            || parsed.height_px.is_some()
+            || parsed.pdf.is_some()
+            || parsed.watch
+            || parsed.batch.is_some()
+            || parsed.control.is_some())
+    {
+        return Err(
+            "--replay runs the recording headlessly with the software backend at its recorded viewport, so it cannot be combined with --backend native, --width, --height, --pdf, --watch, --batch or --control"
+                .to_owned(),
+        );
+    }
+
+    if parsed.dark && parsed.color_scheme == Some(ColorScheme::Light) {
+        return Err("--dark cannot be combined with --color-scheme light".to_owned());
+    }
+
+    if parsed.paper.is_some() && parsed.pdf.is_none() {
+        return Err("--paper needs --pdf".to_owned());
+    }
+
+    if parsed.pdf.is_some()
+        && (parsed.screenshot.is_some()
+            || parsed.dump_outline
+            || parsed.width_px.is_some()
+            || parsed.height_px.is_some())
+    {
+        return Err(
+            "--pdf lays the page out for paper, so it cannot be combined with --screenshot, --dump-outline, --width or --height"
+                .to_owned(),
+        );
+    }
+
+    if parsed.watch {
+        if !matches!(parsed.target, Some(Target::File(_))) {
+            return Err("--watch needs an HTML file path".to_owned());
+        }
+        if parsed.headless
+            || parsed.screenshot.is_some()
+            || parsed.dump_outline
+            || parsed.dump_display_list
+            || parsed.dump_dom
+            || parsed.dump_styles
+            || parsed.dump_text
+            || parsed.budget.is_some()
+            || parsed.pdf.is_some()
+            || parsed.backend == Some(Backend::Software)
+        {
+            return Err(
+                "--watch cannot be combined with --headless, --screenshot, --dump-outline, --dump-display-list, --dump-dom, --dump-styles, --dump-text, --budget, --pdf or --backend software"
+                    .to_owned(),
+            );
+        }
+    }
+
     Ok(parsed)
 }
 
+/// `-` writes the PNG to stdout.
+fn screenshot_target(path: OsString) -> ScreenshotTarget {
+    if path == "-" {
+        ScreenshotTarget::Stdout
+    } else {
+        ScreenshotTarget::File(PathBuf::from(path))
+    }
+}
+
+fn parse_color_scheme(value: &str) -> Result<ColorScheme, String> {
+    ColorScheme::parse(value).ok_or_else(|| {
+        format!("Invalid --color-scheme value: expected light or dark, got {value:?}")
+    })
+}
+
+fn parse_user_agent(value: &str) -> Result<String, String> {
+    RequestHeaders::parse_header(&format!("User-Agent: {value}"))
+        .map(|(_, value)| value)
+        .map_err(|_| format!("Invalid --user-agent value: {value:?}"))
+}
+
+fn parse_timeout_ms(value: &str, flag: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let ms: u64 = value
+        .parse()
+        .map_err(|_| format!("Invalid {flag} value: expected milliseconds, got {value:?}"))?;
+    if ms == 0 {
+        return Err(format!("Invalid {flag} value: must be > 0"));
+    }
+    Ok(Duration::from_millis(ms))
+}
+
+fn parse_retries(value: &str) -> Result<u32, String> {
+    let value = value.trim();
+    value
+        .parse()
+        .map_err(|_| format!("Invalid --retries value: expected a count, got {value:?}"))
+}
+
+fn parse_proxy(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        return Err(format!("Invalid --proxy value: {value:?}"));
+    }
+    Ok(value.to_owned())
+}
+
+fn parse_control(value: &str) -> Result<Channel, String> {
+    Channel::parse(value)
+        .ok_or_else(|| format!("Invalid --control value: expected stdio, got {value:?}"))
+}
+
+fn parse_mixed_content(value: &str) -> Result<MixedContentPolicy, String> {
+    MixedContentPolicy::parse(value).ok_or_else(|| {
+        format!("Invalid --mixed-content value: expected upgrade, block or allow, got {value:?}")
+    })
+}
+
+fn parse_allow_origin(value: &str) -> Result<String, String> {
+    crate::url::Url::parse(value.trim())
+        .map(|url| url.origin())
+        .map_err(|err| format!("Invalid --allow-origin value {value:?}: {err}"))
+}
+
+fn parse_backend(value: &str) -> Result<Backend, String> {
+    Backend::parse(value).ok_or_else(|| {
+        format!("Invalid --backend value: expected native or software, got {value:?}")
+    })
+}
+
+fn parse_paper(value: &str) -> Result<PaperSize, String> {
+    PaperSize::parse(value)
+        .ok_or_else(|| format!("Invalid --paper value: expected a4 or letter, got {value:?}"))
+}
+
 fn parse_dimension_px(value: &str, flag: &str) -> Result<i32, String> {
     let value = value.trim();
     if value.is_empty() {
@@ -125,3 +897,72 @@ fn parse_dimension_px(value: &str, flag: &str) -> Result<i32, String> {
     }
     Ok(px)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(OsString::from))
+    }
+
+    #[test]
+    fn at_most_one_flag_prints_to_stdout() {
+        for args in [
+            ["page.html", "--budget", "bytes=10", "--screenshot", "-"],
+            ["page.html", "--screenshot", "-", "--budget", "bytes=10"],
+        ] {
+            let err = parse(&args).expect_err("--budget with --screenshot - is rejected");
+            assert_eq!(
+                err,
+                "--budget and --screenshot - both print to stdout, so they cannot be combined"
+            );
+        }
+        let err = parse(&["page.html", "--dump-text", "--dump-dom", "--dump-outline"])
+            .expect_err("three stdout dumps are rejected");
+        assert!(err.starts_with("--dump-outline and --dump-dom "), "{err}");
+        assert!(parse(&["page.html", "--budget=bytes=10", "--screenshot", "out.png"]).is_ok());
+    }
+
+    #[test]
+    fn layout_is_virtualized_only_without_whole_page_output() {
+        let virtualizes = |args: &[&str]| parse(args).unwrap().virtualizes_layout();
+        assert!(virtualizes(&["page.html", "--virtualize-layout"]));
+        assert!(!virtualizes(&["page.html"]));
+        for flag in ["--dump-outline", "--dump-display-list", "--dump-text"] {
+            assert!(
+                !virtualizes(&["page.html", "--virtualize-layout", flag]),
+                "{flag}"
+            );
+        }
+        assert!(!virtualizes(&[
+            "page.html",
+            "--virtualize-layout",
+            "--pdf",
+            "out.pdf"
+        ]));
+        assert!(!virtualizes(&[
+            "page.html",
+            "--virtualize-layout",
+            "--screenshot",
+            "out.png",
+            "--screenshot-full-page",
+        ]));
+        assert!(!virtualizes(&[
+            "--batch",
+            "urls.txt",
+            "--out-dir",
+            "shots",
+            "--virtualize-layout",
+            "--screenshot-full-page",
+        ]));
+        assert!(!virtualizes(&[
+            "--batch",
+            "urls.txt",
+            "--out-dir",
+            "shots",
+            "--virtualize-layout",
+            "--dump-display-list",
+        ]));
+    }
+}
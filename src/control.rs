@@ -0,0 +1,532 @@
+use crate::agent::{Agent, visible_text};
+use std::fmt::{self, Write as _};
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+/// A command that was understood but could not be carried out, such as a click on a selector
+/// that matches nothing.
+const COMMAND_FAILED: i32 = -32000;
+/// Deepest nesting of arrays and objects a request may have.
+const MAX_DEPTH: usize = 64;
+
+/// Where `--control` reads commands from and writes responses to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Stdio,
+}
+
+impl Channel {
+    pub fn parse(value: &str) -> Option<Channel> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "stdio" => Some(Channel::Stdio),
+            _ => None,
+        }
+    }
+}
+
+/// Answers the JSON-RPC 2.0 requests on each line of `input` with a line of `output`, until
+/// `input` ends. Requests without an `id` are notifications: they run but get no response.
+///
+/// The methods are `navigate {url}`, `click {selector}`, `type_text {selector, text}`,
+/// `scroll_to {selector}`, `wait_for_selector {selector}`, `screenshot {path}`,
+/// `favicon {path}`, `dump_text {selector?}` and `eval_selector {selector}`, as on [`Agent`].
+pub fn serve(agent: &mut Agent, input: impl BufRead, mut output: impl Write) -> Result<(), String> {
+    for line in input.lines() {
+        let line = line.map_err(|err| format!("Failed to read a command: {err}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = respond(agent, &line) else {
+            continue;
+        };
+        writeln!(output, "{response}")
+            .and_then(|()| output.flush())
+            .map_err(|err| format!("Failed to write a response: {err}"))?;
+    }
+    Ok(())
+}
+
+type Outcome = Result<Json, (i32, String)>;
+
+fn respond(agent: &mut Agent, line: &str) -> Option<String> {
+    let request = match Json::parse(line) {
+        Ok(request @ Json::Object(_)) => request,
+        Ok(_) => {
+            let err = "A request must be an object".to_owned();
+            return Some(response(&Json::Null, Err((INVALID_REQUEST, err))));
+        }
+        Err(err) => return Some(response(&Json::Null, Err((PARSE_ERROR, err)))),
+    };
+    let result = match request.get("method") {
+        Some(Json::String(method)) => run(agent, method, request.get("params")),
+        _ => Err((INVALID_REQUEST, "A request needs a method".to_owned())),
+    };
+    request.get("id").map(|id| response(id, result))
+}
+
+fn run(agent: &mut Agent, method: &str, params: Option<&Json>) -> Outcome {
+    let failed = |err: String| (COMMAND_FAILED, err);
+    match method {
+        "navigate" => {
+            agent
+                .navigate(string_param(params, method, "url")?)
+                .map_err(failed)?;
+            Ok(page_summary(agent))
+        }
+        "click" => {
+            agent
+                .click(string_param(params, method, "selector")?)
+                .map_err(failed)?;
+            Ok(page_summary(agent))
+        }
+        "type_text" => {
+            let selector = string_param(params, method, "selector")?;
+            let text = string_param(params, method, "text")?;
+            agent.type_text(selector, text).map_err(failed)?;
+            Ok(Json::Null)
+        }
+        "scroll_to" => {
+            agent
+                .scroll_to(string_param(params, method, "selector")?)
+                .map_err(failed)?;
+            Ok(Json::Number(f64::from(agent.app().scroll_y_px())))
+        }
+        "wait_for_selector" => {
+            agent
+                .wait_for_selector(string_param(params, method, "selector")?)
+                .map_err(failed)?;
+            Ok(Json::Null)
+        }
+        "screenshot" => {
+            let path = string_param(params, method, "path")?;
+            agent.screenshot(Path::new(path)).map_err(failed)?;
+            Ok(Json::Null)
+        }
+        "favicon" => {
+            let path = string_param(params, method, "path")?;
+            let size = agent.save_favicon(Path::new(path)).map_err(failed)?;
+            Ok(size.map_or(Json::Null, |(width, height)| {
+                Json::Object(vec![
+                    ("width".to_owned(), Json::Number(f64::from(width))),
+                    ("height".to_owned(), Json::Number(f64::from(height))),
+                ])
+            }))
+        }
+        "dump_text" => {
+            let selector = match params.and_then(|params| params.get("selector")) {
+                None => "body",
+                Some(_) => string_param(params, method, "selector")?,
+            };
+            Ok(Json::String(agent.read_text(selector).map_err(failed)?))
+        }
+        "eval_selector" => {
+            let selector = string_param(params, method, "selector")?;
+            Ok(matching_elements(agent, selector))
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method {method:?}"))),
+    }
+}
+
+fn string_param<'a>(
+    params: Option<&'a Json>,
+    method: &str,
+    name: &str,
+) -> Result<&'a str, (i32, String)> {
+    match params.and_then(|params| params.get(name)) {
+        Some(Json::String(value)) => Ok(value),
+        _ => Err((
+            INVALID_PARAMS,
+            format!("{method} needs a string parameter {name:?}"),
+        )),
+    }
+}
+
+fn page_summary(agent: &Agent) -> Json {
+    Json::Object(vec![(
+        "title".to_owned(),
+        Json::String(agent.app().title().to_owned()),
+    )])
+}
+
+/// Each element matching `selector`: its name, attributes, text, and border box in page
+/// coordinates, or `null` for one that got no box of its own.
+fn matching_elements(agent: &Agent, selector: &str) -> Json {
+    let app = agent.app();
+    let elements = app.document().query_selector_all(selector);
+    Json::Array(
+        elements
+            .into_iter()
+            .map(|element| {
+                let attributes = element
+                    .attributes
+                    .to_serialized_pairs()
+                    .into_iter()
+                    .map(|(name, value)| (name, Json::String(value)))
+                    .collect();
+                let border_box = app.element_box(element).map_or(Json::Null, |inspected| {
+                    let rect = inspected.border_box;
+                    Json::Object(
+                        [
+                            ("x", rect.x),
+                            ("y", rect.y),
+                            ("width", rect.width),
+                            ("height", rect.height),
+                        ]
+                        .into_iter()
+                        .map(|(name, px)| (name.to_owned(), Json::Number(f64::from(px))))
+                        .collect(),
+                    )
+                });
+                Json::Object(vec![
+                    ("name".to_owned(), Json::String(element.name.clone())),
+                    ("attributes".to_owned(), Json::Object(attributes)),
+                    ("text".to_owned(), Json::String(visible_text(element))),
+                    ("box".to_owned(), border_box),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn response(id: &Json, result: Outcome) -> String {
+    let outcome = match result {
+        Ok(result) => ("result".to_owned(), result),
+        Err((code, message)) => (
+            "error".to_owned(),
+            Json::Object(vec![
+                ("code".to_owned(), Json::Number(f64::from(code))),
+                ("message".to_owned(), Json::String(message)),
+            ]),
+        ),
+    };
+    Json::Object(vec![
+        ("jsonrpc".to_owned(), Json::String("2.0".to_owned())),
+        ("id".to_owned(), id.clone()),
+        outcome,
+    ])
+    .to_string()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    /// Members in the order written.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn parse(text: &str) -> Result<Json, String> {
+        let mut parser = Parser { text, pos: 0 };
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(parser.error("unexpected text after the value"));
+        }
+        Ok(value)
+    }
+
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(members) => members
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Number(value) if !value.is_finite() => f.write_str("null"),
+            Json::Number(value) if value.fract() == 0.0 && value.abs() < 1e15 => {
+                write!(f, "{}", *value as i64)
+            }
+            Json::Number(value) => write!(f, "{value}"),
+            Json::String(value) => write_string(f, value),
+            Json::Array(items) => {
+                f.write_char('[')?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Object(members) => {
+                f.write_char('{')?;
+                for (index, (name, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_string(f, name)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for ch in value.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            ch if u32::from(ch) < 0x20 => write!(f, "\\u{:04x}", u32::from(ch))?,
+            ch => f.write_char(ch)?,
+        }
+    }
+    f.write_char('"')
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn value(&mut self, depth: usize) -> Result<Json, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.skip_whitespace();
+        let rest = &self.text[self.pos..];
+        for (literal, value) in [
+            ("null", Json::Null),
+            ("true", Json::Bool(true)),
+            ("false", Json::Bool(false)),
+        ] {
+            if rest.starts_with(literal) {
+                self.pos += literal.len();
+                return Ok(value);
+            }
+        }
+        match rest.as_bytes().first() {
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'[') => self.array(depth),
+            Some(b'{') => self.object(depth),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("expected a value")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Json, String> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.eat(b']') {
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value(depth + 1)?);
+            self.skip_whitespace();
+            if self.eat(b']') {
+                return Ok(Json::Array(items));
+            }
+            if !self.eat(b',') {
+                return Err(self.error("expected , or ]"));
+            }
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Json, String> {
+        self.pos += 1;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.eat(b'}') {
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.text.as_bytes().get(self.pos) != Some(&b'"') {
+                return Err(self.error("expected a member name"));
+            }
+            let name = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(b':') {
+                return Err(self.error("expected :"));
+            }
+            let value = self.value(depth + 1)?;
+            members.push((name, value));
+            self.skip_whitespace();
+            if self.eat(b'}') {
+                return Ok(Json::Object(members));
+            }
+            if !self.eat(b',') {
+                return Err(self.error("expected , or }"));
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.pos;
+        while self
+            .text
+            .as_bytes()
+            .get(self.pos)
+            .is_some_and(|b| matches!(b, b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9'))
+        {
+            self.pos += 1;
+        }
+        self.text[start..self.pos]
+            .parse()
+            .map(Json::Number)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    /// Reads a string from its opening quote on.
+    fn string(&mut self) -> Result<String, String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while self
+                .text
+                .as_bytes()
+                .get(self.pos)
+                .is_some_and(|&b| b != b'"' && b != b'\\' && b >= 0x20)
+            {
+                self.pos += 1;
+            }
+            out.push_str(&self.text[start..self.pos]);
+            if self.eat(b'"') {
+                return Ok(out);
+            }
+            if !self.eat(b'\\') {
+                return Err(self.error("unterminated string"));
+            }
+            let escaped = match self.text.as_bytes().get(self.pos) {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{8}',
+                Some(b'f') => '\u{c}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => {
+                    self.pos += 1;
+                    out.push(self.unicode_escape()?);
+                    continue;
+                }
+                _ => return Err(self.error("invalid escape")),
+            };
+            self.pos += 1;
+            out.push(escaped);
+        }
+    }
+
+    /// Reads the digits of a `\u` escape, and of the low surrogate escape after a high one.
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !(self.eat(b'\\') && self.eat(b'u')) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("unpaired surrogate"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| self.error("unpaired surrogate"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid \\u escape"))
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let matched = self.text.as_bytes().get(self.pos) == Some(&byte);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .text
+            .as_bytes()
+            .get(self.pos)
+            .is_some_and(|b| matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn error(&self, what: &str) -> String {
+        format!("Invalid JSON at byte {}: {what}", self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Json, response};
+
+    #[test]
+    fn parses_requests_and_writes_responses() {
+        let request = Json::parse(
+            r#" {"jsonrpc":"2.0","id":7,"method":"type_text",
+                "params":{"selector":"input[name=\"q\"]","text":"caf\u00e9 \ud83d\ude00\n","n":[1.5,-2e3,true,null]}} "#,
+        )
+        .unwrap();
+        assert_eq!(request.get("id"), Some(&Json::Number(7.0)));
+        let params = request.get("params").unwrap();
+        assert_eq!(
+            params.get("selector"),
+            Some(&Json::String("input[name=\"q\"]".to_owned()))
+        );
+        assert_eq!(
+            params.get("text"),
+            Some(&Json::String("café 😀\n".to_owned()))
+        );
+        assert_eq!(
+            params.get("n").unwrap().to_string(),
+            "[1.5,-2000,true,null]"
+        );
+
+        for bad in ["", "{", "{\"a\" 1}", "[1,]", "\"\\ud83d\"", "nul", "{} x"] {
+            assert!(Json::parse(bad).is_err(), "{bad:?} should not parse");
+        }
+        assert!(Json::parse(&"[".repeat(1000)).is_err());
+
+        assert_eq!(
+            response(&Json::Number(7.0), Ok(Json::String("a\"b\u{1}".to_owned()))),
+            r#"{"jsonrpc":"2.0","id":7,"result":"a\"b\u0001"}"#
+        );
+        assert_eq!(
+            response(
+                &Json::Null,
+                Err((-32601, "Unknown method \"x\"".to_owned()))
+            ),
+            r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32601,"message":"Unknown method \"x\""}}"#
+        );
+    }
+}
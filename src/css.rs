@@ -9,6 +9,8 @@ thread_local! {
 #[derive(Clone, Debug, Default)]
 pub struct Stylesheet {
     pub rules: Vec<Rule>,
+    /// `@keyframes` rules, in source order, wherever they were nested.
+    pub keyframes: Vec<Keyframes>,
 }
 
 impl Stylesheet {
@@ -37,6 +39,21 @@ pub struct Rule {
     pub media: Option<String>,
 }
 
+/// An `@keyframes` rule.
+#[derive(Clone, Debug)]
+pub struct Keyframes {
+    pub name: String,
+    /// Sorted by offset; a frame listing several offsets appears once for each.
+    pub frames: Vec<Keyframe>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Keyframe {
+    /// How far through the animation the frame sits, from 0 for `from` to 1 for `to`.
+    pub offset: f32,
+    pub declarations: Vec<Declaration>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Declaration {
     pub name: String,
@@ -89,6 +106,9 @@ pub struct CompoundSelector {
     pub classes: Vec<String>,
     pub attributes: Vec<AttributeSelector>,
     pub pseudo_classes: Vec<PseudoClass>,
+    /// Only ever set on the last compound of a selector, whose subject it then is instead of the
+    /// element itself.
+    pub pseudo_element: Option<PseudoElement>,
     pub unsupported: bool,
 }
 
@@ -100,7 +120,7 @@ impl CompoundSelector {
             .len()
             .saturating_add(self.attributes.len())
             .saturating_add(self.pseudo_classes.len());
-        let mut tags = if self.tag.is_some() { 1usize } else { 0usize };
+        let mut tags = usize::from(self.tag.is_some()) + usize::from(self.pseudo_element.is_some());
 
         for pseudo in &self.pseudo_classes {
             if let PseudoClass::Not(selector) = pseudo {
@@ -131,12 +151,21 @@ pub enum PseudoClass {
     Link,
     Visited,
     Hover,
+    /// `:focus` and `:focus-visible`, which match once a page element can take keyboard focus.
+    Focus,
     Root,
     Checked,
     NthChild(NthChildPattern),
     Not(Box<CompoundSelector>),
 }
 
+/// The pseudo-elements styled from the stylesheet; the rest make a selector unsupported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PseudoElement {
+    FirstLine,
+    FirstLetter,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct NthChildPattern {
     pub a: i32,
@@ -147,6 +176,11 @@ pub fn parse_inline_declarations(source: &str) -> Vec<Declaration> {
     parse_declarations(source)
 }
 
+/// Parses a comma-separated selector list, as passed to `querySelector`.
+pub fn parse_selector_list(source: &str) -> Vec<Selector> {
+    parse_selector_group(source)
+}
+
 fn parse_declarations(source: &str) -> Vec<Declaration> {
     let mut parser = DeclarationParser::new(source);
     parser.parse_all()
@@ -156,6 +190,7 @@ struct Parser<'a> {
     input: &'a str,
     cursor: usize,
     order: u32,
+    keyframes: Vec<Keyframes>,
 }
 
 impl<'a> Parser<'a> {
@@ -164,12 +199,16 @@ impl<'a> Parser<'a> {
             input,
             cursor: 0,
             order: 0,
+            keyframes: Vec::new(),
         }
     }
 
     fn parse_stylesheet(mut self) -> Stylesheet {
         let rules = self.parse_rules(None);
-        Stylesheet { rules }
+        Stylesheet {
+            rules,
+            keyframes: self.keyframes,
+        }
     }
 
     fn parse_rules(&mut self, media: Option<String>) -> Vec<Rule> {
@@ -181,6 +220,8 @@ impl<'a> Parser<'a> {
                     self.parse_media_at_rule(&mut rules, media.as_deref());
                 } else if self.peek_supports_at_rule() {
                     self.parse_supports_at_rule(&mut rules, media.as_deref());
+                } else if self.peek_keyframes_at_rule() {
+                    self.parse_keyframes_at_rule();
                 } else {
                     self.skip_at_rule();
                 }
@@ -260,9 +301,11 @@ impl<'a> Parser<'a> {
             input: inner_css,
             cursor: 0,
             order: self.order,
+            keyframes: Vec::new(),
         };
         out.extend(nested.parse_rules(Some(combined)));
         self.order = nested.order;
+        self.keyframes.append(&mut nested.keyframes);
     }
 
     fn peek_supports_at_rule(&self) -> bool {
@@ -309,9 +352,79 @@ impl<'a> Parser<'a> {
             input: inner_css,
             cursor: 0,
             order: self.order,
+            keyframes: Vec::new(),
         };
         out.extend(nested.parse_rules(media.map(str::to_owned)));
         self.order = nested.order;
+        self.keyframes.append(&mut nested.keyframes);
+    }
+
+    fn peek_keyframes_at_rule(&self) -> bool {
+        let Some(rest) = self.input[self.cursor..].strip_prefix('@') else {
+            return false;
+        };
+        let keyword = rest
+            .split(|ch: char| ch.is_whitespace() || ch == '{' || ch == ';')
+            .next()
+            .unwrap_or_default();
+        keyword.eq_ignore_ascii_case("keyframes")
+            || keyword.eq_ignore_ascii_case("-webkit-keyframes")
+    }
+
+    fn parse_keyframes_at_rule(&mut self) {
+        if self.peek_char() != Some('@') {
+            return;
+        }
+        self.cursor += 1;
+        let _ = self.consume_until_word_end(); // "keyframes"
+
+        let Some(name) = self.consume_until('{') else {
+            return;
+        };
+        if self.peek_char() != Some('{') {
+            return;
+        }
+        self.cursor += 1;
+        let body = self.consume_block_contents();
+        let name = name.trim_matches(['"', '\'']);
+        if name.is_empty() {
+            return;
+        }
+
+        let mut frames = Vec::new();
+        let mut nested = Parser::new(body);
+        while nested.skip_ws_and_comments() {
+            let Some(offsets) = nested.consume_until('{') else {
+                break;
+            };
+            if nested.peek_char() != Some('{') {
+                break;
+            }
+            nested.cursor += 1;
+            let declarations = parse_declarations(nested.consume_block_contents());
+            for offset in offsets.split(',') {
+                let offset = match offset.trim().to_ascii_lowercase().as_str() {
+                    "from" => Some(0.0),
+                    "to" => Some(1.0),
+                    percent => percent
+                        .strip_suffix('%')
+                        .and_then(|percent| percent.trim().parse::<f32>().ok())
+                        .filter(|percent| (0.0..=100.0).contains(percent))
+                        .map(|percent| percent / 100.0),
+                };
+                if let Some(offset) = offset {
+                    frames.push(Keyframe {
+                        offset,
+                        declarations: declarations.clone(),
+                    });
+                }
+            }
+        }
+        frames.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        self.keyframes.push(Keyframes {
+            name: name.to_owned(),
+            frames,
+        });
     }
 
     fn consume_until_word_end(&mut self) -> Option<&'a str> {
@@ -626,6 +739,18 @@ fn parse_compound_selector(mut input: &str) -> CompoundSelector {
                 }
 
                 let (name, after_name) = split_pseudo_name(rest);
+                let pseudo_element = match name {
+                    "first-line" => Some(PseudoElement::FirstLine),
+                    "first-letter" => Some(PseudoElement::FirstLetter),
+                    _ => None,
+                };
+                if let Some(pseudo_element) = pseudo_element {
+                    if !after_name.trim().is_empty() {
+                        selector.unsupported = true;
+                    }
+                    selector.pseudo_element = Some(pseudo_element);
+                    break;
+                }
                 if name.is_empty() || is_pseudo_element || matches!(name, "before" | "after") {
                     selector.unsupported = true;
                     break;
@@ -669,6 +794,7 @@ fn parse_compound_selector(mut input: &str) -> CompoundSelector {
             '[' => {
                 let (attr, rest) = split_until(input, ']');
                 if let Some(attr) = attr.strip_prefix('[') {
+                    let attr = attr.strip_suffix(']').unwrap_or(attr);
                     if let Some(sel) = parse_attribute_selector(attr) {
                         selector.attributes.push(sel);
                     }
@@ -695,7 +821,7 @@ fn parse_not_selector(input: &str) -> Option<CompoundSelector> {
     }
 
     let selector = parse_compound_selector(input);
-    if selector.unsupported {
+    if selector.unsupported || selector.pseudo_element.is_some() {
         return None;
     }
     Some(selector)
@@ -780,6 +906,7 @@ fn parse_pseudo_class(name: &str) -> Option<PseudoClass> {
         "link" => Some(PseudoClass::Link),
         "visited" => Some(PseudoClass::Visited),
         "hover" => Some(PseudoClass::Hover),
+        "focus" | "focus-visible" => Some(PseudoClass::Focus),
         "root" => Some(PseudoClass::Root),
         "checked" => Some(PseudoClass::Checked),
         _ => None,
@@ -1026,6 +1153,24 @@ mod tests {
         assert_eq!(sheet.rules[0].selectors[0].parts[0].classes, vec!["b"]);
     }
 
+    #[test]
+    fn parses_keyframes_rules_apart_from_style_rules() {
+        let sheet = Stylesheet::parse(
+            "@media screen { @keyframes fade { to { opacity: 1 } 0%, 50% { opacity: 0 } } }\n\
+             @-webkit-keyframes spin { from { color: red } bogus { color: blue } }\n\
+             .b { animation: fade 1s; }",
+        );
+        assert_eq!(sheet.rules.len(), 1);
+        assert_eq!(sheet.keyframes.len(), 2);
+        let fade = &sheet.keyframes[0];
+        assert_eq!(fade.name, "fade");
+        let offsets: Vec<f32> = fade.frames.iter().map(|frame| frame.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+        assert_eq!(fade.frames[2].declarations[0].value, "1");
+        assert_eq!(sheet.keyframes[1].name, "spin");
+        assert_eq!(sheet.keyframes[1].frames.len(), 1);
+    }
+
     #[test]
     fn ignores_at_rules_with_slashes_in_blocks() {
         let sheet = Stylesheet::parse(
@@ -1062,6 +1207,10 @@ mod tests {
             selector.parts[0].attributes[0].value.as_deref(),
             Some("submit")
         );
+
+        let selector = &parse_selector_list("a[href]")[0];
+        assert_eq!(selector.parts[0].attributes[0].name, "href");
+        assert_eq!(selector.parts[0].attributes[0].value, None);
     }
 
     #[test]
@@ -1089,6 +1238,34 @@ mod tests {
         assert!(selector.parts[0].unsupported);
     }
 
+    #[test]
+    fn parses_first_line_and_first_letter_pseudo_elements() {
+        let sheet = Stylesheet::parse(
+            "p::first-letter, article p:first-line { color: #000000; } \
+             p::first-line:hover, :not(::first-line) { color: #000000; }",
+        );
+        let selectors = &sheet.rules[0].selectors;
+        assert_eq!(
+            selectors[0].parts[0].pseudo_element,
+            Some(PseudoElement::FirstLetter)
+        );
+        assert_eq!(
+            selectors[1].parts[1].pseudo_element,
+            Some(PseudoElement::FirstLine)
+        );
+        assert_eq!(
+            selectors[1].specificity(),
+            Specificity {
+                ids: 0,
+                classes: 0,
+                tags: 3
+            }
+        );
+        let unsupported = &sheet.rules[1].selectors;
+        assert!(unsupported[0].parts[0].unsupported);
+        assert!(unsupported[1].parts[0].unsupported);
+    }
+
     #[test]
     fn parses_root_pseudo_class() {
         let sheet = Stylesheet::parse(":root { color: #000000; }");
@@ -1,6 +1,114 @@
 use crate::render::Viewport;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering};
+
+/// The user's light or dark preference, tested by `prefers-color-scheme`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorScheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ColorScheme {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "light" => Some(ColorScheme::Light),
+            "dark" => Some(ColorScheme::Dark),
+            _ => None,
+        }
+    }
+}
+
+/// What media queries can test besides the viewport.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MediaPreferences {
+    pub color_scheme: ColorScheme,
+    /// Device pixels per CSS pixel, including page zoom.
+    pub device_pixel_ratio: f32,
+}
+
+impl Default for MediaPreferences {
+    fn default() -> Self {
+        Self {
+            color_scheme: ColorScheme::Light,
+            device_pixel_ratio: 1.0,
+        }
+    }
+}
+
+/// 0 until [`set_color_scheme`] runs, then 1 for light and 2 for dark.
+static COLOR_SCHEME: AtomicU8 = AtomicU8::new(0);
+static DEVICE_PIXEL_RATIO_BITS: AtomicU32 = AtomicU32::new(1.0f32.to_bits());
+static FORCE_DARK: AtomicBool = AtomicBool::new(false);
+static RENDER_NOSCRIPT: AtomicBool = AtomicBool::new(true);
+
+/// Overrides the `OAB_COLOR_SCHEME` environment variable for the rest of the process.
+pub fn set_color_scheme(scheme: ColorScheme) {
+    let value = match scheme {
+        ColorScheme::Light => 1,
+        ColorScheme::Dark => 2,
+    };
+    COLOR_SCHEME.store(value, Ordering::Relaxed);
+}
+
+/// Makes style computers built from now on invert the colors of pages without dark styles.
+pub fn set_force_dark(force: bool) {
+    FORCE_DARK.store(force, Ordering::Relaxed);
+}
+
+pub fn force_dark() -> bool {
+    FORCE_DARK.load(Ordering::Relaxed)
+}
+
+/// Makes style computers built from now on show `<noscript>` content, as a browser without
+/// scripting does, or hide it. Shown by default, since few of a page's scripts run.
+pub fn set_render_noscript(render: bool) {
+    RENDER_NOSCRIPT.store(render, Ordering::Relaxed);
+}
+
+pub fn render_noscript() -> bool {
+    RENDER_NOSCRIPT.load(Ordering::Relaxed)
+}
+
+/// Called by the platform backends whenever their device scale or the page zoom changes.
+pub fn set_device_pixel_ratio(ratio: f32) {
+    if ratio.is_finite() && ratio > 0.0 {
+        DEVICE_PIXEL_RATIO_BITS.store(ratio.to_bits(), Ordering::Relaxed);
+    }
+}
+
+pub fn current_preferences() -> MediaPreferences {
+    let color_scheme = match COLOR_SCHEME.load(Ordering::Relaxed) {
+        1 => ColorScheme::Light,
+        2 => ColorScheme::Dark,
+        _ => color_scheme_from_env(),
+    };
+    MediaPreferences {
+        color_scheme,
+        device_pixel_ratio: f32::from_bits(DEVICE_PIXEL_RATIO_BITS.load(Ordering::Relaxed)),
+    }
+}
+
+fn color_scheme_from_env() -> ColorScheme {
+    static FROM_ENV: OnceLock<ColorScheme> = OnceLock::new();
+    *FROM_ENV.get_or_init(|| {
+        std::env::var("OAB_COLOR_SCHEME")
+            .ok()
+            .and_then(|value| ColorScheme::parse(&value))
+            .unwrap_or_default()
+    })
+}
 
 pub fn media_query_matches(media: &str, viewport: Viewport) -> bool {
+    media_query_matches_with(media, viewport, current_preferences())
+}
+
+pub fn media_query_matches_with(
+    media: &str,
+    viewport: Viewport,
+    preferences: MediaPreferences,
+) -> bool {
     let media = media.trim();
     if media.is_empty() {
         return true;
@@ -11,10 +119,10 @@ pub fn media_query_matches(media: &str, viewport: Viewport) -> bool {
             let part = part.trim();
             if part.is_empty() { None } else { Some(part) }
         })
-        .any(|part| media_query_part_matches(part, viewport))
+        .any(|part| media_query_part_matches(part, viewport, preferences))
 }
 
-fn media_query_part_matches(part: &str, viewport: Viewport) -> bool {
+fn media_query_part_matches(part: &str, viewport: Viewport, preferences: MediaPreferences) -> bool {
     let mut scanner = Scanner::new(part);
     let mut has_any_condition = false;
 
@@ -37,7 +145,7 @@ fn media_query_part_matches(part: &str, viewport: Viewport) -> bool {
             let Some(expr) = scanner.consume_parenthesized() else {
                 return false;
             };
-            if !media_expression_matches(expr, viewport) {
+            if !media_expression_matches(expr, viewport, preferences) {
                 return false;
             }
             continue;
@@ -59,24 +167,95 @@ fn media_type_matches(token: &str) -> bool {
     matches!(token.trim().to_ascii_lowercase().as_str(), "all" | "screen")
 }
 
-fn media_expression_matches(expr: &str, viewport: Viewport) -> bool {
+#[derive(Clone, Copy)]
+enum Range {
+    Min,
+    Max,
+    Exact,
+}
+
+impl Range {
+    fn compare(self, actual: f32, expected: f32) -> bool {
+        match self {
+            Range::Min => actual >= expected,
+            Range::Max => actual <= expected,
+            Range::Exact => actual == expected,
+        }
+    }
+}
+
+fn media_expression_matches(expr: &str, viewport: Viewport, preferences: MediaPreferences) -> bool {
     let mut parts = expr.splitn(2, ':');
     let feature = parts.next().unwrap_or("").trim().to_ascii_lowercase();
-    let value = parts.next().unwrap_or("").trim();
-
-    match feature.as_str() {
-        "min-width" => match parse_length_px(value) {
-            Some(px) => viewport.width_px as f32 >= px,
-            None => false,
-        },
-        "max-width" => match parse_length_px(value) {
-            Some(px) => viewport.width_px as f32 <= px,
-            None => false,
-        },
+    let value = parts.next().map(str::trim);
+
+    let Some(value) = value else {
+        // A feature on its own, like `(orientation)`, asks whether it applies at all.
+        return matches!(
+            feature.as_str(),
+            "width" | "orientation" | "aspect-ratio" | "resolution" | "prefers-color-scheme"
+        ) || feature == "-webkit-device-pixel-ratio";
+    };
+
+    let unprefixed = feature.strip_prefix("-webkit-").unwrap_or(&feature);
+    let (range, name) = if let Some(name) = unprefixed.strip_prefix("min-") {
+        (Range::Min, name)
+    } else if let Some(name) = unprefixed.strip_prefix("max-") {
+        (Range::Max, name)
+    } else {
+        (Range::Exact, unprefixed)
+    };
+
+    let width = viewport.width_px as f32;
+    let height = viewport.height_px as f32;
+    match name {
+        "width" => parse_length_px(value).is_some_and(|px| range.compare(width, px)),
+        "aspect-ratio" => parse_ratio(value)
+            .is_some_and(|ratio| height > 0.0 && range.compare(width / height, ratio)),
+        "resolution" => parse_resolution_dppx(value)
+            .is_some_and(|dppx| range.compare(preferences.device_pixel_ratio, dppx)),
+        "device-pixel-ratio" => value
+            .parse::<f32>()
+            .is_ok_and(|ratio| range.compare(preferences.device_pixel_ratio, ratio)),
+        "orientation" if matches!(range, Range::Exact) => {
+            match value.to_ascii_lowercase().as_str() {
+                "portrait" => height >= width,
+                "landscape" => width > height,
+                _ => false,
+            }
+        }
+        "prefers-color-scheme" if matches!(range, Range::Exact) => {
+            ColorScheme::parse(value) == Some(preferences.color_scheme)
+        }
         _ => false,
     }
 }
 
+/// `16/9`, `16 / 9` or a plain number.
+fn parse_ratio(input: &str) -> Option<f32> {
+    let (numerator, denominator) = match input.split_once('/') {
+        Some((numerator, denominator)) => (numerator, denominator.trim().parse::<f32>().ok()?),
+        None => (input, 1.0),
+    };
+    let numerator = numerator.trim().parse::<f32>().ok()?;
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+/// Resolutions in `dppx` (or its alias `x`), `dpi` or `dpcm`, as dots per CSS pixel.
+fn parse_resolution_dppx(input: &str) -> Option<f32> {
+    let input = input.trim().to_ascii_lowercase();
+    let split = input
+        .find(|ch: char| ch.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    let value = input[..split].trim().parse::<f32>().ok()?;
+    match &input[split..] {
+        "dppx" | "x" => Some(value),
+        "dpi" => Some(value / 96.0),
+        "dpcm" => Some(value * 2.54 / 96.0),
+        _ => None,
+    }
+}
+
 fn parse_length_px(input: &str) -> Option<f32> {
     let value = input.trim();
     let value = value.strip_suffix("px").unwrap_or(value).trim();
@@ -257,6 +436,107 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn matches_color_scheme_preferences() {
+        let viewport = Viewport {
+            width_px: 1024,
+            height_px: 768,
+        };
+        let dark = MediaPreferences {
+            color_scheme: ColorScheme::Dark,
+            ..MediaPreferences::default()
+        };
+        let query = "(prefers-color-scheme: dark)";
+        assert!(media_query_matches_with(query, viewport, dark));
+        assert!(!media_query_matches_with(
+            query,
+            viewport,
+            MediaPreferences::default()
+        ));
+        assert!(media_query_matches_with(
+            "screen and (prefers-color-scheme: light)",
+            viewport,
+            MediaPreferences::default()
+        ));
+    }
+
+    #[test]
+    fn matches_orientation_and_aspect_ratio() {
+        let preferences = MediaPreferences::default();
+        let landscape = Viewport {
+            width_px: 1600,
+            height_px: 900,
+        };
+        let portrait = Viewport {
+            width_px: 400,
+            height_px: 800,
+        };
+        assert!(media_query_matches_with(
+            "(orientation: landscape)",
+            landscape,
+            preferences
+        ));
+        assert!(!media_query_matches_with(
+            "(orientation: landscape)",
+            portrait,
+            preferences
+        ));
+        assert!(media_query_matches_with(
+            "(orientation: portrait)",
+            portrait,
+            preferences
+        ));
+        assert!(media_query_matches_with(
+            "(aspect-ratio: 16/9)",
+            landscape,
+            preferences
+        ));
+        assert!(media_query_matches_with(
+            "(min-aspect-ratio: 4 / 3)",
+            landscape,
+            preferences
+        ));
+        assert!(!media_query_matches_with(
+            "(max-aspect-ratio: 1)",
+            landscape,
+            preferences
+        ));
+        assert!(media_query_matches_with(
+            "(max-aspect-ratio: 1)",
+            portrait,
+            preferences
+        ));
+    }
+
+    #[test]
+    fn matches_resolution_and_device_pixel_ratio() {
+        let viewport = Viewport {
+            width_px: 1024,
+            height_px: 768,
+        };
+        let hidpi = MediaPreferences {
+            device_pixel_ratio: 2.0,
+            ..MediaPreferences::default()
+        };
+        for query in [
+            "(min-resolution: 2dppx)",
+            "(min-resolution: 192dpi)",
+            "(resolution: 2x)",
+            "(-webkit-min-device-pixel-ratio: 1.5)",
+        ] {
+            assert!(media_query_matches_with(query, viewport, hidpi), "{query}");
+            assert!(
+                !media_query_matches_with(query, viewport, MediaPreferences::default()),
+                "{query}"
+            );
+        }
+        assert!(media_query_matches_with(
+            "(max-resolution: 1dppx)",
+            viewport,
+            MediaPreferences::default()
+        ));
+    }
+
     #[test]
     fn supports_comma_separated_or_queries() {
         assert!(media_query_matches(
@@ -85,11 +85,10 @@ fn strip_leading_keyword<'a>(input: &'a str, keyword: &str) -> Option<&'a str> {
 }
 
 fn starts_with_keyword(input: &str, keyword: &str) -> bool {
-    if input.len() < keyword.len() {
-        return false;
-    }
-    let prefix = &input[..keyword.len()];
-    if !prefix.eq_ignore_ascii_case(keyword) {
+    if !input
+        .get(..keyword.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(keyword))
+    {
         return false;
     }
     input
@@ -126,8 +125,9 @@ fn split_top_level_keyword<'a>(input: &'a str, keyword: &str) -> Vec<&'a str> {
         }
 
         if depth == 0
-            && cursor + keyword_len <= bytes.len()
-            && input[cursor..cursor + keyword_len].eq_ignore_ascii_case(keyword)
+            && input
+                .get(cursor..cursor + keyword_len)
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(keyword))
         {
             let before_ok = if cursor == 0 {
                 true
@@ -210,4 +210,12 @@ mod tests {
             "(display:grid) and (mask-image:none)"
         ));
     }
+
+    #[test]
+    fn keywords_are_not_matched_across_multibyte_characters() {
+        assert!(!supports_condition_matches("a€"));
+        assert!(!supports_condition_matches(
+            "(display:grid) a€ (display:grid)"
+        ));
+    }
 }
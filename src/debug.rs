@@ -2,7 +2,9 @@ use std::fmt;
 use std::fmt::Write as _;
 use std::io::{self, Write as IoWrite};
 use std::sync::OnceLock;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+pub mod inspector;
 
 const MAX_LINE_CHARS: usize = 120;
 
@@ -42,46 +44,52 @@ impl Level {
 pub enum Target {
     Nav,
     Net,
+    Html,
     Css,
     Res,
     Layout,
     Render,
+    Js,
 }
 
+const TARGET_COUNT: usize = 8;
+
 impl Target {
     fn tag(self) -> &'static str {
         match self {
             Target::Nav => "NAV",
             Target::Net => "NET",
+            Target::Html => "HTM",
             Target::Css => "CSS",
             Target::Res => "RES",
             Target::Layout => "LYT",
             Target::Render => "RND",
+            Target::Js => "SCR",
         }
     }
 
-    const fn mask(self) -> u64 {
-        match self {
-            Target::Nav => 1 << 0,
-            Target::Net => 1 << 1,
-            Target::Css => 1 << 2,
-            Target::Res => 1 << 3,
-            Target::Layout => 1 << 4,
-            Target::Render => 1 << 5,
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "nav" => Some(Target::Nav),
+            "net" => Some(Target::Net),
+            "html" | "parse" => Some(Target::Html),
+            "css" | "style" => Some(Target::Css),
+            "res" | "resources" => Some(Target::Res),
+            "layout" | "lyt" => Some(Target::Layout),
+            "render" | "rnd" | "paint" => Some(Target::Render),
+            "js" | "script" => Some(Target::Js),
+            _ => None,
         }
     }
-}
 
-const ALL_TARGETS: u64 = Target::Nav.mask()
-    | Target::Net.mask()
-    | Target::Css.mask()
-    | Target::Res.mask()
-    | Target::Layout.mask()
-    | Target::Render.mask();
+    fn index(self) -> usize {
+        self as usize
+    }
+}
 
 struct Config {
-    targets: u64,
-    max_level: Level,
+    /// The most verbose level logged for each target, by [`Target::index`]; `None` is off.
+    levels: [Option<Level>; TARGET_COUNT],
     start: Instant,
 }
 
@@ -89,62 +97,63 @@ static CONFIG: OnceLock<Config> = OnceLock::new();
 
 fn config() -> &'static Config {
     CONFIG.get_or_init(|| {
-        let targets = match std::env::var("OAB_LOG") {
-            Ok(value) => parse_targets_env(Some(value.as_str())),
-            Err(std::env::VarError::NotPresent) => ALL_TARGETS,
-            Err(_) => 0,
-        };
-        let max_level = std::env::var("OAB_LOG_LEVEL")
+        let default_level = std::env::var("OAB_LOG_LEVEL")
             .ok()
             .and_then(|s| Level::parse(&s))
             .unwrap_or(Level::Info);
+        let levels = match std::env::var("OAB_LOG") {
+            Ok(value) => parse_levels_env(&value, default_level),
+            Err(std::env::VarError::NotPresent) => [Some(default_level); TARGET_COUNT],
+            Err(_) => [None; TARGET_COUNT],
+        };
         Config {
-            targets,
-            max_level,
+            levels,
             start: Instant::now(),
         }
     })
 }
 
-fn parse_targets_env(value: Option<&str>) -> u64 {
-    let Some(value) = value else {
-        return 0;
-    };
-
-    let value = value.trim();
-    if value.is_empty() {
-        return 0;
-    }
-
-    match value.to_ascii_lowercase().as_str() {
-        "0" | "false" | "off" | "none" => return 0,
-        "1" | "true" | "on" | "*" | "all" => return ALL_TARGETS,
+/// `OAB_LOG` lists targets, each optionally with a level, e.g. `net=debug,layout=trace`. A bare
+/// target logs at `default_level`, `all` (or `*`) stands for every target, a bare level applies
+/// to every target, and `off` turns a target off.
+fn parse_levels_env(value: &str, default_level: Level) -> [Option<Level>; TARGET_COUNT] {
+    let mut levels = [None; TARGET_COUNT];
+    let value = value.trim().to_ascii_lowercase();
+    match value.as_str() {
+        "" | "0" | "false" | "off" | "none" => return levels,
+        "1" | "true" | "on" => return [Some(default_level); TARGET_COUNT],
         _ => {}
-    };
+    }
 
-    let mut mask = 0u64;
     for token in value.split(|c: char| c == ',' || c.is_whitespace() || c == ';') {
-        let token = token.trim();
         if token.is_empty() {
             continue;
         }
-        match token.to_ascii_lowercase().as_str() {
-            "*" | "all" => return ALL_TARGETS,
-            "nav" => mask |= Target::Nav.mask(),
-            "net" => mask |= Target::Net.mask(),
-            "css" => mask |= Target::Css.mask(),
-            "res" | "resources" => mask |= Target::Res.mask(),
-            "layout" | "lyt" => mask |= Target::Layout.mask(),
-            "render" | "rnd" => mask |= Target::Render.mask(),
-            _ => {}
+        let (name, level) = match token.split_once('=') {
+            Some((name, "off" | "none")) => (name, None),
+            Some((name, level)) => match Level::parse(level) {
+                Some(level) => (name, Some(level)),
+                None => continue,
+            },
+            None => match Level::parse(token) {
+                Some(level) => ("all", Some(level)),
+                None => (token, Some(default_level)),
+            },
+        };
+        match name {
+            "*" | "all" => levels = [level; TARGET_COUNT],
+            name => {
+                if let Some(target) = Target::parse(name) {
+                    levels[target.index()] = level;
+                }
+            }
         }
     }
-    mask
+    levels
 }
 
 pub fn enabled(target: Target, level: Level) -> bool {
-    let cfg = config();
-    (cfg.targets & target.mask()) != 0 && level <= cfg.max_level
+    config().levels[target.index()].is_some_and(|max_level| level <= max_level)
 }
 
 pub fn log(target: Target, level: Level, message: fmt::Arguments<'_>) {
@@ -174,11 +183,62 @@ pub fn log(target: Target, level: Level, message: fmt::Arguments<'_>) {
     );
     truncate_in_place(&mut line, MAX_LINE_CHARS);
 
-    let mut out = io::stdout().lock();
+    // Logs go to stderr so that stdout can carry a screenshot or other command output.
+    let mut out = io::stderr().lock();
     let _ = writeln!(out, "{line}");
     let _ = IoWrite::flush(&mut out);
 }
 
+/// Times a phase from [`span`] until it is dropped or [`Span::end`]ed, then logs
+/// `<name> ms=<elapsed>` at [`Level::Debug`].
+pub struct Span {
+    target: Target,
+    name: &'static str,
+    start: Instant,
+    ended: bool,
+}
+
+pub fn span(target: Target, name: &'static str) -> Span {
+    Span {
+        target,
+        name,
+        start: Instant::now(),
+        ended: false,
+    }
+}
+
+impl Span {
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Logs the span with `details` appended.
+    pub fn end(mut self, details: fmt::Arguments<'_>) {
+        self.ended = true;
+        self.log(details);
+    }
+
+    fn log(&self, details: fmt::Arguments<'_>) {
+        if !enabled(self.target, Level::Debug) {
+            return;
+        }
+        let ms: u64 = self.elapsed().as_millis().try_into().unwrap_or(u64::MAX);
+        log(
+            self.target,
+            Level::Debug,
+            format_args!("{} ms={ms} {details}", self.name),
+        );
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if !self.ended {
+            self.log(format_args!(""));
+        }
+    }
+}
+
 pub fn shorten<'a>(value: &'a str, max_chars: usize) -> std::borrow::Cow<'a, str> {
     let value = value.trim();
     if max_chars == 0 {
@@ -263,3 +323,32 @@ fn truncate_in_place(value: &mut String, max_chars: usize) {
     value.pop();
     value.push('…');
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_per_target_levels() {
+        let levels = parse_levels_env("net=debug, layout=trace,css", Level::Info);
+        assert_eq!(levels[Target::Net.index()], Some(Level::Debug));
+        assert_eq!(levels[Target::Layout.index()], Some(Level::Trace));
+        assert_eq!(levels[Target::Css.index()], Some(Level::Info));
+        assert_eq!(levels[Target::Nav.index()], None);
+
+        let levels = parse_levels_env("all=warn,js=off,paint=debug", Level::Info);
+        assert_eq!(levels[Target::Html.index()], Some(Level::Warn));
+        assert_eq!(levels[Target::Js.index()], None);
+        assert_eq!(levels[Target::Render.index()], Some(Level::Debug));
+
+        assert_eq!(
+            parse_levels_env("debug", Level::Info),
+            [Some(Level::Debug); TARGET_COUNT]
+        );
+        assert_eq!(
+            parse_levels_env("on", Level::Warn),
+            [Some(Level::Warn); TARGET_COUNT]
+        );
+        assert_eq!(parse_levels_env("off", Level::Info), [None; TARGET_COUNT]);
+    }
+}
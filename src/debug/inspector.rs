@@ -0,0 +1,437 @@
+use crate::geom::{Color, Rect};
+use crate::layout::InspectedBox;
+use crate::net::Waterfall;
+use crate::render::{Painter, Viewport};
+use std::fmt::Write as _;
+
+const MARGIN_COLOR: Color = Color {
+    r: 246,
+    g: 178,
+    b: 107,
+    a: 255,
+};
+const BORDER_COLOR: Color = Color {
+    r: 255,
+    g: 229,
+    b: 153,
+    a: 255,
+};
+const PADDING_COLOR: Color = Color {
+    r: 147,
+    g: 196,
+    b: 125,
+    a: 255,
+};
+const CONTENT_COLOR: Color = Color {
+    r: 111,
+    g: 168,
+    b: 220,
+    a: 255,
+};
+const OVERLAY_OPACITY: u8 = 160;
+
+const WATERFALL_MARGIN_PX: i32 = 8;
+const WATERFALL_MAX_WIDTH_PX: i32 = 240;
+const WATERFALL_ROW_PX: i32 = 5;
+const WATERFALL_BAR_PX: i32 = 4;
+/// Left of the bars, filled in [`CRITICAL_COLOR`] on the row of the critical entry.
+const WATERFALL_MARKER_PX: i32 = 4;
+const WATERFALL_BACKGROUND: Color = Color {
+    r: 32,
+    g: 32,
+    b: 32,
+    a: 255,
+};
+const CRITICAL_COLOR: Color = Color {
+    r: 230,
+    g: 60,
+    b: 60,
+    a: 255,
+};
+/// Queued, connecting, downloading and decoding, in that order.
+const PHASE_COLORS: [Color; 4] = [
+    Color {
+        r: 150,
+        g: 150,
+        b: 150,
+        a: 255,
+    },
+    Color {
+        r: 246,
+        g: 178,
+        b: 107,
+        a: 255,
+    },
+    Color {
+        r: 111,
+        g: 168,
+        b: 220,
+        a: 255,
+    },
+    Color {
+        r: 147,
+        g: 196,
+        b: 125,
+        a: 255,
+    },
+];
+
+/// The layout inspector toggled with F12: the box under the pointer is shaded on the page and
+/// its path and computed style are printed to stderr. The page's resource waterfall is drawn in
+/// the bottom left corner while it is open.
+#[derive(Debug, Default)]
+pub struct Inspector {
+    /// In page viewport space; `None` while the pointer is over the chrome.
+    pointer: Option<(i32, i32)>,
+    /// Path and border box of the box last printed, so moving within it stays quiet.
+    reported: Option<(String, Rect)>,
+}
+
+impl Inspector {
+    pub fn set_pointer(&mut self, pointer: Option<(i32, i32)>) {
+        self.pointer = pointer;
+    }
+
+    pub fn hovered<'a>(
+        &self,
+        boxes: &'a [InspectedBox],
+        scroll_y_px: i32,
+    ) -> Option<&'a InspectedBox> {
+        let (x_px, y_px) = self.pointer?;
+        box_at(boxes, x_px, y_px, scroll_y_px)
+    }
+
+    /// Prints the hovered box unless it was the last one printed. Returns whether the hovered box
+    /// changed.
+    pub fn report_hovered(&mut self, boxes: &[InspectedBox], scroll_y_px: i32) -> bool {
+        let hovered = self.hovered(boxes, scroll_y_px);
+        let key = hovered.map(|inspected| (inspected.path.clone(), inspected.border_box));
+        if key == self.reported {
+            return false;
+        }
+        if let Some(inspected) = hovered {
+            eprint!("{}", describe(inspected));
+        }
+        self.reported = key;
+        true
+    }
+}
+
+/// The deepest box containing the page viewport point (`x_px`, `y_px`). Fixed boxes sit above the
+/// scrolled page, so they are hit first.
+pub fn box_at(
+    boxes: &[InspectedBox],
+    x_px: i32,
+    y_px: i32,
+    scroll_y_px: i32,
+) -> Option<&InspectedBox> {
+    let hit = |fixed: bool| {
+        let hit_y_px = if fixed {
+            y_px
+        } else {
+            y_px.saturating_add(scroll_y_px)
+        };
+        boxes
+            .iter()
+            .filter(|inspected| inspected.is_fixed == fixed)
+            .filter(|inspected| inspected.contains_point(x_px, hit_y_px))
+            .max_by_key(|inspected| inspected.depth)
+    };
+    hit(true).or_else(|| hit(false))
+}
+
+/// Shades the margin, border, padding and content areas of `inspected` in translucent colors.
+/// `offset_y_px` is added to the box's coordinates to move them into window space.
+pub fn paint_overlay(
+    painter: &mut dyn Painter,
+    inspected: &InspectedBox,
+    offset_y_px: i32,
+    viewport: Viewport,
+) -> Result<(), String> {
+    let shift = |rect: Rect| Rect {
+        y: rect.y.saturating_add(offset_y_px),
+        ..rect
+    };
+    let rings = [
+        (inspected.margin_box, inspected.border_box, MARGIN_COLOR),
+        (inspected.border_box, inspected.padding_box, BORDER_COLOR),
+        (inspected.padding_box, inspected.content_box, PADDING_COLOR),
+    ];
+    painter.push_opacity(OVERLAY_OPACITY)?;
+    for (outer, inner, color) in rings {
+        fill_ring(painter, shift(outer), shift(inner), color, viewport)?;
+    }
+    fill_clipped(
+        painter,
+        shift(inspected.content_box),
+        CONTENT_COLOR,
+        viewport,
+    )?;
+    painter.pop_opacity(OVERLAY_OPACITY)
+}
+
+/// Draws one bar per resource, latest at the bottom, in a panel in the bottom left corner of
+/// `viewport`.
+pub fn paint_waterfall(
+    painter: &mut dyn Painter,
+    waterfall: &Waterfall,
+    viewport: Viewport,
+) -> Result<(), String> {
+    let rects = waterfall_rects(waterfall, viewport);
+    if rects.is_empty() {
+        return Ok(());
+    }
+    painter.push_opacity(OVERLAY_OPACITY)?;
+    for (rect, color) in rects {
+        fill_clipped(painter, rect, color, viewport)?;
+    }
+    painter.pop_opacity(OVERLAY_OPACITY)
+}
+
+/// The panel behind the waterfall, then each phase of each bar that fits in the bottom third of
+/// `viewport`, dropping the earliest entries when there are too many.
+fn waterfall_rects(waterfall: &Waterfall, viewport: Viewport) -> Vec<(Rect, Color)> {
+    let width_px = WATERFALL_MAX_WIDTH_PX.min(viewport.width_px - 2 * WATERFALL_MARGIN_PX);
+    let bars_width_px = width_px - WATERFALL_MARKER_PX - 2;
+    let max_rows = usize::try_from(viewport.height_px / 3 / WATERFALL_ROW_PX).unwrap_or(0);
+    if bars_width_px <= 0 || max_rows == 0 {
+        return Vec::new();
+    }
+    let bars = waterfall.bars(bars_width_px as usize);
+    let shown = &bars[bars.len().saturating_sub(max_rows)..];
+    if shown.is_empty() {
+        return Vec::new();
+    }
+    let height_px = shown.len() as i32 * WATERFALL_ROW_PX + 1;
+    let panel = Rect {
+        x: WATERFALL_MARGIN_PX,
+        y: viewport.height_px - WATERFALL_MARGIN_PX - height_px,
+        width: width_px,
+        height: height_px,
+    };
+    let mut rects = vec![(panel, WATERFALL_BACKGROUND)];
+    let bars_x = panel.x + 1 + WATERFALL_MARKER_PX;
+    for (row, bar) in shown.iter().enumerate() {
+        let y = panel.y + 1 + row as i32 * WATERFALL_ROW_PX;
+        if bar.critical {
+            let marker = Rect {
+                x: panel.x + 1,
+                y,
+                width: WATERFALL_MARKER_PX - 1,
+                height: WATERFALL_BAR_PX,
+            };
+            rects.push((marker, CRITICAL_COLOR));
+        }
+        let edges = [
+            bar.queued,
+            bar.started,
+            bar.response,
+            bar.finished,
+            bar.done,
+        ];
+        for (phase, color) in PHASE_COLORS.iter().enumerate() {
+            let (from, to) = (edges[phase], edges[phase + 1]);
+            if to <= from {
+                continue;
+            }
+            let segment = Rect {
+                x: bars_x + from as i32,
+                y,
+                width: (to - from) as i32,
+                height: WATERFALL_BAR_PX,
+            };
+            rects.push((segment, *color));
+        }
+    }
+    rects
+}
+
+/// The part of `outer` outside `inner`, as up to four bands.
+fn fill_ring(
+    painter: &mut dyn Painter,
+    outer: Rect,
+    inner: Rect,
+    color: Color,
+    viewport: Viewport,
+) -> Result<(), String> {
+    let top = inner.y.clamp(outer.y, outer.bottom());
+    let bottom = inner.bottom().clamp(top, outer.bottom());
+    let left = inner.x.clamp(outer.x, outer.right());
+    let right = inner.right().clamp(left, outer.right());
+    let bands = [
+        Rect {
+            height: top - outer.y,
+            ..outer
+        },
+        Rect {
+            y: bottom,
+            height: outer.bottom() - bottom,
+            ..outer
+        },
+        Rect {
+            x: outer.x,
+            y: top,
+            width: left - outer.x,
+            height: bottom - top,
+        },
+        Rect {
+            x: right,
+            y: top,
+            width: outer.right() - right,
+            height: bottom - top,
+        },
+    ];
+    for band in bands {
+        fill_clipped(painter, band, color, viewport)?;
+    }
+    Ok(())
+}
+
+fn fill_clipped(
+    painter: &mut dyn Painter,
+    rect: Rect,
+    color: Color,
+    viewport: Viewport,
+) -> Result<(), String> {
+    let x = rect.x.max(0);
+    let y = rect.y.max(0);
+    let width = rect.right().min(viewport.width_px).saturating_sub(x);
+    let height = rect.bottom().min(viewport.height_px).saturating_sub(y);
+    if width <= 0 || height <= 0 {
+        return Ok(());
+    }
+    painter.fill_rect(x, y, width, height, color)
+}
+
+/// The report printed for a hovered box: its path, its four boxes and its computed style.
+pub fn describe(inspected: &InspectedBox) -> String {
+    let mut out = format!("inspect {}", inspected.path);
+    if inspected.is_fixed {
+        out.push_str(" (fixed)");
+    }
+    out.push('\n');
+    for (name, rect) in [
+        ("margin", inspected.margin_box),
+        ("border", inspected.border_box),
+        ("padding", inspected.padding_box),
+        ("content", inspected.content_box),
+    ] {
+        let _ = writeln!(
+            out,
+            "  {name:<8}{},{} {}x{}",
+            rect.x, rect.y, rect.width, rect.height
+        );
+    }
+    let _ = writeln!(out, "{:#?}", inspected.style);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::ComputedStyle;
+
+    fn inspected(path: &str, depth: usize, is_fixed: bool, border_box: Rect) -> InspectedBox {
+        InspectedBox {
+            path: path.to_owned(),
+            depth,
+            is_fixed,
+            margin_box: border_box,
+            border_box,
+            padding_box: border_box,
+            content_box: border_box,
+            style: ComputedStyle::root_defaults(),
+            element: 0,
+        }
+    }
+
+    #[test]
+    fn hits_the_deepest_box_and_fixed_boxes_first() {
+        let rect = |x, y, width, height| Rect {
+            x,
+            y,
+            width,
+            height,
+        };
+        let boxes = vec![
+            inspected("body > div", 1, false, rect(0, 0, 100, 1000)),
+            inspected("body", 0, false, rect(0, 0, 200, 1000)),
+            inspected("body > nav", 1, true, rect(0, 0, 200, 20)),
+        ];
+        let path = |x, y, scroll| box_at(&boxes, x, y, scroll).map(|b| b.path.as_str());
+
+        assert_eq!(path(50, 50, 0), Some("body > div"));
+        assert_eq!(path(150, 50, 0), Some("body"));
+        assert_eq!(path(50, 10, 500), Some("body > nav"));
+        assert_eq!(path(50, 600, 500), None);
+
+        let mut inspector = Inspector::default();
+        inspector.set_pointer(Some((150, 50)));
+        assert!(inspector.report_hovered(&boxes, 0));
+        assert!(!inspector.report_hovered(&boxes, 0));
+        inspector.set_pointer(None);
+        assert!(inspector.report_hovered(&boxes, 0));
+    }
+
+    #[test]
+    fn waterfall_panel_keeps_the_latest_bars_that_fit() {
+        use crate::net::{FetchTiming, WaterfallEntry};
+        use std::time::{Duration, Instant};
+
+        let origin = Instant::now();
+        let at = |ms: u64| origin + Duration::from_millis(ms);
+        let mut waterfall = Waterfall::new();
+        for index in 0..4u64 {
+            waterfall.push(WaterfallEntry {
+                kind: "img",
+                url: format!("https://example.com/{index}.png"),
+                ok: true,
+                bytes: 0,
+                timing: FetchTiming {
+                    queued_at: at(index * 10),
+                    started_at: at(index * 10 + 10),
+                    response_at: Some(at(index * 10 + 20)),
+                    finished_at: at(index * 10 + 30),
+                },
+                decoded_at: Some(at(index * 10 + 40)),
+            });
+        }
+        let viewport = Viewport {
+            width_px: 200,
+            height_px: 45,
+        };
+        let rects = waterfall_rects(&waterfall, viewport);
+
+        let (panel, _) = rects[0];
+        assert_eq!(
+            panel,
+            Rect {
+                x: 8,
+                y: 21,
+                width: 184,
+                height: 16,
+            }
+        );
+        // Three rows fit, so the first entry is left out and the last, which finishes last, is
+        // marked critical.
+        let rows: Vec<i32> = rects[1..].iter().map(|(rect, _)| rect.y).collect();
+        assert!(rows.iter().all(|y| [22, 27, 32].contains(y)));
+        assert!(!rects.iter().any(|(rect, _)| rect.x == 13));
+        assert!(rects.contains(&(
+            Rect {
+                x: 9,
+                y: 32,
+                width: 3,
+                height: 4,
+            },
+            CRITICAL_COLOR
+        )));
+        let last_row: Vec<(i32, i32)> = rects
+            .iter()
+            .filter(|(rect, color)| rect.y == 32 && *color != CRITICAL_COLOR)
+            .map(|(rect, _)| (rect.x, rect.right()))
+            .collect();
+        assert_eq!(last_row.last().map(|(_, right)| *right), Some(13 + 178));
+        assert!(last_row.windows(2).all(|pair| pair[0].1 == pair[1].0));
+    }
+}
@@ -1,6 +1,52 @@
+use crate::css::parse_selector_list;
+use crate::render::canvas::CanvasSurface;
+use crate::style::selector_matches;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Document {
     pub root: Element,
+    /// What inline scripts drew on `<canvas>` elements, keyed by element id.
+    pub canvases: HashMap<String, Arc<CanvasSurface>>,
+    /// Changes made through the mutation API since the last [`Document::take_mutations`].
+    pub mutations: Mutations,
+    /// The `href` of the first `<base>` that has one, as parsed. The page's relative links,
+    /// images and stylesheets resolve against it, itself resolved against the page's address.
+    pub base_href: Option<String>,
+}
+
+/// Dirty subtrees left by mutations, each named by the child indices from the root to it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mutations {
+    /// Disjoint paths: a path is dropped when an ancestor of it is dirty too.
+    pub dirty: Vec<Vec<usize>>,
+    /// A `<style>` or stylesheet `<link>` changed, so the page's stylesheets must be collected
+    /// again.
+    pub stylesheets: bool,
+}
+
+/// Dirty paths kept before the whole document is marked dirty instead.
+const MAX_DIRTY_PATHS: usize = 256;
+
+impl Mutations {
+    pub fn is_empty(&self) -> bool {
+        self.dirty.is_empty() && !self.stylesheets
+    }
+
+    fn mark(&mut self, path: &[usize]) {
+        if self.dirty.iter().any(|dirty| path.starts_with(dirty)) {
+            return;
+        }
+        self.dirty.retain(|dirty| !dirty.starts_with(path));
+        if self.dirty.len() >= MAX_DIRTY_PATHS {
+            self.dirty.clear();
+            self.dirty.push(Vec::new());
+            return;
+        }
+        self.dirty.push(path.to_vec());
+    }
 }
 
 impl Document {
@@ -25,6 +71,307 @@ impl Document {
     pub fn find_first_element_by_id_mut(&mut self, id: &str) -> Option<&mut Element> {
         self.root.find_first_element_by_id_mut(id)
     }
+
+    /// The element at `path`, a list of child indices from the root.
+    pub fn element_at(&self, path: &[usize]) -> Option<&Element> {
+        let mut element = &self.root;
+        for &index in path {
+            match element.children.get(index)? {
+                Node::Element(child) => element = child,
+                Node::Text(_) => return None,
+            }
+        }
+        Some(element)
+    }
+
+    /// The element at `path` for editing in place; its whole subtree is marked dirty. Use the
+    /// methods below to add or remove children, so stylesheets among them are noticed.
+    pub fn edit_element(&mut self, path: &[usize]) -> Option<&mut Element> {
+        let mut touches_stylesheet = false;
+        let mut element = &mut self.root;
+        for &index in path {
+            touches_stylesheet |= is_stylesheet_element(element);
+            match element.children.get_mut(index)? {
+                Node::Element(child) => element = child,
+                Node::Text(_) => return None,
+            }
+        }
+        touches_stylesheet |= is_stylesheet_element(element);
+        self.mutations.mark(path);
+        self.mutations.stylesheets |= touches_stylesheet;
+        Some(element)
+    }
+
+    /// Inserts `node` as child `index` of the element at `parent`, or last when `index` is past
+    /// the end.
+    pub fn insert_child(&mut self, parent: &[usize], index: usize, node: Node) -> bool {
+        let stylesheet = matches!(&node, Node::Element(el) if contains_stylesheet_element(el));
+        let Some(element) = self.edit_element(parent) else {
+            return false;
+        };
+        let index = index.min(element.children.len());
+        element.children.insert(index, node);
+        self.mutations.stylesheets |= stylesheet;
+        true
+    }
+
+    pub fn append_child(&mut self, parent: &[usize], node: Node) -> bool {
+        self.insert_child(parent, usize::MAX, node)
+    }
+
+    pub fn remove_child(&mut self, parent: &[usize], index: usize) -> Option<Node> {
+        let element = self.edit_element(parent)?;
+        if index >= element.children.len() {
+            return None;
+        }
+        let node = element.children.remove(index);
+        if matches!(&node, Node::Element(el) if contains_stylesheet_element(el)) {
+            self.mutations.stylesheets = true;
+        }
+        Some(node)
+    }
+
+    pub fn replace_child(&mut self, parent: &[usize], index: usize, node: Node) -> Option<Node> {
+        let removed = self.remove_child(parent, index)?;
+        self.insert_child(parent, index, node);
+        Some(removed)
+    }
+
+    pub fn set_attribute(&mut self, path: &[usize], name: &str, value: String) -> bool {
+        let Some(element) = self.edit_element(path) else {
+            return false;
+        };
+        element.attributes.remove(name);
+        element.attributes.insert(name.to_owned(), value);
+        true
+    }
+
+    pub fn remove_attribute(&mut self, path: &[usize], name: &str) -> bool {
+        let Some(element) = self.edit_element(path) else {
+            return false;
+        };
+        element.attributes.remove(name);
+        true
+    }
+
+    pub fn set_text_content(&mut self, path: &[usize], text: String) -> bool {
+        self.replace_children(path, vec![Node::Text(text)])
+    }
+
+    pub fn replace_children(&mut self, path: &[usize], children: Vec<Node>) -> bool {
+        let Some(element) = self.edit_element(path) else {
+            return false;
+        };
+        let old = std::mem::replace(&mut element.children, children);
+        let stylesheet = old
+            .iter()
+            .chain(&element.children)
+            .any(|node| matches!(node, Node::Element(el) if contains_stylesheet_element(el)));
+        self.mutations.stylesheets |= stylesheet;
+        true
+    }
+
+    pub fn take_mutations(&mut self) -> Mutations {
+        std::mem::take(&mut self.mutations)
+    }
+
+    /// The first element matching the CSS selector list `selectors`, in document order.
+    pub fn query_selector(&self, selectors: &str) -> Option<&Element> {
+        self.root.query_selector(selectors)
+    }
+
+    /// Every element matching the CSS selector list `selectors`, in document order.
+    pub fn query_selector_all(&self, selectors: &str) -> Vec<&Element> {
+        self.root.query_selector_all(selectors)
+    }
+
+    /// The path of the first element matching `selectors`, for the mutation methods above.
+    pub fn query_selector_path(&self, selectors: &str) -> Option<Vec<usize>> {
+        let selectors = parse_selector_list(selectors);
+        let mut found = None;
+        self.walk_elements(&[], &mut |element, path, ancestors| {
+            if selectors
+                .iter()
+                .any(|selector| selector_matches(selector, element, ancestors))
+            {
+                found = Some(path.to_vec());
+            }
+            found.is_none()
+        });
+        found
+    }
+
+    /// Calls `visit` with each element below the one at `scope` in document order, along with
+    /// its path and its ancestors from the outermost down, until `visit` returns `false`.
+    /// Returns how many elements were visited.
+    pub fn walk_elements<'a>(
+        &'a self,
+        scope: &[usize],
+        visit: &mut ElementVisitor<'a, '_>,
+    ) -> usize {
+        let mut ancestors = Vec::new();
+        let mut element = &self.root;
+        for &index in scope {
+            if element.name != DOCUMENT_ROOT_NAME {
+                ancestors.push(element);
+            }
+            let Some(Node::Element(child)) = element.children.get(index) else {
+                return 0;
+            };
+            element = child;
+        }
+        let mut walk = Walk {
+            path: scope.to_vec(),
+            ancestors,
+            visit,
+            visited: 0,
+        };
+        walk.descend(element);
+        walk.visited
+    }
+
+    /// The text of the first `<title>`, with runs of whitespace collapsed; `None` when there is
+    /// no title or it is blank.
+    pub fn title(&self) -> Option<String> {
+        let title = self.find_first_element_by_name("title")?;
+        let text = title
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Text(text) => Some(text.as_str()),
+                Node::Element(_) => None,
+            })
+            .collect::<String>();
+        let title = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        (!title.is_empty()).then_some(title)
+    }
+
+    /// The `href` of the first `<link>` whose `rel` includes `icon`, as in `rel="shortcut icon"`.
+    pub fn favicon_href(&self) -> Option<&str> {
+        let mut href = None;
+        self.walk_elements(&[], &mut |element, _, _| {
+            let is_icon = element.name == "link"
+                && element.attributes.get("rel").is_some_and(|rel| {
+                    rel.split_whitespace()
+                        .any(|token| token.eq_ignore_ascii_case("icon"))
+                });
+            if is_icon {
+                href = element
+                    .attributes
+                    .get("href")
+                    .filter(|href| !href.trim().is_empty());
+            }
+            href.is_none()
+        });
+        href
+    }
+
+    /// The element tree, one node per line and indented by depth, for `--dump-dom`. Elements are
+    /// written as their name and attributes; text is quoted, and whitespace-only text is left out.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for child in &self.root.children {
+            write_node(&mut out, child, 0);
+        }
+        out
+    }
+}
+
+fn write_node(out: &mut String, node: &Node, depth: usize) {
+    match node {
+        Node::Element(element) => {
+            out.push_str(&"  ".repeat(depth));
+            out.push_str(&element.name);
+            for (name, value) in element.attributes.to_serialized_pairs() {
+                let _ = write!(out, " {name}={value:?}");
+            }
+            out.push('\n');
+            for child in &element.children {
+                write_node(out, child, depth + 1);
+            }
+        }
+        Node::Text(text) if text.trim().is_empty() => {}
+        Node::Text(text) => {
+            let _ = writeln!(out, "{}{text:?}", "  ".repeat(depth));
+        }
+    }
+}
+
+/// Selectors for `ancestors` and then `element`, e.g. `html > body > div#main.card`.
+pub fn element_path(ancestors: &[&Element], element: &Element) -> String {
+    let mut path = String::new();
+    for el in ancestors.iter().copied().chain(std::iter::once(element)) {
+        if !path.is_empty() {
+            path.push_str(" > ");
+        }
+        path.push_str(&el.name);
+        if let Some(id) = &el.attributes.id {
+            path.push('#');
+            path.push_str(id);
+        }
+        for class in &el.attributes.classes {
+            path.push('.');
+            path.push_str(class);
+        }
+    }
+    path
+}
+
+/// The name of [`Document::root`], which is not an element of the page.
+const DOCUMENT_ROOT_NAME: &str = "#document";
+
+/// Receives an element, its path and its ancestors; returns `false` to stop the walk.
+pub type ElementVisitor<'a, 'v> = dyn FnMut(&'a Element, &[usize], &[&'a Element]) -> bool + 'v;
+
+struct Walk<'a, 'v> {
+    path: Vec<usize>,
+    ancestors: Vec<&'a Element>,
+    visit: &'v mut ElementVisitor<'a, 'v>,
+    visited: usize,
+}
+
+impl<'a> Walk<'a, '_> {
+    /// Returns `false` once `visit` asked to stop.
+    fn descend(&mut self, parent: &'a Element) -> bool {
+        let is_root = parent.name == DOCUMENT_ROOT_NAME;
+        if !is_root {
+            self.ancestors.push(parent);
+        }
+        let mut keep_going = true;
+        for (index, child) in parent.children.iter().enumerate() {
+            let Node::Element(child) = child else {
+                continue;
+            };
+            self.visited += 1;
+            self.path.push(index);
+            keep_going = (self.visit)(child, &self.path, &self.ancestors) && self.descend(child);
+            self.path.pop();
+            if !keep_going {
+                break;
+            }
+        }
+        if !is_root {
+            self.ancestors.pop();
+        }
+        keep_going
+    }
+}
+
+fn is_stylesheet_element(element: &Element) -> bool {
+    element.name == "style"
+        || (element.name == "link"
+            && element.attributes.get("rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|token| token.eq_ignore_ascii_case("stylesheet"))
+            }))
+}
+
+fn contains_stylesheet_element(element: &Element) -> bool {
+    is_stylesheet_element(element)
+        || element.children.iter().any(|child| match child {
+            Node::Element(child) => contains_stylesheet_element(child),
+            Node::Text(_) => false,
+        })
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
@@ -49,6 +396,15 @@ impl Attributes {
         }
     }
 
+    pub fn remove(&mut self, name: &str) {
+        match name {
+            "id" => self.id = None,
+            "class" => self.classes.clear(),
+            "style" => self.style = None,
+            _ => self.others.retain(|(k, _)| k != name),
+        }
+    }
+
     pub fn get(&self, name: &str) -> Option<&str> {
         match name {
             "id" => self.id.as_deref(),
@@ -171,6 +527,52 @@ impl Element {
         self.children.clear();
         self.children.push(Node::Text(text));
     }
+
+    /// Whether the element's subtree is out of reach of the user: it has `inert` or
+    /// `aria-hidden="true"`, so its text isn't extracted and its links can't be clicked.
+    pub fn is_inert(&self) -> bool {
+        self.attributes.get("inert").is_some()
+            || self
+                .attributes
+                .get("aria-hidden")
+                .is_some_and(|hidden| hidden.trim().eq_ignore_ascii_case("true"))
+    }
+
+    /// The first descendant matching the CSS selector list `selectors`. Selectors see this
+    /// element as the outermost ancestor, so `div p` called on a `<div>` can match.
+    pub fn query_selector(&self, selectors: &str) -> Option<&Element> {
+        self.select(selectors, 1).into_iter().next()
+    }
+
+    /// Every descendant matching the CSS selector list `selectors`, in document order.
+    pub fn query_selector_all(&self, selectors: &str) -> Vec<&Element> {
+        self.select(selectors, usize::MAX)
+    }
+
+    fn select<'a>(&'a self, selectors: &str, limit: usize) -> Vec<&'a Element> {
+        let selectors = parse_selector_list(selectors);
+        let mut found = Vec::new();
+        if selectors.is_empty() {
+            return found;
+        }
+        let mut visit = |element: &'a Element, _: &[usize], ancestors: &[&'a Element]| {
+            if selectors
+                .iter()
+                .any(|selector| selector_matches(selector, element, ancestors))
+            {
+                found.push(element);
+            }
+            found.len() < limit
+        };
+        let mut walk = Walk {
+            path: Vec::new(),
+            ancestors: Vec::new(),
+            visit: &mut visit,
+            visited: 0,
+        };
+        walk.descend(self);
+        found
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -178,3 +580,118 @@ pub enum Node {
     Element(Element),
     Text(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutations_record_disjoint_dirty_subtrees_and_stylesheet_changes() {
+        let mut document =
+            crate::html::parse_document("<div><p>a</p><p>b</p></div><section></section>");
+        assert!(document.set_text_content(&[0, 0], "x".to_owned()));
+        assert!(document.set_attribute(&[0, 1], "title", "t".to_owned()));
+        assert!(document.remove_attribute(&[1], "hidden"));
+        assert!(!document.set_attribute(&[5], "title", "t".to_owned()));
+        assert_eq!(
+            document.mutations.dirty,
+            vec![vec![0, 0], vec![0, 1], vec![1]]
+        );
+        assert!(!document.mutations.stylesheets);
+
+        let style = crate::html::parse_document("<style>p{}</style>")
+            .root
+            .children;
+        assert!(document.append_child(&[0], style.into_iter().next().unwrap()));
+        let mutations = document.take_mutations();
+        assert_eq!(mutations.dirty, vec![vec![1], vec![0]]);
+        assert!(mutations.stylesheets);
+        assert!(document.mutations.is_empty());
+
+        let removed = document.remove_child(&[0], 0);
+        assert!(matches!(removed, Some(Node::Element(el)) if el.name == "p"));
+        assert!(!document.mutations.stylesheets);
+        assert_eq!(
+            document.element_at(&[0, 0]).map(|el| el.name.as_str()),
+            Some("p")
+        );
+    }
+
+    #[test]
+    fn query_selector_matches_css_selectors_in_document_order() {
+        let document = crate::html::parse_document(
+            r#"<html><body><nav><a href="/" class="home">H</a></nav><main id="content"><p>One</p><div><p class="note">Two</p></div></main></body></html>"#,
+        );
+        let names = |elements: Vec<&Element>| {
+            elements
+                .iter()
+                .map(|el| el.children.iter().map(text_of).collect::<String>())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(document.query_selector_all("main p")), ["One", "Two"]);
+        assert_eq!(
+            names(document.query_selector_all("#content > p, a.home")),
+            ["H", "One"]
+        );
+        assert_eq!(
+            document
+                .query_selector("body > nav a[href]")
+                .and_then(|a| a.attributes.get("href")),
+            Some("/")
+        );
+        assert!(document.query_selector("html > p").is_none());
+        assert!(document.query_selector("").is_none());
+        let path = document.query_selector_path("p.note").unwrap();
+        assert_eq!(
+            names(document.element_at(&path).into_iter().collect()),
+            ["Two"]
+        );
+
+        let main = document.find_first_element_by_id("content").unwrap();
+        assert_eq!(names(main.query_selector_all("main div > p")), ["Two"]);
+        assert_eq!(
+            names(main.query_selector("p").into_iter().collect()),
+            ["One"]
+        );
+        assert!(main.query_selector("nav").is_none());
+    }
+
+    #[test]
+    fn title_and_favicon_come_from_the_head() {
+        let document = crate::html::parse_document(
+            "<html><head><title>\n  News &amp;\n  Weather </title>\
+             <link rel=stylesheet href=a.css><link rel=\"Shortcut Icon\" href=\"/icon.png\">\
+             <link rel=icon href=second.png></head><body><title>Later</title></body></html>",
+        );
+        assert_eq!(document.title().as_deref(), Some("News & Weather"));
+        assert_eq!(document.favicon_href(), Some("/icon.png"));
+
+        let bare = crate::html::parse_document("<p>No head</p><title>  </title>");
+        assert_eq!(bare.title(), None);
+        assert_eq!(bare.favicon_href(), None);
+    }
+
+    #[test]
+    fn to_text_writes_one_node_per_line_indented_by_depth() {
+        let document = crate::html::parse_document(
+            "<html><body>\n  <div id=main class='a b' title=\"x\">Hi <em>there</em></div>\n</body></html>",
+        );
+        assert_eq!(
+            document.to_text(),
+            "html\n\
+             \x20 body\n\
+             \x20   div id=\"main\" class=\"a b\" title=\"x\"\n\
+             \x20     \"Hi \"\n\
+             \x20     em\n\
+             \x20       \"there\"\n"
+        );
+    }
+
+    fn text_of(node: &Node) -> String {
+        match node {
+            Node::Text(text) => text.clone(),
+            Node::Element(el) => el.children.iter().map(text_of).collect(),
+        }
+    }
+}
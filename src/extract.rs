@@ -0,0 +1,532 @@
+use crate::dom::{Document, Element, Node};
+use crate::render::Viewport;
+use crate::style::{ComputedStyle, Display, StyleComputer, Visibility};
+
+/// Elements that start a block of text whatever their `display`, since the user-agent styles
+/// leave several of them inline.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// A block of a page's readable text, in document order. Text has its whitespace collapsed,
+/// with a newline for each `<br>` outside headings, links and table cells; links in it are
+/// written `[text](href)` and images with alt text `![alt](src)`, as in Markdown.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Block {
+    Heading {
+        level: u8,
+        text: String,
+    },
+    Paragraph(String),
+    /// `depth` is 1 for an item of an outermost list. `marker` is `-` or the item's number
+    /// such as `3.`, and empty for text that follows a nested list inside the same item.
+    ListItem {
+        depth: usize,
+        marker: String,
+        text: String,
+    },
+    /// `header` is set for a row of `<th>` cells only.
+    TableRow {
+        header: bool,
+        cells: Vec<String>,
+    },
+    /// The text of a `<pre>`, whitespace kept.
+    Preformatted(String),
+    Quote(String),
+    /// An `<hr>`.
+    Rule,
+}
+
+/// Walks `document` as styled for `viewport` and returns its visible text as blocks: elements
+/// with `display: none` are left out, as are inert subtrees and text with `visibility: hidden`.
+/// `resolve` turns a link's `href` or an image's `src` into the URL to show.
+pub fn extract_blocks(
+    document: &Document,
+    styles: &StyleComputer,
+    viewport: Viewport,
+    resolve: &dyn Fn(&str) -> String,
+) -> Vec<Block> {
+    let mut extractor = Extractor {
+        styles,
+        viewport,
+        resolve,
+        blocks: Vec::new(),
+        text: String::new(),
+        contexts: Vec::new(),
+        lists: Vec::new(),
+        inline_only: 0,
+    };
+    let root = document.render_root();
+    let mut ancestors = Vec::new();
+    extractor.element(root, &ComputedStyle::root_defaults(), &mut ancestors);
+    extractor.flush();
+    extractor.blocks
+}
+
+/// The blocks as Markdown, a blank line between blocks other than neighbouring list items and
+/// table rows.
+pub fn blocks_to_text(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    let mut previous: Option<&Block> = None;
+    for block in blocks {
+        let runs_on = matches!(
+            (previous, block),
+            (Some(Block::ListItem { .. }), Block::ListItem { .. })
+                | (Some(Block::TableRow { .. }), Block::TableRow { .. })
+        );
+        if previous.is_some() && !runs_on {
+            out.push('\n');
+        }
+        match block {
+            Block::Heading { level, text } => {
+                out.push_str(&"#".repeat(usize::from(*level)));
+                out.push(' ');
+                push_lines(&mut out, text, "", "");
+            }
+            Block::Paragraph(text) => push_lines(&mut out, text, "", ""),
+            Block::ListItem {
+                depth,
+                marker,
+                text,
+            } => {
+                let indent = "  ".repeat(depth.saturating_sub(1));
+                let hanging = format!("{indent}{}", " ".repeat(marker.len().max(1) + 1));
+                if marker.is_empty() {
+                    push_lines(&mut out, text, &hanging, &hanging);
+                } else {
+                    push_lines(&mut out, text, &format!("{indent}{marker} "), &hanging);
+                }
+            }
+            Block::TableRow { header, cells } => {
+                let row = |cells: &mut dyn Iterator<Item = String>| {
+                    format!("| {} |\n", cells.collect::<Vec<_>>().join(" | "))
+                };
+                out.push_str(&row(&mut cells.iter().cloned()));
+                if *header {
+                    out.push_str(&row(&mut cells.iter().map(|_| "---".to_owned())));
+                }
+            }
+            Block::Preformatted(text) => {
+                out.push_str("```\n");
+                out.push_str(text.trim_end_matches('\n'));
+                out.push_str("\n```\n");
+            }
+            Block::Quote(text) => push_lines(&mut out, text, "> ", "> "),
+            Block::Rule => out.push_str("---\n"),
+        }
+        previous = Some(block);
+    }
+    out
+}
+
+fn push_lines(out: &mut String, text: &str, first: &str, rest: &str) {
+    for (index, line) in text.lines().enumerate() {
+        out.push_str(if index == 0 { first } else { rest });
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+/// What the text gathered so far becomes once its block ends.
+#[derive(Clone)]
+enum Context {
+    ListItem { depth: usize, marker: String },
+    Quote,
+}
+
+struct Extractor<'a> {
+    styles: &'a StyleComputer,
+    viewport: Viewport,
+    resolve: &'a dyn Fn(&str) -> String,
+    blocks: Vec<Block>,
+    /// Text of the block being gathered.
+    text: String,
+    contexts: Vec<Context>,
+    /// The next number of each open list, outermost first; `None` for an unordered list.
+    lists: Vec<Option<i64>>,
+    /// Above zero inside headings, links and table cells, whose contents stay on one line.
+    inline_only: usize,
+}
+
+impl<'doc> Extractor<'_> {
+    fn element(
+        &mut self,
+        element: &'doc Element,
+        parent: &ComputedStyle,
+        ancestors: &mut Vec<&'doc Element>,
+    ) {
+        let style = self.styles.compute_style_in_viewport(
+            element,
+            parent,
+            ancestors,
+            self.viewport.width_px,
+            self.viewport.height_px,
+        );
+        if style.display == Display::None || element.name == "template" || element.is_inert() {
+            return;
+        }
+        let visible = style.visibility == Visibility::Visible;
+        let is_block = BLOCK_ELEMENTS.contains(&element.name.as_str())
+            || !matches!(style.display, Display::Inline | Display::InlineBlock);
+        if self.inline_only > 0 {
+            if is_block {
+                self.push_space();
+            }
+            self.inline_element(element, &style, visible, ancestors);
+            return;
+        }
+
+        match element.name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush();
+                self.inline_only += 1;
+                self.children(element, &style, visible, ancestors);
+                self.inline_only -= 1;
+                let level = element.name.as_bytes()[1] - b'0';
+                let text = collapse_whitespace(&self.take_text());
+                if !text.is_empty() {
+                    self.blocks.push(Block::Heading { level, text });
+                }
+            }
+            "tr" => {
+                self.flush();
+                self.table_row(element, &style, ancestors);
+            }
+            "pre" => {
+                self.flush();
+                let mut text = String::new();
+                if visible {
+                    collect_raw_text(element, &mut text);
+                }
+                if !text.trim().is_empty() {
+                    self.blocks.push(Block::Preformatted(text));
+                }
+            }
+            "hr" => {
+                self.flush();
+                self.blocks.push(Block::Rule);
+            }
+            "ul" | "ol" => {
+                self.flush();
+                let start = element
+                    .attributes
+                    .get("start")
+                    .and_then(|start| start.trim().parse().ok())
+                    .unwrap_or(1);
+                self.lists.push((element.name == "ol").then_some(start));
+                self.children(element, &style, visible, ancestors);
+                self.flush();
+                self.lists.pop();
+            }
+            "li" => {
+                self.flush();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}.");
+                        *number += 1;
+                        marker
+                    }
+                    _ => "-".to_owned(),
+                };
+                let depth = self.lists.len().max(1);
+                self.within(Context::ListItem { depth, marker }, |this| {
+                    this.children(element, &style, visible, ancestors);
+                });
+            }
+            "blockquote" => {
+                self.flush();
+                self.within(Context::Quote, |this| {
+                    this.children(element, &style, visible, ancestors);
+                });
+            }
+            _ if is_block => {
+                self.flush();
+                self.children(element, &style, visible, ancestors);
+                self.flush();
+            }
+            _ => self.inline_element(element, &style, visible, ancestors),
+        }
+    }
+
+    /// Adds a link, image, line break or other inline element to the text being gathered.
+    fn inline_element(
+        &mut self,
+        element: &'doc Element,
+        style: &ComputedStyle,
+        visible: bool,
+        ancestors: &mut Vec<&'doc Element>,
+    ) {
+        match element.name.as_str() {
+            "br" => {
+                if visible {
+                    let trimmed = self.text.trim_end_matches(' ').len();
+                    self.text.truncate(trimmed);
+                    self.text.push('\n');
+                }
+            }
+            "img" => {
+                let alt = element.attributes.get("alt").unwrap_or_default().trim();
+                if visible && !alt.is_empty() {
+                    self.push_space_if_needed();
+                    let alt = collapse_whitespace(alt);
+                    match element.attributes.get("src").map(str::trim) {
+                        Some(src) if !src.is_empty() && !src.starts_with("data:") => {
+                            let src = (self.resolve)(src);
+                            self.text.push_str(&format!("![{alt}]({src})"));
+                        }
+                        _ => self.text.push_str(&format!("![{alt}]")),
+                    }
+                }
+            }
+            "a" if element
+                .attributes
+                .get("href")
+                .is_some_and(|href| !href.trim().is_empty()) =>
+            {
+                let before = std::mem::take(&mut self.text);
+                self.inline_only += 1;
+                self.children(element, style, visible, ancestors);
+                self.inline_only -= 1;
+                let label = collapse_whitespace(&std::mem::replace(&mut self.text, before));
+                if !label.is_empty() {
+                    let href =
+                        (self.resolve)(element.attributes.get("href").unwrap_or_default().trim());
+                    self.push_space_if_needed();
+                    self.text.push_str(&format!("[{label}]({href})"));
+                }
+            }
+            _ => self.children(element, style, visible, ancestors),
+        }
+    }
+
+    /// Pushes one row of cells; each cell's contents stay on one line.
+    fn table_row(
+        &mut self,
+        row: &'doc Element,
+        style: &ComputedStyle,
+        ancestors: &mut Vec<&'doc Element>,
+    ) {
+        let mut cells = Vec::new();
+        let mut header = true;
+        ancestors.push(row);
+        for child in &row.children {
+            let Node::Element(cell) = child else {
+                continue;
+            };
+            if !matches!(cell.name.as_str(), "td" | "th") {
+                continue;
+            }
+            let cell_style = self.styles.compute_style_in_viewport(
+                cell,
+                style,
+                ancestors,
+                self.viewport.width_px,
+                self.viewport.height_px,
+            );
+            if cell_style.display == Display::None {
+                continue;
+            }
+            header &= cell.name == "th";
+            self.inline_only += 1;
+            let visible = cell_style.visibility == Visibility::Visible;
+            self.children(cell, &cell_style, visible, ancestors);
+            self.inline_only -= 1;
+            cells.push(collapse_whitespace(&self.take_text()));
+        }
+        ancestors.pop();
+        if cells.iter().any(|cell| !cell.is_empty()) {
+            self.blocks.push(Block::TableRow { header, cells });
+        }
+    }
+
+    fn children(
+        &mut self,
+        element: &'doc Element,
+        style: &ComputedStyle,
+        visible: bool,
+        ancestors: &mut Vec<&'doc Element>,
+    ) {
+        ancestors.push(element);
+        for child in &element.children {
+            match child {
+                Node::Text(text) if visible => self.push_text(text),
+                Node::Text(_) => {}
+                Node::Element(child) => self.element(child, style, ancestors),
+            }
+        }
+        ancestors.pop();
+    }
+
+    /// Gathers the blocks `contents` adds as `context`, then ends them.
+    fn within(&mut self, context: Context, contents: impl FnOnce(&mut Self)) {
+        self.contexts.push(context);
+        contents(self);
+        self.flush();
+        self.contexts.pop();
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            if ch.is_whitespace() {
+                self.push_space();
+            } else {
+                self.text.push(ch);
+            }
+        }
+    }
+
+    fn push_space(&mut self) {
+        if !self.text.is_empty() && !self.text.ends_with([' ', '\n']) {
+            self.text.push(' ');
+        }
+    }
+
+    /// Separates a link or image from a word it would otherwise run into.
+    fn push_space_if_needed(&mut self) {
+        if self.text.ends_with(|ch: char| ch.is_alphanumeric()) {
+            self.text.push(' ');
+        }
+    }
+
+    fn take_text(&mut self) -> String {
+        let text = std::mem::take(&mut self.text);
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Ends the block being gathered, as whatever the innermost context makes it.
+    fn flush(&mut self) {
+        let text = self.take_text();
+        if text.is_empty() {
+            return;
+        }
+        let block = match self.contexts.last_mut() {
+            Some(Context::ListItem { depth, marker }) => Block::ListItem {
+                depth: *depth,
+                marker: std::mem::take(marker),
+                text,
+            },
+            Some(Context::Quote) => Block::Quote(text),
+            None => Block::Paragraph(text),
+        };
+        self.blocks.push(block);
+    }
+}
+
+fn collect_raw_text(element: &Element, out: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(child) if child.name == "br" => out.push('\n'),
+            Node::Element(child) => collect_raw_text(child, out),
+        }
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Block, blocks_to_text, extract_blocks};
+    use crate::render::Viewport;
+    use crate::style::StyleComputer;
+
+    #[test]
+    fn extracts_headings_lists_tables_and_links_as_markdown() {
+        let document = crate::html::parse_document(
+            r#"<html><head><title>T</title><style>.gone { display: none }</style></head><body>
+            <h1>Main <a href="/top">title</a></h1>
+            <p>Read   the <a href="docs.html">docs</a>, or<br>see <img src="/logo.png" alt="the logo">.</p>
+            <p class="gone">Hidden</p>
+            <ol start="3"><li>Three<ul><li>Nested</li></ul>after</li><li>Four</li></ol>
+            <table><tr><th>Name</th><th>Age</th></tr><tr><td>Ann <b>B</b></td><td>30</td></tr></table>
+            <blockquote><p>Quoted</p></blockquote>
+            <pre>  a
+  b</pre><hr><section><span style="visibility: hidden">no</span>yes</section>
+            </body></html>"#,
+        );
+        let styles = StyleComputer::from_document(&document);
+        let viewport = Viewport {
+            width_px: 800,
+            height_px: 600,
+        };
+        let resolve = |href: &str| format!("https://example.com/{}", href.trim_start_matches('/'));
+        let blocks = extract_blocks(&document, &styles, viewport, &resolve);
+
+        assert_eq!(
+            blocks[0],
+            Block::Heading {
+                level: 1,
+                text: "Main [title](https://example.com/top)".to_owned(),
+            }
+        );
+        assert_eq!(
+            blocks[1],
+            Block::Paragraph(
+                "Read the [docs](https://example.com/docs.html), or\nsee ![the logo](https://example.com/logo.png)."
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            blocks_to_text(&blocks[2..]),
+            "3. Three\n  - Nested\n  after\n4. Four\n\n| Name | Age |\n| --- | --- |\n| Ann B | 30 |\n\n> Quoted\n\n```\n  a\n  b\n```\n\n---\n\nyes\n"
+        );
+    }
+
+    #[test]
+    fn leaves_out_hidden_and_inert_subtrees() {
+        let document = crate::html::parse_document(
+            r#"<body><p hidden>Template</p><p>Kept <span aria-hidden="true">icon</span>text</p>
+            <nav inert><p>Menu</p></nav><div aria-hidden="false"><p>Shown</p></div></body>"#,
+        );
+        let styles = StyleComputer::from_document(&document);
+        let viewport = Viewport {
+            width_px: 800,
+            height_px: 600,
+        };
+        let blocks = extract_blocks(&document, &styles, viewport, &|href| href.to_owned());
+        assert_eq!(blocks_to_text(&blocks), "Kept text\n\nShown\n");
+    }
+}
@@ -0,0 +1,138 @@
+//! Fuzz targets. Each one accepts arbitrary bytes, runs a parser without touching the network,
+//! the filesystem or a display server, and must return without panicking; errors reported for
+//! malformed input are expected and discarded.
+//!
+//! A cargo-fuzz target forwards its input unchanged:
+//! `fuzz_target!(|data: &[u8]| one_agent_one_browser::fuzz::pipeline(data));`
+//! The `fuzz-replay` binary runs the same targets over files to reproduce a crash.
+
+use crate::css::Stylesheet;
+use crate::render::{FontMetricsPx, TextMeasurer, TextStyle, Viewport};
+use crate::resources::NoResources;
+use crate::style::StyleComputer;
+use crate::url::Url;
+
+pub type Target = fn(&[u8]);
+
+pub const TARGETS: &[(&str, Target)] = &[
+    ("html", html),
+    ("css", css),
+    ("image", image),
+    ("svg", svg),
+    ("url", url),
+    ("js", js),
+    ("pipeline", pipeline),
+];
+
+const PIPELINE_VIEWPORT: Viewport = Viewport {
+    width_px: 800,
+    height_px: 600,
+};
+
+pub fn target(name: &str) -> Option<Target> {
+    TARGETS
+        .iter()
+        .find(|(target_name, _)| *target_name == name)
+        .map(|(_, target)| *target)
+}
+
+pub fn html(data: &[u8]) {
+    let _ = crate::html::parse_document_bytes(data);
+}
+
+pub fn css(data: &[u8]) {
+    let source = String::from_utf8_lossy(data);
+    let _ = Stylesheet::parse(&source);
+    let _ = crate::css::parse_inline_declarations(&source);
+}
+
+/// Decodes images as the browser does, up to the platform codecs behind PNG, JPEG and WebP
+/// pixels, which are not run.
+pub fn image(data: &[u8]) {
+    crate::image::decode_for_fuzzing(data);
+}
+
+pub fn svg(data: &[u8]) {
+    let _ = crate::render::svg::rasterize(&String::from_utf8_lossy(data), 64, 64);
+}
+
+pub fn url(data: &[u8]) {
+    let input = String::from_utf8_lossy(data);
+    if let Ok(url) = Url::parse(&input) {
+        let _ = url.resolve("../a/./b?c#d");
+    }
+    if let Ok(base) = Url::parse("https://example.com/dir/page.html?q#f") {
+        let _ = base.resolve(&input);
+    }
+}
+
+/// Tokenizes and parses a script, and runs the pattern matchers used without the `js-engine`
+/// feature, without executing it.
+pub fn js(data: &[u8]) {
+    crate::js::parse_for_fuzzing(&String::from_utf8_lossy(data));
+}
+
+/// Parses, runs inline scripts, computes styles and lays out a document, with text measured
+/// by a fixed-advance font so results do not depend on installed fonts.
+pub fn pipeline(data: &[u8]) {
+    let mut document = crate::html::parse_document_bytes(data);
+    crate::js::execute_inline_scripts(&mut document);
+    let styles = StyleComputer::from_document(&document);
+    let _ = crate::layout::layout_document(
+        &document,
+        &styles,
+        &FixedAdvanceMeasurer,
+        PIPELINE_VIEWPORT,
+        &NoResources,
+    );
+}
+
+struct FixedAdvanceMeasurer;
+
+impl TextMeasurer for FixedAdvanceMeasurer {
+    fn font_metrics_px(&self, style: TextStyle) -> FontMetricsPx {
+        let size = style.font_size_px.max(0);
+        FontMetricsPx {
+            ascent_px: size.saturating_mul(4) / 5,
+            descent_px: size / 5,
+        }
+    }
+
+    fn text_width_px(&self, text: &str, style: TextStyle) -> Result<i32, String> {
+        let count = i32::try_from(text.chars().count()).unwrap_or(i32::MAX);
+        let advance = style.font_size_px.max(0) / 2;
+        Ok(count
+            .saturating_mul(advance)
+            .saturating_add(style.letter_spacing_total_px(text)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_survive_truncated_and_invalid_input() {
+        let inputs: [&[u8]; 8] = [
+            b"",
+            b"<",
+            b"<div style='width:",
+            b"<table><tr><td><p></table></b>",
+            b"a{color:#12;}}@media (",
+            &[0xff, 0xfe, b'<', 0xc3],
+            b"<table><tr><td colspan=4294967295>x</td></tr></table>",
+            "@supports (display:grid) a\u{20ac} (display:grid) {}".as_bytes(),
+        ];
+        for (_, target) in TARGETS {
+            for input in inputs {
+                target(input);
+            }
+        }
+    }
+
+    #[test]
+    fn looks_up_targets_by_name() {
+        assert!(target("pipeline").is_some());
+        assert!(target("nope").is_none());
+    }
+}
@@ -84,6 +84,40 @@ impl Rect {
         self.y.saturating_add(self.height)
     }
 
+    pub fn is_empty(self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// The overlap of the two rectangles, empty when they don't overlap.
+    pub fn intersection(self, other: Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Rect {
+            x,
+            y,
+            width: self.right().min(other.right()).saturating_sub(x).max(0),
+            height: self.bottom().min(other.bottom()).saturating_sub(y).max(0),
+        }
+    }
+
+    /// The smallest rectangle covering both; an empty rectangle adds nothing.
+    pub fn union(self, other: Rect) -> Rect {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return self;
+        }
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            width: self.right().max(other.right()).saturating_sub(x),
+            height: self.bottom().max(other.bottom()).saturating_sub(y),
+        }
+    }
+
     pub fn inset(self, edges: Edges) -> Rect {
         let x = self.x.saturating_add(edges.left);
         let y = self.y.saturating_add(edges.top);
@@ -1,10 +1,41 @@
-use crate::dom::{Attributes, Document, Element, Node};
+mod entities;
+mod tree;
+
+use crate::dom::{Attributes, Document, Element, Mutations};
+use entities::decode_character_references;
+use std::collections::HashMap;
+use tree::TreeBuilder;
 
 pub fn parse_document(source: &str) -> Document {
     let mut parser = Parser::new(source);
     parser.parse_document()
 }
 
+/// Parses a fetched response body; invalid UTF-8 sequences become U+FFFD. Only the first
+/// [`crate::net::ResourceLimits::max_html_bytes`] are read.
+pub fn parse_document_bytes(bytes: &[u8]) -> Document {
+    let len = parsed_len(bytes.len());
+    parse_document(&String::from_utf8_lossy(&bytes[..len]))
+}
+
+/// The start of `source` that gets parsed, cut at a character boundary once it is longer than
+/// [`crate::net::ResourceLimits::max_html_bytes`].
+pub fn within_size_limit(source: &str) -> &str {
+    &source[..source.floor_char_boundary(parsed_len(source.len()))]
+}
+
+fn parsed_len(len: usize) -> usize {
+    let limit = crate::net::resource_limits().max_html_bytes;
+    if len > limit && crate::debug::enabled(crate::debug::Target::Html, crate::debug::Level::Warn) {
+        crate::debug::log(
+            crate::debug::Target::Html,
+            crate::debug::Level::Warn,
+            format_args!("html! bytes={len} err=truncated limit={limit}"),
+        );
+    }
+    len.min(limit)
+}
+
 struct Parser<'a> {
     input: &'a str,
     cursor: usize,
@@ -16,105 +47,41 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_document(&mut self) -> Document {
-        let mut stack: Vec<Element> = vec![Element {
+        let mut tree = TreeBuilder::new(Element {
             name: "#document".to_owned(),
             attributes: Attributes::default(),
             children: Vec::new(),
-        }];
+        });
 
         while let Some(fragment) = self.next_fragment() {
             match fragment {
-                Fragment::Text(text) => {
-                    let text = decode_html_entities(&text);
-                    if !text.is_empty() {
-                        stack
-                            .last_mut()
-                            .expect("stack never empty")
-                            .children
-                            .push(Node::Text(text));
-                    }
-                }
+                Fragment::Text(text) => tree.text(decode_character_references(&text, false)),
                 Fragment::StartTag {
                     name,
                     attributes,
                     self_closing,
                 } => {
-                    if self_closing || is_void_element(&name) {
-                        stack
-                            .last_mut()
-                            .expect("stack never empty")
-                            .children
-                            .push(Node::Element(Element {
-                                name,
-                                attributes,
-                                children: Vec::new(),
-                            }));
-                        continue;
-                    }
-
-                    if is_raw_text_element(&name) {
-                        stack.push(Element {
-                            name: name.clone(),
-                            attributes,
-                            children: Vec::new(),
-                        });
-
+                    if is_raw_text_element(&name) && !self_closing {
+                        tree.start_tag(name.clone(), attributes, false);
                         let text = self.consume_raw_text_until_end_tag(&name);
-                        if !text.is_empty() {
-                            stack
-                                .last_mut()
-                                .expect("raw text element exists on stack")
-                                .children
-                                .push(Node::Text(text));
-                        }
-                        self.close_element(&mut stack, &name);
-                        continue;
+                        tree.text(text);
+                        tree.end_tag(&name);
                     } else {
-                        stack.push(Element {
-                            name,
-                            attributes,
-                            children: Vec::new(),
-                        });
+                        tree.start_tag(name, attributes, self_closing);
                     }
                 }
-                Fragment::EndTag { name } => {
-                    self.close_element(&mut stack, &name);
-                }
+                Fragment::EndTag { name } => tree.end_tag(&name),
             }
         }
 
-        while stack.len() > 1 {
-            self.close_top(&mut stack);
+        let root = tree.finish();
+        let base_href = first_base_href(&root);
+        Document {
+            root,
+            canvases: HashMap::new(),
+            mutations: Mutations::default(),
+            base_href,
         }
-
-        let root = stack.pop().expect("stack had root");
-
-        Document { root }
-    }
-
-    fn close_element(&self, stack: &mut Vec<Element>, name: &str) {
-        if stack.len() <= 1 {
-            return;
-        }
-
-        if let Some(index) = stack.iter().rposition(|el| el.name == name) {
-            while stack.len() - 1 >= index {
-                self.close_top(stack);
-            }
-        }
-    }
-
-    fn close_top(&self, stack: &mut Vec<Element>) {
-        if stack.len() <= 1 {
-            return;
-        }
-
-        let element = stack.pop().expect("len > 1 implies pop ok");
-        stack
-            .last_mut()
-            .expect("stack never empty")
-            .children
-            .push(Node::Element(element));
     }
 
     fn next_fragment(&mut self) -> Option<Fragment> {
@@ -281,30 +248,23 @@ fn normalize_tag_name(name: &str) -> String {
     name.trim().to_ascii_lowercase()
 }
 
-fn is_void_element(name: &str) -> bool {
-    matches!(
-        name,
-        "area"
-            | "base"
-            | "br"
-            | "col"
-            | "embed"
-            | "hr"
-            | "img"
-            | "input"
-            | "link"
-            | "meta"
-            | "param"
-            | "source"
-            | "track"
-            | "wbr"
-    )
-}
-
 fn is_raw_text_element(name: &str) -> bool {
     matches!(name, "style" | "script")
 }
 
+/// The `href` of the first `<base>` in `element` that has one.
+fn first_base_href(element: &Element) -> Option<String> {
+    if element.name == "base"
+        && let Some(href) = element.attributes.get("href")
+    {
+        return Some(href.trim().to_owned());
+    }
+    element.children.iter().find_map(|child| match child {
+        crate::dom::Node::Element(child) => first_base_href(child),
+        crate::dom::Node::Text(_) => None,
+    })
+}
+
 fn parse_attributes(mut input: &str) -> Attributes {
     let mut attrs = Attributes::default();
 
@@ -358,68 +318,17 @@ fn parse_attributes(mut input: &str) -> Attributes {
             }
         }
 
-        let value = decode_html_entities(&value);
+        let value = decode_character_references(&value, true);
         attrs.insert(name, value);
     }
 
     attrs
 }
 
-fn decode_html_entities(input: &str) -> String {
-    if !input.contains('&') {
-        return input.to_owned();
-    }
-
-    let mut out = String::with_capacity(input.len());
-    let mut rest = input;
-    while let Some(amp) = rest.find('&') {
-        out.push_str(&rest[..amp]);
-        rest = &rest[amp + 1..];
-
-        let Some(semi) = rest.find(';') else {
-            out.push('&');
-            out.push_str(rest);
-            return out;
-        };
-
-        let entity = &rest[..semi];
-        rest = &rest[semi + 1..];
-
-        match entity {
-            "lt" => out.push('<'),
-            "gt" => out.push('>'),
-            "amp" => out.push('&'),
-            "quot" => out.push('"'),
-            "apos" => out.push('\''),
-            "nbsp" => out.push('\u{00A0}'),
-            _ if entity.starts_with("#x") || entity.starts_with("#X") => {
-                if let Ok(value) = u32::from_str_radix(&entity[2..], 16) {
-                    if let Some(ch) = char::from_u32(value) {
-                        out.push(ch);
-                    }
-                }
-            }
-            _ if entity.starts_with('#') => {
-                if let Ok(value) = entity[1..].parse::<u32>() {
-                    if let Some(ch) = char::from_u32(value) {
-                        out.push(ch);
-                    }
-                }
-            }
-            _ => {
-                out.push('&');
-                out.push_str(entity);
-                out.push(';');
-            }
-        }
-    }
-    out.push_str(rest);
-    out
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dom::Node;
 
     #[test]
     fn parses_simple_inline_markup() {
@@ -439,6 +348,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn keeps_the_first_base_href() {
+        let doc = parse_document(
+            "<head><base target=_blank><base href=' /docs/ '><base href=/other/></head><p>x</p>",
+        );
+        assert_eq!(doc.base_href.as_deref(), Some("/docs/"));
+        assert_eq!(parse_document("<p>x</p>").base_href, None);
+    }
+
+    #[test]
+    fn cuts_documents_past_the_size_limit_at_a_character_boundary() {
+        let limit = crate::net::ResourceLimits::new().max_html_bytes;
+        let source = format!("<p>{}", "é".repeat(limit / 2));
+        let kept = within_size_limit(&source);
+        assert!(kept.len() <= limit && kept.len() > limit - 2);
+        assert!(kept.starts_with("<p>é"));
+        assert_eq!(within_size_limit("<p>x</p>"), "<p>x</p>");
+    }
+
     #[test]
     fn treats_void_elements_as_self_closing() {
         let doc = parse_document("<p>hi<br>there</p>");
@@ -471,4 +399,85 @@ mod tests {
         assert!(doc.find_first_element_by_name("body").is_some());
         assert!(doc.find_first_element_by_name("p").is_some());
     }
+
+    fn markup(nodes: &[Node]) -> String {
+        let mut out = String::new();
+        for node in nodes {
+            match node {
+                Node::Text(text) => out.push_str(text),
+                Node::Element(element) => {
+                    out.push_str(&format!("<{}>", element.name));
+                    out.push_str(&markup(&element.children));
+                    out.push_str(&format!("</{}>", element.name));
+                }
+            }
+        }
+        out
+    }
+
+    fn reparse(source: &str) -> String {
+        markup(&parse_document(source).root.children)
+    }
+
+    #[test]
+    fn closes_paragraphs_and_list_items_implicitly() {
+        assert_eq!(
+            reparse("<p>one<p>two<div>three</div>"),
+            "<p>one</p><p>two</p><div>three</div>"
+        );
+        assert_eq!(
+            reparse("<ul><li>a<li>b</ul><dl><dt>t<dd>d</dl>"),
+            "<ul><li>a</li><li>b</li></ul><dl><dt>t</dt><dd>d</dd></dl>"
+        );
+        assert_eq!(reparse("x</p>"), "x<p></p>");
+    }
+
+    #[test]
+    fn repairs_misnested_formatting_elements() {
+        assert_eq!(reparse("<b><i>x</b>y</i>"), "<b><i>x</i></b><i>y</i>");
+        assert_eq!(reparse("<b>1<p>2</b>3</p>"), "<b>1</b><p><b>2</b>3</p>");
+        assert_eq!(reparse("<p><b>x</p>y"), "<p><b>x</b></p><b>y</b>");
+    }
+
+    #[test]
+    fn caps_repeated_formatting_elements() {
+        fn count(nodes: &[Node]) -> usize {
+            nodes
+                .iter()
+                .map(|node| match node {
+                    Node::Element(element) => 1 + count(&element.children),
+                    Node::Text(_) => 1,
+                })
+                .sum()
+        }
+
+        assert_eq!(
+            reparse("<p><b><b><b><b>x</p>y"),
+            "<p><b><b><b><b>x</b></b></b></b></p><b><b><b>y</b></b></b>"
+        );
+        // The 6000 elements opened first nest that deep whatever the cap, which takes more
+        // than a test thread's default stack to build and drop.
+        let nodes = std::thread::Builder::new()
+            .stack_size(64 << 20)
+            .spawn(|| {
+                let source = format!("{}{}", "<b><i>".repeat(3000), "</b>x".repeat(3000));
+                count(&parse_document(&source).root.children)
+            })
+            .expect("spawn parser thread")
+            .join()
+            .expect("parse finishes");
+        assert!(nodes < 20_000, "{nodes} nodes");
+    }
+
+    #[test]
+    fn implies_table_sections_and_foster_parents_stray_content() {
+        assert_eq!(
+            reparse("<table><tr><td>a<td>b</table>"),
+            "<table><tbody><tr><td>a</td><td>b</td></tr></tbody></table>"
+        );
+        assert_eq!(
+            reparse("<table>x<div>y</div><td>z</table>"),
+            "x<div>y</div><table><tbody><tr><td>z</td></tr></tbody></table>"
+        );
+    }
 }
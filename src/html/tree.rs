@@ -0,0 +1,786 @@
+use crate::dom::{Attributes, Element, Node};
+
+/// Builds the element tree from tokens with the common parts of the HTML5 tree construction
+/// rules: implied end tags, element scopes, the adoption agency for misnested formatting
+/// elements, implied table sections and foster parenting.
+pub(super) struct TreeBuilder {
+    stack: Vec<OpenElement>,
+    /// Formatting elements that get reopened after being closed implicitly. `None` is a marker
+    /// pushed by table cells and captions so formatting never leaks out of them.
+    active_formatting: Vec<Option<FormattingEntry>>,
+    next_id: usize,
+}
+
+struct OpenElement {
+    element: Element,
+    id: usize,
+    /// Moved in front of the enclosing table when closed, instead of into the table.
+    fostered: bool,
+}
+
+struct FormattingEntry {
+    /// The [`OpenElement::id`] this entry was last opened as.
+    id: usize,
+    name: String,
+    attributes: Attributes,
+}
+
+#[derive(Clone, Copy)]
+enum Scope {
+    Default,
+    ListItem,
+    Button,
+    Table,
+}
+
+/// How many times one end tag may restructure the tree, as in the HTML standard.
+const ADOPTION_AGENCY_ROUNDS: usize = 8;
+
+impl TreeBuilder {
+    pub(super) fn new(root: Element) -> Self {
+        Self {
+            stack: vec![OpenElement {
+                element: root,
+                id: 0,
+                fostered: false,
+            }],
+            active_formatting: Vec::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Closes everything still open and returns the root.
+    pub(super) fn finish(mut self) -> Element {
+        while self.stack.len() > 1 {
+            self.pop();
+        }
+        self.stack.pop().expect("stack had root").element
+    }
+
+    pub(super) fn text(&mut self, text: String) {
+        if text.is_empty() {
+            return;
+        }
+        let whitespace = text.chars().all(|ch| ch.is_ascii_whitespace());
+        if whitespace && self.current_is_table_section() {
+            self.current_mut().children.push(Node::Text(text));
+            return;
+        }
+        self.reconstruct_active_formatting();
+        if self.current_is_table_section() {
+            self.foster_parent_mut().children.push(Node::Text(text));
+        } else {
+            self.current_mut().children.push(Node::Text(text));
+        }
+    }
+
+    pub(super) fn start_tag(&mut self, name: String, attributes: Attributes, self_closing: bool) {
+        let name = name.as_str();
+        if closes_paragraph(name) && self.in_scope(&["p"], Scope::Button) {
+            self.close_paragraph();
+        }
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" if is_heading(self.current_name()) => {
+                self.pop()
+            }
+            "li" => self.close_list_item(&["li"]),
+            "dd" | "dt" => self.close_list_item(&["dd", "dt"]),
+            "button" if self.in_scope(&["button"], Scope::Default) => self.pop_until(&["button"]),
+            "option" | "optgroup" if self.current_name() == "option" => self.pop(),
+            "a" => {
+                let open_link = self
+                    .formatting_after_marker()
+                    .rev()
+                    .find(|(_, entry)| entry.name == "a")
+                    .map(|(_, entry)| entry.id);
+                if let Some(id) = open_link {
+                    self.adoption_agency("a");
+                    self.active_formatting
+                        .retain(|entry| entry.as_ref().is_none_or(|entry| entry.id != id));
+                }
+            }
+            "table" if self.current_is_table_section() => self.pop_until(&["table"]),
+            "caption" | "colgroup" | "tbody" | "thead" | "tfoot"
+                if self.in_scope(&["table"], Scope::Table) =>
+            {
+                self.clear_to(&["table"]);
+            }
+            "col" if self.in_scope(&["table"], Scope::Table) => {
+                self.clear_to(&["table", "colgroup"]);
+                if self.current_name() == "table" {
+                    self.push_element(implied("colgroup"));
+                }
+            }
+            "tr" => {
+                if self.in_scope(&["tr"], Scope::Table) {
+                    self.pop_until(&["tr"]);
+                }
+                if self.in_scope(&["table"], Scope::Table) {
+                    self.clear_to(&["table", "tbody", "thead", "tfoot"]);
+                    if self.current_name() == "table" {
+                        self.push_element(implied("tbody"));
+                    }
+                }
+            }
+            "td" | "th" => {
+                if self.in_scope(&["td", "th"], Scope::Table) {
+                    self.pop_until(&["td", "th"]);
+                }
+                if self.in_scope(&["table"], Scope::Table) {
+                    self.clear_to(&["table", "tbody", "thead", "tfoot", "tr"]);
+                    if self.current_name() == "table" {
+                        self.push_element(implied("tbody"));
+                    }
+                    if self.current_name() != "tr" {
+                        self.push_element(implied("tr"));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if reconstructs_formatting(name) {
+            self.reconstruct_active_formatting();
+        }
+
+        let element = Element {
+            name: name.to_owned(),
+            attributes,
+            children: Vec::new(),
+        };
+        if self_closing || is_void_element(name) {
+            self.push_element(element);
+            self.pop();
+            return;
+        }
+
+        let entry = is_formatting(name).then(|| FormattingEntry {
+            id: 0,
+            name: element.name.clone(),
+            attributes: element.attributes.clone(),
+        });
+        let id = self.push_element(element);
+        if let Some(mut entry) = entry {
+            entry.id = id;
+            self.push_active_formatting(entry);
+        }
+        if is_formatting_boundary(name) {
+            self.active_formatting.push(None);
+        }
+    }
+
+    pub(super) fn end_tag(&mut self, name: &str) {
+        match name {
+            "p" => {
+                if !self.in_scope(&["p"], Scope::Button) {
+                    self.push_element(implied("p"));
+                }
+                self.close_paragraph();
+            }
+            "li" => {
+                if self.in_scope(&["li"], Scope::ListItem) {
+                    self.generate_implied_end_tags(Some("li"));
+                    self.pop_until(&["li"]);
+                }
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                const HEADINGS: &[&str] = &["h1", "h2", "h3", "h4", "h5", "h6"];
+                if self.in_scope(HEADINGS, Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(HEADINGS);
+                }
+            }
+            "table" | "tbody" | "thead" | "tfoot" | "tr" | "td" | "th" | "caption" => {
+                if self.in_scope(&[name], Scope::Table) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&[name]);
+                }
+            }
+            "br" => self.start_tag("br".to_owned(), Attributes::default(), true),
+            "body" | "html" => {}
+            "head" => {
+                if self.stack.iter().any(|open| open.element.name == "head") {
+                    self.pop_until(&["head"]);
+                }
+            }
+            _ if is_formatting(name) => self.adoption_agency(name),
+            _ if is_special(name) => {
+                if self.in_scope(&[name], Scope::Default) {
+                    self.generate_implied_end_tags(None);
+                    self.pop_until(&[name]);
+                }
+            }
+            _ => self.close_any_other(name),
+        }
+    }
+
+    fn current_mut(&mut self) -> &mut Element {
+        &mut self.stack.last_mut().expect("stack never empty").element
+    }
+
+    fn current_name(&self) -> &str {
+        &self.stack.last().expect("stack never empty").element.name
+    }
+
+    fn current_is_table_section(&self) -> bool {
+        matches!(
+            self.current_name(),
+            "table" | "tbody" | "thead" | "tfoot" | "tr"
+        )
+    }
+
+    /// The element that content misplaced inside a table goes into: the table's parent.
+    fn foster_parent_mut(&mut self) -> &mut Element {
+        let parent = match self
+            .stack
+            .iter()
+            .rposition(|open| open.element.name == "table")
+        {
+            Some(table) => table.saturating_sub(1),
+            None => self.stack.len() - 1,
+        };
+        &mut self.stack[parent].element
+    }
+
+    fn push_element(&mut self, element: Element) -> usize {
+        let fostered = self.current_is_table_section() && !is_table_content(&element.name);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.stack.push(OpenElement {
+            element,
+            id,
+            fostered,
+        });
+        id
+    }
+
+    /// Closes the current element into its parent, or in front of the table it was
+    /// misplaced in.
+    fn pop(&mut self) {
+        if self.stack.len() <= 1 {
+            return;
+        }
+        let open = self.stack.pop().expect("len > 1 implies pop ok");
+        if is_formatting_boundary(&open.element.name) {
+            self.clear_active_formatting_to_marker();
+        }
+        let parent = if open.fostered {
+            self.foster_parent_mut()
+        } else {
+            self.current_mut()
+        };
+        parent.children.push(Node::Element(open.element));
+    }
+
+    /// Pops elements until one named in `names` has been popped.
+    fn pop_until(&mut self, names: &[&str]) {
+        while self.stack.len() > 1 {
+            let done = names.contains(&self.current_name());
+            self.pop();
+            if done {
+                break;
+            }
+        }
+    }
+
+    /// Pops elements until the current one is named in `names`, or is the root.
+    fn clear_to(&mut self, names: &[&str]) {
+        while self.stack.len() > 1 && !names.contains(&self.current_name()) {
+            self.pop();
+        }
+    }
+
+    fn in_scope(&self, names: &[&str], scope: Scope) -> bool {
+        for open in self.stack.iter().rev() {
+            let name = open.element.name.as_str();
+            if names.contains(&name) {
+                return true;
+            }
+            let boundary = match scope {
+                Scope::Table => matches!(name, "html" | "table" | "template"),
+                Scope::Default => is_scope_boundary(name),
+                Scope::ListItem => is_scope_boundary(name) || matches!(name, "ol" | "ul"),
+                Scope::Button => is_scope_boundary(name) || name == "button",
+            };
+            if boundary || open.id == 0 {
+                return false;
+            }
+        }
+        false
+    }
+
+    fn generate_implied_end_tags(&mut self, except: Option<&str>) {
+        while self.stack.len() > 1 {
+            let name = self.current_name();
+            if Some(name) == except || !has_implied_end_tag(name) {
+                break;
+            }
+            self.pop();
+        }
+    }
+
+    fn close_paragraph(&mut self) {
+        self.generate_implied_end_tags(Some("p"));
+        self.pop_until(&["p"]);
+    }
+
+    /// A new `li`, `dd` or `dt` closes the previous item of its list.
+    fn close_list_item(&mut self, names: &[&str]) {
+        for index in (1..self.stack.len()).rev() {
+            let name = self.stack[index].element.name.as_str();
+            if names.contains(&name) {
+                while self.stack.len() > index {
+                    self.pop();
+                }
+                break;
+            }
+            if is_special(name) && !matches!(name, "address" | "div" | "p") {
+                break;
+            }
+        }
+        if self.in_scope(&["p"], Scope::Button) {
+            self.close_paragraph();
+        }
+    }
+
+    /// Closes the innermost open element called `name` unless a special element is in the way.
+    fn close_any_other(&mut self, name: &str) {
+        for index in (1..self.stack.len()).rev() {
+            let open_name = self.stack[index].element.name.as_str();
+            if open_name == name {
+                self.generate_implied_end_tags(Some(name));
+                while self.stack.len() > index {
+                    self.pop();
+                }
+                return;
+            }
+            if is_special(open_name) {
+                return;
+            }
+        }
+    }
+
+    fn formatting_after_marker(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = (usize, &FormattingEntry)> {
+        let start = self
+            .active_formatting
+            .iter()
+            .rposition(Option::is_none)
+            .map_or(0, |marker| marker + 1);
+        self.active_formatting[start..]
+            .iter()
+            .enumerate()
+            .filter_map(move |(offset, entry)| Some((start + offset, entry.as_ref()?)))
+    }
+
+    /// Adds `entry` after applying the standard's "Noah's Ark" clause: past the last marker at
+    /// most three entries share a name and attributes, so repeated unclosed tags can't make
+    /// every later text run reopen thousands of elements.
+    fn push_active_formatting(&mut self, entry: FormattingEntry) {
+        let same: Vec<usize> = self
+            .formatting_after_marker()
+            .filter(|(_, existing)| {
+                existing.name == entry.name && existing.attributes == entry.attributes
+            })
+            .map(|(index, _)| index)
+            .collect();
+        if same.len() >= 3 {
+            self.active_formatting.remove(same[0]);
+        }
+        self.active_formatting.push(Some(entry));
+    }
+
+    fn clear_active_formatting_to_marker(&mut self) {
+        while let Some(entry) = self.active_formatting.pop() {
+            if entry.is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Reopens formatting elements that were closed implicitly, so `<p><b>x</p>y` keeps `y`
+    /// bold.
+    fn reconstruct_active_formatting(&mut self) {
+        let mut first = self.active_formatting.len();
+        while first > 0 {
+            match &self.active_formatting[first - 1] {
+                Some(entry) if !self.is_open(entry.id) => first -= 1,
+                _ => break,
+            }
+        }
+        for index in first..self.active_formatting.len() {
+            let Some(entry) = &self.active_formatting[index] else {
+                continue;
+            };
+            let element = Element {
+                name: entry.name.clone(),
+                attributes: entry.attributes.clone(),
+                children: Vec::new(),
+            };
+            let id = self.push_element(element);
+            if let Some(entry) = &mut self.active_formatting[index] {
+                entry.id = id;
+            }
+        }
+    }
+
+    fn is_open(&self, id: usize) -> bool {
+        self.stack.iter().any(|open| open.id == id)
+    }
+
+    /// The adoption agency algorithm: an end tag for a formatting element that has block
+    /// elements opened inside it closes the formatting element where it was opened and
+    /// re-wraps the blocks' contents in copies of it.
+    fn adoption_agency(&mut self, name: &str) {
+        for _ in 0..ADOPTION_AGENCY_ROUNDS {
+            let Some((entry_index, entry_id, attributes)) = self
+                .formatting_after_marker()
+                .rev()
+                .find(|(_, entry)| entry.name == name)
+                .map(|(index, entry)| (index, entry.id, entry.attributes.clone()))
+            else {
+                self.close_any_other(name);
+                return;
+            };
+            let Some(formatting_index) = self.stack.iter().position(|open| open.id == entry_id)
+            else {
+                self.active_formatting.remove(entry_index);
+                return;
+            };
+            if !self.in_scope(&[name], Scope::Default) {
+                return;
+            }
+
+            let Some(furthest_block) = (formatting_index + 1..self.stack.len())
+                .find(|&index| is_special(&self.stack[index].element.name))
+            else {
+                while self.stack.len() > formatting_index {
+                    self.pop();
+                }
+                self.active_formatting.remove(entry_index);
+                return;
+            };
+
+            // Reopen the formatting elements between the two around the block, since the
+            // originals are about to close along with the element being ended.
+            let mut reopened = Vec::new();
+            for open in &self.stack[formatting_index + 1..furthest_block] {
+                let Some(entry_index) = self
+                    .active_formatting
+                    .iter()
+                    .position(|entry| entry.as_ref().is_some_and(|entry| entry.id == open.id))
+                else {
+                    continue;
+                };
+                reopened.push((
+                    entry_index,
+                    open.element.name.clone(),
+                    open.element.attributes.clone(),
+                ));
+            }
+
+            let mut closing: Vec<OpenElement> =
+                self.stack.drain(formatting_index..furthest_block).collect();
+            let fostered = closing[0].fostered;
+            let mut element = closing
+                .pop()
+                .expect("range holds the formatting element")
+                .element;
+            while let Some(mut parent) = closing.pop() {
+                parent.element.children.push(Node::Element(element));
+                element = parent.element;
+            }
+            let parent = if fostered {
+                self.foster_parent_mut()
+            } else {
+                &mut self.stack[formatting_index - 1].element
+            };
+            parent.children.push(Node::Element(element));
+
+            let mut block_index = formatting_index;
+            for (entry_index, name, attributes) in reopened {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.stack.insert(
+                    block_index,
+                    OpenElement {
+                        element: Element {
+                            name,
+                            attributes,
+                            children: Vec::new(),
+                        },
+                        id,
+                        fostered: false,
+                    },
+                );
+                if let Some(entry) = &mut self.active_formatting[entry_index] {
+                    entry.id = id;
+                }
+                block_index += 1;
+            }
+
+            let block = &mut self.stack[block_index].element;
+            let wrapped = Element {
+                name: name.to_owned(),
+                attributes,
+                children: std::mem::take(&mut block.children),
+            };
+            let id = self.next_id;
+            self.next_id += 1;
+            self.stack.insert(
+                block_index + 1,
+                OpenElement {
+                    element: wrapped,
+                    id,
+                    fostered: false,
+                },
+            );
+            if let Some(entry) = &mut self.active_formatting[entry_index] {
+                entry.id = id;
+            }
+        }
+    }
+}
+
+fn implied(name: &str) -> Element {
+    Element {
+        name: name.to_owned(),
+        attributes: Attributes::default(),
+        children: Vec::new(),
+    }
+}
+
+pub(super) fn is_void_element(name: &str) -> bool {
+    matches!(
+        name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+fn is_heading(name: &str) -> bool {
+    matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6")
+}
+
+fn is_formatting(name: &str) -> bool {
+    matches!(
+        name,
+        "a" | "b"
+            | "big"
+            | "code"
+            | "em"
+            | "font"
+            | "i"
+            | "nobr"
+            | "s"
+            | "small"
+            | "strike"
+            | "strong"
+            | "tt"
+            | "u"
+    )
+}
+
+/// Elements that formatting opened outside of them does not leak into.
+fn is_formatting_boundary(name: &str) -> bool {
+    matches!(
+        name,
+        "applet" | "caption" | "marquee" | "object" | "td" | "th" | "template"
+    )
+}
+
+fn is_scope_boundary(name: &str) -> bool {
+    matches!(
+        name,
+        "applet" | "caption" | "html" | "marquee" | "object" | "table" | "td" | "th" | "template"
+    )
+}
+
+fn has_implied_end_tag(name: &str) -> bool {
+    matches!(
+        name,
+        "dd" | "dt" | "li" | "optgroup" | "option" | "p" | "rb" | "rp" | "rt" | "rtc"
+    )
+}
+
+/// Elements allowed directly inside tables; anything else gets foster-parented.
+fn is_table_content(name: &str) -> bool {
+    matches!(
+        name,
+        "caption"
+            | "col"
+            | "colgroup"
+            | "tbody"
+            | "td"
+            | "tfoot"
+            | "th"
+            | "thead"
+            | "tr"
+            | "script"
+            | "style"
+            | "template"
+            | "form"
+            | "input"
+    )
+}
+
+/// Start tags that end an open `<p>`.
+fn closes_paragraph(name: &str) -> bool {
+    is_heading(name)
+        || matches!(
+            name,
+            "address"
+                | "article"
+                | "aside"
+                | "blockquote"
+                | "center"
+                | "details"
+                | "dialog"
+                | "dir"
+                | "div"
+                | "dl"
+                | "fieldset"
+                | "figcaption"
+                | "figure"
+                | "footer"
+                | "form"
+                | "header"
+                | "hgroup"
+                | "hr"
+                | "listing"
+                | "main"
+                | "menu"
+                | "nav"
+                | "ol"
+                | "p"
+                | "plaintext"
+                | "pre"
+                | "search"
+                | "section"
+                | "summary"
+                | "table"
+                | "ul"
+                | "xmp"
+        )
+}
+
+fn reconstructs_formatting(name: &str) -> bool {
+    !is_special(name)
+        || matches!(
+            name,
+            "applet"
+                | "area"
+                | "br"
+                | "button"
+                | "embed"
+                | "iframe"
+                | "img"
+                | "input"
+                | "keygen"
+                | "marquee"
+                | "object"
+                | "select"
+                | "textarea"
+                | "wbr"
+                | "xmp"
+        )
+}
+
+/// The HTML standard's "special" category: elements that end tags for unrelated elements do
+/// not reach past.
+fn is_special(name: &str) -> bool {
+    is_heading(name)
+        || matches!(
+            name,
+            "address"
+                | "applet"
+                | "area"
+                | "article"
+                | "aside"
+                | "base"
+                | "basefont"
+                | "bgsound"
+                | "blockquote"
+                | "body"
+                | "br"
+                | "button"
+                | "caption"
+                | "center"
+                | "col"
+                | "colgroup"
+                | "dd"
+                | "details"
+                | "dir"
+                | "div"
+                | "dl"
+                | "dt"
+                | "embed"
+                | "fieldset"
+                | "figcaption"
+                | "figure"
+                | "footer"
+                | "form"
+                | "frame"
+                | "frameset"
+                | "head"
+                | "header"
+                | "hgroup"
+                | "hr"
+                | "html"
+                | "iframe"
+                | "img"
+                | "input"
+                | "keygen"
+                | "li"
+                | "link"
+                | "listing"
+                | "main"
+                | "marquee"
+                | "menu"
+                | "meta"
+                | "nav"
+                | "noembed"
+                | "noframes"
+                | "noscript"
+                | "object"
+                | "ol"
+                | "p"
+                | "param"
+                | "plaintext"
+                | "pre"
+                | "script"
+                | "search"
+                | "section"
+                | "select"
+                | "source"
+                | "style"
+                | "summary"
+                | "table"
+                | "tbody"
+                | "td"
+                | "template"
+                | "textarea"
+                | "tfoot"
+                | "th"
+                | "thead"
+                | "title"
+                | "tr"
+                | "track"
+                | "ul"
+                | "wbr"
+                | "xmp"
+        )
+}
@@ -64,45 +64,268 @@ impl Argb32Image {
     }
 }
 
-pub fn decode_image(data: &[u8]) -> Result<Argb32Image, String> {
+/// Images larger than this on either side are rejected before decoding, unless
+/// [`crate::net::ResourceLimits`] says otherwise.
+pub const MAX_IMAGE_DIMENSION: u32 = 16_384;
+/// Caps the decoded ARGB buffer at 256 MiB by default.
+pub const MAX_IMAGE_PIXELS: u64 = 64 * 1024 * 1024;
+/// Deepest element nesting accepted in an SVG document.
+pub const MAX_SVG_DEPTH: usize = 256;
+/// How much of a resource is searched for the root element when sniffing for SVG.
+const SVG_SNIFF_BYTES: usize = 16 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImageError {
+    /// The header declares a size over the configured image limits, [`MAX_IMAGE_DIMENSION`] and
+    /// [`MAX_IMAGE_PIXELS`] by default.
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+    /// Elements nest deeper than [`MAX_SVG_DEPTH`].
+    SvgTooDeep {
+        depth: usize,
+    },
+    /// The SVG declares XML entities, which can expand exponentially.
+    SvgEntities,
+    Unsupported,
+    Decode(String),
+}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::TooLarge { width, height } => {
+                write!(f, "Image dimensions {width}x{height} exceed the limit")
+            }
+            ImageError::SvgTooDeep { depth } => {
+                write!(f, "SVG nesting depth {depth} exceeds the limit")
+            }
+            ImageError::SvgEntities => f.write_str("SVG declares XML entities"),
+            ImageError::Unsupported => f.write_str("Unsupported image format"),
+            ImageError::Decode(err) => f.write_str(err),
+        }
+    }
+}
+
+/// Decodes one format's pixels to premultiplied ARGB.
+type Codec = fn(&[u8]) -> Result<Argb32Image, String>;
+
+/// The codecs [`decode_image`] hands each format's pixels to; the crate decodes icon
+/// directories itself.
+struct Codecs {
+    png: Codec,
+    jpeg: Codec,
+    webp: Codec,
+}
+
+const PLATFORM_CODECS: Codecs = Codecs {
+    png: decode_png_argb32,
+    jpeg: decode_jpeg_argb32,
+    webp: decode_webp_argb32,
+};
+
+pub fn decode_image(data: &[u8]) -> Result<Argb32Image, ImageError> {
+    decode_with(data, &PLATFORM_CODECS)
+}
+
+/// Runs [`decode_image`] over `data` for the `image` fuzz target, with every step the crate
+/// does itself (header sizes and limits, format sniffing, icon directories and their DIB
+/// entries) but without the platform codecs, which the target does not link.
+#[cfg(feature = "fuzzing")]
+pub fn decode_for_fuzzing(data: &[u8]) {
+    fn no_codec(_: &[u8]) -> Result<Argb32Image, String> {
+        Err("No platform codecs in the fuzz target".to_owned())
+    }
+    let _ = looks_like_supported_image(data);
+    let _ = decode_with(
+        data,
+        &Codecs {
+            png: no_codec,
+            jpeg: no_codec,
+            webp: no_codec,
+        },
+    );
+}
+
+fn decode_with(data: &[u8], codecs: &Codecs) -> Result<Argb32Image, ImageError> {
+    if let Some((width, height)) = image_dimensions(data) {
+        check_image_dimensions(width, height)?;
+    }
     if looks_like_webp(data) {
-        return decode_webp_argb32(data);
+        return (codecs.webp)(data).map_err(ImageError::Decode);
+    }
+    if looks_like_png(data) {
+        return (codecs.png)(data).map_err(ImageError::Decode);
+    }
+    if looks_like_jpeg(data) {
+        return (codecs.jpeg)(data).map_err(ImageError::Decode);
+    }
+    if looks_like_ico(data) {
+        return decode_ico_argb32(data, |entry| decode_with(entry, codecs));
+    }
+    Err(ImageError::Unsupported)
+}
+
+pub fn check_image_dimensions(width: u32, height: u32) -> Result<(), ImageError> {
+    let limits = crate::net::resource_limits();
+    if width > limits.max_image_dimension
+        || height > limits.max_image_dimension
+        || u64::from(width) * u64::from(height) > limits.max_image_pixels
+    {
+        return Err(ImageError::TooLarge { width, height });
     }
+    Ok(())
+}
+
+/// How many bytes `data` takes once decoded to ARGB, read from its header; `None` when the
+/// header doesn't say.
+pub fn decoded_size_bytes(data: &[u8]) -> Option<u64> {
+    let (width, height) = image_dimensions(data)?;
+    Some(u64::from(width) * u64::from(height) * 4)
+}
+
+/// Reads the pixel size from a PNG, JPEG or WebP header without decoding.
+fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be32 = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+    let be16 = |at: usize| {
+        Some(u32::from(u16::from_be_bytes(
+            data.get(at..at + 2)?.try_into().ok()?,
+        )))
+    };
+    let le16 = |at: usize| {
+        Some(u32::from(u16::from_le_bytes(
+            data.get(at..at + 2)?.try_into().ok()?,
+        )))
+    };
+    let le24 = |at: usize| {
+        let bytes = data.get(at..at + 3)?;
+        Some(u32::from(bytes[0]) | u32::from(bytes[1]) << 8 | u32::from(bytes[2]) << 16)
+    };
+
     if looks_like_png(data) {
-        return decode_png_argb32(data);
+        if data.get(12..16)? != b"IHDR" {
+            return None;
+        }
+        return Some((be32(16)?, be32(20)?));
+    }
+
+    if looks_like_webp(data) {
+        return match data.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let bits = u32::from_le_bytes(data.get(21..25)?.try_into().ok()?);
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
     }
+
     if looks_like_jpeg(data) {
-        return decode_jpeg_argb32(data);
+        let mut at = 2;
+        loop {
+            while *data.get(at)? != 0xff {
+                at += 1;
+            }
+            while *data.get(at)? == 0xff {
+                at += 1;
+            }
+            let marker = *data.get(at)?;
+            at += 1;
+            if matches!(marker, 0x01 | 0xd0..=0xd7) {
+                continue;
+            }
+            if marker == 0xd9 || marker == 0xda {
+                return None;
+            }
+            let len = be16(at)? as usize;
+            if matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc) {
+                return Some((be16(at + 5)?, be16(at + 3)?));
+            }
+            at += len;
+        }
     }
-    Err("Unsupported image format".to_owned())
+
+    None
+}
+
+/// Rejects SVG documents that could exhaust memory in the platform renderer: entity
+/// declarations (the "billion laughs" expansion) and element nesting past [`MAX_SVG_DEPTH`].
+pub fn check_svg_document(data: &[u8]) -> Result<(), ImageError> {
+    let text = String::from_utf8_lossy(data);
+    if text.contains("<!ENTITY") {
+        return Err(ImageError::SvgEntities);
+    }
+
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut at = 0;
+    while let Some(offset) = bytes[at..].iter().position(|&b| b == b'<') {
+        let start = at + offset;
+        let rest = &text[start..];
+        let (skip_to, delta): (&str, isize) = if rest.starts_with("<!--") {
+            ("-->", 0)
+        } else if rest.starts_with("<![CDATA[") {
+            ("]]>", 0)
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            (">", 0)
+        } else if rest.starts_with("</") {
+            (">", -1)
+        } else {
+            (">", 1)
+        };
+        let Some(end) = rest.find(skip_to) else {
+            break;
+        };
+        let end = start + end + skip_to.len();
+        let self_closing = delta == 1 && text[..end].ends_with("/>");
+        if delta == 1 && !self_closing {
+            depth += 1;
+            if depth > MAX_SVG_DEPTH {
+                return Err(ImageError::SvgTooDeep { depth });
+            }
+        } else if delta == -1 {
+            depth = depth.saturating_sub(1);
+        }
+        at = end;
+    }
+    Ok(())
 }
 
 pub fn looks_like_supported_image(data: &[u8]) -> bool {
     looks_like_webp(data)
         || looks_like_png(data)
         || looks_like_jpeg(data)
+        || looks_like_ico(data)
         || looks_like_svg_document(data)
 }
 
+/// Sniffs for an SVG root element after the XML prolog: the declaration, comments, processing
+/// instructions and an SVG doctype may precede it.
 pub fn looks_like_svg_document(data: &[u8]) -> bool {
-    let text = String::from_utf8_lossy(data);
-    let trimmed = text
+    let text = String::from_utf8_lossy(&data[..data.len().min(SVG_SNIFF_BYTES)]);
+    let mut rest = text
         .trim_start_matches(|ch: char| ch.is_ascii_whitespace() || ch == '\u{feff}' || ch == '\0');
-    if !trimmed.starts_with('<') {
-        return false;
-    }
 
-    let mut search_window = &trimmed[..trimmed.len().min(2048)];
-    if let Some(after_xml_decl) = search_window
-        .strip_prefix("<?xml")
-        .and_then(|rest| rest.find("?>").map(|idx| &rest[idx + 2..]))
-    {
-        search_window = after_xml_decl.trim_start_matches(char::is_whitespace);
+    loop {
+        rest = rest.trim_start_matches(char::is_whitespace);
+        let terminator = if rest.starts_with("<?") {
+            "?>"
+        } else if rest.starts_with("<!--") {
+            "-->"
+        } else {
+            break;
+        };
+        match rest.find(terminator) {
+            Some(end) => rest = &rest[end + terminator.len()..],
+            None => return false,
+        }
     }
 
-    search_window.starts_with("<svg")
-        || search_window.starts_with("<!DOCTYPE svg")
-        || search_window.starts_with("<!doctype svg")
+    rest.starts_with("<svg")
+        || rest.starts_with("<!DOCTYPE svg")
+        || rest.starts_with("<!doctype svg")
 }
 
 fn looks_like_webp(data: &[u8]) -> bool {
@@ -253,6 +476,90 @@ fn looks_like_jpeg(data: &[u8]) -> bool {
     data.len() >= 2 && data[0] == 0xff && data[1] == 0xd8
 }
 
+/// An icon directory (`.ico`): reserved zero, type 1, and at least one entry.
+fn looks_like_ico(data: &[u8]) -> bool {
+    data.len() >= 6 && data[..4] == [0, 0, 1, 0] && (data[4] != 0 || data[5] != 0)
+}
+
+/// Decodes the largest image in an icon directory. Entries are either a whole PNG, handed to
+/// `decode_png`, or a 32bpp bottom-up DIB whose height counts the AND mask too; other depths
+/// are rejected.
+fn decode_ico_argb32(
+    data: &[u8],
+    decode_png: impl Fn(&[u8]) -> Result<Argb32Image, ImageError>,
+) -> Result<Argb32Image, ImageError> {
+    let le16 = |at: usize| {
+        data.get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+    };
+    let le32 = |at: usize| {
+        data.get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let truncated = || ImageError::Decode("Truncated ICO file".to_owned());
+
+    let count = le16(4).ok_or_else(truncated)? as usize;
+    let (mut best, mut best_area) = (None, 0u32);
+    for index in 0..count {
+        let entry = 6 + index * 16;
+        let side = |byte: u8| if byte == 0 { 256 } else { u32::from(byte) };
+        let width = side(*data.get(entry).ok_or_else(truncated)?);
+        let height = side(*data.get(entry + 1).ok_or_else(truncated)?);
+        let size = le32(entry + 8).ok_or_else(truncated)? as usize;
+        let offset = le32(entry + 12).ok_or_else(truncated)? as usize;
+        if width * height > best_area {
+            best_area = width * height;
+            best = Some((offset, size));
+        }
+    }
+    let (offset, size) = best.ok_or_else(truncated)?;
+    let image = offset
+        .checked_add(size)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(truncated)?;
+
+    if looks_like_png(image) {
+        return decode_png(image);
+    }
+
+    let le32 = |at: usize| {
+        image
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let header_len = le32(0).ok_or_else(truncated)? as usize;
+    let width = le32(4).ok_or_else(truncated)?;
+    // The stored height covers the colour rows and then the AND mask rows.
+    let height = le32(8).ok_or_else(truncated)? / 2;
+    let bits_per_pixel = image.get(14..16).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    if bits_per_pixel != Some(32) {
+        return Err(ImageError::Decode(
+            "Only PNG and 32bpp ICO entries are supported".to_owned(),
+        ));
+    }
+    check_image_dimensions(width, height)?;
+
+    let stride = width as usize * 4;
+    let pixels = image
+        .get(header_len..)
+        .filter(|pixels| pixels.len() >= stride * height as usize)
+        .ok_or_else(truncated)?;
+    let mut bgra = Vec::with_capacity(stride * height as usize);
+    for row in pixels.chunks_exact(stride).take(height as usize).rev() {
+        for pixel in row.chunks_exact(4) {
+            let alpha = u16::from(pixel[3]);
+            let premul = |channel: u8| ((u16::from(channel) * alpha + 127) / 255) as u8;
+            bgra.extend_from_slice(&[
+                premul(pixel[0]),
+                premul(pixel[1]),
+                premul(pixel[2]),
+                pixel[3],
+            ]);
+        }
+    }
+    Argb32Image::new(width, height, bgra).map_err(ImageError::Decode)
+}
+
 #[cfg(target_os = "macos")]
 fn decode_jpeg_argb32(data: &[u8]) -> Result<Argb32Image, String> {
     decode_imageio_argb32(data)
@@ -644,7 +951,10 @@ fn premultiply_rgba_to_bgra(rgba: &[u8]) -> Vec<u8> {
 
 #[cfg(test)]
 mod tests {
-    use super::{looks_like_supported_image, looks_like_svg_document};
+    use super::{
+        ImageError, MAX_SVG_DEPTH, check_svg_document, decode_image, image_dimensions,
+        looks_like_supported_image, looks_like_svg_document,
+    };
 
     #[test]
     fn recognizes_svg_documents() {
@@ -654,10 +964,245 @@ mod tests {
         assert!(looks_like_supported_image(svg));
     }
 
+    #[test]
+    fn recognizes_svg_after_comments_and_processing_instructions() {
+        let svg = br#"<?xml version="1.0"?>
+<!-- Generator: Adobe Illustrator 27.0.0 -->
+<?xml-stylesheet href="icons.css"?>
+<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">
+<svg xmlns="http://www.w3.org/2000/svg"></svg>"#;
+        assert!(looks_like_svg_document(svg));
+        assert!(!looks_like_svg_document(b"<!-- <svg> never closed"));
+        assert!(!looks_like_svg_document(
+            b"<!-- icon --><html><svg></svg></html>"
+        ));
+    }
+
     #[test]
     fn does_not_misclassify_html_as_svg() {
         let html = br#"<!doctype html><html><body><svg></svg></body></html>"#;
         assert!(!looks_like_svg_document(html));
         assert!(!looks_like_supported_image(html));
     }
+
+    #[test]
+    fn reads_dimensions_from_image_headers() {
+        let mut png = vec![137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13];
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&640u32.to_be_bytes());
+        png.extend_from_slice(&480u32.to_be_bytes());
+        assert_eq!(image_dimensions(&png), Some((640, 480)));
+
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x01,
+            0xe0, 0x02, 0x80,
+        ];
+        assert_eq!(image_dimensions(&jpeg), Some((640, 480)));
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X".to_vec();
+        webp.extend_from_slice(&[0; 8]);
+        webp.extend_from_slice(&[0x7f, 0x02, 0x00, 0xdf, 0x01, 0x00]);
+        assert_eq!(image_dimensions(&webp), Some((640, 480)));
+    }
+
+    /// A PNG with the given header fields and extra chunks, whose filtered scanlines are stored
+    /// uncompressed.
+    fn png(
+        size: u32,
+        bit_depth: u8,
+        color_type: u8,
+        interlace: u8,
+        chunks: &[(&[u8; 4], &[u8])],
+        scanlines: &[u8],
+    ) -> Vec<u8> {
+        fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            let mut crc = !0u32;
+            for &byte in kind.iter().chain(data) {
+                crc ^= u32::from(byte);
+                for _ in 0..8 {
+                    crc = if crc & 1 == 1 {
+                        (crc >> 1) ^ 0xEDB8_8320
+                    } else {
+                        crc >> 1
+                    };
+                }
+            }
+            out.extend_from_slice(kind);
+            out.extend_from_slice(data);
+            out.extend_from_slice(&(!crc).to_be_bytes());
+        }
+
+        let mut out = vec![137, 80, 78, 71, 13, 10, 26, 10];
+        let mut ihdr = [size.to_be_bytes(), size.to_be_bytes()].concat();
+        ihdr.extend_from_slice(&[bit_depth, color_type, 0, 0, interlace]);
+        chunk(&mut out, b"IHDR", &ihdr);
+        for (kind, data) in chunks {
+            chunk(&mut out, kind, data);
+        }
+        let (mut a, mut b) = (1u32, 0u32);
+        for &byte in scanlines {
+            a = (a + u32::from(byte)) % 65521;
+            b = (b + a) % 65521;
+        }
+        let mut zlib = vec![0x78, 0x01, 0x01];
+        zlib.extend_from_slice(&(scanlines.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(scanlines.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(scanlines);
+        zlib.extend_from_slice(&(b << 16 | a).to_be_bytes());
+        chunk(&mut out, b"IDAT", &zlib);
+        chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    #[test]
+    fn decodes_interlaced_sixteen_bit_grayscale_and_indexed_pngs() {
+        const RED: [u8; 4] = [0, 0, 255, 255];
+        const GREEN: [u8; 4] = [0, 255, 0, 255];
+        const BLUE: [u8; 4] = [255, 0, 0, 255];
+        const WHITE: [u8; 4] = [255; 4];
+        const BLACK: [u8; 4] = [0, 0, 0, 255];
+        const CLEAR: [u8; 4] = [0; 4];
+        let cases: [(&str, Vec<u8>, [[u8; 4]; 4]); 6] = [
+            (
+                "16-bit RGBA",
+                png(
+                    2,
+                    16,
+                    6,
+                    0,
+                    &[],
+                    &[
+                        0, 255, 255, 0, 0, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 255, 255, //
+                        0, 0, 0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0,
+                    ],
+                ),
+                [RED, GREEN, BLUE, CLEAR],
+            ),
+            (
+                "1-bit grayscale",
+                png(2, 1, 0, 0, &[], &[0, 0b1000_0000, 0, 0b0100_0000]),
+                [WHITE, BLACK, BLACK, WHITE],
+            ),
+            (
+                "grayscale with alpha",
+                png(2, 8, 4, 0, &[], &[0, 255, 255, 0, 255, 0, 0, 255, 255, 0]),
+                [WHITE, BLACK, BLACK, CLEAR],
+            ),
+            (
+                "16-bit grayscale with a transparent value",
+                png(
+                    2,
+                    16,
+                    0,
+                    0,
+                    &[(b"tRNS", &[0, 0])],
+                    &[0, 255, 255, 0, 0, 0, 0, 0, 255, 255],
+                ),
+                [WHITE, CLEAR, CLEAR, WHITE],
+            ),
+            (
+                "2-bit palette with tRNS",
+                png(
+                    2,
+                    2,
+                    3,
+                    0,
+                    &[
+                        (b"PLTE", &[255, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0]),
+                        (b"tRNS", &[255, 255, 255, 0]),
+                    ],
+                    &[0, 0b0001_0000, 0, 0b1011_0000],
+                ),
+                [RED, GREEN, BLUE, CLEAR],
+            ),
+            (
+                // Adam7 puts the top-left pixel in pass 1, the top-right in pass 6 and the
+                // bottom row in pass 7.
+                "interlaced RGB",
+                png(
+                    2,
+                    8,
+                    2,
+                    1,
+                    &[],
+                    &[0, 255, 0, 0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 255],
+                ),
+                [RED, GREEN, BLUE, WHITE],
+            ),
+        ];
+        for (name, data, expected) in cases {
+            let image = decode_image(&data).unwrap_or_else(|err| panic!("{name}: {err}"));
+            assert_eq!((image.width, image.height), (2, 2), "{name}");
+            assert_eq!(image.data, expected.concat(), "{name}");
+        }
+    }
+
+    #[test]
+    fn decodes_the_largest_bitmap_in_an_icon_directory() {
+        // Two entries: a 1x1 one and the 2x1 one picked, stored bottom-up with an AND mask.
+        let bitmap = |width: u32, pixels: &[[u8; 4]]| {
+            let mut dib = Vec::new();
+            dib.extend_from_slice(&40u32.to_le_bytes());
+            dib.extend_from_slice(&width.to_le_bytes());
+            dib.extend_from_slice(&2u32.to_le_bytes());
+            dib.extend_from_slice(&1u16.to_le_bytes());
+            dib.extend_from_slice(&32u16.to_le_bytes());
+            dib.resize(40, 0);
+            dib.extend(pixels.concat());
+            dib.extend_from_slice(&[0; 4]);
+            dib
+        };
+        let small = bitmap(1, &[[9, 9, 9, 255]]);
+        let large = bitmap(2, &[[255, 0, 0, 255], [0, 0, 255, 128]]);
+        let mut ico = vec![0, 0, 1, 0, 2, 0];
+        let mut offset = 6 + 2 * 16;
+        for (width, dib) in [(1u8, &small), (2u8, &large)] {
+            ico.extend_from_slice(&[width, 1, 0, 0, 1, 0, 32, 0]);
+            ico.extend_from_slice(&(dib.len() as u32).to_le_bytes());
+            ico.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += dib.len();
+        }
+        ico.extend_from_slice(&small);
+        ico.extend_from_slice(&large);
+
+        assert!(looks_like_supported_image(&ico));
+        let image = decode_image(&ico).unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.data, [255, 0, 0, 255, 0, 0, 128, 128]);
+    }
+
+    #[test]
+    fn rejects_oversized_images_before_decoding() {
+        let mut png = vec![137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13];
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&100_000u32.to_be_bytes());
+        png.extend_from_slice(&100_000u32.to_be_bytes());
+        assert_eq!(
+            decode_image(&png).err(),
+            Some(ImageError::TooLarge {
+                width: 100_000,
+                height: 100_000
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_deeply_nested_svg_and_entities() {
+        let ok = br#"<svg><!-- <g> --><g><rect/></g><g></g></svg>"#;
+        assert_eq!(check_svg_document(ok), Ok(()));
+
+        let deep = format!(
+            "<svg>{}{}</svg>",
+            "<g>".repeat(MAX_SVG_DEPTH),
+            "</g>".repeat(MAX_SVG_DEPTH)
+        );
+        assert!(matches!(
+            check_svg_document(deep.as_bytes()),
+            Err(ImageError::SvgTooDeep { .. })
+        ));
+
+        let laughs = br#"<?xml version="1.0"?><!DOCTYPE svg [<!ENTITY a "lol">]><svg>&a;</svg>"#;
+        assert_eq!(check_svg_document(laughs), Err(ImageError::SvgEntities));
+    }
 }
@@ -0,0 +1,207 @@
+use super::interp::{ElementPath, Host, Interpreter, Value, to_number, to_string, truthy};
+use crate::render::canvas::{Context2d, DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Pixels a page may allocate across all of its canvases; `getContext` returns `null` past it.
+const MAX_CANVAS_PIXELS: u64 = 16 * 1024 * 1024;
+/// Average glyph advance in ems, for `measureText` without access to fonts.
+const AVERAGE_ADVANCE_EM: f64 = 0.55;
+
+impl Interpreter<'_> {
+    pub(super) fn context_property(&self, index: usize, name: &str) -> Value {
+        match name {
+            "canvas" => Value::Host(Host::Element(self.contexts[index].0.clone())),
+            _ => Value::Undefined,
+        }
+    }
+
+    pub(super) fn set_context_property(&mut self, index: usize, name: &str, value: &Value) {
+        let paint = paint(value);
+        let context = &mut self.contexts[index].1;
+        let text = to_string(value);
+        let number = to_number(value);
+        match name {
+            "fillStyle" => {
+                if let Some(paint) = paint {
+                    context.set_fill_style(&paint);
+                }
+            }
+            "strokeStyle" => {
+                if let Some(paint) = paint {
+                    context.set_stroke_style(&paint);
+                }
+            }
+            "lineWidth" => context.set_line_width(number),
+            "lineCap" => context.set_line_cap(&text),
+            "lineJoin" => context.set_line_join(&text),
+            "miterLimit" => context.set_miter_limit(number),
+            "globalAlpha" => context.set_global_alpha(number),
+            "font" => context.set_font(&text),
+            "textAlign" => context.set_text_align(&text),
+            "textBaseline" => context.set_text_baseline(&text),
+            _ => {}
+        }
+    }
+
+    pub(super) fn context_method(
+        &mut self,
+        index: usize,
+        name: &str,
+        args: &[Value],
+    ) -> Result<Value, String> {
+        let n = |index: usize| args.get(index).map_or(f64::NAN, to_number);
+        let text = || args.first().map(to_string).unwrap_or_default();
+        let context = &mut self.contexts[index].1;
+        match name {
+            "fillRect" => context.fill_rect(n(0), n(1), n(2), n(3)),
+            "strokeRect" => context.stroke_rect(n(0), n(1), n(2), n(3)),
+            "clearRect" => context.clear_rect(n(0), n(1), n(2), n(3)),
+            "beginPath" => context.begin_path(),
+            "closePath" => context.close_path(),
+            "moveTo" => context.move_to(n(0), n(1)),
+            "lineTo" => context.line_to(n(0), n(1)),
+            "rect" => context.rect(n(0), n(1), n(2), n(3)),
+            "quadraticCurveTo" => context.quadratic_curve_to(n(0), n(1), n(2), n(3)),
+            "bezierCurveTo" => context.bezier_curve_to(n(0), n(1), n(2), n(3), n(4), n(5)),
+            "arc" => {
+                let anticlockwise = args.get(5).is_some_and(truthy);
+                context.arc(n(0), n(1), n(2), n(3), n(4), anticlockwise);
+            }
+            "ellipse" => {
+                let anticlockwise = args.get(7).is_some_and(truthy);
+                context.ellipse(n(0), n(1), n(2), n(3), n(4), n(5), n(6), anticlockwise);
+            }
+            "fill" => context.fill(text() == "evenodd"),
+            "stroke" => context.stroke(),
+            "fillText" => context.fill_text(&text(), n(1), n(2)),
+            "strokeText" => context.stroke_text(&text(), n(1), n(2)),
+            "save" => context.save(),
+            "restore" => context.restore(),
+            "translate" => context.translate(n(0), n(1)),
+            "scale" => context.scale(n(0), n(1)),
+            "rotate" => context.rotate(n(0)),
+            "transform" => context.transform(n(0), n(1), n(2), n(3), n(4), n(5)),
+            "setTransform" if args.is_empty() => context.reset_transform(),
+            "setTransform" => context.set_transform(n(0), n(1), n(2), n(3), n(4), n(5)),
+            "resetTransform" => context.reset_transform(),
+            "measureText" => {
+                let width =
+                    text().chars().count() as f64 * context.font_size_px() * AVERAGE_ADVANCE_EM;
+                return Ok(Value::Object(Rc::new(RefCell::new(vec![(
+                    Rc::from("width"),
+                    Value::Number(width),
+                )]))));
+            }
+            "createLinearGradient" | "createRadialGradient" => {
+                return Ok(Value::Host(Host::Gradient(Rc::default())));
+            }
+            "setLineDash" => {}
+            _ => return Err(format!("TypeError: context.{name} is not supported")),
+        }
+        Ok(Value::Undefined)
+    }
+
+    /// The context of the canvas at `path`, created on first use at the canvas's size.
+    pub(super) fn context_for(&mut self, path: &ElementPath) -> Value {
+        if let Some(index) = self
+            .contexts
+            .iter()
+            .position(|(existing, _)| existing == path)
+        {
+            return Value::Host(Host::Context(index));
+        }
+        let Some((width, height)) = self.canvas_size(path) else {
+            return Value::Null;
+        };
+        match self.new_context(width, height, None) {
+            Some(context) => {
+                self.contexts.push((path.clone(), context));
+                Value::Host(Host::Context(self.contexts.len() - 1))
+            }
+            None => Value::Null,
+        }
+    }
+
+    /// Sets a canvas's `width` or `height`, which clears it and resets its context's state.
+    pub(super) fn resize_canvas(&mut self, path: &ElementPath, name: &str, value: &Value) {
+        let size = to_number(value);
+        if !size.is_finite() {
+            return;
+        }
+        let size = size.clamp(0.0, f64::from(u32::MAX)) as u32;
+        match self.document.edit_element(path) {
+            Some(element) if element.name == "canvas" => {
+                element.attributes.remove(name);
+                element.attributes.insert(name.to_owned(), size.to_string());
+            }
+            _ => return,
+        }
+
+        let Some(index) = self
+            .contexts
+            .iter()
+            .position(|(existing, _)| existing == path)
+        else {
+            return;
+        };
+        let Some((width, height)) = self.canvas_size(path) else {
+            return;
+        };
+        if let Some(context) = self.new_context(width, height, Some(index)) {
+            self.contexts[index].1 = context;
+        }
+    }
+
+    fn new_context(&self, width: u32, height: u32, replacing: Option<usize>) -> Option<Context2d> {
+        let allocated: u64 = self
+            .contexts
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != replacing)
+            .map(|(_, (_, context))| u64::from(context.width()) * u64::from(context.height()))
+            .sum();
+        if allocated + u64::from(width) * u64::from(height) > MAX_CANVAS_PIXELS {
+            return None;
+        }
+        Context2d::new(width, height).ok()
+    }
+
+    /// The size of the `<canvas>` at `path`, or `None` for other elements.
+    pub(super) fn canvas_size(&self, path: &[usize]) -> Option<(u32, u32)> {
+        let element = self.document.element_at(path)?;
+        if element.name != "canvas" {
+            return None;
+        }
+        let dimension = |name: &str, default: u32| {
+            element
+                .attributes
+                .get(name)
+                .and_then(parse_non_negative_integer)
+                .unwrap_or(default)
+        };
+        Some((
+            dimension("width", DEFAULT_WIDTH),
+            dimension("height", DEFAULT_HEIGHT),
+        ))
+    }
+}
+
+/// The color to paint with: a CSS color string, or a gradient's first stop.
+fn paint(value: &Value) -> Option<String> {
+    match value {
+        Value::Str(text) => Some(text.to_string()),
+        Value::Host(Host::Gradient(color)) => color.borrow().as_deref().map(str::to_owned),
+        _ => None,
+    }
+}
+
+/// HTML's rules for parsing non-negative integers: leading digits, ignoring what follows.
+pub(crate) fn parse_non_negative_integer(value: &str) -> Option<u32> {
+    let value = value.trim_start();
+    let value = value.strip_prefix('+').unwrap_or(value);
+    let len = value
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(value.len());
+    value[..len].parse().ok()
+}
@@ -0,0 +1,655 @@
+use super::interp::{
+    ElementPath, Eval, Host, Interpreter, MAX_ARRAY_LEN, Value, to_string, truthy,
+};
+use crate::css::{Declaration, parse_inline_declarations, parse_selector_list};
+use crate::dom::{Element, Node};
+use crate::style::selector_matches;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Elements without an end tag, which `innerHTML` writes as a start tag alone.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+/// Bytes of `innerHTML` markup parsed per step of the budget.
+const PARSE_BYTES_PER_STEP: u64 = 64;
+
+impl Interpreter<'_> {
+    pub(super) fn document_property(&mut self, name: &str) -> Eval<Value> {
+        let tag = match name {
+            "documentElement" => "html",
+            "head" => "head",
+            "body" => "body",
+            _ => return Ok(Value::Undefined),
+        };
+        let found = self.find_elements(&[], 1, &|element, _| element.name == tag)?;
+        Ok(first_element(found))
+    }
+
+    pub(super) fn document_method(&mut self, name: &str, args: &[Value]) -> Eval<Value> {
+        let first = args.first().map(to_string).unwrap_or_default();
+        match name {
+            "getElementById" => self.element_by_id(&first),
+            _ => self.query_method(&[], name, &first),
+        }
+    }
+
+    pub(super) fn element_by_id(&mut self, id: &str) -> Eval<Value> {
+        let found = self.find_elements(&[], 1, &|element, _| {
+            element.attributes.id.as_deref() == Some(id)
+        })?;
+        Ok(first_element(found))
+    }
+
+    /// The lookups shared by the document and elements, searching the descendants of `scope`.
+    fn query_method(&mut self, scope: &[usize], name: &str, argument: &str) -> Eval<Value> {
+        let (found, all) = match name {
+            "querySelector" | "querySelectorAll" => {
+                let selectors = parse_selector_list(argument);
+                if selectors.is_empty() {
+                    return Err(format!("SyntaxError: {argument:?} is not a valid selector"));
+                }
+                let all = name == "querySelectorAll";
+                let limit = if all { MAX_ARRAY_LEN } else { 1 };
+                let found = self.find_elements(scope, limit, &|element, ancestors| {
+                    selectors
+                        .iter()
+                        .any(|selector| selector_matches(selector, element, ancestors))
+                })?;
+                (found, all)
+            }
+            "getElementsByClassName" => {
+                let classes: Vec<&str> = argument.split_whitespace().collect();
+                let found = self.find_elements(scope, MAX_ARRAY_LEN, &|element, _| {
+                    !classes.is_empty()
+                        && classes
+                            .iter()
+                            .all(|class| element.attributes.has_class(class))
+                })?;
+                (found, true)
+            }
+            "getElementsByTagName" => {
+                let tag = argument.to_ascii_lowercase();
+                let found = self.find_elements(scope, MAX_ARRAY_LEN, &|element, _| {
+                    tag == "*" || element.name == tag
+                })?;
+                (found, true)
+            }
+            _ => return Err(format!("TypeError: document.{name} is not supported")),
+        };
+        Ok(if all {
+            element_list(found)
+        } else {
+            first_element(found)
+        })
+    }
+
+    /// Paths of up to `limit` elements below `scope`, in document order, for which `matches`
+    /// holds. Each element visited counts against the step budget.
+    fn find_elements(
+        &mut self,
+        scope: &[usize],
+        limit: usize,
+        matches: &dyn Fn(&Element, &[&Element]) -> bool,
+    ) -> Eval<Vec<ElementPath>> {
+        let mut found = Vec::new();
+        let visited = self
+            .document
+            .walk_elements(scope, &mut |element, path, ancestors| {
+                if matches(element, ancestors) {
+                    found.push(Rc::from(path));
+                }
+                found.len() < limit
+            });
+        self.charge(visited as u64)?;
+        Ok(found)
+    }
+
+    pub(super) fn element_property(&mut self, path: &ElementPath, name: &str) -> Eval<Value> {
+        if matches!(name, "width" | "height")
+            && let Some((width, height)) = self.canvas_size(path)
+        {
+            let size = if name == "width" { width } else { height };
+            return Ok(Value::Number(f64::from(size)));
+        }
+        let Some(element) = self.document.element_at(path) else {
+            return Ok(Value::Undefined);
+        };
+        let mut visited = 0;
+        let value = match name {
+            "id" => text(element.attributes.id.as_deref().unwrap_or_default()),
+            "className" => text(&element.attributes.classes.join(" ")),
+            "tagName" | "nodeName" => text(&element.name.to_ascii_uppercase()),
+            "nodeType" => Value::Number(1.0),
+            "textContent" | "innerText" => {
+                let mut out = String::new();
+                collect_text(element, &mut out, &mut visited);
+                text(&out)
+            }
+            "innerHTML" => {
+                let mut out = String::new();
+                serialize_children(element, &mut out, &mut visited);
+                text(&out)
+            }
+            "value" | "title" | "href" | "src" | "alt" | "name" | "type" => {
+                text(element.attributes.get(name).unwrap_or_default())
+            }
+            "hidden" | "checked" | "disabled" => {
+                Value::Bool(element.attributes.get(name).is_some())
+            }
+            "children" => {
+                let children = element
+                    .children
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, child)| matches!(child, Node::Element(_)))
+                    .map(|(index, _)| child_path(path, index))
+                    .collect();
+                element_list(children)
+            }
+            "parentElement" | "parentNode" if path.len() > 1 => {
+                Value::Host(Host::Element(Rc::from(&path[..path.len() - 1])))
+            }
+            "parentElement" | "parentNode" => Value::Null,
+            "classList" => Value::Host(Host::ClassList(path.clone())),
+            "style" => Value::Host(Host::Style(path.clone())),
+            _ => Value::Undefined,
+        };
+        self.charge(visited)?;
+        Ok(value)
+    }
+
+    pub(super) fn set_element_property(
+        &mut self,
+        path: &ElementPath,
+        name: &str,
+        value: &Value,
+    ) -> Eval<()> {
+        if matches!(name, "width" | "height") && self.canvas_size(path).is_some() {
+            self.resize_canvas(path, name, value);
+            return Ok(());
+        }
+        let value_text = to_string(value);
+        if name == "className"
+            && self
+                .document
+                .element_at(path)
+                .is_some_and(|element| element.name == "html")
+            && super::should_skip_root_class_assignment(self.document, &value_text)
+        {
+            return Ok(());
+        }
+        if name == "innerHTML" {
+            self.charge(value_text.len() as u64 / PARSE_BYTES_PER_STEP)?;
+        }
+        match name {
+            "id" | "className" | "value" | "title" | "href" | "src" | "alt" | "name" | "type" => {
+                let name = if name == "className" { "class" } else { name };
+                self.document.set_attribute(path, name, value_text);
+            }
+            "textContent" | "innerText" => {
+                self.document.set_text_content(path, value_text);
+            }
+            "innerHTML" => {
+                let children = crate::html::parse_document(&value_text).root.children;
+                self.document.replace_children(path, children);
+            }
+            "hidden" | "checked" | "disabled" => {
+                if truthy(value) {
+                    self.document.set_attribute(path, name, String::new());
+                } else {
+                    self.document.remove_attribute(path, name);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub(super) fn element_method(
+        &mut self,
+        path: &ElementPath,
+        name: &str,
+        args: &[Value],
+    ) -> Eval<Value> {
+        let first = args.first().map(to_string).unwrap_or_default();
+        let second = args.get(1).cloned().unwrap_or(Value::Undefined);
+        Ok(match name {
+            "getContext" if first == "2d" => self.context_for(path),
+            "getContext" => Value::Null,
+            "getAttribute" => self
+                .document
+                .element_at(path)
+                .and_then(|element| attribute(element, &first))
+                .map_or(Value::Null, |value| text(&value)),
+            "hasAttribute" => Value::Bool(
+                self.document
+                    .element_at(path)
+                    .and_then(|element| attribute(element, &first))
+                    .is_some(),
+            ),
+            "setAttribute" => {
+                let name = first.to_ascii_lowercase();
+                if matches!(name.as_str(), "width" | "height") && self.canvas_size(path).is_some() {
+                    self.resize_canvas(path, &name, &second);
+                } else {
+                    self.document.set_attribute(path, &name, to_string(&second));
+                }
+                Value::Undefined
+            }
+            "removeAttribute" => {
+                self.document
+                    .remove_attribute(path, &first.to_ascii_lowercase());
+                Value::Undefined
+            }
+            "getBoundingClientRect" => {
+                let (width, height) = self.canvas_size(path).unwrap_or((0, 0));
+                let entries = [
+                    ("x", 0),
+                    ("y", 0),
+                    ("left", 0),
+                    ("top", 0),
+                    ("width", width),
+                    ("height", height),
+                ]
+                .into_iter()
+                .map(|(key, value)| (Rc::from(key), Value::Number(f64::from(value))))
+                .collect();
+                Value::Object(Rc::new(RefCell::new(entries)))
+            }
+            "remove" => {
+                if let Some((&index, parent)) = path.split_last() {
+                    self.document.remove_child(parent, index);
+                }
+                Value::Undefined
+            }
+            "addEventListener" | "removeEventListener" | "focus" | "blur" | "scrollIntoView" => {
+                Value::Undefined
+            }
+            "querySelector"
+            | "querySelectorAll"
+            | "getElementsByClassName"
+            | "getElementsByTagName" => self.query_method(path, name, &first)?,
+            _ => return Err(format!("TypeError: element.{name} is not supported")),
+        })
+    }
+
+    pub(super) fn class_list_method(
+        &mut self,
+        path: &ElementPath,
+        name: &str,
+        args: &[Value],
+    ) -> Eval<Value> {
+        let names: Vec<String> = args.iter().map(to_string).collect();
+        let first = names.first().cloned().unwrap_or_default();
+        let Some(element) = self.document.edit_element(path) else {
+            return Ok(Value::Undefined);
+        };
+        let classes = &mut element.attributes.classes;
+        Ok(match name {
+            "add" => {
+                for name in names {
+                    if !classes.contains(&name) {
+                        classes.push(name);
+                    }
+                }
+                Value::Undefined
+            }
+            "remove" => {
+                classes.retain(|class| !names.contains(class));
+                Value::Undefined
+            }
+            "toggle" => {
+                let present = classes.contains(&first);
+                let wanted = args.get(1).map_or(!present, truthy);
+                if wanted && !present {
+                    classes.push(first);
+                } else if !wanted {
+                    classes.retain(|class| *class != first);
+                }
+                Value::Bool(wanted)
+            }
+            "contains" => Value::Bool(classes.contains(&first)),
+            "replace" => {
+                let replacement = names.get(1).cloned().unwrap_or_default();
+                let Some(index) = classes.iter().position(|class| *class == first) else {
+                    return Ok(Value::Bool(false));
+                };
+                if classes.contains(&replacement) {
+                    classes.remove(index);
+                } else {
+                    classes[index] = replacement;
+                }
+                Value::Bool(true)
+            }
+            _ => return Err(format!("TypeError: classList.{name} is not supported")),
+        })
+    }
+
+    pub(super) fn style_property(&self, path: &[usize], name: &str) -> Value {
+        let style = self
+            .document
+            .element_at(path)
+            .and_then(|element| element.attributes.style.as_deref())
+            .unwrap_or_default();
+        if name == "cssText" {
+            return text(style);
+        }
+        let property = css_property_name(name);
+        let value = parse_inline_declarations(style)
+            .into_iter()
+            .rev()
+            .find(|declaration| declaration.name == property)
+            .map(|declaration| declaration.value)
+            .unwrap_or_default();
+        text(&value)
+    }
+
+    pub(super) fn set_style_property(&mut self, path: &[usize], name: &str, value: &Value) {
+        let value = to_string(value);
+        if name == "cssText" {
+            if let Some(element) = self.document.edit_element(path) {
+                element.attributes.style = Some(value);
+            }
+            return;
+        }
+        self.update_style(path, &css_property_name(name), &value);
+    }
+
+    pub(super) fn style_method(
+        &mut self,
+        path: &ElementPath,
+        name: &str,
+        args: &[Value],
+    ) -> Eval<Value> {
+        let first = args.first().map(to_string).unwrap_or_default();
+        match name {
+            "getPropertyValue" => return Ok(self.style_property(path, &first)),
+            "setProperty" => {
+                let value = args.get(1).map(to_string).unwrap_or_default();
+                self.update_style(path, &first, &value);
+            }
+            "removeProperty" => self.update_style(path, &first, ""),
+            _ => return Err(format!("TypeError: style.{name} is not supported")),
+        }
+        Ok(Value::Undefined)
+    }
+
+    /// Replaces `property` in the `style` attribute, removing it when `value` is empty.
+    fn update_style(&mut self, path: &[usize], property: &str, value: &str) {
+        let Some(element) = self.document.edit_element(path) else {
+            return;
+        };
+        let mut declarations = element
+            .attributes
+            .style
+            .as_deref()
+            .map(parse_inline_declarations)
+            .unwrap_or_default();
+        declarations.retain(|declaration| declaration.name != property);
+        let value = value.trim();
+        if !value.is_empty() {
+            declarations.push(Declaration {
+                name: property.to_owned(),
+                value: value.to_owned(),
+            });
+        }
+        element.attributes.style = (!declarations.is_empty()).then(|| {
+            declarations
+                .iter()
+                .map(|declaration| format!("{}: {}", declaration.name, declaration.value))
+                .collect::<Vec<_>>()
+                .join("; ")
+        });
+    }
+}
+
+fn text(value: &str) -> Value {
+    Value::Str(Rc::from(value))
+}
+
+fn child_path(path: &[usize], index: usize) -> ElementPath {
+    path.iter().copied().chain([index]).collect()
+}
+
+fn first_element(found: Vec<ElementPath>) -> Value {
+    found
+        .into_iter()
+        .next()
+        .map_or(Value::Null, |path| Value::Host(Host::Element(path)))
+}
+
+fn element_list(found: Vec<ElementPath>) -> Value {
+    let elements = found
+        .into_iter()
+        .map(|path| Value::Host(Host::Element(path)))
+        .collect();
+    Value::Array(Rc::new(RefCell::new(elements)))
+}
+
+/// An attribute as `getAttribute` sees it; `class` is stored split into `classes`.
+fn attribute(element: &Element, name: &str) -> Option<String> {
+    let name = name.to_ascii_lowercase();
+    if name == "class" {
+        let classes = &element.attributes.classes;
+        return (!classes.is_empty()).then(|| classes.join(" "));
+    }
+    element.attributes.get(&name).map(str::to_owned)
+}
+
+/// `backgroundColor` to `background-color`, and `cssFloat` to `float`.
+fn css_property_name(name: &str) -> String {
+    if name == "cssFloat" {
+        return "float".to_owned();
+    }
+    let mut out = String::with_capacity(name.len() + 4);
+    for ch in name.chars() {
+        if ch.is_ascii_uppercase() {
+            out.push('-');
+            out.push(ch.to_ascii_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+fn collect_text(element: &Element, out: &mut String, visited: &mut u64) {
+    for child in &element.children {
+        *visited += 1;
+        match child {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(child) => collect_text(child, out, visited),
+        }
+    }
+}
+
+fn serialize_children(element: &Element, out: &mut String, visited: &mut u64) {
+    let raw_text = matches!(element.name.as_str(), "script" | "style");
+    for child in &element.children {
+        *visited += 1;
+        match child {
+            Node::Text(text) if raw_text => out.push_str(text),
+            Node::Text(text) => escape(text, false, out),
+            Node::Element(child) => {
+                out.push('<');
+                out.push_str(&child.name);
+                for (name, value) in child.attributes.to_serialized_pairs() {
+                    out.push(' ');
+                    out.push_str(&name);
+                    out.push_str("=\"");
+                    escape(&value, true, out);
+                    out.push('"');
+                }
+                out.push('>');
+                if VOID_ELEMENTS.contains(&child.name.as_str()) {
+                    continue;
+                }
+                serialize_children(child, out, visited);
+                out.push_str("</");
+                out.push_str(&child.name);
+                out.push('>');
+            }
+        }
+    }
+}
+
+fn escape(text: &str, attribute: bool, out: &mut String) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '"' if attribute => out.push_str("&quot;"),
+            '<' if !attribute => out.push_str("&lt;"),
+            '>' if !attribute => out.push_str("&gt;"),
+            '\u{a0}' => out.push_str("&nbsp;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(html: &str, script: &str) -> crate::dom::Document {
+        let mut document = crate::html::parse_document(html);
+        {
+            let mut interpreter = Interpreter::new(&mut document);
+            interpreter.run(script);
+            interpreter.finish();
+        }
+        document
+    }
+
+    #[test]
+    fn scripts_query_and_update_elements() {
+        let document = run(
+            r#"<html class="no-js"><body><ul id="menu"><li class="item">One</li><li class="item active">Two</li></ul><p hidden>Note</p></body></html>"#,
+            r#"
+              document.documentElement.classList.replace('no-js', 'js');
+              const items = document.querySelectorAll('#menu > .item');
+              items.forEach((item, index) => item.setAttribute('data-index', String(index)));
+              document.querySelector('.item.active').classList.toggle('active');
+              items[0].classList.add('first', 'open');
+              const note = document.querySelector('p');
+              note.hidden = false;
+              note.style.backgroundColor = 'yellow';
+              note.innerHTML = '<b>' + items.length + '</b> items & more';
+              document.body.querySelector('li').textContent = note.innerHTML;
+            "#,
+        );
+
+        let html = document.find_first_element_by_name("html").unwrap();
+        assert_eq!(html.attributes.classes, vec!["js".to_owned()]);
+        let menu = document.find_first_element_by_id("menu").unwrap();
+        let items: Vec<&Element> = menu
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Element(element) => Some(element),
+                Node::Text(_) => None,
+            })
+            .collect();
+        assert_eq!(items[0].attributes.classes, ["item", "first", "open"]);
+        assert_eq!(items[1].attributes.classes, ["item"]);
+        assert_eq!(items[1].attributes.get("data-index"), Some("1"));
+        assert_eq!(
+            items[0].children,
+            vec![Node::Text("<b>2</b> items &amp; more".to_owned())]
+        );
+
+        let note = document.find_first_element_by_name("p").unwrap();
+        assert_eq!(note.attributes.get("hidden"), None);
+        assert_eq!(
+            note.attributes.style.as_deref(),
+            Some("background-color: yellow")
+        );
+        assert_eq!(
+            note.children[0],
+            Node::Element(Element {
+                name: "b".to_owned(),
+                attributes: Default::default(),
+                children: vec![Node::Text("2".to_owned())],
+            })
+        );
+    }
+
+    fn title_after(script: &str) -> String {
+        let document = run("<html><body><p id=\"out\"></p></body></html>", script);
+        let out = document.find_first_element_by_id("out").unwrap();
+        match out.children.as_slice() {
+            [Node::Text(text)] => text.clone(),
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn catches_thrown_values_and_runs_finally() {
+        assert_eq!(
+            title_after(
+                r#"
+                  const log = [];
+                  function check(n) {
+                    if (n > 1) throw { name: 'RangeError', message: 'too big' };
+                    return n;
+                  }
+                  try {
+                    log.push(check(1));
+                    log.push(check(2));
+                    log.push('unreached');
+                  } catch (err) {
+                    log.push(err.name + ': ' + err.message);
+                  } finally {
+                    log.push('done');
+                  }
+                  try { missing.value; } catch (err) { log.push(err.name); }
+                  try { throw 'bare'; } catch { log.push('no binding'); }
+                  const early = () => { try { return 'try'; } finally { log.push('cleanup'); } };
+                  log.push(early());
+                  document.getElementById('out').textContent = log.join('|');
+                "#
+            ),
+            "1|RangeError: too big|done|ReferenceError|no binding|cleanup|try"
+        );
+    }
+
+    #[test]
+    fn uncaught_throws_stop_only_their_script() {
+        let document = run(
+            r#"<html><body><p id="out">before</p></body></html>"#,
+            "try { throw 'inner'; } finally { document.getElementById('out').textContent = 'finally'; }\n\
+             document.getElementById('out').textContent = 'unreached';",
+        );
+        let out = document.find_first_element_by_id("out").unwrap();
+        assert_eq!(out.children, vec![Node::Text("finally".to_owned())]);
+    }
+
+    #[test]
+    fn budget_errors_are_not_caught() {
+        assert_eq!(
+            title_after(
+                "try { while (true) {} } catch (err) {}\n\
+                 document.getElementById('out').textContent = 'resumed';"
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn parses_and_stringifies_json() {
+        assert_eq!(
+            title_after(
+                r#"
+                  const data = JSON.parse(' {"name": "caf\\u00e9", "tags": ["a", "b"], "n": -1.5e2, "ok": true, "none": null} ');
+                  const parts = [data.name, data.tags.length, data.n, data.ok, data.none === null];
+                  parts.push(JSON.stringify({ a: [1, 'two', null, undefined], b: { c: 'q"\n' }, skip: undefined, f: () => 1 }));
+                  parts.push(JSON.stringify('x'), JSON.stringify(undefined) === undefined);
+                  try { JSON.parse('{"a": 1,}'); } catch (err) { parts.push(err.name); }
+                  document.getElementById('out').textContent = parts.join(' ');
+                "#
+            ),
+            r#"café 2 -150 true true {"a":[1,"two",null,null],"b":{"c":"q\"\n"}} "x" true SyntaxError"#
+        );
+    }
+}
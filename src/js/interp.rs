@@ -0,0 +1,1236 @@
+use super::parser::{
+    BinaryOp, DeclKind, Expr, FunctionBody, FunctionDef, LogicalOp, Stmt, UnaryOp, parse_script,
+};
+use crate::debug;
+use crate::dom::Document;
+use crate::render::canvas::Context2d;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+use std::sync::Arc;
+
+/// Statements and expressions evaluated per page before scripts are stopped.
+const MAX_STEPS: u64 = 2_000_000;
+/// Deepest nesting of evaluation, counting function calls, before a script is stopped.
+const MAX_DEPTH: usize = 192;
+pub(super) const MAX_STRING_CHARS: usize = 1 << 20;
+pub(super) const MAX_ARRAY_LEN: usize = 1 << 16;
+/// Rounds of deferred callbacks (load handlers, then timers and animation frames they queue).
+const MAX_DEFERRED_ROUNDS: usize = 3;
+
+/// An object's own properties in insertion order.
+pub(super) type Properties = Vec<(Rc<str>, Value)>;
+
+/// Child indices from the document root down to an element. Elements have no identity of their
+/// own, so a path goes stale when the children of an element along it are replaced.
+pub(super) type ElementPath = Rc<[usize]>;
+
+#[derive(Clone)]
+pub(super) enum Value {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(Rc<str>),
+    Array(Rc<RefCell<Vec<Value>>>),
+    Object(Rc<RefCell<Properties>>),
+    Function(Rc<Closure>),
+    Host(Host),
+}
+
+/// Objects the page provides rather than the script.
+#[derive(Clone)]
+pub(super) enum Host {
+    Document,
+    Window,
+    Math,
+    Json,
+    Console,
+    /// A global function such as `parseInt`, by name.
+    Builtin(&'static str),
+    Element(ElementPath),
+    /// An element's `classList`.
+    ClassList(ElementPath),
+    /// An element's `style`, which reads and writes its `style` attribute.
+    Style(ElementPath),
+    /// A 2D context, by index into `Interpreter::contexts`.
+    Context(usize),
+    /// A canvas gradient, which paints in the color of its first stop.
+    Gradient(Rc<RefCell<Option<Rc<str>>>>),
+}
+
+pub(super) struct Closure {
+    def: Rc<FunctionDef>,
+    scope: Rc<Scope>,
+}
+
+struct Scope {
+    vars: RefCell<HashMap<Rc<str>, Value>>,
+    parent: Option<Rc<Scope>>,
+    /// Function scopes receive `var` declarations.
+    function: bool,
+}
+
+impl Scope {
+    fn child(parent: &Rc<Scope>, function: bool) -> Rc<Scope> {
+        Rc::new(Scope {
+            vars: RefCell::new(HashMap::new()),
+            parent: Some(parent.clone()),
+            function,
+        })
+    }
+}
+
+enum Flow {
+    Normal,
+    Break,
+    Continue,
+    Return(Value),
+}
+
+pub(super) type Eval<T> = Result<T, String>;
+
+/// Where an assignment stores its value.
+enum Place {
+    Var(Rc<str>),
+    Property(Value, Rc<str>),
+}
+
+const BUILTINS: &[&str] = &[
+    "parseInt",
+    "parseFloat",
+    "Number",
+    "String",
+    "Boolean",
+    "isNaN",
+    "isFinite",
+    "requestAnimationFrame",
+    "setTimeout",
+    "setInterval",
+    "alert",
+];
+
+/// Runs inline scripts against one document. Globals persist from script to script, as in a
+/// browser; an uncaught error stops only the script that raised it.
+pub(super) struct Interpreter<'a> {
+    pub(super) document: &'a mut Document,
+    globals: Rc<Scope>,
+    /// Scopes captured by closures, so reference cycles through them can be broken on drop.
+    captured: Vec<Weak<Scope>>,
+    /// `captured` is pruned of dropped scopes when it reaches this length.
+    prune_at: usize,
+    pub(super) contexts: Vec<(ElementPath, Context2d)>,
+    deferred: Vec<Value>,
+    steps: u64,
+    depth: usize,
+    random_state: u64,
+    /// The value of the last `throw`, with the error it unwinds as, for a `catch` to bind.
+    thrown: Option<(String, Value)>,
+}
+
+impl<'a> Interpreter<'a> {
+    pub(super) fn new(document: &'a mut Document) -> Self {
+        let globals = Rc::new(Scope {
+            vars: RefCell::new(HashMap::new()),
+            parent: None,
+            function: true,
+        });
+        {
+            let mut vars = globals.vars.borrow_mut();
+            vars.insert(Rc::from("document"), Value::Host(Host::Document));
+            vars.insert(Rc::from("window"), Value::Host(Host::Window));
+            vars.insert(Rc::from("Math"), Value::Host(Host::Math));
+            vars.insert(Rc::from("JSON"), Value::Host(Host::Json));
+            vars.insert(Rc::from("console"), Value::Host(Host::Console));
+            for &name in BUILTINS {
+                vars.insert(Rc::from(name), Value::Host(Host::Builtin(name)));
+            }
+        }
+        Self {
+            document,
+            captured: vec![Rc::downgrade(&globals)],
+            prune_at: 1024,
+            globals,
+            contexts: Vec::new(),
+            deferred: Vec::new(),
+            steps: 0,
+            depth: 0,
+            random_state: 0x2545_f491_4f6c_dd1d,
+            thrown: None,
+        }
+    }
+
+    pub(super) fn run(&mut self, source: &str) {
+        let result = parse_script(source).and_then(|program| {
+            let globals = self.globals.clone();
+            self.exec_block(&program, &globals).map(|_| ())
+        });
+        if let Err(err) = result {
+            log_error(&err);
+        }
+    }
+
+    /// Runs callbacks queued for after the scripts, then stores what was drawn on each canvas
+    /// in the document.
+    pub(super) fn finish(&mut self) {
+        for _ in 0..MAX_DEFERRED_ROUNDS {
+            let callbacks = std::mem::take(&mut self.deferred);
+            if callbacks.is_empty() {
+                break;
+            }
+            for callback in callbacks {
+                if let Err(err) = self.call(&callback, vec![Value::Number(0.0)]) {
+                    log_error(&err);
+                }
+            }
+        }
+
+        for (path, context) in std::mem::take(&mut self.contexts) {
+            let Some(id) = self
+                .document
+                .element_at(&path)
+                .and_then(|element| element.attributes.id.clone())
+            else {
+                continue;
+            };
+            match context.into_surface() {
+                Ok(surface) => {
+                    self.document.canvases.insert(id, Arc::new(surface));
+                }
+                Err(err) => log_error(&err),
+            }
+        }
+    }
+
+    pub(super) fn defer(&mut self, callback: Value) {
+        if matches!(callback, Value::Function(_)) {
+            self.deferred.push(callback);
+        }
+    }
+
+    fn step(&mut self) -> Eval<()> {
+        self.charge(1)
+    }
+
+    /// Counts work done outside the evaluator, such as walking the document, against the budget.
+    pub(super) fn charge(&mut self, steps: u64) -> Eval<()> {
+        self.steps = self.steps.saturating_add(steps);
+        if self.steps > MAX_STEPS {
+            return Err("Script exceeded its step budget".to_owned());
+        }
+        Ok(())
+    }
+
+    fn exec_block(&mut self, statements: &[Stmt], scope: &Rc<Scope>) -> Eval<Flow> {
+        for statement in statements {
+            if let Stmt::Function(def) = statement
+                && let Some(name) = &def.name
+            {
+                let closure = self.closure(def, scope);
+                scope.vars.borrow_mut().insert(name.clone(), closure);
+            }
+        }
+        for statement in statements {
+            let flow = self.exec(statement, scope)?;
+            if !matches!(flow, Flow::Normal) {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn exec(&mut self, statement: &Stmt, scope: &Rc<Scope>) -> Eval<Flow> {
+        self.step()?;
+        self.depth += 1;
+        let result = if self.depth > MAX_DEPTH {
+            Err("Script nested too deeply".to_owned())
+        } else {
+            self.exec_inner(statement, scope)
+        };
+        self.depth -= 1;
+        result
+    }
+
+    fn exec_inner(&mut self, statement: &Stmt, scope: &Rc<Scope>) -> Eval<Flow> {
+        match statement {
+            Stmt::Expr(expression) => {
+                self.eval(expression, scope)?;
+            }
+            Stmt::Declare(kind, bindings) => {
+                let target = match kind {
+                    DeclKind::Var => function_scope(scope),
+                    DeclKind::Lexical => scope.clone(),
+                };
+                for (name, value) in bindings {
+                    let value = match value {
+                        Some(value) => self.eval(value, scope)?,
+                        None if *kind == DeclKind::Var
+                            && target.vars.borrow().contains_key(name) =>
+                        {
+                            continue;
+                        }
+                        None => Value::Undefined,
+                    };
+                    target.vars.borrow_mut().insert(name.clone(), value);
+                }
+            }
+            Stmt::Function(_) | Stmt::Empty => {}
+            Stmt::If(test, then, otherwise) => {
+                if truthy(&self.eval(test, scope)?) {
+                    return self.exec(then, scope);
+                }
+                if let Some(otherwise) = otherwise {
+                    return self.exec(otherwise, scope);
+                }
+            }
+            Stmt::For {
+                init,
+                test,
+                update,
+                body,
+            } => {
+                let scope = Scope::child(scope, false);
+                if let Some(init) = init {
+                    self.exec(init, &scope)?;
+                }
+                loop {
+                    if let Some(test) = test
+                        && !truthy(&self.eval(test, &scope)?)
+                    {
+                        break;
+                    }
+                    match self.exec(body, &scope)? {
+                        Flow::Break => break,
+                        flow @ Flow::Return(_) => return Ok(flow),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                    if let Some(update) = update {
+                        self.eval(update, &scope)?;
+                    }
+                }
+            }
+            Stmt::ForOf(_, name, iterable, body) => {
+                let items = match self.eval(iterable, scope)? {
+                    Value::Array(items) => items.borrow().clone(),
+                    Value::Str(text) => text
+                        .chars()
+                        .map(|ch| Value::Str(Rc::from(ch.to_string())))
+                        .collect(),
+                    _ => return Err("for...of over a value that is not iterable".to_owned()),
+                };
+                for item in items {
+                    let scope = Scope::child(scope, false);
+                    scope.vars.borrow_mut().insert(name.clone(), item);
+                    match self.exec(body, &scope)? {
+                        Flow::Break => break,
+                        flow @ Flow::Return(_) => return Ok(flow),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+            }
+            Stmt::While(test, body) => {
+                while truthy(&self.eval(test, scope)?) {
+                    match self.exec(body, scope)? {
+                        Flow::Break => break,
+                        flow @ Flow::Return(_) => return Ok(flow),
+                        Flow::Normal | Flow::Continue => {}
+                    }
+                }
+            }
+            Stmt::Block(statements) => {
+                let scope = Scope::child(scope, false);
+                return self.exec_block(statements, &scope);
+            }
+            Stmt::Return(value) => {
+                let value = match value {
+                    Some(value) => self.eval(value, scope)?,
+                    None => Value::Undefined,
+                };
+                return Ok(Flow::Return(value));
+            }
+            Stmt::Throw(value) => {
+                let value = self.eval(value, scope)?;
+                let err = format!("Uncaught {}", error_text(&value));
+                self.thrown = Some((err.clone(), value));
+                return Err(err);
+            }
+            Stmt::Try {
+                block,
+                catch,
+                finally,
+            } => {
+                let mut result = self.exec_block(block, &Scope::child(scope, false));
+                if let Some((param, handler)) = catch
+                    && let Err(err) = &result
+                    && catchable(err)
+                {
+                    let error = self.caught(err);
+                    let scope = Scope::child(scope, false);
+                    if let Some(param) = param {
+                        scope.vars.borrow_mut().insert(param.clone(), error);
+                    }
+                    result = self.exec_block(handler, &scope);
+                }
+                if let Some(finally) = finally {
+                    let flow = self.exec_block(finally, &Scope::child(scope, false))?;
+                    if !matches!(flow, Flow::Normal) {
+                        return Ok(flow);
+                    }
+                }
+                return result;
+            }
+            Stmt::Break => return Ok(Flow::Break),
+            Stmt::Continue => return Ok(Flow::Continue),
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// The value a `catch` binds for `err`: what was thrown, or an error object for an error
+    /// the interpreter raised itself.
+    fn caught(&mut self, err: &str) -> Value {
+        if let Some((thrown, value)) = self.thrown.take()
+            && thrown == err
+        {
+            return value;
+        }
+        let (name, message) = match err.split_once(": ") {
+            Some((name, message)) if name.ends_with("Error") => (name, message),
+            _ => ("Error", err),
+        };
+        Value::Object(Rc::new(RefCell::new(vec![
+            (Rc::from("name"), Value::Str(Rc::from(name))),
+            (Rc::from("message"), Value::Str(Rc::from(message))),
+        ])))
+    }
+
+    fn closure(&mut self, def: &Rc<FunctionDef>, scope: &Rc<Scope>) -> Value {
+        if self.captured.len() >= self.prune_at {
+            self.captured.retain(|scope| scope.strong_count() > 0);
+            self.prune_at = (self.captured.len() * 2).max(1024);
+        }
+        if !self
+            .captured
+            .iter()
+            .rev()
+            .take(8)
+            .any(|seen| seen.as_ptr() == Rc::as_ptr(scope))
+        {
+            self.captured.push(Rc::downgrade(scope));
+        }
+        Value::Function(Rc::new(Closure {
+            def: def.clone(),
+            scope: scope.clone(),
+        }))
+    }
+
+    fn eval(&mut self, expression: &Expr, scope: &Rc<Scope>) -> Eval<Value> {
+        self.step()?;
+        self.depth += 1;
+        let result = if self.depth > MAX_DEPTH {
+            Err("Script nested too deeply".to_owned())
+        } else {
+            self.eval_inner(expression, scope)
+        };
+        self.depth -= 1;
+        result
+    }
+
+    fn eval_inner(&mut self, expression: &Expr, scope: &Rc<Scope>) -> Eval<Value> {
+        Ok(match expression {
+            Expr::Number(value) => Value::Number(*value),
+            Expr::Str(text) => Value::Str(text.clone()),
+            Expr::Bool(value) => Value::Bool(*value),
+            Expr::Null => Value::Null,
+            Expr::Undefined => Value::Undefined,
+            Expr::Ident(name) => lookup(scope, name)
+                .ok_or_else(|| format!("ReferenceError: {name} is not defined"))?,
+            Expr::Array(items) => {
+                let mut values = Vec::with_capacity(items.len());
+                for item in items {
+                    values.push(self.eval(item, scope)?);
+                }
+                Value::Array(Rc::new(RefCell::new(values)))
+            }
+            Expr::Object(entries) => {
+                let mut values: Properties = Vec::with_capacity(entries.len());
+                for (key, value) in entries {
+                    let value = self.eval(value, scope)?;
+                    values.retain(|(existing, _)| existing != key);
+                    values.push((key.clone(), value));
+                }
+                Value::Object(Rc::new(RefCell::new(values)))
+            }
+            Expr::Function(def) => self.closure(def, scope),
+            Expr::Member(object, name) => {
+                let object = self.eval(object, scope)?;
+                self.get(&object, name)?
+            }
+            Expr::Index(object, key) => {
+                let object = self.eval(object, scope)?;
+                let key = self.eval(key, scope)?;
+                self.get_index(&object, &key)?
+            }
+            Expr::Call(callee, args) => {
+                let receiver = match callee.as_ref() {
+                    Expr::Member(object, name) => Some((self.eval(object, scope)?, name.clone())),
+                    Expr::Index(object, key) => {
+                        let object = self.eval(object, scope)?;
+                        let key = self.eval(key, scope)?;
+                        Some((object, Rc::from(to_string(&key))))
+                    }
+                    _ => None,
+                };
+                let function = match &receiver {
+                    None => Some(self.eval(callee, scope)?),
+                    Some(_) => None,
+                };
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    values.push(self.eval(arg, scope)?);
+                }
+                match (receiver, function) {
+                    (Some((object, name)), _) => self.call_method(&object, &name, values)?,
+                    (None, Some(function)) => self.call(&function, values)?,
+                    (None, None) => Value::Undefined,
+                }
+            }
+            Expr::Unary(operator, operand) => {
+                if *operator == UnaryOp::Typeof
+                    && let Expr::Ident(name) = operand.as_ref()
+                    && lookup(scope, name).is_none()
+                {
+                    return Ok(Value::Str(Rc::from("undefined")));
+                }
+                let value = self.eval(operand, scope)?;
+                match operator {
+                    UnaryOp::Neg => Value::Number(-to_number(&value)),
+                    UnaryOp::Plus => Value::Number(to_number(&value)),
+                    UnaryOp::Not => Value::Bool(!truthy(&value)),
+                    UnaryOp::Typeof => Value::Str(Rc::from(type_of(&value))),
+                }
+            }
+            Expr::Binary(operator, left, right) => {
+                let left = self.eval(left, scope)?;
+                let right = self.eval(right, scope)?;
+                binary(*operator, &left, &right)?
+            }
+            Expr::Logical(operator, left, right) => {
+                let left = self.eval(left, scope)?;
+                let short_circuits = match operator {
+                    LogicalOp::And => !truthy(&left),
+                    LogicalOp::Or => truthy(&left),
+                    LogicalOp::Nullish => !matches!(left, Value::Undefined | Value::Null),
+                };
+                if short_circuits {
+                    left
+                } else {
+                    self.eval(right, scope)?
+                }
+            }
+            Expr::Conditional(test, then, otherwise) => {
+                if truthy(&self.eval(test, scope)?) {
+                    self.eval(then, scope)?
+                } else {
+                    self.eval(otherwise, scope)?
+                }
+            }
+            Expr::Assign(operator, target, value) => {
+                let place = self.place(target, scope)?;
+                let value = match operator {
+                    Some(operator) => {
+                        let current = self.read(&place, scope)?;
+                        let value = self.eval(value, scope)?;
+                        binary(*operator, &current, &value)?
+                    }
+                    None => self.eval(value, scope)?,
+                };
+                self.write(&place, value.clone(), scope)?;
+                value
+            }
+            Expr::Update {
+                increment,
+                prefix,
+                target,
+            } => {
+                let place = self.place(target, scope)?;
+                let old = to_number(&self.read(&place, scope)?);
+                let new = if *increment { old + 1.0 } else { old - 1.0 };
+                self.write(&place, Value::Number(new), scope)?;
+                Value::Number(if *prefix { new } else { old })
+            }
+            Expr::Sequence(items) => {
+                let mut last = Value::Undefined;
+                for item in items {
+                    last = self.eval(item, scope)?;
+                }
+                last
+            }
+        })
+    }
+
+    fn place(&mut self, target: &Expr, scope: &Rc<Scope>) -> Eval<Place> {
+        Ok(match target {
+            Expr::Ident(name) => Place::Var(name.clone()),
+            Expr::Member(object, name) => Place::Property(self.eval(object, scope)?, name.clone()),
+            Expr::Index(object, key) => {
+                let object = self.eval(object, scope)?;
+                let key = self.eval(key, scope)?;
+                Place::Property(object, Rc::from(to_string(&key)))
+            }
+            _ => return Err("Invalid assignment target".to_owned()),
+        })
+    }
+
+    fn read(&mut self, place: &Place, scope: &Rc<Scope>) -> Eval<Value> {
+        match place {
+            Place::Var(name) => {
+                lookup(scope, name).ok_or_else(|| format!("ReferenceError: {name} is not defined"))
+            }
+            Place::Property(object, name) => self.get(object, name),
+        }
+    }
+
+    fn write(&mut self, place: &Place, value: Value, scope: &Rc<Scope>) -> Eval<()> {
+        match place {
+            Place::Var(name) => {
+                if !assign(scope, name, value.clone()) {
+                    self.globals.vars.borrow_mut().insert(name.clone(), value);
+                }
+                Ok(())
+            }
+            Place::Property(object, name) => self.set(object, name, value),
+        }
+    }
+
+    pub(super) fn call(&mut self, function: &Value, args: Vec<Value>) -> Eval<Value> {
+        match function {
+            Value::Function(closure) => {
+                let scope = Scope::child(&closure.scope, true);
+                {
+                    let mut vars = scope.vars.borrow_mut();
+                    let mut args = args.into_iter();
+                    for param in &closure.def.params {
+                        vars.insert(param.clone(), args.next().unwrap_or(Value::Undefined));
+                    }
+                }
+                match &closure.def.body {
+                    FunctionBody::Expr(expression) => self.eval(expression, &scope),
+                    FunctionBody::Block(statements) => match self.exec_block(statements, &scope)? {
+                        Flow::Return(value) => Ok(value),
+                        _ => Ok(Value::Undefined),
+                    },
+                }
+            }
+            Value::Host(Host::Builtin(name)) => self.call_builtin(name, args),
+            _ => Err(format!(
+                "TypeError: {} is not a function",
+                type_of(function)
+            )),
+        }
+    }
+
+    fn call_builtin(&mut self, name: &str, args: Vec<Value>) -> Eval<Value> {
+        let first = args.first().cloned().unwrap_or(Value::Undefined);
+        Ok(match name {
+            "parseInt" => {
+                let text = to_string(&first);
+                let text = text.trim_start();
+                let (sign, digits) = match text.strip_prefix('-') {
+                    Some(rest) => (-1.0, rest),
+                    None => (1.0, text.strip_prefix('+').unwrap_or(text)),
+                };
+                let len = digits
+                    .find(|ch: char| !ch.is_ascii_digit())
+                    .unwrap_or(digits.len());
+                Value::Number(
+                    digits[..len]
+                        .parse::<f64>()
+                        .map_or(f64::NAN, |value| sign * value),
+                )
+            }
+            "parseFloat" => Value::Number(parse_float_prefix(&to_string(&first))),
+            "Number" => Value::Number(if args.is_empty() {
+                0.0
+            } else {
+                to_number(&first)
+            }),
+            "String" => Value::Str(Rc::from(to_string(&first))),
+            "Boolean" => Value::Bool(truthy(&first)),
+            "isNaN" => Value::Bool(to_number(&first).is_nan()),
+            "isFinite" => Value::Bool(to_number(&first).is_finite()),
+            "requestAnimationFrame" | "setTimeout" | "setInterval" => {
+                self.defer(first);
+                Value::Number(self.deferred.len() as f64)
+            }
+            _ => Value::Undefined,
+        })
+    }
+
+    fn get(&mut self, object: &Value, name: &str) -> Eval<Value> {
+        Ok(match object {
+            Value::Undefined | Value::Null => {
+                return Err(format!(
+                    "TypeError: cannot read {name:?} of {}",
+                    to_string(object)
+                ));
+            }
+            Value::Str(text) if name == "length" => Value::Number(text.chars().count() as f64),
+            Value::Array(items) if name == "length" => Value::Number(items.borrow().len() as f64),
+            Value::Object(entries) => entries
+                .borrow()
+                .iter()
+                .find(|(key, _)| key.as_ref() == name)
+                .map_or(Value::Undefined, |(_, value)| value.clone()),
+            Value::Host(Host::Math) => Value::Number(match name {
+                "PI" => std::f64::consts::PI,
+                "E" => std::f64::consts::E,
+                "SQRT2" => std::f64::consts::SQRT_2,
+                "LN2" => std::f64::consts::LN_2,
+                "LN10" => std::f64::consts::LN_10,
+                _ => return Ok(Value::Undefined),
+            }),
+            Value::Host(Host::Window) => match name {
+                "devicePixelRatio" => Value::Number(1.0),
+                _ => lookup(&self.globals, name).unwrap_or(Value::Undefined),
+            },
+            Value::Host(Host::Document) => self.document_property(name)?,
+            Value::Host(Host::Element(path)) => self.element_property(path, name)?,
+            Value::Host(Host::ClassList(path)) if name == "length" => Value::Number(
+                self.document
+                    .element_at(path)
+                    .map_or(0, |el| el.attributes.classes.len()) as f64,
+            ),
+            Value::Host(Host::Style(path)) => self.style_property(path, name),
+            Value::Host(Host::Context(index)) => self.context_property(*index, name),
+            _ => Value::Undefined,
+        })
+    }
+
+    fn get_index(&mut self, object: &Value, key: &Value) -> Eval<Value> {
+        if let Value::Number(index) = key {
+            match object {
+                Value::Array(items) => {
+                    return Ok(array_index(*index, items.borrow().len())
+                        .map_or(Value::Undefined, |index| items.borrow()[index].clone()));
+                }
+                Value::Str(text) => {
+                    return Ok(array_index(*index, usize::MAX)
+                        .and_then(|index| text.chars().nth(index))
+                        .map_or(Value::Undefined, |ch| Value::Str(Rc::from(ch.to_string()))));
+                }
+                _ => {}
+            }
+        }
+        self.get(object, &to_string(key))
+    }
+
+    fn set(&mut self, object: &Value, name: &str, value: Value) -> Eval<()> {
+        match object {
+            Value::Undefined | Value::Null => {
+                return Err(format!(
+                    "TypeError: cannot set {name:?} of {}",
+                    to_string(object)
+                ));
+            }
+            Value::Object(entries) => {
+                let mut entries = entries.borrow_mut();
+                match entries.iter_mut().find(|(key, _)| key.as_ref() == name) {
+                    Some((_, slot)) => *slot = value,
+                    None => entries.push((Rc::from(name), value)),
+                }
+            }
+            Value::Array(items) => {
+                if let Ok(index) = name.parse::<usize>()
+                    && index < MAX_ARRAY_LEN
+                {
+                    let mut items = items.borrow_mut();
+                    if index >= items.len() {
+                        items.resize(index + 1, Value::Undefined);
+                    }
+                    items[index] = value;
+                }
+            }
+            Value::Host(Host::Window) => {
+                if name == "onload" {
+                    self.defer(value);
+                } else {
+                    self.globals.vars.borrow_mut().insert(Rc::from(name), value);
+                }
+            }
+            Value::Host(Host::Element(path)) => self.set_element_property(path, name, &value)?,
+            Value::Host(Host::Style(path)) => self.set_style_property(path, name, &value),
+            Value::Host(Host::Context(index)) => self.set_context_property(*index, name, &value),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn call_method(&mut self, object: &Value, name: &str, args: Vec<Value>) -> Eval<Value> {
+        let first = args.first().cloned().unwrap_or(Value::Undefined);
+        match object {
+            Value::Array(items) => return self.array_method(items, name, args),
+            Value::Number(value) => match name {
+                "toFixed" => {
+                    let digits = to_number(&first);
+                    let digits = if digits.is_finite() {
+                        digits.clamp(0.0, 100.0) as usize
+                    } else {
+                        0
+                    };
+                    return Ok(Value::Str(Rc::from(format!("{value:.digits$}"))));
+                }
+                "toString" => return Ok(Value::Str(Rc::from(format_number(*value)))),
+                _ => {}
+            },
+            Value::Str(text) => match name {
+                "toUpperCase" => return Ok(Value::Str(Rc::from(text.to_uppercase()))),
+                "toLowerCase" => return Ok(Value::Str(Rc::from(text.to_lowercase()))),
+                "trim" => return Ok(Value::Str(Rc::from(text.trim()))),
+                "toString" => return Ok(object.clone()),
+                _ => {}
+            },
+            Value::Host(Host::Math) => return Ok(Value::Number(self.math(name, &args)?)),
+            Value::Host(Host::Json) => return self.json_method(name, &args),
+            Value::Host(Host::Console) => return Ok(Value::Undefined),
+            Value::Host(Host::Window) => match name {
+                "addEventListener" => {
+                    if matches!(to_string(&first).as_str(), "load" | "DOMContentLoaded") {
+                        self.defer(args.get(1).cloned().unwrap_or(Value::Undefined));
+                    }
+                    return Ok(Value::Undefined);
+                }
+                _ => {
+                    let function = lookup(&self.globals, name).unwrap_or(Value::Undefined);
+                    return self.call(&function, args);
+                }
+            },
+            Value::Host(Host::Document) => match name {
+                "addEventListener" => {
+                    if to_string(&first) == "DOMContentLoaded" {
+                        self.defer(args.get(1).cloned().unwrap_or(Value::Undefined));
+                    }
+                    return Ok(Value::Undefined);
+                }
+                _ => return self.document_method(name, &args),
+            },
+            Value::Host(Host::Gradient(color)) if name == "addColorStop" => {
+                let mut color = color.borrow_mut();
+                if color.is_none() {
+                    *color = Some(Rc::from(to_string(
+                        args.get(1).unwrap_or(&Value::Undefined),
+                    )));
+                }
+                return Ok(Value::Undefined);
+            }
+            Value::Host(Host::Element(path)) => return self.element_method(path, name, &args),
+            Value::Host(Host::ClassList(path)) => return self.class_list_method(path, name, &args),
+            Value::Host(Host::Style(path)) => return self.style_method(path, name, &args),
+            Value::Host(Host::Context(index)) => return self.context_method(*index, name, &args),
+            Value::Object(_) => {
+                let function = self.get(object, name)?;
+                return self.call(&function, args);
+            }
+            _ => {}
+        }
+        Err(format!(
+            "TypeError: {name} is not a function on {}",
+            type_of(object)
+        ))
+    }
+
+    fn array_method(
+        &mut self,
+        items: &Rc<RefCell<Vec<Value>>>,
+        name: &str,
+        args: Vec<Value>,
+    ) -> Eval<Value> {
+        let first = args.first().cloned().unwrap_or(Value::Undefined);
+        let snapshot = || items.borrow().clone();
+        Ok(match name {
+            "push" => {
+                let mut items = items.borrow_mut();
+                if items.len() + args.len() > MAX_ARRAY_LEN {
+                    return Err("Array grew past its size limit".to_owned());
+                }
+                items.extend(args);
+                Value::Number(items.len() as f64)
+            }
+            "pop" => items.borrow_mut().pop().unwrap_or(Value::Undefined),
+            "forEach" | "map" | "filter" => {
+                let mut out = Vec::new();
+                for (index, item) in snapshot().into_iter().enumerate() {
+                    let result =
+                        self.call(&first, vec![item.clone(), Value::Number(index as f64)])?;
+                    match name {
+                        "map" => out.push(result),
+                        "filter" if truthy(&result) => out.push(item),
+                        _ => {}
+                    }
+                }
+                if name == "forEach" {
+                    Value::Undefined
+                } else {
+                    Value::Array(Rc::new(RefCell::new(out)))
+                }
+            }
+            "reduce" => {
+                let mut values = snapshot().into_iter().enumerate();
+                let mut accumulator = match args.get(1) {
+                    Some(initial) => initial.clone(),
+                    None => match values.next() {
+                        Some((_, value)) => value,
+                        None => return Err("TypeError: reduce of empty array".to_owned()),
+                    },
+                };
+                for (index, item) in values {
+                    accumulator =
+                        self.call(&first, vec![accumulator, item, Value::Number(index as f64)])?;
+                }
+                accumulator
+            }
+            "join" => {
+                let separator = match &first {
+                    Value::Undefined => ",".to_owned(),
+                    other => to_string(other),
+                };
+                let joined = snapshot()
+                    .iter()
+                    .map(to_string)
+                    .collect::<Vec<_>>()
+                    .join(&separator);
+                checked_string(joined)?
+            }
+            "indexOf" | "includes" => {
+                let position = snapshot()
+                    .iter()
+                    .position(|item| strict_equals(item, &first));
+                if name == "includes" {
+                    Value::Bool(position.is_some())
+                } else {
+                    Value::Number(position.map_or(-1.0, |index| index as f64))
+                }
+            }
+            "slice" => {
+                let items = snapshot();
+                let len = items.len() as f64;
+                let bound = |value: Option<&Value>, default: f64| {
+                    let value = value.map_or(default, to_number);
+                    let value = if value < 0.0 { len + value } else { value };
+                    if value.is_nan() {
+                        0
+                    } else {
+                        value.clamp(0.0, len) as usize
+                    }
+                };
+                let start = bound(args.first(), 0.0);
+                let end = bound(args.get(1), len).max(start);
+                Value::Array(Rc::new(RefCell::new(items[start..end].to_vec())))
+            }
+            _ => return Err(format!("TypeError: {name} is not a function on arrays")),
+        })
+    }
+
+    fn math(&mut self, name: &str, args: &[Value]) -> Eval<f64> {
+        let arg = |index: usize| args.get(index).map_or(f64::NAN, to_number);
+        Ok(match name {
+            "abs" => arg(0).abs(),
+            "floor" => arg(0).floor(),
+            "ceil" => arg(0).ceil(),
+            "round" => (arg(0) + 0.5).floor(),
+            "trunc" => arg(0).trunc(),
+            "sign" => {
+                let value = arg(0);
+                if value == 0.0 || value.is_nan() {
+                    value
+                } else {
+                    value.signum()
+                }
+            }
+            "sqrt" => arg(0).sqrt(),
+            "pow" => arg(0).powf(arg(1)),
+            "exp" => arg(0).exp(),
+            "log" => arg(0).ln(),
+            "sin" => arg(0).sin(),
+            "cos" => arg(0).cos(),
+            "tan" => arg(0).tan(),
+            "atan" => arg(0).atan(),
+            "atan2" => arg(0).atan2(arg(1)),
+            "hypot" => args
+                .iter()
+                .map(to_number)
+                .map(|v| v * v)
+                .sum::<f64>()
+                .sqrt(),
+            "min" => args.iter().map(to_number).fold(f64::INFINITY, |min, v| {
+                if v.is_nan() || v < min { v } else { min }
+            }),
+            "max" => args
+                .iter()
+                .map(to_number)
+                .fold(f64::NEG_INFINITY, |max, v| {
+                    if v.is_nan() || v > max { v } else { max }
+                }),
+            "random" => {
+                // xorshift, seeded the same way on every load so pages render reproducibly.
+                self.random_state ^= self.random_state << 13;
+                self.random_state ^= self.random_state >> 7;
+                self.random_state ^= self.random_state << 17;
+                (self.random_state >> 11) as f64 / (1u64 << 53) as f64
+            }
+            _ => return Err(format!("TypeError: Math.{name} is not a function")),
+        })
+    }
+}
+
+impl Drop for Interpreter<'_> {
+    fn drop(&mut self) {
+        for scope in self.captured.drain(..) {
+            if let Some(scope) = scope.upgrade() {
+                scope.vars.take();
+            }
+        }
+    }
+}
+
+fn log_error(err: &str) {
+    if debug::enabled(debug::Target::Js, debug::Level::Warn) {
+        let err = debug::shorten(err, 96);
+        debug::log(
+            debug::Target::Js,
+            debug::Level::Warn,
+            format_args!("script! err={err}"),
+        );
+    }
+}
+
+/// Running out of budget stops the whole page's scripts, so no `catch` may resume them.
+fn catchable(err: &str) -> bool {
+    !matches!(
+        err,
+        "Script exceeded its step budget" | "Script nested too deeply"
+    )
+}
+
+/// How an uncaught thrown value reads in the log: `name: message` for error objects.
+fn error_text(value: &Value) -> String {
+    if let Value::Object(entries) = value {
+        let entries = entries.borrow();
+        let field = |key: &str| {
+            entries
+                .iter()
+                .find(|(name, _)| name.as_ref() == key)
+                .map(|(_, value)| to_string(value))
+        };
+        if let Some(message) = field("message") {
+            return match field("name") {
+                Some(name) => format!("{name}: {message}"),
+                None => message,
+            };
+        }
+    }
+    to_string(value)
+}
+
+fn function_scope(scope: &Rc<Scope>) -> Rc<Scope> {
+    let mut current = scope.clone();
+    while !current.function {
+        match &current.parent {
+            Some(parent) => current = parent.clone(),
+            None => break,
+        }
+    }
+    current
+}
+
+fn lookup(scope: &Rc<Scope>, name: &str) -> Option<Value> {
+    let mut current = Some(scope);
+    while let Some(scope) = current {
+        if let Some(value) = scope.vars.borrow().get(name) {
+            return Some(value.clone());
+        }
+        current = scope.parent.as_ref();
+    }
+    None
+}
+
+fn assign(scope: &Rc<Scope>, name: &str, value: Value) -> bool {
+    let mut current = Some(scope);
+    while let Some(scope) = current {
+        if let Some(slot) = scope.vars.borrow_mut().get_mut(name) {
+            *slot = value;
+            return true;
+        }
+        current = scope.parent.as_ref();
+    }
+    false
+}
+
+fn array_index(index: f64, len: usize) -> Option<usize> {
+    (index >= 0.0 && index.fract() == 0.0 && index < len as f64).then_some(index as usize)
+}
+
+pub(super) fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Undefined | Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(value) => *value != 0.0 && !value.is_nan(),
+        Value::Str(text) => !text.is_empty(),
+        _ => true,
+    }
+}
+
+pub(super) fn to_number(value: &Value) -> f64 {
+    match value {
+        Value::Null => 0.0,
+        Value::Bool(value) => f64::from(u8::from(*value)),
+        Value::Number(value) => *value,
+        Value::Str(text) => {
+            let text = text.trim();
+            if text.is_empty() {
+                0.0
+            } else {
+                text.parse().unwrap_or(f64::NAN)
+            }
+        }
+        _ => f64::NAN,
+    }
+}
+
+pub(super) fn to_string(value: &Value) -> String {
+    stringify(value, 0)
+}
+
+/// Nested arrays past a few levels, which may be cycles, print as empty.
+fn stringify(value: &Value, depth: usize) -> String {
+    match value {
+        Value::Undefined => "undefined".to_owned(),
+        Value::Null => "null".to_owned(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => format_number(*value),
+        Value::Str(text) => text.to_string(),
+        Value::Array(_) if depth >= 8 => String::new(),
+        Value::Array(items) => items
+            .borrow()
+            .iter()
+            .map(|item| match item {
+                Value::Undefined | Value::Null => String::new(),
+                other => stringify(other, depth + 1),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Object(_) | Value::Host(_) => "[object Object]".to_owned(),
+        Value::Function(_) => "function".to_owned(),
+    }
+}
+
+pub(super) fn format_number(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_owned()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.to_owned()
+    } else if value == 0.0 {
+        "0".to_owned()
+    } else {
+        value.to_string()
+    }
+}
+
+fn parse_float_prefix(text: &str) -> f64 {
+    let text = text.trim_start();
+    (1..=text.len())
+        .rev()
+        .filter(|len| text.is_char_boundary(*len))
+        .find_map(|len| {
+            let prefix = &text[..len];
+            prefix
+                .ends_with(|ch: char| ch.is_ascii_digit() || ch == '.')
+                .then(|| prefix.parse::<f64>().ok())
+                .flatten()
+        })
+        .unwrap_or(f64::NAN)
+}
+
+fn type_of(value: &Value) -> &'static str {
+    match value {
+        Value::Undefined => "undefined",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::Str(_) => "string",
+        Value::Function(_) | Value::Host(Host::Builtin(_)) => "function",
+        _ => "object",
+    }
+}
+
+pub(super) fn checked_string(text: String) -> Eval<Value> {
+    if text.len() > MAX_STRING_CHARS {
+        return Err("String grew past its size limit".to_owned());
+    }
+    Ok(Value::Str(Rc::from(text)))
+}
+
+fn strict_equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Undefined, Value::Undefined) | (Value::Null, Value::Null) => true,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Number(a), Value::Number(b)) => a == b,
+        (Value::Str(a), Value::Str(b)) => a == b,
+        (Value::Array(a), Value::Array(b)) => Rc::ptr_eq(a, b),
+        (Value::Object(a), Value::Object(b)) => Rc::ptr_eq(a, b),
+        (Value::Function(a), Value::Function(b)) => Rc::ptr_eq(a, b),
+        (Value::Host(Host::Element(a)), Value::Host(Host::Element(b))) => a == b,
+        (Value::Host(Host::ClassList(a)), Value::Host(Host::ClassList(b))) => a == b,
+        (Value::Host(Host::Style(a)), Value::Host(Host::Style(b))) => a == b,
+        (Value::Host(Host::Context(a)), Value::Host(Host::Context(b))) => a == b,
+        _ => false,
+    }
+}
+
+fn loose_equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Undefined | Value::Null, Value::Undefined | Value::Null) => true,
+        (Value::Undefined | Value::Null, _) | (_, Value::Undefined | Value::Null) => false,
+        (Value::Number(_) | Value::Str(_) | Value::Bool(_), Value::Number(_) | Value::Bool(_))
+        | (Value::Number(_) | Value::Bool(_), Value::Str(_)) => to_number(left) == to_number(right),
+        _ => strict_equals(left, right),
+    }
+}
+
+fn binary(operator: BinaryOp, left: &Value, right: &Value) -> Eval<Value> {
+    let is_text = |value: &Value| {
+        !matches!(
+            value,
+            Value::Undefined | Value::Null | Value::Bool(_) | Value::Number(_)
+        )
+    };
+    Ok(match operator {
+        BinaryOp::Add if is_text(left) || is_text(right) => {
+            let left = to_string(left);
+            let right = to_string(right);
+            if left.len() + right.len() > MAX_STRING_CHARS {
+                return Err("String grew past its size limit".to_owned());
+            }
+            Value::Str(Rc::from(left + &right))
+        }
+        BinaryOp::Add => Value::Number(to_number(left) + to_number(right)),
+        BinaryOp::Sub => Value::Number(to_number(left) - to_number(right)),
+        BinaryOp::Mul => Value::Number(to_number(left) * to_number(right)),
+        BinaryOp::Div => Value::Number(to_number(left) / to_number(right)),
+        BinaryOp::Rem => Value::Number(to_number(left) % to_number(right)),
+        BinaryOp::Less | BinaryOp::Greater | BinaryOp::LessEq | BinaryOp::GreaterEq => {
+            let ordering = match (left, right) {
+                (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+                _ => to_number(left).partial_cmp(&to_number(right)),
+            };
+            Value::Bool(ordering.is_some_and(|ordering| match operator {
+                BinaryOp::Less => ordering.is_lt(),
+                BinaryOp::Greater => ordering.is_gt(),
+                BinaryOp::LessEq => ordering.is_le(),
+                _ => ordering.is_ge(),
+            }))
+        }
+        BinaryOp::Eq => Value::Bool(loose_equals(left, right)),
+        BinaryOp::NotEq => Value::Bool(!loose_equals(left, right)),
+        BinaryOp::StrictEq => Value::Bool(strict_equals(left, right)),
+        BinaryOp::StrictNotEq => Value::Bool(!strict_equals(left, right)),
+    })
+}
@@ -0,0 +1,322 @@
+use super::interp::{
+    Eval, Host, Interpreter, MAX_ARRAY_LEN, MAX_STRING_CHARS, Value, checked_string, format_number,
+    to_string,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Deepest nesting `JSON.parse` and `JSON.stringify` walk; deeper values, which for
+/// `stringify` may be cycles, are errors.
+const MAX_JSON_DEPTH: usize = 64;
+/// Bytes of JSON text read or written per step of the budget.
+const JSON_BYTES_PER_STEP: u64 = 64;
+
+impl Interpreter<'_> {
+    pub(super) fn json_method(&mut self, name: &str, args: &[Value]) -> Eval<Value> {
+        let first = args.first().cloned().unwrap_or(Value::Undefined);
+        match name {
+            "parse" => {
+                let text = to_string(&first);
+                self.charge(text.len() as u64 / JSON_BYTES_PER_STEP)?;
+                let mut parser = JsonParser {
+                    text: &text,
+                    pos: 0,
+                };
+                let value = parser.value(0)?;
+                parser.skip_whitespace();
+                if parser.pos < text.len() {
+                    return Err(parser.error());
+                }
+                Ok(value)
+            }
+            "stringify" => {
+                let mut out = String::new();
+                if !write_json(&mut out, &first, 0)? {
+                    return Ok(Value::Undefined);
+                }
+                self.charge(out.len() as u64 / JSON_BYTES_PER_STEP)?;
+                checked_string(out)
+            }
+            _ => Err(format!("TypeError: JSON.{name} is not a function")),
+        }
+    }
+}
+
+struct JsonParser<'t> {
+    text: &'t str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self) -> String {
+        format!(
+            "SyntaxError: JSON.parse: unexpected input at offset {}",
+            self.pos
+        )
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let matched = self.peek() == Some(byte);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn value(&mut self, depth: usize) -> Eval<Value> {
+        if depth > MAX_JSON_DEPTH {
+            return Err("SyntaxError: JSON.parse: nested too deeply".to_owned());
+        }
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries: Vec<(Rc<str>, Value)> = Vec::new();
+                if !self.eat(b'}') {
+                    loop {
+                        self.skip_whitespace();
+                        if self.peek() != Some(b'"') {
+                            return Err(self.error());
+                        }
+                        let key: Rc<str> = Rc::from(self.string()?);
+                        if !self.eat(b':') {
+                            return Err(self.error());
+                        }
+                        let value = self.value(depth + 1)?;
+                        match entries.iter_mut().find(|(existing, _)| *existing == key) {
+                            Some((_, slot)) => *slot = value,
+                            None => entries.push((key, value)),
+                        }
+                        if self.eat(b'}') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return Err(self.error());
+                        }
+                    }
+                }
+                Ok(Value::Object(Rc::new(RefCell::new(entries))))
+            }
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat(b']') {
+                    loop {
+                        if items.len() >= MAX_ARRAY_LEN {
+                            return Err("Array grew past its size limit".to_owned());
+                        }
+                        items.push(self.value(depth + 1)?);
+                        if self.eat(b']') {
+                            break;
+                        }
+                        if !self.eat(b',') {
+                            return Err(self.error());
+                        }
+                    }
+                }
+                Ok(Value::Array(Rc::new(RefCell::new(items))))
+            }
+            Some(b'"') => Ok(Value::Str(Rc::from(self.string()?))),
+            Some(b't') => self.literal("true", Value::Bool(true)),
+            Some(b'f') => self.literal("false", Value::Bool(false)),
+            Some(b'n') => self.literal("null", Value::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Eval<Value> {
+        if !self.text[self.pos..].starts_with(word) {
+            return Err(self.error());
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Eval<Value> {
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let from = parser.pos;
+            while matches!(parser.peek(), Some(b'0'..=b'9')) {
+                parser.pos += 1;
+            }
+            parser.pos > from
+        };
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'0') {
+            self.pos += 1;
+        } else if !digits(self) {
+            return Err(self.error());
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !digits(self) {
+                return Err(self.error());
+            }
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                return Err(self.error());
+            }
+        }
+        self.text[start..self.pos]
+            .parse()
+            .map(Value::Number)
+            .map_err(|_| self.error())
+    }
+
+    /// Reads a string starting at its opening quote.
+    fn string(&mut self) -> Eval<String> {
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let Some(ch) = self.text[self.pos..].chars().next() else {
+                return Err(self.error());
+            };
+            self.pos += ch.len_utf8();
+            match ch {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            out.push(self.unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error()),
+                    };
+                    self.pos += 1;
+                    out.push(escaped);
+                }
+                '\u{0}'..='\u{1f}' => return Err(self.error()),
+                _ => out.push(ch),
+            }
+        }
+    }
+
+    /// Reads the hex digits after `\u`, pairing surrogates; an unpaired one reads as U+FFFD.
+    fn unicode_escape(&mut self) -> Eval<char> {
+        let high = self.hex4()?;
+        if !(0xd800..=0xdbff).contains(&high) {
+            return Ok(char::from_u32(high).unwrap_or('\u{fffd}'));
+        }
+        if !self.text[self.pos..].starts_with("\\u") {
+            return Ok('\u{fffd}');
+        }
+        let resume = self.pos;
+        self.pos += 2;
+        let low = self.hex4()?;
+        if !(0xdc00..=0xdfff).contains(&low) {
+            self.pos = resume;
+            return Ok('\u{fffd}');
+        }
+        Ok(
+            char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+                .unwrap_or('\u{fffd}'),
+        )
+    }
+
+    fn hex4(&mut self) -> Eval<u32> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error())?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap_or(0xfffd))
+    }
+}
+
+/// Writes `value` as JSON, returning false for values JSON has no form for (`undefined` and
+/// functions), which objects skip and arrays write as `null`.
+fn write_json(out: &mut String, value: &Value, depth: usize) -> Eval<bool> {
+    if depth > MAX_JSON_DEPTH {
+        return Err("TypeError: JSON.stringify: value nested too deeply or cyclic".to_owned());
+    }
+    if out.len() > MAX_STRING_CHARS {
+        return Err("String grew past its size limit".to_owned());
+    }
+    match value {
+        Value::Undefined | Value::Function(_) | Value::Host(Host::Builtin(_)) => return Ok(false),
+        Value::Null => out.push_str("null"),
+        Value::Bool(value) => out.push_str(if *value { "true" } else { "false" }),
+        Value::Number(value) if value.is_finite() => out.push_str(&format_number(*value)),
+        Value::Number(_) => out.push_str("null"),
+        Value::Str(text) => write_json_string(out, text),
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.borrow().iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                if !write_json(out, item, depth + 1)? {
+                    out.push_str("null");
+                }
+            }
+            out.push(']');
+        }
+        Value::Object(entries) => {
+            out.push('{');
+            let mut first = true;
+            for (key, item) in entries.borrow().iter() {
+                let mark = out.len();
+                if !first {
+                    out.push(',');
+                }
+                write_json_string(out, key);
+                out.push(':');
+                if write_json(out, item, depth + 1)? {
+                    first = false;
+                } else {
+                    out.truncate(mark);
+                }
+            }
+            out.push('}');
+        }
+        Value::Host(_) => out.push_str("{}"),
+    }
+    Ok(true)
+}
+
+fn write_json_string(out: &mut String, text: &str) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\u{0}'..='\u{1f}' => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            _ => out.push(ch),
+        }
+    }
+    out.push('"');
+}
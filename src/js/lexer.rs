@@ -0,0 +1,235 @@
+use super::{is_js_identifier_char, is_js_identifier_start_char, parse_js_string_literal};
+
+/// Longest first, so `===` is not read as `==` followed by `=`.
+const PUNCTUATORS: &[&str] = &[
+    "===", "!==", "=>", "==", "!=", "<=", ">=", "&&", "||", "??", "++", "--", "+=", "-=", "*=",
+    "/=", "%=", "{", "}", "(", ")", "[", "]", ";", ",", ".", "?", ":", "+", "-", "*", "/", "%",
+    "<", ">", "=", "!",
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Token {
+    Number(f64),
+    Str(String),
+    /// A template literal: `strings` has one more entry than `expressions`, whose source text is
+    /// parsed separately.
+    Template {
+        strings: Vec<String>,
+        expressions: Vec<String>,
+    },
+    Ident(String),
+    Punct(&'static str),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Lexeme {
+    pub(super) token: Token,
+    /// Whether a line break precedes the token, which lets a statement end without `;`.
+    pub(super) newline_before: bool,
+}
+
+pub(super) fn tokenize(source: &str) -> Result<Vec<Lexeme>, String> {
+    let mut out = Vec::new();
+    let mut cursor = 0usize;
+    let mut newline_before = false;
+
+    while let Some(ch) = source[cursor..].chars().next() {
+        if ch.is_whitespace() {
+            newline_before |= ch == '\n' || ch == '\r';
+            cursor += ch.len_utf8();
+            continue;
+        }
+        let rest = &source[cursor..];
+        if rest.starts_with("//") {
+            cursor += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if let Some(comment) = rest.strip_prefix("/*") {
+            let end = comment.find("*/").ok_or("Unterminated comment in script")?;
+            newline_before |= rest[..end + 2].contains('\n');
+            cursor += end + 4;
+            continue;
+        }
+
+        let token = if ch == '\'' || ch == '"' {
+            let (text, next) =
+                parse_js_string_literal(source, cursor).ok_or("Unterminated string in script")?;
+            cursor = next;
+            Token::Str(text)
+        } else if ch == '`' {
+            let (token, next) = template(source, cursor + 1)?;
+            cursor = next;
+            token
+        } else if ch.is_ascii_digit()
+            || (ch == '.' && rest[1..].starts_with(|next: char| next.is_ascii_digit()))
+        {
+            let (value, len) = number(rest)?;
+            cursor += len;
+            Token::Number(value)
+        } else if is_js_identifier_start_char(ch) {
+            let len = rest
+                .find(|ch: char| !is_js_identifier_char(ch))
+                .unwrap_or(rest.len());
+            cursor += len;
+            Token::Ident(rest[..len].to_owned())
+        } else {
+            let punct = PUNCTUATORS
+                .iter()
+                .find(|punct| rest.starts_with(**punct))
+                .ok_or_else(|| format!("Unexpected character {ch:?} in script"))?;
+            cursor += punct.len();
+            Token::Punct(punct)
+        };
+        out.push(Lexeme {
+            token,
+            newline_before,
+        });
+        newline_before = false;
+    }
+
+    Ok(out)
+}
+
+fn number(rest: &str) -> Result<(f64, usize), String> {
+    if let Some(hex) = rest.strip_prefix("0x").or_else(|| rest.strip_prefix("0X")) {
+        let len = hex
+            .find(|ch: char| !ch.is_ascii_hexdigit())
+            .unwrap_or(hex.len());
+        let value = u64::from_str_radix(&hex[..len], 16)
+            .map_err(|_| "Invalid hex number in script".to_owned())?;
+        return Ok((value as f64, len + 2));
+    }
+
+    let bytes = rest.as_bytes();
+    let mut len = 0;
+    let digits = |len: &mut usize| {
+        while bytes.get(*len).is_some_and(u8::is_ascii_digit) {
+            *len += 1;
+        }
+    };
+    digits(&mut len);
+    if bytes.get(len) == Some(&b'.') {
+        len += 1;
+        digits(&mut len);
+    }
+    if matches!(bytes.get(len), Some(b'e' | b'E')) {
+        let mut exponent = len + 1;
+        if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+            exponent += 1;
+        }
+        if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+            len = exponent;
+            digits(&mut len);
+        }
+    }
+    let value = rest[..len]
+        .parse()
+        .map_err(|_| format!("Invalid number {:?} in script", &rest[..len]))?;
+    Ok((value, len))
+}
+
+/// Reads a template literal starting just after its opening backtick.
+fn template(source: &str, start: usize) -> Result<(Token, usize), String> {
+    let mut strings = Vec::new();
+    let mut expressions = Vec::new();
+    let mut current = String::new();
+    let mut cursor = start;
+
+    loop {
+        let ch = source[cursor..]
+            .chars()
+            .next()
+            .ok_or("Unterminated template literal in script")?;
+        cursor += ch.len_utf8();
+        match ch {
+            '`' => {
+                strings.push(current);
+                return Ok((
+                    Token::Template {
+                        strings,
+                        expressions,
+                    },
+                    cursor,
+                ));
+            }
+            '\\' => {
+                let escaped = source[cursor..]
+                    .chars()
+                    .next()
+                    .ok_or("Unterminated template literal in script")?;
+                cursor += escaped.len_utf8();
+                current.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    other => other,
+                });
+            }
+            '$' if source[cursor..].starts_with('{') => {
+                let end = matching_brace(source, cursor)?;
+                expressions.push(source[cursor + 1..end].to_owned());
+                strings.push(std::mem::take(&mut current));
+                cursor = end + 1;
+            }
+            other => current.push(other),
+        }
+    }
+}
+
+/// Finds the `}` closing the `{` at `open`, skipping over string literals.
+fn matching_brace(source: &str, open: usize) -> Result<usize, String> {
+    let mut depth = 0usize;
+    let mut cursor = open;
+    while let Some(ch) = source[cursor..].chars().next() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(cursor);
+                }
+            }
+            '\'' | '"' => {
+                let (_, next) = parse_js_string_literal(source, cursor)
+                    .ok_or("Unterminated string in script")?;
+                cursor = next;
+                continue;
+            }
+            _ => {}
+        }
+        cursor += ch.len_utf8();
+    }
+    Err("Unterminated template expression in script".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenizes_numbers_strings_templates_and_punctuators() {
+        let tokens: Vec<Token> =
+            tokenize("x += .5e1; // note\ny === 'a\\'b' /* c */ `v=${n + 1}!`")
+                .unwrap()
+                .into_iter()
+                .map(|lexeme| lexeme.token)
+                .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Ident("x".to_owned()),
+                Token::Punct("+="),
+                Token::Number(5.0),
+                Token::Punct(";"),
+                Token::Ident("y".to_owned()),
+                Token::Punct("==="),
+                Token::Str("a'b".to_owned()),
+                Token::Template {
+                    strings: vec!["v=".to_owned(), "!".to_owned()],
+                    expressions: vec!["n + 1".to_owned()],
+                },
+            ]
+        );
+        assert!(tokenize("a # b").is_err());
+        assert!(tokenize("'open").is_err());
+    }
+}
@@ -1,29 +1,61 @@
+mod canvas;
+mod dom;
+mod interp;
+mod json;
+mod lexer;
+mod parser;
+
 use crate::dom::{Document, Element, Node};
+use interp::Interpreter;
 
 pub fn execute_inline_scripts(document: &mut Document) {
     let mut scripts = Vec::new();
     collect_inline_classic_scripts(&document.root, &mut scripts);
 
-    for source in scripts {
-        if let Some(classes) = parse_document_element_class_name_assignment(&source)
-            && !should_skip_root_class_assignment(document, &classes)
-            && let Some(html) = document.find_first_element_by_name_mut("html")
-        {
-            html.attributes.classes = classes.split_whitespace().map(str::to_owned).collect();
-        }
+    // Without the `js-engine` feature, only pages that draw on a canvas run through the
+    // interpreter, and the others get the pattern matchers instead.
+    if !cfg!(feature = "js-engine") && !scripts.iter().any(|source| source.contains("getContext")) {
+        for source in &scripts {
+            if let Some(classes) = parse_document_element_class_name_assignment(source)
+                && !should_skip_root_class_assignment(document, &classes)
+                && let Some(html) = document.find_first_element_by_name_mut("html")
+            {
+                html.attributes.classes = classes.split_whitespace().map(str::to_owned).collect();
+            }
 
-        for assignment in parse_text_content_assignments(&source) {
-            if let Some(element) = document.find_first_element_by_id_mut(&assignment.element_id) {
-                element.set_text_content(assignment.text);
+            for assignment in parse_text_content_assignments(source) {
+                if let Some(element) = document.find_first_element_by_id_mut(&assignment.element_id)
+                {
+                    element.set_text_content(assignment.text);
+                }
             }
         }
+    } else {
+        let mut interpreter = Interpreter::new(document);
+        for source in &scripts {
+            interpreter.run(source);
+        }
+        interpreter.finish();
     }
 
     inject_vector_appearance_fallback(document);
+    // Scripts run before the first layout, so what they changed is not a pending mutation.
+    document.take_mutations();
+}
+
+/// Runs the script parsers over `source` without executing anything, for the `js` fuzz target.
+#[cfg(feature = "fuzzing")]
+pub fn parse_for_fuzzing(source: &str) {
+    let _ = parser::parse_script(source);
+    let _ = parse_document_element_class_name_assignment(source);
+    let _ = parse_text_content_assignments(source);
 }
 
 fn should_skip_root_class_assignment(document: &Document, assigned_classes: &str) -> bool {
     // We intentionally keep server-rendered no-JS classes unless we have a full JS runtime.
+    if cfg!(feature = "js-engine") {
+        return false;
+    }
     let Some(html) = document.find_first_element_by_name("html") else {
         return false;
     };
@@ -594,6 +626,7 @@ mod tests {
         );
     }
 
+    #[cfg(not(feature = "js-engine"))]
     #[test]
     fn keeps_nojs_root_classes_when_inline_script_switches_to_client_js() {
         let html = r#"
@@ -696,4 +729,115 @@ mod tests {
         }
         false
     }
+
+    #[test]
+    fn draws_on_canvas_through_a_2d_context() {
+        let html = r#"
+<canvas id="chart" width="40" height="20"></canvas>
+<script>
+  const canvas = document.getElementById('chart');
+  const ctx = canvas.getContext('2d');
+  const values = [4, 8, 12];
+  ctx.fillStyle = '#0000ff';
+  for (let i = 0; i < values.length; i++) {
+    ctx.fillRect(i * 10, 20 - values[i], 8, values[i]);
+  }
+  ctx.fillStyle = 'black';
+  ctx.fillText(`total ${values.reduce((sum, v) => sum + v, 0)}`, 2, 10);
+</script>
+"#;
+        let mut document = crate::html::parse_document(html);
+        execute_inline_scripts(&mut document);
+
+        let surface = document
+            .canvases
+            .get("chart")
+            .expect("missing canvas surface");
+        let pixel = |x: u32, y: u32| {
+            let offset = ((y * surface.image.width + x) * 4) as usize;
+            surface.image.data[offset..offset + 4].to_vec()
+        };
+        assert_eq!((surface.image.width, surface.image.height), (40, 20));
+        assert_eq!(pixel(24, 10), vec![255, 0, 0, 255]);
+        assert_eq!(pixel(4, 10), vec![0, 0, 0, 0]);
+        assert_eq!(pixel(9, 19), vec![0, 0, 0, 0]);
+        assert_eq!(surface.texts.len(), 1);
+        assert_eq!(surface.texts[0].text, "total 24");
+        assert_eq!((surface.texts[0].x_px, surface.texts[0].y_px), (2, 10));
+    }
+
+    #[test]
+    fn stops_runaway_canvas_scripts() {
+        let html = r#"
+<canvas id="c"></canvas>
+<script>
+  const ctx = document.getElementById('c').getContext('2d');
+  ctx.fillRect(0, 0, 10, 10);
+  while (true) {}
+</script>
+"#;
+        let mut document = crate::html::parse_document(html);
+        execute_inline_scripts(&mut document);
+
+        let surface = document.canvases.get("c").expect("missing canvas surface");
+        assert_eq!((surface.image.width, surface.image.height), (300, 150));
+    }
+
+    #[cfg(feature = "js-engine")]
+    #[test]
+    fn runs_every_page_through_the_engine() {
+        let html = r#"
+<div class="tabs"><button class="tab">A</button><button class="tab">B</button></div>
+<script>
+  document.addEventListener('DOMContentLoaded', function () {
+    var tabs = document.getElementsByClassName('tab');
+    for (var i = 0; i < tabs.length; i++) {
+      tabs[i].setAttribute('aria-selected', i === 0 ? 'true' : 'false');
+    }
+  });
+</script>
+"#;
+        let mut document = crate::html::parse_document(html);
+        execute_inline_scripts(&mut document);
+
+        let tabs = document
+            .find_first_element_by_name("div")
+            .expect("missing tabs");
+        let selected: Vec<Option<&str>> = tabs
+            .children
+            .iter()
+            .filter_map(|child| match child {
+                Node::Element(tab) => Some(tab.attributes.get("aria-selected")),
+                Node::Text(_) => None,
+            })
+            .collect();
+        assert_eq!(selected, vec![Some("true"), Some("false")]);
+    }
+
+    #[cfg(feature = "js-engine")]
+    #[test]
+    fn runs_scripts_once_and_lets_them_switch_to_client_js() {
+        let html = r#"
+<html class="client-nojs">
+  <body>
+    <p id="count">1</p>
+    <script>
+      document.documentElement.className = "client-js";
+      document.getElementById("count").textContent += "2";
+    </script>
+  </body>
+</html>
+"#;
+        let mut document = crate::html::parse_document(html);
+        execute_inline_scripts(&mut document);
+        let html = document
+            .find_first_element_by_name("html")
+            .expect("missing html element");
+        assert!(html.attributes.has_class("client-js"));
+        assert!(!html.attributes.has_class("client-nojs"));
+        let count = document
+            .find_first_element_by_id("count")
+            .expect("missing count");
+        assert_eq!(count.children, vec![Node::Text("12".to_owned())]);
+    }
 }
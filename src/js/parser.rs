@@ -0,0 +1,838 @@
+use super::lexer::{Lexeme, Token, tokenize};
+use std::rc::Rc;
+
+/// Deepest nesting of statements and expressions; deeper scripts are rejected rather than
+/// risking the parser's stack.
+const MAX_NESTING: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Less,
+    Greater,
+    LessEq,
+    GreaterEq,
+    Eq,
+    NotEq,
+    StrictEq,
+    StrictNotEq,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum LogicalOp {
+    And,
+    Or,
+    Nullish,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum UnaryOp {
+    Neg,
+    Plus,
+    Not,
+    Typeof,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Expr {
+    Number(f64),
+    Str(Rc<str>),
+    Bool(bool),
+    Null,
+    Undefined,
+    Ident(Rc<str>),
+    Array(Vec<Expr>),
+    Object(Vec<(Rc<str>, Expr)>),
+    Function(Rc<FunctionDef>),
+    Member(Box<Expr>, Rc<str>),
+    Index(Box<Expr>, Box<Expr>),
+    Call(Box<Expr>, Vec<Expr>),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Logical(LogicalOp, Box<Expr>, Box<Expr>),
+    Conditional(Box<Expr>, Box<Expr>, Box<Expr>),
+    /// `target = value`, or a compound assignment such as `+=` when an operator is given.
+    Assign(Option<BinaryOp>, Box<Expr>, Box<Expr>),
+    Update {
+        increment: bool,
+        prefix: bool,
+        target: Box<Expr>,
+    },
+    /// The comma operator: every item is evaluated and the last one is the result.
+    Sequence(Vec<Expr>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum DeclKind {
+    Var,
+    Lexical,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Stmt {
+    Expr(Expr),
+    Declare(DeclKind, Vec<(Rc<str>, Option<Expr>)>),
+    Function(Rc<FunctionDef>),
+    If(Expr, Box<Stmt>, Option<Box<Stmt>>),
+    For {
+        init: Option<Box<Stmt>>,
+        test: Option<Expr>,
+        update: Option<Expr>,
+        body: Box<Stmt>,
+    },
+    ForOf(DeclKind, Rc<str>, Expr, Box<Stmt>),
+    While(Expr, Box<Stmt>),
+    Block(Vec<Stmt>),
+    Return(Option<Expr>),
+    Throw(Expr),
+    Try {
+        block: Vec<Stmt>,
+        catch: Option<(Option<Rc<str>>, Vec<Stmt>)>,
+        finally: Option<Vec<Stmt>>,
+    },
+    Break,
+    Continue,
+    Empty,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum FunctionBody {
+    Block(Vec<Stmt>),
+    Expr(Expr),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct FunctionDef {
+    pub(super) name: Option<Rc<str>>,
+    pub(super) params: Vec<Rc<str>>,
+    pub(super) body: FunctionBody,
+}
+
+pub(super) fn parse_script(source: &str) -> Result<Vec<Stmt>, String> {
+    parse_program(source, 0)
+}
+
+fn parse_program(source: &str, depth: usize) -> Result<Vec<Stmt>, String> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        depth,
+    };
+    let mut statements = Vec::new();
+    while !parser.at_end() {
+        statements.push(parser.statement()?);
+    }
+    Ok(statements)
+}
+
+struct Parser {
+    tokens: Vec<Lexeme>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|lexeme| &lexeme.token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens
+            .get(self.pos + offset)
+            .map(|lexeme| &lexeme.token)
+    }
+
+    fn is_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(p)) if *p == punct)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(name)) if name == keyword)
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let matched = self.is_punct(punct);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let matched = self.is_keyword(keyword);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), String> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Expected {punct:?} in script, found {:?}",
+                self.peek()
+            ))
+        }
+    }
+
+    fn identifier(&mut self) -> Result<Rc<str>, String> {
+        match self.peek() {
+            Some(Token::Ident(name)) if !is_reserved(name) => {
+                let name = Rc::from(name.as_str());
+                self.pos += 1;
+                Ok(name)
+            }
+            other => Err(format!("Expected an identifier in script, found {other:?}")),
+        }
+    }
+
+    /// Accepts `;`, or nothing where automatic semicolon insertion would supply one.
+    fn end_statement(&mut self) -> Result<(), String> {
+        if self.eat_punct(";") || self.at_end() || self.is_punct("}") {
+            return Ok(());
+        }
+        if self.tokens[self.pos].newline_before {
+            return Ok(());
+        }
+        Err(format!("Expected ';' in script, found {:?}", self.peek()))
+    }
+
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.depth >= MAX_NESTING {
+            return Err("Script nests too deeply".to_owned());
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn statement(&mut self) -> Result<Stmt, String> {
+        self.nested(Self::statement_inner)
+    }
+
+    fn statement_inner(&mut self) -> Result<Stmt, String> {
+        if self.eat_punct(";") {
+            return Ok(Stmt::Empty);
+        }
+        if self.eat_punct("{") {
+            return Ok(Stmt::Block(self.block_rest()?));
+        }
+        if let Some(kind) = self.declaration_keyword() {
+            let declaration = self.declaration(kind)?;
+            self.end_statement()?;
+            return Ok(declaration);
+        }
+        if self.is_keyword("function") {
+            self.pos += 1;
+            let name = self.identifier()?;
+            return Ok(Stmt::Function(Rc::new(self.function_rest(Some(name))?)));
+        }
+        if self.eat_keyword("if") {
+            self.expect_punct("(")?;
+            let test = self.expression()?;
+            self.expect_punct(")")?;
+            let then = Box::new(self.statement()?);
+            let otherwise = if self.eat_keyword("else") {
+                Some(Box::new(self.statement()?))
+            } else {
+                None
+            };
+            return Ok(Stmt::If(test, then, otherwise));
+        }
+        if self.eat_keyword("for") {
+            return self.for_rest();
+        }
+        if self.eat_keyword("while") {
+            self.expect_punct("(")?;
+            let test = self.expression()?;
+            self.expect_punct(")")?;
+            return Ok(Stmt::While(test, Box::new(self.statement()?)));
+        }
+        if self.eat_keyword("return") {
+            let value = if self.is_punct(";")
+                || self.is_punct("}")
+                || self.at_end()
+                || self.tokens[self.pos].newline_before
+            {
+                None
+            } else {
+                Some(self.expression()?)
+            };
+            self.end_statement()?;
+            return Ok(Stmt::Return(value));
+        }
+        if self.eat_keyword("throw") {
+            let value = self.expression()?;
+            self.end_statement()?;
+            return Ok(Stmt::Throw(value));
+        }
+        if self.eat_keyword("try") {
+            return self.try_rest();
+        }
+        if self.eat_keyword("break") {
+            self.end_statement()?;
+            return Ok(Stmt::Break);
+        }
+        if self.eat_keyword("continue") {
+            self.end_statement()?;
+            return Ok(Stmt::Continue);
+        }
+        if let Some(Token::Ident(name)) = self.peek()
+            && is_reserved(name)
+        {
+            return Err(format!("Unsupported statement {name:?} in script"));
+        }
+
+        let expression = self.expression()?;
+        self.end_statement()?;
+        Ok(Stmt::Expr(expression))
+    }
+
+    fn declaration_keyword(&mut self) -> Option<DeclKind> {
+        let kind = match self.peek() {
+            Some(Token::Ident(name)) if name == "var" => DeclKind::Var,
+            Some(Token::Ident(name)) if name == "let" || name == "const" => DeclKind::Lexical,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(kind)
+    }
+
+    fn declaration(&mut self, kind: DeclKind) -> Result<Stmt, String> {
+        let mut bindings = Vec::new();
+        loop {
+            let name = self.identifier()?;
+            let value = if self.eat_punct("=") {
+                Some(self.assignment()?)
+            } else {
+                None
+            };
+            bindings.push((name, value));
+            if !self.eat_punct(",") {
+                return Ok(Stmt::Declare(kind, bindings));
+            }
+        }
+    }
+
+    fn try_rest(&mut self) -> Result<Stmt, String> {
+        self.expect_punct("{")?;
+        let block = self.block_rest()?;
+        let catch = if self.eat_keyword("catch") {
+            let param = if self.eat_punct("(") {
+                let name = self.identifier()?;
+                self.expect_punct(")")?;
+                Some(name)
+            } else {
+                None
+            };
+            self.expect_punct("{")?;
+            Some((param, self.block_rest()?))
+        } else {
+            None
+        };
+        let finally = if self.eat_keyword("finally") {
+            self.expect_punct("{")?;
+            Some(self.block_rest()?)
+        } else {
+            None
+        };
+        if catch.is_none() && finally.is_none() {
+            return Err("Expected \"catch\" or \"finally\" after try block in script".to_owned());
+        }
+        Ok(Stmt::Try {
+            block,
+            catch,
+            finally,
+        })
+    }
+
+    fn block_rest(&mut self) -> Result<Vec<Stmt>, String> {
+        let mut statements = Vec::new();
+        while !self.eat_punct("}") {
+            if self.at_end() {
+                return Err("Unterminated block in script".to_owned());
+            }
+            statements.push(self.statement()?);
+        }
+        Ok(statements)
+    }
+
+    fn for_rest(&mut self) -> Result<Stmt, String> {
+        self.expect_punct("(")?;
+        let init = if self.eat_punct(";") {
+            None
+        } else if let Some(kind) = self.declaration_keyword() {
+            if matches!(self.peek_at(1), Some(Token::Ident(word)) if word == "of") {
+                let name = self.identifier()?;
+                self.pos += 1;
+                let iterable = self.expression()?;
+                self.expect_punct(")")?;
+                let body = Box::new(self.statement()?);
+                return Ok(Stmt::ForOf(kind, name, iterable, body));
+            }
+            let declaration = self.declaration(kind)?;
+            self.expect_punct(";")?;
+            Some(Box::new(declaration))
+        } else {
+            let init = self.expression()?;
+            self.expect_punct(";")?;
+            Some(Box::new(Stmt::Expr(init)))
+        };
+        let test = if self.is_punct(";") {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.expect_punct(";")?;
+        let update = if self.is_punct(")") {
+            None
+        } else {
+            Some(self.expression()?)
+        };
+        self.expect_punct(")")?;
+        let body = Box::new(self.statement()?);
+        Ok(Stmt::For {
+            init,
+            test,
+            update,
+            body,
+        })
+    }
+
+    /// Parses the parameter list and body after `function` and its optional name.
+    fn function_rest(&mut self, name: Option<Rc<str>>) -> Result<FunctionDef, String> {
+        self.expect_punct("(")?;
+        let mut params = Vec::new();
+        while !self.eat_punct(")") {
+            params.push(self.identifier()?);
+            if !self.is_punct(")") {
+                self.expect_punct(",")?;
+            }
+        }
+        self.expect_punct("{")?;
+        Ok(FunctionDef {
+            name,
+            params,
+            body: FunctionBody::Block(self.block_rest()?),
+        })
+    }
+
+    fn arrow_body(&mut self, params: Vec<Rc<str>>) -> Result<Expr, String> {
+        let body = if self.eat_punct("{") {
+            FunctionBody::Block(self.block_rest()?)
+        } else {
+            FunctionBody::Expr(self.assignment()?)
+        };
+        Ok(Expr::Function(Rc::new(FunctionDef {
+            name: None,
+            params,
+            body,
+        })))
+    }
+
+    /// Whether the tokens ahead are `(a, b) =>`, checked before committing to a parenthesized
+    /// expression.
+    fn arrow_params_ahead(&self) -> Option<(Vec<Rc<str>>, usize)> {
+        let mut params = Vec::new();
+        let mut offset = 1;
+        if !matches!(self.peek_at(offset), Some(Token::Punct(")"))) {
+            loop {
+                match self.peek_at(offset) {
+                    Some(Token::Ident(name)) if !is_reserved(name) => {
+                        params.push(Rc::from(name.as_str()))
+                    }
+                    _ => return None,
+                }
+                offset += 1;
+                match self.peek_at(offset) {
+                    Some(Token::Punct(",")) => offset += 1,
+                    Some(Token::Punct(")")) => break,
+                    _ => return None,
+                }
+            }
+        }
+        matches!(self.peek_at(offset + 1), Some(Token::Punct("=>"))).then_some((params, offset + 2))
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let first = self.assignment()?;
+        if !self.is_punct(",") {
+            return Ok(first);
+        }
+        let mut items = vec![first];
+        while self.eat_punct(",") {
+            items.push(self.assignment()?);
+        }
+        Ok(Expr::Sequence(items))
+    }
+
+    fn assignment(&mut self) -> Result<Expr, String> {
+        self.nested(Self::assignment_inner)
+    }
+
+    fn assignment_inner(&mut self) -> Result<Expr, String> {
+        if let Some(Token::Ident(name)) = self.peek()
+            && !is_reserved(name)
+            && matches!(self.peek_at(1), Some(Token::Punct("=>")))
+        {
+            let param = self.identifier()?;
+            self.pos += 1;
+            return self.arrow_body(vec![param]);
+        }
+        if self.is_punct("(")
+            && let Some((params, len)) = self.arrow_params_ahead()
+        {
+            self.pos += len;
+            return self.arrow_body(params);
+        }
+
+        let target = self.conditional()?;
+        let operator = match self.peek() {
+            Some(Token::Punct("=")) => None,
+            Some(Token::Punct("+=")) => Some(BinaryOp::Add),
+            Some(Token::Punct("-=")) => Some(BinaryOp::Sub),
+            Some(Token::Punct("*=")) => Some(BinaryOp::Mul),
+            Some(Token::Punct("/=")) => Some(BinaryOp::Div),
+            Some(Token::Punct("%=")) => Some(BinaryOp::Rem),
+            _ => return Ok(target),
+        };
+        if !matches!(target, Expr::Ident(_) | Expr::Member(..) | Expr::Index(..)) {
+            return Err("Invalid assignment target in script".to_owned());
+        }
+        self.pos += 1;
+        let value = self.assignment()?;
+        Ok(Expr::Assign(operator, Box::new(target), Box::new(value)))
+    }
+
+    fn conditional(&mut self) -> Result<Expr, String> {
+        let test = self.logical(0)?;
+        if !self.eat_punct("?") {
+            return Ok(test);
+        }
+        let then = self.assignment()?;
+        self.expect_punct(":")?;
+        let otherwise = self.assignment()?;
+        Ok(Expr::Conditional(
+            Box::new(test),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    /// Binary and logical operators by precedence level, loosest first.
+    fn logical(&mut self, level: usize) -> Result<Expr, String> {
+        const LEVELS: &[&[&str]] = &[
+            &["??"],
+            &["||"],
+            &["&&"],
+            &["==", "!=", "===", "!=="],
+            &["<", ">", "<=", ">="],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+        let Some(operators) = LEVELS.get(level) else {
+            return self.unary();
+        };
+        let mut left = self.logical(level + 1)?;
+        while let Some(&Token::Punct(punct)) = self.peek() {
+            if !operators.contains(&punct) {
+                break;
+            }
+            self.pos += 1;
+            let right = Box::new(self.logical(level + 1)?);
+            let left_box = Box::new(left);
+            left = match punct {
+                "??" => Expr::Logical(LogicalOp::Nullish, left_box, right),
+                "||" => Expr::Logical(LogicalOp::Or, left_box, right),
+                "&&" => Expr::Logical(LogicalOp::And, left_box, right),
+                _ => Expr::Binary(binary_op(punct), left_box, right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        self.nested(Self::unary_inner)
+    }
+
+    fn unary_inner(&mut self) -> Result<Expr, String> {
+        let operator = match self.peek() {
+            Some(Token::Punct("-")) => Some(UnaryOp::Neg),
+            Some(Token::Punct("+")) => Some(UnaryOp::Plus),
+            Some(Token::Punct("!")) => Some(UnaryOp::Not),
+            Some(Token::Ident(name)) if name == "typeof" => Some(UnaryOp::Typeof),
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            self.pos += 1;
+            return Ok(Expr::Unary(operator, Box::new(self.unary()?)));
+        }
+        if self.is_punct("++") || self.is_punct("--") {
+            let increment = self.is_punct("++");
+            self.pos += 1;
+            let target = self.unary()?;
+            return Ok(Expr::Update {
+                increment,
+                prefix: true,
+                target: Box::new(target),
+            });
+        }
+
+        let expression = self.postfix()?;
+        if (self.is_punct("++") || self.is_punct("--")) && !self.tokens[self.pos].newline_before {
+            let increment = self.is_punct("++");
+            self.pos += 1;
+            return Ok(Expr::Update {
+                increment,
+                prefix: false,
+                target: Box::new(expression),
+            });
+        }
+        Ok(expression)
+    }
+
+    fn postfix(&mut self) -> Result<Expr, String> {
+        let mut expression = self.primary()?;
+        loop {
+            if self.eat_punct(".") {
+                let Some(Token::Ident(name)) = self.peek() else {
+                    return Err("Expected a property name in script".to_owned());
+                };
+                let name = Rc::from(name.as_str());
+                self.pos += 1;
+                expression = Expr::Member(Box::new(expression), name);
+            } else if self.eat_punct("[") {
+                let index = self.expression()?;
+                self.expect_punct("]")?;
+                expression = Expr::Index(Box::new(expression), Box::new(index));
+            } else if self.eat_punct("(") {
+                let mut args = Vec::new();
+                while !self.eat_punct(")") {
+                    args.push(self.assignment()?);
+                    if !self.is_punct(")") {
+                        self.expect_punct(",")?;
+                    }
+                }
+                expression = Expr::Call(Box::new(expression), args);
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let Some(token) = self.peek().cloned() else {
+            return Err("Unexpected end of script".to_owned());
+        };
+        self.pos += 1;
+        match token {
+            Token::Number(value) => Ok(Expr::Number(value)),
+            Token::Str(text) => Ok(Expr::Str(Rc::from(text))),
+            Token::Template {
+                strings,
+                expressions,
+            } => self.template(strings, expressions),
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Expr::Bool(true)),
+                "false" => Ok(Expr::Bool(false)),
+                "null" => Ok(Expr::Null),
+                "undefined" => Ok(Expr::Undefined),
+                "function" => {
+                    let name = match self.peek() {
+                        Some(Token::Ident(_)) => Some(self.identifier()?),
+                        _ => None,
+                    };
+                    Ok(Expr::Function(Rc::new(self.function_rest(name)?)))
+                }
+                _ if is_reserved(&name) => {
+                    Err(format!("Unsupported expression {name:?} in script"))
+                }
+                _ => Ok(Expr::Ident(Rc::from(name))),
+            },
+            Token::Punct("(") => {
+                let expression = self.expression()?;
+                self.expect_punct(")")?;
+                Ok(expression)
+            }
+            Token::Punct("[") => {
+                let mut items = Vec::new();
+                while !self.eat_punct("]") {
+                    items.push(self.assignment()?);
+                    if !self.is_punct("]") {
+                        self.expect_punct(",")?;
+                    }
+                }
+                Ok(Expr::Array(items))
+            }
+            Token::Punct("{") => {
+                let mut entries = Vec::new();
+                while !self.eat_punct("}") {
+                    let key: Rc<str> = match self.peek().cloned() {
+                        Some(Token::Ident(name)) => Rc::from(name),
+                        Some(Token::Str(text)) => Rc::from(text),
+                        Some(Token::Number(value)) => Rc::from(value.to_string()),
+                        other => {
+                            return Err(format!(
+                                "Expected a property name in script, found {other:?}"
+                            ));
+                        }
+                    };
+                    self.pos += 1;
+                    let value = if self.eat_punct(":") {
+                        self.assignment()?
+                    } else {
+                        Expr::Ident(key.clone())
+                    };
+                    entries.push((key, value));
+                    if !self.is_punct("}") {
+                        self.expect_punct(",")?;
+                    }
+                }
+                Ok(Expr::Object(entries))
+            }
+            other => Err(format!("Unexpected {other:?} in script")),
+        }
+    }
+
+    /// Turns a template literal into string concatenation, starting from a string so every
+    /// `+` concatenates.
+    fn template(&mut self, strings: Vec<String>, expressions: Vec<String>) -> Result<Expr, String> {
+        let mut strings = strings.into_iter();
+        let mut out = Expr::Str(Rc::from(strings.next().unwrap_or_default()));
+        for (source, text) in expressions.iter().zip(strings) {
+            let parsed = self.nested(|parser| {
+                let mut program = parse_program(source, parser.depth)?;
+                match (program.pop(), program.is_empty()) {
+                    (Some(Stmt::Expr(expression)), true) => Ok(expression),
+                    _ => Err("Invalid template expression in script".to_owned()),
+                }
+            })?;
+            out = Expr::Binary(BinaryOp::Add, Box::new(out), Box::new(parsed));
+            out = Expr::Binary(
+                BinaryOp::Add,
+                Box::new(out),
+                Box::new(Expr::Str(Rc::from(text))),
+            );
+        }
+        Ok(out)
+    }
+}
+
+fn binary_op(punct: &str) -> BinaryOp {
+    match punct {
+        "+" => BinaryOp::Add,
+        "-" => BinaryOp::Sub,
+        "*" => BinaryOp::Mul,
+        "/" => BinaryOp::Div,
+        "%" => BinaryOp::Rem,
+        "<" => BinaryOp::Less,
+        ">" => BinaryOp::Greater,
+        "<=" => BinaryOp::LessEq,
+        ">=" => BinaryOp::GreaterEq,
+        "==" => BinaryOp::Eq,
+        "!=" => BinaryOp::NotEq,
+        "===" => BinaryOp::StrictEq,
+        _ => BinaryOp::StrictNotEq,
+    }
+}
+
+/// Keywords, including those of statements the interpreter does not support, so scripts using
+/// them fail to parse instead of being misread.
+fn is_reserved(name: &str) -> bool {
+    matches!(
+        name,
+        "var"
+            | "let"
+            | "const"
+            | "function"
+            | "if"
+            | "else"
+            | "for"
+            | "while"
+            | "do"
+            | "return"
+            | "break"
+            | "continue"
+            | "true"
+            | "false"
+            | "null"
+            | "undefined"
+            | "typeof"
+            | "new"
+            | "class"
+            | "switch"
+            | "case"
+            | "try"
+            | "catch"
+            | "finally"
+            | "throw"
+            | "delete"
+            | "in"
+            | "instanceof"
+            | "this"
+            | "async"
+            | "await"
+            | "yield"
+            | "import"
+            | "export"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_statements_with_precedence_and_arrows() {
+        let program = parse_script(
+            "let total = 1 + 2 * 3\nconst f = (a, b) => a - b;\nfor (const v of list) total += v",
+        )
+        .unwrap();
+        assert_eq!(program.len(), 3);
+        assert_eq!(
+            program[0],
+            Stmt::Declare(
+                DeclKind::Lexical,
+                vec![(
+                    Rc::from("total"),
+                    Some(Expr::Binary(
+                        BinaryOp::Add,
+                        Box::new(Expr::Number(1.0)),
+                        Box::new(Expr::Binary(
+                            BinaryOp::Mul,
+                            Box::new(Expr::Number(2.0)),
+                            Box::new(Expr::Number(3.0)),
+                        )),
+                    )),
+                )],
+            )
+        );
+        assert!(matches!(
+            &program[1],
+            Stmt::Declare(_, bindings) if matches!(&bindings[0].1, Some(Expr::Function(def)) if def.params.len() == 2)
+        ));
+        assert!(matches!(program[2], Stmt::ForOf(..)));
+    }
+
+    #[test]
+    fn rejects_unsupported_or_runaway_syntax() {
+        assert!(parse_script("class A {}").is_err());
+        assert!(parse_script("a b").is_err());
+        assert!(parse_script("x = new Date()").is_err());
+        assert!(parse_script(&format!("x = {}1{}", "(".repeat(200), ")".repeat(200))).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use crate::render::shaping::is_combining_mark;
+
+/// The part of the Unicode bidirectional algorithm a run of text plays, reduced to what a line of
+/// words without explicit embeddings needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BidiClass {
+    Left,
+    Right,
+    EuropeanNumber,
+    ArabicNumber,
+    Whitespace,
+    Neutral,
+}
+
+impl BidiClass {
+    fn base(rtl: bool) -> BidiClass {
+        if rtl {
+            BidiClass::Right
+        } else {
+            BidiClass::Left
+        }
+    }
+
+    pub(super) fn of_char(ch: char) -> BidiClass {
+        match u32::from(ch) {
+            0x0660..=0x0669 | 0x06F0..=0x06F9 => BidiClass::ArabicNumber,
+            0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF => {
+                if is_combining_mark(ch) || !ch.is_alphabetic() {
+                    BidiClass::Neutral
+                } else {
+                    BidiClass::Right
+                }
+            }
+            _ if ch.is_ascii_digit() => BidiClass::EuropeanNumber,
+            _ if ch.is_whitespace() => BidiClass::Whitespace,
+            _ if ch.is_alphabetic() => BidiClass::Left,
+            _ => BidiClass::Neutral,
+        }
+    }
+
+    /// A word takes the direction of its first strong letter, or failing that is a number when
+    /// it has digits.
+    pub(super) fn of_text(text: &str) -> BidiClass {
+        let mut class = if text.chars().all(char::is_whitespace) {
+            BidiClass::Whitespace
+        } else {
+            BidiClass::Neutral
+        };
+        for ch in text.chars() {
+            match BidiClass::of_char(ch) {
+                strong @ (BidiClass::Left | BidiClass::Right) => return strong,
+                number @ (BidiClass::EuropeanNumber | BidiClass::ArabicNumber)
+                    if class == BidiClass::Neutral =>
+                {
+                    class = number;
+                }
+                _ => {}
+            }
+        }
+        class
+    }
+}
+
+/// Whether anything in `text` is written right to left.
+pub(super) fn has_right_to_left(text: &str) -> bool {
+    text.chars().any(|ch| {
+        matches!(
+            BidiClass::of_char(ch),
+            BidiClass::Right | BidiClass::ArabicNumber
+        )
+    })
+}
+
+/// The embedding level of each run on a line, starting from `rtl`'s base level: numbers take
+/// the direction of the letters before them, neutrals that of the letters either side when they
+/// agree and the base direction otherwise, and whitespace at the end of the line the base level.
+pub(super) fn resolve_levels(classes: &[BidiClass], rtl: bool) -> Vec<u8> {
+    let base = BidiClass::base(rtl);
+    let mut strong = base;
+    let mut resolved: Vec<BidiClass> = classes
+        .iter()
+        .map(|&class| match class {
+            BidiClass::Left | BidiClass::Right => {
+                strong = class;
+                class
+            }
+            BidiClass::EuropeanNumber if strong == BidiClass::Left => BidiClass::Left,
+            _ => class,
+        })
+        .collect();
+
+    let direction = |class: BidiClass| match class {
+        BidiClass::Left => Some(BidiClass::Left),
+        BidiClass::Right | BidiClass::EuropeanNumber | BidiClass::ArabicNumber => {
+            Some(BidiClass::Right)
+        }
+        BidiClass::Whitespace | BidiClass::Neutral => None,
+    };
+    let mut index = 0;
+    while index < resolved.len() {
+        if direction(resolved[index]).is_some() {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < resolved.len() && direction(resolved[index]).is_none() {
+            index += 1;
+        }
+        let before = start
+            .checked_sub(1)
+            .and_then(|before| direction(resolved[before]))
+            .unwrap_or(base);
+        let after = resolved
+            .get(index)
+            .and_then(|&after| direction(after))
+            .unwrap_or(base);
+        let class = if before == after { before } else { base };
+        resolved[start..index].fill(class);
+    }
+
+    let base_level = u8::from(rtl);
+    let mut levels: Vec<u8> = resolved
+        .iter()
+        .map(|&class| match (rtl, class) {
+            (false, BidiClass::Right) | (true, BidiClass::Left) => base_level + 1,
+            (false, BidiClass::EuropeanNumber | BidiClass::ArabicNumber) => base_level + 2,
+            (true, BidiClass::EuropeanNumber | BidiClass::ArabicNumber) => base_level + 1,
+            _ => base_level,
+        })
+        .collect();
+    for (level, &class) in levels.iter_mut().zip(classes).rev() {
+        if class != BidiClass::Whitespace {
+            break;
+        }
+        *level = base_level;
+    }
+    levels
+}
+
+/// The order runs at `levels` are shown in from left to right: from the highest level down to
+/// the lowest odd one, every stretch at that level or above is reversed.
+pub(super) fn visual_order(levels: &[u8]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..levels.len()).collect();
+    let Some(&highest) = levels.iter().max() else {
+        return order;
+    };
+    let lowest_odd = levels
+        .iter()
+        .copied()
+        .filter(|level| level % 2 == 1)
+        .min()
+        .unwrap_or(highest + 1);
+    for level in (lowest_odd..=highest).rev() {
+        let mut index = 0;
+        while index < order.len() {
+            if levels[order[index]] < level {
+                index += 1;
+                continue;
+            }
+            let start = index;
+            while index < order.len() && levels[order[index]] >= level {
+                index += 1;
+            }
+            order[start..index].reverse();
+        }
+    }
+    order
+}
+
+/// `text` as it reads from left to right when written right to left: its letters reversed,
+/// each keeping the marks that follow it, and its brackets mirrored.
+pub(super) fn right_to_left_display(text: &str) -> String {
+    let mut clusters: Vec<String> = Vec::new();
+    for ch in text.chars() {
+        match clusters.last_mut() {
+            Some(cluster) if is_combining_mark(ch) => cluster.push(ch),
+            _ => clusters.push(mirror(ch).to_string()),
+        }
+    }
+    clusters.into_iter().rev().collect()
+}
+
+fn mirror(ch: char) -> char {
+    match ch {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        _ => ch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn right_to_left_words_reverse_around_numbers_and_left_to_right_words() {
+        let classes: Vec<BidiClass> = ["שלום", " ", "world", " ", "123", " ", "עולם", " "]
+            .iter()
+            .map(|word| BidiClass::of_text(word))
+            .collect();
+        assert_eq!(classes[2], BidiClass::Left);
+        assert_eq!(classes[4], BidiClass::EuropeanNumber);
+
+        let levels = resolve_levels(&classes, true);
+        assert_eq!(levels, vec![1, 1, 2, 2, 2, 1, 1, 1]);
+        assert_eq!(visual_order(&levels), vec![7, 6, 5, 2, 3, 4, 1, 0]);
+
+        let levels = resolve_levels(&classes, false);
+        assert_eq!(levels, vec![1, 0, 0, 0, 0, 0, 1, 0]);
+        assert_eq!(visual_order(&levels), vec![0, 1, 2, 3, 4, 5, 6, 7]);
+
+        let levels = resolve_levels(
+            &[BidiClass::Right, BidiClass::Neutral, BidiClass::Right],
+            false,
+        );
+        assert_eq!(visual_order(&levels), vec![2, 1, 0]);
+
+        assert_eq!(right_to_left_display("(שלום)"), "(םולש)");
+        assert_eq!(right_to_left_display("בְּ"), "בְּ");
+    }
+}
@@ -0,0 +1,142 @@
+use std::borrow::Cow;
+
+use crate::css::PseudoElement;
+use crate::dom::Element;
+use crate::render::TextStyle;
+use crate::style::{ComputedStyle, CssLength, Float, TextTransform};
+
+use super::LayoutEngine;
+
+/// What the first line of a block's text gets on top of the block's own style: its
+/// `text-indent`, and whatever its `::first-line` and `::first-letter` rules say.
+#[derive(Clone, Debug)]
+pub(super) struct FirstLine {
+    pub(super) indent: CssLength,
+    pub(super) line: Option<Restyle>,
+    pub(super) letter: Option<FirstLetter>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(super) struct FirstLetter {
+    pub(super) restyle: Restyle,
+    /// A drop cap: floated to the start of the first lines, which make room for it.
+    pub(super) floated: bool,
+    pub(super) margin_right_px: i32,
+}
+
+/// How a pseudo-element's style differs from that of the block it belongs to. Only what it
+/// changes is applied over a word's own style, so a link in the first line keeps its color
+/// unless `::first-line` sets one too.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Restyle {
+    base: TextStyle,
+    styled: TextStyle,
+    base_transform: TextTransform,
+    transform: TextTransform,
+}
+
+impl Restyle {
+    fn new(engine: &LayoutEngine<'_>, base: &ComputedStyle, styled: &ComputedStyle) -> Restyle {
+        Restyle {
+            base: engine.text_style_for(base),
+            styled: engine.text_style_for(styled),
+            base_transform: base.text_transform,
+            transform: styled.text_transform,
+        }
+    }
+
+    pub(super) fn style(&self, style: TextStyle) -> TextStyle {
+        fn pick<T: PartialEq>(base: T, styled: T, own: T) -> T {
+            if styled != base { styled } else { own }
+        }
+        let (base, styled) = (self.base, self.styled);
+        TextStyle {
+            color: pick(base.color, styled.color, style.color),
+            bold: pick(base.bold, styled.bold, style.bold),
+            underline: pick(base.underline, styled.underline, style.underline),
+            font_family: pick(base.font_family, styled.font_family, style.font_family),
+            font_size_px: pick(base.font_size_px, styled.font_size_px, style.font_size_px),
+            letter_spacing_px: pick(
+                base.letter_spacing_px,
+                styled.letter_spacing_px,
+                style.letter_spacing_px,
+            ),
+        }
+    }
+
+    pub(super) fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.transform == self.base_transform {
+            Cow::Borrowed(text)
+        } else {
+            self.transform.apply(text)
+        }
+    }
+}
+
+impl LayoutEngine<'_> {
+    /// The first line of the block `ancestors` ends with, laid out with `style`; `None` when it
+    /// is laid out like any other line.
+    pub(super) fn first_line_of(
+        &self,
+        style: &ComputedStyle,
+        ancestors: &[&Element],
+    ) -> Option<FirstLine> {
+        let (&element, outer) = ancestors.split_last()?;
+        let pseudo_style = |pseudo, parent: &ComputedStyle| {
+            self.styles.compute_pseudo_style(
+                element,
+                pseudo,
+                parent,
+                outer,
+                self.viewport.width_px,
+                self.viewport.height_px,
+            )
+        };
+        let line_style = pseudo_style(PseudoElement::FirstLine, style);
+        let letter_style = pseudo_style(
+            PseudoElement::FirstLetter,
+            line_style.as_ref().unwrap_or(style),
+        );
+        if matches!(style.text_indent, CssLength::Px(0))
+            && line_style.is_none()
+            && letter_style.is_none()
+        {
+            return None;
+        }
+        Some(FirstLine {
+            indent: style.text_indent,
+            line: line_style.map(|line_style| Restyle::new(self, style, &line_style)),
+            letter: letter_style.map(|letter_style| FirstLetter {
+                restyle: Restyle::new(self, style, &letter_style),
+                floated: letter_style.float == Float::Left,
+                margin_right_px: letter_style.margin.right.max(0),
+            }),
+        })
+    }
+}
+
+/// Splits `word` after its first letter, along with any punctuation around it, as
+/// `::first-letter` takes it. `None` when there is no letter to take.
+pub(super) fn split_first_letter(word: &str) -> Option<(&str, &str)> {
+    let mut chars = word
+        .char_indices()
+        .skip_while(|(_, ch)| ch.is_ascii_punctuation());
+    chars.next()?;
+    let end = chars
+        .find(|(_, ch)| !ch.is_ascii_punctuation())
+        .map_or(word.len(), |(index, _)| index);
+    Some(word.split_at(end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_letter_takes_the_punctuation_around_it() {
+        assert_eq!(split_first_letter("Once"), Some(("O", "nce")));
+        assert_eq!(split_first_letter("\"Well,\""), Some(("\"W", "ell,\"")));
+        assert_eq!(split_first_letter("A."), Some(("A.", "")));
+        assert_eq!(split_first_letter("..."), None);
+    }
+}
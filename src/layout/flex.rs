@@ -1,27 +1,11 @@
 use crate::dom::{Element, Node};
 use crate::geom::{Rect, Size};
 use crate::style::{
-    ComputedStyle, Display, FlexAlignItems, FlexDirection, FlexJustifyContent, FlexWrap, Position,
-    Visibility,
+    ComputedStyle, CssLength, Display, FlexAlignItems, FlexDirection, FlexJustifyContent, FlexWrap,
+    Position, Visibility,
 };
-use std::rc::Rc;
 
-use super::{LayoutEngine, inline, table};
-
-fn compute_style<'doc>(
-    engine: &LayoutEngine<'_>,
-    element: &'doc Element,
-    parent_style: &ComputedStyle,
-    ancestors: &mut Vec<&'doc Element>,
-) -> ComputedStyle {
-    engine.styles.compute_style_in_viewport(
-        element,
-        parent_style,
-        ancestors,
-        engine.viewport.width_px,
-        engine.viewport.height_px,
-    )
-}
+use super::{LayoutEngine, inline, intrinsic, table};
 
 pub(super) fn layout_flex_row<'doc>(
     engine: &mut LayoutEngine<'_>,
@@ -36,10 +20,10 @@ pub(super) fn layout_flex_row<'doc>(
     }
 
     match style.flex_direction {
-        FlexDirection::Row => {
+        FlexDirection::Row | FlexDirection::RowReverse => {
             layout_flex_row_container(engine, element, style, ancestors, content_box, paint)
         }
-        FlexDirection::Column => {
+        FlexDirection::Column | FlexDirection::ColumnReverse => {
             layout_flex_column_container(engine, element, style, ancestors, content_box, paint)
         }
     }
@@ -57,6 +41,51 @@ struct FlexItem<'doc> {
     margin: crate::geom::Edges,
 }
 
+impl<'doc> FlexItem<'doc> {
+    /// Where the item goes across the line: its own `align-self`, or else its container's
+    /// `align-items`. Runs of text are anonymous items, which always follow their container.
+    fn align(&self, container_style: &ComputedStyle) -> FlexAlignItems {
+        match self.node {
+            FlexNode::Text(_) => container_style.flex_align_items,
+            FlexNode::Element(_) => self
+                .style
+                .flex_align_self
+                .unwrap_or(container_style.flex_align_items),
+        }
+    }
+
+    /// The item with its height set to fill a line `line_height` tall, when it stretches across
+    /// the line: it aligns with `stretch`, leaves its height `auto` and has no `auto` margins
+    /// across it. The set height is what its own children then resolve percentages against.
+    fn stretched(&self, container_style: &ComputedStyle, line_height: i32) -> Option<Self> {
+        if matches!(self.node, FlexNode::Text(_))
+            || self.align(container_style) != FlexAlignItems::Stretch
+            || self.style.height_px.is_some()
+            || self.style.margin_auto.top
+            || self.style.margin_auto.bottom
+        {
+            return None;
+        }
+        let height = line_height
+            .saturating_sub(self.margin.top.saturating_add(self.margin.bottom))
+            .max(0);
+        let mut style = self.style.clone();
+        style.height_px = Some(CssLength::Px(height));
+        Some(FlexItem {
+            node: self.node,
+            style,
+            margin: self.margin,
+        })
+    }
+
+    fn order(&self) -> i32 {
+        match self.node {
+            FlexNode::Text(_) => 0,
+            FlexNode::Element(_) => self.style.flex_order,
+        }
+    }
+}
+
 fn layout_flex_row_container<'doc>(
     engine: &mut LayoutEngine<'_>,
     element: &'doc Element,
@@ -97,81 +126,30 @@ fn layout_flex_row_single_line<'doc>(
     paint: bool,
     items: &[FlexItem<'doc>],
 ) -> Result<i32, String> {
-    let mut sizes = Vec::with_capacity(items.len());
+    let mut measured_main = Vec::with_capacity(items.len());
     for item in items {
-        let main_size = measure_item_main_size_row(
-            engine,
-            container_style,
-            ancestors,
-            item,
-            content_box.width,
-        )?;
-        let border_width = main_size.clamp(0, content_box.width);
-        let border_height = measure_item_border_height(
+        measured_main.push(measure_item_main_size_row(
             engine,
             container_style,
             ancestors,
             item,
-            border_width,
             content_box.width,
-        )?;
-        sizes.push(Size {
-            width: border_width,
-            height: border_height,
-        });
-    }
-
-    distribute_flex_shrink_row(container_style, items, content_box.width, &mut sizes);
-    distribute_flex_grow_row(container_style, items, content_box.width, &mut sizes);
-
-    let mut outer_heights: Vec<i32> = Vec::with_capacity(items.len());
-    for (item, size) in items.iter().zip(&sizes) {
-        outer_heights.push(
-            item.margin
-                .top
-                .saturating_add(size.height)
-                .saturating_add(item.margin.bottom),
-        );
+        )?);
     }
-    let line_height = outer_heights.iter().copied().max().unwrap_or(0).max(0);
-
-    let positions = compute_main_positions(
-        container_style.flex_justify_content,
-        content_box.width,
-        container_style.flex_gap_px,
+    // The one line is as tall as the container when the container's height is set.
+    let line_box = Rect {
+        height: engine.containing_height_px.unwrap_or(0),
+        ..content_box
+    };
+    layout_flex_row_line(
+        engine,
+        container_style,
+        ancestors,
+        line_box,
+        paint,
         items,
-        &sizes,
-    );
-
-    for ((item, size), x_offset) in items.iter().zip(&sizes).zip(positions) {
-        let outer_x = content_box.x.saturating_add(x_offset);
-        let border_x = outer_x.saturating_add(item.margin.left);
-        let border_y = align_cross_start(
-            container_style.flex_align_items,
-            content_box.y,
-            line_height,
-            size.height,
-            item.margin.top,
-            item.margin.bottom,
-        );
-
-        layout_item_box(
-            engine,
-            container_style,
-            ancestors,
-            item,
-            Rect {
-                x: border_x,
-                y: border_y,
-                width: size.width,
-                height: size.height,
-            },
-            content_box.width,
-            paint,
-        )?;
-    }
-
-    Ok(line_height.max(0))
+        &measured_main,
+    )
 }
 
 fn layout_flex_row_wrapped<'doc>(
@@ -218,7 +196,7 @@ fn layout_flex_row_wrapped<'doc>(
                     x: content_box.x,
                     y: cursor_y,
                     width: content_box.width,
-                    height: content_box.height,
+                    height: 0,
                 },
                 paint,
                 &items[line_start..idx],
@@ -241,7 +219,7 @@ fn layout_flex_row_wrapped<'doc>(
                 x: content_box.x,
                 y: cursor_y,
                 width: content_box.width,
-                height: content_box.height,
+                height: 0,
             },
             paint,
             &items[line_start..],
@@ -266,25 +244,33 @@ fn layout_flex_row_line<'doc>(
         return Ok(0);
     }
 
-    let mut sizes = Vec::with_capacity(line_items.len());
-    for (item, &main_size) in line_items.iter().zip(measured_main_sizes) {
-        let border_width = main_size.clamp(0, line_box.width);
-        let border_height = measure_item_border_height(
+    let mut sizes: Vec<Size> = measured_main_sizes
+        .iter()
+        .map(|&main_size| Size {
+            width: main_size.clamp(0, line_box.width),
+            height: 0,
+        })
+        .collect();
+    distribute_flex_shrink_row(
+        engine,
+        container_style,
+        ancestors,
+        line_items,
+        line_box.width,
+        &mut sizes,
+    )?;
+    distribute_flex_grow_row(container_style, line_items, line_box.width, &mut sizes);
+    for (item, size) in line_items.iter().zip(sizes.iter_mut()) {
+        size.height = measure_item_border_height(
             engine,
             container_style,
             ancestors,
             item,
-            border_width,
+            size.width,
             line_box.width,
         )?;
-        sizes.push(Size {
-            width: border_width,
-            height: border_height,
-        });
     }
 
-    distribute_flex_grow_row(container_style, line_items, line_box.width, &mut sizes);
-
     let mut line_height = 0i32;
     for (item, size) in line_items.iter().zip(&sizes) {
         let outer = item
@@ -294,7 +280,7 @@ fn layout_flex_row_line<'doc>(
             .saturating_add(item.margin.bottom);
         line_height = line_height.max(outer);
     }
-    line_height = line_height.max(0);
+    line_height = line_height.max(line_box.height).max(0);
 
     let positions = compute_main_positions(
         container_style.flex_justify_content,
@@ -304,14 +290,31 @@ fn layout_flex_row_line<'doc>(
         &sizes,
     );
 
+    let reverse = container_style.flex_direction == FlexDirection::RowReverse;
     for ((item, size), x_offset) in line_items.iter().zip(&sizes).zip(positions) {
-        let outer_x = line_box.x.saturating_add(x_offset);
+        let outer_x = if reverse {
+            let outer_width = item
+                .margin
+                .left
+                .saturating_add(size.width)
+                .saturating_add(item.margin.right);
+            line_box
+                .right()
+                .saturating_sub(x_offset)
+                .saturating_sub(outer_width)
+        } else {
+            line_box.x.saturating_add(x_offset)
+        };
         let border_x = outer_x.saturating_add(item.margin.left);
+        let stretched = item.stretched(container_style, line_height);
+        let item = stretched.as_ref().unwrap_or(item);
+        let border_height = super::used_height_px(&item.style)
+            .map_or(size.height, |height| height.max(size.height));
         let border_y = align_cross_start(
-            container_style.flex_align_items,
+            item.align(container_style),
             line_box.y,
             line_height,
-            size.height,
+            border_height,
             item.margin.top,
             item.margin.bottom,
         );
@@ -324,7 +327,7 @@ fn layout_flex_row_line<'doc>(
                 x: border_x,
                 y: border_y,
                 width: size.width,
-                height: size.height,
+                height: border_height,
             },
             line_box.width,
             paint,
@@ -342,17 +345,22 @@ fn layout_flex_column_container<'doc>(
     content_box: Rect,
     paint: bool,
 ) -> Result<i32, String> {
-    let items = collect_items(engine, element, style, ancestors)?;
+    let mut items = collect_items(engine, element, style, ancestors)?;
     if items.is_empty() {
         layout_positioned_children(engine, element, style, ancestors, content_box, paint)?;
         return Ok(0);
     }
+    // The column grows to fit its items, so the last one ends up first, at the top.
+    if style.flex_direction == FlexDirection::ColumnReverse {
+        items.reverse();
+    }
 
     let mut cursor_y = content_box.y;
     let gap = style.flex_gap_px.max(0);
 
     for (idx, item) in items.iter().enumerate() {
-        let border_width = resolve_column_item_width(content_box.width, item);
+        let border_width =
+            resolve_column_item_width(engine, style, ancestors, item, content_box.width)?;
         let border_height = measure_item_border_height(
             engine,
             style,
@@ -363,7 +371,7 @@ fn layout_flex_column_container<'doc>(
         )?;
 
         let aligned_x = align_column_cross_start(
-            style.flex_align_items,
+            item.align(style),
             content_box.x,
             content_box.width,
             border_width,
@@ -412,7 +420,7 @@ fn align_cross_start(
     let item_height = item_height.max(0);
     let line_height = line_height.max(0);
     match align {
-        FlexAlignItems::Start => line_y.saturating_add(margin_top),
+        FlexAlignItems::Stretch | FlexAlignItems::Start => line_y.saturating_add(margin_top),
         FlexAlignItems::Center => {
             let remaining = line_height
                 .saturating_sub(
@@ -471,7 +479,7 @@ fn align_column_cross_start(
     }
 
     match align {
-        FlexAlignItems::Start => container_x.saturating_add(margin_left),
+        FlexAlignItems::Stretch | FlexAlignItems::Start => container_x.saturating_add(margin_left),
         FlexAlignItems::Center => container_x
             .saturating_add(margin_left)
             .saturating_add(remaining / 2),
@@ -501,7 +509,7 @@ fn collect_items<'doc>(
                 });
             }
             Node::Element(el) => {
-                let child_style = compute_style(engine, el, style, ancestors);
+                let child_style = engine.compute_style(el, style, ancestors);
                 if child_style.display == Display::None {
                     continue;
                 }
@@ -517,6 +525,7 @@ fn collect_items<'doc>(
             }
         }
     }
+    items.sort_by_key(FlexItem::order);
     Ok(items)
 }
 
@@ -525,18 +534,25 @@ fn layout_positioned_children<'doc>(
     element: &'doc Element,
     container_style: &ComputedStyle,
     ancestors: &mut Vec<&'doc Element>,
-    _containing: Rect,
+    content_box: Rect,
     paint: bool,
 ) -> Result<(), String> {
     let containing = engine.current_positioned_containing_block();
     for child in &element.children {
         let Node::Element(el) = child else { continue };
-        let style = compute_style(engine, el, container_style, ancestors);
+        let style = engine.compute_style(el, container_style, ancestors);
         if style.display == Display::None {
             continue;
         }
         if matches!(style.position, Position::Absolute | Position::Fixed) {
-            engine.layout_positioned_box(el, &style, ancestors, containing, paint)?;
+            engine.layout_positioned_box(
+                el,
+                &style,
+                ancestors,
+                containing,
+                (content_box.x, content_box.y),
+                paint,
+            )?;
         }
     }
     Ok(())
@@ -550,7 +566,7 @@ fn measure_item_main_size_row<'doc>(
     max_width: i32,
 ) -> Result<i32, String> {
     let border_width = if let Some(basis) = item.style.flex_basis_px {
-        basis
+        basis.resolve_px(max_width)
     } else if let Some(width) = item.style.width_px {
         width.resolve_px(max_width)
     } else {
@@ -638,10 +654,7 @@ fn measure_flex_container_max_content_width<'doc>(
     let max_width = max_width.max(0);
     let gap = style.flex_gap_px.max(0);
 
-    let mut primary = match style.flex_direction {
-        FlexDirection::Row => 0i32,
-        FlexDirection::Column => 0i32,
-    };
+    let mut primary = 0i32;
     let mut has_any_item = false;
 
     ancestors.push(element);
@@ -657,7 +670,7 @@ fn measure_flex_container_max_content_width<'doc>(
                 (width, 0i32, 0i32)
             }
             Node::Element(el) => {
-                let child_style = compute_style(engine, el, style, ancestors);
+                let child_style = engine.compute_style(el, style, ancestors);
                 if child_style.display == Display::None {
                     continue;
                 }
@@ -669,7 +682,7 @@ fn measure_flex_container_max_content_width<'doc>(
                 }
 
                 let mut width = if let Some(basis) = child_style.flex_basis_px {
-                    basis.max(0)
+                    basis.resolve_px(max_width).max(0)
                 } else if let Some(width) = child_style.width_px {
                     width.resolve_px(max_width).max(0)
                 } else {
@@ -702,13 +715,13 @@ fn measure_flex_container_max_content_width<'doc>(
             .saturating_add(margin_right);
 
         match style.flex_direction {
-            FlexDirection::Row => {
+            FlexDirection::Row | FlexDirection::RowReverse => {
                 if has_any_item {
                     primary = primary.saturating_add(gap);
                 }
                 primary = primary.saturating_add(outer_width);
             }
-            FlexDirection::Column => {
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
                 primary = primary.max(outer_width);
             }
         }
@@ -738,7 +751,7 @@ fn measure_node_max_content_width<'doc>(
             measure_text_run_width(engine, text, engine.text_style_for(parent_style))
         }
         Node::Element(el) => {
-            let style = compute_style(engine, el, parent_style, ancestors);
+            let style = engine.compute_style(el, parent_style, ancestors);
             if style.display == Display::None {
                 return Ok(0);
             }
@@ -779,7 +792,7 @@ fn measure_inline_children_width<'doc>(
                 pending_space = space_after;
             }
             Node::Element(el) => {
-                let style = compute_style(engine, el, parent_style, ancestors);
+                let style = engine.compute_style(el, parent_style, ancestors);
                 if style.display == Display::None {
                     continue;
                 }
@@ -921,14 +934,16 @@ fn distribute_flex_grow_row<'doc>(
 }
 
 fn distribute_flex_shrink_row<'doc>(
+    engine: &LayoutEngine<'_>,
     container_style: &ComputedStyle,
+    ancestors: &mut Vec<&'doc Element>,
     items: &[FlexItem<'doc>],
     max_width: i32,
     sizes: &mut [Size],
-) {
+) -> Result<(), String> {
     let max_width = max_width.max(0);
     if max_width <= 0 || items.is_empty() || sizes.is_empty() {
-        return;
+        return Ok(());
     }
 
     let gap = container_style.flex_gap_px.max(0);
@@ -941,19 +956,16 @@ fn distribute_flex_shrink_row<'doc>(
     }
 
     if total_outer <= max_width {
-        return;
+        return Ok(());
     }
 
     let mut overflow = total_outer.saturating_sub(max_width);
-    let min_widths: Vec<i32> = items
-        .iter()
-        .map(|item| {
-            item.style
-                .min_width_px
-                .map(|value| value.resolve_px(max_width).max(0))
-                .unwrap_or(0)
-        })
-        .collect();
+    let mut min_widths = Vec::with_capacity(items.len());
+    for item in items {
+        min_widths.push(measure_item_min_main_size_row(
+            engine, ancestors, item, max_width,
+        )?);
+    }
 
     while overflow > 0 {
         let active_indices: Vec<usize> = items
@@ -1017,6 +1029,32 @@ fn distribute_flex_shrink_row<'doc>(
         }
         overflow = overflow.saturating_sub(reduced_this_round);
     }
+    Ok(())
+}
+
+/// The narrowest a row item shrinks to: its `min-width`, or without one its min-content width,
+/// though never wider than a width it sets.
+fn measure_item_min_main_size_row<'doc>(
+    engine: &LayoutEngine<'_>,
+    ancestors: &mut Vec<&'doc Element>,
+    item: &FlexItem<'doc>,
+    max_width: i32,
+) -> Result<i32, String> {
+    if let Some(min) = item.style.min_width_px {
+        return Ok(min.resolve_px(max_width).max(0));
+    }
+    let FlexNode::Element(el) = item.node else {
+        return Ok(0);
+    };
+    let mut min_width =
+        intrinsic::measure_content_widths(engine, el, &item.style, ancestors, max_width)?.min_px;
+    for limit in [item.style.width_px, item.style.max_width_px]
+        .into_iter()
+        .flatten()
+    {
+        min_width = min_width.min(limit.resolve_px(max_width).max(0));
+    }
+    Ok(min_width)
 }
 
 fn compute_main_positions<'doc>(
@@ -1057,6 +1095,14 @@ fn compute_main_positions<'doc>(
                 )
             }
         }
+        FlexJustifyContent::SpaceAround => {
+            let share = remaining / items.len() as i32;
+            (share / 2, gap.saturating_add(share))
+        }
+        FlexJustifyContent::SpaceEvenly => {
+            let share = remaining / (items.len() as i32).saturating_add(1);
+            (share, gap.saturating_add(share))
+        }
     };
 
     let mut positions = Vec::with_capacity(items.len());
@@ -1109,14 +1155,53 @@ fn measure_item_border_height<'doc>(
     }
 }
 
-fn resolve_column_item_width(container_width: i32, item: &FlexItem<'_>) -> i32 {
+/// A column item with an `auto` width fills the column when it stretches, and otherwise shrinks
+/// to fit its content.
+fn resolve_column_item_width<'doc>(
+    engine: &LayoutEngine<'_>,
+    container_style: &ComputedStyle,
+    ancestors: &mut Vec<&'doc Element>,
+    item: &FlexItem<'doc>,
+    container_width: i32,
+) -> Result<i32, String> {
+    let container_width = container_width.max(0);
     if let Some(width) = item.style.width_px {
-        return width
+        return Ok(width
             .resolve_px(container_width)
             .max(0)
-            .min(container_width.max(0));
+            .min(container_width));
+    }
+    let margin_auto = item.style.margin_auto;
+    if item.align(container_style) == FlexAlignItems::Stretch
+        && !margin_auto.left
+        && !margin_auto.right
+    {
+        return Ok(container_width);
     }
-    container_width.max(0)
+
+    let margin_left = if margin_auto.left {
+        0
+    } else {
+        item.margin.left
+    };
+    let margin_right = if margin_auto.right {
+        0
+    } else {
+        item.margin.right
+    };
+    let available = container_width
+        .saturating_sub(margin_left.saturating_add(margin_right))
+        .max(0);
+    let width = match item.node {
+        FlexNode::Text(node) => {
+            inline::measure_inline_nodes(engine, &[node], container_style, ancestors, available)?
+                .width
+        }
+        FlexNode::Element(el) => {
+            measure_element_max_content_width(engine, el, &item.style, ancestors, available)?
+        }
+    };
+    Ok(width.min(available))
 }
 
 fn layout_item_box<'doc>(
@@ -1131,6 +1216,7 @@ fn layout_item_box<'doc>(
     if border_box.width <= 0 {
         return Ok(0);
     }
+    let relative_mark = engine.relative_mark();
 
     let mut background_index = None;
     let mut paint = paint && item.style.visibility == Visibility::Visible;
@@ -1145,9 +1231,13 @@ fn layout_item_box<'doc>(
             .commands
             .push(crate::render::DisplayCommand::PushOpacity(opacity));
     }
+    let transform_group = engine.open_transform(&item.style, paint);
 
     if paint {
         background_index = engine.push_background(border_box, &item.style, 0);
+        if let FlexNode::Element(el) = item.node {
+            engine.record_outline_heading(el, border_box.y);
+        }
     }
 
     let border = item.style.border_width;
@@ -1188,6 +1278,10 @@ fn layout_item_box<'doc>(
                     engine.push_positioned_containing_block(border_box, border);
                     pushed_positioning = true;
                 }
+                let outer_height = engine.enter_containing_height(
+                    super::used_height_px(&item.style),
+                    super::add_edges(border, padding),
+                );
                 ancestors.push(el);
                 let height = match item.style.display {
                     Display::Table => {
@@ -1217,7 +1311,10 @@ fn layout_item_box<'doc>(
                                 content_box,
                                 content_box.y,
                                 paint,
-                                anchor_href(el),
+                                super::anchor_link(el).filter(|_| {
+                                    !el.is_inert()
+                                        && !ancestors.iter().any(|ancestor| ancestor.is_inert())
+                                }),
                             )?
                         } else {
                             engine.layout_flow_children(
@@ -1231,6 +1328,7 @@ fn layout_item_box<'doc>(
                     }
                 };
                 ancestors.pop();
+                engine.containing_height_px = outer_height;
                 if pushed_positioning {
                     let _ = engine.positioned_containing_blocks.pop();
                 }
@@ -1246,11 +1344,11 @@ fn layout_item_box<'doc>(
         .saturating_add(padding.bottom)
         .saturating_add(border.bottom)
         .max(0);
-    if let Some(height) = item.style.height_px {
-        border_height = border_height.max(height.max(0));
+    if let Some(height) = super::used_height_px(&item.style) {
+        border_height = border_height.max(height);
     }
-    if let Some(min_height) = item.style.min_height_px {
-        border_height = border_height.max(min_height.max(0));
+    if let Some(min_height) = super::min_height_px(&item.style) {
+        border_height = border_height.max(min_height);
     }
 
     if let Some(index) = background_index {
@@ -1272,26 +1370,48 @@ fn layout_item_box<'doc>(
             if super::inline::is_replaced_element(el) {
                 engine.paint_replaced_content(el, &item.style, content_box)?;
             }
+            engine.record_inspected_box(
+                el,
+                ancestors,
+                &item.style,
+                Rect {
+                    height: border_height,
+                    ..border_box
+                },
+                padding,
+            );
         }
+        engine.paint_outline(
+            Rect {
+                height: border_height,
+                ..border_box
+            },
+            &item.style,
+        );
     }
 
+    engine.close_transform(
+        transform_group,
+        &item.style,
+        Rect {
+            height: border_height,
+            ..border_box
+        },
+    );
     if needs_opacity_group {
         engine
             .list
             .commands
             .push(crate::render::DisplayCommand::PopOpacity(opacity));
     }
+    if item.style.position == Position::Relative {
+        let containing = Rect {
+            width: padding_reference_width_px,
+            height: 0,
+            ..border_box
+        };
+        engine.offset_relative(relative_mark, &item.style, containing);
+    }
 
     Ok(border_height)
 }
-
-fn anchor_href(element: &Element) -> Option<Rc<str>> {
-    if element.name != "a" {
-        return None;
-    }
-    let href = element.attributes.get("href")?.trim();
-    if href.is_empty() {
-        return None;
-    }
-    Some(Rc::from(href))
-}
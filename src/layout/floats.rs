@@ -1,8 +1,8 @@
 use crate::dom::Element;
 use crate::geom::Rect;
-use crate::style::{ComputedStyle, Float};
+use crate::style::{Clear, ComputedStyle, Float};
 
-use super::{LayoutEngine, flex, inline};
+use super::{LayoutEngine, intrinsic};
 
 #[derive(Clone, Copy, Debug)]
 pub(super) struct FloatPlacement {
@@ -145,8 +145,22 @@ pub(super) fn layout_float<'doc>(
         style.margin.right
     };
 
-    let mut y_outer = cursor_y;
-    loop {
+    let margins_px = margin_left_px.saturating_add(margin_right_px);
+    let widths = intrinsic::measure_shrink_to_fit_widths(
+        engine,
+        element,
+        style,
+        ancestors,
+        containing.width,
+    )?;
+
+    // A float never starts above an earlier one, nor beside one it clears.
+    let mut y_outer = floats
+        .iter()
+        .map(|float| float.rect.y)
+        .fold(cursor_y, i32::max)
+        .max(clearance_y(floats, style.clear).unwrap_or(cursor_y));
+    let (x_outer, outer_width) = loop {
         let clearance = clearance_at_y(floats, containing, y_outer);
         let available_width = containing
             .width
@@ -156,69 +170,23 @@ pub(super) fn layout_float<'doc>(
                     .saturating_add(clearance.right_offset_px),
             )
             .max(0);
+        let outer_width = widths
+            .fit(available_width.saturating_sub(margins_px))
+            .saturating_add(margins_px);
 
-        let border_width =
-            measure_float_border_width(engine, element, style, ancestors, available_width)?;
-        let outer_width = margin_left_px
-            .saturating_add(border_width)
-            .saturating_add(margin_right_px);
-
-        if clearance.next_y.is_none() || outer_width <= available_width {
+        let next_y = clearance.next_y.filter(|&next_y| next_y > y_outer);
+        if outer_width <= available_width || next_y.is_none() {
             let x_outer = match side {
-                Float::Left => containing.x.saturating_add(clearance.left_offset_px),
                 Float::Right => containing
                     .right()
                     .saturating_sub(clearance.right_offset_px)
                     .saturating_sub(outer_width),
-                Float::None => containing.x,
+                _ => containing.x.saturating_add(clearance.left_offset_px),
             };
-
-            let mut float_cursor_y = y_outer;
-            engine.layout_block_box(
-                element,
-                style,
-                parent_style,
-                ancestors,
-                Rect {
-                    x: x_outer,
-                    y: y_outer,
-                    width: outer_width,
-                    height: containing.height,
-                },
-                &mut float_cursor_y,
-                paint,
-                None,
-            )?;
-
-            let outer_height = float_cursor_y.saturating_sub(y_outer).max(0);
-            return Ok(FloatPlacement {
-                side,
-                rect: Rect {
-                    x: x_outer,
-                    y: y_outer,
-                    width: outer_width,
-                    height: outer_height,
-                },
-            });
+            break (x_outer, outer_width);
         }
-
-        let Some(next_y) = clearance.next_y else {
-            break;
-        };
-        if next_y <= y_outer {
-            break;
-        }
-        y_outer = next_y;
-    }
-
-    let border_width =
-        measure_float_border_width(engine, element, style, ancestors, containing.width.max(0))?;
-    let outer_width = margin_left_px
-        .saturating_add(border_width)
-        .saturating_add(margin_right_px);
-    let x_outer = match side {
-        Float::Right => containing.right().saturating_sub(outer_width),
-        _ => containing.x,
+        // The line beside the earlier floats is full, so drop below the first of them to end.
+        y_outer = next_y.unwrap_or(y_outer);
     };
 
     let mut float_cursor_y = y_outer;
@@ -249,34 +217,18 @@ pub(super) fn layout_float<'doc>(
     })
 }
 
-fn measure_float_border_width<'doc>(
-    engine: &LayoutEngine<'_>,
-    element: &'doc Element,
-    style: &ComputedStyle,
-    ancestors: &mut Vec<&'doc Element>,
-    max_width: i32,
-) -> Result<i32, String> {
-    let max_width = max_width.max(0);
-
-    let mut border_width = if inline::is_replaced_element(element) {
-        let size = inline::measure_replaced_element_outer_size(element, style, max_width)?;
-        size.width
-            .saturating_sub(style.margin.left.saturating_add(style.margin.right))
-            .max(0)
-    } else if let Some(width) = style.width_px {
-        width.resolve_px(max_width).max(0)
-    } else {
-        flex::measure_element_max_content_width(engine, element, style, ancestors, max_width)?
-    };
-
-    if let Some(min_width) = style.min_width_px {
-        border_width = border_width.max(min_width.resolve_px(max_width).max(0));
-    }
-    if let Some(max_width_value) = style.max_width_px {
-        border_width = border_width.min(max_width_value.resolve_px(max_width).max(0));
-    }
-
-    Ok(border_width.max(0))
+/// The bottom of the floats that `clear` moves past, or `None` when there are none.
+pub(super) fn clearance_y(floats: &[FloatPlacement], clear: Clear) -> Option<i32> {
+    floats
+        .iter()
+        .filter(|float| match clear {
+            Clear::None => false,
+            Clear::Left => float.side == Float::Left,
+            Clear::Right => float.side == Float::Right,
+            Clear::Both => true,
+        })
+        .map(|float| float.rect.bottom())
+        .max()
 }
 
 fn clearance_at_y(floats: &[FloatPlacement], containing: Rect, y: i32) -> FloatClearance {
@@ -0,0 +1,190 @@
+use crate::dom::{Element, Node};
+use crate::geom::Rect;
+use crate::render::svg::fit_view_box;
+use crate::render::{Point, Transform};
+use crate::style::ComputedStyle;
+
+use super::LayoutEngine;
+
+/// Maps a rectangle in SVG user units to the CSS pixel rectangle bounding its corners.
+fn map_rect(transform: Transform, x: f64, y: f64, width: f64, height: f64) -> Rect {
+    let corners = [
+        (x, y),
+        (x + width, y),
+        (x, y + height),
+        (x + width, y + height),
+    ]
+    .map(|(x, y)| transform.apply(Point::new(x, y)));
+    let left = corners.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let top = corners.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    let right = corners
+        .iter()
+        .map(|p| p.x)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let bottom = corners
+        .iter()
+        .map(|p| p.y)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let to_px = |value: f64| value.round().clamp(i32::MIN as f64, i32::MAX as f64) as i32;
+    Rect {
+        x: to_px(left),
+        y: to_px(top),
+        width: to_px(right - left),
+        height: to_px(bottom - top),
+    }
+}
+
+impl LayoutEngine<'_> {
+    /// Lays out the HTML inside each `<foreignObject>` of an inline SVG with the normal flow
+    /// engine and paints it over the SVG picture at the box bounding the object's mapped rectangle.
+    /// The HTML itself is not rotated or skewed, and keeps its CSS pixel font sizes when the
+    /// viewBox scales the picture.
+    pub(super) fn paint_svg_foreign_objects(
+        &mut self,
+        svg: &Element,
+        svg_style: &ComputedStyle,
+        content_box: Rect,
+    ) -> Result<(), String> {
+        if !contains_foreign_object(svg) {
+            return Ok(());
+        }
+        let viewport_transform =
+            Transform::translate(f64::from(content_box.x), f64::from(content_box.y))
+                .then(view_box_transform(svg, content_box));
+        let mut ancestors = vec![svg];
+        self.paint_foreign_objects_in(svg, svg_style, viewport_transform, &mut ancestors)
+    }
+
+    fn paint_foreign_objects_in<'doc>(
+        &mut self,
+        parent: &'doc Element,
+        parent_style: &ComputedStyle,
+        transform: Transform,
+        ancestors: &mut Vec<&'doc Element>,
+    ) -> Result<(), String> {
+        for child in &parent.children {
+            let Node::Element(element) = child else {
+                continue;
+            };
+            let style = self.compute_style(element, parent_style, ancestors);
+            let transform = element
+                .attributes
+                .get("transform")
+                .and_then(Transform::parse_list)
+                .map_or(transform, |inner| transform.then(inner));
+
+            if element.name != "foreignobject" {
+                ancestors.push(element);
+                self.paint_foreign_objects_in(element, &style, transform, ancestors)?;
+                ancestors.pop();
+                continue;
+            }
+
+            let length = |name: &str| {
+                element
+                    .attributes
+                    .get(name)
+                    .and_then(parse_svg_number)
+                    .unwrap_or(0.0)
+            };
+            let rect = map_rect(
+                transform,
+                length("x"),
+                length("y"),
+                length("width"),
+                length("height"),
+            );
+            if rect.width <= 0 || rect.height <= 0 {
+                continue;
+            }
+            ancestors.push(element);
+            self.layout_flow_children(&element.children, &style, ancestors, rect, true)?;
+            ancestors.pop();
+        }
+        Ok(())
+    }
+}
+
+fn contains_foreign_object(element: &Element) -> bool {
+    element.children.iter().any(|child| match child {
+        Node::Element(el) => el.name == "foreignobject" || contains_foreign_object(el),
+        Node::Text(_) => false,
+    })
+}
+
+/// Maps the `viewBox` onto the content box, honouring `preserveAspectRatio` alignment and
+/// `meet`/`slice`.
+fn view_box_transform(svg: &Element, content_box: Rect) -> Transform {
+    fit_view_box(
+        svg.attributes.get("viewbox"),
+        svg.attributes.get("preserveaspectratio"),
+        f64::from(content_box.width),
+        f64::from(content_box.height),
+    )
+    .unwrap_or(Transform::IDENTITY)
+}
+
+fn parse_svg_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value.strip_suffix("px").unwrap_or(value);
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn composes_translate_and_scale_in_document_order() {
+        let transform = Transform::parse_list("translate(10, 20) scale(2)").unwrap();
+        assert_eq!(
+            map_rect(transform, 1.0, 1.0, 5.0, 5.0),
+            Rect {
+                x: 12,
+                y: 22,
+                width: 10,
+                height: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn view_box_meets_and_centres_by_default() {
+        let doc = crate::html::parse_document(r#"<svg viewBox="0 0 100 50"></svg>"#);
+        let svg = doc.find_first_element_by_name("svg").unwrap();
+        let transform = view_box_transform(
+            svg,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 200,
+                height: 200,
+            },
+        );
+        assert_eq!(transform.a, 2.0);
+        assert_eq!(transform.d, 2.0);
+        assert_eq!(transform.f, 50.0);
+    }
+
+    #[test]
+    fn ignores_non_ascii_alignment() {
+        let doc = crate::html::parse_document(
+            "<svg viewBox='0 0 10 10' preserveAspectRatio='xx\u{20ac}\u{20ac}Mid'></svg>",
+        );
+        let svg = doc.find_first_element_by_name("svg").unwrap();
+        let transform = view_box_transform(
+            svg,
+            Rect {
+                x: 0,
+                y: 0,
+                width: 20,
+                height: 40,
+            },
+        );
+        assert_eq!(transform.a, 2.0);
+        assert_eq!(transform.f, 10.0);
+    }
+}
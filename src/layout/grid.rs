@@ -83,13 +83,7 @@ pub(super) fn layout_grid<'doc>(
                 }
             }
             Node::Element(el) => {
-                let child_style = engine.styles.compute_style_in_viewport(
-                    el,
-                    style,
-                    ancestors,
-                    engine.viewport.width_px,
-                    engine.viewport.height_px,
-                );
+                let child_style = engine.compute_style(el, style, ancestors);
                 if child_style.display == Display::None {
                     continue;
                 }
@@ -200,7 +194,14 @@ pub(super) fn layout_grid<'doc>(
 
     let containing = engine.current_positioned_containing_block();
     for item in positioned {
-        engine.layout_positioned_box(item.element, &item.style, ancestors, containing, paint)?;
+        engine.layout_positioned_box(
+            item.element,
+            &item.style,
+            ancestors,
+            containing,
+            (content_box.x, content_box.y),
+            paint,
+        )?;
     }
 
     Ok(cursor_y.saturating_sub(content_box.y).max(0))
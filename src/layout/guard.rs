@@ -0,0 +1,74 @@
+use crate::debug;
+use crate::dom::Element;
+use crate::geom::Color;
+use crate::render::{DisplayCommand, DrawRect, DrawText, TextStyle};
+
+use super::LayoutEngine;
+
+/// Elements nested deeper than this are left out, so a pathological document can't recurse
+/// through layout until the stack overflows.
+pub(super) const MAX_LAYOUT_DEPTH: usize = 256;
+/// How many times one layout may style an element, intrinsic sizing passes included, before the
+/// rest of the page is left out.
+pub(super) const MAX_LAYOUT_ELEMENTS: usize = 2_000_000;
+
+const BANNER_TEXT: &str = "Part of this page is too deeply nested or too large to show.";
+const BANNER_PADDING_PX: i32 = 6;
+const BANNER_COLOR: Color = Color {
+    r: 0xff,
+    g: 0xf3,
+    b: 0xcd,
+    a: 255,
+};
+
+impl LayoutEngine<'_> {
+    /// Whether an element under `ancestors` is laid out, counting it against the work budget.
+    /// The first element left out logs a warning.
+    pub(super) fn within_budget(&self, ancestors: &[&Element]) -> bool {
+        let styled = self.elements_styled.get().saturating_add(1);
+        self.elements_styled.set(styled);
+        if ancestors.len() < MAX_LAYOUT_DEPTH && styled <= MAX_LAYOUT_ELEMENTS {
+            return true;
+        }
+        if !self.truncated.replace(true)
+            && debug::enabled(debug::Target::Layout, debug::Level::Warn)
+        {
+            let reason = if styled > MAX_LAYOUT_ELEMENTS {
+                "too_large"
+            } else {
+                "too_deep"
+            };
+            debug::log(
+                debug::Target::Layout,
+                debug::Level::Warn,
+                format_args!("layout! depth={} err={reason}", ancestors.len()),
+            );
+        }
+        false
+    }
+
+    /// Says across the top of the viewport that part of the page was left out.
+    pub(super) fn paint_truncation_banner(&mut self) {
+        let style = TextStyle::default();
+        let metrics = self.measurer.font_metrics_px(style);
+        let height_px = metrics
+            .ascent_px
+            .saturating_add(metrics.descent_px)
+            .saturating_add(BANNER_PADDING_PX * 2);
+        self.list.commands.push(DisplayCommand::PushFixed);
+        self.list.commands.push(DisplayCommand::Rect(DrawRect {
+            x_px: 0,
+            y_px: 0,
+            width_px: self.viewport.width_px.max(0),
+            height_px,
+            color: BANNER_COLOR,
+        }));
+        self.list.commands.push(DisplayCommand::Text(DrawText {
+            x_px: BANNER_PADDING_PX,
+            y_px: BANNER_PADDING_PX.saturating_add(metrics.ascent_px),
+            text: BANNER_TEXT.into(),
+            style,
+        }));
+        self.list.commands.push(DisplayCommand::PopFixed);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::dom::{Document, Element, Node};
 use crate::geom::{Edges, Rect};
-use crate::style::{AutoEdges, ComputedStyle, Display, StyleComputer, TextAlign};
+use crate::style::{
+    AutoEdges, ComputedStyle, CssLength, Direction, Display, StyleComputer, TextAlign,
+};
 
 pub(super) fn add_edges(a: Edges, b: Edges) -> Edges {
     Edges {
@@ -11,6 +13,26 @@ pub(super) fn add_edges(a: Edges, b: Edges) -> Edges {
     }
 }
 
+/// The border height `style` sets, kept within its `max-height` and then its `min-height`, or
+/// `None` when its content decides. `compute_style` has already resolved any percentages.
+pub(super) fn used_height_px(style: &ComputedStyle) -> Option<i32> {
+    let height = resolved_px(style.height_px)?;
+    let height = resolved_px(style.max_height_px).map_or(height, |max| height.min(max));
+    Some(
+        min_height_px(style)
+            .map_or(height, |min| height.max(min))
+            .max(0),
+    )
+}
+
+pub(super) fn min_height_px(style: &ComputedStyle) -> Option<i32> {
+    resolved_px(style.min_height_px).map(|height| height.max(0))
+}
+
+fn resolved_px(length: Option<CssLength>) -> Option<i32> {
+    length.map(|length| length.resolve_px(0))
+}
+
 pub(super) fn constrain_flow_content_box(content_box: Rect, flow_area: Rect) -> Rect {
     let left = flow_area.x.max(content_box.x);
     let right = flow_area.right().min(content_box.right());
@@ -74,6 +96,9 @@ pub(super) fn resolve_canvas_background(
             return html_style.background_color;
         }
     }
+    if body_style.background_color.is_none() && styles.inverts_colors() {
+        return Some(crate::style::invert_lightness(crate::geom::Color::WHITE));
+    }
     body_style.background_color
 }
 
@@ -121,6 +146,7 @@ pub(super) fn is_flow_block(style: &ComputedStyle, element: &Element) -> bool {
 
 pub(super) fn apply_block_alignment(
     align: TextAlign,
+    direction: Direction,
     containing: Rect,
     default_x: i32,
     width: i32,
@@ -135,13 +161,13 @@ pub(super) fn apply_block_alignment(
     if available <= width {
         return default_x;
     }
-    match align {
+    match align.resolve(direction) {
         TextAlign::Center => containing.x.saturating_add((available - width) / 2),
         TextAlign::Right => containing
             .x
             .saturating_add(available.saturating_sub(width))
             .saturating_add(margin.left),
-        TextAlign::Left => default_x,
+        _ => default_x,
     }
 }
 
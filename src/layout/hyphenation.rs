@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::hyphenation_patterns::PATTERNS;
+
+/// U+00AD, where a word may break with a hyphen shown at the end of the line.
+pub(super) const SOFT_HYPHEN: char = '\u{00AD}';
+
+/// Letters kept together at the start and end of a hyphenated word, as TeX does for English.
+const LEFT_MIN: usize = 2;
+const RIGHT_MIN: usize = 3;
+
+/// Each pattern's letters, mapped to the priority of a break before each of them and after the
+/// last.
+fn patterns() -> &'static HashMap<String, Vec<u8>> {
+    static PARSED: OnceLock<HashMap<String, Vec<u8>>> = OnceLock::new();
+    PARSED.get_or_init(|| {
+        PATTERNS
+            .split_whitespace()
+            .map(|pattern| {
+                let mut letters = String::new();
+                let mut priorities = vec![0u8];
+                for ch in pattern.chars() {
+                    match ch.to_digit(10) {
+                        Some(digit) => *priorities.last_mut().unwrap_or(&mut 0) = digit as u8,
+                        None => {
+                            letters.push(ch);
+                            priorities.push(0);
+                        }
+                    }
+                }
+                (letters, priorities)
+            })
+            .collect()
+    })
+}
+
+/// `word` with soft hyphens wherever the English patterns allow a break, or `None` when they
+/// allow none. Only a run of ASCII letters is hyphenated; punctuation around it stays as it is.
+pub(super) fn hyphenate(word: &str) -> Option<String> {
+    let start = word.find(|ch: char| ch.is_ascii_alphabetic())?;
+    let end = word
+        .rfind(|ch: char| ch.is_ascii_alphabetic())
+        .map_or(word.len(), |index| index + 1);
+    let core = &word[start..end];
+    if core.len() < LEFT_MIN + RIGHT_MIN || !core.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let padded: Vec<char> = format!(".{}.", core.to_ascii_lowercase()).chars().collect();
+    let patterns = patterns();
+    let mut points = vec![0u8; padded.len() + 1];
+    let mut key = String::new();
+    for from in 0..padded.len() {
+        key.clear();
+        for &ch in &padded[from..] {
+            key.push(ch);
+            if let Some(priorities) = patterns.get(&key) {
+                for (offset, &priority) in priorities.iter().enumerate() {
+                    let point = &mut points[from + offset];
+                    *point = (*point).max(priority);
+                }
+            }
+        }
+    }
+
+    let mut hyphenated = String::with_capacity(word.len() + 8);
+    hyphenated.push_str(&word[..start]);
+    let mut broken = false;
+    for (index, ch) in core.chars().enumerate() {
+        if index >= LEFT_MIN && index + RIGHT_MIN <= core.len() && points[index + 1] % 2 == 1 {
+            hyphenated.push(SOFT_HYPHEN);
+            broken = true;
+        }
+        hyphenated.push(ch);
+    }
+    hyphenated.push_str(&word[end..]);
+    broken.then_some(hyphenated)
+}
+
+/// `text` as it shows when it doesn't break at its soft hyphens.
+pub(super) fn without_soft_hyphens(text: &str) -> Cow<'_, str> {
+    if text.contains(SOFT_HYPHEN) {
+        Cow::Owned(text.replace(SOFT_HYPHEN, ""))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaks(word: &str) -> String {
+        hyphenate(word).map_or_else(|| word.to_string(), |word| word.replace(SOFT_HYPHEN, "-"))
+    }
+
+    #[test]
+    fn english_words_break_where_the_patterns_allow() {
+        assert_eq!(breaks("hyphenation"), "hy-phen-ation");
+        assert_eq!(breaks("Typography,"), "Ty-pog-ra-phy,");
+        assert_eq!(breaks("computer"), "com-puter");
+        assert_eq!(breaks("the"), "the");
+        assert_eq!(breaks("naïveté"), "naïveté");
+        assert_eq!(without_soft_hyphens("co\u{00AD}op"), "coop");
+    }
+}
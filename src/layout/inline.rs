@@ -1,27 +1,59 @@
-use crate::dom::{Element, Node};
+use crate::dom::{Element, Node, element_path};
 use crate::geom::{Rect, Size};
-use crate::render::{DisplayCommand, DrawText, FontMetricsPx, LinkHitRegion, TextStyle};
-use crate::style::{ComputedStyle, Display, TextAlign, Visibility, WhiteSpace};
+use crate::render::canvas;
+use crate::render::{DisplayCommand, DrawText, FontMetricsPx, LinkHitRegion, TextStyle, shaping};
+use crate::style::{
+    ComputedStyle, Direction, Display, FontVariantCaps, Hyphens, TextAlign, VerticalAlign,
+    Visibility, WhiteSpace,
+};
+use std::borrow::Cow;
 use std::rc::Rc;
 
-use super::LayoutEngine;
+use super::bidi::{self, BidiClass};
+use super::first_line::{self, FirstLetter, Restyle};
+use super::outline::heading_level;
+use super::{AnchorLink, LayoutEngine, anchor_link, hyphenation};
 
 #[derive(Clone, Debug)]
 enum InlineToken<'doc> {
-    Word(String, TextStyle, bool, Option<Rc<str>>),
-    Space(TextStyle, bool, Option<Rc<str>>),
+    Word(
+        String,
+        TextStyle,
+        bool,
+        Option<AnchorLink>,
+        Option<Rc<InlineOwner<'doc>>>,
+    ),
+    /// A space between words, widened by the `word-spacing` it carries.
+    Space(TextStyle, i32, bool, Option<AnchorLink>),
+    /// Keeps the tokens either side on one line, as pieces of the same word.
+    Join,
     Newline,
     Spacer(Size),
     ElementBox(InlineElementBox<'doc>),
 }
 
+/// An inline element around a word, kept while the layout is inspected so the element can be
+/// given the boxes of the lines its words end up on.
+#[derive(Debug)]
+struct InlineOwner<'doc> {
+    element: &'doc Element,
+    style: ComputedStyle,
+    path: String,
+    depth: usize,
+    /// The inline element this one sits in, if any.
+    parent: Option<Rc<InlineOwner<'doc>>>,
+}
+
 #[derive(Clone, Debug)]
 struct InlineElementBox<'doc> {
     element: &'doc Element,
     style: ComputedStyle,
     size: Size,
+    /// How far below the top of its margin box the box's own baseline is.
+    baseline_px: i32,
     visible: bool,
-    link_href: Option<Rc<str>>,
+    link: Option<AnchorLink>,
+    owner: Option<Rc<InlineOwner<'doc>>>,
 }
 
 pub(super) fn layout_inline_nodes<'doc>(
@@ -53,7 +85,7 @@ pub(super) fn layout_inline_nodes_with_link<'doc>(
     content_box: Rect,
     start_y: i32,
     paint: bool,
-    link_href: Option<Rc<str>>,
+    link: Option<AnchorLink>,
 ) -> Result<i32, String> {
     let mut tokens = Vec::new();
     let mut cursor = InlineCursor::default();
@@ -65,7 +97,7 @@ pub(super) fn layout_inline_nodes_with_link<'doc>(
             parent_style,
             ancestors,
             paint,
-            link_href.clone(),
+            link.clone(),
             &mut cursor,
             &mut tokens,
             content_box.width,
@@ -111,23 +143,34 @@ pub(super) fn measure_inline_nodes<'doc>(
 }
 
 #[derive(Default)]
-struct InlineCursor {
+struct InlineCursor<'doc> {
     pending_space: Option<PendingSpace>,
+    /// The innermost inline element being collected, while the layout is inspected or inside an
+    /// inline heading, whose outline entry takes the position of its first line.
+    owner: Option<Rc<InlineOwner<'doc>>>,
 }
 
 #[derive(Clone, Debug)]
 struct PendingSpace {
     style: TextStyle,
+    word_spacing_px: i32,
     visible: bool,
-    link_href: Option<Rc<str>>,
+    link: Option<AnchorLink>,
 }
 
-impl InlineCursor {
-    fn mark_pending_space(&mut self, style: TextStyle, visible: bool, link_href: Option<Rc<str>>) {
+impl<'doc> InlineCursor<'doc> {
+    fn mark_pending_space(
+        &mut self,
+        style: TextStyle,
+        word_spacing_px: i32,
+        visible: bool,
+        link: Option<AnchorLink>,
+    ) {
         self.pending_space = Some(PendingSpace {
             style,
+            word_spacing_px,
             visible,
-            link_href,
+            link,
         });
     }
 
@@ -135,7 +178,7 @@ impl InlineCursor {
         self.pending_space = None;
     }
 
-    fn flush_pending_space<'doc>(&mut self, out: &mut Vec<InlineToken<'doc>>) {
+    fn flush_pending_space(&mut self, out: &mut Vec<InlineToken<'doc>>) {
         let Some(space) = self.pending_space.take() else {
             return;
         };
@@ -144,8 +187,9 @@ impl InlineCursor {
         }
         out.push(InlineToken::Space(
             space.style,
+            space.word_spacing_px,
             space.visible,
-            space.link_href,
+            space.link,
         ));
     }
 }
@@ -156,8 +200,8 @@ fn collect_tokens<'doc>(
     parent_style: &ComputedStyle,
     ancestors: &mut Vec<&'doc Element>,
     paint: bool,
-    link_href: Option<Rc<str>>,
-    cursor: &mut InlineCursor,
+    link: Option<AnchorLink>,
+    cursor: &mut InlineCursor<'doc>,
     out: &mut Vec<InlineToken<'doc>>,
     max_width: i32,
 ) -> Result<(), String> {
@@ -166,23 +210,18 @@ fn collect_tokens<'doc>(
             let visible = paint && parent_style.visibility == Visibility::Visible;
             let transformed = parent_style.text_transform.apply(text);
             push_text(
+                engine,
                 transformed.as_ref(),
-                engine.text_style_for(parent_style),
+                parent_style,
                 visible,
-                link_href,
+                link,
                 cursor,
                 out,
             );
             Ok(())
         }
         Node::Element(el) => {
-            let style = engine.styles.compute_style_in_viewport(
-                el,
-                parent_style,
-                ancestors,
-                engine.viewport.width_px,
-                engine.viewport.height_px,
-            );
+            let style = engine.compute_style(el, parent_style, ancestors);
             if style.display == Display::None {
                 return Ok(());
             }
@@ -193,7 +232,11 @@ fn collect_tokens<'doc>(
                 return Ok(());
             }
 
-            let link_href = anchor_href(el).or(link_href);
+            let link = match anchor_link(el) {
+                _ if el.is_inert() => None,
+                Some(own) => (!ancestors.iter().any(|ancestor| ancestor.is_inert())).then_some(own),
+                None => link,
+            };
             let paint = paint && style.visibility == Visibility::Visible;
             if is_replaced_element(el) {
                 cursor.flush_pending_space(out);
@@ -202,8 +245,10 @@ fn collect_tokens<'doc>(
                     element: el,
                     style,
                     size,
+                    baseline_px: size.height,
                     visible: paint,
-                    link_href,
+                    link,
+                    owner: cursor.owner.clone(),
                 }));
                 return Ok(());
             }
@@ -213,6 +258,10 @@ fn collect_tokens<'doc>(
                 Display::Inline => {
                     let padding = style.padding.resolve_px(max_width);
                     push_inline_spacing(out, style.margin.left.saturating_add(padding.left));
+                    let outer_owner = cursor.owner.clone();
+                    if paint && (engine.inspected_boxes.is_some() || heading_level(el).is_some()) {
+                        cursor.owner = Some(inline_owner(el, &style, ancestors, &outer_owner));
+                    }
                     for child in &el.children {
                         collect_tokens(
                             engine,
@@ -220,12 +269,13 @@ fn collect_tokens<'doc>(
                             &style,
                             ancestors,
                             paint,
-                            link_href.clone(),
+                            link.clone(),
                             cursor,
                             out,
                             max_width,
                         )?;
                     }
+                    cursor.owner = outer_owner;
                     push_inline_spacing(out, style.margin.right.saturating_add(padding.right));
                 }
                 _ => {
@@ -233,12 +283,15 @@ fn collect_tokens<'doc>(
                     let size = measure_inline_element_outer_size(
                         engine, el, &style, ancestors, max_width,
                     )?;
+                    let baseline_px = inline_block_baseline_px(engine, el, &style, size, max_width);
                     out.push(InlineToken::ElementBox(InlineElementBox {
                         element: el,
                         style,
                         size,
+                        baseline_px,
                         visible: paint,
-                        link_href,
+                        link,
+                        owner: cursor.owner.clone(),
                     }));
                 }
             }
@@ -248,19 +301,28 @@ fn collect_tokens<'doc>(
     }
 }
 
-fn anchor_href(element: &Element) -> Option<Rc<str>> {
-    if element.name != "a" {
-        return None;
-    }
-    let href = element.attributes.get("href")?.trim();
-    if href.is_empty() {
-        return None;
-    }
-    Some(Rc::from(href))
+/// The owner of the tokens collected inside `element`, the last of `ancestors`.
+fn inline_owner<'doc>(
+    element: &'doc Element,
+    style: &ComputedStyle,
+    ancestors: &[&'doc Element],
+    parent: &Option<Rc<InlineOwner<'doc>>>,
+) -> Rc<InlineOwner<'doc>> {
+    let outer = &ancestors[..ancestors.len() - 1];
+    Rc::new(InlineOwner {
+        element,
+        style: style.clone(),
+        path: element_path(outer, element),
+        depth: outer.len(),
+        parent: parent.clone(),
+    })
 }
 
 pub(super) fn is_replaced_element(element: &Element) -> bool {
-    matches!(element.name.as_str(), "img" | "input" | "svg")
+    matches!(
+        element.name.as_str(),
+        "img" | "input" | "svg" | "canvas" | "video" | "audio"
+    )
 }
 
 fn push_inline_spacing<'doc>(out: &mut Vec<InlineToken<'doc>>, width: i32) {
@@ -271,6 +333,34 @@ fn push_inline_spacing<'doc>(out: &mut Vec<InlineToken<'doc>>, width: i32) {
     out.push(InlineToken::Spacer(Size { width, height: 0 }));
 }
 
+/// The baseline of an inline-block: that of the first line of its content, taken to be a line
+/// in its own font, or the bottom of its margin box when it has no content.
+fn inline_block_baseline_px(
+    engine: &LayoutEngine<'_>,
+    element: &Element,
+    style: &ComputedStyle,
+    size: Size,
+    max_width: i32,
+) -> i32 {
+    if element.children.is_empty() {
+        return size.height;
+    }
+    let metrics = engine
+        .measurer
+        .font_metrics_px(engine.text_style_for(style));
+    let explicit_line_height_px = style
+        .line_height
+        .resolve_px(style.font_size_px)
+        .map(|value| value.max(1));
+    let padding = style.padding.resolve_px(max_width.max(0));
+    style
+        .margin
+        .top
+        .saturating_add(style.border_width.top)
+        .saturating_add(padding.top)
+        .saturating_add(Line::new(explicit_line_height_px, metrics).baseline_offset_px())
+}
+
 fn measure_inline_element_outer_size<'doc>(
     engine: &LayoutEngine<'_>,
     element: &'doc Element,
@@ -309,8 +399,8 @@ fn measure_inline_element_outer_size<'doc>(
     }
     border_width = border_width.min(available_border_width).max(0);
 
-    let mut border_height = if let Some(height) = style.height_px {
-        height.max(0)
+    let mut border_height = if let Some(height) = super::used_height_px(style) {
+        height
     } else {
         let available_content_width = border_width.saturating_sub(horizontal_inset).max(0);
         let nodes: Vec<&Node> = element.children.iter().collect();
@@ -319,8 +409,8 @@ fn measure_inline_element_outer_size<'doc>(
         content_size.height.saturating_add(vertical_inset)
     };
 
-    if let Some(min_height) = style.min_height_px {
-        border_height = border_height.max(min_height.max(0));
+    if let Some(min_height) = super::min_height_px(style) {
+        border_height = border_height.max(min_height);
     }
 
     Ok(Size {
@@ -358,9 +448,8 @@ pub(super) fn measure_replaced_element_outer_size(
             .saturating_sub(horizontal_inset)
             .max(0)
     });
-    let mut content_height = style
-        .height_px
-        .map(|height| height.max(0).saturating_sub(vertical_inset).max(0));
+    let mut content_height =
+        super::used_height_px(style).map(|height| height.saturating_sub(vertical_inset).max(0));
 
     let (intrinsic_width, intrinsic_height) = intrinsic_dimensions(element, style);
     let ratio = intrinsic_aspect_ratio(element, intrinsic_width, intrinsic_height);
@@ -404,8 +493,8 @@ pub(super) fn measure_replaced_element_outer_size(
     }
     border_width = border_width.min(available_border_width).max(0);
 
-    if let Some(min_height) = style.min_height_px {
-        border_height = border_height.max(min_height.max(0));
+    if let Some(min_height) = super::min_height_px(style) {
+        border_height = border_height.max(min_height);
     }
 
     Ok(Size {
@@ -420,6 +509,13 @@ pub(super) fn measure_replaced_element_outer_size(
     })
 }
 
+/// The size of a `<video>` without `width` and `height`, whose poster isn't measured, and of the
+/// controls of an `<audio>`.
+const DEFAULT_VIDEO_WIDTH_PX: i32 = 300;
+const DEFAULT_VIDEO_HEIGHT_PX: i32 = 150;
+const DEFAULT_AUDIO_WIDTH_PX: i32 = 300;
+const DEFAULT_AUDIO_HEIGHT_PX: i32 = 54;
+
 fn intrinsic_dimensions(element: &Element, style: &ComputedStyle) -> (Option<i32>, Option<i32>) {
     let mut width = element
         .attributes
@@ -445,6 +541,21 @@ fn intrinsic_dimensions(element: &Element, style: &ComputedStyle) -> (Option<i32
         }
     }
 
+    if element.name == "canvas" {
+        width = width.or(Some(canvas::DEFAULT_WIDTH as i32));
+        height = height.or(Some(canvas::DEFAULT_HEIGHT as i32));
+    }
+
+    if element.name == "video" {
+        width = width.or(Some(DEFAULT_VIDEO_WIDTH_PX));
+        height = height.or(Some(DEFAULT_VIDEO_HEIGHT_PX));
+    }
+
+    if element.name == "audio" {
+        width = width.or(Some(DEFAULT_AUDIO_WIDTH_PX));
+        height = height.or(Some(DEFAULT_AUDIO_HEIGHT_PX));
+    }
+
     if element.name == "input" {
         let (default_width, default_height) = intrinsic_input_content_dimensions(element, style);
         if width.is_none() {
@@ -579,17 +690,19 @@ pub(super) fn serialize_element_xml(element: &Element) -> String {
 }
 
 fn push_text<'doc>(
+    engine: &LayoutEngine<'_>,
     text: &str,
-    style: TextStyle,
+    parent_style: &ComputedStyle,
     visible: bool,
-    link_href: Option<Rc<str>>,
-    cursor: &mut InlineCursor,
+    link: Option<AnchorLink>,
+    cursor: &mut InlineCursor<'doc>,
     out: &mut Vec<InlineToken<'doc>>,
 ) {
+    let style = engine.text_style_for(parent_style);
     let mut iter = text.chars().peekable();
     while let Some(ch) = iter.next() {
         if ch.is_whitespace() {
-            cursor.mark_pending_space(style, visible, link_href.clone());
+            cursor.mark_pending_space(style, parent_style.word_spacing_px, visible, link.clone());
             continue;
         }
 
@@ -604,8 +717,94 @@ fn push_text<'doc>(
             word.push(next);
             iter.next();
         }
-        out.push(InlineToken::Word(word, style, visible, link_href.clone()));
+        match parent_style.hyphens {
+            Hyphens::None => {
+                if let Cow::Owned(unbroken) = hyphenation::without_soft_hyphens(&word) {
+                    word = unbroken;
+                }
+            }
+            Hyphens::Manual => {}
+            Hyphens::Auto => {
+                if !word.contains(hyphenation::SOFT_HYPHEN)
+                    && let Some(hyphenated) = hyphenation::hyphenate(&word)
+                {
+                    word = hyphenated;
+                }
+            }
+        }
+        if let Cow::Owned(shaped) = shaping::shape(&word) {
+            word = shaped;
+        }
+        if parent_style.font_variant_caps == FontVariantCaps::Normal {
+            out.push(InlineToken::Word(
+                word,
+                style,
+                visible,
+                link.clone(),
+                cursor.owner.clone(),
+            ));
+            continue;
+        }
+        for (index, (run, run_style)) in
+            small_caps_runs(&word, style, parent_style.font_variant_caps)
+                .into_iter()
+                .enumerate()
+        {
+            if index > 0 {
+                out.push(InlineToken::Join);
+            }
+            out.push(InlineToken::Word(
+                run,
+                run_style,
+                visible,
+                link.clone(),
+                cursor.owner.clone(),
+            ));
+        }
+    }
+}
+
+/// `text` in `style` as it shows on a line `restyle` changes, if any.
+fn restyled_word(
+    restyle: Option<Restyle>,
+    text: &str,
+    style: TextStyle,
+) -> (Cow<'_, str>, TextStyle) {
+    match restyle {
+        Some(restyle) => (restyle.text(text), restyle.style(style)),
+        None => (Cow::Borrowed(text), style),
+    }
+}
+
+/// Splits `word` into runs set in `style` and runs of small capitals, synthesized as capitals at
+/// seven tenths of the size.
+fn small_caps_runs(
+    word: &str,
+    style: TextStyle,
+    caps: FontVariantCaps,
+) -> Vec<(String, TextStyle)> {
+    let small = TextStyle {
+        font_size_px: style
+            .font_size_px
+            .saturating_mul(7)
+            .saturating_div(10)
+            .max(1),
+        ..style
+    };
+    let mut runs: Vec<(String, TextStyle)> = Vec::new();
+    for ch in word.chars() {
+        let small_cap = match caps {
+            FontVariantCaps::Normal => false,
+            FontVariantCaps::SmallCaps => ch.is_lowercase(),
+            FontVariantCaps::AllSmallCaps => ch.is_alphabetic(),
+        };
+        let ch_style = if small_cap { small } else { style };
+        match runs.last_mut() {
+            Some((run, run_style)) if *run_style == ch_style => run.extend(ch.to_uppercase()),
+            _ => runs.push((ch.to_uppercase().collect(), ch_style)),
+        }
     }
+    runs
 }
 
 fn layout_tokens<'doc>(
@@ -617,6 +816,7 @@ fn layout_tokens<'doc>(
     start_y: i32,
     paint: bool,
 ) -> Result<i32, String> {
+    let first_line = engine.first_line.take();
     let mut lines: Vec<Line<'doc>> = Vec::new();
     let base_style = engine.text_style_for(parent_style);
     let base_metrics = engine.measurer.font_metrics_px(base_style);
@@ -625,72 +825,168 @@ fn layout_tokens<'doc>(
         .resolve_px(parent_style.font_size_px)
         .map(|value| value.max(1));
     let nowrap = parent_style.white_space == WhiteSpace::NoWrap;
-    let mut line = Line::new(explicit_line_height_px, base_metrics);
-    let mut x_px = 0i32;
 
-    for token in tokens {
+    let FirstLetterSplit {
+        tokens,
+        letter_index,
+        drop_cap,
+    } = match first_line.as_ref().and_then(|first_line| first_line.letter) {
+        Some(letter) => split_first_letter_token(engine, tokens, letter)?,
+        None => FirstLetterSplit::unsplit(tokens),
+    };
+    let line_restyle = first_line.as_ref().and_then(|first_line| first_line.line);
+    let beside_cap = drop_cap.as_ref().map_or(0, |cap| {
+        cap.line_count(Line::new(explicit_line_height_px, base_metrics).height_px)
+    });
+    let new_line = |index: usize| {
+        let mut line = Line::new(explicit_line_height_px, base_metrics);
+        if index < beside_cap {
+            line.inset_px = drop_cap.as_ref().map_or(0, |cap| cap.inset_px);
+        }
+        line
+    };
+
+    let mut line = new_line(0);
+    let mut line_start_px = first_line.as_ref().map_or(0, |first_line| {
+        first_line.indent.resolve_px(content_box.width)
+    });
+    if line_start_px != 0 {
+        line.push(Fragment::Spacer(Size {
+            width: line_start_px,
+            height: 0,
+        }));
+    }
+    let mut x_px = line_start_px;
+    let mut joined = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let on_first_line = lines.is_empty();
+        let joins_previous = std::mem::take(&mut joined);
         match token {
+            InlineToken::Join => joined = true,
             InlineToken::Newline => {
-                lines.push(std::mem::replace(
-                    &mut line,
-                    Line::new(explicit_line_height_px, base_metrics),
-                ));
+                lines.push(std::mem::replace(&mut line, new_line(lines.len() + 1)));
                 x_px = 0;
+                line_start_px = 0;
             }
-            InlineToken::Space(style, visible, link_href) => {
-                if x_px == 0 {
+            InlineToken::Space(style, word_spacing_px, visible, link) => {
+                if x_px == line_start_px {
                     continue;
                 }
-                let space_width_px = engine.measurer.text_width_px(" ", *style)?;
-                if !nowrap && x_px.saturating_add(space_width_px) > content_box.width {
+                let style = match line_restyle {
+                    Some(restyle) if on_first_line => restyle.style(*style),
+                    _ => *style,
+                };
+                let space_width_px = engine
+                    .measurer
+                    .text_width_px(" ", style)?
+                    .saturating_add(*word_spacing_px);
+                if !nowrap
+                    && x_px.saturating_add(space_width_px) > line.available_px(content_box.width)
+                {
                     continue;
                 }
-                let metrics = engine.measurer.font_metrics_px(*style);
+                let metrics = engine.measurer.font_metrics_px(style);
                 line.push(Fragment::Text(
                     " ".to_owned(),
-                    *style,
+                    style,
                     space_width_px,
                     metrics,
                     *visible,
-                    link_href.clone(),
+                    link.clone(),
+                    None,
                 ));
                 x_px = x_px.saturating_add(space_width_px);
             }
-            InlineToken::Word(text, style, visible, link_href) => {
+            InlineToken::Word(text, style, visible, link, owner) => {
                 if text.is_empty() {
                     continue;
                 }
-                let word_width_px = engine.measurer.text_width_px(text, *style)?;
-                if !nowrap && x_px != 0 && x_px.saturating_add(word_width_px) > content_box.width {
-                    lines.push(std::mem::replace(
-                        &mut line,
-                        Line::new(explicit_line_height_px, base_metrics),
-                    ));
+                let restyle = |on_first_line: bool| {
+                    line_restyle.filter(|_| on_first_line && letter_index != Some(index))
+                };
+                let mut rest = text.as_str();
+                let mut joins_previous = joins_previous;
+                loop {
+                    let word_restyle = restyle(lines.is_empty());
+                    let (word, word_style) = restyled_word(word_restyle, rest, *style);
+                    let word = hyphenation::without_soft_hyphens(&word).into_owned();
+                    let word_width_px = engine.measurer.text_width_px(&word, word_style)?;
+                    let room_px = line.available_px(content_box.width).saturating_sub(x_px);
+                    if nowrap || word_width_px <= room_px {
+                        let metrics = engine.measurer.font_metrics_px(word_style);
+                        line.push(Fragment::Text(
+                            word,
+                            word_style,
+                            word_width_px,
+                            metrics,
+                            *visible,
+                            link.clone(),
+                            owner.clone(),
+                        ));
+                        x_px = x_px.saturating_add(word_width_px);
+                        break;
+                    }
+
+                    let at_line_start = joins_previous || x_px == line_start_px;
+                    let mut hyphenated = None;
+                    for (split, _) in rest.match_indices(hyphenation::SOFT_HYPHEN) {
+                        let (head, _) = restyled_word(word_restyle, &rest[..split], *style);
+                        let head = format!("{}-", hyphenation::without_soft_hyphens(&head));
+                        let head_width_px = engine.measurer.text_width_px(&head, word_style)?;
+                        if head_width_px > room_px && (hyphenated.is_some() || !at_line_start) {
+                            break;
+                        }
+                        hyphenated = Some((head, head_width_px, split));
+                        if head_width_px > room_px {
+                            break;
+                        }
+                    }
+                    if let Some((head, head_width_px, split)) = hyphenated {
+                        let metrics = engine.measurer.font_metrics_px(word_style);
+                        line.push(Fragment::Text(
+                            head,
+                            word_style,
+                            head_width_px,
+                            metrics,
+                            *visible,
+                            link.clone(),
+                            owner.clone(),
+                        ));
+                        rest = &rest[split + hyphenation::SOFT_HYPHEN.len_utf8()..];
+                    } else if at_line_start {
+                        let metrics = engine.measurer.font_metrics_px(word_style);
+                        line.push(Fragment::Text(
+                            word,
+                            word_style,
+                            word_width_px,
+                            metrics,
+                            *visible,
+                            link.clone(),
+                            owner.clone(),
+                        ));
+                        x_px = x_px.saturating_add(word_width_px);
+                        break;
+                    }
+                    lines.push(std::mem::replace(&mut line, new_line(lines.len() + 1)));
                     x_px = 0;
+                    line_start_px = 0;
+                    joins_previous = false;
                 }
-
-                let metrics = engine.measurer.font_metrics_px(*style);
-                line.push(Fragment::Text(
-                    text.clone(),
-                    *style,
-                    word_width_px,
-                    metrics,
-                    *visible,
-                    link_href.clone(),
-                ));
-                x_px = x_px.saturating_add(word_width_px);
             }
             InlineToken::Spacer(size) => {
                 line.push(Fragment::Spacer(*size));
                 x_px = x_px.saturating_add(size.width);
             }
             InlineToken::ElementBox(b) => {
-                if !nowrap && x_px != 0 && x_px.saturating_add(b.size.width) > content_box.width {
-                    lines.push(std::mem::replace(
-                        &mut line,
-                        Line::new(explicit_line_height_px, base_metrics),
-                    ));
+                if !nowrap
+                    && !joins_previous
+                    && x_px != line_start_px
+                    && x_px.saturating_add(b.size.width) > line.available_px(content_box.width)
+                {
+                    lines.push(std::mem::replace(&mut line, new_line(lines.len() + 1)));
                     x_px = 0;
+                    line_start_px = 0;
                 }
                 line.push(Fragment::ElementBox(b.clone()));
                 x_px = x_px.saturating_add(b.size.width);
@@ -702,31 +998,64 @@ fn layout_tokens<'doc>(
         lines.push(line);
     }
 
+    if let Some(cap) = drop_cap.as_ref().filter(|cap| paint && cap.visible) {
+        if engine.fixed_depth == 0 {
+            engine.text_right_px = engine
+                .text_right_px
+                .max(content_box.x.saturating_add(cap.width_px));
+        }
+        engine.list.commands.push(DisplayCommand::Text(DrawText {
+            x_px: content_box.x,
+            y_px: start_y.saturating_add(cap.metrics.ascent_px),
+            text: cap.text.clone(),
+            style: cap.style,
+        }));
+    }
+
+    let mut outlined_headings: Vec<&Element> = Vec::new();
     let mut y_px = start_y;
-    for line in lines {
+    for mut line in lines {
         let line_width = line.width_px;
-        let align = parent_style.text_align;
+        let available_px = line.available_px(content_box.width);
+        let align = parent_style.text_align.resolve(parent_style.direction);
         let x_offset = match align {
-            TextAlign::Left => 0,
-            TextAlign::Center => ((content_box.width - line_width) / 2).max(0),
-            TextAlign::Right => (content_box.width - line_width).max(0),
+            TextAlign::Center => ((available_px - line_width) / 2).max(0),
+            TextAlign::Right => (available_px - line_width).max(0),
+            _ => 0,
         };
+        let rtl = parent_style.direction == Direction::Rtl;
+        if rtl
+            || line.fragments.iter().any(|fragment| {
+                matches!(fragment, Fragment::Text(text, ..) if bidi::has_right_to_left(text))
+            })
+        {
+            reorder_bidi(&mut line.fragments, rtl);
+        }
 
         let baseline_y = y_px.saturating_add(line.baseline_offset_px());
-        let mut x_px = content_box.x.saturating_add(x_offset);
-        for frag in line.fragments {
+        let mut x_px = content_box
+            .x
+            .saturating_add(line.inset_px)
+            .saturating_add(x_offset);
+        let mut owner_boxes = Vec::new();
+        for frag in std::mem::take(&mut line.fragments) {
             match frag {
-                Fragment::Text(text, style, width, _metrics, visible, link_href) => {
+                Fragment::Text(text, style, width, _metrics, visible, link, owner) => {
                     if paint && visible {
+                        if engine.fixed_depth == 0 {
+                            engine.text_right_px =
+                                engine.text_right_px.max(x_px.saturating_add(width));
+                        }
                         engine.list.commands.push(DisplayCommand::Text(DrawText {
                             x_px,
                             y_px: baseline_y,
                             text,
                             style,
                         }));
-                        if let Some(href) = link_href {
+                        if let Some(link) = link {
                             engine.link_regions.push(LinkHitRegion {
-                                href,
+                                href: link.href,
+                                opens_new_tab: link.opens_new_tab,
                                 x_px,
                                 y_px,
                                 width_px: width,
@@ -734,6 +1063,13 @@ fn layout_tokens<'doc>(
                                 is_fixed: engine.fixed_depth > 0,
                             });
                         }
+                        let word_box = Rect {
+                            x: x_px,
+                            y: y_px,
+                            width,
+                            height: line.height_px,
+                        };
+                        extend_owner_boxes(&mut owner_boxes, owner.as_ref(), word_box);
                     }
                     x_px = x_px.saturating_add(width);
                 }
@@ -763,9 +1099,16 @@ fn layout_tokens<'doc>(
                                 .saturating_add(element_box.style.margin.bottom),
                         )
                         .max(0);
+                    let top_px = match line.placement(&element_box) {
+                        Placement::Baseline { above_px } => baseline_y.saturating_sub(above_px),
+                        Placement::Top => y_px,
+                        Placement::Bottom => y_px
+                            .saturating_add(line.height_px)
+                            .saturating_sub(element_box.size.height),
+                    };
                     let border_box = Rect {
                         x: x_px.saturating_add(element_box.style.margin.left),
-                        y: y_px.saturating_add(element_box.style.margin.top),
+                        y: top_px.saturating_add(element_box.style.margin.top),
                         width: border_width,
                         height: border_height,
                     };
@@ -774,6 +1117,9 @@ fn layout_tokens<'doc>(
                     if element_paint && element_box.style.opacity == 0 {
                         element_paint = false;
                     }
+                    if element_paint {
+                        engine.record_outline_heading(element_box.element, border_box.y);
+                    }
                     let opacity = element_box.style.opacity;
                     let needs_opacity_group = element_paint && opacity < 255;
                     if needs_opacity_group {
@@ -782,6 +1128,7 @@ fn layout_tokens<'doc>(
                             .commands
                             .push(DisplayCommand::PushOpacity(opacity));
                     }
+                    let transform_group = engine.open_transform(&element_box.style, element_paint);
 
                     if element_paint {
                         let _ = engine.push_background(
@@ -802,10 +1149,12 @@ fn layout_tokens<'doc>(
                                 content_box,
                             )?;
                         }
+                        engine.paint_outline(border_box, &element_box.style);
 
-                        if let Some(href) = element_box.link_href.clone() {
+                        if let Some(link) = element_box.link.clone() {
                             engine.link_regions.push(LinkHitRegion {
-                                href,
+                                href: link.href,
+                                opens_new_tab: link.opens_new_tab,
                                 x_px: border_box.x,
                                 y_px: border_box.y,
                                 width_px: border_box.width,
@@ -813,12 +1162,29 @@ fn layout_tokens<'doc>(
                                 is_fixed: engine.fixed_depth > 0,
                             });
                         }
+
+                        engine.record_inspected_box(
+                            element_box.element,
+                            ancestors,
+                            &element_box.style,
+                            border_box,
+                            element_box.style.padding.resolve_px(content_box.width),
+                        );
+                        extend_owner_boxes(
+                            &mut owner_boxes,
+                            element_box.owner.as_ref(),
+                            border_box,
+                        );
                     }
 
                     if !is_replaced_element(element_box.element) {
                         let padding = element_box.style.padding.resolve_px(content_box.width);
                         let content_box = border_box
                             .inset(super::add_edges(element_box.style.border_width, padding));
+                        let outer_height = engine.enter_containing_height(
+                            super::used_height_px(&element_box.style),
+                            super::add_edges(element_box.style.border_width, padding),
+                        );
                         ancestors.push(element_box.element);
                         engine.layout_flow_children(
                             &element_box.element.children,
@@ -828,8 +1194,10 @@ fn layout_tokens<'doc>(
                             element_paint,
                         )?;
                         ancestors.pop();
+                        engine.containing_height_px = outer_height;
                     }
 
+                    engine.close_transform(transform_group, &element_box.style, border_box);
                     if needs_opacity_group {
                         engine
                             .list
@@ -841,11 +1209,164 @@ fn layout_tokens<'doc>(
                 }
             }
         }
+        for (owner, line_box) in owner_boxes {
+            if heading_level(owner.element).is_some()
+                && !outlined_headings
+                    .iter()
+                    .any(|heading| std::ptr::eq(*heading, owner.element))
+            {
+                outlined_headings.push(owner.element);
+                engine.record_outline_heading(owner.element, line_box.y);
+            }
+            engine.record_inline_box(
+                owner.element,
+                &owner.path,
+                owner.depth,
+                &owner.style,
+                line_box,
+            );
+        }
 
         y_px = y_px.saturating_add(line.height_px);
     }
 
-    Ok(y_px.saturating_sub(start_y).max(0))
+    let cap_height_px = drop_cap.as_ref().map_or(0, DropCap::height_px);
+    Ok(y_px.saturating_sub(start_y).max(cap_height_px).max(0))
+}
+
+/// A floated `::first-letter`, painted at the start of its block's first lines, which make room
+/// for it.
+struct DropCap {
+    text: String,
+    style: TextStyle,
+    metrics: FontMetricsPx,
+    width_px: i32,
+    /// How far the lines beside it start in: its width and right margin.
+    inset_px: i32,
+    visible: bool,
+}
+
+impl DropCap {
+    fn height_px(&self) -> i32 {
+        self.metrics
+            .ascent_px
+            .saturating_add(self.metrics.descent_px)
+    }
+
+    /// How many lines of `line_height_px` it takes to reach past the bottom of the letter.
+    fn line_count(&self, line_height_px: i32) -> usize {
+        let line_height_px = line_height_px.max(1);
+        usize::try_from(self.height_px().saturating_add(line_height_px - 1) / line_height_px)
+            .unwrap_or(0)
+    }
+}
+
+/// The tokens of a line run with the first letter split off its first word.
+struct FirstLetterSplit<'t, 'doc> {
+    tokens: Cow<'t, [InlineToken<'doc>]>,
+    /// Where the first letter became a word of its own.
+    letter_index: Option<usize>,
+    drop_cap: Option<DropCap>,
+}
+
+impl<'t, 'doc> FirstLetterSplit<'t, 'doc> {
+    fn unsplit(tokens: &'t [InlineToken<'doc>]) -> Self {
+        FirstLetterSplit {
+            tokens: Cow::Borrowed(tokens),
+            letter_index: None,
+            drop_cap: None,
+        }
+    }
+}
+
+/// Gives the first letter of the first word in `tokens` its `::first-letter` style: as a word of
+/// its own, or taken out of the line as a drop cap when floated.
+fn split_first_letter_token<'t, 'doc>(
+    engine: &LayoutEngine<'_>,
+    tokens: &'t [InlineToken<'doc>],
+    letter: FirstLetter,
+) -> Result<FirstLetterSplit<'t, 'doc>, String> {
+    let first_word = tokens
+        .iter()
+        .enumerate()
+        .find_map(|(index, token)| match token {
+            InlineToken::Word(text, style, visible, link, owner) => {
+                Some(Some((index, text, *style, *visible, link, owner)))
+            }
+            InlineToken::Newline | InlineToken::ElementBox(_) => Some(None),
+            InlineToken::Space(..) | InlineToken::Join | InlineToken::Spacer(_) => None,
+        })
+        .flatten();
+    let Some((index, text, style, visible, link, owner)) = first_word else {
+        return Ok(FirstLetterSplit::unsplit(tokens));
+    };
+    let Some((first, rest)) = first_line::split_first_letter(text) else {
+        return Ok(FirstLetterSplit::unsplit(tokens));
+    };
+
+    let first = letter.restyle.text(first).into_owned();
+    let letter_style = letter.restyle.style(style);
+    let mut split = Vec::with_capacity(3);
+    let mut drop_cap = None;
+    if letter.floated {
+        let width_px = engine.measurer.text_width_px(&first, letter_style)?;
+        drop_cap = Some(DropCap {
+            text: first,
+            style: letter_style,
+            metrics: engine.measurer.font_metrics_px(letter_style),
+            width_px,
+            inset_px: width_px.saturating_add(letter.margin_right_px),
+            visible,
+        });
+    } else {
+        split.push(InlineToken::Word(
+            first,
+            letter_style,
+            visible,
+            link.clone(),
+            owner.clone(),
+        ));
+    }
+    let letter_index = (!letter.floated).then_some(index);
+    if !rest.is_empty() {
+        if !letter.floated {
+            split.push(InlineToken::Join);
+        }
+        split.push(InlineToken::Word(
+            rest.to_owned(),
+            style,
+            visible,
+            link.clone(),
+            owner.clone(),
+        ));
+    }
+
+    let mut tokens = tokens.to_vec();
+    tokens.splice(index..=index, split);
+    Ok(FirstLetterSplit {
+        tokens: Cow::Owned(tokens),
+        letter_index,
+        drop_cap,
+    })
+}
+
+/// Grows the box on this line of each inline element around a word or inline box to cover
+/// `rect`, adding one for an element not seen on the line yet.
+fn extend_owner_boxes<'doc>(
+    boxes: &mut Vec<(Rc<InlineOwner<'doc>>, Rect)>,
+    mut owner: Option<&Rc<InlineOwner<'doc>>>,
+    rect: Rect,
+) {
+    while let Some(current) = owner {
+        match boxes
+            .iter_mut()
+            .find(|(known, _)| Rc::ptr_eq(known, current))
+        {
+            Some((_, known)) => *known = known.union(rect),
+            None => boxes.push((Rc::clone(current), rect)),
+        }
+        owner = current.parent.as_ref();
+    }
 }
 
 fn measure_tokens<'doc>(
@@ -865,9 +1386,12 @@ fn measure_tokens<'doc>(
     let nowrap = parent_style.white_space == WhiteSpace::NoWrap;
     let mut line = Line::new(explicit_line_height_px, base_metrics);
     let mut x_px = 0i32;
+    let mut joined = false;
 
     for token in tokens {
+        let joins_previous = std::mem::take(&mut joined);
         match token {
+            InlineToken::Join => joined = true,
             InlineToken::Newline => {
                 lines.push(std::mem::replace(
                     &mut line,
@@ -875,11 +1399,14 @@ fn measure_tokens<'doc>(
                 ));
                 x_px = 0;
             }
-            InlineToken::Space(style, _visible, _link_href) => {
+            InlineToken::Space(style, word_spacing_px, _visible, _link) => {
                 if x_px == 0 {
                     continue;
                 }
-                let space_width_px = engine.measurer.text_width_px(" ", *style)?;
+                let space_width_px = engine
+                    .measurer
+                    .text_width_px(" ", *style)?
+                    .saturating_add(*word_spacing_px);
                 if !nowrap && x_px.saturating_add(space_width_px) > max_width {
                     continue;
                 }
@@ -891,15 +1418,21 @@ fn measure_tokens<'doc>(
                     metrics,
                     false,
                     None,
+                    None,
                 ));
                 x_px = x_px.saturating_add(space_width_px);
             }
-            InlineToken::Word(text, style, _visible, _link_href) => {
+            InlineToken::Word(text, style, _visible, _link, _owner) => {
                 if text.is_empty() {
                     continue;
                 }
-                let word_width_px = engine.measurer.text_width_px(text, *style)?;
-                if !nowrap && x_px != 0 && x_px.saturating_add(word_width_px) > max_width {
+                let text = hyphenation::without_soft_hyphens(text);
+                let word_width_px = engine.measurer.text_width_px(&text, *style)?;
+                if !nowrap
+                    && !joins_previous
+                    && x_px != 0
+                    && x_px.saturating_add(word_width_px) > max_width
+                {
                     lines.push(std::mem::replace(
                         &mut line,
                         Line::new(explicit_line_height_px, base_metrics),
@@ -909,12 +1442,13 @@ fn measure_tokens<'doc>(
 
                 let metrics = engine.measurer.font_metrics_px(*style);
                 line.push(Fragment::Text(
-                    text.clone(),
+                    text.into_owned(),
                     *style,
                     word_width_px,
                     metrics,
                     false,
                     None,
+                    None,
                 ));
                 x_px = x_px.saturating_add(word_width_px);
             }
@@ -923,7 +1457,11 @@ fn measure_tokens<'doc>(
                 x_px = x_px.saturating_add(size.width);
             }
             InlineToken::ElementBox(b) => {
-                if !nowrap && x_px != 0 && x_px.saturating_add(b.size.width) > max_width {
+                if !nowrap
+                    && !joins_previous
+                    && x_px != 0
+                    && x_px.saturating_add(b.size.width) > max_width
+                {
                     lines.push(std::mem::replace(
                         &mut line,
                         Line::new(explicit_line_height_px, base_metrics),
@@ -955,19 +1493,72 @@ fn measure_tokens<'doc>(
 
 #[derive(Clone, Debug)]
 enum Fragment<'doc> {
-    Text(String, TextStyle, i32, FontMetricsPx, bool, Option<Rc<str>>),
+    Text(
+        String,
+        TextStyle,
+        i32,
+        FontMetricsPx,
+        bool,
+        Option<AnchorLink>,
+        Option<Rc<InlineOwner<'doc>>>,
+    ),
     Spacer(Size),
     ElementBox(InlineElementBox<'doc>),
 }
 
+/// Puts a line's fragments in the order they are shown in from left to right, and turns around
+/// the text of those written right to left.
+fn reorder_bidi(fragments: &mut Vec<Fragment<'_>>, rtl: bool) {
+    let classes: Vec<BidiClass> = fragments
+        .iter()
+        .map(|fragment| match fragment {
+            Fragment::Text(text, ..) => BidiClass::of_text(text),
+            Fragment::Spacer(_) => BidiClass::Whitespace,
+            Fragment::ElementBox(_) => BidiClass::Neutral,
+        })
+        .collect();
+    let levels = bidi::resolve_levels(&classes, rtl);
+    let mut logical: Vec<Option<Fragment<'_>>> =
+        std::mem::take(fragments).into_iter().map(Some).collect();
+    for index in bidi::visual_order(&levels) {
+        let Some(mut fragment) = logical[index].take() else {
+            continue;
+        };
+        if let Fragment::Text(text, ..) = &mut fragment
+            && levels[index] % 2 == 1
+        {
+            *text = bidi::right_to_left_display(text);
+        }
+        fragments.push(fragment);
+    }
+}
+
+/// Where an inline box goes on its line, from its `vertical-align`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Placement {
+    /// With its margin box reaching this far above the line's baseline.
+    Baseline {
+        above_px: i32,
+    },
+    Top,
+    Bottom,
+}
+
 struct Line<'doc> {
     fragments: Vec<Fragment<'doc>>,
     width_px: i32,
     ascent_px: i32,
     descent_px: i32,
     height_px: i32,
-    max_element_height_px: i32,
+    /// The parent's font, which `middle`, `text-top` and `text-bottom` are measured against.
+    base_metrics: FontMetricsPx,
+    /// How far boxes placed against the baseline reach above and below it, once there are any.
+    element_extent_px: Option<(i32, i32)>,
+    /// The tallest box placed against the top or bottom of the line.
+    aligned_edge_height_px: i32,
     explicit_line_height_px: Option<i32>,
+    /// How far a drop cap beside the line pushes it in.
+    inset_px: i32,
 }
 
 impl<'doc> Line<'doc> {
@@ -982,8 +1573,11 @@ impl<'doc> Line<'doc> {
             ascent_px,
             descent_px,
             height_px,
-            max_element_height_px: 0,
+            base_metrics,
+            element_extent_px: None,
+            aligned_edge_height_px: 0,
             explicit_line_height_px,
+            inset_px: 0,
         };
         line.recompute_height();
         line
@@ -991,7 +1585,7 @@ impl<'doc> Line<'doc> {
 
     fn push(&mut self, fragment: Fragment<'doc>) {
         match &fragment {
-            Fragment::Text(_, _, width, metrics, _, _) => {
+            Fragment::Text(_, _, width, metrics, _, _, _) => {
                 self.width_px = self.width_px.saturating_add(*width);
                 self.ascent_px = self.ascent_px.max(metrics.ascent_px.max(1));
                 self.descent_px = self.descent_px.max(metrics.descent_px.max(0));
@@ -1001,27 +1595,86 @@ impl<'doc> Line<'doc> {
             }
             Fragment::ElementBox(element_box) => {
                 self.width_px = self.width_px.saturating_add(element_box.size.width);
-                self.max_element_height_px = self
-                    .max_element_height_px
-                    .max(element_box.size.height.max(1));
+                let height_px = element_box.size.height.max(1);
+                match self.placement(element_box) {
+                    Placement::Baseline { above_px } => {
+                        let below_px = height_px.saturating_sub(above_px);
+                        self.element_extent_px = Some(match self.element_extent_px {
+                            Some((above, below)) => (above.max(above_px), below.max(below_px)),
+                            None => (above_px, below_px),
+                        });
+                    }
+                    Placement::Top | Placement::Bottom => {
+                        self.aligned_edge_height_px = self.aligned_edge_height_px.max(height_px);
+                    }
+                }
             }
         }
         self.recompute_height();
         self.fragments.push(fragment);
     }
 
-    fn recompute_height(&mut self) {
+    /// How much of `width_px` is left for the line beside any drop cap.
+    fn available_px(&self, width_px: i32) -> i32 {
+        width_px.saturating_sub(self.inset_px)
+    }
+
+    fn placement(&self, element_box: &InlineElementBox<'_>) -> Placement {
+        let height_px = element_box.size.height;
+        let above_px = match element_box.style.vertical_align {
+            VerticalAlign::Top => return Placement::Top,
+            VerticalAlign::Bottom => return Placement::Bottom,
+            VerticalAlign::Baseline => element_box.baseline_px,
+            VerticalAlign::Length(shift) => {
+                let style = &element_box.style;
+                let line_height_px = style
+                    .line_height
+                    .resolve_px(style.font_size_px)
+                    .unwrap_or(style.font_size_px);
+                element_box
+                    .baseline_px
+                    .saturating_add(shift.resolve_px(line_height_px))
+            }
+            VerticalAlign::Middle => {
+                (height_px / 2).saturating_add(self.base_metrics.ascent_px / 4)
+            }
+            VerticalAlign::TextTop => self.base_metrics.ascent_px,
+            VerticalAlign::TextBottom => height_px.saturating_sub(self.base_metrics.descent_px),
+        };
+        Placement::Baseline { above_px }
+    }
+
+    /// How far the text alone reaches above and below the baseline, with its line height
+    /// spread evenly either side.
+    fn text_extent_px(&self) -> (i32, i32) {
         let text_height_px = self.ascent_px.saturating_add(self.descent_px).max(1);
         let base_height_px = self
             .explicit_line_height_px
             .unwrap_or(text_height_px)
             .max(1);
-        self.height_px = base_height_px.max(self.max_element_height_px).max(1);
+        let above_px = self
+            .ascent_px
+            .saturating_add(base_height_px.saturating_sub(text_height_px).max(0) / 2);
+        (above_px, base_height_px.saturating_sub(above_px))
+    }
+
+    fn recompute_height(&mut self) {
+        let (mut above_px, mut below_px) = self.text_extent_px();
+        if let Some((element_above_px, element_below_px)) = self.element_extent_px {
+            above_px = above_px.max(element_above_px);
+            below_px = below_px.max(element_below_px);
+        }
+        self.height_px = above_px
+            .saturating_add(below_px)
+            .max(self.aligned_edge_height_px)
+            .max(1);
     }
 
     fn baseline_offset_px(&self) -> i32 {
-        let text_height_px = self.ascent_px.saturating_add(self.descent_px).max(1);
-        let extra = self.height_px.saturating_sub(text_height_px).max(0);
-        self.ascent_px.saturating_add(extra / 2)
+        let (above_px, _) = self.text_extent_px();
+        self.element_extent_px
+            .map_or(above_px, |(element_above_px, _)| {
+                above_px.max(element_above_px)
+            })
     }
 }
@@ -0,0 +1,144 @@
+use crate::dom::{Element, element_path};
+use crate::geom::{Edges, Rect};
+use crate::render::Viewport;
+use crate::style::ComputedStyle;
+
+use super::LayoutEngine;
+
+/// A laid-out element box, recorded for the layout inspector. Boxes are in document space, or in
+/// viewport space when `is_fixed`.
+#[derive(Clone, Debug)]
+pub struct InspectedBox {
+    /// Selectors from the root down, e.g. `html > body > div#main.card`.
+    pub path: String,
+    /// Number of ancestors; deeper boxes are drawn over shallower ones.
+    pub depth: usize,
+    pub is_fixed: bool,
+    pub margin_box: Rect,
+    pub border_box: Rect,
+    pub padding_box: Rect,
+    pub content_box: Rect,
+    pub style: ComputedStyle,
+    /// The address of the element, which tells it apart while the laid-out document is
+    /// unchanged.
+    pub element: usize,
+}
+
+/// Where an element's box ended up, for automation that needs a point to click.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElementGeometry {
+    /// In document space, or in viewport space when `is_fixed`.
+    pub border_box: Rect,
+    pub is_fixed: bool,
+    /// `border_box` in viewport space at the scroll position it was taken at.
+    pub viewport_box: Rect,
+    /// Whether any of the box shows in the viewport.
+    pub in_viewport: bool,
+}
+
+impl InspectedBox {
+    /// Whether this is the box of `element` in the document that was laid out.
+    pub fn is_box_of(&self, element: &Element) -> bool {
+        self.element == std::ptr::from_ref(element) as usize
+    }
+
+    /// This box shown in `viewport` scrolled down by `scroll_y_px`.
+    pub fn geometry(&self, viewport: Viewport, scroll_y_px: i32) -> ElementGeometry {
+        let scrolled_by = if self.is_fixed { 0 } else { scroll_y_px };
+        let viewport_box = Rect {
+            y: self.border_box.y.saturating_sub(scrolled_by),
+            ..self.border_box
+        };
+        let shown = viewport_box.intersection(Rect {
+            x: 0,
+            y: 0,
+            width: viewport.width_px,
+            height: viewport.height_px,
+        });
+        ElementGeometry {
+            border_box: self.border_box,
+            is_fixed: self.is_fixed,
+            viewport_box,
+            in_viewport: !shown.is_empty(),
+        }
+    }
+
+    pub fn contains_point(&self, x_px: i32, y_px: i32) -> bool {
+        let rect = self.border_box;
+        rect.width > 0
+            && rect.height > 0
+            && x_px >= rect.x
+            && x_px < rect.right()
+            && y_px >= rect.y
+            && y_px < rect.bottom()
+    }
+}
+
+impl LayoutEngine<'_> {
+    /// Records `element`'s boxes when the layout is being inspected. `border_box` has its final
+    /// height and `padding` is resolved against the containing block.
+    pub(super) fn record_inspected_box(
+        &mut self,
+        element: &Element,
+        ancestors: &[&Element],
+        style: &ComputedStyle,
+        border_box: Rect,
+        padding: Edges,
+    ) {
+        let Some(boxes) = self.inspected_boxes.as_mut() else {
+            return;
+        };
+        let margin = style.margin;
+        let padding_box = border_box.inset(style.border_width);
+        boxes.push(InspectedBox {
+            path: element_path(ancestors, element),
+            depth: ancestors.len(),
+            is_fixed: self.fixed_depth > 0,
+            margin_box: Rect {
+                x: border_box.x.saturating_sub(margin.left),
+                y: border_box.y.saturating_sub(margin.top),
+                width: border_box
+                    .width
+                    .saturating_add(margin.left)
+                    .saturating_add(margin.right)
+                    .max(0),
+                height: border_box
+                    .height
+                    .saturating_add(margin.top)
+                    .saturating_add(margin.bottom)
+                    .max(0),
+            },
+            border_box,
+            padding_box,
+            content_box: padding_box.inset(padding),
+            style: style.clone(),
+            element: std::ptr::from_ref(element) as usize,
+        });
+    }
+
+    /// Records the part of an inline element on one line, as the box around its text and inline
+    /// boxes there, when the layout is being inspected.
+    pub(super) fn record_inline_box(
+        &mut self,
+        element: &Element,
+        path: &str,
+        depth: usize,
+        style: &ComputedStyle,
+        line_box: Rect,
+    ) {
+        let Some(boxes) = self.inspected_boxes.as_mut() else {
+            return;
+        };
+        boxes.push(InspectedBox {
+            path: path.to_owned(),
+            depth,
+            is_fixed: self.fixed_depth > 0,
+            margin_box: line_box,
+            border_box: line_box,
+            padding_box: line_box,
+            content_box: line_box,
+            style: style.clone(),
+            element: std::ptr::from_ref(element) as usize,
+        });
+    }
+}
@@ -0,0 +1,234 @@
+use crate::dom::{Element, Node};
+use crate::style::{ComputedStyle, ContentSize, CssLength, Display, Position, WhiteSpace};
+
+use super::{LayoutEngine, flex, inline};
+
+/// The border widths a box can take from its content: anything from its narrowest unbreakable
+/// content up to its content on a single line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct IntrinsicWidths {
+    pub(super) min_px: i32,
+    pub(super) max_px: i32,
+}
+
+impl IntrinsicWidths {
+    /// Shrink-to-fit: as wide as the content wants, but no wider than `available_px` unless the
+    /// content can't get any narrower.
+    pub(super) fn fit(self, available_px: i32) -> i32 {
+        available_px.min(self.max_px).max(self.min_px)
+    }
+
+    fn length(self, size: ContentSize) -> CssLength {
+        match size {
+            ContentSize::MinContent => CssLength::Px(self.min_px),
+            ContentSize::MaxContent => CssLength::Px(self.max_px),
+            ContentSize::FitContent => CssLength::FitContent {
+                min_px: self.min_px,
+                max_px: self.max_px,
+            },
+        }
+    }
+}
+
+impl LayoutEngine<'_> {
+    /// Replaces `min-content`, `max-content` and `fit-content` in `style`'s widths with what
+    /// `element`'s content measures.
+    pub(super) fn resolve_content_widths<'doc>(
+        &self,
+        element: &'doc Element,
+        style: &mut ComputedStyle,
+        ancestors: &[&'doc Element],
+    ) {
+        let is_content = |width: Option<CssLength>| matches!(width, Some(CssLength::Content(_)));
+        if !is_content(style.width_px)
+            && !is_content(style.min_width_px)
+            && !is_content(style.max_width_px)
+        {
+            return;
+        }
+        let widths = measure_content_widths(
+            self,
+            element,
+            style,
+            &mut ancestors.to_vec(),
+            self.viewport.width_px,
+        )
+        .ok();
+        for width in [
+            &mut style.width_px,
+            &mut style.min_width_px,
+            &mut style.max_width_px,
+        ] {
+            if let Some(CssLength::Content(size)) = *width {
+                *width = widths.map(|widths| widths.length(size));
+            }
+        }
+    }
+}
+
+/// The widths `element`'s content alone gives its border box, whatever its own `width`,
+/// `min-width` and `max-width` say.
+pub(super) fn measure_content_widths<'doc>(
+    engine: &LayoutEngine<'_>,
+    element: &'doc Element,
+    style: &ComputedStyle,
+    ancestors: &mut Vec<&'doc Element>,
+    containing_width: i32,
+) -> Result<IntrinsicWidths, String> {
+    let style = &ComputedStyle {
+        width_px: None,
+        min_width_px: None,
+        max_width_px: None,
+        ..style.clone()
+    };
+    let max_px = flex::measure_element_max_content_width(
+        engine,
+        element,
+        style,
+        ancestors,
+        containing_width,
+    )?;
+    let min_px = measure_min_content_width(engine, element, style, ancestors, containing_width)?;
+    Ok(IntrinsicWidths {
+        min_px: min_px.min(max_px),
+        max_px,
+    })
+}
+
+/// The border widths a float or an absolutely positioned box with an `auto` width can take:
+/// with a set width just that one, and otherwise whatever its content allows, kept within its
+/// `min-width` and `max-width`.
+pub(super) fn measure_shrink_to_fit_widths<'doc>(
+    engine: &LayoutEngine<'_>,
+    element: &'doc Element,
+    style: &ComputedStyle,
+    ancestors: &mut Vec<&'doc Element>,
+    containing_width: i32,
+) -> Result<IntrinsicWidths, String> {
+    let containing_width = containing_width.max(0);
+
+    let widths = if inline::is_replaced_element(element) {
+        let size = inline::measure_replaced_element_outer_size(element, style, containing_width)?;
+        let width = size
+            .width
+            .saturating_sub(style.margin.left.saturating_add(style.margin.right))
+            .max(0);
+        IntrinsicWidths {
+            min_px: width,
+            max_px: width,
+        }
+    } else if let Some(width) = style.width_px {
+        let width = width.resolve_px(containing_width).max(0);
+        IntrinsicWidths {
+            min_px: width,
+            max_px: width,
+        }
+    } else {
+        measure_content_widths(engine, element, style, ancestors, containing_width)?
+    };
+
+    let clamp = |width: i32| {
+        let mut width = width;
+        if let Some(max_width) = style.max_width_px {
+            width = width.min(max_width.resolve_px(containing_width).max(0));
+        }
+        if let Some(min_width) = style.min_width_px {
+            width = width.max(min_width.resolve_px(containing_width).max(0));
+        }
+        width.max(0)
+    };
+    Ok(IntrinsicWidths {
+        min_px: clamp(widths.min_px),
+        max_px: clamp(widths.max_px),
+    })
+}
+
+/// The narrowest `element` gets without its content overflowing: its widest word, image or box
+/// with a set width, plus its own padding and border.
+fn measure_min_content_width<'doc>(
+    engine: &LayoutEngine<'_>,
+    element: &'doc Element,
+    style: &ComputedStyle,
+    ancestors: &mut Vec<&'doc Element>,
+    containing_width: i32,
+) -> Result<i32, String> {
+    if inline::is_replaced_element(element) {
+        let size = inline::measure_replaced_element_outer_size(element, style, containing_width)?;
+        return Ok(size
+            .width
+            .saturating_sub(style.margin.left.saturating_add(style.margin.right))
+            .max(0));
+    }
+    match style.width_px {
+        Some(CssLength::FitContent { min_px, .. }) => return Ok(min_px.max(0)),
+        Some(width) => return Ok(width.resolve_px(containing_width).max(0)),
+        None => {}
+    }
+
+    let text_style = engine.text_style_for(style);
+    let mut width_px = 0i32;
+    ancestors.push(element);
+    for child in &element.children {
+        match child {
+            Node::Text(text) => {
+                if style.white_space == WhiteSpace::NoWrap {
+                    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                    width_px = width_px.max(engine.measurer.text_width_px(&line, text_style)?);
+                } else {
+                    for word in text.split_whitespace() {
+                        width_px = width_px.max(engine.measurer.text_width_px(word, text_style)?);
+                    }
+                }
+            }
+            Node::Element(el) => {
+                let child_style = engine.compute_style(el, style, ancestors);
+                if child_style.display == Display::None
+                    || matches!(child_style.position, Position::Absolute | Position::Fixed)
+                {
+                    continue;
+                }
+                let child_width = measure_min_content_width(
+                    engine,
+                    el,
+                    &child_style,
+                    ancestors,
+                    containing_width,
+                )?;
+                width_px = width_px.max(
+                    child_width
+                        .saturating_add(child_style.margin.left)
+                        .saturating_add(child_style.margin.right),
+                );
+            }
+        }
+    }
+    ancestors.pop();
+
+    let padding = style.padding.resolve_px(containing_width);
+    Ok(width_px
+        .saturating_add(style.border_width.left)
+        .saturating_add(padding.left)
+        .saturating_add(padding.right)
+        .saturating_add(style.border_width.right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_stays_between_the_narrowest_and_widest_content() {
+        let widths = IntrinsicWidths {
+            min_px: 30,
+            max_px: 120,
+        };
+        assert_eq!(widths.fit(80), 80);
+        assert_eq!(widths.fit(200), 120);
+        assert_eq!(widths.fit(10), 30);
+        assert!(matches!(
+            widths.length(ContentSize::MinContent),
+            CssLength::Px(30)
+        ));
+        assert_eq!(widths.length(ContentSize::FitContent).resolve_px(200), 120);
+    }
+}
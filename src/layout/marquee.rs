@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use crate::dom::Element;
+use crate::geom::Rect;
+use crate::render::DisplayCommand;
+
+use super::LayoutEngine;
+use super::positioned::RelativeMark;
+
+/// How far a `<marquee>`'s content moves at each step, and how often, without `scrollamount` and
+/// `scrolldelay`. Shorter delays are raised to the minimum unless `truespeed` is set.
+const DEFAULT_SCROLL_AMOUNT_PX: i64 = 6;
+const DEFAULT_SCROLL_DELAY_MS: u64 = 85;
+const MIN_SCROLL_DELAY_MS: u64 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Behavior {
+    /// Enters at one edge and leaves past the other, over and over.
+    Scroll,
+    /// Enters at one edge and stops against the other.
+    Slide,
+    /// Bounces between the edges.
+    Alternate,
+}
+
+impl LayoutEngine<'_> {
+    /// Moves what a `<marquee>` laid out since `mark` along its content box, as far as it has
+    /// travelled in the time it has been shown, and drops what ends up wholly outside the box.
+    /// Without a clock the content stays where the flow put it.
+    pub(super) fn scroll_marquee(
+        &mut self,
+        element: &Element,
+        ancestors: &[&Element],
+        mark: RelativeMark,
+        content_box: Rect,
+    ) -> Result<(), String> {
+        let Some(shown) = self.styles.time_shown(element, ancestors) else {
+            return Ok(());
+        };
+        let mut extent: Option<(i32, i32)> = None;
+        let mut fixed_depth = 0usize;
+        for command in &self.list.commands[mark.commands..] {
+            if let Some((left_px, right_px)) = self.horizontal_extent(command, &mut fixed_depth)? {
+                extent = Some(extent.map_or((left_px, right_px), |(left, right)| {
+                    (left.min(left_px), right.max(right_px))
+                }));
+            }
+        }
+        let Some((left_px, right_px)) = extent else {
+            return Ok(());
+        };
+        let width_px = right_px.saturating_sub(left_px);
+
+        let rightwards = element
+            .attributes
+            .get("direction")
+            .is_some_and(|direction| direction.trim().eq_ignore_ascii_case("right"));
+        let behavior = match element
+            .attributes
+            .get("behavior")
+            .map(|behavior| behavior.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("slide") => Behavior::Slide,
+            Some("alternate") => Behavior::Alternate,
+            _ => Behavior::Scroll,
+        };
+        let (outside_left, flush_left) = (content_box.x.saturating_sub(width_px), content_box.x);
+        let (outside_right, flush_right) = (
+            content_box.right(),
+            content_box.right().saturating_sub(width_px),
+        );
+        let (start_px, end_px) = match (behavior, rightwards) {
+            (Behavior::Scroll, false) => (outside_right, outside_left),
+            (Behavior::Scroll, true) => (outside_left, outside_right),
+            (Behavior::Slide, false) => (outside_right, flush_left),
+            (Behavior::Slide, true) => (outside_left, flush_right),
+            (Behavior::Alternate, false) => (flush_right, flush_left),
+            (Behavior::Alternate, true) => (flush_left, flush_right),
+        };
+
+        let distance = (i64::from(end_px) - i64::from(start_px)).abs();
+        let travelled = travelled_px(element, shown);
+        let moved = match behavior {
+            Behavior::Scroll if distance > 0 => travelled % distance,
+            Behavior::Slide => travelled.min(distance),
+            Behavior::Alternate if distance > 0 => {
+                let bounce = travelled % distance.saturating_mul(2);
+                if bounce <= distance {
+                    bounce
+                } else {
+                    distance.saturating_mul(2) - bounce
+                }
+            }
+            _ => 0,
+        };
+        let moved = i64::from(end_px.saturating_sub(start_px).signum()) * moved;
+        let x_px = i32::try_from(i64::from(start_px) + moved).unwrap_or(start_px);
+        self.translate_since(mark, x_px.saturating_sub(left_px), 0);
+
+        let mut index = mark.commands;
+        let mut fixed_depth = 0usize;
+        while index < self.list.commands.len() {
+            let outside = self
+                .horizontal_extent(&self.list.commands[index], &mut fixed_depth)?
+                .is_some_and(|(left, right)| right <= content_box.x || left >= content_box.right());
+            if outside {
+                self.list.commands.remove(index);
+                continue;
+            }
+            if fixed_depth == 0
+                && let DisplayCommand::Rect(rect) = &mut self.list.commands[index]
+            {
+                let right = rect
+                    .x_px
+                    .saturating_add(rect.width_px)
+                    .min(content_box.right());
+                rect.x_px = rect.x_px.max(content_box.x);
+                rect.width_px = right.saturating_sub(rect.x_px).max(0);
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    /// The left and right edges of what `command` paints, if a marquee moves it: it isn't inside
+    /// one of the fixed groups `fixed_depth` counts.
+    fn horizontal_extent(
+        &self,
+        command: &DisplayCommand,
+        fixed_depth: &mut usize,
+    ) -> Result<Option<(i32, i32)>, String> {
+        let (x_px, width_px) = match command {
+            DisplayCommand::PushFixed => {
+                *fixed_depth = fixed_depth.saturating_add(1);
+                return Ok(None);
+            }
+            DisplayCommand::PopFixed => {
+                *fixed_depth = fixed_depth.saturating_sub(1);
+                return Ok(None);
+            }
+            _ if *fixed_depth > 0 => return Ok(None),
+            DisplayCommand::Rect(rect) => (rect.x_px, rect.width_px),
+            DisplayCommand::LinearGradientRect(rect) => (rect.x_px, rect.width_px),
+            DisplayCommand::RoundedRect(rect) => (rect.x_px, rect.width_px),
+            DisplayCommand::RoundedRectBorder(rect) => (rect.x_px, rect.width_px),
+            DisplayCommand::Image(image) => (image.x_px, image.width_px),
+            DisplayCommand::Svg(svg) => (svg.x_px, svg.width_px),
+            DisplayCommand::Text(text) => (
+                text.x_px,
+                self.measurer.text_width_px(&text.text, text.style)?,
+            ),
+            _ => return Ok(None),
+        };
+        Ok(Some((x_px, x_px.saturating_add(width_px.max(0)))))
+    }
+}
+
+/// How far a marquee's content has moved after `shown`, stepping `scrollamount` pixels every
+/// `scrolldelay` milliseconds.
+fn travelled_px(element: &Element, shown: Duration) -> i64 {
+    let attribute = |name: &str| {
+        element
+            .attributes
+            .get(name)
+            .and_then(|value| value.trim().parse::<u64>().ok())
+    };
+    let amount = attribute("scrollamount").map_or(DEFAULT_SCROLL_AMOUNT_PX, |amount| {
+        i64::try_from(amount).unwrap_or(i64::MAX)
+    });
+    let mut delay_ms = attribute("scrolldelay").unwrap_or(DEFAULT_SCROLL_DELAY_MS);
+    if element.attributes.get("truespeed").is_none() {
+        delay_ms = delay_ms.max(MIN_SCROLL_DELAY_MS);
+    }
+    let steps = shown.as_millis() / u128::from(delay_ms.max(1));
+    i64::try_from(steps)
+        .unwrap_or(i64::MAX)
+        .saturating_mul(amount)
+}
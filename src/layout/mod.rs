@@ -1,31 +1,92 @@
+mod bidi;
+mod first_line;
 mod flex;
 mod floats;
+mod foreign_object;
 mod grid;
+mod guard;
 mod helpers;
+mod hyphenation;
+mod hyphenation_patterns;
 mod inline;
+mod inspect;
+mod intrinsic;
+mod marquee;
+mod outline;
+mod positioned;
 mod replaced;
 mod svg_xml;
 mod table;
+mod transform;
+mod virtualize;
 
 use crate::dom::{Document, Element, Node};
 use crate::geom::{Edges, Rect};
 use crate::image::Argb32Image;
+use crate::render::canvas::CanvasSurface;
 use crate::render::{
     DisplayCommand, DisplayList, DrawLinearGradientRect, DrawRect, DrawRoundedRect,
     DrawRoundedRectBorder, LinkHitRegion, TextMeasurer, TextStyle, Viewport,
 };
 use crate::resources::ResourceLoader;
-use crate::style::{ComputedStyle, Display, Float, Position, StyleComputer, Visibility};
+use crate::style::{
+    ComputedStyle, CssLength, Display, Float, OutlineStyle, Position, RuleMatches, StyleComputer,
+    Visibility,
+};
+use std::cell::Cell;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use helpers::*;
+pub use inspect::{ElementGeometry, InspectedBox};
+pub use outline::OutlineHeading;
+use virtualize::{BlockVirtualizer, VIRTUALIZE_MIN_CHILDREN};
+
+/// The ring `outline-style: auto` paints, as focused elements get by default.
+const FOCUS_RING_WIDTH_PX: i32 = 2;
+const FOCUS_RING_COLOR: crate::geom::Color = crate::geom::Color {
+    r: 0x1a,
+    g: 0x73,
+    b: 0xe8,
+    a: 255,
+};
+
+/// The dimming behind an open `<dialog>`.
+const DIALOG_BACKDROP_COLOR: crate::geom::Color = crate::geom::Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0x1a,
+};
 
 pub struct LayoutOutput {
     pub display_list: DisplayList,
     pub link_regions: Vec<LinkHitRegion>,
     pub document_height_px: i32,
+    /// How far right the scrolling content reaches, and at least the viewport width.
+    pub document_width_px: i32,
     pub canvas_background_color: Option<crate::geom::Color>,
+    /// Headings in document order. With a virtual window, headings in skipped blocks are absent.
+    pub outline: Vec<OutlineHeading>,
+    /// Painted element boxes in the order their layout finished; empty unless inspected.
+    pub inspected_boxes: Vec<InspectedBox>,
+    /// Set when elements nested too deeply, or past the work budget, were left out; a banner
+    /// across the top of the viewport says so.
+    pub truncated: bool,
+}
+
+/// Document-space vertical range that must be laid out exactly. Block children of large
+/// containers that fall entirely outside it are skipped and given an estimated height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VirtualWindow {
+    pub top_px: i32,
+    pub bottom_px: i32,
+}
+
+impl VirtualWindow {
+    pub fn contains_range(self, top_px: i32, bottom_px: i32) -> bool {
+        top_px >= self.top_px && bottom_px <= self.bottom_px
+    }
 }
 
 pub fn layout_document(
@@ -34,9 +95,27 @@ pub fn layout_document(
     measurer: &dyn TextMeasurer,
     viewport: Viewport,
     resources: &dyn ResourceLoader,
+) -> Result<LayoutOutput, String> {
+    layout_document_in_window(document, styles, measurer, viewport, resources, None, false)
+}
+
+pub fn layout_document_in_window(
+    document: &Document,
+    styles: &StyleComputer,
+    measurer: &dyn TextMeasurer,
+    viewport: Viewport,
+    resources: &dyn ResourceLoader,
+    virtual_window: Option<VirtualWindow>,
+    inspect: bool,
 ) -> Result<LayoutOutput, String> {
     let mut engine = LayoutEngine {
+        canvases: &document.canvases,
         styles,
+        rule_matches: styles.match_document(
+            document.render_root(),
+            viewport.width_px,
+            viewport.height_px,
+        ),
         measurer,
         viewport,
         resources,
@@ -45,33 +124,184 @@ pub fn layout_document(
         list: DisplayList::default(),
         link_regions: Vec::new(),
         positioned_containing_blocks: Vec::new(),
+        containing_height_px: Some(viewport.height_px.max(0)),
+        first_line: None,
         fixed_depth: 0,
         canvas_background_color: None,
+        virtual_window,
+        outline: Vec::new(),
+        inspected_boxes: inspect.then(Vec::new),
+        text_right_px: 0,
+        top_layer: DisplayList::default(),
+        top_layer_links: Vec::new(),
+        elements_styled: Cell::new(0),
+        truncated: Cell::new(false),
     };
     let document_height_px = engine.layout_document(document)?;
+    engine.list.commands.append(&mut engine.top_layer.commands);
+    engine.link_regions.append(&mut engine.top_layer_links);
+    let truncated = engine.truncated.get();
+    if truncated {
+        engine.paint_truncation_banner();
+    }
+    let document_width_px = engine.document_width_px();
     Ok(LayoutOutput {
         display_list: engine.list,
         link_regions: engine.link_regions,
         document_height_px,
+        document_width_px,
         canvas_background_color: engine.canvas_background_color,
+        outline: engine.outline,
+        inspected_boxes: engine.inspected_boxes.unwrap_or_default(),
+        truncated,
+    })
+}
+
+/// The nearest `<a href>` around some content, recorded on that content's hit regions.
+#[derive(Clone, Debug)]
+struct AnchorLink {
+    href: Arc<str>,
+    opens_new_tab: bool,
+}
+
+fn anchor_link(element: &Element) -> Option<AnchorLink> {
+    if element.name != "a" {
+        return None;
+    }
+    let href = element.attributes.get("href")?.trim();
+    if href.is_empty() {
+        return None;
+    }
+    let opens_new_tab = element
+        .attributes
+        .get("target")
+        .is_some_and(|target| target.trim().eq_ignore_ascii_case("_blank"));
+    Some(AnchorLink {
+        href: Arc::from(href),
+        opens_new_tab,
     })
 }
 
 struct LayoutEngine<'a> {
+    canvases: &'a HashMap<String, Arc<CanvasSurface>>,
     styles: &'a StyleComputer,
+    /// Selector matches found on worker threads before layout starts.
+    rule_matches: RuleMatches<'a>,
     measurer: &'a dyn TextMeasurer,
     viewport: Viewport,
     resources: &'a dyn ResourceLoader,
-    image_cache: HashMap<String, Rc<Argb32Image>>,
-    svg_cache: HashMap<String, Rc<str>>,
+    image_cache: HashMap<String, Arc<Argb32Image>>,
+    svg_cache: HashMap<String, Arc<str>>,
     list: DisplayList,
     link_regions: Vec<LinkHitRegion>,
     positioned_containing_blocks: Vec<Rect>,
+    /// The content height of the block whose children are being laid out, when it is set rather
+    /// than left to its content; percentage heights resolve against it.
+    containing_height_px: Option<i32>,
+    /// Handed to the run of inline content a block starts with, just before it is laid out.
+    first_line: Option<first_line::FirstLine>,
     fixed_depth: usize,
     canvas_background_color: Option<crate::geom::Color>,
+    virtual_window: Option<VirtualWindow>,
+    outline: Vec<OutlineHeading>,
+    inspected_boxes: Option<Vec<InspectedBox>>,
+    /// The right edge of the text painted outside fixed groups, which the display list doesn't
+    /// hold without measuring it again.
+    text_right_px: i32,
+    /// What open dialogs paint, drawn over the rest of the page once it is laid out.
+    top_layer: DisplayList,
+    top_layer_links: Vec<LinkHitRegion>,
+    /// Counted against [`guard::MAX_LAYOUT_ELEMENTS`].
+    elements_styled: Cell<usize>,
+    /// Set once an element is left out by [`LayoutEngine::within_budget`].
+    truncated: Cell<bool>,
 }
 
 impl LayoutEngine<'_> {
+    fn document_width_px(&self) -> i32 {
+        let mut right_px = self.text_right_px.max(self.viewport.width_px);
+        let mut fixed_depth = 0usize;
+        for command in &self.list.commands {
+            let (x_px, width_px) = match command {
+                DisplayCommand::PushFixed => {
+                    fixed_depth = fixed_depth.saturating_add(1);
+                    continue;
+                }
+                DisplayCommand::PopFixed => {
+                    fixed_depth = fixed_depth.saturating_sub(1);
+                    continue;
+                }
+                _ if fixed_depth > 0 => continue,
+                DisplayCommand::Rect(rect) => (rect.x_px, rect.width_px),
+                DisplayCommand::LinearGradientRect(rect) => (rect.x_px, rect.width_px),
+                DisplayCommand::RoundedRect(rect) => (rect.x_px, rect.width_px),
+                DisplayCommand::RoundedRectBorder(rect) => (rect.x_px, rect.width_px),
+                DisplayCommand::Image(image) => (image.x_px, image.width_px),
+                DisplayCommand::Svg(svg) => (svg.x_px, svg.width_px),
+                _ => continue,
+            };
+            right_px = right_px.max(x_px.saturating_add(width_px.max(0)));
+        }
+        right_px.max(0)
+    }
+
+    fn compute_style(
+        &self,
+        element: &Element,
+        parent_style: &ComputedStyle,
+        ancestors: &[&Element],
+    ) -> ComputedStyle {
+        if !self.within_budget(ancestors) {
+            return ComputedStyle {
+                display: Display::None,
+                ..parent_style.clone()
+            };
+        }
+        let mut style = self.styles.compute_style_with_matches(
+            element,
+            parent_style,
+            ancestors,
+            self.viewport.width_px,
+            self.viewport.height_px,
+            &self.rule_matches,
+        );
+        self.resolve_content_widths(element, &mut style, ancestors);
+        self.resolve_percentage_heights(&mut style);
+        style
+    }
+
+    /// Resolves percentage heights in `style` against its containing block's height, dropping
+    /// them when that height is left to the content instead.
+    fn resolve_percentage_heights(&self, style: &mut ComputedStyle) {
+        let containing_height_px = match style.position {
+            Position::Absolute => Some(self.current_positioned_containing_block().height),
+            Position::Fixed => Some(self.viewport.height_px.max(0)),
+            _ => self.containing_height_px,
+        };
+        for height in [
+            &mut style.height_px,
+            &mut style.min_height_px,
+            &mut style.max_height_px,
+        ] {
+            if let Some(length @ (CssLength::Percent(_) | CssLength::Calc { .. })) = *height {
+                *height = containing_height_px
+                    .map(|containing| CssLength::Px(length.resolve_px(containing)));
+            }
+        }
+    }
+
+    /// Makes the content height of a box whose border height is `used_height` the one its
+    /// children's percentage heights resolve against, returning the height to restore once they
+    /// are laid out.
+    fn enter_containing_height(&mut self, used_height: Option<i32>, insets: Edges) -> Option<i32> {
+        let content_height = used_height.map(|height| {
+            height
+                .saturating_sub(insets.top.saturating_add(insets.bottom))
+                .max(0)
+        });
+        std::mem::replace(&mut self.containing_height_px, content_height)
+    }
+
     fn current_positioned_containing_block(&self) -> Rect {
         self.positioned_containing_blocks
             .last()
@@ -98,7 +328,7 @@ impl LayoutEngine<'_> {
         self.positioned_containing_blocks.push(padding_box);
     }
 
-    fn load_image(&mut self, src: &str) -> Result<Option<Rc<Argb32Image>>, String> {
+    fn load_image(&mut self, src: &str) -> Result<Option<Arc<Argb32Image>>, String> {
         let src = src.trim();
         if src.is_empty() {
             return Ok(None);
@@ -114,13 +344,14 @@ impl LayoutEngine<'_> {
             Ok(image) => image,
             Err(_) => return Ok(None),
         };
+        self.resources.image_decoded(src);
 
-        let image = Rc::new(decoded);
+        let image = Arc::new(decoded);
         self.image_cache.insert(src.to_owned(), image.clone());
         Ok(Some(image))
     }
 
-    fn load_svg(&mut self, src: &str) -> Result<Option<Rc<str>>, String> {
+    fn load_svg(&mut self, src: &str) -> Result<Option<Arc<str>>, String> {
         let src = src.trim();
         if src.is_empty() {
             return Ok(None);
@@ -132,14 +363,16 @@ impl LayoutEngine<'_> {
         let Some(bytes) = self.resources.load_bytes(src)? else {
             return Ok(None);
         };
-        if !crate::image::looks_like_svg_document(bytes.as_ref()) {
+        if !crate::image::looks_like_svg_document(bytes.as_ref())
+            || crate::image::check_svg_document(bytes.as_ref()).is_err()
+        {
             return Ok(None);
         }
 
         let text = String::from_utf8_lossy(bytes.as_ref());
         let trimmed = text.trim_start();
 
-        let svg_xml: Rc<str> = Rc::from(trimmed.to_owned());
+        let svg_xml: Arc<str> = Arc::from(trimmed.to_owned());
         self.svg_cache.insert(src.to_owned(), svg_xml.clone());
         Ok(Some(svg_xml))
     }
@@ -149,25 +382,13 @@ impl LayoutEngine<'_> {
         let root_style = ComputedStyle::root_defaults();
         let mut ancestors = Vec::new();
 
-        let style = self.styles.compute_style_in_viewport(
-            root,
-            &root_style,
-            &ancestors,
-            self.viewport.width_px,
-            self.viewport.height_px,
-        );
+        let style = self.compute_style(root, &root_style, &ancestors);
         let body_style = if root.name == "html" {
             document
                 .find_first_element_by_name("body")
                 .map(|body| {
                     let body_ancestors = vec![root];
-                    self.styles.compute_style_in_viewport(
-                        body,
-                        &style,
-                        &body_ancestors,
-                        self.viewport.width_px,
-                        self.viewport.height_px,
-                    )
+                    self.compute_style(body, &style, &body_ancestors)
                 })
                 .unwrap_or_else(|| style.clone())
         } else {
@@ -218,6 +439,7 @@ impl LayoutEngine<'_> {
         if style.display == Display::None {
             return Ok(());
         }
+        let relative_mark = self.relative_mark();
 
         let mut paint = paint && style.visibility == Visibility::Visible;
         if paint && style.opacity == 0 {
@@ -230,6 +452,7 @@ impl LayoutEngine<'_> {
                 .commands
                 .push(DisplayCommand::PushOpacity(opacity));
         }
+        let transform_group = self.open_transform(style, paint);
         let margin = style.margin;
         let margin_auto = style.margin_auto;
         let border = style.border_width;
@@ -294,7 +517,14 @@ impl LayoutEngine<'_> {
         if margin_auto.left || margin_auto.right {
             x = apply_auto_margin_alignment(margin_auto, containing, x, used_width, margin);
         } else {
-            x = apply_block_alignment(parent_style.text_align, containing, x, used_width, margin);
+            x = apply_block_alignment(
+                parent_style.text_align,
+                parent_style.direction,
+                containing,
+                x,
+                used_width,
+                margin,
+            );
         }
 
         let border_box = Rect {
@@ -303,6 +533,9 @@ impl LayoutEngine<'_> {
             width: used_width,
             height: 0,
         };
+        if paint {
+            self.record_outline_heading(element, y);
+        }
         let content_box = border_box.inset(add_edges(border, padding));
         let child_content_box = flow_override
             .map(|flow| constrain_flow_content_box(content_box, flow))
@@ -334,6 +567,10 @@ impl LayoutEngine<'_> {
                 self.push_positioned_containing_block(border_box, border);
                 pushed_positioning = true;
             }
+            let outer_height =
+                self.enter_containing_height(used_height_px(style), add_edges(border, padding));
+            let marquee_mark = (paint && element.name == "marquee")
+                .then(|| (self.relative_mark(), self.text_right_px));
             ancestors.push(element);
             let content_height = match style.display {
                 Display::Table => {
@@ -354,6 +591,11 @@ impl LayoutEngine<'_> {
                 )?,
             };
             ancestors.pop();
+            if let Some((mark, text_right_px)) = marquee_mark {
+                self.scroll_marquee(element, ancestors, mark, content_box)?;
+                self.text_right_px = text_right_px;
+            }
+            self.containing_height_px = outer_height;
             if pushed_positioning {
                 let _ = self.positioned_containing_blocks.pop();
             }
@@ -366,10 +608,10 @@ impl LayoutEngine<'_> {
             .saturating_add(content_height)
             .saturating_add(padding.bottom)
             .saturating_add(border.bottom);
-        if let Some(height) = style.height_px {
+        if let Some(height) = used_height_px(style) {
             border_height = border_height.max(height);
         }
-        if let Some(min_height) = style.min_height_px {
+        if let Some(min_height) = min_height_px(style) {
             border_height = border_height.max(min_height);
         }
 
@@ -398,11 +640,39 @@ impl LayoutEngine<'_> {
                 .inset(add_edges(border, padding));
                 self.paint_replaced_content(element, style, content_box)?;
             }
+            self.paint_outline(
+                Rect {
+                    height: border_height,
+                    ..border_box
+                },
+                style,
+            );
+            self.record_inspected_box(
+                element,
+                ancestors,
+                style,
+                Rect {
+                    height: border_height,
+                    ..border_box
+                },
+                padding,
+            );
         }
 
+        self.close_transform(
+            transform_group,
+            style,
+            Rect {
+                height: border_height,
+                ..border_box
+            },
+        );
         if needs_opacity_group {
             self.list.commands.push(DisplayCommand::PopOpacity(opacity));
         }
+        if style.position == Position::Relative {
+            self.offset_relative(relative_mark, style, containing);
+        }
 
         *cursor_y = y
             .saturating_add(border_height)
@@ -417,6 +687,7 @@ impl LayoutEngine<'_> {
         style: &ComputedStyle,
         ancestors: &mut Vec<&'doc Element>,
         containing: Rect,
+        static_position: (i32, i32),
         paint: bool,
     ) -> Result<(), String> {
         if style.display == Display::None {
@@ -428,10 +699,31 @@ impl LayoutEngine<'_> {
             paint = false;
         }
         let is_fixed = paint && style.position == Position::Fixed;
+        // A dialog left where its insets don't place it is centred in the viewport once its
+        // height is known, and painted over everything else.
+        let modal_dialog = is_fixed
+            && element.name == "dialog"
+            && style.top_px.is_none()
+            && style.bottom_px.is_none();
+        let mut outer_commands = Vec::new();
+        let mut outer_links = Vec::new();
+        if modal_dialog {
+            std::mem::swap(&mut self.list.commands, &mut outer_commands);
+            std::mem::swap(&mut self.link_regions, &mut outer_links);
+        }
         if is_fixed {
             self.fixed_depth = self.fixed_depth.saturating_add(1);
             self.list.commands.push(DisplayCommand::PushFixed);
         }
+        if modal_dialog {
+            self.list.commands.push(DisplayCommand::Rect(DrawRect {
+                x_px: 0,
+                y_px: 0,
+                width_px: self.viewport.width_px.max(0),
+                height_px: self.viewport.height_px.max(0),
+                color: DIALOG_BACKDROP_COLOR,
+            }));
+        }
 
         let opacity = style.opacity;
         let needs_opacity_group = paint && opacity < 255;
@@ -440,6 +732,7 @@ impl LayoutEngine<'_> {
                 .commands
                 .push(DisplayCommand::PushOpacity(opacity));
         }
+        let transform_group = self.open_transform(style, paint);
 
         let containing = match style.position {
             Position::Fixed => Rect {
@@ -466,74 +759,140 @@ impl LayoutEngine<'_> {
             None
         };
 
-        let mut used_width = if let Some(width) = style
-            .width_px
+        let (static_x, static_y) = static_position;
+        let horizontal = positioned::Constraints {
+            start: style.left_px.map(|left| left.resolve_px(containing.width)),
+            size: style
+                .width_px
+                .map(|width| width.resolve_px(containing.width))
+                .or_else(|| {
+                    replaced_size.map(|size| {
+                        size.width
+                            .saturating_sub(margin.left.saturating_add(margin.right))
+                            .max(0)
+                    })
+                }),
+            end: style
+                .right_px
+                .map(|right| right.resolve_px(containing.width)),
+            margin_start: margin.left,
+            margin_end: margin.right,
+            margin_start_auto: margin_auto.left,
+            margin_end_auto: margin_auto.right,
+            static_start: static_x.saturating_sub(containing.x),
+        };
+        let mut span_x = positioned::resolve(
+            positioned::Axis::Horizontal,
+            horizontal,
+            containing.width,
+            |available| {
+                let widths = intrinsic::measure_content_widths(
+                    self,
+                    element,
+                    style,
+                    ancestors,
+                    containing.width,
+                )?;
+                Ok(widths.fit(available))
+            },
+        )?;
+        let mut used_width = span_x.size;
+        if let Some(max_width) = style
+            .max_width_px
             .map(|width| width.resolve_px(containing.width))
         {
-            width
-        } else if let (Some(left), Some(right)) = (style.left_px, style.right_px) {
-            let left = left.resolve_px(containing.width);
-            let right = right.resolve_px(containing.width);
-            containing.width.saturating_sub(left.saturating_add(right))
-        } else if let Some(size) = replaced_size {
-            size.width
-                .saturating_sub(margin.left.saturating_add(margin.right))
-                .max(0)
-        } else {
-            flex::measure_element_max_content_width(
-                self,
-                element,
-                style,
-                ancestors,
-                containing.width,
-            )?
-        };
+            used_width = used_width.min(max_width);
+        }
         if let Some(min_width) = style
             .min_width_px
             .map(|width| width.resolve_px(containing.width))
         {
             used_width = used_width.max(min_width);
         }
-        if let Some(max_width) = style
-            .max_width_px
-            .map(|width| width.resolve_px(containing.width))
-        {
-            used_width = used_width.min(max_width);
+        if used_width.max(0) != span_x.size {
+            span_x = positioned::resolve(
+                positioned::Axis::Horizontal,
+                positioned::Constraints {
+                    size: Some(used_width),
+                    ..horizontal
+                },
+                containing.width,
+                Ok,
+            )?;
         }
-        used_width = used_width.max(0);
-
-        let mut x = if let Some(left) = style.left_px {
-            containing
-                .x
-                .saturating_add(left.resolve_px(containing.width))
-        } else if let Some(right) = style.right_px {
-            containing
-                .right()
-                .saturating_sub(used_width)
-                .saturating_sub(right.resolve_px(containing.width))
-        } else {
-            containing.x
-        };
-        let y = if let Some(top) = style.top_px {
-            containing
-                .y
-                .saturating_add(top.resolve_px(containing.height))
-        } else {
-            containing.y
-        };
 
-        if !margin_auto.left {
-            x = x.saturating_add(margin.left);
+        let inset_top = style.top_px.map(|top| top.resolve_px(containing.height));
+        let inset_bottom = style
+            .bottom_px
+            .map(|bottom| bottom.resolve_px(containing.height));
+        let vertical = positioned::Constraints {
+            start: inset_top,
+            size: used_height_px(style).or_else(|| {
+                replaced_size.map(|size| {
+                    size.height
+                        .saturating_sub(margin.top.saturating_add(margin.bottom))
+                        .max(0)
+                })
+            }),
+            end: inset_bottom,
+            margin_start: margin.top,
+            margin_end: margin.bottom,
+            margin_start_auto: margin_auto.top,
+            margin_end_auto: margin_auto.bottom,
+            static_start: static_y.saturating_sub(containing.y),
+        };
+        let sized_by_content =
+            vertical.size.is_none() && (inset_top.is_none() || inset_bottom.is_none());
+        let insets = add_edges(border, padding);
+        // Only a box hanging from its bottom inset needs its content height before it is placed;
+        // any other box takes the height of its content once laid out.
+        let mut span_y = positioned::resolve(
+            positioned::Axis::Vertical,
+            vertical,
+            containing.height,
+            |_| {
+                if inset_bottom.is_none() {
+                    return Ok(0);
+                }
+                let border_box = Rect {
+                    x: 0,
+                    y: 0,
+                    width: span_x.size,
+                    height: 0,
+                };
+                let content_height = self.layout_positioned_contents(
+                    element, style, ancestors, border_box, padding, false,
+                )?;
+                Ok(insets
+                    .top
+                    .saturating_add(content_height)
+                    .saturating_add(insets.bottom))
+            },
+        )?;
+        if let Some(min_height) = min_height_px(style)
+            && span_y.size < min_height
+        {
+            span_y = positioned::resolve(
+                positioned::Axis::Vertical,
+                positioned::Constraints {
+                    size: Some(min_height),
+                    ..vertical
+                },
+                containing.height,
+                Ok,
+            )?;
         }
-        let y = y.saturating_add(margin.top);
 
         let border_box = Rect {
-            x,
-            y,
-            width: used_width,
+            x: containing.x.saturating_add(span_x.start),
+            y: containing.y.saturating_add(span_y.start),
+            width: span_x.size,
             height: 0,
         };
-        let content_box = border_box.inset(add_edges(border, padding));
+        let dialog_mark = modal_dialog.then(|| self.relative_mark());
+        if paint {
+            self.record_outline_heading(element, border_box.y);
+        }
 
         let background_index = if paint {
             self.push_background(border_box, style, 0)
@@ -556,49 +915,27 @@ impl LayoutEngine<'_> {
                 )
                 .max(0)
         } else {
-            let mut pushed_positioning = false;
-            if style.position != Position::Static {
-                self.push_positioned_containing_block(border_box, border);
-                pushed_positioning = true;
-            }
-            ancestors.push(element);
-            let content_height = match style.display {
-                Display::Table => {
-                    table::layout_table(self, element, style, ancestors, content_box, paint)?.height
-                }
-                Display::Flex => {
-                    flex::layout_flex_row(self, element, style, ancestors, content_box, paint)?
-                }
-                Display::Grid => {
-                    grid::layout_grid(self, element, style, ancestors, content_box, paint)?
-                }
-                _ => self.layout_flow_children(
-                    &element.children,
-                    style,
-                    ancestors,
-                    content_box,
-                    paint,
-                )?,
-            };
-            ancestors.pop();
-            if pushed_positioning {
-                let _ = self.positioned_containing_blocks.pop();
-            }
-            content_height
+            let known_height = if sized_by_content { 0 } else { span_y.size };
+            self.layout_positioned_contents(
+                element,
+                style,
+                ancestors,
+                Rect {
+                    height: known_height,
+                    ..border_box
+                },
+                padding,
+                paint,
+            )?
         };
 
-        let mut border_height = border
+        let border_height = border
             .top
             .saturating_add(padding.top)
             .saturating_add(content_height)
             .saturating_add(padding.bottom)
-            .saturating_add(border.bottom);
-        if let Some(height) = style.height_px {
-            border_height = border_height.max(height);
-        }
-        if let Some(min_height) = style.min_height_px {
-            border_height = border_height.max(min_height);
-        }
+            .saturating_add(border.bottom)
+            .max(span_y.size);
 
         if let Some(index) = background_index {
             self.set_background_height(index, border_height);
@@ -625,6 +962,36 @@ impl LayoutEngine<'_> {
                 .inset(add_edges(border, padding));
                 self.paint_replaced_content(element, style, content_box)?;
             }
+            self.paint_outline(
+                Rect {
+                    height: border_height,
+                    ..border_box
+                },
+                style,
+            );
+            self.record_inspected_box(
+                element,
+                ancestors,
+                style,
+                Rect {
+                    height: border_height,
+                    ..border_box
+                },
+                padding,
+            );
+        }
+
+        self.close_transform(
+            transform_group,
+            style,
+            Rect {
+                height: border_height,
+                ..border_box
+            },
+        );
+        if let Some(mark) = dialog_mark {
+            let top_px = self.viewport.height_px.saturating_sub(border_height).max(0) / 2;
+            self.translate_since(mark, 0, top_px.saturating_sub(border_box.y));
         }
 
         if needs_opacity_group {
@@ -635,10 +1002,57 @@ impl LayoutEngine<'_> {
             self.list.commands.push(DisplayCommand::PopFixed);
             self.fixed_depth = self.fixed_depth.saturating_sub(1);
         }
+        if modal_dialog {
+            std::mem::swap(&mut self.list.commands, &mut outer_commands);
+            std::mem::swap(&mut self.link_regions, &mut outer_links);
+            self.top_layer.commands.append(&mut outer_commands);
+            self.top_layer_links.append(&mut outer_links);
+        }
 
         Ok(())
     }
 
+    /// Lays out the children of a positioned box, whose height is 0 while its content sets it,
+    /// and returns the height of its content box.
+    fn layout_positioned_contents<'doc>(
+        &mut self,
+        element: &'doc Element,
+        style: &ComputedStyle,
+        ancestors: &mut Vec<&'doc Element>,
+        border_box: Rect,
+        padding: Edges,
+        paint: bool,
+    ) -> Result<i32, String> {
+        let border = style.border_width;
+        let content_box = Rect {
+            height: 0,
+            ..border_box
+        }
+        .inset(add_edges(border, padding));
+        self.push_positioned_containing_block(border_box, border);
+        let known_height = Some(border_box.height).filter(|&height| height > 0);
+        let outer_height = self.enter_containing_height(known_height, add_edges(border, padding));
+        ancestors.push(element);
+        let content_height = match style.display {
+            Display::Table => {
+                table::layout_table(self, element, style, ancestors, content_box, paint)?.height
+            }
+            Display::Flex => {
+                flex::layout_flex_row(self, element, style, ancestors, content_box, paint)?
+            }
+            Display::Grid => {
+                grid::layout_grid(self, element, style, ancestors, content_box, paint)?
+            }
+            _ => {
+                self.layout_flow_children(&element.children, style, ancestors, content_box, paint)?
+            }
+        };
+        ancestors.pop();
+        self.containing_height_px = outer_height;
+        let _ = self.positioned_containing_blocks.pop();
+        Ok(content_height)
+    }
+
     fn layout_flow_children<'doc>(
         &mut self,
         children: &'doc [Node],
@@ -652,34 +1066,28 @@ impl LayoutEngine<'_> {
             links: Vec<LinkHitRegion>,
         }
 
-        let inherited_link_href = ancestors.iter().rev().find_map(|ancestor| {
-            if ancestor.name != "a" {
-                return None;
-            }
-            let href = ancestor.attributes.get("href")?.trim();
-            if href.is_empty() {
-                return None;
-            }
-            Some(Rc::<str>::from(href))
-        });
+        let inherited_link = ancestors
+            .iter()
+            .rev()
+            .find_map(|ancestor| anchor_link(ancestor))
+            .filter(|_| !ancestors.iter().any(|ancestor| ancestor.is_inert()));
 
         let mut cursor_y = content_box.y;
         let mut inline_nodes: Vec<&'doc Node> = Vec::new();
+        let mut first_line = self.first_line_of(parent_style, ancestors);
         let mut floats: Vec<floats::FloatPlacement> = Vec::new();
         let mut max_float_bottom = cursor_y;
         let mut deferred_floats: Vec<DeferredFloatPaint> = Vec::new();
+        let mut virtualizer = self
+            .virtual_window
+            .filter(|_| children.len() >= VIRTUALIZE_MIN_CHILDREN)
+            .map(BlockVirtualizer::new);
 
         for child in children {
             match child {
                 Node::Text(_) => inline_nodes.push(child),
                 Node::Element(el) => {
-                    let style = self.styles.compute_style_in_viewport(
-                        el,
-                        parent_style,
-                        ancestors,
-                        self.viewport.width_px,
-                        self.viewport.height_px,
-                    );
+                    let style = self.compute_style(el, parent_style, ancestors);
                     if style.display == Display::None {
                         continue;
                     }
@@ -691,6 +1099,7 @@ impl LayoutEngine<'_> {
                             let (flow_box, new_y) =
                                 floats::flow_area_at_y(&floats, content_box, cursor_y);
                             cursor_y = new_y;
+                            self.first_line = first_line.take();
                             let height = inline::layout_inline_nodes_with_link(
                                 self,
                                 &inline_nodes,
@@ -699,7 +1108,7 @@ impl LayoutEngine<'_> {
                                 flow_box,
                                 cursor_y,
                                 paint,
-                                inherited_link_href.clone(),
+                                inherited_link.clone(),
                             )?;
                             cursor_y = cursor_y.saturating_add(height);
                             inline_nodes.clear();
@@ -738,6 +1147,7 @@ impl LayoutEngine<'_> {
                             let (flow_box, new_y) =
                                 floats::flow_area_at_y(&floats, content_box, cursor_y);
                             cursor_y = new_y;
+                            self.first_line = first_line.take();
                             let height = inline::layout_inline_nodes_with_link(
                                 self,
                                 &inline_nodes,
@@ -746,14 +1156,21 @@ impl LayoutEngine<'_> {
                                 flow_box,
                                 cursor_y,
                                 paint,
-                                inherited_link_href.clone(),
+                                inherited_link.clone(),
                             )?;
                             cursor_y = cursor_y.saturating_add(height);
                             inline_nodes.clear();
                         }
 
                         let containing = self.current_positioned_containing_block();
-                        self.layout_positioned_box(el, &style, ancestors, containing, paint)?;
+                        self.layout_positioned_box(
+                            el,
+                            &style,
+                            ancestors,
+                            containing,
+                            (content_box.x, cursor_y),
+                            paint,
+                        )?;
                         continue;
                     }
 
@@ -762,6 +1179,7 @@ impl LayoutEngine<'_> {
                             let (flow_box, new_y) =
                                 floats::flow_area_at_y(&floats, content_box, cursor_y);
                             cursor_y = new_y;
+                            self.first_line = first_line.take();
                             let height = inline::layout_inline_nodes_with_link(
                                 self,
                                 &inline_nodes,
@@ -770,11 +1188,24 @@ impl LayoutEngine<'_> {
                                 flow_box,
                                 cursor_y,
                                 paint,
-                                inherited_link_href.clone(),
+                                inherited_link.clone(),
                             )?;
                             cursor_y = cursor_y.saturating_add(height);
                             inline_nodes.clear();
                         }
+                        first_line = None;
+
+                        if let Some(virtualizer) =
+                            virtualizer.as_mut().filter(|_| floats.is_empty())
+                            && let Some(estimate) = virtualizer.skip_estimate(cursor_y)
+                        {
+                            cursor_y = cursor_y.saturating_add(estimate);
+                            continue;
+                        }
+                        if let Some(clear_y) = floats::clearance_y(&floats, style.clear) {
+                            cursor_y = cursor_y.max(clear_y.saturating_sub(style.margin.top));
+                        }
+                        let block_top = cursor_y;
 
                         let establishes_bfc = establishes_block_formatting_context(&style);
                         if establishes_bfc {
@@ -825,6 +1256,9 @@ impl LayoutEngine<'_> {
                             )?;
                             cursor_y = child_cursor_y;
                         }
+                        if let Some(virtualizer) = virtualizer.as_mut() {
+                            virtualizer.record(cursor_y.saturating_sub(block_top));
+                        }
                     } else {
                         inline_nodes.push(child);
                     }
@@ -835,6 +1269,7 @@ impl LayoutEngine<'_> {
         if !inline_nodes.is_empty() {
             let (flow_box, new_y) = floats::flow_area_at_y(&floats, content_box, cursor_y);
             cursor_y = new_y;
+            self.first_line = first_line.take();
             let height = inline::layout_inline_nodes_with_link(
                 self,
                 &inline_nodes,
@@ -843,7 +1278,7 @@ impl LayoutEngine<'_> {
                 flow_box,
                 cursor_y,
                 paint,
-                inherited_link_href,
+                inherited_link,
             )?;
             cursor_y = cursor_y.saturating_add(height);
         }
@@ -967,6 +1402,49 @@ impl LayoutEngine<'_> {
         }
     }
 
+    /// The outline goes over the box's content and takes up no room, so it is painted once the
+    /// box is, without moving anything.
+    fn paint_outline(&mut self, border_box: Rect, style: &ComputedStyle) {
+        let (width_px, color) = match style.outline_style {
+            OutlineStyle::None => return,
+            OutlineStyle::Solid => (
+                style.outline_width_px,
+                style.outline_color.unwrap_or(style.color),
+            ),
+            OutlineStyle::Auto => (
+                FOCUS_RING_WIDTH_PX,
+                style.outline_color.unwrap_or(FOCUS_RING_COLOR),
+            ),
+        };
+        if width_px <= 0 || color.a == 0 {
+            return;
+        }
+        let outset_px = style.outline_offset_px.saturating_add(width_px);
+        let width = border_box.width.saturating_add(outset_px.saturating_mul(2));
+        let height = border_box
+            .height
+            .saturating_add(outset_px.saturating_mul(2));
+        if width <= 0 || height <= 0 {
+            return;
+        }
+        let radius_px = if style.border_radius_px > 0 {
+            style.border_radius_px.saturating_add(outset_px).max(0)
+        } else {
+            0
+        };
+        self.list
+            .commands
+            .push(DisplayCommand::RoundedRectBorder(DrawRoundedRectBorder {
+                x_px: border_box.x.saturating_sub(outset_px),
+                y_px: border_box.y.saturating_sub(outset_px),
+                width_px: width,
+                height_px: height,
+                radius_px,
+                border_width_px: width_px,
+                color,
+            }));
+    }
+
     fn push_background(
         &mut self,
         border_box: Rect,
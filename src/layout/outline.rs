@@ -0,0 +1,54 @@
+use crate::dom::{Element, Node};
+
+use super::LayoutEngine;
+
+/// A painted `<h1>`–`<h6>` heading. `y_px` is the top of its border box in document space.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutlineHeading {
+    pub level: u8,
+    pub text: String,
+    pub id: Option<String>,
+    pub y_px: i32,
+}
+
+impl LayoutEngine<'_> {
+    /// Headings inside fixed-position boxes are skipped since they have no document position.
+    pub(super) fn record_outline_heading(&mut self, element: &Element, y_px: i32) {
+        if self.fixed_depth > 0 {
+            return;
+        }
+        let Some(level) = heading_level(element) else {
+            return;
+        };
+        let mut text = String::new();
+        collect_text(element, &mut text);
+        self.outline.push(OutlineHeading {
+            level,
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            id: element.attributes.id.clone(),
+            y_px,
+        });
+    }
+}
+
+pub(super) fn heading_level(element: &Element) -> Option<u8> {
+    match element.name.as_str() {
+        "h1" => Some(1),
+        "h2" => Some(2),
+        "h3" => Some(3),
+        "h4" => Some(4),
+        "h5" => Some(5),
+        "h6" => Some(6),
+        _ => None,
+    }
+}
+
+fn collect_text(element: &Element, out: &mut String) {
+    for child in &element.children {
+        match child {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(el) if el.name == "script" || el.name == "style" => {}
+            Node::Element(el) => collect_text(el, out),
+        }
+    }
+}
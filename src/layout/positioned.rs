@@ -0,0 +1,272 @@
+use crate::geom::Rect;
+use crate::render::DisplayCommand;
+use crate::style::ComputedStyle;
+
+use super::LayoutEngine;
+
+/// Which axis of an absolutely positioned box is being resolved; they differ only in how an
+/// over-constrained pair of auto margins centres the box.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// The constraints on one axis of an absolutely positioned box, all in pixels against its
+/// containing block: `left`/`width`/`right` or `top`/`height`/`bottom`, `None` when `auto`.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Constraints {
+    pub(super) start: Option<i32>,
+    pub(super) size: Option<i32>,
+    pub(super) end: Option<i32>,
+    pub(super) margin_start: i32,
+    pub(super) margin_end: i32,
+    pub(super) margin_start_auto: bool,
+    pub(super) margin_end_auto: bool,
+    /// Where the box would have started had it not been positioned, from the start of the
+    /// containing block.
+    pub(super) static_start: i32,
+}
+
+/// Where a box's border box starts, from the start of its containing block, and how long it is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Span {
+    pub(super) start: i32,
+    pub(super) size: i32,
+}
+
+/// Solves `start + margin_start + size + margin_end + end = containing_px` the way CSS 2.2
+/// §10.3.7 and §10.6.4 do. `fit_size` gives the size an `auto` box takes within the space it is
+/// offered, and is only called when neither `size` nor both insets pin it.
+pub(super) fn resolve(
+    axis: Axis,
+    constraints: Constraints,
+    containing_px: i32,
+    fit_size: impl FnOnce(i32) -> Result<i32, String>,
+) -> Result<Span, String> {
+    let Constraints {
+        start,
+        size,
+        end,
+        margin_start,
+        margin_end,
+        margin_start_auto,
+        margin_end_auto,
+        static_start,
+    } = constraints;
+
+    if let (Some(start), Some(size), Some(end)) = (start, size, end) {
+        let size = size.max(0);
+        let remaining = containing_px
+            .saturating_sub(start)
+            .saturating_sub(size)
+            .saturating_sub(end);
+        let margin_start = match (margin_start_auto, margin_end_auto) {
+            (true, true) if axis == Axis::Horizontal && remaining < 0 => 0,
+            (true, true) => remaining / 2,
+            (true, false) => remaining.saturating_sub(margin_end),
+            (false, _) => margin_start,
+        };
+        return Ok(Span {
+            start: start.saturating_add(margin_start),
+            size,
+        });
+    }
+
+    let start_margin = if margin_start_auto { 0 } else { margin_start };
+    let end_margin = if margin_end_auto { 0 } else { margin_end };
+    let margins = start_margin.saturating_add(end_margin);
+
+    let (start, size) = match (start, size, end) {
+        (Some(start), None, Some(end)) => {
+            let size = containing_px
+                .saturating_sub(start)
+                .saturating_sub(margins)
+                .saturating_sub(end);
+            (start, size)
+        }
+        (None, size, Some(end)) => {
+            let size = match size {
+                Some(size) => size,
+                None => fit_size(containing_px.saturating_sub(margins).saturating_sub(end))?,
+            }
+            .max(0);
+            let start = containing_px
+                .saturating_sub(end)
+                .saturating_sub(end_margin)
+                .saturating_sub(size)
+                .saturating_sub(start_margin);
+            (start, size)
+        }
+        (start, size, _) => {
+            let start = start.unwrap_or(static_start);
+            let size = match size {
+                Some(size) => size,
+                None => fit_size(containing_px.saturating_sub(start).saturating_sub(margins))?,
+            };
+            (start, size)
+        }
+    };
+    Ok(Span {
+        start: start.saturating_add(start_margin),
+        size: size.max(0),
+    })
+}
+
+/// How much had been laid out when a relatively positioned box started, so that what it adds
+/// can be moved by its offsets afterwards.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct RelativeMark {
+    pub(super) commands: usize,
+    links: usize,
+    inspected_boxes: usize,
+    headings: usize,
+}
+
+impl LayoutEngine<'_> {
+    pub(super) fn relative_mark(&self) -> RelativeMark {
+        RelativeMark {
+            commands: self.list.commands.len(),
+            links: self.link_regions.len(),
+            inspected_boxes: self.inspected_boxes.as_ref().map_or(0, Vec::len),
+            headings: self.outline.len(),
+        }
+    }
+
+    /// Moves a `position: relative` box and everything inside it, laid out since `mark`, by its
+    /// `left`/`right`/`top`/`bottom` offsets. The box keeps the space it took in the flow, and
+    /// fixed descendants stay where they are.
+    pub(super) fn offset_relative(
+        &mut self,
+        mark: RelativeMark,
+        style: &ComputedStyle,
+        containing: Rect,
+    ) {
+        let (dx_px, dy_px) = relative_offset(style, containing);
+        self.translate_since(mark, dx_px, dy_px);
+    }
+
+    /// Moves everything laid out since `mark` outside fixed groups by `(dx_px, dy_px)`.
+    pub(super) fn translate_since(&mut self, mark: RelativeMark, dx_px: i32, dy_px: i32) {
+        if (dx_px, dy_px) == (0, 0) {
+            return;
+        }
+        let mut fixed_depth = 0usize;
+        for command in self.list.commands.iter_mut().skip(mark.commands) {
+            match command {
+                DisplayCommand::PushFixed => fixed_depth = fixed_depth.saturating_add(1),
+                DisplayCommand::PopFixed => fixed_depth = fixed_depth.saturating_sub(1),
+                _ if fixed_depth > 0 => {}
+                _ => command.translate(dx_px, dy_px),
+            }
+        }
+        let inside_fixed = self.fixed_depth > 0;
+        for region in self.link_regions.iter_mut().skip(mark.links) {
+            if region.is_fixed == inside_fixed {
+                region.x_px = region.x_px.saturating_add(dx_px);
+                region.y_px = region.y_px.saturating_add(dy_px);
+            }
+        }
+        for inspected in self
+            .inspected_boxes
+            .iter_mut()
+            .flatten()
+            .skip(mark.inspected_boxes)
+        {
+            if inspected.is_fixed == inside_fixed {
+                for rect in [
+                    &mut inspected.margin_box,
+                    &mut inspected.border_box,
+                    &mut inspected.padding_box,
+                    &mut inspected.content_box,
+                ] {
+                    rect.x = rect.x.saturating_add(dx_px);
+                    rect.y = rect.y.saturating_add(dy_px);
+                }
+            }
+        }
+        for heading in self.outline.iter_mut().skip(mark.headings) {
+            heading.y_px = heading.y_px.saturating_add(dy_px);
+        }
+    }
+}
+
+/// `left` wins over `right` and `top` over `bottom`, as in a left-to-right block.
+fn relative_offset(style: &ComputedStyle, containing: Rect) -> (i32, i32) {
+    let dx_px = match (style.left_px, style.right_px) {
+        (Some(left), _) => left.resolve_px(containing.width),
+        (None, Some(right)) => right.resolve_px(containing.width).saturating_neg(),
+        (None, None) => 0,
+    };
+    let dy_px = match (style.top_px, style.bottom_px) {
+        (Some(top), _) => top.resolve_px(containing.height),
+        (None, Some(bottom)) => bottom.resolve_px(containing.height).saturating_neg(),
+        (None, None) => 0,
+    };
+    (dx_px, dy_px)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(start: Option<i32>, size: Option<i32>, end: Option<i32>) -> Constraints {
+        Constraints {
+            start,
+            size,
+            end,
+            margin_start: 5,
+            margin_end: 7,
+            margin_start_auto: false,
+            margin_end_auto: false,
+            static_start: 30,
+        }
+    }
+
+    fn span(start: i32, size: i32) -> Span {
+        Span { start, size }
+    }
+
+    #[test]
+    fn insets_margins_and_sizes_resolve_on_either_axis() {
+        let fit = |available: i32| Ok(available.min(40));
+        let solve = |axis, constraints| resolve(axis, constraints, 200, fit).unwrap();
+        let horizontal = |constraints| solve(Axis::Horizontal, constraints);
+
+        assert_eq!(horizontal(constraints(None, None, None)), span(35, 40));
+        assert_eq!(horizontal(constraints(None, Some(50), None)), span(35, 50));
+        assert_eq!(
+            horizontal(constraints(Some(10), None, Some(20))),
+            span(15, 158)
+        );
+        assert_eq!(horizontal(constraints(None, None, Some(20))), span(133, 40));
+        assert_eq!(
+            horizontal(constraints(None, Some(60), Some(20))),
+            span(113, 60)
+        );
+        assert_eq!(horizontal(constraints(Some(10), None, None)), span(15, 40));
+        assert_eq!(
+            horizontal(constraints(Some(10), Some(60), Some(20))),
+            span(15, 60)
+        );
+
+        let centred = Constraints {
+            margin_start_auto: true,
+            margin_end_auto: true,
+            ..constraints(Some(10), Some(60), Some(20))
+        };
+        assert_eq!(horizontal(centred), span(65, 60));
+        let pushed_to_the_end = Constraints {
+            margin_start_auto: true,
+            ..constraints(Some(10), Some(60), Some(20))
+        };
+        assert_eq!(horizontal(pushed_to_the_end), span(113, 60));
+
+        let too_wide = Constraints {
+            size: Some(300),
+            ..centred
+        };
+        assert_eq!(horizontal(too_wide), span(10, 300));
+        assert_eq!(solve(Axis::Vertical, too_wide), span(-55, 300));
+    }
+}
@@ -1,11 +1,42 @@
 use crate::dom::Element;
 use crate::geom::{Color, Rect};
-use crate::render::{DisplayCommand, DrawImage, DrawSvg, DrawText};
-use crate::style::ComputedStyle;
-use std::rc::Rc;
+use crate::render::canvas::TextBaseline;
+use crate::render::{
+    DisplayCommand, DrawImage, DrawRect, DrawRoundedRect, DrawSvg, DrawText, TextStyle,
+};
+use crate::style::{ComputedStyle, TextAlign};
+use std::sync::Arc;
 
 use super::{LayoutEngine, inline};
 
+/// Media doesn't play: a `<video>` shows its poster or this backdrop with a play button, and
+/// `controls` draw a strip of this height along its bottom edge or, for `<audio>`, fill it.
+const MEDIA_BACKDROP_COLOR: Color = Color {
+    r: 0x20,
+    g: 0x21,
+    b: 0x24,
+    a: 255,
+};
+const MEDIA_CONTROLS_HEIGHT_PX: i32 = 32;
+const VIDEO_CONTROLS_COLOR: Color = Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0x99,
+};
+const AUDIO_CONTROLS_COLOR: Color = Color {
+    r: 0xf1,
+    g: 0xf3,
+    b: 0xf4,
+    a: 255,
+};
+const PLAY_BUTTON_COLOR: Color = Color {
+    r: 0xff,
+    g: 0xff,
+    b: 0xff,
+    a: 0x4d,
+};
+
 impl LayoutEngine<'_> {
     pub(super) fn paint_replaced_content(
         &mut self,
@@ -49,16 +80,193 @@ impl LayoutEngine<'_> {
                     width_px: content_box.width,
                     height_px: content_box.height,
                     opacity: 255,
-                    svg_xml: Rc::from(xml),
+                    svg_xml: Arc::from(xml),
                 }));
+                self.paint_svg_foreign_objects(element, style, content_box)?;
             }
             "input" => self.paint_input_control(element, style, content_box)?,
+            "canvas" => self.paint_canvas(element, content_box)?,
+            "video" => self.paint_video(element, content_box)?,
+            "audio" => self.paint_media_controls(content_box, AUDIO_CONTROLS_COLOR, Color::BLACK),
             _ => {}
         }
 
         Ok(())
     }
 
+    /// Paints what scripts drew on a `<canvas>`, stretched to its content box.
+    fn paint_canvas(&mut self, element: &Element, content_box: Rect) -> Result<(), String> {
+        let Some(surface) = element
+            .attributes
+            .id
+            .as_deref()
+            .and_then(|id| self.canvases.get(id))
+        else {
+            return Ok(());
+        };
+        let surface = surface.clone();
+        let image_width = i32::try_from(surface.image.width)
+            .unwrap_or(i32::MAX)
+            .max(1);
+        let image_height = i32::try_from(surface.image.height)
+            .unwrap_or(i32::MAX)
+            .max(1);
+        self.list.commands.push(DisplayCommand::Image(DrawImage {
+            x_px: content_box.x,
+            y_px: content_box.y,
+            width_px: content_box.width,
+            height_px: content_box.height,
+            opacity: 255,
+            image: surface.image.clone(),
+        }));
+
+        let scale_x = f64::from(content_box.width) / f64::from(image_width);
+        let scale_y = f64::from(content_box.height) / f64::from(image_height);
+        let scale = |value: i32, factor: f64| (f64::from(value) * factor).round() as i32;
+        for run in &surface.texts {
+            let text_style = TextStyle {
+                color: run.color,
+                bold: run.bold,
+                font_family: run.font_family,
+                font_size_px: scale(run.font_size_px, scale_y).max(1),
+                ..TextStyle::default()
+            };
+            let mut x_px = content_box.x.saturating_add(scale(run.x_px, scale_x));
+            let mut y_px = content_box.y.saturating_add(scale(run.y_px, scale_y));
+            if run.align != TextAlign::Left {
+                let width_px = self.measurer.text_width_px(&run.text, text_style)?;
+                x_px = x_px.saturating_sub(match run.align {
+                    TextAlign::Center => width_px / 2,
+                    _ => width_px,
+                });
+            }
+            let metrics = self.measurer.font_metrics_px(text_style);
+            y_px = y_px.saturating_add(match run.baseline {
+                TextBaseline::Alphabetic => 0,
+                TextBaseline::Top => metrics.ascent_px,
+                TextBaseline::Middle => (metrics.ascent_px - metrics.descent_px) / 2,
+                TextBaseline::Bottom => -metrics.descent_px,
+            });
+            self.list.commands.push(DisplayCommand::Text(DrawText {
+                x_px,
+                y_px,
+                text: run.text.clone(),
+                style: text_style,
+            }));
+        }
+        Ok(())
+    }
+
+    /// A `<video>`'s poster, fitted inside its content box, or a backdrop with a play button.
+    fn paint_video(&mut self, element: &Element, content_box: Rect) -> Result<(), String> {
+        let poster = match element.attributes.get("poster") {
+            Some(src) => self.load_image(src)?,
+            None => None,
+        };
+        if let Some(image) = poster {
+            let image_width = i64::from(image.width.max(1));
+            let image_height = i64::from(image.height.max(1));
+            let (box_width, box_height) =
+                (i64::from(content_box.width), i64::from(content_box.height));
+            let (width_px, height_px) = if box_width * image_height <= box_height * image_width {
+                (box_width, box_width * image_height / image_width)
+            } else {
+                (box_height * image_width / image_height, box_height)
+            };
+            let (width_px, height_px) = (width_px as i32, height_px as i32);
+            self.list.commands.push(DisplayCommand::Image(DrawImage {
+                x_px: content_box.x + (content_box.width - width_px) / 2,
+                y_px: content_box.y + (content_box.height - height_px) / 2,
+                width_px,
+                height_px,
+                opacity: 255,
+                image,
+            }));
+        } else {
+            self.list.commands.push(DisplayCommand::Rect(DrawRect {
+                x_px: content_box.x,
+                y_px: content_box.y,
+                width_px: content_box.width,
+                height_px: content_box.height,
+                color: MEDIA_BACKDROP_COLOR,
+            }));
+            let diameter_px = (content_box.width.min(content_box.height) / 3).clamp(16, 68);
+            let center_x = content_box.x + content_box.width / 2;
+            let center_y = content_box.y + content_box.height / 2;
+            self.list
+                .commands
+                .push(DisplayCommand::RoundedRect(DrawRoundedRect {
+                    x_px: center_x - diameter_px / 2,
+                    y_px: center_y - diameter_px / 2,
+                    width_px: diameter_px,
+                    height_px: diameter_px,
+                    radius_px: diameter_px / 2,
+                    color: PLAY_BUTTON_COLOR,
+                }));
+            self.paint_play_glyph(center_x, center_y, diameter_px / 2, Color::WHITE);
+        }
+
+        if element.attributes.get("controls").is_some() {
+            let height_px = MEDIA_CONTROLS_HEIGHT_PX.min(content_box.height);
+            let strip = Rect {
+                y: content_box.bottom() - height_px,
+                height: height_px,
+                ..content_box
+            };
+            self.paint_media_controls(strip, VIDEO_CONTROLS_COLOR, Color::WHITE);
+        }
+        Ok(())
+    }
+
+    /// A strip with a play button at its start and an empty progress track after it.
+    fn paint_media_controls(&mut self, strip: Rect, background: Color, foreground: Color) {
+        self.list.commands.push(DisplayCommand::Rect(DrawRect {
+            x_px: strip.x,
+            y_px: strip.y,
+            width_px: strip.width,
+            height_px: strip.height,
+            color: background,
+        }));
+        let glyph_px = (strip.height / 2).max(1);
+        let center_y = strip.y + strip.height / 2;
+        self.paint_play_glyph(strip.x + strip.height / 2, center_y, glyph_px, foreground);
+        let track_x = strip.x + strip.height;
+        let track_width = strip.width - strip.height - strip.height / 2;
+        if track_width > 0 {
+            self.list.commands.push(DisplayCommand::Rect(DrawRect {
+                x_px: track_x,
+                y_px: center_y - 1,
+                width_px: track_width,
+                height_px: 2,
+                color: Color {
+                    a: 0x66,
+                    ..foreground
+                },
+            }));
+        }
+    }
+
+    /// A right-pointing triangle `size_px` tall, centred on the point given, drawn a row at a
+    /// time since the display list has no paths.
+    fn paint_play_glyph(&mut self, center_x: i32, center_y: i32, size_px: i32, color: Color) {
+        let width_px = size_px * 7 / 8;
+        let left_px = center_x - width_px / 3;
+        let top_px = center_y - size_px / 2;
+        for row in 0..size_px {
+            let reach = size_px - (2 * row + 1 - size_px).abs();
+            let row_width = reach * width_px / size_px.max(1);
+            if row_width > 0 {
+                self.list.commands.push(DisplayCommand::Rect(DrawRect {
+                    x_px: left_px,
+                    y_px: top_px + row,
+                    width_px: row_width,
+                    height_px: 1,
+                    color,
+                }));
+            }
+        }
+    }
+
     fn paint_input_control(
         &mut self,
         element: &Element,
@@ -34,6 +34,8 @@ fn write_element_xml(element: &Element, out: &mut String, svg_mode: bool) {
     for child in &element.children {
         match child {
             Node::Text(text) => write_xml_escaped(text, out, false),
+            // foreignObject content is laid out as HTML on top of the rendered picture.
+            Node::Element(child) if svg_mode && child.name == "foreignobject" => {}
             Node::Element(child) => write_element_xml(child, out, svg_mode),
         }
     }
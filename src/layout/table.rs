@@ -5,6 +5,9 @@ use crate::style::{ComputedStyle, Display, TextAlign, Visibility};
 
 use super::LayoutEngine;
 
+/// The widest `colspan` honoured, as in HTML; larger values are clamped to it.
+const MAX_COLSPAN: usize = 1000;
+
 pub(super) fn measure_auto_table_width<'doc>(
     engine: &LayoutEngine<'_>,
     table: &'doc Element,
@@ -63,17 +66,11 @@ pub(super) fn layout_table<'doc>(
 
     let mut y = content_box.y;
     if let Some(caption) = collect_table_caption(table) {
-        let mut caption_style = engine.styles.compute_style_in_viewport(
-            caption,
-            table_style,
-            ancestors,
-            engine.viewport.width_px,
-            engine.viewport.height_px,
-        );
+        let mut caption_style = engine.compute_style(caption, table_style, ancestors);
         if caption_style.display == Display::Inline {
             caption_style.display = Display::Block;
         }
-        if caption_style.text_align == TextAlign::Left {
+        if matches!(caption_style.text_align, TextAlign::Left | TextAlign::Start) {
             caption_style.text_align = TextAlign::Center;
         }
         engine.layout_block_box(
@@ -94,30 +91,18 @@ pub(super) fn layout_table<'doc>(
     }
 
     for row in &grid.rows {
-        let row_style = engine.styles.compute_style_in_viewport(
-            row.element,
-            table_style,
-            ancestors,
-            engine.viewport.width_px,
-            engine.viewport.height_px,
-        );
+        let row_style = engine.compute_style(row.element, table_style, ancestors);
         if row_style.display == Display::None {
             continue;
         }
         let row_paint = paint && row_style.visibility == Visibility::Visible;
 
-        let mut row_height = row_style.height_px.unwrap_or(0).max(0);
+        let mut row_height = super::used_height_px(&row_style).unwrap_or(0);
 
         ancestors.push(row.element);
         let mut x = content_box.x;
         for cell in &row.cells {
-            let cell_style = engine.styles.compute_style_in_viewport(
-                cell.element,
-                &row_style,
-                ancestors,
-                engine.viewport.width_px,
-                engine.viewport.height_px,
-            );
+            let cell_style = engine.compute_style(cell.element, &row_style, ancestors);
             if cell_style.display == Display::None {
                 continue;
             }
@@ -159,6 +144,8 @@ pub(super) fn layout_table<'doc>(
                 None
             };
 
+            let outer_height =
+                engine.enter_containing_height(super::used_height_px(&cell_style), padding);
             ancestors.push(cell.element);
             let content_height = engine.layout_flow_children(
                 &cell.element.children,
@@ -168,11 +155,12 @@ pub(super) fn layout_table<'doc>(
                 cell_paint,
             )?;
             ancestors.pop();
+            engine.containing_height_px = outer_height;
             let mut cell_height = padding
                 .top
                 .saturating_add(content_height)
                 .saturating_add(padding.bottom);
-            if let Some(min_height) = cell_style.height_px {
+            if let Some(min_height) = super::used_height_px(&cell_style) {
                 cell_height = cell_height.max(min_height);
             }
 
@@ -221,13 +209,7 @@ fn compute_intrinsic_column_widths<'doc>(
 
     for row in &grid.rows {
         for cell in &row.cells {
-            let cell_style = engine.styles.compute_style_in_viewport(
-                cell.element,
-                table_style,
-                ancestors,
-                engine.viewport.width_px,
-                engine.viewport.height_px,
-            );
+            let cell_style = engine.compute_style(cell.element, table_style, ancestors);
             let min_width =
                 measure_cell_min_width(engine, cell.element, &cell_style, ancestors, cellpadding)?;
             let target_width = cell_style
@@ -241,7 +223,8 @@ fn compute_intrinsic_column_widths<'doc>(
                 cell,
                 target_width,
                 cellspacing,
-                cell_style.width_px.is_some() || cell_style.text_align == TextAlign::Right,
+                cell_style.width_px.is_some()
+                    || cell_style.text_align.resolve(cell_style.direction) == TextAlign::Right,
             );
         }
     }
@@ -333,7 +316,7 @@ fn build_grid<'doc>(rows: Vec<&'doc Element>) -> Grid<'doc> {
                 .get("colspan")
                 .and_then(parse_usize)
                 .unwrap_or(1)
-                .max(1);
+                .clamp(1, MAX_COLSPAN);
             cells.push(GridCell {
                 element: el,
                 col_index,
@@ -463,13 +446,7 @@ fn measure_caption_min_width<'doc>(
     let Some(caption) = collect_table_caption(table) else {
         return Ok(0);
     };
-    let style = engine.styles.compute_style_in_viewport(
-        caption,
-        table_style,
-        ancestors,
-        engine.viewport.width_px,
-        engine.viewport.height_px,
-    );
+    let style = engine.compute_style(caption, table_style, ancestors);
     let mut width = 0i32;
     ancestors.push(caption);
     measure_inline_words(
@@ -536,13 +513,7 @@ fn measure_inline_words<'doc>(
                 }
             }
             Node::Element(el) => {
-                let child_style = engine.styles.compute_style_in_viewport(
-                    el,
-                    style,
-                    ancestors,
-                    engine.viewport.width_px,
-                    engine.viewport.height_px,
-                );
+                let child_style = engine.compute_style(el, style, ancestors);
                 if child_style.display == Display::None {
                     continue;
                 }
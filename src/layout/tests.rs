@@ -584,3 +584,1244 @@ fn media_query_can_enable_svg_img_rendering() {
         "SVG should render when media query enables display"
     );
 }
+
+#[test]
+fn virtual_window_skips_blocks_far_from_the_window() {
+    let mut html = String::from("<style>body { margin: 0; } p { margin: 0; }</style>");
+    for idx in 0..1000 {
+        html.push_str(&format!("<p>row{idx}</p>"));
+    }
+    let doc = crate::html::parse_document(&html);
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let viewport = Viewport {
+        width_px: 200,
+        height_px: 100,
+    };
+
+    let full = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        viewport,
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+    let windowed = layout_document_in_window(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        viewport,
+        &crate::resources::NoResources,
+        Some(VirtualWindow {
+            top_px: 0,
+            bottom_px: 300,
+        }),
+        false,
+    )
+    .unwrap();
+
+    let text_count = |output: &LayoutOutput| {
+        output
+            .display_list
+            .commands
+            .iter()
+            .filter(|cmd| matches!(cmd, DisplayCommand::Text(_)))
+            .count()
+    };
+    assert_eq!(text_count(&full), 1000);
+    assert!(text_count(&windowed) < 100);
+    assert_eq!(windowed.document_height_px, full.document_height_px);
+}
+
+#[test]
+fn inspected_layout_records_element_boxes() {
+    let doc = crate::html::parse_document(
+        "<html><style>body { margin: 0; } #card { margin: 10px; padding: 5px; border: 2px solid red; }</style><body><div id=card class=note>Hi</div></body></html>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document_in_window(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 200,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+        None,
+        true,
+    )
+    .unwrap();
+
+    let card = output
+        .inspected_boxes
+        .iter()
+        .find(|inspected| inspected.path == "html > body > div#card.note")
+        .unwrap();
+    assert_eq!(card.depth, 2);
+    assert_eq!(
+        card.border_box,
+        Rect {
+            x: 10,
+            y: 10,
+            width: 180,
+            height: 24,
+        }
+    );
+    assert_eq!(card.margin_box.height, 44);
+    assert_eq!(card.content_box.x, 17);
+    assert_eq!(card.content_box.height, 10);
+}
+
+#[test]
+fn inspected_layout_records_inline_boxes_per_line() {
+    let doc = crate::html::parse_document(
+        "<html><style>body, p { margin: 0; }</style><body><p>ab <a href=x>cd <b>ef</b> gh ij</a></p></body></html>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document_in_window(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 10,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+        None,
+        true,
+    )
+    .unwrap();
+
+    let boxes_of = |path: &str| {
+        output
+            .inspected_boxes
+            .iter()
+            .filter(|inspected| inspected.path == path)
+            .map(|inspected| inspected.border_box)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        boxes_of("html > body > p > a"),
+        [
+            Rect {
+                x: 3,
+                y: 0,
+                width: 5,
+                height: 10,
+            },
+            Rect {
+                x: 0,
+                y: 10,
+                width: 5,
+                height: 10,
+            },
+        ]
+    );
+    assert_eq!(
+        boxes_of("html > body > p > a > b"),
+        [Rect {
+            x: 6,
+            y: 0,
+            width: 2,
+            height: 10,
+        }]
+    );
+}
+
+#[test]
+fn svg_foreign_object_html_is_laid_out_at_mapped_rect() {
+    let doc = crate::html::parse_document(
+        r#"<svg width="200" height="100" viewBox="0 0 100 50"><rect width="100" height="50"/><g transform="translate(10, 5)"><foreignObject x="0" y="0" width="50" height="20"><div>Hello</div></foreignObject></g></svg>"#,
+    );
+    let viewport = Viewport {
+        width_px: 400,
+        height_px: 300,
+    };
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        viewport,
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let svg = output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|cmd| match cmd {
+            DisplayCommand::Svg(svg) => Some(svg),
+            _ => None,
+        })
+        .expect("inline SVG should render via DrawSvg");
+    assert!(!svg.svg_xml.contains("foreignObject"));
+    assert!(!svg.svg_xml.contains("Hello"));
+
+    let text = output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|cmd| match cmd {
+            DisplayCommand::Text(text) if text.text == "Hello" => Some(text),
+            _ => None,
+        })
+        .expect("foreignObject HTML should render as text");
+    assert_eq!(text.x_px - svg.x_px, 20);
+    let offset_y = text.y_px - svg.y_px;
+    assert!((10..30).contains(&offset_y), "offset_y = {offset_y}");
+}
+
+#[test]
+fn outline_lists_headings_with_positions() {
+    let doc = crate::html::parse_document(
+        r#"<body style="margin: 0"><h1 id="top">Guide <em>one</em></h1><p>Intro</p><h2>Setup</h2><h3 style="display: none">Hidden</h3><section><h3 id="deep">  Deep
+        dive </h3></section><h4>Notes</h4><div style="display: flex"><h5>Aside</h5></div></body>"#,
+    );
+    let viewport = Viewport {
+        width_px: 400,
+        height_px: 300,
+    };
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        viewport,
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let summary: Vec<(u8, &str, Option<&str>)> = output
+        .outline
+        .iter()
+        .map(|heading| (heading.level, heading.text.as_str(), heading.id.as_deref()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (1, "Guide one", Some("top")),
+            (2, "Setup", None),
+            (3, "Deep dive", Some("deep")),
+            (4, "Notes", None),
+            (5, "Aside", None),
+        ]
+    );
+    assert!(output.outline[0].y_px >= 0);
+    assert!(output.outline[0].y_px < output.outline[1].y_px);
+    assert!(output.outline[1].y_px < output.outline[2].y_px);
+    assert!(output.outline[2].y_px < output.outline[4].y_px);
+}
+
+#[test]
+fn canvas_paints_its_surface_and_text_runs() {
+    let mut doc = crate::html::parse_document(
+        r#"<canvas id="c" width="20" height="10" style="width: 40px; height: 20px"></canvas>"#,
+    );
+    let mut context = crate::render::canvas::Context2d::new(20, 10).unwrap();
+    context.set_text_align("center");
+    context.set_text_baseline("top");
+    context.fill_text("hi", 10.0, 2.0);
+    doc.canvases
+        .insert("c".to_owned(), Arc::new(context.into_surface().unwrap()));
+    let viewport = Viewport {
+        width_px: 200,
+        height_px: 200,
+    };
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(&doc, &styles, &FixedMeasurer, viewport, &SvgOnlyResources)
+        .expect("layout should succeed");
+
+    let image = output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|cmd| match cmd {
+            DisplayCommand::Image(image) => Some(image),
+            _ => None,
+        })
+        .expect("canvas should paint an image");
+    assert_eq!((image.width_px, image.height_px), (40, 20));
+    let text = output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|cmd| match cmd {
+            DisplayCommand::Text(text) => Some(text),
+            _ => None,
+        })
+        .expect("canvas should paint its text");
+    assert_eq!(text.text, "hi");
+    assert_eq!(text.x_px, image.x_px + 20 - 1);
+    assert_eq!(text.y_px, image.y_px + 4 + 8);
+    assert_eq!(text.style.font_size_px, 20);
+}
+
+#[test]
+fn document_width_reaches_the_widest_content_but_not_fixed_boxes() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='width: 25px; height: 10px; background: #ff0000'></div>\
+         <p style='white-space: nowrap'>A line of text that runs on</p>\
+         <div style='position: fixed; width: 900px; height: 10px; background: #0000ff'></div>\
+         </body>",
+    );
+    let viewport = Viewport {
+        width_px: 20,
+        height_px: 200,
+    };
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let layout = |viewport| {
+        layout_document(
+            &doc,
+            &styles,
+            &FixedMeasurer,
+            viewport,
+            &crate::resources::NoResources,
+        )
+        .unwrap()
+        .document_width_px
+    };
+    assert_eq!(layout(viewport), 27);
+    assert_eq!(
+        layout(Viewport {
+            width_px: 500,
+            ..viewport
+        }),
+        500
+    );
+}
+
+/// The `(x, y, width, height)` of the first rect painted in `rgb`.
+fn rect_of(output: &LayoutOutput, rgb: (u8, u8, u8)) -> (i32, i32, i32, i32) {
+    output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|command| match command {
+            DisplayCommand::Rect(rect) if (rect.color.r, rect.color.g, rect.color.b) == rgb => {
+                Some((rect.x_px, rect.y_px, rect.width_px, rect.height_px))
+            }
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn absolute_boxes_resolve_insets_on_both_axes() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'><div style='height: 30px'></div>\
+         <div style='position: absolute; right: 10px; bottom: 10px; margin-right: 5px; \
+         background: #ff0000'><div style='width: 30px; height: 20px'></div></div>\
+         <div style='position: absolute; left: 0; top: 10px; bottom: 20px; width: 40px; \
+         background: #00ff00'></div>\
+         <div style='position: absolute; left: 0; right: 0; top: 0; width: 50px; height: 10px; \
+         margin: 0 auto; background: #0000ff'></div>\
+         <div style='position: absolute; width: 10px; height: 10px; background: #ffff00'></div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 200,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+    assert_eq!(rect_of(&output, (255, 0, 0)), (155, 70, 30, 20));
+    assert_eq!(rect_of(&output, (0, 255, 0)), (0, 10, 40, 70));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (75, 0, 50, 10));
+    assert_eq!(rect_of(&output, (255, 255, 0)), (0, 30, 10, 10));
+}
+
+#[test]
+fn relative_offsets_move_boxes_without_moving_the_flow() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='position: relative; left: 10px; top: 5px; height: 20px; background: #ff0000'>\
+         <div style='position: absolute; left: 0; top: 0; width: 5px; height: 5px; \
+         background: #00ff00'></div>\
+         <div style='position: fixed; left: 0; top: 0; width: 5px; height: 5px; \
+         background: #0000ff'></div></div>\
+         <div style='position: relative; right: 10px; bottom: 5px; height: 10px; \
+         background: #ffff00'></div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+    assert_eq!(rect_of(&output, (255, 0, 0)), (10, 5, 100, 20));
+    assert_eq!(rect_of(&output, (0, 255, 0)), (10, 5, 5, 5));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (0, 0, 5, 5));
+    assert_eq!(rect_of(&output, (255, 255, 0)), (-10, 15, 100, 10));
+}
+
+#[test]
+fn transformed_boxes_are_wrapped_in_a_group_about_their_middle() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='position: relative; left: 10px; width: 40px; height: 20px; \
+         transform: translate(5px, 0) rotate(90deg)'><p style='margin: 0'>x</p></div>\
+         <div style='display: flex'><div style='width: 10px; height: 10px; \
+         transform: scale(2)'></div></div>\
+         <span style='display: inline-block; width: 6px; height: 6px; transform: none'></span>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+    let commands = &output.display_list.commands;
+    let groups: Vec<(usize, crate::render::Transform)> = commands
+        .iter()
+        .enumerate()
+        .filter_map(|(index, command)| match command {
+            DisplayCommand::PushTransform(transform) => Some((index, *transform)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(groups.len(), 2);
+    let pops = commands
+        .iter()
+        .filter(|command| matches!(command, DisplayCommand::PopTransform))
+        .count();
+    assert_eq!(pops, 2);
+    assert!(matches!(commands[groups[0].0 + 1], DisplayCommand::Text(_)));
+
+    let maps = |transform: crate::render::Transform, from: (f64, f64), to: (f64, f64)| {
+        let point = transform.apply(crate::render::Point::new(from.0, from.1));
+        assert!(
+            (point.x - to.0).abs() < 1e-9 && (point.y - to.1).abs() < 1e-9,
+            "{from:?} -> {point:?}"
+        );
+    };
+    // Rotated about (30, 10) once moved 10px right, then moved 5px further.
+    maps(groups[0].1, (30.0, 10.0), (35.0, 10.0));
+    maps(groups[0].1, (50.0, 10.0), (35.0, 30.0));
+    // Doubled about the middle of the flex item at (5, 25).
+    maps(groups[1].1, (0.0, 20.0), (-5.0, 15.0));
+}
+
+#[test]
+fn floats_drop_below_full_lines_and_clear_moves_blocks_past_them() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='float: left; width: 60px; height: 20px; background: #ff0000'></div>\
+         <div style='float: left; background: #00ff00'><div style='width: 50px; height: 10px'>\
+         </div></div>\
+         <div style='float: right; width: 10px; height: 5px; background: #0000ff'></div>\
+         <div style='clear: left; height: 5px; background: #ffff00'></div>\
+         <div style='float: left; width: 70px; height: 10px; background: #ff00ff'></div>\
+         <div style='float: left; background: #00ffff'>abcdefgh abcdefgh abcdefgh abcdefgh</div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    assert_eq!(rect_of(&output, (0, 255, 0)), (0, 20, 50, 10));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (90, 20, 10, 5));
+    assert_eq!(rect_of(&output, (255, 255, 0)), (0, 30, 100, 5));
+    let (x_px, y_px, width_px, _) = rect_of(&output, (0, 255, 255));
+    assert_eq!((x_px, y_px, width_px), (70, 35, 30));
+}
+
+#[test]
+fn content_keywords_size_boxes_by_their_content() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='width: min-content; background: #ff0000'>abcdefgh abcd</div>\
+         <div style='width: max-content; background: #00ff00'>abcd efgh</div>\
+         <div style='width: fit-content; padding: 0 2px; background: #0000ff'>abcd efgh</div>\
+         <div style='width: fit-content; background: #ffff00'>abcdefghij abcdefghij abcdefghij \
+         abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij abcdefghij</div>\
+         <div style='width: 5px; min-width: max-content; background: #ff00ff'>abcd efgh</div>\
+         <div style='position: absolute; left: 95px; top: 0; background: #00ffff'>abcdefghij</div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    assert_eq!(rect_of(&output, (255, 0, 0)), (0, 0, 8, 20));
+    assert_eq!(rect_of(&output, (0, 255, 0)).2, 9);
+    assert_eq!(rect_of(&output, (0, 0, 255)).2, 13);
+    assert_eq!(rect_of(&output, (255, 255, 0)).2, 100);
+    assert_eq!(rect_of(&output, (255, 0, 255)).2, 9);
+    assert_eq!(rect_of(&output, (0, 255, 255)), (95, 0, 10, 10));
+}
+
+#[test]
+fn percentage_heights_resolve_against_set_containing_heights() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='height: 50px'>\
+         <div style='height: 50%; background: #ff0000'></div>\
+         <div style='height: 10px; min-height: 40%; background: #00ff00'></div>\
+         <div style='height: 100%; max-height: 30%; background: #0000ff'></div>\
+         </div>\
+         <div><div style='height: 50%; background: #ffff00'>x</div></div>\
+         <div style='height: 40px; padding: 5px'>\
+         <div style='height: 100%; background: #ff00ff'></div></div>\
+         <div style='position: absolute; top: 0; height: 25%; width: 5px; background: #00ffff'>\
+         </div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 200,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    assert_eq!(rect_of(&output, (255, 0, 0)), (0, 0, 100, 25));
+    assert_eq!(rect_of(&output, (0, 255, 0)), (0, 25, 100, 20));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (0, 45, 100, 15));
+    assert_eq!(rect_of(&output, (255, 255, 0)), (0, 60, 100, 10));
+    assert_eq!(rect_of(&output, (255, 0, 255)), (5, 75, 90, 30));
+    assert_eq!(rect_of(&output, (0, 255, 255)), (0, 0, 5, 50));
+}
+
+#[test]
+fn flex_items_reorder_align_themselves_and_shrink_no_narrower_than_their_content() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='display: flex; width: 100px'>\
+         <div style='order: 2; width: 80px; background: #ff0000'>xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx</div>\
+         <div style='width: 80px; height: 30px; background: #00ff00'></div>\
+         <div style='order: 1; width: 40px; height: 10px; align-self: flex-end; \
+         background: #0000ff'></div>\
+         </div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 200,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    assert_eq!(rect_of(&output, (0, 255, 0)), (0, 0, 34, 30));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (34, 20, 16, 10));
+    assert_eq!(rect_of(&output, (255, 0, 0)), (50, 0, 50, 30));
+}
+
+#[test]
+fn flex_lines_reverse_and_spread_items_around_and_evenly() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='display: flex; flex-direction: row-reverse'>\
+         <div style='width: 10px; height: 10px; background: #ff0000'></div>\
+         <div style='width: 10px; height: 10px; background: #00ff00'></div></div>\
+         <div style='display: flex; justify-content: space-around'>\
+         <div style='width: 10px; height: 10px; background: #0000ff'></div>\
+         <div style='width: 10px; height: 10px; background: #ffff00'></div></div>\
+         <div style='display: flex; justify-content: space-evenly'>\
+         <div style='width: 10px; height: 10px; background: #ff00ff'></div>\
+         <div style='width: 10px; height: 10px; background: #00ffff'></div></div>\
+         <div style='display: flex; flex-direction: column-reverse'>\
+         <div style='width: 10px; height: 10px; background: #800000'></div>\
+         <div style='width: 10px; height: 10px; background: #008000'></div></div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    assert_eq!(rect_of(&output, (255, 0, 0)), (90, 0, 10, 10));
+    assert_eq!(rect_of(&output, (0, 255, 0)), (80, 0, 10, 10));
+    assert_eq!(rect_of(&output, (0, 0, 255)).0, 20);
+    assert_eq!(rect_of(&output, (255, 255, 0)).0, 70);
+    assert_eq!(rect_of(&output, (255, 0, 255)).0, 26);
+    assert_eq!(rect_of(&output, (0, 255, 255)).0, 62);
+    assert_eq!(rect_of(&output, (0, 128, 0)).1, 30);
+    assert_eq!(rect_of(&output, (128, 0, 0)).1, 40);
+}
+
+#[test]
+fn flex_items_stretch_across_the_line_and_pass_their_height_on() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='display: flex; height: 40px'>\
+         <div style='width: 20px; background: #ff0000'></div>\
+         <div style='width: 20px; height: 10px; background: #00ff00'></div>\
+         <div style='width: 20px; margin-top: 5px; background: #0000ff'>\
+         <div style='height: 50%; background: #ffff00'></div></div>\
+         </div>\
+         <div style='display: flex; flex-direction: column; align-items: center'>\
+         <div style='background: #ff00ff'>abcd</div></div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    assert_eq!(rect_of(&output, (255, 0, 0)), (0, 0, 20, 40));
+    assert_eq!(rect_of(&output, (0, 255, 0)), (20, 0, 20, 10));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (40, 5, 20, 35));
+    assert_eq!(rect_of(&output, (255, 255, 0)), (40, 5, 20, 18));
+    assert_eq!(rect_of(&output, (255, 0, 255)), (48, 40, 4, 10));
+}
+
+#[test]
+fn first_lines_indent_and_take_their_pseudo_element_styles() {
+    let doc = crate::html::parse_document(
+        "<head><style>\
+         .b::first-line { color: #ff0000; text-transform: uppercase }\
+         .b::first-letter { color: #0000ff }\
+         .c { line-height: 4px }\
+         .c::first-letter { float: left; margin-right: 2px }\
+         </style></head>\
+         <body style='margin: 0'>\
+         <div style='width: 12px; text-indent: 5px'>aaa bbb ccc</div>\
+         <div class='b' style='width: 10px'>once upon time</div>\
+         <div class='c' style='width: 12px'>drop cap text goes here again</div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let text = |word: &str| {
+        output
+            .display_list
+            .commands
+            .iter()
+            .find_map(|command| match command {
+                DisplayCommand::Text(text) if text.text == word => {
+                    Some((text.x_px, text.y_px, text.style.color))
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("{word} should be painted"))
+    };
+    let red = crate::geom::Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    let blue = crate::geom::Color {
+        r: 0,
+        g: 0,
+        b: 255,
+        a: 255,
+    };
+
+    assert_eq!(text("aaa").0, 5);
+    assert_eq!(text("ccc").0, 0);
+
+    assert_eq!(text("O"), (0, 28, blue));
+    assert_eq!(text("NCE"), (1, 28, red));
+    assert_eq!(text("UPON"), (5, 28, red));
+    assert_eq!(text("time"), (0, 38, crate::geom::Color::BLACK));
+
+    assert_eq!(text("d").0, 0);
+    assert_eq!(text("rop").0, 3);
+    assert_eq!(text("text"), (3, 52, crate::geom::Color::BLACK));
+    assert_eq!(text("here").0, 3);
+    assert_eq!(text("again").0, 0);
+}
+
+#[test]
+fn word_spacing_widens_spaces_and_small_caps_stay_in_one_word() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='width: 6px; font-size: 20px; word-spacing: 2px; font-variant: small-caps'>\
+         ab Hello</div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let texts: Vec<_> = output
+        .display_list
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            DisplayCommand::Text(text) => {
+                Some((text.text.as_str(), text.x_px, text.style.font_size_px))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![("AB", 0, 14), (" ", 2, 20), ("H", 5, 20), ("ELLO", 6, 14)]
+    );
+}
+
+#[test]
+fn vertical_align_places_inline_boxes_against_the_text() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'><div style='width: 400px'>ab\
+         <img width='4' height='30' style='background: #ff0000'>\
+         <img width='4' height='20' style='background: #00ff00; vertical-align: middle'>\
+         <img width='4' height='12' style='background: #0000ff; vertical-align: top'>\
+         <img width='4' height='6' style='background: #ffff00; vertical-align: bottom'>\
+         <img width='4' height='4' style='background: #00ffff; vertical-align: text-top'>\
+         <img width='4' height='10' style='background: #ff00ff; vertical-align: 5px'>\
+         <span style='display: inline-block; background: #808080'>x</span>\
+         </div></body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 400,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let top = |rgb| rect_of(&output, rgb).1;
+    assert_eq!(top((255, 0, 0)), 0);
+    assert_eq!(top((0, 255, 0)), 18);
+    assert_eq!(top((0, 0, 255)), 0);
+    assert_eq!(top((255, 255, 0)), 32);
+    assert_eq!(top((0, 255, 255)), 22);
+    assert_eq!(top((255, 0, 255)), 15);
+    assert_eq!(top((128, 128, 128)), 22);
+    let baseline = output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|command| match command {
+            DisplayCommand::Text(text) if text.text == "ab" => Some(text.y_px),
+            _ => None,
+        });
+    assert_eq!(baseline, Some(30));
+}
+
+#[test]
+fn right_to_left_lines_start_on_the_right_and_reorder_their_words() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div dir='rtl' style='width: 100px'>שלום world 123</div>\
+         <div style='direction: rtl; width: 100px'>\
+         <div style='width: 40px; height: 5px; background: #ff0000'></div></div>\
+         <div style='width: 100px; text-align: end'>ab</div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let x = |word: &str| {
+        output
+            .display_list
+            .commands
+            .iter()
+            .find_map(|command| match command {
+                DisplayCommand::Text(text) if text.text == word => Some(text.x_px),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("{word} should be painted"))
+    };
+    assert_eq!(x("world"), 82);
+    assert_eq!(x("123"), 88);
+    assert_eq!(x("םולש"), 92);
+    assert_eq!(rect_of(&output, (255, 0, 0)).0, 60);
+    assert_eq!(x("ab"), 98);
+}
+
+#[test]
+fn words_break_at_soft_hyphens_and_hyphenate_with_hyphens_auto() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='width: 10px'>ab extra&shy;ordinary</div>\
+         <div style='width: 3px; hyphens: none'>co&shy;op</div>\
+         <div style='width: 8px; hyphens: auto'>hyphenation</div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let texts: Vec<(&str, i32, i32)> = output
+        .display_list
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            DisplayCommand::Text(text) if text.text != " " => {
+                Some((text.text.as_str(), text.x_px, text.y_px))
+            }
+            _ => None,
+        })
+        .collect();
+    let line = |word: &str| {
+        texts
+            .iter()
+            .find(|(text, ..)| *text == word)
+            .map(|&(_, x, y)| (x, y))
+            .unwrap_or_else(|| panic!("{word} should be painted"))
+    };
+    assert_eq!(line("extra-").1, line("ab").1);
+    assert_eq!(line("ordinary"), (0, line("ab").1 + 10));
+    assert!(line("coop").1 > line("ordinary").1);
+    assert_eq!(line("hyphen-").1 + 10, line("ation").1);
+}
+
+#[test]
+fn outlines_paint_around_the_border_box_without_moving_anything() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div style='margin: 10px; width: 20px; height: 10px; background: #ff0000; \
+         outline: 2px solid #00ff00; outline-offset: 1px'></div>\
+         <div style='margin: 0 10px; width: 20px; height: 10px; background: #0000ff; \
+         outline: auto'></div>\
+         <div style='width: 20px; height: 10px; outline: 3px none #00ff00'></div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 100,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let rings: Vec<&DrawRoundedRectBorder> = output
+        .display_list
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            DisplayCommand::RoundedRectBorder(ring) => Some(ring),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rings.len(), 2);
+    assert_eq!(
+        (
+            rings[0].x_px,
+            rings[0].y_px,
+            rings[0].width_px,
+            rings[0].height_px
+        ),
+        (7, 7, 26, 16)
+    );
+    assert_eq!(rings[0].border_width_px, 2);
+    assert_eq!(rings[0].color.g, 255);
+    assert_eq!(rings[1].border_width_px, 2);
+    assert_eq!((rings[1].x_px, rings[1].y_px), (8, 28));
+
+    assert_eq!(rect_of(&output, (255, 0, 0)), (10, 10, 20, 10));
+    assert_eq!(rect_of(&output, (0, 0, 255)), (10, 30, 20, 10));
+}
+
+#[test]
+fn transitioned_colors_ease_from_the_old_value_once_a_clock_is_set() {
+    let page = |background: &str| {
+        crate::html::parse_document(&format!(
+            "<body style='margin: 0'><div style='height: 10px; background: {background}; \
+             transition: background-color 100ms linear'></div></body>"
+        ))
+    };
+    let styles = crate::style::StyleComputer::empty();
+    let layout = |doc: &crate::dom::Document| {
+        layout_document(
+            doc,
+            &styles,
+            &FixedMeasurer,
+            Viewport {
+                width_px: 100,
+                height_px: 100,
+            },
+            &crate::resources::NoResources,
+        )
+        .unwrap()
+    };
+    let start = std::time::Instant::now();
+    styles.set_animation_time(start);
+    layout(&page("#c80000"));
+    let changed = page("#000064");
+    assert_eq!(rect_of(&layout(&changed), (200, 0, 0)), (0, 0, 100, 10));
+    assert!(styles.is_animating());
+
+    styles.set_animation_time(start + std::time::Duration::from_millis(50));
+    assert_eq!(rect_of(&layout(&changed), (100, 0, 50)), (0, 0, 100, 10));
+
+    styles.set_animation_time(start + std::time::Duration::from_millis(100));
+    assert_eq!(rect_of(&layout(&changed), (0, 0, 100)), (0, 0, 100, 10));
+    assert!(!styles.is_animating());
+}
+
+#[test]
+fn marquee_content_slides_across_its_box_and_blink_text_stays_put() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'><marquee width=100>abcd efgh</marquee>\
+         <marquee width=100 behavior=alternate direction=right>ab</marquee>\
+         <p><blink>on</blink></p></body>",
+    );
+    let styles = crate::style::StyleComputer::empty();
+    let layout = || {
+        layout_document(
+            &doc,
+            &styles,
+            &FixedMeasurer,
+            Viewport {
+                width_px: 200,
+                height_px: 100,
+            },
+            &crate::resources::NoResources,
+        )
+        .unwrap()
+    };
+    let texts = |output: &LayoutOutput| {
+        output
+            .display_list
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DisplayCommand::Text(text) => Some((text.text.clone(), text.x_px, text.y_px)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+    let text = |output: &LayoutOutput, wanted: &str| {
+        texts(output)
+            .into_iter()
+            .find(|(text, _, _)| text == wanted)
+            .map(|(_, x_px, _)| x_px)
+    };
+
+    let still = layout();
+    assert_eq!(text(&still, "abcd"), Some(0));
+    assert_eq!(text(&still, "ab"), Some(0));
+    assert_eq!(text(&still, "on"), Some(0));
+    assert!(!styles.is_animating());
+
+    let start = std::time::Instant::now();
+    styles.set_animation_time(start);
+    let entering = layout();
+    assert_eq!(text(&entering, "abcd"), None);
+    assert_eq!(text(&entering, "ab"), Some(0));
+    assert!(styles.is_animating());
+
+    styles.set_animation_time(start + std::time::Duration::from_millis(850));
+    let moving = layout();
+    assert_eq!(text(&moving, "abcd"), Some(40));
+    assert_eq!(text(&moving, "efgh"), Some(45));
+    assert_eq!(text(&moving, "ab"), Some(60));
+    assert_eq!(text(&moving, "on"), Some(0));
+    assert_eq!(moving.document_width_px, 200);
+}
+
+#[test]
+fn media_elements_keep_their_size_and_show_a_placeholder() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'>\
+         <div><video><source src=clip.mp4>Your browser can't play this</video></div>\
+         <div><video width=100 height=60 controls></video></div>\
+         <div><audio controls src=song.mp3></audio><audio src=song.mp3></audio></div>\
+         </body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 400,
+            height_px: 400,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let rects: Vec<(i32, i32, i32, i32, u8)> = output
+        .display_list
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            DisplayCommand::Rect(rect) if rect.height_px > 2 => Some((
+                rect.x_px,
+                rect.y_px,
+                rect.width_px,
+                rect.height_px,
+                rect.color.a,
+            )),
+            _ => None,
+        })
+        .collect();
+    let backdrop = rect_of(&output, (0x20, 0x21, 0x24));
+    assert_eq!((backdrop.2, backdrop.3), (300, 150));
+    let small = rects
+        .iter()
+        .find(|rect| (rect.2, rect.3) == (100, 60))
+        .unwrap();
+    let strip = rects
+        .iter()
+        .find(|rect| (rect.2, rect.3, rect.4) == (100, 32, 0x99))
+        .unwrap();
+    assert_eq!((strip.0, strip.1), (small.0, small.1 + 28));
+    let audio = rect_of(&output, (0xf1, 0xf3, 0xf4));
+    assert_eq!((audio.2, audio.3), (300, 54));
+    let audio_strips = rects
+        .iter()
+        .filter(|rect| (rect.2, rect.3) == (300, 54))
+        .count();
+    assert_eq!(audio_strips, 1);
+    assert!(!output.display_list.commands.iter().any(
+        |command| matches!(command, DisplayCommand::Text(text) if text.text.contains("browser"))
+    ));
+}
+
+#[test]
+fn open_dialogs_are_centred_over_a_backdrop_outside_the_flow() {
+    let doc = crate::html::parse_document(
+        "<body style='margin: 0'><p>before</p><dialog open>hello</dialog>\
+         <dialog>closed</dialog><p>after</p></body>",
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 200,
+            height_px: 100,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let texts: Vec<(&str, i32, i32)> = output
+        .display_list
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            DisplayCommand::Text(text) => Some((text.text.as_str(), text.x_px, text.y_px)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        vec![("before", 0, 8), ("after", 0, 18), ("hello", 97, 53)]
+    );
+    let backdrop = output
+        .display_list
+        .commands
+        .iter()
+        .find_map(|command| match command {
+            DisplayCommand::Rect(rect) if rect.color.a < 255 => Some(rect),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(
+        (
+            backdrop.x_px,
+            backdrop.y_px,
+            backdrop.width_px,
+            backdrop.height_px
+        ),
+        (0, 0, 200, 100)
+    );
+    assert_eq!(rect_of(&output, (255, 255, 255)), (78, 26, 43, 48));
+}
+
+#[test]
+fn hidden_elements_are_not_laid_out_and_inert_links_cannot_be_hit() {
+    let doc = crate::html::parse_document(
+        r#"<body><div hidden><p>template</p></div><p><a href="/shown">shown</a></p>
+           <div inert><a href="/inert">inert</a></div>
+           <a href="/outer"><span aria-hidden="true">icon</span></a>
+           <p aria-hidden="true"><span><a href="/nested">nested</a></span></p>
+           <nav style="display: flex" inert><a href="/flex">flex</a></nav></body>"#,
+    );
+    let styles = crate::style::StyleComputer::from_document(&doc);
+    let output = layout_document(
+        &doc,
+        &styles,
+        &FixedMeasurer,
+        Viewport {
+            width_px: 200,
+            height_px: 200,
+        },
+        &crate::resources::NoResources,
+    )
+    .unwrap();
+
+    let texts: Vec<&str> = output
+        .display_list
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            DisplayCommand::Text(text) => Some(text.text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(!texts.contains(&"template"));
+    assert!(texts.contains(&"inert") && texts.contains(&"nested"));
+    let hrefs: Vec<&str> = output
+        .link_regions
+        .iter()
+        .map(|region| region.href.as_ref())
+        .collect();
+    assert_eq!(hrefs, vec!["/shown"]);
+}
+
+#[test]
+fn elements_nested_past_the_depth_limit_are_left_out_under_a_banner() {
+    for (open, close) in [
+        ("<div>", "</div>"),
+        ("<span>", "</span>"),
+        ("<table><tr><td>", "</td></tr></table>"),
+        ("<div style='display:flex'>", "</div>"),
+    ] {
+        let html = format!(
+            "<p>shallow</p>{}deep{}",
+            open.repeat(guard::MAX_LAYOUT_DEPTH * 2),
+            close.repeat(guard::MAX_LAYOUT_DEPTH * 2)
+        );
+        let doc = crate::html::parse_document(&html);
+        let styles = crate::style::StyleComputer::from_document(&doc);
+        let output = layout_document(
+            &doc,
+            &styles,
+            &FixedMeasurer,
+            Viewport {
+                width_px: 800,
+                height_px: 200,
+            },
+            &crate::resources::NoResources,
+        )
+        .unwrap();
+
+        let texts: Vec<&str> = output
+            .display_list
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DisplayCommand::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(output.truncated, "{open}");
+        assert!(
+            texts.contains(&"shallow") && !texts.contains(&"deep"),
+            "{open}"
+        );
+        assert!(
+            texts
+                .last()
+                .is_some_and(|text| text.starts_with("Part of this page"))
+        );
+    }
+}
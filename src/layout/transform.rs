@@ -0,0 +1,54 @@
+use crate::geom::Rect;
+use crate::render::{DisplayCommand, Transform};
+use crate::style::{ComputedStyle, TransformFunction};
+
+use super::LayoutEngine;
+
+impl LayoutEngine<'_> {
+    /// Opens a transform group for a box with a `transform`, returning where its matrix goes
+    /// once [`LayoutEngine::close_transform`] knows the box's size.
+    pub(super) fn open_transform(&mut self, style: &ComputedStyle, paint: bool) -> Option<usize> {
+        if !paint || style.transform.is_empty() {
+            return None;
+        }
+        self.list
+            .commands
+            .push(DisplayCommand::PushTransform(Transform::IDENTITY));
+        Some(self.list.commands.len() - 1)
+    }
+
+    /// Closes the group `open_transform` opened, applying the functions about the middle of
+    /// `border_box`.
+    pub(super) fn close_transform(
+        &mut self,
+        group: Option<usize>,
+        style: &ComputedStyle,
+        border_box: Rect,
+    ) {
+        let Some(index) = group else {
+            return;
+        };
+        let matrix = style
+            .transform
+            .iter()
+            .fold(Transform::IDENTITY, |matrix, function| {
+                matrix.then(function_matrix(*function))
+            })
+            .about(
+                f64::from(border_box.x) + f64::from(border_box.width) / 2.0,
+                f64::from(border_box.y) + f64::from(border_box.height) / 2.0,
+            );
+        if let Some(command) = self.list.commands.get_mut(index) {
+            *command = DisplayCommand::PushTransform(matrix);
+        }
+        self.list.commands.push(DisplayCommand::PopTransform);
+    }
+}
+
+fn function_matrix(function: TransformFunction) -> Transform {
+    match function {
+        TransformFunction::Translate(x, y) => Transform::translate(f64::from(x), f64::from(y)),
+        TransformFunction::Rotate(degrees) => Transform::rotate(f64::from(degrees)),
+        TransformFunction::Scale(x, y) => Transform::scale(f64::from(x), f64::from(y)),
+    }
+}
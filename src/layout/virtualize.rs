@@ -0,0 +1,79 @@
+use super::VirtualWindow;
+
+// Block containers with fewer children than this are always laid out in full.
+pub(super) const VIRTUALIZE_MIN_CHILDREN: usize = 256;
+// Children laid out unconditionally to seed the height estimate for skipped siblings.
+const SAMPLE_CHILDREN: usize = 16;
+
+pub(super) struct BlockVirtualizer {
+    window: VirtualWindow,
+    laid_out_count: usize,
+    laid_out_height_px: i64,
+}
+
+impl BlockVirtualizer {
+    pub(super) fn new(window: VirtualWindow) -> BlockVirtualizer {
+        BlockVirtualizer {
+            window,
+            laid_out_count: 0,
+            laid_out_height_px: 0,
+        }
+    }
+
+    pub(super) fn record(&mut self, height_px: i32) {
+        self.laid_out_count = self.laid_out_count.saturating_add(1);
+        self.laid_out_height_px = self
+            .laid_out_height_px
+            .saturating_add(i64::from(height_px.max(0)));
+    }
+
+    /// Returns the estimated height to advance by when the next block child starting at
+    /// `top_px` can be skipped, or `None` when it has to be laid out.
+    pub(super) fn skip_estimate(&self, top_px: i32) -> Option<i32> {
+        if self.laid_out_count < SAMPLE_CHILDREN {
+            return None;
+        }
+        let count = i64::try_from(self.laid_out_count).unwrap_or(i64::MAX);
+        let estimate = i32::try_from(self.laid_out_height_px / count).unwrap_or(i32::MAX);
+        let bottom_px = top_px.saturating_add(estimate);
+        let outside = top_px >= self.window.bottom_px || bottom_px <= self.window.top_px;
+        outside.then_some(estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded(window: VirtualWindow, height_px: i32) -> BlockVirtualizer {
+        let mut virtualizer = BlockVirtualizer::new(window);
+        for _ in 0..SAMPLE_CHILDREN {
+            virtualizer.record(height_px);
+        }
+        virtualizer
+    }
+
+    #[test]
+    fn lays_out_everything_until_seeded() {
+        let window = VirtualWindow {
+            top_px: 1000,
+            bottom_px: 2000,
+        };
+        let virtualizer = BlockVirtualizer::new(window);
+        assert_eq!(virtualizer.skip_estimate(0), None);
+        assert_eq!(virtualizer.skip_estimate(5000), None);
+    }
+
+    #[test]
+    fn skips_blocks_entirely_outside_the_window() {
+        let window = VirtualWindow {
+            top_px: 1000,
+            bottom_px: 2000,
+        };
+        let virtualizer = seeded(window, 20);
+        assert_eq!(virtualizer.skip_estimate(0), Some(20));
+        assert_eq!(virtualizer.skip_estimate(985), None);
+        assert_eq!(virtualizer.skip_estimate(1500), None);
+        assert_eq!(virtualizer.skip_estimate(2000), Some(20));
+    }
+}
@@ -1,23 +1,34 @@
+pub mod agent;
 pub mod app;
+pub mod batch;
 pub mod browser;
+pub mod budget;
 pub mod cli;
+pub mod control;
 pub mod css;
 pub mod css_media;
 pub mod css_supports;
 pub mod debug;
 pub mod dom;
+pub mod extract;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
 pub mod geom;
 pub mod html;
 pub mod image;
 pub mod js;
 pub mod layout;
 pub mod net;
+pub mod pdf;
 pub mod platform;
 pub mod png;
+pub mod reader;
+pub mod reftest;
 pub mod render;
 pub mod resources;
 pub mod style;
 pub mod url;
+pub mod watch;
 
 #[cfg(target_os = "windows")]
 mod win;
@@ -1,4 +1,8 @@
-use one_agent_one_browser::{browser, cli, platform};
+use one_agent_one_browser::{
+    agent, batch, browser, cli, control, css_media, extract, net, pdf, platform, resources,
+};
+use std::path::Path;
+use std::sync::Arc;
 
 fn main() {
     let args = match cli::parse_args(std::env::args_os().skip(1)) {
@@ -9,9 +13,70 @@ fn main() {
         }
     };
 
-    let app = match args.target {
-        Some(cli::Target::File(path)) => browser::BrowserApp::from_file(&path),
-        Some(cli::Target::Url(url)) => browser::BrowserApp::from_url(&url),
+    if let Some(scheme) = args.color_scheme {
+        css_media::set_color_scheme(scheme);
+    }
+    if args.dark {
+        css_media::set_color_scheme(css_media::ColorScheme::Dark);
+        css_media::set_force_dark(true);
+    }
+    if args.no_noscript {
+        css_media::set_render_noscript(false);
+    }
+    if let Some(policy) = args.mixed_content {
+        net::set_mixed_content_policy(policy);
+    }
+    if args.user_agent.is_some() || !args.headers.is_empty() {
+        net::set_request_headers(net::RequestHeaders {
+            user_agent: args.user_agent.clone(),
+            extra: args.headers.clone(),
+        });
+    }
+    if args.proxy.is_some() || args.no_proxy.is_some() {
+        let mut proxies = net::ProxySettings::from_env();
+        if let Some(proxy) = &args.proxy {
+            proxies.http = Some(proxy.clone());
+            proxies.https = Some(proxy.clone());
+        }
+        if let Some(no_proxy) = &args.no_proxy {
+            proxies.no_proxy = no_proxy.clone();
+        }
+        net::set_proxy_settings(proxies);
+    }
+    if args.connect_timeout.is_some() || args.read_timeout.is_some() || args.retries.is_some() {
+        let defaults = net::FetchLimits::new();
+        net::set_fetch_limits(net::FetchLimits {
+            connect_timeout: args.connect_timeout.unwrap_or(defaults.connect_timeout),
+            read_timeout: args.read_timeout.unwrap_or(defaults.read_timeout),
+            retries: args.retries.unwrap_or(defaults.retries),
+        });
+    }
+    if !args.allow_origins.is_empty() {
+        resources::set_resource_policy(resources::ResourcePolicy {
+            allowed_origins: args.allow_origins.clone(),
+        });
+    }
+    if let Some(path) = &args.block_list {
+        match std::fs::read_to_string(path) {
+            Ok(text) => net::set_request_filter(Arc::new(net::BlockList::parse(&text))),
+            Err(err) => {
+                eprintln!("Failed to read block list {}: {err}", path.display());
+                std::process::exit(2);
+            }
+        }
+    }
+
+    if let (Some(list), Some(out_dir)) = (&args.batch, &args.out_dir) {
+        std::process::exit(run_batch(&args, list, out_dir));
+    }
+
+    if let Some(control::Channel::Stdio) = args.control {
+        std::process::exit(run_control(&args));
+    }
+
+    let app = match &args.target {
+        Some(cli::Target::File(path)) => browser::BrowserApp::from_file(path),
+        Some(cli::Target::Url(url)) => browser::BrowserApp::from_url(url),
         None => browser::BrowserApp::from_html("Hello World", "<p>Hello World</p>"),
     };
 
@@ -23,15 +88,193 @@ fn main() {
         }
     };
 
+    app.set_layout_virtualization(args.virtualizes_layout());
+    app.set_watch_files(args.watch);
+    app.set_timing_report(args.timing);
+    app.set_reader_mode(args.reader);
+    let page_setup = pdf::PageSetup::new(args.paper.unwrap_or_default());
+    let backend = if args.replay.is_some() {
+        platform::Backend::Software
+    } else {
+        args.backend.unwrap_or_default()
+    };
+    let headless = args.headless || backend == platform::Backend::Software;
+    let runs_to_load = args.dump_outline
+        || args.dump_display_list
+        || args.dump_dom
+        || args.dump_styles
+        || args.dump_text
+        || args.budget.is_some()
+        || args.pdf.is_some();
+    // A replay shows the location strip the recorded window had, so inputs land where they did.
+    app.set_chrome_visible(
+        args.replay.is_some() || (!headless && !runs_to_load && args.screenshot.is_none()),
+    );
+
+    let (initial_width_px, initial_height_px) = if args.pdf.is_some() {
+        let content = page_setup.content_viewport();
+        (Some(content.width_px), Some(content.height_px))
+    } else {
+        (args.width_px, args.height_px)
+    };
+
     let title = app.title().to_owned();
     let options = platform::WindowOptions {
-        screenshot_path: args.screenshot_path,
-        headless: args.headless,
-        initial_width_px: args.width_px,
-        initial_height_px: args.height_px,
+        backend,
+        screenshot: args.screenshot,
+        full_page_screenshot: args.full_page_screenshot,
+        // The outline, display list, DOM, style and text dumps, budget report and PDF are written
+        // once the page has loaded, so there is no window to keep open.
+        headless: headless || runs_to_load,
+        initial_width_px,
+        initial_height_px,
+        record: args.record,
+        replay: args.replay,
     };
     if let Err(err) = platform::run_window(&title, options, &mut app) {
         eprintln!("{err}");
         std::process::exit(1);
     }
+
+    if let Some(path) = &args.pdf
+        && let Err(err) = app.write_pdf(path, page_setup)
+    {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+
+    if args.dump_outline {
+        for heading in app.outline() {
+            let indent = "  ".repeat(usize::from(heading.level.saturating_sub(1)));
+            let id = heading
+                .id
+                .as_deref()
+                .map(|id| format!(" #{id}"))
+                .unwrap_or_default();
+            println!(
+                "{indent}h{} y={}{id} {}",
+                heading.level, heading.y_px, heading.text
+            );
+        }
+    }
+
+    if args.dump_display_list
+        && let Some(display_list) = app.display_list()
+    {
+        print!("{}", display_list.to_text());
+    }
+
+    if args.dump_dom {
+        print!("{}", app.document().to_text());
+    }
+
+    if args.dump_styles {
+        print!("{}", app.computed_styles_text());
+    }
+
+    if args.dump_text {
+        print!("{}", extract::blocks_to_text(&app.text_blocks()));
+    }
+
+    if let Some(budget) = args.budget {
+        let report = budget.check(&app.page_metrics());
+        println!("{}", report.to_json());
+        if report.exceeded() {
+            std::process::exit(3);
+        }
+    }
+}
+
+/// Screenshots every URL of `list` into `out_dir` from this one process, so startup is paid
+/// once for the whole list. Returns the exit status.
+fn run_batch(args: &cli::Args, list: &Path, out_dir: &Path) -> i32 {
+    let urls = match std::fs::read_to_string(list) {
+        Ok(text) => batch::parse_url_list(&text),
+        Err(err) => {
+            eprintln!("Failed to read URL list {}: {err}", list.display());
+            return 2;
+        }
+    };
+    if let Err(err) = std::fs::create_dir_all(out_dir) {
+        eprintln!("Failed to create {}: {err}", out_dir.display());
+        return 1;
+    }
+
+    let mut failed = false;
+    for (index, url) in urls.iter().enumerate() {
+        let stem = batch::output_stem(index, url);
+        if let Err(err) = screenshot_url(args, url, out_dir, &stem) {
+            eprintln!("{url}: {err}");
+            failed = true;
+        }
+    }
+    i32::from(failed)
+}
+
+fn screenshot_url(args: &cli::Args, url: &str, out_dir: &Path, stem: &str) -> Result<(), String> {
+    let mut app = browser::BrowserApp::from_url(url)?;
+    app.set_layout_virtualization(args.virtualizes_layout());
+    app.set_timing_report(args.timing);
+    app.set_reader_mode(args.reader);
+    app.set_chrome_visible(false);
+
+    let title = app.title().to_owned();
+    let options = platform::WindowOptions {
+        backend: args.backend.unwrap_or_default(),
+        screenshot: Some(platform::ScreenshotTarget::File(
+            out_dir.join(format!("{stem}.png")),
+        )),
+        full_page_screenshot: args.full_page_screenshot,
+        headless: true,
+        initial_width_px: args.width_px,
+        initial_height_px: args.height_px,
+        record: None,
+        replay: None,
+    };
+    platform::run_window(&title, options, &mut app)?;
+
+    if args.dump_display_list
+        && let Some(display_list) = app.display_list()
+    {
+        let path = out_dir.join(format!("{stem}.txt"));
+        std::fs::write(&path, display_list.to_text())
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Serves `--control stdio`: JSON-RPC requests on stdin, responses on stdout. Returns the exit
+/// status.
+fn run_control(args: &cli::Args) -> i32 {
+    let mut agent =
+        match agent::Agent::new(args.width_px.unwrap_or(1024), args.height_px.unwrap_or(768)) {
+            Ok(agent) => agent,
+            Err(err) => {
+                eprintln!("{err}");
+                return 1;
+            }
+        };
+    agent.set_reader_mode(args.reader);
+    let location = match &args.target {
+        Some(cli::Target::File(path)) => Some(path.to_string_lossy().into_owned()),
+        Some(cli::Target::Url(url)) => Some(url.clone()),
+        None => None,
+    };
+    if let Some(location) = location
+        && let Err(err) = agent.navigate(&location)
+    {
+        eprintln!("{err}");
+        return 1;
+    }
+    match control::serve(
+        &mut agent,
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+    ) {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{err}");
+            1
+        }
+    }
 }
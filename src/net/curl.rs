@@ -1,19 +1,64 @@
+use super::TlsInfo;
+use super::limits::{connection_error, status_error};
+use super::resume::{RangeRequest, Transfer};
+use crate::url::Url;
 use std::ffi::{CStr, CString};
 use std::os::raw::{c_char, c_int, c_long};
 use std::sync::OnceLock;
+use std::time::Duration;
 
 type CURLcode = c_int;
 type CURLoption = c_int;
 type CURLINFO = c_int;
+type CURLMcode = c_int;
+type CURLMoption = c_int;
 
 type WriteFn = extern "C" fn(*mut c_char, usize, usize, *mut std::ffi::c_void) -> usize;
+type DebugFn = extern "C" fn(*mut CURL, c_int, *mut c_char, usize, *mut std::ffi::c_void) -> c_int;
 
 #[repr(C)]
 struct CURL {
     _private: [u8; 0],
 }
 
+#[repr(C)]
+struct CurlMulti {
+    _private: [u8; 0],
+}
+
+#[repr(C)]
+struct CurlMsg {
+    msg: c_int,
+    easy_handle: *mut CURL,
+    data: CurlMsgData,
+}
+
+#[repr(C)]
+union CurlMsgData {
+    _whatever: *mut std::ffi::c_void,
+    result: CURLcode,
+}
+
+#[repr(C)]
+struct CurlSlist {
+    data: *mut c_char,
+    next: *mut CurlSlist,
+}
+
+#[repr(C)]
+struct CurlCertinfo {
+    num_of_certs: c_int,
+    certinfo: *mut *mut CurlSlist,
+}
+
 const CURLE_OK: CURLcode = 0;
+const CURLE_COULDNT_CONNECT: CURLcode = 7;
+const CURLE_PARTIAL_FILE: CURLcode = 18;
+const CURLE_HTTP_RETURNED_ERROR: CURLcode = 22;
+const CURLE_OPERATION_TIMEDOUT: CURLcode = 28;
+const CURLE_GOT_NOTHING: CURLcode = 52;
+const CURLE_SEND_ERROR: CURLcode = 55;
+const CURLE_RECV_ERROR: CURLcode = 56;
 
 const CURL_GLOBAL_DEFAULT: c_long = 3;
 
@@ -27,10 +72,36 @@ const CURLOPT_ACCEPT_ENCODING: CURLoption = 10102;
 const CURLOPT_TIMEOUT_MS: CURLoption = 155;
 const CURLOPT_CONNECTTIMEOUT_MS: CURLoption = 156;
 const CURLOPT_NOSIGNAL: CURLoption = 99;
+const CURLOPT_RANGE: CURLoption = 10007;
+const CURLOPT_HTTPHEADER: CURLoption = 10023;
+const CURLOPT_HEADERDATA: CURLoption = 10029;
+const CURLOPT_HEADERFUNCTION: CURLoption = 20079;
+const CURLOPT_VERBOSE: CURLoption = 41;
+const CURLOPT_CERTINFO: CURLoption = 172;
+const CURLOPT_DEBUGFUNCTION: CURLoption = 20094;
+const CURLOPT_DEBUGDATA: CURLoption = 10095;
+const CURLOPT_HTTP_VERSION: CURLoption = 84;
+const CURLOPT_PIPEWAIT: CURLoption = 237;
+const CURLOPT_PROXY: CURLoption = 10004;
+const CURLOPT_NOPROXY: CURLoption = 10177;
+
+const CURL_HTTP_VERSION_2TLS: c_long = 4;
+
+const CURLM_OK: CURLMcode = 0;
+const CURLMSG_DONE: c_int = 1;
+const CURLMOPT_PIPELINING: CURLMoption = 3;
+const CURLMOPT_MAX_HOST_CONNECTIONS: CURLMoption = 7;
+const CURLMOPT_MAX_TOTAL_CONNECTIONS: CURLMoption = 13;
+const CURLPIPE_MULTIPLEX: c_long = 2;
+/// Connections per origin, for origins that only speak HTTP/1.1.
+const MAX_HOST_CONNECTIONS: c_long = 6;
 
 const CURLINFO_RESPONSE_CODE: CURLINFO = 0x200002;
+const CURLINFO_STARTTRANSFER_TIME: CURLINFO = 0x300011;
+const CURLINFO_CERTINFO: CURLINFO = 0x400022;
 
-const MAX_DOWNLOAD_BYTES: usize = 10 * 1024 * 1024;
+/// The `curl_infotype` of informational text handed to the debug callback.
+const CURLINFO_TEXT: c_int = 0;
 
 #[link(name = "curl")]
 unsafe extern "C" {
@@ -41,6 +112,23 @@ unsafe extern "C" {
     fn curl_easy_setopt(handle: *mut CURL, option: CURLoption, ...) -> CURLcode;
     fn curl_easy_getinfo(handle: *mut CURL, info: CURLINFO, ...) -> CURLcode;
     fn curl_easy_strerror(code: CURLcode) -> *const c_char;
+    fn curl_slist_append(list: *mut CurlSlist, value: *const c_char) -> *mut CurlSlist;
+    fn curl_slist_free_all(list: *mut CurlSlist);
+    fn curl_multi_init() -> *mut CurlMulti;
+    fn curl_multi_cleanup(multi: *mut CurlMulti) -> CURLMcode;
+    fn curl_multi_setopt(multi: *mut CurlMulti, option: CURLMoption, ...) -> CURLMcode;
+    fn curl_multi_add_handle(multi: *mut CurlMulti, handle: *mut CURL) -> CURLMcode;
+    fn curl_multi_remove_handle(multi: *mut CurlMulti, handle: *mut CURL) -> CURLMcode;
+    fn curl_multi_perform(multi: *mut CurlMulti, running_handles: *mut c_int) -> CURLMcode;
+    fn curl_multi_poll(
+        multi: *mut CurlMulti,
+        extra_fds: *mut std::ffi::c_void,
+        extra_nfds: u32,
+        timeout_ms: c_int,
+        numfds: *mut c_int,
+    ) -> CURLMcode;
+    fn curl_multi_info_read(multi: *mut CurlMulti, msgs_in_queue: *mut c_int) -> *mut CurlMsg;
+    fn curl_multi_strerror(code: CURLMcode) -> *const c_char;
 }
 
 fn ensure_global_init() -> Result<(), String> {
@@ -56,70 +144,491 @@ fn ensure_global_init() -> Result<(), String> {
     .clone()
 }
 
-pub(super) fn fetch_url_bytes(url: &str) -> Result<Vec<u8>, String> {
-    ensure_global_init()?;
+pub(super) fn fetch_url(url: &str, range: Option<&RangeRequest>) -> Result<Transfer, String> {
+    let easy = Easy::new(url, range)?;
+    let code = unsafe { curl_easy_perform(easy.handle) };
+    easy.finish(code)
+}
+
+/// Runs many transfers from one thread. Requests to an origin that speaks HTTP/2 share one
+/// connection as separate streams; connections are kept for later requests either way.
+pub(super) struct Multi {
+    handle: *mut CurlMulti,
+    /// Transfers in progress, by the token `add` was given.
+    running: Vec<(u64, Easy)>,
+}
 
-    let c_url = CString::new(url).map_err(|_| "URL contains an unexpected NUL byte".to_owned())?;
+// A multi handle and its easy handles may move between threads as long as only one thread
+// uses them at a time, which owning them through `Multi` ensures.
+unsafe impl Send for Multi {}
 
-    let handle = unsafe { curl_easy_init() };
-    if handle.is_null() {
-        return Err("curl_easy_init failed".to_owned());
+impl Multi {
+    /// `max_connections` caps the connections open at once, across all origins.
+    pub(super) fn new(max_connections: usize) -> Result<Multi, String> {
+        ensure_global_init()?;
+        let handle = unsafe { curl_multi_init() };
+        if handle.is_null() {
+            return Err("curl_multi_init failed".to_owned());
+        }
+        let multi = Multi {
+            handle,
+            running: Vec::new(),
+        };
+        multi.setopt(CURLMOPT_PIPELINING, CURLPIPE_MULTIPLEX)?;
+        multi.setopt(CURLMOPT_MAX_HOST_CONNECTIONS, MAX_HOST_CONNECTIONS)?;
+        multi.setopt(
+            CURLMOPT_MAX_TOTAL_CONNECTIONS,
+            c_long::try_from(max_connections).unwrap_or(c_long::MAX),
+        )?;
+        Ok(multi)
     }
 
-    let mut buffer: Vec<u8> = Vec::new();
-    let mut ctx = WriteContext {
-        buffer: &mut buffer,
-        max_bytes: MAX_DOWNLOAD_BYTES,
-    };
+    pub(super) fn add(&mut self, token: u64, url: &str) -> Result<(), String> {
+        let easy = Easy::new(url, None)?;
+        // Wait for a connection being set up to the same origin to turn out to be HTTP/2
+        // rather than opening another one.
+        setopt_long(easy.handle, CURLOPT_PIPEWAIT, 1)?;
+        let code = unsafe { curl_multi_add_handle(self.handle, easy.handle) };
+        if code != CURLM_OK {
+            return Err(format!(
+                "curl_multi_add_handle failed: {}",
+                curl_multi_error(code)
+            ));
+        }
+        self.running.push((token, easy));
+        Ok(())
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
 
-    let user_agent = CString::new("one-agent-one-browser/0.1")
-        .map_err(|_| "User-Agent contains an unexpected NUL byte".to_owned())?;
-    let accept_encoding = CString::new("")
-        .map_err(|_| "Accept-Encoding contains an unexpected NUL byte".to_owned())?;
+    /// Abandons the transfer `add` was given `token` for.
+    pub(super) fn remove(&mut self, token: u64) {
+        let Some(index) = self
+            .running
+            .iter()
+            .position(|(running, _)| *running == token)
+        else {
+            return;
+        };
+        let (_, easy) = self.running.swap_remove(index);
+        unsafe { curl_multi_remove_handle(self.handle, easy.handle) };
+    }
 
-    let _cleanup = CurlHandle(handle);
-    setopt_ptr(handle, CURLOPT_URL, c_url.as_ptr())?;
-    setopt_long(handle, CURLOPT_FOLLOWLOCATION, 1)?;
-    setopt_long(handle, CURLOPT_FAILONERROR, 1)?;
-    setopt_long(handle, CURLOPT_TIMEOUT_MS, 15_000)?;
-    setopt_long(handle, CURLOPT_CONNECTTIMEOUT_MS, 5_000)?;
-    setopt_long(handle, CURLOPT_NOSIGNAL, 1)?;
-    setopt_ptr(handle, CURLOPT_USERAGENT, user_agent.as_ptr())?;
-    setopt_ptr(handle, CURLOPT_ACCEPT_ENCODING, accept_encoding.as_ptr())?;
+    /// Moves every transfer along, waiting up to `timeout` for the network when none finished,
+    /// and returns the ones that are done.
+    pub(super) fn perform(&mut self, timeout: Duration) -> Vec<(u64, Result<Transfer, String>)> {
+        let mut still_running: c_int = 0;
+        let code = unsafe { curl_multi_perform(self.handle, &mut still_running) };
+        if code != CURLM_OK {
+            let err = format!("curl_multi_perform failed: {}", curl_multi_error(code));
+            return std::mem::take(&mut self.running)
+                .into_iter()
+                .map(|(token, easy)| {
+                    unsafe { curl_multi_remove_handle(self.handle, easy.handle) };
+                    (token, Err(err.clone()))
+                })
+                .collect();
+        }
 
-    setopt_ptr(
-        handle,
-        CURLOPT_WRITEDATA,
-        (&mut ctx as *mut WriteContext).cast::<std::ffi::c_void>(),
-    )?;
-    setopt_write_fn(handle, CURLOPT_WRITEFUNCTION, write_callback)?;
+        let mut finished = Vec::new();
+        loop {
+            let mut queued: c_int = 0;
+            let message = unsafe { curl_multi_info_read(self.handle, &mut queued) };
+            if message.is_null() {
+                break;
+            }
+            // The message is only valid until the next call into the multi handle.
+            let (kind, handle, code) = unsafe {
+                let message = &*message;
+                (message.msg, message.easy_handle, message.data.result)
+            };
+            if kind != CURLMSG_DONE {
+                continue;
+            }
+            let Some(index) = self
+                .running
+                .iter()
+                .position(|(_, easy)| easy.handle == handle)
+            else {
+                continue;
+            };
+            let (token, easy) = self.running.swap_remove(index);
+            unsafe { curl_multi_remove_handle(self.handle, easy.handle) };
+            finished.push((token, easy.finish(code)));
+        }
 
-    let code = unsafe { curl_easy_perform(handle) };
-    if code != CURLE_OK {
-        return Err(format!("Failed to fetch {url}: {}", curl_error(code)));
+        if finished.is_empty() && !self.running.is_empty() {
+            let timeout_ms = c_int::try_from(timeout.as_millis()).unwrap_or(c_int::MAX);
+            unsafe {
+                curl_multi_poll(
+                    self.handle,
+                    std::ptr::null_mut(),
+                    0,
+                    timeout_ms,
+                    std::ptr::null_mut(),
+                );
+            }
+        }
+        finished
+    }
+
+    fn setopt(&self, option: CURLMoption, value: c_long) -> Result<(), String> {
+        let code = unsafe { curl_multi_setopt(self.handle, option, value) };
+        if code == CURLM_OK {
+            Ok(())
+        } else {
+            Err(format!(
+                "curl_multi_setopt failed: {}",
+                curl_multi_error(code)
+            ))
+        }
     }
+}
 
-    let response_code = getinfo_long(handle, CURLINFO_RESPONSE_CODE)?;
-    if !(200..=399).contains(&response_code) {
-        return Err(format!(
-            "Unexpected HTTP status {response_code} fetching {url}"
-        ));
+impl Drop for Multi {
+    fn drop(&mut self) {
+        for (_, easy) in self.running.drain(..) {
+            unsafe { curl_multi_remove_handle(self.handle, easy.handle) };
+        }
+        unsafe { curl_multi_cleanup(self.handle) };
     }
+}
 
-    Ok(buffer)
+/// An easy handle set up to GET one URL, with the state its callbacks write into. The state
+/// is boxed because curl holds pointers to it while the `Easy` itself moves around.
+struct Easy {
+    handle: *mut CURL,
+    url: String,
+    state: Box<EasyState>,
+    /// Sent with the request; curl reads the list rather than copying it.
+    request_headers: CurlHeaderList,
 }
 
-struct CurlHandle(*mut CURL);
+struct EasyState {
+    body: WriteContext,
+    headers: HeaderContext,
+    tls_protocol: Option<String>,
+}
+
+impl Easy {
+    fn new(url: &str, range: Option<&RangeRequest>) -> Result<Easy, String> {
+        ensure_global_init()?;
+
+        let c_url =
+            CString::new(url).map_err(|_| "URL contains an unexpected NUL byte".to_owned())?;
+
+        let handle = unsafe { curl_easy_init() };
+        if handle.is_null() {
+            return Err("curl_easy_init failed".to_owned());
+        }
+
+        let already_read = range.map_or(0, |range| range.start);
+        let mut easy = Easy {
+            handle,
+            url: url.to_owned(),
+            state: Box::new(EasyState {
+                body: WriteContext {
+                    buffer: Vec::new(),
+                    max_bytes: super::resource_limits()
+                        .max_response_bytes
+                        .saturating_sub(usize::try_from(already_read).unwrap_or(usize::MAX)),
+                    exceeded: false,
+                },
+                headers: HeaderContext::default(),
+                tls_protocol: None,
+            }),
+            request_headers: CurlHeaderList(std::ptr::null_mut()),
+        };
+
+        let request_headers = super::request_headers();
+        let user_agent = CString::new(request_headers.user_agent())
+            .map_err(|_| "User-Agent contains an unexpected NUL byte".to_owned())?;
+        let accept_encoding = CString::new("")
+            .map_err(|_| "Accept-Encoding contains an unexpected NUL byte".to_owned())?;
+
+        setopt_ptr(handle, CURLOPT_URL, c_url.as_ptr())?;
+        setopt_long(handle, CURLOPT_FOLLOWLOCATION, 1)?;
+        setopt_long(handle, CURLOPT_FAILONERROR, 1)?;
+        let limits = super::fetch_limits();
+        setopt_long(
+            handle,
+            CURLOPT_TIMEOUT_MS,
+            millis(limits.connect_timeout + limits.read_timeout),
+        )?;
+        setopt_long(
+            handle,
+            CURLOPT_CONNECTTIMEOUT_MS,
+            millis(limits.connect_timeout),
+        )?;
+        setopt_long(handle, CURLOPT_NOSIGNAL, 1)?;
+        setopt_ptr(handle, CURLOPT_USERAGENT, user_agent.as_ptr())?;
+        setopt_ptr(handle, CURLOPT_ACCEPT_ENCODING, accept_encoding.as_ptr())?;
+        // A curl built without HTTP/2 refuses this and stays on HTTP/1.1.
+        let _ = setopt_long(handle, CURLOPT_HTTP_VERSION, CURL_HTTP_VERSION_2TLS);
+
+        for line in request_headers.lines() {
+            let line = CString::new(line)
+                .map_err(|_| "Request header contains an unexpected NUL byte".to_owned())?;
+            easy.request_headers.append(&line)?;
+        }
+        if let Some(range) = range {
+            let if_range = CString::new(format!("If-Range: {}", range.if_range))
+                .map_err(|_| "If-Range contains an unexpected NUL byte".to_owned())?;
+            easy.request_headers.append(&if_range)?;
+            let range_c = CString::new(format!("{}-", range.start))
+                .map_err(|_| "Range contains an unexpected NUL byte".to_owned())?;
+            setopt_ptr(handle, CURLOPT_RANGE, range_c.as_ptr())?;
+        }
+        if !easy.request_headers.0.is_null() {
+            setopt_ptr(handle, CURLOPT_HTTPHEADER, easy.request_headers.0)?;
+        }
+
+        // Set even when empty, so curl connects directly rather than reading the environment
+        // itself.
+        let proxies = super::proxy_settings();
+        let proxy = Url::parse(url)
+            .ok()
+            .and_then(|url| proxies.proxy_for(&url).map(str::to_owned))
+            .unwrap_or_default();
+        let proxy =
+            CString::new(proxy).map_err(|_| "Proxy contains an unexpected NUL byte".to_owned())?;
+        let no_proxy = CString::new(proxies.no_proxy.join(","))
+            .map_err(|_| "No-proxy list contains an unexpected NUL byte".to_owned())?;
+        setopt_ptr(handle, CURLOPT_PROXY, proxy.as_ptr())?;
+        setopt_ptr(handle, CURLOPT_NOPROXY, no_proxy.as_ptr())?;
 
-impl Drop for CurlHandle {
+        let state = &mut *easy.state;
+        setopt_ptr(
+            handle,
+            CURLOPT_WRITEDATA,
+            (&mut state.body as *mut WriteContext).cast::<std::ffi::c_void>(),
+        )?;
+        setopt_write_fn(handle, CURLOPT_WRITEFUNCTION, write_callback)?;
+        setopt_ptr(
+            handle,
+            CURLOPT_HEADERDATA,
+            (&mut state.headers as *mut HeaderContext).cast::<std::ffi::c_void>(),
+        )?;
+        setopt_write_fn(handle, CURLOPT_HEADERFUNCTION, header_callback)?;
+        // curl names the negotiated TLS version only in its verbose text, which the debug
+        // callback reads instead of it going to stderr.
+        setopt_long(handle, CURLOPT_CERTINFO, 1)?;
+        setopt_long(handle, CURLOPT_VERBOSE, 1)?;
+        setopt_ptr(
+            handle,
+            CURLOPT_DEBUGDATA,
+            (&mut state.tls_protocol as *mut Option<String>).cast::<std::ffi::c_void>(),
+        )?;
+        setopt_debug_fn(handle, CURLOPT_DEBUGFUNCTION, debug_callback)?;
+        Ok(easy)
+    }
+
+    /// The transfer as it stood when curl finished it with `code`.
+    fn finish(mut self, code: CURLcode) -> Result<Transfer, String> {
+        let url = &self.url;
+        if self.state.body.exceeded {
+            return Err(format!(
+                "Response exceeds maximum size ({} bytes) fetching {url}",
+                super::resource_limits().max_response_bytes
+            ));
+        }
+        let response_code = getinfo_long(self.handle, CURLINFO_RESPONSE_CODE)?;
+        let interrupted = if code == CURLE_OK {
+            None
+        } else if matches!(
+            code,
+            CURLE_PARTIAL_FILE | CURLE_OPERATION_TIMEDOUT | CURLE_RECV_ERROR
+        ) && matches!(response_code, 200 | 206)
+        {
+            Some(format!("Failed to fetch {url}: {}", curl_error(code)))
+        } else if code == CURLE_HTTP_RETURNED_ERROR {
+            return Err(status_error(response_code as u32, url));
+        } else if matches!(
+            code,
+            CURLE_COULDNT_CONNECT | CURLE_GOT_NOTHING | CURLE_SEND_ERROR | CURLE_RECV_ERROR
+        ) {
+            return Err(connection_error(format_args!(
+                "{url}: {}",
+                curl_error(code)
+            )));
+        } else {
+            return Err(format!("Failed to fetch {url}: {}", curl_error(code)));
+        };
+
+        if !(200..=399).contains(&response_code) {
+            return Err(status_error(response_code as u32, url));
+        }
+
+        let time_to_first_byte = getinfo_double(self.handle, CURLINFO_STARTTRANSFER_TIME)
+            .ok()
+            .filter(|secs| secs.is_finite() && *secs >= 0.0)
+            .map(Duration::from_secs_f64);
+        let tls = tls_info(self.handle, self.state.tls_protocol.take());
+        let headers = std::mem::take(&mut self.state.headers);
+
+        Ok(Transfer {
+            status: response_code as u32,
+            body: std::mem::take(&mut self.state.body.buffer),
+            time_to_first_byte,
+            etag: headers.etag,
+            last_modified: headers.last_modified,
+            content_range: headers.content_range,
+            encoded: headers.encoded,
+            interrupted,
+            tls,
+        })
+    }
+}
+
+impl Drop for Easy {
     fn drop(&mut self) {
-        unsafe { curl_easy_cleanup(self.0) };
+        unsafe { curl_easy_cleanup(self.handle) };
     }
 }
 
-struct WriteContext<'a> {
-    buffer: &'a mut Vec<u8>,
+struct CurlHeaderList(*mut CurlSlist);
+
+impl CurlHeaderList {
+    fn append(&mut self, header: &CStr) -> Result<(), String> {
+        let list = unsafe { curl_slist_append(self.0, header.as_ptr()) };
+        if list.is_null() {
+            return Err("curl_slist_append failed".to_owned());
+        }
+        self.0 = list;
+        Ok(())
+    }
+}
+
+impl Drop for CurlHeaderList {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { curl_slist_free_all(self.0) };
+        }
+    }
+}
+
+/// Response headers needed to resume a transfer. curl reports the headers of every response
+/// in a redirect chain, so a new status line starts over.
+#[derive(Default)]
+struct HeaderContext {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_range: Option<String>,
+    encoded: bool,
+}
+
+extern "C" fn header_callback(
+    ptr: *mut c_char,
+    size: usize,
+    nmemb: usize,
+    userdata: *mut std::ffi::c_void,
+) -> usize {
+    let Some(total) = size.checked_mul(nmemb) else {
+        return 0;
+    };
+    let ctx = unsafe { &mut *(userdata.cast::<HeaderContext>()) };
+    let bytes = unsafe { std::slice::from_raw_parts(ptr.cast::<u8>(), total) };
+    let line = String::from_utf8_lossy(bytes);
+
+    if line.starts_with("HTTP/") {
+        *ctx = HeaderContext::default();
+        return total;
+    }
+    let Some((name, value)) = line.split_once(':') else {
+        return total;
+    };
+    let value = value.trim().to_owned();
+    let name = name.trim();
+    if name.eq_ignore_ascii_case("etag") {
+        ctx.etag = Some(value);
+    } else if name.eq_ignore_ascii_case("last-modified") {
+        ctx.last_modified = Some(value);
+    } else if name.eq_ignore_ascii_case("content-range") {
+        ctx.content_range = Some(value);
+    } else if name.eq_ignore_ascii_case("content-encoding") {
+        ctx.encoded = !value.eq_ignore_ascii_case("identity");
+    }
+    total
+}
+
+extern "C" fn debug_callback(
+    _handle: *mut CURL,
+    kind: c_int,
+    data: *mut c_char,
+    size: usize,
+    userdata: *mut std::ffi::c_void,
+) -> c_int {
+    if kind != CURLINFO_TEXT || data.is_null() {
+        return 0;
+    }
+    let protocol = unsafe { &mut *(userdata.cast::<Option<String>>()) };
+    let bytes = unsafe { std::slice::from_raw_parts(data.cast::<u8>(), size) };
+    if let Some(version) = tls_protocol_from_text(&String::from_utf8_lossy(bytes)) {
+        *protocol = Some(version.to_owned());
+    }
+    0
+}
+
+/// The TLS version in curl's `SSL connection using TLSv1.3 / <cipher> / ...` line.
+fn tls_protocol_from_text(text: &str) -> Option<&str> {
+    let rest = text.trim().strip_prefix("SSL connection using ")?;
+    let version = rest.split(" / ").next()?.trim();
+    (!version.is_empty()).then_some(version)
+}
+
+/// What curl reported about the TLS connection of the last response, or `None` when there was
+/// none. `CURLINFO_CERTINFO` lists the chain as `Name:value` lines, the server's own
+/// certificate first.
+fn tls_info(handle: *mut CURL, protocol: Option<String>) -> Option<TlsInfo> {
+    let mut info = TlsInfo {
+        protocol,
+        ..TlsInfo::default()
+    };
+    let mut certinfo: *mut CurlCertinfo = std::ptr::null_mut();
+    let code = unsafe {
+        curl_easy_getinfo(
+            handle,
+            CURLINFO_CERTINFO,
+            &mut certinfo as *mut *mut CurlCertinfo,
+        )
+    };
+    if code == CURLE_OK && !certinfo.is_null() {
+        let certinfo = unsafe { &*certinfo };
+        if certinfo.num_of_certs > 0 && !certinfo.certinfo.is_null() {
+            let mut node = unsafe { *certinfo.certinfo };
+            while !node.is_null() {
+                let entry = unsafe { &*node };
+                if !entry.data.is_null() {
+                    let field = unsafe { CStr::from_ptr(entry.data) }.to_string_lossy();
+                    set_certificate_field(&mut info, &field);
+                }
+                node = entry.next;
+            }
+        }
+    }
+    (info != TlsInfo::default()).then_some(info)
+}
+
+fn set_certificate_field(info: &mut TlsInfo, field: &str) {
+    let Some((name, value)) = field.split_once(':') else {
+        return;
+    };
+    let value = Some(value.trim().to_owned());
+    match name {
+        "Subject" => info.subject = value,
+        "Issuer" => info.issuer = value,
+        "Start date" => info.valid_from = value,
+        "Expire date" => info.valid_until = value,
+        _ => {}
+    }
+}
+
+struct WriteContext {
+    buffer: Vec<u8>,
     max_bytes: usize,
+    exceeded: bool,
 }
 
 extern "C" fn write_callback(
@@ -135,8 +644,9 @@ extern "C" fn write_callback(
         return 0;
     }
 
-    let ctx = unsafe { &mut *(userdata.cast::<WriteContext<'_>>()) };
+    let ctx = unsafe { &mut *(userdata.cast::<WriteContext>()) };
     if ctx.buffer.len().saturating_add(total) > ctx.max_bytes {
+        ctx.exceeded = true;
         return 0;
     }
 
@@ -145,6 +655,10 @@ extern "C" fn write_callback(
     total
 }
 
+fn millis(duration: Duration) -> c_long {
+    c_long::try_from(duration.as_millis()).unwrap_or(c_long::MAX)
+}
+
 fn setopt_long(handle: *mut CURL, option: CURLoption, value: c_long) -> Result<(), String> {
     let code = unsafe { curl_easy_setopt(handle, option, value) };
     if code == CURLE_OK {
@@ -172,6 +686,15 @@ fn setopt_write_fn(handle: *mut CURL, option: CURLoption, value: WriteFn) -> Res
     }
 }
 
+fn setopt_debug_fn(handle: *mut CURL, option: CURLoption, value: DebugFn) -> Result<(), String> {
+    let code = unsafe { curl_easy_setopt(handle, option, value) };
+    if code == CURLE_OK {
+        Ok(())
+    } else {
+        Err(format!("curl_easy_setopt failed: {}", curl_error(code)))
+    }
+}
+
 fn getinfo_long(handle: *mut CURL, info: CURLINFO) -> Result<i64, String> {
     let mut out: c_long = 0;
     let code = unsafe { curl_easy_getinfo(handle, info, &mut out as *mut c_long) };
@@ -182,6 +705,16 @@ fn getinfo_long(handle: *mut CURL, info: CURLINFO) -> Result<i64, String> {
     }
 }
 
+fn getinfo_double(handle: *mut CURL, info: CURLINFO) -> Result<f64, String> {
+    let mut out: f64 = 0.0;
+    let code = unsafe { curl_easy_getinfo(handle, info, &mut out as *mut f64) };
+    if code == CURLE_OK {
+        Ok(out)
+    } else {
+        Err(format!("curl_easy_getinfo failed: {}", curl_error(code)))
+    }
+}
+
 fn curl_error(code: CURLcode) -> String {
     let ptr = unsafe { curl_easy_strerror(code) };
     if ptr.is_null() {
@@ -191,3 +724,54 @@ fn curl_error(code: CURLcode) -> String {
         .to_string_lossy()
         .into_owned()
 }
+
+fn curl_multi_error(code: CURLMcode) -> String {
+    let ptr = unsafe { curl_multi_strerror(code) };
+    if ptr.is_null() {
+        return format!("curl multi error {code}");
+    }
+    unsafe { CStr::from_ptr(ptr) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TlsInfo, set_certificate_field, tls_protocol_from_text};
+
+    #[test]
+    fn reads_the_tls_version_from_curls_connection_text() {
+        assert_eq!(
+            tls_protocol_from_text(
+                "SSL connection using TLSv1.3 / TLS_AES_256_GCM_SHA384 / x25519 / RSASSA-PSS\n"
+            ),
+            Some("TLSv1.3")
+        );
+        assert_eq!(tls_protocol_from_text("ALPN: server accepted h2\n"), None);
+    }
+
+    #[test]
+    fn reads_the_certificate_fields_shown_to_the_user() {
+        let mut info = TlsInfo::default();
+        for field in [
+            "Subject:CN = example.com",
+            "Issuer:C = US, O = Let's Encrypt, CN = R3",
+            "Version:2",
+            "Start date:Mar  1 00:00:00 2026 GMT",
+            "Expire date:May 30 23:59:59 2026 GMT",
+        ] {
+            set_certificate_field(&mut info, field);
+        }
+        assert_eq!(info.subject.as_deref(), Some("CN = example.com"));
+        assert_eq!(
+            info.issuer.as_deref(),
+            Some("C = US, O = Let's Encrypt, CN = R3")
+        );
+        assert_eq!(info.valid_from.as_deref(), Some("Mar  1 00:00:00 2026 GMT"));
+        assert_eq!(
+            info.valid_until.as_deref(),
+            Some("May 30 23:59:59 2026 GMT")
+        );
+        assert_eq!(info.protocol, None);
+    }
+}
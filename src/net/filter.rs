@@ -0,0 +1,163 @@
+use crate::url::Url;
+use std::sync::{Arc, RwLock};
+
+/// What happens to a request a [`RequestFilter`] looked at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RequestDecision {
+    Allow,
+    /// Fail the request without touching the network.
+    Block,
+    /// Fetch this URL instead.
+    Rewrite(String),
+}
+
+/// Looks at every URL before it is fetched, whether a page, a stylesheet or an image. Redirects
+/// the server answers with are followed without asking again.
+pub trait RequestFilter: Send + Sync {
+    fn filter(&self, url: &str) -> RequestDecision;
+}
+
+static REQUEST_FILTER: RwLock<Option<Arc<dyn RequestFilter>>> = RwLock::new(None);
+
+/// Installs `filter` for every fetch from now on, replacing any earlier one.
+pub fn set_request_filter(filter: Arc<dyn RequestFilter>) {
+    if let Ok(mut installed) = REQUEST_FILTER.write() {
+        *installed = Some(filter);
+    }
+}
+
+pub fn clear_request_filter() {
+    if let Ok(mut installed) = REQUEST_FILTER.write() {
+        *installed = None;
+    }
+}
+
+/// The URL to fetch for `url`, or an error when the installed filter blocks it.
+pub(super) fn filtered_url(url: String) -> Result<String, String> {
+    let filter = match REQUEST_FILTER.read() {
+        Ok(installed) => installed.clone(),
+        Err(_) => None,
+    };
+    let Some(filter) = filter else {
+        return Ok(url);
+    };
+    match filter.filter(&url) {
+        RequestDecision::Allow => Ok(url),
+        RequestDecision::Block => Err(format!("Blocked by the request filter: {url}")),
+        RequestDecision::Rewrite(rewritten) => Ok(rewritten),
+    }
+}
+
+/// Blocks URLs matching the lines of a block list file. A line is a host, which also covers its
+/// subdomains (`||host^` as ad blockers write it works too), or a host and path prefix such as
+/// `example.com/ads/`. `@@` in front of a rule makes it an exception that lets matching URLs
+/// through. Blank lines and lines starting with `#` or `!` are skipped.
+#[derive(Clone, Debug, Default)]
+pub struct BlockList {
+    blocked: Vec<Rule>,
+    allowed: Vec<Rule>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    host: String,
+    /// Empty for a rule that covers the whole host.
+    path_prefix: String,
+}
+
+impl BlockList {
+    pub fn parse(text: &str) -> BlockList {
+        let mut list = BlockList::default();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(['#', '!']) {
+                continue;
+            }
+            let (rules, line) = match line.strip_prefix("@@") {
+                Some(line) => (&mut list.allowed, line),
+                None => (&mut list.blocked, line),
+            };
+            if let Some(rule) = Rule::parse(line) {
+                rules.push(rule);
+            }
+        }
+        list
+    }
+
+    pub fn blocks(&self, url: &str) -> bool {
+        let Ok(url) = Url::parse(url) else {
+            return false;
+        };
+        let matches = |rule: &Rule| rule.matches(&url);
+        self.blocked.iter().any(matches) && !self.allowed.iter().any(matches)
+    }
+}
+
+impl RequestFilter for BlockList {
+    fn filter(&self, url: &str) -> RequestDecision {
+        if self.blocks(url) {
+            RequestDecision::Block
+        } else {
+            RequestDecision::Allow
+        }
+    }
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Rule> {
+        let line = line.strip_prefix("||").unwrap_or(line);
+        let line = line.trim_end_matches('^');
+        let line = line
+            .split_once("://")
+            .map_or(line, |(_, after_scheme)| after_scheme);
+        let (host, path) = match line.find('/') {
+            Some(slash) => line.split_at(slash),
+            None => (line, ""),
+        };
+        let host = host.trim_start_matches("*.").to_ascii_lowercase();
+        if host.is_empty() {
+            return None;
+        }
+        Some(Rule {
+            host,
+            path_prefix: if path == "/" {
+                String::new()
+            } else {
+                path.to_owned()
+            },
+        })
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let host = url.host();
+        let host_matches = host == self.host
+            || host
+                .strip_suffix(self.host.as_str())
+                .is_some_and(|subdomain| subdomain.ends_with('.'));
+        host_matches && url.path_and_query().starts_with(&self.path_prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlockList;
+
+    #[test]
+    fn block_lists_match_hosts_subdomains_and_path_prefixes() {
+        let list = BlockList::parse(
+            "# trackers\n\
+             ||google-analytics.com^\n\
+             doubleclick.net\n\
+             example.com/ads/\n\
+             @@ok.doubleclick.net\n\
+             ! comment\n",
+        );
+        assert!(list.blocks("https://www.google-analytics.com/collect?v=1"));
+        assert!(list.blocks("https://doubleclick.net/"));
+        assert!(list.blocks("http://ad.doubleclick.net/x.js"));
+        assert!(!list.blocks("https://ok.doubleclick.net/x.js"));
+        assert!(!list.blocks("https://notdoubleclick.net/"));
+        assert!(list.blocks("https://example.com/ads/banner.png"));
+        assert!(!list.blocks("https://example.com/article"));
+    }
+}
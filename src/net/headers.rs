@@ -0,0 +1,175 @@
+use std::sync::{OnceLock, RwLock};
+
+/// The `User-Agent` requests carry unless [`RequestHeaders::user_agent`] replaces it.
+pub const DEFAULT_USER_AGENT: &str = "one-agent-one-browser/0.1";
+/// Sent as `Accept-Language` when the locale doesn't name a language.
+const FALLBACK_ACCEPT_LANGUAGE: &str = "en-US,en;q=0.9";
+
+/// Headers added to every request, on top of the ones the fetch itself needs such as `Host`
+/// and `Range`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestHeaders {
+    pub user_agent: Option<String>,
+    /// Sent in order after `Accept-Language`. A `User-Agent` or `Accept-Language` here replaces
+    /// the default one.
+    pub extra: Vec<(String, String)>,
+}
+
+static REQUEST_HEADERS: RwLock<RequestHeaders> = RwLock::new(RequestHeaders::new());
+
+/// Replaces the headers every fetch from now on carries.
+pub fn set_request_headers(headers: RequestHeaders) {
+    if let Ok(mut installed) = REQUEST_HEADERS.write() {
+        *installed = headers;
+    }
+}
+
+pub fn request_headers() -> RequestHeaders {
+    REQUEST_HEADERS
+        .read()
+        .map(|installed| installed.clone())
+        .unwrap_or_default()
+}
+
+impl RequestHeaders {
+    pub const fn new() -> RequestHeaders {
+        RequestHeaders {
+            user_agent: None,
+            extra: Vec::new(),
+        }
+    }
+
+    /// Splits a `Name: value` header, refusing names that aren't HTTP tokens and values that
+    /// would end the header early.
+    pub fn parse_header(text: &str) -> Result<(String, String), String> {
+        let (name, value) = text
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid header {text:?}: expected Name: value"))?;
+        let name = name.trim();
+        let value = value.trim();
+        if name.is_empty() || !name.bytes().all(is_token_byte) {
+            return Err(format!("Invalid header name {name:?}"));
+        }
+        if value.contains(['\r', '\n', '\0']) {
+            return Err(format!("Invalid value for header {name}"));
+        }
+        Ok((name.to_owned(), value.to_owned()))
+    }
+
+    pub(super) fn user_agent(&self) -> &str {
+        self.extra_header("user-agent")
+            .or(self.user_agent.as_deref())
+            .unwrap_or(DEFAULT_USER_AGENT)
+    }
+
+    /// Every header other than `User-Agent`, as `Name: value` lines without line breaks.
+    pub(super) fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(self.extra.len() + 1);
+        if self.extra_header("accept-language").is_none() {
+            lines.push(format!("Accept-Language: {}", default_accept_language()));
+        }
+        lines.extend(
+            self.extra
+                .iter()
+                .filter(|(name, _)| !name.eq_ignore_ascii_case("user-agent"))
+                .map(|(name, value)| format!("{name}: {value}")),
+        );
+        lines
+    }
+
+    fn extra_header(&self, name: &str) -> Option<&str> {
+        self.extra
+            .iter()
+            .rev()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn is_token_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte)
+}
+
+/// The user's language from the locale, as POSIX systems name it, with English after it.
+fn default_accept_language() -> &'static str {
+    static FROM_ENV: OnceLock<String> = OnceLock::new();
+    FROM_ENV.get_or_init(|| {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| accept_language_for_locale(&locale))
+            .unwrap_or_else(|| FALLBACK_ACCEPT_LANGUAGE.to_owned())
+    })
+}
+
+/// `de_DE.UTF-8` becomes `de-DE,de;q=0.9,en;q=0.8`; `C` and `POSIX` name no language.
+fn accept_language_for_locale(locale: &str) -> Option<String> {
+    let name = locale.split(['.', '@']).next()?;
+    let (language, region) = match name.split_once('_') {
+        Some((language, region)) => (language, Some(region)),
+        None => (name, None),
+    };
+    if language.len() < 2
+        || language.len() > 3
+        || !language.bytes().all(|byte| byte.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    let language = language.to_ascii_lowercase();
+    let mut tags = Vec::new();
+    if let Some(region) = region.filter(|region| region.bytes().all(|b| b.is_ascii_alphanumeric()))
+    {
+        tags.push(format!("{language}-{}", region.to_ascii_uppercase()));
+    }
+    tags.push(language.clone());
+    if language != "en" {
+        tags.push("en".to_owned());
+    }
+    let mut header = tags[0].clone();
+    for (index, tag) in tags.iter().enumerate().skip(1) {
+        header.push_str(&format!(",{tag};q=0.{}", 10 - index));
+    }
+    Some(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RequestHeaders, accept_language_for_locale};
+
+    #[test]
+    fn accept_language_follows_the_locale() {
+        assert_eq!(
+            accept_language_for_locale("de_DE.UTF-8").as_deref(),
+            Some("de-DE,de;q=0.9,en;q=0.8")
+        );
+        assert_eq!(
+            accept_language_for_locale("en_GB.UTF-8@euro").as_deref(),
+            Some("en-GB,en;q=0.9")
+        );
+        assert_eq!(
+            accept_language_for_locale("fr").as_deref(),
+            Some("fr,en;q=0.9")
+        );
+        assert_eq!(accept_language_for_locale("C.UTF-8"), None);
+        assert_eq!(accept_language_for_locale("POSIX"), None);
+    }
+
+    #[test]
+    fn extra_headers_replace_the_defaults() {
+        let mut headers = RequestHeaders::new();
+        assert_eq!(headers.user_agent(), super::DEFAULT_USER_AGENT);
+        headers.user_agent = Some("Mozilla/5.0".to_owned());
+        headers
+            .extra
+            .push(RequestHeaders::parse_header("Accept-Language: nl").unwrap());
+        headers
+            .extra
+            .push(RequestHeaders::parse_header("DNT:1").unwrap());
+        assert_eq!(headers.user_agent(), "Mozilla/5.0");
+        assert_eq!(headers.lines(), ["Accept-Language: nl", "DNT: 1"]);
+
+        assert!(RequestHeaders::parse_header("Bad Name: x").is_err());
+        assert!(RequestHeaders::parse_header("no colon").is_err());
+    }
+}
@@ -0,0 +1,660 @@
+use super::limits::{connection_error, status_error};
+use super::proxy::{Proxy, ProxyKind, ProxySettings};
+use super::resume::{RangeRequest, Transfer};
+use crate::url::{Scheme, Url};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const MAX_REDIRECTS: u32 = 20;
+/// Longest status line, header line or chunk-size line read.
+const MAX_LINE_BYTES: u64 = 16 * 1024;
+const MAX_HEADERS: usize = 256;
+/// Idle keep-alive connections kept across all hosts; the oldest is closed to make room.
+const MAX_IDLE_CONNECTIONS: usize = 8;
+
+/// Connections whose last response was read to the end and that the server left open.
+static IDLE_CONNECTIONS: Mutex<Vec<(Route, BufReader<Connection>)>> = Mutex::new(Vec::new());
+
+/// Fetches `url` over plain HTTP/1.1 with nothing but `std::net`, following redirects and
+/// reusing kept-alive connections. `https://` needs a TLS library and is refused.
+pub(super) fn fetch_url(url: &str, range: Option<&RangeRequest>) -> Result<Transfer, String> {
+    fetch(url, range, &super::proxy_settings())
+}
+
+fn fetch(
+    url: &str,
+    range: Option<&RangeRequest>,
+    proxies: &ProxySettings,
+) -> Result<Transfer, String> {
+    let started_at = Instant::now();
+    let limits = super::fetch_limits();
+    let deadline = started_at + limits.connect_timeout + limits.read_timeout;
+    let already_read = range.map_or(0, |range| range.start);
+    let max_bytes = super::resource_limits()
+        .max_response_bytes
+        .saturating_sub(usize::try_from(already_read).unwrap_or(usize::MAX));
+
+    let mut current = Url::parse(url)?;
+    for _ in 0..=MAX_REDIRECTS {
+        if current.scheme() == Scheme::Https {
+            return Err(format!(
+                "Failed to fetch {url}: the native HTTP client has no TLS for {}",
+                current.without_fragment()
+            ));
+        }
+        let proxy = proxies.proxy_for(&current).map(Proxy::parse).transpose()?;
+        let exchange = exchange(
+            &current,
+            proxy,
+            range,
+            limits.connect_timeout,
+            deadline,
+            max_bytes,
+        )
+        .map_err(|err| {
+            if is_connection_failure(&err) {
+                connection_error(format_args!("{url}: {err}"))
+            } else {
+                format!("Failed to fetch {url}: {err}")
+            }
+        })?;
+        let head = exchange.head;
+
+        if matches!(head.status, 301 | 302 | 303 | 307 | 308)
+            && let Some(location) = head.header("location")
+        {
+            current = current
+                .resolve(location)
+                .ok_or_else(|| format!("Invalid redirect to {location:?} fetching {url}"))?;
+            continue;
+        }
+
+        let interrupted = match exchange.body_error {
+            None => None,
+            Some(BodyError::TooLarge) => {
+                return Err(format!(
+                    "Response exceeds maximum size ({} bytes) fetching {url}",
+                    super::resource_limits().max_response_bytes
+                ));
+            }
+            Some(BodyError::Io(err)) if matches!(head.status, 200 | 206) => {
+                Some(format!("Failed to fetch {url}: {err}"))
+            }
+            Some(BodyError::Io(err)) => return Err(format!("Failed to fetch {url}: {err}")),
+        };
+        if !(200..=399).contains(&head.status) {
+            return Err(status_error(head.status, url));
+        }
+
+        return Ok(Transfer {
+            status: head.status,
+            body: exchange.body,
+            time_to_first_byte: Some(exchange.first_byte_at.duration_since(started_at)),
+            etag: head.header("etag").map(str::to_owned),
+            last_modified: head.header("last-modified").map(str::to_owned),
+            content_range: head.header("content-range").map(str::to_owned),
+            encoded: head
+                .header("content-encoding")
+                .is_some_and(|coding| !coding.eq_ignore_ascii_case("identity")),
+            interrupted,
+            tls: None,
+        });
+    }
+    Err(format!("Too many redirects fetching {url}"))
+}
+
+/// Where a connection leads: the origin, and the proxy it goes through.
+#[derive(Clone, PartialEq, Eq)]
+struct Route {
+    host: String,
+    port: u16,
+    proxy: Option<Proxy>,
+}
+
+/// A socket whose reads and writes give up at `deadline`, which each request moves.
+struct Connection {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Connection {
+    fn remaining(&self) -> io::Result<Duration> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        Ok(remaining)
+    }
+}
+
+impl Read for Connection {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.stream.set_read_timeout(Some(self.remaining()?))?;
+        self.stream.read(buf)
+    }
+}
+
+impl Write for Connection {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.stream.set_write_timeout(Some(self.remaining()?))?;
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+struct Head {
+    status: u32,
+    /// Whether the server will keep the connection open after this response.
+    keep_alive: bool,
+    /// Names lowercased.
+    headers: Vec<(String, String)>,
+}
+
+impl Head {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+enum BodyError {
+    TooLarge,
+    Io(io::Error),
+}
+
+impl From<io::Error> for BodyError {
+    fn from(err: io::Error) -> Self {
+        BodyError::Io(err)
+    }
+}
+
+/// One request and its response. A failure after the head arrived is kept in `body_error`
+/// alongside what was read, so the transfer can be resumed.
+struct Exchange {
+    head: Head,
+    first_byte_at: Instant,
+    body: Vec<u8>,
+    body_error: Option<BodyError>,
+}
+
+fn exchange(
+    url: &Url,
+    proxy: Option<Proxy>,
+    range: Option<&RangeRequest>,
+    connect_timeout: Duration,
+    deadline: Instant,
+    max_bytes: usize,
+) -> io::Result<Exchange> {
+    // An HTTP proxy is sent the whole URL; through SOCKS the request is the one the origin
+    // would get.
+    let absolute = proxy
+        .as_ref()
+        .is_some_and(|proxy| proxy.kind == ProxyKind::Http);
+    let origin = Route {
+        host: url.host().to_owned(),
+        port: url.port().unwrap_or(url.scheme().default_port()),
+        proxy,
+    };
+    let request = request_text(url, range, absolute);
+
+    // A kept-alive connection the server has since closed fails before any of the response
+    // arrives; the request is then sent again on a new one.
+    let (mut reader, head, first_byte_at) = match take_idle(&origin, deadline)
+        .map(|mut reader| send_and_read_head(&mut reader, &request).map(|head| (reader, head)))
+    {
+        Some(Ok((reader, (head, first_byte_at)))) => (reader, head, first_byte_at),
+        Some(Err(_)) | None => {
+            let mut reader = BufReader::new(connect(&origin, connect_timeout, deadline)?);
+            let (head, first_byte_at) = send_and_read_head(&mut reader, &request)?;
+            (reader, head, first_byte_at)
+        }
+    };
+
+    let mut body = Vec::new();
+    let body_error = read_body(&mut reader, &head, &mut body, max_bytes).err();
+    let framed = matches!(head.status, 204 | 304)
+        || head.header("content-length").is_some()
+        || is_chunked(&head);
+    if body_error.is_none() && head.keep_alive && framed && reader.buffer().is_empty() {
+        put_idle(origin, reader);
+    }
+    Ok(Exchange {
+        head,
+        first_byte_at,
+        body,
+        body_error,
+    })
+}
+
+fn request_text(url: &Url, range: Option<&RangeRequest>, absolute: bool) -> String {
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host()),
+        None => url.host().to_owned(),
+    };
+    let headers = super::request_headers();
+    let target = if absolute {
+        url.without_fragment()
+    } else {
+        url.path_and_query()
+    };
+    let mut request = format!(
+        "GET {target} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: {}\r\nAccept: */*\r\n",
+        headers.user_agent()
+    );
+    for line in headers.lines() {
+        request.push_str(&line);
+        request.push_str("\r\n");
+    }
+    if let Some(range) = range {
+        request.push_str(&format!(
+            "Range: bytes={}-\r\nIf-Range: {}\r\n",
+            range.start, range.if_range
+        ));
+    }
+    request.push_str("\r\n");
+    request
+}
+
+/// Whether `err` means the server refused the connection or dropped it before answering.
+fn is_connection_failure(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+fn connect(route: &Route, timeout: Duration, deadline: Instant) -> io::Result<Connection> {
+    let Some(proxy) = &route.proxy else {
+        return connect_to(&route.host, route.port, timeout, deadline);
+    };
+    let mut connection = connect_to(&proxy.host, proxy.port, timeout, deadline)?;
+    if proxy.kind != ProxyKind::Http {
+        socks5_connect(&mut connection, route, proxy.kind)?;
+    }
+    Ok(connection)
+}
+
+fn connect_to(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+    deadline: Instant,
+) -> io::Result<Connection> {
+    let host = unbracketed(host);
+    let mut last_err = io::Error::new(io::ErrorKind::NotFound, "host has no addresses");
+    for address in (host, port).to_socket_addrs()? {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        match TcpStream::connect_timeout(&address, remaining.min(timeout)) {
+            Ok(stream) => {
+                stream.set_nodelay(true)?;
+                return Ok(Connection { stream, deadline });
+            }
+            Err(err) => last_err = err,
+        }
+    }
+    Err(last_err)
+}
+
+/// Asks a SOCKS5 proxy that needs no authentication to connect on to `route` (RFC 1928).
+fn socks5_connect(connection: &mut Connection, route: &Route, kind: ProxyKind) -> io::Result<()> {
+    connection.write_all(&[5, 1, 0])?;
+    let mut choice = [0u8; 2];
+    connection.read_exact(&mut choice)?;
+    if choice != [5, 0] {
+        return Err(io::Error::other("SOCKS5 proxy wants authentication"));
+    }
+
+    let host = unbracketed(&route.host);
+    let mut request = vec![5, 1, 0];
+    if kind == ProxyKind::Socks5Hostname {
+        let length = u8::try_from(host.len())
+            .map_err(|_| invalid_data(format!("host name too long for SOCKS5: {host}")))?;
+        request.push(3);
+        request.push(length);
+        request.extend_from_slice(host.as_bytes());
+    } else {
+        let address = (host, route.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "host has no addresses"))?;
+        match address.ip() {
+            IpAddr::V4(ip) => {
+                request.push(1);
+                request.extend_from_slice(&ip.octets());
+            }
+            IpAddr::V6(ip) => {
+                request.push(4);
+                request.extend_from_slice(&ip.octets());
+            }
+        }
+    }
+    request.extend_from_slice(&route.port.to_be_bytes());
+    connection.write_all(&request)?;
+
+    let mut reply = [0u8; 4];
+    connection.read_exact(&mut reply)?;
+    if reply[0] != 5 || reply[1] != 0 {
+        return Err(io::Error::other(format!(
+            "SOCKS5 proxy refused the connection (reply {})",
+            reply[1]
+        )));
+    }
+    // The reply ends with the address the proxy bound, which nothing here needs.
+    let address_length = match reply[3] {
+        1 => 4,
+        4 => 16,
+        3 => {
+            let mut length = [0u8; 1];
+            connection.read_exact(&mut length)?;
+            usize::from(length[0])
+        }
+        kind => return Err(invalid_data(format!("SOCKS5 address type {kind}"))),
+    };
+    connection.read_exact(&mut vec![0; address_length + 2])
+}
+
+/// Bracketed IPv6 literals resolve without their brackets.
+fn unbracketed(host: &str) -> &str {
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn take_idle(origin: &Route, deadline: Instant) -> Option<BufReader<Connection>> {
+    let mut idle = IDLE_CONNECTIONS.lock().ok()?;
+    let index = idle
+        .iter()
+        .position(|(idle_origin, _)| idle_origin == origin)?;
+    let (_, mut reader) = idle.remove(index);
+    reader.get_mut().deadline = deadline;
+    Some(reader)
+}
+
+fn put_idle(origin: Route, reader: BufReader<Connection>) {
+    let Ok(mut idle) = IDLE_CONNECTIONS.lock() else {
+        return;
+    };
+    if idle.len() == MAX_IDLE_CONNECTIONS {
+        idle.remove(0);
+    }
+    idle.push((origin, reader));
+}
+
+fn send_and_read_head(
+    reader: &mut BufReader<Connection>,
+    request: &str,
+) -> io::Result<(Head, Instant)> {
+    reader.get_mut().write_all(request.as_bytes())?;
+    reader.get_mut().flush()?;
+    if reader.fill_buf()?.is_empty() {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let first_byte_at = Instant::now();
+    loop {
+        let head = read_head(reader)?;
+        // Interim responses such as `100 Continue` precede the real one.
+        if !(100..200).contains(&head.status) {
+            return Ok((head, first_byte_at));
+        }
+    }
+}
+
+fn read_head(reader: &mut impl BufRead) -> io::Result<Head> {
+    let status_line = read_line(reader)?;
+    let mut parts = status_line.splitn(3, ' ');
+    let version = parts.next().unwrap_or_default();
+    let status = parts
+        .next()
+        .and_then(|status| status.parse::<u32>().ok())
+        .filter(|_| version.starts_with("HTTP/1."))
+        .ok_or_else(|| invalid_data(format!("malformed status line {status_line:?}")))?;
+
+    let mut headers = Vec::new();
+    loop {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Err(invalid_data("too many response headers".to_owned()));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_owned()));
+        }
+    }
+
+    let connection = headers
+        .iter()
+        .filter(|(name, _)| name == "connection")
+        .flat_map(|(_, value)| value.split(','))
+        .map(str::trim);
+    let keep_alive = if version == "HTTP/1.0" {
+        connection
+            .clone()
+            .any(|option| option.eq_ignore_ascii_case("keep-alive"))
+    } else {
+        !connection
+            .clone()
+            .any(|option| option.eq_ignore_ascii_case("close"))
+    };
+    Ok(Head {
+        status,
+        keep_alive,
+        headers,
+    })
+}
+
+fn is_chunked(head: &Head) -> bool {
+    head.header("transfer-encoding").is_some_and(|codings| {
+        codings
+            .rsplit(',')
+            .next()
+            .is_some_and(|last| last.trim().eq_ignore_ascii_case("chunked"))
+    })
+}
+
+/// Reads the body `head` announces into `body`: chunks, a Content-Length, or everything up
+/// to the connection closing.
+fn read_body(
+    reader: &mut impl BufRead,
+    head: &Head,
+    body: &mut Vec<u8>,
+    max_bytes: usize,
+) -> Result<(), BodyError> {
+    if matches!(head.status, 204 | 304) {
+        return Ok(());
+    }
+    if is_chunked(head) {
+        return read_chunked(reader, body, max_bytes);
+    }
+    if let Some(length) = head.header("content-length") {
+        let length: usize = length
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid Content-Length {length:?}")))?;
+        if length > max_bytes {
+            return Err(BodyError::TooLarge);
+        }
+        return read_exactly(reader, body, length);
+    }
+    let limit = u64::try_from(max_bytes)
+        .unwrap_or(u64::MAX)
+        .saturating_add(1);
+    reader.take(limit).read_to_end(body)?;
+    if body.len() > max_bytes {
+        return Err(BodyError::TooLarge);
+    }
+    Ok(())
+}
+
+fn read_chunked(
+    reader: &mut impl BufRead,
+    body: &mut Vec<u8>,
+    max_bytes: usize,
+) -> Result<(), BodyError> {
+    loop {
+        let line = read_line(reader)?;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| invalid_data(format!("invalid chunk size {size:?}")))?;
+        if size == 0 {
+            // Trailer fields, which nothing here needs, end with an empty line.
+            while !read_line(reader)?.is_empty() {}
+            return Ok(());
+        }
+        if body.len().saturating_add(size) > max_bytes {
+            return Err(BodyError::TooLarge);
+        }
+        read_exactly(reader, body, size)?;
+        if !read_line(reader)?.is_empty() {
+            return Err(invalid_data("chunk longer than its size".to_owned()).into());
+        }
+    }
+}
+
+/// Appends exactly `length` bytes to `body`, keeping what arrived if the connection ends first.
+fn read_exactly(
+    reader: &mut impl BufRead,
+    body: &mut Vec<u8>,
+    length: usize,
+) -> Result<(), BodyError> {
+    let expected = body.len() + length;
+    reader
+        .take(u64::try_from(length).unwrap_or(u64::MAX))
+        .read_to_end(body)?;
+    if body.len() < expected {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+    Ok(())
+}
+
+/// One CRLF- or LF-terminated line without its terminator.
+fn read_line(reader: &mut impl BufRead) -> io::Result<String> {
+    let mut line = Vec::new();
+    reader.take(MAX_LINE_BYTES).read_until(b'\n', &mut line)?;
+    if line.pop() != Some(b'\n') {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(String::from_utf8_lossy(&line).into_owned())
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn reads_chunked_bodies_with_extensions_and_trailers() {
+        let mut reader: &[u8] =
+            b"HTTP/1.1 200 OK\r\nTransfer-Encoding: gzip, chunked\r\n\r\n5;x=1\r\nhello\r\n1\r\n!\r\n0\r\nExpires: never\r\n\r\nrest";
+        let head = read_head(&mut reader).unwrap();
+        assert!(head.keep_alive);
+        assert!(is_chunked(&head));
+        let mut body = Vec::new();
+        assert!(read_body(&mut reader, &head, &mut body, 100).is_ok());
+        assert_eq!(body, b"hello!");
+        assert_eq!(reader, b"rest");
+
+        let mut reader: &[u8] = b"5\r\nhel";
+        let mut body = Vec::new();
+        assert!(matches!(
+            read_chunked(&mut reader, &mut body, 100),
+            Err(BodyError::Io(_))
+        ));
+        assert_eq!(body, b"hel");
+        assert!(matches!(
+            read_chunked(&mut &b"ff\r\n"[..], &mut Vec::new(), 100),
+            Err(BodyError::TooLarge)
+        ));
+    }
+
+    #[test]
+    fn follows_redirects_over_one_kept_alive_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut writer = stream;
+            let mut requests = Vec::new();
+            for response in [
+                "HTTP/1.1 302 Found\r\nLocation: /b\r\nContent-Length: 0\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nETag: \"v1\"\r\nConnection: close\r\n\r\nok",
+            ] {
+                requests.push(read_line(&mut reader).unwrap());
+                while !read_line(&mut reader).unwrap().is_empty() {}
+                writer.write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+
+        let transfer = fetch(
+            &format!("http://127.0.0.1:{port}/a"),
+            None,
+            &ProxySettings::default(),
+        )
+        .unwrap();
+        assert_eq!(transfer.status, 200);
+        assert_eq!(transfer.body, b"ok");
+        assert_eq!(transfer.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(
+            server.join().unwrap(),
+            ["GET /a HTTP/1.1", "GET /b HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn connects_through_a_socks5_proxy_by_host_name() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let proxy = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).unwrap();
+            stream.write_all(&[5, 0]).unwrap();
+            let mut request = [0u8; 5];
+            stream.read_exact(&mut request).unwrap();
+            let mut target = vec![0u8; usize::from(request[4]) + 2];
+            stream.read_exact(&mut target).unwrap();
+            stream.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let request_line = read_line(&mut reader).unwrap();
+            while !read_line(&mut reader).unwrap().is_empty() {}
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+            (greeting, request[3], target, request_line)
+        });
+
+        let settings = ProxySettings {
+            http: Some(format!("socks5h://127.0.0.1:{port}")),
+            ..ProxySettings::default()
+        };
+        let transfer = fetch("http://example.test/page", None, &settings).unwrap();
+        assert_eq!(transfer.body, b"ok");
+        let (greeting, address_type, target, request_line) = proxy.join().unwrap();
+        assert_eq!(greeting, [5, 1, 0]);
+        assert_eq!(address_type, 3);
+        assert_eq!(target, b"example.test\0\x50");
+        assert_eq!(request_line, "GET /page HTTP/1.1");
+    }
+}
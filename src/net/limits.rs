@@ -0,0 +1,169 @@
+use std::fmt::Display;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Statuses that say the server may well answer a moment later.
+const TRANSIENT_STATUSES: [u32; 5] = [408, 429, 502, 503, 504];
+/// Wait before the first retry; each further one waits twice as long.
+const FIRST_BACKOFF: Duration = Duration::from_millis(250);
+const CONNECTION_FAILED: &str = "Connection failed: ";
+const UNEXPECTED_STATUS: &str = "Unexpected HTTP status ";
+
+/// How long a fetch waits on the network, and how often one that failed for a reason that may
+/// pass is tried again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FetchLimits {
+    /// For the TCP connection to be set up, TLS aside.
+    pub connect_timeout: Duration,
+    /// For the request to be sent and the whole response read, once connected.
+    pub read_timeout: Duration,
+    /// Further attempts after a refused or reset connection or a 408, 429, 502, 503 or 504.
+    /// Timeouts are not retried.
+    pub retries: u32,
+}
+
+impl FetchLimits {
+    pub const fn new() -> FetchLimits {
+        FetchLimits {
+            connect_timeout: Duration::from_secs(5),
+            read_timeout: Duration::from_secs(10),
+            retries: 1,
+        }
+    }
+}
+
+impl Default for FetchLimits {
+    fn default() -> FetchLimits {
+        FetchLimits::new()
+    }
+}
+
+static FETCH_LIMITS: RwLock<FetchLimits> = RwLock::new(FetchLimits::new());
+
+/// Replaces the limits every fetch from now on runs under.
+pub fn set_fetch_limits(limits: FetchLimits) {
+    if let Ok(mut installed) = FETCH_LIMITS.write() {
+        *installed = limits;
+    }
+}
+
+pub fn fetch_limits() -> FetchLimits {
+    FETCH_LIMITS
+        .read()
+        .map(|installed| *installed)
+        .unwrap_or_default()
+}
+
+/// How much memory a page's resources may take. What goes past a limit is cut off or left out
+/// of the page, so a hostile page degrades instead of exhausting the host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// The largest response body, after Content-Encoding has been undone, so a small compressed
+    /// body cannot expand without bound.
+    pub max_response_bytes: usize,
+    /// How much of an HTML document is parsed; the rest is dropped.
+    pub max_html_bytes: usize,
+    /// The largest stylesheet applied; larger ones are left out.
+    pub max_css_bytes: usize,
+    /// Images larger than this on either side, or with more pixels, are rejected before
+    /// decoding.
+    pub max_image_dimension: u32,
+    pub max_image_pixels: u64,
+    /// The most bytes a page's images may hold, encoded and decoded together; images past it
+    /// are left out.
+    pub max_page_bytes: u64,
+}
+
+impl ResourceLimits {
+    pub const fn new() -> ResourceLimits {
+        ResourceLimits {
+            max_response_bytes: 10 * 1024 * 1024,
+            max_html_bytes: 10 * 1024 * 1024,
+            max_css_bytes: 4 * 1024 * 1024,
+            max_image_dimension: crate::image::MAX_IMAGE_DIMENSION,
+            max_image_pixels: crate::image::MAX_IMAGE_PIXELS,
+            max_page_bytes: 1024 * 1024 * 1024,
+        }
+    }
+}
+
+impl Default for ResourceLimits {
+    fn default() -> ResourceLimits {
+        ResourceLimits::new()
+    }
+}
+
+static RESOURCE_LIMITS: RwLock<ResourceLimits> = RwLock::new(ResourceLimits::new());
+
+/// Replaces the limits every page loaded from now on runs under.
+pub fn set_resource_limits(limits: ResourceLimits) {
+    if let Ok(mut installed) = RESOURCE_LIMITS.write() {
+        *installed = limits;
+    }
+}
+
+pub fn resource_limits() -> ResourceLimits {
+    RESOURCE_LIMITS
+        .read()
+        .map(|installed| *installed)
+        .unwrap_or_default()
+}
+
+/// The error for a connection that was refused, reset or closed before the response, which
+/// [`retry`] tries again.
+pub(super) fn connection_error(detail: impl Display) -> String {
+    format!("{CONNECTION_FAILED}{detail}")
+}
+
+/// The error for a response whose status is neither a success nor a redirect.
+pub(super) fn status_error(status: u32, url: &str) -> String {
+    format!("{UNEXPECTED_STATUS}{status} fetching {url}")
+}
+
+/// Whether an error from [`connection_error`] or [`status_error`] may pass on its own.
+pub(super) fn is_transient(err: &str) -> bool {
+    err.starts_with(CONNECTION_FAILED)
+        || err
+            .strip_prefix(UNEXPECTED_STATUS)
+            .and_then(|rest| rest.split(' ').next())
+            .and_then(|status| status.parse().ok())
+            .is_some_and(|status| TRANSIENT_STATUSES.contains(&status))
+}
+
+/// Runs `fetch` again after `first` while it fails transiently, up to
+/// [`FetchLimits::retries`] times, waiting longer before each attempt.
+pub(super) fn retry<T>(
+    first: Result<T, String>,
+    mut fetch: impl FnMut() -> Result<T, String>,
+) -> Result<T, String> {
+    let retries = fetch_limits().retries;
+    let mut result = first;
+    let mut backoff = FIRST_BACKOFF;
+    for _ in 0..retries {
+        match &result {
+            Err(err) if is_transient(err) => {}
+            _ => break,
+        }
+        std::thread::sleep(backoff);
+        backoff = backoff.saturating_mul(2);
+        result = fetch();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{connection_error, is_transient, status_error};
+
+    #[test]
+    fn only_refused_connections_and_busy_servers_are_transient() {
+        assert!(is_transient(&connection_error("connection refused")));
+        assert!(is_transient(&status_error(503, "http://example.com/")));
+        assert!(is_transient(&status_error(429, "http://example.com/")));
+        assert!(!is_transient(&status_error(404, "http://example.com/")));
+        assert!(!is_transient(&status_error(500, "http://example.com/")));
+        assert!(!is_transient(
+            "Failed to fetch http://example.com/: timed out"
+        ));
+    }
+}
@@ -1,17 +1,72 @@
-#[cfg(not(target_os = "windows"))]
+#[cfg(not(any(target_os = "windows", feature = "native-http")))]
 mod curl;
+mod filter;
+mod headers;
+mod http1;
+mod limits;
 mod pool;
+mod proxy;
+mod resume;
+mod security;
+mod waterfall;
 #[cfg(target_os = "windows")]
 mod winhttp;
 
-pub use pool::{FetchEvent, FetchPool, RequestId};
+use std::time::Duration;
 
-pub fn fetch_url_bytes(url: &str) -> Result<Vec<u8>, String> {
+pub use filter::{
+    BlockList, RequestDecision, RequestFilter, clear_request_filter, set_request_filter,
+};
+pub use headers::{DEFAULT_USER_AGENT, RequestHeaders, request_headers, set_request_headers};
+pub use limits::{
+    FetchLimits, ResourceLimits, fetch_limits, resource_limits, set_fetch_limits,
+    set_resource_limits,
+};
+pub use pool::{FetchEvent, FetchPool, FetchTiming, RequestId};
+pub use proxy::{ProxySettings, parse_no_proxy, proxy_settings, set_proxy_settings};
+pub use security::{
+    MixedContentPolicy, TlsInfo, is_mixed_content, mixed_content_policy, set_mixed_content_policy,
+};
+pub use waterfall::{Waterfall, WaterfallBar, WaterfallEntry};
+
+struct Response {
+    body: Vec<u8>,
+    time_to_first_byte: Option<Duration>,
+    tls: Option<TlsInfo>,
+}
+
+fn fetch_url(url: &str) -> Result<Response, String> {
+    limits::retry(fetch_url_once(url), || fetch_url_once(url))
+}
+
+fn fetch_url_once(url: &str) -> Result<Response, String> {
     #[cfg(target_os = "windows")]
-    return winhttp::fetch_url_bytes(url);
+    if !native_http_selected() {
+        return resume::fetch_resumable(url, winhttp::fetch_url);
+    }
 
-    #[cfg(not(target_os = "windows"))]
-    return curl::fetch_url_bytes(url);
+    #[cfg(not(any(target_os = "windows", feature = "native-http")))]
+    if !native_http_selected() {
+        return resume::fetch_resumable(url, curl::fetch_url);
+    }
+
+    resume::fetch_resumable(url, http1::fetch_url)
+}
+
+/// Whether `OAB_HTTP_BACKEND=native` asks for the built-in HTTP/1.1 client over the platform's
+/// library. Builds with the `native-http` feature have no libcurl to choose.
+#[cfg(any(target_os = "windows", not(feature = "native-http")))]
+fn native_http_selected() -> bool {
+    static NATIVE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *NATIVE.get_or_init(|| {
+        std::env::var("OAB_HTTP_BACKEND")
+            .is_ok_and(|backend| backend.trim().eq_ignore_ascii_case("native"))
+    })
+}
+
+pub fn fetch_url_bytes(url: &str) -> Result<Vec<u8>, String> {
+    let url = filter::filtered_url(url.to_owned())?;
+    fetch_url(&url).map(|response| response.body)
 }
 
 pub fn fetch_url_text(url: &str) -> Result<String, String> {
@@ -1,5 +1,10 @@
+use super::TlsInfo;
+#[cfg(not(any(target_os = "windows", feature = "native-http")))]
+use super::resume::{RangeRequest, Transfer};
 use crate::debug;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Duration, Instant};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct RequestId(u64);
@@ -15,12 +20,45 @@ pub struct FetchEvent {
     pub id: RequestId,
     pub url: String,
     pub result: Result<Vec<u8>, String>,
+    pub timing: FetchTiming,
+    /// For a successful `https://` fetch, what its connection reported.
+    pub tls: Option<TlsInfo>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FetchTiming {
+    pub queued_at: Instant,
+    pub started_at: Instant,
+    pub response_at: Option<Instant>,
+    pub finished_at: Instant,
+}
+
+impl FetchTiming {
+    pub fn queued(&self) -> Duration {
+        self.started_at.saturating_duration_since(self.queued_at)
+    }
+
+    pub fn connecting(&self) -> Duration {
+        self.response_at
+            .unwrap_or(self.finished_at)
+            .saturating_duration_since(self.started_at)
+    }
+
+    pub fn downloading(&self) -> Duration {
+        self.response_at
+            .map(|response_at| self.finished_at.saturating_duration_since(response_at))
+            .unwrap_or_default()
+    }
 }
 
 pub struct FetchPool {
     job_tx: mpsc::Sender<Job>,
+    /// For answering requests the request filter blocks without queueing them.
+    event_tx: mpsc::Sender<FetchEvent>,
     event_rx: mpsc::Receiver<FetchEvent>,
     next_id: u64,
+    /// Requests whose id is below this were cancelled; the fetchers skip or abandon them.
+    cancelled_below: Arc<AtomicU64>,
     label: &'static str,
 }
 
@@ -29,18 +67,20 @@ impl FetchPool {
         let worker_count = worker_count.max(1);
         let (job_tx, job_rx) = mpsc::channel::<Job>();
         let (event_tx, event_rx) = mpsc::channel::<FetchEvent>();
-        let shared_rx = Arc::new(Mutex::new(job_rx));
-
-        for _ in 0..worker_count {
-            let shared_rx = Arc::clone(&shared_rx);
-            let event_tx = event_tx.clone();
-            std::thread::spawn(move || worker_loop(shared_rx, event_tx));
-        }
+        let cancelled_below = Arc::new(AtomicU64::new(0));
+        spawn_fetchers(
+            worker_count,
+            job_rx,
+            event_tx.clone(),
+            Arc::clone(&cancelled_below),
+        );
 
         FetchPool {
             job_tx,
+            event_tx,
             event_rx,
             next_id: 1,
+            cancelled_below,
             label: "pool",
         }
     }
@@ -53,10 +93,30 @@ impl FetchPool {
     pub fn fetch_bytes(&mut self, url: String) -> Result<RequestId, String> {
         let id = RequestId(self.next_id);
         self.next_id = self.next_id.saturating_add(1);
+        let queued_at = Instant::now();
+        let url = match super::filter::filtered_url(url.clone()) {
+            Ok(url) => url,
+            Err(err) => {
+                if debug::enabled(debug::Target::Net, debug::Level::Info) {
+                    let url = debug::shorten(&url, 64);
+                    debug::log(
+                        debug::Target::Net,
+                        debug::Level::Info,
+                        format_args!(
+                            "req! p={} id={} url={url} err=blocked",
+                            self.label,
+                            id.as_u64()
+                        ),
+                    );
+                }
+                send_response(&self.event_tx, id, url, queued_at, queued_at, Err(err));
+                return Ok(id);
+            }
+        };
         let url_for_log = debug::enabled(debug::Target::Net, debug::Level::Debug)
             .then(|| debug::shorten(&url, 64).into_owned());
 
-        let job = Job::Fetch { id, url };
+        let job = Job::Fetch { id, url, queued_at };
         if let Err(err) = self.job_tx.send(job) {
             let url = match err.0 {
                 Job::Fetch { url, .. } => url,
@@ -86,8 +146,29 @@ impl FetchPool {
         Ok(id)
     }
 
+    /// Drops every request made so far: queued ones are never started, transfers in progress
+    /// are abandoned where the backend allows it, and no event arrives for any of them.
+    pub fn cancel_all(&mut self) {
+        if self.cancelled_below.swap(self.next_id, Ordering::Relaxed) == self.next_id {
+            return;
+        }
+        if debug::enabled(debug::Target::Net, debug::Level::Debug) {
+            debug::log(
+                debug::Target::Net,
+                debug::Level::Debug,
+                format_args!("req~ p={} below={}", self.label, self.next_id),
+            );
+        }
+    }
+
     pub fn try_recv(&mut self) -> Option<FetchEvent> {
-        let event = self.event_rx.try_recv().ok()?;
+        let cancelled_below = self.cancelled_below.load(Ordering::Relaxed);
+        let event = loop {
+            let event = self.event_rx.try_recv().ok()?;
+            if event.id.as_u64() >= cancelled_below {
+                break event;
+            }
+        };
         if debug::enabled(debug::Target::Net, debug::Level::Warn) {
             if let Err(err) = &event.result {
                 let url = debug::shorten(&event.url, 64);
@@ -125,11 +206,59 @@ impl FetchPool {
     }
 }
 
+impl Drop for FetchPool {
+    fn drop(&mut self) {
+        self.cancel_all();
+    }
+}
+
 enum Job {
-    Fetch { id: RequestId, url: String },
+    Fetch {
+        id: RequestId,
+        url: String,
+        queued_at: Instant,
+    },
 }
 
-fn worker_loop(shared_rx: Arc<Mutex<mpsc::Receiver<Job>>>, event_tx: mpsc::Sender<FetchEvent>) {
+/// Starts what runs the pool's jobs: one thread driving a curl multi handle, which opens at
+/// most `worker_count` connections and multiplexes HTTP/2 requests over them, or else
+/// `worker_count` threads fetching one URL at a time.
+fn spawn_fetchers(
+    worker_count: usize,
+    job_rx: mpsc::Receiver<Job>,
+    event_tx: mpsc::Sender<FetchEvent>,
+    cancelled_below: Arc<AtomicU64>,
+) {
+    #[cfg(not(any(target_os = "windows", feature = "native-http")))]
+    if !super::native_http_selected()
+        && let Ok(multi) = super::curl::Multi::new(worker_count)
+    {
+        std::thread::spawn(move || {
+            multi_loop(
+                multi,
+                job_rx,
+                event_tx,
+                cancelled_below,
+                super::curl::fetch_url,
+            )
+        });
+        return;
+    }
+
+    let shared_rx = Arc::new(Mutex::new(job_rx));
+    for _ in 0..worker_count {
+        let shared_rx = Arc::clone(&shared_rx);
+        let event_tx = event_tx.clone();
+        let cancelled_below = Arc::clone(&cancelled_below);
+        std::thread::spawn(move || worker_loop(shared_rx, event_tx, cancelled_below));
+    }
+}
+
+fn worker_loop(
+    shared_rx: Arc<Mutex<mpsc::Receiver<Job>>>,
+    event_tx: mpsc::Sender<FetchEvent>,
+    cancelled_below: Arc<AtomicU64>,
+) {
     loop {
         let job = match shared_rx.lock() {
             Ok(rx) => rx.recv(),
@@ -142,10 +271,381 @@ fn worker_loop(shared_rx: Arc<Mutex<mpsc::Receiver<Job>>>, event_tx: mpsc::Sende
         };
 
         match job {
-            Job::Fetch { id, url } => {
-                let result = super::fetch_url_bytes(&url);
-                let _ = event_tx.send(FetchEvent { id, url, result });
+            Job::Fetch { id, .. } if id.as_u64() < cancelled_below.load(Ordering::Relaxed) => {}
+            Job::Fetch { id, url, queued_at } => {
+                let started_at = Instant::now();
+                let response = super::fetch_url(&url);
+                send_response(&event_tx, id, url, queued_at, started_at, response);
+            }
+        }
+    }
+}
+
+/// How long the multi thread waits on the network before looking for new jobs again.
+#[cfg(not(any(target_os = "windows", feature = "native-http")))]
+const MULTI_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The transfers `multi_loop` runs side by side, each known by the token it was added with.
+#[cfg(not(any(target_os = "windows", feature = "native-http")))]
+trait MultiTransfers {
+    fn add(&mut self, token: u64, url: &str) -> Result<(), String>;
+    fn is_empty(&self) -> bool;
+    fn remove(&mut self, token: u64);
+    fn perform(&mut self, timeout: Duration) -> Vec<(u64, Result<Transfer, String>)>;
+}
+
+#[cfg(not(any(target_os = "windows", feature = "native-http")))]
+impl MultiTransfers for super::curl::Multi {
+    fn add(&mut self, token: u64, url: &str) -> Result<(), String> {
+        self.add(token, url)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn remove(&mut self, token: u64) {
+        self.remove(token)
+    }
+
+    fn perform(&mut self, timeout: Duration) -> Vec<(u64, Result<Transfer, String>)> {
+        self.perform(timeout)
+    }
+}
+
+/// `fetch` makes the blocking requests that retry a failed transfer or continue one that broke
+/// off, each on a thread of its own.
+#[cfg(not(any(target_os = "windows", feature = "native-http")))]
+fn multi_loop(
+    mut multi: impl MultiTransfers,
+    job_rx: mpsc::Receiver<Job>,
+    event_tx: mpsc::Sender<FetchEvent>,
+    cancelled_below: Arc<AtomicU64>,
+    fetch: fn(&str, Option<&RangeRequest>) -> Result<Transfer, String>,
+) {
+    let mut in_flight: std::collections::HashMap<u64, (RequestId, String, Instant, Instant)> =
+        std::collections::HashMap::new();
+    loop {
+        // Block on the queue only while nothing is being transferred.
+        let mut next = if multi.is_empty() {
+            match job_rx.recv() {
+                Ok(job) => Some(job),
+                Err(_) => return,
+            }
+        } else {
+            None
+        };
+        loop {
+            let job = match next.take().map_or_else(|| job_rx.try_recv(), Ok) {
+                Ok(job) => job,
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => return,
+            };
+            let Job::Fetch { id, url, queued_at } = job;
+            if id.as_u64() < cancelled_below.load(Ordering::Relaxed) {
+                continue;
+            }
+            let started_at = Instant::now();
+            match multi.add(id.as_u64(), &url) {
+                Ok(()) => {
+                    in_flight.insert(id.as_u64(), (id, url, queued_at, started_at));
+                }
+                Err(err) => send_response(&event_tx, id, url, queued_at, started_at, Err(err)),
+            }
+        }
+
+        let cancelled_before = cancelled_below.load(Ordering::Relaxed);
+        in_flight.retain(|&token, _| {
+            let cancelled = token < cancelled_before;
+            if cancelled {
+                multi.remove(token);
+            }
+            !cancelled
+        });
+
+        for (token, transfer) in multi.perform(MULTI_POLL_INTERVAL) {
+            let Some((id, url, queued_at, started_at)) = in_flight.remove(&token) else {
+                continue;
+            };
+            let transfer = match transfer {
+                Ok(transfer) => transfer,
+                Err(err) if super::limits::is_transient(&err) => {
+                    // Retrying waits first, so it also happens off this thread.
+                    let event_tx = event_tx.clone();
+                    std::thread::spawn(move || {
+                        let response = super::limits::retry(Err(err), || {
+                            super::resume::fetch_resumable(&url, fetch)
+                        });
+                        send_response(&event_tx, id, url, queued_at, started_at, response);
+                    });
+                    continue;
+                }
+                Err(err) => {
+                    send_response(&event_tx, id, url, queued_at, started_at, Err(err));
+                    continue;
+                }
+            };
+            if transfer.interrupted.is_none() {
+                let response = super::resume::resume_transfer(&url, transfer, fetch);
+                send_response(&event_tx, id, url, queued_at, started_at, response);
+                continue;
             }
+            // Asking for the rest blocks, so it happens off the thread the other transfers
+            // depend on.
+            let event_tx = event_tx.clone();
+            std::thread::spawn(move || {
+                let response = super::resume::resume_transfer(&url, transfer, fetch);
+                send_response(&event_tx, id, url, queued_at, started_at, response);
+            });
+        }
+    }
+}
+
+fn send_response(
+    event_tx: &mpsc::Sender<FetchEvent>,
+    id: RequestId,
+    url: String,
+    queued_at: Instant,
+    started_at: Instant,
+    response: Result<super::Response, String>,
+) {
+    let finished_at = Instant::now();
+    let response_at = response
+        .as_ref()
+        .ok()
+        .and_then(|response| response.time_to_first_byte)
+        .and_then(|ttfb| started_at.checked_add(ttfb))
+        .map(|response_at| response_at.min(finished_at));
+    let timing = FetchTiming {
+        queued_at,
+        started_at,
+        response_at,
+        finished_at,
+    };
+    let (result, tls) = match response {
+        Ok(response) => (Ok(response.body), response.tls),
+        Err(err) => (Err(err), None),
+    };
+    let _ = event_tx.send(FetchEvent {
+        id,
+        url,
+        result,
+        timing,
+        tls,
+    });
+}
+
+#[cfg(all(test, not(any(target_os = "windows", feature = "native-http"))))]
+mod tests {
+    use super::*;
+
+    /// Finishes transfers in the reverse of the order they were added, as `done` says, and
+    /// logs every call so tests can see what the loop asked for.
+    struct FakeMulti {
+        running: Vec<(u64, String)>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MultiTransfers for FakeMulti {
+        fn add(&mut self, token: u64, url: &str) -> Result<(), String> {
+            self.log.lock().unwrap().push(format!("add {token}"));
+            if url.ends_with("/bad") {
+                return Err("unsupported URL".to_owned());
+            }
+            self.running.push((token, url.to_owned()));
+            Ok(())
+        }
+
+        fn is_empty(&self) -> bool {
+            self.running.is_empty()
+        }
+
+        fn remove(&mut self, token: u64) {
+            self.log.lock().unwrap().push(format!("remove {token}"));
+            self.running.retain(|(running, _)| *running != token);
+        }
+
+        fn perform(&mut self, timeout: Duration) -> Vec<(u64, Result<Transfer, String>)> {
+            let (done, waiting) = std::mem::take(&mut self.running)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(_, url)| !url.ends_with("/slow"));
+            self.running = waiting;
+            if done.is_empty() {
+                std::thread::sleep(timeout);
+            }
+            done.into_iter()
+                .rev()
+                .map(|(token, url)| (token, done_transfer(&url)))
+                .collect()
+        }
+    }
+
+    fn transfer(status: u32, body: &[u8]) -> Transfer {
+        Transfer {
+            status,
+            body: body.to_vec(),
+            time_to_first_byte: None,
+            etag: Some("\"v1\"".to_owned()),
+            last_modified: None,
+            content_range: None,
+            encoded: false,
+            interrupted: None,
+            tls: None,
+        }
+    }
+
+    fn done_transfer(url: &str) -> Result<Transfer, String> {
+        if url.ends_with("/fail") {
+            return Err("HTTP 404".to_owned());
         }
+        let mut transfer = transfer(200, url.as_bytes());
+        if url.ends_with("/cut") {
+            transfer.body.truncate(4);
+            transfer.interrupted = Some("connection reset".to_owned());
+        }
+        Ok(transfer)
+    }
+
+    /// Held by a test to keep continuations from finishing.
+    static RESUME_GATE: Mutex<()> = Mutex::new(());
+
+    fn resume_fetch(url: &str, range: Option<&RangeRequest>) -> Result<Transfer, String> {
+        let _gate = RESUME_GATE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let start = range.map_or(0, |range| range.start);
+        let mut rest = transfer(206, &url.as_bytes()[start as usize..]);
+        rest.content_range = Some(format!("bytes {start}-{}/{}", url.len() - 1, url.len()));
+        Ok(rest)
+    }
+
+    struct Harness {
+        job_tx: mpsc::Sender<Job>,
+        event_rx: mpsc::Receiver<FetchEvent>,
+        cancelled_below: Arc<AtomicU64>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Harness {
+        fn start() -> Harness {
+            let (job_tx, job_rx) = mpsc::channel();
+            let (event_tx, event_rx) = mpsc::channel();
+            let cancelled_below = Arc::new(AtomicU64::new(0));
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let multi = FakeMulti {
+                running: Vec::new(),
+                log: Arc::clone(&log),
+            };
+            let cancelled = Arc::clone(&cancelled_below);
+            std::thread::spawn(move || {
+                multi_loop(multi, job_rx, event_tx, cancelled, resume_fetch)
+            });
+            Harness {
+                job_tx,
+                event_rx,
+                cancelled_below,
+                log,
+            }
+        }
+
+        fn fetch(&self, id: u64, path: &str) {
+            let job = Job::Fetch {
+                id: RequestId(id),
+                url: format!("http://example.com{path}"),
+                queued_at: Instant::now(),
+            };
+            self.job_tx.send(job).unwrap();
+        }
+
+        fn next_event(&self) -> FetchEvent {
+            self.event_rx.recv_timeout(Duration::from_secs(5)).unwrap()
+        }
+
+        fn wait_for_log(&self, line: &str) {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !self.log.lock().unwrap().iter().any(|logged| logged == line) {
+                assert!(Instant::now() < deadline, "never logged {line:?}");
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    #[test]
+    fn finished_transfers_are_answered_under_the_id_they_were_queued_with() {
+        let harness = Harness::start();
+        harness.fetch(1, "/slow");
+        harness.wait_for_log("add 1");
+        // Queued while a transfer runs, so the loop picks them up between polls.
+        harness.fetch(2, "/a");
+        harness.fetch(3, "/fail");
+        harness.fetch(4, "/bad");
+
+        let mut events: Vec<_> = (0..3).map(|_| harness.next_event()).collect();
+        events.sort_by_key(|event| event.id.as_u64());
+        let summary: Vec<_> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.id.as_u64(),
+                    event.url.as_str(),
+                    event
+                        .result
+                        .as_ref()
+                        .map(Vec::as_slice)
+                        .map_err(String::as_str),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, "http://example.com/a", Ok(&b"http://example.com/a"[..])),
+                (3, "http://example.com/fail", Err("HTTP 404")),
+                (4, "http://example.com/bad", Err("unsupported URL")),
+            ]
+        );
+        assert!(
+            events
+                .iter()
+                .all(|event| event.timing.started_at >= event.timing.queued_at)
+        );
+        assert!(harness.event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn cancelling_drops_running_transfers_and_skips_queued_ones() {
+        let harness = Harness::start();
+        harness.fetch(1, "/slow");
+        harness.wait_for_log("add 1");
+        harness.cancelled_below.store(3, Ordering::Relaxed);
+        harness.wait_for_log("remove 1");
+
+        harness.fetch(2, "/a");
+        harness.fetch(3, "/b");
+        let event = harness.next_event();
+        assert_eq!(event.id, RequestId(3));
+        assert!(
+            !harness
+                .log
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|line| line == "add 2")
+        );
+    }
+
+    #[test]
+    fn broken_off_transfers_are_continued_without_holding_up_the_others() {
+        let harness = Harness::start();
+        let gate = RESUME_GATE.lock().unwrap();
+        harness.fetch(1, "/cut");
+        harness.wait_for_log("add 1");
+        harness.fetch(2, "/a");
+        assert_eq!(harness.next_event().id, RequestId(2));
+
+        drop(gate);
+        let resumed = harness.next_event();
+        assert_eq!(resumed.id, RequestId(1));
+        assert_eq!(resumed.result.unwrap(), b"http://example.com/cut");
     }
 }
@@ -0,0 +1,208 @@
+use crate::url::{Scheme, Url};
+use std::sync::{OnceLock, RwLock};
+
+/// Port a proxy URL without one is reached on, as curl assumes.
+const DEFAULT_PROXY_PORT: u16 = 1080;
+
+/// Which proxies requests go through. Proxies are URLs such as `http://proxy:3128` or
+/// `socks5h://127.0.0.1:9050`; one without a scheme is an HTTP proxy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProxySettings {
+    pub http: Option<String>,
+    pub https: Option<String>,
+    /// Hosts reached directly. An entry also covers its subdomains, and `*` covers every host.
+    pub no_proxy: Vec<String>,
+}
+
+static OVERRIDE: RwLock<Option<ProxySettings>> = RwLock::new(None);
+
+/// Replaces the settings the environment gave for the rest of the process.
+pub fn set_proxy_settings(settings: ProxySettings) {
+    if let Ok(mut installed) = OVERRIDE.write() {
+        *installed = Some(settings);
+    }
+}
+
+/// The settings from [`set_proxy_settings`], or else from the environment.
+pub fn proxy_settings() -> ProxySettings {
+    if let Ok(installed) = OVERRIDE.read()
+        && let Some(settings) = installed.as_ref()
+    {
+        return settings.clone();
+    }
+    static FROM_ENV: OnceLock<ProxySettings> = OnceLock::new();
+    FROM_ENV.get_or_init(ProxySettings::from_env).clone()
+}
+
+impl ProxySettings {
+    /// Reads `http_proxy`, `https_proxy`, `all_proxy` and `no_proxy` as curl does. `HTTP_PROXY`
+    /// is left out, since CGI servers set it from a request header.
+    pub fn from_env() -> ProxySettings {
+        let var = |names: &[&str]| {
+            names
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .map(|value| value.trim().to_owned())
+                .find(|value| !value.is_empty())
+        };
+        let all = var(&["all_proxy", "ALL_PROXY"]);
+        ProxySettings {
+            http: var(&["http_proxy"]).or_else(|| all.clone()),
+            https: var(&["https_proxy", "HTTPS_PROXY"]).or(all),
+            no_proxy: var(&["no_proxy", "NO_PROXY"])
+                .map(|list| parse_no_proxy(&list))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The proxy to fetch `url` through, or `None` to connect directly.
+    pub fn proxy_for(&self, url: &Url) -> Option<&str> {
+        let proxy = match url.scheme() {
+            Scheme::Http => self.http.as_deref(),
+            Scheme::Https => self.https.as_deref(),
+        }?;
+        (!self.bypasses(url.host())).then_some(proxy)
+    }
+
+    fn bypasses(&self, host: &str) -> bool {
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        self.no_proxy.iter().any(|entry| {
+            entry == "*"
+                || host.eq_ignore_ascii_case(entry)
+                || host
+                    .len()
+                    .checked_sub(entry.len() + 1)
+                    .is_some_and(|split| {
+                        host.as_bytes()[split] == b'.'
+                            && host[split + 1..].eq_ignore_ascii_case(entry)
+                    })
+        })
+    }
+}
+
+/// Splits a comma-separated `no_proxy` list into hosts, leaving out leading dots and ports.
+pub fn parse_no_proxy(list: &str) -> Vec<String> {
+    list.split([',', ' '])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let entry = entry.trim_start_matches('.');
+            let entry = match entry.rsplit_once(':') {
+                Some((host, port))
+                    if !host.contains(':') && port.bytes().all(|b| b.is_ascii_digit()) =>
+                {
+                    host
+                }
+                _ => entry,
+            };
+            entry
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_owned()
+        })
+        .collect()
+}
+
+/// How a proxy is spoken to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ProxyKind {
+    /// Sent the full URL in the request line.
+    Http,
+    /// Asked to connect to an address resolved here.
+    Socks5,
+    /// Asked to connect to a host name it resolves itself.
+    Socks5Hostname,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Proxy {
+    pub(super) kind: ProxyKind,
+    pub(super) host: String,
+    pub(super) port: u16,
+}
+
+impl Proxy {
+    pub(super) fn parse(proxy: &str) -> Result<Proxy, String> {
+        let (scheme, rest) = proxy.split_once("://").unwrap_or(("http", proxy));
+        let kind = match scheme.to_ascii_lowercase().as_str() {
+            "http" => ProxyKind::Http,
+            "socks5" => ProxyKind::Socks5,
+            "socks5h" => ProxyKind::Socks5Hostname,
+            _ => return Err(format!("Unsupported proxy scheme in {proxy:?}")),
+        };
+        let authority = rest.split('/').next().unwrap_or_default();
+        let authority = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, host_and_port)| host_and_port);
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => (
+                host,
+                port.parse()
+                    .map_err(|_| format!("Invalid proxy port in {proxy:?}"))?,
+            ),
+            _ => (authority, DEFAULT_PROXY_PORT),
+        };
+        if host.is_empty() {
+            return Err(format!("Invalid proxy {proxy:?}: missing host"));
+        }
+        Ok(Proxy {
+            kind,
+            host: host.to_ascii_lowercase(),
+            port,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Proxy, ProxyKind, ProxySettings, parse_no_proxy};
+    use crate::url::Url;
+
+    #[test]
+    fn no_proxy_hosts_and_their_subdomains_are_reached_directly() {
+        let settings = ProxySettings {
+            http: Some("proxy:3128".to_owned()),
+            https: None,
+            no_proxy: parse_no_proxy("localhost, .internal.example:8080,10.0.0.1"),
+        };
+        let proxy_for = |url: &str| {
+            settings
+                .proxy_for(&Url::parse(url).unwrap())
+                .map(str::to_owned)
+        };
+        assert_eq!(
+            proxy_for("http://example.com/"),
+            Some("proxy:3128".to_owned())
+        );
+        assert_eq!(proxy_for("https://example.com/"), None);
+        assert_eq!(proxy_for("http://localhost:8000/"), None);
+        assert_eq!(proxy_for("http://wiki.internal.example/"), None);
+        assert_eq!(
+            proxy_for("http://notinternal.example/"),
+            Some("proxy:3128".to_owned())
+        );
+        assert_eq!(proxy_for("http://10.0.0.1/"), None);
+    }
+
+    #[test]
+    fn parses_proxy_urls() {
+        assert_eq!(
+            Proxy::parse("proxy.corp:3128").unwrap(),
+            Proxy {
+                kind: ProxyKind::Http,
+                host: "proxy.corp".to_owned(),
+                port: 3128,
+            }
+        );
+        assert_eq!(
+            Proxy::parse("socks5h://user:pw@127.0.0.1:9050/").unwrap(),
+            Proxy {
+                kind: ProxyKind::Socks5Hostname,
+                host: "127.0.0.1".to_owned(),
+                port: 9050,
+            }
+        );
+        assert_eq!(Proxy::parse("socks5://[::1]").unwrap().port, 1080);
+        assert!(Proxy::parse("ftp://proxy").is_err());
+    }
+}
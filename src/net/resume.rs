@@ -0,0 +1,272 @@
+use super::limits::status_error;
+use super::{Response, TlsInfo};
+use std::time::Duration;
+
+/// How many times a transfer that broke off part-way is continued before giving up.
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+
+/// Asks the backend for the rest of a representation, starting at byte `start`. `if_range` is
+/// the validator from the earlier response; when the resource has changed since, the server
+/// answers with the full new representation instead of a 206.
+pub(super) struct RangeRequest {
+    pub(super) start: u64,
+    pub(super) if_range: String,
+}
+
+/// One HTTP exchange as seen by a backend, after redirects.
+pub(super) struct Transfer {
+    pub(super) status: u32,
+    pub(super) body: Vec<u8>,
+    pub(super) time_to_first_byte: Option<Duration>,
+    pub(super) etag: Option<String>,
+    pub(super) last_modified: Option<String>,
+    pub(super) content_range: Option<String>,
+    /// Content-Encoding other than identity. Byte offsets into a decoded body do not line up
+    /// with the encoded bytes a range request would address, so such bodies are not resumed.
+    pub(super) encoded: bool,
+    /// Set when the connection failed after the headers arrived; `body` holds what was read.
+    pub(super) interrupted: Option<String>,
+    /// For `https://` responses, what the backend could find out about the connection.
+    pub(super) tls: Option<TlsInfo>,
+}
+
+/// Runs `fetch` and, while the transfer keeps breaking off, asks for the remaining bytes with
+/// `Range` and `If-Range`. A 206 whose Content-Range starts where the body ends is appended;
+/// a 200 replaces the body, since the server either ignored the range or the resource changed;
+/// any other answer fails the transfer.
+pub(super) fn fetch_resumable(
+    url: &str,
+    mut fetch: impl FnMut(&str, Option<&RangeRequest>) -> Result<Transfer, String>,
+) -> Result<Response, String> {
+    let first = fetch(url, None)?;
+    resume_transfer(url, first, fetch)
+}
+
+/// Continues `first`, a transfer of `url` made without a range, as `fetch_resumable` does.
+pub(super) fn resume_transfer(
+    url: &str,
+    first: Transfer,
+    mut fetch: impl FnMut(&str, Option<&RangeRequest>) -> Result<Transfer, String>,
+) -> Result<Response, String> {
+    let time_to_first_byte = first.time_to_first_byte;
+    let mut validator = if_range_validator(&first);
+    let tls = first.tls;
+    let mut body = first.body;
+    let mut interrupted = first.interrupted;
+
+    let mut attempts = 0;
+    while let Some(err) = interrupted.take() {
+        let Some(if_range) = validator.clone() else {
+            return Err(err);
+        };
+        if attempts == MAX_RESUME_ATTEMPTS {
+            return Err(err);
+        }
+        attempts += 1;
+
+        let range = RangeRequest {
+            start: body.len() as u64,
+            if_range,
+        };
+        let next = match fetch(url, Some(&range)) {
+            Ok(next) => next,
+            Err(err) => {
+                interrupted = Some(err);
+                continue;
+            }
+        };
+
+        match next.status {
+            206 => {
+                if next.content_range.as_deref().and_then(content_range_start) != Some(range.start)
+                {
+                    return Err(format!("Mismatched Content-Range resuming {url}"));
+                }
+                body.extend_from_slice(&next.body);
+            }
+            200 => {
+                validator = if_range_validator(&next);
+                body = next.body;
+            }
+            status => return Err(status_error(status, url)),
+        }
+        interrupted = next.interrupted;
+    }
+
+    Ok(Response {
+        body,
+        time_to_first_byte,
+        tls,
+    })
+}
+
+/// `If-Range` only accepts a strong ETag or a Last-Modified date.
+fn if_range_validator(transfer: &Transfer) -> Option<String> {
+    if transfer.encoded || !matches!(transfer.status, 200 | 206) {
+        return None;
+    }
+    if let Some(etag) = transfer.etag.as_deref().map(str::trim)
+        && !etag.is_empty()
+    {
+        return (!etag.starts_with("W/")).then(|| etag.to_owned());
+    }
+    transfer
+        .last_modified
+        .as_deref()
+        .map(str::trim)
+        .filter(|date| !date.is_empty())
+        .map(str::to_owned)
+}
+
+/// Parses the first byte position of a `Content-Range: bytes <first>-<last>/<length>` header.
+fn content_range_start(header: &str) -> Option<u64> {
+    let (unit, range) = header.trim().split_once(' ')?;
+    if !unit.eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let (span, _length) = range.trim_start().split_once('/')?;
+    let (first, last) = span.split_once('-')?;
+    let first: u64 = first.parse().ok()?;
+    let last: u64 = last.parse().ok()?;
+    (first <= last).then_some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(status: u32, body: &[u8]) -> Transfer {
+        Transfer {
+            status,
+            body: body.to_vec(),
+            time_to_first_byte: None,
+            etag: Some("\"v1\"".to_owned()),
+            last_modified: None,
+            content_range: None,
+            encoded: false,
+            interrupted: None,
+            tls: None,
+        }
+    }
+
+    fn interrupted(mut transfer: Transfer) -> Transfer {
+        transfer.interrupted = Some("connection reset".to_owned());
+        transfer
+    }
+
+    #[test]
+    fn parses_content_range_start() {
+        assert_eq!(content_range_start("bytes 100-199/200"), Some(100));
+        assert_eq!(content_range_start("bytes 0-9/*"), Some(0));
+        assert_eq!(content_range_start("bytes */200"), None);
+        assert_eq!(content_range_start("items 1-2/3"), None);
+        assert_eq!(content_range_start("bytes 9-1/10"), None);
+    }
+
+    #[test]
+    fn if_range_needs_a_strong_validator() {
+        assert_eq!(
+            if_range_validator(&transfer(200, b"")),
+            Some("\"v1\"".to_owned())
+        );
+
+        let mut weak = transfer(200, b"");
+        weak.etag = Some("W/\"v1\"".to_owned());
+        assert_eq!(if_range_validator(&weak), None);
+
+        let mut dated = transfer(200, b"");
+        dated.etag = None;
+        dated.last_modified = Some("Wed, 21 Oct 2015 07:28:00 GMT".to_owned());
+        assert_eq!(
+            if_range_validator(&dated),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT".to_owned())
+        );
+
+        let mut gzipped = transfer(200, b"");
+        gzipped.encoded = true;
+        assert_eq!(if_range_validator(&gzipped), None);
+    }
+
+    #[test]
+    fn resumes_from_where_the_body_stopped() {
+        let mut requests: Vec<Option<(u64, String)>> = Vec::new();
+        let response = fetch_resumable("http://example.com/big", |_, range| {
+            requests.push(range.map(|range| (range.start, range.if_range.clone())));
+            Ok(match requests.len() {
+                1 => interrupted(transfer(200, b"hello ")),
+                2 => {
+                    let mut next = interrupted(transfer(206, b"wor"));
+                    next.content_range = Some("bytes 6-10/11".to_owned());
+                    next
+                }
+                _ => {
+                    let mut next = transfer(206, b"ld");
+                    next.content_range = Some("bytes 9-10/11".to_owned());
+                    next
+                }
+            })
+        })
+        .unwrap();
+        assert_eq!(response.body, b"hello world");
+        assert_eq!(
+            requests,
+            vec![
+                None,
+                Some((6, "\"v1\"".to_owned())),
+                Some((9, "\"v1\"".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn restarts_when_the_resource_changed() {
+        let mut ranges = Vec::new();
+        let response = fetch_resumable("http://example.com/big", |_, range| {
+            ranges.push(range.map(|range| range.start));
+            Ok(match ranges.len() {
+                1 => interrupted(transfer(200, b"old par")),
+                2 => interrupted(transfer(200, b"new")),
+                _ => {
+                    let mut next = transfer(206, b" body");
+                    next.content_range = Some("bytes 3-7/8".to_owned());
+                    next
+                }
+            })
+        })
+        .unwrap();
+        assert_eq!(response.body, b"new body");
+        assert_eq!(ranges, vec![None, Some(7), Some(3)]);
+    }
+
+    #[test]
+    fn fails_on_any_other_answer_to_a_range() {
+        let err = fetch_resumable("http://example.com/big", |_, range| {
+            Ok(match range {
+                None => interrupted(transfer(200, b"part")),
+                Some(_) => transfer(204, b""),
+            })
+        });
+        assert_eq!(err.err(), Some(status_error(204, "http://example.com/big")));
+    }
+
+    #[test]
+    fn gives_up_without_a_validator_or_after_repeated_failures() {
+        let err = fetch_resumable("http://example.com/big", |_, _| {
+            let mut first = interrupted(transfer(200, b"part"));
+            first.etag = None;
+            Ok(first)
+        });
+        assert_eq!(err.err().as_deref(), Some("connection reset"));
+
+        let mut calls = 0;
+        let err = fetch_resumable("http://example.com/big", |_, range| {
+            calls += 1;
+            match range {
+                None => Ok(interrupted(transfer(200, b"part"))),
+                Some(_) => Err("timed out".to_owned()),
+            }
+        });
+        assert_eq!(err.err().as_deref(), Some("timed out"));
+        assert_eq!(calls, 1 + MAX_RESUME_ATTEMPTS);
+    }
+}
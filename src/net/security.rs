@@ -0,0 +1,120 @@
+use crate::url::{Scheme, Url};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// What the TLS connection of an `https://` response reported about itself and the server's
+/// certificate. Fields the backend couldn't find out are `None`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsInfo {
+    /// The negotiated protocol version, such as `TLSv1.3`.
+    pub protocol: Option<String>,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    /// When the certificate became and stops being valid, as the TLS library writes dates.
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+}
+
+/// What happens to an `http://` stylesheet or image on an `https://` page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MixedContentPolicy {
+    /// Fetch it over `https://` instead.
+    #[default]
+    Upgrade,
+    /// Leave it out of the page.
+    Block,
+    /// Fetch it as written; the page is then no longer shown as secure.
+    Allow,
+}
+
+impl MixedContentPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "upgrade" => Some(MixedContentPolicy::Upgrade),
+            "block" => Some(MixedContentPolicy::Block),
+            "allow" => Some(MixedContentPolicy::Allow),
+            _ => None,
+        }
+    }
+
+    /// Where a subresource of `page` is fetched from, or `None` when it is blocked.
+    pub fn apply(self, page: &Url, resource: Url) -> Option<Url> {
+        if !is_mixed_content(page, &resource) {
+            return Some(resource);
+        }
+        match self {
+            MixedContentPolicy::Upgrade => Some(resource.upgraded_to_https()),
+            MixedContentPolicy::Block => None,
+            MixedContentPolicy::Allow => Some(resource),
+        }
+    }
+}
+
+/// Whether fetching `resource` for `page` would undo the page's `https://`.
+pub fn is_mixed_content(page: &Url, resource: &Url) -> bool {
+    page.scheme() == Scheme::Https && resource.scheme() == Scheme::Http
+}
+
+/// 0 until [`set_mixed_content_policy`] runs, then 1 + the policy's position.
+static MIXED_CONTENT_POLICY: AtomicU8 = AtomicU8::new(0);
+
+/// Overrides the `OAB_MIXED_CONTENT` environment variable for the rest of the process.
+pub fn set_mixed_content_policy(policy: MixedContentPolicy) {
+    let value = match policy {
+        MixedContentPolicy::Upgrade => 1,
+        MixedContentPolicy::Block => 2,
+        MixedContentPolicy::Allow => 3,
+    };
+    MIXED_CONTENT_POLICY.store(value, Ordering::Relaxed);
+}
+
+pub fn mixed_content_policy() -> MixedContentPolicy {
+    match MIXED_CONTENT_POLICY.load(Ordering::Relaxed) {
+        1 => MixedContentPolicy::Upgrade,
+        2 => MixedContentPolicy::Block,
+        3 => MixedContentPolicy::Allow,
+        _ => mixed_content_policy_from_env(),
+    }
+}
+
+fn mixed_content_policy_from_env() -> MixedContentPolicy {
+    static FROM_ENV: OnceLock<MixedContentPolicy> = OnceLock::new();
+    *FROM_ENV.get_or_init(|| {
+        std::env::var("OAB_MIXED_CONTENT")
+            .ok()
+            .and_then(|value| MixedContentPolicy::parse(&value))
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MixedContentPolicy;
+    use crate::url::Url;
+
+    #[test]
+    fn only_http_subresources_of_https_pages_are_mixed() {
+        let secure = Url::parse("https://example.com/").unwrap();
+        let plain = Url::parse("http://example.com/").unwrap();
+        let image = Url::parse("http://cdn.example.com:8080/a.png?x=1").unwrap();
+
+        assert_eq!(
+            MixedContentPolicy::Upgrade
+                .apply(&secure, image.clone())
+                .map(|url| url.as_str().to_owned()),
+            Some("https://cdn.example.com:8080/a.png?x=1".to_owned())
+        );
+        assert_eq!(
+            MixedContentPolicy::Block.apply(&secure, image.clone()),
+            None
+        );
+        assert_eq!(
+            MixedContentPolicy::Allow.apply(&secure, image.clone()),
+            Some(image.clone())
+        );
+        assert_eq!(
+            MixedContentPolicy::Block.apply(&plain, image.clone()),
+            Some(image)
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use super::FetchTiming;
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: usize = 32;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WaterfallEntry {
+    pub kind: &'static str,
+    pub url: String,
+    pub ok: bool,
+    pub bytes: usize,
+    pub timing: FetchTiming,
+    pub decoded_at: Option<Instant>,
+}
+
+impl WaterfallEntry {
+    pub fn done_at(&self) -> Instant {
+        self.decoded_at
+            .map_or(self.timing.finished_at, |decoded_at| {
+                decoded_at.max(self.timing.finished_at)
+            })
+    }
+
+    pub fn decoding(&self) -> Duration {
+        self.done_at()
+            .saturating_duration_since(self.timing.finished_at)
+    }
+}
+
+/// Where one entry's phases begin and end, as columns out of the width given to
+/// [`Waterfall::bars`]: queued from `queued` to `started`, connecting until `response`,
+/// downloading until `finished` and decoding until `done`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaterfallBar {
+    pub queued: usize,
+    pub started: usize,
+    pub response: usize,
+    pub finished: usize,
+    pub done: usize,
+    /// Set for the [`Waterfall::critical_entry`].
+    pub critical: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Waterfall {
+    entries: Vec<WaterfallEntry>,
+}
+
+impl Waterfall {
+    pub fn new() -> Waterfall {
+        Waterfall::default()
+    }
+
+    pub fn push(&mut self, entry: WaterfallEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = WaterfallEntry>) {
+        self.entries.extend(entries);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[WaterfallEntry] {
+        &self.entries
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.bytes as u64).sum()
+    }
+
+    /// Fills in when each fetched entry not yet known to be decoded was, from `decoded_at` by
+    /// URL. Returns whether any entry changed.
+    pub fn mark_decoded(&mut self, decoded_at: impl Fn(&str) -> Option<Instant>) -> bool {
+        let mut changed = false;
+        for entry in &mut self.entries {
+            if entry.ok
+                && entry.decoded_at.is_none()
+                && let Some(at) = decoded_at(&entry.url)
+            {
+                entry.decoded_at = Some(at);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// The entry that finished last, i.e. the one readiness was waiting on.
+    pub fn critical_entry(&self) -> Option<&WaterfallEntry> {
+        self.entries.iter().max_by_key(|entry| entry.done_at())
+    }
+
+    /// One bar per entry, spread over `width` columns from when the first entry was queued to
+    /// when the last was done.
+    pub fn bars(&self, width: usize) -> Vec<WaterfallBar> {
+        let Some(origin) = self
+            .entries
+            .iter()
+            .map(|entry| entry.timing.queued_at)
+            .min()
+        else {
+            return Vec::new();
+        };
+        let end = self
+            .entries
+            .iter()
+            .map(WaterfallEntry::done_at)
+            .max()
+            .unwrap_or(origin);
+        let span = end
+            .saturating_duration_since(origin)
+            .max(Duration::from_millis(1));
+        let critical = self.critical_entry();
+        let column = |at: Instant| -> usize {
+            let offset = at.saturating_duration_since(origin).as_secs_f64();
+            let column = (offset / span.as_secs_f64() * width as f64).round() as usize;
+            column.min(width)
+        };
+        self.entries
+            .iter()
+            .map(|entry| {
+                let timing = &entry.timing;
+                WaterfallBar {
+                    queued: column(timing.queued_at),
+                    started: column(timing.started_at),
+                    response: column(timing.response_at.unwrap_or(timing.finished_at)),
+                    finished: column(timing.finished_at),
+                    done: column(entry.done_at()),
+                    critical: critical.is_some_and(|critical| std::ptr::eq(critical, entry)),
+                }
+            })
+            .collect()
+    }
+
+    pub fn render_lines(&self) -> Vec<String> {
+        let Some(origin) = self
+            .entries
+            .iter()
+            .map(|entry| entry.timing.queued_at)
+            .min()
+        else {
+            return Vec::new();
+        };
+
+        let mut lines = Vec::with_capacity(self.entries.len());
+        for (entry, bar_columns) in self.entries.iter().zip(self.bars(BAR_WIDTH)) {
+            let timing = &entry.timing;
+            let phases = [
+                (bar_columns.started, '.'),
+                (bar_columns.response, '-'),
+                (bar_columns.finished, '='),
+                (bar_columns.done, '#'),
+            ];
+
+            let mut bar = String::with_capacity(BAR_WIDTH);
+            let mut filled = bar_columns.queued;
+            bar.extend(std::iter::repeat_n(' ', filled));
+            for (until, ch) in phases {
+                if until > filled {
+                    bar.extend(std::iter::repeat_n(ch, until - filled));
+                    filled = until;
+                }
+            }
+            bar.extend(std::iter::repeat_n(' ', BAR_WIDTH - filled));
+
+            let marker = if bar_columns.critical { '*' } else { ' ' };
+            let status = if entry.ok { "ok" } else { "err" };
+            let mut line = String::new();
+            let _ = write!(
+                &mut line,
+                "{marker}{:<4} |{bar}| start={} q={} c={} d={} p={} {status} {}",
+                entry.kind,
+                duration_ms(timing.queued_at.saturating_duration_since(origin)),
+                duration_ms(timing.queued()),
+                duration_ms(timing.connecting()),
+                duration_ms(timing.downloading()),
+                duration_ms(entry.decoding()),
+                entry.url,
+            );
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    duration.as_millis().try_into().unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &'static str, origin: Instant, offsets_ms: [u64; 5]) -> WaterfallEntry {
+        let at = |ms: u64| origin + Duration::from_millis(ms);
+        WaterfallEntry {
+            kind,
+            url: format!("https://example.com/{kind}"),
+            ok: true,
+            bytes: 0,
+            timing: FetchTiming {
+                queued_at: at(offsets_ms[0]),
+                started_at: at(offsets_ms[1]),
+                response_at: Some(at(offsets_ms[2])),
+                finished_at: at(offsets_ms[3]),
+            },
+            decoded_at: Some(at(offsets_ms[4])),
+        }
+    }
+
+    #[test]
+    fn marks_the_last_finishing_entry_as_critical() {
+        let origin = Instant::now();
+        let mut waterfall = Waterfall::new();
+        waterfall.push(entry("html", origin, [0, 0, 10, 20, 24]));
+        waterfall.push(entry("css", origin, [24, 30, 60, 90, 100]));
+        waterfall.push(entry("img", origin, [30, 40, 50, 60, 60]));
+
+        assert_eq!(waterfall.critical_entry().unwrap().kind, "css");
+
+        let lines = waterfall.render_lines();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("*css "));
+        assert!(lines[0].starts_with(" html"));
+        assert!(lines[1].contains("q=6 c=30 d=30 p=10"));
+    }
+
+    #[test]
+    fn decode_times_fill_in_fetched_entries_only() {
+        let origin = Instant::now();
+        let mut waterfall = Waterfall::new();
+        let mut fetched = entry("img", origin, [0, 10, 20, 30, 0]);
+        fetched.decoded_at = None;
+        let failed = WaterfallEntry {
+            ok: false,
+            url: "https://example.com/broken".to_owned(),
+            ..fetched.clone()
+        };
+        waterfall.push(fetched);
+        waterfall.push(failed);
+
+        let decoded = origin + Duration::from_millis(45);
+        assert!(waterfall.mark_decoded(|_| Some(decoded)));
+        assert_eq!(waterfall.entries()[0].decoded_at, Some(decoded));
+        assert_eq!(waterfall.entries()[1].decoded_at, None);
+        assert_eq!(waterfall.entries()[0].decoding(), Duration::from_millis(15));
+        assert!(!waterfall.mark_decoded(|_| Some(origin)));
+    }
+
+    #[test]
+    fn bars_span_the_full_width() {
+        let origin = Instant::now();
+        let mut waterfall = Waterfall::new();
+        waterfall.push(entry("html", origin, [0, 10, 20, 30, 40]));
+
+        let line = &waterfall.render_lines()[0];
+        let bar = line.split('|').nth(1).unwrap();
+        assert_eq!(bar.chars().count(), BAR_WIDTH);
+        assert_eq!(bar, "........--------========########");
+    }
+}
@@ -1,5 +1,10 @@
+use super::limits::{connection_error, status_error};
+use super::proxy::{Proxy, ProxyKind};
+use super::resume::{RangeRequest, Transfer};
+use super::{ProxySettings, RequestHeaders, TlsInfo};
 use crate::url::{Scheme, Url};
 use core::ffi::c_void;
+use std::time::Instant;
 
 type Bool = i32;
 type DWORD = u32;
@@ -8,13 +13,16 @@ type InternetPort = u16;
 
 const TRUE: Bool = 1;
 
-const MAX_DOWNLOAD_BYTES: usize = 10 * 1024 * 1024;
 const MAX_REDIRECTS: usize = 10;
 
 const WINHTTP_ACCESS_TYPE_DEFAULT_PROXY: DWORD = 0;
+const WINHTTP_ACCESS_TYPE_NAMED_PROXY: DWORD = 3;
 
 const WINHTTP_FLAG_SECURE: DWORD = 0x0080_0000;
 
+const ERROR_WINHTTP_CANNOT_CONNECT: DWORD = 12029;
+const ERROR_WINHTTP_CONNECTION_ERROR: DWORD = 12030;
+
 const WINHTTP_OPTION_REDIRECT_POLICY: DWORD = 88;
 const WINHTTP_OPTION_REDIRECT_POLICY_NEVER: DWORD = 0;
 
@@ -22,12 +30,58 @@ const WINHTTP_OPTION_DECOMPRESSION: DWORD = 118;
 const WINHTTP_DECOMPRESSION_FLAG_GZIP: DWORD = 0x0000_0001;
 const WINHTTP_DECOMPRESSION_FLAG_DEFLATE: DWORD = 0x0000_0002;
 
+const WINHTTP_OPTION_SECURITY_CERTIFICATE_STRUCT: DWORD = 32;
+const WINHTTP_OPTION_SECURITY_INFO: DWORD = 151;
+
+const SP_PROT_TLS1_0: DWORD = 0x0000_00c0;
+const SP_PROT_TLS1_1: DWORD = 0x0000_0300;
+const SP_PROT_TLS1_2: DWORD = 0x0000_0c00;
+const SP_PROT_TLS1_3: DWORD = 0x0000_3000;
+
 const WINHTTP_QUERY_STATUS_CODE: DWORD = 19;
 const WINHTTP_QUERY_LOCATION: DWORD = 33;
+const WINHTTP_QUERY_CUSTOM: DWORD = 65535;
 const WINHTTP_QUERY_FLAG_NUMBER: DWORD = 0x2000_0000;
 
 const ERROR_INSUFFICIENT_BUFFER: DWORD = 122;
 
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct FileTime {
+    low: DWORD,
+    high: DWORD,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct SystemTime {
+    year: u16,
+    month: u16,
+    _day_of_week: u16,
+    day: u16,
+    hour: u16,
+    minute: u16,
+    second: u16,
+    _milliseconds: u16,
+}
+
+/// `WINHTTP_CERTIFICATE_INFO`; WinHTTP allocates the strings, which are freed with `LocalFree`.
+#[repr(C)]
+struct CertificateInfo {
+    expiry: FileTime,
+    start: FileTime,
+    subject_info: *mut u16,
+    issuer_info: *mut u16,
+    protocol_name: *mut u16,
+    signature_alg_name: *mut u16,
+    encryption_alg_name: *mut u16,
+    _key_size: DWORD,
+}
+
+/// Room for `WINHTTP_SECURITY_INFO`, which starts with the `dwProtocol` of a
+/// `SecPkgContext_ConnectionInfo`.
+const SECURITY_INFO_DWORDS: usize = 256;
+
 #[link(name = "winhttp")]
 unsafe extern "system" {
     fn WinHttpOpen(
@@ -91,11 +145,19 @@ unsafe extern "system" {
         buffer: *const c_void,
         size: DWORD,
     ) -> Bool;
+    fn WinHttpQueryOption(
+        handle: HInternet,
+        option: DWORD,
+        buffer: *mut c_void,
+        buffer_len: *mut DWORD,
+    ) -> Bool;
 }
 
 #[link(name = "kernel32")]
 unsafe extern "system" {
     fn GetLastError() -> DWORD;
+    fn LocalFree(memory: *mut c_void) -> *mut c_void;
+    fn FileTimeToSystemTime(file_time: *const FileTime, system_time: *mut SystemTime) -> Bool;
     fn FormatMessageW(
         flags: DWORD,
         source: *const c_void,
@@ -107,14 +169,22 @@ unsafe extern "system" {
     ) -> DWORD;
 }
 
-pub(super) fn fetch_url_bytes(url: &str) -> Result<Vec<u8>, String> {
+pub(super) fn fetch_url(url: &str, range: Option<&RangeRequest>) -> Result<Transfer, String> {
+    let started = Instant::now();
     let mut current = Url::parse(url).map_err(|err| format!("Invalid URL {url:?}: {err}"))?;
 
-    let session = WinHttpHandle::open("one-agent-one-browser/0.1")?;
-    session.set_timeouts(5_000, 5_000, 15_000, 15_000)?;
+    let request_headers = super::request_headers();
+    let session = WinHttpHandle::open(
+        request_headers.user_agent(),
+        winhttp_proxy(&super::proxy_settings())?,
+    )?;
+    let limits = super::fetch_limits();
+    let connect_ms = i32::try_from(limits.connect_timeout.as_millis()).unwrap_or(i32::MAX);
+    let read_ms = i32::try_from(limits.read_timeout.as_millis()).unwrap_or(i32::MAX);
+    session.set_timeouts(connect_ms, connect_ms, read_ms, read_ms)?;
 
     for redirect in 0..=MAX_REDIRECTS {
-        let response = fetch_once(&session, &current)?;
+        let response = fetch_once(&session, &current, range, &request_headers)?;
 
         if is_redirect_status(response.status_code) {
             if redirect == MAX_REDIRECTS {
@@ -138,14 +208,24 @@ pub(super) fn fetch_url_bytes(url: &str) -> Result<Vec<u8>, String> {
         }
 
         if (200..=399).contains(&response.status_code) {
-            return Ok(response.body);
+            return Ok(Transfer {
+                status: response.status_code,
+                body: response.body,
+                time_to_first_byte: Some(
+                    response
+                        .headers_received_at
+                        .saturating_duration_since(started),
+                ),
+                etag: response.etag,
+                last_modified: response.last_modified,
+                content_range: response.content_range,
+                encoded: response.encoded,
+                interrupted: response.interrupted,
+                tls: response.tls,
+            });
         }
 
-        return Err(format!(
-            "Unexpected HTTP status {} fetching {}",
-            response.status_code,
-            current.as_str()
-        ));
+        return Err(status_error(response.status_code, current.as_str()));
     }
 
     Err(format!("Too many redirects fetching {}", current.as_str()))
@@ -154,10 +234,22 @@ pub(super) fn fetch_url_bytes(url: &str) -> Result<Vec<u8>, String> {
 struct FetchResponse {
     status_code: u32,
     location: Option<String>,
+    headers_received_at: Instant,
     body: Vec<u8>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_range: Option<String>,
+    encoded: bool,
+    interrupted: Option<String>,
+    tls: Option<TlsInfo>,
 }
 
-fn fetch_once(session: &WinHttpHandle, url: &Url) -> Result<FetchResponse, String> {
+fn fetch_once(
+    session: &WinHttpHandle,
+    url: &Url,
+    range: Option<&RangeRequest>,
+    request_headers: &RequestHeaders,
+) -> Result<FetchResponse, String> {
     let host = url.host();
     let host_w = wide_null_terminated(host);
     let path_w = wide_null_terminated(url.path_and_query());
@@ -178,13 +270,25 @@ fn fetch_once(session: &WinHttpHandle, url: &Url) -> Result<FetchResponse, Strin
 
     request.set_redirect_policy_never()?;
 
+    let mut headers = String::new();
+    for line in request_headers.lines() {
+        headers.push_str(&line);
+        headers.push_str("\r\n");
+    }
     if !request.enable_decompression()? {
         // Ensure we can still parse text payloads by opting out of compression.
-        request.send(Some("Accept-Encoding: identity\r\n"))?;
-    } else {
-        request.send(None)?;
+        headers.push_str("Accept-Encoding: identity\r\n");
     }
+    if let Some(range) = range {
+        headers.push_str(&format!(
+            "Range: bytes={}-\r\nIf-Range: {}\r\n",
+            range.start, range.if_range
+        ));
+    }
+    request.send((!headers.is_empty()).then_some(headers.as_str()))?;
     request.receive_response()?;
+    let headers_received_at = Instant::now();
+    let tls = (url.scheme() == Scheme::Https).then(|| request.query_tls_info());
 
     let status_code = request.query_status_code()?;
     let location = if is_redirect_status(status_code) {
@@ -193,16 +297,37 @@ fn fetch_once(session: &WinHttpHandle, url: &Url) -> Result<FetchResponse, Strin
         None
     };
 
-    let body = if is_redirect_status(status_code) {
-        Vec::new()
+    let etag = request.query_custom_header("ETag")?;
+    let last_modified = request.query_custom_header("Last-Modified")?;
+    let content_range = request.query_custom_header("Content-Range")?;
+    let encoded = request
+        .query_custom_header("Content-Encoding")?
+        .is_some_and(|encoding| !encoding.trim().eq_ignore_ascii_case("identity"));
+
+    let mut body = Vec::new();
+    let interrupted = if is_redirect_status(status_code) {
+        None
     } else {
-        request.read_to_end(MAX_DOWNLOAD_BYTES)?
+        let already_read = range.map_or(0, |range| range.start);
+        let max_bytes = super::resource_limits()
+            .max_response_bytes
+            .saturating_sub(usize::try_from(already_read).unwrap_or(usize::MAX));
+        request
+            .read_to_end(&mut body, max_bytes)?
+            .map(|err| format!("Failed to fetch {}: {err}", url.as_str()))
     };
 
     Ok(FetchResponse {
         status_code,
         location,
+        headers_received_at,
         body,
+        etag,
+        last_modified,
+        content_range,
+        encoded,
+        interrupted,
+        tls,
     })
 }
 
@@ -210,19 +335,67 @@ fn is_redirect_status(status: u32) -> bool {
     matches!(status, 301 | 302 | 303 | 307 | 308)
 }
 
+/// The proxy list and bypass list WinHTTP takes for `settings`, or `None` to use the system's
+/// proxy configuration. WinHTTP speaks only to HTTP proxies.
+fn winhttp_proxy(settings: &ProxySettings) -> Result<Option<(String, String)>, String> {
+    if settings.no_proxy.iter().any(|entry| entry == "*") {
+        return Ok(None);
+    }
+    let mut proxies = Vec::new();
+    for (scheme, proxy) in [("http", &settings.http), ("https", &settings.https)] {
+        let Some(proxy) = proxy else {
+            continue;
+        };
+        let parsed = Proxy::parse(proxy)?;
+        if parsed.kind != ProxyKind::Http {
+            return Err(format!("WinHTTP does not support SOCKS proxies: {proxy}"));
+        }
+        proxies.push(format!("{scheme}={}:{}", parsed.host, parsed.port));
+    }
+    if proxies.is_empty() {
+        return Ok(None);
+    }
+    let bypass = settings
+        .no_proxy
+        .iter()
+        .flat_map(|host| [host.clone(), format!("*.{host}")])
+        .collect::<Vec<_>>()
+        .join(";");
+    Ok(Some((proxies.join(";"), bypass)))
+}
+
 struct WinHttpHandle(HInternet);
 
 impl WinHttpHandle {
-    fn open(user_agent: &str) -> Result<Self, String> {
+    fn open(user_agent: &str, proxy: Option<(String, String)>) -> Result<Self, String> {
         let ua_w = wide_null_terminated(user_agent);
-        let handle = unsafe {
-            WinHttpOpen(
-                ua_w.as_ptr(),
-                WINHTTP_ACCESS_TYPE_DEFAULT_PROXY,
-                std::ptr::null(),
-                std::ptr::null(),
-                0,
-            )
+        let handle = match proxy {
+            Some((proxy, bypass)) => {
+                let proxy_w = wide_null_terminated(&proxy);
+                let bypass_w = wide_null_terminated(&bypass);
+                unsafe {
+                    WinHttpOpen(
+                        ua_w.as_ptr(),
+                        WINHTTP_ACCESS_TYPE_NAMED_PROXY,
+                        proxy_w.as_ptr(),
+                        if bypass.is_empty() {
+                            std::ptr::null()
+                        } else {
+                            bypass_w.as_ptr()
+                        },
+                        0,
+                    )
+                }
+            }
+            None => unsafe {
+                WinHttpOpen(
+                    ua_w.as_ptr(),
+                    WINHTTP_ACCESS_TYPE_DEFAULT_PROXY,
+                    std::ptr::null(),
+                    std::ptr::null(),
+                    0,
+                )
+            },
         };
         if handle.is_null() {
             return Err(format!(
@@ -384,10 +557,7 @@ impl WinHttpRequest {
         if ok == TRUE {
             Ok(())
         } else {
-            Err(format!(
-                "WinHttpSendRequest failed: {}",
-                win32_error_message(last_error())
-            ))
+            Err(request_error("WinHttpSendRequest"))
         }
     }
 
@@ -396,11 +566,59 @@ impl WinHttpRequest {
         if ok == TRUE {
             Ok(())
         } else {
-            Err(format!(
-                "WinHttpReceiveResponse failed: {}",
-                win32_error_message(last_error())
-            ))
+            Err(request_error("WinHttpReceiveResponse"))
+        }
+    }
+
+    /// The server certificate and negotiated protocol, as far as WinHTTP reports them.
+    fn query_tls_info(&self) -> TlsInfo {
+        let mut info = TlsInfo::default();
+
+        let mut certificate = CertificateInfo {
+            expiry: FileTime::default(),
+            start: FileTime::default(),
+            subject_info: std::ptr::null_mut(),
+            issuer_info: std::ptr::null_mut(),
+            protocol_name: std::ptr::null_mut(),
+            signature_alg_name: std::ptr::null_mut(),
+            encryption_alg_name: std::ptr::null_mut(),
+            _key_size: 0,
+        };
+        let mut len = std::mem::size_of::<CertificateInfo>() as DWORD;
+        let ok = unsafe {
+            WinHttpQueryOption(
+                self.0.0,
+                WINHTTP_OPTION_SECURITY_CERTIFICATE_STRUCT,
+                (&mut certificate as *mut CertificateInfo).cast::<c_void>(),
+                &mut len,
+            )
+        };
+        if ok == TRUE {
+            info.subject = take_local_string(certificate.subject_info);
+            info.issuer = take_local_string(certificate.issuer_info);
+            info.protocol = take_local_string(certificate.protocol_name);
+            take_local_string(certificate.signature_alg_name);
+            take_local_string(certificate.encryption_alg_name);
+            info.valid_from = file_time_text(certificate.start);
+            info.valid_until = file_time_text(certificate.expiry);
         }
+
+        let mut security = [0 as DWORD; SECURITY_INFO_DWORDS];
+        let mut len = std::mem::size_of_val(&security) as DWORD;
+        let ok = unsafe {
+            WinHttpQueryOption(
+                self.0.0,
+                WINHTTP_OPTION_SECURITY_INFO,
+                security.as_mut_ptr().cast::<c_void>(),
+                &mut len,
+            )
+        };
+        if ok == TRUE
+            && let Some(protocol) = protocol_name(security[0])
+        {
+            info.protocol = Some(protocol.to_owned());
+        }
+        info
     }
 
     fn query_status_code(&self) -> Result<u32, String> {
@@ -427,12 +645,30 @@ impl WinHttpRequest {
     }
 
     fn query_header_string(&self, info_level: DWORD) -> Result<Option<String>, String> {
+        self.query_header(info_level, None)
+    }
+
+    fn query_custom_header(&self, name: &str) -> Result<Option<String>, String> {
+        self.query_header(WINHTTP_QUERY_CUSTOM, Some(name))
+    }
+
+    fn query_header(
+        &self,
+        info_level: DWORD,
+        name: Option<&str>,
+    ) -> Result<Option<String>, String> {
+        let name_w = name.map(wide_null_terminated);
+        let name_ptr = name_w
+            .as_ref()
+            .map(|v| v.as_ptr())
+            .unwrap_or_else(std::ptr::null);
+
         let mut needed_bytes: DWORD = 0;
         let ok = unsafe {
             WinHttpQueryHeaders(
                 self.0.0,
                 info_level,
-                std::ptr::null(),
+                name_ptr,
                 std::ptr::null_mut(),
                 &mut needed_bytes,
                 std::ptr::null_mut(),
@@ -461,7 +697,7 @@ impl WinHttpRequest {
             WinHttpQueryHeaders(
                 self.0.0,
                 info_level,
-                std::ptr::null(),
+                name_ptr,
                 buf.as_mut_ptr().cast::<c_void>(),
                 &mut buf_len_bytes,
                 std::ptr::null_mut(),
@@ -482,16 +718,17 @@ impl WinHttpRequest {
         Ok(Some(String::from_utf16_lossy(&buf)))
     }
 
-    fn read_to_end(&self, max_bytes: usize) -> Result<Vec<u8>, String> {
-        let mut out: Vec<u8> = Vec::new();
+    /// Appends the body to `out`. Returns `Ok(Some(err))` when the connection failed part-way,
+    /// leaving the bytes read so far in `out`.
+    fn read_to_end(&self, out: &mut Vec<u8>, max_bytes: usize) -> Result<Option<String>, String> {
         loop {
             let mut available: DWORD = 0;
             let ok = unsafe { WinHttpQueryDataAvailable(self.0.0, &mut available) };
             if ok != TRUE {
-                return Err(format!(
+                return Ok(Some(format!(
                     "WinHttpQueryDataAvailable failed: {}",
                     win32_error_message(last_error())
-                ));
+                )));
             }
             if available == 0 {
                 break;
@@ -515,10 +752,10 @@ impl WinHttpRequest {
                 )
             };
             if ok != TRUE {
-                return Err(format!(
+                return Ok(Some(format!(
                     "WinHttpReadData failed: {}",
                     win32_error_message(last_error())
-                ));
+                )));
             }
             let read_usize: usize = read
                 .try_into()
@@ -526,7 +763,53 @@ impl WinHttpRequest {
             chunk.truncate(read_usize);
             out.extend_from_slice(&chunk);
         }
-        Ok(out)
+        Ok(None)
+    }
+}
+
+/// Copies and frees a string WinHTTP allocated with `LocalAlloc`.
+fn take_local_string(ptr: *mut u16) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    let mut len = 0usize;
+    while unsafe { *ptr.add(len) } != 0 {
+        len += 1;
+    }
+    let text = String::from_utf16_lossy(unsafe { std::slice::from_raw_parts(ptr, len) });
+    unsafe {
+        LocalFree(ptr.cast::<c_void>());
+    }
+    let text = text.trim().to_owned();
+    (!text.is_empty()).then_some(text)
+}
+
+fn file_time_text(time: FileTime) -> Option<String> {
+    if time.low == 0 && time.high == 0 {
+        return None;
+    }
+    let mut system = SystemTime::default();
+    if unsafe { FileTimeToSystemTime(&time, &mut system) } != TRUE {
+        return None;
+    }
+    Some(format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        system.year, system.month, system.day, system.hour, system.minute, system.second
+    ))
+}
+
+/// The TLS version in an `SP_PROT_*` mask, client or server side.
+fn protocol_name(protocol: DWORD) -> Option<&'static str> {
+    if protocol & SP_PROT_TLS1_3 != 0 {
+        Some("TLSv1.3")
+    } else if protocol & SP_PROT_TLS1_2 != 0 {
+        Some("TLSv1.2")
+    } else if protocol & SP_PROT_TLS1_1 != 0 {
+        Some("TLSv1.1")
+    } else if protocol & SP_PROT_TLS1_0 != 0 {
+        Some("TLSv1.0")
+    } else {
+        None
     }
 }
 
@@ -536,6 +819,20 @@ fn wide_null_terminated(input: &str) -> Vec<u16> {
     out
 }
 
+/// The error for a failed send or receive; a refused or dropped connection is retried.
+fn request_error(call: &str) -> String {
+    let code = last_error();
+    let message = format!("{call} failed: {}", win32_error_message(code));
+    if matches!(
+        code,
+        ERROR_WINHTTP_CANNOT_CONNECT | ERROR_WINHTTP_CONNECTION_ERROR
+    ) {
+        connection_error(message)
+    } else {
+        message
+    }
+}
+
 fn last_error() -> DWORD {
     unsafe { GetLastError() }
 }
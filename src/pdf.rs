@@ -0,0 +1,900 @@
+use crate::geom::Color;
+use crate::image::Argb32Image;
+use crate::render::{DisplayCommand, DisplayList, TextStyle, Viewport};
+use crate::style::{FontFamily, GradientDirection};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// PDF user space units (points) per CSS pixel.
+const PT_PER_PX: f32 = 0.75;
+/// Bands a linear gradient is drawn with.
+const GRADIENT_BANDS: i32 = 64;
+/// SVGs are rasterized at this multiple of their CSS size so they stay sharp when printed.
+const SVG_RASTER_SCALE: u32 = 2;
+/// The shape of a cubic Bézier quarter circle.
+const BEZIER_CIRCLE: f32 = 0.552_284_8;
+
+/// Paper sizes `--pdf` lays pages out for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaperSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PaperSize {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "a4" => Some(PaperSize::A4),
+            "letter" => Some(PaperSize::Letter),
+            _ => None,
+        }
+    }
+
+    /// Width and height in CSS pixels.
+    fn size_px(self) -> (f32, f32) {
+        match self {
+            PaperSize::A4 => (210.0 / 25.4 * 96.0, 297.0 / 25.4 * 96.0),
+            PaperSize::Letter => (8.5 * 96.0, 11.0 * 96.0),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageSetup {
+    pub paper: PaperSize,
+    pub margin_px: i32,
+}
+
+impl PageSetup {
+    /// Half an inch on every side.
+    pub const DEFAULT_MARGIN_PX: i32 = 48;
+
+    pub fn new(paper: PaperSize) -> Self {
+        Self {
+            paper,
+            margin_px: Self::DEFAULT_MARGIN_PX,
+        }
+    }
+
+    /// The area inside the margins, which the document is laid out against.
+    pub fn content_viewport(self) -> Viewport {
+        let (width_px, height_px) = self.paper.size_px();
+        let margins_px = self.margin_px.saturating_mul(2) as f32;
+        Viewport {
+            width_px: ((width_px - margins_px).floor() as i32).max(1),
+            height_px: ((height_px - margins_px).floor() as i32).max(1),
+        }
+    }
+}
+
+pub fn write_pdf(
+    path: &Path,
+    display_list: &DisplayList,
+    document_height_px: i32,
+    setup: PageSetup,
+) -> Result<(), String> {
+    let file = std::fs::File::create(path)
+        .map_err(|err| format!("Failed to create PDF {}: {err}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    write_pdf_to(&mut writer, display_list, document_height_px, setup)?;
+    writer
+        .flush()
+        .map_err(|err| format!("Failed to write PDF {}: {err}", path.display()))
+}
+
+pub fn write_pdf_to(
+    writer: &mut impl Write,
+    display_list: &DisplayList,
+    document_height_px: i32,
+    setup: PageSetup,
+) -> Result<(), String> {
+    let content = setup.content_viewport();
+    let breaks = page_breaks(display_list, document_height_px, content.height_px);
+    let mut resources = Resources::default();
+    let mut pages = Vec::with_capacity(breaks.len());
+    for (index, &top_px) in breaks.iter().enumerate() {
+        let bottom_px = breaks
+            .get(index + 1)
+            .copied()
+            .unwrap_or(top_px.saturating_add(content.height_px));
+        pages.push(page_content(
+            display_list,
+            top_px,
+            bottom_px.saturating_sub(top_px),
+            setup,
+            &mut resources,
+        )?);
+    }
+    let bytes = assemble(&pages, &resources, setup);
+    writer
+        .write_all(&bytes)
+        .map_err(|err| format!("Failed to write PDF: {err}"))
+}
+
+/// Where each page starts, in document pixels. A page ends early rather than cut through a
+/// line of text or an image, unless that would leave it less than a quarter full.
+pub fn page_breaks(
+    display_list: &DisplayList,
+    document_height_px: i32,
+    page_height_px: i32,
+) -> Vec<i32> {
+    let page_height_px = page_height_px.max(1);
+    let mut unbreakable = Vec::new();
+    let mut fixed_depth = 0usize;
+    for command in &display_list.commands {
+        let span = match command {
+            DisplayCommand::PushFixed => {
+                fixed_depth += 1;
+                None
+            }
+            DisplayCommand::PopFixed => {
+                fixed_depth = fixed_depth.saturating_sub(1);
+                None
+            }
+            _ if fixed_depth > 0 => None,
+            DisplayCommand::Text(text) => Some(text_extent(text.y_px, text.style)),
+            DisplayCommand::Image(image) => Some((image.y_px, image.y_px + image.height_px)),
+            DisplayCommand::Svg(svg) => Some((svg.y_px, svg.y_px + svg.height_px)),
+            _ => None,
+        };
+        if let Some(span) = span {
+            unbreakable.push(span);
+        }
+    }
+
+    let mut breaks = vec![0];
+    let mut top_px = 0i32;
+    while top_px.saturating_add(page_height_px) < document_height_px {
+        let limit_px = top_px + page_height_px;
+        let mut candidate_px = limit_px;
+        while let Some(top) = unbreakable
+            .iter()
+            .filter(|(top, bottom)| *top < candidate_px && *bottom > candidate_px)
+            .map(|(top, _)| *top)
+            .min()
+        {
+            candidate_px = top;
+        }
+        if candidate_px <= top_px + page_height_px / 4 {
+            candidate_px = limit_px;
+        }
+        breaks.push(candidate_px);
+        top_px = candidate_px;
+    }
+    breaks
+}
+
+/// The top and bottom of a line of text drawn on `baseline_px`, allowing for descenders.
+fn text_extent(baseline_px: i32, style: TextStyle) -> (i32, i32) {
+    let size = style.font_size_px.max(1);
+    (baseline_px - size, baseline_px + (size + 3) / 4)
+}
+
+#[derive(Default)]
+struct Resources {
+    /// Alpha values, out of 255, used with the `gs` operator.
+    alphas: BTreeSet<u8>,
+    images: Vec<RasterImage>,
+    image_ids: HashMap<usize, usize>,
+}
+
+struct RasterImage {
+    width: u32,
+    height: u32,
+    rgb: Vec<u8>,
+    alpha: Option<Vec<u8>>,
+}
+
+impl Resources {
+    fn image(&mut self, image: &Arc<Argb32Image>) -> usize {
+        let key = Arc::as_ptr(image) as usize;
+        if let Some(&index) = self.image_ids.get(&key) {
+            return index;
+        }
+        let index = self.push_image(image);
+        self.image_ids.insert(key, index);
+        index
+    }
+
+    fn push_image(&mut self, image: &Argb32Image) -> usize {
+        let pixels = image.width as usize * image.height as usize;
+        let mut rgb = Vec::with_capacity(pixels * 3);
+        let mut alpha = Vec::with_capacity(pixels);
+        for bgra in image.data.chunks_exact(4) {
+            let a = bgra[3];
+            let unpremultiply = |channel: u8| {
+                if a == 0 {
+                    0
+                } else {
+                    ((u32::from(channel) * 255 + u32::from(a) / 2) / u32::from(a)).min(255) as u8
+                }
+            };
+            rgb.extend_from_slice(&[
+                unpremultiply(bgra[2]),
+                unpremultiply(bgra[1]),
+                unpremultiply(bgra[0]),
+            ]);
+            alpha.push(a);
+        }
+        let opaque = alpha.iter().all(|&a| a == 255);
+        self.images.push(RasterImage {
+            width: image.width,
+            height: image.height,
+            rgb,
+            alpha: (!opaque).then_some(alpha),
+        });
+        self.images.len() - 1
+    }
+}
+
+/// Draws the slice of the document from `top_px` down `height_px` pixels, plus fixed content,
+/// in a coordinate system of CSS pixels with the origin at the top-left of the content area.
+fn page_content(
+    display_list: &DisplayList,
+    top_px: i32,
+    height_px: i32,
+    setup: PageSetup,
+    resources: &mut Resources,
+) -> Result<String, String> {
+    let (_, paper_height_px) = setup.paper.size_px();
+    let content = setup.content_viewport();
+    let margin_px = setup.margin_px as f32;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{} 0 0 {} {} {} cm",
+        num(PT_PER_PX),
+        num(-PT_PER_PX),
+        num(margin_px * PT_PER_PX),
+        num((paper_height_px - margin_px) * PT_PER_PX)
+    );
+    let _ = writeln!(out, "0 0 {} {} re W n", content.width_px, content.height_px);
+    // Anything below where the page was cut is repeated at the top of the next page.
+    let slice_clip = format!("q 0 0 {} {height_px} re W n\n", content.width_px);
+    out.push_str(&slice_clip);
+
+    let mut fixed_depth = 0usize;
+    let mut opacity = vec![1.0f32];
+    for command in &display_list.commands {
+        let alpha = *opacity.last().unwrap_or(&1.0);
+        let offset_px = if fixed_depth > 0 { 0 } else { top_px };
+        let clip_bottom_px = if fixed_depth > 0 {
+            content.height_px
+        } else {
+            height_px
+        };
+        let visible = |y_px: i32, h_px: i32| {
+            let y_px = y_px - offset_px;
+            h_px > 0 && y_px < clip_bottom_px && y_px + h_px > 0
+        };
+        match command {
+            DisplayCommand::PushFixed => {
+                if fixed_depth == 0 {
+                    out.push_str("Q\n");
+                }
+                fixed_depth += 1;
+            }
+            DisplayCommand::PopFixed => {
+                if fixed_depth == 1 {
+                    out.push_str(&slice_clip);
+                }
+                fixed_depth = fixed_depth.saturating_sub(1);
+            }
+            // Paper shows transformed elements where layout put them.
+            DisplayCommand::PushTransform(_) | DisplayCommand::PopTransform => {}
+            DisplayCommand::PushOpacity(value) => opacity.push(alpha * f32::from(*value) / 255.0),
+            DisplayCommand::PopOpacity(_) => {
+                if opacity.len() > 1 {
+                    opacity.pop();
+                }
+            }
+            DisplayCommand::Rect(rect) => {
+                if visible(rect.y_px, rect.height_px) {
+                    with_fill(&mut out, resources, rect.color, alpha, |out| {
+                        let _ = writeln!(
+                            out,
+                            "{} {} {} {} re f",
+                            rect.x_px,
+                            rect.y_px - offset_px,
+                            rect.width_px,
+                            rect.height_px
+                        );
+                    });
+                }
+            }
+            DisplayCommand::LinearGradientRect(rect) => {
+                if visible(rect.y_px, rect.height_px) && rect.width_px > 0 {
+                    let vertical = matches!(
+                        rect.direction,
+                        GradientDirection::TopToBottom | GradientDirection::BottomToTop
+                    );
+                    let (start, end) = match rect.direction {
+                        GradientDirection::TopToBottom | GradientDirection::LeftToRight => {
+                            (rect.start_color, rect.end_color)
+                        }
+                        GradientDirection::BottomToTop | GradientDirection::RightToLeft => {
+                            (rect.end_color, rect.start_color)
+                        }
+                    };
+                    let length = if vertical {
+                        rect.height_px
+                    } else {
+                        rect.width_px
+                    } as f32;
+                    let bands = GRADIENT_BANDS.min(length.ceil() as i32).max(1);
+                    let y_px = (rect.y_px - offset_px) as f32;
+                    for band in 0..bands {
+                        let from = length * band as f32 / bands as f32;
+                        let to = length * (band + 1) as f32 / bands as f32;
+                        let t = (band as f32 + 0.5) / bands as f32;
+                        let color = lerp_color(start, end, t);
+                        with_fill(&mut out, resources, color, alpha, |out| {
+                            let (x, y, w, h) = if vertical {
+                                (
+                                    rect.x_px as f32,
+                                    y_px + from,
+                                    rect.width_px as f32,
+                                    to - from,
+                                )
+                            } else {
+                                (
+                                    rect.x_px as f32 + from,
+                                    y_px,
+                                    to - from,
+                                    rect.height_px as f32,
+                                )
+                            };
+                            let _ =
+                                writeln!(out, "{} {} {} {} re f", num(x), num(y), num(w), num(h));
+                        });
+                    }
+                }
+            }
+            DisplayCommand::RoundedRect(rect) => {
+                if visible(rect.y_px, rect.height_px) && rect.width_px > 0 {
+                    with_fill(&mut out, resources, rect.color, alpha, |out| {
+                        rounded_rect_path(
+                            out,
+                            rect.x_px as f32,
+                            (rect.y_px - offset_px) as f32,
+                            rect.width_px as f32,
+                            rect.height_px as f32,
+                            rect.radius_px as f32,
+                        );
+                        out.push_str("f\n");
+                    });
+                }
+            }
+            DisplayCommand::RoundedRectBorder(rect) => {
+                if visible(rect.y_px, rect.height_px) && rect.width_px > 0 {
+                    let border = rect.border_width_px.max(1) as f32;
+                    with_fill(&mut out, resources, rect.color, alpha, |out| {
+                        let _ = writeln!(
+                            out,
+                            "{} {} {} RG {} w",
+                            channel(rect.color.r),
+                            channel(rect.color.g),
+                            channel(rect.color.b),
+                            num(border)
+                        );
+                        rounded_rect_path(
+                            out,
+                            rect.x_px as f32 + border / 2.0,
+                            (rect.y_px - offset_px) as f32 + border / 2.0,
+                            rect.width_px as f32 - border,
+                            rect.height_px as f32 - border,
+                            (rect.radius_px as f32 - border / 2.0).max(0.0),
+                        );
+                        out.push_str("S\n");
+                    });
+                }
+            }
+            DisplayCommand::Text(text) => {
+                let (top_px, bottom_px) = text_extent(text.y_px, text.style);
+                if visible(top_px, bottom_px - top_px) {
+                    draw_text(
+                        &mut out,
+                        resources,
+                        text.x_px,
+                        text.y_px - offset_px,
+                        &text.text,
+                        text.style,
+                        alpha,
+                    );
+                }
+            }
+            DisplayCommand::Image(image) => {
+                if visible(image.y_px, image.height_px) && image.width_px > 0 {
+                    let index = resources.image(&image.image);
+                    let alpha = alpha * f32::from(image.opacity) / 255.0;
+                    draw_image(
+                        &mut out,
+                        resources,
+                        index,
+                        (image.x_px, image.y_px - offset_px),
+                        (image.width_px, image.height_px),
+                        alpha,
+                    );
+                }
+            }
+            DisplayCommand::Svg(svg) => {
+                if visible(svg.y_px, svg.height_px) && svg.width_px > 0 {
+                    let raster = crate::render::svg::rasterize(
+                        &svg.svg_xml,
+                        (svg.width_px as u32).saturating_mul(SVG_RASTER_SCALE),
+                        (svg.height_px as u32).saturating_mul(SVG_RASTER_SCALE),
+                    )?;
+                    let index = resources.push_image(&raster);
+                    let alpha = alpha * f32::from(svg.opacity) / 255.0;
+                    draw_image(
+                        &mut out,
+                        resources,
+                        index,
+                        (svg.x_px, svg.y_px - offset_px),
+                        (svg.width_px, svg.height_px),
+                        alpha,
+                    );
+                }
+            }
+        }
+    }
+    if fixed_depth == 0 {
+        out.push_str("Q\n");
+    }
+    Ok(out)
+}
+
+/// Runs `draw` with `color` as the fill color, inside its own graphics state when it is
+/// translucent.
+fn with_fill(
+    out: &mut String,
+    resources: &mut Resources,
+    color: Color,
+    opacity: f32,
+    draw: impl FnOnce(&mut String),
+) {
+    let alpha = alpha_byte(opacity * f32::from(color.a) / 255.0);
+    if alpha == 0 {
+        return;
+    }
+    out.push_str("q\n");
+    if alpha < 255 {
+        resources.alphas.insert(alpha);
+        let _ = writeln!(out, "/GA{alpha} gs");
+    }
+    let _ = writeln!(
+        out,
+        "{} {} {} rg",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    );
+    draw(out);
+    out.push_str("Q\n");
+}
+
+fn draw_text(
+    out: &mut String,
+    resources: &mut Resources,
+    x_px: i32,
+    baseline_px: i32,
+    text: &str,
+    style: TextStyle,
+    opacity: f32,
+) {
+    let size = style.font_size_px.max(1);
+    with_fill(out, resources, style.color, opacity, |out| {
+        // The page is flipped to run downwards, so glyphs get flipped back upright.
+        let _ = writeln!(
+            out,
+            "BT /{} {size} Tf {} Tc 1 0 0 -1 {x_px} {baseline_px} Tm ({}) Tj ET",
+            font_name(style),
+            style.letter_spacing_px,
+            escape_text(text)
+        );
+        if style.underline {
+            let advance = if style.font_family == FontFamily::Monospace {
+                0.6
+            } else {
+                0.5
+            };
+            let width = text.chars().count() as f32 * (size as f32 * advance)
+                + style.letter_spacing_total_px(text) as f32;
+            let _ = writeln!(out, "{x_px} {} {} 1 re f", baseline_px + 1, num(width));
+        }
+    });
+}
+
+fn draw_image(
+    out: &mut String,
+    resources: &mut Resources,
+    index: usize,
+    (x_px, y_px): (i32, i32),
+    (width_px, height_px): (i32, i32),
+    opacity: f32,
+) {
+    let alpha = alpha_byte(opacity);
+    if alpha == 0 {
+        return;
+    }
+    out.push_str("q\n");
+    if alpha < 255 {
+        resources.alphas.insert(alpha);
+        let _ = writeln!(out, "/GA{alpha} gs");
+    }
+    // Image space runs upwards, so the unit square is flipped onto the box.
+    let _ = writeln!(
+        out,
+        "{width_px} 0 0 {} {x_px} {} cm /Im{index} Do",
+        -height_px,
+        y_px + height_px
+    );
+    out.push_str("Q\n");
+}
+
+fn rounded_rect_path(out: &mut String, x: f32, y: f32, width: f32, height: f32, radius: f32) {
+    let r = radius.min(width / 2.0).min(height / 2.0).max(0.0);
+    let k = r * (1.0 - BEZIER_CIRCLE);
+    let (right, bottom) = (x + width, y + height);
+    let _ = writeln!(out, "{} {} m", num(x + r), num(y));
+    let _ = writeln!(out, "{} {} l", num(right - r), num(y));
+    let _ = writeln!(
+        out,
+        "{} {} {} {} {} {} c",
+        num(right - k),
+        num(y),
+        num(right),
+        num(y + k),
+        num(right),
+        num(y + r)
+    );
+    let _ = writeln!(out, "{} {} l", num(right), num(bottom - r));
+    let _ = writeln!(
+        out,
+        "{} {} {} {} {} {} c",
+        num(right),
+        num(bottom - k),
+        num(right - k),
+        num(bottom),
+        num(right - r),
+        num(bottom)
+    );
+    let _ = writeln!(out, "{} {} l", num(x + r), num(bottom));
+    let _ = writeln!(
+        out,
+        "{} {} {} {} {} {} c",
+        num(x + k),
+        num(bottom),
+        num(x),
+        num(bottom - k),
+        num(x),
+        num(bottom - r)
+    );
+    let _ = writeln!(out, "{} {} l", num(x), num(y + r));
+    let _ = writeln!(
+        out,
+        "{} {} {} {} {} {} c h",
+        num(x),
+        num(y + k),
+        num(x + k),
+        num(y),
+        num(x + r),
+        num(y)
+    );
+}
+
+/// The standard PDF fonts, which every reader has, so none need embedding.
+const FONTS: [(&str, &str); 6] = [
+    ("F1", "Helvetica"),
+    ("F2", "Helvetica-Bold"),
+    ("F3", "Times-Roman"),
+    ("F4", "Times-Bold"),
+    ("F5", "Courier"),
+    ("F6", "Courier-Bold"),
+];
+
+fn font_name(style: TextStyle) -> &'static str {
+    let index = match style.font_family {
+        FontFamily::SansSerif => 0,
+        FontFamily::Serif => 2,
+        FontFamily::Monospace => 4,
+    } + usize::from(style.bold);
+    FONTS[index].0
+}
+
+/// Encodes `text` as a PDF string in the fonts' WinAnsi encoding; other characters become `?`.
+fn escape_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        let byte = win_ansi_byte(ch).unwrap_or(b'?');
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(char::from(byte));
+            }
+            0x20..=0x7e => out.push(char::from(byte)),
+            _ => {
+                let _ = write!(out, "\\{byte:03o}");
+            }
+        }
+    }
+    out
+}
+
+fn win_ansi_byte(ch: char) -> Option<u8> {
+    let code = u32::from(ch);
+    match code {
+        0x20..=0x7e | 0xa0..=0xff => Some(code as u8),
+        0x09 | 0x0a | 0x0d => Some(b' '),
+        _ => {
+            let byte = match ch {
+                '\u{20AC}' => 0x80,
+                '\u{201A}' => 0x82,
+                '\u{192}' => 0x83,
+                '\u{201E}' => 0x84,
+                '\u{2026}' => 0x85,
+                '\u{2020}' => 0x86,
+                '\u{2021}' => 0x87,
+                '\u{2C6}' => 0x88,
+                '\u{2030}' => 0x89,
+                '\u{160}' => 0x8a,
+                '\u{2039}' => 0x8b,
+                '\u{152}' => 0x8c,
+                '\u{17D}' => 0x8e,
+                '\u{2018}' => 0x91,
+                '\u{2019}' => 0x92,
+                '\u{201C}' => 0x93,
+                '\u{201D}' => 0x94,
+                '\u{2022}' => 0x95,
+                '\u{2013}' => 0x96,
+                '\u{2014}' => 0x97,
+                '\u{2DC}' => 0x98,
+                '\u{2122}' => 0x99,
+                '\u{161}' => 0x9a,
+                '\u{203A}' => 0x9b,
+                '\u{153}' => 0x9c,
+                '\u{17E}' => 0x9e,
+                '\u{178}' => 0x9f,
+                _ => return None,
+            };
+            Some(byte)
+        }
+    }
+}
+
+fn lerp_color(start: Color, end: Color, t: f32) -> Color {
+    let lerp = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+    Color {
+        r: lerp(start.r, end.r),
+        g: lerp(start.g, end.g),
+        b: lerp(start.b, end.b),
+        a: lerp(start.a, end.a),
+    }
+}
+
+fn alpha_byte(alpha: f32) -> u8 {
+    (alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn channel(value: u8) -> String {
+    num(f32::from(value) / 255.0)
+}
+
+/// Formats a number with at most three decimals and no trailing zeros.
+fn num(value: f32) -> String {
+    let mut text = format!("{value:.3}");
+    while text.ends_with('0') {
+        text.pop();
+    }
+    if text.ends_with('.') {
+        text.pop();
+    }
+    if text == "-0" { "0".to_owned() } else { text }
+}
+
+/// Lays out the file: catalog, page tree, fonts, graphics states, images, then each page and
+/// its content stream, followed by the cross-reference table.
+fn assemble(pages: &[String], resources: &Resources, setup: PageSetup) -> Vec<u8> {
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+    let reserve = |objects: &mut Vec<Vec<u8>>| {
+        objects.push(Vec::new());
+        objects.len()
+    };
+    let catalog = reserve(&mut objects);
+    let page_tree = reserve(&mut objects);
+
+    let mut fonts = String::new();
+    for (name, base_font) in FONTS {
+        let id = reserve(&mut objects);
+        objects[id - 1] = format!(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /{base_font} /Encoding /WinAnsiEncoding >>"
+        )
+        .into_bytes();
+        let _ = write!(fonts, "/{name} {id} 0 R ");
+    }
+
+    let mut states = String::new();
+    for alpha in &resources.alphas {
+        let id = reserve(&mut objects);
+        let value = num(f32::from(*alpha) / 255.0);
+        objects[id - 1] = format!("<< /Type /ExtGState /ca {value} /CA {value} >>").into_bytes();
+        let _ = write!(states, "/GA{alpha} {id} 0 R ");
+    }
+
+    let mut images = String::new();
+    for (index, image) in resources.images.iter().enumerate() {
+        let mask = image.alpha.as_ref().map(|alpha| {
+            let id = reserve(&mut objects);
+            objects[id - 1] = stream(
+                &format!(
+                    "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8",
+                    image.width, image.height
+                ),
+                alpha,
+            );
+            id
+        });
+        let id = reserve(&mut objects);
+        let smask = mask
+            .map(|mask| format!(" /SMask {mask} 0 R"))
+            .unwrap_or_default();
+        objects[id - 1] = stream(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8{smask}",
+                image.width, image.height
+            ),
+            &image.rgb,
+        );
+        let _ = write!(images, "/Im{index} {id} 0 R ");
+    }
+
+    let (width_px, height_px) = setup.paper.size_px();
+    let media_box = format!(
+        "[0 0 {} {}]",
+        num(width_px * PT_PER_PX),
+        num(height_px * PT_PER_PX)
+    );
+    let resource_dict =
+        format!("<< /Font << {fonts}>> /ExtGState << {states}>> /XObject << {images}>> >>");
+    let mut kids = String::new();
+    for content in pages {
+        let page = reserve(&mut objects);
+        let stream_id = reserve(&mut objects);
+        objects[page - 1] = format!(
+            "<< /Type /Page /Parent {page_tree} 0 R /MediaBox {media_box} /Resources {resource_dict} /Contents {stream_id} 0 R >>"
+        )
+        .into_bytes();
+        objects[stream_id - 1] = stream("", content.as_bytes());
+        let _ = write!(kids, "{page} 0 R ");
+    }
+    objects[catalog - 1] = format!("<< /Type /Catalog /Pages {page_tree} 0 R >>").into_bytes();
+    objects[page_tree - 1] = format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.trim_end(),
+        pages.len()
+    )
+    .into_bytes();
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, body) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+        out.extend_from_slice(body);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+    let xref = out.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(table, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root {catalog} 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    );
+    out.extend_from_slice(table.as_bytes());
+    out
+}
+
+fn stream(dict: &str, data: &[u8]) -> Vec<u8> {
+    let mut out = format!("<< {dict} /Length {} >>\nstream\n", data.len()).into_bytes();
+    out.extend_from_slice(data);
+    out.extend_from_slice(b"\nendstream");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{DrawRect, DrawText};
+
+    fn text_at(y_px: i32) -> DisplayCommand {
+        DisplayCommand::Text(DrawText {
+            x_px: 0,
+            y_px,
+            text: "line".to_owned(),
+            style: TextStyle::default(),
+        })
+    }
+
+    #[test]
+    fn page_breaks_fall_between_lines_of_text() {
+        let list = DisplayList {
+            commands: (1..=30).map(|line| text_at(line * 40)).collect(),
+        };
+        // The line on baseline 520 spans 504..524, so the first page ends above it.
+        let breaks = page_breaks(&list, 1220, 510);
+        assert_eq!(breaks, vec![0, 504, 1014]);
+    }
+
+    #[test]
+    fn writes_a_page_per_break_with_a_valid_cross_reference_table() {
+        let mut commands = vec![DisplayCommand::Rect(DrawRect {
+            x_px: 0,
+            y_px: 0,
+            width_px: 100,
+            height_px: 2000,
+            color: Color {
+                r: 255,
+                g: 0,
+                b: 0,
+                a: 128,
+            },
+        })];
+        commands.push(text_at(20));
+        commands.push(DisplayCommand::Image(crate::render::DrawImage {
+            x_px: 0,
+            y_px: 1500,
+            width_px: 2,
+            height_px: 1,
+            opacity: 255,
+            image: Arc::new(Argb32Image::new(2, 1, vec![0, 0, 255, 255, 0, 0, 0, 0]).unwrap()),
+        }));
+        let list = DisplayList { commands };
+        let setup = PageSetup::new(PaperSize::Letter);
+        let mut bytes = Vec::new();
+        write_pdf_to(&mut bytes, &list, 2000, setup).unwrap();
+        // Byte for byte, so offsets into it are file offsets.
+        let text: String = bytes
+            .iter()
+            .map(|&byte| {
+                if byte.is_ascii() {
+                    char::from(byte)
+                } else {
+                    '?'
+                }
+            })
+            .collect();
+
+        assert!(text.starts_with("%PDF-1.4"));
+        assert_eq!(text.matches("/Type /Page ").count(), 3);
+        assert!(text.contains("/MediaBox [0 0 612 792]"));
+        assert!(text.contains("/GA128"));
+        assert!(text.contains("/SMask"));
+        assert!(text.contains("(line) Tj"));
+
+        let xref = text.rfind("startxref\n").unwrap();
+        let xref_offset: usize = text[xref + 10..].lines().next().unwrap().parse().unwrap();
+        assert!(text[xref_offset..].starts_with("xref\n"));
+        for (index, line) in text[xref_offset..].lines().skip(3).enumerate() {
+            let Some(offset) = line.strip_suffix(" 00000 n ") else {
+                break;
+            };
+            let offset: usize = offset.parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+
+    #[test]
+    fn escapes_text_into_win_ansi() {
+        assert_eq!(
+            escape_text("a(b)\\ caf\u{e9} \u{2014} \u{4e2d}"),
+            "a\\(b\\)\\\\ caf\\351 \\227 ?"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+/// Only the motion this close to the fingers lifting counts towards a fling.
+const VELOCITY_WINDOW_MS: u32 = 100;
+/// Fingers that rested this long before lifting stopped the scroll rather than flinging it.
+const MAX_REST_BEFORE_LIFT_MS: u32 = 50;
+
+/// Tracks a touchpad scroll in progress, to tell how fast it was going when the fingers lifted.
+#[derive(Debug, Default)]
+pub(super) struct ScrollVelocity {
+    /// `(time_ms, delta_px)` for each recent axis event, oldest first.
+    samples: VecDeque<(u32, f64)>,
+}
+
+impl ScrollVelocity {
+    pub(super) fn push(&mut self, time_ms: u32, delta_px: f64) {
+        while self
+            .samples
+            .front()
+            .is_some_and(|&(sample_ms, _)| time_ms.wrapping_sub(sample_ms) > VELOCITY_WINDOW_MS)
+        {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time_ms, delta_px));
+    }
+
+    /// The speed in px per second when the scroll stopped at `stop_ms`, and forgets the scroll.
+    /// `None` when there's too little motion to tell, or the fingers came to rest first.
+    pub(super) fn take_velocity(&mut self, stop_ms: u32) -> Option<f64> {
+        let samples = std::mem::take(&mut self.samples);
+        let &(first_ms, _) = samples.front()?;
+        let &(last_ms, _) = samples.back()?;
+        let span_ms = last_ms.wrapping_sub(first_ms);
+        if span_ms == 0 || stop_ms.wrapping_sub(last_ms) > MAX_REST_BEFORE_LIFT_MS {
+            return None;
+        }
+        // The first sample's delta was covered before the window starts.
+        let distance_px: f64 = samples.iter().skip(1).map(|&(_, delta_px)| delta_px).sum();
+        Some(distance_px * 1000.0 / f64::from(span_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScrollVelocity;
+
+    #[test]
+    fn velocity_comes_from_the_motion_just_before_the_fingers_lift() {
+        let mut velocity = ScrollVelocity::default();
+        // A slow start that falls out of the window, then 10px every 10ms.
+        velocity.push(0, 1.0);
+        for time_ms in (200..=300).step_by(10) {
+            velocity.push(time_ms, 10.0);
+        }
+        assert_eq!(velocity.take_velocity(310), Some(1000.0));
+        assert_eq!(velocity.take_velocity(310), None);
+
+        for time_ms in (0..=100).step_by(10) {
+            velocity.push(time_ms, -10.0);
+        }
+        assert_eq!(velocity.take_velocity(400), None);
+    }
+}
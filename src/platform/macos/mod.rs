@@ -2,7 +2,6 @@ mod headless;
 mod painter;
 mod scale;
 mod scaled;
-mod svg;
 mod windowed;
 
 use super::WindowOptions;
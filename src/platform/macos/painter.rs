@@ -1,6 +1,9 @@
 use crate::geom::Color;
 use crate::image::{Argb32Image, RgbImage};
-use crate::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use crate::render::{
+    FillRule, FontMetricsPx, LineCap, LineJoin, Painter, PathCommand, StrokeStyle, TextMeasurer,
+    TextStyle, Transform, Viewport, shaping,
+};
 use crate::style::FontFamily;
 use core::ffi::{c_double, c_int, c_uint, c_void};
 use std::cell::RefCell;
@@ -70,10 +73,15 @@ const BITMAP_INFO_BGRA_PREMULTIPLIED: c_uint =
 const BLEND_MODE_NORMAL: c_int = 0;
 
 const PATH_DRAW_MODE_FILL: c_int = 0;
+const PATH_DRAW_MODE_EO_FILL: c_int = 1;
 const PATH_DRAW_MODE_STROKE: c_int = 2;
 
 const LINE_CAP_BUTT: c_int = 0;
+const LINE_CAP_ROUND: c_int = 1;
+const LINE_CAP_SQUARE: c_int = 2;
 const LINE_JOIN_MITER: c_int = 0;
+const LINE_JOIN_ROUND: c_int = 1;
+const LINE_JOIN_BEVEL: c_int = 2;
 
 type CTFontSymbolicTraits = u32;
 const K_CTFONT_BOLD_TRAIT: CTFontSymbolicTraits = 1 << 1;
@@ -164,6 +172,27 @@ unsafe extern "C" {
     fn CGContextSetLineWidth(c: CGContextRef, width: CGFloat);
     fn CGContextSetLineCap(c: CGContextRef, cap: c_int);
     fn CGContextSetLineJoin(c: CGContextRef, join: c_int);
+    fn CGContextSetMiterLimit(c: CGContextRef, limit: CGFloat);
+    fn CGContextBeginPath(c: CGContextRef);
+    fn CGContextMoveToPoint(c: CGContextRef, x: CGFloat, y: CGFloat);
+    fn CGContextAddLineToPoint(c: CGContextRef, x: CGFloat, y: CGFloat);
+    fn CGContextAddQuadCurveToPoint(
+        c: CGContextRef,
+        cpx: CGFloat,
+        cpy: CGFloat,
+        x: CGFloat,
+        y: CGFloat,
+    );
+    fn CGContextAddCurveToPoint(
+        c: CGContextRef,
+        cp1x: CGFloat,
+        cp1y: CGFloat,
+        cp2x: CGFloat,
+        cp2y: CGFloat,
+        x: CGFloat,
+        y: CGFloat,
+    );
+    fn CGContextClosePath(c: CGContextRef);
     fn CGContextAddPath(c: CGContextRef, path: CGPathRef);
     fn CGContextDrawPath(c: CGContextRef, mode: c_int);
 
@@ -182,6 +211,8 @@ unsafe extern "C" {
     fn CGContextSetBlendMode(c: CGContextRef, mode: c_int);
     fn CGContextSaveGState(c: CGContextRef);
     fn CGContextRestoreGState(c: CGContextRef);
+    fn CGContextClipToRect(c: CGContextRef, rect: CGRect);
+    fn CGContextConcatCTM(c: CGContextRef, transform: CGAffineTransform);
 
     fn CGContextSetTextMatrix(c: CGContextRef, t: CGAffineTransform);
     fn CGContextSetTextPosition(c: CGContextRef, x: CGFloat, y: CGFloat);
@@ -201,6 +232,8 @@ pub struct MacPainter {
     height_px: i32,
     data: Vec<u8>,
     opacity_depth: usize,
+    clip_depth: usize,
+    transform_depth: usize,
     font_cache: RefCell<HashMap<FontKey, CTFontRef>>,
 }
 
@@ -219,6 +252,8 @@ impl MacPainter {
             height_px,
             data,
             opacity_depth: 0,
+            clip_depth: 0,
+            transform_depth: 0,
             font_cache: RefCell::new(HashMap::new()),
         })
     }
@@ -243,6 +278,8 @@ impl MacPainter {
         self.height_px = height_px;
         self.data = data;
         self.opacity_depth = 0;
+        self.clip_depth = 0;
+        self.transform_depth = 0;
         Ok(())
     }
 
@@ -297,6 +334,39 @@ impl MacPainter {
         self.height_px.saturating_sub(y_px) as CGFloat
     }
 
+    /// Replaces the context's path with `path`, flipped into Quartz coordinates.
+    fn add_path(&self, path: &[PathCommand]) {
+        let height = self.height_px as CGFloat;
+        unsafe {
+            CGContextBeginPath(self.ctx);
+            for command in path {
+                match *command {
+                    PathCommand::MoveTo(to) => CGContextMoveToPoint(self.ctx, to.x, height - to.y),
+                    PathCommand::LineTo(to) => {
+                        CGContextAddLineToPoint(self.ctx, to.x, height - to.y)
+                    }
+                    PathCommand::QuadTo(control, to) => CGContextAddQuadCurveToPoint(
+                        self.ctx,
+                        control.x,
+                        height - control.y,
+                        to.x,
+                        height - to.y,
+                    ),
+                    PathCommand::CubicTo(first, second, to) => CGContextAddCurveToPoint(
+                        self.ctx,
+                        first.x,
+                        height - first.y,
+                        second.x,
+                        height - second.y,
+                        to.x,
+                        height - to.y,
+                    ),
+                    PathCommand::Close => CGContextClosePath(self.ctx),
+                }
+            }
+        }
+    }
+
     fn font_for(&self, style: TextStyle) -> CTFontRef {
         let key = FontKey {
             family: style.font_family,
@@ -358,7 +428,10 @@ impl MacPainter {
             return Ok(());
         }
 
-        let cf_text = cf_string(text).ok_or_else(|| "Text contains invalid UTF-8".to_owned())?;
+        // Layout hands over right-to-left text already in display order, so the left-to-right
+        // override keeps Core Text from reordering it again.
+        let text = format!("\u{202D}{text}");
+        let cf_text = cf_string(&text).ok_or_else(|| "Text contains invalid UTF-8".to_owned())?;
 
         let keys: [*const c_void; 2] = [unsafe { kCTFontAttributeName as *const c_void }, unsafe {
             kCTForegroundColorFromContextAttributeName as *const c_void
@@ -563,17 +636,9 @@ impl TextMeasurer for MacPainter {
             return self.text_width_no_spacing(text, style);
         }
 
-        let mut total_width: i64 = 0;
-        let mut first = true;
-        for ch in text.chars() {
-            if !first {
-                total_width += i64::from(style.letter_spacing_px);
-            }
-            first = false;
-
-            let mut buf = [0u8; 4];
-            let ch = ch.encode_utf8(&mut buf);
-            total_width += i64::from(self.text_width_no_spacing(ch, style)?);
+        let mut total_width = i64::from(style.letter_spacing_total_px(text));
+        for cluster in shaping::clusters(text) {
+            total_width += i64::from(self.text_width_no_spacing(cluster, style)?);
         }
         Ok(total_width.clamp(0, i64::from(i32::MAX)) as i32)
     }
@@ -733,17 +798,12 @@ impl Painter for MacPainter {
             self.draw_text_run(x_px, y_px, text, style)?;
         } else {
             let mut cursor_x = x_px;
-            let mut first = true;
-            for ch in text.chars() {
-                if !first {
+            for (index, cluster) in shaping::clusters(text).enumerate() {
+                if index > 0 {
                     cursor_x = cursor_x.saturating_add(style.letter_spacing_px);
                 }
-                first = false;
-
-                let mut buf = [0u8; 4];
-                let ch = ch.encode_utf8(&mut buf);
-                self.draw_text_run(cursor_x, y_px, ch, style)?;
-                cursor_x = cursor_x.saturating_add(self.text_width_no_spacing(ch, style)?);
+                self.draw_text_run(cursor_x, y_px, cluster, style)?;
+                cursor_x = cursor_x.saturating_add(self.text_width_no_spacing(cluster, style)?);
             }
         }
 
@@ -814,49 +874,128 @@ impl Painter for MacPainter {
         Ok(())
     }
 
-    fn draw_svg(
+    fn push_clip_rect(
         &mut self,
         x_px: i32,
         y_px: i32,
         width_px: i32,
         height_px: i32,
-        svg_xml: &str,
-        opacity: u8,
     ) -> Result<(), String> {
-        if width_px <= 0 || height_px <= 0 || opacity == 0 {
-            return Ok(());
+        let rect = self.rect_to_quartz(x_px, y_px, width_px, height_px);
+        self.clip_depth = self.clip_depth.saturating_add(1);
+        unsafe {
+            CGContextSaveGState(self.ctx);
+            CGContextClipToRect(self.ctx, rect);
         }
+        Ok(())
+    }
 
-        let svg_xml = svg_xml.trim();
-        if svg_xml.is_empty() {
+    fn pop_clip(&mut self) -> Result<(), String> {
+        if self.clip_depth == 0 {
+            return Err("clip stack underflow".to_owned());
+        }
+        self.clip_depth -= 1;
+        unsafe {
+            CGContextRestoreGState(self.ctx);
+        }
+        Ok(())
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        // Quartz puts the origin at the bottom left, so the transform is wrapped in the flip
+        // between the two.
+        let height = self.height_px as CGFloat;
+        let quartz = CGAffineTransform {
+            a: transform.a,
+            b: -transform.b,
+            c: -transform.c,
+            d: transform.d,
+            tx: transform.c * height + transform.e,
+            ty: height - transform.d * height - transform.f,
+        };
+        self.transform_depth = self.transform_depth.saturating_add(1);
+        unsafe {
+            CGContextSaveGState(self.ctx);
+            CGContextConcatCTM(self.ctx, quartz);
+        }
+        Ok(())
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        if self.transform_depth == 0 {
+            return Err("transform stack underflow".to_owned());
+        }
+        self.transform_depth -= 1;
+        unsafe {
+            CGContextRestoreGState(self.ctx);
+        }
+        Ok(())
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        if path.is_empty() {
             return Ok(());
         }
+        let mode = match rule {
+            FillRule::NonZero => PATH_DRAW_MODE_FILL,
+            FillRule::EvenOdd => PATH_DRAW_MODE_EO_FILL,
+        };
+        unsafe {
+            CGContextSetRGBFillColor(
+                self.ctx,
+                (color.r as CGFloat) / 255.0,
+                (color.g as CGFloat) / 255.0,
+                (color.b as CGFloat) / 255.0,
+                (color.a as CGFloat) / 255.0,
+            );
+        }
+        self.add_path(path);
+        unsafe {
+            CGContextDrawPath(self.ctx, mode);
+        }
+        Ok(())
+    }
 
-        let cg_image = match super::svg::rasterize_svg_to_cgimage(svg_xml, width_px, height_px) {
-            Ok(image) => image,
-            Err(message) => {
-                crate::debug::log(
-                    crate::debug::Target::Render,
-                    crate::debug::Level::Warn,
-                    format_args!(
-                        "SVG render failed: {}",
-                        crate::debug::shorten(&message, 120)
-                    ),
-                );
-                return Ok(());
-            }
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        if path.is_empty() || !(style.width > 0.0 && style.width.is_finite()) {
+            return Ok(());
+        }
+        let cap = match style.cap {
+            LineCap::Butt => LINE_CAP_BUTT,
+            LineCap::Round => LINE_CAP_ROUND,
+            LineCap::Square => LINE_CAP_SQUARE,
+        };
+        let join = match style.join {
+            LineJoin::Miter => LINE_JOIN_MITER,
+            LineJoin::Round => LINE_JOIN_ROUND,
+            LineJoin::Bevel => LINE_JOIN_BEVEL,
         };
-        let rect = self.rect_to_quartz(x_px, y_px, width_px, height_px);
         unsafe {
-            if opacity == 255 {
-                CGContextDrawImage(self.ctx, rect, cg_image);
-            } else {
-                CGContextSaveGState(self.ctx);
-                CGContextSetAlpha(self.ctx, (opacity as CGFloat) / 255.0);
-                CGContextDrawImage(self.ctx, rect, cg_image);
-                CGContextRestoreGState(self.ctx);
-            }
-            CGImageRelease(cg_image);
+            CGContextSetRGBStrokeColor(
+                self.ctx,
+                (color.r as CGFloat) / 255.0,
+                (color.g as CGFloat) / 255.0,
+                (color.b as CGFloat) / 255.0,
+                (color.a as CGFloat) / 255.0,
+            );
+            CGContextSetLineWidth(self.ctx, style.width);
+            CGContextSetLineCap(self.ctx, cap);
+            CGContextSetLineJoin(self.ctx, join);
+            CGContextSetMiterLimit(self.ctx, style.miter_limit);
+        }
+        self.add_path(path);
+        unsafe {
+            CGContextDrawPath(self.ctx, PATH_DRAW_MODE_STROKE);
         }
         Ok(())
     }
@@ -31,6 +31,17 @@ impl ScaleFactor {
         Self { scale_1024 }
     }
 
+    /// Device pixels per CSS pixel, as CSS `resolution` media queries see it.
+    pub fn device_pixel_ratio(self) -> f32 {
+        self.scale_1024 as f32 / 1024.0
+    }
+
+    /// The scale with page zoom applied on top, e.g. `zoomed(125)` for 125%.
+    pub fn zoomed(self, percent: u32) -> Self {
+        let scaled = u64::from(self.scale_1024) * u64::from(percent) / 100;
+        Self::new(u32::try_from(scaled).unwrap_or(u32::MAX))
+    }
+
     pub fn css_size_to_device_px(self, css_px: i32) -> i32 {
         let css_px = i64::from(css_px);
         let scaled = mul_div_round_nearest(css_px, i64::from(self.scale_1024), 1024);
@@ -1,6 +1,8 @@
 use super::painter::MacPainter;
 use super::scale::ScaleFactor;
-use crate::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle};
+use crate::render::{
+    FillRule, FontMetricsPx, Painter, PathCommand, StrokeStyle, TextMeasurer, TextStyle, Transform,
+};
 
 pub(super) struct ScaledPainter<'a> {
     inner: &'a mut MacPainter,
@@ -9,6 +11,8 @@ pub(super) struct ScaledPainter<'a> {
 
 impl<'a> ScaledPainter<'a> {
     pub fn new(inner: &'a mut MacPainter, scale: ScaleFactor) -> Self {
+        // Every frame is painted through here, so media queries see the current scale.
+        crate::css_media::set_device_pixel_ratio(scale.device_pixel_ratio());
         Self { inner, scale }
     }
 
@@ -19,6 +23,14 @@ impl<'a> ScaledPainter<'a> {
             ..style
         }
     }
+
+    fn scale_path(&self, path: &[PathCommand]) -> Vec<PathCommand> {
+        let ratio = f64::from(self.scale.device_pixel_ratio());
+        let scale = Transform::scale(ratio, ratio);
+        path.iter()
+            .map(|command| command.transformed(&scale))
+            .collect()
+    }
 }
 
 impl TextMeasurer for ScaledPainter<'_> {
@@ -172,6 +184,61 @@ impl Painter for ScaledPainter<'_> {
         )
     }
 
+    fn push_clip_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+    ) -> Result<(), String> {
+        let (x_device_px, width_device_px) = self.scale.css_span_to_device_px(x_px, width_px);
+        let (y_device_px, height_device_px) = self.scale.css_span_to_device_px(y_px, height_px);
+        self.inner
+            .push_clip_rect(x_device_px, y_device_px, width_device_px, height_device_px)
+    }
+
+    fn pop_clip(&mut self) -> Result<(), String> {
+        self.inner.pop_clip()
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        // Conjugated by the scale, so only the translation changes.
+        let ratio = f64::from(self.scale.device_pixel_ratio());
+        self.inner.push_transform(Transform {
+            e: transform.e * ratio,
+            f: transform.f * ratio,
+            ..transform
+        })
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        self.inner.pop_transform()
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: crate::geom::Color,
+    ) -> Result<(), String> {
+        let path = self.scale_path(path);
+        self.inner.fill_path(&path, rule, color)
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: crate::geom::Color,
+    ) -> Result<(), String> {
+        let path = self.scale_path(path);
+        let style = StrokeStyle {
+            width: style.width * f64::from(self.scale.device_pixel_ratio()),
+            ..*style
+        };
+        self.inner.stroke_path(&path, &style, color)
+    }
+
     fn flush(&mut self) -> Result<(), String> {
         self.inner.flush()
     }
@@ -2,19 +2,38 @@ use super::WindowOptions;
 use super::painter::MacPainter;
 use super::scale::ScaleFactor;
 use super::scaled::ScaledPainter;
-use crate::app::App;
+use crate::app::{App, Key, KeyInput, ZoomCommand};
+use crate::image::RgbImage;
+use crate::platform::{FrameScheduler, FrameStep, FrameSurface, WindowIdentity};
 use crate::render::Viewport;
 use core::ffi::{c_char, c_double, c_long, c_ulong, c_void};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 const MAX_EVENTS_PER_TICK: usize = 512;
 
-const SCREENSHOT_RESOURCE_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
-
 const EVENT_TYPE_LEFT_MOUSE_DOWN: c_ulong = 1;
+const EVENT_TYPE_LEFT_MOUSE_UP: c_ulong = 2;
+const EVENT_TYPE_MOUSE_MOVED: c_ulong = 5;
+const EVENT_TYPE_LEFT_MOUSE_DRAGGED: c_ulong = 6;
 const EVENT_TYPE_KEY_DOWN: c_ulong = 10;
 const EVENT_TYPE_SCROLL_WHEEL: c_ulong = 22;
+const EVENT_TYPE_OTHER_MOUSE_DOWN: c_ulong = 25;
+const MOUSE_BUTTON_BACK: c_long = 3;
+const MOUSE_BUTTON_FORWARD: c_long = 4;
+const KEY_CODE_RETURN: u16 = 36;
+const KEY_CODE_TAB: u16 = 48;
 const KEY_CODE_DELETE: u16 = 51;
+const KEY_CODE_ESCAPE: u16 = 53;
+const KEY_CODE_F12: u16 = 111;
+const KEY_CODE_KEYPAD_ENTER: u16 = 76;
+const KEY_CODE_HOME: u16 = 115;
+const KEY_CODE_FORWARD_DELETE: u16 = 117;
+const KEY_CODE_END: u16 = 119;
+const KEY_CODE_LEFT_ARROW: u16 = 123;
+const KEY_CODE_RIGHT_ARROW: u16 = 124;
+const MODIFIER_FLAG_CONTROL: c_ulong = 1 << 18;
+const MODIFIER_FLAG_OPTION: c_ulong = 1 << 19;
+const MODIFIER_FLAG_COMMAND: c_ulong = 1 << 20;
 
 type Id = *mut c_void;
 type Sel = *mut c_void;
@@ -82,9 +101,13 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
     }
 
     let mut cocoa = CocoaApp::new(title, initial_width_css, initial_height_css)?;
-    let mut scale = ScaleFactor::detect(false, Some(cocoa.backing_scale_factor()));
+    let mut identity = WindowIdentity::new(title);
+    let mut base_scale = ScaleFactor::detect(false, Some(cocoa.backing_scale_factor()));
+    // Event locations are in window points; `zoom` divides them into page CSS pixels.
+    let mut zoom = ScaleFactor::new(1024).zoomed(app.zoom_percent());
+    let mut scale = base_scale.zoomed(app.zoom_percent());
 
-    let mut viewport = cocoa.device_viewport(scale)?;
+    let mut viewport = cocoa.device_viewport(base_scale)?;
     let mut css_viewport = Viewport {
         width_px: scale.device_size_to_css_px(viewport.width_px),
         height_px: scale.device_size_to_css_px(viewport.height_px),
@@ -92,12 +115,10 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
 
     let mut painter = MacPainter::new(viewport)?;
 
-    let mut screenshot_path = options.screenshot_path;
-    let mut needs_redraw = true;
+    let mut frames = FrameScheduler::new(&options);
     let mut should_exit = false;
-    let mut has_rendered_ready_state = false;
-    let mut resource_wait_started: Option<Instant> = None;
     let mut scroll_accum_y: c_double = 0.0;
+    let mut scroll_accum_x: c_double = 0.0;
 
     loop {
         let _pool = AutoreleasePool::new();
@@ -115,32 +136,95 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
             let event_type = cocoa.event_type(event);
             match event_type {
                 EVENT_TYPE_LEFT_MOUSE_DOWN => {
-                    if let Some((x_css, y_css)) = cocoa.event_location_css(event) {
+                    if let Some((x_pt, y_pt)) = cocoa.event_location_css(event) {
+                        let x_css = zoom.device_delta_to_css_px(x_pt);
+                        let y_css = zoom.device_delta_to_css_px(y_pt);
                         let tick = app.mouse_down(x_css, y_css, css_viewport)?;
-                        if tick.needs_redraw {
-                            needs_redraw = true;
-                        }
+                        frames.note(tick);
+                    }
+                    cocoa.send_event(event);
+                }
+                EVENT_TYPE_LEFT_MOUSE_UP => {
+                    if let Some((x_pt, y_pt)) = cocoa.event_location_css(event) {
+                        let x_css = zoom.device_delta_to_css_px(x_pt);
+                        let y_css = zoom.device_delta_to_css_px(y_pt);
+                        let tick = app.mouse_up(x_css, y_css, css_viewport)?;
+                        frames.note(tick);
+                    }
+                    cocoa.send_event(event);
+                }
+                EVENT_TYPE_MOUSE_MOVED | EVENT_TYPE_LEFT_MOUSE_DRAGGED => {
+                    if let Some((x_pt, y_pt)) = cocoa.event_location_css(event) {
+                        let x_css = zoom.device_delta_to_css_px(x_pt);
+                        let y_css = zoom.device_delta_to_css_px(y_pt);
+                        let tick = app.mouse_move(x_css, y_css, css_viewport)?;
+                        frames.note(tick);
                     }
                     cocoa.send_event(event);
                 }
+                EVENT_TYPE_OTHER_MOUSE_DOWN => {
+                    let tick = match cocoa.event_button_number(event) {
+                        MOUSE_BUTTON_BACK => app.navigate_back()?,
+                        MOUSE_BUTTON_FORWARD => app.navigate_forward()?,
+                        _ => Default::default(),
+                    };
+                    frames.note(tick);
+                    cocoa.send_event(event);
+                }
                 EVENT_TYPE_SCROLL_WHEEL => {
                     scroll_accum_y += cocoa.event_scroll_delta_y(event);
-                    let delta_y_css = (-scroll_accum_y).trunc() as i32;
-                    if delta_y_css != 0 {
-                        scroll_accum_y += delta_y_css as c_double;
+                    let delta_y_pt = (-scroll_accum_y).trunc() as i32;
+                    if delta_y_pt != 0 {
+                        scroll_accum_y += delta_y_pt as c_double;
+                        let delta_y_css = zoom.device_delta_to_css_px(delta_y_pt);
                         let tick = app.mouse_wheel(delta_y_css, css_viewport)?;
-                        if tick.needs_redraw {
-                            needs_redraw = true;
-                        }
+                        frames.note(tick);
+                    }
+                    // AppKit turns Shift with a vertical wheel into horizontal deltas itself.
+                    scroll_accum_x += cocoa.event_scroll_delta_x(event);
+                    let delta_x_pt = (-scroll_accum_x).trunc() as i32;
+                    if delta_x_pt != 0 {
+                        scroll_accum_x += delta_x_pt as c_double;
+                        let delta_x_css = zoom.device_delta_to_css_px(delta_x_pt);
+                        let tick = app.horizontal_wheel(delta_x_css, css_viewport)?;
+                        frames.note(tick);
                     }
                     cocoa.send_event(event);
                 }
                 EVENT_TYPE_KEY_DOWN => {
+                    let input = cocoa.event_key_input(event);
+                    if let Some(command) = input.and_then(ZoomCommand::from_key) {
+                        app.set_zoom_percent(command.apply(app.zoom_percent()));
+                        zoom = ScaleFactor::new(1024).zoomed(app.zoom_percent());
+                        scale = base_scale.zoomed(app.zoom_percent());
+                        css_viewport = Viewport {
+                            width_px: scale.device_size_to_css_px(viewport.width_px),
+                            height_px: scale.device_size_to_css_px(viewport.height_px),
+                        };
+                        frames.invalidate();
+                        processed += 1;
+                        continue;
+                    }
+                    if input.is_some_and(KeyInput::is_paste) {
+                        if let Some(text) = cocoa.pasteboard_text() {
+                            let tick = app.paste_text(&text, css_viewport)?;
+                            frames.note(tick);
+                        }
+                        processed += 1;
+                        continue;
+                    }
+                    let consumed = match input {
+                        Some(input) => app.key_down(input, css_viewport)?,
+                        None => None,
+                    };
+                    if let Some(tick) = consumed {
+                        frames.note(tick);
+                        processed += 1;
+                        continue;
+                    }
                     if cocoa.event_key_code(event) == KEY_CODE_DELETE {
                         let tick = app.navigate_back()?;
-                        if tick.needs_redraw {
-                            needs_redraw = true;
-                        }
+                        frames.note(tick);
                         processed += 1;
                         continue;
                     }
@@ -162,8 +246,9 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
         if let Some(backing) = cocoa.backing_scale_factor_checked() {
             let next_scale = ScaleFactor::detect(false, Some(backing));
             let next_viewport = cocoa.device_viewport(next_scale)?;
-            if next_scale != scale || next_viewport != viewport {
-                scale = next_scale;
+            if next_scale != base_scale || next_viewport != viewport {
+                base_scale = next_scale;
+                scale = base_scale.zoomed(app.zoom_percent());
                 viewport = next_viewport;
                 css_viewport = Viewport {
                     width_px: scale.device_size_to_css_px(viewport.width_px),
@@ -171,91 +256,27 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
                 };
                 painter.ensure_back_buffer(viewport)?;
                 cocoa.set_contents_scale(backing);
-                needs_redraw = true;
-                has_rendered_ready_state = false;
-                resource_wait_started = None;
+                frames.invalidate();
             }
         }
 
         let tick = app.tick()?;
-        if tick.needs_redraw {
-            needs_redraw = true;
-        }
-
-        let ready_for_screenshot = tick.ready_for_screenshot;
-        if !ready_for_screenshot {
-            has_rendered_ready_state = false;
-            resource_wait_started = None;
+        if let Some(title) = identity.title_change(app) {
+            set_window_title(cocoa.window, &title)?;
         }
 
-        let should_wait_for_resources = tick.pending_resources > 0;
-        let timed_out_waiting_for_resources = resource_wait_started
-            .is_some_and(|started| started.elapsed() >= SCREENSHOT_RESOURCE_WAIT_TIMEOUT);
-        let can_complete = !should_wait_for_resources || timed_out_waiting_for_resources;
-
-        let wants_screenshot = screenshot_path.is_some();
-        let should_complete_screenshot =
-            wants_screenshot && ready_for_screenshot && has_rendered_ready_state;
-
-        let mut capture_now = false;
-        let mut capture_after_render = false;
-
-        if ready_for_screenshot && wants_screenshot && !has_rendered_ready_state {
-            needs_redraw = true;
-        } else if ready_for_screenshot && should_wait_for_resources && has_rendered_ready_state {
-            resource_wait_started.get_or_insert(Instant::now());
-        } else if ready_for_screenshot && has_rendered_ready_state {
-            resource_wait_started = None;
-        }
-
-        if ready_for_screenshot && has_rendered_ready_state && can_complete {
-            if should_complete_screenshot {
-                if needs_redraw {
-                    capture_after_render = true;
-                } else {
-                    capture_now = true;
-                }
-            }
-        }
-
-        if capture_now {
-            let Some(path) = screenshot_path.take() else {
-                return Err(
-                    "Internal error: capture_now set but screenshot path missing".to_owned(),
-                );
-            };
-            let rgb = painter.capture_back_buffer_rgb()?;
-            crate::png::write_rgb_png(&path, &rgb)?;
+        let mut surface = CocoaSurface {
+            cocoa: &cocoa,
+            painter: &mut painter,
+            scale,
+            viewport,
+            css_viewport,
+        };
+        if frames.frame(app, tick, &mut surface)? == FrameStep::Done {
             break;
         }
 
-        if needs_redraw {
-            painter.ensure_back_buffer(viewport)?;
-            let mut scaled_painter = ScaledPainter::new(&mut painter, scale);
-            app.render(&mut scaled_painter, css_viewport)?;
-            needs_redraw = false;
-
-            let image = painter.create_cgimage()?;
-            cocoa.present_image(image);
-            unsafe { CFRelease(image as *const c_void) };
-
-            if ready_for_screenshot {
-                has_rendered_ready_state = true;
-                if capture_after_render {
-                    let Some(path) = screenshot_path.take() else {
-                        return Err(
-                            "Internal error: capture_after_render set but screenshot path missing"
-                                .to_owned(),
-                        );
-                    };
-                    let rgb = painter.capture_back_buffer_rgb()?;
-                    crate::png::write_rgb_png(&path, &rgb)?;
-                    break;
-                }
-            }
-        }
-
-        if processed == 0 && !needs_redraw {
+        if processed == 0 {
             std::thread::sleep(Duration::from_millis(10));
         }
     }
@@ -264,6 +285,40 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
     Ok(())
 }
 
+/// The window's back buffer, shown through the view's layer.
+struct CocoaSurface<'a> {
+    cocoa: &'a CocoaApp,
+    painter: &'a mut MacPainter,
+    scale: ScaleFactor,
+    viewport: Viewport,
+    css_viewport: Viewport,
+}
+
+impl<A: App + ?Sized> FrameSurface<A> for CocoaSurface<'_> {
+    fn page_viewport(&self) -> Viewport {
+        self.css_viewport
+    }
+
+    fn render(&mut self, app: &mut A) -> Result<(), String> {
+        self.painter.ensure_back_buffer(self.viewport)?;
+        app.render(
+            &mut ScaledPainter::new(self.painter, self.scale),
+            self.css_viewport,
+        )
+    }
+
+    fn present(&mut self) -> Result<(), String> {
+        let image = self.painter.create_cgimage()?;
+        self.cocoa.present_image(image);
+        unsafe { CFRelease(image as *const c_void) };
+        Ok(())
+    }
+
+    fn capture(&mut self) -> Result<RgbImage, String> {
+        self.painter.capture_back_buffer_rgb()
+    }
+}
+
 struct CocoaApp {
     app: Id,
     window: Id,
@@ -459,12 +514,125 @@ impl CocoaApp {
         }
     }
 
+    fn event_scroll_delta_x(&self, event: Id) -> c_double {
+        unsafe {
+            let f: unsafe extern "C" fn(Id, Sel) -> c_double =
+                std::mem::transmute(objc_msg_send_ptr());
+            f(event, sel(b"scrollingDeltaX\0"))
+        }
+    }
+
     fn event_key_code(&self, event: Id) -> u16 {
         unsafe {
             let f: unsafe extern "C" fn(Id, Sel) -> u16 = std::mem::transmute(objc_msg_send_ptr());
             f(event, sel(b"keyCode\0"))
         }
     }
+
+    fn event_button_number(&self, event: Id) -> c_long {
+        unsafe {
+            let f: unsafe extern "C" fn(Id, Sel) -> c_long =
+                std::mem::transmute(objc_msg_send_ptr());
+            f(event, sel(b"buttonNumber\0"))
+        }
+    }
+
+    fn event_modifier_flags(&self, event: Id) -> c_ulong {
+        unsafe {
+            let f: unsafe extern "C" fn(Id, Sel) -> c_ulong =
+                std::mem::transmute(objc_msg_send_ptr());
+            f(event, sel(b"modifierFlags\0"))
+        }
+    }
+
+    fn event_characters(&self, event: Id, ignoring_modifiers: bool) -> Option<String> {
+        let selector: &[u8] = if ignoring_modifiers {
+            b"charactersIgnoringModifiers\0"
+        } else {
+            b"characters\0"
+        };
+        unsafe {
+            let f: unsafe extern "C" fn(Id, Sel) -> Id = std::mem::transmute(objc_msg_send_ptr());
+            let string = f(event, sel(selector));
+            if string.is_null() {
+                return None;
+            }
+            let f: unsafe extern "C" fn(Id, Sel) -> *const c_char =
+                std::mem::transmute(objc_msg_send_ptr());
+            let utf8 = f(string, sel(b"UTF8String\0"));
+            if utf8.is_null() {
+                return None;
+            }
+            Some(
+                std::ffi::CStr::from_ptr(utf8)
+                    .to_string_lossy()
+                    .into_owned(),
+            )
+        }
+    }
+
+    /// The general pasteboard's text, if it holds any.
+    fn pasteboard_text(&self) -> Option<String> {
+        let _pool = AutoreleasePool::new();
+        let pasteboard = unsafe {
+            let f: unsafe extern "C" fn(Id, Sel) -> Id = std::mem::transmute(objc_msg_send_ptr());
+            f(class(b"NSPasteboard\0"), sel(b"generalPasteboard\0"))
+        };
+        if pasteboard.is_null() {
+            return None;
+        }
+        let text_type = nsstring("public.utf8-plain-text").ok()?;
+        let string = unsafe {
+            let f: unsafe extern "C" fn(Id, Sel, Id) -> Id =
+                std::mem::transmute(objc_msg_send_ptr());
+            f(pasteboard, sel(b"stringForType:\0"), text_type)
+        };
+        unsafe { CFRelease(text_type as *const c_void) };
+        if string.is_null() {
+            return None;
+        }
+        let utf8 = unsafe {
+            let f: unsafe extern "C" fn(Id, Sel) -> *const c_char =
+                std::mem::transmute(objc_msg_send_ptr());
+            f(string, sel(b"UTF8String\0"))
+        };
+        if utf8.is_null() {
+            return None;
+        }
+        Some(
+            unsafe { std::ffi::CStr::from_ptr(utf8) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// Command is reported as `ctrl` so that Cmd+L focuses the location field as on other macOS browsers.
+    fn event_key_input(&self, event: Id) -> Option<KeyInput> {
+        let flags = self.event_modifier_flags(event);
+        let ctrl = flags & (MODIFIER_FLAG_CONTROL | MODIFIER_FLAG_COMMAND) != 0;
+        let alt = flags & MODIFIER_FLAG_OPTION != 0;
+        let key = match self.event_key_code(event) {
+            KEY_CODE_RETURN | KEY_CODE_KEYPAD_ENTER => Key::Enter,
+            KEY_CODE_TAB => Key::Tab,
+            KEY_CODE_DELETE => Key::Backspace,
+            KEY_CODE_ESCAPE => Key::Escape,
+            KEY_CODE_F12 => Key::F12,
+            KEY_CODE_HOME => Key::Home,
+            KEY_CODE_FORWARD_DELETE => Key::Delete,
+            KEY_CODE_END => Key::End,
+            KEY_CODE_LEFT_ARROW => Key::Left,
+            KEY_CODE_RIGHT_ARROW => Key::Right,
+            _ => {
+                let ch = self.event_characters(event, ctrl)?.chars().next()?;
+                // AppKit reports function and arrow keys as private-use characters.
+                if ch.is_control() || ('\u{f700}'..='\u{f8ff}').contains(&ch) {
+                    return None;
+                }
+                Key::Char(ch)
+            }
+        };
+        Some(KeyInput { key, ctrl, alt })
+    }
 }
 
 struct AutoreleasePool(Id);
@@ -492,6 +660,15 @@ impl Drop for AutoreleasePool {
     }
 }
 
+fn set_window_title(window: Id, title: &str) -> Result<(), String> {
+    let title = nsstring(title)?;
+    let set_title: unsafe extern "C" fn(Id, Sel, Id) =
+        unsafe { std::mem::transmute(objc_msg_send_ptr()) };
+    unsafe { set_title(window, sel(b"setTitle:\0"), title) };
+    unsafe { CFRelease(title as *const c_void) };
+    Ok(())
+}
+
 fn create_window(width_css: i32, height_css: i32, title: &str) -> Result<Id, String> {
     let window_cls = class(b"NSWindow\0");
 
@@ -528,15 +705,12 @@ fn create_window(width_css: i32, height_css: i32, title: &str) -> Result<Id, Str
         return Err("NSWindow.initWithContentRect returned null".to_owned());
     }
 
-    let title = nsstring(title)?;
-    let set_title: unsafe extern "C" fn(Id, Sel, Id) =
-        unsafe { std::mem::transmute(objc_msg_send_ptr()) };
-    unsafe { set_title(window, sel(b"setTitle:\0"), title) };
-    unsafe { CFRelease(title as *const c_void) };
+    set_window_title(window, title)?;
 
     let released: unsafe extern "C" fn(Id, Sel, ObjcBool) =
         unsafe { std::mem::transmute(objc_msg_send_ptr()) };
     unsafe { released(window, sel(b"setReleasedWhenClosed:\0"), NO) };
+    unsafe { released(window, sel(b"setAcceptsMouseMovedEvents:\0"), YES) };
 
     let make_key: unsafe extern "C" fn(Id, Sel, Id) =
         unsafe { std::mem::transmute(objc_msg_send_ptr()) };
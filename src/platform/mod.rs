@@ -1,5 +1,11 @@
+#[cfg(target_os = "linux")]
+mod fling;
 #[cfg(target_os = "macos")]
 mod macos;
+mod recording;
+mod software;
+#[cfg(target_os = "linux")]
+mod touch;
 #[cfg(target_os = "linux")]
 mod wayland;
 #[cfg(target_os = "windows")]
@@ -7,20 +13,345 @@ mod windows;
 #[cfg(target_os = "linux")]
 mod x11;
 
-use crate::app::App;
+pub(crate) use software::SoftwarePainter;
+
+use crate::app::{App, TickResult};
+use crate::image::{Argb32Image, RgbImage};
+use crate::render::Viewport;
 #[cfg(target_os = "linux")]
 use std::ffi::OsStr;
+use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a screenshot or headless run waits for resources still loading once the page is
+/// ready, before it finishes without them.
+const SCREENSHOT_RESOURCE_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Default, Clone)]
 pub struct WindowOptions {
-    pub screenshot_path: Option<PathBuf>,
+    pub backend: Backend,
+    pub screenshot: Option<ScreenshotTarget>,
+    /// Capture the whole page rather than the viewport.
+    pub full_page_screenshot: bool,
     pub headless: bool,
     pub initial_width_px: Option<i32>,
     pub initial_height_px: Option<i32>,
+    /// Append every input the window gets to this file.
+    pub record: Option<PathBuf>,
+    /// Feed the inputs of a recording to the app, headlessly with the software backend.
+    pub replay: Option<PathBuf>,
+}
+
+/// What paints the page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// The platform's window system and text rendering.
+    #[default]
+    Native,
+    /// The built-in CPU rasterizer and font; always headless.
+    Software,
+}
+
+impl Backend {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "native" => Some(Backend::Native),
+            "software" => Some(Backend::Software),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreenshotTarget {
+    File(PathBuf),
+    Stdout,
+}
+
+impl ScreenshotTarget {
+    fn write_png(&self, image: &RgbImage) -> Result<(), String> {
+        match self {
+            ScreenshotTarget::File(path) => crate::png::write_rgb_png(path, image),
+            ScreenshotTarget::Stdout => {
+                let mut out = std::io::stdout().lock();
+                crate::png::write_rgb_png_to(&mut out, image)?;
+                out.flush()
+                    .map_err(|err| format!("Failed to flush stdout: {err}"))
+            }
+        }
+    }
+}
+
+/// The title and icon a window shows, so backends only pass on what the app changed.
+struct WindowIdentity {
+    title: Option<String>,
+    icon: Option<Arc<Argb32Image>>,
+}
+
+impl WindowIdentity {
+    /// Starts from the title the window was created with.
+    fn new(title: &str) -> Self {
+        Self {
+            title: Some(title.to_owned()),
+            icon: None,
+        }
+    }
+
+    /// The app's title, when it differs from the one shown.
+    fn title_change<A: App + ?Sized>(&mut self, app: &A) -> Option<String> {
+        let title = app.window_title()?;
+        if self.title.as_deref() == Some(title.as_str()) {
+            return None;
+        }
+        self.title = Some(title.clone());
+        Some(title)
+    }
+
+    /// The app's icon, when it is a different image from the one shown.
+    fn icon_change<A: App + ?Sized>(&mut self, app: &A) -> Option<Arc<Argb32Image>> {
+        let icon = app.window_icon()?;
+        if self
+            .icon
+            .as_ref()
+            .is_some_and(|shown| Arc::ptr_eq(shown, &icon))
+        {
+            return None;
+        }
+        self.icon = Some(Arc::clone(&icon));
+        Some(icon)
+    }
+}
+
+/// Renders the page at one viewport height after another and stacks the captures into a single
+/// image of the whole page. The last tile is scrolled only as far as the page goes, so it
+/// overlaps the one above it. `render_tile` paints the app at its current scroll offset and
+/// returns the back buffer.
+fn capture_full_page<A: App + ?Sized>(
+    app: &mut A,
+    viewport: Viewport,
+    mut render_tile: impl FnMut(&mut A) -> Result<RgbImage, String>,
+) -> Result<RgbImage, String> {
+    let viewport_height_px = viewport.height_px.max(1);
+    let mut page: Option<RgbImage> = None;
+    let mut top_px = 0i32;
+    loop {
+        app.set_scroll_y_px(top_px);
+        let tile = render_tile(app)?;
+        let document_height_px = app
+            .document_height_px()
+            .unwrap_or(0)
+            .max(viewport_height_px);
+        // Tiles are in device pixels; the page is scrolled in CSS pixels.
+        let to_device_rows = |css_px: i32| {
+            (i64::from(css_px.max(0)) * i64::from(tile.height) / i64::from(viewport_height_px))
+                as usize
+        };
+        let page = match &mut page {
+            Some(page) => page,
+            None => {
+                let height = u32::try_from(to_device_rows(document_height_px))
+                    .map_err(|_| "Full-page screenshot is too tall".to_owned())?;
+                let len = (tile.width as usize)
+                    .checked_mul(height as usize)
+                    .and_then(|pixels| pixels.checked_mul(3))
+                    .ok_or_else(|| "Full-page screenshot is too large".to_owned())?;
+                page.insert(RgbImage::new(tile.width, height, vec![255; len])?)
+            }
+        };
+        if tile.width != page.width {
+            return Err(format!(
+                "Full-page screenshot tile width changed from {} to {}",
+                page.width, tile.width
+            ));
+        }
+
+        let stride = tile.row_stride_bytes();
+        let page_rows = page.height as usize;
+        let first_row = to_device_rows(app.scroll_y_px()).min(page_rows);
+        let rows = (tile.height as usize).min(page_rows - first_row);
+        page.data[first_row * stride..(first_row + rows) * stride]
+            .copy_from_slice(&tile.data[..rows * stride]);
+
+        top_px = top_px.saturating_add(viewport_height_px);
+        if top_px >= document_height_px {
+            break;
+        }
+    }
+    page.ok_or_else(|| "Internal error: full-page screenshot has no tiles".to_owned())
+}
+
+/// A backend's back buffer and window, as `FrameScheduler` draws and captures them.
+trait FrameSurface<A: App + ?Sized> {
+    /// The viewport the page is laid out in, in CSS pixels.
+    fn page_viewport(&self) -> Viewport;
+
+    /// Whether a frame drawn now could be shown; Wayland waits for the compositor's callback.
+    fn can_present(&self) -> bool {
+        true
+    }
+
+    /// Paints the app into the back buffer.
+    fn render(&mut self, app: &mut A) -> Result<(), String>;
+
+    /// Shows the back buffer in the window.
+    fn present(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Reads the back buffer back for a screenshot.
+    fn capture(&mut self) -> Result<RgbImage, String>;
+}
+
+/// What a backend's loop does after `FrameScheduler::frame`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FrameStep {
+    /// Nothing is left to draw until an event or the app asks for it, or until the frame can be
+    /// shown, so the loop waits for events.
+    Idle,
+    /// The screenshot was written or the headless run finished, so the window closes.
+    Done,
+}
+
+/// When each backend redraws, and when a screenshot or headless run is complete: once the page
+/// is ready, has been drawn since, and has its resources loaded or has waited
+/// `SCREENSHOT_RESOURCE_WAIT_TIMEOUT` for them. Backends only feed it events and a surface.
+struct FrameScheduler {
+    screenshot: Option<ScreenshotTarget>,
+    full_page: bool,
+    headless: bool,
+    needs_redraw: bool,
+    has_rendered_ready_state: bool,
+    resource_wait_started: Option<Instant>,
+}
+
+impl FrameScheduler {
+    fn new(options: &WindowOptions) -> Self {
+        Self {
+            screenshot: options.screenshot.clone(),
+            full_page: options.full_page_screenshot,
+            headless: options.headless,
+            needs_redraw: true,
+            has_rendered_ready_state: false,
+            resource_wait_started: None,
+        }
+    }
+
+    fn request_redraw(&mut self) {
+        self.needs_redraw = true;
+    }
+
+    /// Redraws if `tick`, from the app handling an event, asks for it.
+    fn note(&mut self, tick: TickResult) {
+        self.needs_redraw |= tick.needs_redraw;
+    }
+
+    /// The viewport or scale changed, so the page is drawn again before a screenshot is taken.
+    fn invalidate(&mut self) {
+        self.needs_redraw = true;
+        self.has_rendered_ready_state = false;
+        self.resource_wait_started = None;
+    }
+
+    /// Takes the app's `tick` for this turn of the loop: draws a due frame into `surface` when it
+    /// can be shown, and writes the screenshot once the page is ready for it.
+    fn frame<A: App + ?Sized>(
+        &mut self,
+        app: &mut A,
+        tick: TickResult,
+        surface: &mut impl FrameSurface<A>,
+    ) -> Result<FrameStep, String> {
+        self.note(tick);
+        let ready_for_screenshot = tick.ready_for_screenshot;
+        if !ready_for_screenshot {
+            self.has_rendered_ready_state = false;
+            self.resource_wait_started = None;
+        }
+
+        let should_wait_for_resources = tick.pending_resources > 0;
+        let timed_out_waiting_for_resources = self
+            .resource_wait_started
+            .is_some_and(|started| started.elapsed() >= SCREENSHOT_RESOURCE_WAIT_TIMEOUT);
+        let can_complete = !should_wait_for_resources || timed_out_waiting_for_resources;
+        let wants_screenshot = self.screenshot.is_some();
+
+        if ready_for_screenshot {
+            if !self.has_rendered_ready_state {
+                if wants_screenshot || self.headless {
+                    self.needs_redraw = true;
+                }
+            } else if should_wait_for_resources {
+                self.resource_wait_started.get_or_insert(Instant::now());
+            } else {
+                self.resource_wait_started = None;
+            }
+        }
+
+        let complete = ready_for_screenshot && self.has_rendered_ready_state && can_complete;
+        if complete && !self.needs_redraw {
+            if wants_screenshot {
+                self.write_screenshot(app, surface)?;
+                return Ok(FrameStep::Done);
+            }
+            if self.headless {
+                return Ok(FrameStep::Done);
+            }
+        }
+
+        if self.needs_redraw && surface.can_present() {
+            surface.render(app)?;
+            self.needs_redraw = false;
+            surface.present()?;
+            if ready_for_screenshot {
+                self.has_rendered_ready_state = true;
+                if complete && wants_screenshot {
+                    self.write_screenshot(app, surface)?;
+                    return Ok(FrameStep::Done);
+                }
+            }
+        }
+
+        Ok(FrameStep::Idle)
+    }
+
+    fn write_screenshot<A: App + ?Sized>(
+        &mut self,
+        app: &mut A,
+        surface: &mut impl FrameSurface<A>,
+    ) -> Result<(), String> {
+        let Some(target) = self.screenshot.take() else {
+            return Ok(());
+        };
+        let rgb = if self.full_page {
+            capture_full_page(app, surface.page_viewport(), |app| {
+                surface.render(app)?;
+                surface.capture()
+            })?
+        } else {
+            surface.capture()?
+        };
+        target.write_png(&rgb)
+    }
 }
 
-pub fn run_window(title: &str, options: WindowOptions, app: &mut impl App) -> Result<(), String> {
+pub fn run_window(
+    title: &str,
+    mut options: WindowOptions,
+    app: &mut impl App,
+) -> Result<(), String> {
+    if let Some(path) = options.record.take() {
+        let mut recorder = recording::Recorder::create(&path, app)?;
+        return open_window(title, options, &mut recorder);
+    }
+    open_window(title, options, app)
+}
+
+fn open_window(title: &str, options: WindowOptions, app: &mut impl App) -> Result<(), String> {
+    if options.backend == Backend::Software || options.replay.is_some() {
+        return software::run_window(options, app);
+    }
+
     #[cfg(target_os = "linux")]
     return run_linux_window(title, options, app);
 
@@ -164,9 +495,15 @@ fn is_wayland_session_from_values(
 #[cfg(all(test, target_os = "linux"))]
 mod tests {
     use super::{
-        LinuxBackendPreference, is_wayland_session_from_values, linux_backend_preference_from_str,
+        FrameScheduler, FrameStep, FrameSurface, LinuxBackendPreference, WindowIdentity,
+        WindowOptions, capture_full_page, is_wayland_session_from_values,
+        linux_backend_preference_from_str,
     };
+    use crate::app::{App, TickResult};
+    use crate::image::{Argb32Image, RgbImage};
+    use crate::render::{Painter, Viewport};
     use std::ffi::OsStr;
+    use std::sync::Arc;
 
     #[test]
     fn linux_backend_preference_parses_expected_values() {
@@ -209,4 +546,181 @@ mod tests {
         assert!(!is_wayland_session_from_values(None, Some("x11")));
         assert!(!is_wayland_session_from_values(Some(OsStr::new("")), None));
     }
+
+    /// A 250px page whose tiles paint each device row with the CSS row it shows.
+    struct StripedPage {
+        scroll_y_px: i32,
+    }
+
+    impl App for StripedPage {
+        fn tick(&mut self) -> Result<TickResult, String> {
+            Ok(TickResult::default())
+        }
+
+        fn render(
+            &mut self,
+            _painter: &mut dyn Painter,
+            _viewport: Viewport,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn document_height_px(&self) -> Option<i32> {
+            Some(250)
+        }
+
+        fn scroll_y_px(&self) -> i32 {
+            self.scroll_y_px
+        }
+
+        fn set_scroll_y_px(&mut self, y_px: i32) {
+            self.scroll_y_px = y_px.min(150);
+        }
+    }
+
+    struct TitledPage {
+        title: &'static str,
+        icon: Option<Arc<Argb32Image>>,
+    }
+
+    impl App for TitledPage {
+        fn tick(&mut self) -> Result<TickResult, String> {
+            Ok(TickResult::default())
+        }
+
+        fn render(
+            &mut self,
+            _painter: &mut dyn Painter,
+            _viewport: Viewport,
+        ) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn window_title(&self) -> Option<String> {
+            Some(self.title.to_owned())
+        }
+
+        fn window_icon(&self) -> Option<Arc<Argb32Image>> {
+            self.icon.clone()
+        }
+    }
+
+    #[test]
+    fn window_identity_reports_only_changes() {
+        let mut identity = WindowIdentity::new("https://example.com/");
+        let mut page = TitledPage {
+            title: "https://example.com/",
+            icon: None,
+        };
+        assert_eq!(identity.title_change(&page), None);
+        assert!(identity.icon_change(&page).is_none());
+
+        page.title = "Example Domain";
+        page.icon = Some(Arc::new(Argb32Image::new(1, 1, vec![0; 4]).unwrap()));
+        assert_eq!(
+            identity.title_change(&page).as_deref(),
+            Some("Example Domain")
+        );
+        assert!(identity.icon_change(&page).is_some());
+        assert_eq!(identity.title_change(&page), None);
+        assert!(identity.icon_change(&page).is_none());
+    }
+
+    #[test]
+    fn full_page_capture_stitches_scrolled_tiles() {
+        let mut app = StripedPage { scroll_y_px: 0 };
+        let viewport = Viewport {
+            width_px: 2,
+            height_px: 100,
+        };
+        let mut tiles = 0;
+        let page = capture_full_page(&mut app, viewport, |app| {
+            tiles += 1;
+            let top = app.scroll_y_px();
+            let data = (0..200)
+                .flat_map(|row| [(top + row / 2) as u8; 6])
+                .collect();
+            RgbImage::new(2, 200, data)
+        })
+        .unwrap();
+
+        assert_eq!(tiles, 3);
+        assert_eq!((page.width, page.height), (2, 500));
+        for row in 0..500 {
+            assert_eq!(page.data[row * 6], (row / 2) as u8, "row {row}");
+        }
+    }
+
+    /// Counts the frames drawn into it.
+    struct CountingSurface {
+        renders: usize,
+    }
+
+    impl<A: App + ?Sized> FrameSurface<A> for CountingSurface {
+        fn page_viewport(&self) -> Viewport {
+            Viewport {
+                width_px: 100,
+                height_px: 100,
+            }
+        }
+
+        fn render(&mut self, _app: &mut A) -> Result<(), String> {
+            self.renders += 1;
+            Ok(())
+        }
+
+        fn capture(&mut self) -> Result<RgbImage, String> {
+            RgbImage::new(1, 1, vec![0; 3])
+        }
+    }
+
+    #[test]
+    fn headless_runs_finish_once_the_ready_page_is_drawn_and_loaded() {
+        let mut frames = FrameScheduler::new(&WindowOptions {
+            headless: true,
+            ..WindowOptions::default()
+        });
+        let mut app = StripedPage { scroll_y_px: 0 };
+        let mut surface = CountingSurface { renders: 0 };
+        let loading = TickResult::default();
+        let ready = TickResult {
+            ready_for_screenshot: true,
+            pending_resources: 1,
+            ..TickResult::default()
+        };
+        let loaded = TickResult {
+            ready_for_screenshot: true,
+            ..TickResult::default()
+        };
+
+        assert_eq!(
+            frames.frame(&mut app, loading, &mut surface),
+            Ok(FrameStep::Idle)
+        );
+        assert_eq!(
+            frames.frame(&mut app, loading, &mut surface),
+            Ok(FrameStep::Idle)
+        );
+        assert_eq!(surface.renders, 1);
+        assert_eq!(
+            frames.frame(&mut app, ready, &mut surface),
+            Ok(FrameStep::Idle)
+        );
+        assert_eq!(
+            frames.frame(&mut app, ready, &mut surface),
+            Ok(FrameStep::Idle)
+        );
+        assert_eq!(surface.renders, 2);
+
+        frames.invalidate();
+        assert_eq!(
+            frames.frame(&mut app, loaded, &mut surface),
+            Ok(FrameStep::Idle)
+        );
+        assert_eq!(surface.renders, 3);
+        assert_eq!(
+            frames.frame(&mut app, loaded, &mut surface),
+            Ok(FrameStep::Done)
+        );
+    }
 }
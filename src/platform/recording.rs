@@ -0,0 +1,503 @@
+use crate::app::{App, Key, KeyInput, TickResult};
+use crate::image::Argb32Image;
+use crate::render::{Painter, Viewport};
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const HEADER: &str = "# one-agent-one-browser input recording";
+
+/// One input the platform handed to the app.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum InputEvent {
+    Back,
+    Forward,
+    MouseDown { x_px: i32, y_px: i32 },
+    MouseUp { x_px: i32, y_px: i32 },
+    MouseMove { x_px: i32, y_px: i32 },
+    Wheel { delta_y_px: i32 },
+    HorizontalWheel { delta_x_px: i32 },
+    WheelNotch { delta_y_px: i32 },
+    Fling { velocity_y_px_per_s: f64 },
+    Key(KeyInput),
+    Paste(String),
+    Drop(String),
+    Zoom(u32),
+}
+
+/// An input, when it came after the recording started, and the CSS viewport it was given with.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct RecordedEvent {
+    pub(super) at: Duration,
+    pub(super) viewport: Viewport,
+    pub(super) event: InputEvent,
+}
+
+impl RecordedEvent {
+    /// One line: `<ms> <width>x<height> <event> <args...>`.
+    fn to_line(&self) -> String {
+        let event = match &self.event {
+            InputEvent::Back => "back".to_owned(),
+            InputEvent::Forward => "forward".to_owned(),
+            InputEvent::MouseDown { x_px, y_px } => format!("mouse_down {x_px} {y_px}"),
+            InputEvent::MouseUp { x_px, y_px } => format!("mouse_up {x_px} {y_px}"),
+            InputEvent::MouseMove { x_px, y_px } => format!("mouse_move {x_px} {y_px}"),
+            InputEvent::Wheel { delta_y_px } => format!("wheel {delta_y_px}"),
+            InputEvent::HorizontalWheel { delta_x_px } => format!("hwheel {delta_x_px}"),
+            InputEvent::WheelNotch { delta_y_px } => format!("wheel_notch {delta_y_px}"),
+            InputEvent::Fling {
+                velocity_y_px_per_s,
+            } => format!("fling {velocity_y_px_per_s}"),
+            InputEvent::Key(input) => {
+                let mut line = format!("key {}", key_name(input.key));
+                if input.ctrl {
+                    line.push_str(" ctrl");
+                }
+                if input.alt {
+                    line.push_str(" alt");
+                }
+                line
+            }
+            InputEvent::Paste(text) => format!("paste {}", escape(text)),
+            InputEvent::Drop(text) => format!("drop {}", escape(text)),
+            InputEvent::Zoom(percent) => format!("zoom {percent}"),
+        };
+        format!(
+            "{} {}x{} {event}",
+            self.at.as_millis(),
+            self.viewport.width_px,
+            self.viewport.height_px
+        )
+    }
+
+    fn parse_line(line: &str) -> Result<RecordedEvent, String> {
+        let mut parts = line.split_whitespace();
+        let mut next = || parts.next().ok_or_else(|| "missing field".to_owned());
+        let at = Duration::from_millis(number(next()?)?);
+        let (width, height) = next()?
+            .split_once('x')
+            .ok_or_else(|| "expected a <width>x<height> viewport".to_owned())?;
+        let viewport = Viewport {
+            width_px: number(width)?,
+            height_px: number(height)?,
+        };
+        let event = match next()? {
+            "back" => InputEvent::Back,
+            "forward" => InputEvent::Forward,
+            "mouse_down" => InputEvent::MouseDown {
+                x_px: number(next()?)?,
+                y_px: number(next()?)?,
+            },
+            "mouse_up" => InputEvent::MouseUp {
+                x_px: number(next()?)?,
+                y_px: number(next()?)?,
+            },
+            "mouse_move" => InputEvent::MouseMove {
+                x_px: number(next()?)?,
+                y_px: number(next()?)?,
+            },
+            "wheel" => InputEvent::Wheel {
+                delta_y_px: number(next()?)?,
+            },
+            "hwheel" => InputEvent::HorizontalWheel {
+                delta_x_px: number(next()?)?,
+            },
+            "wheel_notch" => InputEvent::WheelNotch {
+                delta_y_px: number(next()?)?,
+            },
+            "fling" => InputEvent::Fling {
+                velocity_y_px_per_s: number(next()?)?,
+            },
+            "key" => {
+                let key = parse_key(next()?)?;
+                let mut input = KeyInput::plain(key);
+                while let Ok(modifier) = next() {
+                    match modifier {
+                        "ctrl" => input.ctrl = true,
+                        "alt" => input.alt = true,
+                        other => return Err(format!("unknown key modifier {other:?}")),
+                    }
+                }
+                InputEvent::Key(input)
+            }
+            "paste" => InputEvent::Paste(unescape(next().unwrap_or_default())?),
+            "drop" => InputEvent::Drop(unescape(next().unwrap_or_default())?),
+            "zoom" => InputEvent::Zoom(number(next()?)?),
+            other => return Err(format!("unknown event {other:?}")),
+        };
+        if parts.next().is_some() {
+            return Err("unexpected trailing fields".to_owned());
+        }
+        Ok(RecordedEvent {
+            at,
+            viewport,
+            event,
+        })
+    }
+}
+
+fn number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid number {value:?}"))
+}
+
+fn key_name(key: Key) -> String {
+    match key {
+        Key::Char(ch) => format!("char:{:x}", u32::from(ch)),
+        Key::Backspace => "backspace".to_owned(),
+        Key::Delete => "delete".to_owned(),
+        Key::Enter => "enter".to_owned(),
+        Key::Escape => "escape".to_owned(),
+        Key::Tab => "tab".to_owned(),
+        Key::Left => "left".to_owned(),
+        Key::Right => "right".to_owned(),
+        Key::Home => "home".to_owned(),
+        Key::End => "end".to_owned(),
+        Key::F12 => "f12".to_owned(),
+    }
+}
+
+fn parse_key(name: &str) -> Result<Key, String> {
+    if let Some(hex) = name.strip_prefix("char:") {
+        return u32::from_str_radix(hex, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .map(Key::Char)
+            .ok_or_else(|| format!("invalid key {name:?}"));
+    }
+    Ok(match name {
+        "backspace" => Key::Backspace,
+        "delete" => Key::Delete,
+        "enter" => Key::Enter,
+        "escape" => Key::Escape,
+        "tab" => Key::Tab,
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "f12" => Key::F12,
+        _ => return Err(format!("unknown key {name:?}")),
+    })
+}
+
+/// Keeps text to one whitespace-free field.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            ' ' => escaped.push_str("\\s"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            ch if ch.is_whitespace() => escaped.push_str(&format!("\\u{:x};", u32::from(ch))),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn unescape(field: &str) -> Result<String, String> {
+    let mut text = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => text.push('\\'),
+            Some('s') => text.push(' '),
+            Some('t') => text.push('\t'),
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take_while(|&ch| ch != ';').collect();
+                let ch = u32::from_str_radix(&hex, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| format!("invalid escape \\u{hex};"))?;
+                text.push(ch);
+            }
+            other => return Err(format!("invalid escape in {field:?}: \\{other:?}")),
+        }
+    }
+    Ok(text)
+}
+
+/// Reads a file written by [`Recorder`].
+pub(super) fn read_recording(path: &Path) -> Result<Vec<RecordedEvent>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read input recording {}: {err}", path.display()))?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(index, line)| {
+            RecordedEvent::parse_line(line).map_err(|err| {
+                format!(
+                    "Invalid input recording {} line {}: {err}",
+                    path.display(),
+                    index + 1
+                )
+            })
+        })
+        .collect()
+}
+
+/// Hands a recorded input to the app as the platform did. Keys the app does not consume are
+/// dropped: the defaults a platform falls back to are recorded as events of their own.
+pub(super) fn dispatch<A: App + ?Sized>(
+    app: &mut A,
+    event: &InputEvent,
+    viewport: Viewport,
+) -> Result<TickResult, String> {
+    match event {
+        InputEvent::Back => app.navigate_back(),
+        InputEvent::Forward => app.navigate_forward(),
+        InputEvent::MouseDown { x_px, y_px } => app.mouse_down(*x_px, *y_px, viewport),
+        InputEvent::MouseUp { x_px, y_px } => app.mouse_up(*x_px, *y_px, viewport),
+        InputEvent::MouseMove { x_px, y_px } => app.mouse_move(*x_px, *y_px, viewport),
+        InputEvent::Wheel { delta_y_px } => app.mouse_wheel(*delta_y_px, viewport),
+        InputEvent::HorizontalWheel { delta_x_px } => app.horizontal_wheel(*delta_x_px, viewport),
+        InputEvent::WheelNotch { delta_y_px } => app.wheel_notch(*delta_y_px, viewport),
+        InputEvent::Fling {
+            velocity_y_px_per_s,
+        } => app.scroll_fling(*velocity_y_px_per_s, viewport),
+        InputEvent::Key(input) => Ok(app.key_down(*input, viewport)?.unwrap_or_default()),
+        InputEvent::Paste(text) => app.paste_text(text, viewport),
+        InputEvent::Drop(text) => app.open_dropped(text, viewport),
+        InputEvent::Zoom(percent) => {
+            app.set_zoom_percent(*percent);
+            Ok(TickResult {
+                needs_redraw: true,
+                ..TickResult::default()
+            })
+        }
+    }
+}
+
+/// Passes everything through to `app`, appending each input to a file with the time since the
+/// recorder was created. Every line is written as it happens, so a crash keeps what led up to it.
+pub(super) struct Recorder<'a, A: App + ?Sized> {
+    app: &'a mut A,
+    file: File,
+    started: Instant,
+    /// The viewport of the last render, for inputs that come without one.
+    viewport: Viewport,
+    /// A failed write from a call that cannot return it, reported by the next tick.
+    error: Option<String>,
+}
+
+impl<'a, A: App + ?Sized> Recorder<'a, A> {
+    pub(super) fn create(path: &Path, app: &'a mut A) -> Result<Self, String> {
+        let mut file = File::create(path)
+            .map_err(|err| format!("Failed to create input recording {}: {err}", path.display()))?;
+        writeln!(file, "{HEADER}")
+            .map_err(|err| format!("Failed to write input recording {}: {err}", path.display()))?;
+        Ok(Self {
+            app,
+            file,
+            started: Instant::now(),
+            viewport: Viewport::default(),
+            error: None,
+        })
+    }
+
+    fn record(&mut self, event: InputEvent, viewport: Viewport) -> Result<(), String> {
+        let event = RecordedEvent {
+            at: self.started.elapsed(),
+            viewport,
+            event,
+        };
+        writeln!(self.file, "{}", event.to_line())
+            .map_err(|err| format!("Failed to write input recording: {err}"))
+    }
+}
+
+impl<A: App + ?Sized> App for Recorder<'_, A> {
+    fn tick(&mut self) -> Result<TickResult, String> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.app.tick()
+    }
+
+    fn render(&mut self, painter: &mut dyn Painter, viewport: Viewport) -> Result<(), String> {
+        self.viewport = viewport;
+        self.app.render(painter, viewport)
+    }
+
+    fn navigate_back(&mut self) -> Result<TickResult, String> {
+        self.record(InputEvent::Back, self.viewport)?;
+        self.app.navigate_back()
+    }
+
+    fn navigate_forward(&mut self) -> Result<TickResult, String> {
+        self.record(InputEvent::Forward, self.viewport)?;
+        self.app.navigate_forward()
+    }
+
+    fn mouse_down(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        self.record(InputEvent::MouseDown { x_px, y_px }, viewport)?;
+        self.app.mouse_down(x_px, y_px, viewport)
+    }
+
+    fn mouse_up(&mut self, x_px: i32, y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
+        self.record(InputEvent::MouseUp { x_px, y_px }, viewport)?;
+        self.app.mouse_up(x_px, y_px, viewport)
+    }
+
+    fn mouse_wheel(&mut self, delta_y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
+        self.record(InputEvent::Wheel { delta_y_px }, viewport)?;
+        self.app.mouse_wheel(delta_y_px, viewport)
+    }
+
+    fn horizontal_wheel(
+        &mut self,
+        delta_x_px: i32,
+        viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        self.record(InputEvent::HorizontalWheel { delta_x_px }, viewport)?;
+        self.app.horizontal_wheel(delta_x_px, viewport)
+    }
+
+    fn wheel_notch(&mut self, delta_y_px: i32, viewport: Viewport) -> Result<TickResult, String> {
+        self.record(InputEvent::WheelNotch { delta_y_px }, viewport)?;
+        self.app.wheel_notch(delta_y_px, viewport)
+    }
+
+    fn scroll_fling(
+        &mut self,
+        velocity_y_px_per_s: f64,
+        viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        self.record(
+            InputEvent::Fling {
+                velocity_y_px_per_s,
+            },
+            viewport,
+        )?;
+        self.app.scroll_fling(velocity_y_px_per_s, viewport)
+    }
+
+    fn mouse_move(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        viewport: Viewport,
+    ) -> Result<TickResult, String> {
+        self.record(InputEvent::MouseMove { x_px, y_px }, viewport)?;
+        self.app.mouse_move(x_px, y_px, viewport)
+    }
+
+    fn key_down(
+        &mut self,
+        input: KeyInput,
+        viewport: Viewport,
+    ) -> Result<Option<TickResult>, String> {
+        self.record(InputEvent::Key(input), viewport)?;
+        self.app.key_down(input, viewport)
+    }
+
+    fn paste_text(&mut self, text: &str, viewport: Viewport) -> Result<TickResult, String> {
+        self.record(InputEvent::Paste(text.to_owned()), viewport)?;
+        self.app.paste_text(text, viewport)
+    }
+
+    fn open_dropped(&mut self, dropped: &str, viewport: Viewport) -> Result<TickResult, String> {
+        self.record(InputEvent::Drop(dropped.to_owned()), viewport)?;
+        self.app.open_dropped(dropped, viewport)
+    }
+
+    fn zoom_percent(&self) -> u32 {
+        self.app.zoom_percent()
+    }
+
+    fn set_zoom_percent(&mut self, percent: u32) {
+        if let Err(err) = self.record(InputEvent::Zoom(percent), self.viewport) {
+            self.error.get_or_insert(err);
+        }
+        self.app.set_zoom_percent(percent);
+    }
+
+    fn document_height_px(&self) -> Option<i32> {
+        self.app.document_height_px()
+    }
+
+    fn scroll_y_px(&self) -> i32 {
+        self.app.scroll_y_px()
+    }
+
+    fn set_scroll_y_px(&mut self, y_px: i32) {
+        self.app.set_scroll_y_px(y_px);
+    }
+
+    fn window_title(&self) -> Option<String> {
+        self.app.window_title()
+    }
+
+    fn window_icon(&self) -> Option<Arc<Argb32Image>> {
+        self.app.window_icon()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_survive_a_round_trip_through_the_file_format() {
+        let viewport = Viewport {
+            width_px: 800,
+            height_px: 600,
+        };
+        let events = vec![
+            InputEvent::MouseDown { x_px: 10, y_px: -3 },
+            InputEvent::WheelNotch { delta_y_px: 120 },
+            InputEvent::HorizontalWheel { delta_x_px: -48 },
+            InputEvent::Fling {
+                velocity_y_px_per_s: -812.5,
+            },
+            InputEvent::Key(KeyInput {
+                key: Key::Char(' '),
+                ctrl: true,
+                alt: false,
+            }),
+            InputEvent::Key(KeyInput::plain(Key::Backspace)),
+            InputEvent::Paste("a b\\c\nd\u{a0}".to_owned()),
+            InputEvent::Drop(String::new()),
+            InputEvent::Zoom(125),
+            InputEvent::Back,
+        ];
+        for (index, event) in events.into_iter().enumerate() {
+            let recorded = RecordedEvent {
+                at: Duration::from_millis(index as u64 * 250),
+                viewport,
+                event,
+            };
+            let line = recorded.to_line();
+            assert_eq!(line.lines().count(), 1, "{line:?}");
+            assert_eq!(RecordedEvent::parse_line(&line), Ok(recorded));
+        }
+
+        assert_eq!(
+            RecordedEvent::parse_line("5 10x10 mouse_down 1 2")
+                .unwrap()
+                .event,
+            InputEvent::MouseDown { x_px: 1, y_px: 2 }
+        );
+        assert!(RecordedEvent::parse_line("5 10x10 mouse_down 1").is_err());
+        assert!(RecordedEvent::parse_line("5 10x10 teleport").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use super::font_data;
+use crate::geom::Color;
+use crate::render::svg::path::{self, PathCommand, Point};
+use crate::render::svg::raster::{Canvas, FillRule};
+use crate::render::{FontMetricsPx, TextStyle, shaping};
+use crate::style::FontFamily;
+use std::sync::OnceLock;
+
+/// Advances and outlines in [`FaceData`] are in thousandths of an em.
+const UNITS_PER_EM: f64 = 1000.0;
+/// Glyph outlines are flattened to within this many pixels.
+const TOLERANCE_PX: f64 = 0.1;
+/// Bold is synthesized by drawing each glyph again this far to the right, in ems.
+const BOLD_OFFSET_EM: f64 = 0.04;
+
+/// A typeface generated into `font_data`: glyphs sorted by character, each with its advance and
+/// its outline as relative SVG path data with y running down from the baseline. The glyph for
+/// `'\0'` is drawn for characters the face lacks.
+pub(super) struct FaceData {
+    pub(super) ascent: i32,
+    pub(super) descent: i32,
+    pub(super) glyphs: &'static [(char, u16, &'static str)],
+}
+
+/// A rasterized glyph: `coverage` rows `width` pixels wide, placed `left`/`top` pixels from the
+/// pen position on the baseline.
+pub(super) struct GlyphMask {
+    pub(super) left: i32,
+    pub(super) top: i32,
+    pub(super) width: usize,
+    pub(super) coverage: Vec<u8>,
+}
+
+fn face(family: FontFamily) -> (&'static FaceData, usize) {
+    match family {
+        FontFamily::SansSerif => (&font_data::SANS, 0),
+        FontFamily::Serif => (&font_data::SERIF, 1),
+        FontFamily::Monospace => (&font_data::MONOSPACE, 2),
+    }
+}
+
+fn outlines(family: FontFamily) -> &'static [Vec<PathCommand>] {
+    static PARSED: [OnceLock<Vec<Vec<PathCommand>>>; 3] =
+        [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+    let (data, index) = face(family);
+    PARSED[index].get_or_init(|| {
+        data.glyphs
+            .iter()
+            .map(|(_, _, outline)| path::parse_path_data(outline))
+            .collect()
+    })
+}
+
+/// Index of the glyph drawn for `ch`, or `None` for characters that draw nothing.
+fn glyph_index(data: &FaceData, ch: char) -> Option<usize> {
+    let ch = match ch {
+        '\u{200b}'..='\u{200f}' | '\u{2060}' | '\u{feff}' | '\u{ad}' => return None,
+        ch if ch.is_control() => ' ',
+        ch => ch,
+    };
+    Some(
+        data.glyphs
+            .binary_search_by_key(&ch, |(glyph_ch, _, _)| *glyph_ch)
+            .unwrap_or(0),
+    )
+}
+
+fn size_px(style: TextStyle) -> f64 {
+    f64::from(style.font_size_px.max(1))
+}
+
+fn bold_offset_px(style: TextStyle) -> f64 {
+    if style.bold {
+        (size_px(style) * BOLD_OFFSET_EM).max(0.5)
+    } else {
+        0.0
+    }
+}
+
+pub(super) fn metrics(style: TextStyle) -> FontMetricsPx {
+    let (data, _) = face(style.font_family);
+    let scale = size_px(style) / UNITS_PER_EM;
+    FontMetricsPx {
+        ascent_px: (f64::from(data.ascent) * scale).round() as i32,
+        descent_px: (f64::from(data.descent) * scale).round() as i32,
+    }
+}
+
+/// Calls `glyph` with each character of `text` and its pen offset from the start of the run,
+/// then returns the run's width. Letter spacing goes between clusters.
+pub(super) fn lay_out(text: &str, style: TextStyle, mut glyph: impl FnMut(char, f64)) -> f64 {
+    let (data, _) = face(style.font_family);
+    let scale = size_px(style) / UNITS_PER_EM;
+    let bold_offset = bold_offset_px(style);
+    let mut pen = 0.0;
+    for (index, ch) in text.chars().enumerate() {
+        if index > 0 && !shaping::is_combining_mark(ch) {
+            pen += f64::from(style.letter_spacing_px);
+        }
+        let Some(glyph_index) = glyph_index(data, ch) else {
+            continue;
+        };
+        glyph(ch, pen);
+        pen += f64::from(data.glyphs[glyph_index].1) * scale + bold_offset;
+    }
+    pen
+}
+
+pub(super) fn text_width_px(text: &str, style: TextStyle) -> i32 {
+    lay_out(text, style, |_, _| {}).round().max(0.0) as i32
+}
+
+/// Renders the glyph for `ch`; `None` when it has no ink, as for a space.
+pub(super) fn rasterize(ch: char, style: TextStyle) -> Option<GlyphMask> {
+    let (data, _) = face(style.font_family);
+    let commands = &outlines(style.font_family)[glyph_index(data, ch)?];
+    if commands.is_empty() {
+        return None;
+    }
+    let scale = size_px(style) / UNITS_PER_EM;
+    let scaled: Vec<PathCommand> = commands
+        .iter()
+        .map(|command| scale_command(*command, scale))
+        .collect();
+    let polygons: Vec<Vec<Point>> = path::flatten(&scaled, TOLERANCE_PX)
+        .into_iter()
+        .map(|polyline| polyline.points)
+        .filter(|points| points.len() > 2)
+        .collect();
+
+    let bold_offset = bold_offset_px(style);
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+    for point in polygons.iter().flatten() {
+        min_x = min_x.min(point.x);
+        min_y = min_y.min(point.y);
+        max_x = max_x.max(point.x + bold_offset);
+        max_y = max_y.max(point.y);
+    }
+    if min_x > max_x || min_y > max_y {
+        return None;
+    }
+    let left = min_x.floor();
+    let top = min_y.floor();
+    let width = (max_x.ceil() - left) as u32 + 1;
+    let height = (max_y.ceil() - top) as u32 + 1;
+
+    let mut canvas = Canvas::new(width, height);
+    let shift = |dx: f64| -> Vec<Vec<Point>> {
+        polygons
+            .iter()
+            .map(|polygon| {
+                polygon
+                    .iter()
+                    .map(|point| Point::new(point.x - left + dx, point.y - top))
+                    .collect()
+            })
+            .collect()
+    };
+    canvas.fill(&shift(0.0), FillRule::NonZero, Color::WHITE, 1.0);
+    if bold_offset > 0.0 {
+        canvas.fill(&shift(bold_offset), FillRule::NonZero, Color::WHITE, 1.0);
+    }
+    let coverage = canvas
+        .pixels()
+        .chunks_exact(4)
+        .map(|pixel| pixel[3])
+        .collect();
+    Some(GlyphMask {
+        left: left as i32,
+        top: top as i32,
+        width: width as usize,
+        coverage,
+    })
+}
+
+fn scale_command(command: PathCommand, scale: f64) -> PathCommand {
+    let scaled = |point: Point| Point::new(point.x * scale, point.y * scale);
+    match command {
+        PathCommand::MoveTo(to) => PathCommand::MoveTo(scaled(to)),
+        PathCommand::LineTo(to) => PathCommand::LineTo(scaled(to)),
+        PathCommand::QuadTo(control, to) => PathCommand::QuadTo(scaled(control), scaled(to)),
+        PathCommand::CubicTo(first, second, to) => {
+            PathCommand::CubicTo(scaled(first), scaled(second), scaled(to))
+        }
+        PathCommand::Close => PathCommand::Close,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_face_has_sorted_glyphs_with_outlines() {
+        for family in [
+            FontFamily::SansSerif,
+            FontFamily::Serif,
+            FontFamily::Monospace,
+        ] {
+            let (data, _) = face(family);
+            assert_eq!(data.glyphs[0].0, '\0');
+            assert!(data.glyphs.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            let outlines = outlines(family);
+            for (index, (ch, _, outline)) in data.glyphs.iter().enumerate() {
+                assert_eq!(
+                    outline.is_empty(),
+                    outlines[index].is_empty(),
+                    "outline of {ch:?} did not parse"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn measures_and_rasterizes_text() {
+        let style = TextStyle {
+            font_size_px: 20,
+            ..TextStyle::default()
+        };
+        let mono = TextStyle {
+            font_family: FontFamily::Monospace,
+            ..style
+        };
+        assert_eq!(text_width_px("iiii", mono), text_width_px("MMMM", mono));
+        assert!(text_width_px("iiii", style) < text_width_px("MMMM", style));
+        let bold = TextStyle {
+            bold: true,
+            ..style
+        };
+        assert!(text_width_px("Hello", bold) > text_width_px("Hello", style));
+        let spaced = TextStyle {
+            letter_spacing_px: 2,
+            ..style
+        };
+        assert_eq!(
+            text_width_px("Hello", spaced),
+            text_width_px("Hello", style) + 8
+        );
+
+        let glyph = rasterize('H', style).unwrap();
+        let height = glyph.coverage.len() / glyph.width;
+        // Cap height sits above the baseline.
+        assert!((-(height as i32)..=-14).contains(&glyph.top));
+        assert!(glyph.coverage.iter().any(|&coverage| coverage == 255));
+        assert!(rasterize(' ', style).is_none());
+        assert!(rasterize('\u{4e2d}', style).is_some());
+    }
+}
@@ -0,0 +1,3223 @@
+// Glyph outlines and advances from the DejaVu Sans, DejaVu Serif and DejaVu Sans Mono fonts,
+// scaled to 1000 units per em, as relative SVG path data with y running down.
+//
+// Copyright (c) 2003 by Bitstream, Inc. All Rights Reserved.
+// Bitstream Vera is a trademark of Bitstream, Inc.
+// DejaVu changes are in public domain.
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of the fonts accompanying this license ("Fonts") and associated
+// documentation files (the "Font Software"), to reproduce and distribute the
+// Font Software, including without limitation the rights to use, copy, merge,
+// publish, distribute, and/or sell copies of the Font Software, and to permit
+// persons to whom the Font Software is furnished to do so, subject to the
+// following conditions:
+//
+// The above copyright and trademark notices and this permission notice shall
+// be included in all copies of one or more of the Font Software typefaces.
+//
+// The Font Software may be modified, altered, or added to, and in particular
+// the designs of glyphs or characters in the Fonts may be modified and
+// additional glyphs or characters may be added to the Fonts, only if the fonts
+// are renamed to names not containing either the words "Bitstream" or the word
+// "Vera".
+//
+// This License becomes null and void to the extent applicable to Fonts or Font
+// Software that has been modified and is distributed under the "Bitstream
+// Vera" names.
+//
+// The Font Software may be sold as part of a larger software package but no
+// copy of one or more of the Font Software typefaces may be sold by itself.
+//
+// THE FONT SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS
+// OR IMPLIED, INCLUDING BUT NOT LIMITED TO ANY WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT OF COPYRIGHT, PATENT,
+// TRADEMARK, OR OTHER RIGHT. IN NO EVENT SHALL BITSTREAM OR THE GNOME
+// FOUNDATION BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, INCLUDING
+// ANY GENERAL, SPECIAL, INDIRECT, INCIDENTAL, OR CONSEQUENTIAL DAMAGES,
+// WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF
+// THE USE OR INABILITY TO USE THE FONT SOFTWARE OR FROM OTHER DEALINGS IN THE
+// FONT SOFTWARE.
+//
+// Except as contained in this notice, the names of Gnome, the Gnome
+// Foundation, and Bitstream Inc., shall not be used in advertising or
+// otherwise to promote the sale, use or other dealings in this Font Software
+// without prior written authorization from the Gnome Foundation or Bitstream
+// Inc., respectively. For further information, contact: fonts at gnome dot
+// org.
+
+use super::font::FaceData;
+
+pub(super) static SANS: FaceData = FaceData {
+    ascent: 928,
+    descent: 236,
+    glyphs: &[
+        (
+            '\0',
+            600,
+            "m50 177l0-882 500 0 0 882-500 0zm56-56l388 0 0-770-388 0 0 770z",
+        ),
+        ('\u{20}', 318, ""),
+        (
+            '!',
+            401,
+            "m151-124l99 0 0 124-99 0 0-124zm0-605l99 0 0 320-10 174-79 0-10-174 0-320z",
+        ),
+        (
+            '"',
+            460,
+            "m179-729l0 271-83 0 0-271 83 0zm185 0l0 271-83 0 0-271 83 0z",
+        ),
+        (
+            '#',
+            838,
+            "m511-440l-142 0-41 163 143 0 40-163zm-73-278l-51 203 143 0 51-203 78 0-50 203 152 0 0 75-171 0-40 163 155 0 0 75-174 0-51 202-78 0 50-202-143 0-50 202-79 0 51-202-154 0 0-75 172 0 41-163-157 0 0-75 176 0 50-203 79 0z",
+        ),
+        (
+            '$',
+            636,
+            "m338 147l-49 0 0-147q-52-1-103-12-51-11-103-33l0-88q50 31 101 47 51 16 105 17l0-223q-108-18-157-60-49-42-49-115 0-79 53-125 53-46 153-53l0-115 49 0 0 114q45 1 88 9 42 8 83 21l0 85q-41-20-83-31-43-12-88-14l0 209q111 17 163 61 52 44 52 120 0 82-55 130-56 48-160 55l0 148zm-49-523l0-200q-57 6-87 32-29 26-29 69 0 42 27 65 28 24 89 34zm49 94l0 211q62-8 94-35 31-27 31-71 0-43-30-69-30-25-95-36z",
+        ),
+        (
+            '%',
+            950,
+            "m727-321q-42 0-67 36-24 36-24 101 0 63 24 100 25 36 67 36 42 0 66-36 24-37 24-100 0-64-24-100-24-37-66-37zm0-62q77 0 123 54 45 54 45 145 0 91-45 144-46 54-123 54-78 0-123-54-46-53-46-144 0-92 46-146 45-53 123-53zm-504-297q-42 0-66 36-24 37-24 100 0 65 24 101 24 36 66 36 43 0 67-36 24-36 24-101 0-63-24-100-25-36-67-36zm441-62l78 0-456 756-78 0 456-756zm-441 0q77 0 123 54 46 53 46 144 0 92-46 146-45 53-123 53-77 0-123-53-45-54-45-146 0-91 45-144 46-54 123-54z",
+        ),
+        (
+            '&',
+            780,
+            "m243-392q-44 39-65 78-21 40-21 83 0 71 52 119 52 47 130 47 46 0 87-15 40-16 76-47l-259-265zm69-55l248 254q29-43 45-93 16-49 19-105l91 0q-6 64-31 127-26 63-71 125l136 139-123 0-70-72q-51 44-106 65-56 21-120 21-118 0-192-67-75-67-75-172 0-63 33-117 32-55 98-104-24-31-36-62-12-30-12-59 0-79 54-127 54-48 144-48 41 0 81 8 40 9 82 27l0 89q-43-23-81-35-39-12-72-12-51 0-83 27-32 27-32 70 0 25 15 50 14 25 58 71z",
+        ),
+        ('\'', 275, "m179-729l0 271-83 0 0-271 83 0z"),
+        (
+            '(',
+            390,
+            "m310-759q-65 113-97 223-32 109-32 222 0 113 32 224 32 110 97 222l-78 0q-73-115-110-226-36-111-36-220 0-109 36-220 36-110 110-225l78 0z",
+        ),
+        (
+            ')',
+            390,
+            "m80-759l78 0q73 115 110 225 36 111 36 220 0 109-36 220-37 111-110 226l-78 0q65-112 97-222 32-111 32-224 0-113-32-222-32-110-97-223z",
+        ),
+        (
+            '*',
+            500,
+            "m470-609l-175 95 175 95-28 48-164-99 0 184-56 0 0-184-164 99-28-48 175-95-175-95 28-48 164 99 0-184 56 0 0 184 164-99 28 48z",
+        ),
+        (
+            '+',
+            838,
+            "m460-627l0 272 272 0 0 83-272 0 0 272-82 0 0-272-272 0 0-83 272 0 0-272 82 0z",
+        ),
+        (',', 318, "m117-124l103 0 0 84-80 156-63 0 40-156 0-84z"),
+        ('-', 361, "m49-314l263 0 0 80-263 0 0-80z"),
+        ('.', 318, "m107-124l103 0 0 124-103 0 0-124z"),
+        ('/', 337, "m254-729l83 0-254 822-83 0 254-822z"),
+        (
+            '0',
+            636,
+            "m318-664q-76 0-114 75-39 75-39 225 0 150 39 225 38 75 114 75 77 0 115-75 38-75 38-225 0-150-38-225-38-75-115-75zm0-78q122 0 187 97 65 97 65 281 0 184-65 281-65 97-187 97-123 0-188-97-64-97-64-281 0-184 64-281 65-97 188-97z",
+        ),
+        (
+            '1',
+            636,
+            "m124-83l161 0 0-556-175 35 0-90 174-35 99 0 0 646 161 0 0 83-420 0 0-83z",
+        ),
+        (
+            '2',
+            636,
+            "m192-83l344 0 0 83-463 0 0-83q56-58 153-156 97-98 122-126 48-53 66-90 19-37 19-73 0-58-41-94-40-37-106-37-46 0-98 16-51 16-110 49l0-100q60-24 112-36 51-12 94-12 113 0 180 56 68 57 68 152 0 45-16 85-17 40-62 95-12 14-78 82-65 67-184 189z",
+        ),
+        (
+            '3',
+            636,
+            "m406-393q71 15 110 63 40 48 40 118 0 108-74 167-74 59-211 59-46 0-95-9-48-9-100-27l0-95q41 24 90 36 49 12 102 12 93 0 142-37 48-36 48-106 0-64-45-100-45-37-126-37l-85 0 0-81 89 0q73 0 111-29 39-29 39-84 0-56-40-86-40-30-114-30-40 0-87 9-46 8-102 27l0-88q56-16 105-23 49-8 93-8 112 0 178 51 65 51 65 138 0 60-35 102-34 42-98 58z",
+        ),
+        (
+            '4',
+            636,
+            "m378-643l-249 389 249 0 0-389zm-26-86l124 0 0 475 104 0 0 82-104 0 0 172-98 0 0-172-329 0 0-95 303-462z",
+        ),
+        (
+            '5',
+            636,
+            "m108-729l387 0 0 83-297 0 0 179q22-8 44-11 21-4 42-4 122 0 194 67 71 67 71 181 0 118-73 183-74 65-207 65-46 0-93-8-48-7-99-23l0-99q44 24 91 36 47 11 99 11 85 0 134-45 49-44 49-120 0-76-49-120-49-45-134-45-39 0-79 9-39 9-80 27l0-366z",
+        ),
+        (
+            '6',
+            636,
+            "m330-404q-66 0-105 46-39 45-39 124 0 79 39 124 39 46 105 46 66 0 105-46 39-45 39-124 0-79-39-124-39-46-105-46zm196-309l0 90q-37-18-75-27-38-9-75-9-98 0-150 66-51 66-58 199 29-43 72-66 44-22 96-22 110 0 174 66 63 67 63 182 0 112-67 180-66 68-176 68-126 0-193-97-67-97-67-281 0-173 82-276 82-102 220-102 37 0 75 7 38 7 79 22z",
+        ),
+        (
+            '7',
+            636,
+            "m82-729l469 0 0 42-265 687-103 0 249-646-350 0 0-83z",
+        ),
+        (
+            '8',
+            636,
+            "m318-346q-70 0-110 38-41 37-41 103 0 66 41 103 40 38 110 38 70 0 110-38 41-38 41-103 0-66-40-103-40-38-111-38zm-99-42q-63-16-99-60-35-43-35-105 0-88 62-139 62-50 171-50 109 0 171 50 62 51 62 139 0 62-35 105-36 44-99 60 71 16 111 64 40 49 40 119 0 106-65 163-65 56-185 56-121 0-186-56-64-57-64-163 0-70 40-119 40-48 111-64zm-36-156q0 57 35 88 36 32 100 32 63 0 99-32 36-31 36-88 0-57-36-88-36-32-99-32-64 0-100 32-35 31-35 88z",
+        ),
+        (
+            '9',
+            636,
+            "m110-15l0-90q37 18 75 27 38 9 75 9 97 0 148-65 52-66 60-200-29 42-72 64-43 23-96 23-110 0-174-66-63-66-63-181 0-112 67-180 66-68 176-68 127 0 194 97 66 97 66 281 0 173-82 276-82 102-220 102-37 0-75-7-38-7-79-22zm196-309q67 0 106-46 38-45 38-124 0-79-38-124-39-46-106-46-66 0-105 46-39 45-39 124 0 79 39 124 39 46 105 46z",
+        ),
+        (
+            ':',
+            337,
+            "m117-124l103 0 0 124-103 0 0-124zm0-393l103 0 0 124-103 0 0-124z",
+        ),
+        (
+            ';',
+            337,
+            "m117-517l103 0 0 124-103 0 0-124zm0 393l103 0 0 84-80 156-63 0 40-156 0-84z",
+        ),
+        (
+            '<',
+            838,
+            "m732-492l-504 179 504 178 0 89-626-227 0-81 626-227 0 89z",
+        ),
+        (
+            '=',
+            838,
+            "m106-454l626 0 0 82-626 0 0-82zm0 199l626 0 0 83-626 0 0-83z",
+        ),
+        (
+            '>',
+            838,
+            "m106-492l0-89 626 227 0 81-626 227 0-89 503-178-503-179z",
+        ),
+        (
+            '?',
+            531,
+            "m191-124l99 0 0 124-99 0 0-124zm96-72l-93 0 0-75q0-49 14-81 13-32 57-74l44-43q28-26 41-49 12-23 12-47 0-43-32-70-32-27-85-27-38 0-82 17-44 17-91 50l0-92q46-28 93-41 47-14 97-14 90 0 144 48 55 47 55 124 0 37-17 70-18 34-62 76l-43 42q-23 23-33 36-9 13-13 25-3 10-5 25-1 15-1 40l0 60z",
+        ),
+        (
+            '@',
+            1000,
+            "m372-262q0 70 34 110 35 39 96 39 60 0 94-40 35-40 35-109 0-69-35-109-35-40-95-40-59 0-94 40-35 40-35 109zm266 146q-29 37-67 55-38 18-88 18-84 0-137-61-52-61-52-158 0-98 52-159 53-61 137-61 50 0 88 18 38 19 67 56l0-64 70 0 0 359q71-11 112-65 40-54 40-141 0-52-16-98-15-46-46-85-51-64-124-98-73-34-159-34-60 0-115 16-55 16-102 47-77 50-120 131-43 81-43 175 0 77 28 145 28 68 81 120 51 51 118 78 68 26 145 26 63 0 123-21 61-21 112-61l44 54q-61 48-133 73-72 25-146 25-90 0-171-32-80-32-142-93-63-61-96-141-32-81-32-173 0-89 33-169 33-81 95-142 63-62 146-95 82-33 174-33 104 0 192 42 89 43 149 121 37 48 56 104 19 56 19 116 0 129-78 203-77 74-214 77l0-75z",
+        ),
+        (
+            'A',
+            684,
+            "m342-632l-134 363 268 0-134-363zm-56-97l112 0 278 729-103 0-66-187-329 0-66 187-104 0 278-729z",
+        ),
+        (
+            'B',
+            686,
+            "m197-348l0 267 158 0q80 0 118-33 38-33 38-101 0-68-38-101-38-32-118-32l-158 0zm0-300l0 220 146 0q72 0 107-27 36-27 36-83 0-55-36-82-35-28-107-28l-146 0zm-99-81l252 0q113 0 174 47 61 47 61 133 0 67-31 107-32 39-92 49 73 16 113 65 40 50 40 124 0 98-67 151-66 53-188 53l-262 0 0-729z",
+        ),
+        (
+            'C',
+            698,
+            "m644-673l0 104q-50-46-106-69-57-23-120-23-125 0-192 77-66 76-66 220 0 144 66 220 67 77 192 77 63 0 120-23 56-23 106-69l0 103q-52 35-110 52-57 18-122 18-165 0-260-101-96-101-96-277 0-176 96-277 95-101 260-101 66 0 124 17 57 17 108 52z",
+        ),
+        (
+            'D',
+            770,
+            "m197-648l0 567 119 0q151 0 221-69 70-68 70-215 0-147-70-215-70-68-221-68l-119 0zm-99-81l203 0q212 0 311 88 99 88 99 276 0 188-99 277-100 88-311 88l-203 0 0-729z",
+        ),
+        (
+            'E',
+            632,
+            "m98-729l461 0 0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-470 0 0-729z",
+        ),
+        (
+            'F',
+            575,
+            "m98-729l419 0 0 83-320 0 0 215 289 0 0 83-289 0 0 348-99 0 0-729z",
+        ),
+        (
+            'G',
+            775,
+            "m595-104l0-196-161 0 0-81 259 0 0 313q-57 41-126 62-69 20-147 20-171 0-268-100-96-100-96-278 0-178 96-278 97-100 268-100 71 0 136 18 64 17 118 51l0 105q-55-46-116-70-62-23-130-23-134 0-201 75-67 74-67 222 0 148 67 222 67 75 201 75 52 0 93-9 41-9 74-28z",
+        ),
+        (
+            'H',
+            752,
+            "m98-729l99 0 0 299 358 0 0-299 99 0 0 729-99 0 0-347-358 0 0 347-99 0 0-729z",
+        ),
+        ('I', 295, "m98-729l99 0 0 729-99 0 0-729z"),
+        (
+            'J',
+            295,
+            "m98-729l99 0 0 678q0 132-50 191-50 60-161 60l-38 0 0-83 31 0q65 0 92-37 27-36 27-131l0-678z",
+        ),
+        (
+            'K',
+            656,
+            "m98-729l99 0 0 308 327-308 127 0-362 340 388 389-130 0-350-351 0 351-99 0 0-729z",
+        ),
+        ('L', 557, "m98-729l99 0 0 646 355 0 0 83-454 0 0-729z"),
+        (
+            'M',
+            863,
+            "m98-729l147 0 186 496 187-496 147 0 0 729-96 0 0-640-188 500-99 0-188-500 0 640-96 0 0-729z",
+        ),
+        (
+            'N',
+            748,
+            "m98-729l133 0 323 610 0-610 96 0 0 729-133 0-323-610 0 610-96 0 0-729z",
+        ),
+        (
+            'O',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm0-80q153 0 245 102 92 103 92 276 0 173-92 276-92 102-245 102-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102z",
+        ),
+        (
+            'P',
+            603,
+            "m197-648l0 274 124 0q69 0 107-36 37-35 37-101 0-66-37-101-38-36-107-36l-124 0zm-99-81l223 0q122 0 185 55 63 56 63 163 0 108-63 163-63 55-185 55l-124 0 0 293-99 0 0-729z",
+        ),
+        (
+            'Q',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm138 649l130 142-119 0-108-117q-16 1-25 2-8 0-16 0-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102 153 0 245 102 92 103 92 276 0 127-51 218-51 90-148 133z",
+        ),
+        (
+            'R',
+            695,
+            "m444-342q32 11 62 46 30 35 60 97l100 199-106 0-93-187q-36-73-70-97-34-24-93-24l-107 0 0 308-99 0 0-729 223 0q125 0 187 52 61 52 61 158 0 69-32 115-32 45-93 62zm-247-306l0 259 124 0q71 0 107-33 37-33 37-97 0-64-37-97-36-32-107-32l-124 0z",
+        ),
+        (
+            'S',
+            635,
+            "m535-705l0 96q-56-27-106-40-50-13-96-13-81 0-125 31-43 31-43 89 0 48 29 73 29 25 110 40l60 12q110 21 162 74 53 53 53 142 0 106-71 161-71 54-208 54-52 0-110-12-59-11-121-34l0-102q60 34 117 51 58 17 114 17 84 0 130-33 46-33 46-95 0-54-33-84-33-30-108-45l-60-12q-110-22-159-69-50-47-50-130 0-97 68-152 68-56 188-56 51 0 104 9 53 9 109 28z",
+        ),
+        (
+            'T',
+            611,
+            "m-3-729l617 0 0 83-259 0 0 646-99 0 0-646-259 0 0-83z",
+        ),
+        (
+            'U',
+            732,
+            "m87-729l99 0 0 443q0 117 42 168 43 52 138 52 95 0 138-52 42-51 42-168l0-443 99 0 0 455q0 143-71 216-70 72-208 72-138 0-208-72-71-73-71-216l0-455z",
+        ),
+        (
+            'V',
+            684,
+            "m286 0l-278-729 103 0 231 614 231-614 103 0-278 729-112 0z",
+        ),
+        (
+            'W',
+            989,
+            "m33-729l100 0 153 616 153-616 111 0 153 616 153-616 100 0-183 729-124 0-154-633-155 633-124 0-183-729z",
+        ),
+        (
+            'X',
+            685,
+            "m63-729l106 0 181 271 182-271 106 0-234 350 250 379-106 0-205-310-207 310-106 0 260-389-227-340z",
+        ),
+        (
+            'Y',
+            611,
+            "m-2-729l106 0 202 300 201-300 106 0-258 382 0 347-99 0 0-347-258-382z",
+        ),
+        (
+            'Z',
+            685,
+            "m56-729l573 0 0 75-461 571 472 0 0 83-595 0 0-75 461-571-450 0 0-83z",
+        ),
+        (
+            '[',
+            390,
+            "m86-760l207 0 0 70-117 0 0 752 117 0 0 70-207 0 0-892z",
+        ),
+        ('\\', 337, "m83-729l254 822-83 0-254-822 83 0z"),
+        (
+            ']',
+            390,
+            "m304-760l0 892-207 0 0-70 117 0 0-752-117 0 0-70 207 0z",
+        ),
+        (
+            '^',
+            838,
+            "m467-729l265 272-98 0-215-193-215 193-98 0 265-272 96 0z",
+        ),
+        ('_', 500, "m510 166l0 70-520 0 0-70 520 0z"),
+        ('`', 500, "m179-800l138 183-75 0-159-183 96 0z"),
+        (
+            'a',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186z",
+        ),
+        (
+            'b',
+            635,
+            "m487-273q0-99-41-155-41-57-112-57-71 0-112 57-41 56-41 155 0 99 41 155 41 57 112 57 71 0 112-57 41-56 41-155zm-306-191q28-49 71-72 44-24 104-24 100 0 162 79 62 79 62 208 0 129-62 208-62 79-162 79-60 0-104-24-43-23-71-72l0 82-90 0 0-760 90 0 0 296z",
+        ),
+        (
+            'c',
+            550,
+            "m488-526l0 84q-38-21-76-32-39-10-78-10-87 0-136 56-48 55-48 155 0 100 48 155 49 56 136 56 39 0 78-10 38-11 76-32l0 83q-38 18-78 27-40 8-86 8-123 0-196-78-73-77-73-209 0-134 73-211 74-76 202-76 42 0 82 8 39 9 76 26z",
+        ),
+        (
+            'd',
+            635,
+            "m454-464l0-296 90 0 0 760-90 0 0-82q-28 49-72 72-43 24-103 24-99 0-161-79-63-79-63-208 0-129 63-208 62-79 161-79 60 0 103 24 44 23 72 72zm-306 191q0 99 40 155 41 57 113 57 71 0 112-57 41-56 41-155 0-99-41-155-41-57-112-57-72 0-113 57-40 56-40 155z",
+        ),
+        (
+            'e',
+            615,
+            "m562-296l0 44-413 0q6 93 56 142 50 48 139 48 52 0 100-12 49-13 97-39l0 85q-48 21-99 32-51 10-103 10-131 0-207-76-77-76-77-206 0-134 73-213 72-79 195-79 111 0 175 71 64 71 64 193zm-90-26q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0z",
+        ),
+        (
+            'f',
+            352,
+            "m371-760l0 75-86 0q-48 0-67 19-19 20-19 71l0 48 148 0 0 70-148 0 0 477-90 0 0-477-86 0 0-70 86 0 0-38q0-91 43-133 42-42 134-42l85 0z",
+        ),
+        (
+            'g',
+            635,
+            "m454-280q0-97-40-151-40-54-113-54-72 0-113 54-40 54-40 151 0 97 40 151 41 54 113 54 73 0 113-54 40-54 40-151zm90 212q0 140-62 208-62 68-190 68-47 0-89-7-42-7-82-22l0-87q40 21 79 32 38 10 78 10 88 0 132-46 44-46 44-140l0-44q-28 48-72 72-43 24-103 24-101 0-163-76-61-77-61-204 0-127 61-204 62-76 163-76 60 0 103 24 44 24 72 72l0-83 90 0 0 479z",
+        ),
+        (
+            'h',
+            634,
+            "m549-330l0 330-90 0 0-327q0-78-31-117-30-38-90-38-73 0-115 46-42 47-42 127l0 309-90 0 0-760 90 0 0 298q32-49 76-74 44-24 101-24 94 0 142 58 49 59 49 172z",
+        ),
+        (
+            'i',
+            278,
+            "m94-547l90 0 0 547-90 0 0-547zm0-213l90 0 0 114-90 0 0-114z",
+        ),
+        (
+            'j',
+            278,
+            "m94-547l90 0 0 557q0 104-40 151-40 47-128 47l-34 0 0-76 24 0q51 0 70-24 18-24 18-98l0-557zm0-213l90 0 0 114-90 0 0-114z",
+        ),
+        (
+            'k',
+            579,
+            "m91-760l90 0 0 449 268-236 115 0-290 256 302 291-117 0-278-267 0 267-90 0 0-760z",
+        ),
+        ('l', 278, "m94-760l90 0 0 760-90 0 0-760z"),
+        (
+            'm',
+            974,
+            "m520-442q34-60 80-89 47-29 111-29 85 0 131 60 47 60 47 170l0 330-90 0 0-327q0-79-28-117-28-38-85-38-70 0-110 46-41 47-41 127l0 309-90 0 0-327q0-79-28-117-28-38-86-38-69 0-109 46-41 47-41 127l0 309-90 0 0-547 90 0 0 85q31-50 74-74 43-24 102-24 60 0 101 30 42 30 62 88z",
+        ),
+        (
+            'n',
+            634,
+            "m549-330l0 330-90 0 0-327q0-78-31-117-30-38-90-38-73 0-115 46-42 47-42 127l0 309-90 0 0-547 90 0 0 85q32-49 76-74 44-24 101-24 94 0 142 58 49 59 49 172z",
+        ),
+        (
+            'o',
+            612,
+            "m306-484q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57zm0-76q117 0 184 76 67 76 67 211 0 134-67 211-67 76-184 76-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76z",
+        ),
+        (
+            'p',
+            635,
+            "m181-82l0 290-90 0 0-755 90 0 0 83q28-49 71-72 44-24 104-24 100 0 162 79 62 79 62 208 0 129-62 208-62 79-162 79-60 0-104-24-43-23-71-72zm306-191q0-99-41-155-41-57-112-57-71 0-112 57-41 56-41 155 0 99 41 155 41 57 112 57 71 0 112-57 41-56 41-155z",
+        ),
+        (
+            'q',
+            635,
+            "m148-273q0 99 40 155 41 57 113 57 71 0 112-57 41-56 41-155 0-99-41-155-41-57-112-57-72 0-113 57-40 56-40 155zm306 191q-28 49-72 72-43 24-103 24-99 0-161-79-63-79-63-208 0-129 63-208 62-79 161-79 60 0 103 24 44 23 72 72l0-83 90 0 0 755-90 0 0-290z",
+        ),
+        (
+            'r',
+            411,
+            "m411-463q-15-9-33-13-18-4-39-4-76 0-117 50-41 49-41 142l0 288-90 0 0-547 90 0 0 85q28-50 73-74 46-24 111-24 10 0 21 1 11 1 25 4l0 92z",
+        ),
+        (
+            's',
+            521,
+            "m443-531l0 85q-38-19-79-29-41-10-85-10-67 0-101 21-33 20-33 61 0 31 24 49 24 18 96 34l31 7q96 21 136 58 40 37 40 104 0 76-60 121-60 44-166 44-44 0-92-8-47-9-100-26l0-93q50 26 98 39 49 13 96 13 64 0 98-21 34-22 34-62 0-37-24-56-25-20-109-38l-31-7q-84-18-121-54-37-36-37-100 0-77 54-119 55-42 156-42 50 0 94 8 44 7 81 21z",
+        ),
+        (
+            't',
+            392,
+            "m183-702l0 155 185 0 0 70-185 0 0 297q0 67 19 86 18 19 74 19l92 0 0 75-92 0q-104 0-144-39-39-39-39-141l0-297-66 0 0-70 66 0 0-155 90 0z",
+        ),
+        (
+            'u',
+            634,
+            "m85-216l0-331 90 0 0 328q0 77 30 116 30 39 91 39 73 0 115-46 42-47 42-127l0-310 90 0 0 547-90 0 0-84q-33 50-76 74-43 24-100 24-94 0-143-58-49-59-49-172zm226-344z",
+        ),
+        (
+            'v',
+            592,
+            "m30-547l95 0 171 459 171-459 95 0-205 547-122 0-205-547z",
+        ),
+        (
+            'w',
+            818,
+            "m42-547l90 0 112 427 112-427 106 0 112 427 112-427 90 0-143 547-106 0-118-448-118 448-106 0-143-547z",
+        ),
+        (
+            'x',
+            592,
+            "m549-547l-198 266 208 281-106 0-159-215-159 215-106 0 212-286-194-261 106 0 145 195 145-195 106 0z",
+        ),
+        (
+            'y',
+            592,
+            "m322 51q-38 97-74 127-37 30-97 30l-72 0 0-75 53 0q37 0 58-17 20-18 45-84l16-41-221-538 95 0 171 428 171-428 95 0-240 598z",
+        ),
+        (
+            'z',
+            525,
+            "m55-547l427 0 0 82-338 393 338 0 0 72-439 0 0-82 338-393-326 0 0-72z",
+        ),
+        (
+            '{',
+            636,
+            "m511 93l0 70-30 0q-122 0-163-36-41-36-41-144l0-117q0-74-27-102-26-28-95-28l-30 0 0-70 30 0q70 0 96-28 26-28 26-101l0-117q0-108 41-144 41-36 163-36l30 0 0 70-33 0q-69 0-90 22-21 21-21 90l0 121q0 77-22 111-22 35-76 47 54 13 76 48 22 35 22 111l0 121q0 69 21 91 21 21 90 21l33 0z",
+        ),
+        ('|', 337, "m210-764l0 1000-83 0 0-1000 83 0z"),
+        (
+            '}',
+            636,
+            "m125 93l34 0q69 0 89-21 21-21 21-91l0-121q0-76 22-111 22-35 76-48-54-12-76-47-22-34-22-111l0-121q0-69-21-90-20-22-89-22l-34 0 0-70 31 0q121 0 162 36 41 36 41 144l0 117q0 73 27 101 26 28 95 28l30 0 0 70-30 0q-69 0-95 28-27 28-27 102l0 117q0 108-41 144-41 36-162 36l-31 0 0-70z",
+        ),
+        (
+            '~',
+            838,
+            "m732-399l0 87q-51 39-95 56-44 16-91 16-54 0-125-29-5-2-8-3-3-1-11-4-75-30-121-30-43 0-85 18-42 19-90 60l0-87q51-39 95-55 44-17 91-17 54 0 125 29 5 2 8 3 4 1 11 4 75 30 121 30 42 0 83-19 42-18 92-59z",
+        ),
+        ('\u{a0}', 318, ""),
+        (
+            '\u{a1}',
+            401,
+            "m250-423l-99 0 0-124 99 0 0 124zm0 605l-99 0 0-320 10-174 79 0 10 174 0 320z",
+        ),
+        (
+            '\u{a2}',
+            636,
+            "m331-64l0-418q-72 9-112 64-40 55-40 145 0 90 40 145 40 56 112 64zm186-462l0 84q-36-20-69-30-33-10-67-12l0 422q34-2 67-12 34-11 69-30l0 83q-32 15-65 23-34 9-71 12l0 139-50 0 0-141q-118-10-183-84-64-75-64-201 0-126 64-201 65-74 183-85l0-140 50 0 0 140q36 2 69 10 34 8 67 23z",
+        ),
+        (
+            '\u{a3}',
+            636,
+            "m538-713l0 89q-37-20-70-30-33-10-63-10-72 0-101 38-28 38-28 141l0 105 191 0 0 70-191 0 0 227 272 0 0 83-485 0 0-83 115 0 0-227-97 0 0-70 97 0 0-116q0-128 52-187 53-59 166-59 30 0 67 8 37 7 75 21z",
+        ),
+        (
+            '\u{a4}',
+            636,
+            "m435-485l101-102 56 56-101 101q18 29 27 58 9 29 9 59 0 31-9 59-10 28-29 56l102 101-56 55-101-101q-29 19-57 29-28 9-58 9-28 0-57-9-30-9-60-27l-101 101-55-56 101-101q-18-30-27-59-9-29-9-57 0-31 9-59 9-29 28-56l-101-101 56-56 101 101q27-19 56-28 28-9 59-9 29 0 57 9 29 9 59 27zm15 171q0-55-38-92-37-38-93-38-55 0-93 38-39 37-39 92 0 56 39 94 38 38 93 38 55 0 93-38 38-39 38-94z",
+        ),
+        (
+            '\u{a5}',
+            636,
+            "m569-222l-202 0 0 222-98 0 0-222-203 0 0-60 203 0 0-25-41-76-162 0 0-60 129 0-155-286 93 0 185 342 184-342 93 0-155 286 129 0 0 60-162 0-41 76 0 25 203 0 0 60z",
+        ),
+        (
+            '\u{a6}',
+            337,
+            "m210-199l0 370-83 0 0-370 83 0zm0-500l0 370-83 0 0-370 83 0z",
+        ),
+        (
+            '\u{a7}',
+            500,
+            "m185-457q-31 22-46 44-15 22-15 45 0 37 34 70 34 32 156 98 31-22 46-44 15-23 15-45 0-37-35-70-35-33-155-98zm220-257l0 80q-41-19-76-28-35-10-62-10-47 0-73 20-27 19-27 54 0 44 101 100 12 7 19 11 103 58 135 95 32 38 32 88 0 44-22 78-23 34-68 60 30 25 44 52 14 27 14 58 0 69-50 110-50 41-134 41-36 0-74-7-37-7-80-21l0-80q42 19 78 29 37 9 65 9 50 0 77-21 28-20 28-57 0-50-108-110l-12-7q-105-59-136-96-31-37-31-87 0-44 23-79 22-35 67-59-29-22-43-49-15-27-15-61 0-64 49-103 49-38 130-38 36 0 73 7 37 7 76 21z",
+        ),
+        (
+            '\u{a8}',
+            500,
+            "m296-758l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{a9}',
+            1000,
+            "m500-725q74 0 138 27 65 27 118 80 53 53 80 117 26 64 26 139 0 74-26 138-27 64-80 117-53 53-118 80-64 27-138 27-74 0-138-27-65-27-118-80-53-53-80-117-26-64-26-138 0-75 26-139 27-64 80-117 53-53 118-80 64-27 138-27zm0 50q-64 0-119 23-55 23-101 69-46 46-70 102-23 56-23 119 0 63 23 118 24 56 70 102 46 46 101 69 55 23 119 23 64 0 120-23 55-23 101-69 46-46 69-101 22-55 22-119 0-65-23-120-23-56-68-101-46-46-101-69-56-23-120-23zm146 120l0 63q-32-16-64-24-32-8-64-8-73 0-114 43-40 43-40 119 0 78 42 120 41 43 117 43 31 0 61-7 30-8 62-25l0 62q-33 14-66 21-34 7-68 7-105 0-166-59-62-60-62-162 0-102 62-161 61-59 166-59 36 0 69 7 33 7 65 20z",
+        ),
+        (
+            '\u{aa}',
+            471,
+            "m68-289l336 0 0 60-336 0 0-60zm335-280l0 219-73 0 0-55q-21 31-56 47-36 17-81 17-63 0-100-33-37-33-37-88 0-66 46-100 47-33 139-33l89 0 0-2q0-45-28-67-29-22-86-22-30 0-64 8-33 9-66 25l0-62q36-14 71-21 35-6 69-6 89 0 133 43 44 43 44 130zm-135 30q-79 0-110 17-30 16-30 54 0 31 24 50 24 19 64 19 51 0 82-35 32-35 32-91l0-14-62 0z",
+        ),
+        (
+            '\u{ab}',
+            612,
+            "m518-517l0 93-147 131 147 131 0 93-229-204 0-40 229-204zm-212 0l0 93-147 131 147 131 0 93-229-204 0-40 229-204z",
+        ),
+        ('\u{ac}', 838, "m106-421l626 0 0 281-82 0 0-198-544 0 0-83z"),
+        ('\u{ad}', 361, "m49-314l263 0 0 80-263 0 0-80z"),
+        (
+            '\u{ae}',
+            1000,
+            "m500-675q-64 0-119 23-55 23-101 69-46 46-70 102-23 56-23 119 0 63 23 118 24 56 70 102 46 46 101 69 55 23 119 23 64 0 120-23 55-23 101-69 46-46 69-101 22-55 22-119 0-65-23-120-23-56-68-101-46-46-101-69-56-23-120-23zm0-50q74 0 138 27 65 27 118 80 53 53 80 117 26 64 26 139 0 74-26 138-27 64-80 117-53 53-118 80-64 27-138 27-74 0-138-27-65-27-118-80-53-53-80-117-26-64-26-138 0-75 26-139 27-64 80-117 53-53 118-80 64-27 138-27zm-13 202l-60 0 0 135 60 0q54 0 75-15 21-15 21-52 0-37-21-53-22-15-75-15zm3-50q86 0 128 29 43 29 43 88 0 42-25 70-26 27-73 35 12 4 28 23 16 18 39 54l67 109-84 0-63-102q-29-47-47-59-18-13-44-13l-32 0 0 174-76 0 0-408 139 0z",
+        ),
+        ('\u{af}', 500, "m104-745l292 0 0 72-292 0 0-72z"),
+        (
+            '\u{b0}',
+            500,
+            "m250-679q-39 0-66 27-27 27-27 66 0 39 27 66 27 26 66 26 39 0 66-26 27-27 27-66 0-39-27-66-27-27-66-27zm0-63q31 0 60 12 29 12 50 34 22 22 34 50 11 28 11 60 0 65-45 110-46 44-111 44-66 0-110-44-44-44-44-110 0-65 45-110 45-46 110-46z",
+        ),
+        (
+            '\u{b1}',
+            838,
+            "m460-627l0 189 272 0 0 83-272 0 0 189-82 0 0-189-272 0 0-83 272 0 0-189 82 0zm-354 544l626 0 0 83-626 0 0-83z",
+        ),
+        (
+            '\u{b2}',
+            401,
+            "m131-382l207 0 0 56-292 0 0-54q16-15 47-42 168-149 168-195 0-32-25-52-26-20-67-20-25 0-55 9-30 8-65 25l0-60q37-14 69-21 33-6 61-6 71 0 113 32 43 32 43 85 0 68-162 207-27 23-42 36z",
+        ),
+        (
+            '\u{b3}',
+            401,
+            "m256-547q45 9 70 35 24 27 24 67 0 61-46 93-47 33-133 33-28 0-59-5-30-5-64-15l0-59q25 13 54 20 29 6 62 6 53 0 82-20 30-19 30-53 0-36-27-55-27-18-80-18l-42 0 0-53 46 0q46 0 69-15 24-16 24-45 0-28-24-43-25-15-71-15-20 0-45 5-24 4-64 15l0-56q36-8 67-13 31-4 58-4 70 0 111 28 42 29 42 78 0 33-22 56-22 24-62 33z",
+        ),
+        ('\u{b4}', 500, "m318-800l97 0-159 184-75 0 137-184z"),
+        (
+            '\u{b5}',
+            636,
+            "m85 208l0-755 90 0 0 340q0 71 33 107 34 36 100 36 72 0 108-41 37-41 37-123l0-319 90 0 0 421q0 29 9 43 8 14 26 14 5 0 12-3 8-2 22-8l0 72q-20 11-38 16-18 6-35 6-34 0-54-19-20-19-27-58-24 39-60 58-35 19-83 19-50 0-85-19-35-19-55-57l0 270-90 0z",
+        ),
+        (
+            '\u{b6}',
+            636,
+            "m309-729l219 0 0 825-69 0 0-765-93 0 0 765-69 0 0-413q-105-8-163-62-57-54-57-144 0-93 63-149 64-57 169-57z",
+        ),
+        ('\u{b7}', 318, "m107-409l103 0 0 124-103 0 0-124z"),
+        (
+            '\u{b8}',
+            500,
+            "m291 0q27 30 40 56 13 26 13 49 0 43-29 65-29 23-87 23-22 0-44-3-21-3-42-9l0-64q17 8 35 12 18 4 41 4 29 0 44-12 14-12 14-34 0-15-10-36-11-21-33-51l58 0z",
+        ),
+        (
+            '\u{b9}',
+            401,
+            "m76-380l100 0 0-298-109 20 0-57 112-19 67 0 0 354 100 0 0 54-270 0 0-54z",
+        ),
+        (
+            '\u{ba}',
+            471,
+            "m68-289l336 0 0 60-336 0 0-60zm168-453q87 0 138 54 50 54 50 147 0 93-50 147-51 53-138 53-88 0-138-53-51-54-51-147 0-93 51-147 50-54 138-54zm0 56q-51 0-82 40-31 39-31 105 0 65 31 104 31 39 82 39 51 0 82-39 30-39 30-104 0-67-30-106-30-39-82-39z",
+        ),
+        (
+            '\u{bb}',
+            612,
+            "m94-517l229 204 0 40-229 204 0-93 147-131-147-131 0-93zm212 0l229 204 0 40-229 204 0-93 147-131-147-131 0-93z",
+        ),
+        (
+            '\u{bc}',
+            969,
+            "m76-380l100 0 0-298-109 20 0-57 112-19 67 0 0 354 100 0 0 54-270 0 0-54zm725 31l-151 205 151 0 0-205zm-11-59l81 0 0 264 66 0 0 53-66 0 0 91-70 0 0-91-202 0 0-59 191-258zm-117-334l78 0-455 756-78 0 455-756z",
+        ),
+        (
+            '\u{bd}',
+            969,
+            "m76-380l100 0 0-298-109 20 0-57 112-19 67 0 0 354 100 0 0 54-270 0 0-54zm623 324l207 0 0 56-292 0 0-54q16-15 47-42 168-149 168-195 0-32-25-52-26-20-67-20-26 0-55 9-30 8-65 25l0-60q37-13 69-20 33-7 61-7 71 0 113 32 43 32 43 85 0 68-162 207-27 23-42 36zm-26-686l78 0-455 756-78 0 455-756z",
+        ),
+        (
+            '\u{be}',
+            969,
+            "m256-547q45 9 70 35 24 27 24 67 0 61-46 93-47 33-133 33-28 0-59-5-30-5-64-15l0-59q25 13 54 20 29 6 62 6 53 0 82-20 30-19 30-53 0-36-27-55-27-18-80-18l-42 0 0-53 46 0q46 0 69-15 24-16 24-45 0-28-24-43-25-15-71-15-20 0-45 5-24 4-64 15l0-56q36-8 67-13 31-4 58-4 70 0 111 28 42 29 42 78 0 33-22 56-22 24-62 33zm545 198l-151 205 151 0 0-205zm-11-59l81 0 0 264 66 0 0 53-66 0 0 91-70 0 0-91-202 0 0-59 191-258zm-117-334l78 0-455 756-78 0 455-756z",
+        ),
+        (
+            '\u{bf}',
+            531,
+            "m244-351l93 0 0 76q0 49-13 81-14 32-58 74l-44 43q-28 26-40 49-13 23-13 47 0 43 32 70 32 27 85 27 38 0 82-17 44-17 91-50l0 92q-46 28-93 41-47 14-97 14-90 0-145-47-54-47-54-125 0-37 18-70 17-34 61-76l43-42q23-23 32-36 10-13 14-25 3-10 4-25 2-15 2-41l0-60zm96-72l-99 0 0-124 99 0 0 124z",
+        ),
+        (
+            '\u{c0}',
+            684,
+            "m342-632l-134 363 268 0-134-363zm-56-97l112 0 278 729-103 0-66-187-329 0-66 187-104 0 278-729zm28-198l96 129-75 0-112-129 91 0z",
+        ),
+        (
+            '\u{c1}',
+            684,
+            "m342-632l-134 363 268 0-134-363zm-56-97l112 0 278 729-103 0-66-187-329 0-66 187-104 0 278-729zm83-198l90 0-111 129-75 0 96-129z",
+        ),
+        (
+            '\u{c2}',
+            684,
+            "m342-632l-134 363 268 0-134-363zm-56-97l112 0 278 729-103 0-66-187-329 0-66 187-104 0 278-729zm10-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{c3}',
+            684,
+            "m342-632l-134 363 268 0-134-363zm-56-97l112 0 278 729-103 0-66-187-329 0-66 187-104 0 278-729zm54-106l-28-16q-12-7-20-9-7-3-13-3-18 0-27 12-10 12-10 34l0 3-61 0q0-49 25-78 25-29 67-29 18 0 33 4 14 4 38 17l28 15q11 6 19 9 8 3 16 3 15 0 25-13 10-12 10-32l0-3 61 0q-1 49-26 78-25 29-66 29-17 0-31-4-15-4-40-17z",
+        ),
+        (
+            '\u{c4}',
+            684,
+            "m342-632l-134 363 268 0-134-363zm-56-97l112 0 278 729-103 0-66-187-329 0-66 187-104 0 278-729zm102-184l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{c5}',
+            684,
+            "m416-794q0-31-22-52-21-22-52-22-31 0-52 21-22 21-22 53 0 31 22 52 21 22 52 22 31 0 52-22 22-21 22-52zm-74 162l-133 363 266 0-133-363zm-74-50q-30-20-45-48-15-28-15-64 0-56 39-95 39-39 95-39 55 0 94 39 40 39 40 95 0 34-16 63-15 29-44 49l260 682-103 0-66-187-329 0-66 187-104 0 260-682z",
+        ),
+        (
+            '\u{c6}',
+            974,
+            "m901-729l0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-470 0 0-187-258 0-78 187-100 0 305-729 592 0zm-524 77l-161 383 224 0 0-383-63 0z",
+        ),
+        (
+            '\u{c7}',
+            698,
+            "m644-673l0 104q-50-46-106-69-57-23-120-23-125 0-192 77-66 76-66 220 0 144 66 220 67 77 192 77 63 0 120-23 56-23 106-69l0 103q-52 35-110 52-57 18-122 18-165 0-260-101-96-101-96-277 0-176 96-277 95-101 260-101 66 0 124 17 57 17 108 52zm-206 673q27 30 40 56 13 26 13 49 0 43-29 65-29 23-87 23-22 0-43-3-22-3-43-9l0-64q17 8 35 12 18 4 41 4 29 0 43-12 15-12 15-34 0-15-11-36-10-21-32-51l58 0z",
+        ),
+        (
+            '\u{c8}',
+            632,
+            "m98-729l461 0 0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-470 0 0-729zm201-198l96 129-75 0-112-129 91 0z",
+        ),
+        (
+            '\u{c9}',
+            632,
+            "m98-729l461 0 0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-470 0 0-729zm256-198l90 0-111 129-75 0 96-129z",
+        ),
+        (
+            '\u{ca}',
+            632,
+            "m98-729l461 0 0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-470 0 0-729zm183-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{cb}',
+            632,
+            "m98-729l461 0 0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-470 0 0-729zm275-184l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{cc}',
+            295,
+            "m98-729l99 0 0 729-99 0 0-729zm22-198l96 129-75 0-112-129 91 0z",
+        ),
+        (
+            '\u{cd}',
+            295,
+            "m98-729l99 0 0 729-99 0 0-729zm77-198l90 0-111 129-75 0 96-129z",
+        ),
+        (
+            '\u{ce}',
+            295,
+            "m98-729l99 0 0 729-99 0 0-729zm4-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{cf}',
+            295,
+            "m98-729l99 0 0 729-99 0 0-729zm96-184l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{d0}',
+            775,
+            "m103-729l203 0q212 0 311 88 99 88 99 276 0 188-100 277-99 88-310 88l-203 0 0-342-98 0 0-70 98 0 0-317zm99 81l0 236 164 0 0 70-164 0 0 261 119 0q151 0 221-69 70-68 70-215 0-147-70-215-70-68-221-68l-119 0z",
+        ),
+        (
+            '\u{d1}',
+            748,
+            "m98-729l133 0 323 610 0-610 96 0 0 729-133 0-323-610 0 610-96 0 0-729zm274-106l-28-16q-12-7-20-9-7-3-13-3-18 0-27 12-10 12-10 34l0 3-61 0q0-49 25-78 25-29 67-29 18 0 33 4 15 4 38 17l28 15q11 6 20 9 8 3 15 3 16 0 25-13 10-12 10-32l0-3 61 0q-1 49-26 78-25 29-66 29-17 0-31-4-15-4-40-17z",
+        ),
+        (
+            '\u{d2}',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm0-80q153 0 245 102 92 103 92 276 0 173-92 276-92 102-245 102-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102zm-28-185l96 129-75 0-112-129 91 0z",
+        ),
+        (
+            '\u{d3}',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm0-80q153 0 245 102 92 103 92 276 0 173-92 276-92 102-245 102-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102zm27-185l90 0-111 129-75 0 96-129z",
+        ),
+        (
+            '\u{d4}',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm0-80q153 0 245 102 92 103 92 276 0 173-92 276-92 102-245 102-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102zm-46-186l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{d5}',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm0-80q153 0 245 102 92 103 92 276 0 173-92 276-92 102-245 102-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102zm-2-93l-28-16q-12-7-20-9-7-3-13-3-18 0-27 12-10 12-10 34l0 3-61 0q0-49 25-78 25-29 67-29 18 0 33 4 15 4 38 17l28 15q11 6 20 9 8 3 15 3 16 0 25-13 10-12 10-32l0-3 61 0q-1 49-26 78-25 29-66 29-17 0-31-4-15-4-40-17z",
+        ),
+        (
+            '\u{d6}',
+            787,
+            "m394-662q-107 0-170 80-64 80-64 218 0 138 64 218 63 80 170 80 107 0 170-80 63-80 63-218 0-138-63-218-63-80-170-80zm0-80q153 0 245 102 92 103 92 276 0 173-92 276-92 102-245 102-154 0-246-102-92-103-92-276 0-173 92-276 92-102 246-102zm46-171l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{d7}',
+            838,
+            "m701-537l-223 224 223 223-58 59-224-224-224 224-58-59 223-223-223-224 58-59 224 224 224-224 58 59z",
+        ),
+        (
+            '\u{d8}',
+            787,
+            "m589-543l-350 413q30 31 69 48 40 16 86 16 107 0 170-80 63-80 63-218 0-54-9-99-10-45-29-80zm-40-55q-30-31-69-48-40-16-86-16-107 0-170 80-64 80-64 218 0 55 10 101 9 46 28 79l351-414zm-416 491q-38-50-57-115-20-64-20-142 0-173 92-276 92-102 246-102 64 0 118 18 53 19 96 56l79-93 50 43-83 97q38 50 58 115 19 65 19 142 0 173-92 276-92 102-245 102-62 0-116-18-54-18-99-55l-79 93-50-43 83-98z",
+        ),
+        (
+            '\u{d9}',
+            732,
+            "m87-729l99 0 0 443q0 117 42 168 43 52 138 52 95 0 138-52 42-51 42-168l0-443 99 0 0 455q0 143-71 216-70 72-208 72-138 0-208-72-71-73-71-216l0-455zm251-198l96 129-75 0-112-129 91 0z",
+        ),
+        (
+            '\u{da}',
+            732,
+            "m87-729l99 0 0 443q0 117 42 168 43 52 138 52 95 0 138-52 42-51 42-168l0-443 99 0 0 455q0 143-71 216-70 72-208 72-138 0-208-72-71-73-71-216l0-455zm306-198l90 0-111 129-75 0 96-129z",
+        ),
+        (
+            '\u{db}',
+            732,
+            "m87-729l99 0 0 443q0 117 42 168 43 52 138 52 95 0 138-52 42-51 42-168l0-443 99 0 0 455q0 143-71 216-70 72-208 72-138 0-208-72-71-73-71-216l0-455zm233-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{dc}',
+            732,
+            "m87-729l99 0 0 443q0 117 42 168 43 52 138 52 95 0 138-52 42-51 42-168l0-443 99 0 0 455q0 143-71 216-70 72-208 72-138 0-208-72-71-73-71-216l0-455zm325-184l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{dd}',
+            611,
+            "m-2-729l106 0 202 300 201-300 106 0-258 382 0 347-99 0 0-347-258-382zm335-198l90 0-111 129-75 0 96-129z",
+        ),
+        (
+            '\u{de}',
+            605,
+            "m98-729l99 0 0 129 124 0q122 0 185 55 63 55 63 162 0 108-63 163-63 55-185 55l-124 0 0 165-99 0 0-729zm99 210l0 273 124 0q69 0 107-36 37-35 37-101 0-65-37-101-38-35-107-35l-124 0z",
+        ),
+        (
+            '\u{df}',
+            630,
+            "m91-555q0-98 59-151 58-54 164-54 102 0 155 57 53 57 55 166-74 4-115 32-41 28-41 75 0 23 14 42 14 20 46 41l28 18q81 52 104 87 24 34 24 83 0 84-55 129-55 44-158 44-31 0-65-6-33-6-68-18l0-80q39 15 73 22 34 7 66 7 57 0 86-23 29-23 29-68 0-31-14-52-15-20-64-50l-45-27q-47-29-68-61-21-33-21-78 0-62 40-104 41-42 115-56-4-50-36-78-32-27-87-27-63 0-96 34-34 34-34 98l0 553-91 0 0-555z",
+        ),
+        (
+            '\u{e0}',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186zm-303-488l138 183-75 0-159-183 96 0z",
+        ),
+        (
+            '\u{e1}',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186zm-164-488l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{e2}',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186zm-268-488l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{e3}',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186zm-234-357l-28-27q-10-10-18-14-9-5-15-5-19 0-27 18-9 18-10 58l-61 0q1-66 26-102 25-36 69-36 19 0 34 7 16 7 34 23l28 27q11 10 19 14 8 5 14 5 19 0 27-18 9-18 10-58l61 0q-1 66-26 102-25 36-69 36-19 0-34-7-16-7-34-23z",
+        ),
+        (
+            '\u{e4}',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186zm-186-446l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{e5}',
+            613,
+            "m343-275q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0zm179-37l0 312-90 0 0-83q-31 50-76 73-46 24-113 24-84 0-133-47-50-47-50-126 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 119 0 178 62 58 61 58 186zm-158-432q0-31-22-52-21-22-52-22-32 0-53 22-21 21-21 52 0 32 21 53 21 21 53 21 31 0 52-22 22-21 22-52zm60 0q0 56-39 95-39 39-95 39-56 0-95-39-39-39-39-95 0-56 39-95 39-39 95-39 56 0 95 39 39 39 39 95z",
+        ),
+        (
+            '\u{e6}',
+            982,
+            "m839-322q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0zm-353-148q36-44 88-67 51-23 116-23 110 0 174 71 65 71 65 193l0 44-413 0q6 93 56 142 50 49 139 49 51 0 99-13 49-13 98-39l0 85q-49 21-100 32-50 10-102 10-81 0-142-30-61-29-98-87-36 59-88 88-53 29-123 29-92 0-143-46-52-45-52-127 0-92 62-139 62-47 184-47l126 0 0-9q0-62-40-96-41-34-115-34-47 0-91 12-45 11-86 33l0-83q49-19 96-28 46-10 90-10 69 0 121 24 52 23 79 66zm-143 195q-109 0-151 25-42 25-42 85 0 48 32 76 31 28 85 28 75 0 120-53 45-53 45-141l0-20-89 0z",
+        ),
+        (
+            '\u{e7}',
+            550,
+            "m488-526l0 84q-38-21-76-32-39-10-78-10-87 0-136 56-48 55-48 155 0 100 48 155 49 56 136 56 39 0 78-10 38-11 76-32l0 83q-38 18-78 27-40 8-86 8-123 0-196-78-73-77-73-209 0-134 73-211 74-76 202-76 42 0 82 8 39 9 76 26zm-127 526q27 30 40 56 13 26 13 49 0 43-30 65-29 23-86 23-23 0-44-3-21-3-42-9l0-64q17 8 35 12 18 4 41 4 28 0 43-12 15-12 15-34 0-15-10-36-11-21-33-51l58 0z",
+        ),
+        (
+            '\u{e8}',
+            615,
+            "m562-296l0 44-413 0q6 93 56 142 50 48 139 48 52 0 100-12 49-13 97-39l0 85q-48 21-99 32-51 10-103 10-131 0-207-76-77-76-77-206 0-134 73-213 72-79 195-79 111 0 175 71 64 71 64 193zm-90-26q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0zm-225-478l138 183-75 0-159-183 96 0z",
+        ),
+        (
+            '\u{e9}',
+            615,
+            "m562-296l0 44-413 0q6 93 56 142 50 48 139 48 52 0 100-12 49-13 97-39l0 85q-48 21-99 32-51 10-103 10-131 0-207-76-77-76-77-206 0-134 73-213 72-79 195-79 111 0 175 71 64 71 64 193zm-90-26q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0zm-86-478l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{ea}',
+            615,
+            "m562-296l0 44-413 0q6 93 56 142 50 48 139 48 52 0 100-12 49-13 97-39l0 85q-48 21-99 32-51 10-103 10-131 0-207-76-77-76-77-206 0-134 73-213 72-79 195-79 111 0 175 71 64 71 64 193zm-90-26q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0zm-190-478l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{eb}',
+            615,
+            "m562-296l0 44-413 0q6 93 56 142 50 48 139 48 52 0 100-12 49-13 97-39l0 85q-48 21-99 32-51 10-103 10-131 0-207-76-77-76-77-206 0-134 73-213 72-79 195-79 111 0 175 71 64 71 64 193zm-90-26q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0zm-108-436l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{ec}',
+            278,
+            "m68-800l138 183-75 0-159-183 96 0zm26 253l90 0 0 547-90 0 0-547zm45-13z",
+        ),
+        (
+            '\u{ed}',
+            278,
+            "m207-800l97 0-159 184-75 0 137-184zm-113 253l90 0 0 547-90 0 0-547zm45-13z",
+        ),
+        (
+            '\u{ee}',
+            278,
+            "m94-547l90 0 0 547-90 0 0-547zm45-13zm-36-240l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{ef}',
+            278,
+            "m94-547l90 0 0 547-90 0 0-547zm45-13zm46-198l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{f0}',
+            612,
+            "m409-447q-24-8-45-12-22-4-42-4-82 0-127 53-45 53-45 148 0 92 42 146 43 54 114 54 71 0 114-54 42-54 42-146 0-60-13-106-13-46-40-79zm31-110q61 70 89 140 28 70 28 155 0 124-69 200-70 76-182 76-113 0-182-76-69-76-69-200 0-122 67-198 68-76 176-76 9 0 22 1 12 1 33 4l-78-88-156 52-16-48 137-45-88-100 111 0 62 71 163-54 16 47-144 48 80 91z",
+        ),
+        (
+            '\u{f1}',
+            634,
+            "m549-330l0 330-90 0 0-327q0-78-31-117-30-38-90-38-73 0-115 46-42 47-42 127l0 309-90 0 0-547 90 0 0 85q32-49 76-74 44-24 101-24 94 0 142 58 49 59 49 172zm-227-339l-28-27q-10-10-18-14-8-5-15-5-18 0-27 18-9 18-10 58l-61 0q1-66 26-102 25-36 69-36 19 0 34 7 16 7 34 23l28 27q11 10 19 14 8 5 14 5 19 0 27-18 9-18 10-58l61 0q-1 66-25 102-25 36-70 36-18 0-34-7-16-7-34-23z",
+        ),
+        (
+            '\u{f2}',
+            612,
+            "m306-484q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57zm0-76q117 0 184 76 67 76 67 211 0 134-67 211-67 76-184 76-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76zm-71-240l138 183-75 0-159-183 96 0z",
+        ),
+        (
+            '\u{f3}',
+            612,
+            "m306-484q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57zm0-76q117 0 184 76 67 76 67 211 0 134-67 211-67 76-184 76-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76zm68-240l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{f4}',
+            612,
+            "m306-484q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57zm0-76q117 0 184 76 67 76 67 211 0 134-67 211-67 76-184 76-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76zm-36-240l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{f5}',
+            612,
+            "m306-484q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57zm0-76q117 0 184 76 67 76 67 211 0 134-67 211-67 76-184 76-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76zm-2-109l-28-27q-10-10-18-14-8-5-15-5-18 0-27 18-9 18-10 58l-61 0q1-66 26-102 25-36 69-36 19 0 34 7 16 7 34 23l28 27q11 10 19 14 8 5 14 5 19 0 27-18 9-18 10-58l61 0q-1 66-25 102-25 36-70 36-18 0-34-7-16-7-34-23z",
+        ),
+        (
+            '\u{f6}',
+            612,
+            "m306-484q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57zm0-76q117 0 184 76 67 76 67 211 0 134-67 211-67 76-184 76-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76zm46-198l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{f7}',
+            838,
+            "m359-554l120 0 0 120-120 0 0-120zm0 361l120 0 0 120-120 0 0-120zm-253-162l626 0 0 83-626 0 0-83z",
+        ),
+        (
+            '\u{f8}',
+            612,
+            "m442-391l-238 289q20 20 45 30 25 10 57 10 72 0 114-56 42-57 42-155 0-39-5-67-5-29-15-51zm-35-53q-20-20-45-30-26-10-56-10-74 0-115 58-41 57-41 160 0 35 4 62 5 27 15 49l238-289zm-299 363q-26-38-40-86-13-48-13-106 0-135 67-211 66-76 184-76 46 0 84 12 39 13 72 38l68-82 46 39-71 87q26 38 39 86 13 48 13 107 0 134-67 211-67 76-184 76-47 0-86-12-39-13-70-38l-68 82-47-38 73-89z",
+        ),
+        (
+            '\u{f9}',
+            634,
+            "m85-216l0-331 90 0 0 328q0 77 30 116 30 39 91 39 73 0 115-46 42-47 42-127l0-310 90 0 0 547-90 0 0-84q-33 50-76 74-43 24-100 24-94 0-143-58-49-59-49-172zm226-344zm-72-240l138 183-75 0-159-183 96 0z",
+        ),
+        (
+            '\u{fa}',
+            634,
+            "m85-216l0-331 90 0 0 328q0 77 30 116 30 39 91 39 73 0 115-46 42-47 42-127l0-310 90 0 0 547-90 0 0-84q-33 50-76 74-43 24-100 24-94 0-143-58-49-59-49-172zm226-344zm67-240l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{fb}',
+            634,
+            "m85-216l0-331 90 0 0 328q0 77 30 116 30 39 91 39 73 0 115-46 42-47 42-127l0-310 90 0 0 547-90 0 0-84q-33 50-76 74-43 24-100 24-94 0-143-58-49-59-49-172zm226-344zm-37-240l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{fc}',
+            634,
+            "m85-216l0-331 90 0 0 328q0 77 30 116 30 39 91 39 73 0 115-46 42-47 42-127l0-310 90 0 0 547-90 0 0-84q-33 50-76 74-43 24-100 24-94 0-143-58-49-59-49-172zm226-344zm45-198l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{fd}',
+            592,
+            "m322 51q-38 97-74 127-37 30-97 30l-72 0 0-75 53 0q37 0 58-17 20-18 45-84l16-41-221-538 95 0 171 428 171-428 95 0-240 598zm42-851l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{fe}',
+            635,
+            "m181-82l0 290-90 0 0-968 90 0 0 296q28-49 71-72 44-24 104-24 100 0 162 79 62 79 62 208 0 129-62 208-62 79-162 79-60 0-104-24-43-23-71-72zm306-191q0-99-41-155-41-57-112-57-71 0-112 57-41 56-41 155 0 99 41 155 41 57 112 57 71 0 112-57 41-56 41-155z",
+        ),
+        (
+            '\u{ff}',
+            592,
+            "m322 51q-38 97-74 127-37 30-97 30l-72 0 0-75 53 0q37 0 58-17 20-18 45-84l16-41-221-538 95 0 171 428 171-428 95 0-240 598zm20-809l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{152}',
+            1070,
+            "m997-729l0 83-362 0 0 216 347 0 0 83-347 0 0 264 371 0 0 83-520 0q-212 0-321-93-109-93-109-272 0-178 109-271 109-93 321-93l511 0zm-461 83l-63 0q-157 0-235 70-78 70-78 211 0 141 78 211 78 71 235 71l63 0 0-563z",
+        ),
+        (
+            '\u{153}',
+            1023,
+            "m880-322q-1-74-41-118-40-44-107-44-75 0-120 42-45 43-52 120l320 0zm90 26l0 44-413 0q6 93 56 142 50 48 139 48 52 0 100-12 49-13 97-39l0 85q-49 21-99 32-51 10-103 10-78 0-137-27-59-27-99-80-34 54-85 81-51 26-120 26-118 0-184-76-67-77-67-211 0-135 67-211 66-76 184-76 69 0 120 27 52 27 84 80 38-52 94-79 57-28 127-28 110 0 175 71 64 71 64 193zm-664-188q-72 0-114 56-42 57-42 155 0 98 42 155 41 56 114 56 72 0 114-56 42-57 42-155 0-97-42-154-42-57-114-57z",
+        ),
+        (
+            '\u{160}',
+            635,
+            "m535-705l0 96q-56-27-106-40-50-13-96-13-81 0-125 31-43 31-43 89 0 48 29 73 29 25 110 40l60 12q110 21 162 74 53 53 53 142 0 106-71 161-71 54-208 54-52 0-110-12-59-11-121-34l0-102q60 34 117 51 58 17 114 17 84 0 130-33 46-33 46-95 0-54-33-84-33-30-108-45l-60-12q-110-22-159-69-50-47-50-130 0-97 68-152 68-56 188-56 51 0 104 9 53 9 109 28zm-263-93l-103-130 68 0 81 87 81-87 68 0-103 130-92 0z",
+        ),
+        (
+            '\u{161}',
+            521,
+            "m443-531l0 85q-38-19-79-29-41-10-85-10-67 0-101 21-33 20-33 61 0 31 24 49 24 18 96 34l31 7q96 21 136 58 40 37 40 104 0 76-60 121-60 44-166 44-44 0-92-8-47-9-100-26l0-93q50 26 98 39 49 13 96 13 64 0 98-21 34-22 34-62 0-37-24-56-25-20-109-38l-31-7q-84-18-121-54-37-36-37-100 0-77 54-119 55-42 156-42 50 0 94 8 44 7 81 21zm-208-85l-120-184 68 0 88 120 88-120 68 0-120 184-72 0zm34 56z",
+        ),
+        (
+            '\u{178}',
+            611,
+            "m-2-729l106 0 202 300 201-300 106 0-258 382 0 347-99 0 0-347-258-382zm354-184l99 0 0 99-99 0 0-99zm-191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{17d}',
+            685,
+            "m56-729l573 0 0 75-461 571 472 0 0 83-595 0 0-75 461-571-450 0 0-83zm241-69l-103-130 68 0 81 87 81-87 68 0-103 130-92 0z",
+        ),
+        (
+            '\u{17e}',
+            525,
+            "m55-547l427 0 0 82-338 393 338 0 0 72-439 0 0-82 338-393-326 0 0-72zm172-69l-120-184 68 0 88 120 88-120 68 0-120 184-72 0z",
+        ),
+        (
+            '\u{192}',
+            352,
+            "m371-760l0 75-86 0q-48 0-67 19-19 20-19 71l0 48 148 0 0 70-148 0 0 510q0 92-43 133-42 42-134 42l-85 0 0-75 86 0q48 0 67-19 19-20 19-71l0-520-86 0 0-70 86 0 0-38q0-91 43-133 42-42 134-42l85 0z",
+        ),
+        (
+            '\u{2c6}',
+            500,
+            "m214-800l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{2dc}',
+            500,
+            "m248-669l-28-27q-11-10-19-14-8-5-14-5-19 0-27 18-9 18-10 58l-61 0q1-66 26-102 25-36 69-36 19 0 34 7 16 7 34 23l28 27q11 10 19 14 8 5 14 5 19 0 27-18 9-18 10-58l61 0q-1 66-26 102-25 36-69 36-19 0-34-7-16-7-34-23z",
+        ),
+        ('\u{2013}', 500, "m49-309l402 0 0 70-402 0 0-70z"),
+        ('\u{2014}', 1000, "m49-309l902 0 0 70-902 0 0-70z"),
+        (
+            '\u{2018}',
+            318,
+            "m188-489l-103 0 0-84 80-156 63 0-40 156 0 84z",
+        ),
+        (
+            '\u{2019}',
+            318,
+            "m127-729l103 0 0 74-80 156-63 0 40-156 0-74z",
+        ),
+        (
+            '\u{201a}',
+            318,
+            "m125-124l103 0 0 84-80 156-63 0 40-156 0-84z",
+        ),
+        (
+            '\u{201c}',
+            518,
+            "m188-489l-103 0 0-84 80-156 63 0-40 156 0 84zm200 0l-103 0 0-84 80-156 63 0-40 156 0 84z",
+        ),
+        (
+            '\u{201d}',
+            518,
+            "m125-729l103 0 0 84-80 156-63 0 40-156 0-84zm200 0l103 0 0 84-80 156-63 0 40-156 0-84z",
+        ),
+        (
+            '\u{201e}',
+            518,
+            "m325-124l103 0 0 84-80 156-63 0 40-156 0-84zm-200 0l103 0 0 84-80 156-63 0 40-156 0-84z",
+        ),
+        (
+            '\u{2020}',
+            500,
+            "m207-729l86 0 0 205 179 0 0 75-179 0 0 545-86 0 0-545-179 0 0-75 179 0 0-205z",
+        ),
+        (
+            '\u{2021}',
+            500,
+            "m472-109l-179 0 0 205-86 0 0-205-179 0 0-75 179 0 0-265-179 0 0-75 179 0 0-205 86 0 0 205 179 0 0 75-179 0 0 265 179 0 0 75z",
+        ),
+        (
+            '\u{2022}',
+            590,
+            "m150-372q0-61 42-102 42-42 104-42 60 0 102 42 42 41 42 102 0 61-42 103-42 42-103 42-61 0-103-42-42-42-42-103z",
+        ),
+        (
+            '\u{2026}',
+            1000,
+            "m448-124l104 0 0 124-104 0 0-124zm333 0l104 0 0 124-104 0 0-124zm-666 0l104 0 0 124-104 0 0-124z",
+        ),
+        (
+            '\u{2030}',
+            1342,
+            "m1119-321q-42 0-67 36-24 36-24 101 0 63 24 100 25 36 67 36 42 0 66-36 24-37 24-100 0-64-24-100-24-37-66-37zm0-62q77 0 123 54 45 54 45 145 0 91-45 144-46 54-123 54-78 0-123-54-46-53-46-144 0-92 46-146 46-53 123-53zm-896-359q77 0 123 54 46 53 46 144 0 92-46 146-45 53-123 53-77 0-123-53-45-54-45-146 0-91 45-144 46-54 123-54zm441 0l78 0-456 756-78 0 456-756zm-441 62q-42 0-66 36-24 37-24 100 0 65 24 101 24 36 66 36 43 0 67-36 24-36 24-101 0-63-24-100-25-36-67-36zm504 297q77 0 123 54 45 54 45 145 0 91-45 144-46 54-123 54-78 0-123-54-46-53-46-144 0-92 46-146 45-53 123-53zm0 62q-42 0-67 36-24 36-24 101 0 63 24 100 25 36 67 36 42 0 66-36 24-37 24-100 0-64-24-100-24-37-66-37z",
+        ),
+        (
+            '\u{2039}',
+            400,
+            "m306-517l0 93-147 131 147 131 0 93-229-204 0-40 229-204z",
+        ),
+        (
+            '\u{203a}',
+            400,
+            "m94-517l229 204 0 40-229 204 0-93 147-131-147-131 0-93z",
+        ),
+        (
+            '\u{20ac}',
+            636,
+            "m570-673l0 104q-45-51-86-73-41-22-91-22-77 0-126 49-49 49-65 140l282 0-27 60-263 0q-1 12-1 23 0 11 0 28 0 16 0 27 0 11 1 23l218 0-27 60-183 0q16 91 65 140 49 50 126 50 50 0 91-22 41-22 86-73l0 103q-44 35-89 52-45 18-92 18-116 0-191-70-75-71-95-198l-103 0 27-60 68 0q0-11-1-22 0-12 0-28 0-17 0-28 1-12 1-23l-95 0 27-60 76 0q20-126 95-197 76-70 191-70 48 0 93 17 45 17 88 52z",
+        ),
+        (
+            '\u{2122}',
+            1000,
+            "m536-729l85 125 80-125 83 0 0 282-55 0 0-236-95 148-27 0-99-148 0 236-56 0 0-282 84 0zm-139 0l0 46-99 0 0 236-56 0 0-236-98 0 0-46 253 0z",
+        ),
+        (
+            '\u{2190}',
+            838,
+            "m49-292l0-43 192-192 58 58-113 114 595 0 0 83-595 0 113 114-58 58-192-192z",
+        ),
+        (
+            '\u{2191}',
+            838,
+            "m397-732l44 0 191 192-58 59-114-115 0 596-83 0 0-596-114 115-58-59 192-192z",
+        ),
+        (
+            '\u{2192}',
+            838,
+            "m789-335l0 43-192 192-58-58 113-114-595 0 0-83 595 0-113-114 58-58 192 192z",
+        ),
+        (
+            '\u{2193}',
+            838,
+            "m441 3l-44 0-192-192 58-59 114 115 0-596 83 0 0 596 114-115 58 59-191 192z",
+        ),
+        ('\u{2212}', 838, "m106-355l626 0 0 83-626 0 0-83z"),
+        ('\u{25b2}', 769, "m3 123l381-766 382 766-763 0z"),
+        ('\u{25bc}', 769, "m3-643l763 0-382 766-381-766z"),
+        (
+            '\u{fffd}',
+            1025,
+            "m513-912l498 498-498 498-498-498 498-498zm-50 788l0 124 99 0 0-124-99 0zm96-72l0-60q0-25 1-40 2-15 5-25 4-12 13-25 11-15 33-36l43-42q45-44 61-75 18-34 18-71 0-77-55-124-54-48-144-48-50 0-97 14-47 13-93 41l0 92q47-33 91-50 44-17 82-17 53 0 85 27 32 27 32 70 0 24-12 47-13 23-41 49l-44 43q-44 42-57 74-14 32-14 81l0 75 93 0z",
+        ),
+    ],
+};
+
+pub(super) static SERIF: FaceData = FaceData {
+    ascent: 928,
+    descent: 236,
+    glyphs: &[
+        (
+            '\0',
+            600,
+            "m50 177l0-882 500 0 0 882-500 0zm56-56l388 0 0-770-388 0 0 770z",
+        ),
+        ('\u{20}', 318, ""),
+        (
+            '!',
+            402,
+            "m136-51q0-27 18-46 19-19 47-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-18-18-19-18-47zm2-678l126 0-33 401 0 123-60 0 0-123-33-401z",
+        ),
+        (
+            '"',
+            460,
+            "m176-729l0 271-78 0 0-271 78 0zm186 0l0 271-78 0 0-271 78 0z",
+        ),
+        (
+            '#',
+            838,
+            "m509-440l-138 0-41 163 139 0 40-163zm-69-278l-50 203 138 0 50-203 84 0-51 203 150 0 0 75-169 0-40 163 153 0 0 75-172 0-50 202-84 0 51-202-138 0-51 202-83 0 50-202-151 0 0-75 170 0 40-163-154 0 0-75 173 0 51-203 83 0z",
+        ),
+        (
+            '$',
+            636,
+            "m337-33q55-5 84-32 29-27 29-73 0-42-28-72-29-29-85-44l0 221zm-49-547q-50 4-78 30-28 25-28 68 0 39 26 65 25 26 80 42l0-205zm-202 545l0-135 53 0q2 67 39 101 37 34 110 36l0-237q-112-31-158-72-45-41-45-108 0-78 53-126 54-47 150-53l0-131 49 0 0 131q49 4 95 16 46 12 89 32l0 128-53 0q-6-57-40-89-33-33-91-38l0 221q120 33 168 75 48 42 48 113 0 81-57 130-56 49-159 56l0 132-49 0 0-132q-47-1-97-13-50-13-105-37z",
+        ),
+        (
+            '%',
+            950,
+            "m224-697q-43 0-67 41-24 40-24 113 0 72 25 113 24 41 66 41 42 0 66-41 24-41 24-113 0-72-24-113-24-41-66-41zm503 358q-42 0-67 41-24 41-24 114 0 72 24 112 25 41 67 41 42 0 66-41 24-40 24-112 0-72-24-114-24-41-66-41zm0-44q77 0 123 54 45 54 45 145 0 91-45 144-46 54-123 54-78 0-123-54-46-53-46-144 0-92 46-146 45-53 123-53zm-61-359l74 0-456 756-74 0 456-756zm-443 0q77 0 123 54 46 53 46 144 0 92-46 146-45 53-123 53-77 0-123-53-45-54-45-146 0-91 45-144 46-54 123-54z",
+        ),
+        (
+            '&',
+            890,
+            "m540-113l-284-299q-43 38-64 82-21 44-21 94 0 81 56 136 56 55 140 55 50 0 94-17 44-18 79-51zm303 113l-196 0-67-71q-51 43-109 64-58 21-126 21-126 0-199-64-74-65-74-175 0-65 37-121 37-57 113-106-25-30-36-60-12-29-12-61 0-78 56-123 56-46 152-46 33 0 76 8 44 8 100 25l0 126-55 0q-7-52-38-79-31-28-85-28-50 0-82 28-31 27-31 71 0 30 17 59 16 30 69 85l252 266q34-39 53-85 19-45 24-96l-88 0 0-52 231 0 0 52-84 0q-8 64-32 119-24 55-65 102l84 89 115 0 0 52z",
+        ),
+        ('\'', 275, "m176-729l0 271-78 0 0-271 78 0z"),
+        (
+            '(',
+            390,
+            "m319 156q-119-53-179-168-61-116-61-290 0-174 61-290 60-115 179-168l0 48q-75 52-108 146-33 94-33 264 0 170 33 264 33 94 108 146l0 48z",
+        ),
+        (
+            ')',
+            390,
+            "m71 156l0-48q75-52 108-146 33-94 33-264 0-170-33-264-33-94-108-146l0-48q119 53 179 168 61 116 61 290 0 174-61 290-60 115-179 168z",
+        ),
+        (
+            '*',
+            500,
+            "m484-604l-191 89 191 90-37 58-170-106 6 186-66 0 6-186-170 106-37-58 191-89-191-90 37-58 170 106-6-186 66 0-6 186 170-106 37 58z",
+        ),
+        (
+            '+',
+            838,
+            "m458-627l0 274 274 0 0 79-274 0 0 274-78 0 0-274-274 0 0-79 274 0 0-274 78 0z",
+        ),
+        (
+            ',',
+            318,
+            "m36 97q46-34 68-79 21-46 21-111l0-18 96 0q-4 84-39 143-36 60-107 104l-39-39z",
+        ),
+        ('-', 338, "m44-306l250 0 0 76-250 0 0-76z"),
+        (
+            '.',
+            318,
+            "m94-51q0-27 18-46 19-19 47-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-18-18-19-18-47z",
+        ),
+        ('/', 337, "m259-729l78 0-259 822-78 0 259-822z"),
+        (
+            '0',
+            636,
+            "m318-34q75 0 112-82 37-82 37-248 0-166-37-248-37-82-112-82-75 0-112 82-37 82-37 248 0 166 37 248 37 82 112 82zm0 48q-119 0-186-100-66-99-66-278 0-179 66-278 67-100 186-100 119 0 186 100 66 99 66 278 0 179-66 278-67 100-186 100z",
+        ),
+        (
+            '1',
+            636,
+            "m142 0l0-52 127 0 0-606-147 95 0-64 178-115 67 0 0 690 127 0 0 52-352 0z",
+        ),
+        (
+            '2',
+            636,
+            "m128-555l-55 0 0-130q52-28 105-43 53-14 104-14 114 0 180 55 66 55 66 150 0 107-150 256-11 11-17 17l-184 184 304 0 0-90 57 0 0 170-470 0 0-53 221-221q73-73 105-134 31-62 31-129 0-74-39-115-38-42-105-42-70 0-108 34-38 35-45 105z",
+        ),
+        (
+            '3',
+            636,
+            "m97-698q57-22 109-33 53-11 99-11 107 0 167 46 60 46 60 128 0 66-42 110-41 44-117 60 90 13 139 66 50 52 50 135 0 102-68 157-69 54-198 54-57 0-112-12-54-12-108-37l0-142 55 0q5 71 47 107 43 36 120 36 75 0 118-44 43-43 43-118 0-86-45-130-44-44-130-44l-46 0 0-50 24 0q86 0 128-36 43-35 43-106 0-64-35-98-35-34-101-34-66 0-103 32-36 31-42 92l-55 0 0-128z",
+        ),
+        (
+            '4',
+            636,
+            "m349-247l0-388-249 388 249 0zm215 247l-332 0 0-52 117 0 0-143-318 0 0-53 319-494 97 0 0 495 139 0 0 52-139 0 0 143 117 0 0 52z",
+        ),
+        (
+            '5',
+            636,
+            "m503-729l0 80-334 0 0 209q25-18 59-26 34-9 76-9 118 0 186 65 69 66 69 179 0 116-69 181-69 64-194 64-50 0-103-12-53-12-108-37l0-142 55 0q5 70 44 107 40 36 112 36 77 0 118-51 42-51 42-146 0-95-41-145-41-51-119-51-44 0-78 15-33 16-59 49l-42 0 0-366 386 0z",
+        ),
+        (
+            '6',
+            636,
+            "m327-34q69 0 106-50 37-51 37-146 0-95-37-146-37-50-106-50-70 0-107 49-36 49-36 141 0 97 37 150 37 52 106 52zm-159-367q33-37 75-55 42-18 95-18 109 0 172 66 63 65 63 178 0 111-68 178-68 66-182 66-123 0-189-92-67-92-67-263 0-191 79-296 78-105 221-105 39 0 81 7 43 7 87 22l0 120-55 0q-6-49-38-75-31-26-85-26-95 0-141 72-47 72-48 221z",
+        ),
+        (
+            '7',
+            636,
+            "m564-679l-285 679-73 0 272-649-337 0 0 90-57 0 0-170 480 0 0 50z",
+        ),
+        (
+            '8',
+            636,
+            "m466-199q0-78-39-121-39-44-109-44-70 0-109 44-39 43-39 121 0 78 39 121 39 44 109 44 70 0 109-44 39-43 39-121zm-20-354q0-67-34-104-34-37-94-37-60 0-94 37-34 37-34 104 0 67 34 104 34 37 94 37 60 0 94-37 34-37 34-104zm-53 165q83 11 129 61 47 50 47 128 0 102-65 157-65 56-186 56-121 0-186-56-65-55-65-157 0-78 47-128 46-50 129-61-74-13-113-56-39-43-39-109 0-88 61-139 60-50 166-50 106 0 166 50 61 51 61 139 0 66-39 109-40 43-113 56z",
+        ),
+        (
+            '9',
+            636,
+            "m468-327q-33 37-76 55-42 18-95 18-109 0-171-66-63-65-63-178 0-111 68-178 68-66 182-66 124 0 190 92 66 92 66 263 0 191-79 296-78 105-221 105-39 0-81-7-42-7-87-22l0-121 55 0q6 49 38 76 32 26 85 26 95 0 141-72 46-71 48-221zm-159-367q-69 0-106 50-37 51-37 146 0 95 37 146 37 50 106 50 69 0 106-49 37-49 37-141 0-97-37-150-37-52-106-52z",
+        ),
+        (
+            ':',
+            337,
+            "m104-51q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 28-18 47-19 18-47 18-28 0-47-18-18-19-18-47zm0-318q0-27 19-46 19-19 46-19 28 0 47 18 18 19 18 47 0 28-18 47-19 18-47 18-27 0-46-19-19-19-19-46z",
+        ),
+        (
+            ';',
+            337,
+            "m36 97q46-34 68-79 21-46 21-111l0-18 96 0q-4 84-39 143-36 60-107 104l-39-39zm70-466q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-27 0-46-19-19-19-19-46z",
+        ),
+        (
+            '<',
+            838,
+            "m732-498l-507 185 507 184 0 83-626-227 0-81 626-227 0 83z",
+        ),
+        (
+            '=',
+            838,
+            "m106-452l626 0 0 78-626 0 0-78zm0 199l626 0 0 78-626 0 0-78z",
+        ),
+        (
+            '>',
+            838,
+            "m106-498l0-83 626 227 0 81-626 227 0-83 507-184-507-185z",
+        ),
+        (
+            '?',
+            536,
+            "m176-51q0-27 18-46 19-19 47-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-18-18-19-18-47zm-108-646q52-23 98-34 47-11 88-11 108 0 171 52 63 52 63 141 0 91-54 149-54 57-163 82l0 124-60 0 0-160q86-21 130-70 44-50 44-126 0-67-36-106-36-38-97-38-56 0-92 32-35 32-45 92l-47 0 0-127z",
+        ),
+        (
+            '@',
+            1000,
+            "m632-121q-29 40-65 59-36 20-81 20-86 0-139-61-53-61-53-159 0-98 53-159 53-61 139-61 45 0 81 20 36 19 65 59l0-69 76 0 0 377q83-18 129-79 46-61 46-153 0-54-17-103-16-48-49-89-52-65-127-99-74-35-161-35-44 0-87 9-43 10-85 29-99 45-153 137-55 92-55 213 0 81 25 150 24 69 70 119 53 58 126 88 73 31 158 31 63 0 118-19 54-18 101-55l26 34q-53 45-120 68-67 23-146 23-90 0-171-32-81-33-142-93-63-62-96-142-32-80-32-172 0-90 32-169 33-80 96-142 62-62 146-95 84-33 179-33 105 0 191 40 86 41 144 117 37 48 56 104 19 56 19 117 0 131-79 207-80 76-218 77l0-79zm0-116l0-51q0-61-35-99-35-38-91-38-59 0-92 43-33 43-33 120 0 77 33 120 33 43 93 43 55 0 90-39 35-38 35-99z",
+        ),
+        (
+            'A',
+            722,
+            "m200-264l268 0-134-347-134 347zm-206 264l0-52 64 0 260-677 82 0 260 677 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0z",
+        ),
+        (
+            'B',
+            735,
+            "m247-52l146 0q88 0 129-38 40-38 40-121 0-82-40-120-41-38-129-38l-146 0 0 317zm0-369l124 0q80 0 117-31 37-30 37-97 0-67-37-97-37-31-117-31l-124 0 0 256zm-192 421l0-52 93 0 0-625-93 0 0-52 360 0q110 0 166 45 56 45 56 135 0 65-39 103-38 39-113 48 93 12 141 60 48 47 48 127 0 108-68 159-69 52-214 52l-337 0z",
+        ),
+        (
+            'C',
+            765,
+            "m705-193q-32 102-108 155-76 52-192 52-72 0-133-24-61-25-108-72-55-55-82-124-26-70-26-158 0-170 98-274 98-104 259-104 60 0 127 16 67 15 145 47l0 168-55 0q-18-92-72-135-55-44-153-44-117 0-177 83-60 83-60 243 0 160 60 243 60 83 177 83 81 0 134-39 53-39 76-116l90 0z",
+        ),
+        (
+            'D',
+            802,
+            "m247-52l91 0q142 0 218-81 76-81 76-232 0-152-76-232-76-80-218-80l-91 0 0 625zm-192 52l0-52 93 0 0-625-93 0 0-52 290 0q189 0 294 96 105 96 105 268 0 172-106 269-105 96-293 96l-290 0z",
+        ),
+        (
+            'E',
+            730,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 587 0 0 162-60 0 0-102-335 0 0 244 239 0 0-91 60 0 0 242-60 0 0-91-239 0 0 305 343 0 0-102 60 0 0 162-595 0z",
+        ),
+        (
+            'F',
+            694,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 597 0 0 162-60 0 0-102-345 0 0 244 249 0 0-91 60 0 0 242-60 0 0-91-249 0 0 313 117 0 0 52-309 0z",
+        ),
+        (
+            'G',
+            799,
+            "m640-511q-17-92-71-135-54-44-151-44-126 0-188 81-62 81-62 245 0 161 64 244 64 82 188 82 55 0 106-14 50-13 95-41l0-188-137 0 0-52 236 0 0 272q-65 38-140 57-75 18-160 18-165 0-264-103-100-103-100-275 0-173 100-276 99-102 268-102 62 0 129 14 67 15 142 44l0 173-55 0z",
+        ),
+        (
+            'H',
+            872,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 252 378 0 0-252-93 0 0-52 285 0 0 52-93 0 0 625 93 0 0 52-285 0 0-52 93 0 0-313-378 0 0 313 93 0 0 52-285 0z",
+        ),
+        (
+            'I',
+            395,
+            "m247-52l93 0 0 52-285 0 0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 625z",
+        ),
+        (
+            'J',
+            401,
+            "m-84 175l0-115 56 0q1 48 22 72 22 24 65 24 58 0 81-36 23-37 23-144l0-653-113 0 0-52 305 0 0 52-93 0 0 657q0 121-48 174-48 54-156 54-35 0-70-8-36-9-72-25z",
+        ),
+        (
+            'K',
+            747,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 276 314-276-79 0 0-52 242 0 0 52-82 0-313 275 350 350 83 0 0 52-169 0-346-346 0 294 93 0 0 52-285 0z",
+        ),
+        (
+            'L',
+            664,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 617 333 0 0-122 60 0 0 182-585 0z",
+        ),
+        (
+            'M',
+            1024,
+            "m55 0l0-52 93 0 0-625-98 0 0-52 212 0 256 519 256-519 199 0 0 52-97 0 0 625 93 0 0 52-285 0 0-52 93 0 0-563-250 508-69 0-250-508 0 563 93 0 0 52-246 0z",
+        ),
+        (
+            'N',
+            875,
+            "m49 0l0-52 98 0 0-625-98 0 0-52 187 0 437 575 0-523-98 0 0-52 256 0 0 52-98 0 0 691-59 0-467-614 0 548 98 0 0 52-256 0z",
+        ),
+        (
+            'O',
+            820,
+            "m410-38q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82zm0 52q-74 0-136-24-63-25-110-72-55-55-82-124-26-69-26-158 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 88-27 158-27 70-81 124-48 48-110 72-61 24-136 24z",
+        ),
+        (
+            'P',
+            673,
+            "m247-371l129 0q73 0 111-39 38-40 38-114 0-75-38-114-38-39-111-39l-129 0 0 306zm-192 371l0-52 93 0 0-625-93 0 0-52 345 0q109 0 173 55 64 56 64 150 0 94-64 150-64 55-173 55l-153 0 0 267 113 0 0 52-305 0z",
+        ),
+        (
+            'Q',
+            820,
+            "m422 14q-168 0-267-102-99-103-99-276 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 145-73 244-73 98-202 126 26 33 64 48 38 16 91 16l15 0 0 90q-82-4-141-40-59-37-96-106zm-12-52q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82z",
+        ),
+        (
+            'R',
+            753,
+            "m479-362q34 9 59 32 24 22 44 62l106 216 89 0 0 52-172 0-114-232q-33-67-60-87-27-20-75-20l-109 0 0 287 103 0 0 52-295 0 0-52 93 0 0-625-93 0 0-52 370 0q106 0 164 51 58 51 58 144 0 75-43 118-42 43-125 54zm-232-29l144 0q74 0 109-35 35-35 35-108 0-73-35-108-35-35-109-35l-144 0 0 286z",
+        ),
+        (
+            'S',
+            685,
+            "m93-35l0-166 56 0q2 83 48 123 46 40 139 40 87 0 132-34 46-35 46-101 0-53-28-81-27-28-116-55l-96-29q-105-32-148-79-42-47-42-130 0-93 66-144 66-51 185-51 51 0 111 11 61 11 129 32l0 155-55 0q-8-77-52-112-43-34-131-34-77 0-117 32-41 31-41 91 0 52 31 82 30 30 128 59l90 27q99 30 142 76 42 46 42 124 0 106-68 159-68 54-202 54-60 0-122-12-63-12-127-37z",
+        ),
+        (
+            'T',
+            667,
+            "m191 0l0-52 93 0 0-619-214 0 0 114-60 0 0-172 647 0 0 172-60 0 0-114-214 0 0 619 93 0 0 52-285 0z",
+        ),
+        (
+            'U',
+            843,
+            "m140-677l-93 0 0-52 285 0 0 52-93 0 0 377q0 140 45 195 46 55 158 55 112 0 158-55 45-55 45-195l0-377-93 0 0-52 246 0 0 52-93 0 0 387q0 166-64 235-64 69-217 69-153 0-218-70-66-69-66-234l0-387z",
+        ),
+        (
+            'V',
+            722,
+            "m175-677l215 560 215-560-82 0 0-52 214 0 0 52-71 0-260 677-84 0-259-677-73 0 0-52 266 0 0 52-81 0z",
+        ),
+        (
+            'W',
+            1028,
+            "m762 0l-80 0-167-593-167 593-80 0-191-677-72 0 0-52 266 0 0 52-91 0 152 539 166-591 79 0 169 597 153-545-84 0 0-52 210 0 0 52-72 0-191 677z",
+        ),
+        (
+            'X',
+            712,
+            "m331-312l-178 260 94 0 0 52-241 0 0-52 84 0 210-306-213-319-78 0 0-52 289 0 0 52-86 0 156 235 161-235-94 0 0-52 239 0 0 52-82 0-193 281 230 344 78 0 0 52-289 0 0-52 87 0-174-260z",
+        ),
+        (
+            'Y',
+            660,
+            "m189 0l0-52 93 0 0-262-225-363-68 0 0-52 271 0 0 52-85 0 182 295 182-295-82 0 0-52 212 0 0 52-69 0-219 354 0 271 93 0 0 52-285 0z",
+        ),
+        (
+            'Z',
+            695,
+            "m45 0l0-35 454-634-377 0 0 109-60 0 0-169 576 0 0 35-454 634 412 0 0-102 60 0 0 162-611 0z",
+        ),
+        (
+            '[',
+            390,
+            "m86-760l227 0 0 52-134 0 0 788 134 0 0 52-227 0 0-892z",
+        ),
+        ('\\', 337, "m78-729l259 822-78 0-259-822 78 0z"),
+        (
+            ']',
+            390,
+            "m304-760l0 892-227 0 0-52 134 0 0-788-134 0 0-52 227 0z",
+        ),
+        (
+            '^',
+            838,
+            "m467-729l265 272-75 0-238-186-238 186-75 0 265-272 96 0z",
+        ),
+        ('_', 500, "m500 197l0 39-500 0 0-39 500 0z"),
+        ('`', 500, "m179-799l127 184-57 0-166-184 96 0z"),
+        (
+            'a',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156z",
+        ),
+        (
+            'b',
+            640,
+            "m115-52l0-656-86 0 0-52 176 0 0 322q26-49 67-72 41-23 101-23 96 0 157 75 60 76 60 198 0 122-60 198-61 76-157 76-60 0-101-23-41-23-67-72l0 81-176 0 0-52 86 0zm90-182q0 94 36 143 36 49 104 49 69 0 105-55 35-55 35-163 0-108-35-162-36-55-105-55-68 0-104 49-36 50-36 143l0 51z",
+        ),
+        (
+            'c',
+            560,
+            "m514-156q-19 83-73 126-54 44-140 44-113 0-182-75-69-75-69-199 0-124 69-198 69-75 182-75 49 0 98 11 49 12 98 35l0 133-52 0q-10-68-45-100-34-31-98-31-73 0-110 57-37 56-37 168 0 113 37 170 37 56 110 56 58 0 92-30 35-31 48-92l72 0z",
+        ),
+        (
+            'd',
+            640,
+            "m525-52l86 0 0 52-176 0 0-81q-26 49-67 72-41 23-101 23-96 0-157-76-60-76-60-198 0-122 60-198 61-75 157-75 60 0 101 23 41 23 67 72l0-270-85 0 0-52 175 0 0 708zm-90-182l0-51q0-93-36-143-36-49-104-49-69 0-105 55-35 54-35 162 0 108 35 163 36 55 105 55 68 0 104-49 36-49 36-143z",
+        ),
+        (
+            'e',
+            592,
+            "m542-250l-387 0 0 4q0 105 39 158 40 54 117 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-114 0-183-75-69-75-69-199 0-123 68-198 68-75 178-75 118 0 181 73 63 72 65 210zm-106-52q-3-91-38-137-36-46-102-46-62 0-98 47-35 46-43 136l281 0z",
+        ),
+        (
+            'f',
+            370,
+            "m430-637l-47 0q-1-37-21-56-20-19-59-19-50 0-71 28-20 27-20 98l0 67 145 0 0 52-145 0 0 415 115 0 0 52-291 0 0-52 86 0 0-415-86 0 0-52 86 0 0-65q0-87 45-132 45-44 133-44 33 0 66 6 32 6 64 18l0 99z",
+        ),
+        (
+            'g',
+            640,
+            "m525-467l0 456q0 112-61 173-62 60-176 60-51 0-98-9-47-9-90-28l0-109 47 0q9 51 41 74 33 24 94 24 79 0 116-45 37-45 37-140l0-70q-26 49-67 72-41 23-101 23-96 0-157-76-60-76-60-198 0-122 60-198 61-75 157-75 60 0 101 23 41 23 67 72l0-81 176 0 0 52-86 0zm-90 182q0-93-36-143-36-49-104-49-69 0-105 55-35 54-35 162 0 108 35 163 36 55 105 55 68 0 104-49 36-49 36-143l0-51z",
+        ),
+        (
+            'h',
+            644,
+            "m41 0l0-52 81 0 0-656-86 0 0-52 176 0 0 333q25-52 64-79 40-27 93-27 86 0 127 49 40 49 40 154l0 278 80 0 0 52-248 0 0-52 78 0 0-250q0-95-24-130-23-35-82-35-63 0-96 45-32 46-32 133l0 237 78 0 0 52-249 0z",
+        ),
+        (
+            'i',
+            320,
+            "m97-680q0-23 17-40 16-16 39-16 22 0 39 16 16 17 16 40 0 23-16 39-16 16-39 16-23 0-39-16-17-16-17-39zm115 628l85 0 0 52-261 0 0-52 86 0 0-415-86 0 0-52 176 0 0 467z",
+        ),
+        (
+            'j',
+            310,
+            "m100-680q0-23 16-40 17-16 40-16 22 0 38 16 17 17 17 40 0 23-16 39-16 16-39 16-23 0-40-16-16-16-16-39zm22 213l-85 0 0-52 175 0 0 570q0 80-48 125-47 46-131 46-35 0-67-8-32-8-62-24l0-107 46 0q4 47 24 69 21 22 61 22 45 0 66-30 21-30 21-93l0-518z",
+        ),
+        (
+            'k',
+            606,
+            "m286 0l-252 0 0-52 81 0 0-656-86 0 0-52 176 0 0 495 219-202-75 0 0-52 235 0 0 52-89 0-154 143 197 272 75 0 0 52-256 0 0-52 74 0-155-213-71 66 0 147 81 0 0 52z",
+        ),
+        (
+            'l',
+            320,
+            "m205-52l85 0 0 52-261 0 0-52 86 0 0-656-86 0 0-52 176 0 0 708z",
+        ),
+        (
+            'm',
+            948,
+            "m518-418q26-57 66-86 41-29 94-29 82 0 122 51 40 50 40 152l0 278 81 0 0 52-249 0 0-52 78 0 0-268q0-79-24-113-23-34-77-34-60 0-91 45-32 46-32 133l0 237 78 0 0 52-246 0 0-52 78 0 0-271q0-78-24-111-23-33-77-33-60 0-91 45-32 46-32 133l0 237 78 0 0 52-249 0 0-52 81 0 0-416-86 0 0-51 176 0 0 92q25-52 64-79 38-27 87-27 60 0 101 30 40 30 54 85z",
+        ),
+        (
+            'n',
+            644,
+            "m41 0l0-52 81 0 0-415-86 0 0-52 176 0 0 92q25-52 64-79 40-27 93-27 86 0 127 49 40 49 40 154l0 278 80 0 0 52-248 0 0-52 78 0 0-250q0-95-24-130-23-36-82-36-63 0-96 46-32 46-32 133l0 237 78 0 0 52-249 0z",
+        ),
+        (
+            'o',
+            602,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm0 48q-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 114 0 183 75 68 74 68 198 0 124-68 199-69 75-183 75z",
+        ),
+        (
+            'p',
+            640,
+            "m205-285l0 51q0 94 36 143 36 49 104 49 69 0 105-55 35-55 35-163 0-108-35-162-36-55-105-55-68 0-104 49-36 50-36 143zm-90-182l-86 0 0-52 176 0 0 81q26-49 67-72 41-23 101-23 96 0 157 75 60 76 60 198 0 122-60 198-61 76-157 76-60 0-101-23-41-23-67-72l0 237 85 0 0 52-261 0 0-52 86 0 0-623z",
+        ),
+        (
+            'q',
+            640,
+            "m525-467l0 623 86 0 0 52-261 0 0-52 85 0 0-237q-26 49-67 72-41 23-101 23-96 0-157-76-60-76-60-198 0-122 60-198 61-75 157-75 60 0 101 23 41 23 67 72l0-81 176 0 0 52-86 0zm-90 182q0-93-36-143-36-49-104-49-69 0-105 55-35 54-35 162 0 108 35 163 36 55 105 55 68 0 104-49 36-49 36-143l0-51z",
+        ),
+        (
+            'r',
+            478,
+            "m478-520l0 130-52 0q-2-39-21-58-19-19-56-19-66 0-101 46-36 46-36 132l0 237 104 0 0 52-275 0 0-52 81 0 0-416-86 0 0-51 176 0 0 92q26-54 68-80 41-26 101-26 22 0 46 3 24 4 51 10z",
+        ),
+        (
+            's',
+            513,
+            "m56-29l0-121 52 0q2 58 36 87 35 29 102 29 61 0 92-23 32-23 32-66 0-34-23-55-23-21-98-45l-65-22q-67-22-97-54-30-32-30-82 0-71 53-111 52-41 144-41 41 0 86 11 46 10 94 31l0 113-52 0q-2-50-35-78-33-29-90-29-57 0-86 20-29 20-29 60 0 33 22 53 22 19 88 40l71 22q74 23 107 58 32 34 32 88 0 74-56 116-57 42-156 42-50 0-98-10-48-11-96-33z",
+        ),
+        (
+            't',
+            402,
+            "m108-467l-79 0 0-52 79 0 0-161 90 0 0 161 169 0 0 52-169 0 0 330q0 66 13 85 13 18 47 18 35 0 51-21 16-21 17-67l68 0q-4 71-38 104-35 32-106 32-78 0-110-34-32-35-32-117l0-330z",
+        ),
+        (
+            'u',
+            644,
+            "m354-519l168 0 0 467 85 0 0 52-175 0 0-92q-25 52-64 79-40 27-92 27-87 0-128-49-40-49-40-154l0-278-81 0 0-52 171 0 0 302q0 95 24 130 23 35 82 35 63 0 96-46 32-46 32-133l0-236-78 0 0-52z",
+        ),
+        (
+            'v',
+            565,
+            "m247 0l-191-467-59 0 0-52 239 0 0 52-83 0 146 357 146-357-78 0 0-52 195 0 0 52-58 0-191 467-66 0z",
+        ),
+        (
+            'w',
+            856,
+            "m480-519l133 405 117-353-75 0 0-52 188 0 0 52-58 0-154 467-75 0-128-388-128 388-72 0-154-467-58 0 0-52 235 0 0 52-84 0 116 353 134-405 63 0z",
+        ),
+        (
+            'x',
+            564,
+            "m291-317l109-150-70 0 0-52 200 0 0 52-69 0-139 192 162 223 68 0 0 52-240 0 0-52 66 0-113-155-113 155 67 0 0 52-197 0 0-52 69 0 143-197-158-218-64 0 0-52 232 0 0 52-62 0 109 150z",
+        ),
+        (
+            'y',
+            565,
+            "m216 95l34-86-194-476-59 0 0-52 239 0 0 52-83 0 146 357 146-357-78 0 0-52 195 0 0 52-58 0-238 584q-24 61-54 83-30 22-84 22-23 0-47-4-24-4-49-11l0-99 46 0q3 33 17 48 14 14 43 14 27 0 44-15 16-15 34-60z",
+        ),
+        (
+            'z',
+            527,
+            "m40 0l0-42 325-425-257 0 0 90-52 0 0-142 425 0 0 42-325 425 282 0 0-94 52 0 0 146-450 0z",
+        ),
+        (
+            '{',
+            636,
+            "m511 112l0 51-30 0q-122 0-163-36-41-36-41-144l0-126q0-73-27-101-26-29-95-29l-30 0 0-51 30 0q69 0 95-28 27-29 27-102l0-126q0-108 41-144 41-36 163-36l30 0 0 51-33 0q-69 0-90 21-21 22-21 91l0 140q0 77-25 111-24 33-87 47 64 17 88 49 24 33 24 110l0 140q0 69 21 90 21 22 90 22l33 0z",
+        ),
+        ('|', 337, "m208-764l0 1000-79 0 0-1000 79 0z"),
+        (
+            '}',
+            636,
+            "m125 112l34 0q69 0 89-22 21-21 21-90l0-140q0-77 24-110 24-32 88-49-64-14-88-47-24-34-24-111l0-140q0-70-21-91-20-21-89-21l-34 0 0-51 31 0q121 0 162 36 41 36 41 144l0 126q0 73 27 102 26 28 95 28l30 0 0 51-30 0q-69 0-95 29-27 28-27 101l0 126q0 108-41 144-41 36-162 36l-31 0 0-51z",
+        ),
+        (
+            '~',
+            838,
+            "m732-394l0 72q-49 43-93 64-44 20-89 20-47 0-132-32l-5-2q-5-2-16-6-72-28-118-28-45 0-87 18-43 17-86 55l0-74q49-43 92-63 44-19 90-19 47 0 132 32l5 2q5 2 15 6 71 28 118 28 45 0 88-18 43-18 86-55z",
+        ),
+        ('\u{a0}', 318, ""),
+        (
+            '\u{a1}',
+            402,
+            "m136-496q0-27 18-46 19-19 47-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-19-18-19-18-46zm2 678l33-401 0-123 60 0 0 123 33 401-126 0z",
+        ),
+        (
+            '\u{a2}',
+            636,
+            "m307-484q-63 7-95 64-31 56-31 160 0 105 31 162 32 56 95 63l0-449zm232 328q-17 75-63 118-46 42-120 51l0 132-49 0 0-132q-107-9-169-83-63-73-63-190 0-118 63-191 62-73 169-81l0-130 49 0 0 130q45 4 86 15 41 11 80 30l0 133-52 0q-9-60-37-92-28-32-77-38l0 449q44-6 72-37 29-31 39-84l72 0z",
+        ),
+        (
+            '\u{a3}',
+            636,
+            "m538-598l-51 0q-5-47-33-72-27-24-74-24-62 0-92 38-29 38-29 118l0 144 201 0 0 52-201 0 0 283 233 0 0-111 57 0 0 170-482 0 0-52 94 0 0-290-94 0 0-52 94 0 0-144q0-100 56-152 56-52 164-52 41 0 81 6 39 6 76 18l0 120z",
+        ),
+        (
+            '\u{a4}',
+            636,
+            "m440-481l102-101 46 46-102 102q21 29 31 59 10 30 10 62 0 34-10 63-10 28-32 56l101 101-47 46-101-100q-27 22-56 32-29 10-63 10-31 0-61-10-31-10-61-30l-100 100-46-46 100-101q-20-30-30-60-10-30-10-61 0-34 10-63 10-29 32-57l-101-101 46-46 101 101q28-21 57-31 29-11 63-11 32 0 62 10 30 10 59 30zm10 167q0-55-38-92-37-38-93-38-55 0-93 38-38 37-38 92 0 56 38 94 38 38 93 38 55 0 93-38 38-39 38-94z",
+        ),
+        (
+            '\u{a5}',
+            636,
+            "m176 0l0-52 93 0 0-179-197 0 0-51 197 0 0-32-40-69-157 0 0-51 128 0-140-243-68 0 0-52 270 0 0 52-85 0 167 295 166-295-82 0 0-52 211 0 0 52-69 0-139 243 133 0 0 51-162 0-35 60 0 41 197 0 0 51-197 0 0 179 93 0 0 52-284 0z",
+        ),
+        (
+            '\u{a6}',
+            337,
+            "m208-199l0 370-79 0 0-370 79 0zm0-500l0 370-79 0 0-370 79 0z",
+        ),
+        (
+            '\u{a7}',
+            500,
+            "m185-457q-33 22-47 43-13 20-13 46 0 64 177 163l16 9q28-18 42-42 15-23 15-51 0-37-34-69-34-33-156-99zm220-258l0 99-45 0q-1-35-26-56-26-20-69-20-49 0-77 24-28 23-28 64 0 47 120 114 13 8 19 11 95 53 125 89 31 37 31 86 0 46-25 77-26 31-82 52 38 23 56 54 18 31 18 75 0 64-50 102-50 39-134 39-44 0-83-7-39-6-77-20l0-102 45 0q1 39 27 59 27 20 79 20 51 0 81-24 29-24 29-65 0-55-111-117-11-6-16-9-106-59-136-96-31-37-31-85 0-46 29-79 29-32 82-47-40-24-60-55-19-30-19-69 0-64 49-103 49-38 130-38 38 0 75 6 37 7 74 21z",
+        ),
+        (
+            '\u{a8}',
+            500,
+            "m155-766q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{a9}',
+            1000,
+            "m672-280q-13 64-58 98-44 35-113 35-91 0-145-59-55-58-55-156 0-98 55-157 55-59 145-59 39 0 77 9 38 9 80 28l0 104-41 0q-8-53-36-78-28-25-79-25-58 0-88 44-29 45-29 134 0 89 29 133 29 44 87 44 47 0 75-23 28-23 38-72l58 0zm-172-445q74 0 138 27 65 27 118 80 53 53 80 117 26 64 26 139 0 74-26 138-27 64-80 117-53 53-118 80-64 27-138 27-74 0-138-27-65-27-118-80-53-53-80-117-26-64-26-138 0-75 26-139 27-64 80-117 53-53 118-80 64-27 138-27zm0 50q-64 0-119 23-55 23-101 69-46 45-69 101-23 56-23 120 0 64 23 119 23 55 69 101 46 46 101 69 55 23 119 23 64 0 120-23 55-23 101-69 46-46 69-101 22-55 22-119 0-65-23-120-23-56-68-101-46-46-101-69-56-23-120-23z",
+        ),
+        (
+            '\u{aa}',
+            475,
+            "m409-595l0 185 39 0 0 41-111 0 0-39q-24 25-55 36-30 12-72 12-69 0-109-32-40-31-40-86 0-56 47-87 46-31 129-31l100 0 0-22q0-41-29-64-28-23-79-23-42 0-67 17-25 17-32 51l-37 0 0-77q37-14 73-21 35-7 67-7 86 0 131 38 45 37 45 109zm-72 108l0-72-92 0q-51 0-77 19-27 20-27 58 0 40 25 60 24 21 72 21 45 0 72-23 27-24 27-63zm-256 175l341 0 0 51-341 0 0-51z",
+        ),
+        (
+            '\u{ab}',
+            612,
+            "m518-517l0 56-145 168 145 168 0 56-229-204 0-40 229-204zm-212 0l0 56-145 168 145 168 0 56-229-204 0-40 229-204z",
+        ),
+        ('\u{ac}', 838, "m106-421l626 0 0 281-78 0 0-202-548 0 0-79z"),
+        ('\u{ad}', 338, "m44-306l250 0 0 76-250 0 0-76z"),
+        (
+            '\u{ae}',
+            1000,
+            "m437-386l61 0q41 0 61-18 20-19 20-56 0-36-21-54-20-19-60-19l-61 0 0 147zm120 18q21 7 35 20 14 12 24 31l57 112 37 0 0 33-93 0-64-124q-17-34-32-46-15-11-39-11l-45 0 0 148 41 0 0 33-155 0 0-33 41 0 0-328-41 0 0-33 203 0q62 0 94 28 32 27 32 80 0 41-24 63-23 22-71 27zm-57-307q-64 0-119 23-55 23-101 69-46 45-69 101-23 56-23 120 0 64 23 119 23 55 69 101 46 46 101 69 55 23 119 23 64 0 120-23 55-23 101-69 46-46 69-101 22-55 22-119 0-65-23-120-23-56-68-101-46-46-101-69-56-23-120-23zm0-50q74 0 138 27 65 27 118 80 53 53 80 117 26 64 26 139 0 74-26 138-27 64-80 117-53 53-118 80-64 27-138 27-74 0-138-27-65-27-118-80-53-53-80-117-26-64-26-138 0-75 26-139 27-64 80-117 53-53 118-80 64-27 138-27z",
+        ),
+        ('\u{af}', 500, "m104-745l292 0 0 72-292 0 0-72z"),
+        (
+            '\u{b0}',
+            500,
+            "m250-675q-37 0-62 25-26 26-26 64 0 37 25 62 25 25 63 25 37 0 62-25 26-25 26-62 0-37-26-63-26-26-62-26zm0-67q31 0 60 12 29 12 50 34 22 22 34 50 11 28 11 60 0 65-45 110-46 44-111 44-66 0-110-44-44-44-44-110 0-65 45-110 45-46 110-46z",
+        ),
+        (
+            '\u{b1}',
+            838,
+            "m458-627l0 191 274 0 0 79-274 0 0 191-78 0 0-191-274 0 0-79 274 0 0-191 78 0zm-352 549l626 0 0 78-626 0 0-78z",
+        ),
+        (
+            '\u{b2}',
+            401,
+            "m83-633l-34 0 0-77q32-16 66-24 34-8 68-8 67 0 109 32 42 32 42 83 0 60-95 145l-9 8-116 105 188 0 0-53 36 0 0 96-294 0 0-37 139-124q42-37 60-67 18-30 18-62 0-42-23-66-24-24-65-24-38 0-61 18-23 19-29 55z",
+        ),
+        (
+            '\u{b3}',
+            401,
+            "m61-717q36-12 69-19 32-6 61-6 67 0 103 26 37 27 37 75 0 35-26 59-26 25-72 33 58 8 87 37 30 29 30 78 0 56-42 86-43 29-123 29-35 0-69-7-34-7-67-20l0-83 34 0q2 36 26 55 24 19 69 19 46 0 71-21 25-22 25-62 0-45-25-68-25-24-74-24l-26 0 0-36 13 0q47 0 72-18 24-18 24-54 0-33-20-50-20-18-57-18-40 0-61 16-21 15-25 47l-34 0 0-74z",
+        ),
+        ('\u{b4}', 500, "m318-800l97 0-166 184-57 0 126-184z"),
+        (
+            '\u{b5}',
+            650,
+            "m361-519l168 0 0 468 85 0 0 51-175 0 0-92q-25 52-61 79-36 27-80 27-28 0-51-8-23-9-42-27l0 177 85 0 0 52-261 0 0-52 86 0 0-623-81 0 0-52 171 0 0 302q0 95 23 130 24 35 83 35 63 0 95-46 33-46 33-133l0-236-78 0 0-52z",
+        ),
+        (
+            '\u{b6}',
+            636,
+            "m309-729l263 0 0 52-58 0 0 773-52 0 0-773-113 0 0 773-52 0 0-413q-105-8-163-62-57-54-57-144 0-93 63-149 64-57 169-57z",
+        ),
+        (
+            '\u{b7}',
+            318,
+            "m93-347q0-28 19-47 18-18 46-18 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-46-18-19-19-19-47z",
+        ),
+        (
+            '\u{b8}',
+            500,
+            "m282 0q31 31 46 58 16 28 16 52 0 39-30 61-31 22-86 22-20 0-42-3-21-3-44-9l0-64q20 8 40 13 19 4 36 4 28 0 44-13 16-13 16-35 0-14-11-36-11-21-32-50l47 0z",
+        ),
+        (
+            '\u{b9}',
+            401,
+            "m86-326l0-41 84 0 0-310-98 52 0-46 117-63 50 0 0 367 84 0 0 41-237 0z",
+        ),
+        (
+            '\u{ba}',
+            470,
+            "m235-397q52 0 78-39 26-39 26-115 0-76-27-115-26-39-77-39-51 0-77 39-27 40-27 115 0 76 27 115 26 39 77 39zm0 37q-86 0-137-52-52-53-52-139 0-86 52-139 51-52 137-52 85 0 137 52 52 53 52 139 0 87-52 139-51 52-137 52zm-168 48l336 0 0 51-336 0 0-51z",
+        ),
+        (
+            '\u{bb}',
+            612,
+            "m306-517l229 204 0 40-229 204 0-56 145-168-145-168 0-56zm-212 0l229 204 0 40-229 204 0-56 145-168-145-168 0-56z",
+        ),
+        (
+            '\u{bc}',
+            969,
+            "m788-148l0-199-150 199 150 0zm141 148l-212 0 0-41 71 0 0-67-194 0 0-41 195-259 69 0 0 260 80 0 0 40-80 0 0 67 71 0 0 41zm-843-326l0-41 84 0 0-310-98 52 0-46 117-63 50 0 0 367 84 0 0 41-237 0zm592-416l73 0-460 756-73 0 460-756z",
+        ),
+        (
+            '\u{bd}',
+            969,
+            "m86-326l0-41 84 0 0-310-98 52 0-46 117-63 50 0 0 367 84 0 0 41-237 0zm592-416l73 0-460 756-73 0 460-756zm-27 435l-34 0 0-77q32-15 66-24 34-8 68-8 67 0 109 32 42 32 42 83 0 60-95 145l-9 8-116 105 188 0 0-53 36 0 0 96-294 0 0-37 139-124q42-37 60-67 18-30 18-62 0-42-23-66-24-24-65-24-38 0-61 18-23 19-29 55z",
+        ),
+        (
+            '\u{be}',
+            969,
+            "m788-148l0-199-150 199 150 0zm141 148l-212 0 0-41 71 0 0-67-194 0 0-41 195-259 69 0 0 260 80 0 0 40-80 0 0 67 71 0 0 41zm-868-717q36-12 69-19 32-6 61-6 67 0 103 26 37 27 37 75 0 35-26 59-26 25-72 33 58 8 87 37 30 29 30 78 0 56-42 86-43 29-123 29-35 0-69-7-34-7-67-20l0-83 34 0q2 36 26 55 24 19 69 19 46 0 71-21 25-22 25-62 0-45-25-68-25-24-74-24l-26 0 0-36 13 0q47 0 72-18 24-18 24-54 0-33-20-50-20-18-57-18-40 0-61 16-21 15-25 47l-34 0 0-74zm617-25l73 0-460 756-73 0 460-756z",
+        ),
+        (
+            '\u{bf}',
+            536,
+            "m380-496q0 27-19 46-19 19-46 19-27 0-46-19-19-19-19-46 0-27 19-46 19-19 46-19 28 0 47 19 18 19 18 46zm108 646q-52 23-98 34-47 11-88 11-108 0-171-52-63-52-63-141 0-91 54-148 54-58 163-83l0-124 60 0 0 160q-86 21-130 71-44 49-44 125 0 67 36 105 36 39 97 39 55 0 91-32 36-32 46-92l47 0 0 127z",
+        ),
+        (
+            '\u{c0}',
+            722,
+            "m200-264l268 0-134-347-134 347zm-206 264l0-52 64 0 260-677 82 0 260 677 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0zm333-927l89 129-57 0-128-129 96 0z",
+        ),
+        (
+            '\u{c1}',
+            722,
+            "m200-264l268 0-134-347-134 347zm-206 264l0-52 64 0 260-677 82 0 260 677 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0zm398-927l96 0-128 129-57 0 89-129z",
+        ),
+        (
+            '\u{c2}',
+            722,
+            "m200-264l268 0-134-347-134 347zm-206 264l0-52 64 0 260-677 82 0 260 677 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0zm324-927l86 0 113 129-57 0-99-79-99 79-57 0 113-129z",
+        ),
+        (
+            '\u{c3}',
+            722,
+            "m200-264l268 0-134-347-134 347zm-206 264l0-52 64 0 260-677 82 0 260 677 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0zm360-837l-23-14q-10-6-20-9-10-4-18-4-19 0-31 14-13 14-15 36l-47 0q1-49 26-78 24-29 66-29 18 0 33 5 15 4 42 19l24 13q11 6 21 10 11 3 20 3 20 0 32-13 11-14 11-37l47 0q-1 51-26 79-25 28-71 28-33 0-68-22l-3-1z",
+        ),
+        (
+            '\u{c4}',
+            722,
+            "m200-264l268 0-134-347-134 347zm-206 264l0-52 64 0 260-677 82 0 260 677 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0zm272-920q23 0 40 17 17 17 17 40 0 24-17 41-16 16-40 16-24 0-40-16-17-17-17-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 24-17 41-16 16-40 16-24 0-40-16-17-17-17-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{c5}',
+            722,
+            "m433-794q0-31-21-52-22-22-53-22-31 0-53 21-21 21-21 53 0 31 21 52 22 22 53 22 31 0 53-22 21-21 21-52zm-233 530l267 0-133-347-134 347zm-206 264l0-52 64 0 239-624q-35-18-53-48-19-29-19-70 0-56 39-95 39-39 95-39 56 0 95 39 39 39 39 95 0 39-19 70-19 31-54 48l240 624 72 0 0 52-265 0 0-52 81 0-61-160-307 0-61 160 80 0 0 52-205 0z",
+        ),
+        (
+            '\u{c6}',
+            1001,
+            "m208-264l239 0 0-413-49 0-190 413zm146 264l0-52 93 0 0-160-263 0-74 160 80 0 0 52-207 0 0-52 64 0 288-625-105 0 0-52 711 0 0 162-60 0 0-102-336 0 0 244 240 0 0-91 60 0 0 242-60 0 0-91-240 0 0 305 344 0 0-102 60 0 0 162-595 0z",
+        ),
+        (
+            '\u{c7}',
+            765,
+            "m705-193q-32 102-108 155-76 52-192 52-72 0-133-24-61-25-108-72-55-55-82-124-26-70-26-158 0-170 98-274 98-104 259-104 60 0 127 16 67 15 145 47l0 168-55 0q-18-92-72-135-55-44-153-44-117 0-177 83-60 83-60 243 0 160 60 243 60 83 177 83 81 0 134-39 53-39 76-116l90 0zm-261 193q32 31 47 58 15 28 15 52 0 39-30 61-31 22-86 22-20 0-42-3-21-3-44-9l0-64q20 8 40 13 19 4 36 4 28 0 44-13 16-13 16-35 0-14-11-36-11-21-32-50l47 0z",
+        ),
+        (
+            '\u{c8}',
+            730,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 587 0 0 162-60 0 0-102-335 0 0 244 239 0 0-91 60 0 0 242-60 0 0-91-239 0 0 305 343 0 0-102 60 0 0 162-595 0zm276-927l89 129-57 0-128-129 96 0z",
+        ),
+        (
+            '\u{c9}',
+            730,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 587 0 0 162-60 0 0-102-335 0 0 244 239 0 0-91 60 0 0 242-60 0 0-91-239 0 0 305 343 0 0-102 60 0 0 162-595 0zm341-927l96 0-128 129-57 0 89-129z",
+        ),
+        (
+            '\u{ca}',
+            730,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 587 0 0 162-60 0 0-102-335 0 0 244 239 0 0-91 60 0 0 242-60 0 0-91-239 0 0 305 343 0 0-102 60 0 0 162-595 0zm267-927l86 0 113 129-57 0-99-79-99 79-57 0 113-129z",
+        ),
+        (
+            '\u{cb}',
+            730,
+            "m55 0l0-52 93 0 0-625-93 0 0-52 587 0 0 162-60 0 0-102-335 0 0 244 239 0 0-91 60 0 0 242-60 0 0-91-239 0 0 305 343 0 0-102 60 0 0 162-595 0zm215-920q23 0 40 17 17 17 17 40 0 24-17 41-16 16-40 16-24 0-40-16-17-17-17-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 24-17 41-16 16-40 16-24 0-40-16-17-17-17-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{cc}',
+            395,
+            "m247-52l93 0 0 52-285 0 0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 625zm-83-875l89 129-57 0-128-129 96 0z",
+        ),
+        (
+            '\u{cd}',
+            395,
+            "m247-52l93 0 0 52-285 0 0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 625zm-18-875l96 0-128 129-57 0 89-129z",
+        ),
+        (
+            '\u{ce}',
+            395,
+            "m247-52l93 0 0 52-285 0 0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 625zm-92-875l86 0 113 129-57 0-99-79-99 79-57 0 113-129z",
+        ),
+        (
+            '\u{cf}',
+            395,
+            "m247-52l93 0 0 52-285 0 0-52 93 0 0-625-93 0 0-52 285 0 0 52-93 0 0 625zm-144-868q23 0 40 17 17 17 17 40 0 24-16 41-17 16-41 16-24 0-41-16-16-17-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 24-16 41-17 16-41 16-24 0-41-16-16-17-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{d0}',
+            807,
+            "m251-52l92 0q142 0 218-81 76-81 76-232 0-152-76-232-76-80-218-80l-92 0 0 265 164 0 0 61-164 0 0 299zm-191 52l0-52 93 0 0-299-98 0 0-61 98 0 0-265-93 0 0-52 290 0q189 0 294 96 105 96 105 268 0 172-105 269-106 96-294 96l-290 0z",
+        ),
+        (
+            '\u{d1}',
+            875,
+            "m49 0l0-52 98 0 0-625-98 0 0-52 187 0 437 575 0-523-98 0 0-52 256 0 0 52-98 0 0 691-59 0-467-614 0 548 98 0 0 52-256 0zm383-837l-24-14q-10-6-20-9-10-4-18-4-19 0-32 14-12 14-14 36l-47 0q1-49 26-78 25-29 66-29 18 0 33 5 15 4 42 19l24 13q11 6 22 10 10 3 19 3 20 0 31-13 12-14 12-37l47 0q-1 51-26 79-25 28-71 28-33 0-68-22l-2-1z",
+        ),
+        (
+            '\u{d2}',
+            820,
+            "m410-38q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82zm0 52q-74 0-136-24-63-25-110-72-55-55-82-124-26-69-26-158 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 88-27 158-27 70-81 124-48 48-110 72-61 24-136 24zm-34-941l89 129-57 0-128-129 96 0z",
+        ),
+        (
+            '\u{d3}',
+            820,
+            "m410-38q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82zm0 52q-74 0-136-24-63-25-110-72-55-55-82-124-26-69-26-158 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 88-27 158-27 70-81 124-48 48-110 72-61 24-136 24zm31-941l96 0-128 129-57 0 89-129z",
+        ),
+        (
+            '\u{d4}',
+            820,
+            "m410-38q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82zm0 52q-74 0-136-24-63-25-110-72-55-55-82-124-26-69-26-158 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 88-27 158-27 70-81 124-48 48-110 72-61 24-136 24zm-43-941l86 0 113 129-57 0-99-79-99 79-57 0 113-129z",
+        ),
+        (
+            '\u{d5}',
+            820,
+            "m410-38q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82zm0 52q-74 0-136-24-63-25-110-72-55-55-82-124-26-69-26-158 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 88-27 158-27 70-81 124-48 48-110 72-61 24-136 24zm-6-851l-24-14q-9-6-19-9-10-4-19-4-19 0-32 14-12 14-14 36l-47 0q1-49 26-78 25-29 66-29 18 0 33 5 15 4 42 19l24 13q11 6 22 10 11 3 19 3 20 0 31-13 12-14 12-37l47 0q-1 51-26 79-25 28-71 28-33 0-68-22l-2-1z",
+        ),
+        (
+            '\u{d6}',
+            820,
+            "m410-38q120 0 181-82 61-83 61-244 0-161-61-244-61-82-181-82-120 0-181 82-61 83-61 244 0 161 61 244 61 82 181 82zm0 52q-74 0-136-24-63-25-110-72-55-55-82-124-26-69-26-158 0-88 26-158 27-69 82-124 48-48 110-72 61-24 136-24 159 0 256 104 98 104 98 274 0 88-27 158-27 70-81 124-48 48-110 72-61 24-136 24zm-95-934q23 0 40 17 17 17 17 40 0 24-16 41-17 16-41 16-24 0-41-16-16-17-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 24-16 41-17 16-41 16-24 0-41-16-16-17-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{d7}',
+            838,
+            "m700-539l-226 226 226 225-56 55-225-225-225 225-56-55 225-225-225-226 56-55 225 225 225-225 56 55z",
+        ),
+        (
+            '\u{d8}',
+            820,
+            "m593-605q-32-43-77-64-45-21-106-21-120 0-181 82-61 83-61 244 0 58 8 106 8 47 24 86l393-433zm-367 480q31 43 77 65 46 22 107 22 120 0 181-82 61-83 61-244 0-57-8-105-8-48-25-88l-393 432zm453-498q43 53 64 117 21 64 21 142 0 169-98 274-98 104-256 104-69 0-127-21-58-21-107-63l-89 98-38-34 91-101q-42-51-63-115-21-64-21-142 0-169 98-274 98-104 256-104 69 0 128 20 58 21 105 62l87-97 39 35-90 99z",
+        ),
+        (
+            '\u{d9}',
+            843,
+            "m140-677l-93 0 0-52 285 0 0 52-93 0 0 377q0 140 45 195 46 55 158 55 112 0 158-55 45-55 45-195l0-377-93 0 0-52 246 0 0 52-93 0 0 387q0 166-64 235-64 69-217 69-153 0-218-70-66-69-66-234l0-387zm267-250l89 129-57 0-128-129 96 0z",
+        ),
+        (
+            '\u{da}',
+            843,
+            "m140-677l-93 0 0-52 285 0 0 52-93 0 0 377q0 140 45 195 46 55 158 55 112 0 158-55 45-55 45-195l0-377-93 0 0-52 246 0 0 52-93 0 0 387q0 166-64 235-64 69-217 69-153 0-218-70-66-69-66-234l0-387zm332-250l96 0-128 129-57 0 89-129z",
+        ),
+        (
+            '\u{db}',
+            843,
+            "m140-677l-93 0 0-52 285 0 0 52-93 0 0 377q0 140 45 195 46 55 158 55 112 0 158-55 45-55 45-195l0-377-93 0 0-52 246 0 0 52-93 0 0 387q0 166-64 235-64 69-217 69-153 0-218-70-66-69-66-234l0-387zm258-250l86 0 113 129-57 0-99-79-99 79-57 0 113-129z",
+        ),
+        (
+            '\u{dc}',
+            843,
+            "m140-677l-93 0 0-52 285 0 0 52-93 0 0 377q0 140 45 195 46 55 158 55 112 0 158-55 45-55 45-195l0-377-93 0 0-52 246 0 0 52-93 0 0 387q0 166-64 235-64 69-217 69-153 0-218-70-66-69-66-234l0-387zm206-243q23 0 40 17 17 17 17 40 0 24-17 41-16 16-40 16-24 0-40-16-17-17-17-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 24-17 41-16 16-40 16-24 0-40-16-17-17-17-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{dd}',
+            660,
+            "m189 0l0-52 93 0 0-262-225-363-68 0 0-52 271 0 0 52-85 0 182 295 182-295-82 0 0-52 212 0 0 52-69 0-219 354 0 271 93 0 0 52-285 0zm172-927l96 0-128 129-57 0 89-129z",
+        ),
+        (
+            '\u{de}',
+            676,
+            "m247-212l129 0q73 0 111-40 38-39 38-113 0-74-38-113-38-40-111-40l-129 0 0 306zm-192 212l0-52 93 0 0-625-93 0 0-52 305 0 0 52-113 0 0 107 153 0q109 0 173 56 64 55 64 149 0 94-64 150-64 56-173 56l-153 0 0 107 113 0 0 52-305 0z",
+        ),
+        (
+            '\u{df}',
+            668,
+            "m459-591q-4-58-38-89-34-32-92-32-64 0-94 32-30 31-30 99l0 581-176 0 0-52 86 0 0-527q0-86 56-133 56-48 158-48 104 0 159 51 54 51 60 154l-11 0q-71 0-111 27-41 26-41 71 0 25 13 44 13 19 49 42l43 27q76 48 105 90 29 41 29 97 0 75-56 123-57 48-147 48-42 0-83-9-41-9-79-26l0-114 53 0q2 50 29 75 27 26 77 26 54 0 85-30 31-29 31-80 0-41-20-70-19-28-76-64l-42-26q-51-32-72-64-22-31-22-71 0-60 41-100 41-40 116-52z",
+        ),
+        (
+            '\u{e0}',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156zm-281-475l127 184-57 0-166-184 96 0z",
+        ),
+        (
+            '\u{e1}',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156zm-142-476l97 0-166 184-57 0 126-184z",
+        ),
+        (
+            '\u{e2}',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156zm-251-476l82 0 115 184-57 0-99-127-99 127-57 0 115-184z",
+        ),
+        (
+            '\u{e3}',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156zm-212-345l-28-27q-13-12-21-16-8-3-17-3-21 0-32 19-12 20-14 57l-47 0q1-66 25-102 25-36 70-36 18 0 34 7 16 7 34 23l28 27q11 11 19 15 8 4 19 4 21 0 32-19 12-20 14-57l47 0q-1 66-26 102-25 36-69 36-19 0-34-7-16-7-34-23z",
+        ),
+        (
+            '\u{e4}',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156zm-305-442q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-16 41-17 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{e5}',
+            596,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm90-161l0 272 80 0 0 52-170 0 0-56q-30 36-69 53-39 17-91 17-87 0-138-46-50-46-50-124 0-80 58-124 58-45 164-45l126 0 0-36q0-59-36-91-36-33-101-33-53 0-85 25-32 24-40 72l-46 0 0-105q47-20 91-30 44-10 86-10 108 0 165 53 56 54 56 156zm-76-420q0 56-39 95-39 39-95 39-56 0-95-39-39-39-39-95 0-56 39-95 39-39 95-39 56 0 95 39 39 39 39 95zm-60 0q0-31-22-52-21-22-52-22-32 0-53 22-21 21-21 52 0 32 21 53 21 21 53 21 31 0 52-22 22-21 22-52z",
+        ),
+        (
+            '\u{e6}',
+            940,
+            "m398-163l0-110-116 0q-67 0-100 29-32 29-32 88 0 54 33 86 33 32 90 32 56 0 91-34 34-35 34-91zm53 90q-40 44-89 65-50 22-111 22-96 0-149-44-52-45-52-126 0-80 58-124 59-45 164-45l126 0 0-36q0-58-35-91-35-33-98-33-57 0-89 24-32 24-40 73l-46 0 0-105q47-20 91-30 44-10 86-10 69 0 116 21 47 21 74 65 36-43 83-65 47-21 104-21 118 0 181 73 63 72 65 210l-387 0 0 4q0 105 39 158 40 54 117 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-60 0-110-22-51-22-89-65zm333-229q-3-91-38-137-36-46-102-46-62 0-98 47-36 46-43 136l281 0z",
+        ),
+        (
+            '\u{e7}',
+            560,
+            "m514-156q-19 83-73 126-54 44-140 44-113 0-182-75-69-75-69-199 0-124 69-198 69-75 182-75 49 0 98 11 49 12 98 35l0 133-52 0q-10-68-45-100-34-31-98-31-73 0-110 57-37 56-37 168 0 113 37 170 37 56 110 56 58 0 92-30 35-31 48-92l72 0zm-183 156q31 31 47 58 15 28 15 52 0 39-31 61-30 22-85 22-21 0-43-3-21-3-43-9l0-64q20 8 39 13 20 4 37 4 27 0 43-13 17-13 17-35 0-14-11-36-11-21-32-50l47 0z",
+        ),
+        (
+            '\u{e8}',
+            592,
+            "m542-250l-387 0 0 4q0 105 39 158 40 54 117 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-114 0-183-75-69-75-69-199 0-123 68-198 68-75 178-75 118 0 181 73 63 72 65 210zm-106-52q-3-91-38-137-36-46-102-46-62 0-98 47-35 46-43 136l281 0zm-211-497l127 184-57 0-166-184 96 0z",
+        ),
+        (
+            '\u{e9}',
+            592,
+            "m542-250l-387 0 0 4q0 105 39 158 40 54 117 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-114 0-183-75-69-75-69-199 0-123 68-198 68-75 178-75 118 0 181 73 63 72 65 210zm-106-52q-3-91-38-137-36-46-102-46-62 0-98 47-35 46-43 136l281 0zm-72-498l97 0-166 184-57 0 126-184z",
+        ),
+        (
+            '\u{ea}',
+            592,
+            "m542-250l-387 0 0 4q0 105 39 158 40 54 117 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-114 0-183-75-69-75-69-199 0-123 68-198 68-75 178-75 118 0 181 73 63 72 65 210zm-106-52q-3-91-38-137-36-46-102-46-62 0-98 47-35 46-43 136l281 0zm-181-498l82 0 115 184-57 0-99-127-99 127-57 0 115-184z",
+        ),
+        (
+            '\u{eb}',
+            592,
+            "m542-250l-387 0 0 4q0 105 39 158 40 54 117 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-114 0-183-75-69-75-69-199 0-123 68-198 68-75 178-75 118 0 181 73 63 72 65 210zm-106-52q-3-91-38-137-36-46-102-46-62 0-98 47-35 46-43 136l281 0zm-235-464q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{ec}',
+            320,
+            "m212-52l85 0 0 52-261 0 0-52 86 0 0-415-86 0 0-52 176 0 0 467zm-123-747l127 184-57 0-166-184 96 0z",
+        ),
+        (
+            '\u{ed}',
+            320,
+            "m212-52l85 0 0 52-261 0 0-52 86 0 0-415-86 0 0-52 176 0 0 467zm16-748l97 0-166 184-57 0 126-184z",
+        ),
+        (
+            '\u{ee}',
+            320,
+            "m212-52l85 0 0 52-261 0 0-52 86 0 0-415-86 0 0-52 176 0 0 467zm-93-748l82 0 115 184-57 0-99-127-99 127-57 0 115-184z",
+        ),
+        (
+            '\u{ef}',
+            320,
+            "m212-52l85 0 0 52-261 0 0-52 86 0 0-415-86 0 0-52 176 0 0 467zm-147-714q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-24 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{f0}',
+            602,
+            "m401-453q-22-5-42-7-20-2-39-2-85 0-125 52-40 53-40 162 0 102 38 158 38 56 108 56 70 0 108-56 38-56 38-160 0-55-11-106-12-52-35-97zm-30-185q89 81 135 176 46 96 46 202 0 124-68 199-69 75-183 75-112 0-181-74-70-74-70-193 0-115 69-186 69-71 180-71 17 0 35 2 19 1 44 5-11-26-28-52-18-26-43-53l-157 73-18-38 143-66q-30-28-69-48-38-20-84-31l9-42q58 13 109 36 51 23 96 56l157-73 18 38-140 65z",
+        ),
+        (
+            '\u{f1}',
+            644,
+            "m41 0l0-52 81 0 0-415-86 0 0-52 176 0 0 92q25-52 64-79 40-27 93-27 86 0 127 49 40 49 40 154l0 278 80 0 0 52-248 0 0-52 78 0 0-250q0-95-24-130-23-36-82-36-63 0-96 46-32 46-32 133l0 237 78 0 0 52-249 0zm279-669l-28-27q-13-12-21-16-8-3-17-3-21 0-32 19-12 20-14 57l-47 0q1-66 25-102 25-36 70-36 18 0 34 7 16 7 34 23l28 27q11 11 19 15 8 4 19 4 21 0 32-19 12-20 14-57l47 0q-1 66-26 102-25 36-69 36-19 0-34-7-16-7-34-23z",
+        ),
+        (
+            '\u{f2}',
+            602,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm0 48q-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 114 0 183 75 68 74 68 198 0 124-68 199-69 75-183 75zm-71-813l127 184-57 0-166-184 96 0z",
+        ),
+        (
+            '\u{f3}',
+            602,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm0 48q-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 114 0 183 75 68 74 68 198 0 124-68 199-69 75-183 75zm68-814l97 0-166 184-57 0 126-184z",
+        ),
+        (
+            '\u{f4}',
+            602,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm0 48q-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 114 0 183 75 68 74 68 198 0 124-68 199-69 75-183 75zm-41-814l82 0 115 184-57 0-99-127-99 127-57 0 115-184z",
+        ),
+        (
+            '\u{f5}',
+            602,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm0 48q-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 114 0 183 75 68 74 68 198 0 124-68 199-69 75-183 75zm-2-683l-28-27q-13-12-21-16-8-3-17-3-21 0-33 19-11 20-13 57l-47 0q1-66 26-102 24-36 69-36 18 0 34 7 16 7 34 23l28 27q11 11 19 15 8 4 19 4 21 0 33-19 11-20 13-57l47 0q-1 66-26 102-25 36-69 36-19 0-35-7-15-7-33-23z",
+        ),
+        (
+            '\u{f6}',
+            602,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm0 48q-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 114 0 183 75 68 74 68 198 0 124-68 199-69 75-183 75zm-95-780q23 0 40 17 17 17 17 40 0 25-17 41-16 16-40 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-17 41-16 16-40 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{f7}',
+            838,
+            "m354-494q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-19-18-18-18-46zm0 361q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-18-18-19-18-47zm-248-220l626 0 0 79-626 0 0-79z",
+        ),
+        (
+            '\u{f8}',
+            602,
+            "m410-429q-18-28-46-42-27-14-63-14-72 0-109 57-37 57-37 168 0 35 3 64 4 29 11 53l241-286zm-219 336q19 30 47 45 27 14 63 14 72 0 109-58 37-57 37-168 0-37-4-66-4-30-11-55l-241 288zm299-357q31 36 46 84 16 49 16 106 0 124-68 199-69 75-183 75-46 0-85-13-39-13-72-39l-70 84-37-31 73-87q-30-37-45-84-15-48-15-104 0-124 68-198 69-75 183-75 46 0 85 13 39 12 70 36l68-81 37 31-71 84z",
+        ),
+        (
+            '\u{f9}',
+            644,
+            "m354-519l168 0 0 467 85 0 0 52-175 0 0-92q-25 52-64 79-40 27-92 27-87 0-128-49-40-49-40-154l0-278-81 0 0-52 171 0 0 302q0 95 24 130 23 35 82 35 63 0 96-46 32-46 32-133l0-236-78 0 0-52zm-140-280l127 184-57 0-166-184 96 0z",
+        ),
+        (
+            '\u{fa}',
+            644,
+            "m354-519l168 0 0 467 85 0 0 52-175 0 0-92q-25 52-64 79-40 27-92 27-87 0-128-49-40-49-40-154l0-278-81 0 0-52 171 0 0 302q0 95 24 130 23 35 82 35 63 0 96-46 32-46 32-133l0-236-78 0 0-52zm-1-281l97 0-166 184-57 0 126-184z",
+        ),
+        (
+            '\u{fb}',
+            644,
+            "m354-519l168 0 0 467 85 0 0 52-175 0 0-92q-25 52-64 79-40 27-92 27-87 0-128-49-40-49-40-154l0-278-81 0 0-52 171 0 0 302q0 95 24 130 23 35 82 35 63 0 96-46 32-46 32-133l0-236-78 0 0-52zm-110-281l82 0 115 184-57 0-99-127-99 127-57 0 115-184z",
+        ),
+        (
+            '\u{fc}',
+            644,
+            "m354-519l168 0 0 467 85 0 0 52-175 0 0-92q-25 52-64 79-40 27-92 27-87 0-128-49-40-49-40-154l0-278-81 0 0-52 171 0 0 302q0 95 24 130 23 35 82 35 63 0 96-46 32-46 32-133l0-236-78 0 0-52zm-164-247q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-24 0-40-16-17-16-17-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-25 0-41-16-16-16-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{fd}',
+            565,
+            "m216 95l34-86-194-476-59 0 0-52 239 0 0 52-83 0 146 357 146-357-78 0 0-52 195 0 0 52-58 0-238 584q-24 61-54 83-30 22-84 22-23 0-47-4-24-4-49-11l0-99 46 0q3 33 17 48 14 14 43 14 27 0 44-15 16-15 34-60zm135-895l97 0-166 184-57 0 126-184z",
+        ),
+        (
+            '\u{fe}',
+            640,
+            "m205-285l0 51q0 94 36 143 36 49 104 49 69 0 105-55 35-55 35-163 0-108-35-162-36-55-105-55-68 0-104 49-36 50-36 143zm-90-423l-86 0 0-52 176 0 0 322q26-49 67-72 41-23 101-23 96 0 157 75 60 76 60 198 0 122-60 198-61 76-157 76-60 0-101-23-41-23-67-72l0 237 85 0 0 52-261 0 0-52 86 0 0-864z",
+        ),
+        (
+            '\u{ff}',
+            565,
+            "m216 95l34-86-194-476-59 0 0-52 239 0 0 52-83 0 146 357 146-357-78 0 0-52 195 0 0 52-58 0-238 584q-24 61-54 83-30 22-84 22-23 0-47-4-24-4-49-11l0-99 46 0q3 33 17 48 14 14 43 14 27 0 44-15 16-15 34-60zm-28-861q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-24 0-40-16-17-16-17-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 25-16 41-16 16-41 16-24 0-40-16-17-16-17-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{152}',
+            1137,
+            "m555-52l0-625-91 0q-143 0-218 80-76 80-76 231 0 152 76 233 76 81 218 81l91 0zm502 52l-600 0q-188 0-293-96-106-97-106-270 0-172 106-268 105-95 293-95l592 0 0 162-60 0 0-102-335 0 0 244 240 0 0-91 59 0 0 242-59 0 0-91-240 0 0 305 343 0 0-102 60 0 0 162z",
+        ),
+        (
+            '\u{153}',
+            989,
+            "m301-34q72 0 109-57 37-57 37-169 0-111-37-168-37-57-109-57-72 0-109 57-37 57-37 168 0 112 37 169 37 57 109 57zm638-216l-387 0 0 4q0 105 40 158 39 54 116 54 59 0 97-31 38-31 53-92l72 0q-21 85-79 128-58 43-152 43-63 0-113-24-51-24-87-71-34 47-85 71-50 24-113 24-113 0-182-75-69-75-69-199 0-124 68-198 69-75 183-75 64 0 115 24 50 24 84 71 36-47 84-71 49-24 109-24 118 0 181 73 63 72 65 210zm-106-52q-3-91-39-137-35-46-101-46-62 0-97 47-36 46-44 136l281 0z",
+        ),
+        (
+            '\u{160}',
+            685,
+            "m93-35l0-166 56 0q2 83 48 123 46 40 139 40 87 0 132-34 46-35 46-101 0-53-28-81-27-28-116-55l-96-29q-105-32-148-79-42-47-42-130 0-93 66-144 66-51 185-51 51 0 111 11 61 11 129 32l0 155-55 0q-8-77-52-112-43-34-131-34-77 0-117 32-41 31-41 91 0 52 31 82 30 30 128 59l90 27q99 30 142 76 42 46 42 124 0 106-68 159-68 54-202 54-60 0-122-12-63-12-127-37zm207-763l-113-129 57 0 99 79 99-79 57 0-113 129-86 0z",
+        ),
+        (
+            '\u{161}',
+            513,
+            "m56-29l0-121 52 0q2 58 36 87 35 29 102 29 61 0 92-23 32-23 32-66 0-34-23-55-23-21-98-45l-65-22q-67-22-97-54-30-32-30-82 0-71 53-111 52-41 144-41 41 0 86 11 46 10 94 31l0 113-52 0q-2-50-35-78-33-29-90-29-57 0-86 20-29 20-29 60 0 33 22 53 22 19 88 40l71 22q74 23 107 58 32 34 32 88 0 74-56 116-57 42-156 42-50 0-98-10-48-11-96-33zm160-587l-115-184 57 0 99 127 99-127 57 0-115 184-82 0z",
+        ),
+        (
+            '\u{178}',
+            660,
+            "m189 0l0-52 93 0 0-262-225-363-68 0 0-52 271 0 0 52-85 0 182 295 182-295-82 0 0-52 212 0 0 52-69 0-219 354 0 271 93 0 0 52-285 0zm46-920q23 0 40 17 17 17 17 40 0 24-16 41-17 16-41 16-24 0-41-16-16-17-16-41 0-23 17-40 17-17 40-17zm190 0q23 0 40 17 17 17 17 40 0 24-16 41-17 16-41 16-24 0-41-16-16-17-16-41 0-23 17-40 17-17 40-17z",
+        ),
+        (
+            '\u{17d}',
+            695,
+            "m45 0l0-35 454-634-377 0 0 109-60 0 0-169 576 0 0 35-454 634 412 0 0-102 60 0 0 162-611 0zm260-798l-113-129 57 0 99 79 99-79 57 0-113 129-86 0z",
+        ),
+        (
+            '\u{17e}',
+            527,
+            "m40 0l0-42 325-425-257 0 0 90-52 0 0-142 425 0 0 42-325 425 282 0 0-94 52 0 0 146-450 0zm183-616l-115-184 57 0 99 127 99-127 57 0-115 184-82 0z",
+        ),
+        (
+            '\u{192}',
+            370,
+            "m430-637l-47 0q0-10-2-20-5-22-19-36-20-19-59-19-50 0-71 27-20 28-20 99l0 67 145 0 0 52-145 0 0 476q0 87-45 132-45 44-133 44-33 0-66-6-32-6-64-18l0-99 47 0q0 11 2 20 5 22 19 36 20 19 59 19 50 0 71-28 20-27 20-100l0-476-86 0 0-52 86 0 0-65q0-87 45-131 45-45 133-45 33 0 66 6 32 6 64 18l0 99z",
+        ),
+        (
+            '\u{2c6}',
+            500,
+            "m209-800l82 0 115 184-57 0-99-127-99 127-57 0 115-184z",
+        ),
+        (
+            '\u{2dc}',
+            500,
+            "m248-669l-28-27q-12-12-20-16-9-3-18-3-20 0-32 19-12 20-14 57l-47 0q1-66 26-102 25-36 69-36 19 0 34 7 16 7 34 23l28 27q11 11 20 15 8 4 18 4 21 0 32-19 12-20 14-57l47 0q-1 66-26 102-25 36-69 36-19 0-34-7-16-7-34-23z",
+        ),
+        ('\u{2013}', 500, "m44-298l412 0 0 65-412 0 0-65z"),
+        ('\u{2014}', 1000, "m44-298l912 0 0 65-912 0 0-65z"),
+        (
+            '\u{2018}',
+            318,
+            "m268-703q-38 31-54 73-17 41-17 104l0 31-95 0q1-85 32-144 30-59 96-103l38 39z",
+        ),
+        (
+            '\u{2019}',
+            318,
+            "m71-521q38-31 55-73 17-42 17-105l0-30 95 0q-1 84-32 143-30 60-97 104l-38-39z",
+        ),
+        (
+            '\u{201a}',
+            318,
+            "m44 97q46-34 68-79 21-46 21-111l0-18 96 0q-4 84-39 143-36 60-107 104l-39-39z",
+        ),
+        (
+            '\u{201c}',
+            511,
+            "m461-703q-37 31-54 73-17 41-17 104l0 31-95 0q1-86 31-145 30-58 97-102l38 39zm-193 0q-38 31-54 73-17 41-17 104l0 31-95 0q1-85 32-144 30-59 96-103l38 39z",
+        ),
+        (
+            '\u{201d}',
+            511,
+            "m71-521q38-31 55-73 17-42 17-105l0-30 95 0q-1 84-32 143-30 60-97 104l-38-39zm194 0q37-31 54-73 17-41 17-104l0-31 95 0q-1 85-31 144-31 59-97 103l-38-39z",
+        ),
+        (
+            '\u{201e}',
+            518,
+            "m44 97q46-34 68-79 21-46 21-111l0-18 96 0q-4 84-39 143-36 60-107 104l-39-39zm193 0q46-34 67-79 22-46 22-111l0-18 96 0q-4 84-39 143-35 60-107 104l-39-39z",
+        ),
+        (
+            '\u{2020}',
+            500,
+            "m206-729l88 0-17 216 195-14 0 81-195-14 17 556-88 0 17-556-195 14 0-81 195 14-17-216z",
+        ),
+        (
+            '\u{2021}',
+            500,
+            "m206-729l88 0-17 216 195-14 0 81-195-14 17 144-17 143 195-14 0 81-195-14 17 216-88 0 17-216-195 14 0-81 195 14-17-143 17-144-195 14 0-81 195 14-17-216z",
+        ),
+        (
+            '\u{2022}',
+            590,
+            "m150-372q0-61 42-102 42-42 104-42 60 0 102 42 42 41 42 102 0 61-42 103-42 42-103 42-61 0-103-42-42-42-42-103z",
+        ),
+        (
+            '\u{2026}',
+            1000,
+            "m768-51q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-18-18-19-18-47zm-333 0q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-46-18-19-19-19-47zm-333 0q0-27 19-46 19-19 46-19 27 0 46 19 19 19 19 46 0 27-19 46-19 19-46 19-28 0-47-18-18-19-18-47z",
+        ),
+        (
+            '\u{2030}',
+            1342,
+            "m1119-383q77 0 123 54 45 54 45 145 0 91-45 144-46 54-123 54-78 0-123-54-46-53-46-144 0-92 46-146 46-53 123-53zm0 44q-42 0-67 41-24 41-24 114 0 72 24 112 25 41 67 41 42 0 66-41 24-40 24-112 0-72-24-114-24-41-66-41zm-895-358q-43 0-67 41-24 40-24 113 0 71 25 112 24 41 66 41 42 0 66-40 24-41 24-113 0-72-24-113-24-41-66-41zm503 359q-43 0-67 40-24 41-24 114 0 72 24 112 25 41 67 41 42 0 66-41 24-40 24-112 0-72-23-113-24-41-66-41l-1 0zm0-45q77 0 123 54 45 54 45 145 0 91-45 144-46 54-123 54-78 0-123-54-46-53-46-144 0-92 46-146 45-53 123-53zm-61-359l74 0-456 756-74 0 456-756zm-443 0q77 0 123 54 46 53 46 144 0 92-46 146-45 53-123 53-77 0-123-53-45-54-45-146 0-91 45-144 46-54 123-54z",
+        ),
+        (
+            '\u{2039}',
+            400,
+            "m306-517l0 56-145 168 145 168 0 56-229-204 0-40 229-204z",
+        ),
+        (
+            '\u{203a}',
+            400,
+            "m94-517l229 204 0 40-229 204 0-56 145-168-145-168 0-56z",
+        ),
+        (
+            '\u{20ac}',
+            636,
+            "m555-193q-21 100-77 153-57 54-141 54-92 0-159-74-66-74-89-203l-93 0 22-51 66 0q-1-7-1-19-1-24-1-31 0-16 0-28 1-12 2-23l-88 0 22-51 71 0q23-129 89-202 66-74 159-74 48 0 97 15 48 15 109 49l0 167-52 0q-10-88-47-135-37-48-97-48-66 0-107 58-40 58-50 170l246 0-23 51-226 0q0 7-1 18-1 25-1 33 0 5 1 22 0 16 0 28l183 0-23 51-156 0q11 112 51 171 40 58 106 58 57 0 94-41 37-41 49-118l65 0z",
+        ),
+        (
+            '\u{2122}',
+            1000,
+            "m546-729l85 132 88-132 107 0 0 32-42 0 0 218 42 0 0 32-139 0 0-32 42 0 0-208-102 152-21 0-97-152 0 208 41 0 0 32-118 0 0-32 42 0 0-218-42 0 0-32 114 0zm-428 0l272 0 0 83-33 0 0-51-75 0 0 218 42 0 0 32-140 0 0-32 42 0 0-218-76 0 0 51-32 0 0-83z",
+        ),
+        (
+            '\u{2190}',
+            838,
+            "m781-363l0 79-629 0 94 165-5 0-204-202 0-5 204-201 5 0-94 164 629 0z",
+        ),
+        (
+            '\u{2191}',
+            838,
+            "m379 0l0-629-164 94 0-5 202-204 4 0 202 204 0 5-165-94 0 629-79 0z",
+        ),
+        (
+            '\u{2192}',
+            838,
+            "m57-363l629 0-94-164 5 0 204 201 0 5-204 202-5 0 94-165-629 0 0-79z",
+        ),
+        (
+            '\u{2193}',
+            838,
+            "m458-724l0 629 165-94 0 5-202 204-4 0-202-204 0-5 164 94 0-629 79 0z",
+        ),
+        ('\u{2212}', 838, "m106-353l626 0 0 79-626 0 0-79z"),
+        ('\u{25b2}', 769, "m3 123l381-766 382 766-763 0z"),
+        ('\u{25bc}', 769, "m3-643l763 0-382 766-381-766z"),
+        (
+            '\u{fffd}',
+            1025,
+            "m513 14q27 0 46-19 19-19 19-46 0-27-19-46-19-19-46-19-28 0-47 19-19 19-19 47 0 27 19 46 19 18 47 18zm-174-711l0 127 48 0q10-60 45-92 36-32 91-32 62 0 97 38 36 39 36 106 0 76-44 126-43 49-130 70l0 160 60 0 0-124q109-25 163-82 54-58 54-149 0-89-63-141-62-52-171-52-41 0-87 11-47 11-99 34zm174-215l498 498-498 498-498-498 498-498z",
+        ),
+    ],
+};
+
+pub(super) static MONOSPACE: FaceData = FaceData {
+    ascent: 928,
+    descent: 236,
+    glyphs: &[
+        (
+            '\0',
+            602,
+            "m51 177l0-882 500 0 0 882-500 0zm56-56l388 0 0-770-388 0 0 770z",
+        ),
+        ('\u{20}', 602, ""),
+        (
+            '!',
+            602,
+            "m252-729l99 0 0 320-10 174-79 0-10-174 0-320zm0 605l99 0 0 124-99 0 0-124z",
+        ),
+        (
+            '"',
+            602,
+            "m437-729l0 271-85 0 0-271 85 0zm-187 0l0 271-85 0 0-271 85 0z",
+        ),
+        (
+            '#',
+            602,
+            "m334-718l-51 203 120 0 51-203 78 0-51 203 119 0 0 75-137 0-41 163 122 0 0 75-141 0-51 202-78 0 51-202-120 0-51 202-78 0 51-202-126 0 0-75 145 0 41-163-130 0 0-75 148 0 51-203 78 0zm51 278l-120 0-41 163 120 0 41-163z",
+        ),
+        (
+            '$',
+            602,
+            "m338-283l0 212q54-2 84-30 30-28 30-77 0-46-28-71-27-25-86-34zm-49-93l0-202q-51 2-79 30-29 27-29 73 0 42 27 67 26 24 81 32zm49 523l-49 0 0-147q-50-2-99-14-49-11-97-31l0-88q49 30 99 47 49 16 97 17l0-224q-98-15-147-59-49-45-49-118 0-77 51-122 52-46 145-53l0-115 49 0 0 115q39 2 78 9 40 8 81 21l0 84q-41-21-81-33-39-11-78-13l0 211q100 15 153 62 53 48 53 123 0 75-57 125-57 51-149 55l0 148z",
+        ),
+        (
+            '%',
+            602,
+            "m340-156q0 38 26 64 25 26 63 26 38 0 64-26 26-26 26-64 0-37-27-64-26-26-63-26-38 0-63 26-26 26-26 64zm-66 0q0-66 45-111 45-45 110-45 31 0 59 12 29 11 51 34 23 23 35 51 12 28 12 59 0 65-46 110-45 46-111 46-66 0-111-44-44-45-44-112zm-215-71l-17-47 509-204 20 47-512 204zm23-316q0 39 26 65 25 25 64 25 37 0 64-26 26-26 26-64 0-38-26-64-27-26-64-26-38 0-64 26-26 26-26 64zm-66 0q0-66 45-111 45-45 111-45 31 0 60 11 28 12 50 34 22 22 34 50 12 29 12 61 0 65-46 111-45 45-110 45-66 0-111-45-45-45-45-111z",
+        ),
+        (
+            '&',
+            602,
+            "m267-443l203 273q19-24 28-60 10-37 10-88 0-15-2-50l0-3 80 0 0 19q0 79-18 138-18 59-55 102l83 112-104 0-38-53q-40 34-86 51-45 16-97 16-105 0-174-66-69-65-69-165 0-67 34-124 33-57 101-106-24-35-36-69-12-33-12-68 0-73 49-116 48-42 133-42 32 0 64 6 32 6 66 17l0 89q-29-19-59-28-31-9-65-9-47 0-75 22-27 22-27 60 0 29 14 61 14 31 52 81zm-64 51q-45 36-67 76-22 39-22 84 0 74 48 122 49 49 125 49 21 0 43-6 23-6 44-17 13-7 22-13 8-6 16-12l-209-283z",
+        ),
+        ('\'', 602, "m343-729l0 271-85 0 0-271 85 0z"),
+        (
+            '(',
+            602,
+            "m432-759q-65 112-97 223-32 110-32 222 0 112 32 223 32 111 97 223l-78 0q-74-116-110-226-36-111-36-220 0-109 36-220 36-110 110-225l78 0z",
+        ),
+        (
+            ')',
+            602,
+            "m170-759l78 0q74 115 110 225 36 111 36 220 0 110-36 220-36 111-110 226l-78 0q65-113 97-224 32-111 32-222 0-111-32-222-32-111-97-223z",
+        ),
+        (
+            '*',
+            602,
+            "m521-609l-175 95 175 95-28 48-164-99 0 184-56 0 0-184-164 99-28-48 175-95-175-95 28-48 164 99 0-184 56 0 0 184 164-99 28 48z",
+        ),
+        (
+            '+',
+            602,
+            "m342-572l0 217 217 0 0 83-217 0 0 217-82 0 0-217-217 0 0-83 217 0 0-217 82 0z",
+        ),
+        (',', 602, "m245-148l123 0 0 101-96 187-75 0 48-187 0-101z"),
+        ('-', 602, "m174-314l254 0 0 80-254 0 0-80z"),
+        ('.', 602, "m239-149l123 0 0 149-123 0 0-149z"),
+        ('/', 602, "m434-729l93 0-384 822-93 0 384-822z"),
+        (
+            '0',
+            602,
+            "m236-366q0-27 18-46 19-20 46-20 27 0 46 20 20 19 20 46 0 27-19 46-19 19-47 19-28 0-46-19-18-18-18-46zm65-298q-69 0-103 74-34 74-34 226 0 152 34 226 34 74 103 74 69 0 103-74 34-74 34-226 0-152-34-226-34-74-103-74zm0-78q116 0 176 96 60 95 60 282 0 187-60 282-60 96-176 96-117 0-177-96-59-95-59-282 0-187 59-282 60-96 177-96z",
+        ),
+        (
+            '1',
+            602,
+            "m132-83l153 0 0-557-165 37 0-90 164-36 99 0 0 646 151 0 0 83-402 0 0-83z",
+        ),
+        (
+            '2',
+            602,
+            "m182-83l335 0 0 83-443 0 0-83q92-96 160-170 68-74 94-104 49-60 66-97 17-36 17-75 0-61-36-95-36-35-98-35-45 0-93 16-49 16-104 49l0-100q50-24 99-36 49-12 96-12 107 0 172 57 65 57 65 149 0 47-22 94-21 46-70 103-27 32-79 88-52 56-159 168z",
+        ),
+        (
+            '3',
+            602,
+            "m379-390q72 19 110 68 38 48 38 121 0 100-67 157-68 58-188 58-50 0-102-9-53-9-103-27l0-98q50 26 98 38 48 13 96 13 81 0 125-37 43-36 43-105 0-63-43-101-44-37-118-37l-75 0 0-81 75 0q68 0 106-30 38-30 38-83 0-56-36-86-35-30-100-30-44 0-90 9-46 10-97 30l0-91q59-16 105-23 46-8 82-8 106 0 170 54 64 53 64 141 0 60-34 100-33 40-97 57z",
+        ),
+        (
+            '4',
+            602,
+            "m359-639l-230 385 230 0 0-385zm-16-90l114 0 0 475 97 0 0 80-97 0 0 174-98 0 0-174-309 0 0-93 293-462z",
+        ),
+        (
+            '5',
+            602,
+            "m101-729l369 0 0 83-279 0 0 179q21-8 42-11 21-4 43-4 113 0 180 67 66 67 66 181 0 115-70 182-69 66-190 66-58 0-106-8-48-7-86-23l0-100q45 24 90 36 46 12 93 12 81 0 125-43 45-43 45-122 0-78-45-122-46-43-128-43-40 0-78 9-37 9-71 27l0-366z",
+        ),
+        (
+            '6',
+            602,
+            "m484-713l0 91q-31-18-66-28-34-9-72-9-94 0-142 71-48 70-48 207 23-49 64-75 42-26 96-26 105 0 163 64 58 65 58 184 0 118-59 183-60 65-168 65-127 0-186-91-59-91-59-287 0-184 71-281 71-97 206-97 36 0 72 8 36 7 70 21zm-176 308q-63 0-99 45-36 46-36 126 0 80 36 126 36 45 99 45 66 0 99-43 33-43 33-128 0-85-33-128-33-43-99-43z",
+        ),
+        (
+            '7',
+            602,
+            "m68-729l459 0 0 42-261 687-103 0 254-646-349 0 0-83z",
+        ),
+        (
+            '8',
+            602,
+            "m301-346q-66 0-102 37-36 37-36 104 0 67 37 105 36 37 101 37 66 0 102-37 36-37 36-105 0-67-37-104-36-37-101-37zm-86-41q-63-16-99-60-35-44-35-106 0-87 59-138 59-51 161-51 102 0 161 51 59 51 59 138 0 62-35 106-36 44-99 60 73 16 112 65 39 49 39 126 0 99-63 154-63 56-174 56-112 0-175-56-62-55-62-153 0-78 39-127 39-49 112-65zm-36-157q0 59 31 90 32 30 91 30 59 0 91-30 31-31 31-90 0-60-31-91-31-31-91-31-59 0-91 32-31 31-31 90z",
+        ),
+        (
+            '9',
+            602,
+            "m291-323q63 0 99-45 36-46 36-126 0-80-36-126-36-45-99-45-65 0-99 43-33 43-33 128 0 85 33 128 33 43 99 43zm-176 308l0-91q31 18 65 28 35 9 73 9 94 0 142-71 48-70 48-207-23 49-65 75-41 26-95 26-105 0-163-65-58-65-58-184 0-118 60-183 59-64 167-64 127 0 186 91 59 91 59 287 0 184-71 281-71 97-206 97-36 0-72-8-36-7-70-21z",
+        ),
+        (
+            ':',
+            602,
+            "m239-519l123 0 0 148-123 0 0-148zm0 370l123 0 0 149-123 0 0-149z",
+        ),
+        (
+            ';',
+            602,
+            "m245-148l123 0 0 101-96 187-75 0 48-187 0-101zm-6-371l123 0 0 148-123 0 0-148z",
+        ),
+        (
+            '<',
+            602,
+            "m559-469l-415 156 415 155 0 89-516-204 0-81 516-204 0 89z",
+        ),
+        (
+            '=',
+            602,
+            "m43-256l516 0 0 84-516 0 0-84zm0-198l516 0 0 83-516 0 0-83z",
+        ),
+        (
+            '>',
+            602,
+            "m43-469l0-89 516 204 0 81-516 204 0-89 415-155-415-156z",
+        ),
+        (
+            '?',
+            602,
+            "m334-196l-93 0 0-75q0-48 15-81 15-34 56-74l44-43q30-29 42-51 11-21 11-45 0-43-32-70-32-27-85-27-38 0-82 17-43 17-91 50l0-92q46-28 93-41 46-14 97-14 91 0 145 47 54 47 54 125 0 37-16 69-17 32-63 77l-43 42q-33 32-42 52-10 21-10 51l0 23 0 60zm-96 72l99 0 0 124-99 0 0-124z",
+        ),
+        (
+            '@',
+            602,
+            "m507-266q0-63-31-101-32-38-84-38-52 0-84 38-31 38-31 101 0 63 31 101 32 38 84 38 52 0 84-38 31-38 31-101zm68 200l-70 0 0-54q-18 31-50 48-32 16-72 16-79 0-131-59-51-59-51-151 0-92 51-151 52-59 131-59 39 0 71 17 33 17 51 47l0-31q0-76-43-123-43-46-114-46-120 0-191 94-71 93-71 253 0 161 80 256 81 95 215 95 26 0 53-5 26-5 54-15l23 66q-31 12-61 18-30 6-58 6-174 0-276-114-103-114-103-307 0-191 92-303 92-113 246-113 102 0 163 65 61 65 61 174l0 376z",
+        ),
+        (
+            'A',
+            602,
+            "m301-642l-104 373 208 0-104-373zm-60-87l120 0 223 729-102 0-54-190-255 0-53 190-102 0 223-729z",
+        ),
+        (
+            'B',
+            602,
+            "m180-348l0 267 117 0q86 0 123-30 36-30 36-99 0-71-38-104-39-34-121-34l-117 0zm0-300l0 220 115 0q71 0 103-28 32-27 32-88 0-55-32-80-31-24-103-24l-115 0zm-99-81l216 0q112 0 173 49 60 48 60 136 0 67-32 106-32 38-96 48 72 11 112 62 41 50 41 128 0 99-65 150-65 50-193 50l-216 0 0-729z",
+        ),
+        (
+            'C',
+            602,
+            "m524-26q-38 20-78 30-39 10-83 10-140 0-217-99-78-99-78-279 0-178 78-278 78-100 217-100 44 0 83 10 40 10 78 30l0 101q-36-30-78-45-41-16-83-16-96 0-144 74-48 74-48 224 0 150 48 224 48 74 144 74 43 0 84-16 41-15 77-45l0 101z",
+        ),
+        (
+            'D',
+            602,
+            "m213-81q124 0 173-61 50-62 50-222 0-161-49-222-49-62-174-62l-47 0 0 567 47 0zm2-648q167 0 246 89 79 89 79 276 0 187-79 276-79 88-246 88l-148 0 0-729 148 0z",
+        ),
+        (
+            'E',
+            602,
+            "m96-729l433 0 0 83-334 0 0 216 319 0 0 83-319 0 0 264 343 0 0 83-442 0 0-729z",
+        ),
+        (
+            'F',
+            602,
+            "m114-729l429 0 0 83-330 0 0 215 299 0 0 83-299 0 0 348-99 0 0-729z",
+        ),
+        (
+            'G',
+            602,
+            "m539-60q-39 37-89 56-50 18-107 18-139 0-216-100-77-99-77-278 0-178 78-278 78-100 217-100 46 0 88 13 42 13 81 39l0 101q-39-37-80-55-42-18-89-18-96 0-144 74-48 75-48 224 0 152 47 225 46 73 143 73 32 0 57-8 25-7 45-23l0-196-106 0 0-81 200 0 0 314z",
+        ),
+        (
+            'H',
+            602,
+            "m67-729l99 0 0 299 270 0 0-299 99 0 0 729-99 0 0-347-270 0 0 347-99 0 0-729z",
+        ),
+        (
+            'I',
+            602,
+            "m98-729l405 0 0 83-153 0 0 563 153 0 0 83-405 0 0-83 153 0 0-563-153 0 0-83z",
+        ),
+        (
+            'J',
+            602,
+            "m53-30l0-115q45 40 92 59 47 20 98 20 70 0 97-36 28-37 28-136l0-408-186 0 0-83 285 0 0 491q0 138-51 195-52 57-173 57-47 0-93-10-46-11-97-34z",
+        ),
+        (
+            'K',
+            602,
+            "m67-729l99 0 0 324 308-324 116 0-284 298 292 431-119 0-238-365-75 80 0 285-99 0 0-729z",
+        ),
+        ('L', 602, "m105-729l99 0 0 646 352 0 0 83-451 0 0-729z"),
+        (
+            'M',
+            602,
+            "m42-729l132 0 126 371 127-371 132 0 0 729-91 0 0-644-130 384-75 0-130-384 0 644-91 0 0-729z",
+        ),
+        (
+            'N',
+            602,
+            "m68-729l125 0 246 600 0-600 95 0 0 729-125 0-246-600 0 600-95 0 0-729z",
+        ),
+        (
+            'O',
+            602,
+            "m442-364q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230zm103 0q0 191-61 284-60 94-183 94-124 0-184-93-60-93-60-285 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284z",
+        ),
+        (
+            'P',
+            602,
+            "m195-648l0 274 114 0q68 0 107-36 38-36 38-101 0-65-38-101-38-36-107-36l-114 0zm-99-81l213 0q122 0 185 55 63 56 63 163 0 108-63 163-62 55-185 55l-114 0 0 293-99 0 0-729z",
+        ),
+        (
+            'Q',
+            602,
+            "m320 13q-4 0-10 1-6 0-10 0-122 0-182-94-61-93-61-284 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284 0 144-33 232-34 87-102 122l98 93-74 49-114-119zm122-377q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230z",
+        ),
+        (
+            'R',
+            602,
+            "m371-344q38 10 65 37 27 27 67 108l99 199-106 0-87-184q-37-79-67-102-30-22-79-22l-94 0 0 308-99 0 0-729 203 0q120 0 184 54 64 54 64 157 0 72-39 118-40 46-111 56zm-202-304l0 259 108 0q71 0 105-32 35-32 35-97 0-63-37-96-37-34-107-34l-104 0z",
+        ),
+        (
+            'S',
+            602,
+            "m494-704l0 100q-45-29-90-44-45-14-91-14-70 0-111 32-40 33-40 88 0 48 26 74 27 25 100 42l52 12q102 24 149 75 47 51 47 140 0 104-64 159-65 54-188 54-51 0-103-11-52-11-104-33l0-105q56 36 106 53 50 16 101 16 75 0 116-34 42-33 42-93 0-55-28-83-29-29-100-45l-53-12q-101-23-147-69-46-47-46-125 0-98 66-157 65-58 174-58 42 0 88 10 47 9 98 28z",
+        ),
+        (
+            'T',
+            602,
+            "m23-729l556 0 0 83-228 0 0 646-99 0 0-646-229 0 0-83z",
+        ),
+        (
+            'U',
+            602,
+            "m72-280l0-449 99 0 0 494q0 53 3 76 3 23 10 35 16 29 46 44 29 14 71 14 42 0 71-14 30-15 46-44 7-12 10-34 3-23 3-76l0-495 99 0 0 449q0 112-14 159-14 47-48 78-32 29-74 43-41 14-93 14-51 0-93-14-41-14-74-43-34-30-48-78-14-48-14-159z",
+        ),
+        (
+            'V',
+            602,
+            "m301-83l171-646 102 0-213 729-120 0-213-729 102 0 171 646z",
+        ),
+        (
+            'W',
+            602,
+            "m0-729l96 0 70 592 83-392 103 0 84 393 70-593 96 0-109 729-93 0-99-433-99 433-93 0-109-729z",
+        ),
+        (
+            'X',
+            602,
+            "m42-729l106 0 160 275 163-275 106 0-218 343 234 386-106 0-179-314-193 314-106 0 244-386-211-343z",
+        ),
+        (
+            'Y',
+            602,
+            "m18-729l105 0 178 322 177-322 106 0-234 402 0 327-99 0 0-327-233-402z",
+        ),
+        (
+            'Z',
+            602,
+            "m87-729l473 0 0 75-381 571 392 0 0 83-495 0 0-75 371-571-360 0 0-83z",
+        ),
+        (
+            '[',
+            602,
+            "m226-760l207 0 0 70-117 0 0 752 117 0 0 70-207 0 0-892z",
+        ),
+        ('\\', 602, "m143-729l384 822-93 0-384-822 93 0z"),
+        (
+            ']',
+            602,
+            "m376-760l0 892-207 0 0-70 117 0 0-752-117 0 0-70 207 0z",
+        ),
+        (
+            '^',
+            602,
+            "m344-729l223 272-87 0-179-193-179 193-87 0 223-272 86 0z",
+        ),
+        ('_', 602, "m602 197l0 39-602 0 0-39 602 0z"),
+        ('`', 602, "m233-800l137 184-75 0-159-184 97 0z"),
+        (
+            'a',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108z",
+        ),
+        (
+            'b',
+            602,
+            "m448-273q0-104-33-157-33-54-98-54-66 0-99 54-34 53-34 157 0 104 34 157 33 54 99 54 65 0 98-53 33-53 33-158zm-264-204q22-40 60-61 37-22 87-22 99 0 155 76 57 76 57 209 0 135-57 212-57 77-156 77-49 0-86-21-37-21-60-62l0 69-90 0 0-760 90 0 0 283z",
+        ),
+        (
+            'c',
+            602,
+            "m518-28q-36 21-74 32-39 10-79 10-127 0-199-76-71-76-71-211 0-135 71-211 72-76 199-76 40 0 77 10 38 10 76 32l0 94q-36-32-72-46-35-14-81-14-84 0-129 54-46 55-46 157 0 101 46 156 45 55 129 55 47 0 84-14 37-15 69-45l0 93z",
+        ),
+        (
+            'd',
+            602,
+            "m419-477l0-283 90 0 0 760-90 0 0-69q-23 41-60 62-37 21-86 21-99 0-156-77-57-77-57-212 0-133 57-209 57-76 156-76 49 0 87 21 37 21 59 62zm-264 204q0 105 33 158 33 53 98 53 65 0 99-54 34-53 34-157 0-104-34-157-34-54-99-54-65 0-98 54-33 53-33 157z",
+        ),
+        (
+            'e',
+            602,
+            "m543-296l0 44-389 0 0 3q0 89 46 138 47 49 132 49 43 0 90-14 47-13 100-41l0 89q-51 21-99 32-48 10-92 10-127 0-199-76-72-77-72-211 0-131 70-209 71-78 188-78 104 0 164 71 61 71 61 193zm-90-26q-2-79-37-120-36-42-102-42-65 0-107 43-42 43-50 119l296 0z",
+        ),
+        (
+            'f',
+            602,
+            "m519-760l0 75-102 0q-48 0-67 19-19 20-19 71l0 48 188 0 0 70-188 0 0 477-90 0 0-477-146 0 0-70 146 0 0-38q0-90 41-133 42-42 129-42l108 0z",
+        ),
+        (
+            'g',
+            602,
+            "m419-278q0-101-33-154-33-52-96-52-66 0-100 52-35 53-35 154 0 101 35 154 35 53 101 53 62 0 95-53 33-53 33-154zm90 243q0 123-58 187-58 63-171 63-37 0-78-7-40-7-81-20l0-89q48 23 87 33 39 11 72 11 73 0 106-39 33-40 33-126l0-4 0-61q-22 46-59 69-37 22-90 22-96 0-153-76-57-77-57-206 0-129 57-206 57-76 153-76 53 0 90 21 36 21 59 65l0-71 90 0 0 510z",
+        ),
+        (
+            'h',
+            602,
+            "m513-339l0 339-90 0 0-339q0-74-26-109-26-34-81-34-63 0-97 44-34 45-34 129l0 309-90 0 0-760 90 0 0 295q24-47 65-71 41-24 97-24 84 0 125 55 41 55 41 166z",
+        ),
+        (
+            'i',
+            602,
+            "m125-547l230 0 0 477 178 0 0 70-446 0 0-70 178 0 0-407-140 0 0-70zm140-213l90 0 0 114-90 0 0-114z",
+        ),
+        (
+            'j',
+            602,
+            "m293 10l0-487-155 0 0-70 245 0 0 557q0 95-44 146-44 52-124 52l-124 0 0-76 114 0q44 0 66-30 22-31 22-92zm0-770l90 0 0 114-90 0 0-114z",
+        ),
+        (
+            'k',
+            602,
+            "m115-760l93 0 0 440 236-227 109 0-215 206 249 341-110 0-202-282-67 63 0 219-93 0 0-760z",
+        ),
+        (
+            'l',
+            602,
+            "m312-198q0 60 22 91 22 31 66 31l105 0 0 76-114 0q-80 0-125-52-44-52-44-146l0-497-144 0 0-70 234 0 0 567z",
+        ),
+        (
+            'm',
+            602,
+            "m330-491q17-35 42-52 26-17 62-17 66 0 93 51 27 51 27 192l0 317-82 0 0-313q0-116-13-144-13-28-47-28-39 0-54 30-14 30-14 142l0 313-82 0 0-313q0-117-14-145-14-27-50-27-35 0-49 30-14 30-14 142l0 313-82 0 0-547 82 0 0 47q16-29 40-44 24-16 55-16 37 0 62 17 24 17 38 52z",
+        ),
+        (
+            'n',
+            602,
+            "m513-339l0 339-90 0 0-339q0-74-26-109-26-34-81-34-63 0-97 44-34 45-34 129l0 309-90 0 0-547 90 0 0 82q24-47 65-71 41-24 97-24 84 0 125 55 41 55 41 166z",
+        ),
+        (
+            'o',
+            602,
+            "m301-484q-69 0-104 54-35 53-35 157 0 104 35 157 35 54 104 54 69 0 104-54 35-53 35-157 0-104-35-157-35-54-104-54zm0-76q114 0 174 74 60 73 60 213 0 140-60 213-60 74-174 74-114 0-174-74-60-73-60-213 0-140 60-213 60-74 174-74z",
+        ),
+        (
+            'p',
+            602,
+            "m183-69l0 277-90 0 0-755 90 0 0 70q23-41 60-62 37-21 86-21 99 0 155 76 57 77 57 213 0 133-57 209-56 76-155 76-50 0-87-21-37-21-59-62zm264-204q0-104-33-157-33-54-98-54-66 0-100 54-33 53-33 157 0 104 33 157 34 54 100 54 65 0 98-53 33-53 33-158z",
+        ),
+        (
+            'q',
+            602,
+            "m162-271q0 104 33 157 33 54 98 54 65 0 99-54 33-53 33-157 0-104-33-157-34-54-99-54-65 0-98 54-33 53-33 157zm263 203q-22 41-59 62-38 22-87 22-98 0-155-76-57-76-57-209 0-136 57-213 56-76 155-76 49 0 86 21 37 21 60 62l0-70 90 0 0 755-90 0 0-278z",
+        ),
+        (
+            'r',
+            602,
+            "m564-434q-29-23-58-33-30-10-66-10-84 0-128 53-45 52-45 152l0 272-90 0 0-547 90 0 0 107q23-58 69-89 47-31 111-31 33 0 62 8 29 9 55 26l0 92z",
+        ),
+        (
+            's',
+            602,
+            "m475-528l0 88q-38-22-77-34-40-11-80-11-61 0-91 20-30 20-30 60 0 37 23 55 22 18 111 35l36 7q67 13 101 51 35 38 35 99 0 81-57 126-58 46-161 46-40 0-85-8-44-9-96-26l0-93q50 26 96 39 46 13 87 13 60 0 93-24 32-24 32-68 0-62-120-86l-3-1-34-7q-78-15-113-51-36-36-36-98 0-79 53-122 53-42 152-42 44 0 85 8 40 8 79 24z",
+        ),
+        (
+            't',
+            602,
+            "m300-702l0 155 204 0 0 70-204 0 0 297q0 60 23 84 23 24 80 24l101 0 0 72-110 0q-101 0-142-40-42-41-42-140l0-297-146 0 0-70 146 0 0-155 90 0z",
+        ),
+        (
+            'u',
+            602,
+            "m95-207l0-339 90 0 0 339q0 74 26 109 26 34 81 34 63 0 97-44 34-45 34-129l0-309 90 0 0 546-90 0 0-82q-24 47-65 72-42 24-97 24-84 0-125-55-41-55-41-166z",
+        ),
+        (
+            'v',
+            602,
+            "m49-547l93 0 159 459 159-459 93 0-194 547-116 0-194-547z",
+        ),
+        (
+            'w',
+            602,
+            "m0-547l89 0 95 442 78-282 77 0 79 282 95-442 89 0-128 547-86 0-87-300-87 300-86 0-128-547z",
+        ),
+        (
+            'x',
+            602,
+            "m546-547l-196 262 215 285-104 0-160-219-160 219-104 0 215-285-196-262 100 0 145 198 144-198 101 0z",
+        ),
+        (
+            'y',
+            602,
+            "m419-176q-23 57-57 151-49 129-65 157-23 38-57 57-33 19-78 19l-72 0 0-75 53 0q40 0 63-23 22-23 56-119l-211-538 95 0 162 428 160-428 95 0-144 371z",
+        ),
+        (
+            'z',
+            602,
+            "m111-548l397 0 0 82-314 393 314 0 0 73-409 0 0-83 314-393-302 0 0-72z",
+        ),
+        (
+            '{',
+            602,
+            "m494 93l0 70-31 0q-122 0-163-36-41-36-41-144l0-117q0-74-26-102-26-28-95-28l-30 0 0-70 30 0q69 0 95-28 26-28 26-101l0-117q0-108 41-144 41-36 163-36l31 0 0 70-34 0q-68 0-89 22-21 21-21 90l0 121q0 77-22 111-22 35-76 47 54 13 76 48 22 35 22 111l0 121q0 70 21 91 21 21 89 21l34 0z",
+        ),
+        ('|', 602, "m343-764l0 1000-84 0 0-1000 84 0z"),
+        (
+            '}',
+            602,
+            "m108 93l33 0q69 0 90-21 21-22 21-91l0-121q0-76 22-111 22-35 76-48-54-12-76-47-22-34-22-111l0-121q0-69-21-90-21-22-90-22l-33 0 0-70 30 0q122 0 163 36 41 36 41 144l0 117q0 73 26 101 26 28 95 28l31 0 0 70-31 0q-69 0-95 28-26 28-26 102l0 117q0 108-41 144-41 36-163 36l-30 0 0-70z",
+        ),
+        (
+            '~',
+            602,
+            "m559-381l0 85q-37 29-71 42-35 14-74 14-44 0-99-25-11-5-16-7-38-16-63-22-25-6-50-6-39 0-73 14-34 15-70 46l0-85q38-29 73-43 36-13 77-13 26 0 51 5 24 6 60 21 6 2 16 7 56 27 102 27 34 0 68-15 33-15 69-45z",
+        ),
+        ('\u{a0}', 602, ""),
+        (
+            '\u{a1}',
+            602,
+            "m252-423l0-124 99 0 0 124-99 0zm0 605l0-320 10-174 79 0 10 174 0 320-99 0z",
+        ),
+        (
+            '\u{a2}',
+            602,
+            "m518-526l0 84q-33-19-64-30-32-11-63-13l0 425q31-2 63-13 32-11 64-31l0 84q-36 15-68 24-31 8-59 9l0 140-50 0 0-140q-110-11-173-87-64-76-64-199 0-122 64-199 64-78 173-87l0-140 50 0 0 140q28 1 59 9 32 9 68 24zm-177 464l0-422q-65 6-104 64-39 57-39 147 0 90 39 147 39 58 104 64z",
+        ),
+        (
+            '\u{a3}',
+            602,
+            "m533-714l0 90q-30-22-61-32-31-11-64-11-65 0-96 43-31 44-31 138l0 106 181 0 0 70-181 0 0 227 262 0 0 83-475 0 0-83 115 0 0-227-97 0 0-70 97 0 0-116q0-124 53-185 54-61 163-61 32 0 65 7 34 7 69 21z",
+        ),
+        (
+            '\u{a4}',
+            602,
+            "m424-314q0-44-30-74-31-30-75-30-44 0-75 30-30 30-30 74 0 45 30 76 31 30 75 30 44 0 75-31 30-31 30-75zm-12-137l81-81 44 45-81 81q15 24 22 47 7 23 7 46 0 24-7 47-8 22-23 44l82 81-46 46-81-82q-22 15-44 23-23 7-47 7-24 0-47-7-23-7-47-21l-80 80-45-44 81-81q-15-25-22-48-7-23-7-45 0-24 8-47 7-23 22-45l-82-81 46-46 81 82q23-15 45-22 22-8 47-8 22 0 45 7 23 7 48 22z",
+        ),
+        (
+            '\u{a5}',
+            602,
+            "m18-729l105 0 178 322 177-322 106 0-161 274 123 0 0 54-154 0-42 74 0 17 196 0 0 54-196 0 0 256-99 0 0-256-195 0 0-54 195 0 0-17-44-74-151 0 0-54 119 0-157-274z",
+        ),
+        (
+            '\u{a6}',
+            602,
+            "m343-699l0 370-84 0 0-370 84 0zm0 500l0 370-84 0 0-370 84 0z",
+        ),
+        (
+            '\u{a7}',
+            602,
+            "m457-714l0 80q-40-19-75-28-35-10-63-10-48 0-74 20-26 19-26 54 0 44 100 100 13 7 20 11 103 58 135 95 32 38 32 88 0 44-22 78-23 34-68 60 30 25 44 52 14 27 14 58 0 69-50 110-49 41-134 41-36 0-74-7-37-7-80-21l0-80q43 19 79 29 36 9 64 9 50 0 77-21 28-20 28-57 0-50-108-110l-12-7q-104-59-136-96-31-37-31-87 0-44 23-79 23-35 67-59-29-22-43-49-15-27-15-61 0-64 49-103 49-38 130-38 35 0 72 7 38 7 77 21zm-220 257q-30 22-45 44-15 22-15 45 0 37 35 70 34 33 154 98 30-22 45-44 15-22 15-45 0-37-35-71-35-33-154-97z",
+        ),
+        (
+            '\u{a8}',
+            602,
+            "m156-758l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{a9}',
+            602,
+            "m422-523l0 53q-28-14-56-20-27-7-55-7-57 0-90 37-33 36-33 98 0 65 34 100 34 34 98 34 27 0 52-6 25-6 50-19l0 51q-26 11-54 16-27 6-57 6-88 0-139-49-50-49-50-133 0-84 51-134 51-49 138-49 30 0 57 5 28 6 54 17zm-121-90q-52 0-96 18-44 18-81 55-37 37-56 82-18 44-18 96 0 51 18 96 19 44 56 81 37 37 82 55 44 19 95 19 52 0 96-19 44-18 81-55 37-37 56-81 18-45 18-96 0-52-18-96-19-45-56-82-37-37-81-55-44-18-96-18zm0-50q62 0 115 22 53 22 97 66 44 44 67 98 22 54 22 115 0 61-22 114-22 54-67 98-44 45-97 67-54 22-115 22-61 0-115-22-53-22-97-67-45-44-67-98-22-53-22-114 0-61 22-115 23-54 67-98 44-44 97-66 53-22 115-22z",
+        ),
+        (
+            '\u{aa}',
+            602,
+            "m334-539q-70 0-100 17-31 16-31 54 0 31 21 50 22 19 57 19 51 0 83-35 32-35 32-91l0-14-62 0zm132-30l0 219-70 0 0-55q-24 32-56 48-32 16-72 16-63 0-100-33-36-33-36-90 0-63 46-97 45-34 129-34l89 0 0-2q0-44-29-67-29-22-86-22-25 0-55 8-31 9-64 25l0-62q33-14 66-21 33-6 64-6 90 0 132 42 42 42 42 131zm-323 280l326 0 0 60-326 0 0-60z",
+        ),
+        (
+            '\u{ab}',
+            602,
+            "m287-517l0 93-147 131 147 131 0 93-229-204 0-40 229-204zm222 0l0 93-147 131 147 131 0 93-229-204 0-40 229-204z",
+        ),
+        ('\u{ac}', 602, "m43-421l516 0 0 240-82 0 0-156-434 0 0-84z"),
+        ('\u{ad}', 602, "m174-314l254 0 0 80-254 0 0-80z"),
+        (
+            '\u{ae}',
+            602,
+            "m293-537q72 0 107 24 35 24 35 73 0 35-21 58-22 22-61 29 9 2 23 19 15 16 33 45l56 91-70 0-52-85q-24-39-39-49-15-11-37-11l-27 0 0 145-63 0 0-339 116 0zm-3 41l-50 0 0 113 50 0q44 0 62-13 18-13 18-44 0-30-18-43-18-13-62-13zm11-167q62 0 115 22 53 22 97 66 44 44 67 98 22 54 22 115 0 61-22 114-22 54-67 98-44 45-97 67-54 22-115 22-61 0-115-22-53-22-97-67-45-44-67-98-22-53-22-114 0-61 22-115 23-54 67-98 44-44 97-66 53-22 115-22zm0 50q-52 0-96 18-44 18-81 55-37 37-56 82-18 44-18 96 0 51 18 96 19 44 56 81 37 37 82 55 44 19 95 19 52 0 96-19 44-18 81-55 37-37 56-81 18-45 18-96 0-52-18-96-19-45-56-82-37-37-81-55-44-18-96-18z",
+        ),
+        ('\u{af}', 602, "m155-745l292 0 0 72-292 0 0-72z"),
+        (
+            '\u{b0}',
+            602,
+            "m301-742q32 0 61 12 28 12 49 34 22 22 33 50 12 28 12 60 0 65-46 110-45 44-110 44-66 0-110-44-44-44-44-110 0-65 45-110 45-46 110-46zm0 63q-39 0-66 27-27 27-27 66 0 39 26 66 27 26 66 26 39 0 66-27 28-27 28-65 0-39-28-66-27-27-65-27z",
+        ),
+        (
+            '\u{b1}',
+            602,
+            "m43-83l516 0 0 83-516 0 0-83zm299-489l0 161 217 0 0 83-217 0 0 162-82 0 0-162-217 0 0-83 217 0 0-161 82 0z",
+        ),
+        (
+            '\u{b2}',
+            602,
+            "m236-382l200 0 0 56-279 0 0-54 113-110q49-48 69-78 20-29 20-54 0-30-25-48-24-19-64-19-24 0-52 9-27 9-58 26l0-61q32-14 62-21 29-6 58-6 69 0 111 32 42 33 42 85 0 28-18 57-18 29-76 86l-103 100z",
+        ),
+        (
+            '\u{b3}',
+            602,
+            "m357-547q46 10 70 37 24 26 24 65 0 61-47 93-46 33-132 33-28 0-56-5-28-5-57-15l0-59q34 13 61 20 28 6 50 6 49 0 78-20 29-20 29-53 0-36-27-55-27-18-80-18l-32 0 0-53 36 0q45 0 69-15 24-16 24-45 0-27-23-43-24-15-66-15-22 0-49 6-27 6-56 17l0-59q34-8 63-13 28-4 52-4 71 0 112 28 41 29 41 78 0 34-21 57-22 23-63 32z",
+        ),
+        ('\u{b4}', 602, "m369-800l97 0-159 184-75 0 137-184z"),
+        (
+            '\u{b5}',
+            602,
+            "m95 209l0-756 90 0 0 340q0 69 29 106 30 37 84 37 62 0 94-42 31-41 31-122l0-319 90 0 0 422q0 29 8 43 8 13 24 13 4 0 11-3 7-2 21-8l0 72q-18 11-35 16-17 6-33 6-30 0-49-19-18-19-24-58-22 39-54 58-33 19-77 19-45 0-76-18-31-19-52-58l0 271-82 0z",
+        ),
+        (
+            '\u{b6}',
+            602,
+            "m284-729l219 0 0 825-69 0 0-765-93 0 0 765-69 0 0-413q-105-8-162-62-58-54-58-144 0-93 64-149 63-57 168-57z",
+        ),
+        ('\u{b7}', 602, "m239-422l123 0 0 149-123 0 0-149z"),
+        (
+            '\u{b8}',
+            602,
+            "m342 0q27 30 40 56 13 26 13 49 0 43-29 65-30 23-87 23-22 0-43-3-22-3-43-9l0-64q16 8 35 12 18 4 41 4 28 0 43-12 15-12 15-34 0-15-11-36-10-21-32-51l58 0z",
+        ),
+        (
+            '\u{b9}',
+            602,
+            "m177-380l100 0 0-298-109 20 0-57 112-19 67 0 0 354 100 0 0 54-270 0 0-54z",
+        ),
+        (
+            '\u{ba}',
+            602,
+            "m301-685q-50 0-78 38-28 38-28 106 0 68 28 105 28 38 78 38 49 0 77-38 29-39 29-105 0-68-28-106-28-38-78-38zm0-57q83 0 133 54 49 55 49 147 0 91-49 145-50 55-133 55-83 0-133-55-49-54-49-145 0-92 49-147 50-54 133-54zm-166 453l330 0 0 60-330 0 0-60z",
+        ),
+        (
+            '\u{bb}',
+            602,
+            "m316-517l229 204 0 40-229 204 0-93 147-131-147-131 0-93zm-222 0l229 204 0 40-229 204 0-93 147-131-147-131 0-93z",
+        ),
+        (
+            '\u{bc}',
+            602,
+            "m398-276l79 0 0 263 57 0 0 54-57 0 0 91-67 0 0-91-189 0 0-59 177-258zm12 61l-134 202 134 0 0-202zm-359-241l100 0 0-298-109 20 0-57 112-19 67 0 0 354 100 0 0 54-270 0 0-54zm-26 210l-12-53 518-128 13 53-519 128z",
+        ),
+        (
+            '\u{bd}',
+            602,
+            "m51-456l100 0 0-298-109 20 0-57 112-19 67 0 0 354 100 0 0 54-270 0 0-54zm-26 210l-12-53 518-128 13 53-519 128zm309 322l200 0 0 56-279 0 0-54 113-110q49-48 69-78 20-29 20-54 0-30-25-48-24-19-64-19-24 0-52 9-27 9-58 26l0-61q32-14 62-21 29-6 58-6 69 0 111 32 42 33 42 85 0 28-18 57-18 29-76 86l-103 100z",
+        ),
+        (
+            '\u{be}',
+            602,
+            "m398-276l79 0 0 263 57 0 0 54-57 0 0 91-67 0 0-91-189 0 0-59 177-258zm12 61l-134 202 134 0 0-202zm-164-408q46 10 70 37 24 26 24 65 0 61-46 93-46 33-133 33-28 0-56-5-28-5-57-15l0-59q34 13 62 20 27 6 49 6 49 0 78-20 29-20 29-53 0-36-27-55-27-18-80-18l-32 0 0-53 36 0q45 0 69-15 24-16 24-45 0-27-24-43-23-15-65-15-21 0-49 6-27 6-56 17l0-59q34-9 62-13 29-4 53-4 71 0 112 28 41 29 41 78 0 34-22 57-21 23-62 32zm-221 377l-12-53 518-128 13 53-519 128z",
+        ),
+        (
+            '\u{bf}',
+            602,
+            "m268-351l93 0 0 75q0 48-15 82-15 33-56 73l-44 43q-30 29-42 50-11 22-11 46 0 44 32 70 32 27 85 27 38 0 82-17 43-16 91-50l0 92q-46 28-93 42-46 13-97 13-91 0-145-47-54-46-54-125 0-37 16-69 17-32 63-77l43-42q33-31 42-51 9-21 9-52 0-1 0-3 1-11 1-20l0-60zm96-72l-99 0 0-124 99 0 0 124z",
+        ),
+        (
+            '\u{c0}',
+            602,
+            "m301-642l-104 373 208 0-104-373zm-60-87l120 0 223 729-102 0-54-190-255 0-53 190-102 0 223-729zm33-198l96 129-75 0-111-129 90 0z",
+        ),
+        (
+            '\u{c1}',
+            602,
+            "m301-642l-104 373 208 0-104-373zm-60-87l120 0 223 729-102 0-54-190-255 0-53 190-102 0 223-729zm87-198l91 0-112 129-75 0 96-129z",
+        ),
+        (
+            '\u{c2}',
+            602,
+            "m301-642l-104 373 208 0-104-373zm-60-87l120 0 223 729-102 0-54-190-255 0-53 190-102 0 223-729zm14-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{c3}',
+            602,
+            "m301-642l-104 373 208 0-104-373zm-60-87l120 0 223 729-102 0-54-190-255 0-53 190-102 0 223-729zm58-106l-28-16q-12-7-19-9-8-3-14-3-17 0-27 12-10 12-10 34l0 3-61 0q0-49 25-78 25-29 67-29 18 0 32 4 15 4 39 17l28 15q11 6 19 9 9 3 16 3 15 0 25-13 10-12 10-32l0-3 61 0q-1 49-26 78-25 29-66 29-16 0-31-4-15-4-40-17z",
+        ),
+        (
+            '\u{c4}',
+            602,
+            "m301-642l-104 373 208 0-104-373zm-60-87l120 0 223 729-102 0-54-190-255 0-53 190-102 0 223-729zm-85-184l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{c5}',
+            602,
+            "m375-794q0-31-21-52-22-22-53-22-31 0-53 21-21 21-21 53 0 31 21 52 22 22 53 22 31 0 53-22 21-21 21-52zm-74 154l-104 371 208 0-104-371zm-74-43q-28-16-44-45-16-30-16-66 0-56 39-95 39-39 95-39 55 0 95 39 39 39 39 95 0 36-15 64-16 29-45 47l209 683-102 0-54-190-255 0-53 190-102 0 209-683z",
+        ),
+        (
+            '\u{c6}',
+            602,
+            "m567-729l0 83-165 0 0 216 150 0 0 83-150 0 0 264 174 0 0 83-265 0 0-187-172 0-49 187-90 0 200-729 367 0zm-308 83l-99 377 151 0 0-377-52 0z",
+        ),
+        (
+            '\u{c7}',
+            602,
+            "m391 0q27 30 40 56 13 26 13 49 0 43-30 65-29 23-86 23-22 0-44-3-21-3-42-9l0-64q16 8 34 12 19 4 42 4 28 0 43-12 15-12 15-34 0-15-11-36-11-21-32-51l58 0zm133-26q-38 20-78 30-39 10-83 10-140 0-217-99-78-99-78-279 0-178 78-278 78-100 217-100 44 0 83 10 40 10 78 30l0 101q-36-30-78-45-41-16-83-16-96 0-144 74-48 74-48 224 0 150 48 224 48 74 144 74 43 0 84-16 41-15 77-45l0 101z",
+        ),
+        (
+            '\u{c8}',
+            602,
+            "m96-729l433 0 0 83-334 0 0 216 319 0 0 83-319 0 0 264 343 0 0 83-442 0 0-729zm187-198l96 129-75 0-111-129 90 0z",
+        ),
+        (
+            '\u{c9}',
+            602,
+            "m96-729l433 0 0 83-334 0 0 216 319 0 0 83-319 0 0 264 343 0 0 83-442 0 0-729zm241-198l91 0-112 129-75 0 96-129z",
+        ),
+        (
+            '\u{ca}',
+            602,
+            "m96-729l433 0 0 83-334 0 0 216 319 0 0 83-319 0 0 264 343 0 0 83-442 0 0-729zm168-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{cb}',
+            602,
+            "m96-729l433 0 0 83-334 0 0 216 319 0 0 83-319 0 0 264 343 0 0 83-442 0 0-729zm69-184l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{cc}',
+            602,
+            "m98-729l405 0 0 83-153 0 0 563 153 0 0 83-405 0 0-83 153 0 0-563-153 0 0-83zm176-198l96 129-75 0-111-129 90 0z",
+        ),
+        (
+            '\u{cd}',
+            602,
+            "m98-729l405 0 0 83-153 0 0 563 153 0 0 83-405 0 0-83 153 0 0-563-153 0 0-83zm230-198l91 0-112 129-75 0 96-129z",
+        ),
+        (
+            '\u{ce}',
+            602,
+            "m98-729l405 0 0 83-153 0 0 563 153 0 0 83-405 0 0-83 153 0 0-563-153 0 0-83zm157-199l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{cf}',
+            602,
+            "m98-729l405 0 0 83-153 0 0 563 153 0 0 83-405 0 0-83 153 0 0-563-153 0 0-83zm58-184l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{d0}',
+            602,
+            "m213-729q167 0 246 89 79 89 79 276 0 187-80 276-79 88-245 88l-148 0 0-346-61 0 0-73 61 0 0-310 148 0zm0 648q124 0 173-61 50-62 50-222 0-161-49-222-49-62-174-62l-47 0 0 229 129 0 0 73-129 0 0 265 47 0z",
+        ),
+        (
+            '\u{d1}',
+            602,
+            "m68-729l125 0 246 600 0-600 95 0 0 729-125 0-246-600 0 600-95 0 0-729zm231-108l-28-16q-12-7-19-9-8-3-14-3-17 0-27 12-10 12-10 34l0 3-61 0q0-49 25-78 25-29 67-29 18 0 32 4 15 4 39 17l28 15q11 6 19 9 9 3 16 3 15 0 25-13 10-12 10-32l0-3 61 0q-1 49-26 78-25 29-66 29-16 0-31-4-15-4-40-17z",
+        ),
+        (
+            '\u{d2}',
+            602,
+            "m442-364q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230zm103 0q0 191-61 284-60 94-183 94-124 0-184-93-60-93-60-285 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284zm-271-563l96 129-75 0-111-129 90 0z",
+        ),
+        (
+            '\u{d3}',
+            602,
+            "m442-364q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230zm103 0q0 191-61 284-60 94-183 94-124 0-184-93-60-93-60-285 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284zm-217-563l91 0-112 129-75 0 96-129z",
+        ),
+        (
+            '\u{d4}',
+            602,
+            "m442-364q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230zm103 0q0 191-61 284-60 94-183 94-124 0-184-93-60-93-60-285 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284zm-290-564l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{d5}',
+            602,
+            "m442-364q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230zm103 0q0 191-61 284-60 94-183 94-124 0-184-93-60-93-60-285 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284zm-246-471l-28-16q-12-7-19-9-8-3-14-3-17 0-27 12-10 12-10 34l0 3-61 0q0-49 25-78 25-29 67-29 18 0 32 4 15 4 39 17l28 15q11 6 19 9 9 3 16 3 15 0 25-13 10-12 10-32l0-3 61 0q-1 49-26 78-25 29-66 29-16 0-31-4-15-4-40-17z",
+        ),
+        (
+            '\u{d6}',
+            602,
+            "m442-364q0-160-33-229-33-69-108-69-75 0-108 69-33 69-33 229 0 160 33 229 33 69 108 69 75 0 108-68 33-69 33-230zm103 0q0 191-61 284-60 94-183 94-124 0-184-93-60-93-60-285 0-191 61-284 60-94 183-94 123 0 183 94 61 93 61 284zm-389-549l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{d7}',
+            602,
+            "m73-143l171-170-171-171 57-57 171 171 171-171 57 57-171 171 170 170-57 58-170-171-170 171-58-58z",
+        ),
+        (
+            '\u{d8}',
+            602,
+            "m181-148q15 38 47 60 32 22 73 22 75 0 108-68 33-69 33-230 0-53-2-86-3-34-8-56l-251 358zm-16-82l246-351q-12-40-40-61-28-20-70-20-77 0-109 71-32 71-32 256 0 39 2 63 1 25 3 42zm339-379q20 40 30 103 11 63 11 142 0 191-61 284-60 94-183 94-59 0-103-20-44-19-74-59l-70 99-50-34 87-123q-16-36-25-100-9-64-9-141 0-191 61-284 60-94 183-94 56 0 98 20 42 20 70 59l68-98 49 36-82 116z",
+        ),
+        (
+            '\u{d9}',
+            602,
+            "m72-280l0-449 99 0 0 494q0 53 3 76 3 23 10 35 16 29 46 44 29 14 71 14 42 0 71-14 30-15 46-44 7-12 10-34 3-23 3-76l0-495 99 0 0 449q0 112-14 159-14 47-48 78-32 29-74 43-41 14-93 14-51 0-93-14-41-14-74-43-34-30-48-78-14-48-14-159zm202-647l96 129-75 0-111-129 90 0z",
+        ),
+        (
+            '\u{da}',
+            602,
+            "m72-280l0-449 99 0 0 494q0 53 3 76 3 23 10 35 16 29 46 44 29 14 71 14 42 0 71-14 30-15 46-44 7-12 10-34 3-23 3-76l0-495 99 0 0 449q0 112-14 159-14 47-48 78-32 29-74 43-41 14-93 14-51 0-93-14-41-14-74-43-34-30-48-78-14-48-14-159zm256-647l91 0-112 129-75 0 96-129z",
+        ),
+        (
+            '\u{db}',
+            602,
+            "m72-280l0-449 99 0 0 494q0 53 3 76 3 23 10 35 16 29 46 44 29 14 71 14 42 0 71-14 30-15 46-44 7-12 10-34 3-23 3-76l0-495 99 0 0 449q0 112-14 159-14 47-48 78-32 29-74 43-41 14-93 14-51 0-93-14-41-14-74-43-34-30-48-78-14-48-14-159zm183-648l92 0 103 130-68 0-81-87-81 87-68 0 103-130z",
+        ),
+        (
+            '\u{dc}',
+            602,
+            "m72-280l0-449 99 0 0 494q0 53 3 76 3 23 10 35 16 29 46 44 29 14 71 14 42 0 71-14 30-15 46-44 7-12 10-34 3-23 3-76l0-495 99 0 0 449q0 112-14 159-14 47-48 78-32 29-74 43-41 14-93 14-51 0-93-14-41-14-74-43-34-30-48-78-14-48-14-159zm84-633l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{dd}',
+            602,
+            "m18-729l105 0 178 322 177-322 106 0-234 402 0 327-99 0 0-327-233-402zm310-198l91 0-112 129-75 0 96-129z",
+        ),
+        (
+            '\u{de}',
+            602,
+            "m197-516l0 256 114 0q77 0 115-32 39-32 39-96 0-64-39-96-38-32-115-32l-114 0zm-99-213l99 0 0 132 124 0q127 0 187 51 61 51 61 158 0 107-61 158-60 51-187 51l-124 0 0 179-99 0 0-729z",
+        ),
+        (
+            '\u{df}',
+            602,
+            "m92-555q0-104 51-155 51-50 157-50 99 0 150 54 52 54 53 160-76 6-117 36-41 31-41 80 0 24 13 42 14 19 47 40l28 18q74 45 101 85 27 40 27 97 0 75-55 118-54 44-150 44-34 0-67-6-33-6-65-18l0-80q37 15 70 22 32 8 61 8 53 0 84-24 31-23 31-63 0-35-16-58-16-23-74-59l-33-19q-45-28-67-60-22-32-22-71 0-63 40-104 39-42 115-59-1-52-31-80-29-28-84-28-59 0-87 32-28 32-28 100l0 553-91 0 0-555z",
+        ),
+        (
+            '\u{e0}',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108zm-284-488l137 184-75 0-159-184 97 0z",
+        ),
+        (
+            '\u{e1}',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108zm-148-488l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{e2}',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108zm-252-488l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{e3}',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108zm-218-357l-28-27q-10-10-18-14-8-5-15-5-19 0-28 18-8 18-9 58l-61 0q1-66 26-102 24-36 69-36 19 0 35 7 15 7 33 23l28 27q10 10 18 14 8 5 15 5 19 0 28-18 8-18 9-58l61 0q-1 66-26 102-24 36-69 36-19 0-35-7-15-7-33-23z",
+        ),
+        (
+            '\u{e4}',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108zm-361-446l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{e5}',
+            602,
+            "m343-275l-30 0q-79 0-119 27-39 28-39 83 0 49 29 77 30 27 83 27 74 0 117-51 42-52 43-143l0-20-84 0zm174-37l0 312-90 0 0-81q-29 49-73 72-43 23-106 23-83 0-133-47-50-47-50-126 0-91 61-139 62-47 180-47l121 0 0-14q-1-66-33-95-33-30-105-30-46 0-93 13-47 13-91 39l0-90q50-19 95-28 46-10 89-10 68 0 116 20 48 20 78 60 18 24 26 60 8 36 8 108zm-82-432q0 56-39 95-39 39-95 39-56 0-95-39-39-39-39-95 0-56 39-95 39-39 95-39 56 0 95 39 39 39 39 95zm-60 0q0-31-21-52-22-22-53-22-31 0-53 22-21 21-21 52 0 32 21 53 22 21 53 21 31 0 53-22 21-21 21-52z",
+        ),
+        (
+            '\u{e6}',
+            602,
+            "m265-216l0-35-24 0q-82 0-111 22-30 22-30 77 0 44 22 68 22 23 62 23 45 0 63-33 18-32 18-122zm241-105l0-25q0-74-19-106-19-33-61-33-43 0-62 33-18 34-18 110l0 21 160 0zm80 70l-240 0q0 7 0 15-1 9-1 26 0 79 25 114 25 35 79 35 39 0 71-13 31-12 55-37l0 84q-27 20-59 31-32 10-67 10-53 0-89-20-37-20-53-58-19 39-51 58-33 20-80 20-76 0-116-42-40-42-40-122 0-83 49-127 49-44 142-44l57 0 0-43q0-58-24-90-24-31-70-31-28 0-60 11-32 10-62 29l0-82q37-17 70-25 32-8 61-8 45 0 75 16 30 15 48 46 16-30 48-46 33-16 76-16 85 0 120 58 36 58 36 207l0 44z",
+        ),
+        (
+            '\u{e7}',
+            602,
+            "m393 0q27 30 40 56 13 26 13 49 0 43-30 65-29 23-86 23-22 0-44-3-21-3-42-9l0-64q16 8 34 12 19 4 42 4 28 0 43-12 15-12 15-34 0-15-11-36-11-21-32-51l58 0zm125-28q-36 21-74 32-39 10-79 10-127 0-199-76-71-76-71-211 0-135 71-211 72-76 199-76 40 0 77 10 38 10 76 32l0 94q-36-32-72-46-35-14-81-14-84 0-129 54-46 55-46 157 0 101 46 156 45 55 129 55 47 0 84-14 37-15 69-45l0 93z",
+        ),
+        (
+            '\u{e8}',
+            602,
+            "m543-296l0 44-389 0 0 3q0 89 46 138 47 49 132 49 43 0 90-14 47-13 100-41l0 89q-51 21-99 32-48 10-92 10-127 0-199-76-72-77-72-211 0-131 70-209 71-78 188-78 104 0 164 71 61 71 61 193zm-90-26q-2-79-37-120-36-42-102-42-65 0-107 43-42 43-50 119l296 0zm-213-478l137 184-75 0-159-184 97 0z",
+        ),
+        (
+            '\u{e9}',
+            602,
+            "m543-296l0 44-389 0 0 3q0 89 46 138 47 49 132 49 43 0 90-14 47-13 100-41l0 89q-51 21-99 32-48 10-92 10-127 0-199-76-72-77-72-211 0-131 70-209 71-78 188-78 104 0 164 71 61 71 61 193zm-90-26q-2-79-37-120-36-42-102-42-65 0-107 43-42 43-50 119l296 0zm-77-478l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{ea}',
+            602,
+            "m543-296l0 44-389 0 0 3q0 89 46 138 47 49 132 49 43 0 90-14 47-13 100-41l0 89q-51 21-99 32-48 10-92 10-127 0-199-76-72-77-72-211 0-131 70-209 71-78 188-78 104 0 164 71 61 71 61 193zm-90-26q-2-79-37-120-36-42-102-42-65 0-107 43-42 43-50 119l296 0zm-181-478l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{eb}',
+            602,
+            "m543-296l0 44-389 0 0 3q0 89 46 138 47 49 132 49 43 0 90-14 47-13 100-41l0 89q-51 21-99 32-48 10-92 10-127 0-199-76-72-77-72-211 0-131 70-209 71-78 188-78 104 0 164 71 61 71 61 193zm-90-26q-2-79-37-120-36-42-102-42-65 0-107 43-42 43-50 119l296 0zm-290-436l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{ec}',
+            602,
+            "m125-547l230 0 0 477 178 0 0 70-446 0 0-70 178 0 0-407-140 0 0-70zm108-253l137 184-75 0-159-184 97 0z",
+        ),
+        (
+            '\u{ed}',
+            602,
+            "m125-547l230 0 0 477 178 0 0 70-446 0 0-70 178 0 0-407-140 0 0-70zm244-253l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{ee}',
+            602,
+            "m125-547l230 0 0 477 178 0 0 70-446 0 0-70 178 0 0-407-140 0 0-70zm140-253l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{ef}',
+            602,
+            "m125-547l230 0 0 477 178 0 0 70-446 0 0-70 178 0 0-407-140 0 0-70zm42-211l100 0 0 99-100 0 0-99zm192 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{f0}',
+            602,
+            "m350-648q96 104 140 196 45 93 45 191 0 131-61 203-61 72-173 72-112 0-173-72-61-72-61-203 0-130 61-202 61-72 171-72 16 0 25 1 9 0 16 1-16-22-34-44-17-22-36-43l-136 45-15-48 116-39-89-98 107 0 62 71 141-46 16 48-122 39zm37 200q-17-7-37-10-20-3-42-3-71 0-108 51-38 52-38 149 0 95 36 147 36 52 103 52 67 0 103-52 36-52 36-147 0-54-14-103-14-50-39-84z",
+        ),
+        (
+            '\u{f1}',
+            602,
+            "m513-339l0 339-90 0 0-339q0-74-26-109-26-34-81-34-63 0-97 44-34 45-34 129l0 309-90 0 0-547 90 0 0 82q24-47 65-71 41-24 97-24 84 0 125 55 41 55 41 166zm-214-330l-28-27q-10-10-18-14-8-5-15-5-19 0-28 18-8 18-9 58l-61 0q1-66 26-102 24-36 69-36 19 0 35 7 15 7 33 23l28 27q10 10 18 14 8 5 15 5 19 0 28-18 8-18 9-58l61 0q-1 66-26 102-24 36-69 36-19 0-35-7-15-7-33-23z",
+        ),
+        (
+            '\u{f2}',
+            602,
+            "m301-484q-69 0-104 54-35 53-35 157 0 104 35 157 35 54 104 54 69 0 104-54 35-53 35-157 0-104-35-157-35-54-104-54zm0-76q114 0 174 74 60 73 60 213 0 140-60 213-60 74-174 74-114 0-174-74-60-73-60-213 0-140 60-213 60-74 174-74zm-68-240l137 184-75 0-159-184 97 0z",
+        ),
+        (
+            '\u{f3}',
+            602,
+            "m301-484q-69 0-104 54-35 53-35 157 0 104 35 157 35 54 104 54 69 0 104-54 35-53 35-157 0-104-35-157-35-54-104-54zm0-76q114 0 174 74 60 73 60 213 0 140-60 213-60 74-174 74-114 0-174-74-60-73-60-213 0-140 60-213 60-74 174-74zm68-240l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{f4}',
+            602,
+            "m301-484q-69 0-104 54-35 53-35 157 0 104 35 157 35 54 104 54 69 0 104-54 35-53 35-157 0-104-35-157-35-54-104-54zm0-76q114 0 174 74 60 73 60 213 0 140-60 213-60 74-174 74-114 0-174-74-60-73-60-213 0-140 60-213 60-74 174-74zm-36-240l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{f5}',
+            602,
+            "m301-484q-69 0-104 54-35 53-35 157 0 104 35 157 35 54 104 54 69 0 104-54 35-53 35-157 0-104-35-157-35-54-104-54zm0-76q114 0 174 74 60 73 60 213 0 140-60 213-60 74-174 74-114 0-174-74-60-73-60-213 0-140 60-213 60-74 174-74zm-2-109l-28-27q-10-10-18-14-8-5-15-5-19 0-28 18-8 18-9 58l-61 0q1-66 26-102 24-36 69-36 19 0 35 7 15 7 33 23l28 27q10 10 18 14 8 5 15 5 19 0 28-18 8-18 9-58l61 0q-1 66-26 102-24 36-69 36-19 0-35-7-15-7-33-23z",
+        ),
+        (
+            '\u{f6}',
+            602,
+            "m301-484q-69 0-104 54-35 53-35 157 0 104 35 157 35 54 104 54 69 0 104-54 35-53 35-157 0-104-35-157-35-54-104-54zm0-76q114 0 174 74 60 73 60 213 0 140-60 213-60 74-174 74-114 0-174-74-60-73-60-213 0-140 60-213 60-74 174-74zm-145-198l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{f7}',
+            602,
+            "m241-193l120 0 0 120-120 0 0-120zm0-361l120 0 0 120-120 0 0-120zm-198 199l516 0 0 83-516 0 0-83z",
+        ),
+        (
+            '\u{f8}',
+            602,
+            "m428-381l-226 273q18 23 42 34 25 12 57 12 69 0 104-54 35-53 35-157 0-25-3-53-3-27-9-55zm-29-57q-17-23-41-34-25-12-57-12-68 0-105 52-36 53-36 151 0 24 4 52 3 28 10 63l225-272zm-293 350q-19-35-29-82-10-48-10-103 0-140 60-213 60-74 174-74 49 0 87 14 39 13 68 40l72-86 45 38-80 95q21 32 31 79 11 47 11 107 0 140-60 213-60 74-174 74-51 0-89-14-38-15-66-44l-78 91-45-37 83-98z",
+        ),
+        (
+            '\u{f9}',
+            602,
+            "m95-207l0-339 90 0 0 339q0 74 26 109 26 34 81 34 63 0 97-44 34-45 34-129l0-309 90 0 0 546-90 0 0-82q-24 47-65 72-42 24-97 24-84 0-125-55-41-55-41-166zm138-593l137 184-75 0-159-184 97 0z",
+        ),
+        (
+            '\u{fa}',
+            602,
+            "m95-207l0-339 90 0 0 339q0 74 26 109 26 34 81 34 63 0 97-44 34-45 34-129l0-309 90 0 0 546-90 0 0-82q-24 47-65 72-42 24-97 24-84 0-125-55-41-55-41-166zm274-593l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{fb}',
+            602,
+            "m95-207l0-339 90 0 0 339q0 74 26 109 26 34 81 34 63 0 97-44 34-45 34-129l0-309 90 0 0 546-90 0 0-82q-24 47-65 72-42 24-97 24-84 0-125-55-41-55-41-166zm170-593l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{fc}',
+            602,
+            "m95-207l0-339 90 0 0 339q0 74 26 109 26 34 81 34 63 0 97-44 34-45 34-129l0-309 90 0 0 546-90 0 0-82q-24 47-65 72-42 24-97 24-84 0-125-55-41-55-41-166zm61-551l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{fd}',
+            602,
+            "m419-176q-23 57-57 151-49 129-65 157-23 38-57 57-33 19-78 19l-72 0 0-75 53 0q40 0 63-23 22-23 56-119l-211-538 95 0 162 428 160-428 95 0-144 371zm-50-624l97 0-159 184-75 0 137-184z",
+        ),
+        (
+            '\u{fe}',
+            602,
+            "m183-69l0 277-90 0 0-973 90 0 0 288q23-41 60-62 37-21 86-21 99 0 155 76 57 77 57 213 0 133-57 209-56 76-155 76-50 0-87-21-37-21-59-62zm264-204q0-104-33-157-33-54-98-54-66 0-100 54-33 53-33 157 0 104 33 157 34 54 100 54 65 0 98-53 33-53 33-158z",
+        ),
+        (
+            '\u{ff}',
+            602,
+            "m419-176q-23 57-57 151-49 129-65 157-23 38-57 57-33 19-78 19l-72 0 0-75 53 0q40 0 63-23 22-23 56-119l-211-538 95 0 162 428 160-428 95 0-144 371zm-263-582l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{152}',
+            602,
+            "m594-83l0 83-295 0q-144 0-204-82-60-81-60-282 0-203 59-284 60-81 205-81l290 0 0 83-175 0 0 216 160 0 0 83-160 0 0 264 180 0zm-306-563q-86 0-120 56-34 56-34 226 0 169 34 225 34 56 120 56l30 0 0-563-30 0z",
+        ),
+        (
+            '\u{153}',
+            602,
+            "m511-321q0-5 1-15 0-9 0-13 0-71-20-104-19-33-61-33-42 0-61 34-19 33-19 110l0 21 160 0zm-329 261q50 0 70-41 20-41 20-158 0-142-20-185-19-42-70-42-50 0-70 41-19 41-19 172 0 131 19 172 20 41 70 41zm409-191l-240 0 0 41q0 80 24 115 24 35 79 35 39 0 71-13 32-13 55-38l0 84q-29 21-60 31-30 10-66 10-48 0-84-16-36-17-59-49-25 33-57 49-31 16-72 16-92 0-134-68-41-67-41-219 0-152 41-219 42-68 134-68 44 0 75 15 31 15 54 47 18-30 50-46 32-16 74-16 86 0 121 58 35 58 35 207l0 44z",
+        ),
+        (
+            '\u{160}',
+            602,
+            "m494-704l0 100q-45-29-90-44-45-14-91-14-70 0-111 32-40 33-40 88 0 48 26 74 27 25 100 42l52 12q102 24 149 75 47 51 47 140 0 104-64 159-65 54-188 54-51 0-103-11-52-11-104-33l0-105q56 36 106 53 50 16 101 16 75 0 116-34 42-33 42-93 0-55-28-83-29-29-100-45l-53-12q-101-23-147-69-46-47-46-125 0-98 66-157 65-58 174-58 42 0 88 10 47 9 98 28zm-239-94l-103-130 68 0 81 87 81-87 68 0-103 130-92 0z",
+        ),
+        (
+            '\u{161}',
+            602,
+            "m475-528l0 88q-38-22-77-34-40-11-80-11-61 0-91 20-30 20-30 60 0 37 23 55 22 18 111 35l36 7q67 13 101 51 35 38 35 99 0 81-57 126-58 46-161 46-40 0-85-8-44-9-96-26l0-93q50 26 96 39 46 13 87 13 60 0 93-24 32-24 32-68 0-62-120-86l-3-1-34-7q-78-15-113-51-36-36-36-98 0-79 53-122 53-42 152-42 44 0 85 8 40 8 79 24zm-210-88l-120-184 68 0 88 120 88-120 68 0-120 184-72 0z",
+        ),
+        (
+            '\u{178}',
+            602,
+            "m18-729l105 0 178 322 177-322 106 0-234 402 0 327-99 0 0-327-233-402zm138-184l99 0 0 99-99 0 0-99zm191 0l99 0 0 99-99 0 0-99z",
+        ),
+        (
+            '\u{17d}',
+            602,
+            "m87-729l473 0 0 75-381 571 392 0 0 83-495 0 0-75 371-571-360 0 0-83zm168-69l-103-130 68 0 81 87 81-87 68 0-103 130-92 0z",
+        ),
+        (
+            '\u{17e}',
+            602,
+            "m111-548l397 0 0 82-314 393 314 0 0 73-409 0 0-83 314-393-302 0 0-72zm154-68l-120-184 68 0 88 120 88-120 68 0-120 184-72 0z",
+        ),
+        (
+            '\u{192}',
+            602,
+            "m519-760l0 75-102 0-1 0q-47 0-66 20-19 19-19 70l0 48 188 0 0 70-188 0 0 487q0 104-39 151-41 47-129 47l-34 0 0-76 24 0q51 0 70-24 18-24 18-98l-1-487-145 0 0-70 145 0 0-38q0-88 42-133 18-19 48-29 34-13 81-13l5 0 1 0 102 0z",
+        ),
+        (
+            '\u{2c6}',
+            602,
+            "m265-800l72 0 120 184-68 0-88-120-88 120-68 0 120-184z",
+        ),
+        (
+            '\u{2dc}',
+            602,
+            "m299-669l-28-27q-10-10-18-14-8-5-15-5-19 0-28 18-8 18-9 58l-61 0q1-66 26-102 24-36 69-36 19 0 35 7 15 7 33 23l28 27q10 10 18 14 8 5 15 5 19 0 28-18 8-18 9-58l61 0q-1 66-26 102-24 36-69 36-19 0-35-7-15-7-33-23z",
+        ),
+        ('\u{2013}', 602, "m0-309l602 0 0 69-602 0 0-69z"),
+        ('\u{2014}', 602, "m0-309l602 0 0 69-602 0 0-69z"),
+        (
+            '\u{2018}',
+            602,
+            "m349-472l-123 0 0-101 96-187 75 0-48 187 0 101z",
+        ),
+        (
+            '\u{2019}',
+            602,
+            "m274-760l123 0 0 101-96 187-75 0 48-187 0-101z",
+        ),
+        (
+            '\u{201a}',
+            602,
+            "m245-148l123 0 0 101-96 187-75 0 48-187 0-101z",
+        ),
+        (
+            '\u{201c}',
+            602,
+            "m451-472l-123 0 0-101 96-187 75 0-48 187 0 101zm-224 0l-124 0 0-101 97-187 75 0-48 187 0 101z",
+        ),
+        (
+            '\u{201d}',
+            602,
+            "m375-760l123 0 0 101-96 187-75 0 48-187 0-101zm-224 0l123 0 0 101-96 187-75 0 48-187 0-101z",
+        ),
+        (
+            '\u{201e}',
+            602,
+            "m375-148l123 0 0 101-96 187-75 0 48-187 0-101zm-224 0l123 0 0 101-96 187-75 0 48-187 0-101z",
+        ),
+        (
+            '\u{2020}',
+            602,
+            "m258-729l86 0 0 205 179 0 0 75-179 0 0 545-86 0 0-545-179 0 0-75 179 0 0-205z",
+        ),
+        (
+            '\u{2021}',
+            602,
+            "m523-109l-179 0 0 205-86 0 0-205-179 0 0-75 179 0 0-265-179 0 0-75 179 0 0-205 86 0 0 205 179 0 0 75-179 0 0 265 179 0 0 75z",
+        ),
+        (
+            '\u{2022}',
+            602,
+            "m156-372q0-61 42-102 42-42 103-42 60 0 103 42 42 42 42 102 0 60-42 102-43 43-104 43-61 0-102-42-42-42-42-103z",
+        ),
+        (
+            '\u{2026}',
+            602,
+            "m39-149l123 0 0 149-123 0 0-149zm400 0l123 0 0 149-123 0 0-149zm-200 0l123 0 0 149-123 0 0-149z",
+        ),
+        (
+            '\u{2030}',
+            602,
+            "m35-277l-18-47 510-203 19 47-511 203zm46 136q0 35 23 59 23 23 57 23 33 0 57-24 24-24 24-58 0-33-24-57-24-24-57-24-34 0-57 24-23 23-23 57zm-59 0q0-59 40-99 41-41 99-41 59 0 99 41 41 40 41 99 0 59-41 100-41 41-99 41-59 0-99-40-40-41-40-101zm37-418q0 34 23 57 24 24 58 24 34 0 58-24 23-23 23-57 0-33-24-57-24-24-57-24-34 0-58 24-23 23-23 57zm-59 0q0-59 40-99 41-41 100-41 59 0 100 41 40 40 40 99 0 59-40 100-41 41-100 41-59 0-100-41-40-41-40-100zm381 418q0 35 23 59 24 23 58 23 34 0 58-23 23-24 23-59 0-33-24-57-24-24-57-24-34 0-58 24-23 23-23 57zm-59 0q0-59 40-99 41-41 100-41 59 0 100 41 40 40 40 99 0 59-40 100-41 41-100 41-59 0-100-41-40-41-40-100z",
+        ),
+        (
+            '\u{2039}',
+            602,
+            "m398-517l0 93-147 131 147 131 0 93-229-204 0-40 229-204z",
+        ),
+        (
+            '\u{203a}',
+            602,
+            "m205-517l229 204 0 40-229 204 0-93 147-131-147-131 0-93z",
+        ),
+        (
+            '\u{20ac}',
+            602,
+            "m103-463q23-138 90-209 67-70 176-70 41 0 77 10 36 9 72 30l0 101q-32-30-71-47-39-16-77-16-71 0-114 51-42 51-54 150l235 0-24 53-216 0q-1 10-1 21 0 11 0 33 0 7 0 16 0 10 1 21l176 0-24 54-147 0q11 99 54 150 42 51 114 51 39 0 77-16 38-17 71-47l0 101q-35 21-71 30-36 10-78 10-110 0-177-70-67-70-89-209l-85 0 24-54 57 0q0-6-1-15-1-22-1-30 0-9 1-32 1-9 1-14l-81 0 24-53 61 0z",
+        ),
+        (
+            '\u{2122}',
+            602,
+            "m214-729l0 46-79 0 0 236-56 0 0-236-79 0 0-46 214 0zm124 0l67 125 61-125 84 0 0 282-56 0 0-235-76 147-27 0-81-147 0 235-55 0 0-282 83 0z",
+        ),
+        (
+            '\u{2190}',
+            602,
+            "m32-254l0-40 142-142 44 44-78 78 430 0 0 80-430 0 78 78-44 44-142-142z",
+        ),
+        (
+            '\u{2191}',
+            602,
+            "m321-538l142 142-44 44-78-78 0 430-80 0 0-430-78 78-44-44 142-142 40 0z",
+        ),
+        (
+            '\u{2192}',
+            602,
+            "m570-254l-142 142-44-44 78-78-430 0 0-80 430 0-78-78 44-44 142 142 0 40z",
+        ),
+        (
+            '\u{2193}',
+            602,
+            "m281 0l-142-142 44-44 78 78 0-430 80 0 0 430 78-78 44 44-142 142-40 0z",
+        ),
+        ('\u{2212}', 602, "m43-355l516 0 0 83-516 0 0-83z"),
+        ('\u{25b2}', 602, "m3 38q0 0 298-596 0 0 298 596 0 0-596 0z"),
+        (
+            '\u{25bc}',
+            602,
+            "m301 38q0 0-298-596 0 0 596 0 0 0-298 596z",
+        ),
+        (
+            '\u{fffd}',
+            602,
+            "m301-887q0 0 261 239 0 0 0 493 0 0-261 239 0 0-261-239 0 0 0-493 0 0 261-239zm46 691l0-83q0-30 9-50 9-20 43-53 0 0 43-42 46-45 63-77 16-32 16-69 0-78-54-125-54-47-145-47-51 0-98 14-46 13-92 41 0 0 0 92 48-33 92-50 43-17 81-17 53 0 85 27 32 27 32 70 0 24-12 45-11 22-41 51 0 0-44 43-41 41-55 73-16 34-16 82 0 0 0 75l93 0zm-96 72l0 124 99 0 0-124-99 0z",
+        ),
+    ],
+};
@@ -0,0 +1,123 @@
+mod font;
+mod font_data;
+mod painter;
+
+use super::recording::{self, RecordedEvent};
+use super::{SCREENSHOT_RESOURCE_WAIT_TIMEOUT, WindowOptions, capture_full_page};
+use crate::app::App;
+use crate::render::Viewport;
+pub(crate) use painter::SoftwarePainter;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Renders headlessly into memory at one device pixel per CSS pixel, so screenshots can be taken
+/// where there is no display server.
+///
+/// A replayed recording starts once the page has loaded. Each input waits for the page to settle
+/// after the one before it and for at least as long as the recording left between them, and is
+/// given at the viewport it was recorded with; the screenshot is taken after the last.
+pub(super) fn run_window<A: App + ?Sized>(
+    options: WindowOptions,
+    app: &mut A,
+) -> Result<(), String> {
+    let mut replay: VecDeque<RecordedEvent> = match &options.replay {
+        Some(path) => recording::read_recording(path)?.into(),
+        None => VecDeque::new(),
+    };
+    let mut viewport = match replay.front() {
+        Some(first) => first.viewport,
+        None => Viewport {
+            width_px: options.initial_width_px.unwrap_or(1024),
+            height_px: options.initial_height_px.unwrap_or(768),
+        },
+    };
+    if viewport.width_px <= 0 || viewport.height_px <= 0 {
+        return Err(format!(
+            "Invalid initial window size: {}x{}",
+            viewport.width_px, viewport.height_px
+        ));
+    }
+    crate::css_media::set_device_pixel_ratio(1.0);
+    let mut painter = SoftwarePainter::new(viewport)?;
+
+    let mut screenshot = options.screenshot;
+    let full_page = options.full_page_screenshot;
+    let mut needs_redraw = true;
+    let mut has_rendered_ready_state = false;
+    let mut resource_wait_started: Option<Instant> = None;
+    // When the last replayed input was given, and when it had been recorded.
+    let mut last_input: Option<(Instant, Duration)> = None;
+
+    loop {
+        let tick = app.tick()?;
+        if tick.needs_redraw {
+            needs_redraw = true;
+        }
+        let ready_for_screenshot = tick.ready_for_screenshot;
+        if !ready_for_screenshot {
+            has_rendered_ready_state = false;
+            resource_wait_started = None;
+        }
+
+        let should_wait_for_resources = tick.pending_resources > 0;
+        let timed_out_waiting_for_resources = resource_wait_started
+            .is_some_and(|started| started.elapsed() >= SCREENSHOT_RESOURCE_WAIT_TIMEOUT);
+        let can_complete = !should_wait_for_resources || timed_out_waiting_for_resources;
+
+        if ready_for_screenshot && !has_rendered_ready_state {
+            needs_redraw = true;
+        } else if ready_for_screenshot && should_wait_for_resources {
+            resource_wait_started.get_or_insert(Instant::now());
+        } else if ready_for_screenshot {
+            resource_wait_started = None;
+        }
+
+        if needs_redraw {
+            painter.ensure_back_buffer(viewport)?;
+            app.render(&mut painter, viewport)?;
+            needs_redraw = false;
+            if ready_for_screenshot {
+                has_rendered_ready_state = true;
+            }
+            continue;
+        }
+
+        if ready_for_screenshot && has_rendered_ready_state && can_complete {
+            if let Some(next) = replay.front() {
+                let due = last_input.is_none_or(|(given, recorded)| {
+                    given.elapsed() >= next.at.saturating_sub(recorded)
+                });
+                if due {
+                    if next.viewport.width_px > 0 && next.viewport.height_px > 0 {
+                        needs_redraw |= next.viewport != viewport;
+                        viewport = next.viewport;
+                    }
+                    let tick = recording::dispatch(app, &next.event, viewport)?;
+                    needs_redraw |= tick.needs_redraw;
+                    last_input = Some((Instant::now(), next.at));
+                    replay.pop_front();
+                    continue;
+                }
+                std::thread::sleep(Duration::from_millis(10));
+                continue;
+            }
+            if let Some(target) = screenshot.take() {
+                let rgb = if full_page {
+                    capture_full_page(app, viewport, |app| {
+                        painter.ensure_back_buffer(viewport)?;
+                        app.render(&mut painter, viewport)?;
+                        painter.capture_back_buffer_rgb()
+                    })?
+                } else {
+                    painter.capture_back_buffer_rgb()?
+                };
+                target.write_png(&rgb)?;
+            }
+            break;
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,611 @@
+use super::font::{self, GlyphMask};
+use crate::geom::{Color, Rect};
+use crate::image::{Argb32Image, RgbImage};
+use crate::render::svg::path;
+use crate::render::svg::raster::Canvas;
+use crate::render::svg::rect_path;
+use crate::render::{
+    FillRule, FontMetricsPx, Painter, PathCommand, Point, StrokeStyle, TextMeasurer, TextStyle,
+    Transform, Viewport,
+};
+use crate::style::FontFamily;
+use std::collections::HashMap;
+
+/// Rounded corners and paths are flattened to within this many pixels.
+const TOLERANCE_PX: f64 = 0.2;
+/// Rasterized glyphs kept between frames; the cache starts over once it is full.
+const MAX_CACHED_GLYPHS: usize = 4096;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+    ch: char,
+    font_family: FontFamily,
+    bold: bool,
+    font_size_px: i32,
+}
+
+/// Paints into memory with the built-in rasterizer and font, needing no display server.
+pub(crate) struct SoftwarePainter {
+    /// The frame, then one layer per open `push_opacity`.
+    layers: Vec<(Canvas, u8)>,
+    glyphs: HashMap<GlyphKey, Option<GlyphMask>>,
+    /// Whether the frame holds the last flushed frame; false while it is new.
+    retained: bool,
+    /// Every open `push_transform` combined; text and images follow its position and scale but
+    /// stay upright.
+    transform: Transform,
+    /// What each open `push_transform` replaced.
+    saved_transforms: Vec<Transform>,
+    /// The area `set_clip` limits painting to.
+    damage_clip: Option<Rect>,
+    /// The pixel bounds of each open `push_clip_rect`, each within the last.
+    clips: Vec<Rect>,
+}
+
+impl SoftwarePainter {
+    pub(crate) fn new(viewport: Viewport) -> Result<Self, String> {
+        let (width, height) = validate_viewport(viewport)?;
+        Ok(Self {
+            layers: vec![(Canvas::new(width, height), 255)],
+            glyphs: HashMap::new(),
+            retained: false,
+            transform: Transform::IDENTITY,
+            saved_transforms: Vec::new(),
+            damage_clip: None,
+            clips: Vec::new(),
+        })
+    }
+
+    pub(crate) fn ensure_back_buffer(&mut self, viewport: Viewport) -> Result<(), String> {
+        let (width, height) = validate_viewport(viewport)?;
+        let frame = &self.layers[0].0;
+        if frame.width() == width as usize && frame.height() == height as usize {
+            return Ok(());
+        }
+        self.layers = vec![(Canvas::new(width, height), 255)];
+        self.retained = false;
+        Ok(())
+    }
+
+    pub(crate) fn capture_back_buffer_rgb(&self) -> Result<RgbImage, String> {
+        let frame = &self.layers[0].0;
+        let mut rgb = Vec::with_capacity(frame.width() * frame.height() * 3);
+        for pixel in frame.pixels().chunks_exact(4) {
+            rgb.extend_from_slice(&[pixel[2], pixel[1], pixel[0]]);
+        }
+        RgbImage::new(frame.width() as u32, frame.height() as u32, rgb)
+    }
+
+    fn target(&mut self) -> &mut Canvas {
+        let top = self.layers.len() - 1;
+        &mut self.layers[top].0
+    }
+
+    /// Clips every layer to the damage area and the innermost pushed clip.
+    fn apply_clip(&mut self) {
+        let clip = match (self.damage_clip, self.clips.last()) {
+            (Some(damage), Some(&clip)) => Some(damage.intersection(clip)),
+            (damage, clip) => clip.copied().or(damage),
+        };
+        for (layer, _) in &mut self.layers {
+            layer.set_clip(clip);
+        }
+    }
+
+    /// The whole pixels covering the rectangle once it is transformed.
+    fn device_bounds(&self, x_px: i32, y_px: i32, width_px: i32, height_px: i32) -> Rect {
+        let (x, y) = (f64::from(x_px), f64::from(y_px));
+        let (right, bottom) = (x + f64::from(width_px), y + f64::from(height_px));
+        let corners = [(x, y), (right, y), (x, bottom), (right, bottom)]
+            .map(|(x, y)| self.transform.apply(Point::new(x, y)));
+        let left = corners.iter().map(|point| point.x).fold(f64::MAX, f64::min);
+        let top = corners.iter().map(|point| point.y).fold(f64::MAX, f64::min);
+        let right = corners.iter().map(|point| point.x).fold(f64::MIN, f64::max);
+        let bottom = corners.iter().map(|point| point.y).fold(f64::MIN, f64::max);
+        let (left, top) = (left.floor() as i32, top.floor() as i32);
+        Rect {
+            x: left,
+            y: top,
+            width: (right.ceil() as i32).saturating_sub(left),
+            height: (bottom.ceil() as i32).saturating_sub(top),
+        }
+    }
+
+    /// How finely curves are flattened, in user units; `None` when the transform collapses
+    /// everything to nothing.
+    fn tolerance(&self) -> Option<f64> {
+        let scale = self.transform.max_scale();
+        (scale.is_finite() && scale > 0.0).then(|| TOLERANCE_PX / scale)
+    }
+
+    fn fill_outlines(&mut self, outlines: Vec<Vec<Point>>, rule: FillRule, color: Color) {
+        let transform = self.transform;
+        let outlines: Vec<Vec<Point>> = if transform == Transform::IDENTITY {
+            outlines
+        } else {
+            outlines
+                .into_iter()
+                .map(|outline| {
+                    outline
+                        .into_iter()
+                        .map(|point| transform.apply(point))
+                        .collect()
+                })
+                .collect()
+        };
+        self.target().fill(&outlines, rule, color, 1.0);
+    }
+}
+
+impl TextMeasurer for SoftwarePainter {
+    fn font_metrics_px(&self, style: TextStyle) -> FontMetricsPx {
+        font::metrics(style)
+    }
+
+    fn text_width_px(&self, text: &str, style: TextStyle) -> Result<i32, String> {
+        Ok(font::text_width_px(text, style))
+    }
+}
+
+impl Painter for SoftwarePainter {
+    fn clear(&mut self) -> Result<(), String> {
+        self.layers.truncate(1);
+        let frame = self.target();
+        let (width, height) = (frame.width() as i32, frame.height() as i32);
+        frame.fill_rect(0, 0, width, height, Color::WHITE);
+        Ok(())
+    }
+
+    fn push_opacity(&mut self, opacity: u8) -> Result<(), String> {
+        let layer = self.target().same_size();
+        self.layers.push((layer, opacity));
+        Ok(())
+    }
+
+    fn pop_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        if self.layers.len() < 2 {
+            return Err("opacity stack underflow".to_owned());
+        }
+        let Some((layer, opacity)) = self.layers.pop() else {
+            return Ok(());
+        };
+        self.target().composite(&layer, f64::from(opacity) / 255.0);
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        color: Color,
+    ) -> Result<(), String> {
+        if self.transform != Transform::IDENTITY {
+            if width_px > 0 && height_px > 0 {
+                let outline = rounded_rect(
+                    f64::from(x_px),
+                    f64::from(y_px),
+                    f64::from(width_px),
+                    f64::from(height_px),
+                    0.0,
+                );
+                self.fill_outlines(vec![outline], FillRule::NonZero, color);
+            }
+            return Ok(());
+        }
+        self.target()
+            .fill_rect(x_px, y_px, width_px, height_px, color);
+        Ok(())
+    }
+
+    fn fill_rounded_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        radius_px: i32,
+        color: Color,
+    ) -> Result<(), String> {
+        if width_px <= 0 || height_px <= 0 {
+            return Ok(());
+        }
+        let radius_px = radius_px.max(0).min(width_px / 2).min(height_px / 2);
+        if radius_px == 0 {
+            return self.fill_rect(x_px, y_px, width_px, height_px, color);
+        }
+        let outline = rounded_rect(
+            f64::from(x_px),
+            f64::from(y_px),
+            f64::from(width_px),
+            f64::from(height_px),
+            f64::from(radius_px),
+        );
+        self.fill_outlines(vec![outline], FillRule::NonZero, color);
+        Ok(())
+    }
+
+    fn stroke_rounded_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        radius_px: i32,
+        border_width_px: i32,
+        color: Color,
+    ) -> Result<(), String> {
+        if width_px <= 0 || height_px <= 0 || border_width_px <= 0 {
+            return Ok(());
+        }
+        // The stroke is centered on the box's edge.
+        let radius = f64::from(radius_px.max(0).min(width_px / 2).min(height_px / 2));
+        let half = f64::from(border_width_px) / 2.0;
+        let (x, y) = (f64::from(x_px), f64::from(y_px));
+        let (width, height) = (f64::from(width_px), f64::from(height_px));
+        let mut outlines = vec![rounded_rect(
+            x - half,
+            y - half,
+            width + 2.0 * half,
+            height + 2.0 * half,
+            if radius > 0.0 { radius + half } else { 0.0 },
+        )];
+        if width > 2.0 * half && height > 2.0 * half {
+            outlines.push(rounded_rect(
+                x + half,
+                y + half,
+                width - 2.0 * half,
+                height - 2.0 * half,
+                (radius - half).max(0.0),
+            ));
+        }
+        self.fill_outlines(outlines, FillRule::EvenOdd, color);
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        text: &str,
+        style: TextStyle,
+    ) -> Result<(), String> {
+        if self.glyphs.len() > MAX_CACHED_GLYPHS {
+            self.glyphs.clear();
+        }
+        let transform = self.transform;
+        let scale = (transform.a * transform.d - transform.b * transform.c)
+            .abs()
+            .sqrt();
+        if !(scale.is_finite() && scale > 0.0) {
+            return Ok(());
+        }
+        let origin = transform.apply(Point::new(f64::from(x_px), f64::from(y_px)));
+        let (origin_x, origin_y) = (origin.x.round() as i32, origin.y.round() as i32);
+        let glyph_style = TextStyle {
+            font_size_px: (f64::from(style.font_size_px) * scale).round() as i32,
+            letter_spacing_px: (f64::from(style.letter_spacing_px) * scale).round() as i32,
+            ..style
+        };
+        let top = self.layers.len() - 1;
+        let target = &mut self.layers[top].0;
+        let glyphs = &mut self.glyphs;
+        let width = font::lay_out(text, glyph_style, |ch, pen| {
+            let key = GlyphKey {
+                ch,
+                font_family: glyph_style.font_family,
+                bold: glyph_style.bold,
+                font_size_px: glyph_style.font_size_px,
+            };
+            let Some(glyph) = glyphs
+                .entry(key)
+                .or_insert_with(|| font::rasterize(ch, glyph_style))
+            else {
+                return;
+            };
+            let glyph_x = origin_x.saturating_add((pen.round()) as i32);
+            target.fill_mask(
+                glyph_x.saturating_add(glyph.left),
+                origin_y.saturating_add(glyph.top),
+                glyph.width,
+                &glyph.coverage,
+                style.color,
+            );
+        });
+        if style.underline {
+            let width_px = (width / scale).round() as i32;
+            self.fill_rect(x_px, y_px.saturating_add(1), width_px, 1, style.color)?;
+        }
+        Ok(())
+    }
+
+    fn draw_image(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+        image: &Argb32Image,
+        opacity: u8,
+    ) -> Result<(), String> {
+        if width_px <= 0 || height_px <= 0 {
+            return Ok(());
+        }
+        let bounds = self.device_bounds(x_px, y_px, width_px, height_px);
+        self.target().draw_image(
+            image,
+            bounds.x,
+            bounds.y,
+            bounds.width,
+            bounds.height,
+            f64::from(opacity) / 255.0,
+        );
+        Ok(())
+    }
+
+    fn push_clip_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+    ) -> Result<(), String> {
+        // A rotated or skewed clip is widened to its bounding box.
+        let mut bounds = self.device_bounds(x_px, y_px, width_px.max(0), height_px.max(0));
+        if let Some(&outer) = self.clips.last() {
+            bounds = bounds.intersection(outer);
+        }
+        self.clips.push(bounds);
+        self.apply_clip();
+        Ok(())
+    }
+
+    fn pop_clip(&mut self) -> Result<(), String> {
+        if self.clips.pop().is_none() {
+            return Err("clip stack underflow".to_owned());
+        }
+        self.apply_clip();
+        Ok(())
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        self.saved_transforms.push(self.transform);
+        self.transform = self.transform.then(transform);
+        Ok(())
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        let Some(transform) = self.saved_transforms.pop() else {
+            return Err("transform stack underflow".to_owned());
+        };
+        self.transform = transform;
+        Ok(())
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        let Some(tolerance) = self.tolerance() else {
+            return Ok(());
+        };
+        let outlines = path::flatten(path, tolerance)
+            .into_iter()
+            .map(|polyline| polyline.points)
+            .collect();
+        self.fill_outlines(outlines, rule, color);
+        Ok(())
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        let Some(tolerance) = self.tolerance() else {
+            return Ok(());
+        };
+        let polylines = path::flatten(path, tolerance);
+        let outlines = path::stroke_polygons(&polylines, style, tolerance);
+        self.fill_outlines(outlines, FillRule::NonZero, color);
+        Ok(())
+    }
+
+    fn retains_frame(&self) -> bool {
+        self.retained
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), String> {
+        self.damage_clip = clip;
+        self.apply_clip();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        self.retained = true;
+        Ok(())
+    }
+}
+
+fn rounded_rect(x: f64, y: f64, width: f64, height: f64, radius: f64) -> Vec<Point> {
+    let commands = rect_path(x, y, width, height, radius, radius);
+    path::flatten(&commands, TOLERANCE_PX)
+        .into_iter()
+        .next()
+        .map(|polyline| polyline.points)
+        .unwrap_or_default()
+}
+
+fn validate_viewport(viewport: Viewport) -> Result<(u32, u32), String> {
+    let (Ok(width), Ok(height)) = (
+        u32::try_from(viewport.width_px),
+        u32::try_from(viewport.height_px),
+    ) else {
+        return Err(format!(
+            "Invalid software viewport: {}x{}",
+            viewport.width_px, viewport.height_px
+        ));
+    };
+    crate::image::check_image_dimensions(width, height).map_err(|err| err.to_string())?;
+    if width == 0 || height == 0 {
+        return Err(format!("Invalid software viewport: {width}x{height}"));
+    }
+    Ok((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::browser::BrowserApp;
+
+    fn pixel(image: &RgbImage, x: usize, y: usize) -> [u8; 3] {
+        let offset = (y * image.width as usize + x) * 3;
+        [
+            image.data[offset],
+            image.data[offset + 1],
+            image.data[offset + 2],
+        ]
+    }
+
+    #[test]
+    fn paints_shapes_translucency_and_text() {
+        let mut painter = SoftwarePainter::new(Viewport {
+            width_px: 80,
+            height_px: 40,
+        })
+        .unwrap();
+        let red = Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        painter.clear().unwrap();
+        painter.fill_rect(0, 0, 10, 10, red).unwrap();
+        painter.push_opacity(128).unwrap();
+        painter.fill_rect(10, 0, 10, 10, red).unwrap();
+        painter.pop_opacity(128).unwrap();
+        painter.fill_rounded_rect(20, 0, 20, 20, 10, red).unwrap();
+        painter
+            .draw_text(0, 36, "Hi", TextStyle::default())
+            .unwrap();
+
+        let image = painter.capture_back_buffer_rgb().unwrap();
+        assert_eq!(pixel(&image, 5, 5), [255, 0, 0]);
+        assert_eq!(pixel(&image, 15, 5), [255, 127, 127]);
+        assert_eq!(pixel(&image, 30, 10), [255, 0, 0]);
+        // Outside the rounded corner.
+        assert_eq!(pixel(&image, 20, 0), [255, 255, 255]);
+        let inked = (0..20).any(|x| (24..36).any(|y| pixel(&image, x, y) == [0, 0, 0]));
+        assert!(inked);
+    }
+
+    #[test]
+    fn clips_and_transforms_rects_and_paths() {
+        let mut painter = SoftwarePainter::new(Viewport {
+            width_px: 40,
+            height_px: 40,
+        })
+        .unwrap();
+        let red = Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        painter.clear().unwrap();
+        painter
+            .push_transform(Transform::translate(10.0, 10.0))
+            .unwrap();
+        painter.push_clip_rect(0, 0, 10, 10).unwrap();
+        painter.fill_rect(-10, -10, 40, 40, red).unwrap();
+        painter.pop_clip().unwrap();
+        let square = [
+            PathCommand::MoveTo(Point::new(20.0, 0.0)),
+            PathCommand::LineTo(Point::new(30.0, 0.0)),
+            PathCommand::LineTo(Point::new(30.0, 10.0)),
+            PathCommand::LineTo(Point::new(20.0, 10.0)),
+            PathCommand::Close,
+        ];
+        painter.fill_path(&square, FillRule::NonZero, red).unwrap();
+        painter.pop_transform().unwrap();
+        assert!(painter.pop_clip().is_err());
+
+        let image = painter.capture_back_buffer_rgb().unwrap();
+        assert_eq!(pixel(&image, 15, 15), [255, 0, 0]);
+        // Outside the clip.
+        assert_eq!(pixel(&image, 5, 5), [255, 255, 255]);
+        assert_eq!(pixel(&image, 25, 15), [255, 255, 255]);
+        assert_eq!(pixel(&image, 35, 15), [255, 0, 0]);
+        assert_eq!(pixel(&image, 35, 25), [255, 255, 255]);
+    }
+
+    #[test]
+    fn renders_a_page_without_a_display_server() {
+        let mut app = BrowserApp::from_html(
+            "test",
+            "<body style='margin:0;background:#00f'><p style='color:#fff'>Hello</p></body>",
+        )
+        .unwrap();
+        let viewport = Viewport {
+            width_px: 200,
+            height_px: 100,
+        };
+        let mut painter = SoftwarePainter::new(viewport).unwrap();
+        app.render(&mut painter, viewport).unwrap();
+
+        let image = painter.capture_back_buffer_rgb().unwrap();
+        assert_eq!(pixel(&image, 199, 99), [0, 0, 255]);
+        let text_pixels = image
+            .data
+            .chunks_exact(3)
+            .filter(|pixel| pixel[0] > 200 && pixel[1] > 200)
+            .count();
+        assert!(text_pixels > 10);
+    }
+
+    #[test]
+    fn repaints_only_what_changed_since_the_last_frame() {
+        let html = "<html><head></head><body style='margin:0'><p>One</p>\
+                    <div style='height:40px;background:#0f0'></div><p>Two</p></body></html>";
+        let viewport = Viewport {
+            width_px: 200,
+            height_px: 150,
+        };
+        let mut app = BrowserApp::from_html("test", html).unwrap();
+        let mut painter = SoftwarePainter::new(viewport).unwrap();
+        app.render(&mut painter, viewport).unwrap();
+        let red = Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        // Outside anything that changes, so only a full repaint would cover it.
+        painter.fill_rect(190, 140, 10, 10, red).unwrap();
+
+        let paragraph = [0, 1, 0];
+        assert!(
+            app.document_mut()
+                .set_text_content(&paragraph, "Changed".to_owned())
+        );
+        app.tick().unwrap();
+        app.render(&mut painter, viewport).unwrap();
+        let partial = painter.capture_back_buffer_rgb().unwrap();
+
+        let mut fresh = SoftwarePainter::new(viewport).unwrap();
+        app.render(&mut fresh, viewport).unwrap();
+        let full = fresh.capture_back_buffer_rgb().unwrap();
+
+        assert_eq!(pixel(&partial, 195, 145), [255, 0, 0]);
+        for y in 0..140 {
+            for x in 0..200 {
+                assert_eq!(pixel(&partial, x, y), pixel(&full, x, y), "at {x},{y}");
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use super::fling::ScrollVelocity;
+use crate::app::ZoomCommand;
+
+/// A finger that moves further than this is dragging, not tapping.
+const TAP_SLOP_PX: f64 = 12.0;
+/// A finger held longer than this before lifting isn't a tap.
+const TAP_MAX_MS: u32 = 500;
+/// Pinching the fingers this much further apart (or closer together) zooms a step.
+const PINCH_STEP_RATIO: f64 = 1.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TouchPhase {
+    Down,
+    Motion,
+    Up,
+    /// The compositor or server took the touches over, as for a system gesture.
+    Cancel,
+}
+
+/// One touch point changing, in window device pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct TouchEvent {
+    pub(super) phase: TouchPhase,
+    pub(super) id: i32,
+    pub(super) x_px: f64,
+    pub(super) y_px: f64,
+    pub(super) time_ms: u32,
+}
+
+/// What a gesture asks of the app, mapped onto what a mouse and keyboard would do.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum TouchAction {
+    /// A click at this point, in window device pixels.
+    Tap {
+        x_px: i32,
+        y_px: i32,
+    },
+    /// Scroll further down the page by this many device pixels, as a touchpad would.
+    Scroll {
+        delta_y_px: i32,
+    },
+    /// The finger lifted mid-drag at this speed, in device pixels per second.
+    Fling {
+        velocity_y_px_per_s: f64,
+    },
+    Zoom(ZoomCommand),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Gesture {
+    Idle,
+    /// One finger is down and hasn't moved far: a tap, until it does.
+    Pressed {
+        x_px: f64,
+        y_px: f64,
+        time_ms: u32,
+    },
+    /// One finger is dragging the page, which has been scrolled up to `anchor_y_px`.
+    Dragging {
+        anchor_y_px: f64,
+    },
+    /// Two fingers are pinching, last zoomed when `distance_px` apart.
+    Pinching {
+        distance_px: f64,
+    },
+    /// The gesture is over, though fingers are still down.
+    Finished,
+}
+
+/// Turns touch points into taps, drags and pinches.
+#[derive(Debug)]
+pub(super) struct TouchGestures {
+    /// `(id, x_px, y_px)` for each finger down.
+    points: Vec<(i32, f64, f64)>,
+    gesture: Gesture,
+    velocity: ScrollVelocity,
+}
+
+impl Default for TouchGestures {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            gesture: Gesture::Idle,
+            velocity: ScrollVelocity::default(),
+        }
+    }
+}
+
+impl TouchGestures {
+    pub(super) fn event(&mut self, event: TouchEvent) -> Option<TouchAction> {
+        match event.phase {
+            TouchPhase::Down => {
+                self.points.retain(|&(id, _, _)| id != event.id);
+                self.points.push((event.id, event.x_px, event.y_px));
+                self.gesture = match self.points.len() {
+                    1 => Gesture::Pressed {
+                        x_px: event.x_px,
+                        y_px: event.y_px,
+                        time_ms: event.time_ms,
+                    },
+                    2 => Gesture::Pinching {
+                        distance_px: self.spread_px(),
+                    },
+                    _ => Gesture::Finished,
+                };
+                None
+            }
+            TouchPhase::Motion => {
+                let point = self.points.iter_mut().find(|(id, _, _)| *id == event.id)?;
+                let previous_y_px = point.2;
+                *point = (event.id, event.x_px, event.y_px);
+                self.motion(event, previous_y_px)
+            }
+            TouchPhase::Up => {
+                let had_point = self.points.iter().any(|&(id, _, _)| id == event.id);
+                self.points.retain(|&(id, _, _)| id != event.id);
+                if !had_point {
+                    return None;
+                }
+                let gesture = std::mem::replace(
+                    &mut self.gesture,
+                    if self.points.is_empty() {
+                        Gesture::Idle
+                    } else {
+                        Gesture::Finished
+                    },
+                );
+                match gesture {
+                    Gesture::Pressed {
+                        x_px,
+                        y_px,
+                        time_ms,
+                    } if event.time_ms.wrapping_sub(time_ms) <= TAP_MAX_MS => {
+                        Some(TouchAction::Tap {
+                            x_px: x_px.floor() as i32,
+                            y_px: y_px.floor() as i32,
+                        })
+                    }
+                    Gesture::Dragging { .. } => {
+                        self.velocity
+                            .take_velocity(event.time_ms)
+                            .map(|velocity_y_px_per_s| TouchAction::Fling {
+                                velocity_y_px_per_s,
+                            })
+                    }
+                    _ => None,
+                }
+            }
+            TouchPhase::Cancel => {
+                self.points.clear();
+                self.gesture = Gesture::Idle;
+                self.velocity = ScrollVelocity::default();
+                None
+            }
+        }
+    }
+
+    fn motion(&mut self, event: TouchEvent, previous_y_px: f64) -> Option<TouchAction> {
+        match self.gesture {
+            Gesture::Pressed { x_px, y_px, .. } => {
+                if (event.x_px - x_px).hypot(event.y_px - y_px) <= TAP_SLOP_PX {
+                    return None;
+                }
+                self.velocity = ScrollVelocity::default();
+                self.gesture = Gesture::Dragging { anchor_y_px: y_px };
+                self.drag(event, previous_y_px)
+            }
+            Gesture::Dragging { .. } => self.drag(event, previous_y_px),
+            Gesture::Pinching { distance_px } => {
+                let spread_px = self.spread_px();
+                let command = if spread_px >= distance_px * PINCH_STEP_RATIO {
+                    ZoomCommand::In
+                } else if spread_px * PINCH_STEP_RATIO <= distance_px {
+                    ZoomCommand::Out
+                } else {
+                    return None;
+                };
+                self.gesture = Gesture::Pinching {
+                    distance_px: spread_px,
+                };
+                Some(TouchAction::Zoom(command))
+            }
+            Gesture::Idle | Gesture::Finished => None,
+        }
+    }
+
+    /// Scrolls by the whole pixels the finger has moved past the anchor; the page follows the
+    /// finger, so dragging up scrolls down.
+    fn drag(&mut self, event: TouchEvent, previous_y_px: f64) -> Option<TouchAction> {
+        let Gesture::Dragging { anchor_y_px } = self.gesture else {
+            return None;
+        };
+        self.velocity
+            .push(event.time_ms, previous_y_px - event.y_px);
+        let delta_y_px = (anchor_y_px - event.y_px).trunc();
+        if delta_y_px == 0.0 {
+            return None;
+        }
+        self.gesture = Gesture::Dragging {
+            anchor_y_px: anchor_y_px - delta_y_px,
+        };
+        Some(TouchAction::Scroll {
+            delta_y_px: delta_y_px as i32,
+        })
+    }
+
+    /// How far apart the first two fingers are.
+    fn spread_px(&self) -> f64 {
+        match self.points.as_slice() {
+            [(_, x0, y0), (_, x1, y1), ..] => (x1 - x0).hypot(y1 - y0),
+            _ => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TouchAction, TouchEvent, TouchGestures, TouchPhase};
+    use crate::app::ZoomCommand;
+
+    fn touch(phase: TouchPhase, id: i32, x_px: f64, y_px: f64, time_ms: u32) -> TouchEvent {
+        TouchEvent {
+            phase,
+            id,
+            x_px,
+            y_px,
+            time_ms,
+        }
+    }
+
+    #[test]
+    fn a_short_still_touch_taps() {
+        let mut gestures = TouchGestures::default();
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Down, 1, 40.5, 60.0, 0)),
+            None
+        );
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 1, 44.0, 62.0, 50)),
+            None
+        );
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Up, 1, 0.0, 0.0, 100)),
+            Some(TouchAction::Tap { x_px: 40, y_px: 60 })
+        );
+
+        gestures.event(touch(TouchPhase::Down, 1, 40.0, 60.0, 1000));
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Up, 1, 0.0, 0.0, 2000)),
+            None
+        );
+    }
+
+    #[test]
+    fn dragging_scrolls_the_page_with_the_finger_then_flings() {
+        let mut gestures = TouchGestures::default();
+        gestures.event(touch(TouchPhase::Down, 1, 100.0, 300.0, 0));
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 1, 100.0, 279.5, 10)),
+            Some(TouchAction::Scroll { delta_y_px: 20 })
+        );
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 1, 100.0, 259.5, 20)),
+            Some(TouchAction::Scroll { delta_y_px: 20 })
+        );
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Up, 1, 0.0, 0.0, 25)),
+            Some(TouchAction::Fling {
+                velocity_y_px_per_s: 2000.0
+            })
+        );
+    }
+
+    #[test]
+    fn pinching_zooms_in_steps() {
+        let mut gestures = TouchGestures::default();
+        gestures.event(touch(TouchPhase::Down, 1, 100.0, 100.0, 0));
+        gestures.event(touch(TouchPhase::Down, 2, 200.0, 100.0, 10));
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 2, 220.0, 100.0, 20)),
+            None
+        );
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 2, 230.0, 100.0, 30)),
+            Some(TouchAction::Zoom(ZoomCommand::In))
+        );
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 1, 150.0, 100.0, 40)),
+            Some(TouchAction::Zoom(ZoomCommand::Out))
+        );
+
+        // Lifting one finger ends the pinch without tapping or scrolling.
+        assert_eq!(gestures.event(touch(TouchPhase::Up, 2, 0.0, 0.0, 50)), None);
+        assert_eq!(
+            gestures.event(touch(TouchPhase::Motion, 1, 100.0, 0.0, 60)),
+            None
+        );
+        assert_eq!(gestures.event(touch(TouchPhase::Up, 1, 0.0, 0.0, 70)), None);
+    }
+}
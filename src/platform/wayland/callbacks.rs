@@ -1,20 +1,26 @@
+use super::keymap::{Keymap, MOD_SHIFT};
+use super::selection::{Offer, Selection};
 use super::sys::*;
+use crate::platform::fling::ScrollVelocity;
+use crate::platform::touch::{TouchAction, TouchEvent, TouchGestures, TouchPhase};
 use core::ffi::{c_char, c_void};
 use std::ffi::CStr;
+use std::fs::File;
 use std::os::fd::FromRawFd;
+use std::os::unix::fs::FileExt;
 
 const WHEEL_SCROLL_STEP_PX: i32 = 48;
 const KEY_BACKSPACE: u32 = 14;
 const KEY_ESCAPE: u32 = 1;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum KeyAction {
+pub(super) enum KeyAction {
     None,
     NavigateBack,
     Exit,
 }
 
-fn key_action(key: u32) -> KeyAction {
+pub(super) fn key_action(key: u32) -> KeyAction {
     if key == KEY_BACKSPACE {
         KeyAction::NavigateBack
     } else if key == KEY_ESCAPE {
@@ -24,6 +30,12 @@ fn key_action(key: u32) -> KeyAction {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+pub(super) struct PendingKey {
+    pub(super) key: u32,
+    pub(super) modifiers: u32,
+}
+
 pub(super) struct CallbackState {
     pub(super) setup_error: Option<String>,
 
@@ -32,7 +44,33 @@ pub(super) struct CallbackState {
     pub(super) seat: *mut wl_seat,
     pub(super) pointer: *mut wl_pointer,
     pub(super) keyboard: *mut wl_keyboard,
+    pub(super) touch: *mut wl_touch,
     pub(super) wm_base: *mut xdg_wm_base,
+    /// Null when the compositor doesn't take window icons.
+    pub(super) icon_manager: *mut xdg_toplevel_icon_manager_v1,
+    /// Null when the compositor can't scale buffers to a different surface size.
+    pub(super) viewporter: *mut wp_viewporter,
+    /// Null when the compositor only reports whole-number scales.
+    pub(super) fractional_scale_manager: *mut wp_fractional_scale_manager_v1,
+    /// The scale the compositor would like the surface drawn at, in 120ths.
+    pub(super) preferred_scale_120: Option<u32>,
+    /// Null when the compositor has no clipboard.
+    pub(super) data_device_manager: *mut wl_data_device_manager,
+    /// Null when the compositor has no primary selection.
+    pub(super) primary_selection_manager: *mut zwp_primary_selection_device_manager_v1,
+    pub(super) data_device: *mut wl_data_device,
+    pub(super) primary_selection_device: *mut zwp_primary_selection_device_v1,
+    /// Offers announced but not yet named as a selection.
+    pub(super) offers: Vec<Offer>,
+    pub(super) clipboard: Option<Offer>,
+    pub(super) primary_selection: Option<Offer>,
+    pub(super) pending_pastes: Vec<Selection>,
+    /// What is being dragged over the window, if it could be dropped.
+    pub(super) drag_offer: Option<Offer>,
+    pub(super) pending_drop: Option<Offer>,
+    pub(super) outputs: Vec<Output>,
+    /// The outputs some part of the surface is showing on.
+    pub(super) entered_outputs: Vec<*mut wl_output>,
 
     pub(super) supports_argb8888: bool,
     pub(super) configured: bool,
@@ -41,12 +79,35 @@ pub(super) struct CallbackState {
 
     pub(super) pointer_x_css_px: i32,
     pub(super) pointer_y_css_px: i32,
+    pub(super) pointer_moved: bool,
     pub(super) pending_mouse_downs: u32,
+    pub(super) pending_mouse_ups: u32,
     pub(super) pending_back_navigations: u32,
+    pub(super) pending_forward_navigations: u32,
     pub(super) pending_wheel_css_px: i32,
-
-    pub(super) buffer_ptr: *mut wl_buffer,
-    pub(super) buffer_busy: bool,
+    /// Wheel notches, which the app glides through rather than jumps.
+    pub(super) pending_wheel_notch_px: i32,
+    /// Sideways scrolling, from the horizontal axis or the vertical one with Shift held.
+    pub(super) pending_horizontal_wheel_px: i32,
+    pub(super) horizontal_remainder_px: f64,
+    /// Set when a touchpad scroll ended at speed.
+    pub(super) pending_fling_px_per_s: Option<f64>,
+    /// What is scrolling in the current pointer frame, when the compositor says.
+    pub(super) axis_source: Option<u32>,
+    /// The fraction of a pixel touchpad scrolling has moved past the last whole pixel.
+    pub(super) axis_remainder_px: f64,
+    pub(super) scroll_velocity: ScrollVelocity,
+    pub(super) touch_gestures: TouchGestures,
+    pub(super) pending_touch_actions: Vec<TouchAction>,
+    pub(super) pending_keys: Vec<PendingKey>,
+    pub(super) keyboard_modifiers: u32,
+    /// The compositor's keymap; `None` until it arrives, or when it couldn't be read.
+    pub(super) keymap: Option<Keymap>,
+
+    /// Buffers attached to the surface that the compositor hasn't released yet.
+    pub(super) busy_buffers: Vec<*mut wl_buffer>,
+    /// Requested with each commit; null once the compositor asks for the next frame.
+    pub(super) frame_callback: *mut wl_callback,
 }
 
 impl Default for CallbackState {
@@ -58,22 +119,98 @@ impl Default for CallbackState {
             seat: std::ptr::null_mut(),
             pointer: std::ptr::null_mut(),
             keyboard: std::ptr::null_mut(),
+            touch: std::ptr::null_mut(),
             wm_base: std::ptr::null_mut(),
+            icon_manager: std::ptr::null_mut(),
+            viewporter: std::ptr::null_mut(),
+            fractional_scale_manager: std::ptr::null_mut(),
+            preferred_scale_120: None,
+            data_device_manager: std::ptr::null_mut(),
+            primary_selection_manager: std::ptr::null_mut(),
+            data_device: std::ptr::null_mut(),
+            primary_selection_device: std::ptr::null_mut(),
+            offers: Vec::new(),
+            clipboard: None,
+            primary_selection: None,
+            pending_pastes: Vec::new(),
+            drag_offer: None,
+            pending_drop: None,
+            outputs: Vec::new(),
+            entered_outputs: Vec::new(),
             supports_argb8888: false,
             configured: false,
             pending_resize: None,
             should_exit: false,
             pointer_x_css_px: 0,
             pointer_y_css_px: 0,
+            pointer_moved: false,
             pending_mouse_downs: 0,
+            pending_mouse_ups: 0,
             pending_back_navigations: 0,
+            pending_forward_navigations: 0,
             pending_wheel_css_px: 0,
-            buffer_ptr: std::ptr::null_mut(),
-            buffer_busy: false,
+            pending_wheel_notch_px: 0,
+            pending_horizontal_wheel_px: 0,
+            horizontal_remainder_px: 0.0,
+            pending_fling_px_per_s: None,
+            axis_source: None,
+            axis_remainder_px: 0.0,
+            scroll_velocity: ScrollVelocity::default(),
+            touch_gestures: TouchGestures::default(),
+            pending_touch_actions: Vec::new(),
+            pending_keys: Vec::new(),
+            keyboard_modifiers: 0,
+            keymap: None,
+            busy_buffers: Vec::new(),
+            frame_callback: std::ptr::null_mut(),
         }
     }
 }
 
+impl CallbackState {
+    /// The whole-number scale of the densest output the surface is on; `None` until the surface
+    /// is on one.
+    pub(super) fn output_scale(&self) -> Option<i32> {
+        self.outputs
+            .iter()
+            .filter(|output| self.entered_outputs.contains(&output.proxy))
+            .map(|output| output.scale)
+            .max()
+    }
+
+    fn offer_mut(&mut self, proxy: *mut wl_proxy) -> Option<&mut Offer> {
+        self.offers
+            .iter_mut()
+            .chain(self.clipboard.as_mut())
+            .chain(self.primary_selection.as_mut())
+            .find(|offer| offer.proxy == proxy)
+    }
+
+    /// Makes the announced `offer` the current `selection`, or clears it when `offer` is null.
+    fn set_selection(&mut self, selection: Selection, offer: *mut wl_proxy) {
+        let index = self.offers.iter().position(|known| known.proxy == offer);
+        let offer = index.map(|index| self.offers.remove(index));
+        let previous = match selection {
+            Selection::Clipboard => std::mem::replace(&mut self.clipboard, offer),
+            Selection::Primary => std::mem::replace(&mut self.primary_selection, offer),
+        };
+        if let Some(previous) = previous {
+            unsafe {
+                previous.destroy();
+            }
+        }
+    }
+}
+
+/// A monitor the compositor advertises.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Output {
+    /// The output's registry name, by which it's removed.
+    name: u32,
+    pub(super) proxy: *mut wl_output,
+    scale: i32,
+}
+
 pub(super) fn take_setup_error(state: &mut CallbackState) -> Option<String> {
     state.setup_error.take()
 }
@@ -126,6 +263,18 @@ const SHM_LISTENER: wl_shm_listener = wl_shm_listener {
     format: Some(handle_shm_format),
 };
 
+const OUTPUT_LISTENER: wl_output_listener = wl_output_listener {
+    geometry: Some(handle_output_geometry),
+    mode: Some(handle_output_mode),
+    done: Some(handle_output_done),
+    scale: Some(handle_output_scale),
+};
+
+pub(super) const SURFACE_LISTENER: wl_surface_listener = wl_surface_listener {
+    enter: Some(handle_surface_enter),
+    leave: Some(handle_surface_leave),
+};
+
 const SEAT_LISTENER: wl_seat_listener = wl_seat_listener {
     capabilities: Some(handle_seat_capabilities),
     name: Some(handle_seat_name),
@@ -154,6 +303,47 @@ const KEYBOARD_LISTENER: wl_keyboard_listener = wl_keyboard_listener {
     repeat_info: Some(handle_keyboard_repeat_info),
 };
 
+const TOUCH_LISTENER: wl_touch_listener = wl_touch_listener {
+    down: Some(handle_touch_down),
+    up: Some(handle_touch_up),
+    motion: Some(handle_touch_motion),
+    frame: Some(handle_touch_frame),
+    cancel: Some(handle_touch_cancel),
+    shape: Some(handle_touch_shape),
+    orientation: Some(handle_touch_orientation),
+};
+
+pub(super) const DATA_DEVICE_LISTENER: wl_data_device_listener = wl_data_device_listener {
+    data_offer: Some(handle_data_device_data_offer),
+    enter: Some(handle_data_device_enter),
+    leave: Some(handle_data_device_leave),
+    motion: Some(handle_data_device_motion),
+    drop: Some(handle_data_device_drop),
+    selection: Some(handle_data_device_selection),
+};
+
+const DATA_OFFER_LISTENER: wl_data_offer_listener = wl_data_offer_listener {
+    offer: Some(handle_offer_mime_type),
+    source_actions: Some(handle_data_offer_source_actions),
+    action: Some(handle_data_offer_action),
+};
+
+pub(super) const PRIMARY_SELECTION_DEVICE_LISTENER: zwp_primary_selection_device_v1_listener =
+    zwp_primary_selection_device_v1_listener {
+        data_offer: Some(handle_primary_selection_data_offer),
+        selection: Some(handle_primary_selection_selection),
+    };
+
+const PRIMARY_SELECTION_OFFER_LISTENER: zwp_primary_selection_offer_v1_listener =
+    zwp_primary_selection_offer_v1_listener {
+        offer: Some(handle_offer_mime_type),
+    };
+
+pub(super) const FRACTIONAL_SCALE_LISTENER: wp_fractional_scale_v1_listener =
+    wp_fractional_scale_v1_listener {
+        preferred_scale: Some(handle_fractional_scale_preferred_scale),
+    };
+
 const WM_BASE_LISTENER: xdg_wm_base_listener = xdg_wm_base_listener {
     ping: Some(handle_wm_base_ping),
 };
@@ -173,6 +363,10 @@ pub(super) const WL_BUFFER_LISTENER: wl_buffer_listener = wl_buffer_listener {
     release: Some(handle_buffer_release),
 };
 
+pub(super) const WL_CALLBACK_LISTENER: wl_callback_listener = wl_callback_listener {
+    done: Some(handle_frame_done),
+};
+
 unsafe extern "C" fn handle_registry_global(
     data: *mut c_void,
     registry: *mut wl_registry,
@@ -229,6 +423,62 @@ unsafe extern "C" fn handle_registry_global(
         return;
     }
 
+    if interface_name == b"wl_output" {
+        // Optional: without outputs the surface keeps the startup scale.
+        let output = unsafe { oab_wl_registry_bind_output(registry, name, version.min(2)) };
+        if output.is_null() {
+            return;
+        }
+        if unsafe { add_proxy_listener(output, &OUTPUT_LISTENER, state, "wl_output") }.is_err() {
+            unsafe {
+                wl_proxy_destroy(output.cast::<wl_proxy>());
+            }
+            return;
+        }
+        state.outputs.push(Output {
+            name,
+            proxy: output,
+            scale: 1,
+        });
+        return;
+    }
+
+    if interface_name == b"wl_data_device_manager" && state.data_device_manager.is_null() {
+        // Optional, as is the primary selection: without them nothing can be pasted.
+        state.data_device_manager =
+            unsafe { oab_wl_registry_bind_data_device_manager(registry, name, version.min(3)) };
+        return;
+    }
+
+    if interface_name == b"zwp_primary_selection_device_manager_v1"
+        && state.primary_selection_manager.is_null()
+    {
+        state.primary_selection_manager =
+            unsafe { oab_wl_registry_bind_primary_selection_device_manager(registry, name, 1) };
+        return;
+    }
+
+    if interface_name == b"xdg_toplevel_icon_manager_v1" && state.icon_manager.is_null() {
+        // Optional: without it the window keeps the compositor's default icon.
+        state.icon_manager =
+            unsafe { oab_wl_registry_bind_toplevel_icon_manager(registry, name, 1) };
+        return;
+    }
+
+    if interface_name == b"wp_viewporter" && state.viewporter.is_null() {
+        state.viewporter = unsafe { oab_wl_registry_bind_viewporter(registry, name, 1) };
+        return;
+    }
+
+    if interface_name == b"wp_fractional_scale_manager_v1"
+        && state.fractional_scale_manager.is_null()
+    {
+        // Optional, with the viewporter: without them the buffer scale stays a whole number.
+        state.fractional_scale_manager =
+            unsafe { oab_wl_registry_bind_fractional_scale_manager(registry, name, 1) };
+        return;
+    }
+
     if interface_name == b"xdg_wm_base" && state.wm_base.is_null() {
         state.wm_base = unsafe { oab_wl_registry_bind_xdg_wm_base(registry, name, version.min(6)) };
         if state.wm_base.is_null() {
@@ -248,12 +498,76 @@ unsafe extern "C" fn handle_registry_global(
 }
 
 unsafe extern "C" fn handle_registry_global_remove(
-    _data: *mut c_void,
+    data: *mut c_void,
     _registry: *mut wl_registry,
-    _name: u32,
+    name: u32,
+) {
+    let state = unsafe { state_from_data(data) };
+    let Some(index) = state.outputs.iter().position(|output| output.name == name) else {
+        return;
+    };
+    let output = state.outputs.remove(index);
+    state
+        .entered_outputs
+        .retain(|&entered| entered != output.proxy);
+    unsafe {
+        wl_proxy_destroy(output.proxy.cast::<wl_proxy>());
+    }
+}
+
+unsafe extern "C" fn handle_output_geometry(
+    _data: *mut c_void,
+    _output: *mut wl_output,
+    _x: i32,
+    _y: i32,
+    _physical_width: i32,
+    _physical_height: i32,
+    _subpixel: i32,
+    _make: *const c_char,
+    _model: *const c_char,
+    _transform: i32,
 ) {
 }
 
+unsafe extern "C" fn handle_output_mode(
+    _data: *mut c_void,
+    _output: *mut wl_output,
+    _flags: u32,
+    _width: i32,
+    _height: i32,
+    _refresh: i32,
+) {
+}
+
+unsafe extern "C" fn handle_output_done(_data: *mut c_void, _output: *mut wl_output) {}
+
+unsafe extern "C" fn handle_output_scale(data: *mut c_void, output: *mut wl_output, factor: i32) {
+    let state = unsafe { state_from_data(data) };
+    if let Some(known) = state.outputs.iter_mut().find(|known| known.proxy == output) {
+        known.scale = factor.max(1);
+    }
+}
+
+unsafe extern "C" fn handle_surface_enter(
+    data: *mut c_void,
+    _surface: *mut wl_surface,
+    output: *mut wl_output,
+) {
+    let state = unsafe { state_from_data(data) };
+    if !output.is_null() && !state.entered_outputs.contains(&output) {
+        state.entered_outputs.push(output);
+    }
+}
+
+unsafe extern "C" fn handle_surface_leave(
+    data: *mut c_void,
+    _surface: *mut wl_surface,
+    output: *mut wl_output,
+) {
+    let state = unsafe { state_from_data(data) };
+    state.entered_outputs.retain(|&entered| entered != output);
+}
+
 unsafe extern "C" fn handle_shm_format(data: *mut c_void, _shm: *mut wl_shm, format: u32) {
     let state = unsafe { state_from_data(data) };
     if format == WL_SHM_FORMAT_ARGB8888 {
@@ -321,6 +635,34 @@ unsafe extern "C" fn handle_seat_capabilities(
         }
         state.keyboard = std::ptr::null_mut();
     }
+
+    if (capabilities & WL_SEAT_CAPABILITY_TOUCH) != 0 {
+        if state.touch.is_null() {
+            let touch = unsafe { oab_wl_seat_get_touch(seat) };
+            if touch.is_null() {
+                record_setup_error(state, "wl_seat_get_touch returned null".to_owned());
+                return;
+            }
+
+            let add_result =
+                unsafe { add_proxy_listener(touch, &TOUCH_LISTENER, state, "wl_touch") };
+            if let Err(err) = add_result {
+                unsafe {
+                    wl_proxy_destroy(touch.cast::<wl_proxy>());
+                }
+                record_setup_error(state, err);
+                return;
+            }
+
+            state.touch = touch;
+        }
+    } else if !state.touch.is_null() {
+        unsafe {
+            wl_proxy_destroy(state.touch.cast::<wl_proxy>());
+        }
+        state.touch = std::ptr::null_mut();
+        state.touch_gestures = TouchGestures::default();
+    }
 }
 
 unsafe extern "C" fn handle_seat_name(
@@ -361,6 +703,7 @@ unsafe extern "C" fn handle_pointer_motion(
     let state = unsafe { state_from_data(data) };
     state.pointer_x_css_px = fixed_to_i32(surface_x);
     state.pointer_y_css_px = fixed_to_i32(surface_y);
+    state.pointer_moved = true;
 }
 
 unsafe extern "C" fn handle_pointer_button(
@@ -371,29 +714,58 @@ unsafe extern "C" fn handle_pointer_button(
     button: u32,
     state_value: u32,
 ) {
+    let state = unsafe { state_from_data(data) };
     if state_value != WL_POINTER_BUTTON_STATE_PRESSED {
+        if button == BTN_LEFT {
+            state.pending_mouse_ups = state.pending_mouse_ups.saturating_add(1);
+        }
         return;
     }
 
-    let state = unsafe { state_from_data(data) };
     if button == BTN_LEFT {
         state.pending_mouse_downs = state.pending_mouse_downs.saturating_add(1);
+    } else if button == BTN_MIDDLE {
+        state.pending_pastes.push(Selection::Primary);
     } else if button == BTN_SIDE {
         state.pending_back_navigations = state.pending_back_navigations.saturating_add(1);
+    } else if button == BTN_EXTRA {
+        state.pending_forward_navigations = state.pending_forward_navigations.saturating_add(1);
     }
 }
 
 unsafe extern "C" fn handle_pointer_axis(
     data: *mut c_void,
     _pointer: *mut wl_pointer,
-    _time: u32,
+    time: u32,
     axis: u32,
     value: wl_fixed_t,
 ) {
+    let state = unsafe { state_from_data(data) };
+    if axis == WL_POINTER_AXIS_HORIZONTAL_SCROLL
+        || (axis == WL_POINTER_AXIS_VERTICAL_SCROLL && state.keyboard_modifiers & MOD_SHIFT != 0)
+    {
+        let delta = wl_fixed_to_f64(value) + state.horizontal_remainder_px;
+        let whole = delta.trunc();
+        state.horizontal_remainder_px = delta - whole;
+        state.pending_horizontal_wheel_px = state
+            .pending_horizontal_wheel_px
+            .saturating_add(whole as i32);
+        return;
+    }
     if axis != WL_POINTER_AXIS_VERTICAL_SCROLL {
         return;
     }
 
+    if state.axis_source == Some(WL_POINTER_AXIS_SOURCE_FINGER) {
+        let delta = wl_fixed_to_f64(value);
+        state.scroll_velocity.push(time, delta);
+        let delta = delta + state.axis_remainder_px;
+        let whole = delta.trunc();
+        state.axis_remainder_px = delta - whole;
+        state.pending_wheel_css_px = state.pending_wheel_css_px.saturating_add(whole as i32);
+        return;
+    }
+
     let mut delta = fixed_to_i32(value);
     if delta == 0 {
         let sign = wl_fixed_to_f64(value).signum() as i32;
@@ -406,25 +778,41 @@ unsafe extern "C" fn handle_pointer_axis(
         return;
     }
 
-    let state = unsafe { state_from_data(data) };
-    state.pending_wheel_css_px = state.pending_wheel_css_px.saturating_add(delta);
+    if state.axis_source == Some(WL_POINTER_AXIS_SOURCE_WHEEL) {
+        state.pending_wheel_notch_px = state.pending_wheel_notch_px.saturating_add(delta);
+    } else {
+        state.pending_wheel_css_px = state.pending_wheel_css_px.saturating_add(delta);
+    }
 }
 
-unsafe extern "C" fn handle_pointer_frame(_data: *mut c_void, _pointer: *mut wl_pointer) {}
+unsafe extern "C" fn handle_pointer_frame(data: *mut c_void, _pointer: *mut wl_pointer) {
+    let state = unsafe { state_from_data(data) };
+    state.axis_source = None;
+}
 
 unsafe extern "C" fn handle_pointer_axis_source(
-    _data: *mut c_void,
+    data: *mut c_void,
     _pointer: *mut wl_pointer,
-    _axis_source: u32,
+    axis_source: u32,
 ) {
+    let state = unsafe { state_from_data(data) };
+    state.axis_source = Some(axis_source);
 }
 
 unsafe extern "C" fn handle_pointer_axis_stop(
-    _data: *mut c_void,
+    data: *mut c_void,
     _pointer: *mut wl_pointer,
-    _time: u32,
-    _axis: u32,
+    time: u32,
+    axis: u32,
 ) {
+    if axis != WL_POINTER_AXIS_VERTICAL_SCROLL {
+        return;
+    }
+    let state = unsafe { state_from_data(data) };
+    state.axis_remainder_px = 0.0;
+    if let Some(velocity) = state.scroll_velocity.take_velocity(time) {
+        state.pending_fling_px_per_s = Some(velocity);
+    }
 }
 
 unsafe extern "C" fn handle_pointer_axis_discrete(
@@ -451,16 +839,302 @@ unsafe extern "C" fn handle_pointer_axis_relative_direction(
 ) {
 }
 
-unsafe extern "C" fn handle_keyboard_keymap(
+fn record_touch(state: &mut CallbackState, event: TouchEvent) {
+    if let Some(action) = state.touch_gestures.event(event) {
+        state.pending_touch_actions.push(action);
+    }
+}
+
+unsafe extern "C" fn handle_data_device_data_offer(
+    data: *mut c_void,
+    _device: *mut wl_data_device,
+    offer: *mut wl_data_offer,
+) {
+    let state = unsafe { state_from_data(data) };
+    if offer.is_null() {
+        return;
+    }
+    if unsafe { add_proxy_listener(offer, &DATA_OFFER_LISTENER, state, "wl_data_offer") }.is_err() {
+        unsafe {
+            oab_wl_data_offer_destroy(offer);
+        }
+        return;
+    }
+    state.offers.push(Offer::new(offer, Selection::Clipboard));
+}
+
+/// Something is being dragged over the window. Files, links and text are taken as a copy; the
+/// offer of anything else is let go.
+unsafe extern "C" fn handle_data_device_enter(
+    data: *mut c_void,
+    _device: *mut wl_data_device,
+    serial: u32,
+    _surface: *mut wl_surface,
+    _x: wl_fixed_t,
+    _y: wl_fixed_t,
+    offer: *mut wl_data_offer,
+) {
+    let state = unsafe { state_from_data(data) };
+    let Some(index) = state.offers.iter().position(|known| known.proxy == offer) else {
+        return;
+    };
+    let offer = state.offers.remove(index);
+    let Some(mime_type) = offer.drop_mime_type() else {
+        unsafe {
+            offer.destroy();
+        }
+        return;
+    };
+    unsafe {
+        oab_wl_data_offer_accept(offer.proxy, serial, mime_type.as_ptr());
+        oab_wl_data_offer_set_actions(
+            offer.proxy,
+            WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY,
+            WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY,
+        );
+    }
+    if let Some(previous) = state.drag_offer.replace(offer) {
+        unsafe {
+            previous.destroy();
+        }
+    }
+}
+
+unsafe extern "C" fn handle_data_device_leave(data: *mut c_void, _device: *mut wl_data_device) {
+    let state = unsafe { state_from_data(data) };
+    if let Some(offer) = state.drag_offer.take() {
+        unsafe {
+            offer.destroy();
+        }
+    }
+}
+
+unsafe extern "C" fn handle_data_device_motion(
+    _data: *mut c_void,
+    _device: *mut wl_data_device,
+    _time: u32,
+    _x: wl_fixed_t,
+    _y: wl_fixed_t,
+) {
+}
+
+/// The offer is kept past `leave`, for the drop to be read once the events are dispatched.
+unsafe extern "C" fn handle_data_device_drop(data: *mut c_void, _device: *mut wl_data_device) {
+    let state = unsafe { state_from_data(data) };
+    if let Some(offer) = state.drag_offer.take()
+        && let Some(previous) = state.pending_drop.replace(offer)
+    {
+        unsafe {
+            previous.destroy();
+        }
+    }
+}
+
+unsafe extern "C" fn handle_data_device_selection(
+    data: *mut c_void,
+    _device: *mut wl_data_device,
+    offer: *mut wl_data_offer,
+) {
+    let state = unsafe { state_from_data(data) };
+    state.set_selection(Selection::Clipboard, offer);
+}
+
+/// Shared by clipboard and primary selection offers, which announce their types alike.
+unsafe extern "C" fn handle_offer_mime_type(
+    data: *mut c_void,
+    offer: *mut wl_proxy,
+    mime_type: *const c_char,
+) {
+    let state = unsafe { state_from_data(data) };
+    if mime_type.is_null() {
+        return;
+    }
+    let mime_type = unsafe { CStr::from_ptr(mime_type) };
+    if let Some(offer) = state.offer_mut(offer) {
+        offer.add_mime_type(mime_type);
+    }
+}
+
+unsafe extern "C" fn handle_data_offer_source_actions(
+    _data: *mut c_void,
+    _offer: *mut wl_data_offer,
+    _source_actions: u32,
+) {
+}
+
+unsafe extern "C" fn handle_data_offer_action(
+    _data: *mut c_void,
+    _offer: *mut wl_data_offer,
+    _dnd_action: u32,
+) {
+}
+
+unsafe extern "C" fn handle_primary_selection_data_offer(
+    data: *mut c_void,
+    _device: *mut zwp_primary_selection_device_v1,
+    offer: *mut zwp_primary_selection_offer_v1,
+) {
+    let state = unsafe { state_from_data(data) };
+    if offer.is_null() {
+        return;
+    }
+    let add_result = unsafe {
+        add_proxy_listener(
+            offer,
+            &PRIMARY_SELECTION_OFFER_LISTENER,
+            state,
+            "zwp_primary_selection_offer_v1",
+        )
+    };
+    if add_result.is_err() {
+        unsafe {
+            oab_zwp_primary_selection_offer_destroy(offer);
+        }
+        return;
+    }
+    state.offers.push(Offer::new(offer, Selection::Primary));
+}
+
+unsafe extern "C" fn handle_primary_selection_selection(
+    data: *mut c_void,
+    _device: *mut zwp_primary_selection_device_v1,
+    offer: *mut zwp_primary_selection_offer_v1,
+) {
+    let state = unsafe { state_from_data(data) };
+    state.set_selection(Selection::Primary, offer);
+}
+
+unsafe extern "C" fn handle_fractional_scale_preferred_scale(
+    data: *mut c_void,
+    _fractional_scale: *mut wp_fractional_scale_v1,
+    scale: u32,
+) {
+    let state = unsafe { state_from_data(data) };
+    if scale > 0 {
+        state.preferred_scale_120 = Some(scale);
+    }
+}
+
+unsafe extern "C" fn handle_touch_down(
+    data: *mut c_void,
+    _touch: *mut wl_touch,
+    _serial: u32,
+    time: u32,
+    _surface: *mut wl_surface,
+    id: i32,
+    x: wl_fixed_t,
+    y: wl_fixed_t,
+) {
+    let state = unsafe { state_from_data(data) };
+    record_touch(
+        state,
+        TouchEvent {
+            phase: TouchPhase::Down,
+            id,
+            x_px: wl_fixed_to_f64(x),
+            y_px: wl_fixed_to_f64(y),
+            time_ms: time,
+        },
+    );
+}
+
+unsafe extern "C" fn handle_touch_up(
+    data: *mut c_void,
+    _touch: *mut wl_touch,
+    _serial: u32,
+    time: u32,
+    id: i32,
+) {
+    let state = unsafe { state_from_data(data) };
+    record_touch(
+        state,
+        TouchEvent {
+            phase: TouchPhase::Up,
+            id,
+            x_px: 0.0,
+            y_px: 0.0,
+            time_ms: time,
+        },
+    );
+}
+
+unsafe extern "C" fn handle_touch_motion(
+    data: *mut c_void,
+    _touch: *mut wl_touch,
+    time: u32,
+    id: i32,
+    x: wl_fixed_t,
+    y: wl_fixed_t,
+) {
+    let state = unsafe { state_from_data(data) };
+    record_touch(
+        state,
+        TouchEvent {
+            phase: TouchPhase::Motion,
+            id,
+            x_px: wl_fixed_to_f64(x),
+            y_px: wl_fixed_to_f64(y),
+            time_ms: time,
+        },
+    );
+}
+
+unsafe extern "C" fn handle_touch_frame(_data: *mut c_void, _touch: *mut wl_touch) {}
+
+unsafe extern "C" fn handle_touch_cancel(data: *mut c_void, _touch: *mut wl_touch) {
+    let state = unsafe { state_from_data(data) };
+    record_touch(
+        state,
+        TouchEvent {
+            phase: TouchPhase::Cancel,
+            id: 0,
+            x_px: 0.0,
+            y_px: 0.0,
+            time_ms: 0,
+        },
+    );
+}
+
+unsafe extern "C" fn handle_touch_shape(
     _data: *mut c_void,
+    _touch: *mut wl_touch,
+    _id: i32,
+    _major: wl_fixed_t,
+    _minor: wl_fixed_t,
+) {
+}
+
+unsafe extern "C" fn handle_touch_orientation(
+    _data: *mut c_void,
+    _touch: *mut wl_touch,
+    _id: i32,
+    _orientation: wl_fixed_t,
+) {
+}
+
+unsafe extern "C" fn handle_keyboard_keymap(
+    data: *mut c_void,
     _keyboard: *mut wl_keyboard,
-    _format: u32,
+    format: u32,
     fd: i32,
-    _size: u32,
+    size: u32,
 ) {
-    if fd >= 0 {
-        let _ = unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) };
+    if fd < 0 {
+        return;
     }
+    let file = unsafe { File::from_raw_fd(fd) };
+    let state = unsafe { state_from_data(data) };
+    state.keymap = None;
+    if format != WL_KEYBOARD_KEYMAP_FORMAT_XKB_V1 {
+        return;
+    }
+    // The file may be shared with other clients, so it is read without moving its offset.
+    let mut text = vec![0u8; size as usize];
+    if file.read_exact_at(&mut text, 0).is_err() {
+        return;
+    }
+    let text = String::from_utf8_lossy(&text);
+    state.keymap = Keymap::parse(text.trim_end_matches('\0'));
 }
 
 unsafe extern "C" fn handle_keyboard_enter(
@@ -493,26 +1167,23 @@ unsafe extern "C" fn handle_keyboard_key(
     }
 
     let state = unsafe { state_from_data(data) };
-    match key_action(key) {
-        KeyAction::NavigateBack => {
-            state.pending_back_navigations = state.pending_back_navigations.saturating_add(1);
-        }
-        KeyAction::Exit => {
-            state.should_exit = true;
-        }
-        KeyAction::None => {}
-    }
+    state.pending_keys.push(PendingKey {
+        key,
+        modifiers: state.keyboard_modifiers,
+    });
 }
 
 unsafe extern "C" fn handle_keyboard_modifiers(
-    _data: *mut c_void,
+    data: *mut c_void,
     _keyboard: *mut wl_keyboard,
     _serial: u32,
-    _mods_depressed: u32,
-    _mods_latched: u32,
-    _mods_locked: u32,
+    mods_depressed: u32,
+    mods_latched: u32,
+    mods_locked: u32,
     _group: u32,
 ) {
+    let state = unsafe { state_from_data(data) };
+    state.keyboard_modifiers = mods_depressed | mods_latched | mods_locked;
 }
 
 unsafe extern "C" fn handle_keyboard_repeat_info(
@@ -580,14 +1251,26 @@ unsafe extern "C" fn handle_xdg_toplevel_wm_capabilities(
 
 unsafe extern "C" fn handle_buffer_release(data: *mut c_void, buffer: *mut wl_buffer) {
     let state = unsafe { state_from_data(data) };
-    if state.buffer_ptr == buffer {
-        state.buffer_busy = false;
+    state.busy_buffers.retain(|busy| *busy != buffer);
+}
+
+unsafe extern "C" fn handle_frame_done(
+    data: *mut c_void,
+    callback: *mut wl_callback,
+    _callback_data: u32,
+) {
+    let state = unsafe { state_from_data(data) };
+    if state.frame_callback == callback {
+        state.frame_callback = std::ptr::null_mut();
+    }
+    unsafe {
+        wl_proxy_destroy(callback.cast::<wl_proxy>());
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{CallbackState, KeyAction, XDG_TOPLEVEL_LISTENER, key_action};
+    use super::{CallbackState, KeyAction, WL_BUFFER_LISTENER, XDG_TOPLEVEL_LISTENER, key_action};
 
     #[test]
     fn xdg_toplevel_close_requests_exit() {
@@ -607,6 +1290,23 @@ mod tests {
         assert!(state.should_exit);
     }
 
+    #[test]
+    fn buffer_release_frees_only_that_buffer() {
+        let mut state = CallbackState::default();
+        let front = std::ptr::dangling_mut();
+        let back = std::ptr::without_provenance_mut(2 * align_of::<usize>());
+        state.busy_buffers = vec![front, back];
+
+        let release = WL_BUFFER_LISTENER
+            .release
+            .expect("wl_buffer release listener must be wired");
+        unsafe {
+            release((&mut state as *mut CallbackState).cast(), front);
+        }
+
+        assert_eq!(state.busy_buffers, vec![back]);
+    }
+
     #[test]
     fn wayland_key_action_maps_backspace_and_escape() {
         assert_eq!(key_action(super::KEY_BACKSPACE), KeyAction::NavigateBack);
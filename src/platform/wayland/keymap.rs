@@ -0,0 +1,526 @@
+use crate::app::{Key, KeyInput};
+use std::collections::HashMap;
+
+// Modifier bits as laid out by the default xkb keymap that compositors send.
+pub(super) const MOD_SHIFT: u32 = 1 << 0;
+const MOD_CAPS_LOCK: u32 = 1 << 1;
+const MOD_CONTROL: u32 = 1 << 2;
+const MOD_ALT: u32 = 1 << 3;
+
+/// xkb keycodes are evdev keycodes shifted past the eight X11 reserved codes.
+const XKB_KEYCODE_OFFSET: u32 = 8;
+
+/// The first group of the compositor's xkb keymap: each key's unshifted and shifted keysym.
+///
+/// Only keycode names and the symbols of the first group are read; key types, levels past
+/// the second and actions are ignored.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct Keymap {
+    levels: HashMap<u32, (Keysym, Option<Keysym>)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Keysym {
+    Named(Key),
+    Char(char),
+}
+
+impl Keymap {
+    /// Parses an `xkb_keymap` in the XKB v1 text format; `None` when it has no usable keys.
+    pub(super) fn parse(text: &str) -> Option<Keymap> {
+        let keycodes = parse_keycodes(section(text, "xkb_keycodes")?);
+        let mut levels = HashMap::new();
+        for (name, body) in key_blocks(section(text, "xkb_symbols")?) {
+            let Some(&code) = keycodes.get(name) else {
+                continue;
+            };
+            let Some(evdev) = code.checked_sub(XKB_KEYCODE_OFFSET) else {
+                continue;
+            };
+            // The first group's list, which may be written as `symbols[Group1]= [ ... ]`.
+            let symbols = match body.find("symbols[") {
+                Some(at) => body[at..].find('=').map_or("", |eq| &body[at + eq + 1..]),
+                None => body,
+            };
+            let Some(list) = symbols.find('[').and_then(|start| {
+                let list = &symbols[start + 1..];
+                list.find(']').map(|end| &list[..end])
+            }) else {
+                continue;
+            };
+            let mut names = list.split(',').map(str::trim);
+            let Some(plain) = names.next().and_then(keysym) else {
+                continue;
+            };
+            levels.insert(evdev, (plain, names.next().and_then(keysym)));
+        }
+        (!levels.is_empty()).then_some(Keymap { levels })
+    }
+
+    fn keysym(&self, key: u32, shift: bool, caps_lock: bool) -> Option<Keysym> {
+        let &(plain, shifted) = self.levels.get(&key)?;
+        // Caps Lock only shifts keys whose levels are a letter's two cases.
+        let alphabetic = matches!(
+            (plain, shifted),
+            (Keysym::Char(lower), Some(Keysym::Char(upper)))
+                if lower.is_lowercase() && lower.to_uppercase().eq([upper])
+        );
+        let shift = shift != (caps_lock && alphabetic);
+        Some(match shifted {
+            Some(shifted) if shift => shifted,
+            _ => plain,
+        })
+    }
+}
+
+/// Translates an evdev keycode through `keymap`, or through a fixed US layout for keys it
+/// doesn't cover or when the compositor's keymap couldn't be read.
+pub(super) fn key_input(keymap: Option<&Keymap>, key: u32, modifiers: u32) -> Option<KeyInput> {
+    let ctrl = modifiers & MOD_CONTROL != 0;
+    let alt = modifiers & MOD_ALT != 0;
+    let shift = modifiers & MOD_SHIFT != 0;
+    let caps_lock = modifiers & MOD_CAPS_LOCK != 0;
+    let keysym = keymap
+        .and_then(|keymap| keymap.keysym(key, shift, caps_lock))
+        .or_else(|| us_layout_keysym(key, shift, caps_lock))?;
+    let key = match keysym {
+        Keysym::Named(key) => key,
+        Keysym::Char(ch) => Key::Char(ch),
+    };
+    Some(KeyInput { key, ctrl, alt })
+}
+
+fn us_layout_keysym(key: u32, shift: bool, caps_lock: bool) -> Option<Keysym> {
+    let named = match key {
+        1 => Some(Key::Escape),
+        14 => Some(Key::Backspace),
+        15 => Some(Key::Tab),
+        28 | 96 => Some(Key::Enter),
+        88 => Some(Key::F12),
+        102 => Some(Key::Home),
+        105 => Some(Key::Left),
+        106 => Some(Key::Right),
+        107 => Some(Key::End),
+        111 => Some(Key::Delete),
+        _ => None,
+    };
+    if let Some(key) = named {
+        return Some(Keysym::Named(key));
+    }
+
+    let (plain, shifted) = us_layout_char(key)?;
+    let mut shift = shift;
+    if plain.is_ascii_lowercase() && caps_lock {
+        shift = !shift;
+    }
+    Some(Keysym::Char(if shift { shifted } else { plain }))
+}
+
+fn us_layout_char(key: u32) -> Option<(char, char)> {
+    const DIGITS: &[u8; 10] = b"1234567890";
+    const DIGITS_SHIFTED: &[u8; 10] = b"!@#$%^&*()";
+    const TOP_ROW: &[u8; 10] = b"qwertyuiop";
+    const HOME_ROW: &[u8; 9] = b"asdfghjkl";
+    const BOTTOM_ROW: &[u8; 7] = b"zxcvbnm";
+
+    let letter = |row: &[u8], index: u32| {
+        let ch = char::from(row[index as usize]);
+        (ch, ch.to_ascii_uppercase())
+    };
+    let pair = match key {
+        2..=11 => (
+            char::from(DIGITS[(key - 2) as usize]),
+            char::from(DIGITS_SHIFTED[(key - 2) as usize]),
+        ),
+        12 => ('-', '_'),
+        13 => ('=', '+'),
+        16..=25 => letter(TOP_ROW, key - 16),
+        26 => ('[', '{'),
+        27 => (']', '}'),
+        30..=38 => letter(HOME_ROW, key - 30),
+        39 => (';', ':'),
+        40 => ('\'', '"'),
+        41 => ('`', '~'),
+        43 => ('\\', '|'),
+        44..=50 => letter(BOTTOM_ROW, key - 44),
+        51 => (',', '<'),
+        52 => ('.', '>'),
+        53 => ('/', '?'),
+        57 => (' ', ' '),
+        _ => return None,
+    };
+    Some(pair)
+}
+
+/// The body of the `kind { ... };` section, e.g. `xkb_symbols`.
+fn section<'a>(text: &'a str, kind: &str) -> Option<&'a str> {
+    let start = text.find(kind)?;
+    let open = start + text[start..].find('{')?;
+    let mut depth = 0usize;
+    for (offset, ch) in text[open..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&text[open + 1..open + offset]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `<NAME> = code;` entries, with `alias <A> = <B>;` resolved.
+fn parse_keycodes(section: &str) -> HashMap<&str, u32> {
+    let mut codes = HashMap::new();
+    let mut aliases = Vec::new();
+    for statement in section.split(';') {
+        let statement = statement.trim();
+        let (target, value) = match statement.split_once('=') {
+            Some((target, value)) => (target.trim(), value.trim()),
+            None => continue,
+        };
+        if let Some(alias) = target.strip_prefix("alias") {
+            aliases.push((key_name(alias.trim()), key_name(value)));
+        } else if let (Some(name), Ok(code)) = (key_name(target), value.parse::<u32>()) {
+            codes.insert(name, code);
+        }
+    }
+    for (alias, name) in aliases {
+        if let (Some(alias), Some(code)) = (alias, name.and_then(|name| codes.get(name).copied())) {
+            codes.insert(alias, code);
+        }
+    }
+    codes
+}
+
+fn key_name(text: &str) -> Option<&str> {
+    text.strip_prefix('<')?.strip_suffix('>')
+}
+
+/// Each `key <NAME> { ... };` block in a symbols section, as the name and the braces' body.
+fn key_blocks(section: &str) -> impl Iterator<Item = (&str, &str)> {
+    let mut rest = section;
+    std::iter::from_fn(move || {
+        loop {
+            let start = rest.find("key")?;
+            let after = &rest[start + 3..];
+            let at_word_start = rest[..start]
+                .chars()
+                .next_back()
+                .is_none_or(|ch| ch.is_whitespace() || ch == ';' || ch == '{');
+            rest = after;
+            let trimmed = after.trim_start();
+            if !at_word_start || !trimmed.starts_with('<') {
+                continue;
+            }
+            let name_end = trimmed.find('>')?;
+            let name = &trimmed[1..name_end];
+            let body = &trimmed[name_end + 1..];
+            let open = body.find('{')?;
+            let close = open + body[open..].find('}')?;
+            rest = &body[close + 1..];
+            return Some((name, &body[open + 1..close]));
+        }
+    })
+}
+
+/// The keysym named `name`: a named key, a character from its X11 keysym name, or a Unicode
+/// keysym written as `U20AC`.
+fn keysym(name: &str) -> Option<Keysym> {
+    let named = match name {
+        "Escape" => Some(Key::Escape),
+        "BackSpace" => Some(Key::Backspace),
+        "Tab" | "ISO_Left_Tab" => Some(Key::Tab),
+        "Return" | "KP_Enter" => Some(Key::Enter),
+        "F12" => Some(Key::F12),
+        "Home" => Some(Key::Home),
+        "Left" => Some(Key::Left),
+        "Right" => Some(Key::Right),
+        "End" => Some(Key::End),
+        "Delete" => Some(Key::Delete),
+        _ => None,
+    };
+    if let Some(key) = named {
+        return Some(Keysym::Named(key));
+    }
+    let mut chars = name.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next())
+        && ch.is_ascii_alphanumeric()
+    {
+        return Some(Keysym::Char(ch));
+    }
+    if let Some(hex) = name.strip_prefix('U')
+        && hex.len() >= 4
+        && let Ok(code) = u32::from_str_radix(hex, 16)
+    {
+        return char::from_u32(code)
+            .filter(|ch| !ch.is_control())
+            .map(Keysym::Char);
+    }
+    if name == "EuroSign" {
+        return Some(Keysym::Char('€'));
+    }
+    let position = |names: &[&str]| names.iter().position(|candidate| *candidate == name);
+    if let Some(index) = position(&ASCII_PUNCTUATION_KEYSYMS) {
+        return ASCII_PUNCTUATION.chars().nth(index).map(Keysym::Char);
+    }
+    position(&LATIN1_KEYSYMS)
+        .and_then(|index| char::from_u32(0xa0 + index as u32))
+        .map(Keysym::Char)
+}
+
+/// Keysym names of the printable ASCII characters that aren't letters or digits.
+const ASCII_PUNCTUATION: &str = " !\"#$%&'()*+,-./:;<=>?@[\\]^_`{|}~";
+const ASCII_PUNCTUATION_KEYSYMS: [&str; 33] = [
+    "space",
+    "exclam",
+    "quotedbl",
+    "numbersign",
+    "dollar",
+    "percent",
+    "ampersand",
+    "apostrophe",
+    "parenleft",
+    "parenright",
+    "asterisk",
+    "plus",
+    "comma",
+    "minus",
+    "period",
+    "slash",
+    "colon",
+    "semicolon",
+    "less",
+    "equal",
+    "greater",
+    "question",
+    "at",
+    "bracketleft",
+    "backslash",
+    "bracketright",
+    "asciicircum",
+    "underscore",
+    "grave",
+    "braceleft",
+    "bar",
+    "braceright",
+    "asciitilde",
+];
+
+/// Keysym names of U+00A0 to U+00FF, in order.
+const LATIN1_KEYSYMS: [&str; 96] = [
+    "nobreakspace",
+    "exclamdown",
+    "cent",
+    "sterling",
+    "currency",
+    "yen",
+    "brokenbar",
+    "section",
+    "diaeresis",
+    "copyright",
+    "ordfeminine",
+    "guillemotleft",
+    "notsign",
+    "hyphen",
+    "registered",
+    "macron",
+    "degree",
+    "plusminus",
+    "twosuperior",
+    "threesuperior",
+    "acute",
+    "mu",
+    "paragraph",
+    "periodcentered",
+    "cedilla",
+    "onesuperior",
+    "masculine",
+    "guillemotright",
+    "onequarter",
+    "onehalf",
+    "threequarters",
+    "questiondown",
+    "Agrave",
+    "Aacute",
+    "Acircumflex",
+    "Atilde",
+    "Adiaeresis",
+    "Aring",
+    "AE",
+    "Ccedilla",
+    "Egrave",
+    "Eacute",
+    "Ecircumflex",
+    "Ediaeresis",
+    "Igrave",
+    "Iacute",
+    "Icircumflex",
+    "Idiaeresis",
+    "ETH",
+    "Ntilde",
+    "Ograve",
+    "Oacute",
+    "Ocircumflex",
+    "Otilde",
+    "Odiaeresis",
+    "multiply",
+    "Oslash",
+    "Ugrave",
+    "Uacute",
+    "Ucircumflex",
+    "Udiaeresis",
+    "Yacute",
+    "THORN",
+    "ssharp",
+    "agrave",
+    "aacute",
+    "acircumflex",
+    "atilde",
+    "adiaeresis",
+    "aring",
+    "ae",
+    "ccedilla",
+    "egrave",
+    "eacute",
+    "ecircumflex",
+    "ediaeresis",
+    "igrave",
+    "iacute",
+    "icircumflex",
+    "idiaeresis",
+    "eth",
+    "ntilde",
+    "ograve",
+    "oacute",
+    "ocircumflex",
+    "otilde",
+    "odiaeresis",
+    "division",
+    "oslash",
+    "ugrave",
+    "uacute",
+    "ucircumflex",
+    "udiaeresis",
+    "yacute",
+    "thorn",
+    "ydiaeresis",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_letters_with_shift_and_caps_lock() {
+        assert_eq!(
+            key_input(None, 16, 0),
+            Some(KeyInput::plain(Key::Char('q')))
+        );
+        assert_eq!(
+            key_input(None, 16, MOD_SHIFT),
+            Some(KeyInput::plain(Key::Char('Q')))
+        );
+        assert_eq!(
+            key_input(None, 16, MOD_CAPS_LOCK),
+            Some(KeyInput::plain(Key::Char('Q')))
+        );
+        assert_eq!(
+            key_input(None, 2, MOD_CAPS_LOCK),
+            Some(KeyInput::plain(Key::Char('1')))
+        );
+        assert_eq!(
+            key_input(None, 52, MOD_SHIFT),
+            Some(KeyInput::plain(Key::Char('>')))
+        );
+    }
+
+    #[test]
+    fn reports_control_and_named_keys() {
+        assert_eq!(
+            key_input(None, 38, MOD_CONTROL),
+            Some(KeyInput {
+                key: Key::Char('l'),
+                ctrl: true,
+                alt: false,
+            })
+        );
+        assert_eq!(
+            key_input(None, 105, MOD_ALT),
+            Some(KeyInput {
+                key: Key::Left,
+                ctrl: false,
+                alt: true,
+            })
+        );
+        assert_eq!(key_input(None, 28, 0), Some(KeyInput::plain(Key::Enter)));
+        assert_eq!(key_input(None, 111, 0), Some(KeyInput::plain(Key::Delete)));
+        assert_eq!(key_input(None, 58, 0), None);
+    }
+
+    #[test]
+    fn translates_through_the_compositor_keymap() {
+        let keymap = Keymap::parse(
+            "xkb_keymap {\n\
+             xkb_keycodes \"(unnamed)\" {\n\
+             \tminimum = 8;\n\
+             \t<ESC> = 9;\n\
+             \t<AE01> = 10;\n\
+             \t<AD06> = 29;\n\
+             \t<AC10> = 47;\n\
+             \t<TLDE> = 49;\n\
+             \talias <AC12> = <BKSL>;\n\
+             \t<BKSL> = 51;\n\
+             };\n\
+             xkb_types \"(unnamed)\" { type \"ONE_LEVEL\" { modifiers= none; }; };\n\
+             xkb_symbols \"(unnamed)\" {\n\
+             \tname[group1]=\"German\";\n\
+             \tkey <ESC> {\t[ Escape ] };\n\
+             \tkey <AE01> {\t[ 1, exclam, onesuperior ] };\n\
+             \tkey <AD06> {\ttype= \"FOUR_LEVEL_ALPHABETIC\", symbols[1]= [ z, Z, leftarrow ] };\n\
+             \tkey <AC10> {\t[ odiaeresis, Odiaeresis ] };\n\
+             \tkey <TLDE> {\t[ asciicircum, degree ] };\n\
+             \tkey <AC12> {\t[ numbersign, apostrophe ] };\n\
+             \tmodifier_map Control { <LCTL> };\n\
+             };\n\
+             };\n",
+        )
+        .expect("keymap with keys");
+        let keymap = Some(&keymap);
+
+        assert_eq!(
+            key_input(keymap, 21, 0),
+            Some(KeyInput::plain(Key::Char('z')))
+        );
+        assert_eq!(
+            key_input(keymap, 39, MOD_SHIFT),
+            Some(KeyInput::plain(Key::Char('Ö')))
+        );
+        assert_eq!(
+            key_input(keymap, 39, MOD_CAPS_LOCK),
+            Some(KeyInput::plain(Key::Char('Ö')))
+        );
+        assert_eq!(
+            key_input(keymap, 2, MOD_SHIFT | MOD_CAPS_LOCK),
+            Some(KeyInput::plain(Key::Char('!')))
+        );
+        assert_eq!(
+            key_input(keymap, 41, MOD_SHIFT),
+            Some(KeyInput::plain(Key::Char('°')))
+        );
+        assert_eq!(
+            key_input(keymap, 43, 0),
+            Some(KeyInput::plain(Key::Char('#')))
+        );
+        assert_eq!(key_input(keymap, 1, 0), Some(KeyInput::plain(Key::Escape)));
+        // Keys the keymap leaves out still go through the US layout.
+        assert_eq!(
+            key_input(keymap, 16, 0),
+            Some(KeyInput::plain(Key::Char('q')))
+        );
+        assert_eq!(Keymap::parse("xkb_keymap { };"), None);
+    }
+}
@@ -1,12 +1,17 @@
 mod cairo;
 mod callbacks;
+mod keymap;
 mod painter;
 mod scale;
 mod scaled;
+mod selection;
 mod sys;
 
-use super::WindowOptions;
-use crate::app::App;
+use super::touch::TouchAction;
+use super::{FrameScheduler, FrameStep, FrameSurface, WindowIdentity, WindowOptions};
+use crate::app::{App, KeyInput, ZoomCommand};
+use crate::geom::Rect;
+use crate::image::{Argb32Image, RgbImage};
 use crate::render::Viewport;
 use core::ffi::{c_int, c_void};
 use std::ffi::CString;
@@ -15,18 +20,25 @@ use std::io;
 use std::os::fd::{AsRawFd, OwnedFd};
 use std::os::unix::fs::OpenOptionsExt;
 use std::path::PathBuf;
-use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use callbacks::{
-    CallbackState, REGISTRY_LISTENER, WL_BUFFER_LISTENER, XDG_SURFACE_LISTENER,
-    XDG_TOPLEVEL_LISTENER, add_proxy_listener, take_setup_error,
+    CallbackState, DATA_DEVICE_LISTENER, FRACTIONAL_SCALE_LISTENER, KeyAction,
+    PRIMARY_SELECTION_DEVICE_LISTENER, REGISTRY_LISTENER, SURFACE_LISTENER, WL_BUFFER_LISTENER,
+    WL_CALLBACK_LISTENER, XDG_SURFACE_LISTENER, XDG_TOPLEVEL_LISTENER, add_proxy_listener,
+    key_action, take_setup_error,
 };
 use painter::WaylandPainter;
-use scale::ScaleFactor;
+use scale::{BufferScale, ScaleFactor};
 use scaled::ScaledPainter;
+use selection::Selection;
 use sys::*;
 
-const SCREENSHOT_RESOURCE_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Shared-memory buffers per surface: one on screen, one being painted, and a spare for
+/// compositors that hold on to the last buffer until the next commit.
+const MAX_SHM_BUFFERS: usize = 3;
+/// Past this many stale rectangles, a buffer is refreshed in full.
+const MAX_STALE_RECTS: usize = 32;
 
 const POLLIN: i16 = 0x001;
 const POLLERR: i16 = 0x008;
@@ -111,6 +123,9 @@ fn run_window_with_display<A: App>(
     if !state.supports_argb8888 {
         return Err("Wayland wl_shm does not advertise WL_SHM_FORMAT_ARGB8888".to_owned());
     }
+    unsafe {
+        create_selection_devices(&mut state, state_ptr)?;
+    }
 
     let surface = unsafe { oab_wl_compositor_create_surface(state.compositor) };
     if surface.is_null() {
@@ -140,6 +155,7 @@ fn run_window_with_display<A: App>(
         .map_err(|_| "Internal error constructing app id".to_owned())?;
 
     unsafe {
+        add_proxy_listener(surface, &SURFACE_LISTENER, state_ptr, "wl_surface")?;
         add_proxy_listener(xdg_surface, &XDG_SURFACE_LISTENER, state_ptr, "xdg_surface")?;
         add_proxy_listener(
             xdg_toplevel,
@@ -151,13 +167,12 @@ fn run_window_with_display<A: App>(
         oab_xdg_toplevel_set_title(xdg_toplevel, title_cstr.as_ptr());
         oab_xdg_toplevel_set_app_id(xdg_toplevel, app_id_cstr.as_ptr());
     }
+    let mut identity = WindowIdentity::new(title);
 
-    let detected_scale = ScaleFactor::detect();
-    let buffer_scale = detected_scale.scale_int().max(1);
-    let scale = ScaleFactor::new((buffer_scale as u32).saturating_mul(1024));
-
+    let initial_scale = ScaleFactor::detect().scale_int().max(1);
+    let mut buffer_scale = BufferScale::Integer(initial_scale);
     unsafe {
-        oab_wl_surface_set_buffer_scale(surface, buffer_scale);
+        oab_wl_surface_set_buffer_scale(surface, initial_scale);
         oab_wl_surface_commit(surface);
     }
 
@@ -202,22 +217,30 @@ fn run_window_with_display<A: App>(
         };
     }
 
-    let mut viewport = Viewport {
-        width_px: scale.css_size_to_device_px(css_viewport.width_px),
-        height_px: scale.css_size_to_device_px(css_viewport.height_px),
-    };
+    // `OAB_SCALE` pins the scale; otherwise it follows the outputs the window is on.
+    let scale_pinned = ScaleFactor::from_env().is_some();
+    // Fractional scaling takes both protocols: one to hear the scale, one to present at it.
+    let (wp_viewport, fractional_scale) =
+        if state.viewporter.is_null() || state.fractional_scale_manager.is_null() || scale_pinned {
+            (std::ptr::null_mut(), std::ptr::null_mut())
+        } else {
+            unsafe { fractional_scale_objects(&state, state_ptr, surface) }
+        };
+
+    let mut viewport = buffer_scale.buffer_size(css_viewport);
+    // The surface keeps its size under page zoom; the page sees a viewport divided by `zoom`
+    // and is painted at the buffer scale multiplied by it.
+    let mut zoom_percent = app.zoom_percent();
+    let mut zoom = ScaleFactor::new(1024).zoomed(zoom_percent);
+    let mut scale = buffer_scale.factor().zoomed(zoom_percent);
+    let mut page_viewport = zoomed_viewport(css_viewport, zoom);
 
     let mut painter = WaylandPainter::new(viewport)?;
-    let mut shm_buffer: Option<ShmBuffer> = None;
+    let mut swap_chain = SwapChain::default();
 
-    let mut screenshot_path = options.screenshot_path;
-    let headless = options.headless;
+    let mut frames = FrameScheduler::new(&options);
 
     let loop_result = (|| {
-        let mut needs_redraw = true;
-        let mut has_rendered_ready_state = false;
-        let mut resource_wait_started: Option<Instant> = None;
-
         loop {
             dispatch_events(display, 0)?;
 
@@ -234,148 +257,82 @@ fn run_window_with_display<A: App>(
                         width_px: width_css,
                         height_px: height_css,
                     };
-                    viewport = Viewport {
-                        width_px: scale.css_size_to_device_px(width_css),
-                        height_px: scale.css_size_to_device_px(height_css),
-                    };
-                    needs_redraw = true;
-                    has_rendered_ready_state = false;
-                    resource_wait_started = None;
+                    viewport = buffer_scale.buffer_size(css_viewport);
+                    page_viewport = zoomed_viewport(css_viewport, zoom);
+                    frames.invalidate();
                 }
             }
 
-            consume_input_events(app, &mut state, css_viewport, &mut needs_redraw)?;
+            if !scale_pinned
+                && let Some(preferred) = preferred_buffer_scale(&state, !wp_viewport.is_null())
+                && preferred != buffer_scale
+            {
+                buffer_scale = preferred;
+                viewport = buffer_scale.buffer_size(css_viewport);
+                scale = buffer_scale.factor().zoomed(zoom_percent);
+                frames.invalidate();
+            }
 
-            let tick = app.tick()?;
-            if tick.needs_redraw {
-                needs_redraw = true;
+            consume_input_events(app, &mut state, page_viewport, zoom, &mut frames)?;
+            paste_selections(app, &mut state, display, page_viewport, &mut frames)?;
+            open_drop(app, &mut state, display, page_viewport, &mut frames)?;
+            if state.should_exit {
+                break;
             }
-            let ready_for_screenshot = tick.ready_for_screenshot;
-            if !ready_for_screenshot {
-                has_rendered_ready_state = false;
-                resource_wait_started = None;
+            if app.zoom_percent() != zoom_percent {
+                zoom_percent = app.zoom_percent();
+                zoom = ScaleFactor::new(1024).zoomed(zoom_percent);
+                scale = buffer_scale.factor().zoomed(zoom_percent);
+                page_viewport = zoomed_viewport(css_viewport, zoom);
+                frames.invalidate();
             }
 
-            let should_wait_for_resources = tick.pending_resources > 0;
-            let timed_out_waiting_for_resources = resource_wait_started
-                .is_some_and(|started| started.elapsed() >= SCREENSHOT_RESOURCE_WAIT_TIMEOUT);
-            let can_complete = !should_wait_for_resources || timed_out_waiting_for_resources;
-
-            let wants_screenshot = screenshot_path.is_some();
-            let should_complete_headless = headless && !wants_screenshot;
-            let should_complete_screenshot =
-                wants_screenshot && ready_for_screenshot && has_rendered_ready_state;
-
-            let mut capture_now = false;
-            let mut capture_after_render = false;
-            let mut exit_headless_now = false;
-
-            if ready_for_screenshot && (wants_screenshot || headless) && !has_rendered_ready_state {
-                needs_redraw = true;
-            } else if ready_for_screenshot && should_wait_for_resources && has_rendered_ready_state
+            let tick = app.tick()?;
+            if let Some(title) = identity.title_change(app)
+                && let Ok(title) = CString::new(title.replace('\0', ""))
             {
-                resource_wait_started.get_or_insert(Instant::now());
-            } else if ready_for_screenshot && has_rendered_ready_state {
-                resource_wait_started = None;
-            }
-
-            if ready_for_screenshot && has_rendered_ready_state && can_complete {
-                if should_complete_screenshot {
-                    if needs_redraw {
-                        capture_after_render = true;
-                    } else {
-                        capture_now = true;
-                    }
-                } else if should_complete_headless && !needs_redraw {
-                    exit_headless_now = true;
+                unsafe {
+                    oab_xdg_toplevel_set_title(xdg_toplevel, title.as_ptr());
                 }
             }
-
-            if exit_headless_now {
-                break;
+            if let Some(icon) = identity.icon_change(app) {
+                set_window_icon(&state, xdg_toplevel, &icon)?;
             }
-
-            if capture_now {
-                let Some(path) = screenshot_path.take() else {
-                    return Err(
-                        "Internal error: capture_now set but screenshot path missing".to_owned(),
-                    );
-                };
-                let rgb = painter.capture_back_buffer_rgb()?;
-                crate::png::write_rgb_png(&path, &rgb)?;
+            let mut frame_surface = WaylandSurface {
+                painter: &mut painter,
+                swap_chain: &mut swap_chain,
+                state: &mut state,
+                state_ptr,
+                display,
+                surface,
+                wp_viewport,
+                buffer_scale,
+                css_viewport,
+                viewport,
+                page_viewport,
+                scale,
+                headless: options.headless,
+            };
+            if frames.frame(app, tick, &mut frame_surface)? == FrameStep::Done {
                 break;
             }
 
-            let can_present = if headless { true } else { state.configured };
-            if needs_redraw && can_present {
-                painter.ensure_back_buffer(viewport)?;
-                let mut scaled_painter = ScaledPainter::new(&mut painter, scale);
-                app.render(&mut scaled_painter, css_viewport)?;
-                needs_redraw = false;
-
-                if !headless {
-                    let shm = state.shm;
-                    ensure_shm_buffer(
-                        &mut shm_buffer,
-                        &mut state,
-                        state_ptr,
-                        shm,
-                        viewport.width_px,
-                        viewport.height_px,
-                    )?;
-
-                    let buffer = shm_buffer
-                        .as_mut()
-                        .ok_or_else(|| "Internal error: shared-memory buffer missing".to_owned())?;
-                    copy_bgra_to_shm(buffer, painter.bgra())?;
-
-                    unsafe {
-                        oab_wl_surface_set_buffer_scale(surface, buffer_scale);
-                        oab_wl_surface_attach(surface, buffer.buffer, 0, 0);
-                        oab_wl_surface_damage_buffer(
-                            surface,
-                            0,
-                            0,
-                            viewport.width_px,
-                            viewport.height_px,
-                        );
-                        oab_wl_surface_commit(surface);
-                    }
-                    state.buffer_busy = true;
-
-                    flush_display(display)?;
-                }
-
-                if ready_for_screenshot {
-                    has_rendered_ready_state = true;
-                    if capture_after_render {
-                        let Some(path) = screenshot_path.take() else {
-                            return Err(
-                                "Internal error: capture_after_render set but screenshot path missing"
-                                    .to_owned(),
-                            );
-                        };
-                        let rgb = painter.capture_back_buffer_rgb()?;
-                        crate::png::write_rgb_png(&path, &rgb)?;
-                        break;
-                    }
-                }
-            }
-
-            if !needs_redraw {
-                dispatch_events(display, 10)?;
-                if state.should_exit {
-                    break;
-                }
+            dispatch_events(display, 10)?;
+            if state.should_exit {
+                break;
             }
         }
 
         Ok(())
     })();
 
-    drop(shm_buffer);
+    drop(swap_chain);
 
     unsafe {
+        if !state.frame_callback.is_null() {
+            wl_proxy_destroy(state.frame_callback.cast::<wl_proxy>());
+            state.frame_callback = std::ptr::null_mut();
+        }
         if !state.pointer.is_null() {
             wl_proxy_destroy(state.pointer.cast::<wl_proxy>());
             state.pointer = std::ptr::null_mut();
@@ -384,15 +341,67 @@ fn run_window_with_display<A: App>(
             wl_proxy_destroy(state.keyboard.cast::<wl_proxy>());
             state.keyboard = std::ptr::null_mut();
         }
+        if !state.touch.is_null() {
+            wl_proxy_destroy(state.touch.cast::<wl_proxy>());
+            state.touch = std::ptr::null_mut();
+        }
         if !state.seat.is_null() {
             wl_proxy_destroy(state.seat.cast::<wl_proxy>());
             state.seat = std::ptr::null_mut();
         }
 
+        if !fractional_scale.is_null() {
+            oab_wp_fractional_scale_destroy(fractional_scale);
+        }
+        if !wp_viewport.is_null() {
+            oab_wp_viewport_destroy(wp_viewport);
+        }
         oab_xdg_toplevel_destroy(xdg_toplevel);
         oab_xdg_surface_destroy(xdg_surface);
         oab_wl_surface_destroy(surface);
 
+        for offer in state
+            .offers
+            .drain(..)
+            .chain(state.clipboard.take())
+            .chain(state.primary_selection.take())
+            .chain(state.drag_offer.take())
+            .chain(state.pending_drop.take())
+        {
+            offer.destroy();
+        }
+        if !state.data_device.is_null() {
+            oab_wl_data_device_release(state.data_device);
+            state.data_device = std::ptr::null_mut();
+        }
+        if !state.primary_selection_device.is_null() {
+            oab_zwp_primary_selection_device_destroy(state.primary_selection_device);
+            state.primary_selection_device = std::ptr::null_mut();
+        }
+        if !state.data_device_manager.is_null() {
+            oab_wl_data_device_manager_destroy(state.data_device_manager);
+            state.data_device_manager = std::ptr::null_mut();
+        }
+        if !state.primary_selection_manager.is_null() {
+            oab_zwp_primary_selection_device_manager_destroy(state.primary_selection_manager);
+            state.primary_selection_manager = std::ptr::null_mut();
+        }
+        for output in state.outputs.drain(..) {
+            wl_proxy_destroy(output.proxy.cast::<wl_proxy>());
+        }
+        state.entered_outputs.clear();
+        if !state.viewporter.is_null() {
+            oab_wp_viewporter_destroy(state.viewporter);
+            state.viewporter = std::ptr::null_mut();
+        }
+        if !state.fractional_scale_manager.is_null() {
+            oab_wp_fractional_scale_manager_destroy(state.fractional_scale_manager);
+            state.fractional_scale_manager = std::ptr::null_mut();
+        }
+        if !state.icon_manager.is_null() {
+            oab_xdg_toplevel_icon_manager_destroy(state.icon_manager);
+            state.icon_manager = std::ptr::null_mut();
+        }
         if !state.wm_base.is_null() {
             oab_xdg_wm_base_destroy(state.wm_base);
             state.wm_base = std::ptr::null_mut();
@@ -411,97 +420,401 @@ fn run_window_with_display<A: App>(
     loop_result
 }
 
+/// The scale the compositor wants the surface at: the fractional one when it can be presented,
+/// else that of the densest output the surface is on.
+fn preferred_buffer_scale(state: &CallbackState, fractional: bool) -> Option<BufferScale> {
+    fractional
+        .then_some(state.preferred_scale_120)
+        .flatten()
+        .map(BufferScale::Fractional)
+        .or_else(|| state.output_scale().map(BufferScale::Integer))
+}
+
+/// A viewport and fractional-scale object for `surface`; both null if either can't be made.
+unsafe fn fractional_scale_objects(
+    state: &CallbackState,
+    state_ptr: *mut CallbackState,
+    surface: *mut wl_surface,
+) -> (*mut wp_viewport, *mut wp_fractional_scale_v1) {
+    let null = (std::ptr::null_mut(), std::ptr::null_mut());
+    let wp_viewport = unsafe { oab_wp_viewporter_get_viewport(state.viewporter, surface) };
+    if wp_viewport.is_null() {
+        return null;
+    }
+    let fractional_scale = unsafe {
+        oab_wp_fractional_scale_manager_get_fractional_scale(
+            state.fractional_scale_manager,
+            surface,
+        )
+    };
+    let listening = !fractional_scale.is_null()
+        && unsafe {
+            add_proxy_listener(
+                fractional_scale,
+                &FRACTIONAL_SCALE_LISTENER,
+                state_ptr,
+                "wp_fractional_scale_v1",
+            )
+        }
+        .is_ok();
+    if !listening {
+        unsafe {
+            if !fractional_scale.is_null() {
+                oab_wp_fractional_scale_destroy(fractional_scale);
+            }
+            oab_wp_viewport_destroy(wp_viewport);
+        }
+        return null;
+    }
+    (wp_viewport, fractional_scale)
+}
+
+/// The window's back buffer, presented through the swap chain once the compositor has shown the
+/// frame before; headless windows are never presented.
+struct WaylandSurface<'a> {
+    painter: &'a mut WaylandPainter,
+    swap_chain: &'a mut SwapChain,
+    state: &'a mut CallbackState,
+    state_ptr: *mut CallbackState,
+    display: *mut wl_display,
+    surface: *mut wl_surface,
+    wp_viewport: *mut wp_viewport,
+    buffer_scale: BufferScale,
+    css_viewport: Viewport,
+    viewport: Viewport,
+    page_viewport: Viewport,
+    scale: ScaleFactor,
+    headless: bool,
+}
+
+impl<A: App + ?Sized> FrameSurface<A> for WaylandSurface<'_> {
+    fn page_viewport(&self) -> Viewport {
+        self.page_viewport
+    }
+
+    // Windowed frames are paced by the compositor's frame callbacks.
+    fn can_present(&self) -> bool {
+        self.headless
+            || (self.state.configured
+                && self.state.frame_callback.is_null()
+                && self.swap_chain.can_acquire(
+                    self.state,
+                    self.viewport.width_px,
+                    self.viewport.height_px,
+                ))
+    }
+
+    fn render(&mut self, app: &mut A) -> Result<(), String> {
+        self.painter.ensure_back_buffer(self.viewport)?;
+        app.render(
+            &mut ScaledPainter::new(self.painter, self.scale),
+            self.page_viewport,
+        )
+    }
+
+    fn present(&mut self) -> Result<(), String> {
+        if self.headless {
+            return Ok(());
+        }
+        let damage = self.painter.take_damage();
+        self.swap_chain.add_damage(damage.as_deref());
+        let damage = damage.unwrap_or_else(|| {
+            vec![Rect {
+                x: 0,
+                y: 0,
+                width: self.viewport.width_px,
+                height: self.viewport.height_px,
+            }]
+        });
+        if damage.is_empty() {
+            return Ok(());
+        }
+        let shm = self.state.shm;
+        let buffer = self.swap_chain.acquire(
+            self.state,
+            self.state_ptr,
+            shm,
+            self.viewport.width_px,
+            self.viewport.height_px,
+        )?;
+        buffer.update_from(self.painter.bgra())?;
+
+        unsafe {
+            set_surface_scale(
+                self.surface,
+                self.wp_viewport,
+                self.buffer_scale,
+                self.css_viewport,
+            );
+            oab_wl_surface_attach(self.surface, buffer.shm.buffer, 0, 0);
+            for rect in &damage {
+                oab_wl_surface_damage_buffer(self.surface, rect.x, rect.y, rect.width, rect.height);
+            }
+            let callback = oab_wl_surface_frame(self.surface);
+            if !callback.is_null() {
+                add_proxy_listener(
+                    callback,
+                    &WL_CALLBACK_LISTENER,
+                    self.state_ptr,
+                    "wl_callback",
+                )?;
+                self.state.frame_callback = callback;
+            }
+            oab_wl_surface_commit(self.surface);
+        }
+        self.state.busy_buffers.push(buffer.shm.buffer);
+
+        flush_display(self.display)
+    }
+
+    fn capture(&mut self) -> Result<RgbImage, String> {
+        self.painter.capture_back_buffer_rgb()
+    }
+}
+
+/// Tells the compositor how the buffer about to be attached maps onto the `logical`-sized
+/// surface.
+unsafe fn set_surface_scale(
+    surface: *mut wl_surface,
+    wp_viewport: *mut wp_viewport,
+    buffer_scale: BufferScale,
+    logical: Viewport,
+) {
+    match buffer_scale {
+        BufferScale::Integer(scale) => unsafe {
+            oab_wl_surface_set_buffer_scale(surface, scale);
+        },
+        BufferScale::Fractional(_) => unsafe {
+            oab_wl_surface_set_buffer_scale(surface, 1);
+            oab_wp_viewport_set_destination(wp_viewport, logical.width_px, logical.height_px);
+        },
+    }
+}
+
+fn zoomed_viewport(surface_viewport: Viewport, zoom: ScaleFactor) -> Viewport {
+    Viewport {
+        width_px: zoom
+            .device_delta_to_css_px(surface_viewport.width_px)
+            .max(1),
+        height_px: zoom
+            .device_delta_to_css_px(surface_viewport.height_px)
+            .max(1),
+    }
+}
+
+/// Pointer positions and wheel deltas arrive in surface coordinates and are divided by `zoom`
+/// before they reach the page.
 fn consume_input_events<A: App>(
     app: &mut A,
     state: &mut CallbackState,
     css_viewport: Viewport,
-    needs_redraw: &mut bool,
+    zoom: ScaleFactor,
+    frames: &mut FrameScheduler,
 ) -> Result<(), String> {
+    if std::mem::take(&mut state.pointer_moved) {
+        let tick = app.mouse_move(
+            zoom.device_delta_to_css_px(state.pointer_x_css_px),
+            zoom.device_delta_to_css_px(state.pointer_y_css_px),
+            css_viewport,
+        )?;
+        frames.note(tick);
+    }
+
     let mouse_downs = std::mem::take(&mut state.pending_mouse_downs);
     for _ in 0..mouse_downs {
-        let tick = app.mouse_down(state.pointer_x_css_px, state.pointer_y_css_px, css_viewport)?;
-        if tick.needs_redraw {
-            *needs_redraw = true;
+        let tick = app.mouse_down(
+            zoom.device_delta_to_css_px(state.pointer_x_css_px),
+            zoom.device_delta_to_css_px(state.pointer_y_css_px),
+            css_viewport,
+        )?;
+        frames.note(tick);
+    }
+
+    let mouse_ups = std::mem::take(&mut state.pending_mouse_ups);
+    for _ in 0..mouse_ups {
+        let tick = app.mouse_up(
+            zoom.device_delta_to_css_px(state.pointer_x_css_px),
+            zoom.device_delta_to_css_px(state.pointer_y_css_px),
+            css_viewport,
+        )?;
+        frames.note(tick);
+    }
+
+    let keys = std::mem::take(&mut state.pending_keys);
+    for pending in keys {
+        let input = keymap::key_input(state.keymap.as_ref(), pending.key, pending.modifiers);
+        if let Some(command) = input.and_then(ZoomCommand::from_key) {
+            app.set_zoom_percent(command.apply(app.zoom_percent()));
+            continue;
+        }
+        if input.is_some_and(KeyInput::is_paste) {
+            state.pending_pastes.push(Selection::Clipboard);
+            continue;
+        }
+        if let Some(input) = input
+            && let Some(tick) = app.key_down(input, css_viewport)?
+        {
+            frames.note(tick);
+            continue;
+        }
+        match key_action(pending.key) {
+            KeyAction::NavigateBack => {
+                state.pending_back_navigations = state.pending_back_navigations.saturating_add(1);
+            }
+            KeyAction::Exit => {
+                state.should_exit = true;
+            }
+            KeyAction::None => {}
         }
     }
 
     let back_navigations = std::mem::take(&mut state.pending_back_navigations);
     for _ in 0..back_navigations {
         let tick = app.navigate_back()?;
-        if tick.needs_redraw {
-            *needs_redraw = true;
-        }
+        frames.note(tick);
+    }
+
+    let forward_navigations = std::mem::take(&mut state.pending_forward_navigations);
+    for _ in 0..forward_navigations {
+        let tick = app.navigate_forward()?;
+        frames.note(tick);
+    }
+
+    let notch_delta = std::mem::take(&mut state.pending_wheel_notch_px);
+    if notch_delta != 0 {
+        let tick = app.wheel_notch(zoom.device_delta_to_css_px(notch_delta), css_viewport)?;
+        frames.note(tick);
+    }
+
+    let horizontal_delta = std::mem::take(&mut state.pending_horizontal_wheel_px);
+    if horizontal_delta != 0 {
+        let tick =
+            app.horizontal_wheel(zoom.device_delta_to_css_px(horizontal_delta), css_viewport)?;
+        frames.note(tick);
     }
 
     let wheel_delta = std::mem::take(&mut state.pending_wheel_css_px);
     if wheel_delta != 0 {
-        let tick = app.mouse_wheel(wheel_delta, css_viewport)?;
-        if tick.needs_redraw {
-            *needs_redraw = true;
-        }
+        let tick = app.mouse_wheel(zoom.device_delta_to_css_px(wheel_delta), css_viewport)?;
+        frames.note(tick);
+    }
+
+    for action in std::mem::take(&mut state.pending_touch_actions) {
+        let tick = match action {
+            TouchAction::Tap { x_px, y_px } => {
+                let x_css = zoom.device_delta_to_css_px(x_px);
+                let y_css = zoom.device_delta_to_css_px(y_px);
+                frames.note(app.mouse_move(x_css, y_css, css_viewport)?);
+                app.mouse_down(x_css, y_css, css_viewport)?
+            }
+            TouchAction::Scroll { delta_y_px } => {
+                app.mouse_wheel(zoom.device_delta_to_css_px(delta_y_px), css_viewport)?
+            }
+            TouchAction::Fling {
+                velocity_y_px_per_s,
+            } => app.scroll_fling(
+                velocity_y_px_per_s / f64::from(zoom.device_pixel_ratio()),
+                css_viewport,
+            )?,
+            TouchAction::Zoom(command) => {
+                app.set_zoom_percent(command.apply(app.zoom_percent()));
+                continue;
+            }
+        };
+        frames.note(tick);
+    }
+
+    if let Some(velocity_px_per_s) = state.pending_fling_px_per_s.take() {
+        let velocity_css_px_per_s = velocity_px_per_s / f64::from(zoom.device_pixel_ratio());
+        let tick = app.scroll_fling(velocity_css_px_per_s, css_viewport)?;
+        frames.note(tick);
     }
 
     Ok(())
 }
 
-fn ensure_shm_buffer(
-    slot: &mut Option<ShmBuffer>,
+/// Listens for the seat's clipboard and primary selection, where the compositor has them.
+unsafe fn create_selection_devices(
     state: &mut CallbackState,
     state_ptr: *mut CallbackState,
-    shm: *mut wl_shm,
-    width_px: i32,
-    height_px: i32,
 ) -> Result<(), String> {
-    if width_px <= 0 || height_px <= 0 {
-        return Err(format!(
-            "Invalid Wayland buffer size: {}x{}",
-            width_px, height_px
-        ));
+    if state.seat.is_null() {
+        return Ok(());
     }
-
-    let needs_recreate = slot.as_ref().is_none_or(|buffer| {
-        buffer.width_px != width_px
-            || buffer.height_px != height_px
-            || (state.buffer_busy && state.buffer_ptr == buffer.buffer)
-    });
-
-    if needs_recreate {
-        if let Some(old) = slot.take()
-            && state.buffer_ptr == old.buffer
-        {
-            state.buffer_ptr = std::ptr::null_mut();
-            state.buffer_busy = false;
+    if !state.data_device_manager.is_null() {
+        let device = unsafe {
+            oab_wl_data_device_manager_get_data_device(state.data_device_manager, state.seat)
+        };
+        if !device.is_null() {
+            state.data_device = device;
+            unsafe {
+                add_proxy_listener(device, &DATA_DEVICE_LISTENER, state_ptr, "wl_data_device")?;
+            }
         }
-
-        let mut buffer = ShmBuffer::new(shm, width_px, height_px)?;
-
-        unsafe {
-            add_proxy_listener(buffer.buffer, &WL_BUFFER_LISTENER, state_ptr, "wl_buffer")?;
+    }
+    if !state.primary_selection_manager.is_null() {
+        let device = unsafe {
+            oab_zwp_primary_selection_device_manager_get_device(
+                state.primary_selection_manager,
+                state.seat,
+            )
+        };
+        if !device.is_null() {
+            state.primary_selection_device = device;
+            unsafe {
+                add_proxy_listener(
+                    device,
+                    &PRIMARY_SELECTION_DEVICE_LISTENER,
+                    state_ptr,
+                    "zwp_primary_selection_device_v1",
+                )?;
+            }
         }
-
-        state.buffer_ptr = buffer.buffer;
-        state.buffer_busy = false;
-        buffer.clear();
-
-        *slot = Some(buffer);
     }
-
     Ok(())
 }
 
-fn copy_bgra_to_shm(buffer: &mut ShmBuffer, bgra: &[u8]) -> Result<(), String> {
-    if bgra.len() != buffer.len {
-        return Err(format!(
-            "Wayland shared-memory buffer length mismatch: expected {}, got {}",
-            buffer.len,
-            bgra.len()
-        ));
+fn paste_selections<A: App>(
+    app: &mut A,
+    state: &mut CallbackState,
+    display: *mut wl_display,
+    css_viewport: Viewport,
+    frames: &mut FrameScheduler,
+) -> Result<(), String> {
+    for selection in std::mem::take(&mut state.pending_pastes) {
+        let offer = match selection {
+            Selection::Clipboard => state.clipboard.as_ref(),
+            Selection::Primary => state.primary_selection.as_ref(),
+        };
+        let Some(text) = offer.and_then(|offer| unsafe { offer.read_text(display) }) else {
+            continue;
+        };
+        let tick = app.paste_text(&text, css_viewport)?;
+        frames.note(tick);
     }
+    Ok(())
+}
 
+fn open_drop<A: App>(
+    app: &mut A,
+    state: &mut CallbackState,
+    display: *mut wl_display,
+    css_viewport: Viewport,
+    frames: &mut FrameScheduler,
+) -> Result<(), String> {
+    let Some(offer) = state.pending_drop.take() else {
+        return Ok(());
+    };
+    let dropped = unsafe { offer.read_drop(display) };
     unsafe {
-        std::ptr::copy_nonoverlapping(bgra.as_ptr(), buffer.data_ptr, bgra.len());
+        oab_wl_data_offer_finish(offer.proxy);
+        offer.destroy();
     }
-
+    let Some(dropped) = dropped else {
+        return Ok(());
+    };
+    let tick = app.open_dropped(&dropped, css_viewport)?;
+    frames.note(tick);
     Ok(())
 }
 
@@ -657,6 +970,200 @@ fn is_map_failed(ptr: *mut c_void) -> bool {
     ptr == (-1isize as *mut c_void)
 }
 
+/// The surface's shared-memory buffers, painted in turn so a frame is never written into a
+/// buffer the compositor is still reading.
+#[derive(Default)]
+struct SwapChain {
+    buffers: Vec<SwapBuffer>,
+}
+
+struct SwapBuffer {
+    shm: ShmBuffer,
+    /// Areas that changed since this buffer was last updated; `None` when all of it is stale.
+    stale: Option<Vec<Rect>>,
+}
+
+impl SwapChain {
+    fn can_acquire(&self, state: &CallbackState, width_px: i32, height_px: i32) -> bool {
+        let mut sized = 0usize;
+        for buffer in &self.buffers {
+            if buffer.shm.width_px != width_px || buffer.shm.height_px != height_px {
+                continue;
+            }
+            if !state.busy_buffers.contains(&buffer.shm.buffer) {
+                return true;
+            }
+            sized += 1;
+        }
+        sized < MAX_SHM_BUFFERS
+    }
+
+    /// Marks `damage` stale in every buffer; `None` marks all of them.
+    fn add_damage(&mut self, damage: Option<&[Rect]>) {
+        for buffer in &mut self.buffers {
+            match (&mut buffer.stale, damage) {
+                (Some(stale), Some(damage)) => {
+                    stale.extend_from_slice(damage);
+                    if stale.len() > MAX_STALE_RECTS {
+                        buffer.stale = None;
+                    }
+                }
+                (stale, None) => *stale = None,
+                (None, Some(_)) => {}
+            }
+        }
+    }
+
+    /// A released buffer of the given size, creating one when all of them are busy. Buffers
+    /// of another size are dropped.
+    fn acquire(
+        &mut self,
+        state: &mut CallbackState,
+        state_ptr: *mut CallbackState,
+        shm: *mut wl_shm,
+        width_px: i32,
+        height_px: i32,
+    ) -> Result<&mut SwapBuffer, String> {
+        self.buffers.retain(|buffer| {
+            let keep = buffer.shm.width_px == width_px && buffer.shm.height_px == height_px;
+            if !keep {
+                state.busy_buffers.retain(|busy| *busy != buffer.shm.buffer);
+            }
+            keep
+        });
+
+        let free = self
+            .buffers
+            .iter()
+            .position(|buffer| !state.busy_buffers.contains(&buffer.shm.buffer));
+        let index = match free {
+            Some(index) => index,
+            None => {
+                if self.buffers.len() >= MAX_SHM_BUFFERS {
+                    return Err("Internal error: every Wayland buffer is busy".to_owned());
+                }
+                let mut shm = ShmBuffer::new(shm, width_px, height_px)?;
+                unsafe {
+                    add_proxy_listener(shm.buffer, &WL_BUFFER_LISTENER, state_ptr, "wl_buffer")?;
+                }
+                shm.clear();
+                self.buffers.push(SwapBuffer { shm, stale: None });
+                self.buffers.len() - 1
+            }
+        };
+        Ok(&mut self.buffers[index])
+    }
+}
+
+impl SwapBuffer {
+    /// Copies the stale areas of `bgra`, the painter's back buffer, into this buffer.
+    fn update_from(&mut self, bgra: &[u8]) -> Result<(), String> {
+        let full = Rect {
+            x: 0,
+            y: 0,
+            width: self.shm.width_px,
+            height: self.shm.height_px,
+        };
+        for rect in self.stale.take().unwrap_or_else(|| vec![full]) {
+            copy_bgra_rect_to_shm(&mut self.shm, bgra, rect)?;
+        }
+        self.stale = Some(Vec::new());
+        Ok(())
+    }
+}
+
+fn copy_bgra_rect_to_shm(buffer: &mut ShmBuffer, bgra: &[u8], rect: Rect) -> Result<(), String> {
+    if bgra.len() != buffer.len {
+        return Err(format!(
+            "Wayland shared-memory buffer length mismatch: expected {}, got {}",
+            buffer.len,
+            bgra.len()
+        ));
+    }
+
+    let rect = rect.intersection(Rect {
+        x: 0,
+        y: 0,
+        width: buffer.width_px,
+        height: buffer.height_px,
+    });
+    if rect.is_empty() {
+        return Ok(());
+    }
+    let stride = buffer.width_px as usize * 4;
+    let row_len = rect.width as usize * 4;
+    for y in rect.y..rect.bottom() {
+        let offset = y as usize * stride + rect.x as usize * 4;
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bgra.as_ptr().add(offset),
+                buffer.data_ptr.add(offset),
+                row_len,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Hands `icon` to the compositor through xdg-toplevel-icon, when it supports that.
+fn set_window_icon(
+    state: &CallbackState,
+    toplevel: *mut xdg_toplevel,
+    icon: &Argb32Image,
+) -> Result<(), String> {
+    if state.icon_manager.is_null() {
+        return Ok(());
+    }
+    let icon = square_icon(icon);
+    let side = i32::try_from(icon.width).map_err(|_| "Window icon is too large".to_owned())?;
+    let mut buffer = ShmBuffer::new(state.shm, side, side)?;
+    copy_bgra_rect_to_shm(
+        &mut buffer,
+        &icon.data,
+        Rect {
+            x: 0,
+            y: 0,
+            width: side,
+            height: side,
+        },
+    )?;
+
+    let xdg_icon = unsafe { oab_xdg_toplevel_icon_manager_create_icon(state.icon_manager) };
+    if xdg_icon.is_null() {
+        return Err("xdg_toplevel_icon_manager_v1.create_icon returned null".to_owned());
+    }
+    // The icon stays set once its object is gone, and the buffer only has to outlive the object.
+    unsafe {
+        oab_xdg_toplevel_icon_add_buffer(xdg_icon, buffer.buffer, 1);
+        oab_xdg_toplevel_icon_manager_set_icon(state.icon_manager, toplevel, xdg_icon);
+        oab_xdg_toplevel_icon_destroy(xdg_icon);
+    }
+    drop(buffer);
+    Ok(())
+}
+
+/// Icon buffers must be square, so a wide or tall image is centred on a transparent square.
+fn square_icon(icon: &Argb32Image) -> Argb32Image {
+    let side = icon.width.max(icon.height);
+    if icon.width == icon.height {
+        return icon.clone();
+    }
+    let stride = side as usize * 4;
+    let x = (side - icon.width) as usize / 2;
+    let y = (side - icon.height) as usize / 2;
+    let mut data = vec![0; stride * side as usize];
+    for (row, pixels) in icon.data.chunks_exact(icon.row_stride_bytes()).enumerate() {
+        let start = (y + row) * stride + x * 4;
+        data[start..start + pixels.len()].copy_from_slice(pixels);
+    }
+    Argb32Image {
+        width: side,
+        height: side,
+        data,
+    }
+}
+
 struct ShmBuffer {
     buffer: *mut wl_buffer,
     data_ptr: *mut u8,
@@ -768,3 +1275,17 @@ impl Drop for ShmBuffer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::square_icon;
+    use crate::image::Argb32Image;
+
+    #[test]
+    fn wide_icons_are_centred_on_a_square() {
+        let icon = Argb32Image::new(2, 1, [[1; 4], [2; 4]].concat()).unwrap();
+        let square = square_icon(&icon);
+        assert_eq!((square.width, square.height), (2, 2));
+        assert_eq!(square.data, [[1; 4], [2; 4], [0; 4], [0; 4]].concat());
+    }
+}
@@ -1,6 +1,9 @@
-use crate::geom::Color;
+use crate::geom::{Color, Rect};
 use crate::image::{Argb32Image, RgbImage};
-use crate::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use crate::render::{
+    FillRule, FontMetricsPx, Painter, PathCommand, StrokeStyle, TextMeasurer, TextStyle, Transform,
+    Viewport,
+};
 
 use super::cairo::CairoCanvas;
 
@@ -10,6 +13,15 @@ pub struct WaylandPainter {
     bgra: Vec<u8>,
     cairo: CairoCanvas,
     opacity_depth: usize,
+    clip_depth: usize,
+    transform_depth: usize,
+    clip: Option<Rect>,
+    /// Whether the back buffer holds the last flushed frame; false while it is new.
+    retained: bool,
+    /// Areas painted since the last presented frame, which is all of them once `damaged_all` is
+    /// set.
+    damage: Vec<Rect>,
+    damaged_all: bool,
 }
 
 impl WaylandPainter {
@@ -23,6 +35,12 @@ impl WaylandPainter {
             bgra,
             cairo,
             opacity_depth: 0,
+            clip_depth: 0,
+            transform_depth: 0,
+            clip: None,
+            retained: false,
+            damage: Vec::new(),
+            damaged_all: true,
         })
     }
 
@@ -38,6 +56,9 @@ impl WaylandPainter {
         self.cairo
             .recreate_image(self.width_px, self.height_px, &mut self.bgra)?;
         self.opacity_depth = 0;
+        self.clip_depth = 0;
+        self.transform_depth = 0;
+        self.retained = false;
         Ok(())
     }
 
@@ -69,6 +90,29 @@ impl WaylandPainter {
     pub fn bgra(&self) -> &[u8] {
         &self.bgra
     }
+
+    /// The areas painted since the last call, clipped to the buffer; `None` when the whole buffer
+    /// has to be presented.
+    pub fn take_damage(&mut self) -> Option<Vec<Rect>> {
+        let damaged_all = std::mem::take(&mut self.damaged_all);
+        let damage = std::mem::take(&mut self.damage);
+        if damaged_all {
+            return None;
+        }
+        let full = Rect {
+            x: 0,
+            y: 0,
+            width: self.width_px,
+            height: self.height_px,
+        };
+        Some(
+            damage
+                .into_iter()
+                .map(|rect| rect.intersection(full))
+                .filter(|rect| !rect.is_empty())
+                .collect(),
+        )
+    }
 }
 
 impl TextMeasurer for WaylandPainter {
@@ -83,6 +127,11 @@ impl TextMeasurer for WaylandPainter {
 
 impl Painter for WaylandPainter {
     fn clear(&mut self) -> Result<(), String> {
+        match self.clip {
+            Some(clip) if !self.damaged_all => self.damage.push(clip),
+            Some(_) => {}
+            None => self.damaged_all = true,
+        }
         self.fill_rect(0, 0, self.width_px, self.height_px, Color::WHITE)
     }
 
@@ -207,20 +256,74 @@ impl Painter for WaylandPainter {
         Ok(())
     }
 
-    fn draw_svg(
+    fn push_clip_rect(
         &mut self,
         x_px: i32,
         y_px: i32,
         width_px: i32,
         height_px: i32,
-        svg_xml: &str,
-        opacity: u8,
     ) -> Result<(), String> {
-        self.cairo
-            .draw_svg(x_px, y_px, width_px, height_px, svg_xml, opacity)
+        self.clip_depth = self.clip_depth.saturating_add(1);
+        self.cairo.push_clip_rect(x_px, y_px, width_px, height_px);
+        Ok(())
+    }
+
+    fn pop_clip(&mut self) -> Result<(), String> {
+        if self.clip_depth == 0 {
+            return Err("clip stack underflow".to_owned());
+        }
+        self.clip_depth -= 1;
+        self.cairo.restore();
+        Ok(())
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        self.transform_depth = self.transform_depth.saturating_add(1);
+        self.cairo.push_transform(transform);
+        Ok(())
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        if self.transform_depth == 0 {
+            return Err("transform stack underflow".to_owned());
+        }
+        self.transform_depth -= 1;
+        self.cairo.restore();
+        Ok(())
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        self.cairo.fill_path(path, rule, color);
+        Ok(())
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        self.cairo.stroke_path(path, style, color);
+        Ok(())
+    }
+
+    fn retains_frame(&self) -> bool {
+        self.retained
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), String> {
+        self.clip = clip;
+        self.cairo.set_clip(clip);
+        Ok(())
     }
 
     fn flush(&mut self) -> Result<(), String> {
+        self.retained = true;
         Ok(())
     }
 }
@@ -1,6 +1,10 @@
+use crate::render::Viewport;
+
 const SCALE_ONE_1024: u32 = 1024;
 const MIN_SCALE_1024: u32 = 256; // 0.25x
 const MAX_SCALE_1024: u32 = 8192; // 8.0x
+/// wp_fractional_scale_v1 reports scales in 120ths, e.g. 150 for 125%.
+const FRACTIONAL_SCALE_ONE: i64 = 120;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) struct ScaleFactor {
@@ -9,10 +13,12 @@ pub(super) struct ScaleFactor {
 
 impl ScaleFactor {
     pub fn detect() -> Self {
-        if let Some(scale) = scale_from_env() {
-            return Self::new(scale);
-        }
-        Self::new(SCALE_ONE_1024)
+        Self::from_env().unwrap_or(Self::new(SCALE_ONE_1024))
+    }
+
+    /// The scale `OAB_SCALE` asks for, which takes precedence over the compositor's.
+    pub fn from_env() -> Option<Self> {
+        scale_from_env().map(Self::new)
     }
 
     pub fn new(scale_1024: u32) -> Self {
@@ -23,6 +29,17 @@ impl ScaleFactor {
         Self { scale_1024 }
     }
 
+    /// Device pixels per CSS pixel, as CSS `resolution` media queries see it.
+    pub fn device_pixel_ratio(self) -> f32 {
+        self.scale_1024 as f32 / 1024.0
+    }
+
+    /// The scale with page zoom applied on top, e.g. `zoomed(125)` for 125%.
+    pub fn zoomed(self, percent: u32) -> Self {
+        let scaled = u64::from(self.scale_1024) * u64::from(percent) / 100;
+        Self::new(u32::try_from(scaled).unwrap_or(u32::MAX))
+    }
+
     pub fn scale_int(self) -> i32 {
         ((self.scale_1024 + 512) / 1024) as i32
     }
@@ -63,6 +80,56 @@ impl ScaleFactor {
     }
 }
 
+/// How the surface's buffer maps onto its size in logical pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum BufferScale {
+    /// `wl_surface.set_buffer_scale`: the buffer is a whole multiple of the logical size.
+    Integer(i32),
+    /// The compositor's preferred scale in 120ths; wp_viewporter maps the buffer onto the
+    /// logical size, so it can be a fraction larger.
+    Fractional(u32),
+}
+
+impl BufferScale {
+    pub fn factor(self) -> ScaleFactor {
+        match self {
+            BufferScale::Integer(scale) => {
+                ScaleFactor::new((scale.max(1) as u32).saturating_mul(SCALE_ONE_1024))
+            }
+            BufferScale::Fractional(scale_120) => ScaleFactor::new(clamp_i64_to_u32(
+                mul_div_round_nearest(i64::from(scale_120), 1024, FRACTIONAL_SCALE_ONE),
+            )),
+        }
+    }
+
+    /// The buffer size for a surface `logical` pixels in size. Fractional sizes round as the
+    /// protocol specifies, so the compositor maps buffer pixels to output pixels one to one.
+    pub fn buffer_size(self, logical: Viewport) -> Viewport {
+        match self {
+            BufferScale::Integer(_) => Viewport {
+                width_px: self.factor().css_size_to_device_px(logical.width_px),
+                height_px: self.factor().css_size_to_device_px(logical.height_px),
+            },
+            BufferScale::Fractional(scale_120) => {
+                let scale = |logical_px: i32| {
+                    clamp_i64_to_i32(
+                        mul_div_round_nearest(
+                            i64::from(logical_px),
+                            i64::from(scale_120),
+                            FRACTIONAL_SCALE_ONE,
+                        )
+                        .max(1),
+                    )
+                };
+                Viewport {
+                    width_px: scale(logical.width_px),
+                    height_px: scale(logical.height_px),
+                }
+            }
+        }
+    }
+}
+
 fn scale_from_env() -> Option<u32> {
     let value = std::env::var("OAB_SCALE").ok()?;
     let value = value.trim();
@@ -85,16 +152,16 @@ fn scale_from_env() -> Option<u32> {
     Some(clamp_i64_to_u32(scale_1024))
 }
 
+/// Rounds halves up rather than away from zero, so shifting a span by whole device pixels never
+/// changes its rounded width: an edge lands on the same pixel above and below the origin.
 fn mul_div_round_nearest(value: i64, num: i64, den: i64) -> i64 {
-    if den == 0 {
+    if den <= 0 {
         return 0;
     }
-    let product = value.saturating_mul(num);
-    if product >= 0 {
-        (product + den / 2) / den
-    } else {
-        (product - den / 2) / den
-    }
+    value
+        .saturating_mul(num)
+        .saturating_add(den / 2)
+        .div_euclid(den)
 }
 
 fn clamp_i64_to_u32(value: i64) -> u32 {
@@ -116,3 +183,34 @@ fn clamp_i64_to_i32(value: i64) -> i32 {
     }
     value as i32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferScale, ScaleFactor};
+    use crate::render::Viewport;
+
+    #[test]
+    fn equal_spans_round_to_equal_widths_on_either_side_of_the_origin() {
+        let scale = ScaleFactor::new(1280);
+        assert_eq!(scale.css_span_to_device_px(-2, 4), (-2, 5));
+        assert_eq!(scale.css_span_to_device_px(2, 4), (3, 5));
+        assert_eq!(scale.css_span_to_device_px(6, 4), (8, 5));
+    }
+
+    #[test]
+    fn fractional_buffers_round_the_logical_size_by_the_preferred_scale() {
+        let logical = Viewport {
+            width_px: 1001,
+            height_px: 3,
+        };
+        let buffer = BufferScale::Fractional(150).buffer_size(logical);
+        assert_eq!((buffer.width_px, buffer.height_px), (1251, 4));
+        assert_eq!(
+            BufferScale::Fractional(150).factor(),
+            ScaleFactor::new(1280)
+        );
+
+        let buffer = BufferScale::Integer(2).buffer_size(logical);
+        assert_eq!((buffer.width_px, buffer.height_px), (2002, 6));
+    }
+}
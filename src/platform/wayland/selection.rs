@@ -0,0 +1,154 @@
+use super::sys::*;
+use super::{POLLIN, PollFd, flush_display, poll};
+use std::ffi::CStr;
+use std::io::Read;
+use std::os::fd::AsRawFd;
+
+/// Text types a paste asks for, most preferred first.
+const TEXT_MIME_TYPES: [&CStr; 3] = [c"text/plain;charset=utf-8", c"UTF8_STRING", c"text/plain"];
+/// What a drop of files or links from another application carries.
+const URI_LIST_MIME_TYPE: &CStr = c"text/uri-list";
+/// Longest paste or drop read; the rest of a larger one is dropped.
+const MAX_PASTE_BYTES: u64 = 4 << 20;
+/// How long a paste or drop waits on the owner for more text before giving up.
+const READ_TIMEOUT_MS: i32 = 1000;
+
+/// Which selection a paste reads: the clipboard for Ctrl+V, the primary selection for a middle
+/// click.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Selection {
+    Clipboard,
+    Primary,
+}
+
+/// Data another client offers, as a `wl_data_offer` for the clipboard or a
+/// `zwp_primary_selection_offer_v1` for the primary selection.
+#[derive(Debug)]
+pub(super) struct Offer {
+    pub(super) proxy: *mut wl_proxy,
+    selection: Selection,
+    /// The most preferred of `TEXT_MIME_TYPES` on offer.
+    text_type: Option<usize>,
+    uri_list: bool,
+}
+
+impl Offer {
+    pub(super) fn new(proxy: *mut wl_proxy, selection: Selection) -> Self {
+        Self {
+            proxy,
+            selection,
+            text_type: None,
+            uri_list: false,
+        }
+    }
+
+    pub(super) fn add_mime_type(&mut self, mime_type: &CStr) {
+        if mime_type == URI_LIST_MIME_TYPE {
+            self.uri_list = true;
+        }
+        let Some(rank) = TEXT_MIME_TYPES.iter().position(|&text| text == mime_type) else {
+            return;
+        };
+        if self.text_type.is_none_or(|best| rank < best) {
+            self.text_type = Some(rank);
+        }
+    }
+
+    /// The type a drop would be read as: a list of files or links where there is one, or else
+    /// text. `None` when the offer has neither and a drop should be refused.
+    pub(super) fn drop_mime_type(&self) -> Option<&'static CStr> {
+        if self.uri_list {
+            return Some(URI_LIST_MIME_TYPE);
+        }
+        self.text_type.map(|rank| TEXT_MIME_TYPES[rank])
+    }
+
+    /// Asks the owner for the text and reads it from a pipe; `None` when nothing textual is on
+    /// offer or the owner doesn't answer in time.
+    pub(super) unsafe fn read_text(&self, display: *mut wl_display) -> Option<String> {
+        unsafe { self.read(display, TEXT_MIME_TYPES[self.text_type?]) }
+    }
+
+    /// Reads a drop as `drop_mime_type`.
+    pub(super) unsafe fn read_drop(&self, display: *mut wl_display) -> Option<String> {
+        unsafe { self.read(display, self.drop_mime_type()?) }
+    }
+
+    unsafe fn read(&self, display: *mut wl_display, mime_type: &CStr) -> Option<String> {
+        let mime_type = mime_type.as_ptr();
+        let (reader, writer) = std::io::pipe().ok()?;
+        unsafe {
+            match self.selection {
+                Selection::Clipboard => {
+                    oab_wl_data_offer_receive(self.proxy, mime_type, writer.as_raw_fd());
+                }
+                Selection::Primary => {
+                    oab_zwp_primary_selection_offer_receive(
+                        self.proxy,
+                        mime_type,
+                        writer.as_raw_fd(),
+                    );
+                }
+            }
+        }
+        // The compositor has its own copy of the write end once the request is sent.
+        flush_display(display).ok()?;
+        drop(writer);
+
+        let mut bytes = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let mut reader = reader.take(MAX_PASTE_BYTES);
+        loop {
+            let mut pollfd = PollFd {
+                fd: reader.get_ref().as_raw_fd(),
+                events: POLLIN,
+                revents: 0,
+            };
+            if unsafe { poll(&mut pollfd, 1, READ_TIMEOUT_MS) } <= 0 {
+                return None;
+            }
+            match reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(len) => bytes.extend_from_slice(&chunk[..len]),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(_) => return None,
+            }
+        }
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    pub(super) unsafe fn destroy(self) {
+        unsafe {
+            match self.selection {
+                Selection::Clipboard => oab_wl_data_offer_destroy(self.proxy),
+                Selection::Primary => oab_zwp_primary_selection_offer_destroy(self.proxy),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Offer, Selection};
+
+    #[test]
+    fn offers_keep_the_most_preferred_text_type() {
+        let mut offer = Offer::new(std::ptr::null_mut(), Selection::Clipboard);
+        offer.add_mime_type(c"image/png");
+        assert_eq!(offer.text_type, None);
+        offer.add_mime_type(c"text/plain");
+        offer.add_mime_type(c"text/plain;charset=utf-8");
+        offer.add_mime_type(c"UTF8_STRING");
+        assert_eq!(offer.text_type, Some(0));
+    }
+
+    #[test]
+    fn drops_prefer_a_uri_list_over_text() {
+        let mut offer = Offer::new(std::ptr::null_mut(), Selection::Clipboard);
+        assert_eq!(offer.drop_mime_type(), None);
+        offer.add_mime_type(c"text/plain");
+        assert_eq!(offer.drop_mime_type(), Some(c"text/plain"));
+        offer.add_mime_type(c"text/uri-list");
+        assert_eq!(offer.drop_mime_type(), Some(c"text/uri-list"));
+    }
+}
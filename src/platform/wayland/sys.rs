@@ -40,16 +40,31 @@ impl<const N: usize> InterfaceTypeList<N> {
 pub type wl_display = wl_proxy;
 pub type wl_registry = wl_proxy;
 pub type wl_compositor = wl_proxy;
+pub type wl_output = wl_proxy;
 pub type wl_surface = wl_proxy;
 pub type wl_shm = wl_proxy;
 pub type wl_shm_pool = wl_proxy;
 pub type wl_buffer = wl_proxy;
+pub type wl_callback = wl_proxy;
 pub type wl_seat = wl_proxy;
 pub type wl_pointer = wl_proxy;
 pub type wl_keyboard = wl_proxy;
+pub type wl_touch = wl_proxy;
 pub type xdg_wm_base = wl_proxy;
 pub type xdg_surface = wl_proxy;
 pub type xdg_toplevel = wl_proxy;
+pub type xdg_toplevel_icon_manager_v1 = wl_proxy;
+pub type xdg_toplevel_icon_v1 = wl_proxy;
+pub type wp_viewporter = wl_proxy;
+pub type wp_viewport = wl_proxy;
+pub type wp_fractional_scale_manager_v1 = wl_proxy;
+pub type wp_fractional_scale_v1 = wl_proxy;
+pub type wl_data_device_manager = wl_proxy;
+pub type wl_data_device = wl_proxy;
+pub type wl_data_offer = wl_proxy;
+pub type zwp_primary_selection_device_manager_v1 = wl_proxy;
+pub type zwp_primary_selection_device_v1 = wl_proxy;
+pub type zwp_primary_selection_offer_v1 = wl_proxy;
 
 pub type wl_fixed_t = i32;
 
@@ -85,6 +100,53 @@ pub struct wl_buffer_listener {
     pub release: Option<unsafe extern "C" fn(data: *mut c_void, buffer: *mut wl_buffer)>,
 }
 
+#[repr(C)]
+pub struct wl_callback_listener {
+    pub done: Option<
+        unsafe extern "C" fn(data: *mut c_void, callback: *mut wl_callback, callback_data: u32),
+    >,
+}
+
+#[repr(C)]
+pub struct wl_output_listener {
+    pub geometry: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            output: *mut wl_output,
+            x: i32,
+            y: i32,
+            physical_width: i32,
+            physical_height: i32,
+            subpixel: i32,
+            make: *const c_char,
+            model: *const c_char,
+            transform: i32,
+        ),
+    >,
+    pub mode: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            output: *mut wl_output,
+            flags: u32,
+            width: i32,
+            height: i32,
+            refresh: i32,
+        ),
+    >,
+    pub done: Option<unsafe extern "C" fn(data: *mut c_void, output: *mut wl_output)>,
+    pub scale: Option<unsafe extern "C" fn(data: *mut c_void, output: *mut wl_output, factor: i32)>,
+}
+
+#[repr(C)]
+pub struct wl_surface_listener {
+    pub enter: Option<
+        unsafe extern "C" fn(data: *mut c_void, surface: *mut wl_surface, output: *mut wl_output),
+    >,
+    pub leave: Option<
+        unsafe extern "C" fn(data: *mut c_void, surface: *mut wl_surface, output: *mut wl_output),
+    >,
+}
+
 #[repr(C)]
 pub struct wl_seat_listener {
     pub capabilities:
@@ -217,6 +279,156 @@ pub struct wl_keyboard_listener {
     >,
 }
 
+#[repr(C)]
+pub struct wl_touch_listener {
+    pub down: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            touch: *mut wl_touch,
+            serial: u32,
+            time: u32,
+            surface: *mut wl_surface,
+            id: i32,
+            x: wl_fixed_t,
+            y: wl_fixed_t,
+        ),
+    >,
+    pub up: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            touch: *mut wl_touch,
+            serial: u32,
+            time: u32,
+            id: i32,
+        ),
+    >,
+    pub motion: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            touch: *mut wl_touch,
+            time: u32,
+            id: i32,
+            x: wl_fixed_t,
+            y: wl_fixed_t,
+        ),
+    >,
+    pub frame: Option<unsafe extern "C" fn(data: *mut c_void, touch: *mut wl_touch)>,
+    pub cancel: Option<unsafe extern "C" fn(data: *mut c_void, touch: *mut wl_touch)>,
+    pub shape: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            touch: *mut wl_touch,
+            id: i32,
+            major: wl_fixed_t,
+            minor: wl_fixed_t,
+        ),
+    >,
+    pub orientation: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            touch: *mut wl_touch,
+            id: i32,
+            orientation: wl_fixed_t,
+        ),
+    >,
+}
+
+#[repr(C)]
+pub struct wp_fractional_scale_v1_listener {
+    pub preferred_scale: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            fractional_scale: *mut wp_fractional_scale_v1,
+            scale: u32,
+        ),
+    >,
+}
+
+#[repr(C)]
+pub struct wl_data_device_listener {
+    pub data_offer: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            device: *mut wl_data_device,
+            offer: *mut wl_data_offer,
+        ),
+    >,
+    pub enter: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            device: *mut wl_data_device,
+            serial: u32,
+            surface: *mut wl_surface,
+            x: wl_fixed_t,
+            y: wl_fixed_t,
+            offer: *mut wl_data_offer,
+        ),
+    >,
+    pub leave: Option<unsafe extern "C" fn(data: *mut c_void, device: *mut wl_data_device)>,
+    pub motion: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            device: *mut wl_data_device,
+            time: u32,
+            x: wl_fixed_t,
+            y: wl_fixed_t,
+        ),
+    >,
+    pub drop: Option<unsafe extern "C" fn(data: *mut c_void, device: *mut wl_data_device)>,
+    pub selection: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            device: *mut wl_data_device,
+            offer: *mut wl_data_offer,
+        ),
+    >,
+}
+
+#[repr(C)]
+pub struct wl_data_offer_listener {
+    pub offer: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            offer: *mut wl_data_offer,
+            mime_type: *const c_char,
+        ),
+    >,
+    pub source_actions: Option<
+        unsafe extern "C" fn(data: *mut c_void, offer: *mut wl_data_offer, source_actions: u32),
+    >,
+    pub action:
+        Option<unsafe extern "C" fn(data: *mut c_void, offer: *mut wl_data_offer, dnd_action: u32)>,
+}
+
+#[repr(C)]
+pub struct zwp_primary_selection_device_v1_listener {
+    pub data_offer: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            device: *mut zwp_primary_selection_device_v1,
+            offer: *mut zwp_primary_selection_offer_v1,
+        ),
+    >,
+    pub selection: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            device: *mut zwp_primary_selection_device_v1,
+            offer: *mut zwp_primary_selection_offer_v1,
+        ),
+    >,
+}
+
+#[repr(C)]
+pub struct zwp_primary_selection_offer_v1_listener {
+    pub offer: Option<
+        unsafe extern "C" fn(
+            data: *mut c_void,
+            offer: *mut zwp_primary_selection_offer_v1,
+            mime_type: *const c_char,
+        ),
+    >,
+}
+
 #[repr(C)]
 pub struct xdg_wm_base_listener {
     pub ping:
@@ -262,13 +474,22 @@ pub const WL_SHM_FORMAT_ARGB8888: u32 = 0;
 
 pub const WL_SEAT_CAPABILITY_POINTER: u32 = 1;
 pub const WL_SEAT_CAPABILITY_KEYBOARD: u32 = 2;
+pub const WL_SEAT_CAPABILITY_TOUCH: u32 = 4;
 
+pub const WL_KEYBOARD_KEYMAP_FORMAT_XKB_V1: u32 = 1;
 pub const WL_KEYBOARD_KEY_STATE_PRESSED: u32 = 1;
 pub const WL_POINTER_BUTTON_STATE_PRESSED: u32 = 1;
 pub const WL_POINTER_AXIS_VERTICAL_SCROLL: u32 = 0;
+pub const WL_POINTER_AXIS_HORIZONTAL_SCROLL: u32 = 1;
+pub const WL_POINTER_AXIS_SOURCE_WHEEL: u32 = 0;
+pub const WL_POINTER_AXIS_SOURCE_FINGER: u32 = 1;
+
+pub const WL_DATA_DEVICE_MANAGER_DND_ACTION_COPY: u32 = 1;
 
 pub const BTN_LEFT: u32 = 0x110;
+pub const BTN_MIDDLE: u32 = 0x112;
 pub const BTN_SIDE: u32 = 0x113;
+pub const BTN_EXTRA: u32 = 0x114;
 
 const WL_MARSHAL_FLAG_DESTROY: c_uint = 1 << 0;
 
@@ -282,11 +503,13 @@ const WL_SHM_RELEASE: c_uint = 1;
 const WL_BUFFER_DESTROY: c_uint = 0;
 const WL_SURFACE_DESTROY: c_uint = 0;
 const WL_SURFACE_ATTACH: c_uint = 1;
+const WL_SURFACE_FRAME: c_uint = 3;
 const WL_SURFACE_COMMIT: c_uint = 6;
 const WL_SURFACE_SET_BUFFER_SCALE: c_uint = 8;
 const WL_SURFACE_DAMAGE_BUFFER: c_uint = 9;
 const WL_SEAT_GET_POINTER: c_uint = 0;
 const WL_SEAT_GET_KEYBOARD: c_uint = 1;
+const WL_SEAT_GET_TOUCH: c_uint = 2;
 const XDG_WM_BASE_DESTROY: c_uint = 0;
 const XDG_WM_BASE_GET_XDG_SURFACE: c_uint = 2;
 const XDG_WM_BASE_PONG: c_uint = 3;
@@ -296,6 +519,30 @@ const XDG_SURFACE_ACK_CONFIGURE: c_uint = 4;
 const XDG_TOPLEVEL_DESTROY: c_uint = 0;
 const XDG_TOPLEVEL_SET_TITLE: c_uint = 2;
 const XDG_TOPLEVEL_SET_APP_ID: c_uint = 3;
+const XDG_TOPLEVEL_ICON_MANAGER_DESTROY: c_uint = 0;
+const XDG_TOPLEVEL_ICON_MANAGER_CREATE_ICON: c_uint = 1;
+const XDG_TOPLEVEL_ICON_MANAGER_SET_ICON: c_uint = 2;
+const XDG_TOPLEVEL_ICON_DESTROY: c_uint = 0;
+const XDG_TOPLEVEL_ICON_ADD_BUFFER: c_uint = 2;
+const WP_VIEWPORTER_DESTROY: c_uint = 0;
+const WP_VIEWPORTER_GET_VIEWPORT: c_uint = 1;
+const WP_VIEWPORT_DESTROY: c_uint = 0;
+const WP_VIEWPORT_SET_DESTINATION: c_uint = 2;
+const WP_FRACTIONAL_SCALE_MANAGER_DESTROY: c_uint = 0;
+const WP_FRACTIONAL_SCALE_MANAGER_GET_FRACTIONAL_SCALE: c_uint = 1;
+const WP_FRACTIONAL_SCALE_DESTROY: c_uint = 0;
+const WL_DATA_DEVICE_MANAGER_GET_DATA_DEVICE: c_uint = 1;
+const WL_DATA_DEVICE_RELEASE: c_uint = 2;
+const WL_DATA_OFFER_ACCEPT: c_uint = 0;
+const WL_DATA_OFFER_RECEIVE: c_uint = 1;
+const WL_DATA_OFFER_DESTROY: c_uint = 2;
+const WL_DATA_OFFER_FINISH: c_uint = 3;
+const WL_DATA_OFFER_SET_ACTIONS: c_uint = 4;
+const ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_GET_DEVICE: c_uint = 1;
+const ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_DESTROY: c_uint = 2;
+const ZWP_PRIMARY_SELECTION_DEVICE_DESTROY: c_uint = 1;
+const ZWP_PRIMARY_SELECTION_OFFER_RECEIVE: c_uint = 0;
+const ZWP_PRIMARY_SELECTION_OFFER_DESTROY: c_uint = 1;
 
 #[link(name = "wayland-client")]
 unsafe extern "C" {
@@ -332,10 +579,14 @@ unsafe extern "C" {
     static wl_shm_interface: wl_interface;
     static wl_shm_pool_interface: wl_interface;
     static wl_buffer_interface: wl_interface;
+    static wl_callback_interface: wl_interface;
     static wl_surface_interface: wl_interface;
     static wl_seat_interface: wl_interface;
     static wl_pointer_interface: wl_interface;
     static wl_keyboard_interface: wl_interface;
+    static wl_touch_interface: wl_interface;
+    static wl_data_device_manager_interface: wl_interface;
+    static wl_data_device_interface: wl_interface;
 }
 
 static XDG_WM_BASE_CREATE_POSITIONER_TYPES: InterfaceTypeList<1> =
@@ -359,10 +610,32 @@ static XDG_TOPLEVEL_RESIZE_TYPES: InterfaceTypeList<1> =
     InterfaceTypeList([unsafe { &wl_seat_interface }]);
 static XDG_TOPLEVEL_SET_FULLSCREEN_TYPES: InterfaceTypeList<1> =
     InterfaceTypeList([unsafe { &wl_output_interface }]);
+static XDG_TOPLEVEL_ICON_MANAGER_CREATE_ICON_TYPES: InterfaceTypeList<1> =
+    InterfaceTypeList([&XDG_TOPLEVEL_ICON_INTERFACE]);
+static XDG_TOPLEVEL_ICON_MANAGER_SET_ICON_TYPES: InterfaceTypeList<2> =
+    InterfaceTypeList([&XDG_TOPLEVEL_INTERFACE, &XDG_TOPLEVEL_ICON_INTERFACE]);
+static XDG_TOPLEVEL_ICON_ADD_BUFFER_TYPES: InterfaceTypeList<2> =
+    InterfaceTypeList([unsafe { &wl_buffer_interface }, std::ptr::null()]);
+static WP_VIEWPORTER_GET_VIEWPORT_TYPES: InterfaceTypeList<2> =
+    InterfaceTypeList([&WP_VIEWPORT_INTERFACE, unsafe { &wl_surface_interface }]);
+static WP_FRACTIONAL_SCALE_MANAGER_GET_FRACTIONAL_SCALE_TYPES: InterfaceTypeList<2> =
+    InterfaceTypeList([&WP_FRACTIONAL_SCALE_INTERFACE, unsafe {
+        &wl_surface_interface
+    }]);
 static XDG_POPUP_GRAB_TYPES: InterfaceTypeList<1> =
     InterfaceTypeList([unsafe { &wl_seat_interface }]);
 static XDG_POPUP_REPOSITION_TYPES: InterfaceTypeList<2> =
     InterfaceTypeList([&XDG_POSITIONER_INTERFACE, unsafe { &wl_seat_interface }]);
+static ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_CREATE_SOURCE_TYPES: InterfaceTypeList<1> =
+    InterfaceTypeList([&ZWP_PRIMARY_SELECTION_SOURCE_INTERFACE]);
+static ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_GET_DEVICE_TYPES: InterfaceTypeList<2> =
+    InterfaceTypeList([&ZWP_PRIMARY_SELECTION_DEVICE_INTERFACE, unsafe {
+        &wl_seat_interface
+    }]);
+static ZWP_PRIMARY_SELECTION_DEVICE_SET_SELECTION_TYPES: InterfaceTypeList<2> =
+    InterfaceTypeList([&ZWP_PRIMARY_SELECTION_SOURCE_INTERFACE, std::ptr::null()]);
+static ZWP_PRIMARY_SELECTION_DEVICE_OFFER_TYPES: InterfaceTypeList<1> =
+    InterfaceTypeList([&ZWP_PRIMARY_SELECTION_OFFER_INTERFACE]);
 
 static XDG_WM_BASE_REQUESTS: [wl_message; 4] = [
     wl_message {
@@ -612,6 +885,200 @@ static XDG_POPUP_EVENTS: [wl_message; 3] = [
     },
 ];
 
+static XDG_TOPLEVEL_ICON_MANAGER_REQUESTS: [wl_message; 3] = [
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"create_icon\0".as_ptr().cast::<c_char>(),
+        signature: b"n\0".as_ptr().cast::<c_char>(),
+        types: XDG_TOPLEVEL_ICON_MANAGER_CREATE_ICON_TYPES.as_ptr(),
+    },
+    wl_message {
+        name: b"set_icon\0".as_ptr().cast::<c_char>(),
+        signature: b"o?o\0".as_ptr().cast::<c_char>(),
+        types: XDG_TOPLEVEL_ICON_MANAGER_SET_ICON_TYPES.as_ptr(),
+    },
+];
+
+static XDG_TOPLEVEL_ICON_MANAGER_EVENTS: [wl_message; 2] = [
+    wl_message {
+        name: b"icon_size\0".as_ptr().cast::<c_char>(),
+        signature: b"i\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"done\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
+static XDG_TOPLEVEL_ICON_REQUESTS: [wl_message; 3] = [
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"set_name\0".as_ptr().cast::<c_char>(),
+        signature: b"s\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"add_buffer\0".as_ptr().cast::<c_char>(),
+        signature: b"oi\0".as_ptr().cast::<c_char>(),
+        types: XDG_TOPLEVEL_ICON_ADD_BUFFER_TYPES.as_ptr(),
+    },
+];
+
+static WP_VIEWPORTER_REQUESTS: [wl_message; 2] = [
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"get_viewport\0".as_ptr().cast::<c_char>(),
+        signature: b"no\0".as_ptr().cast::<c_char>(),
+        types: WP_VIEWPORTER_GET_VIEWPORT_TYPES.as_ptr(),
+    },
+];
+
+static WP_VIEWPORT_REQUESTS: [wl_message; 3] = [
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"set_source\0".as_ptr().cast::<c_char>(),
+        signature: b"ffff\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"set_destination\0".as_ptr().cast::<c_char>(),
+        signature: b"ii\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
+static WP_FRACTIONAL_SCALE_MANAGER_REQUESTS: [wl_message; 2] = [
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"get_fractional_scale\0".as_ptr().cast::<c_char>(),
+        signature: b"no\0".as_ptr().cast::<c_char>(),
+        types: WP_FRACTIONAL_SCALE_MANAGER_GET_FRACTIONAL_SCALE_TYPES.as_ptr(),
+    },
+];
+
+static WP_FRACTIONAL_SCALE_REQUESTS: [wl_message; 1] = [wl_message {
+    name: b"destroy\0".as_ptr().cast::<c_char>(),
+    signature: b"\0".as_ptr().cast::<c_char>(),
+    types: std::ptr::null(),
+}];
+
+static WP_FRACTIONAL_SCALE_EVENTS: [wl_message; 1] = [wl_message {
+    name: b"preferred_scale\0".as_ptr().cast::<c_char>(),
+    signature: b"u\0".as_ptr().cast::<c_char>(),
+    types: std::ptr::null(),
+}];
+
+static ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_REQUESTS: [wl_message; 3] = [
+    wl_message {
+        name: b"create_source\0".as_ptr().cast::<c_char>(),
+        signature: b"n\0".as_ptr().cast::<c_char>(),
+        types: ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_CREATE_SOURCE_TYPES.as_ptr(),
+    },
+    wl_message {
+        name: b"get_device\0".as_ptr().cast::<c_char>(),
+        signature: b"no\0".as_ptr().cast::<c_char>(),
+        types: ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_GET_DEVICE_TYPES.as_ptr(),
+    },
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
+static ZWP_PRIMARY_SELECTION_DEVICE_REQUESTS: [wl_message; 2] = [
+    wl_message {
+        name: b"set_selection\0".as_ptr().cast::<c_char>(),
+        signature: b"?ou\0".as_ptr().cast::<c_char>(),
+        types: ZWP_PRIMARY_SELECTION_DEVICE_SET_SELECTION_TYPES.as_ptr(),
+    },
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
+static ZWP_PRIMARY_SELECTION_DEVICE_EVENTS: [wl_message; 2] = [
+    wl_message {
+        name: b"data_offer\0".as_ptr().cast::<c_char>(),
+        signature: b"n\0".as_ptr().cast::<c_char>(),
+        types: ZWP_PRIMARY_SELECTION_DEVICE_OFFER_TYPES.as_ptr(),
+    },
+    wl_message {
+        name: b"selection\0".as_ptr().cast::<c_char>(),
+        signature: b"?o\0".as_ptr().cast::<c_char>(),
+        types: ZWP_PRIMARY_SELECTION_DEVICE_OFFER_TYPES.as_ptr(),
+    },
+];
+
+static ZWP_PRIMARY_SELECTION_OFFER_REQUESTS: [wl_message; 2] = [
+    wl_message {
+        name: b"receive\0".as_ptr().cast::<c_char>(),
+        signature: b"sh\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
+static ZWP_PRIMARY_SELECTION_OFFER_EVENTS: [wl_message; 1] = [wl_message {
+    name: b"offer\0".as_ptr().cast::<c_char>(),
+    signature: b"s\0".as_ptr().cast::<c_char>(),
+    types: std::ptr::null(),
+}];
+
+static ZWP_PRIMARY_SELECTION_SOURCE_REQUESTS: [wl_message; 2] = [
+    wl_message {
+        name: b"offer\0".as_ptr().cast::<c_char>(),
+        signature: b"s\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"destroy\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
+static ZWP_PRIMARY_SELECTION_SOURCE_EVENTS: [wl_message; 2] = [
+    wl_message {
+        name: b"send\0".as_ptr().cast::<c_char>(),
+        signature: b"sh\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+    wl_message {
+        name: b"cancelled\0".as_ptr().cast::<c_char>(),
+        signature: b"\0".as_ptr().cast::<c_char>(),
+        types: std::ptr::null(),
+    },
+];
+
 static XDG_WM_BASE_INTERFACE: wl_interface = wl_interface {
     name: b"xdg_wm_base\0".as_ptr().cast::<c_char>(),
     version: 6,
@@ -657,12 +1124,112 @@ static XDG_POPUP_INTERFACE: wl_interface = wl_interface {
     events: XDG_POPUP_EVENTS.as_ptr(),
 };
 
-pub unsafe fn oab_wl_display_get_registry(display: *mut wl_display) -> *mut wl_registry {
-    let display_proxy = display.cast::<wl_proxy>();
-    let version = unsafe { wl_proxy_get_version(display_proxy) };
-    unsafe {
-        wl_proxy_marshal_flags(
-            display_proxy,
+static XDG_TOPLEVEL_ICON_MANAGER_INTERFACE: wl_interface = wl_interface {
+    name: b"xdg_toplevel_icon_manager_v1\0".as_ptr().cast::<c_char>(),
+    version: 1,
+    method_count: XDG_TOPLEVEL_ICON_MANAGER_REQUESTS.len() as c_int,
+    methods: XDG_TOPLEVEL_ICON_MANAGER_REQUESTS.as_ptr(),
+    event_count: XDG_TOPLEVEL_ICON_MANAGER_EVENTS.len() as c_int,
+    events: XDG_TOPLEVEL_ICON_MANAGER_EVENTS.as_ptr(),
+};
+
+static XDG_TOPLEVEL_ICON_INTERFACE: wl_interface = wl_interface {
+    name: b"xdg_toplevel_icon_v1\0".as_ptr().cast::<c_char>(),
+    version: 1,
+    method_count: XDG_TOPLEVEL_ICON_REQUESTS.len() as c_int,
+    methods: XDG_TOPLEVEL_ICON_REQUESTS.as_ptr(),
+    event_count: 0,
+    events: std::ptr::null(),
+};
+
+static WP_VIEWPORTER_INTERFACE: wl_interface = wl_interface {
+    name: b"wp_viewporter\0".as_ptr().cast::<c_char>(),
+    version: 1,
+    method_count: WP_VIEWPORTER_REQUESTS.len() as c_int,
+    methods: WP_VIEWPORTER_REQUESTS.as_ptr(),
+    event_count: 0,
+    events: std::ptr::null(),
+};
+
+static WP_VIEWPORT_INTERFACE: wl_interface = wl_interface {
+    name: b"wp_viewport\0".as_ptr().cast::<c_char>(),
+    version: 1,
+    method_count: WP_VIEWPORT_REQUESTS.len() as c_int,
+    methods: WP_VIEWPORT_REQUESTS.as_ptr(),
+    event_count: 0,
+    events: std::ptr::null(),
+};
+
+static WP_FRACTIONAL_SCALE_MANAGER_INTERFACE: wl_interface = wl_interface {
+    name: b"wp_fractional_scale_manager_v1\0"
+        .as_ptr()
+        .cast::<c_char>(),
+    version: 1,
+    method_count: WP_FRACTIONAL_SCALE_MANAGER_REQUESTS.len() as c_int,
+    methods: WP_FRACTIONAL_SCALE_MANAGER_REQUESTS.as_ptr(),
+    event_count: 0,
+    events: std::ptr::null(),
+};
+
+static WP_FRACTIONAL_SCALE_INTERFACE: wl_interface = wl_interface {
+    name: b"wp_fractional_scale_v1\0".as_ptr().cast::<c_char>(),
+    version: 1,
+    method_count: WP_FRACTIONAL_SCALE_REQUESTS.len() as c_int,
+    methods: WP_FRACTIONAL_SCALE_REQUESTS.as_ptr(),
+    event_count: WP_FRACTIONAL_SCALE_EVENTS.len() as c_int,
+    events: WP_FRACTIONAL_SCALE_EVENTS.as_ptr(),
+};
+
+static ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_INTERFACE: wl_interface = wl_interface {
+    name: b"zwp_primary_selection_device_manager_v1\0"
+        .as_ptr()
+        .cast::<c_char>(),
+    version: 1,
+    method_count: ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_REQUESTS.len() as c_int,
+    methods: ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_REQUESTS.as_ptr(),
+    event_count: 0,
+    events: std::ptr::null(),
+};
+
+static ZWP_PRIMARY_SELECTION_DEVICE_INTERFACE: wl_interface = wl_interface {
+    name: b"zwp_primary_selection_device_v1\0"
+        .as_ptr()
+        .cast::<c_char>(),
+    version: 1,
+    method_count: ZWP_PRIMARY_SELECTION_DEVICE_REQUESTS.len() as c_int,
+    methods: ZWP_PRIMARY_SELECTION_DEVICE_REQUESTS.as_ptr(),
+    event_count: ZWP_PRIMARY_SELECTION_DEVICE_EVENTS.len() as c_int,
+    events: ZWP_PRIMARY_SELECTION_DEVICE_EVENTS.as_ptr(),
+};
+
+static ZWP_PRIMARY_SELECTION_OFFER_INTERFACE: wl_interface = wl_interface {
+    name: b"zwp_primary_selection_offer_v1\0"
+        .as_ptr()
+        .cast::<c_char>(),
+    version: 1,
+    method_count: ZWP_PRIMARY_SELECTION_OFFER_REQUESTS.len() as c_int,
+    methods: ZWP_PRIMARY_SELECTION_OFFER_REQUESTS.as_ptr(),
+    event_count: ZWP_PRIMARY_SELECTION_OFFER_EVENTS.len() as c_int,
+    events: ZWP_PRIMARY_SELECTION_OFFER_EVENTS.as_ptr(),
+};
+
+static ZWP_PRIMARY_SELECTION_SOURCE_INTERFACE: wl_interface = wl_interface {
+    name: b"zwp_primary_selection_source_v1\0"
+        .as_ptr()
+        .cast::<c_char>(),
+    version: 1,
+    method_count: ZWP_PRIMARY_SELECTION_SOURCE_REQUESTS.len() as c_int,
+    methods: ZWP_PRIMARY_SELECTION_SOURCE_REQUESTS.as_ptr(),
+    event_count: ZWP_PRIMARY_SELECTION_SOURCE_EVENTS.len() as c_int,
+    events: ZWP_PRIMARY_SELECTION_SOURCE_EVENTS.as_ptr(),
+};
+
+pub unsafe fn oab_wl_display_get_registry(display: *mut wl_display) -> *mut wl_registry {
+    let display_proxy = display.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(display_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            display_proxy,
             WL_DISPLAY_GET_REGISTRY,
             &wl_registry_interface,
             version,
@@ -683,6 +1250,16 @@ pub unsafe fn oab_wl_registry_bind_compositor(
         .cast::<wl_compositor>()
 }
 
+pub unsafe fn oab_wl_registry_bind_output(
+    registry: *mut wl_registry,
+    name: c_uint,
+    version: c_uint,
+) -> *mut wl_output {
+    let interface = unsafe { &wl_output_interface };
+    unsafe { bind_registry_interface(registry, name, version, interface, b"wl_output\0") }
+        .cast::<wl_output>()
+}
+
 pub unsafe fn oab_wl_registry_bind_shm(
     registry: *mut wl_registry,
     name: c_uint,
@@ -713,6 +1290,88 @@ pub unsafe fn oab_wl_registry_bind_xdg_wm_base(
         .cast::<xdg_wm_base>()
 }
 
+pub unsafe fn oab_wl_registry_bind_toplevel_icon_manager(
+    registry: *mut wl_registry,
+    name: c_uint,
+    version: c_uint,
+) -> *mut xdg_toplevel_icon_manager_v1 {
+    let interface = &XDG_TOPLEVEL_ICON_MANAGER_INTERFACE;
+    unsafe {
+        bind_registry_interface(
+            registry,
+            name,
+            version,
+            interface,
+            b"xdg_toplevel_icon_manager_v1\0",
+        )
+    }
+    .cast::<xdg_toplevel_icon_manager_v1>()
+}
+
+pub unsafe fn oab_wl_registry_bind_viewporter(
+    registry: *mut wl_registry,
+    name: c_uint,
+    version: c_uint,
+) -> *mut wp_viewporter {
+    let interface = &WP_VIEWPORTER_INTERFACE;
+    unsafe { bind_registry_interface(registry, name, version, interface, b"wp_viewporter\0") }
+        .cast::<wp_viewporter>()
+}
+
+pub unsafe fn oab_wl_registry_bind_fractional_scale_manager(
+    registry: *mut wl_registry,
+    name: c_uint,
+    version: c_uint,
+) -> *mut wp_fractional_scale_manager_v1 {
+    let interface = &WP_FRACTIONAL_SCALE_MANAGER_INTERFACE;
+    unsafe {
+        bind_registry_interface(
+            registry,
+            name,
+            version,
+            interface,
+            b"wp_fractional_scale_manager_v1\0",
+        )
+    }
+    .cast::<wp_fractional_scale_manager_v1>()
+}
+
+pub unsafe fn oab_wl_registry_bind_data_device_manager(
+    registry: *mut wl_registry,
+    name: c_uint,
+    version: c_uint,
+) -> *mut wl_data_device_manager {
+    let interface = unsafe { &wl_data_device_manager_interface };
+    unsafe {
+        bind_registry_interface(
+            registry,
+            name,
+            version,
+            interface,
+            b"wl_data_device_manager\0",
+        )
+    }
+    .cast::<wl_data_device_manager>()
+}
+
+pub unsafe fn oab_wl_registry_bind_primary_selection_device_manager(
+    registry: *mut wl_registry,
+    name: c_uint,
+    version: c_uint,
+) -> *mut zwp_primary_selection_device_manager_v1 {
+    let interface = &ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_INTERFACE;
+    unsafe {
+        bind_registry_interface(
+            registry,
+            name,
+            version,
+            interface,
+            b"zwp_primary_selection_device_manager_v1\0",
+        )
+    }
+    .cast::<zwp_primary_selection_device_manager_v1>()
+}
+
 unsafe fn bind_registry_interface(
     registry: *mut wl_registry,
     name: c_uint,
@@ -874,6 +1533,22 @@ pub unsafe fn oab_wl_surface_damage_buffer(
     }
 }
 
+pub unsafe fn oab_wl_surface_frame(surface: *mut wl_surface) -> *mut wl_callback {
+    let surface_proxy = surface.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(surface_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            surface_proxy,
+            WL_SURFACE_FRAME,
+            &wl_callback_interface,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+        )
+    }
+    .cast::<wl_callback>()
+}
+
 pub unsafe fn oab_wl_surface_set_buffer_scale(surface: *mut wl_surface, scale: c_int) {
     let surface_proxy = surface.cast::<wl_proxy>();
     let version = unsafe { wl_proxy_get_version(surface_proxy) };
@@ -949,6 +1624,22 @@ pub unsafe fn oab_wl_seat_get_keyboard(seat: *mut wl_seat) -> *mut wl_keyboard {
     .cast::<wl_keyboard>()
 }
 
+pub unsafe fn oab_wl_seat_get_touch(seat: *mut wl_seat) -> *mut wl_touch {
+    let seat_proxy = seat.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(seat_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            seat_proxy,
+            WL_SEAT_GET_TOUCH,
+            &wl_touch_interface,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+        )
+    }
+    .cast::<wl_touch>()
+}
+
 pub unsafe fn oab_wl_shm_release(shm: *mut wl_shm) {
     let shm_proxy = shm.cast::<wl_proxy>();
     let version = unsafe { wl_proxy_get_version(shm_proxy) };
@@ -1101,6 +1792,437 @@ pub unsafe fn oab_xdg_toplevel_destroy(toplevel: *mut xdg_toplevel) {
     }
 }
 
+pub unsafe fn oab_xdg_toplevel_icon_manager_create_icon(
+    manager: *mut xdg_toplevel_icon_manager_v1,
+) -> *mut xdg_toplevel_icon_v1 {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            XDG_TOPLEVEL_ICON_MANAGER_CREATE_ICON,
+            &XDG_TOPLEVEL_ICON_INTERFACE,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+        )
+    }
+    .cast::<xdg_toplevel_icon_v1>()
+}
+
+pub unsafe fn oab_xdg_toplevel_icon_manager_set_icon(
+    manager: *mut xdg_toplevel_icon_manager_v1,
+    toplevel: *mut xdg_toplevel,
+    icon: *mut xdg_toplevel_icon_v1,
+) {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            XDG_TOPLEVEL_ICON_MANAGER_SET_ICON,
+            std::ptr::null(),
+            version,
+            0,
+            toplevel,
+            icon,
+        );
+    }
+}
+
+pub unsafe fn oab_xdg_toplevel_icon_manager_destroy(manager: *mut xdg_toplevel_icon_manager_v1) {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            XDG_TOPLEVEL_ICON_MANAGER_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_xdg_toplevel_icon_add_buffer(
+    icon: *mut xdg_toplevel_icon_v1,
+    buffer: *mut wl_buffer,
+    scale: c_int,
+) {
+    let icon_proxy = icon.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(icon_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            icon_proxy,
+            XDG_TOPLEVEL_ICON_ADD_BUFFER,
+            std::ptr::null(),
+            version,
+            0,
+            buffer,
+            scale,
+        );
+    }
+}
+
+pub unsafe fn oab_xdg_toplevel_icon_destroy(icon: *mut xdg_toplevel_icon_v1) {
+    let icon_proxy = icon.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(icon_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            icon_proxy,
+            XDG_TOPLEVEL_ICON_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_wp_viewporter_get_viewport(
+    viewporter: *mut wp_viewporter,
+    surface: *mut wl_surface,
+) -> *mut wp_viewport {
+    let viewporter_proxy = viewporter.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(viewporter_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            viewporter_proxy,
+            WP_VIEWPORTER_GET_VIEWPORT,
+            &WP_VIEWPORT_INTERFACE,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+            surface,
+        )
+    }
+    .cast::<wp_viewport>()
+}
+
+pub unsafe fn oab_wp_viewporter_destroy(viewporter: *mut wp_viewporter) {
+    let viewporter_proxy = viewporter.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(viewporter_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            viewporter_proxy,
+            WP_VIEWPORTER_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_wp_viewport_set_destination(viewport: *mut wp_viewport, width: i32, height: i32) {
+    let viewport_proxy = viewport.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(viewport_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            viewport_proxy,
+            WP_VIEWPORT_SET_DESTINATION,
+            std::ptr::null(),
+            version,
+            0,
+            width,
+            height,
+        );
+    }
+}
+
+pub unsafe fn oab_wp_viewport_destroy(viewport: *mut wp_viewport) {
+    let viewport_proxy = viewport.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(viewport_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            viewport_proxy,
+            WP_VIEWPORT_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_wp_fractional_scale_manager_get_fractional_scale(
+    manager: *mut wp_fractional_scale_manager_v1,
+    surface: *mut wl_surface,
+) -> *mut wp_fractional_scale_v1 {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            WP_FRACTIONAL_SCALE_MANAGER_GET_FRACTIONAL_SCALE,
+            &WP_FRACTIONAL_SCALE_INTERFACE,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+            surface,
+        )
+    }
+    .cast::<wp_fractional_scale_v1>()
+}
+
+pub unsafe fn oab_wp_fractional_scale_manager_destroy(
+    manager: *mut wp_fractional_scale_manager_v1,
+) {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            WP_FRACTIONAL_SCALE_MANAGER_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_wp_fractional_scale_destroy(fractional_scale: *mut wp_fractional_scale_v1) {
+    let fractional_scale_proxy = fractional_scale.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(fractional_scale_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            fractional_scale_proxy,
+            WP_FRACTIONAL_SCALE_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_wl_data_device_manager_get_data_device(
+    manager: *mut wl_data_device_manager,
+    seat: *mut wl_seat,
+) -> *mut wl_data_device {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            WL_DATA_DEVICE_MANAGER_GET_DATA_DEVICE,
+            &wl_data_device_interface,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+            seat,
+        )
+    }
+    .cast::<wl_data_device>()
+}
+
+/// `wl_data_device_manager` has no destructor request, so this only frees the proxy.
+pub unsafe fn oab_wl_data_device_manager_destroy(manager: *mut wl_data_device_manager) {
+    unsafe {
+        wl_proxy_destroy(manager.cast::<wl_proxy>());
+    }
+}
+
+pub unsafe fn oab_wl_data_device_release(device: *mut wl_data_device) {
+    let device_proxy = device.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(device_proxy) };
+    if version < 2 {
+        unsafe {
+            wl_proxy_destroy(device_proxy);
+        }
+        return;
+    }
+    unsafe {
+        wl_proxy_marshal_flags(
+            device_proxy,
+            WL_DATA_DEVICE_RELEASE,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+/// Tells the source whether a drop would be taken: `mime_type` is the type it would be read as,
+/// or null to refuse.
+pub unsafe fn oab_wl_data_offer_accept(
+    offer: *mut wl_data_offer,
+    serial: u32,
+    mime_type: *const c_char,
+) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            WL_DATA_OFFER_ACCEPT,
+            std::ptr::null(),
+            version,
+            0,
+            serial,
+            mime_type,
+        );
+    }
+}
+
+pub unsafe fn oab_wl_data_offer_receive(
+    offer: *mut wl_data_offer,
+    mime_type: *const c_char,
+    fd: c_int,
+) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            WL_DATA_OFFER_RECEIVE,
+            std::ptr::null(),
+            version,
+            0,
+            mime_type,
+            fd,
+        );
+    }
+}
+
+/// Only for drag-and-drop offers on version 3 and up, once the dropped data has been read.
+pub unsafe fn oab_wl_data_offer_finish(offer: *mut wl_data_offer) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    if version < 3 {
+        return;
+    }
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            WL_DATA_OFFER_FINISH,
+            std::ptr::null(),
+            version,
+            0,
+        );
+    }
+}
+
+/// Drag-and-drop actions on version 3 and up; older offers always copy.
+pub unsafe fn oab_wl_data_offer_set_actions(
+    offer: *mut wl_data_offer,
+    dnd_actions: u32,
+    preferred_action: u32,
+) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    if version < 3 {
+        return;
+    }
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            WL_DATA_OFFER_SET_ACTIONS,
+            std::ptr::null(),
+            version,
+            0,
+            dnd_actions,
+            preferred_action,
+        );
+    }
+}
+
+pub unsafe fn oab_wl_data_offer_destroy(offer: *mut wl_data_offer) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            WL_DATA_OFFER_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_zwp_primary_selection_device_manager_get_device(
+    manager: *mut zwp_primary_selection_device_manager_v1,
+    seat: *mut wl_seat,
+) -> *mut zwp_primary_selection_device_v1 {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_GET_DEVICE,
+            &ZWP_PRIMARY_SELECTION_DEVICE_INTERFACE,
+            version,
+            0,
+            std::ptr::null_mut::<wl_proxy>(),
+            seat,
+        )
+    }
+    .cast::<zwp_primary_selection_device_v1>()
+}
+
+pub unsafe fn oab_zwp_primary_selection_device_manager_destroy(
+    manager: *mut zwp_primary_selection_device_manager_v1,
+) {
+    let manager_proxy = manager.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(manager_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            manager_proxy,
+            ZWP_PRIMARY_SELECTION_DEVICE_MANAGER_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_zwp_primary_selection_device_destroy(
+    device: *mut zwp_primary_selection_device_v1,
+) {
+    let device_proxy = device.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(device_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            device_proxy,
+            ZWP_PRIMARY_SELECTION_DEVICE_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
+pub unsafe fn oab_zwp_primary_selection_offer_receive(
+    offer: *mut zwp_primary_selection_offer_v1,
+    mime_type: *const c_char,
+    fd: c_int,
+) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            ZWP_PRIMARY_SELECTION_OFFER_RECEIVE,
+            std::ptr::null(),
+            version,
+            0,
+            mime_type,
+            fd,
+        );
+    }
+}
+
+pub unsafe fn oab_zwp_primary_selection_offer_destroy(offer: *mut zwp_primary_selection_offer_v1) {
+    let offer_proxy = offer.cast::<wl_proxy>();
+    let version = unsafe { wl_proxy_get_version(offer_proxy) };
+    unsafe {
+        wl_proxy_marshal_flags(
+            offer_proxy,
+            ZWP_PRIMARY_SELECTION_OFFER_DESTROY,
+            std::ptr::null(),
+            version,
+            WL_MARSHAL_FLAG_DESTROY,
+        );
+    }
+}
+
 #[inline]
 pub fn wl_fixed_to_f64(value: wl_fixed_t) -> f64 {
     f64::from(value) / 256.0
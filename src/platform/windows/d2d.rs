@@ -9,7 +9,9 @@ pub(super) enum ID2D1DeviceContext5 {}
 pub(super) enum ID2D1Bitmap1 {}
 pub(super) enum ID2D1Layer {}
 pub(super) enum ID2D1SolidColorBrush {}
-pub(super) enum ID2D1SvgDocument {}
+pub(super) enum ID2D1PathGeometry {}
+pub(super) enum ID2D1GeometrySink {}
+pub(super) enum ID2D1StrokeStyle {}
 
 pub(super) const D2D1_DRAW_TEXT_OPTIONS_NONE: u32 = 0;
 pub(super) const D2D1_TEXT_ANTIALIAS_MODE_CLEARTYPE: u32 = 1;
@@ -25,6 +27,20 @@ pub(super) const D2D1_LAYER_OPTIONS1_NONE: u32 = 0;
 
 pub(super) const D2D1_MAP_OPTIONS_READ: u32 = 0x1;
 
+pub(super) const D2D1_FILL_MODE_ALTERNATE: u32 = 0;
+pub(super) const D2D1_FILL_MODE_WINDING: u32 = 1;
+pub(super) const D2D1_FIGURE_BEGIN_FILLED: u32 = 0;
+pub(super) const D2D1_FIGURE_END_OPEN: u32 = 0;
+pub(super) const D2D1_FIGURE_END_CLOSED: u32 = 1;
+
+pub(super) const D2D1_CAP_STYLE_FLAT: u32 = 0;
+pub(super) const D2D1_CAP_STYLE_SQUARE: u32 = 1;
+pub(super) const D2D1_CAP_STYLE_ROUND: u32 = 2;
+pub(super) const D2D1_LINE_JOIN_BEVEL: u32 = 1;
+pub(super) const D2D1_LINE_JOIN_ROUND: u32 = 2;
+pub(super) const D2D1_LINE_JOIN_MITER_OR_BEVEL: u32 = 3;
+pub(super) const D2D1_DASH_STYLE_SOLID: u32 = 0;
+
 const D2D1_FACTORY_TYPE_SINGLE_THREADED: u32 = 0;
 
 const DXGI_FORMAT_B8G8R8A8_UNORM: u32 = 87;
@@ -140,6 +156,33 @@ pub(super) struct D2D1_LAYER_PARAMETERS1 {
     pub(super) layer_options1: u32,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct D2D1_BEZIER_SEGMENT {
+    pub(super) point1: D2D1_POINT_2F,
+    pub(super) point2: D2D1_POINT_2F,
+    pub(super) point3: D2D1_POINT_2F,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct D2D1_QUADRATIC_BEZIER_SEGMENT {
+    pub(super) point1: D2D1_POINT_2F,
+    pub(super) point2: D2D1_POINT_2F,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct D2D1_STROKE_STYLE_PROPERTIES {
+    pub(super) start_cap: u32,
+    pub(super) end_cap: u32,
+    pub(super) dash_cap: u32,
+    pub(super) line_join: u32,
+    pub(super) miter_limit: f32,
+    pub(super) dash_style: u32,
+    pub(super) dash_offset: f32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default)]
 pub(super) struct D2D1_MAPPED_RECT {
@@ -216,6 +259,160 @@ pub(super) fn factory_create_device(
     Ok(ComPtr::from_raw(device))
 }
 
+pub(super) fn factory_create_path_geometry(
+    factory: &ComPtr<ID2D1Factory1>,
+) -> Result<ComPtr<ID2D1PathGeometry>, HResultError> {
+    let mut geometry: *mut ID2D1PathGeometry = std::ptr::null_mut();
+    let hr = unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *mut *mut ID2D1PathGeometry) -> HRESULT =
+            std::mem::transmute(vtbl_entry(factory.as_ptr().cast::<c_void>(), 10));
+        f(factory.as_ptr().cast::<c_void>(), &mut geometry)
+    };
+    if !com::succeeded(hr) {
+        return Err(HResultError {
+            hr,
+            context: "ID2D1Factory::CreatePathGeometry failed",
+        });
+    }
+    if geometry.is_null() {
+        return Err(HResultError {
+            hr: -1,
+            context: "CreatePathGeometry returned null",
+        });
+    }
+    Ok(ComPtr::from_raw(geometry))
+}
+
+pub(super) fn factory_create_stroke_style(
+    factory: &ComPtr<ID2D1Factory1>,
+    properties: &D2D1_STROKE_STYLE_PROPERTIES,
+) -> Result<ComPtr<ID2D1StrokeStyle>, HResultError> {
+    let mut style: *mut ID2D1StrokeStyle = std::ptr::null_mut();
+    let hr = unsafe {
+        let f: unsafe extern "system" fn(
+            *mut c_void,
+            *const D2D1_STROKE_STYLE_PROPERTIES,
+            *const f32,
+            u32,
+            *mut *mut ID2D1StrokeStyle,
+        ) -> HRESULT = std::mem::transmute(vtbl_entry(factory.as_ptr().cast::<c_void>(), 11));
+        f(
+            factory.as_ptr().cast::<c_void>(),
+            properties,
+            std::ptr::null(),
+            0,
+            &mut style,
+        )
+    };
+    if !com::succeeded(hr) {
+        return Err(HResultError {
+            hr,
+            context: "ID2D1Factory::CreateStrokeStyle failed",
+        });
+    }
+    if style.is_null() {
+        return Err(HResultError {
+            hr: -1,
+            context: "CreateStrokeStyle returned null",
+        });
+    }
+    Ok(ComPtr::from_raw(style))
+}
+
+pub(super) fn path_geometry_open(
+    geometry: &ComPtr<ID2D1PathGeometry>,
+) -> Result<ComPtr<ID2D1GeometrySink>, HResultError> {
+    let mut sink: *mut ID2D1GeometrySink = std::ptr::null_mut();
+    let hr = unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *mut *mut ID2D1GeometrySink) -> HRESULT =
+            std::mem::transmute(vtbl_entry(geometry.as_ptr().cast::<c_void>(), 17));
+        f(geometry.as_ptr().cast::<c_void>(), &mut sink)
+    };
+    if !com::succeeded(hr) {
+        return Err(HResultError {
+            hr,
+            context: "ID2D1PathGeometry::Open failed",
+        });
+    }
+    if sink.is_null() {
+        return Err(HResultError {
+            hr: -1,
+            context: "ID2D1PathGeometry::Open returned null",
+        });
+    }
+    Ok(ComPtr::from_raw(sink))
+}
+
+pub(super) fn sink_set_fill_mode(sink: &ComPtr<ID2D1GeometrySink>, fill_mode: u32) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, u32) =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 3));
+        f(sink.as_ptr().cast::<c_void>(), fill_mode);
+    }
+}
+
+pub(super) fn sink_begin_figure(
+    sink: &ComPtr<ID2D1GeometrySink>,
+    start: D2D1_POINT_2F,
+    figure_begin: u32,
+) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, D2D1_POINT_2F, u32) =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 5));
+        f(sink.as_ptr().cast::<c_void>(), start, figure_begin);
+    }
+}
+
+pub(super) fn sink_end_figure(sink: &ComPtr<ID2D1GeometrySink>, figure_end: u32) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, u32) =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 8));
+        f(sink.as_ptr().cast::<c_void>(), figure_end);
+    }
+}
+
+pub(super) fn sink_close(sink: &ComPtr<ID2D1GeometrySink>) -> Result<(), HResultError> {
+    let hr = unsafe {
+        let f: unsafe extern "system" fn(*mut c_void) -> HRESULT =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 9));
+        f(sink.as_ptr().cast::<c_void>())
+    };
+    if !com::succeeded(hr) {
+        return Err(HResultError {
+            hr,
+            context: "ID2D1GeometrySink::Close failed",
+        });
+    }
+    Ok(())
+}
+
+pub(super) fn sink_add_line(sink: &ComPtr<ID2D1GeometrySink>, point: D2D1_POINT_2F) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, D2D1_POINT_2F) =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 10));
+        f(sink.as_ptr().cast::<c_void>(), point);
+    }
+}
+
+pub(super) fn sink_add_bezier(sink: &ComPtr<ID2D1GeometrySink>, bezier: &D2D1_BEZIER_SEGMENT) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *const D2D1_BEZIER_SEGMENT) =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 11));
+        f(sink.as_ptr().cast::<c_void>(), bezier);
+    }
+}
+
+pub(super) fn sink_add_quadratic_bezier(
+    sink: &ComPtr<ID2D1GeometrySink>,
+    bezier: &D2D1_QUADRATIC_BEZIER_SEGMENT,
+) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *const D2D1_QUADRATIC_BEZIER_SEGMENT) =
+            std::mem::transmute(vtbl_entry(sink.as_ptr().cast::<c_void>(), 12));
+        f(sink.as_ptr().cast::<c_void>(), bezier);
+    }
+}
+
 pub(super) fn device_create_device_context(
     device: &ComPtr<ID2D1Device>,
 ) -> Result<ComPtr<ID2D1DeviceContext5>, HResultError> {
@@ -508,6 +705,63 @@ pub(super) fn ctx_draw_rounded_rectangle(
     }
 }
 
+pub(super) fn ctx_draw_geometry(
+    ctx: &ComPtr<ID2D1DeviceContext5>,
+    geometry: &ComPtr<ID2D1PathGeometry>,
+    brush: *mut ID2D1SolidColorBrush,
+    stroke_width: f32,
+    stroke_style: &ComPtr<ID2D1StrokeStyle>,
+) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *mut c_void, *mut c_void, f32, *mut c_void) =
+            std::mem::transmute(vtbl_entry(ctx.as_ptr().cast::<c_void>(), 22));
+        f(
+            ctx.as_ptr().cast::<c_void>(),
+            geometry.as_ptr().cast::<c_void>(),
+            brush.cast::<c_void>(),
+            stroke_width,
+            stroke_style.as_ptr().cast::<c_void>(),
+        );
+    }
+}
+
+pub(super) fn ctx_fill_geometry(
+    ctx: &ComPtr<ID2D1DeviceContext5>,
+    geometry: &ComPtr<ID2D1PathGeometry>,
+    brush: *mut ID2D1SolidColorBrush,
+) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *mut c_void, *mut c_void, *mut c_void) =
+            std::mem::transmute(vtbl_entry(ctx.as_ptr().cast::<c_void>(), 23));
+        f(
+            ctx.as_ptr().cast::<c_void>(),
+            geometry.as_ptr().cast::<c_void>(),
+            brush.cast::<c_void>(),
+            std::ptr::null_mut(),
+        );
+    }
+}
+
+pub(super) fn ctx_push_axis_aligned_clip(
+    ctx: &ComPtr<ID2D1DeviceContext5>,
+    rect: &D2D1_RECT_F,
+    antialias_mode: u32,
+) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void, *const D2D1_RECT_F, u32) =
+            std::mem::transmute(vtbl_entry(ctx.as_ptr().cast::<c_void>(), 45));
+        f(ctx.as_ptr().cast::<c_void>(), rect, antialias_mode);
+    }
+}
+
+pub(super) fn ctx_pop_axis_aligned_clip(ctx: &ComPtr<ID2D1DeviceContext5>) {
+    unsafe {
+        let f: unsafe extern "system" fn(*mut c_void) =
+            std::mem::transmute(vtbl_entry(ctx.as_ptr().cast::<c_void>(), 46));
+        f(ctx.as_ptr().cast::<c_void>());
+    }
+}
+
 pub(super) fn ctx_draw_bitmap(
     ctx: &ComPtr<ID2D1DeviceContext5>,
     bitmap: &ComPtr<ID2D1Bitmap1>,
@@ -562,52 +816,6 @@ pub(super) fn ctx_draw_text_layout(
     }
 }
 
-pub(super) fn ctx_create_svg_document(
-    ctx: &ComPtr<ID2D1DeviceContext5>,
-    svg_stream: *mut c_void,
-    viewport_size: D2D1_SIZE_F,
-) -> Result<ComPtr<ID2D1SvgDocument>, HResultError> {
-    let mut doc: *mut ID2D1SvgDocument = std::ptr::null_mut();
-    let hr = unsafe {
-        let f: unsafe extern "system" fn(
-            *mut c_void,
-            *mut c_void,
-            D2D1_SIZE_F,
-            *mut *mut ID2D1SvgDocument,
-        ) -> HRESULT = std::mem::transmute(vtbl_entry(ctx.as_ptr().cast::<c_void>(), 115));
-        f(
-            ctx.as_ptr().cast::<c_void>(),
-            svg_stream,
-            viewport_size,
-            &mut doc,
-        )
-    };
-    if !com::succeeded(hr) {
-        return Err(HResultError {
-            hr,
-            context: "ID2D1DeviceContext5::CreateSvgDocument failed",
-        });
-    }
-    if doc.is_null() {
-        return Err(HResultError {
-            hr: -1,
-            context: "CreateSvgDocument returned null",
-        });
-    }
-    Ok(ComPtr::from_raw(doc))
-}
-
-pub(super) fn ctx_draw_svg_document(
-    ctx: &ComPtr<ID2D1DeviceContext5>,
-    doc: &ComPtr<ID2D1SvgDocument>,
-) {
-    unsafe {
-        let f: unsafe extern "system" fn(*mut c_void, *mut c_void) =
-            std::mem::transmute(vtbl_entry(ctx.as_ptr().cast::<c_void>(), 116));
-        f(ctx.as_ptr().cast::<c_void>(), doc.as_ptr().cast::<c_void>());
-    }
-}
-
 pub(super) fn bitmap_copy_from_bitmap(
     dst: &ComPtr<ID2D1Bitmap1>,
     src: &ComPtr<ID2D1Bitmap1>,
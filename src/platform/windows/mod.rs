@@ -6,12 +6,16 @@ mod headless;
 mod painter;
 mod scale;
 mod scaled;
-mod svg;
 mod windowed;
 mod wstr;
 
-use super::WindowOptions;
+use super::{FrameSurface, WindowOptions};
 use crate::app::App;
+use crate::image::RgbImage;
+use crate::render::Viewport;
+use painter::WinPainter;
+use scale::ScaleFactor;
+use scaled::ScaledPainter;
 
 pub fn run_window<A: App>(title: &str, options: WindowOptions, app: &mut A) -> Result<(), String> {
     if options.headless {
@@ -19,3 +23,34 @@ pub fn run_window<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
     }
     windowed::run(title, options, app)
 }
+
+/// The back buffer at its current size and scale; rendering into it also shows it in the window,
+/// if there is one.
+struct WinSurface<'a> {
+    painter: &'a mut WinPainter,
+    scale: ScaleFactor,
+    viewport: Viewport,
+    css_viewport: Viewport,
+}
+
+impl<A: App + ?Sized> FrameSurface<A> for WinSurface<'_> {
+    fn page_viewport(&self) -> Viewport {
+        self.css_viewport
+    }
+
+    fn can_present(&self) -> bool {
+        self.viewport.width_px > 0 && self.viewport.height_px > 0
+    }
+
+    fn render(&mut self, app: &mut A) -> Result<(), String> {
+        self.painter.ensure_back_buffer(self.viewport)?;
+        app.render(
+            &mut ScaledPainter::new(self.painter, self.scale),
+            self.css_viewport,
+        )
+    }
+
+    fn capture(&mut self) -> Result<RgbImage, String> {
+        self.painter.capture_back_buffer_rgb()
+    }
+}
@@ -2,15 +2,16 @@ use super::d2d;
 use super::d3d11;
 use super::dwrite;
 use super::gdi;
-use super::svg;
 use super::wstr;
 use crate::debug;
 use crate::geom::Color;
 use crate::image::{Argb32Image, RgbImage};
-use crate::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use crate::render::{
+    FillRule, FontMetricsPx, LineCap, LineJoin, Painter, PathCommand, Point, StrokeStyle,
+    TextMeasurer, TextStyle, Transform, Viewport, shaping,
+};
 use crate::style::FontFamily;
 use crate::win::com::ComPtr;
-use crate::win::stream;
 use core::ffi::c_void;
 use std::collections::HashMap;
 
@@ -25,12 +26,17 @@ pub(super) struct WinPainter {
     bgra: Vec<u8>,
     in_draw: bool,
     opacity_layers: Vec<ComPtr<d2d::ID2D1Layer>>,
+    clip_depth: usize,
+    /// Every open `push_transform` combined.
+    transform: Transform,
+    /// What each open `push_transform` replaced.
+    saved_transforms: Vec<Transform>,
     brush_cache: HashMap<u32, ComPtr<d2d::ID2D1SolidColorBrush>>,
     text_formats: std::cell::RefCell<HashMap<FontKey, ComPtr<dwrite::IDWriteTextFormat>>>,
     font_metrics: std::cell::RefCell<HashMap<FontKey, FontMetricsPx>>,
 
     _d3d: d3d11::D3DDevices,
-    _d2d_factory: ComPtr<d2d::ID2D1Factory1>,
+    d2d_factory: ComPtr<d2d::ID2D1Factory1>,
     _d2d_device: ComPtr<d2d::ID2D1Device>,
     d2d_ctx: ComPtr<d2d::ID2D1DeviceContext5>,
     d2d_target: ComPtr<d2d::ID2D1Bitmap1>,
@@ -73,11 +79,14 @@ impl WinPainter {
             bgra,
             in_draw: false,
             opacity_layers: Vec::new(),
+            clip_depth: 0,
+            transform: Transform::IDENTITY,
+            saved_transforms: Vec::new(),
             brush_cache: HashMap::new(),
             text_formats: std::cell::RefCell::new(HashMap::new()),
             font_metrics: std::cell::RefCell::new(HashMap::new()),
             _d3d: d3d,
-            _d2d_factory: d2d_factory,
+            d2d_factory,
             _d2d_device: d2d_device,
             d2d_ctx,
             d2d_target,
@@ -157,10 +166,82 @@ impl WinPainter {
         d2d::ctx_begin_draw(&self.d2d_ctx);
         d2d::ctx_set_unit_mode(&self.d2d_ctx, d2d::D2D1_UNIT_MODE_PIXELS);
         d2d::ctx_set_text_antialias_mode(&self.d2d_ctx, d2d::D2D1_TEXT_ANTIALIAS_MODE_CLEARTYPE);
-        d2d::ctx_set_transform(&self.d2d_ctx, &d2d::D2D1_IDENTITY_MATRIX);
+        d2d::ctx_set_transform(&self.d2d_ctx, &to_d2d_matrix(self.transform));
         self.in_draw = true;
     }
 
+    /// Builds `path` as a Direct2D geometry, which wants every figure begun and ended
+    /// explicitly.
+    fn path_geometry(
+        &self,
+        path: &[PathCommand],
+        fill_mode: u32,
+    ) -> Result<ComPtr<d2d::ID2D1PathGeometry>, String> {
+        let geometry =
+            d2d::factory_create_path_geometry(&self.d2d_factory).map_err(|err| err.message())?;
+        let sink = d2d::path_geometry_open(&geometry).map_err(|err| err.message())?;
+        d2d::sink_set_fill_mode(&sink, fill_mode);
+
+        let mut start = d2d::D2D1_POINT_2F::default();
+        let mut current = start;
+        let mut open = false;
+        for command in path {
+            if !open && !matches!(command, PathCommand::MoveTo(_) | PathCommand::Close) {
+                d2d::sink_begin_figure(&sink, current, d2d::D2D1_FIGURE_BEGIN_FILLED);
+                start = current;
+                open = true;
+            }
+            match *command {
+                PathCommand::MoveTo(to) => {
+                    if open {
+                        d2d::sink_end_figure(&sink, d2d::D2D1_FIGURE_END_OPEN);
+                    }
+                    current = to_d2d_point(to);
+                    d2d::sink_begin_figure(&sink, current, d2d::D2D1_FIGURE_BEGIN_FILLED);
+                    start = current;
+                    open = true;
+                }
+                PathCommand::LineTo(to) => {
+                    current = to_d2d_point(to);
+                    d2d::sink_add_line(&sink, current);
+                }
+                PathCommand::QuadTo(control, to) => {
+                    current = to_d2d_point(to);
+                    d2d::sink_add_quadratic_bezier(
+                        &sink,
+                        &d2d::D2D1_QUADRATIC_BEZIER_SEGMENT {
+                            point1: to_d2d_point(control),
+                            point2: current,
+                        },
+                    );
+                }
+                PathCommand::CubicTo(first, second, to) => {
+                    current = to_d2d_point(to);
+                    d2d::sink_add_bezier(
+                        &sink,
+                        &d2d::D2D1_BEZIER_SEGMENT {
+                            point1: to_d2d_point(first),
+                            point2: to_d2d_point(second),
+                            point3: current,
+                        },
+                    );
+                }
+                PathCommand::Close => {
+                    if open {
+                        d2d::sink_end_figure(&sink, d2d::D2D1_FIGURE_END_CLOSED);
+                        open = false;
+                    }
+                    current = start;
+                }
+            }
+        }
+        if open {
+            d2d::sink_end_figure(&sink, d2d::D2D1_FIGURE_END_OPEN);
+        }
+        d2d::sink_close(&sink).map_err(|err| err.message())?;
+        Ok(geometry)
+    }
+
     fn brush_for(&mut self, color: Color) -> Result<*mut d2d::ID2D1SolidColorBrush, String> {
         let key = (u32::from(color.r) << 24)
             | (u32::from(color.g) << 16)
@@ -267,7 +348,12 @@ impl WinPainter {
         };
 
         let format_ptr = self.text_format_ptr(style)?;
-        let text_w: Vec<u16> = text.encode_utf16().collect();
+        // Layout hands over right-to-left text already in display order, so the left-to-right
+        // override keeps DirectWrite from reordering it again.
+        let text_w: Vec<u16> = "\u{202D}"
+            .encode_utf16()
+            .chain(text.encode_utf16())
+            .collect();
         let len: u32 = text_w
             .len()
             .try_into()
@@ -362,17 +448,9 @@ impl TextMeasurer for WinPainter {
             return self.text_width_no_spacing(text, style);
         }
 
-        let mut total_width: i64 = 0;
-        let mut first = true;
-        for ch in text.chars() {
-            if !first {
-                total_width += i64::from(style.letter_spacing_px);
-            }
-            first = false;
-
-            let mut buf = [0u8; 4];
-            let ch = ch.encode_utf8(&mut buf);
-            total_width += i64::from(self.text_width_no_spacing(ch, style)?);
+        let mut total_width = i64::from(style.letter_spacing_total_px(text));
+        for cluster in shaping::clusters(text) {
+            total_width += i64::from(self.text_width_no_spacing(cluster, style)?);
         }
 
         Ok(total_width.clamp(0, i64::from(i32::MAX)) as i32)
@@ -538,17 +616,12 @@ impl Painter for WinPainter {
             self.draw_text_run(x_px, y_px, text, style)?;
         } else {
             let mut cursor_x = x_px;
-            let mut first = true;
-            for ch in text.chars() {
-                if !first {
+            for (index, cluster) in shaping::clusters(text).enumerate() {
+                if index > 0 {
                     cursor_x = cursor_x.saturating_add(style.letter_spacing_px);
                 }
-                first = false;
-
-                let mut buf = [0u8; 4];
-                let ch = ch.encode_utf8(&mut buf);
-                self.draw_text_run(cursor_x, y_px, ch, style)?;
-                cursor_x = cursor_x.saturating_add(self.text_width_no_spacing(ch, style)?);
+                self.draw_text_run(cursor_x, y_px, cluster, style)?;
+                cursor_x = cursor_x.saturating_add(self.text_width_no_spacing(cluster, style)?);
             }
         }
 
@@ -602,70 +675,122 @@ impl Painter for WinPainter {
         Ok(())
     }
 
-    fn draw_svg(
+    fn push_clip_rect(
         &mut self,
         x_px: i32,
         y_px: i32,
         width_px: i32,
         height_px: i32,
-        svg_xml: &str,
-        opacity: u8,
     ) -> Result<(), String> {
-        if width_px <= 0 || height_px <= 0 || opacity == 0 {
-            return Ok(());
-        }
+        self.begin_draw_if_needed();
+        let rect = d2d::D2D1_RECT_F {
+            left: x_px as f32,
+            top: y_px as f32,
+            right: x_px.saturating_add(width_px.max(0)) as f32,
+            bottom: y_px.saturating_add(height_px.max(0)) as f32,
+        };
+        d2d::ctx_push_axis_aligned_clip(
+            &self.d2d_ctx,
+            &rect,
+            d2d::D2D1_ANTIALIAS_MODE_PER_PRIMITIVE,
+        );
+        self.clip_depth = self.clip_depth.saturating_add(1);
+        Ok(())
+    }
 
-        let svg_xml = svg_xml.trim();
-        if svg_xml.is_empty() {
-            return Ok(());
+    fn pop_clip(&mut self) -> Result<(), String> {
+        if self.clip_depth == 0 {
+            return Err("clip stack underflow".to_owned());
         }
-
         self.begin_draw_if_needed();
+        d2d::ctx_pop_axis_aligned_clip(&self.d2d_ctx);
+        self.clip_depth -= 1;
+        Ok(())
+    }
 
-        let svg_xml = svg::ensure_xlink_namespace(svg_xml);
-        let stream = stream::create_istream_from_bytes(svg_xml.as_ref().as_bytes())
-            .map_err(|err| err.message())?;
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        self.begin_draw_if_needed();
+        self.saved_transforms.push(self.transform);
+        self.transform = self.transform.then(transform);
+        d2d::ctx_set_transform(&self.d2d_ctx, &to_d2d_matrix(self.transform));
+        Ok(())
+    }
 
-        let doc = match d2d::ctx_create_svg_document(
-            &self.d2d_ctx,
-            stream.as_ptr().cast::<c_void>(),
-            d2d::D2D1_SIZE_F {
-                width: width_px as f32,
-                height: height_px as f32,
-            },
-        ) {
-            Ok(doc) => doc,
-            Err(err) => {
-                debug::log(
-                    debug::Target::Render,
-                    debug::Level::Warn,
-                    format_args!("SVG render failed: {}", debug::shorten(&err.message(), 120)),
-                );
-                return Ok(());
-            }
+    fn pop_transform(&mut self) -> Result<(), String> {
+        let Some(transform) = self.saved_transforms.pop() else {
+            return Err("transform stack underflow".to_owned());
         };
+        self.begin_draw_if_needed();
+        self.transform = transform;
+        d2d::ctx_set_transform(&self.d2d_ctx, &to_d2d_matrix(self.transform));
+        Ok(())
+    }
 
-        let needs_local_opacity = opacity < 255;
-        if needs_local_opacity {
-            self.push_opacity(opacity)?;
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        if path.is_empty() || color.a == 0 {
+            return Ok(());
         }
-
-        let translate = d2d::D2D1_MATRIX_3X2_F {
-            m11: 1.0,
-            m12: 0.0,
-            m21: 0.0,
-            m22: 1.0,
-            dx: x_px as f32,
-            dy: y_px as f32,
+        let fill_mode = match rule {
+            FillRule::NonZero => d2d::D2D1_FILL_MODE_WINDING,
+            FillRule::EvenOdd => d2d::D2D1_FILL_MODE_ALTERNATE,
         };
-        d2d::ctx_set_transform(&self.d2d_ctx, &translate);
-        d2d::ctx_draw_svg_document(&self.d2d_ctx, &doc);
-        d2d::ctx_set_transform(&self.d2d_ctx, &d2d::D2D1_IDENTITY_MATRIX);
+        let geometry = self.path_geometry(path, fill_mode)?;
 
-        if needs_local_opacity {
-            self.pop_opacity(opacity)?;
+        self.begin_draw_if_needed();
+        let brush = self.brush_for(color)?;
+        d2d::ctx_fill_geometry(&self.d2d_ctx, &geometry, brush);
+        Ok(())
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        if path.is_empty() || color.a == 0 || !(style.width > 0.0 && style.width.is_finite()) {
+            return Ok(());
         }
+        let cap = match style.cap {
+            LineCap::Butt => d2d::D2D1_CAP_STYLE_FLAT,
+            LineCap::Round => d2d::D2D1_CAP_STYLE_ROUND,
+            LineCap::Square => d2d::D2D1_CAP_STYLE_SQUARE,
+        };
+        let line_join = match style.join {
+            LineJoin::Miter => d2d::D2D1_LINE_JOIN_MITER_OR_BEVEL,
+            LineJoin::Round => d2d::D2D1_LINE_JOIN_ROUND,
+            LineJoin::Bevel => d2d::D2D1_LINE_JOIN_BEVEL,
+        };
+        let stroke_style = d2d::factory_create_stroke_style(
+            &self.d2d_factory,
+            &d2d::D2D1_STROKE_STYLE_PROPERTIES {
+                start_cap: cap,
+                end_cap: cap,
+                dash_cap: cap,
+                line_join,
+                // Direct2D measures the miter against half the stroke width.
+                miter_limit: (style.miter_limit * 2.0) as f32,
+                dash_style: d2d::D2D1_DASH_STYLE_SOLID,
+                dash_offset: 0.0,
+            },
+        )
+        .map_err(|err| err.message())?;
+        let geometry = self.path_geometry(path, d2d::D2D1_FILL_MODE_WINDING)?;
 
+        self.begin_draw_if_needed();
+        let brush = self.brush_for(color)?;
+        d2d::ctx_draw_geometry(
+            &self.d2d_ctx,
+            &geometry,
+            brush,
+            style.width as f32,
+            &stroke_style,
+        );
         Ok(())
     }
 
@@ -786,6 +911,24 @@ fn create_back_buffers(
     Ok((target, readback, bgra))
 }
 
+fn to_d2d_point(point: Point) -> d2d::D2D1_POINT_2F {
+    d2d::D2D1_POINT_2F {
+        x: point.x as f32,
+        y: point.y as f32,
+    }
+}
+
+fn to_d2d_matrix(transform: Transform) -> d2d::D2D1_MATRIX_3X2_F {
+    d2d::D2D1_MATRIX_3X2_F {
+        m11: transform.a as f32,
+        m12: transform.b as f32,
+        m21: transform.c as f32,
+        m22: transform.d as f32,
+        dx: transform.e as f32,
+        dy: transform.f as f32,
+    }
+}
+
 fn to_d2d_color(color: Color) -> d2d::D2D1_COLOR_F {
     d2d::D2D1_COLOR_F {
         r: (color.r as f32) / 255.0,
@@ -1,15 +1,16 @@
+use super::WinSurface;
 use super::WindowOptions;
 use super::painter::WinPainter;
 use super::scale::ScaleFactor;
-use super::scaled::ScaledPainter;
 use super::wstr;
-use crate::app::App;
+use crate::app::{App, Key, KeyInput, ZoomCommand};
+use crate::image::Argb32Image;
+use crate::platform::{FrameScheduler, FrameStep, FrameSurface, WindowIdentity};
 use crate::render::Viewport;
 use core::ffi::c_void;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 const MAX_EVENTS_PER_TICK: usize = 512;
-const SCREENSHOT_RESOURCE_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
 const WHEEL_SCROLL_STEP_PX: i32 = 48;
 
 type BOOL = i32;
@@ -38,7 +39,18 @@ const PM_REMOVE: UINT = 0x0001;
 const SW_SHOW: i32 = 5;
 
 const VK_BACK: WPARAM = 0x08;
+const VK_TAB: WPARAM = 0x09;
+const VK_RETURN: WPARAM = 0x0d;
+const VK_CONTROL: i32 = 0x11;
 const VK_ESCAPE: WPARAM = 0x1b;
+const VK_END: WPARAM = 0x23;
+const VK_HOME: WPARAM = 0x24;
+const VK_LEFT: WPARAM = 0x25;
+const VK_RIGHT: WPARAM = 0x27;
+const VK_DELETE: WPARAM = 0x2e;
+const VK_F12: WPARAM = 0x7b;
+const VK_OEM_PLUS: WPARAM = 0xbb;
+const VK_OEM_MINUS: WPARAM = 0xbd;
 
 const WM_NCCREATE: UINT = 0x0081;
 const WM_DESTROY: UINT = 0x0002;
@@ -46,15 +58,30 @@ const WM_CLOSE: UINT = 0x0010;
 const WM_PAINT: UINT = 0x000f;
 const WM_ERASEBKGND: UINT = 0x0014;
 const WM_SIZE: UINT = 0x0005;
+const WM_ENTERSIZEMOVE: UINT = 0x0231;
+const WM_EXITSIZEMOVE: UINT = 0x0232;
+const WM_SETICON: UINT = 0x0080;
 const WM_KEYDOWN: UINT = 0x0100;
+const WM_CHAR: UINT = 0x0102;
+const WM_SYSKEYDOWN: UINT = 0x0104;
+const WM_MOUSEMOVE: UINT = 0x0200;
 const WM_LBUTTONDOWN: UINT = 0x0201;
+const WM_LBUTTONUP: UINT = 0x0202;
 const WM_MOUSEWHEEL: UINT = 0x020a;
+const WM_MOUSEHWHEEL: UINT = 0x020e;
+const MK_SHIFT: WPARAM = 0x0004;
 const WM_XBUTTONDOWN: UINT = 0x020b;
 const WM_DPICHANGED: UINT = 0x02e0;
 const WM_QUIT: UINT = 0x0012;
 
+const ICON_SMALL: WPARAM = 0;
+const ICON_BIG: WPARAM = 1;
+
+const SIZE_MINIMIZED: WPARAM = 1;
+
 const WHEEL_DELTA: i32 = 120;
 const XBUTTON1: u16 = 0x0001;
+const XBUTTON2: u16 = 0x0002;
 
 const WS_OVERLAPPEDWINDOW: DWORD = 0x00cf_0000;
 const WS_VISIBLE: DWORD = 0x1000_0000;
@@ -151,6 +178,7 @@ unsafe extern "system" {
     fn PeekMessageW(msg: *mut MSG, hwnd: HWND, min: UINT, max: UINT, remove: UINT) -> BOOL;
     fn TranslateMessage(msg: *const MSG) -> BOOL;
     fn DispatchMessageW(msg: *const MSG) -> LRESULT;
+    fn GetKeyState(virtual_key: i32) -> i16;
     fn PostQuitMessage(exit_code: i32);
     fn AdjustWindowRectEx(rect: *mut RECT, style: DWORD, menu: BOOL, ex_style: DWORD) -> BOOL;
     fn GetClientRect(hwnd: HWND, rect: *mut RECT) -> BOOL;
@@ -170,6 +198,18 @@ unsafe extern "system" {
         cy: i32,
         flags: UINT,
     ) -> BOOL;
+    fn SetWindowTextW(hwnd: HWND, text: *const u16) -> BOOL;
+    fn SendMessageW(hwnd: HWND, msg: UINT, w_param: WPARAM, l_param: LPARAM) -> LRESULT;
+    fn CreateIcon(
+        instance: HINSTANCE,
+        width: i32,
+        height: i32,
+        planes: u8,
+        bits_per_pixel: u8,
+        and_bits: *const u8,
+        xor_bits: *const u8,
+    ) -> HICON;
+    fn DestroyIcon(icon: HICON) -> BOOL;
 }
 
 const SWP_NOZORDER: UINT = 0x0004;
@@ -177,9 +217,14 @@ const SWP_NOACTIVATE: UINT = 0x0010;
 
 #[derive(Clone, Copy, Debug)]
 enum WindowEvent {
+    MouseMove { x_px: i32, y_px: i32 },
     MouseDown { x_px: i32, y_px: i32 },
+    MouseUp { x_px: i32, y_px: i32 },
     MouseWheel { wheel_delta: i32 },
+    HorizontalWheel { wheel_delta: i32 },
     NavigateBack,
+    NavigateForward,
+    Key(KeyInput),
 }
 
 #[derive(Debug)]
@@ -188,9 +233,49 @@ struct WindowState {
     needs_redraw: bool,
     dpi_changed: bool,
     new_client_size: Option<(i32, i32)>,
+    in_size_move: bool,
+    // SAFETY: `frame` points at the `ResizeFrame` on `run`'s stack and is only set while one
+    // pass of its message loop dispatches messages; `run` clears it before that frame goes out
+    // of scope, so `wnd_proc` never follows a dangling pointer.
+    live_resize: Option<LiveResize>,
     events: Vec<WindowEvent>,
 }
 
+/// How `wnd_proc` lays the page out again at each size while the window frame is dragged, since
+/// Windows runs its own message loop until the drag ends and `run` gets no turn before then.
+#[derive(Clone, Copy, Debug)]
+struct LiveResize {
+    frame: *mut c_void,
+    render: unsafe fn(*mut c_void, Viewport),
+}
+
+struct ResizeFrame<'a, A> {
+    app: &'a mut A,
+    painter: &'a mut WinPainter,
+    scale: ScaleFactor,
+    error: Option<String>,
+}
+
+/// Renders the `ResizeFrame<A>` behind `frame` at `viewport`, keeping the first error for `run`.
+unsafe fn render_resize_frame<A: App>(frame: *mut c_void, viewport: Viewport) {
+    let frame = unsafe { &mut *frame.cast::<ResizeFrame<'_, A>>() };
+    if frame.error.is_some() || viewport.width_px <= 0 || viewport.height_px <= 0 {
+        return;
+    }
+    let mut surface = WinSurface {
+        painter: frame.painter,
+        scale: frame.scale,
+        viewport,
+        css_viewport: Viewport {
+            width_px: frame.scale.device_size_to_css_px(viewport.width_px),
+            height_px: frame.scale.device_size_to_css_px(viewport.height_px),
+        },
+    };
+    if let Err(err) = surface.render(&mut *frame.app) {
+        frame.error = Some(err);
+    }
+}
+
 impl WindowState {
     fn new() -> Self {
         Self {
@@ -198,6 +283,8 @@ impl WindowState {
             needs_redraw: false,
             dpi_changed: false,
             new_client_size: None,
+            in_size_move: false,
+            live_resize: None,
             events: Vec::new(),
         }
     }
@@ -231,8 +318,11 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
         &class_name,
         state_ptr,
     )?;
+    let mut identity = WindowIdentity::new(title);
+    let mut window_icon: HICON = std::ptr::null_mut();
 
-    let mut scale = ScaleFactor::detect(false, Some(hwnd));
+    let mut base_scale = ScaleFactor::detect(false, Some(hwnd));
+    let mut scale = base_scale.zoomed(app.zoom_percent());
 
     let mut viewport = client_viewport(hwnd)?;
     if viewport.width_px <= 0 || viewport.height_px <= 0 {
@@ -247,15 +337,22 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
 
     let mut painter = WinPainter::new(viewport, Some(hwnd))?;
 
-    let mut screenshot_path = options.screenshot_path;
-
-    let mut needs_redraw = true;
+    let mut frames = FrameScheduler::new(&options);
     let mut should_exit = false;
-    let mut has_rendered_ready_state = false;
-    let mut resource_wait_started: Option<Instant> = None;
     let mut wheel_accum: i32 = 0;
+    let mut horizontal_wheel_accum: i32 = 0;
 
     loop {
+        let mut resize_frame = ResizeFrame {
+            app: &mut *app,
+            painter: &mut painter,
+            scale,
+            error: None,
+        };
+        state.live_resize = Some(LiveResize {
+            frame: (&mut resize_frame as *mut ResizeFrame<'_, A>).cast(),
+            render: render_resize_frame::<A>,
+        });
         let mut processed = 0usize;
         while processed < MAX_EVENTS_PER_TICK {
             let mut msg = MSG {
@@ -280,6 +377,10 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
             }
             processed += 1;
         }
+        state.live_resize = None;
+        if let Some(err) = resize_frame.error {
+            return Err(err);
+        }
 
         if should_exit || state.should_close {
             break;
@@ -288,11 +389,10 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
         if state.dpi_changed {
             state.dpi_changed = false;
             let next_scale = ScaleFactor::detect(false, Some(hwnd));
-            if next_scale != scale {
-                scale = next_scale;
-                needs_redraw = true;
-                has_rendered_ready_state = false;
-                resource_wait_started = None;
+            if next_scale != base_scale {
+                base_scale = next_scale;
+                scale = base_scale.zoomed(app.zoom_percent());
+                frames.invalidate();
             }
             viewport = client_viewport(hwnd)?;
             css_viewport = Viewport {
@@ -310,44 +410,87 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
                 width_px: scale.device_size_to_css_px(viewport.width_px),
                 height_px: scale.device_size_to_css_px(viewport.height_px),
             };
-            needs_redraw = true;
-            has_rendered_ready_state = false;
-            resource_wait_started = None;
+            frames.invalidate();
         }
 
         if state.needs_redraw {
             state.needs_redraw = false;
-            needs_redraw = true;
+            frames.request_redraw();
         }
 
         let events = std::mem::take(&mut state.events);
         for event in events {
             match event {
+                WindowEvent::MouseMove { x_px, y_px } => {
+                    let x_css = scale.device_coord_to_css_px(x_px);
+                    let y_css = scale.device_coord_to_css_px(y_px);
+                    let tick = app.mouse_move(x_css, y_css, css_viewport)?;
+                    frames.note(tick);
+                }
                 WindowEvent::MouseDown { x_px, y_px } => {
                     let x_css = scale.device_coord_to_css_px(x_px);
                     let y_css = scale.device_coord_to_css_px(y_px);
                     let tick = app.mouse_down(x_css, y_css, css_viewport)?;
-                    if tick.needs_redraw {
-                        needs_redraw = true;
-                    }
+                    frames.note(tick);
+                }
+                WindowEvent::MouseUp { x_px, y_px } => {
+                    let x_css = scale.device_coord_to_css_px(x_px);
+                    let y_css = scale.device_coord_to_css_px(y_px);
+                    let tick = app.mouse_up(x_css, y_css, css_viewport)?;
+                    frames.note(tick);
                 }
                 WindowEvent::MouseWheel { wheel_delta } => {
-                    wheel_accum = wheel_accum.saturating_add(wheel_delta);
-                    let steps = wheel_accum / WHEEL_DELTA;
-                    if steps != 0 {
-                        wheel_accum -= steps * WHEEL_DELTA;
-                        let delta_y_device_px = (-steps).saturating_mul(WHEEL_SCROLL_STEP_PX);
+                    let delta_y_device_px = -wheel_delta_to_px(&mut wheel_accum, wheel_delta);
+                    if delta_y_device_px != 0 {
                         let delta_y_css = scale.device_delta_to_css_px(delta_y_device_px);
-                        let tick = app.mouse_wheel(delta_y_css, css_viewport)?;
-                        if tick.needs_redraw {
-                            needs_redraw = true;
-                        }
+                        // Whole notches come from a plain wheel and may glide, like X11's
+                        // buttons 4 and 5; anything finer is a high-resolution wheel or touchpad.
+                        let tick = if wheel_delta % WHEEL_DELTA == 0 {
+                            app.wheel_notch(delta_y_css, css_viewport)?
+                        } else {
+                            app.mouse_wheel(delta_y_css, css_viewport)?
+                        };
+                        frames.note(tick);
+                    }
+                }
+                WindowEvent::HorizontalWheel { wheel_delta } => {
+                    let delta_x_device_px =
+                        wheel_delta_to_px(&mut horizontal_wheel_accum, wheel_delta);
+                    if delta_x_device_px != 0 {
+                        let delta_x_css = scale.device_delta_to_css_px(delta_x_device_px);
+                        let tick = app.horizontal_wheel(delta_x_css, css_viewport)?;
+                        frames.note(tick);
                     }
                 }
                 WindowEvent::NavigateBack => {
                     let tick = app.navigate_back()?;
-                    if tick.needs_redraw {
-                        needs_redraw = true;
+                    frames.note(tick);
+                }
+                WindowEvent::NavigateForward => {
+                    let tick = app.navigate_forward()?;
+                    frames.note(tick);
+                }
+                WindowEvent::Key(input) => {
+                    if let Some(command) = ZoomCommand::from_key(input) {
+                        app.set_zoom_percent(command.apply(app.zoom_percent()));
+                        scale = base_scale.zoomed(app.zoom_percent());
+                        css_viewport = Viewport {
+                            width_px: scale.device_size_to_css_px(viewport.width_px),
+                            height_px: scale.device_size_to_css_px(viewport.height_px),
+                        };
+                        frames.invalidate();
+                        continue;
+                    }
+                    match app.key_down(input, css_viewport)? {
+                        Some(tick) => frames.note(tick),
+                        None => match input.key {
+                            Key::Backspace => {
+                                let tick = app.navigate_back()?;
+                                frames.note(tick);
+                            }
+                            Key::Escape => should_exit = true,
+                            _ => {}
+                        },
                     }
                 }
             }
@@ -358,94 +501,114 @@ pub(super) fn run<A: App>(title: &str, options: WindowOptions, app: &mut A) -> R
         }
 
         let tick = app.tick()?;
-        if tick.needs_redraw {
-            needs_redraw = true;
-        }
-
-        let ready_for_screenshot = tick.ready_for_screenshot;
-        if !ready_for_screenshot {
-            has_rendered_ready_state = false;
-            resource_wait_started = None;
-        }
-
-        let should_wait_for_resources = tick.pending_resources > 0;
-        let timed_out_waiting_for_resources = resource_wait_started
-            .is_some_and(|started| started.elapsed() >= SCREENSHOT_RESOURCE_WAIT_TIMEOUT);
-        let can_complete = !should_wait_for_resources || timed_out_waiting_for_resources;
-
-        let wants_screenshot = screenshot_path.is_some();
-        let should_complete_screenshot =
-            wants_screenshot && ready_for_screenshot && has_rendered_ready_state;
-
-        let mut capture_now = false;
-        let mut capture_after_render = false;
-
-        if ready_for_screenshot && wants_screenshot && !has_rendered_ready_state {
-            needs_redraw = true;
-        } else if ready_for_screenshot && should_wait_for_resources && has_rendered_ready_state {
-            resource_wait_started.get_or_insert(Instant::now());
-        } else if ready_for_screenshot && has_rendered_ready_state {
-            resource_wait_started = None;
-        }
-
-        if ready_for_screenshot
-            && has_rendered_ready_state
-            && can_complete
-            && should_complete_screenshot
-        {
-            if needs_redraw {
-                capture_after_render = true;
-            } else {
-                capture_now = true;
+        if let Some(title) = identity.title_change(app) {
+            let title_w = wstr::utf16_nul(&title);
+            unsafe {
+                SetWindowTextW(hwnd, title_w.as_ptr());
             }
         }
-
-        if capture_now {
-            let Some(path) = screenshot_path.take() else {
-                return Err(
-                    "Internal error: capture_now set but screenshot path missing".to_owned(),
-                );
-            };
-            let rgb = painter.capture_back_buffer_rgb()?;
-            crate::png::write_rgb_png(&path, &rgb)?;
-            break;
+        if let Some(icon) = identity.icon_change(app) {
+            window_icon = set_window_icon(hwnd, &icon, window_icon);
         }
 
-        if needs_redraw {
-            if viewport.width_px > 0 && viewport.height_px > 0 {
-                painter.ensure_back_buffer(viewport)?;
-                let mut scaled_painter = ScaledPainter::new(&mut painter, scale);
-                app.render(&mut scaled_painter, css_viewport)?;
-                needs_redraw = false;
-
-                if ready_for_screenshot {
-                    has_rendered_ready_state = true;
-                    if capture_after_render {
-                        let Some(path) = screenshot_path.take() else {
-                            return Err("Internal error: capture_after_render set but screenshot path missing".to_owned());
-                        };
-                        let rgb = painter.capture_back_buffer_rgb()?;
-                        crate::png::write_rgb_png(&path, &rgb)?;
-                        break;
-                    }
-                }
-            } else {
-                needs_redraw = false;
-            }
+        let mut surface = WinSurface {
+            painter: &mut painter,
+            scale,
+            viewport,
+            css_viewport,
+        };
+        if frames.frame(app, tick, &mut surface)? == FrameStep::Done {
+            break;
         }
 
-        if !needs_redraw {
-            std::thread::sleep(Duration::from_millis(10));
-        }
+        std::thread::sleep(Duration::from_millis(10));
     }
 
     unsafe {
         let _ = DestroyWindow(hwnd);
+        if !window_icon.is_null() {
+            let _ = DestroyIcon(window_icon);
+        }
     }
 
     Ok(())
 }
 
+/// Shows `icon` in the title bar and taskbar in place of `shown`, which is destroyed, and
+/// returns the handle now in use. `shown` stays when the icon can't be made.
+fn set_window_icon(hwnd: HWND, icon: &Argb32Image, shown: HICON) -> HICON {
+    let (Ok(width), Ok(height)) = (i32::try_from(icon.width), i32::try_from(icon.height)) else {
+        return shown;
+    };
+    // A 32bpp icon takes straight-alpha BGRA rows; its AND mask, one bit per pixel in rows
+    // padded to 16 bits, is ignored where there is alpha but must still be given.
+    let xor_bits: Vec<u8> = icon
+        .data
+        .chunks_exact(4)
+        .flat_map(|bgra| {
+            let alpha = u32::from(bgra[3]);
+            let unpremultiply = |channel: u8| {
+                if alpha == 0 {
+                    0
+                } else {
+                    ((u32::from(channel) * 255 + alpha / 2) / alpha).min(255) as u8
+                }
+            };
+            [
+                unpremultiply(bgra[0]),
+                unpremultiply(bgra[1]),
+                unpremultiply(bgra[2]),
+                bgra[3],
+            ]
+        })
+        .collect();
+    let and_bits = vec![0u8; icon.width.div_ceil(16) as usize * 2 * icon.height as usize];
+    let handle = unsafe {
+        CreateIcon(
+            std::ptr::null_mut(),
+            width,
+            height,
+            1,
+            32,
+            and_bits.as_ptr(),
+            xor_bits.as_ptr(),
+        )
+    };
+    if handle.is_null() {
+        return shown;
+    }
+    unsafe {
+        SendMessageW(hwnd, WM_SETICON, ICON_SMALL, handle as LPARAM);
+        SendMessageW(hwnd, WM_SETICON, ICON_BIG, handle as LPARAM);
+        if !shown.is_null() {
+            let _ = DestroyIcon(shown);
+        }
+    }
+    handle
+}
+
+/// Printable keys arrive through `WM_CHAR`, except Ctrl chords which it folds into control codes.
+fn virtual_key(code: WPARAM, ctrl: bool) -> Option<Key> {
+    match code {
+        VK_BACK => Some(Key::Backspace),
+        VK_RETURN => Some(Key::Enter),
+        VK_ESCAPE => Some(Key::Escape),
+        VK_TAB => Some(Key::Tab),
+        VK_END => Some(Key::End),
+        VK_HOME => Some(Key::Home),
+        VK_LEFT => Some(Key::Left),
+        VK_RIGHT => Some(Key::Right),
+        VK_DELETE => Some(Key::Delete),
+        VK_F12 => Some(Key::F12),
+        VK_OEM_PLUS if ctrl => Some(Key::Char('=')),
+        VK_OEM_MINUS if ctrl => Some(Key::Char('-')),
+        0x30..=0x39 | 0x41..=0x5a if ctrl => u8::try_from(code)
+            .ok()
+            .map(|code| Key::Char(char::from(code).to_ascii_lowercase())),
+        _ => None,
+    }
+}
+
 fn create_window(
     title: &str,
     client_width_px: i32,
@@ -571,20 +734,56 @@ unsafe extern "system" fn wnd_proc(
                 return 0;
             }
             WM_KEYDOWN => {
-                if w_param == VK_BACK {
+                let ctrl = GetKeyState(VK_CONTROL) < 0;
+                if let Some(key) = virtual_key(w_param, ctrl) {
                     if let Some(state) = state {
-                        state.events.push(WindowEvent::NavigateBack);
+                        state.events.push(WindowEvent::Key(KeyInput {
+                            key,
+                            ctrl,
+                            alt: false,
+                        }));
                     }
                     return 0;
                 }
-                if w_param == VK_ESCAPE {
+            }
+            WM_SYSKEYDOWN => {
+                // Alt chords arrive here; only Alt+Left/Right are taken so Alt+F4 and menus still work.
+                if w_param == VK_LEFT || w_param == VK_RIGHT {
+                    if let Some(state) = state {
+                        let key = if w_param == VK_LEFT {
+                            Key::Left
+                        } else {
+                            Key::Right
+                        };
+                        state.events.push(WindowEvent::Key(KeyInput {
+                            key,
+                            ctrl: false,
+                            alt: true,
+                        }));
+                    }
+                    return 0;
+                }
+            }
+            WM_CHAR => {
+                let ch = u32::try_from(w_param).ok().and_then(char::from_u32);
+                if let Some(ch) = ch.filter(|ch| !ch.is_control()) {
                     if let Some(state) = state {
-                        state.should_close = true;
+                        state
+                            .events
+                            .push(WindowEvent::Key(KeyInput::plain(Key::Char(ch))));
                     }
-                    let _ = DestroyWindow(hwnd);
                     return 0;
                 }
             }
+            WM_MOUSEMOVE => {
+                if let Some(state) = state {
+                    state.events.push(WindowEvent::MouseMove {
+                        x_px: get_x_lparam(l_param),
+                        y_px: get_y_lparam(l_param),
+                    });
+                }
+                return 0;
+            }
             WM_LBUTTONDOWN => {
                 if let Some(state) = state {
                     state.events.push(WindowEvent::MouseDown {
@@ -594,9 +793,31 @@ unsafe extern "system" fn wnd_proc(
                 }
                 return 0;
             }
+            WM_LBUTTONUP => {
+                if let Some(state) = state {
+                    state.events.push(WindowEvent::MouseUp {
+                        x_px: get_x_lparam(l_param),
+                        y_px: get_y_lparam(l_param),
+                    });
+                }
+                return 0;
+            }
             WM_MOUSEWHEEL => {
                 if let Some(state) = state {
-                    state.events.push(WindowEvent::MouseWheel {
+                    let wheel_delta = get_wheel_delta_wparam(w_param);
+                    state.events.push(if w_param & MK_SHIFT != 0 {
+                        WindowEvent::HorizontalWheel {
+                            wheel_delta: -wheel_delta,
+                        }
+                    } else {
+                        WindowEvent::MouseWheel { wheel_delta }
+                    });
+                }
+                return 0;
+            }
+            WM_MOUSEHWHEEL => {
+                if let Some(state) = state {
+                    state.events.push(WindowEvent::HorizontalWheel {
                         wheel_delta: get_wheel_delta_wparam(w_param),
                     });
                 }
@@ -608,13 +829,37 @@ unsafe extern "system" fn wnd_proc(
                     if let Some(state) = state {
                         state.events.push(WindowEvent::NavigateBack);
                     }
+                } else if button == XBUTTON2 {
+                    if let Some(state) = state {
+                        state.events.push(WindowEvent::NavigateForward);
+                    }
                 }
-                return 0;
+                return 1;
             }
             WM_SIZE => {
-                if let Some(state) = state {
-                    state.new_client_size = Some((get_x_lparam(l_param), get_y_lparam(l_param)));
+                if let Some(state) = state
+                    && w_param != SIZE_MINIMIZED
+                {
+                    let (width_px, height_px) = (get_x_lparam(l_param), get_y_lparam(l_param));
+                    state.new_client_size = Some((width_px, height_px));
                     state.needs_redraw = true;
+                    if state.in_size_move
+                        && let Some(live_resize) = state.live_resize
+                    {
+                        (live_resize.render)(
+                            live_resize.frame,
+                            Viewport {
+                                width_px,
+                                height_px,
+                            },
+                        );
+                    }
+                }
+                return 0;
+            }
+            WM_ENTERSIZEMOVE | WM_EXITSIZEMOVE => {
+                if let Some(state) = state {
+                    state.in_size_move = msg == WM_ENTERSIZEMOVE;
                 }
                 return 0;
             }
@@ -684,6 +929,51 @@ fn get_wheel_delta_wparam(w_param: WPARAM) -> i32 {
     (delta as i16) as i32
 }
 
+/// Turns `wheel_delta`, in 120ths of a notch, into device pixels, carrying what falls short of a
+/// whole pixel in `remainder` so a high-resolution wheel's small deltas still add up.
+fn wheel_delta_to_px(remainder: &mut i32, wheel_delta: i32) -> i32 {
+    let scaled = remainder.saturating_add(wheel_delta.saturating_mul(WHEEL_SCROLL_STEP_PX));
+    let px = scaled / WHEEL_DELTA;
+    *remainder = scaled - px * WHEEL_DELTA;
+    px
+}
+
 fn get_xbutton_wparam(w_param: WPARAM) -> u16 {
     ((w_param as u32 >> 16) & 0xFFFF) as u16
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wheel_deltas_carry_fractions_of_a_pixel_in_either_direction() {
+        let mut remainder = 0;
+        assert_eq!(
+            wheel_delta_to_px(&mut remainder, WHEEL_DELTA),
+            WHEEL_SCROLL_STEP_PX
+        );
+        assert_eq!(remainder, 0);
+
+        // A high-resolution wheel's small deltas add up to whole pixels.
+        let steps: Vec<i32> = (0..3)
+            .map(|_| wheel_delta_to_px(&mut remainder, 1))
+            .collect();
+        assert_eq!(steps, vec![0, 0, 1]);
+        assert_eq!(remainder, 3 * WHEEL_SCROLL_STEP_PX - WHEEL_DELTA);
+
+        // Scrolling back carries the remainder through zero.
+        let mut remainder = 0;
+        assert_eq!(wheel_delta_to_px(&mut remainder, -1), 0);
+        assert_eq!(remainder, -WHEEL_SCROLL_STEP_PX);
+        assert_eq!(wheel_delta_to_px(&mut remainder, -2), -1);
+        assert_eq!(remainder, WHEEL_DELTA - 3 * WHEEL_SCROLL_STEP_PX);
+        assert_eq!(wheel_delta_to_px(&mut remainder, 1), 0);
+        assert_eq!(remainder, WHEEL_DELTA - 2 * WHEEL_SCROLL_STEP_PX);
+        // What was carried forwards takes a pixel off a whole notch back.
+        assert_eq!(
+            wheel_delta_to_px(&mut remainder, -WHEEL_DELTA),
+            1 - WHEEL_SCROLL_STEP_PX
+        );
+    }
+}
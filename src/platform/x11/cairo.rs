@@ -1,7 +1,8 @@
-use crate::geom::Color;
-use crate::render::TextStyle;
-use core::ffi::{c_char, c_double, c_int, c_void};
-use std::borrow::Cow;
+use crate::geom::{Color, Rect};
+use crate::render::{
+    FillRule, LineCap, LineJoin, PathCommand, Point, StrokeStyle, TextStyle, Transform, shaping,
+};
+use core::ffi::{c_char, c_double, c_int};
 use std::ffi::{CStr, CString};
 
 use super::xlib::{Display, Drawable, Visual};
@@ -26,6 +27,16 @@ struct cairo_text_extents_t {
     y_advance: c_double,
 }
 
+#[repr(C)]
+struct cairo_matrix_t {
+    xx: c_double,
+    yx: c_double,
+    xy: c_double,
+    yy: c_double,
+    x0: c_double,
+    y0: c_double,
+}
+
 #[allow(non_camel_case_types)]
 type cairo_status_t = c_int;
 const CAIRO_STATUS_SUCCESS: cairo_status_t = 0;
@@ -52,6 +63,32 @@ enum cairo_font_weight_t {
     CAIRO_FONT_WEIGHT_BOLD = 1,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+enum cairo_fill_rule_t {
+    CAIRO_FILL_RULE_WINDING = 0,
+    CAIRO_FILL_RULE_EVEN_ODD = 1,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+enum cairo_line_cap_t {
+    CAIRO_LINE_CAP_BUTT = 0,
+    CAIRO_LINE_CAP_ROUND = 1,
+    CAIRO_LINE_CAP_SQUARE = 2,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+enum cairo_line_join_t {
+    CAIRO_LINE_JOIN_MITER = 0,
+    CAIRO_LINE_JOIN_ROUND = 1,
+    CAIRO_LINE_JOIN_BEVEL = 2,
+}
+
 #[link(name = "cairo")]
 unsafe extern "C" {
     fn cairo_xlib_surface_create(
@@ -86,6 +123,8 @@ unsafe extern "C" {
     );
     fn cairo_fill(cr: *mut cairo_t);
     fn cairo_new_path(cr: *mut cairo_t);
+    fn cairo_clip(cr: *mut cairo_t);
+    fn cairo_reset_clip(cr: *mut cairo_t);
 
     fn cairo_move_to(cr: *mut cairo_t, x: c_double, y: c_double);
     fn cairo_line_to(cr: *mut cairo_t, x: c_double, y: c_double);
@@ -98,13 +137,27 @@ unsafe extern "C" {
         angle2: c_double,
     );
     fn cairo_close_path(cr: *mut cairo_t);
+    fn cairo_curve_to(
+        cr: *mut cairo_t,
+        x1: c_double,
+        y1: c_double,
+        x2: c_double,
+        y2: c_double,
+        x3: c_double,
+        y3: c_double,
+    );
+    fn cairo_set_fill_rule(cr: *mut cairo_t, fill_rule: cairo_fill_rule_t);
     fn cairo_set_line_width(cr: *mut cairo_t, width: c_double);
+    fn cairo_set_line_cap(cr: *mut cairo_t, line_cap: cairo_line_cap_t);
+    fn cairo_set_line_join(cr: *mut cairo_t, line_join: cairo_line_join_t);
+    fn cairo_set_miter_limit(cr: *mut cairo_t, limit: c_double);
     fn cairo_stroke(cr: *mut cairo_t);
 
     fn cairo_save(cr: *mut cairo_t);
     fn cairo_restore(cr: *mut cairo_t);
     fn cairo_translate(cr: *mut cairo_t, tx: c_double, ty: c_double);
     fn cairo_scale(cr: *mut cairo_t, sx: c_double, sy: c_double);
+    fn cairo_transform(cr: *mut cairo_t, matrix: *const cairo_matrix_t);
     fn cairo_set_source_surface(
         cr: *mut cairo_t,
         surface: *mut cairo_surface_t,
@@ -139,51 +192,6 @@ unsafe extern "C" {
     ) -> *mut cairo_surface_t;
 }
 
-#[repr(C)]
-struct RsvgHandle {
-    _private: [u8; 0],
-}
-
-#[repr(C)]
-struct GError {
-    domain: u32,
-    code: c_int,
-    message: *mut c_char,
-}
-
-#[repr(C)]
-struct RsvgRectangle {
-    x: c_double,
-    y: c_double,
-    width: c_double,
-    height: c_double,
-}
-
-#[link(name = "rsvg-2")]
-unsafe extern "C" {
-    fn rsvg_handle_new_from_data(
-        data: *const u8,
-        data_len: usize,
-        error: *mut *mut GError,
-    ) -> *mut RsvgHandle;
-    fn rsvg_handle_render_document(
-        handle: *mut RsvgHandle,
-        cr: *mut cairo_t,
-        viewport: *const RsvgRectangle,
-        error: *mut *mut GError,
-    ) -> c_int;
-}
-
-#[link(name = "gobject-2.0")]
-unsafe extern "C" {
-    fn g_object_unref(obj: *mut c_void);
-}
-
-#[link(name = "glib-2.0")]
-unsafe extern "C" {
-    fn g_error_free(error: *mut GError);
-}
-
 pub struct CairoCanvas {
     display: *mut Display,
     visual: *mut Visual,
@@ -291,6 +299,142 @@ impl CairoCanvas {
         }
     }
 
+    /// Limits painting to `clip`; `None` paints everywhere.
+    pub fn set_clip(&mut self, clip: Option<Rect>) {
+        if self.cr.is_null() {
+            return;
+        }
+        unsafe {
+            cairo_reset_clip(self.cr);
+            if let Some(rect) = clip {
+                cairo_rectangle(
+                    self.cr,
+                    f64::from(rect.x),
+                    f64::from(rect.y),
+                    f64::from(rect.width.max(0)),
+                    f64::from(rect.height.max(0)),
+                );
+                cairo_clip(self.cr);
+            }
+        }
+    }
+
+    /// Limits painting to the rectangle, within any clip already pushed, until
+    /// [`CairoCanvas::restore`].
+    pub fn push_clip_rect(&mut self, x_px: i32, y_px: i32, width_px: i32, height_px: i32) {
+        if self.cr.is_null() {
+            return;
+        }
+        unsafe {
+            cairo_save(self.cr);
+            cairo_rectangle(
+                self.cr,
+                f64::from(x_px),
+                f64::from(y_px),
+                f64::from(width_px.max(0)),
+                f64::from(height_px.max(0)),
+            );
+            cairo_clip(self.cr);
+        }
+    }
+
+    /// Maps everything painted until [`CairoCanvas::restore`] through `transform`.
+    pub fn push_transform(&mut self, transform: Transform) {
+        if self.cr.is_null() {
+            return;
+        }
+        let determinant = transform.a * transform.d - transform.b * transform.c;
+        unsafe {
+            cairo_save(self.cr);
+            // A matrix Cairo can't invert would put the context in an error state for good;
+            // it paints nothing anyway, so an empty clip stands in for it.
+            if determinant == 0.0 || !determinant.is_finite() {
+                cairo_rectangle(self.cr, 0.0, 0.0, 0.0, 0.0);
+                cairo_clip(self.cr);
+                return;
+            }
+            let matrix = cairo_matrix_t {
+                xx: transform.a,
+                yx: transform.b,
+                xy: transform.c,
+                yy: transform.d,
+                x0: transform.e,
+                y0: transform.f,
+            };
+            cairo_transform(self.cr, &matrix);
+        }
+    }
+
+    /// Undoes the last `push_clip_rect` or `push_transform`.
+    pub fn restore(&mut self) {
+        if self.cr.is_null() {
+            return;
+        }
+        unsafe { cairo_restore(self.cr) };
+    }
+
+    pub fn fill_path(&mut self, path: &[PathCommand], rule: FillRule, color: Color) {
+        if self.cr.is_null() {
+            return;
+        }
+        let fill_rule = match rule {
+            FillRule::NonZero => cairo_fill_rule_t::CAIRO_FILL_RULE_WINDING,
+            FillRule::EvenOdd => cairo_fill_rule_t::CAIRO_FILL_RULE_EVEN_ODD,
+        };
+        unsafe {
+            cairo_save(self.cr);
+            cairo_set_source_rgba(
+                self.cr,
+                f64::from(color.r) / 255.0,
+                f64::from(color.g) / 255.0,
+                f64::from(color.b) / 255.0,
+                f64::from(color.a) / 255.0,
+            );
+            cairo_set_fill_rule(self.cr, fill_rule);
+            add_path(self.cr, path);
+            cairo_fill(self.cr);
+            cairo_restore(self.cr);
+            cairo_surface_flush(self.surface);
+        }
+    }
+
+    pub fn stroke_path(&mut self, path: &[PathCommand], style: &StrokeStyle, color: Color) {
+        if self.cr.is_null() {
+            return;
+        }
+        if !(style.width > 0.0 && style.width.is_finite()) {
+            return;
+        }
+        let line_cap = match style.cap {
+            LineCap::Butt => cairo_line_cap_t::CAIRO_LINE_CAP_BUTT,
+            LineCap::Round => cairo_line_cap_t::CAIRO_LINE_CAP_ROUND,
+            LineCap::Square => cairo_line_cap_t::CAIRO_LINE_CAP_SQUARE,
+        };
+        let line_join = match style.join {
+            LineJoin::Miter => cairo_line_join_t::CAIRO_LINE_JOIN_MITER,
+            LineJoin::Round => cairo_line_join_t::CAIRO_LINE_JOIN_ROUND,
+            LineJoin::Bevel => cairo_line_join_t::CAIRO_LINE_JOIN_BEVEL,
+        };
+        unsafe {
+            cairo_save(self.cr);
+            cairo_set_source_rgba(
+                self.cr,
+                f64::from(color.r) / 255.0,
+                f64::from(color.g) / 255.0,
+                f64::from(color.b) / 255.0,
+                f64::from(color.a) / 255.0,
+            );
+            cairo_set_line_width(self.cr, style.width);
+            cairo_set_line_cap(self.cr, line_cap);
+            cairo_set_line_join(self.cr, line_join);
+            cairo_set_miter_limit(self.cr, style.miter_limit);
+            add_path(self.cr, path);
+            cairo_stroke(self.cr);
+            cairo_restore(self.cr);
+            cairo_surface_flush(self.surface);
+        }
+    }
+
     pub fn push_group(&mut self) {
         if self.cr.is_null() {
             return;
@@ -362,20 +506,15 @@ impl CairoCanvas {
                 cairo_show_text(self.cr, text.as_ptr());
             } else {
                 let mut cursor_x = f64::from(x_px);
-                let mut first = true;
-                for ch in text.chars() {
-                    if !first {
+                for (index, cluster) in shaping::clusters(text).enumerate() {
+                    if index > 0 {
                         cursor_x += f64::from(style.letter_spacing_px);
                     }
-                    first = false;
-
-                    let mut utf8 = [0u8; 4];
-                    let encoded = ch.encode_utf8(&mut utf8);
-                    let mut c_buf = [0u8; 5];
-                    c_buf[..encoded.len()].copy_from_slice(encoded.as_bytes());
+                    let cluster =
+                        CString::new(cluster).map_err(|_| "text contains a NUL byte".to_owned())?;
 
                     cairo_move_to(self.cr, cursor_x, f64::from(y_px));
-                    cairo_show_text(self.cr, c_buf.as_ptr().cast::<c_char>());
+                    cairo_show_text(self.cr, cluster.as_ptr());
 
                     let mut extents = cairo_text_extents_t {
                         x_bearing: 0.0,
@@ -385,7 +524,7 @@ impl CairoCanvas {
                         x_advance: 0.0,
                         y_advance: 0.0,
                     };
-                    cairo_text_extents(self.cr, c_buf.as_ptr().cast::<c_char>(), &mut extents);
+                    cairo_text_extents(self.cr, cluster.as_ptr(), &mut extents);
                     cursor_x += extents.x_advance;
                 }
             }
@@ -545,103 +684,6 @@ impl CairoCanvas {
         }
         unsafe { cairo_surface_destroy(surface) };
     }
-
-    pub fn draw_svg(
-        &mut self,
-        x_px: i32,
-        y_px: i32,
-        width_px: i32,
-        height_px: i32,
-        svg_xml: &str,
-        opacity: u8,
-    ) -> Result<(), String> {
-        if self.cr.is_null() {
-            return Ok(());
-        }
-        let status = unsafe { cairo_status(self.cr) };
-        if status != CAIRO_STATUS_SUCCESS {
-            return Ok(());
-        }
-        if width_px <= 0 || height_px <= 0 {
-            return Ok(());
-        }
-        if opacity == 0 {
-            return Ok(());
-        }
-
-        let svg_xml = svg_xml.trim();
-        if svg_xml.is_empty() {
-            return Ok(());
-        }
-
-        let svg_xml = ensure_xlink_namespace(svg_xml);
-        let svg_xml = svg_xml.as_ref();
-
-        let mut parse_error: *mut GError = std::ptr::null_mut();
-        let handle =
-            unsafe { rsvg_handle_new_from_data(svg_xml.as_ptr(), svg_xml.len(), &mut parse_error) };
-
-        if handle.is_null() {
-            let message = gerror_message_and_free(parse_error);
-            return Err(format!("Failed to parse SVG: {message}"));
-        }
-
-        struct Handle(*mut RsvgHandle);
-        impl Drop for Handle {
-            fn drop(&mut self) {
-                unsafe { g_object_unref(self.0 as *mut c_void) };
-            }
-        }
-        let handle = Handle(handle);
-
-        let viewport = RsvgRectangle {
-            x: 0.0,
-            y: 0.0,
-            width: f64::from(width_px),
-            height: f64::from(height_px),
-        };
-
-        unsafe {
-            cairo_save(self.cr);
-            cairo_translate(self.cr, f64::from(x_px), f64::from(y_px));
-            if opacity != 255 {
-                cairo_push_group(self.cr);
-            }
-        }
-
-        let status = unsafe { cairo_status(self.cr) };
-        if status != CAIRO_STATUS_SUCCESS {
-            unsafe {
-                cairo_restore(self.cr);
-                cairo_surface_flush(self.surface);
-            }
-            return Ok(());
-        }
-
-        let mut render_error: *mut GError = std::ptr::null_mut();
-        let rendered =
-            unsafe { rsvg_handle_render_document(handle.0, self.cr, &viewport, &mut render_error) };
-
-        let result = if rendered == 0 {
-            Err(format!(
-                "Failed to render SVG: {}",
-                gerror_message_and_free(render_error)
-            ))
-        } else {
-            Ok(())
-        };
-
-        unsafe {
-            if opacity != 255 {
-                cairo_pop_group_to_source(self.cr);
-                cairo_paint_with_alpha(self.cr, f64::from(opacity) / 255.0);
-            }
-            cairo_restore(self.cr);
-            cairo_surface_flush(self.surface);
-        }
-
-        result
-    }
 }
 
 impl Drop for CairoCanvas {
@@ -660,86 +702,46 @@ fn cairo_status_message(status: cairo_status_t) -> String {
         .into_owned()
 }
 
-fn gerror_message_and_free(error: *mut GError) -> String {
-    if error.is_null() {
-        return "unknown error".to_owned();
-    }
-
-    let message = unsafe {
-        let ptr = (*error).message;
-        if ptr.is_null() {
-            "unknown error".to_owned()
-        } else {
-            CStr::from_ptr(ptr).to_string_lossy().into_owned()
-        }
-    };
-
-    unsafe { g_error_free(error) };
-    message
-}
-
-fn ensure_xlink_namespace(svg_xml: &str) -> Cow<'_, str> {
-    if !svg_xml.contains("xlink:") || svg_xml.contains("xmlns:xlink") {
-        return Cow::Borrowed(svg_xml);
-    }
-
-    let Some(svg_start) = svg_xml.find("<svg") else {
-        return Cow::Borrowed(svg_xml);
-    };
-
-    let Some(svg_end) = find_tag_end(svg_xml, svg_start) else {
-        return Cow::Borrowed(svg_xml);
-    };
-
-    let insert_at = start_tag_insert_pos(svg_xml, svg_start, svg_end);
-    let injection = r#" xmlns:xlink="http://www.w3.org/1999/xlink""#;
-
-    let mut out = String::with_capacity(svg_xml.len() + injection.len());
-    out.push_str(&svg_xml[..insert_at]);
-    out.push_str(injection);
-    out.push_str(&svg_xml[insert_at..]);
-    Cow::Owned(out)
-}
-
-fn find_tag_end(input: &str, start: usize) -> Option<usize> {
-    let bytes = input.as_bytes();
-    let mut idx = start;
-    let mut quote: Option<u8> = None;
-
-    while idx < bytes.len() {
-        let b = bytes[idx];
-        if let Some(q) = quote {
-            if b == q {
-                quote = None;
+/// Replaces the current path with `path`. Cairo has no quadratic curves, so they are raised to
+/// cubics.
+fn add_path(cr: *mut cairo_t, path: &[PathCommand]) {
+    let mut start = Point::default();
+    let mut current = Point::default();
+    unsafe {
+        cairo_new_path(cr);
+        for command in path {
+            match *command {
+                PathCommand::MoveTo(to) => {
+                    cairo_move_to(cr, to.x, to.y);
+                    start = to;
+                    current = to;
+                }
+                PathCommand::LineTo(to) => {
+                    cairo_line_to(cr, to.x, to.y);
+                    current = to;
+                }
+                PathCommand::QuadTo(control, to) => {
+                    let first = Point::new(
+                        current.x + (control.x - current.x) * 2.0 / 3.0,
+                        current.y + (control.y - current.y) * 2.0 / 3.0,
+                    );
+                    let second = Point::new(
+                        to.x + (control.x - to.x) * 2.0 / 3.0,
+                        to.y + (control.y - to.y) * 2.0 / 3.0,
+                    );
+                    cairo_curve_to(cr, first.x, first.y, second.x, second.y, to.x, to.y);
+                    current = to;
+                }
+                PathCommand::CubicTo(first, second, to) => {
+                    cairo_curve_to(cr, first.x, first.y, second.x, second.y, to.x, to.y);
+                    current = to;
+                }
+                PathCommand::Close => {
+                    cairo_close_path(cr);
+                    current = start;
+                }
             }
-            idx += 1;
-            continue;
         }
-
-        match b {
-            b'"' | b'\'' => quote = Some(b),
-            b'>' => return Some(idx),
-            _ => {}
-        }
-        idx += 1;
-    }
-
-    None
-}
-
-fn start_tag_insert_pos(input: &str, start: usize, end: usize) -> usize {
-    debug_assert!(start <= end);
-
-    let bytes = input.as_bytes();
-    let mut idx = end;
-    while idx > start && bytes[idx.saturating_sub(1)].is_ascii_whitespace() {
-        idx = idx.saturating_sub(1);
-    }
-
-    if idx > start && bytes[idx.saturating_sub(1)] == b'/' {
-        idx.saturating_sub(1)
-    } else {
-        end
     }
 }
 
@@ -0,0 +1,240 @@
+use core::ffi::{c_int, c_long, c_uchar, c_ulong};
+
+use super::xlib::{
+    Atom, Display, EVENT_TYPE_CLIENT_MESSAGE, PROP_MODE_REPLACE, Window, XA_ATOM, XChangeProperty,
+    XClientMessageEvent, XConvertSelection, XEvent, XFree, XGetWindowProperty, XInternAtom,
+    XSelectionEvent, XSendEvent,
+};
+
+/// The XDND protocol version announced and spoken; sources fall back to it when older.
+const XDND_VERSION: c_ulong = 5;
+/// Most types read from a source's `XdndTypeList`.
+const MAX_OFFERED_TYPES: c_long = 64;
+
+/// Takes drops from other clients through XDND. The source names the types it offers when the
+/// drag enters the window; a drop converts `XdndSelection` onto a property of the window, and the
+/// data arrives with the `SelectionNotify` that follows.
+pub struct DragAndDrop {
+    enter: Atom,
+    position: Atom,
+    status: Atom,
+    leave: Atom,
+    drop: Atom,
+    finished: Atom,
+    selection: Atom,
+    action_copy: Atom,
+    type_list: Atom,
+    /// Types a drop is read as, most preferred first.
+    drop_types: [Atom; 4],
+    property: Atom,
+    /// The window being dragged from, while a drag is over this one.
+    source: Window,
+    /// The type a drop would be read as, or `None` when the drag offers nothing readable.
+    drop_type: Option<Atom>,
+}
+
+impl DragAndDrop {
+    /// Marks `window` as taking drops.
+    pub fn new(display: *mut Display, window: Window) -> Self {
+        let atom = |name: &std::ffi::CStr| unsafe { XInternAtom(display, name.as_ptr(), 0) };
+        let aware = atom(c"XdndAware");
+        let version: c_ulong = XDND_VERSION;
+        unsafe {
+            XChangeProperty(
+                display,
+                window,
+                aware,
+                XA_ATOM,
+                32,
+                PROP_MODE_REPLACE,
+                (&raw const version).cast::<c_uchar>(),
+                1,
+            );
+        }
+        Self {
+            enter: atom(c"XdndEnter"),
+            position: atom(c"XdndPosition"),
+            status: atom(c"XdndStatus"),
+            leave: atom(c"XdndLeave"),
+            drop: atom(c"XdndDrop"),
+            finished: atom(c"XdndFinished"),
+            selection: atom(c"XdndSelection"),
+            action_copy: atom(c"XdndActionCopy"),
+            type_list: atom(c"XdndTypeList"),
+            drop_types: [
+                atom(c"text/uri-list"),
+                atom(c"text/plain;charset=utf-8"),
+                atom(c"UTF8_STRING"),
+                atom(c"text/plain"),
+            ],
+            property: atom(c"OAB_DROP"),
+            source: 0,
+            drop_type: None,
+        }
+    }
+
+    /// Answers an XDND client message; `false` when `message` isn't one.
+    pub fn client_message(
+        &mut self,
+        display: *mut Display,
+        window: Window,
+        message: &XClientMessageEvent,
+    ) -> bool {
+        let data = unsafe { message.data.l };
+        let source = data[0] as Window;
+        if message.message_type == self.enter {
+            let flags = data[1] as c_ulong;
+            let offered = if flags & 1 != 0 {
+                self.offered_types(display, source)
+            } else {
+                data[2..].iter().map(|&atom| atom as Atom).collect()
+            };
+            self.source = source;
+            self.drop_type = preferred_type(&self.drop_types, &offered);
+        } else if message.message_type == self.position {
+            if source != self.source {
+                return true;
+            }
+            let accepted = self.drop_type.is_some();
+            let action = if accepted { self.action_copy } else { 0 };
+            self.send(
+                display,
+                source,
+                self.status,
+                [
+                    window as c_long,
+                    c_long::from(accepted),
+                    0,
+                    0,
+                    action as c_long,
+                ],
+            );
+        } else if message.message_type == self.leave {
+            if source == self.source {
+                self.source = 0;
+                self.drop_type = None;
+            }
+        } else if message.message_type == self.drop {
+            if source != self.source {
+                return true;
+            }
+            match self.drop_type {
+                Some(drop_type) => unsafe {
+                    XConvertSelection(
+                        display,
+                        self.selection,
+                        drop_type,
+                        self.property,
+                        window,
+                        data[2] as c_ulong,
+                    );
+                },
+                None => self.finish(display, window, false),
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Whether `event` delivers a drop. Its property is `None` when the source couldn't convert
+    /// it; either way the drop ends with `finish`.
+    pub fn is_drop(&self, event: &XSelectionEvent) -> bool {
+        event.selection == self.selection && self.source != 0
+    }
+
+    /// Tells the source the drop is over, so it can clean up.
+    pub fn finish(&mut self, display: *mut Display, window: Window, accepted: bool) {
+        let action = if accepted { self.action_copy } else { 0 };
+        self.send(
+            display,
+            self.source,
+            self.finished,
+            [
+                window as c_long,
+                c_long::from(accepted),
+                action as c_long,
+                0,
+                0,
+            ],
+        );
+        self.source = 0;
+        self.drop_type = None;
+    }
+
+    /// The `XdndTypeList` a source lists more than three types in.
+    fn offered_types(&self, display: *mut Display, source: Window) -> Vec<Atom> {
+        let mut actual_type: Atom = 0;
+        let mut actual_format: c_int = 0;
+        let mut nitems: c_ulong = 0;
+        let mut bytes_after: c_ulong = 0;
+        let mut prop: *mut c_uchar = std::ptr::null_mut();
+        let status = unsafe {
+            XGetWindowProperty(
+                display,
+                source,
+                self.type_list,
+                0,
+                MAX_OFFERED_TYPES,
+                0,
+                XA_ATOM,
+                &mut actual_type,
+                &mut actual_format,
+                &mut nitems,
+                &mut bytes_after,
+                &mut prop,
+            )
+        };
+        if status != 0 || prop.is_null() {
+            return Vec::new();
+        }
+        // Xlib returns format-32 items as C longs, whatever their size.
+        let types = if actual_type == XA_ATOM && actual_format == 32 {
+            let len = usize::try_from(nitems).unwrap_or(0);
+            unsafe { std::slice::from_raw_parts(prop.cast::<Atom>(), len) }.to_vec()
+        } else {
+            Vec::new()
+        };
+        unsafe {
+            XFree(prop.cast());
+        }
+        types
+    }
+
+    fn send(&self, display: *mut Display, target: Window, message_type: Atom, l: [c_long; 5]) {
+        let mut event = XEvent { inner: [0; 24] };
+        let message = event.inner.as_mut_ptr().cast::<XClientMessageEvent>();
+        unsafe {
+            (*message).type_ = EVENT_TYPE_CLIENT_MESSAGE;
+            (*message).display = display;
+            (*message).window = target;
+            (*message).message_type = message_type;
+            (*message).format = 32;
+            (*message).data.l = l;
+            XSendEvent(display, target, 0, 0, &mut event);
+        }
+    }
+}
+
+/// The first of `preferred` that `offered` has.
+fn preferred_type(preferred: &[Atom], offered: &[Atom]) -> Option<Atom> {
+    preferred
+        .iter()
+        .copied()
+        .find(|atom| offered.contains(atom))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::preferred_type;
+
+    #[test]
+    fn drops_are_read_as_the_most_preferred_offered_type() {
+        let (uri_list, utf8, plain) = (10, 11, 12);
+        assert_eq!(
+            preferred_type(&[uri_list, utf8, plain], &[plain, 99, uri_list]),
+            Some(uri_list)
+        );
+        assert_eq!(preferred_type(&[uri_list, utf8], &[0, 0, 0]), None);
+    }
+}
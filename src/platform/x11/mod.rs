@@ -1,21 +1,37 @@
 mod cairo;
+mod dnd;
 mod painter;
+mod randr;
 mod scale;
+mod selection;
+mod window_props;
 mod xft;
+mod xinput;
 mod xlib;
-
-use super::WindowOptions;
-use crate::app::App;
-use crate::geom::Color;
-use crate::image::Argb32Image;
-use crate::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+mod xrender;
+mod xshm;
+
+use super::touch::{TouchAction, TouchGestures};
+use super::{FrameScheduler, FrameStep, FrameSurface, WindowIdentity, WindowOptions};
+use crate::app::{App, Key, KeyInput, TickResult, ZoomCommand};
+use crate::geom::{Color, Rect};
+use crate::image::{Argb32Image, RgbImage};
+use crate::render::{
+    FillRule, FontMetricsPx, Painter, PathCommand, StrokeStyle, TextMeasurer, TextStyle, Transform,
+    Viewport,
+};
 use core::ffi::{c_int, c_uint, c_ulong};
 use std::ffi::{CString, OsStr};
 use std::path::Path;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
+use dnd::DragAndDrop;
 use painter::X11Painter;
+use randr::Monitors;
 use scale::ScaleFactor;
+use selection::{Selection, Selections};
+use window_props::WindowProperties;
+use xinput::{ScrollDelta, XInput};
 use xlib::*;
 
 // Avoid starving rendering when the X server generates events faster than we can drain them
@@ -24,8 +40,6 @@ const MAX_X11_EVENTS_PER_TICK: usize = 512;
 
 const X11_SOCKET_DIR: &str = "/tmp/.X11-unix";
 
-const SCREENSHOT_RESOURCE_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
-
 const WHEEL_SCROLL_STEP_PX: i32 = 48;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -45,6 +59,58 @@ fn key_action(keysym: KeySym) -> KeyAction {
     }
 }
 
+fn named_key(keysym: KeySym) -> Option<Key> {
+    match keysym {
+        KEYSYM_BACKSPACE => Some(Key::Backspace),
+        KEYSYM_RETURN | KEYSYM_KP_ENTER => Some(Key::Enter),
+        KEYSYM_ESCAPE => Some(Key::Escape),
+        KEYSYM_TAB | KEYSYM_ISO_LEFT_TAB => Some(Key::Tab),
+        KEYSYM_HOME => Some(Key::Home),
+        KEYSYM_LEFT => Some(Key::Left),
+        KEYSYM_RIGHT => Some(Key::Right),
+        KEYSYM_END => Some(Key::End),
+        KEYSYM_F12 => Some(Key::F12),
+        KEYSYM_DELETE => Some(Key::Delete),
+        _ => None,
+    }
+}
+
+fn key_input(key: &XKeyEvent) -> Option<KeyInput> {
+    let key_ptr = key as *const XKeyEvent as *mut XKeyEvent;
+    let ctrl = key.state & CONTROL_MASK != 0;
+    let alt = key.state & MOD1_MASK != 0;
+    let mut buffer = [0 as core::ffi::c_char; 8];
+    let mut keysym: KeySym = 0;
+    let len = unsafe {
+        XLookupString(
+            key_ptr,
+            buffer.as_mut_ptr(),
+            buffer.len() as c_int,
+            &mut keysym,
+            std::ptr::null_mut(),
+        )
+    };
+    if let Some(key) = named_key(keysym) {
+        return Some(KeyInput { key, ctrl, alt });
+    }
+    if ctrl {
+        // XLookupString folds Ctrl+letter into a control character; report the plain key instead.
+        let keysym = unsafe { XLookupKeysym(key_ptr, 0) };
+        let ch = u8::try_from(keysym).ok().map(char::from)?;
+        return ch.is_ascii_graphic().then_some(KeyInput {
+            key: Key::Char(ch),
+            ctrl,
+            alt,
+        });
+    }
+    // The string is Latin-1 when no input method is attached.
+    (len == 1).then(|| KeyInput {
+        key: Key::Char(char::from(buffer[0] as u8)),
+        ctrl,
+        alt,
+    })
+}
+
 pub fn run_window<A: App>(title: &str, options: WindowOptions, app: &mut A) -> Result<(), String> {
     let display = open_x11_display()?;
 
@@ -159,6 +225,22 @@ fn is_wayland_session_from_values(
         || xdg_session_type.is_some_and(|value| value.eq_ignore_ascii_case("wayland"))
 }
 
+/// Zooms the page, returning the scale and CSS viewport it's now laid out at.
+fn apply_zoom<A: App>(
+    app: &mut A,
+    command: ZoomCommand,
+    base_scale: ScaleFactor,
+    viewport: Viewport,
+) -> (ScaleFactor, Viewport) {
+    app.set_zoom_percent(command.apply(app.zoom_percent()));
+    let scale = base_scale.zoomed(app.zoom_percent());
+    let css_viewport = Viewport {
+        width_px: scale.device_size_to_css_px(viewport.width_px),
+        height_px: scale.device_size_to_css_px(viewport.height_px),
+    };
+    (scale, css_viewport)
+}
+
 fn run_window_with_display<A: App>(
     display: *mut Display,
     title: &str,
@@ -166,7 +248,8 @@ fn run_window_with_display<A: App>(
     app: &mut A,
 ) -> Result<(), String> {
     let screen = unsafe { XDefaultScreen(display) };
-    let scale = ScaleFactor::detect(display, screen);
+    let settings_scale = ScaleFactor::detect(display, screen);
+    let mut base_scale = settings_scale;
     let visual = unsafe { XDefaultVisual(display, screen) };
     if visual.is_null() {
         return Err("XDefaultVisual returned null".to_owned());
@@ -190,8 +273,8 @@ fn run_window_with_display<A: App>(
             "Invalid initial window size: {initial_width_css_i32}x{initial_height_css_i32}"
         ));
     }
-    let initial_width_device_i32 = scale.css_size_to_device_px(initial_width_css_i32);
-    let initial_height_device_i32 = scale.css_size_to_device_px(initial_height_css_i32);
+    let initial_width_device_i32 = base_scale.css_size_to_device_px(initial_width_css_i32);
+    let initial_height_device_i32 = base_scale.css_size_to_device_px(initial_height_css_i32);
     let initial_width: c_uint = initial_width_device_i32
         .try_into()
         .map_err(|_| format!("Initial width out of range: {initial_width_device_i32}"))?;
@@ -213,11 +296,11 @@ fn run_window_with_display<A: App>(
         )
     };
 
-    let window_title =
-        CString::new(title).map_err(|_| "Window title contains a NUL byte".to_owned())?;
-    unsafe {
-        XStoreName(display, window, window_title.as_ptr());
-    }
+    let window_properties = WindowProperties::new(display);
+    let selections = Selections::new(display);
+    let mut drag_and_drop = DragAndDrop::new(display, window);
+    window_properties.set_title(display, window, title);
+    let mut identity = WindowIdentity::new(title);
 
     let wm_protocols_atom_name =
         CString::new("WM_PROTOCOLS").map_err(|_| "Invalid atom name".to_owned())?;
@@ -244,11 +327,25 @@ fn run_window_with_display<A: App>(
                 EVENT_MASK_EXPOSURE
                     | EVENT_MASK_KEY_PRESS
                     | EVENT_MASK_BUTTON_PRESS
+                    | EVENT_MASK_BUTTON_RELEASE
+                    | EVENT_MASK_POINTER_MOTION
                     | EVENT_MASK_STRUCTURE_NOTIFY,
             );
             XMapWindow(display, window);
         }
     }
+    let mut xinput = if options.headless {
+        None
+    } else {
+        XInput::new(display, window)
+    };
+    let mut touch_gestures = TouchGestures::default();
+    // `OAB_SCALE` pins the scale; otherwise it follows the monitor the window is on.
+    let monitors = if options.headless || ScaleFactor::from_env().is_some() {
+        None
+    } else {
+        Monitors::new(display, root_window)
+    };
 
     let depth_i32 = unsafe { XDefaultDepth(display, screen) };
     let depth: c_uint = depth_i32
@@ -287,19 +384,17 @@ fn run_window_with_display<A: App>(
         width_px: initial_width_device_i32,
         height_px: initial_height_device_i32,
     };
+    let mut scale = base_scale.zoomed(app.zoom_percent());
     let mut css_viewport = Viewport {
         width_px: scale.device_size_to_css_px(viewport.width_px),
         height_px: scale.device_size_to_css_px(viewport.height_px),
     };
 
-    let mut screenshot_path = options.screenshot_path;
-    let headless = options.headless;
+    let mut frames = FrameScheduler::new(&options);
 
     let loop_result = (|| {
-        let mut needs_redraw = true;
         let mut should_exit = false;
-        let mut has_rendered_ready_state = false;
-        let mut resource_wait_started: Option<Instant> = None;
+        let mut monitor_may_have_changed = true;
 
         loop {
             let mut processed_events = 0usize;
@@ -309,12 +404,91 @@ fn run_window_with_display<A: App>(
                     XNextEvent(display, &mut event);
                 }
 
+                if let Some(input) = xinput
+                    .as_mut()
+                    .and_then(|xinput| xinput.event(display, &mut event))
+                {
+                    if let Some((x_px, y_px)) = input.position {
+                        let x_css = scale.device_coord_to_css_px(x_px);
+                        let y_css = scale.device_coord_to_css_px(y_px);
+                        let tick = app.mouse_move(x_css, y_css, css_viewport)?;
+                        frames.note(tick);
+                    }
+                    if let Some(
+                        ScrollDelta::Notches(delta_x_px) | ScrollDelta::Precise(delta_x_px),
+                    ) = input.horizontal_scroll
+                    {
+                        let tick = app.horizontal_wheel(
+                            scale.device_delta_to_css_px(delta_x_px),
+                            css_viewport,
+                        )?;
+                        frames.note(tick);
+                    }
+                    let tick =
+                        match input.scroll {
+                            Some(ScrollDelta::Notches(delta_y_px)) => Some(app.wheel_notch(
+                                scale.device_delta_to_css_px(delta_y_px),
+                                css_viewport,
+                            )?),
+                            Some(ScrollDelta::Precise(delta_y_px)) => Some(app.mouse_wheel(
+                                scale.device_delta_to_css_px(delta_y_px),
+                                css_viewport,
+                            )?),
+                            None => None,
+                        };
+                    if let Some(tick) = tick {
+                        frames.note(tick);
+                    }
+                    let action = input.touch.and_then(|touch| touch_gestures.event(touch));
+                    let tick = match action {
+                        Some(TouchAction::Tap { x_px, y_px }) => {
+                            let x_css = scale.device_coord_to_css_px(x_px);
+                            let y_css = scale.device_coord_to_css_px(y_px);
+                            frames.note(app.mouse_move(x_css, y_css, css_viewport)?);
+                            Some(app.mouse_down(x_css, y_css, css_viewport)?)
+                        }
+                        Some(TouchAction::Scroll { delta_y_px }) => {
+                            Some(app.mouse_wheel(
+                                scale.device_delta_to_css_px(delta_y_px),
+                                css_viewport,
+                            )?)
+                        }
+                        Some(TouchAction::Fling {
+                            velocity_y_px_per_s,
+                        }) => Some(app.scroll_fling(
+                            velocity_y_px_per_s / f64::from(scale.device_pixel_ratio()),
+                            css_viewport,
+                        )?),
+                        Some(TouchAction::Zoom(command)) => {
+                            (scale, css_viewport) = apply_zoom(app, command, base_scale, viewport);
+                            frames.invalidate();
+                            None
+                        }
+                        None => None,
+                    };
+                    if let Some(tick) = tick {
+                        frames.note(tick);
+                    }
+                    processed_events += 1;
+                    continue;
+                }
+
+                if monitors
+                    .as_ref()
+                    .is_some_and(|monitors| monitors.is_screen_change(&mut event))
+                {
+                    monitor_may_have_changed = true;
+                    processed_events += 1;
+                    continue;
+                }
+
                 match event.event_type() {
                     EVENT_TYPE_EXPOSE => {
                         let expose: &XExposeEvent =
                             unsafe { &*(event.inner.as_ptr() as *const XExposeEvent) };
                         if expose.count == 0 {
-                            needs_redraw = true;
+                            painter.damage_all();
+                            frames.request_redraw();
                         }
                     }
                     EVENT_TYPE_CONFIGURE_NOTIFY => {
@@ -328,9 +502,8 @@ fn run_window_with_display<A: App>(
                             width_px: scale.device_size_to_css_px(viewport.width_px),
                             height_px: scale.device_size_to_css_px(viewport.height_px),
                         };
-                        needs_redraw = true;
-                        has_rendered_ready_state = false;
-                        resource_wait_started = None;
+                        frames.invalidate();
+                        monitor_may_have_changed = true;
                     }
                     EVENT_TYPE_BUTTON_PRESS => {
                         let button: &XButtonEvent =
@@ -339,38 +512,94 @@ fn run_window_with_display<A: App>(
                             let x_css = scale.device_coord_to_css_px(button.x);
                             let y_css = scale.device_coord_to_css_px(button.y);
                             let tick = app.mouse_down(x_css, y_css, css_viewport)?;
-                            if tick.needs_redraw {
-                                needs_redraw = true;
-                            }
+                            frames.note(tick);
+                        } else if button.button == 2 {
+                            selections.request(display, window, Selection::Primary);
                         } else if button.button == 8 {
                             let tick = app.navigate_back()?;
-                            if tick.needs_redraw {
-                                needs_redraw = true;
-                            }
-                        } else if button.button == 4 || button.button == 5 {
-                            let delta_y_px = if button.button == 4 {
+                            frames.note(tick);
+                        } else if button.button == 9 {
+                            let tick = app.navigate_forward()?;
+                            frames.note(tick);
+                        } else if (button.button == 4 || button.button == 5)
+                            && !xinput.as_ref().is_some_and(XInput::handles_wheel_buttons)
+                        {
+                            let delta_px = if button.button == 4 {
                                 -WHEEL_SCROLL_STEP_PX
                             } else {
                                 WHEEL_SCROLL_STEP_PX
                             };
-                            let delta_y_css = scale.device_delta_to_css_px(delta_y_px);
-                            let tick = app.mouse_wheel(delta_y_css, css_viewport)?;
-                            if tick.needs_redraw {
-                                needs_redraw = true;
-                            }
+                            let delta_css = scale.device_delta_to_css_px(delta_px);
+                            let tick = if button.state & SHIFT_MASK != 0 {
+                                app.horizontal_wheel(delta_css, css_viewport)?
+                            } else {
+                                app.wheel_notch(delta_css, css_viewport)?
+                            };
+                            frames.note(tick);
+                        } else if (button.button == 6 || button.button == 7)
+                            && !xinput.as_ref().is_some_and(XInput::handles_tilt_buttons)
+                        {
+                            let delta_x_px = if button.button == 6 {
+                                -WHEEL_SCROLL_STEP_PX
+                            } else {
+                                WHEEL_SCROLL_STEP_PX
+                            };
+                            let delta_x_css = scale.device_delta_to_css_px(delta_x_px);
+                            let tick = app.horizontal_wheel(delta_x_css, css_viewport)?;
+                            frames.note(tick);
                         }
                     }
+                    EVENT_TYPE_BUTTON_RELEASE => {
+                        let button: &XButtonEvent =
+                            unsafe { &*(event.inner.as_ptr() as *const XButtonEvent) };
+                        if button.button == 1 {
+                            let x_css = scale.device_coord_to_css_px(button.x);
+                            let y_css = scale.device_coord_to_css_px(button.y);
+                            let tick = app.mouse_up(x_css, y_css, css_viewport)?;
+                            frames.note(tick);
+                        }
+                    }
+                    EVENT_TYPE_MOTION_NOTIFY => {
+                        let motion: &XMotionEvent =
+                            unsafe { &*(event.inner.as_ptr() as *const XMotionEvent) };
+                        let x_css = scale.device_coord_to_css_px(motion.x);
+                        let y_css = scale.device_coord_to_css_px(motion.y);
+                        let tick = app.mouse_move(x_css, y_css, css_viewport)?;
+                        frames.note(tick);
+                    }
                     EVENT_TYPE_KEY_PRESS => {
                         let key: &XKeyEvent =
                             unsafe { &*(event.inner.as_ptr() as *const XKeyEvent) };
+                        let consumed = match key_input(key) {
+                            Some(input) if input.is_paste() => {
+                                selections.request(display, window, Selection::Clipboard);
+                                Some(TickResult::default())
+                            }
+                            Some(input) => match ZoomCommand::from_key(input) {
+                                Some(command) => {
+                                    (scale, css_viewport) =
+                                        apply_zoom(app, command, base_scale, viewport);
+                                    frames.invalidate();
+                                    Some(TickResult::default())
+                                }
+                                None => app.key_down(input, css_viewport)?,
+                            },
+                            None => None,
+                        };
                         let keysym =
                             unsafe { XLookupKeysym(key as *const XKeyEvent as *mut XKeyEvent, 0) };
-                        match key_action(keysym) {
+                        let action = if consumed.is_some() {
+                            KeyAction::None
+                        } else {
+                            key_action(keysym)
+                        };
+                        if let Some(tick) = consumed {
+                            frames.note(tick);
+                        }
+                        match action {
                             KeyAction::NavigateBack => {
                                 let tick = app.navigate_back()?;
-                                if tick.needs_redraw {
-                                    needs_redraw = true;
-                                }
+                                frames.note(tick);
                             }
                             KeyAction::Exit => {
                                 should_exit = true;
@@ -379,11 +608,31 @@ fn run_window_with_display<A: App>(
                             KeyAction::None => {}
                         }
                     }
+                    EVENT_TYPE_SELECTION_NOTIFY => {
+                        let notify: &XSelectionEvent =
+                            unsafe { &*(event.inner.as_ptr() as *const XSelectionEvent) };
+                        if drag_and_drop.is_drop(notify) {
+                            let dropped = (notify.property != 0)
+                                .then(|| {
+                                    selections.take_property_text(display, window, notify.property)
+                                })
+                                .flatten();
+                            drag_and_drop.finish(display, window, dropped.is_some());
+                            if let Some(dropped) = dropped {
+                                let tick = app.open_dropped(&dropped, css_viewport)?;
+                                frames.note(tick);
+                            }
+                        } else if let Some(text) = selections.text(display, notify) {
+                            let tick = app.paste_text(&text, css_viewport)?;
+                            frames.note(tick);
+                        }
+                    }
                     EVENT_TYPE_CLIENT_MESSAGE => {
                         let message: &XClientMessageEvent =
                             unsafe { &*(event.inner.as_ptr() as *const XClientMessageEvent) };
                         let data = unsafe { message.data.l };
-                        if message.message_type == wm_protocols_atom
+                        if !drag_and_drop.client_message(display, window, message)
+                            && message.message_type == wm_protocols_atom
                             && data[0] as c_ulong == wm_delete_window
                         {
                             should_exit = true;
@@ -399,92 +648,40 @@ fn run_window_with_display<A: App>(
                 break;
             }
 
-            let tick = app.tick()?;
-            if tick.needs_redraw {
-                needs_redraw = true;
-            }
-            let ready_for_screenshot = tick.ready_for_screenshot;
-            if !ready_for_screenshot {
-                has_rendered_ready_state = false;
-                resource_wait_started = None;
-            }
-
-            let should_wait_for_resources = tick.pending_resources > 0;
-            let timed_out_waiting_for_resources = resource_wait_started
-                .is_some_and(|started| started.elapsed() >= SCREENSHOT_RESOURCE_WAIT_TIMEOUT);
-            let can_complete = !should_wait_for_resources || timed_out_waiting_for_resources;
-
-            let wants_screenshot = screenshot_path.is_some();
-            let should_complete_headless = headless && !wants_screenshot;
-            let should_complete_screenshot =
-                wants_screenshot && ready_for_screenshot && has_rendered_ready_state;
-
-            let mut capture_now = false;
-            let mut capture_after_render = false;
-            let mut exit_headless_now = false;
-
-            if ready_for_screenshot && (wants_screenshot || headless) && !has_rendered_ready_state {
-                needs_redraw = true;
-            } else if ready_for_screenshot && should_wait_for_resources && has_rendered_ready_state
-            {
-                resource_wait_started.get_or_insert(Instant::now());
-            } else if ready_for_screenshot && has_rendered_ready_state {
-                resource_wait_started = None;
-            }
-
-            if ready_for_screenshot && has_rendered_ready_state && can_complete {
-                if should_complete_screenshot {
-                    if needs_redraw {
-                        capture_after_render = true;
-                    } else {
-                        capture_now = true;
-                    }
-                } else if should_complete_headless && !needs_redraw {
-                    exit_headless_now = true;
+            if monitor_may_have_changed && let Some(monitors) = &monitors {
+                monitor_may_have_changed = false;
+                let monitor_scale = monitors.scale_for(display, window, viewport, settings_scale);
+                if monitor_scale != base_scale {
+                    base_scale = monitor_scale;
+                    scale = base_scale.zoomed(app.zoom_percent());
+                    css_viewport = Viewport {
+                        width_px: scale.device_size_to_css_px(viewport.width_px),
+                        height_px: scale.device_size_to_css_px(viewport.height_px),
+                    };
+                    painter.damage_all();
+                    frames.invalidate();
                 }
             }
 
-            if exit_headless_now {
-                break;
+            let tick = app.tick()?;
+            if let Some(title) = identity.title_change(app) {
+                window_properties.set_title(display, window, &title);
             }
-
-            if capture_now {
-                let Some(path) = screenshot_path.take() else {
-                    return Err(
-                        "Internal error: capture_now set but screenshot path missing".to_owned(),
-                    );
-                };
-                unsafe {
-                    XSync(display, 0);
-                }
-                let rgb = painter.capture_back_buffer_rgb()?;
-                crate::png::write_rgb_png(&path, &rgb)?;
-                break;
+            if let Some(icon) = identity.icon_change(app) {
+                window_properties.set_icon(display, window, &icon);
             }
-
-            if needs_redraw {
-                painter.ensure_back_buffer(viewport)?;
-                let mut scaled_painter = ScaledPainter::new(&mut painter, scale);
-                app.render(&mut scaled_painter, css_viewport)?;
-                needs_redraw = false;
-
-                if ready_for_screenshot {
-                    has_rendered_ready_state = true;
-                    if capture_after_render {
-                        let Some(path) = screenshot_path.take() else {
-                            return Err("Internal error: capture_after_render set but screenshot path missing".to_owned());
-                        };
-                        unsafe {
-                            XSync(display, 0);
-                        }
-                        let rgb = painter.capture_back_buffer_rgb()?;
-                        crate::png::write_rgb_png(&path, &rgb)?;
-                        break;
-                    }
-                }
+            let mut surface = X11Surface {
+                display,
+                painter: &mut painter,
+                scale,
+                viewport,
+                css_viewport,
+            };
+            if frames.frame(app, tick, &mut surface)? == FrameStep::Done {
+                break;
             }
 
-            if unsafe { XPending(display) } == 0 && !needs_redraw {
+            if unsafe { XPending(display) } == 0 {
                 std::thread::sleep(Duration::from_millis(10));
             }
         }
@@ -503,6 +700,36 @@ fn run_window_with_display<A: App>(
     loop_result
 }
 
+/// The window's back buffer at its current size and scale.
+struct X11Surface<'a> {
+    display: *mut Display,
+    painter: &'a mut X11Painter,
+    scale: ScaleFactor,
+    viewport: Viewport,
+    css_viewport: Viewport,
+}
+
+impl<A: App + ?Sized> FrameSurface<A> for X11Surface<'_> {
+    fn page_viewport(&self) -> Viewport {
+        self.css_viewport
+    }
+
+    fn render(&mut self, app: &mut A) -> Result<(), String> {
+        self.painter.ensure_back_buffer(self.viewport)?;
+        app.render(
+            &mut ScaledPainter::new(self.painter, self.scale),
+            self.css_viewport,
+        )
+    }
+
+    fn capture(&mut self) -> Result<RgbImage, String> {
+        unsafe {
+            XSync(self.display, 0);
+        }
+        self.painter.capture_back_buffer_rgb()
+    }
+}
+
 struct ScaledPainter<'a> {
     inner: &'a mut X11Painter,
     scale: ScaleFactor,
@@ -510,6 +737,8 @@ struct ScaledPainter<'a> {
 
 impl<'a> ScaledPainter<'a> {
     fn new(inner: &'a mut X11Painter, scale: ScaleFactor) -> Self {
+        // Every frame is painted through here, so media queries see the current scale.
+        crate::css_media::set_device_pixel_ratio(scale.device_pixel_ratio());
         Self { inner, scale }
     }
 
@@ -520,6 +749,14 @@ impl<'a> ScaledPainter<'a> {
             ..style
         }
     }
+
+    fn scale_path(&self, path: &[PathCommand]) -> Vec<PathCommand> {
+        let ratio = f64::from(self.scale.device_pixel_ratio());
+        let scale = Transform::scale(ratio, ratio);
+        path.iter()
+            .map(|command| command.transformed(&scale))
+            .collect()
+    }
 }
 
 impl TextMeasurer for ScaledPainter<'_> {
@@ -673,6 +910,80 @@ impl Painter for ScaledPainter<'_> {
         )
     }
 
+    fn push_clip_rect(
+        &mut self,
+        x_px: i32,
+        y_px: i32,
+        width_px: i32,
+        height_px: i32,
+    ) -> Result<(), String> {
+        let (x_device_px, width_device_px) = self.scale.css_span_to_device_px(x_px, width_px);
+        let (y_device_px, height_device_px) = self.scale.css_span_to_device_px(y_px, height_px);
+        self.inner
+            .push_clip_rect(x_device_px, y_device_px, width_device_px, height_device_px)
+    }
+
+    fn pop_clip(&mut self) -> Result<(), String> {
+        self.inner.pop_clip()
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        // Conjugated by the scale, so only the translation changes.
+        let ratio = f64::from(self.scale.device_pixel_ratio());
+        self.inner.push_transform(Transform {
+            e: transform.e * ratio,
+            f: transform.f * ratio,
+            ..transform
+        })
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        self.inner.pop_transform()
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        let path = self.scale_path(path);
+        self.inner.fill_path(&path, rule, color)
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        let path = self.scale_path(path);
+        let style = StrokeStyle {
+            width: style.width * f64::from(self.scale.device_pixel_ratio()),
+            ..*style
+        };
+        self.inner.stroke_path(&path, &style, color)
+    }
+
+    fn retains_frame(&self) -> bool {
+        self.inner.retains_frame()
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), String> {
+        // Grown by a device pixel, so antialiased edges rounded outwards stay inside.
+        let clip = clip.map(|clip| {
+            let (x, width) = self.scale.css_span_to_device_px(clip.x, clip.width);
+            let (y, height) = self.scale.css_span_to_device_px(clip.y, clip.height);
+            Rect {
+                x: x.saturating_sub(1),
+                y: y.saturating_sub(1),
+                width: width.saturating_add(2),
+                height: height.saturating_add(2),
+            }
+        });
+        self.inner.set_clip(clip)
+    }
+
     fn flush(&mut self) -> Result<(), String> {
         self.inner.flush()
     }
@@ -681,7 +992,7 @@ impl Painter for ScaledPainter<'_> {
 #[cfg(test)]
 mod tests {
     use super::{
-        KeyAction, is_wayland_session_from_values, key_action,
+        Key, KeyAction, is_wayland_session_from_values, key_action, named_key,
         parse_x11_display_candidates_from_names,
     };
     use std::ffi::OsStr;
@@ -706,6 +1017,14 @@ mod tests {
         assert!(!is_wayland_session_from_values(Some(OsStr::new("")), None));
     }
 
+    #[test]
+    fn x11_named_keys_cover_url_editing() {
+        assert_eq!(named_key(super::KEYSYM_RETURN), Some(Key::Enter));
+        assert_eq!(named_key(super::KEYSYM_KP_ENTER), Some(Key::Enter));
+        assert_eq!(named_key(super::KEYSYM_DELETE), Some(Key::Delete));
+        assert_eq!(named_key(super::KeySym::from(b'a')), None);
+    }
+
     #[test]
     fn x11_key_action_maps_backspace_and_escape() {
         assert_eq!(key_action(super::KEYSYM_BACKSPACE), KeyAction::NavigateBack);
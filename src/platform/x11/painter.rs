@@ -1,14 +1,19 @@
-use crate::geom::Color;
+use crate::geom::{Color, Rect};
 use crate::image::{Argb32Image, RgbImage};
-use crate::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use crate::render::{
+    FillRule, FontMetricsPx, Painter, PathCommand, StrokeStyle, TextMeasurer, TextStyle, Transform,
+    Viewport, shaping,
+};
 use core::ffi::{c_int, c_uint, c_ulong};
 
 use super::cairo::CairoCanvas;
 use super::xft::XftRenderer;
 use super::xlib::{
-    self, ALL_PLANES, Colormap, Display, Drawable, GC, IMAGE_FORMAT_Z_PIXMAP, Pixmap, Visual,
-    Window,
+    self, ALL_PLANES, Colormap, Display, Drawable, GC, IMAGE_FORMAT_Z_PIXMAP, LSB_FIRST, Pixmap,
+    Visual, Window, XImage,
 };
+use super::xrender::ImageCompositor;
+use super::xshm::{self, ShmImage};
 
 pub struct X11Painter {
     pub display: *mut Display,
@@ -21,9 +26,22 @@ pub struct X11Painter {
     black_pixel: c_ulong,
     white_pixel: c_ulong,
     visual_masks: (c_ulong, c_ulong, c_ulong),
+    visual: *mut Visual,
     xft: XftRenderer,
     cairo: CairoCanvas,
+    /// XRender and MIT-SHM image drawing, when the server offers both.
+    compositor: Option<ImageCompositor>,
+    /// Whether screenshots can be read back through MIT-SHM.
+    shm_images: bool,
     opacity_depth: usize,
+    clip_depth: usize,
+    transform_depth: usize,
+    clip: Option<Rect>,
+    /// Whether the back buffer holds the last flushed frame; false while it is new.
+    retained: bool,
+    /// Areas painted since the last flush, which is all of them once `damaged_all` is set.
+    damage: Vec<Rect>,
+    damaged_all: bool,
 }
 
 impl X11Painter {
@@ -50,6 +68,7 @@ impl X11Painter {
             back_buffer_width as i32,
             back_buffer_height as i32,
         )?;
+        let compositor = ImageCompositor::new(display, visual, back_buffer as Drawable);
         Ok(Self {
             display,
             window,
@@ -61,9 +80,18 @@ impl X11Painter {
             black_pixel,
             white_pixel,
             visual_masks,
+            visual,
             xft,
             cairo,
+            compositor,
+            shm_images: xshm::query_support(display).images,
             opacity_depth: 0,
+            clip_depth: 0,
+            transform_depth: 0,
+            clip: None,
+            retained: false,
+            damage: Vec::new(),
+            damaged_all: true,
         })
     }
 
@@ -101,6 +129,9 @@ impl X11Painter {
         self.xft.recreate_draw(new_back_buffer as Drawable)?;
         self.cairo
             .recreate(new_back_buffer as Drawable, width_i32, height_i32)?;
+        if let Some(compositor) = &mut self.compositor {
+            compositor.recreate(new_back_buffer as Drawable)?;
+        }
 
         unsafe {
             xlib::XFreePixmap(self.display, self.back_buffer);
@@ -109,12 +140,19 @@ impl X11Painter {
         self.back_buffer = new_back_buffer;
         self.back_buffer_width = width;
         self.back_buffer_height = height;
+        self.retained = false;
         Ok(())
     }
 
     pub fn destroy_xft_resources(&mut self) {
         self.xft.destroy();
         self.cairo.destroy();
+        self.compositor = None;
+    }
+
+    /// Copies the whole back buffer to the window on the next flush, as after an expose.
+    pub fn damage_all(&mut self) {
+        self.damaged_all = true;
     }
 
     pub fn back_buffer(&self) -> Pixmap {
@@ -131,28 +169,49 @@ impl X11Painter {
             .try_into()
             .map_err(|_| "Screenshot height out of range".to_owned())?;
 
-        let ximage = unsafe {
-            xlib::XGetImage(
+        // Reading through a shared segment skips streaming the pixels over the connection.
+        let shm_image = if self.shm_images {
+            ShmImage::get(
                 self.display,
                 self.back_buffer,
-                0,
-                0,
+                self.visual,
+                self.back_buffer_depth,
                 self.back_buffer_width,
                 self.back_buffer_height,
-                ALL_PLANES,
-                IMAGE_FORMAT_Z_PIXMAP,
             )
+            .ok()
+        } else {
+            None
+        };
+        let fetched;
+        let ximage: *mut XImage = match &shm_image {
+            Some(image) => image.as_ptr(),
+            None => {
+                let ximage = unsafe {
+                    xlib::XGetImage(
+                        self.display,
+                        self.back_buffer,
+                        0,
+                        0,
+                        self.back_buffer_width,
+                        self.back_buffer_height,
+                        ALL_PLANES,
+                        IMAGE_FORMAT_Z_PIXMAP,
+                    )
+                };
+                if ximage.is_null() {
+                    return Err("XGetImage returned null".to_owned());
+                }
+                fetched = xlib::XImageHandle(ximage);
+                fetched.0
+            }
         };
-        if ximage.is_null() {
-            return Err("XGetImage returned null".to_owned());
-        }
-        let ximage = xlib::XImageHandle(ximage);
 
         let (masks, get_pixel) = unsafe {
             let masks = (
-                (*ximage.0).red_mask,
-                (*ximage.0).green_mask,
-                (*ximage.0).blue_mask,
+                (*ximage).red_mask,
+                (*ximage).green_mask,
+                (*ximage).blue_mask,
             );
             let masks = if masks.0 == 0 && masks.1 == 0 && masks.2 == 0 {
                 self.visual_masks
@@ -160,7 +219,7 @@ impl X11Painter {
                 masks
             };
 
-            (masks, (*ximage.0).f.get_pixel)
+            (masks, (*ximage).f.get_pixel)
         };
 
         let get_pixel = get_pixel.ok_or_else(|| "XImage is missing get_pixel".to_owned())?;
@@ -173,12 +232,33 @@ impl X11Painter {
             .ok_or_else(|| "Screenshot buffer size overflow".to_owned())?;
         let mut rgb = Vec::with_capacity(expected_len);
 
+        // 32-bit little-endian pixels, the common case, are read straight from the image.
+        let direct_rows = unsafe {
+            ((*ximage).bits_per_pixel == 32 && (*ximage).byte_order == LSB_FIRST).then(|| {
+                let stride = (*ximage).bytes_per_line as usize;
+                std::slice::from_raw_parts((*ximage).data.cast::<u8>(), stride * height)
+                    .chunks_exact(stride)
+            })
+        };
+        if let Some(rows) = direct_rows {
+            for row in rows {
+                for pixel in row[..width * 4].chunks_exact(4) {
+                    let pixel_u64 =
+                        u64::from(u32::from_le_bytes([pixel[0], pixel[1], pixel[2], pixel[3]]));
+                    rgb.push(extract_channel(pixel_u64, masks.0));
+                    rgb.push(extract_channel(pixel_u64, masks.1));
+                    rgb.push(extract_channel(pixel_u64, masks.2));
+                }
+            }
+            return RgbImage::new(width_u32, height_u32, rgb);
+        }
+
         for y in 0..height {
             for x in 0..width {
-                let pixel_u64 = unsafe { get_pixel(ximage.0, x as c_int, y as c_int) as u64 };
-                let r = extract_channel(pixel_u64, masks.0 as u64);
-                let g = extract_channel(pixel_u64, masks.1 as u64);
-                let b = extract_channel(pixel_u64, masks.2 as u64);
+                let pixel_u64 = unsafe { get_pixel(ximage, x as c_int, y as c_int) as u64 };
+                let r = extract_channel(pixel_u64, masks.0);
+                let g = extract_channel(pixel_u64, masks.1);
+                let b = extract_channel(pixel_u64, masks.2);
                 rgb.push(r);
                 rgb.push(g);
                 rgb.push(b);
@@ -188,6 +268,12 @@ impl X11Painter {
         RgbImage::new(width_u32, height_u32, rgb)
     }
 
+    /// Whether painting has to go through Cairo, as Xlib, Xft and XRender know nothing of
+    /// groups, pushed clips or transforms.
+    fn through_cairo(&self) -> bool {
+        self.opacity_depth > 0 || self.clip_depth > 0 || self.transform_depth > 0
+    }
+
     fn set_foreground(&mut self, color: Color) {
         let pixel = self.pixel_for_color(color);
         unsafe {
@@ -223,6 +309,10 @@ impl TextMeasurer for X11Painter {
 
 impl Painter for X11Painter {
     fn clear(&mut self) -> Result<(), String> {
+        match self.clip {
+            Some(clip) => self.damage.push(clip),
+            None => self.damaged_all = true,
+        }
         self.fill_rect(
             0,
             0,
@@ -262,7 +352,7 @@ impl Painter for X11Painter {
             return Ok(());
         }
 
-        if self.opacity_depth > 0 || color.a != 255 {
+        if self.through_cairo() || color.a != 255 {
             self.cairo.fill_rect(x_px, y_px, width_px, height_px, color);
             return Ok(());
         }
@@ -332,10 +422,28 @@ impl Painter for X11Painter {
         text: &str,
         style: TextStyle,
     ) -> Result<(), String> {
-        if self.opacity_depth == 0 {
+        if !self.through_cairo() {
             self.xft.draw_text(x_px, y_px, text, style)?;
-        } else {
+        } else if style.letter_spacing_px == 0 {
             self.cairo.draw_text(x_px, y_px, text, style)?;
+        } else {
+            // Cairo's advances differ from the Xft ones the text was measured with, so each
+            // cluster goes where Xft put it.
+            let mut cursor_x = x_px;
+            for cluster in shaping::clusters(text) {
+                self.cairo.draw_text(
+                    cursor_x,
+                    y_px,
+                    cluster,
+                    TextStyle {
+                        letter_spacing_px: 0,
+                        ..style
+                    },
+                )?;
+                cursor_x = cursor_x
+                    .saturating_add(self.xft.text_width_px(cluster, style)?)
+                    .saturating_add(style.letter_spacing_px);
+            }
         }
 
         if style.underline {
@@ -364,6 +472,22 @@ impl Painter for X11Painter {
             return Ok(());
         }
 
+        if !self.through_cairo()
+            && let Some(compositor) = &mut self.compositor
+        {
+            return compositor.draw_image(
+                self.back_buffer as Drawable,
+                Rect {
+                    x: x_px,
+                    y: y_px,
+                    width: width_px,
+                    height: height_px,
+                },
+                image,
+                opacity,
+            );
+        }
+
         let mut data = image.data.clone();
         let surface = self.cairo.create_argb32_surface_for_data(
             &mut data,
@@ -385,35 +509,129 @@ impl Painter for X11Painter {
         Ok(())
     }
 
-    fn draw_svg(
+    fn push_clip_rect(
         &mut self,
         x_px: i32,
         y_px: i32,
         width_px: i32,
         height_px: i32,
-        svg_xml: &str,
-        opacity: u8,
     ) -> Result<(), String> {
-        self.cairo
-            .draw_svg(x_px, y_px, width_px, height_px, svg_xml, opacity)
+        self.clip_depth = self.clip_depth.saturating_add(1);
+        self.cairo.push_clip_rect(x_px, y_px, width_px, height_px);
+        Ok(())
+    }
+
+    fn pop_clip(&mut self) -> Result<(), String> {
+        if self.clip_depth == 0 {
+            return Err("clip stack underflow".to_owned());
+        }
+        self.clip_depth -= 1;
+        self.cairo.restore();
+        Ok(())
+    }
+
+    fn push_transform(&mut self, transform: Transform) -> Result<(), String> {
+        self.transform_depth = self.transform_depth.saturating_add(1);
+        self.cairo.push_transform(transform);
+        Ok(())
+    }
+
+    fn pop_transform(&mut self) -> Result<(), String> {
+        if self.transform_depth == 0 {
+            return Err("transform stack underflow".to_owned());
+        }
+        self.transform_depth -= 1;
+        self.cairo.restore();
+        Ok(())
+    }
+
+    fn fill_path(
+        &mut self,
+        path: &[PathCommand],
+        rule: FillRule,
+        color: Color,
+    ) -> Result<(), String> {
+        self.cairo.fill_path(path, rule, color);
+        Ok(())
+    }
+
+    fn stroke_path(
+        &mut self,
+        path: &[PathCommand],
+        style: &StrokeStyle,
+        color: Color,
+    ) -> Result<(), String> {
+        self.cairo.stroke_path(path, style, color);
+        Ok(())
+    }
+
+    fn retains_frame(&self) -> bool {
+        self.retained
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), String> {
+        self.clip = clip;
+        let rectangle = clip.map(xlib::x_rectangle);
+        unsafe {
+            match &rectangle {
+                Some(rectangle) => xlib::XSetClipRectangles(
+                    self.display,
+                    self.gc,
+                    0,
+                    0,
+                    rectangle,
+                    1,
+                    xlib::CLIP_ORDERING_UNSORTED,
+                ),
+                None => xlib::XSetClipMask(self.display, self.gc, 0),
+            };
+        }
+        self.xft.set_clip(rectangle);
+        self.cairo.set_clip(clip);
+        if let Some(compositor) = &mut self.compositor {
+            compositor.set_clip(clip);
+        }
+        Ok(())
     }
 
     fn flush(&mut self) -> Result<(), String> {
+        let full = Rect {
+            x: 0,
+            y: 0,
+            width: self.back_buffer_width as i32,
+            height: self.back_buffer_height as i32,
+        };
+        let damage = if self.damaged_all {
+            vec![full]
+        } else {
+            std::mem::take(&mut self.damage)
+        };
+        for rect in damage {
+            let rect = rect.intersection(full);
+            if rect.is_empty() {
+                continue;
+            }
+            unsafe {
+                xlib::XCopyArea(
+                    self.display,
+                    self.back_buffer,
+                    self.window,
+                    self.gc,
+                    rect.x,
+                    rect.y,
+                    rect.width as c_uint,
+                    rect.height as c_uint,
+                    rect.x,
+                    rect.y,
+                );
+            }
+        }
         unsafe {
-            xlib::XCopyArea(
-                self.display,
-                self.back_buffer,
-                self.window,
-                self.gc,
-                0,
-                0,
-                self.back_buffer_width,
-                self.back_buffer_height,
-                0,
-                0,
-            );
             xlib::XFlush(self.display);
         }
+        self.damage.clear();
+        self.damaged_all = false;
+        self.retained = true;
         Ok(())
     }
 }
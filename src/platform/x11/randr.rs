@@ -0,0 +1,151 @@
+use core::ffi::{c_int, c_ulong};
+
+use super::scale::ScaleFactor;
+use super::xlib::{Atom, Bool, Display, Window, XEvent, XTranslateCoordinates};
+use crate::render::Viewport;
+
+const RR_SCREEN_CHANGE_NOTIFY: c_int = 0;
+const RR_SCREEN_CHANGE_NOTIFY_MASK: c_int = 1 << 0;
+const MM_PER_INCH_10: i64 = 254;
+
+#[repr(C)]
+struct XRRMonitorInfo {
+    name: Atom,
+    primary: Bool,
+    automatic: Bool,
+    noutput: c_int,
+    x: c_int,
+    y: c_int,
+    width: c_int,
+    height: c_int,
+    mwidth: c_int,
+    mheight: c_int,
+    outputs: *mut c_ulong,
+}
+
+#[link(name = "Xrandr")]
+unsafe extern "C" {
+    fn XRRQueryExtension(
+        display: *mut Display,
+        event_base_return: *mut c_int,
+        error_base_return: *mut c_int,
+    ) -> Bool;
+    fn XRRQueryVersion(
+        display: *mut Display,
+        major_version_return: *mut c_int,
+        minor_version_return: *mut c_int,
+    ) -> c_int;
+    fn XRRSelectInput(display: *mut Display, window: Window, mask: c_int);
+    fn XRRUpdateConfiguration(event: *mut XEvent) -> c_int;
+    fn XRRGetMonitors(
+        display: *mut Display,
+        window: Window,
+        get_active: Bool,
+        nmonitors: *mut c_int,
+    ) -> *mut XRRMonitorInfo;
+    fn XRRFreeMonitors(monitors: *mut XRRMonitorInfo);
+}
+
+/// The monitors the root window spans, through RandR 1.5, for the density of the one the window
+/// is on.
+pub struct Monitors {
+    root: Window,
+    event_base: c_int,
+}
+
+impl Monitors {
+    /// Watches `root` for monitors changing; `None` when the server's RandR is older than 1.5.
+    pub fn new(display: *mut Display, root: Window) -> Option<Self> {
+        let mut event_base = 0;
+        let mut error_base = 0;
+        if unsafe { XRRQueryExtension(display, &mut event_base, &mut error_base) } == 0 {
+            return None;
+        }
+        let mut major = 0;
+        let mut minor = 0;
+        if unsafe { XRRQueryVersion(display, &mut major, &mut minor) } == 0
+            || (major, minor) < (1, 5)
+        {
+            return None;
+        }
+        unsafe {
+            XRRSelectInput(display, root, RR_SCREEN_CHANGE_NOTIFY_MASK);
+        }
+        Some(Self { root, event_base })
+    }
+
+    /// Whether `event` says the monitors changed, in which case Xlib has been told too.
+    pub fn is_screen_change(&self, event: &mut XEvent) -> bool {
+        if event.event_type() != self.event_base + RR_SCREEN_CHANGE_NOTIFY {
+            return false;
+        }
+        unsafe {
+            XRRUpdateConfiguration(event);
+        }
+        true
+    }
+
+    /// `settings_scale`, which the desktop chose for the primary monitor, carried onto the monitor
+    /// under the middle of `window`.
+    pub fn scale_for(
+        &self,
+        display: *mut Display,
+        window: Window,
+        viewport: Viewport,
+        settings_scale: ScaleFactor,
+    ) -> ScaleFactor {
+        let mut x = 0;
+        let mut y = 0;
+        let mut child: Window = 0;
+        let translated = unsafe {
+            XTranslateCoordinates(
+                display,
+                window,
+                self.root,
+                viewport.width_px / 2,
+                viewport.height_px / 2,
+                &mut x,
+                &mut y,
+                &mut child,
+            )
+        };
+        if translated == 0 {
+            return settings_scale;
+        }
+
+        let mut count = 0;
+        let list = unsafe { XRRGetMonitors(display, self.root, 1, &mut count) };
+        if list.is_null() {
+            return settings_scale;
+        }
+        let monitors =
+            unsafe { std::slice::from_raw_parts(list, usize::try_from(count).unwrap_or(0)) };
+        let reference = monitors
+            .iter()
+            .find(|monitor| monitor.primary != 0)
+            .or(monitors.first());
+        let current = monitors.iter().find(|monitor| {
+            (monitor.x..monitor.x + monitor.width).contains(&x)
+                && (monitor.y..monitor.y + monitor.height).contains(&y)
+        });
+        let scale = match (current, reference) {
+            (Some(current), Some(reference)) => {
+                settings_scale.for_monitor_dpi(monitor_dpi(current), monitor_dpi(reference))
+            }
+            _ => settings_scale,
+        };
+        unsafe {
+            XRRFreeMonitors(list);
+        }
+        scale
+    }
+}
+
+/// Horizontal dots per inch, or 0 when the monitor doesn't report its physical size.
+fn monitor_dpi(monitor: &XRRMonitorInfo) -> u32 {
+    if monitor.mwidth <= 0 || monitor.width <= 0 {
+        return 0;
+    }
+    let dpi = i64::from(monitor.width) * MM_PER_INCH_10 / (i64::from(monitor.mwidth) * 10);
+    u32::try_from(dpi).unwrap_or(0)
+}
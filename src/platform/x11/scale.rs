@@ -16,8 +16,8 @@ pub(super) struct ScaleFactor {
 
 impl ScaleFactor {
     pub fn detect(display: *mut Display, screen: c_int) -> Self {
-        if let Some(scale) = scale_from_env() {
-            return Self::new(scale);
+        if let Some(scale) = Self::from_env() {
+            return scale;
         }
         if let Some(scale) = scale_from_xsettings(display, screen) {
             return Self::new(scale);
@@ -28,6 +28,11 @@ impl ScaleFactor {
         Self::new(SCALE_ONE_1024)
     }
 
+    /// The scale `OAB_SCALE` asks for, which takes precedence over the desktop's.
+    pub fn from_env() -> Option<Self> {
+        scale_from_env().map(Self::new)
+    }
+
     pub fn new(scale_1024: u32) -> Self {
         let mut scale_1024 = scale_1024.clamp(MIN_SCALE_1024, MAX_SCALE_1024);
         if scale_1024 == 0 {
@@ -36,6 +41,28 @@ impl ScaleFactor {
         Self { scale_1024 }
     }
 
+    /// Device pixels per CSS pixel, as CSS `resolution` media queries see it.
+    pub fn device_pixel_ratio(self) -> f32 {
+        self.scale_1024 as f32 / 1024.0
+    }
+
+    /// The scale with page zoom applied on top, e.g. `zoomed(125)` for 125%.
+    pub fn zoomed(self, percent: u32) -> Self {
+        let scaled = u64::from(self.scale_1024) * u64::from(percent) / 100;
+        Self::new(u32::try_from(scaled).unwrap_or(u32::MAX))
+    }
+
+    /// The scale carried from a monitor `reference_dpi` dense onto one `dpi` dense, to the nearest
+    /// quarter of the ratio so near-equal monitors share a scale.
+    pub fn for_monitor_dpi(self, dpi: u32, reference_dpi: u32) -> Self {
+        if dpi == 0 || reference_dpi == 0 {
+            return self;
+        }
+        let quarters = div_round_nearest(i64::from(dpi) * 4, i64::from(reference_dpi)).max(1);
+        let scaled = mul_div_round_nearest(i64::from(self.scale_1024), quarters, 4);
+        Self::new(clamp_i64_to_u32(scaled))
+    }
+
     pub fn css_size_to_device_px(self, css_px: i32) -> i32 {
         let css_px = i64::from(css_px);
         let scaled = mul_div_round_nearest(css_px, i64::from(self.scale_1024), 1024);
@@ -403,4 +430,26 @@ mod tests {
         // CSS pixel 2 starts at device 3
         assert_eq!(scale.device_coord_to_css_px(3), 2);
     }
+
+    #[test]
+    fn zoom_multiplies_the_platform_scale() {
+        let scale = ScaleFactor::new(1280).zoomed(200);
+
+        assert_eq!(scale, ScaleFactor::new(2560));
+        assert_eq!(scale.css_size_to_device_px(100), 250);
+        assert_eq!(
+            ScaleFactor::new(1024).zoomed(1),
+            ScaleFactor::new(MIN_SCALE_1024)
+        );
+    }
+
+    #[test]
+    fn monitor_scale_follows_the_dpi_ratio_in_quarters() {
+        let scale = ScaleFactor::new(1280);
+
+        assert_eq!(scale.for_monitor_dpi(192, 96), ScaleFactor::new(2560));
+        assert_eq!(scale.for_monitor_dpi(101, 96), scale);
+        assert_eq!(scale.for_monitor_dpi(144, 96), ScaleFactor::new(1920));
+        assert_eq!(scale.for_monitor_dpi(0, 96), scale);
+    }
 }
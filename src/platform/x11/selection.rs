@@ -0,0 +1,107 @@
+use core::ffi::{c_int, c_long, c_uchar, c_ulong};
+
+use super::xlib::{
+    Atom, CURRENT_TIME, Display, Window, XA_PRIMARY, XConvertSelection, XFree, XGetWindowProperty,
+    XInternAtom, XSelectionEvent,
+};
+
+/// Largest paste read, in 32-bit units as `XGetWindowProperty` counts.
+const MAX_PASTE_LONGS: c_long = 1 << 20;
+
+/// Which selection a paste reads: `CLIPBOARD` for Ctrl+V, `PRIMARY` for a middle click.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    Clipboard,
+    Primary,
+}
+
+/// Pastes text from selections other clients own. A request converts the selection onto a
+/// property of the window; the text arrives with the `SelectionNotify` that follows.
+pub struct Selections {
+    clipboard: Atom,
+    utf8_string: Atom,
+    incr: Atom,
+    property: Atom,
+}
+
+impl Selections {
+    pub fn new(display: *mut Display) -> Self {
+        let atom = |name: &std::ffi::CStr| unsafe { XInternAtom(display, name.as_ptr(), 0) };
+        Self {
+            clipboard: atom(c"CLIPBOARD"),
+            utf8_string: atom(c"UTF8_STRING"),
+            incr: atom(c"INCR"),
+            property: atom(c"OAB_PASTE"),
+        }
+    }
+
+    pub fn request(&self, display: *mut Display, window: Window, selection: Selection) {
+        let selection = match selection {
+            Selection::Clipboard => self.clipboard,
+            Selection::Primary => XA_PRIMARY,
+        };
+        unsafe {
+            XConvertSelection(
+                display,
+                selection,
+                self.utf8_string,
+                self.property,
+                window,
+                CURRENT_TIME,
+            );
+        }
+    }
+
+    /// The text a `SelectionNotify` delivered, or `None` when the owner refused or sent it in
+    /// increments, which aren't read.
+    pub fn text(&self, display: *mut Display, event: &XSelectionEvent) -> Option<String> {
+        if event.property != self.property {
+            return None;
+        }
+        self.take_property_text(display, event.requestor, self.property)
+    }
+
+    /// Reads and deletes the text a selection owner converted onto `property` of `window`.
+    pub fn take_property_text(
+        &self,
+        display: *mut Display,
+        window: Window,
+        property: Atom,
+    ) -> Option<String> {
+        let mut actual_type: Atom = 0;
+        let mut actual_format: c_int = 0;
+        let mut nitems: c_ulong = 0;
+        let mut bytes_after: c_ulong = 0;
+        let mut prop: *mut c_uchar = std::ptr::null_mut();
+        let status = unsafe {
+            XGetWindowProperty(
+                display,
+                window,
+                property,
+                0,
+                MAX_PASTE_LONGS,
+                1,
+                0,
+                &mut actual_type,
+                &mut actual_format,
+                &mut nitems,
+                &mut bytes_after,
+                &mut prop,
+            )
+        };
+        if status != 0 || prop.is_null() {
+            return None;
+        }
+        let text = (actual_type != self.incr && actual_format == 8)
+            .then(|| {
+                let len = usize::try_from(nitems).ok()?;
+                let bytes = unsafe { std::slice::from_raw_parts(prop, len) };
+                Some(String::from_utf8_lossy(bytes).into_owned())
+            })
+            .flatten();
+        unsafe {
+            XFree(prop.cast());
+        }
+        text
+    }
+}
@@ -0,0 +1,99 @@
+use crate::image::Argb32Image;
+use core::ffi::{c_int, c_uchar, c_ulong};
+use std::ffi::CString;
+
+use super::xlib::{
+    Atom, Display, PROP_MODE_REPLACE, Window, XA_CARDINAL, XChangeProperty, XInternAtom, XStoreName,
+};
+
+/// Sets the title and icon the window manager shows for the window.
+pub struct WindowProperties {
+    net_wm_name: Atom,
+    net_wm_icon: Atom,
+    utf8_string: Atom,
+}
+
+impl WindowProperties {
+    pub fn new(display: *mut Display) -> Self {
+        let atom = |name: &std::ffi::CStr| unsafe { XInternAtom(display, name.as_ptr(), 0) };
+        Self {
+            net_wm_name: atom(c"_NET_WM_NAME"),
+            net_wm_icon: atom(c"_NET_WM_ICON"),
+            utf8_string: atom(c"UTF8_STRING"),
+        }
+    }
+
+    /// Sets `WM_NAME` for older window managers and `_NET_WM_NAME`, which carries UTF-8, for the
+    /// rest. NUL bytes are dropped.
+    pub fn set_title(&self, display: *mut Display, window: Window, title: &str) {
+        let Ok(title) = CString::new(title.replace('\0', "")) else {
+            return;
+        };
+        let bytes = title.as_bytes();
+        unsafe {
+            XStoreName(display, window, title.as_ptr());
+            XChangeProperty(
+                display,
+                window,
+                self.net_wm_name,
+                self.utf8_string,
+                8,
+                PROP_MODE_REPLACE,
+                bytes.as_ptr(),
+                bytes.len() as c_int,
+            );
+        }
+    }
+
+    pub fn set_icon(&self, display: *mut Display, window: Window, icon: &Argb32Image) {
+        let data = net_wm_icon_data(icon);
+        unsafe {
+            XChangeProperty(
+                display,
+                window,
+                self.net_wm_icon,
+                XA_CARDINAL,
+                32,
+                PROP_MODE_REPLACE,
+                data.as_ptr().cast::<c_uchar>(),
+                data.len() as c_int,
+            );
+        }
+    }
+}
+
+/// `_NET_WM_ICON` wants the width, the height, and then one straight-alpha ARGB pixel per
+/// item. Xlib passes format-32 items as C longs, whatever their size.
+fn net_wm_icon_data(icon: &Argb32Image) -> Vec<c_ulong> {
+    let mut data = Vec::with_capacity(2 + icon.data.len() / 4);
+    data.push(c_ulong::from(icon.width));
+    data.push(c_ulong::from(icon.height));
+    data.extend(icon.data.chunks_exact(4).map(|bgra| {
+        let alpha = u32::from(bgra[3]);
+        let unpremultiply = |channel: u8| {
+            (u32::from(channel) * 255 + alpha / 2)
+                .checked_div(alpha)
+                .unwrap_or(0)
+        };
+        c_ulong::from(
+            alpha << 24
+                | unpremultiply(bgra[2]) << 16
+                | unpremultiply(bgra[1]) << 8
+                | unpremultiply(bgra[0]),
+        )
+    }));
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::net_wm_icon_data;
+    use crate::image::Argb32Image;
+
+    #[test]
+    fn icon_property_is_size_then_straight_argb_pixels() {
+        // Opaque red, then blue at half opacity.
+        let icon = Argb32Image::new(2, 1, vec![0, 0, 255, 255, 128, 0, 0, 128]).unwrap();
+        assert_eq!(net_wm_icon_data(&icon), [2, 1, 0xffff_0000, 0x8000_00ff]);
+    }
+}
@@ -1,12 +1,12 @@
 use crate::geom::Color;
-use crate::render::{FontMetricsPx, TextStyle};
+use crate::render::{FontMetricsPx, TextStyle, shaping};
 use crate::style::FontFamily;
 use core::ffi::{c_char, c_int, c_short, c_uchar, c_ulong, c_ushort, c_void};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::ffi::CString;
 
-use super::xlib::{Bool, Colormap, Display, Drawable, Visual};
+use super::xlib::{Bool, Colormap, Display, Drawable, Visual, XRectangle};
 
 pub type XftDraw = c_void;
 
@@ -54,6 +54,14 @@ unsafe extern "C" {
         colormap: Colormap,
     ) -> *mut XftDraw;
     fn XftDrawDestroy(draw: *mut XftDraw);
+    fn XftDrawSetClipRectangles(
+        draw: *mut XftDraw,
+        x_origin: c_int,
+        y_origin: c_int,
+        rects: *const XRectangle,
+        n: c_int,
+    ) -> Bool;
+    fn XftDrawSetClip(draw: *mut XftDraw, region: *mut c_void) -> Bool;
 
     fn XftFontOpenName(dpy: *mut Display, screen: c_int, name: *const c_char) -> *mut XftFont;
     fn XftFontClose(dpy: *mut Display, font: *mut XftFont);
@@ -153,6 +161,19 @@ impl XftRenderer {
         Ok(())
     }
 
+    /// Limits drawing to `clip`; `None` draws everywhere.
+    pub fn set_clip(&mut self, clip: Option<XRectangle>) {
+        if self.draw.is_null() {
+            return;
+        }
+        unsafe {
+            match clip {
+                Some(rect) => XftDrawSetClipRectangles(self.draw, 0, 0, &rect, 1),
+                None => XftDrawSetClip(self.draw, std::ptr::null_mut()),
+            };
+        }
+    }
+
     pub fn destroy(&mut self) {
         if self.draw.is_null() {
             return;
@@ -191,17 +212,9 @@ impl XftRenderer {
             return self.text_width_px_no_spacing(text, style);
         }
 
-        let mut total_width: i64 = 0;
-        let mut first = true;
-        for ch in text.chars() {
-            if !first {
-                total_width += i64::from(style.letter_spacing_px);
-            }
-            first = false;
-
-            let mut buf = [0u8; 4];
-            let ch = ch.encode_utf8(&mut buf);
-            total_width += i64::from(self.text_width_px_no_spacing(ch, style)?);
+        let mut total_width = i64::from(style.letter_spacing_total_px(text));
+        for cluster in shaping::clusters(text) {
+            total_width += i64::from(self.text_width_px_no_spacing(cluster, style)?);
         }
 
         Ok(total_width.clamp(0, i64::from(i32::MAX)) as i32)
@@ -239,16 +252,11 @@ impl XftRenderer {
         }
 
         let mut cursor_x = x_px;
-        let mut first = true;
-        for ch in text.chars() {
-            if !first {
+        for (index, cluster) in shaping::clusters(text).enumerate() {
+            if index > 0 {
                 cursor_x = cursor_x.saturating_add(style.letter_spacing_px);
             }
-            first = false;
-
-            let mut buf = [0u8; 4];
-            let ch = ch.encode_utf8(&mut buf);
-            let len: c_int = ch
+            let len: c_int = cluster
                 .len()
                 .try_into()
                 .map_err(|_| "text length out of range for Xft".to_owned())?;
@@ -259,11 +267,11 @@ impl XftRenderer {
                     font,
                     cursor_x,
                     y_px,
-                    ch.as_ptr().cast::<c_uchar>(),
+                    cluster.as_ptr().cast::<c_uchar>(),
                     len,
                 );
             }
-            cursor_x = cursor_x.saturating_add(self.text_width_px_no_spacing(ch, style)?);
+            cursor_x = cursor_x.saturating_add(self.text_width_px_no_spacing(cluster, style)?);
         }
         Ok(())
     }
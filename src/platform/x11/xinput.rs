@@ -0,0 +1,469 @@
+use core::ffi::{c_char, c_double, c_int, c_uchar, c_uint, c_ulong, c_void};
+
+use super::xlib::{Bool, Display, Window, XEvent};
+use crate::platform::touch::{TouchEvent, TouchPhase};
+
+const GENERIC_EVENT: c_int = 35;
+const XI_ALL_MASTER_DEVICES: c_int = 1;
+const XI_DEVICE_CHANGED: c_int = 1;
+const XI_MOTION: c_int = 6;
+const XI_TOUCH_BEGIN: c_int = 18;
+const XI_TOUCH_UPDATE: c_int = 19;
+const XI_TOUCH_END: c_int = 20;
+const XI_MASTER_POINTER: c_int = 1;
+const XI_VALUATOR_CLASS: c_int = 2;
+const XI_SCROLL_CLASS: c_int = 3;
+const XI_SCROLL_TYPE_VERTICAL: c_int = 1;
+const XI_SCROLL_TYPE_HORIZONTAL: c_int = 2;
+const SHIFT_MASK: c_int = 1 << 0;
+const SUCCESS: c_int = 0;
+
+#[repr(C)]
+struct XIEventMask {
+    deviceid: c_int,
+    mask_len: c_int,
+    mask: *mut c_uchar,
+}
+
+#[repr(C)]
+struct XIDeviceInfo {
+    deviceid: c_int,
+    name: *mut c_char,
+    use_: c_int,
+    attachment: c_int,
+    enabled: Bool,
+    num_classes: c_int,
+    classes: *mut *mut XIAnyClassInfo,
+}
+
+#[repr(C)]
+struct XIAnyClassInfo {
+    type_: c_int,
+    sourceid: c_int,
+}
+
+#[repr(C)]
+struct XIValuatorClassInfo {
+    type_: c_int,
+    sourceid: c_int,
+    number: c_int,
+    label: c_ulong,
+    min: c_double,
+    max: c_double,
+    value: c_double,
+    resolution: c_int,
+    mode: c_int,
+}
+
+#[repr(C)]
+struct XIScrollClassInfo {
+    type_: c_int,
+    sourceid: c_int,
+    number: c_int,
+    scroll_type: c_int,
+    increment: c_double,
+    flags: c_int,
+}
+
+#[repr(C)]
+struct XGenericEventCookie {
+    type_: c_int,
+    serial: c_ulong,
+    send_event: Bool,
+    display: *mut Display,
+    extension: c_int,
+    evtype: c_int,
+    cookie: c_uint,
+    data: *mut c_void,
+}
+
+#[repr(C)]
+struct XIDeviceChangedEvent {
+    type_: c_int,
+    serial: c_ulong,
+    send_event: Bool,
+    display: *mut Display,
+    extension: c_int,
+    evtype: c_int,
+    time: c_ulong,
+    deviceid: c_int,
+}
+
+#[repr(C)]
+struct XIMask {
+    mask_len: c_int,
+    mask: *mut c_uchar,
+}
+
+#[repr(C)]
+struct XIValuatorState {
+    mask_len: c_int,
+    mask: *mut c_uchar,
+    values: *mut c_double,
+}
+
+#[repr(C)]
+struct XIModifierState {
+    base: c_int,
+    latched: c_int,
+    locked: c_int,
+    effective: c_int,
+}
+
+#[repr(C)]
+struct XIDeviceEvent {
+    type_: c_int,
+    serial: c_ulong,
+    send_event: Bool,
+    display: *mut Display,
+    extension: c_int,
+    evtype: c_int,
+    time: c_ulong,
+    deviceid: c_int,
+    sourceid: c_int,
+    detail: c_int,
+    root: Window,
+    event: Window,
+    child: Window,
+    root_x: c_double,
+    root_y: c_double,
+    event_x: c_double,
+    event_y: c_double,
+    flags: c_int,
+    buttons: XIMask,
+    valuators: XIValuatorState,
+    mods: XIModifierState,
+}
+
+#[link(name = "X11")]
+unsafe extern "C" {
+    fn XQueryExtension(
+        display: *mut Display,
+        name: *const c_char,
+        major_opcode: *mut c_int,
+        first_event: *mut c_int,
+        first_error: *mut c_int,
+    ) -> Bool;
+    fn XGetEventData(display: *mut Display, cookie: *mut XGenericEventCookie) -> Bool;
+    fn XFreeEventData(display: *mut Display, cookie: *mut XGenericEventCookie);
+}
+
+#[link(name = "Xi")]
+unsafe extern "C" {
+    fn XIQueryVersion(display: *mut Display, major: *mut c_int, minor: *mut c_int) -> c_int;
+    fn XISelectEvents(
+        display: *mut Display,
+        window: Window,
+        masks: *mut XIEventMask,
+        num_masks: c_int,
+    ) -> c_int;
+    fn XIQueryDevice(
+        display: *mut Display,
+        deviceid: c_int,
+        ndevices: *mut c_int,
+    ) -> *mut XIDeviceInfo;
+    fn XIFreeDeviceInfo(info: *mut XIDeviceInfo);
+}
+
+/// What an XInput2 event means for the app: the pointer moving to window device pixels, a scroll,
+/// or both; or a touch.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointerInput {
+    pub position: Option<(i32, i32)>,
+    pub scroll: Option<ScrollDelta>,
+    /// A sideways scroll, positive to the right: the horizontal valuator's, or the vertical one's
+    /// while Shift is held.
+    pub horizontal_scroll: Option<ScrollDelta>,
+    pub touch: Option<TouchEvent>,
+}
+
+/// A scroll in device pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScrollDelta {
+    /// Whole wheel notches.
+    Notches(i32),
+    /// A touchpad's or free-spinning wheel's fraction of a notch.
+    Precise(i32),
+}
+
+/// Pointer input through XInput2. With 2.1, the master pointer reports scrolls as a valuator that
+/// moves by `increment` per wheel notch, and a touchpad moves it by fractions of that. With 2.2,
+/// touchscreens report each finger rather than emulating a pointer.
+pub struct XInput {
+    opcode: c_int,
+    vertical: Option<ScrollValuator>,
+    horizontal: Option<ScrollValuator>,
+    last_position: Option<(i32, i32)>,
+}
+
+impl XInput {
+    /// Selects pointer motion, and touches when the server has XInput 2.2, through XInput2 instead
+    /// of the core protocol. `None` when the server lacks XInput 2.1, in which case scrolling
+    /// stays on buttons 4 and 5.
+    pub fn new(display: *mut Display, window: Window) -> Option<Self> {
+        let (mut opcode, mut first_event, mut first_error) = (0, 0, 0);
+        let present = unsafe {
+            XQueryExtension(
+                display,
+                c"XInputExtension".as_ptr(),
+                &mut opcode,
+                &mut first_event,
+                &mut first_error,
+            )
+        } != 0;
+        let (mut major, mut minor) = (2, 2);
+        if !present
+            || unsafe { XIQueryVersion(display, &mut major, &mut minor) } != SUCCESS
+            || (major, minor) < (2, 1)
+        {
+            return None;
+        }
+
+        let mut events = vec![XI_DEVICE_CHANGED, XI_MOTION];
+        if (major, minor) >= (2, 2) {
+            events.extend([XI_TOUCH_BEGIN, XI_TOUCH_UPDATE, XI_TOUCH_END]);
+        }
+        let mut mask = [0u8; 3];
+        for event in events {
+            mask[event as usize / 8] |= 1 << (event % 8);
+        }
+        let mut event_mask = XIEventMask {
+            deviceid: XI_ALL_MASTER_DEVICES,
+            mask_len: mask.len() as c_int,
+            mask: mask.as_mut_ptr(),
+        };
+        if unsafe { XISelectEvents(display, window, &mut event_mask, 1) } != SUCCESS {
+            return None;
+        }
+        Some(Self {
+            opcode,
+            vertical: query_scroll_valuator(
+                display,
+                XI_ALL_MASTER_DEVICES,
+                XI_SCROLL_TYPE_VERTICAL,
+            ),
+            horizontal: query_scroll_valuator(
+                display,
+                XI_ALL_MASTER_DEVICES,
+                XI_SCROLL_TYPE_HORIZONTAL,
+            ),
+            last_position: None,
+        })
+    }
+
+    /// Whether the server reports the wheel through a scroll valuator, so the buttons 4 and 5 it
+    /// also emulates are duplicates.
+    pub fn handles_wheel_buttons(&self) -> bool {
+        self.vertical.is_some()
+    }
+
+    /// Like [`XInput::handles_wheel_buttons`], for the tilt buttons 6 and 7.
+    pub fn handles_tilt_buttons(&self) -> bool {
+        self.horizontal.is_some()
+    }
+
+    /// Reads an XInput2 event; `None` for any other event.
+    pub fn event(&mut self, display: *mut Display, event: &mut XEvent) -> Option<PointerInput> {
+        let cookie = event.inner.as_mut_ptr().cast::<XGenericEventCookie>();
+        unsafe {
+            if (*cookie).type_ != GENERIC_EVENT
+                || (*cookie).extension != self.opcode
+                || XGetEventData(display, cookie) == 0
+            {
+                return None;
+            }
+        }
+        let input = match unsafe { (*cookie).evtype } {
+            XI_MOTION => {
+                let device_event = unsafe { &*(*cookie).data.cast::<XIDeviceEvent>() };
+                self.motion(device_event)
+            }
+            evtype @ (XI_TOUCH_BEGIN | XI_TOUCH_UPDATE | XI_TOUCH_END) => {
+                let device_event = unsafe { &*(*cookie).data.cast::<XIDeviceEvent>() };
+                let phase = match evtype {
+                    XI_TOUCH_BEGIN => TouchPhase::Down,
+                    XI_TOUCH_UPDATE => TouchPhase::Motion,
+                    _ => TouchPhase::Up,
+                };
+                PointerInput {
+                    touch: Some(TouchEvent {
+                        phase,
+                        id: device_event.detail,
+                        x_px: device_event.event_x,
+                        y_px: device_event.event_y,
+                        time_ms: device_event.time as u32,
+                    }),
+                    ..PointerInput::default()
+                }
+            }
+            XI_DEVICE_CHANGED => {
+                // Another device now drives the pointer, with valuators of its own.
+                let changed = unsafe { &*(*cookie).data.cast::<XIDeviceChangedEvent>() };
+                self.vertical =
+                    query_scroll_valuator(display, changed.deviceid, XI_SCROLL_TYPE_VERTICAL);
+                self.horizontal =
+                    query_scroll_valuator(display, changed.deviceid, XI_SCROLL_TYPE_HORIZONTAL);
+                PointerInput::default()
+            }
+            _ => PointerInput::default(),
+        };
+        unsafe {
+            XFreeEventData(display, cookie);
+        }
+        Some(input)
+    }
+
+    fn motion(&mut self, event: &XIDeviceEvent) -> PointerInput {
+        let position = (event.event_x.floor() as i32, event.event_y.floor() as i32);
+        let moved = self.last_position != Some(position);
+        self.last_position = Some(position);
+
+        let scroll = self.vertical.as_mut().and_then(|vertical| {
+            valuator_value(&event.valuators, vertical.number)
+                .and_then(|value| vertical.scroll_delta(value))
+        });
+        let horizontal_scroll = self.horizontal.as_mut().and_then(|horizontal| {
+            valuator_value(&event.valuators, horizontal.number)
+                .and_then(|value| horizontal.scroll_delta(value))
+        });
+        let (scroll, horizontal_scroll) = if event.mods.effective & SHIFT_MASK != 0 {
+            (None, horizontal_scroll.or(scroll))
+        } else {
+            (scroll, horizontal_scroll)
+        };
+        PointerInput {
+            position: moved.then_some(position),
+            scroll,
+            horizontal_scroll,
+            touch: None,
+        }
+    }
+}
+
+/// The scroll valuator of `scroll_type` of the master pointer among `deviceid`, starting from
+/// where it is now.
+fn query_scroll_valuator(
+    display: *mut Display,
+    deviceid: c_int,
+    scroll_type: c_int,
+) -> Option<ScrollValuator> {
+    let mut count = 0;
+    let devices = unsafe { XIQueryDevice(display, deviceid, &mut count) };
+    if devices.is_null() {
+        return None;
+    }
+    let devices = unsafe { std::slice::from_raw_parts(devices, count.max(0) as usize) };
+    let valuator = devices
+        .iter()
+        .filter(|device| device.use_ == XI_MASTER_POINTER)
+        .find_map(|device| scroll_valuator(device, scroll_type));
+    unsafe {
+        XIFreeDeviceInfo(devices.as_ptr().cast_mut());
+    }
+    valuator
+}
+
+fn scroll_valuator(device: &XIDeviceInfo, scroll_type: c_int) -> Option<ScrollValuator> {
+    let classes = if device.classes.is_null() {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(device.classes, device.num_classes.max(0) as usize) }
+    };
+    let class_of_type = |type_: c_int| {
+        classes
+            .iter()
+            .filter(|class| !class.is_null())
+            .filter(move |class| unsafe { (***class).type_ } == type_)
+    };
+    let scroll = class_of_type(XI_SCROLL_CLASS)
+        .map(|class| unsafe { &*class.cast::<XIScrollClassInfo>() })
+        .find(|scroll| scroll.scroll_type == scroll_type && scroll.increment != 0.0)?;
+    let mut valuator = ScrollValuator::new(scroll.number, scroll.increment);
+    valuator.last_value = class_of_type(XI_VALUATOR_CLASS)
+        .map(|class| unsafe { &*class.cast::<XIValuatorClassInfo>() })
+        .find(|axis| axis.number == scroll.number)
+        .map(|axis| axis.value);
+    Some(valuator)
+}
+
+/// The value of valuator `number` in an event, if the event carries it. Values are packed, one
+/// for each bit set in the mask.
+fn valuator_value(state: &XIValuatorState, number: c_int) -> Option<f64> {
+    if state.mask.is_null() || number < 0 {
+        return None;
+    }
+    let mask = unsafe { std::slice::from_raw_parts(state.mask, state.mask_len.max(0) as usize) };
+    let number = number as usize;
+    if mask
+        .get(number / 8)
+        .is_none_or(|byte| byte & (1 << (number % 8)) == 0)
+    {
+        return None;
+    }
+    let index = (0..number)
+        .filter(|bit| mask[bit / 8] & (1 << (bit % 8)) != 0)
+        .count();
+    Some(unsafe { *state.values.add(index) })
+}
+
+struct ScrollValuator {
+    number: c_int,
+    increment: f64,
+    last_value: Option<f64>,
+    remainder_px: f64,
+}
+
+impl ScrollValuator {
+    fn new(number: c_int, increment: f64) -> Self {
+        Self {
+            number,
+            increment,
+            last_value: None,
+            remainder_px: 0.0,
+        }
+    }
+
+    /// How far a move of the valuator to `value` scrolls. Without a previous value, this one only
+    /// sets the baseline, as the valuator is absolute.
+    fn scroll_delta(&mut self, value: f64) -> Option<ScrollDelta> {
+        let previous = self.last_value.replace(value)?;
+        let notches = (value - previous) / self.increment;
+        if notches == 0.0 {
+            return None;
+        }
+        if notches.fract() == 0.0 {
+            self.remainder_px = 0.0;
+            return Some(ScrollDelta::Notches(
+                (notches as i32).saturating_mul(super::WHEEL_SCROLL_STEP_PX),
+            ));
+        }
+        let px = notches * f64::from(super::WHEEL_SCROLL_STEP_PX) + self.remainder_px;
+        let whole_px = px.trunc();
+        self.remainder_px = px - whole_px;
+        (whole_px != 0.0).then_some(ScrollDelta::Precise(whole_px as i32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScrollDelta, ScrollValuator};
+
+    #[test]
+    fn scroll_valuator_tells_wheel_notches_from_touchpad_motion() {
+        let mut valuator = ScrollValuator::new(3, 15.0);
+        assert_eq!(valuator.scroll_delta(300.0), None);
+        assert_eq!(valuator.scroll_delta(330.0), Some(ScrollDelta::Notches(96)));
+        assert_eq!(
+            valuator.scroll_delta(315.0),
+            Some(ScrollDelta::Notches(-48))
+        );
+
+        // A quarter notch is 12px, and fractions of a pixel carry over to the next motion.
+        assert_eq!(
+            valuator.scroll_delta(318.75),
+            Some(ScrollDelta::Precise(12))
+        );
+        assert_eq!(valuator.scroll_delta(318.85), None);
+        assert_eq!(valuator.scroll_delta(319.25), Some(ScrollDelta::Precise(1)));
+    }
+}
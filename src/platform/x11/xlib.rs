@@ -1,4 +1,4 @@
-use core::ffi::{c_char, c_int, c_long, c_uchar, c_uint, c_ulong, c_void};
+use core::ffi::{c_char, c_int, c_long, c_short, c_uchar, c_uint, c_ulong, c_ushort, c_void};
 
 pub type Atom = c_ulong;
 pub type Bool = c_int;
@@ -11,7 +11,23 @@ pub type Pixmap = c_ulong;
 pub type Window = c_ulong;
 
 pub const KEYSYM_BACKSPACE: KeySym = 0xff08;
+pub const KEYSYM_TAB: KeySym = 0xff09;
+pub const KEYSYM_RETURN: KeySym = 0xff0d;
 pub const KEYSYM_ESCAPE: KeySym = 0xff1b;
+pub const KEYSYM_HOME: KeySym = 0xff50;
+pub const KEYSYM_LEFT: KeySym = 0xff51;
+pub const KEYSYM_RIGHT: KeySym = 0xff53;
+pub const KEYSYM_END: KeySym = 0xff57;
+pub const KEYSYM_F12: KeySym = 0xffc9;
+pub const KEYSYM_KP_ENTER: KeySym = 0xff8d;
+pub const KEYSYM_DELETE: KeySym = 0xffff;
+pub const KEYSYM_ISO_LEFT_TAB: KeySym = 0xfe20;
+
+pub const SHIFT_MASK: c_uint = 1 << 0;
+pub const CONTROL_MASK: c_uint = 1 << 2;
+pub const MOD1_MASK: c_uint = 1 << 3;
+
+pub const CLIP_ORDERING_UNSORTED: c_int = 0;
 
 #[repr(C)]
 pub struct Visual {
@@ -28,17 +44,31 @@ pub struct Visual {
 pub const ALL_PLANES: c_ulong = !0;
 pub const EVENT_TYPE_KEY_PRESS: c_int = 2;
 pub const EVENT_TYPE_BUTTON_PRESS: c_int = 4;
+pub const EVENT_TYPE_BUTTON_RELEASE: c_int = 5;
+pub const EVENT_TYPE_MOTION_NOTIFY: c_int = 6;
 pub const EVENT_TYPE_EXPOSE: c_int = 12;
 pub const EVENT_TYPE_CONFIGURE_NOTIFY: c_int = 22;
+pub const EVENT_TYPE_SELECTION_NOTIFY: c_int = 31;
 pub const EVENT_TYPE_CLIENT_MESSAGE: c_int = 33;
 
 pub const EVENT_MASK_KEY_PRESS: c_long = 1 << 0;
 pub const EVENT_MASK_BUTTON_PRESS: c_long = 1 << 2;
+pub const EVENT_MASK_BUTTON_RELEASE: c_long = 1 << 3;
+pub const EVENT_MASK_POINTER_MOTION: c_long = 1 << 6;
 pub const EVENT_MASK_EXPOSURE: c_long = 1 << 15;
 pub const EVENT_MASK_STRUCTURE_NOTIFY: c_long = 1 << 17;
 
 pub const IMAGE_FORMAT_Z_PIXMAP: c_int = 2;
 
+pub const XA_PRIMARY: Atom = 1;
+pub const XA_ATOM: Atom = 4;
+pub const XA_CARDINAL: Atom = 6;
+pub const CURRENT_TIME: c_ulong = 0;
+pub const PROP_MODE_REPLACE: c_int = 0;
+pub const LSB_FIRST: c_int = 0;
+
+pub type XErrorHandler = Option<unsafe extern "C" fn(*mut Display, *mut c_void) -> c_int>;
+
 #[repr(C)]
 pub struct XExposeEvent {
     pub type_: c_int,
@@ -89,6 +119,25 @@ pub struct XButtonEvent {
     pub same_screen: Bool,
 }
 
+#[repr(C)]
+pub struct XMotionEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub root: Window,
+    pub subwindow: Window,
+    pub time: c_ulong,
+    pub x: c_int,
+    pub y: c_int,
+    pub x_root: c_int,
+    pub y_root: c_int,
+    pub state: c_uint,
+    pub is_hint: c_char,
+    pub same_screen: Bool,
+}
+
 #[repr(C)]
 pub struct XKeyEvent {
     pub type_: c_int,
@@ -125,6 +174,19 @@ pub struct XClientMessageEvent {
     pub data: XClientMessageData,
 }
 
+#[repr(C)]
+pub struct XSelectionEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+    pub time: c_ulong,
+}
+
 #[repr(C)]
 pub struct XImageFuncs {
     pub create_image: Option<
@@ -172,6 +234,26 @@ pub struct XImage {
 
 pub struct XImageHandle(pub *mut XImage);
 
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct XRectangle {
+    pub x: c_short,
+    pub y: c_short,
+    pub width: c_ushort,
+    pub height: c_ushort,
+}
+
+/// `rect` clamped to the range X11 rectangles can express.
+pub fn x_rectangle(rect: crate::geom::Rect) -> XRectangle {
+    let clamp = |value: i32| value.clamp(i32::from(c_short::MIN), i32::from(c_short::MAX));
+    XRectangle {
+        x: clamp(rect.x) as c_short,
+        y: clamp(rect.y) as c_short,
+        width: rect.width.clamp(0, i32::from(c_ushort::MAX)) as c_ushort,
+        height: rect.height.clamp(0, i32::from(c_ushort::MAX)) as c_ushort,
+    }
+}
+
 impl Drop for XImageHandle {
     fn drop(&mut self) {
         let image = self.0;
@@ -259,6 +341,17 @@ unsafe extern "C" {
         dest_y: c_int,
     ) -> c_int;
 
+    pub fn XSetClipRectangles(
+        display: *mut Display,
+        gc: GC,
+        clip_x_origin: c_int,
+        clip_y_origin: c_int,
+        rectangles: *const XRectangle,
+        n: c_int,
+        ordering: c_int,
+    ) -> c_int;
+    pub fn XSetClipMask(display: *mut Display, gc: GC, pixmap: Pixmap) -> c_int;
+
     pub fn XFreePixmap(display: *mut Display, pixmap: Pixmap) -> c_int;
 
     pub fn XInternAtom(
@@ -273,7 +366,45 @@ unsafe extern "C" {
         count: c_int,
     ) -> c_int;
 
+    pub fn XChangeProperty(
+        display: *mut Display,
+        window: Window,
+        property: Atom,
+        type_: Atom,
+        format: c_int,
+        mode: c_int,
+        data: *const c_uchar,
+        nelements: c_int,
+    ) -> c_int;
+
     pub fn XGetSelectionOwner(display: *mut Display, selection: Atom) -> Window;
+    pub fn XConvertSelection(
+        display: *mut Display,
+        selection: Atom,
+        target: Atom,
+        property: Atom,
+        requestor: Window,
+        time: c_ulong,
+    ) -> c_int;
+
+    pub fn XTranslateCoordinates(
+        display: *mut Display,
+        src_w: Window,
+        dest_w: Window,
+        src_x: c_int,
+        src_y: c_int,
+        dest_x_return: *mut c_int,
+        dest_y_return: *mut c_int,
+        child_return: *mut Window,
+    ) -> Bool;
+
+    pub fn XSendEvent(
+        display: *mut Display,
+        window: Window,
+        propagate: Bool,
+        event_mask: c_long,
+        event_send: *mut XEvent,
+    ) -> c_int;
 
     pub fn XGetWindowProperty(
         display: *mut Display,
@@ -297,9 +428,17 @@ unsafe extern "C" {
     pub fn XPending(display: *mut Display) -> c_int;
     pub fn XNextEvent(display: *mut Display, event_return: *mut XEvent) -> c_int;
     pub fn XLookupKeysym(key_event: *mut XKeyEvent, index: c_int) -> KeySym;
+    pub fn XLookupString(
+        key_event: *mut XKeyEvent,
+        buffer_return: *mut c_char,
+        bytes_buffer: c_int,
+        keysym_return: *mut KeySym,
+        status_in_out: *mut c_void,
+    ) -> c_int;
     pub fn XDestroyWindow(display: *mut Display, window: Window) -> c_int;
     pub fn XFlush(display: *mut Display) -> c_int;
     pub fn XSync(display: *mut Display, discard: Bool) -> c_int;
+    pub fn XSetErrorHandler(handler: XErrorHandler) -> XErrorHandler;
 
     pub fn XGetImage(
         display: *mut Display,
@@ -0,0 +1,313 @@
+use crate::geom::Rect;
+use crate::image::Argb32Image;
+use core::ffi::{c_char, c_int, c_uint, c_ulong, c_ushort, c_void};
+
+use super::xlib::{self, Display, Drawable, Visual, XRectangle};
+use super::xshm::{self, SharedSegment};
+
+pub type Picture = c_ulong;
+type XFixed = c_int;
+type XRenderPictFormat = c_void;
+
+const PICT_OP_OVER: c_int = 3;
+const PICT_STANDARD_ARGB32: c_int = 0;
+const ARGB32_DEPTH: c_uint = 32;
+const FIXED_ONE: f64 = 65536.0;
+/// Segments grow in steps of this, so a page of similar images doesn't reattach for each one.
+const SEGMENT_GRANULE_BYTES: usize = 1 << 20;
+
+#[repr(C)]
+struct XTransform {
+    matrix: [[XFixed; 3]; 3],
+}
+
+#[repr(C)]
+struct XRenderColor {
+    red: c_ushort,
+    green: c_ushort,
+    blue: c_ushort,
+    alpha: c_ushort,
+}
+
+#[link(name = "Xrender")]
+unsafe extern "C" {
+    fn XRenderQueryExtension(
+        display: *mut Display,
+        event_base: *mut c_int,
+        error_base: *mut c_int,
+    ) -> xlib::Bool;
+    fn XRenderFindVisualFormat(
+        display: *mut Display,
+        visual: *const Visual,
+    ) -> *mut XRenderPictFormat;
+    fn XRenderFindStandardFormat(display: *mut Display, format: c_int) -> *mut XRenderPictFormat;
+    fn XRenderCreatePicture(
+        display: *mut Display,
+        drawable: Drawable,
+        format: *const XRenderPictFormat,
+        value_mask: c_ulong,
+        attributes: *const c_void,
+    ) -> Picture;
+    fn XRenderFreePicture(display: *mut Display, picture: Picture);
+    fn XRenderCreateSolidFill(display: *mut Display, color: *const XRenderColor) -> Picture;
+    fn XRenderSetPictureTransform(
+        display: *mut Display,
+        picture: Picture,
+        transform: *mut XTransform,
+    );
+    fn XRenderSetPictureFilter(
+        display: *mut Display,
+        picture: Picture,
+        filter: *const c_char,
+        params: *mut XFixed,
+        param_count: c_int,
+    );
+    fn XRenderSetPictureClipRectangles(
+        display: *mut Display,
+        picture: Picture,
+        x_origin: c_int,
+        y_origin: c_int,
+        rectangles: *const XRectangle,
+        count: c_int,
+    );
+    fn XRenderComposite(
+        display: *mut Display,
+        op: c_int,
+        source: Picture,
+        mask: Picture,
+        destination: Picture,
+        source_x: c_int,
+        source_y: c_int,
+        mask_x: c_int,
+        mask_y: c_int,
+        destination_x: c_int,
+        destination_y: c_int,
+        width: c_uint,
+        height: c_uint,
+    );
+}
+
+/// Composites images onto the back buffer server-side, handing their pixels over in a
+/// MIT-SHM pixmap rather than streaming them through the connection.
+pub struct ImageCompositor {
+    display: *mut Display,
+    argb32: *mut XRenderPictFormat,
+    destination_format: *mut XRenderPictFormat,
+    destination: Picture,
+    clip: Option<Rect>,
+    segment: Option<SharedSegment>,
+    /// Whether a composite queued since the last sync may still read the segment.
+    segment_in_use: bool,
+}
+
+impl ImageCompositor {
+    /// `None` when the server lacks XRender or shared pixmaps, or can't attach a segment (as
+    /// over the network); images are then drawn through cairo.
+    pub fn new(display: *mut Display, visual: *mut Visual, back_buffer: Drawable) -> Option<Self> {
+        let (mut event_base, mut error_base) = (0, 0);
+        if unsafe { XRenderQueryExtension(display, &mut event_base, &mut error_base) } == 0
+            || !xshm::query_support(display).pixmaps
+        {
+            return None;
+        }
+        let argb32 = unsafe { XRenderFindStandardFormat(display, PICT_STANDARD_ARGB32) };
+        let destination_format = unsafe { XRenderFindVisualFormat(display, visual) };
+        if argb32.is_null() || destination_format.is_null() {
+            return None;
+        }
+        let segment = SharedSegment::new(display, SEGMENT_GRANULE_BYTES).ok()?;
+        let mut compositor = Self {
+            display,
+            argb32,
+            destination_format,
+            destination: 0,
+            clip: None,
+            segment: Some(segment),
+            segment_in_use: false,
+        };
+        compositor.recreate(back_buffer).ok()?;
+        Some(compositor)
+    }
+
+    pub fn recreate(&mut self, back_buffer: Drawable) -> Result<(), String> {
+        self.free_destination();
+        let destination = unsafe {
+            XRenderCreatePicture(
+                self.display,
+                back_buffer,
+                self.destination_format,
+                0,
+                std::ptr::null(),
+            )
+        };
+        if destination == 0 {
+            return Err("XRenderCreatePicture failed for the back buffer".to_owned());
+        }
+        self.destination = destination;
+        self.set_clip(self.clip);
+        Ok(())
+    }
+
+    pub fn set_clip(&mut self, clip: Option<Rect>) {
+        self.clip = clip;
+        if self.destination == 0 {
+            return;
+        }
+        let rectangle = xlib::x_rectangle(clip.unwrap_or(Rect {
+            x: 0,
+            y: 0,
+            width: i32::MAX,
+            height: i32::MAX,
+        }));
+        unsafe {
+            XRenderSetPictureClipRectangles(self.display, self.destination, 0, 0, &rectangle, 1);
+        }
+    }
+
+    /// Draws `image` scaled to fill `destination`, in back buffer pixels.
+    pub fn draw_image(
+        &mut self,
+        back_buffer: Drawable,
+        destination: Rect,
+        image: &Argb32Image,
+        opacity: u8,
+    ) -> Result<(), String> {
+        let Rect {
+            x: x_px,
+            y: y_px,
+            width: width_px,
+            height: height_px,
+        } = destination;
+        let segment = self.segment_for(image.data.len())?;
+        segment.as_mut_slice()[..image.data.len()].copy_from_slice(&image.data);
+        let pixmap = segment.create_pixmap(back_buffer, image.width, image.height, ARGB32_DEPTH)?;
+
+        let source =
+            unsafe { XRenderCreatePicture(self.display, pixmap, self.argb32, 0, std::ptr::null()) };
+        if source == 0 {
+            unsafe {
+                xlib::XFreePixmap(self.display, pixmap);
+            }
+            return Err("XRenderCreatePicture failed for an image".to_owned());
+        }
+        if i64::from(image.width) != i64::from(width_px)
+            || i64::from(image.height) != i64::from(height_px)
+        {
+            let mut transform = scale_transform(
+                f64::from(image.width) / f64::from(width_px),
+                f64::from(image.height) / f64::from(height_px),
+            );
+            unsafe {
+                XRenderSetPictureTransform(self.display, source, &mut transform);
+                XRenderSetPictureFilter(
+                    self.display,
+                    source,
+                    c"good".as_ptr(),
+                    std::ptr::null_mut(),
+                    0,
+                );
+            }
+        }
+        let mask = if opacity < 255 {
+            let alpha = c_ushort::from(opacity) * 257;
+            let color = XRenderColor {
+                red: 0,
+                green: 0,
+                blue: 0,
+                alpha,
+            };
+            unsafe { XRenderCreateSolidFill(self.display, &color) }
+        } else {
+            0
+        };
+
+        unsafe {
+            XRenderComposite(
+                self.display,
+                PICT_OP_OVER,
+                source,
+                mask,
+                self.destination,
+                0,
+                0,
+                0,
+                0,
+                x_px,
+                y_px,
+                width_px as c_uint,
+                height_px as c_uint,
+            );
+            if mask != 0 {
+                XRenderFreePicture(self.display, mask);
+            }
+            XRenderFreePicture(self.display, source);
+            xlib::XFreePixmap(self.display, pixmap);
+        }
+        self.segment_in_use = true;
+        Ok(())
+    }
+
+    /// A segment of at least `len` bytes that the server is done reading.
+    fn segment_for(&mut self, len: usize) -> Result<&mut SharedSegment, String> {
+        if self.segment_in_use {
+            unsafe {
+                xlib::XSync(self.display, 0);
+            }
+            self.segment_in_use = false;
+        }
+        if self
+            .segment
+            .as_ref()
+            .is_none_or(|segment| segment.len() < len)
+        {
+            self.segment = None;
+            let len = len.div_ceil(SEGMENT_GRANULE_BYTES) * SEGMENT_GRANULE_BYTES;
+            self.segment = Some(SharedSegment::new(self.display, len)?);
+        }
+        self.segment
+            .as_mut()
+            .ok_or_else(|| "Internal error: shared-memory segment missing".to_owned())
+    }
+
+    fn free_destination(&mut self) {
+        if self.destination != 0 {
+            unsafe {
+                XRenderFreePicture(self.display, self.destination);
+            }
+            self.destination = 0;
+        }
+    }
+}
+
+impl Drop for ImageCompositor {
+    fn drop(&mut self) {
+        self.free_destination();
+    }
+}
+
+/// Maps destination pixels to source pixels, `x_ratio` and `y_ratio` source pixels apart.
+fn scale_transform(x_ratio: f64, y_ratio: f64) -> XTransform {
+    let fixed = |value: f64| (value * FIXED_ONE).round() as XFixed;
+    XTransform {
+        matrix: [
+            [fixed(x_ratio), 0, 0],
+            [0, fixed(y_ratio), 0],
+            [0, 0, fixed(1.0)],
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scale_transform;
+
+    #[test]
+    fn scale_transform_maps_destination_pixels_to_source_pixels() {
+        // A 200px-wide image drawn 100px wide, and a 50px-tall one drawn 100px tall.
+        let transform = scale_transform(200.0 / 100.0, 50.0 / 100.0);
+        assert_eq!(
+            transform.matrix,
+            [[2 << 16, 0, 0], [0, 1 << 15, 0], [0, 0, 1 << 16]]
+        );
+    }
+}
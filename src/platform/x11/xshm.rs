@@ -0,0 +1,272 @@
+use core::ffi::{c_char, c_int, c_uint, c_ulong, c_void};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::xlib::{
+    self, Bool, Display, Drawable, IMAGE_FORMAT_Z_PIXMAP, Pixmap, Visual, XImage, XImageHandle,
+};
+
+const IPC_PRIVATE: c_int = 0;
+const IPC_CREAT: c_int = 0o1000;
+const IPC_RMID: c_int = 0;
+const SHM_MODE: c_int = 0o600;
+
+#[repr(C)]
+pub struct XShmSegmentInfo {
+    shmseg: c_ulong,
+    shmid: c_int,
+    shmaddr: *mut c_char,
+    read_only: Bool,
+}
+
+#[link(name = "Xext")]
+unsafe extern "C" {
+    fn XShmQueryVersion(
+        display: *mut Display,
+        major: *mut c_int,
+        minor: *mut c_int,
+        shared_pixmaps: *mut Bool,
+    ) -> Bool;
+    fn XShmPixmapFormat(display: *mut Display) -> c_int;
+    fn XShmAttach(display: *mut Display, info: *mut XShmSegmentInfo) -> Bool;
+    fn XShmDetach(display: *mut Display, info: *mut XShmSegmentInfo) -> Bool;
+    fn XShmCreatePixmap(
+        display: *mut Display,
+        drawable: Drawable,
+        data: *mut c_char,
+        info: *mut XShmSegmentInfo,
+        width: c_uint,
+        height: c_uint,
+        depth: c_uint,
+    ) -> Pixmap;
+    fn XShmCreateImage(
+        display: *mut Display,
+        visual: *mut Visual,
+        depth: c_uint,
+        format: c_int,
+        data: *mut c_char,
+        info: *mut XShmSegmentInfo,
+        width: c_uint,
+        height: c_uint,
+    ) -> *mut XImage;
+    fn XShmGetImage(
+        display: *mut Display,
+        drawable: Drawable,
+        image: *mut XImage,
+        x: c_int,
+        y: c_int,
+        plane_mask: c_ulong,
+    ) -> Bool;
+}
+
+unsafe extern "C" {
+    fn shmget(key: c_int, size: usize, flags: c_int) -> c_int;
+    fn shmat(shmid: c_int, address: *const c_void, flags: c_int) -> *mut c_void;
+    fn shmdt(address: *const c_void) -> c_int;
+    fn shmctl(shmid: c_int, command: c_int, buffer: *mut c_void) -> c_int;
+}
+
+/// Which parts of MIT-SHM the server offers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShmSupport {
+    pub images: bool,
+    /// Pixmaps backed by a segment, which XRender can composite from directly.
+    pub pixmaps: bool,
+}
+
+pub fn query_support(display: *mut Display) -> ShmSupport {
+    let (mut major, mut minor, mut shared_pixmaps) = (0, 0, 0);
+    let images =
+        unsafe { XShmQueryVersion(display, &mut major, &mut minor, &mut shared_pixmaps) } != 0;
+    let pixmaps = images
+        && shared_pixmaps != 0
+        && unsafe { XShmPixmapFormat(display) } == IMAGE_FORMAT_Z_PIXMAP;
+    ShmSupport { images, pixmaps }
+}
+
+/// A System V shared-memory segment attached to both this process and the X server.
+pub struct SharedSegment {
+    display: *mut Display,
+    info: Box<XShmSegmentInfo>,
+    len: usize,
+}
+
+impl SharedSegment {
+    /// Fails when the server can't attach the segment, as when it runs on another machine.
+    pub fn new(display: *mut Display, len: usize) -> Result<Self, String> {
+        if len == 0 {
+            return Err("Shared-memory segment size must be > 0".to_owned());
+        }
+        let shmid = unsafe { shmget(IPC_PRIVATE, len, IPC_CREAT | SHM_MODE) };
+        if shmid < 0 {
+            return Err(format!(
+                "shmget failed: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        let address = unsafe { shmat(shmid, std::ptr::null(), 0) };
+        if address as isize == -1 {
+            let err = std::io::Error::last_os_error();
+            unsafe {
+                shmctl(shmid, IPC_RMID, std::ptr::null_mut());
+            }
+            return Err(format!("shmat failed: {err}"));
+        }
+
+        let mut info = Box::new(XShmSegmentInfo {
+            shmseg: 0,
+            shmid,
+            shmaddr: address.cast::<c_char>(),
+            read_only: 0,
+        });
+        let attached = unsafe { attach(display, &mut info) };
+        // The segment goes away once both sides have detached.
+        unsafe {
+            shmctl(shmid, IPC_RMID, std::ptr::null_mut());
+        }
+        if !attached {
+            unsafe {
+                shmdt(address);
+            }
+            return Err("XShmAttach failed".to_owned());
+        }
+        Ok(Self { display, info, len })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.info.shmaddr.cast::<u8>(), self.len) }
+    }
+
+    /// A `depth` pixmap whose pixels are the start of this segment, row after row.
+    pub fn create_pixmap(
+        &mut self,
+        drawable: Drawable,
+        width: c_uint,
+        height: c_uint,
+        depth: c_uint,
+    ) -> Result<Pixmap, String> {
+        let pixmap = unsafe {
+            XShmCreatePixmap(
+                self.display,
+                drawable,
+                self.info.shmaddr,
+                &mut *self.info,
+                width,
+                height,
+                depth,
+            )
+        };
+        if pixmap == 0 {
+            return Err("XShmCreatePixmap failed".to_owned());
+        }
+        Ok(pixmap)
+    }
+}
+
+impl Drop for SharedSegment {
+    fn drop(&mut self) {
+        unsafe {
+            XShmDetach(self.display, &mut *self.info);
+            xlib::XSync(self.display, 0);
+            shmdt(self.info.shmaddr.cast::<c_void>());
+        }
+    }
+}
+
+/// A Z-pixmap image read into a segment of its own.
+pub struct ShmImage {
+    image: XImageHandle,
+    _segment: SharedSegment,
+}
+
+impl ShmImage {
+    pub fn get(
+        display: *mut Display,
+        drawable: Drawable,
+        visual: *mut Visual,
+        depth: c_uint,
+        width: c_uint,
+        height: c_uint,
+    ) -> Result<Self, String> {
+        let mut info = XShmSegmentInfo {
+            shmseg: 0,
+            shmid: -1,
+            shmaddr: std::ptr::null_mut(),
+            read_only: 0,
+        };
+        let image = unsafe {
+            XShmCreateImage(
+                display,
+                visual,
+                depth,
+                IMAGE_FORMAT_Z_PIXMAP,
+                std::ptr::null_mut(),
+                &mut info,
+                width,
+                height,
+            )
+        };
+        if image.is_null() {
+            return Err("XShmCreateImage returned null".to_owned());
+        }
+        // `obdata` points at the stack `info` until the segment replaces it.
+        unsafe {
+            (*image).obdata = std::ptr::null_mut();
+        }
+        let image = XImageHandle(image);
+        let len = unsafe { (*image.0).bytes_per_line as usize * (*image.0).height as usize };
+        let mut segment = SharedSegment::new(display, len)?;
+        // XShmGetImage finds the segment through `obdata`.
+        unsafe {
+            (*image.0).data = segment.info.shmaddr;
+            (*image.0).obdata = (&mut *segment.info as *mut XShmSegmentInfo).cast::<c_void>();
+        }
+        let image = ShmImage {
+            image,
+            _segment: segment,
+        };
+        let ok = unsafe { XShmGetImage(display, drawable, image.image.0, 0, 0, xlib::ALL_PLANES) };
+        if ok == 0 {
+            return Err("XShmGetImage failed".to_owned());
+        }
+        Ok(image)
+    }
+
+    pub fn as_ptr(&self) -> *mut XImage {
+        self.image.0
+    }
+}
+
+impl Drop for ShmImage {
+    fn drop(&mut self) {
+        // The pixels and segment info belong to the segment, not to Xlib.
+        unsafe {
+            (*self.image.0).data = std::ptr::null_mut();
+            (*self.image.0).obdata = std::ptr::null_mut();
+        }
+    }
+}
+
+static ATTACH_FAILED: AtomicBool = AtomicBool::new(false);
+
+unsafe extern "C" fn record_attach_error(_display: *mut Display, _event: *mut c_void) -> c_int {
+    ATTACH_FAILED.store(true, Ordering::Relaxed);
+    0
+}
+
+/// Attaches the segment, trapping the error a server that can't reach it reports instead of
+/// letting Xlib's default handler exit.
+unsafe fn attach(display: *mut Display, info: &mut XShmSegmentInfo) -> bool {
+    unsafe {
+        xlib::XSync(display, 0);
+        ATTACH_FAILED.store(false, Ordering::Relaxed);
+        let previous = xlib::XSetErrorHandler(Some(record_attach_error));
+        let attached = XShmAttach(display, info) != 0;
+        xlib::XSync(display, 0);
+        xlib::XSetErrorHandler(previous);
+        attached && !ATTACH_FAILED.load(Ordering::Relaxed)
+    }
+}
@@ -1,69 +1,399 @@
-use crate::image::RgbImage;
+use crate::image::{Argb32Image, RgbImage};
+use std::collections::HashMap;
 use std::io::{BufWriter, Write};
 
 const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
 const COLOR_TYPE_TRUECOLOR: u8 = 2;
+const COLOR_TYPE_INDEXED: u8 = 3;
+const COLOR_TYPE_TRUECOLOR_ALPHA: u8 = 6;
 const BIT_DEPTH_8: u8 = 8;
 const FILTER_NONE: u8 = 0;
+const FILTER_SUB: u8 = 1;
+const FILTER_UP: u8 = 2;
+const FILTER_AVERAGE: u8 = 3;
+const FILTER_PAETH: u8 = 4;
 const COMPRESSION_METHOD_DEFLATE: u8 = 0;
 const FILTER_METHOD_ADAPTIVE: u8 = 0;
 const INTERLACE_NONE: u8 = 0;
+const MAX_PALETTE_LEN: usize = 256;
+const MAX_COMPRESSION_LEVEL: u8 = 9;
+const WINDOW_SIZE: usize = 32 * 1024;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const HASH_BITS: u32 = 15;
+const END_OF_BLOCK: u16 = 256;
+/// Per level: how many earlier positions are tried, and the match length that ends the search.
+const MATCH_SEARCH: [(usize, usize); 10] = [
+    (0, 0),
+    (4, 8),
+    (8, 16),
+    (16, 32),
+    (32, 64),
+    (64, 128),
+    (128, 128),
+    (256, MAX_MATCH),
+    (1024, MAX_MATCH),
+    (4096, MAX_MATCH),
+];
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA_BITS: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA_BITS: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// How a PNG is encoded. The default stores the scanlines uncompressed, which is the fastest and
+/// what `render-test` reads back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PngOptions {
+    /// zlib-style level: 0 stores the scanlines as they are; 1 through 9 filter each row and
+    /// compress it, searching harder for repeats at higher levels. Levels above 9 act as 9.
+    pub compression_level: u8,
+    /// Writes an indexed image when there are at most 256 distinct colors, which keeps
+    /// screenshots of flat pages small. Images with more colors are written as truecolor.
+    pub palette: bool,
+}
 
 pub fn write_rgb_png(path: &std::path::Path, image: &RgbImage) -> Result<(), String> {
+    write_rgb_png_with(path, image, PngOptions::default())
+}
+
+pub fn write_rgb_png_to(writer: &mut impl Write, image: &RgbImage) -> Result<(), String> {
+    write_rgb_png_to_with(writer, image, PngOptions::default())
+}
+
+pub fn write_rgb_png_with(
+    path: &std::path::Path,
+    image: &RgbImage,
+    options: PngOptions,
+) -> Result<(), String> {
+    write_file(path, |writer| write_rgb_png_to_with(writer, image, options))
+}
+
+pub fn write_rgb_png_to_with(
+    writer: &mut impl Write,
+    image: &RgbImage,
+    options: PngOptions,
+) -> Result<(), String> {
+    let scanlines = Scanlines::new(image.width, image.height, 3, &image.data, options.palette)?;
+    write_png(writer, &scanlines, options)
+}
+
+/// Writes a premultiplied image with its alpha channel, so transparent areas stay transparent.
+pub fn write_rgba_png(
+    path: &std::path::Path,
+    image: &Argb32Image,
+    options: PngOptions,
+) -> Result<(), String> {
+    write_file(path, |writer| write_rgba_png_to(writer, image, options))
+}
+
+pub fn write_rgba_png_to(
+    writer: &mut impl Write,
+    image: &Argb32Image,
+    options: PngOptions,
+) -> Result<(), String> {
+    let rgba = straight_rgba(image);
+    let scanlines = Scanlines::new(image.width, image.height, 4, &rgba, options.palette)?;
+    write_png(writer, &scanlines, options)
+}
+
+fn write_file(
+    path: &std::path::Path,
+    write: impl FnOnce(&mut BufWriter<std::fs::File>) -> Result<(), String>,
+) -> Result<(), String> {
     let file = std::fs::File::create(path)
         .map_err(|err| format!("Failed to create {}: {err}", path.display()))?;
     let mut writer = BufWriter::new(file);
+    write(&mut writer)?;
+    writer
+        .flush()
+        .map_err(|err| format!("Failed to flush {}: {err}", path.display()))?;
 
+    Ok(())
+}
+
+fn write_png(
+    writer: &mut impl Write,
+    scanlines: &Scanlines,
+    options: PngOptions,
+) -> Result<(), String> {
     writer
         .write_all(&PNG_SIGNATURE)
         .map_err(|err| format!("Failed to write PNG signature: {err}"))?;
 
     let mut ihdr = Vec::with_capacity(13);
-    ihdr.extend_from_slice(&image.width.to_be_bytes());
-    ihdr.extend_from_slice(&image.height.to_be_bytes());
-    ihdr.push(BIT_DEPTH_8);
-    ihdr.push(COLOR_TYPE_TRUECOLOR);
+    ihdr.extend_from_slice(&scanlines.width.to_be_bytes());
+    ihdr.extend_from_slice(&scanlines.height.to_be_bytes());
+    ihdr.push(scanlines.bit_depth);
+    ihdr.push(scanlines.color_type);
     ihdr.push(COMPRESSION_METHOD_DEFLATE);
     ihdr.push(FILTER_METHOD_ADAPTIVE);
     ihdr.push(INTERLACE_NONE);
-    write_chunk(&mut writer, *b"IHDR", &ihdr)?;
+    write_chunk(writer, *b"IHDR", &ihdr)?;
 
-    let scanlines = build_scanlines(image)?;
-    let compressed = zlib_compress_stored(&scanlines)?;
-    write_chunk(&mut writer, *b"IDAT", &compressed)?;
-    write_chunk(&mut writer, *b"IEND", &[])?;
+    if !scanlines.palette.is_empty() {
+        let plte: Vec<u8> = scanlines
+            .palette
+            .iter()
+            .flat_map(|&[r, g, b, _]| [r, g, b])
+            .collect();
+        write_chunk(writer, *b"PLTE", &plte)?;
+        // Entries past the last translucent one are opaque and may be left out.
+        let translucent = scanlines
+            .palette
+            .iter()
+            .rposition(|color| color[3] != 255)
+            .map_or(0, |index| index + 1);
+        if translucent > 0 {
+            let trns: Vec<u8> = scanlines.palette[..translucent]
+                .iter()
+                .map(|color| color[3])
+                .collect();
+            write_chunk(writer, *b"tRNS", &trns)?;
+        }
+    }
 
-    writer
-        .flush()
-        .map_err(|err| format!("Failed to flush {}: {err}", path.display()))?;
+    let level = options.compression_level.min(MAX_COMPRESSION_LEVEL);
+    // Filters rarely help indexed rows, so those are only compressed.
+    let adaptive = level > 0 && scanlines.color_type != COLOR_TYPE_INDEXED;
+    let filtered = scanlines.filter(adaptive);
+    let compressed = if level == 0 {
+        zlib_compress_stored(&filtered)?
+    } else {
+        zlib_compress(&filtered, level)
+    };
+    write_chunk(writer, *b"IDAT", &compressed)?;
+    write_chunk(writer, *b"IEND", &[])?;
 
     Ok(())
 }
 
-fn build_scanlines(image: &RgbImage) -> Result<Vec<u8>, String> {
-    let row_stride = image.row_stride_bytes();
-    let total_len = image
-        .height
-        .checked_mul(row_stride as u32 + 1)
-        .ok_or_else(|| "Scanline buffer size overflow".to_owned())? as usize;
-
-    let mut out = Vec::with_capacity(total_len);
-    for row in 0..image.height as usize {
-        out.push(FILTER_NONE);
-        let start = row
-            .checked_mul(row_stride)
-            .ok_or_else(|| "Scanline offset overflow".to_owned())?;
-        let end = start
-            .checked_add(row_stride)
-            .ok_or_else(|| "Scanline offset overflow".to_owned())?;
-        out.extend_from_slice(
-            image
-                .data
-                .get(start..end)
-                .ok_or_else(|| "Scanline slice out of bounds".to_owned())?,
-        );
+fn straight_rgba(image: &Argb32Image) -> Vec<u8> {
+    let mut rgba = Vec::with_capacity(image.data.len());
+    for bgra in image.data.chunks_exact(4) {
+        let a = bgra[3];
+        let unpremultiply = |channel: u8| {
+            if a == 0 {
+                0
+            } else {
+                ((u32::from(channel) * 255 + u32::from(a) / 2) / u32::from(a)).min(255) as u8
+            }
+        };
+        rgba.extend_from_slice(&[
+            unpremultiply(bgra[2]),
+            unpremultiply(bgra[1]),
+            unpremultiply(bgra[0]),
+            a,
+        ]);
+    }
+    rgba
+}
+
+/// Unfiltered image rows in the layout the IHDR describes.
+struct Scanlines {
+    width: u32,
+    height: u32,
+    bit_depth: u8,
+    color_type: u8,
+    /// Bytes per pixel, at least one; the distance the Sub, Average and Paeth filters look back.
+    pixel_bytes: usize,
+    row_bytes: usize,
+    data: Vec<u8>,
+    /// RGBA entries of an indexed image; empty for truecolor.
+    palette: Vec<[u8; 4]>,
+}
+
+impl Scanlines {
+    /// `pixels` holds `channels` (3 or 4) bytes of straight RGB(A) per pixel.
+    fn new(
+        width: u32,
+        height: u32,
+        channels: usize,
+        pixels: &[u8],
+        palette: bool,
+    ) -> Result<Self, String> {
+        let row_bytes = (width as usize)
+            .checked_mul(channels)
+            .ok_or_else(|| "Scanline size overflow".to_owned())?;
+        let total_len = row_bytes
+            .checked_mul(height as usize)
+            .ok_or_else(|| "Scanline buffer size overflow".to_owned())?;
+        if pixels.len() != total_len {
+            return Err(format!(
+                "Invalid PNG pixel buffer length: expected {total_len} bytes, got {}",
+                pixels.len()
+            ));
+        }
+
+        if palette && let Some((colors, indices)) = index_colors(pixels, channels) {
+            let bit_depth: u8 = match colors.len() {
+                0..=2 => 1,
+                3..=4 => 2,
+                5..=16 => 4,
+                _ => 8,
+            };
+            let per_byte = usize::from(8 / bit_depth);
+            let row_bytes = (width as usize).div_ceil(per_byte);
+            let mut data = vec![0u8; row_bytes * height as usize];
+            if width > 0 {
+                for (row, indices) in indices.chunks_exact(width as usize).enumerate() {
+                    let packed = &mut data[row * row_bytes..(row + 1) * row_bytes];
+                    for (x, &index) in indices.iter().enumerate() {
+                        let shift = 8 - usize::from(bit_depth) * (x % per_byte + 1);
+                        packed[x / per_byte] |= index << shift;
+                    }
+                }
+            }
+            return Ok(Self {
+                width,
+                height,
+                bit_depth,
+                color_type: COLOR_TYPE_INDEXED,
+                pixel_bytes: 1,
+                row_bytes,
+                data,
+                palette: colors,
+            });
+        }
+
+        Ok(Self {
+            width,
+            height,
+            bit_depth: BIT_DEPTH_8,
+            color_type: if channels == 4 {
+                COLOR_TYPE_TRUECOLOR_ALPHA
+            } else {
+                COLOR_TYPE_TRUECOLOR
+            },
+            pixel_bytes: channels,
+            row_bytes,
+            data: pixels.to_vec(),
+            palette: Vec::new(),
+        })
+    }
+
+    /// Prefixes each row with its filter type. Adaptive filtering picks, per row, the filter whose
+    /// output has the smallest sum of absolute differences, which tends to compress best.
+    fn filter(&self, adaptive: bool) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.height as usize * (self.row_bytes + 1));
+        let mut previous = vec![0u8; self.row_bytes];
+        let mut candidate = vec![0u8; self.row_bytes];
+        let mut best = vec![0u8; self.row_bytes];
+        for row in 0..self.height as usize {
+            let current = &self.data[row * self.row_bytes..(row + 1) * self.row_bytes];
+            if !adaptive {
+                out.push(FILTER_NONE);
+                out.extend_from_slice(current);
+                continue;
+            }
+            let mut best_filter = FILTER_NONE;
+            let mut best_score = u64::MAX;
+            for filter in [
+                FILTER_NONE,
+                FILTER_SUB,
+                FILTER_UP,
+                FILTER_AVERAGE,
+                FILTER_PAETH,
+            ] {
+                apply_filter(filter, current, &previous, self.pixel_bytes, &mut candidate);
+                let score = candidate
+                    .iter()
+                    .map(|&byte| u64::from((byte as i8).unsigned_abs()))
+                    .sum();
+                if score < best_score {
+                    best_score = score;
+                    best_filter = filter;
+                    std::mem::swap(&mut best, &mut candidate);
+                }
+            }
+            out.push(best_filter);
+            out.extend_from_slice(&best);
+            previous.copy_from_slice(current);
+        }
+        out
+    }
+}
+
+/// Maps each pixel to a palette entry, or `None` when there are more than 256 colors.
+fn index_colors(pixels: &[u8], channels: usize) -> Option<(Vec<[u8; 4]>, Vec<u8>)> {
+    let mut palette: Vec<[u8; 4]> = Vec::new();
+    let mut lookup: HashMap<[u8; 4], u8> = HashMap::new();
+    let mut indices = Vec::with_capacity(pixels.len() / channels);
+    let mut last: Option<([u8; 4], u8)> = None;
+    for pixel in pixels.chunks_exact(channels) {
+        let color = [
+            pixel[0],
+            pixel[1],
+            pixel[2],
+            if channels == 4 { pixel[3] } else { 255 },
+        ];
+        let index = match last {
+            Some((last_color, index)) if last_color == color => index,
+            _ => match lookup.get(&color) {
+                Some(&index) => index,
+                None => {
+                    if palette.len() == MAX_PALETTE_LEN {
+                        return None;
+                    }
+                    let index = palette.len() as u8;
+                    palette.push(color);
+                    lookup.insert(color, index);
+                    index
+                }
+            },
+        };
+        last = Some((color, index));
+        indices.push(index);
+    }
+    Some((palette, indices))
+}
+
+fn apply_filter(filter: u8, row: &[u8], previous: &[u8], pixel_bytes: usize, out: &mut [u8]) {
+    for i in 0..row.len() {
+        let left = if i >= pixel_bytes {
+            row[i - pixel_bytes]
+        } else {
+            0
+        };
+        let up = previous[i];
+        let up_left = if i >= pixel_bytes {
+            previous[i - pixel_bytes]
+        } else {
+            0
+        };
+        let predicted = match filter {
+            FILTER_SUB => left,
+            FILTER_UP => up,
+            FILTER_AVERAGE => ((u16::from(left) + u16::from(up)) / 2) as u8,
+            FILTER_PAETH => paeth(left, up, up_left),
+            _ => 0,
+        };
+        out[i] = row[i].wrapping_sub(predicted);
+    }
+}
+
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = i16::from(left) + i16::from(up) - i16::from(up_left);
+    let distance_left = (estimate - i16::from(left)).abs();
+    let distance_up = (estimate - i16::from(up)).abs();
+    let distance_up_left = (estimate - i16::from(up_left)).abs();
+    if distance_left <= distance_up && distance_left <= distance_up_left {
+        left
+    } else if distance_up <= distance_up_left {
+        up
+    } else {
+        up_left
     }
-    Ok(out)
 }
 
 fn zlib_compress_stored(uncompressed: &[u8]) -> Result<Vec<u8>, String> {
@@ -101,6 +431,168 @@ fn zlib_compress_stored(uncompressed: &[u8]) -> Result<Vec<u8>, String> {
     Ok(out)
 }
 
+/// Compresses into a single DEFLATE block with the fixed Huffman codes, finding repeats with hash
+/// chains whose search depth grows with `level` (1 to 9).
+fn zlib_compress(uncompressed: &[u8], level: u8) -> Vec<u8> {
+    // The header's FLEVEL bits advertise roughly how hard the compressor tried.
+    let flags = match level {
+        1 => 0x01,
+        2..=5 => 0x5E,
+        6 => 0x9C,
+        _ => 0xDA,
+    };
+    let mut bits = BitWriter::default();
+    bits.write(1, 1);
+    bits.write(1, 2);
+    let (max_chain, nice_len) = MATCH_SEARCH[usize::from(level.min(MAX_COMPRESSION_LEVEL))];
+    let mut matcher = Matcher::new(uncompressed);
+    let mut pos = 0usize;
+    while pos < uncompressed.len() {
+        match matcher.longest_match(pos, max_chain, nice_len) {
+            Some((length, distance)) => {
+                write_match(&mut bits, length, distance);
+                for inserted in pos..pos + length {
+                    matcher.insert(inserted);
+                }
+                pos += length;
+            }
+            None => {
+                write_fixed_symbol(&mut bits, u16::from(uncompressed[pos]));
+                matcher.insert(pos);
+                pos += 1;
+            }
+        }
+    }
+    write_fixed_symbol(&mut bits, END_OF_BLOCK);
+
+    let mut out = vec![0x78, flags];
+    out.extend_from_slice(&bits.finish());
+    let mut adler = Adler32::new();
+    adler.update(uncompressed);
+    out.extend_from_slice(&adler.finish().to_be_bytes());
+    out
+}
+
+/// Hash chains over every position inserted so far, as in zlib: `head` holds the latest position
+/// for each hash of three bytes and `prev` links each position to the one before it.
+struct Matcher<'a> {
+    data: &'a [u8],
+    head: Vec<usize>,
+    prev: Vec<usize>,
+}
+
+impl<'a> Matcher<'a> {
+    const NONE: usize = usize::MAX;
+
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            head: vec![Self::NONE; 1 << HASH_BITS],
+            prev: vec![Self::NONE; WINDOW_SIZE],
+        }
+    }
+
+    fn hash(&self, pos: usize) -> Option<usize> {
+        let bytes = self.data.get(pos..pos + MIN_MATCH)?;
+        let key = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]);
+        Some((key.wrapping_mul(0x9E37_79B1) >> (32 - HASH_BITS)) as usize)
+    }
+
+    fn insert(&mut self, pos: usize) {
+        if let Some(hash) = self.hash(pos) {
+            self.prev[pos % WINDOW_SIZE] = self.head[hash];
+            self.head[hash] = pos;
+        }
+    }
+
+    /// The longest earlier repeat of the bytes at `pos`, as `(length, distance)`.
+    fn longest_match(
+        &self,
+        pos: usize,
+        max_chain: usize,
+        nice_len: usize,
+    ) -> Option<(usize, usize)> {
+        let hash = self.hash(pos)?;
+        let max_len = (self.data.len() - pos).min(MAX_MATCH);
+        let mut best: Option<(usize, usize)> = None;
+        let mut candidate = self.head[hash];
+        let mut chain = max_chain;
+        while candidate != Self::NONE && pos - candidate <= WINDOW_SIZE && chain > 0 {
+            let length = self.data[candidate..candidate + max_len]
+                .iter()
+                .zip(&self.data[pos..pos + max_len])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if length >= MIN_MATCH && best.is_none_or(|(best_len, _)| length > best_len) {
+                best = Some((length, pos - candidate));
+                if length >= nice_len.min(max_len) {
+                    break;
+                }
+            }
+            candidate = self.prev[candidate % WINDOW_SIZE];
+            chain -= 1;
+        }
+        best
+    }
+}
+
+/// Packs DEFLATE's bit stream, least significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    out: Vec<u8>,
+    pending: u64,
+    pending_len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, len: u32) {
+        self.pending |= u64::from(value) << self.pending_len;
+        self.pending_len += len;
+        while self.pending_len >= 8 {
+            self.out.push(self.pending as u8);
+            self.pending >>= 8;
+            self.pending_len -= 8;
+        }
+    }
+
+    /// Huffman codes go out most significant bit first.
+    fn write_code(&mut self, code: u32, len: u32) {
+        self.write(code.reverse_bits() >> (32 - len), len);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.pending_len > 0 {
+            self.out.push(self.pending as u8);
+        }
+        self.out
+    }
+}
+
+fn write_fixed_symbol(bits: &mut BitWriter, symbol: u16) {
+    let symbol = u32::from(symbol);
+    match symbol {
+        0..=143 => bits.write_code(0x30 + symbol, 8),
+        144..=255 => bits.write_code(0x190 + symbol - 144, 9),
+        256..=279 => bits.write_code(symbol - 256, 7),
+        _ => bits.write_code(0xC0 + symbol - 280, 8),
+    }
+}
+
+fn write_match(bits: &mut BitWriter, length: usize, distance: usize) {
+    let length_code = LENGTH_BASE.partition_point(|&base| usize::from(base) <= length) - 1;
+    write_fixed_symbol(bits, 257 + length_code as u16);
+    bits.write(
+        (length - usize::from(LENGTH_BASE[length_code])) as u32,
+        LENGTH_EXTRA_BITS[length_code],
+    );
+    let distance_code = DISTANCE_BASE.partition_point(|&base| usize::from(base) <= distance) - 1;
+    bits.write_code(distance_code as u32, 5);
+    bits.write(
+        (distance - usize::from(DISTANCE_BASE[distance_code])) as u32,
+        DISTANCE_EXTRA_BITS[distance_code],
+    );
+}
+
 fn write_chunk(writer: &mut impl Write, chunk_type: [u8; 4], data: &[u8]) -> Result<(), String> {
     let len_u32: u32 = data
         .len()
@@ -166,3 +658,268 @@ impl Adler32 {
         (self.s2 << 16) | self.s1
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Decoded {
+        ihdr: Vec<u8>,
+        plte: Option<Vec<u8>>,
+        trns: Option<Vec<u8>>,
+        /// Unfiltered rows, concatenated.
+        rows: Vec<u8>,
+    }
+
+    fn decode(png: &[u8]) -> Decoded {
+        assert_eq!(png[..8], PNG_SIGNATURE);
+        let mut chunks: HashMap<[u8; 4], Vec<u8>> = HashMap::new();
+        let mut offset = 8;
+        while offset < png.len() {
+            let len = u32::from_be_bytes(png[offset..offset + 4].try_into().unwrap()) as usize;
+            let kind: [u8; 4] = png[offset + 4..offset + 8].try_into().unwrap();
+            let data = &png[offset + 8..offset + 8 + len];
+            let crc =
+                u32::from_be_bytes(png[offset + 8 + len..offset + 12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(&png[offset + 4..offset + 8 + len]));
+            chunks.entry(kind).or_default().extend_from_slice(data);
+            offset += 12 + len;
+        }
+        let ihdr = chunks[b"IHDR"].clone();
+        let width = u32::from_be_bytes(ihdr[0..4].try_into().unwrap()) as usize;
+        let height = u32::from_be_bytes(ihdr[4..8].try_into().unwrap()) as usize;
+        let (bit_depth, color_type) = (usize::from(ihdr[8]), ihdr[9]);
+        let channels = match color_type {
+            COLOR_TYPE_TRUECOLOR => 3,
+            COLOR_TYPE_TRUECOLOR_ALPHA => 4,
+            _ => 1,
+        };
+        let pixel_bytes = (channels * bit_depth).div_ceil(8);
+        let row_bytes = (width * channels * bit_depth).div_ceil(8);
+
+        let filtered = inflate(&chunks[b"IDAT"]);
+        assert_eq!(filtered.len(), height * (row_bytes + 1));
+        let mut rows = Vec::new();
+        let mut previous = vec![0u8; row_bytes];
+        for line in filtered.chunks_exact(row_bytes + 1) {
+            let mut row = line[1..].to_vec();
+            for i in 0..row_bytes {
+                let left = if i >= pixel_bytes {
+                    row[i - pixel_bytes]
+                } else {
+                    0
+                };
+                let up_left = if i >= pixel_bytes {
+                    previous[i - pixel_bytes]
+                } else {
+                    0
+                };
+                let predicted = match line[0] {
+                    FILTER_NONE => 0,
+                    FILTER_SUB => left,
+                    FILTER_UP => previous[i],
+                    FILTER_AVERAGE => ((u16::from(left) + u16::from(previous[i])) / 2) as u8,
+                    FILTER_PAETH => paeth(left, previous[i], up_left),
+                    filter => panic!("unknown filter {filter}"),
+                };
+                row[i] = row[i].wrapping_add(predicted);
+            }
+            rows.extend_from_slice(&row);
+            previous = row;
+        }
+        Decoded {
+            ihdr,
+            plte: chunks.get(b"PLTE").cloned(),
+            trns: chunks.get(b"tRNS").cloned(),
+            rows,
+        }
+    }
+
+    /// Decodes stored and fixed-Huffman blocks, the two kinds the writer produces.
+    fn inflate(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(zlib[0], 0x78);
+        assert_eq!((u16::from(zlib[0]) << 8 | u16::from(zlib[1])) % 31, 0);
+        let body = &zlib[2..zlib.len() - 4];
+        let mut bit = 0usize;
+        let read = |bit: &mut usize, count: usize| -> usize {
+            let mut value = 0;
+            for shift in 0..count {
+                value |= usize::from(body[*bit / 8] >> (*bit % 8) & 1) << shift;
+                *bit += 1;
+            }
+            value
+        };
+        let mut out: Vec<u8> = Vec::new();
+        loop {
+            let last = read(&mut bit, 1) == 1;
+            match read(&mut bit, 2) {
+                0 => {
+                    let byte = bit.div_ceil(8);
+                    let len = usize::from(u16::from_le_bytes([body[byte], body[byte + 1]]));
+                    out.extend_from_slice(&body[byte + 4..byte + 4 + len]);
+                    bit = (byte + 4 + len) * 8;
+                }
+                1 => loop {
+                    let mut code = 0;
+                    let mut len = 0;
+                    let symbol = loop {
+                        code = code << 1 | read(&mut bit, 1);
+                        len += 1;
+                        match (len, code) {
+                            (7, 0..=0x17) => break code + 256,
+                            (8, 0x30..=0xBF) => break code - 0x30,
+                            (8, 0xC0..=0xC7) => break code - 0xC0 + 280,
+                            (9, 0x190..=0x1FF) => break code - 0x190 + 144,
+                            _ => {}
+                        }
+                    };
+                    if symbol < 256 {
+                        out.push(symbol as u8);
+                        continue;
+                    }
+                    if symbol == 256 {
+                        break;
+                    }
+                    let index = symbol - 257;
+                    let length = usize::from(LENGTH_BASE[index])
+                        + read(&mut bit, LENGTH_EXTRA_BITS[index] as usize);
+                    let mut distance_code = 0;
+                    for _ in 0..5 {
+                        distance_code = distance_code << 1 | read(&mut bit, 1);
+                    }
+                    let distance = usize::from(DISTANCE_BASE[distance_code])
+                        + read(&mut bit, DISTANCE_EXTRA_BITS[distance_code] as usize);
+                    for _ in 0..length {
+                        out.push(out[out.len() - distance]);
+                    }
+                },
+                kind => panic!("unexpected block type {kind}"),
+            }
+            if last {
+                break;
+            }
+        }
+        let mut adler = Adler32::new();
+        adler.update(&out);
+        assert_eq!(adler.finish().to_be_bytes(), zlib[zlib.len() - 4..]);
+        out
+    }
+
+    fn screenshot_like(width: u32, height: u32) -> RgbImage {
+        let mut data = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = if y < 8 {
+                    [32, 64, (x * 255 / width) as u8]
+                } else if (x / 5 + y / 3) % 7 == 0 {
+                    [0, 0, 0]
+                } else {
+                    [255, 255, 255]
+                };
+                data.extend_from_slice(&pixel);
+            }
+        }
+        RgbImage::new(width, height, data).unwrap()
+    }
+
+    fn encode_rgb(image: &RgbImage, options: PngOptions) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_rgb_png_to_with(&mut out, image, options).unwrap();
+        out
+    }
+
+    #[test]
+    fn compression_levels_round_trip_and_shrink_the_file() {
+        let image = screenshot_like(97, 41);
+        let stored = encode_rgb(&image, PngOptions::default());
+        let mut plain = Vec::new();
+        write_rgb_png_to(&mut plain, &image).unwrap();
+        assert_eq!(plain, stored);
+        assert_eq!(decode(&stored).rows, image.data);
+
+        let mut previous_len = stored.len();
+        for level in [1, 6, 9, 200] {
+            let compressed = encode_rgb(
+                &image,
+                PngOptions {
+                    compression_level: level,
+                    ..PngOptions::default()
+                },
+            );
+            let decoded = decode(&compressed);
+            assert_eq!(decoded.ihdr[8..10], [BIT_DEPTH_8, COLOR_TYPE_TRUECOLOR]);
+            assert_eq!(decoded.rows, image.data, "level {level}");
+            assert!(compressed.len() <= previous_len, "level {level}");
+            previous_len = compressed.len();
+        }
+        assert!(previous_len * 10 < stored.len());
+    }
+
+    #[test]
+    fn few_colors_are_written_as_a_packed_palette() {
+        let image = screenshot_like(13, 20);
+        let decoded = decode(&encode_rgb(
+            &image,
+            PngOptions {
+                palette: true,
+                ..PngOptions::default()
+            },
+        ));
+        let plte = decoded.plte.unwrap();
+        // 13 gradient shades plus black and white fit in four bits.
+        assert_eq!(plte.len(), 15 * 3);
+        assert_eq!(decoded.ihdr[8..10], [4, COLOR_TYPE_INDEXED]);
+        assert!(decoded.trns.is_none());
+        let row_bytes = 7;
+        let mut rgb = Vec::new();
+        for y in 0..20 {
+            for x in 0..13 {
+                let byte = decoded.rows[y * row_bytes + x / 2];
+                let index = usize::from(if x % 2 == 0 { byte >> 4 } else { byte & 15 });
+                rgb.extend_from_slice(&plte[index * 3..index * 3 + 3]);
+            }
+        }
+        assert_eq!(rgb, image.data);
+
+        let colorful = screenshot_like(300, 20);
+        let decoded = decode(&encode_rgb(
+            &colorful,
+            PngOptions {
+                compression_level: 6,
+                palette: true,
+            },
+        ));
+        assert!(decoded.plte.is_none());
+        assert_eq!(decoded.rows, colorful.data);
+    }
+
+    #[test]
+    fn alpha_is_unpremultiplied_and_kept() {
+        // Premultiplied BGRA: opaque red, half-transparent blue, fully transparent.
+        let image =
+            Argb32Image::new(3, 1, vec![0, 0, 255, 255, 128, 0, 0, 128, 0, 0, 0, 0]).unwrap();
+        let mut out = Vec::new();
+        write_rgba_png_to(&mut out, &image, PngOptions::default()).unwrap();
+        let decoded = decode(&out);
+        assert_eq!(
+            decoded.ihdr[8..10],
+            [BIT_DEPTH_8, COLOR_TYPE_TRUECOLOR_ALPHA]
+        );
+        assert_eq!(decoded.rows, [255, 0, 0, 255, 0, 0, 255, 128, 0, 0, 0, 0]);
+
+        let mut out = Vec::new();
+        write_rgba_png_to(
+            &mut out,
+            &image,
+            PngOptions {
+                compression_level: 9,
+                palette: true,
+            },
+        )
+        .unwrap();
+        let decoded = decode(&out);
+        assert_eq!(decoded.ihdr[8..10], [2, COLOR_TYPE_INDEXED]);
+        // The opaque red entry comes first, so tRNS still has to cover it.
+        assert_eq!(decoded.trns.unwrap(), [255, 128, 0]);
+    }
+}
@@ -0,0 +1,403 @@
+use crate::dom::{Attributes, Document, Element, Mutations, Node};
+use std::collections::{BTreeMap, HashMap};
+
+/// How reader mode shows an article: one narrow column of large, evenly spaced text.
+pub const STYLESHEET: &str = "
+html { background: #fbfaf7; color: #1f1f1f; }
+body { margin: 0; padding: 40px 20px 80px; }
+article {
+  display: block;
+  max-width: 680px;
+  margin: 0 auto;
+  font-family: Georgia, 'Times New Roman', serif;
+  font-size: 20px;
+  line-height: 1.6;
+}
+h1, h2, h3, h4, h5, h6 {
+  font-family: system-ui, -apple-system, 'Segoe UI', sans-serif;
+  line-height: 1.25;
+  margin: 1.4em 0 0.5em;
+}
+h1 { font-size: 34px; margin-top: 0; }
+h2 { font-size: 26px; }
+h3 { font-size: 22px; }
+p, ul, ol, blockquote, pre, table, figure { margin: 0 0 1em; }
+a { color: #0b57d0; }
+img { max-width: 100%; height: auto; }
+figcaption { font-size: 16px; color: #5f5f5f; }
+blockquote { border-left: 4px solid #d6d3cc; padding-left: 16px; color: #4a4a4a; }
+pre, code { font-family: ui-monospace, Menlo, Consolas, monospace; font-size: 16px; }
+pre { background: #f0eee9; padding: 12px; white-space: pre-wrap; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #d6d3cc; padding: 4px 8px; }
+@media (prefers-color-scheme: dark) {
+  html { background: #1c1b19; color: #e6e3dc; }
+  a { color: #8ab4f8; }
+  blockquote { border-left-color: #4a4843; color: #bdb9b0; }
+  pre { background: #2a2926; }
+  figcaption { color: #a8a49b; }
+  th, td { border-color: #4a4843; }
+}
+";
+
+/// Never part of an article, wherever they are.
+const BOILERPLATE_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "nav", "aside", "footer", "form", "iframe",
+    "object", "embed", "button", "input", "select", "textarea", "canvas", "dialog", "menu", "svg",
+    "link", "meta",
+];
+
+/// Class and id words of page furniture.
+const UNLIKELY_HINTS: &[&str] = &[
+    "ad-",
+    "ads",
+    "advert",
+    "banner",
+    "breadcrumb",
+    "comment",
+    "cookie",
+    "footer",
+    "masthead",
+    "menu",
+    "modal",
+    "nav",
+    "newsletter",
+    "popup",
+    "promo",
+    "related",
+    "share",
+    "sidebar",
+    "social",
+    "sponsor",
+    "subscribe",
+    "widget",
+];
+
+/// Class and id words of the main text, which outweigh an unlikely hint in the same name.
+const LIKELY_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "main", "page", "post", "story", "text",
+];
+
+/// Attributes kept on the extracted elements; the rest, styles and classes included, belonged to
+/// the page's own look.
+const KEPT_ATTRIBUTES: &[&str] = &[
+    "href", "src", "srcset", "alt", "title", "width", "height", "colspan", "rowspan", "start",
+    "lang", "dir", "datetime",
+];
+
+/// Paragraphs shorter than this say too little to tell where the article is.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Picks the main article out of `document` in the manner of Readability: paragraphs score the
+/// elements around them by how much text and how many commas they hold, and the best-scoring
+/// element, with those of its siblings that score well too, is the article. Navigation, forms,
+/// sidebars, link lists and the page's styling are left out. Returns `None` when no paragraph is
+/// long enough to go by, as on a page that is not an article.
+pub fn extract_article(document: &Document, title: &str) -> Option<Document> {
+    let mut candidates: BTreeMap<Vec<usize>, Candidate<'_>> = BTreeMap::new();
+    let mut path = Vec::new();
+    let mut ancestors = Vec::new();
+    score_paragraphs(&document.root, &mut path, &mut ancestors, &mut candidates);
+
+    let scores: BTreeMap<&Vec<usize>, f64> = candidates
+        .iter()
+        .map(|(path, candidate)| (path, candidate.final_score()))
+        .collect();
+    let (best_path, best_score) = scores.iter().fold(
+        None::<(&Vec<usize>, f64)>,
+        |best, (&path, &score)| match best {
+            Some(best) if best.1 >= score => Some(best),
+            _ => Some((path, score)),
+        },
+    )?;
+    let best_path = best_path.clone();
+
+    // Siblings that carry on the article, such as the other sections of a split-up story.
+    let mut content = Vec::new();
+    match best_path.split_last().and_then(|(_, parent)| {
+        document
+            .element_at(parent)
+            .map(|element| (parent.to_vec(), element))
+    }) {
+        Some((parent_path, parent)) if parent.name != "#document" && parent.name != "html" => {
+            let threshold = (best_score * 0.2).max(10.0);
+            for (index, child) in parent.children.iter().enumerate() {
+                let Node::Element(child) = child else {
+                    continue;
+                };
+                let mut child_path = parent_path.clone();
+                child_path.push(index);
+                let keep = child_path == best_path
+                    || scores
+                        .get(&child_path)
+                        .is_some_and(|&score| score >= threshold)
+                    || (child.name == "p" && text_len(child) > 80 && link_density(child) < 0.25);
+                if keep && let Some(clean) = clean_element(child) {
+                    content.push(Node::Element(clean));
+                }
+            }
+        }
+        _ => content.extend(
+            document
+                .element_at(&best_path)
+                .and_then(clean_element)
+                .map(Node::Element),
+        ),
+    }
+
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !title.is_empty() && !content.iter().any(contains_h1) {
+        content.insert(
+            0,
+            Node::Element(element("h1", vec![Node::Text(title.clone())])),
+        );
+    }
+
+    let head = element(
+        "head",
+        vec![Node::Element(element("title", vec![Node::Text(title)]))],
+    );
+    let body = element("body", vec![Node::Element(element("article", content))]);
+    let html = element("html", vec![Node::Element(head), Node::Element(body)]);
+    Some(Document {
+        root: element("#document", vec![Node::Element(html)]),
+        canvases: HashMap::new(),
+        mutations: Mutations::default(),
+        base_href: document.base_href.clone(),
+    })
+}
+
+struct Candidate<'a> {
+    element: &'a Element,
+    score: f64,
+}
+
+impl Candidate<'_> {
+    /// An element made mostly of links is a menu rather than an article.
+    fn final_score(&self) -> f64 {
+        self.score * (1.0 - link_density(self.element))
+    }
+}
+
+fn score_paragraphs<'a>(
+    element: &'a Element,
+    path: &mut Vec<usize>,
+    ancestors: &mut Vec<(Vec<usize>, &'a Element)>,
+    candidates: &mut BTreeMap<Vec<usize>, Candidate<'a>>,
+) {
+    for (index, child) in element.children.iter().enumerate() {
+        let Node::Element(child) = child else {
+            continue;
+        };
+        if is_boilerplate(child) || is_unlikely(child) {
+            continue;
+        }
+        path.push(index);
+        if matches!(child.name.as_str(), "p" | "pre" | "td") {
+            let text = collapsed_text(child);
+            let chars = text.chars().count();
+            if chars >= MIN_PARAGRAPH_CHARS {
+                let commas = text.matches([',', '，', '、']).count();
+                let score = 1.0 + commas as f64 + (chars / 100).min(3) as f64;
+                // The paragraph's parent gets its full score, the grandparent half of it.
+                for (level, (ancestor_path, ancestor)) in ancestors.iter().rev().take(2).enumerate()
+                {
+                    let candidate =
+                        candidates
+                            .entry(ancestor_path.clone())
+                            .or_insert_with(|| Candidate {
+                                element: ancestor,
+                                score: initial_score(ancestor),
+                            });
+                    candidate.score += score / (level + 1) as f64;
+                }
+            }
+        }
+        ancestors.push((path.clone(), child));
+        score_paragraphs(child, path, ancestors, candidates);
+        ancestors.pop();
+        path.pop();
+    }
+}
+
+fn initial_score(element: &Element) -> f64 {
+    let tag = match element.name.as_str() {
+        "article" | "main" => 10.0,
+        "div" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    tag + class_weight(element)
+}
+
+/// The words of an element's id and classes, lowercased.
+fn name_hints(element: &Element) -> String {
+    let mut hints = element.attributes.classes.join(" ");
+    if let Some(id) = &element.attributes.id {
+        hints.push(' ');
+        hints.push_str(id);
+    }
+    hints.to_ascii_lowercase()
+}
+
+fn class_weight(element: &Element) -> f64 {
+    let hints = name_hints(element);
+    let mut weight = 0.0;
+    if UNLIKELY_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight -= 25.0;
+    }
+    if LIKELY_HINTS.iter().any(|hint| hints.contains(hint)) {
+        weight += 25.0;
+    }
+    weight
+}
+
+fn is_boilerplate(element: &Element) -> bool {
+    BOILERPLATE_ELEMENTS.contains(&element.name.as_str())
+        || element.attributes.get("hidden").is_some()
+        || element.attributes.get("aria-hidden") == Some("true")
+        || element.attributes.style.as_deref().is_some_and(|style| {
+            let style: String = style.split_whitespace().collect();
+            style.to_ascii_lowercase().contains("display:none")
+        })
+}
+
+/// Page furniture by its class or id, unless the name also says it holds the text.
+fn is_unlikely(element: &Element) -> bool {
+    if matches!(element.name.as_str(), "html" | "body" | "article" | "main") {
+        return false;
+    }
+    let hints = name_hints(element);
+    UNLIKELY_HINTS.iter().any(|hint| hints.contains(hint))
+        && !LIKELY_HINTS.iter().any(|hint| hints.contains(hint))
+}
+
+/// A copy of `element` without boilerplate, link lists or the page's attributes.
+fn clean_element(element: &Element) -> Option<Element> {
+    if is_boilerplate(element) || is_unlikely(element) {
+        return None;
+    }
+    if matches!(
+        element.name.as_str(),
+        "div" | "section" | "ul" | "ol" | "table" | "header"
+    ) && link_density(element) > 0.5
+        && text_len(element) < 500
+    {
+        return None;
+    }
+    let mut attributes = Attributes::default();
+    for (name, value) in element.attributes.to_serialized_pairs() {
+        if KEPT_ATTRIBUTES.contains(&name.as_str()) {
+            attributes.insert(name, value);
+        }
+    }
+    let children = element
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Node::Text(text) => Some(Node::Text(text.clone())),
+            Node::Element(child) => clean_element(child).map(Node::Element),
+        })
+        .collect();
+    Some(Element {
+        name: element.name.clone(),
+        attributes,
+        children,
+    })
+}
+
+fn element(name: &str, children: Vec<Node>) -> Element {
+    Element {
+        name: name.to_owned(),
+        attributes: Attributes::default(),
+        children,
+    }
+}
+
+fn contains_h1(node: &Node) -> bool {
+    match node {
+        Node::Element(element) => element.name == "h1" || element.children.iter().any(contains_h1),
+        Node::Text(_) => false,
+    }
+}
+
+fn collapsed_text(element: &Element) -> String {
+    crate::agent::visible_text(element)
+}
+
+fn text_len(element: &Element) -> usize {
+    collapsed_text(element).chars().count()
+}
+
+/// The share of an element's text that is inside links.
+fn link_density(element: &Element) -> f64 {
+    let total = text_len(element);
+    if total == 0 {
+        return 0.0;
+    }
+    link_text_len(element) as f64 / total as f64
+}
+
+fn link_text_len(element: &Element) -> usize {
+    element
+        .children
+        .iter()
+        .map(|child| match child {
+            Node::Element(child) if child.name == "a" => text_len(child),
+            Node::Element(child) => link_text_len(child),
+            Node::Text(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_article;
+    use crate::agent::visible_text;
+
+    #[test]
+    fn picks_the_article_and_leaves_out_page_furniture() {
+        let paragraph = "This sentence, which goes on for a while, is part of the story itself";
+        let document = crate::html::parse_document(&format!(
+            r#"<html><head><title>A  story</title><style>p {{ color: red }}</style></head><body>
+            <nav><a href="/">Home</a> <a href="/about">About</a></nav>
+            <div id="sidebar"><p>{paragraph} but sits in the sidebar.</p></div>
+            <div class="post-content">
+              <p class="lead" style="font-size: 40px">{paragraph}, first.</p>
+              <script>track()</script>
+              <p>{paragraph}, second, with <a href="/more">a link</a>.</p>
+              <ul class="share"><li><a href="/x">Share</a></li><li><a href="/y">Tweet</a></li></ul>
+              <form><input name="q"></form>
+            </div>
+            <div class="post-content-more"><p>{paragraph}, third.</p><p>{paragraph}, fourth.</p></div>
+            <footer><p>{paragraph} but is the footer.</p></footer>
+            </body></html>"#
+        ));
+
+        let article = extract_article(&document, " A  story ").unwrap();
+        let text = visible_text(article.query_selector("article").unwrap());
+        assert!(text.starts_with("A story This sentence"), "{text}");
+        for part in ["first.", "second, with a link", "third.", "fourth."] {
+            assert!(text.contains(part), "{part} missing from {text}");
+        }
+        for furniture in ["Home", "sidebar", "track", "Share", "footer"] {
+            assert!(!text.contains(furniture), "{furniture} kept in {text}");
+        }
+        let lead = article.query_selector("article p").unwrap();
+        assert!(lead.attributes.classes.is_empty());
+        assert_eq!(lead.attributes.style, None);
+        assert_eq!(
+            article
+                .query_selector("article a")
+                .unwrap()
+                .attributes
+                .get("href"),
+            Some("/more")
+        );
+
+        let menu = crate::html::parse_document("<ul><li><a href=/>Home</a></li></ul>");
+        assert!(extract_article(&menu, "Menu").is_none());
+    }
+}
@@ -0,0 +1,161 @@
+use crate::dom::Document;
+use crate::image::RgbImage;
+
+/// Whether a test must look the same as its reference or different from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Relation {
+    Match,
+    Mismatch,
+}
+
+/// A `<link rel="match">` or `<link rel="mismatch">` of a test page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    pub relation: Relation,
+    pub href: String,
+}
+
+/// The references a test page names, in document order. A page with none is not a test, such as
+/// a reference or a support file.
+pub fn references(document: &Document) -> Vec<Reference> {
+    document
+        .query_selector_all("link[rel][href]")
+        .into_iter()
+        .filter_map(|link| {
+            let href = link.attributes.get("href")?.trim();
+            let rel = link.attributes.get("rel")?;
+            let relation = rel.split_ascii_whitespace().find_map(|token| {
+                if token.eq_ignore_ascii_case("match") {
+                    Some(Relation::Match)
+                } else if token.eq_ignore_ascii_case("mismatch") {
+                    Some(Relation::Mismatch)
+                } else {
+                    None
+                }
+            })?;
+            (!href.is_empty()).then(|| Reference {
+                relation,
+                href: href.to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Where two captures of the same size differ.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ImageDiff {
+    /// Pixels with a channel that differs by more than the tolerance.
+    pub differing_pixels: u64,
+    pub total_pixels: u64,
+    /// The largest difference of any channel, whether or not it was tolerated.
+    pub max_channel_difference: u8,
+    /// `(min_x, min_y, max_x, max_y)` of the differing pixels, inclusive.
+    pub bounds: Option<(u32, u32, u32, u32)>,
+}
+
+/// Compares `test` with `reference` pixel by pixel; a pixel differs when one of its channels is
+/// more than `tolerance` apart.
+pub fn compare(test: &RgbImage, reference: &RgbImage, tolerance: u8) -> Result<ImageDiff, String> {
+    if (test.width, test.height) != (reference.width, reference.height) {
+        return Err(format!(
+            "Captures differ in size: {}x{} and {}x{}",
+            test.width, test.height, reference.width, reference.height
+        ));
+    }
+    let mut diff = ImageDiff {
+        total_pixels: u64::from(test.width) * u64::from(test.height),
+        ..ImageDiff::default()
+    };
+    let width = test.width.max(1) as usize;
+    for (index, (a, b)) in test
+        .data
+        .chunks_exact(3)
+        .zip(reference.data.chunks_exact(3))
+        .enumerate()
+    {
+        let channel_difference = a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0);
+        diff.max_channel_difference = diff.max_channel_difference.max(channel_difference);
+        if channel_difference <= tolerance {
+            continue;
+        }
+        diff.differing_pixels += 1;
+        let (x, y) = ((index % width) as u32, (index / width) as u32);
+        diff.bounds = Some(match diff.bounds {
+            None => (x, y, x, y),
+            Some((min_x, min_y, max_x, max_y)) => {
+                (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+            }
+        });
+    }
+    Ok(diff)
+}
+
+/// The reference faded out, with the pixels [`compare`] finds different painted red.
+pub fn diff_image(
+    test: &RgbImage,
+    reference: &RgbImage,
+    tolerance: u8,
+) -> Result<RgbImage, String> {
+    compare(test, reference, tolerance)?;
+    let mut data = Vec::with_capacity(reference.data.len());
+    for (a, b) in test
+        .data
+        .chunks_exact(3)
+        .zip(reference.data.chunks_exact(3))
+    {
+        let differs = a.iter().zip(b).any(|(a, b)| a.abs_diff(*b) > tolerance);
+        if differs {
+            data.extend_from_slice(&[255, 0, 0]);
+        } else {
+            data.extend(b.iter().map(|&channel| 191 + channel / 4));
+        }
+    }
+    RgbImage::new(reference.width, reference.height, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_are_read_from_links_and_differences_bounded() {
+        let document = crate::html::parse_document(
+            "<link rel=stylesheet href=a.css><link rel=match href=ref.html>\
+             <link rel='MISMATCH' href=' other.html '><link rel=match>",
+        );
+        assert_eq!(
+            references(&document),
+            [
+                Reference {
+                    relation: Relation::Match,
+                    href: "ref.html".to_owned(),
+                },
+                Reference {
+                    relation: Relation::Mismatch,
+                    href: "other.html".to_owned(),
+                },
+            ]
+        );
+
+        let white = RgbImage::new(3, 2, vec![255; 18]).unwrap();
+        let mut marked = white.clone();
+        marked.data[3 * 4] = 250;
+        marked.data[3 * 5 + 1] = 0;
+        let diff = compare(&marked, &white, 5).unwrap();
+        assert_eq!(diff.differing_pixels, 1);
+        assert_eq!(diff.total_pixels, 6);
+        assert_eq!(diff.max_channel_difference, 255);
+        assert_eq!(diff.bounds, Some((2, 1, 2, 1)));
+        assert_eq!(compare(&marked, &white, 255).unwrap().differing_pixels, 0);
+        assert_eq!(
+            &diff_image(&marked, &white, 5).unwrap().data[15..],
+            &[255, 0, 0]
+        );
+        assert!(compare(&white, &RgbImage::new(2, 3, vec![255; 18]).unwrap(), 0).is_err());
+    }
+}
@@ -1,7 +1,17 @@
-use crate::geom::Color;
+pub mod canvas;
+pub mod damage;
+mod dump;
+pub mod shaping;
+pub mod svg;
+
+pub use svg::path::{LineCap, LineJoin, PathCommand, Point, StrokeStyle, Transform};
+pub use svg::raster::FillRule;
+
+use crate::debug;
+use crate::geom::{Color, Rect};
 use crate::image::Argb32Image;
 use crate::style::{FontFamily, GradientDirection};
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TextStyle {
@@ -26,6 +36,18 @@ impl Default for TextStyle {
     }
 }
 
+impl TextStyle {
+    /// What `letter-spacing` adds to a run of `text`: one gap between each pair of its
+    /// clusters. Every backend measures runs this way, so a word is painted as wide as layout
+    /// measured it.
+    pub fn letter_spacing_total_px(&self, text: &str) -> i32 {
+        let gaps = shaping::clusters(text).count().saturating_sub(1);
+        i32::try_from(gaps)
+            .unwrap_or(i32::MAX)
+            .saturating_mul(self.letter_spacing_px)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct FontMetricsPx {
     pub ascent_px: i32,
@@ -100,7 +122,7 @@ pub struct DrawImage {
     pub width_px: i32,
     pub height_px: i32,
     pub opacity: u8,
-    pub image: Rc<Argb32Image>,
+    pub image: Arc<Argb32Image>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -110,10 +132,10 @@ pub struct DrawSvg {
     pub width_px: i32,
     pub height_px: i32,
     pub opacity: u8,
-    pub svg_xml: Rc<str>,
+    pub svg_xml: Arc<str>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DisplayCommand {
     Rect(DrawRect),
     LinearGradientRect(DrawLinearGradientRect),
@@ -126,16 +148,48 @@ pub enum DisplayCommand {
     PopOpacity(u8),
     PushFixed,
     PopFixed,
+    /// Maps everything up to the matching `PopTransform` through the transform, in the same
+    /// space as the commands' own coordinates.
+    PushTransform(Transform),
+    PopTransform,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+impl DisplayCommand {
+    /// Moves what the command draws by `(dx_px, dy_px)`.
+    pub fn translate(&mut self, dx_px: i32, dy_px: i32) {
+        let (x_px, y_px) = match self {
+            DisplayCommand::Rect(rect) => (&mut rect.x_px, &mut rect.y_px),
+            DisplayCommand::LinearGradientRect(rect) => (&mut rect.x_px, &mut rect.y_px),
+            DisplayCommand::RoundedRect(rect) => (&mut rect.x_px, &mut rect.y_px),
+            DisplayCommand::RoundedRectBorder(rect) => (&mut rect.x_px, &mut rect.y_px),
+            DisplayCommand::Text(text) => (&mut text.x_px, &mut text.y_px),
+            DisplayCommand::Image(image) => (&mut image.x_px, &mut image.y_px),
+            DisplayCommand::Svg(svg) => (&mut svg.x_px, &mut svg.y_px),
+            DisplayCommand::PushTransform(transform) => {
+                *transform = transform.about(f64::from(dx_px), f64::from(dy_px));
+                return;
+            }
+            DisplayCommand::PushOpacity(_)
+            | DisplayCommand::PopOpacity(_)
+            | DisplayCommand::PushFixed
+            | DisplayCommand::PopFixed
+            | DisplayCommand::PopTransform => return,
+        };
+        *x_px = x_px.saturating_add(dx_px);
+        *y_px = y_px.saturating_add(dy_px);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct DisplayList {
     pub commands: Vec<DisplayCommand>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct LinkHitRegion {
-    pub href: Rc<str>,
+    pub href: Arc<str>,
+    /// Set for `target="_blank"` links.
+    pub opens_new_tab: bool,
     pub x_px: i32,
     pub y_px: i32,
     pub width_px: i32,
@@ -206,6 +260,48 @@ pub trait Painter: TextMeasurer {
         image: &Argb32Image,
         opacity: u8,
     ) -> Result<(), String>;
+    /// Limits painting to the rectangle, mapped through the current transform, until the
+    /// matching [`Painter::pop_clip`]. Clip, transform and opacity pushes nest like brackets.
+    /// Every platform painter implements the clip, transform and path methods; the defaults
+    /// ignore them, for painters that only record what was drawn.
+    fn push_clip_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+    fn pop_clip(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+    /// Maps everything painted until the matching [`Painter::pop_transform`] through
+    /// `transform`, inside any transform already pushed.
+    fn push_transform(&mut self, _transform: Transform) -> Result<(), String> {
+        Ok(())
+    }
+    fn pop_transform(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+    fn fill_path(
+        &mut self,
+        _path: &[PathCommand],
+        _rule: FillRule,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+    fn stroke_path(
+        &mut self,
+        _path: &[PathCommand],
+        _style: &StrokeStyle,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+    /// Rasterizes the document with the built-in renderer and draws it as an image, so SVG looks
+    /// the same on every backend.
     fn draw_svg(
         &mut self,
         x_px: i32,
@@ -214,6 +310,33 @@ pub trait Painter: TextMeasurer {
         height_px: i32,
         svg_xml: &str,
         opacity: u8,
-    ) -> Result<(), String>;
+    ) -> Result<(), String> {
+        if width_px <= 0 || height_px <= 0 || opacity == 0 {
+            return Ok(());
+        }
+        let image = match svg::rasterize_cached(svg_xml, width_px as u32, height_px as u32) {
+            Ok(image) => image,
+            Err(err) => {
+                debug::log(
+                    debug::Target::Render,
+                    debug::Level::Warn,
+                    format_args!("SVG render failed: {}", debug::shorten(&err, 120)),
+                );
+                return Ok(());
+            }
+        };
+        self.draw_image(x_px, y_px, width_px, height_px, &image, opacity)
+    }
+    /// Whether the back buffer still holds the last flushed frame, so a frame may repaint only
+    /// the areas that changed. Painters that can't clip, or that start each frame blank, keep the
+    /// default and are always repainted in full.
+    fn retains_frame(&self) -> bool {
+        false
+    }
+    /// Limits painting, starting with [`Painter::clear`], to `clip` in window pixels; `None`
+    /// paints everywhere. Only called when [`Painter::retains_frame`] is true.
+    fn set_clip(&mut self, _clip: Option<Rect>) -> Result<(), String> {
+        Ok(())
+    }
     fn flush(&mut self) -> Result<(), String>;
 }
@@ -0,0 +1,597 @@
+use super::svg::path::{self, LineCap, LineJoin, PathCommand, Point, StrokeStyle, Transform};
+use super::svg::raster::{Canvas, FillRule};
+use crate::geom::Color;
+use crate::image::{Argb32Image, check_image_dimensions};
+use crate::style::{FontFamily, TextAlign};
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::sync::Arc;
+
+/// Size of a `<canvas>` without `width`/`height` attributes.
+pub const DEFAULT_WIDTH: u32 = 300;
+pub const DEFAULT_HEIGHT: u32 = 150;
+
+/// Curves are flattened to within this many device pixels.
+const TOLERANCE_PX: f64 = 0.2;
+/// Text runs kept per canvas; `fillText` calls past this are dropped.
+const MAX_TEXTS: usize = 10_000;
+/// Deepest `save()` nesting; further saves are ignored.
+const MAX_SAVED_STATES: usize = 1024;
+
+/// What scripts drew on a `<canvas>`: a bitmap at the canvas's own resolution, plus text runs
+/// that layout paints on top with the page's fonts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanvasSurface {
+    pub image: Arc<Argb32Image>,
+    pub texts: Vec<CanvasText>,
+}
+
+/// A `fillText` run in canvas pixels, anchored at `(x_px, y_px)` as `align` and `baseline` say.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanvasText {
+    pub x_px: i32,
+    pub y_px: i32,
+    pub text: String,
+    pub color: Color,
+    pub font_size_px: i32,
+    pub bold: bool,
+    pub font_family: FontFamily,
+    pub align: TextAlign,
+    pub baseline: TextBaseline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextBaseline {
+    Alphabetic,
+    Top,
+    Middle,
+    Bottom,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct DrawState {
+    transform: Transform,
+    fill: Color,
+    stroke: Color,
+    stroke_style: StrokeStyle,
+    global_alpha: f64,
+    font_size_px: f64,
+    bold: bool,
+    font_family: FontFamily,
+    text_align: TextAlign,
+    text_baseline: TextBaseline,
+}
+
+impl Default for DrawState {
+    fn default() -> Self {
+        Self {
+            transform: Transform::IDENTITY,
+            fill: Color::BLACK,
+            stroke: Color::BLACK,
+            stroke_style: StrokeStyle {
+                width: 1.0,
+                cap: LineCap::Butt,
+                join: LineJoin::Miter,
+                miter_limit: 10.0,
+            },
+            global_alpha: 1.0,
+            font_size_px: 10.0,
+            bold: false,
+            font_family: FontFamily::SansSerif,
+            text_align: TextAlign::Left,
+            text_baseline: TextBaseline::Alphabetic,
+        }
+    }
+}
+
+/// A `CanvasRenderingContext2D` subset. Path points are transformed as they are added, the way
+/// the canvas spec requires, so the current path is kept in device pixels.
+pub struct Context2d {
+    canvas: Canvas,
+    width: u32,
+    height: u32,
+    state: DrawState,
+    saved: Vec<DrawState>,
+    path: Vec<PathCommand>,
+    current: Option<Point>,
+    subpath_start: Point,
+    texts: Vec<CanvasText>,
+}
+
+impl Context2d {
+    pub fn new(width: u32, height: u32) -> Result<Self, String> {
+        check_image_dimensions(width, height).map_err(|err| err.to_string())?;
+        Ok(Self {
+            canvas: Canvas::new(width, height),
+            width,
+            height,
+            state: DrawState::default(),
+            saved: Vec::new(),
+            path: Vec::new(),
+            current: None,
+            subpath_start: Point::default(),
+            texts: Vec::new(),
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn font_size_px(&self) -> f64 {
+        self.state.font_size_px
+    }
+
+    pub fn into_surface(self) -> Result<CanvasSurface, String> {
+        Ok(CanvasSurface {
+            image: Arc::new(self.canvas.into_image()?),
+            texts: self.texts,
+        })
+    }
+
+    pub fn set_fill_style(&mut self, value: &str) {
+        if let Some(color) = super::svg::parse_color(value) {
+            self.state.fill = color;
+        }
+    }
+
+    pub fn set_stroke_style(&mut self, value: &str) {
+        if let Some(color) = super::svg::parse_color(value) {
+            self.state.stroke = color;
+        }
+    }
+
+    pub fn set_line_width(&mut self, width: f64) {
+        if width.is_finite() && width > 0.0 {
+            self.state.stroke_style.width = width;
+        }
+    }
+
+    pub fn set_line_cap(&mut self, value: &str) {
+        self.state.stroke_style.cap = match value {
+            "butt" => LineCap::Butt,
+            "round" => LineCap::Round,
+            "square" => LineCap::Square,
+            _ => return,
+        };
+    }
+
+    pub fn set_line_join(&mut self, value: &str) {
+        self.state.stroke_style.join = match value {
+            "miter" => LineJoin::Miter,
+            "round" => LineJoin::Round,
+            "bevel" => LineJoin::Bevel,
+            _ => return,
+        };
+    }
+
+    pub fn set_miter_limit(&mut self, limit: f64) {
+        if limit.is_finite() && limit > 0.0 {
+            self.state.stroke_style.miter_limit = limit;
+        }
+    }
+
+    pub fn set_global_alpha(&mut self, alpha: f64) {
+        if (0.0..=1.0).contains(&alpha) {
+            self.state.global_alpha = alpha;
+        }
+    }
+
+    /// Takes the size, weight and generic family from a CSS `font` shorthand such as
+    /// `bold 14px sans-serif`; a value without a pixel size is ignored, as browsers do.
+    pub fn set_font(&mut self, value: &str) {
+        let mut size = None;
+        let mut bold = false;
+        let mut family = FontFamily::SansSerif;
+        for word in value.split([' ', ',']).filter(|word| !word.is_empty()) {
+            let word = word.trim_matches(['"', '\'']).to_ascii_lowercase();
+            let size_part = word.split('/').next().unwrap_or_default();
+            if let Some(px) = size_part.strip_suffix("px") {
+                size = px
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|px| px.is_finite() && *px > 0.0);
+                continue;
+            }
+            match word.as_str() {
+                "bold" | "bolder" | "600" | "700" | "800" | "900" => bold = true,
+                "serif" | "georgia" | "times" => family = FontFamily::Serif,
+                "monospace" | "courier" => family = FontFamily::Monospace,
+                _ => {}
+            }
+        }
+        if let Some(size) = size {
+            self.state.font_size_px = size;
+            self.state.bold = bold;
+            self.state.font_family = family;
+        }
+    }
+
+    pub fn set_text_align(&mut self, value: &str) {
+        self.state.text_align = match value {
+            "left" | "start" => TextAlign::Left,
+            "center" => TextAlign::Center,
+            "right" | "end" => TextAlign::Right,
+            _ => return,
+        };
+    }
+
+    pub fn set_text_baseline(&mut self, value: &str) {
+        self.state.text_baseline = match value {
+            "alphabetic" => TextBaseline::Alphabetic,
+            "top" | "hanging" => TextBaseline::Top,
+            "middle" => TextBaseline::Middle,
+            "bottom" | "ideographic" => TextBaseline::Bottom,
+            _ => return,
+        };
+    }
+
+    pub fn save(&mut self) {
+        if self.saved.len() < MAX_SAVED_STATES {
+            self.saved.push(self.state);
+        }
+    }
+
+    pub fn restore(&mut self) {
+        if let Some(state) = self.saved.pop() {
+            self.state = state;
+        }
+    }
+
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.transform(1.0, 0.0, 0.0, 1.0, x, y);
+    }
+
+    pub fn scale(&mut self, x: f64, y: f64) {
+        self.transform(x, 0.0, 0.0, y, 0.0, 0.0);
+    }
+
+    pub fn rotate(&mut self, radians: f64) {
+        let (sin, cos) = radians.sin_cos();
+        self.transform(cos, sin, -sin, cos, 0.0, 0.0);
+    }
+
+    pub fn transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        if [a, b, c, d, e, f].iter().all(|value| value.is_finite()) {
+            self.state.transform = self.state.transform.then(Transform { a, b, c, d, e, f });
+        }
+    }
+
+    pub fn set_transform(&mut self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) {
+        if [a, b, c, d, e, f].iter().all(|value| value.is_finite()) {
+            self.state.transform = Transform { a, b, c, d, e, f };
+        }
+    }
+
+    pub fn reset_transform(&mut self) {
+        self.state.transform = Transform::IDENTITY;
+    }
+
+    pub fn begin_path(&mut self) {
+        self.path.clear();
+        self.current = None;
+    }
+
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        let point = self.device(x, y);
+        self.path.push(PathCommand::MoveTo(point));
+        self.current = Some(point);
+        self.subpath_start = point;
+    }
+
+    pub fn line_to(&mut self, x: f64, y: f64) {
+        if self.current.is_none() {
+            self.move_to(x, y);
+            return;
+        }
+        let point = self.device(x, y);
+        self.path.push(PathCommand::LineTo(point));
+        self.current = Some(point);
+    }
+
+    pub fn quadratic_curve_to(&mut self, cx: f64, cy: f64, x: f64, y: f64) {
+        if self.current.is_none() {
+            self.move_to(cx, cy);
+        }
+        let point = self.device(x, y);
+        self.path
+            .push(PathCommand::QuadTo(self.device(cx, cy), point));
+        self.current = Some(point);
+    }
+
+    pub fn bezier_curve_to(&mut self, c1x: f64, c1y: f64, c2x: f64, c2y: f64, x: f64, y: f64) {
+        if self.current.is_none() {
+            self.move_to(c1x, c1y);
+        }
+        let point = self.device(x, y);
+        self.path.push(PathCommand::CubicTo(
+            self.device(c1x, c1y),
+            self.device(c2x, c2y),
+            point,
+        ));
+        self.current = Some(point);
+    }
+
+    pub fn close_path(&mut self) {
+        if self.current.is_some() {
+            self.path.push(PathCommand::Close);
+            self.current = Some(self.subpath_start);
+        }
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.move_to(x, y);
+        self.line_to(x + width, y);
+        self.line_to(x + width, y + height);
+        self.line_to(x, y + height);
+        self.close_path();
+        self.move_to(x, y);
+    }
+
+    pub fn arc(&mut self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) {
+        self.ellipse(x, y, radius, radius, 0.0, start, end, anticlockwise);
+    }
+
+    /// Adds an elliptical arc as quarter-turn cubic béziers, joined to the current point by a
+    /// straight line.
+    #[allow(clippy::too_many_arguments)]
+    pub fn ellipse(
+        &mut self,
+        x: f64,
+        y: f64,
+        radius_x: f64,
+        radius_y: f64,
+        rotation: f64,
+        start: f64,
+        end: f64,
+        anticlockwise: bool,
+    ) {
+        let values = [x, y, radius_x, radius_y, rotation, start, end];
+        if !values.iter().all(|value| value.is_finite()) || radius_x < 0.0 || radius_y < 0.0 {
+            return;
+        }
+        let sweep = if anticlockwise {
+            if start - end >= TAU {
+                -TAU
+            } else {
+                -(start - end).rem_euclid(TAU)
+            }
+        } else if end - start >= TAU {
+            TAU
+        } else {
+            (end - start).rem_euclid(TAU)
+        };
+
+        let (sin_rotation, cos_rotation) = rotation.sin_cos();
+        let on_ellipse = |angle: f64, scale: f64| {
+            let (sin, cos) = angle.sin_cos();
+            (cos * scale, sin * scale)
+        };
+        let to_user = |(ux, uy): (f64, f64)| {
+            let px = ux * radius_x;
+            let py = uy * radius_y;
+            (
+                x + px * cos_rotation - py * sin_rotation,
+                y + px * sin_rotation + py * cos_rotation,
+            )
+        };
+
+        let (start_x, start_y) = to_user(on_ellipse(start, 1.0));
+        self.line_to(start_x, start_y);
+
+        let segments = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = sweep / segments as f64;
+        let handle = 4.0 / 3.0 * (step / 4.0).tan();
+        for segment in 0..segments {
+            let from = start + step * segment as f64;
+            let to = from + step;
+            let (from_cos, from_sin) = on_ellipse(from, 1.0);
+            let (to_cos, to_sin) = on_ellipse(to, 1.0);
+            let c1 = to_user((from_cos - handle * from_sin, from_sin + handle * from_cos));
+            let c2 = to_user((to_cos + handle * to_sin, to_sin - handle * to_cos));
+            let end = to_user((to_cos, to_sin));
+            self.bezier_curve_to(c1.0, c1.1, c2.0, c2.1, end.0, end.1);
+        }
+    }
+
+    pub fn fill(&mut self, even_odd: bool) {
+        let rule = if even_odd {
+            FillRule::EvenOdd
+        } else {
+            FillRule::NonZero
+        };
+        let polygons = path::flatten(&self.path, TOLERANCE_PX)
+            .into_iter()
+            .map(|polyline| polyline.points)
+            .collect::<Vec<_>>();
+        self.canvas
+            .fill(&polygons, rule, self.state.fill, self.state.global_alpha);
+    }
+
+    pub fn stroke(&mut self) {
+        let polylines = path::flatten(&self.path, TOLERANCE_PX);
+        let transform = self.state.transform;
+        let scale = (transform.a * transform.d - transform.b * transform.c)
+            .abs()
+            .sqrt();
+        let style = StrokeStyle {
+            width: self.state.stroke_style.width * scale,
+            ..self.state.stroke_style
+        };
+        let polygons = path::stroke_polygons(&polylines, &style, TOLERANCE_PX);
+        self.canvas.fill(
+            &polygons,
+            FillRule::NonZero,
+            self.state.stroke,
+            self.state.global_alpha,
+        );
+    }
+
+    pub fn fill_rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.with_rect_path(x, y, width, height, |context| context.fill(false));
+    }
+
+    pub fn stroke_rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.with_rect_path(x, y, width, height, Context2d::stroke);
+    }
+
+    /// Clears the device-space bounds of the rectangle, along with text anchored inside it.
+    pub fn clear_rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        let corners = [
+            self.device(x, y),
+            self.device(x + width, y),
+            self.device(x, y + height),
+            self.device(x + width, y + height),
+        ];
+        let left = corners.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+        let right = corners
+            .iter()
+            .map(|p| p.x)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let top = corners.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+        let bottom = corners
+            .iter()
+            .map(|p| p.y)
+            .fold(f64::NEG_INFINITY, f64::max);
+        self.canvas.clear(left, top, right, bottom);
+        self.texts.retain(|text| {
+            let (x, y) = (f64::from(text.x_px), f64::from(text.y_px));
+            !(left <= x && x < right && top <= y && y < bottom)
+        });
+    }
+
+    pub fn fill_text(&mut self, text: &str, x: f64, y: f64) {
+        self.push_text(text, x, y, self.state.fill);
+    }
+
+    pub fn stroke_text(&mut self, text: &str, x: f64, y: f64) {
+        self.push_text(text, x, y, self.state.stroke);
+    }
+
+    fn push_text(&mut self, text: &str, x: f64, y: f64, color: Color) {
+        if text.is_empty() || self.texts.len() >= MAX_TEXTS {
+            return;
+        }
+        let anchor = self.device(x, y);
+        let transform = self.state.transform;
+        let scale = (transform.a * transform.d - transform.b * transform.c)
+            .abs()
+            .sqrt();
+        let font_size_px = (self.state.font_size_px * scale).round();
+        if !(anchor.x.is_finite() && anchor.y.is_finite() && font_size_px >= 1.0) {
+            return;
+        }
+        let alpha = (f64::from(color.a) * self.state.global_alpha).round() as u8;
+        self.texts.push(CanvasText {
+            x_px: anchor.x.round().clamp(-1e9, 1e9) as i32,
+            y_px: anchor.y.round().clamp(-1e9, 1e9) as i32,
+            text: text
+                .chars()
+                .map(|ch| if ch.is_whitespace() { ' ' } else { ch })
+                .collect(),
+            color: Color { a: alpha, ..color },
+            font_size_px: font_size_px.min(1e6) as i32,
+            bold: self.state.bold,
+            font_family: self.state.font_family,
+            align: self.state.text_align,
+            baseline: self.state.text_baseline,
+        });
+    }
+
+    fn with_rect_path(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        paint: impl FnOnce(&mut Self),
+    ) {
+        let path = std::mem::take(&mut self.path);
+        let current = self.current.take();
+        let subpath_start = self.subpath_start;
+        self.rect(x, y, width, height);
+        paint(self);
+        self.path = path;
+        self.current = current;
+        self.subpath_start = subpath_start;
+    }
+
+    fn device(&self, x: f64, y: f64) -> Point {
+        self.state.transform.apply(Point::new(x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixel(surface: &CanvasSurface, x: u32, y: u32) -> [u8; 4] {
+        let offset = ((y * surface.image.width + x) * 4) as usize;
+        surface.image.data[offset..offset + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn fills_strokes_and_clears_rectangles() {
+        let mut context = Context2d::new(20, 20).unwrap();
+        context.set_fill_style("#ff0000");
+        context.fill_rect(2.0, 2.0, 10.0, 10.0);
+        context.clear_rect(4.0, 4.0, 2.0, 2.0);
+        context.set_stroke_style("blue");
+        context.set_line_width(2.0);
+        context.stroke_rect(14.0, 14.0, 4.0, 4.0);
+        let surface = context.into_surface().unwrap();
+
+        assert_eq!(pixel(&surface, 8, 8), [0, 0, 255, 255]);
+        assert_eq!(pixel(&surface, 4, 4), [0, 0, 0, 0]);
+        assert_eq!(pixel(&surface, 0, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&surface, 14, 16), [255, 0, 0, 255]);
+        assert_eq!(pixel(&surface, 16, 16), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn applies_transforms_and_state_stack_to_paths() {
+        let mut context = Context2d::new(40, 40).unwrap();
+        context.save();
+        context.translate(20.0, 20.0);
+        context.set_fill_style("rgb(0, 128, 0)");
+        context.begin_path();
+        context.arc(0.0, 0.0, 10.0, 0.0, TAU, false);
+        context.fill(false);
+        context.restore();
+        context.fill_rect(0.0, 0.0, 2.0, 2.0);
+        let surface = context.into_surface().unwrap();
+
+        assert_eq!(pixel(&surface, 20, 20), [0, 128, 0, 255]);
+        assert_eq!(pixel(&surface, 20, 12), [0, 128, 0, 255]);
+        assert_eq!(pixel(&surface, 20, 8), [0, 0, 0, 0]);
+        assert_eq!(pixel(&surface, 1, 1), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn records_text_runs_in_device_pixels() {
+        let mut context = Context2d::new(100, 50).unwrap();
+        context.scale(2.0, 2.0);
+        context.set_font("bold 12px serif");
+        context.set_text_align("center");
+        context.fill_text("Q1\nsales", 20.0, 10.0);
+        let surface = context.into_surface().unwrap();
+
+        assert_eq!(
+            surface.texts,
+            vec![CanvasText {
+                x_px: 40,
+                y_px: 20,
+                text: "Q1 sales".to_owned(),
+                color: Color::BLACK,
+                font_size_px: 24,
+                bold: true,
+                font_family: FontFamily::Serif,
+                align: TextAlign::Center,
+                baseline: TextBaseline::Alphabetic,
+            }]
+        );
+    }
+}
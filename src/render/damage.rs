@@ -0,0 +1,260 @@
+use super::{DisplayCommand, DisplayList, TextMeasurer};
+use crate::geom::Rect;
+
+/// Past this many rectangles, damage is merged into one, so a frame isn't replayed too often.
+const MAX_DAMAGE_RECTS: usize = 8;
+/// Slack around each changed command, for antialiased edges and glyph overhang.
+const PAD_PX: i32 = 2;
+
+/// The areas, in window pixels, that `current` paints differently from `previous` when both are
+/// painted at the same scroll offset. `to_window` moves a command's document-space bounds into
+/// the window, given whether the command is inside a fixed group.
+///
+/// Returns `None` when the whole window has to be repainted: an opacity, fixed or transform group
+/// was added, removed or changed, or the change is inside a transform group.
+pub fn display_list_damage(
+    previous: &DisplayList,
+    current: &DisplayList,
+    measurer: &dyn TextMeasurer,
+    to_window: impl Fn(Rect, bool) -> Rect,
+) -> Option<Vec<Rect>> {
+    let (previous, current) = (&previous.commands, &current.commands);
+    let prefix = previous
+        .iter()
+        .zip(current)
+        .take_while(|(previous, current)| previous == current)
+        .count();
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(current[prefix..].iter().rev())
+        .take_while(|(previous, current)| previous == current)
+        .count();
+    let changed = previous[prefix..previous.len() - suffix]
+        .iter()
+        .chain(&current[prefix..current.len() - suffix]);
+
+    let fixed = current[..prefix]
+        .iter()
+        .fold(0usize, |depth, command| match command {
+            DisplayCommand::PushFixed => depth.saturating_add(1),
+            DisplayCommand::PopFixed => depth.saturating_sub(1),
+            _ => depth,
+        })
+        > 0;
+    let transformed = current[..prefix]
+        .iter()
+        .fold(0usize, |depth, command| match command {
+            DisplayCommand::PushTransform(_) => depth.saturating_add(1),
+            DisplayCommand::PopTransform => depth.saturating_sub(1),
+            _ => depth,
+        })
+        > 0;
+    if transformed {
+        return None;
+    }
+    let mut damage = Vec::new();
+    for command in changed {
+        let bounds = command_bounds(command, measurer)?;
+        damage.push(to_window(bounds, fixed));
+    }
+    Some(merge_damage(damage))
+}
+
+/// The area `command` paints, in document space, padded for antialiasing. `None` for the
+/// commands that open and close groups.
+pub fn command_bounds(command: &DisplayCommand, measurer: &dyn TextMeasurer) -> Option<Rect> {
+    let (top, bottom) = vertical_extent(command, measurer)?;
+    let (x, width) = match command {
+        DisplayCommand::Rect(rect) => (rect.x_px, rect.width_px),
+        DisplayCommand::LinearGradientRect(rect) => (rect.x_px, rect.width_px),
+        DisplayCommand::RoundedRect(rect) => (rect.x_px, rect.width_px),
+        DisplayCommand::RoundedRectBorder(rect) => {
+            let half = rect.border_width_px.max(0) / 2 + 1;
+            (
+                rect.x_px.saturating_sub(half),
+                rect.width_px.saturating_add(half.saturating_mul(2)),
+            )
+        }
+        DisplayCommand::Text(text) => (
+            text.x_px,
+            measurer
+                .text_width_px(&text.text, text.style)
+                .unwrap_or(i32::MAX / 2),
+        ),
+        DisplayCommand::Image(image) => (image.x_px, image.width_px),
+        DisplayCommand::Svg(svg) => (svg.x_px, svg.width_px),
+        _ => return None,
+    };
+    Some(Rect {
+        x: x.saturating_sub(PAD_PX),
+        y: top,
+        width: width.max(0).saturating_add(PAD_PX * 2),
+        height: bottom.saturating_sub(top),
+    })
+}
+
+/// The rows `command` paints, as `(top, bottom)` in document space, padded for antialiasing.
+/// Cheaper than [`command_bounds`], as text isn't measured.
+pub fn vertical_extent(
+    command: &DisplayCommand,
+    measurer: &dyn TextMeasurer,
+) -> Option<(i32, i32)> {
+    let (y, height) = match command {
+        DisplayCommand::Rect(rect) => (rect.y_px, rect.height_px),
+        DisplayCommand::LinearGradientRect(rect) => (rect.y_px, rect.height_px),
+        DisplayCommand::RoundedRect(rect) => (rect.y_px, rect.height_px),
+        DisplayCommand::RoundedRectBorder(rect) => {
+            let half = rect.border_width_px.max(0) / 2 + 1;
+            (
+                rect.y_px.saturating_sub(half),
+                rect.height_px.saturating_add(half.saturating_mul(2)),
+            )
+        }
+        DisplayCommand::Text(text) => {
+            // The underline sits a pixel below the baseline.
+            let metrics = measurer.font_metrics_px(text.style);
+            (
+                text.y_px.saturating_sub(metrics.ascent_px),
+                metrics.line_height_px().saturating_add(2),
+            )
+        }
+        DisplayCommand::Image(image) => (image.y_px, image.height_px),
+        DisplayCommand::Svg(svg) => (svg.y_px, svg.height_px),
+        _ => return None,
+    };
+    Some((
+        y.saturating_sub(PAD_PX),
+        y.saturating_add(height.max(0)).saturating_add(PAD_PX),
+    ))
+}
+
+/// Drops empty rectangles, merges overlapping ones, and falls back to a single bounding
+/// rectangle when too many remain.
+pub fn merge_damage(rects: Vec<Rect>) -> Vec<Rect> {
+    let mut merged: Vec<Rect> = Vec::new();
+    for mut rect in rects.into_iter().filter(|rect| !rect.is_empty()) {
+        while let Some(index) = merged
+            .iter()
+            .position(|other| !other.intersection(rect).is_empty())
+        {
+            rect = rect.union(merged.swap_remove(index));
+        }
+        merged.push(rect);
+    }
+    if merged.len() > MAX_DAMAGE_RECTS {
+        let bounds = merged
+            .iter()
+            .fold(Rect::default(), |bounds, rect| bounds.union(*rect));
+        return vec![bounds];
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geom::Color;
+    use crate::render::{DrawRect, DrawText, FontMetricsPx, TextStyle};
+
+    struct FixedMeasurer;
+
+    impl TextMeasurer for FixedMeasurer {
+        fn font_metrics_px(&self, _style: TextStyle) -> FontMetricsPx {
+            FontMetricsPx {
+                ascent_px: 12,
+                descent_px: 4,
+            }
+        }
+
+        fn text_width_px(&self, text: &str, _style: TextStyle) -> Result<i32, String> {
+            Ok(text.len() as i32 * 8)
+        }
+    }
+
+    fn rect(y_px: i32, color: Color) -> DisplayCommand {
+        DisplayCommand::Rect(DrawRect {
+            x_px: 10,
+            y_px,
+            width_px: 100,
+            height_px: 20,
+            color,
+        })
+    }
+
+    fn text(y_px: i32, text: &str) -> DisplayCommand {
+        DisplayCommand::Text(DrawText {
+            x_px: 10,
+            y_px,
+            text: text.to_owned(),
+            style: TextStyle::default(),
+        })
+    }
+
+    #[test]
+    fn damages_only_the_commands_that_changed() {
+        let previous = DisplayList {
+            commands: vec![
+                rect(0, Color::WHITE),
+                text(100, "Loading"),
+                rect(200, Color::BLACK),
+            ],
+        };
+        let current = DisplayList {
+            commands: vec![
+                rect(0, Color::WHITE),
+                text(100, "Done"),
+                rect(200, Color::BLACK),
+            ],
+        };
+        let scrolled = |rect: Rect, fixed: bool| Rect {
+            y: if fixed { rect.y } else { rect.y - 50 },
+            ..rect
+        };
+
+        let damage = display_list_damage(&previous, &current, &FixedMeasurer, scrolled);
+        // "Loading" and "Done" overlap, so they merge into one rectangle.
+        assert_eq!(
+            damage,
+            Some(vec![Rect {
+                x: 8,
+                y: 36,
+                width: 60,
+                height: 22,
+            }])
+        );
+        assert_eq!(
+            display_list_damage(&current, &current, &FixedMeasurer, scrolled),
+            Some(Vec::new())
+        );
+
+        let mut grouped = current.clone();
+        grouped.commands.insert(1, DisplayCommand::PushOpacity(128));
+        grouped.commands.push(DisplayCommand::PopOpacity(128));
+        assert_eq!(
+            display_list_damage(&current, &grouped, &FixedMeasurer, scrolled),
+            None
+        );
+    }
+
+    #[test]
+    fn collapses_scattered_damage_into_one_rectangle() {
+        let spread = (0..20)
+            .map(|index| Rect {
+                x: 0,
+                y: index * 100,
+                width: 10,
+                height: 10,
+            })
+            .collect();
+        assert_eq!(
+            merge_damage(spread),
+            vec![Rect {
+                x: 0,
+                y: 0,
+                width: 10,
+                height: 1910,
+            }]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use super::{DisplayCommand, DisplayList, TextStyle};
+use crate::geom::Color;
+use crate::style::{FontFamily, GradientDirection};
+use std::fmt::Write as _;
+
+impl DisplayList {
+    /// One line per command, with the commands between a push and its pop indented, for golden
+    /// files. Positions and sizes are in CSS px; colors are `#rrggbb`, or `#rrggbbaa` when not
+    /// opaque.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let mut depth = 0usize;
+        for command in &self.commands {
+            if matches!(
+                command,
+                DisplayCommand::PopOpacity(_)
+                    | DisplayCommand::PopFixed
+                    | DisplayCommand::PopTransform
+            ) {
+                depth = depth.saturating_sub(1);
+            }
+            out.push_str(&"  ".repeat(depth));
+            write_command(&mut out, command);
+            out.push('\n');
+            if matches!(
+                command,
+                DisplayCommand::PushOpacity(_)
+                    | DisplayCommand::PushFixed
+                    | DisplayCommand::PushTransform(_)
+            ) {
+                depth += 1;
+            }
+        }
+        out
+    }
+}
+
+fn write_command(out: &mut String, command: &DisplayCommand) {
+    let _ = match command {
+        DisplayCommand::Rect(rect) => write!(
+            out,
+            "rect {},{} {}x{} {}",
+            rect.x_px,
+            rect.y_px,
+            rect.width_px,
+            rect.height_px,
+            hex(rect.color)
+        ),
+        DisplayCommand::LinearGradientRect(gradient) => write!(
+            out,
+            "gradient {},{} {}x{} {} {} {}",
+            gradient.x_px,
+            gradient.y_px,
+            gradient.width_px,
+            gradient.height_px,
+            match gradient.direction {
+                GradientDirection::TopToBottom => "to-bottom",
+                GradientDirection::BottomToTop => "to-top",
+                GradientDirection::LeftToRight => "to-right",
+                GradientDirection::RightToLeft => "to-left",
+            },
+            hex(gradient.start_color),
+            hex(gradient.end_color)
+        ),
+        DisplayCommand::RoundedRect(rect) => write!(
+            out,
+            "rounded-rect {},{} {}x{} radius={} {}",
+            rect.x_px,
+            rect.y_px,
+            rect.width_px,
+            rect.height_px,
+            rect.radius_px,
+            hex(rect.color)
+        ),
+        DisplayCommand::RoundedRectBorder(border) => write!(
+            out,
+            "rounded-border {},{} {}x{} radius={} width={} {}",
+            border.x_px,
+            border.y_px,
+            border.width_px,
+            border.height_px,
+            border.radius_px,
+            border.border_width_px,
+            hex(border.color)
+        ),
+        DisplayCommand::Text(text) => write!(
+            out,
+            "text {},{} {:?} {}",
+            text.x_px,
+            text.y_px,
+            text.text,
+            text_style(text.style)
+        ),
+        DisplayCommand::Image(image) => write!(
+            out,
+            "image {},{} {}x{} source={}x{} opacity={}",
+            image.x_px,
+            image.y_px,
+            image.width_px,
+            image.height_px,
+            image.image.width,
+            image.image.height,
+            image.opacity
+        ),
+        DisplayCommand::Svg(svg) => write!(
+            out,
+            "svg {},{} {}x{} opacity={}",
+            svg.x_px, svg.y_px, svg.width_px, svg.height_px, svg.opacity
+        ),
+        DisplayCommand::PushOpacity(opacity) => write!(out, "push-opacity {opacity}"),
+        DisplayCommand::PopOpacity(opacity) => write!(out, "pop-opacity {opacity}"),
+        DisplayCommand::PushFixed => write!(out, "push-fixed"),
+        DisplayCommand::PopFixed => write!(out, "pop-fixed"),
+        DisplayCommand::PushTransform(transform) => write!(
+            out,
+            "push-transform {} {} {} {} {} {}",
+            transform.a, transform.b, transform.c, transform.d, transform.e, transform.f
+        ),
+        DisplayCommand::PopTransform => write!(out, "pop-transform"),
+    };
+}
+
+fn text_style(style: TextStyle) -> String {
+    let mut out = format!(
+        "{} {}px",
+        match style.font_family {
+            FontFamily::SansSerif => "sans-serif",
+            FontFamily::Serif => "serif",
+            FontFamily::Monospace => "monospace",
+        },
+        style.font_size_px
+    );
+    if style.bold {
+        out.push_str(" bold");
+    }
+    if style.underline {
+        out.push_str(" underline");
+    }
+    if style.letter_spacing_px != 0 {
+        let _ = write!(out, " letter-spacing={}px", style.letter_spacing_px);
+    }
+    let _ = write!(out, " {}", hex(style.color));
+    out
+}
+
+fn hex(color: Color) -> String {
+    if color.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            color.r, color.g, color.b, color.a
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{DrawRect, DrawText};
+
+    #[test]
+    fn writes_a_line_per_command_and_indents_groups() {
+        let list = DisplayList {
+            commands: vec![
+                DisplayCommand::Rect(DrawRect {
+                    x_px: 0,
+                    y_px: 0,
+                    width_px: 800,
+                    height_px: 600,
+                    color: Color::WHITE,
+                }),
+                DisplayCommand::PushOpacity(128),
+                DisplayCommand::Text(DrawText {
+                    x_px: 8,
+                    y_px: 24,
+                    text: "Say \"hi\"".to_owned(),
+                    style: TextStyle {
+                        bold: true,
+                        color: Color {
+                            r: 0x12,
+                            g: 0x34,
+                            b: 0x56,
+                            a: 0x80,
+                        },
+                        ..TextStyle::default()
+                    },
+                }),
+                DisplayCommand::PopOpacity(128),
+            ],
+        };
+        assert_eq!(
+            list.to_text(),
+            "rect 0,0 800x600 #ffffff\n\
+             push-opacity 128\n\
+             \x20 text 8,24 \"Say \\\"hi\\\"\" sans-serif 16px bold #12345680\n\
+             pop-opacity 128\n"
+        );
+    }
+}
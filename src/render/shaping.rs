@@ -0,0 +1,232 @@
+//! The shaping text goes through before it is measured and drawn, so every backend measures
+//! and paints the same characters: Arabic letters take the form their neighbours join them with,
+//! and combining marks stay with the character they sit on.
+
+use std::borrow::Cow;
+
+/// How an Arabic letter joins the letters either side of it, in logical order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Joining {
+    /// Joins neither side, like hamza.
+    None,
+    /// Joins only the letter before it, like alef.
+    Right,
+    /// Joins both sides.
+    Dual,
+    /// Tatweel, which joins both sides and has no forms of its own.
+    Causing,
+}
+
+/// An Arabic letter's joining type and its presentation forms: isolated, final, initial and
+/// medial, in that order, as many as it has.
+fn arabic_forms(ch: char) -> Option<(Joining, &'static [u32])> {
+    const FORMS: &[(u32, Joining, &[u32])] = &[
+        (0x0621, Joining::None, &[0xFE80]),
+        (0x0622, Joining::Right, &[0xFE81, 0xFE82]),
+        (0x0623, Joining::Right, &[0xFE83, 0xFE84]),
+        (0x0624, Joining::Right, &[0xFE85, 0xFE86]),
+        (0x0625, Joining::Right, &[0xFE87, 0xFE88]),
+        (0x0626, Joining::Dual, &[0xFE89, 0xFE8A, 0xFE8B, 0xFE8C]),
+        (0x0627, Joining::Right, &[0xFE8D, 0xFE8E]),
+        (0x0628, Joining::Dual, &[0xFE8F, 0xFE90, 0xFE91, 0xFE92]),
+        (0x0629, Joining::Right, &[0xFE93, 0xFE94]),
+        (0x062A, Joining::Dual, &[0xFE95, 0xFE96, 0xFE97, 0xFE98]),
+        (0x062B, Joining::Dual, &[0xFE99, 0xFE9A, 0xFE9B, 0xFE9C]),
+        (0x062C, Joining::Dual, &[0xFE9D, 0xFE9E, 0xFE9F, 0xFEA0]),
+        (0x062D, Joining::Dual, &[0xFEA1, 0xFEA2, 0xFEA3, 0xFEA4]),
+        (0x062E, Joining::Dual, &[0xFEA5, 0xFEA6, 0xFEA7, 0xFEA8]),
+        (0x062F, Joining::Right, &[0xFEA9, 0xFEAA]),
+        (0x0630, Joining::Right, &[0xFEAB, 0xFEAC]),
+        (0x0631, Joining::Right, &[0xFEAD, 0xFEAE]),
+        (0x0632, Joining::Right, &[0xFEAF, 0xFEB0]),
+        (0x0633, Joining::Dual, &[0xFEB1, 0xFEB2, 0xFEB3, 0xFEB4]),
+        (0x0634, Joining::Dual, &[0xFEB5, 0xFEB6, 0xFEB7, 0xFEB8]),
+        (0x0635, Joining::Dual, &[0xFEB9, 0xFEBA, 0xFEBB, 0xFEBC]),
+        (0x0636, Joining::Dual, &[0xFEBD, 0xFEBE, 0xFEBF, 0xFEC0]),
+        (0x0637, Joining::Dual, &[0xFEC1, 0xFEC2, 0xFEC3, 0xFEC4]),
+        (0x0638, Joining::Dual, &[0xFEC5, 0xFEC6, 0xFEC7, 0xFEC8]),
+        (0x0639, Joining::Dual, &[0xFEC9, 0xFECA, 0xFECB, 0xFECC]),
+        (0x063A, Joining::Dual, &[0xFECD, 0xFECE, 0xFECF, 0xFED0]),
+        (0x0640, Joining::Causing, &[]),
+        (0x0641, Joining::Dual, &[0xFED1, 0xFED2, 0xFED3, 0xFED4]),
+        (0x0642, Joining::Dual, &[0xFED5, 0xFED6, 0xFED7, 0xFED8]),
+        (0x0643, Joining::Dual, &[0xFED9, 0xFEDA, 0xFEDB, 0xFEDC]),
+        (0x0644, Joining::Dual, &[0xFEDD, 0xFEDE, 0xFEDF, 0xFEE0]),
+        (0x0645, Joining::Dual, &[0xFEE1, 0xFEE2, 0xFEE3, 0xFEE4]),
+        (0x0646, Joining::Dual, &[0xFEE5, 0xFEE6, 0xFEE7, 0xFEE8]),
+        (0x0647, Joining::Dual, &[0xFEE9, 0xFEEA, 0xFEEB, 0xFEEC]),
+        (0x0648, Joining::Right, &[0xFEED, 0xFEEE]),
+        (0x0649, Joining::Dual, &[0xFEEF, 0xFEF0, 0xFBE8, 0xFBE9]),
+        (0x064A, Joining::Dual, &[0xFEF1, 0xFEF2, 0xFEF3, 0xFEF4]),
+        (0x067E, Joining::Dual, &[0xFB56, 0xFB57, 0xFB58, 0xFB59]),
+        (0x0686, Joining::Dual, &[0xFB7A, 0xFB7B, 0xFB7C, 0xFB7D]),
+        (0x0698, Joining::Right, &[0xFB8A, 0xFB8B]),
+        (0x06A9, Joining::Dual, &[0xFB8E, 0xFB8F, 0xFB90, 0xFB91]),
+        (0x06AF, Joining::Dual, &[0xFB92, 0xFB93, 0xFB94, 0xFB95]),
+        (0x06CC, Joining::Dual, &[0xFBFC, 0xFBFD, 0xFBFE, 0xFBFF]),
+    ];
+    let code = u32::from(ch);
+    FORMS
+        .binary_search_by_key(&code, |&(base, _, _)| base)
+        .ok()
+        .map(|index| (FORMS[index].1, FORMS[index].2))
+}
+
+/// The ligature lam takes with the alef after it, isolated and final.
+fn lam_alef(alef: char) -> Option<[u32; 2]> {
+    match alef {
+        '\u{0622}' => Some([0xFEF5, 0xFEF6]),
+        '\u{0623}' => Some([0xFEF7, 0xFEF8]),
+        '\u{0625}' => Some([0xFEF9, 0xFEFA]),
+        '\u{0627}' => Some([0xFEFB, 0xFEFC]),
+        _ => None,
+    }
+}
+
+/// Whether `ch` is drawn over the character before it rather than after it: a combining mark,
+/// an Indic vowel sign or virama, a joiner or a variation selector.
+pub fn is_combining_mark(ch: char) -> bool {
+    let code = u32::from(ch);
+    if (0x0900..=0x0DFF).contains(&code) {
+        return matches!(code & 0x7F, 0x00..=0x03 | 0x3A..=0x4F | 0x51..=0x57 | 0x62..=0x63);
+    }
+    matches!(
+        code,
+        0x0300..=0x036F
+            | 0x0483..=0x0489
+            | 0x0591..=0x05BD
+            | 0x05BF
+            | 0x05C1..=0x05C2
+            | 0x05C4..=0x05C5
+            | 0x05C7
+            | 0x0610..=0x061A
+            | 0x064B..=0x065F
+            | 0x0670
+            | 0x06D6..=0x06DC
+            | 0x06DF..=0x06E4
+            | 0x06E7..=0x06E8
+            | 0x06EA..=0x06ED
+            | 0x0E31
+            | 0x0E34..=0x0E3A
+            | 0x0E47..=0x0E4E
+            | 0x1AB0..=0x1AFF
+            | 0x1DC0..=0x1DFF
+            | 0x200C..=0x200D
+            | 0x20D0..=0x20FF
+            | 0xFE00..=0xFE0F
+            | 0xFE20..=0xFE2F
+    )
+}
+
+/// Splits `text` into the clusters it is drawn in: each character along with the combining
+/// marks after it. Letter spacing goes between clusters, never inside one.
+pub fn clusters(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let mut chars = rest.char_indices();
+        chars.next()?;
+        let end = chars
+            .find(|&(_, ch)| !is_combining_mark(ch))
+            .map_or(rest.len(), |(index, _)| index);
+        let (cluster, tail) = rest.split_at(end);
+        rest = tail;
+        Some(cluster)
+    })
+}
+
+/// `text` with its Arabic letters in the forms their neighbours join them with, and lam before
+/// alef as their ligature. Text without Arabic comes back as it is.
+pub fn shape(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(|ch| arabic_forms(ch).is_some()) {
+        return Cow::Borrowed(text);
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let joining = |index: usize| arabic_forms(chars[index]).map(|(joining, _)| joining);
+    let neighbour = |mut index: usize, forward: bool| loop {
+        index = if forward {
+            index.checked_add(1).filter(|&next| next < chars.len())?
+        } else {
+            index.checked_sub(1)?
+        };
+        if !is_combining_mark(chars[index]) {
+            return Some(index);
+        }
+    };
+    let joins_before = |index: usize| {
+        matches!(
+            joining(index),
+            Some(Joining::Right | Joining::Dual | Joining::Causing)
+        ) && neighbour(index, false)
+            .is_some_and(|before| matches!(joining(before), Some(Joining::Dual | Joining::Causing)))
+    };
+    let joins_after = |index: usize| {
+        matches!(joining(index), Some(Joining::Dual | Joining::Causing))
+            && neighbour(index, true).is_some_and(|after| {
+                matches!(
+                    joining(after),
+                    Some(Joining::Right | Joining::Dual | Joining::Causing)
+                )
+            })
+    };
+
+    let mut shaped = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let ch = chars[index];
+        let before = joins_before(index);
+        if ch == '\u{0644}'
+            && let Some(ligature) = chars.get(index + 1).and_then(|&alef| lam_alef(alef))
+        {
+            shaped.extend(char::from_u32(ligature[usize::from(before)]));
+            index += 2;
+            continue;
+        }
+        let form = match arabic_forms(ch) {
+            Some((_, forms)) if !forms.is_empty() => {
+                let wanted = match (before, joins_after(index)) {
+                    (true, true) => 3,
+                    (false, true) => 2,
+                    (true, false) => 1,
+                    (false, false) => 0,
+                };
+                forms.get(wanted).and_then(|&form| char::from_u32(form))
+            }
+            _ => None,
+        };
+        shaped.push(form.unwrap_or(ch));
+        index += 1;
+    }
+    Cow::Owned(shaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arabic_letters_join_and_marks_stay_with_their_letter() {
+        // ب ي ت: initial, medial, final.
+        assert_eq!(
+            shape("\u{0628}\u{064A}\u{062A}"),
+            "\u{FE91}\u{FEF4}\u{FE96}"
+        );
+        // د joins only the letter before it, so the ر after it stands alone.
+        assert_eq!(
+            shape("\u{0628}\u{062F}\u{0631}"),
+            "\u{FE91}\u{FEAA}\u{FEAD}"
+        );
+        // A mark between two letters doesn't break the join.
+        assert_eq!(
+            shape("\u{0628}\u{064E}\u{062A}"),
+            "\u{FE91}\u{064E}\u{FE96}"
+        );
+        // Lam-alef, alone and after a letter that joins it.
+        assert_eq!(shape("\u{0644}\u{0627}"), "\u{FEFB}");
+        assert_eq!(shape("\u{0643}\u{0644}\u{0627}"), "\u{FEDB}\u{FEFC}");
+        assert!(matches!(shape("plain"), Cow::Borrowed("plain")));
+
+        let clustered: Vec<&str> = clusters("e\u{0301}a\u{0915}\u{093F}").collect();
+        assert_eq!(clustered, vec!["e\u{0301}", "a", "\u{0915}\u{093F}"]);
+        assert_eq!(clusters("").count(), 0);
+    }
+}
@@ -0,0 +1,968 @@
+pub(crate) mod path;
+pub(crate) mod raster;
+mod xml;
+
+use crate::geom::Color;
+use crate::image::{Argb32Image, check_image_dimensions};
+use path::{LineCap, LineJoin, PathCommand, Point, StrokeStyle, Transform};
+use raster::{Canvas, FillRule};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use xml::XmlElement;
+
+/// Curves are flattened to within this many device pixels of the true outline.
+const TOLERANCE_PX: f64 = 0.2;
+/// How many `<use>` references may be followed in a chain; also breaks reference cycles.
+const MAX_USE_DEPTH: usize = 16;
+/// Elements drawn per document, counting each `<use>` expansion, so references that fan out
+/// cannot make rendering take exponential time.
+const MAX_RENDERED_ELEMENTS: usize = 100_000;
+/// Group opacity needs an offscreen canvas per level; groups nested deeper draw opaquely.
+const MAX_OPACITY_LAYERS: usize = 8;
+/// Upper bound on the pixel data kept by [`rasterize_cached`].
+const CACHE_BYTES: usize = 64 * 1024 * 1024;
+/// Bézier handle length for a quarter ellipse, as a fraction of the radius.
+const KAPPA: f64 = 0.552_284_749_8;
+
+/// Elements that only draw when referenced, or that draw nothing this renderer supports.
+const NOT_RENDERED: &[&str] = &[
+    "clipPath",
+    "defs",
+    "desc",
+    "foreignObject",
+    "image",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "pattern",
+    "radialGradient",
+    "script",
+    "style",
+    "symbol",
+    "text",
+    "title",
+];
+
+/// Renders an SVG document into a `width_px` × `height_px` image. The document's viewBox (or,
+/// without one, its width and height) is fitted to the image following `preserveAspectRatio`.
+///
+/// Supported: `rect`, `circle`, `ellipse`, `line`, `polyline`, `polygon` and `path`, grouped
+/// with `g`, `a`, nested `svg` and `use`; solid fill and stroke with their opacities, rules,
+/// caps and joins; group opacity and transforms. Gradients paint in their first stop's color.
+pub fn rasterize(svg_xml: &str, width_px: u32, height_px: u32) -> Result<Argb32Image, String> {
+    check_image_dimensions(width_px, height_px).map_err(|err| err.to_string())?;
+    let root = xml::parse_document(svg_xml.trim())?;
+    if root.local_name() != "svg" {
+        return Err(format!("Expected <svg> but found <{}>", root.name));
+    }
+
+    let mut ids = HashMap::new();
+    collect_ids(&root, &mut ids);
+    let renderer = Renderer {
+        root: &root,
+        ids,
+        remaining_elements: Cell::new(MAX_RENDERED_ELEMENTS),
+    };
+
+    let target = Viewport {
+        width: f64::from(width_px),
+        height: f64::from(height_px),
+    };
+    let view_box = parse_view_box(root.attr("viewBox")).or_else(|| {
+        // Without a viewBox, an absolute width and height still give the drawing a size to fit.
+        let width = parse_length(root.attr("width"), 0.0).filter(|width| *width > 0.0)?;
+        let height = parse_length(root.attr("height"), 0.0).filter(|height| *height > 0.0)?;
+        Some(ViewBox {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height,
+        })
+    });
+    let (transform, viewport) = match view_box {
+        Some(view_box) => (
+            view_box.fit(target, root.attr("preserveAspectRatio")),
+            view_box.viewport(),
+        ),
+        None => (Transform::IDENTITY, target),
+    };
+
+    let mut canvas = Canvas::new(width_px, height_px);
+    let context = Context {
+        transform,
+        style: Style::default(),
+        viewport,
+        use_depth: 0,
+        opacity_layers: 0,
+    };
+    renderer.render_element(&root, &mut canvas, &context);
+    canvas.into_image()
+}
+
+/// [`rasterize`], reusing the image from an earlier call with the same document and size so
+/// that repainting a page does not render each of its SVGs again.
+pub fn rasterize_cached(
+    svg_xml: &str,
+    width_px: u32,
+    height_px: u32,
+) -> Result<Arc<Argb32Image>, String> {
+    thread_local! {
+        static CACHE: RefCell<Vec<(CacheKey, Arc<Argb32Image>)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    let mut hasher = DefaultHasher::new();
+    svg_xml.hash(&mut hasher);
+    let key = CacheKey {
+        hash: hasher.finish(),
+        len: svg_xml.len(),
+        width: width_px,
+        height: height_px,
+    };
+
+    let cached = CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let index = cache.iter().position(|(entry, _)| *entry == key)?;
+        // Most recently used entries live at the end.
+        let entry = cache.remove(index);
+        let image = entry.1.clone();
+        cache.push(entry);
+        Some(image)
+    });
+    if let Some(image) = cached {
+        return Ok(image);
+    }
+
+    let image = Arc::new(rasterize(svg_xml, width_px, height_px)?);
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if image.data.len() > CACHE_BYTES {
+            return;
+        }
+        cache.push((key, image.clone()));
+        let mut total: usize = cache.iter().map(|(_, image)| image.data.len()).sum();
+        while total > CACHE_BYTES {
+            let (_, evicted) = cache.remove(0);
+            total -= evicted.data.len();
+        }
+    });
+    Ok(image)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CacheKey {
+    hash: u64,
+    len: usize,
+    width: u32,
+    height: u32,
+}
+
+fn collect_ids<'a>(element: &'a XmlElement, ids: &mut HashMap<&'a str, &'a XmlElement>) {
+    if let Some(id) = element.attr("id") {
+        ids.entry(id).or_insert(element);
+    }
+    for child in &element.children {
+        collect_ids(child, ids);
+    }
+}
+
+/// The size percentages resolve against, in user units.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Viewport {
+    width: f64,
+    height: f64,
+}
+
+impl Viewport {
+    /// The reference for lengths that are neither horizontal nor vertical, like radii.
+    fn diagonal(self) -> f64 {
+        ((self.width * self.width + self.height * self.height) / 2.0).sqrt()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ViewBox {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl ViewBox {
+    fn viewport(self) -> Viewport {
+        Viewport {
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Maps the view box onto `(0, 0)`–`target` as `preserveAspectRatio` says.
+    fn fit(self, target: Viewport, preserve_aspect_ratio: Option<&str>) -> Transform {
+        let mut tokens = preserve_aspect_ratio
+            .unwrap_or("")
+            .split_ascii_whitespace()
+            .filter(|token| *token != "defer");
+        let align = tokens.next().unwrap_or("xMidYMid");
+        let slice = tokens.next() == Some("slice");
+
+        let scale_x = target.width / self.width;
+        let scale_y = target.height / self.height;
+        let to_origin = Transform::translate(-self.x, -self.y);
+        if align == "none" {
+            return Transform::scale(scale_x, scale_y).then(to_origin);
+        }
+        let scale = if slice {
+            scale_x.max(scale_y)
+        } else {
+            scale_x.min(scale_y)
+        };
+        let extra_x = target.width - self.width * scale;
+        let extra_y = target.height - self.height * scale;
+        let offset = |part: Option<&str>, extra: f64| match part.map(|part| &part[1..]) {
+            Some("Min") => 0.0,
+            Some("Max") => extra,
+            _ => extra / 2.0,
+        };
+        let (x_part, y_part) = if align.len() == 8 && align.is_ascii() {
+            (Some(&align[..4]), Some(&align[4..]))
+        } else {
+            (None, None)
+        };
+        Transform::translate(offset(x_part, extra_x), offset(y_part, extra_y))
+            .then(Transform::scale(scale, scale))
+            .then(to_origin)
+    }
+}
+
+/// Maps an inline `<svg>` element's `viewBox` onto a `width` × `height` box as its
+/// `preserveAspectRatio` says, or `None` when the viewBox is missing or invalid.
+pub(crate) fn fit_view_box(
+    view_box: Option<&str>,
+    preserve_aspect_ratio: Option<&str>,
+    width: f64,
+    height: f64,
+) -> Option<Transform> {
+    let target = Viewport { width, height };
+    Some(parse_view_box(view_box)?.fit(target, preserve_aspect_ratio))
+}
+
+fn parse_view_box(value: Option<&str>) -> Option<ViewBox> {
+    match path::parse_number_list(value?)?.as_slice() {
+        &[x, y, width, height] if width > 0.0 && height > 0.0 => Some(ViewBox {
+            x,
+            y,
+            width,
+            height,
+        }),
+        _ => None,
+    }
+}
+
+/// Parses a length in user units. Percentages resolve against `reference`; absolute units use
+/// 96 pixels per inch and font-relative ones a 16px font.
+fn parse_length(value: Option<&str>, reference: f64) -> Option<f64> {
+    const UNITS: &[(&str, f64)] = &[
+        ("px", 1.0),
+        ("pt", 96.0 / 72.0),
+        ("pc", 16.0),
+        ("mm", 96.0 / 25.4),
+        ("cm", 96.0 / 2.54),
+        ("in", 96.0),
+        ("em", 16.0),
+        ("ex", 8.0),
+    ];
+    let value = value?.trim();
+    let (number, factor) = if let Some(number) = value.strip_suffix('%') {
+        (number, reference / 100.0)
+    } else {
+        UNITS
+            .iter()
+            .find_map(|(unit, factor)| Some((value.strip_suffix(unit)?, *factor)))
+            .unwrap_or((value, 1.0))
+    };
+    let number: f64 = number.trim().parse().ok()?;
+    Some(number * factor).filter(|length| length.is_finite())
+}
+
+fn parse_opacity(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let opacity = match value.strip_suffix('%') {
+        Some(percent) => percent.trim().parse::<f64>().ok()? / 100.0,
+        None => value.parse::<f64>().ok()?,
+    };
+    opacity.is_finite().then(|| opacity.clamp(0.0, 1.0))
+}
+
+pub(crate) fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim();
+    if let Some(color) = crate::style::parse_css_color(value) {
+        return Some(color);
+    }
+    let (r, g, b) = match value.to_ascii_lowercase().as_str() {
+        "transparent" => {
+            return Some(Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            });
+        }
+        "red" => (255, 0, 0),
+        "green" => (0, 128, 0),
+        "lime" => (0, 255, 0),
+        "blue" => (0, 0, 255),
+        "yellow" => (255, 255, 0),
+        "orange" => (255, 165, 0),
+        "purple" => (128, 0, 128),
+        "fuchsia" | "magenta" => (255, 0, 255),
+        "aqua" | "cyan" => (0, 255, 255),
+        "teal" => (0, 128, 128),
+        "navy" => (0, 0, 128),
+        "maroon" => (128, 0, 0),
+        "olive" => (128, 128, 0),
+        "silver" => (192, 192, 192),
+        "gray" | "grey" => (128, 128, 128),
+        "darkgray" | "darkgrey" => (169, 169, 169),
+        "lightgray" | "lightgrey" => (211, 211, 211),
+        "brown" => (165, 42, 42),
+        "pink" => (255, 192, 203),
+        "gold" => (255, 215, 0),
+        _ => return None,
+    };
+    Some(Color { r, g, b, a: 255 })
+}
+
+/// Presentation attributes followed by `style` declarations, so the latter win when applied in
+/// order.
+fn declarations(element: &XmlElement) -> Vec<(&str, &str)> {
+    let mut declarations: Vec<(&str, &str)> = element
+        .attributes
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    if let Some(style) = element.attr("style") {
+        for declaration in style.split(';') {
+            if let Some((name, value)) = declaration.split_once(':') {
+                let value = value.trim();
+                let value = value.strip_suffix("!important").unwrap_or(value);
+                declarations.push((name.trim(), value.trim()));
+            }
+        }
+    }
+    declarations
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Paint {
+    None,
+    Color(Color),
+    CurrentColor,
+}
+
+/// The inherited properties in effect for an element.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Style {
+    color: Color,
+    fill: Paint,
+    fill_opacity: f64,
+    fill_rule: FillRule,
+    stroke: Paint,
+    stroke_opacity: f64,
+    stroke_style: StrokeStyle,
+    visible: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            color: Color::BLACK,
+            fill: Paint::Color(Color::BLACK),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: Paint::None,
+            stroke_opacity: 1.0,
+            stroke_style: StrokeStyle {
+                width: 1.0,
+                cap: LineCap::Butt,
+                join: LineJoin::Miter,
+                miter_limit: 4.0,
+            },
+            visible: true,
+        }
+    }
+}
+
+impl Style {
+    fn resolve(&self, paint: Paint) -> Option<Color> {
+        match paint {
+            Paint::None => None,
+            Paint::Color(color) => Some(color),
+            Paint::CurrentColor => Some(self.color),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Context {
+    /// User space to device pixels.
+    transform: Transform,
+    style: Style,
+    viewport: Viewport,
+    use_depth: usize,
+    opacity_layers: usize,
+}
+
+struct Renderer<'a> {
+    root: &'a XmlElement,
+    ids: HashMap<&'a str, &'a XmlElement>,
+    remaining_elements: Cell<usize>,
+}
+
+impl Renderer<'_> {
+    fn render_element(&self, element: &XmlElement, canvas: &mut Canvas, parent: &Context) {
+        if NOT_RENDERED.contains(&element.local_name()) {
+            return;
+        }
+        let Some(remaining) = self.remaining_elements.get().checked_sub(1) else {
+            return;
+        };
+        self.remaining_elements.set(remaining);
+        self.render_styled(element, canvas, parent, Renderer::draw);
+    }
+
+    /// Applies the element's properties, transform and opacity around `draw`.
+    fn render_styled(
+        &self,
+        element: &XmlElement,
+        canvas: &mut Canvas,
+        parent: &Context,
+        draw: fn(&Self, &XmlElement, &mut Canvas, &Context),
+    ) {
+        let mut context = *parent;
+        let mut opacity = 1.0;
+        for (name, value) in declarations(element) {
+            let value = value.trim();
+            if value == "inherit" {
+                continue;
+            }
+            let style = &mut context.style;
+            match name {
+                "display" if value == "none" => return,
+                "opacity" => opacity = parse_opacity(value).unwrap_or(opacity),
+                "color" => style.color = parse_color(value).unwrap_or(style.color),
+                "fill" => style.fill = self.parse_paint(value).unwrap_or(style.fill),
+                "stroke" => style.stroke = self.parse_paint(value).unwrap_or(style.stroke),
+                "fill-opacity" => {
+                    style.fill_opacity = parse_opacity(value).unwrap_or(style.fill_opacity);
+                }
+                "stroke-opacity" => {
+                    style.stroke_opacity = parse_opacity(value).unwrap_or(style.stroke_opacity);
+                }
+                "fill-rule" => match value {
+                    "nonzero" => style.fill_rule = FillRule::NonZero,
+                    "evenodd" => style.fill_rule = FillRule::EvenOdd,
+                    _ => {}
+                },
+                "stroke-width" => {
+                    if let Some(width) = parse_length(Some(value), parent.viewport.diagonal())
+                        .filter(|width| *width >= 0.0)
+                    {
+                        style.stroke_style.width = width;
+                    }
+                }
+                "stroke-linecap" => match value {
+                    "butt" => style.stroke_style.cap = LineCap::Butt,
+                    "round" => style.stroke_style.cap = LineCap::Round,
+                    "square" => style.stroke_style.cap = LineCap::Square,
+                    _ => {}
+                },
+                "stroke-linejoin" => match value {
+                    "miter" | "miter-clip" | "arcs" => style.stroke_style.join = LineJoin::Miter,
+                    "round" => style.stroke_style.join = LineJoin::Round,
+                    "bevel" => style.stroke_style.join = LineJoin::Bevel,
+                    _ => {}
+                },
+                "stroke-miterlimit" => {
+                    if let Ok(limit) = value.parse::<f64>()
+                        && limit >= 1.0
+                    {
+                        style.stroke_style.miter_limit = limit;
+                    }
+                }
+                "visibility" => style.visible = value == "visible",
+                _ => {}
+            }
+        }
+        if let Some(transform) = element.attr("transform").and_then(Transform::parse_list) {
+            context.transform = context.transform.then(transform);
+        }
+
+        if opacity <= 0.0 {
+            return;
+        }
+        if opacity < 1.0 && context.opacity_layers < MAX_OPACITY_LAYERS {
+            context.opacity_layers += 1;
+            let mut layer = canvas.same_size();
+            draw(self, element, &mut layer, &context);
+            canvas.composite(&layer, opacity);
+        } else {
+            draw(self, element, canvas, &context);
+        }
+    }
+
+    fn draw(&self, element: &XmlElement, canvas: &mut Canvas, context: &Context) {
+        match element.local_name() {
+            "svg" if !std::ptr::eq(element, self.root) => {
+                let viewport = context.viewport;
+                let x = parse_length(element.attr("x"), viewport.width).unwrap_or(0.0);
+                let y = parse_length(element.attr("y"), viewport.height).unwrap_or(0.0);
+                let target = Viewport {
+                    width: parse_length(element.attr("width"), viewport.width)
+                        .unwrap_or(viewport.width),
+                    height: parse_length(element.attr("height"), viewport.height)
+                        .unwrap_or(viewport.height),
+                };
+                let mut inner = *context;
+                inner.transform = context.transform.then(Transform::translate(x, y));
+                inner.viewport = target;
+                if let Some(view_box) = parse_view_box(element.attr("viewBox")) {
+                    inner.transform = inner
+                        .transform
+                        .then(view_box.fit(target, element.attr("preserveAspectRatio")));
+                    inner.viewport = view_box.viewport();
+                }
+                self.draw_children(element, canvas, &inner);
+            }
+            "svg" | "g" | "a" => self.draw_children(element, canvas, context),
+            "switch" => {
+                if let Some(child) = element.children.first() {
+                    self.render_element(child, canvas, context);
+                }
+            }
+            "use" => self.draw_use(element, canvas, context),
+            _ => {
+                if let Some(commands) = shape_path(element, context.viewport) {
+                    paint_shape(&commands, canvas, context);
+                }
+            }
+        }
+    }
+
+    fn draw_children(&self, element: &XmlElement, canvas: &mut Canvas, context: &Context) {
+        for child in &element.children {
+            self.render_element(child, canvas, context);
+        }
+    }
+
+    fn draw_use(&self, element: &XmlElement, canvas: &mut Canvas, context: &Context) {
+        if context.use_depth >= MAX_USE_DEPTH {
+            return;
+        }
+        let Some(target) = element
+            .attr("href")
+            .or_else(|| element.attr("xlink:href"))
+            .and_then(|href| href.trim().strip_prefix('#'))
+            .and_then(|id| self.ids.get(id))
+        else {
+            return;
+        };
+
+        let viewport = context.viewport;
+        let x = parse_length(element.attr("x"), viewport.width).unwrap_or(0.0);
+        let y = parse_length(element.attr("y"), viewport.height).unwrap_or(0.0);
+        let mut inner = *context;
+        inner.transform = context.transform.then(Transform::translate(x, y));
+        inner.use_depth += 1;
+
+        if target.local_name() != "symbol" {
+            self.render_element(target, canvas, &inner);
+            return;
+        }
+        let size = Viewport {
+            width: parse_length(element.attr("width"), viewport.width).unwrap_or(viewport.width),
+            height: parse_length(element.attr("height"), viewport.height)
+                .unwrap_or(viewport.height),
+        };
+        inner.viewport = size;
+        if let Some(view_box) = parse_view_box(target.attr("viewBox")) {
+            inner.transform = inner
+                .transform
+                .then(view_box.fit(size, target.attr("preserveAspectRatio")));
+            inner.viewport = view_box.viewport();
+        }
+        self.render_styled(target, canvas, &inner, Renderer::draw_children);
+    }
+
+    fn parse_paint(&self, value: &str) -> Option<Paint> {
+        if value == "none" {
+            return Some(Paint::None);
+        }
+        if value.eq_ignore_ascii_case("currentColor") {
+            return Some(Paint::CurrentColor);
+        }
+        if let Some(rest) = value.strip_prefix("url(") {
+            let (reference, fallback) = rest.split_once(')')?;
+            let color = reference
+                .trim()
+                .trim_matches(['"', '\''])
+                .strip_prefix('#')
+                .and_then(|id| self.gradient_color(id, 0));
+            if let Some(color) = color {
+                return Some(Paint::Color(color));
+            }
+            let fallback = fallback.trim();
+            if fallback.is_empty() {
+                return Some(Paint::None);
+            }
+            return self.parse_paint(fallback);
+        }
+        parse_color(value).map(Paint::Color)
+    }
+
+    /// The first stop's color of gradient `id`, following `href` to a template gradient when it
+    /// has no stops of its own.
+    fn gradient_color(&self, id: &str, depth: usize) -> Option<Color> {
+        let gradient = self.ids.get(id)?;
+        if !matches!(gradient.local_name(), "linearGradient" | "radialGradient") {
+            return None;
+        }
+        let Some(stop) = gradient
+            .children
+            .iter()
+            .find(|child| child.local_name() == "stop")
+        else {
+            if depth >= MAX_USE_DEPTH {
+                return None;
+            }
+            let href = gradient
+                .attr("href")
+                .or_else(|| gradient.attr("xlink:href"))?;
+            return self.gradient_color(href.trim().strip_prefix('#')?, depth + 1);
+        };
+
+        let mut color = Color::BLACK;
+        let mut opacity = 1.0;
+        for (name, value) in declarations(stop) {
+            match name {
+                "stop-color" => color = parse_color(value).unwrap_or(color),
+                "stop-opacity" => opacity = parse_opacity(value).unwrap_or(opacity),
+                _ => {}
+            }
+        }
+        color.a = (f64::from(color.a) * opacity).round() as u8;
+        Some(color)
+    }
+}
+
+/// The outline of a basic shape or path in user units, or `None` when it draws nothing.
+fn shape_path(element: &XmlElement, viewport: Viewport) -> Option<Vec<PathCommand>> {
+    let horizontal = |name: &str| parse_length(element.attr(name), viewport.width);
+    let vertical = |name: &str| parse_length(element.attr(name), viewport.height);
+    match element.local_name() {
+        "rect" => {
+            let x = horizontal("x").unwrap_or(0.0);
+            let y = vertical("y").unwrap_or(0.0);
+            let width = horizontal("width").filter(|width| *width > 0.0)?;
+            let height = vertical("height").filter(|height| *height > 0.0)?;
+            let rx = horizontal("rx").filter(|rx| *rx >= 0.0);
+            let ry = vertical("ry").filter(|ry| *ry >= 0.0);
+            let (rx, ry) = match (rx, ry) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(radius), None) | (None, Some(radius)) => (radius, radius),
+                (None, None) => (0.0, 0.0),
+            };
+            Some(rect_path(
+                x,
+                y,
+                width,
+                height,
+                rx.min(width / 2.0),
+                ry.min(height / 2.0),
+            ))
+        }
+        "circle" => {
+            let radius = parse_length(element.attr("r"), viewport.diagonal())
+                .filter(|radius| *radius > 0.0)?;
+            let center = Point::new(
+                horizontal("cx").unwrap_or(0.0),
+                vertical("cy").unwrap_or(0.0),
+            );
+            Some(ellipse_path(center, radius, radius))
+        }
+        "ellipse" => {
+            let (rx, ry) = match (horizontal("rx"), vertical("ry")) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(radius), None) | (None, Some(radius)) => (radius, radius),
+                (None, None) => return None,
+            };
+            if rx <= 0.0 || ry <= 0.0 {
+                return None;
+            }
+            let center = Point::new(
+                horizontal("cx").unwrap_or(0.0),
+                vertical("cy").unwrap_or(0.0),
+            );
+            Some(ellipse_path(center, rx, ry))
+        }
+        "line" => Some(vec![
+            PathCommand::MoveTo(Point::new(
+                horizontal("x1").unwrap_or(0.0),
+                vertical("y1").unwrap_or(0.0),
+            )),
+            PathCommand::LineTo(Point::new(
+                horizontal("x2").unwrap_or(0.0),
+                vertical("y2").unwrap_or(0.0),
+            )),
+        ]),
+        name @ ("polyline" | "polygon") => {
+            let numbers = path::parse_number_list(element.attr("points")?)?;
+            let mut commands: Vec<PathCommand> = numbers
+                .chunks_exact(2)
+                .enumerate()
+                .map(|(index, pair)| {
+                    let point = Point::new(pair[0], pair[1]);
+                    if index == 0 {
+                        PathCommand::MoveTo(point)
+                    } else {
+                        PathCommand::LineTo(point)
+                    }
+                })
+                .collect();
+            if commands.is_empty() {
+                return None;
+            }
+            if name == "polygon" {
+                commands.push(PathCommand::Close);
+            }
+            Some(commands)
+        }
+        "path" => Some(path::parse_path_data(element.attr("d")?)),
+        _ => None,
+    }
+}
+
+pub(crate) fn rect_path(
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    rx: f64,
+    ry: f64,
+) -> Vec<PathCommand> {
+    let right = x + width;
+    let bottom = y + height;
+    if rx <= 0.0 || ry <= 0.0 {
+        return vec![
+            PathCommand::MoveTo(Point::new(x, y)),
+            PathCommand::LineTo(Point::new(right, y)),
+            PathCommand::LineTo(Point::new(right, bottom)),
+            PathCommand::LineTo(Point::new(x, bottom)),
+            PathCommand::Close,
+        ];
+    }
+    let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+    vec![
+        PathCommand::MoveTo(Point::new(x + rx, y)),
+        PathCommand::LineTo(Point::new(right - rx, y)),
+        PathCommand::CubicTo(
+            Point::new(right - rx + kx, y),
+            Point::new(right, y + ry - ky),
+            Point::new(right, y + ry),
+        ),
+        PathCommand::LineTo(Point::new(right, bottom - ry)),
+        PathCommand::CubicTo(
+            Point::new(right, bottom - ry + ky),
+            Point::new(right - rx + kx, bottom),
+            Point::new(right - rx, bottom),
+        ),
+        PathCommand::LineTo(Point::new(x + rx, bottom)),
+        PathCommand::CubicTo(
+            Point::new(x + rx - kx, bottom),
+            Point::new(x, bottom - ry + ky),
+            Point::new(x, bottom - ry),
+        ),
+        PathCommand::LineTo(Point::new(x, y + ry)),
+        PathCommand::CubicTo(
+            Point::new(x, y + ry - ky),
+            Point::new(x + rx - kx, y),
+            Point::new(x + rx, y),
+        ),
+        PathCommand::Close,
+    ]
+}
+
+fn ellipse_path(center: Point, rx: f64, ry: f64) -> Vec<PathCommand> {
+    let Point { x, y } = center;
+    let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+    vec![
+        PathCommand::MoveTo(Point::new(x + rx, y)),
+        PathCommand::CubicTo(
+            Point::new(x + rx, y + ky),
+            Point::new(x + kx, y + ry),
+            Point::new(x, y + ry),
+        ),
+        PathCommand::CubicTo(
+            Point::new(x - kx, y + ry),
+            Point::new(x - rx, y + ky),
+            Point::new(x - rx, y),
+        ),
+        PathCommand::CubicTo(
+            Point::new(x - rx, y - ky),
+            Point::new(x - kx, y - ry),
+            Point::new(x, y - ry),
+        ),
+        PathCommand::CubicTo(
+            Point::new(x + kx, y - ry),
+            Point::new(x + rx, y - ky),
+            Point::new(x + rx, y),
+        ),
+        PathCommand::Close,
+    ]
+}
+
+fn paint_shape(commands: &[PathCommand], canvas: &mut Canvas, context: &Context) {
+    let style = &context.style;
+    let scale = context.transform.max_scale();
+    if !(style.visible && scale.is_finite() && scale > 0.0) {
+        return;
+    }
+    let tolerance = TOLERANCE_PX / scale;
+    let polylines = path::flatten(commands, tolerance);
+    let to_device = |polygons: Vec<Vec<Point>>| -> Vec<Vec<Point>> {
+        polygons
+            .into_iter()
+            .map(|polygon| {
+                polygon
+                    .into_iter()
+                    .map(|point| context.transform.apply(point))
+                    .collect()
+            })
+            .collect()
+    };
+
+    if let Some(color) = style.resolve(style.fill) {
+        let outlines = polylines
+            .iter()
+            .map(|polyline| polyline.points.clone())
+            .collect();
+        canvas.fill(
+            &to_device(outlines),
+            style.fill_rule,
+            color,
+            style.fill_opacity,
+        );
+    }
+    if let Some(color) = style.resolve(style.stroke) {
+        let outlines = path::stroke_polygons(&polylines, &style.stroke_style, tolerance);
+        canvas.fill(
+            &to_device(outlines),
+            FillRule::NonZero,
+            color,
+            style.stroke_opacity,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Straight (unpremultiplied) RGBA at `(x, y)`.
+    fn pixel(image: &Argb32Image, x: u32, y: u32) -> [u8; 4] {
+        let at = (y * image.width + x) as usize * 4;
+        let (b, g, r, a) = (
+            image.data[at],
+            image.data[at + 1],
+            image.data[at + 2],
+            image.data[at + 3],
+        );
+        if a == 0 {
+            return [0, 0, 0, 0];
+        }
+        let unpremultiply = |channel: u8| (u32::from(channel) * 255 / u32::from(a)) as u8;
+        [unpremultiply(r), unpremultiply(g), unpremultiply(b), a]
+    }
+
+    #[test]
+    fn scales_the_view_box_to_the_image() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">
+            <rect x="5" width="5" height="10" fill="red"/></svg>"#;
+        let image = rasterize(svg, 20, 20).unwrap();
+        assert_eq!(pixel(&image, 5, 10), [0, 0, 0, 0]);
+        assert_eq!(pixel(&image, 15, 10), [255, 0, 0, 255]);
+
+        // A wide target centers the square view box.
+        let image = rasterize(svg, 40, 20).unwrap();
+        assert_eq!(pixel(&image, 15, 10), [0, 0, 0, 0]);
+        assert_eq!(pixel(&image, 25, 10), [255, 0, 0, 255]);
+        assert_eq!(pixel(&image, 35, 10), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fills_circles_with_antialiased_edges() {
+        let svg = r##"<svg viewBox="0 0 20 20"><circle cx="10" cy="10" r="8" fill="#00f"/></svg>"##;
+        let image = rasterize(svg, 20, 20).unwrap();
+        assert_eq!(pixel(&image, 10, 10), [0, 0, 255, 255]);
+        assert_eq!(pixel(&image, 0, 0)[3], 0);
+        let edge = pixel(&image, 10, 2)[3];
+        assert!(edge > 0 && edge < 255, "edge alpha {edge}");
+    }
+
+    #[test]
+    fn strokes_inherit_from_groups_and_honor_opacity() {
+        let svg = r#"<svg width="10" height="10">
+            <g stroke="currentColor" style="color: lime; fill: none" stroke-width="2">
+              <line x1="0" y1="5" x2="10" y2="5"/>
+            </g>
+            <rect width="10" height="2" fill="blue" opacity="0.5"/>
+        </svg>"#;
+        let image = rasterize(svg, 10, 10).unwrap();
+        assert_eq!(pixel(&image, 3, 5), [0, 255, 0, 255]);
+        assert_eq!(pixel(&image, 3, 8)[3], 0);
+        let translucent = pixel(&image, 3, 0);
+        assert_eq!(translucent[2], 255);
+        assert!((127..=128).contains(&translucent[3]));
+    }
+
+    #[test]
+    fn applies_transforms_and_even_odd_fills() {
+        let svg = r#"<svg viewBox="0 0 10 10">
+            <path transform="translate(10 0) scale(-1 1)" fill-rule="evenodd"
+                  d="M0 0h6v10H0z M2 2h2v6H2z"/></svg>"#;
+        let image = rasterize(svg, 10, 10).unwrap();
+        // Mirrored into x 4..10, with a hole at x 6..8.
+        assert_eq!(pixel(&image, 2, 5)[3], 0);
+        assert_eq!(pixel(&image, 5, 5)[3], 255);
+        assert_eq!(pixel(&image, 7, 5)[3], 0);
+        assert_eq!(pixel(&image, 9, 5)[3], 255);
+    }
+
+    #[test]
+    fn draws_symbols_through_use() {
+        let svg = r##"<svg xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 20 10">
+            <defs><symbol id="dot" viewBox="0 0 2 2"><rect width="2" height="2"/></symbol></defs>
+            <use xlink:href="#dot" x="10" width="10" height="10" fill="gray"/>
+            <use href="#loop" id="loop"/>
+        </svg>"##;
+        let image = rasterize(svg, 20, 10).unwrap();
+        assert_eq!(pixel(&image, 5, 5)[3], 0);
+        assert_eq!(pixel(&image, 15, 5), [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn rejects_documents_it_cannot_draw() {
+        assert!(rasterize("<html/>", 10, 10).is_err());
+        assert!(rasterize("<svg><g></svg>", 10, 10).is_err());
+        assert!(rasterize("<svg/>", 100_000, 10).is_err());
+    }
+
+    #[test]
+    fn caches_by_document_and_size() {
+        let svg = "<svg viewBox='0 0 1 1'><rect width='1' height='1'/></svg>";
+        let first = rasterize_cached(svg, 4, 4).unwrap();
+        assert!(Arc::ptr_eq(&first, &rasterize_cached(svg, 4, 4).unwrap()));
+        assert!(!Arc::ptr_eq(&first, &rasterize_cached(svg, 8, 8).unwrap()));
+    }
+}
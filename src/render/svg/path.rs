@@ -0,0 +1,970 @@
+use std::f64::consts::{FRAC_PI_2, PI, TAU};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+
+    fn scale(self, factor: f64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+
+    fn length(self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    /// Rotated a quarter turn: `(x, y)` becomes `(-y, x)`.
+    fn perp(self) -> Point {
+        Point::new(-self.y, self.x)
+    }
+
+    fn dot(self, other: Point) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    fn cross(self, other: Point) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+/// An affine transform mapping `(x, y)` to `(a·x + c·y + e, b·x + d·y + f)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    pub fn translate(x: f64, y: f64) -> Transform {
+        Transform {
+            e: x,
+            f: y,
+            ..Transform::IDENTITY
+        }
+    }
+
+    pub fn scale(x: f64, y: f64) -> Transform {
+        Transform {
+            a: x,
+            d: y,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// Clockwise, as the y axis points down.
+    pub fn rotate(degrees: f64) -> Transform {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Transform {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Transform::IDENTITY
+        }
+    }
+
+    /// `other` applied first, then `self`.
+    pub fn then(self, other: Transform) -> Transform {
+        Transform {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            e: self.a * other.e + self.c * other.f + self.e,
+            f: self.b * other.e + self.d * other.f + self.f,
+        }
+    }
+
+    /// The same transform for content moved by `(dx, dy)`: it moves the content back, applies
+    /// `self`, then moves the result along again.
+    pub fn about(self, dx: f64, dy: f64) -> Transform {
+        Transform::translate(dx, dy)
+            .then(self)
+            .then(Transform::translate(-dx, -dy))
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.e,
+            self.b * point.x + self.d * point.y + self.f,
+        )
+    }
+
+    /// How far one user unit can stretch in device pixels.
+    pub(crate) fn max_scale(&self) -> f64 {
+        self.a.hypot(self.b).max(self.c.hypot(self.d))
+    }
+
+    /// Parses a `transform` attribute. An invalid list yields `None`, which SVG treats as no
+    /// transform at all.
+    pub(crate) fn parse_list(input: &str) -> Option<Transform> {
+        let mut transform = Transform::IDENTITY;
+        let mut rest = input.trim_start_matches(is_separator);
+        while !rest.is_empty() {
+            let open = rest.find('(')?;
+            let close = rest.find(')')?;
+            if close < open {
+                return None;
+            }
+            let name = rest[..open].trim();
+            let args = parse_number_list(&rest[open + 1..close])?;
+            let next = match (name, args.as_slice()) {
+                ("matrix", &[a, b, c, d, e, f]) => Transform { a, b, c, d, e, f },
+                ("translate", &[x]) => Transform::translate(x, 0.0),
+                ("translate", &[x, y]) => Transform::translate(x, y),
+                ("scale", &[s]) => Transform::scale(s, s),
+                ("scale", &[x, y]) => Transform::scale(x, y),
+                ("rotate", &[angle]) => Transform::rotate(angle),
+                ("rotate", &[angle, cx, cy]) => Transform::translate(cx, cy)
+                    .then(Transform::rotate(angle))
+                    .then(Transform::translate(-cx, -cy)),
+                ("skewX", &[angle]) => Transform {
+                    c: angle.to_radians().tan(),
+                    ..Transform::IDENTITY
+                },
+                ("skewY", &[angle]) => Transform {
+                    b: angle.to_radians().tan(),
+                    ..Transform::IDENTITY
+                },
+                _ => return None,
+            };
+            transform = transform.then(next);
+            rest = rest[close + 1..].trim_start_matches(is_separator);
+        }
+        Some(transform)
+    }
+}
+
+fn is_separator(ch: char) -> bool {
+    ch.is_ascii_whitespace() || ch == ','
+}
+
+pub(crate) fn parse_number_list(input: &str) -> Option<Vec<f64>> {
+    let mut cursor = Cursor::new(input);
+    let mut numbers = Vec::new();
+    cursor.skip_separators();
+    while !cursor.at_end() {
+        numbers.push(cursor.number()?);
+        cursor.skip_separators();
+    }
+    Some(numbers)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCommand {
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo(Point, Point),
+    CubicTo(Point, Point, Point),
+    Close,
+}
+
+impl PathCommand {
+    /// The command with every point mapped through `transform`.
+    pub fn transformed(self, transform: &Transform) -> PathCommand {
+        match self {
+            PathCommand::MoveTo(to) => PathCommand::MoveTo(transform.apply(to)),
+            PathCommand::LineTo(to) => PathCommand::LineTo(transform.apply(to)),
+            PathCommand::QuadTo(control, to) => {
+                PathCommand::QuadTo(transform.apply(control), transform.apply(to))
+            }
+            PathCommand::CubicTo(first, second, to) => PathCommand::CubicTo(
+                transform.apply(first),
+                transform.apply(second),
+                transform.apply(to),
+            ),
+            PathCommand::Close => PathCommand::Close,
+        }
+    }
+}
+
+/// A flattened subpath.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Polyline {
+    pub(crate) points: Vec<Point>,
+    pub(crate) closed: bool,
+}
+
+/// Parses path data into absolute commands, with arcs converted to cubic béziers. As SVG
+/// requires, everything up to the first error is kept.
+pub(crate) fn parse_path_data(data: &str) -> Vec<PathCommand> {
+    let mut parser = PathParser {
+        cursor: Cursor::new(data),
+        out: Vec::new(),
+        current: Point::default(),
+        subpath_start: Point::default(),
+        last_cubic_control: None,
+        last_quad_control: None,
+    };
+    let mut command: Option<char> = None;
+
+    loop {
+        parser.cursor.skip_separators();
+        if parser.cursor.at_end() {
+            break;
+        }
+        if let Some(letter) = parser.cursor.command_letter() {
+            command = Some(letter);
+        }
+        let Some(letter) = command else {
+            break;
+        };
+        if parser.segment(letter).is_none() {
+            break;
+        }
+        command = match letter {
+            // Further coordinate pairs after a moveto are implicit linetos.
+            'M' => Some('L'),
+            'm' => Some('l'),
+            // A closepath takes no arguments, so a number after it is an error.
+            'Z' | 'z' => None,
+            _ => command,
+        };
+    }
+    parser.out
+}
+
+struct PathParser<'a> {
+    cursor: Cursor<'a>,
+    out: Vec<PathCommand>,
+    current: Point,
+    subpath_start: Point,
+    /// The control points S/s and T/t reflect, set only right after a curve of the same kind.
+    last_cubic_control: Option<Point>,
+    last_quad_control: Option<Point>,
+}
+
+impl PathParser<'_> {
+    fn segment(&mut self, letter: char) -> Option<()> {
+        let origin = if letter.is_ascii_lowercase() {
+            self.current
+        } else {
+            Point::default()
+        };
+        let mut cubic_control = None;
+        let mut quad_control = None;
+        let to = match letter.to_ascii_uppercase() {
+            'M' => {
+                let to = self.point(origin)?;
+                self.out.push(PathCommand::MoveTo(to));
+                self.subpath_start = to;
+                to
+            }
+            'L' => {
+                let to = self.point(origin)?;
+                self.out.push(PathCommand::LineTo(to));
+                to
+            }
+            'H' => {
+                let to = Point::new(origin.x + self.cursor.number()?, self.current.y);
+                self.out.push(PathCommand::LineTo(to));
+                to
+            }
+            'V' => {
+                let to = Point::new(self.current.x, origin.y + self.cursor.number()?);
+                self.out.push(PathCommand::LineTo(to));
+                to
+            }
+            'C' => {
+                let c1 = self.point(origin)?;
+                let c2 = self.point(origin)?;
+                let to = self.point(origin)?;
+                self.out.push(PathCommand::CubicTo(c1, c2, to));
+                cubic_control = Some(c2);
+                to
+            }
+            'S' => {
+                let c1 = reflect(self.last_cubic_control, self.current);
+                let c2 = self.point(origin)?;
+                let to = self.point(origin)?;
+                self.out.push(PathCommand::CubicTo(c1, c2, to));
+                cubic_control = Some(c2);
+                to
+            }
+            'Q' => {
+                let control = self.point(origin)?;
+                let to = self.point(origin)?;
+                self.out.push(PathCommand::QuadTo(control, to));
+                quad_control = Some(control);
+                to
+            }
+            'T' => {
+                let control = reflect(self.last_quad_control, self.current);
+                let to = self.point(origin)?;
+                self.out.push(PathCommand::QuadTo(control, to));
+                quad_control = Some(control);
+                to
+            }
+            'A' => {
+                let rx = self.cursor.number()?;
+                self.cursor.skip_separators();
+                let ry = self.cursor.number()?;
+                self.cursor.skip_separators();
+                let rotation = self.cursor.number()?;
+                self.cursor.skip_separators();
+                let large_arc = self.cursor.flag()?;
+                self.cursor.skip_separators();
+                let sweep = self.cursor.flag()?;
+                let to = self.point(origin)?;
+                let arc = ArcSegment {
+                    rx,
+                    ry,
+                    rotation,
+                    large_arc,
+                    sweep,
+                };
+                arc.to_cubics(self.current, to, &mut self.out);
+                to
+            }
+            'Z' => {
+                self.out.push(PathCommand::Close);
+                self.subpath_start
+            }
+            _ => return None,
+        };
+        self.current = to;
+        self.last_cubic_control = cubic_control;
+        self.last_quad_control = quad_control;
+        Some(())
+    }
+
+    fn point(&mut self, origin: Point) -> Option<Point> {
+        self.cursor.skip_separators();
+        let x = self.cursor.number()?;
+        self.cursor.skip_separators();
+        let y = self.cursor.number()?;
+        Some(origin.add(Point::new(x, y)))
+    }
+}
+
+fn reflect(control: Option<Point>, current: Point) -> Point {
+    control.map_or(current, |control| current.scale(2.0).sub(control))
+}
+
+struct ArcSegment {
+    rx: f64,
+    ry: f64,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+}
+
+impl ArcSegment {
+    /// Converts the endpoint parameterization to a center one (SVG 2 appendix B.2.4), then
+    /// approximates each quarter of the sweep with a cubic.
+    fn to_cubics(&self, from: Point, to: Point, out: &mut Vec<PathCommand>) {
+        if from == to {
+            return;
+        }
+        let mut rx = self.rx.abs();
+        let mut ry = self.ry.abs();
+        if rx == 0.0 || ry == 0.0 {
+            out.push(PathCommand::LineTo(to));
+            return;
+        }
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let half = from.sub(to).scale(0.5);
+        let x1 = cos * half.x + sin * half.y;
+        let y1 = -sin * half.x + cos * half.y;
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+        let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (numerator / denominator).max(0.0).sqrt();
+        if self.large_arc == self.sweep {
+            coef = -coef;
+        }
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let mid = from.add(to).scale(0.5);
+        let center = Point::new(cos * cx1 - sin * cy1 + mid.x, sin * cx1 + cos * cy1 + mid.y);
+
+        let start = Point::new((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let end = Point::new((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+        let theta = start.y.atan2(start.x);
+        let mut delta = start.cross(end).atan2(start.dot(end));
+        if !self.sweep && delta > 0.0 {
+            delta -= TAU;
+        } else if self.sweep && delta < 0.0 {
+            delta += TAU;
+        }
+
+        let map = |unit: Point| {
+            Point::new(
+                cos * rx * unit.x - sin * ry * unit.y + center.x,
+                sin * rx * unit.x + cos * ry * unit.y + center.y,
+            )
+        };
+        let pieces = (delta.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = delta / pieces as f64;
+        let handle = 4.0 / 3.0 * (step / 4.0).tan();
+        for piece in 0..pieces {
+            let a1 = theta + step * piece as f64;
+            let a2 = a1 + step;
+            let (s1, c1) = a1.sin_cos();
+            let (s2, c2) = a2.sin_cos();
+            let control1 = Point::new(c1 - handle * s1, s1 + handle * c1);
+            let control2 = Point::new(c2 + handle * s2, s2 - handle * c2);
+            let end = if piece + 1 == pieces {
+                to
+            } else {
+                map(Point::new(c2, s2))
+            };
+            out.push(PathCommand::CubicTo(map(control1), map(control2), end));
+        }
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            bytes: input.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|byte| byte.is_ascii_whitespace() || byte == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn command_letter(&mut self) -> Option<char> {
+        let letter = self.peek().filter(|byte| {
+            matches!(
+                byte.to_ascii_uppercase(),
+                b'M' | b'L' | b'H' | b'V' | b'C' | b'S' | b'Q' | b'T' | b'A' | b'Z'
+            )
+        })?;
+        self.pos += 1;
+        Some(char::from(letter))
+    }
+
+    fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(flag)
+    }
+
+    /// Parses an SVG number. Numbers may run together, so `1.5.5-2` is `1.5`, `.5` and `-2`.
+    fn number(&mut self) -> Option<f64> {
+        let start = self.pos;
+        if matches!(self.peek(), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let digits_start = self.pos;
+        self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.skip_digits();
+        }
+        if self.pos == digits_start || &self.bytes[digits_start..self.pos] == b"." {
+            self.pos = start;
+            return None;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            let exponent_start = self.pos;
+            self.skip_digits();
+            if self.pos == exponent_start {
+                self.pos = mantissa_end;
+            }
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?;
+        text.parse::<f64>().ok().filter(|number| number.is_finite())
+    }
+
+    fn skip_digits(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+}
+
+/// Turns curves into line segments that stay within `tolerance` user units of the true curve.
+pub(crate) fn flatten(commands: &[PathCommand], tolerance: f64) -> Vec<Polyline> {
+    let mut polylines: Vec<Polyline> = Vec::new();
+    let mut current = Polyline::default();
+    let mut last = Point::default();
+    let mut subpath_start = Point::default();
+
+    fn finish(polylines: &mut Vec<Polyline>, current: &mut Polyline) {
+        if !current.points.is_empty() {
+            polylines.push(std::mem::take(current));
+        }
+    }
+
+    for command in commands {
+        match *command {
+            PathCommand::MoveTo(to) => {
+                finish(&mut polylines, &mut current);
+                current.points.push(to);
+                subpath_start = to;
+                last = to;
+            }
+            PathCommand::LineTo(to) => {
+                if current.points.is_empty() {
+                    current.points.push(last);
+                }
+                current.points.push(to);
+                last = to;
+            }
+            PathCommand::QuadTo(control, to) => {
+                if current.points.is_empty() {
+                    current.points.push(last);
+                }
+                let dd = last.sub(control.scale(2.0)).add(to).length();
+                let steps = segment_count(dd / (4.0 * tolerance));
+                for step in 1..=steps {
+                    let t = step as f64 / steps as f64;
+                    let mt = 1.0 - t;
+                    current.points.push(
+                        last.scale(mt * mt)
+                            .add(control.scale(2.0 * mt * t))
+                            .add(to.scale(t * t)),
+                    );
+                }
+                last = to;
+            }
+            PathCommand::CubicTo(c1, c2, to) => {
+                if current.points.is_empty() {
+                    current.points.push(last);
+                }
+                let dd = last
+                    .sub(c1.scale(2.0))
+                    .add(c2)
+                    .length()
+                    .max(c1.sub(c2.scale(2.0)).add(to).length());
+                let steps = segment_count(3.0 * dd / (4.0 * tolerance));
+                for step in 1..=steps {
+                    let t = step as f64 / steps as f64;
+                    let mt = 1.0 - t;
+                    current.points.push(
+                        last.scale(mt * mt * mt)
+                            .add(c1.scale(3.0 * mt * mt * t))
+                            .add(c2.scale(3.0 * mt * t * t))
+                            .add(to.scale(t * t * t)),
+                    );
+                }
+                last = to;
+            }
+            PathCommand::Close => {
+                if !current.points.is_empty() {
+                    current.closed = true;
+                    finish(&mut polylines, &mut current);
+                }
+                last = subpath_start;
+            }
+        }
+    }
+    finish(&mut polylines, &mut current);
+    polylines
+}
+
+/// Segments needed when the squared segment count must reach `squared`.
+fn segment_count(squared: f64) -> usize {
+    if squared.is_finite() {
+        (squared.sqrt().ceil() as usize).clamp(1, 1000)
+    } else {
+        1
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f64,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f64,
+}
+
+/// Outlines a stroke as polygons that all wind the same way, so filling them with the nonzero
+/// rule paints their union.
+pub(crate) fn stroke_polygons(
+    polylines: &[Polyline],
+    style: &StrokeStyle,
+    tolerance: f64,
+) -> Vec<Vec<Point>> {
+    let half = style.width / 2.0;
+    let mut polygons = Vec::new();
+    if half <= 0.0 || !half.is_finite() {
+        return polygons;
+    }
+    let circle_steps = circle_segments(half, tolerance);
+
+    for polyline in polylines {
+        let mut points = polyline.points.clone();
+        points.dedup();
+        if polyline.closed && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        if points.len() == 1 {
+            // A zero-length subpath still shows its caps as a dot.
+            if polyline.points.len() > 1 || polyline.closed {
+                let center = points[0];
+                match style.cap {
+                    LineCap::Butt => {}
+                    LineCap::Round => polygons.push(circle(center, half, circle_steps)),
+                    LineCap::Square => polygons.push(vec![
+                        Point::new(center.x - half, center.y - half),
+                        Point::new(center.x + half, center.y - half),
+                        Point::new(center.x + half, center.y + half),
+                        Point::new(center.x - half, center.y + half),
+                    ]),
+                }
+            }
+            continue;
+        }
+
+        let closed = polyline.closed && points.len() > 2;
+        let segment_count = if closed {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let direction = |index: usize| {
+            let from = points[index % points.len()];
+            let to = points[(index + 1) % points.len()];
+            let delta = to.sub(from);
+            delta.scale(1.0 / delta.length())
+        };
+
+        for index in 0..segment_count {
+            let from = points[index];
+            let to = points[(index + 1) % points.len()];
+            let normal = direction(index).perp().scale(half);
+            polygons.push(vec![
+                from.add(normal),
+                to.add(normal),
+                to.sub(normal),
+                from.sub(normal),
+            ]);
+        }
+
+        let joins = if closed {
+            0..points.len()
+        } else {
+            1..points.len() - 1
+        };
+        for index in joins {
+            let incoming = direction((index + points.len() - 1) % points.len());
+            let outgoing = direction(index);
+            push_join(
+                &mut polygons,
+                points[index],
+                incoming,
+                outgoing,
+                style,
+                circle_steps,
+            );
+        }
+
+        if !closed {
+            let start_direction = direction(0);
+            let end_direction = direction(points.len() - 2);
+            push_cap(
+                &mut polygons,
+                points[0],
+                start_direction.scale(-1.0),
+                style,
+                circle_steps,
+            );
+            push_cap(
+                &mut polygons,
+                points[points.len() - 1],
+                end_direction,
+                style,
+                circle_steps,
+            );
+        }
+    }
+
+    for polygon in &mut polygons {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    polygons
+}
+
+fn push_join(
+    polygons: &mut Vec<Vec<Point>>,
+    vertex: Point,
+    incoming: Point,
+    outgoing: Point,
+    style: &StrokeStyle,
+    circle_steps: usize,
+) {
+    let half = style.width / 2.0;
+    let turn = incoming.cross(outgoing);
+    let dot = incoming.dot(outgoing);
+    if turn.abs() < 1e-9 && dot > 0.0 {
+        return;
+    }
+    if style.join == LineJoin::Round {
+        polygons.push(circle(vertex, half, circle_steps));
+        return;
+    }
+
+    // The outer side of the turn is opposite to the direction the path bends.
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let outer_in = incoming.perp().scale(side);
+    let outer_out = outgoing.perp().scale(side);
+    if style.join == LineJoin::Miter {
+        // miter length / stroke width = 1 / sin(θ/2), where θ is the angle between segments.
+        let sin_half_angle = ((1.0 + dot) / 2.0).max(0.0).sqrt();
+        if sin_half_angle > 0.0 && 1.0 / sin_half_angle <= style.miter_limit {
+            let bisector = outer_in.add(outer_out);
+            let tip = vertex.add(bisector.scale(2.0 * half / bisector.dot(bisector)));
+            polygons.push(vec![
+                vertex,
+                vertex.add(outer_in.scale(half)),
+                tip,
+                vertex.add(outer_out.scale(half)),
+            ]);
+            return;
+        }
+    }
+    polygons.push(vec![
+        vertex,
+        vertex.add(outer_in.scale(half)),
+        vertex.add(outer_out.scale(half)),
+    ]);
+}
+
+/// Adds the cap at `end`, where `outward` points away from the line.
+fn push_cap(
+    polygons: &mut Vec<Vec<Point>>,
+    end: Point,
+    outward: Point,
+    style: &StrokeStyle,
+    circle_steps: usize,
+) {
+    let half = style.width / 2.0;
+    match style.cap {
+        LineCap::Butt => {}
+        LineCap::Round => polygons.push(circle(end, half, circle_steps)),
+        LineCap::Square => {
+            let normal = outward.perp().scale(half);
+            let extent = outward.scale(half);
+            polygons.push(vec![
+                end.add(normal),
+                end.add(normal).add(extent),
+                end.sub(normal).add(extent),
+                end.sub(normal),
+            ]);
+        }
+    }
+}
+
+fn circle_segments(radius: f64, tolerance: f64) -> usize {
+    if radius <= tolerance {
+        return 8;
+    }
+    let step = 2.0 * (1.0 - tolerance / radius).acos();
+    ((TAU / step).ceil() as usize).clamp(8, 256)
+}
+
+fn circle(center: Point, radius: f64, steps: usize) -> Vec<Point> {
+    (0..steps)
+        .map(|step| {
+            let angle = 2.0 * PI * step as f64 / steps as f64;
+            Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+fn signed_area(polygon: &[Point]) -> f64 {
+    let mut area = 0.0;
+    for (index, point) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()];
+        area += point.cross(next);
+    }
+    area / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Point, b: Point) -> bool {
+        (a.x - b.x).abs() < 1e-6 && (a.y - b.y).abs() < 1e-6
+    }
+
+    #[test]
+    fn parses_compact_path_data() {
+        assert_eq!(
+            parse_path_data("M1.5.5l2-1h1V3zm1,1"),
+            vec![
+                PathCommand::MoveTo(Point::new(1.5, 0.5)),
+                PathCommand::LineTo(Point::new(3.5, -0.5)),
+                PathCommand::LineTo(Point::new(4.5, -0.5)),
+                PathCommand::LineTo(Point::new(4.5, 3.0)),
+                PathCommand::Close,
+                PathCommand::MoveTo(Point::new(2.5, 1.5)),
+            ]
+        );
+        // Implicit lineto after moveto, and everything before an error is kept.
+        assert_eq!(
+            parse_path_data("M0 0 10 0 L 5 x"),
+            vec![
+                PathCommand::MoveTo(Point::new(0.0, 0.0)),
+                PathCommand::LineTo(Point::new(10.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reflects_smooth_curve_control_points() {
+        let commands = parse_path_data("M0 0 C0 10 10 10 10 0 S20 -10 20 0 T30 0");
+        assert_eq!(
+            commands[2],
+            PathCommand::CubicTo(
+                Point::new(10.0, -10.0),
+                Point::new(20.0, -10.0),
+                Point::new(20.0, 0.0)
+            )
+        );
+        // T after a cubic has no quadratic control point to reflect.
+        assert_eq!(
+            commands[3],
+            PathCommand::QuadTo(Point::new(20.0, 0.0), Point::new(30.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn converts_arcs_to_cubics_on_the_ellipse() {
+        // A half circle of radius 5 from (0,0) to (10,0) sweeping through (5,-5) or (5,5).
+        let commands = parse_path_data("M0 0 A5 5 0 0 1 10 0");
+        let polylines = flatten(&commands, 0.01);
+        let points = &polylines[0].points;
+        assert!(close(*points.last().unwrap(), Point::new(10.0, 0.0)));
+        for point in points {
+            let radius = point.sub(Point::new(5.0, 0.0)).length();
+            assert!((radius - 5.0).abs() < 0.02, "{point:?} is off the circle");
+            assert!(point.y <= 1e-9);
+        }
+
+        // Radii too small for the endpoints are scaled up; flags packed without separators.
+        let commands = parse_path_data("M0 0a1 1 0 0010 0");
+        let polylines = flatten(&commands, 0.01);
+        assert!(polylines[0].points.iter().any(|point| point.y > 4.9));
+    }
+
+    #[test]
+    fn parses_transform_lists() {
+        let transform = Transform::parse_list("translate(10,20) scale(2)").unwrap();
+        assert!(close(
+            transform.apply(Point::new(1.0, 1.0)),
+            Point::new(12.0, 22.0)
+        ));
+        let rotate = Transform::parse_list("rotate(90 5 5)").unwrap();
+        assert!(close(
+            rotate.apply(Point::new(10.0, 5.0)),
+            Point::new(5.0, 10.0)
+        ));
+        assert!(Transform::parse_list("scale(1,2,3)").is_none());
+        assert_eq!(Transform::parse_list(""), Some(Transform::IDENTITY));
+    }
+
+    #[test]
+    fn stroke_outlines_wind_the_same_way() {
+        let polyline = Polyline {
+            points: vec![
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 10.0),
+            ],
+            closed: false,
+        };
+        let style = StrokeStyle {
+            width: 2.0,
+            cap: LineCap::Square,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+        };
+        let polygons = stroke_polygons(&[polyline], &style, 0.1);
+        // Two segments, one miter join and two caps.
+        assert_eq!(polygons.len(), 5);
+        assert!(polygons.iter().all(|polygon| signed_area(polygon) > 0.0));
+        assert!(
+            polygons
+                .iter()
+                .flatten()
+                .any(|point| close(*point, Point::new(11.0, -1.0)))
+        );
+    }
+}
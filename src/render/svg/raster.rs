@@ -0,0 +1,416 @@
+use super::path::Point;
+use crate::geom::{Color, Rect};
+use crate::image::Argb32Image;
+use std::ops::Range;
+
+/// Vertical samples per pixel row; horizontal coverage is computed exactly.
+const SUBSAMPLES: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+/// A premultiplied BGRA pixel buffer, laid out like [`Argb32Image`].
+pub(crate) struct Canvas {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+    /// The columns and rows painting is limited to.
+    clip: (Range<usize>, Range<usize>),
+}
+
+struct Edge {
+    top: f64,
+    bottom: f64,
+    x_at_top: f64,
+    slope: f64,
+    winding: i32,
+}
+
+impl Canvas {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        let width = width as usize;
+        let height = height as usize;
+        Self {
+            width,
+            height,
+            data: vec![0; width * height * 4],
+            clip: (0..width, 0..height),
+        }
+    }
+
+    /// A transparent canvas of the same size and clip.
+    pub(crate) fn same_size(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            data: vec![0; self.data.len()],
+            clip: self.clip.clone(),
+        }
+    }
+
+    /// Limits painting to `clip`; `None` paints the whole canvas.
+    pub(crate) fn set_clip(&mut self, clip: Option<Rect>) {
+        let clip = clip.unwrap_or(Rect {
+            x: 0,
+            y: 0,
+            width: self.width as i32,
+            height: self.height as i32,
+        });
+        self.clip = (
+            clip_span(clip.x, clip.width, self.width),
+            clip_span(clip.y, clip.height, self.height),
+        );
+    }
+
+    fn columns(&self, x: i32, width: i32) -> Range<usize> {
+        intersect(clip_span(x, width, self.width), &self.clip.0)
+    }
+
+    fn rows(&self, y: i32, height: i32) -> Range<usize> {
+        intersect(clip_span(y, height, self.height), &self.clip.1)
+    }
+
+    pub(crate) fn into_image(self) -> Result<Argb32Image, String> {
+        Argb32Image::new(self.width as u32, self.height as u32, self.data)
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Premultiplied BGRA rows, top to bottom.
+    pub(crate) fn pixels(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Fills the whole pixels of `[x, x + width) x [y, y + height)`, clipped to the canvas.
+    pub(crate) fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let xs = self.columns(x, width);
+        let ys = self.rows(y, height);
+        if xs.is_empty() || color.a == 0 {
+            return;
+        }
+        let alpha = f64::from(color.a) / 255.0;
+        let source = [
+            f64::from(color.b) * alpha,
+            f64::from(color.g) * alpha,
+            f64::from(color.r) * alpha,
+            f64::from(color.a),
+        ];
+        let opaque = [color.b, color.g, color.r, 255];
+        for row in ys {
+            let start = (row * self.width + xs.start) * 4;
+            let end = (row * self.width + xs.end) * 4;
+            for pixel in self.data[start..end].chunks_exact_mut(4) {
+                if color.a == 255 {
+                    pixel.copy_from_slice(&opaque);
+                } else {
+                    blend(pixel, source);
+                }
+            }
+        }
+    }
+
+    /// Paints `color` through an 8-bit coverage mask `mask_width` pixels wide, with the mask's
+    /// top-left pixel at `(x, y)`.
+    pub(crate) fn fill_mask(
+        &mut self,
+        x: i32,
+        y: i32,
+        mask_width: usize,
+        mask: &[u8],
+        color: Color,
+    ) {
+        if mask_width == 0 || color.a == 0 {
+            return;
+        }
+        let mask_height = mask.len() / mask_width;
+        let xs = self.columns(x, mask_width as i32);
+        let ys = self.rows(y, mask_height as i32);
+        let alpha = f64::from(color.a) / 255.0 / 255.0;
+        for row in ys {
+            let mask_row = (row as i64 - i64::from(y)) as usize * mask_width;
+            for column in xs.clone() {
+                let coverage = mask[mask_row + (column as i64 - i64::from(x)) as usize];
+                if coverage == 0 {
+                    continue;
+                }
+                let source_alpha = alpha * f64::from(coverage);
+                let offset = (row * self.width + column) * 4;
+                blend(
+                    &mut self.data[offset..offset + 4],
+                    [
+                        f64::from(color.b) * source_alpha,
+                        f64::from(color.g) * source_alpha,
+                        f64::from(color.r) * source_alpha,
+                        255.0 * source_alpha,
+                    ],
+                );
+            }
+        }
+    }
+
+    /// Draws `image` stretched over `[x, x + width) x [y, y + height)` at `opacity`, taking
+    /// each pixel from the nearest source pixel.
+    pub(crate) fn draw_image(
+        &mut self,
+        image: &Argb32Image,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        opacity: f64,
+    ) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        if width <= 0 || height <= 0 || image.width == 0 || image.height == 0 || opacity <= 0.0 {
+            return;
+        }
+        let xs = self.columns(x, width);
+        let ys = self.rows(y, height);
+        let source_width = image.width as usize;
+        let scale_x = f64::from(image.width) / f64::from(width);
+        let scale_y = f64::from(image.height) / f64::from(height);
+        for row in ys {
+            let source_row = (((row as f64 - f64::from(y) + 0.5) * scale_y) as usize)
+                .min(image.height as usize - 1);
+            for column in xs.clone() {
+                let source_column = (((column as f64 - f64::from(x) + 0.5) * scale_x) as usize)
+                    .min(source_width - 1);
+                let source_offset = (source_row * source_width + source_column) * 4;
+                let source = &image.data[source_offset..source_offset + 4];
+                if source[3] == 0 {
+                    continue;
+                }
+                let offset = (row * self.width + column) * 4;
+                blend(
+                    &mut self.data[offset..offset + 4],
+                    [
+                        f64::from(source[0]) * opacity,
+                        f64::from(source[1]) * opacity,
+                        f64::from(source[2]) * opacity,
+                        f64::from(source[3]) * opacity,
+                    ],
+                );
+            }
+        }
+    }
+
+    /// Fills the area enclosed by `polygons` (device pixel coordinates, each implicitly closed)
+    /// with `color` scaled by `opacity`, antialiasing the edges.
+    pub(crate) fn fill(
+        &mut self,
+        polygons: &[Vec<Point>],
+        rule: FillRule,
+        color: Color,
+        opacity: f64,
+    ) {
+        let alpha = f64::from(color.a) / 255.0 * opacity.clamp(0.0, 1.0);
+        if alpha <= 0.0 || self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        let mut edges = Vec::new();
+        for polygon in polygons {
+            for (index, &from) in polygon.iter().enumerate() {
+                let to = polygon[(index + 1) % polygon.len()];
+                if from.y == to.y || ![from.x, from.y, to.x, to.y].iter().all(|v| v.is_finite()) {
+                    continue;
+                }
+                let (upper, lower, winding) = if from.y < to.y {
+                    (from, to, 1)
+                } else {
+                    (to, from, -1)
+                };
+                edges.push(Edge {
+                    top: upper.y,
+                    bottom: lower.y,
+                    x_at_top: upper.x,
+                    slope: (lower.x - upper.x) / (lower.y - upper.y),
+                    winding,
+                });
+            }
+        }
+        if edges.is_empty() {
+            return;
+        }
+        edges.sort_by(|a, b| a.top.total_cmp(&b.top));
+
+        let first_row = (edges[0].top.floor().max(0.0) as usize).max(self.clip.1.start);
+        let last_row = (edges
+            .iter()
+            .map(|edge| edge.bottom)
+            .fold(f64::MIN, f64::max)
+            .ceil()
+            .min(self.height as f64) as usize)
+            .min(self.clip.1.end);
+
+        let width = self.width;
+        let sample_weight = 1.0 / SUBSAMPLES as f64;
+        // `partial` holds coverage of pixels a span only partly covers; `runs` holds where fully
+        // covered runs start and stop, summed left to right into the coverage of each pixel.
+        let mut partial = vec![0.0f64; width + 1];
+        let mut runs = vec![0.0f64; width + 1];
+        let mut active: Vec<usize> = Vec::new();
+        let mut next_edge = 0;
+        let mut crossings: Vec<(f64, i32)> = Vec::new();
+
+        for row in first_row..last_row {
+            let row_top = row as f64;
+            while next_edge < edges.len() && edges[next_edge].top < row_top + 1.0 {
+                active.push(next_edge);
+                next_edge += 1;
+            }
+            active.retain(|&index| edges[index].bottom > row_top);
+            if active.is_empty() {
+                continue;
+            }
+
+            let mut touched = false;
+            for sample in 0..SUBSAMPLES {
+                let y = row_top + (sample as f64 + 0.5) * sample_weight;
+                crossings.clear();
+                for &index in &active {
+                    let edge = &edges[index];
+                    if edge.top <= y && y < edge.bottom {
+                        crossings.push((edge.x_at_top + (y - edge.top) * edge.slope, edge.winding));
+                    }
+                }
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let inside = match rule {
+                        FillRule::NonZero => winding != 0,
+                        FillRule::EvenOdd => winding % 2 != 0,
+                    };
+                    if inside {
+                        touched |=
+                            add_span(&mut partial, &mut runs, pair[0].0, pair[1].0, sample_weight);
+                    }
+                }
+            }
+            if !touched {
+                continue;
+            }
+
+            let row_start = row * width * 4;
+            let mut run = 0.0;
+            for x in 0..width {
+                run += runs[x];
+                let coverage = (run + partial[x]).min(1.0);
+                partial[x] = 0.0;
+                runs[x] = 0.0;
+                if coverage <= 0.0 || !self.clip.0.contains(&x) {
+                    continue;
+                }
+                let source_alpha = alpha * coverage;
+                let pixel = &mut self.data[row_start + x * 4..row_start + x * 4 + 4];
+                blend(
+                    pixel,
+                    [
+                        f64::from(color.b) * source_alpha,
+                        f64::from(color.g) * source_alpha,
+                        f64::from(color.r) * source_alpha,
+                        255.0 * source_alpha,
+                    ],
+                );
+            }
+            partial[width] = 0.0;
+            runs[width] = 0.0;
+        }
+    }
+
+    /// Makes the pixels whose centers lie in `[left, right) x [top, bottom)` transparent.
+    pub(crate) fn clear(&mut self, left: f64, top: f64, right: f64, bottom: f64) {
+        let columns = |from: f64, to: f64, limit: usize| {
+            let from = from.round().clamp(0.0, limit as f64) as usize;
+            let to = to.round().clamp(0.0, limit as f64) as usize;
+            from..to.max(from)
+        };
+        let xs = columns(left, right, self.width);
+        for row in columns(top, bottom, self.height) {
+            let start = (row * self.width + xs.start) * 4;
+            let end = (row * self.width + xs.end) * 4;
+            self.data[start..end].fill(0);
+        }
+    }
+
+    /// Draws `layer` over this canvas at `opacity`.
+    pub(crate) fn composite(&mut self, layer: &Canvas, opacity: f64) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        let (xs, ys) = self.clip.clone();
+        for row in ys {
+            let start = (row * self.width + xs.start) * 4;
+            let end = (row * self.width + xs.end) * 4;
+            for (pixel, source) in self.data[start..end]
+                .chunks_exact_mut(4)
+                .zip(layer.data[start..end].chunks_exact(4))
+            {
+                if source[3] == 0 {
+                    continue;
+                }
+                blend(
+                    pixel,
+                    [
+                        f64::from(source[0]) * opacity,
+                        f64::from(source[1]) * opacity,
+                        f64::from(source[2]) * opacity,
+                        f64::from(source[3]) * opacity,
+                    ],
+                );
+            }
+        }
+    }
+}
+
+/// The part of `[start, start + len)` inside `0..limit`.
+fn intersect(span: Range<usize>, clip: &Range<usize>) -> Range<usize> {
+    let start = span.start.max(clip.start);
+    start..span.end.min(clip.end).max(start)
+}
+
+fn clip_span(start: i32, len: i32, limit: usize) -> std::ops::Range<usize> {
+    let end = i64::from(start) + i64::from(len.max(0));
+    let start = i64::from(start).clamp(0, limit as i64) as usize;
+    let end = end.clamp(0, limit as i64) as usize;
+    start..end.max(start)
+}
+
+/// Adds horizontal coverage for `[left, right)`, returning whether any pixel was touched.
+fn add_span(partial: &mut [f64], runs: &mut [f64], left: f64, right: f64, weight: f64) -> bool {
+    let width = (partial.len() - 1) as f64;
+    let left = left.clamp(0.0, width);
+    let right = right.clamp(0.0, width);
+    if left.is_nan() || right.is_nan() || right <= left {
+        return false;
+    }
+    let first = left.floor() as usize;
+    let last = right.floor() as usize;
+    if first == last {
+        partial[first] += (right - left) * weight;
+        return true;
+    }
+    partial[first] += (first as f64 + 1.0 - left) * weight;
+    runs[first + 1] += weight;
+    runs[last] -= weight;
+    partial[last] += (right - last as f64) * weight;
+    true
+}
+
+/// Source-over for premultiplied pixels; `source` channels are in 0..=255.
+fn blend(pixel: &mut [u8], source: [f64; 4]) {
+    let keep = 1.0 - source[3] / 255.0;
+    for (channel, source) in pixel.iter_mut().zip(source) {
+        *channel = (source + f64::from(*channel) * keep)
+            .round()
+            .clamp(0.0, 255.0) as u8;
+    }
+}
@@ -0,0 +1,257 @@
+use crate::image::MAX_SVG_DEPTH;
+
+/// An element of the SVG document. Text content is dropped since nothing the renderer draws
+/// depends on it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct XmlElement {
+    pub(super) name: String,
+    pub(super) attributes: Vec<(String, String)>,
+    pub(super) children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    pub(super) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The name without a namespace prefix, so `svg:rect` and `rect` match alike.
+    pub(super) fn local_name(&self) -> &str {
+        self.name
+            .rsplit_once(':')
+            .map_or(self.name.as_str(), |(_, local)| local)
+    }
+}
+
+/// Parses the outermost element of `xml`. Comments, processing instructions, CDATA and the
+/// doctype are skipped; entity references other than the predefined and numeric ones are left
+/// as written.
+pub(super) fn parse_document(xml: &str) -> Result<XmlElement, String> {
+    let mut parser = Parser { input: xml, pos: 0 };
+    let mut stack: Vec<XmlElement> = Vec::new();
+
+    loop {
+        let Some(offset) = parser.rest().find('<') else {
+            return Err("Unexpected end of SVG document".to_owned());
+        };
+        parser.pos += offset;
+        let rest = parser.rest();
+
+        if rest.starts_with("<!--") {
+            parser.skip_past("-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            parser.skip_past("]]>")?;
+        } else if rest.starts_with("<?") {
+            parser.skip_past("?>")?;
+        } else if rest.starts_with("<!") {
+            parser.skip_declaration()?;
+        } else if rest.starts_with("</") {
+            parser.pos += 2;
+            let name = parser.name()?;
+            parser.skip_whitespace();
+            parser.expect('>')?;
+            let element = stack
+                .pop()
+                .filter(|open| open.name == name)
+                .ok_or_else(|| format!("Unexpected </{name}> in SVG document"))?;
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => return Ok(element),
+            }
+        } else {
+            parser.pos += 1;
+            let (element, self_closing) = parser.start_tag()?;
+            if self_closing {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            } else {
+                if stack.len() >= MAX_SVG_DEPTH {
+                    return Err(format!("SVG nesting exceeds {MAX_SVG_DEPTH} levels"));
+                }
+                stack.push(element);
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_past(&mut self, terminator: &str) -> Result<(), String> {
+        let end = self
+            .rest()
+            .find(terminator)
+            .ok_or_else(|| format!("Unterminated markup in SVG document, expected {terminator}"))?;
+        self.pos += end + terminator.len();
+        Ok(())
+    }
+
+    /// Skips `<!DOCTYPE ...>`, including an internal subset in brackets.
+    fn skip_declaration(&mut self) -> Result<(), String> {
+        let mut bracket_depth = 0usize;
+        for (offset, ch) in self.rest().char_indices() {
+            match ch {
+                '[' => bracket_depth += 1,
+                ']' => bracket_depth = bracket_depth.saturating_sub(1),
+                '>' if bracket_depth == 0 => {
+                    self.pos += offset + 1;
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        Err("Unterminated declaration in SVG document".to_owned())
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, ch: char) -> Result<(), String> {
+        if self.rest().starts_with(ch) {
+            self.pos += ch.len_utf8();
+            Ok(())
+        } else {
+            Err(format!("Expected {ch:?} in SVG document"))
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let len = rest
+            .find(|ch: char| ch.is_whitespace() || matches!(ch, '>' | '/' | '='))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err("Expected a name in SVG document".to_owned());
+        }
+        self.pos += len;
+        Ok(rest[..len].to_owned())
+    }
+
+    /// Parses the tag after its `<`, returning the element and whether it ended with `/>`.
+    fn start_tag(&mut self) -> Result<(XmlElement, bool), String> {
+        let mut element = XmlElement {
+            name: self.name()?,
+            ..XmlElement::default()
+        };
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok((element, true));
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok((element, false));
+            }
+            if rest.is_empty() {
+                return Err(format!("Unterminated <{}> in SVG document", element.name));
+            }
+
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect('=')?;
+            self.skip_whitespace();
+            let quote = self
+                .rest()
+                .chars()
+                .next()
+                .filter(|ch| matches!(ch, '"' | '\''))
+                .ok_or_else(|| format!("Expected a quoted value for {name} in SVG document"))?;
+            self.pos += 1;
+            let end = self
+                .rest()
+                .find(quote)
+                .ok_or_else(|| format!("Unterminated value for {name} in SVG document"))?;
+            let value = decode_entities(&self.rest()[..end]);
+            self.pos += end + 1;
+            element.attributes.push((name, value));
+        }
+    }
+}
+
+fn decode_entities(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let entity = &rest[1..semi];
+            let ch = match entity {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                _ => {
+                    let code = if let Some(hex) = entity
+                        .strip_prefix("#x")
+                        .or_else(|| entity.strip_prefix("#X"))
+                    {
+                        u32::from_str_radix(hex, 16).ok()?
+                    } else {
+                        entity.strip_prefix('#')?.parse().ok()?
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((ch, semi + 1))
+        });
+        match decoded {
+            Some((ch, len)) => {
+                out.push(ch);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_elements_and_attributes() {
+        let doc = parse_document(
+            "<?xml version=\"1.0\"?><!DOCTYPE svg [<!ELEMENT svg ANY>]>\
+             <svg viewBox='0 0 10 10'><!-- icon --><g fill=\"a&amp;b&#x41;\">\
+             <rect width=\"1\"/>text</g><![CDATA[<x>]]></svg>",
+        )
+        .unwrap();
+        assert_eq!(doc.name, "svg");
+        assert_eq!(doc.attr("viewBox"), Some("0 0 10 10"));
+        assert_eq!(doc.children.len(), 1);
+        let group = &doc.children[0];
+        assert_eq!(group.attr("fill"), Some("a&bA"));
+        assert_eq!(group.children[0].name, "rect");
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert!(parse_document("<svg><g></svg>").is_err());
+        assert!(parse_document("<svg width=10/>").is_err());
+        assert!(parse_document("<svg>").is_err());
+        let deep = "<g>".repeat(MAX_SVG_DEPTH + 1);
+        assert!(parse_document(&format!("<svg>{deep}")).is_err());
+    }
+}
@@ -1,13 +1,18 @@
 use crate::debug;
 use crate::net;
-use crate::url::Url;
+use crate::url::{DataUrl, Url};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 pub trait ResourceLoader {
     fn load_bytes(&self, reference: &str) -> Result<Option<Arc<Vec<u8>>>, String>;
+
+    /// Told when the image `load_bytes` returned for `reference` has been decoded, for the
+    /// resource waterfall.
+    fn image_decoded(&self, _reference: &str) {}
 }
 
 pub struct NoResources;
@@ -24,8 +29,61 @@ pub enum ResourceBase {
     FileDir(PathBuf),
 }
 
+/// Which subresources a page may load, checked before a file is read or a fetch starts. Pages
+/// from the web never read local files.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResourcePolicy {
+    /// Origins such as `https://cdn.example.com` that subresources may come from besides the
+    /// page's own. Empty allows every origin.
+    pub allowed_origins: Vec<String>,
+}
+
+impl ResourcePolicy {
+    /// Whether a page at `page`, or a local page when `None`, may fetch `resource`.
+    pub fn allows_url(&self, page: Option<&Url>, resource: &Url) -> bool {
+        if self.allowed_origins.is_empty() {
+            return true;
+        }
+        let origin = resource.origin();
+        page.is_some_and(|page| page.origin() == origin) || self.allowed_origins.contains(&origin)
+    }
+
+    /// Why `resource` may not be loaded for `page`, or `None` when it may.
+    fn blocks(&self, page: Option<&Url>, resource: &ResolvedReference) -> Option<&'static str> {
+        match resource {
+            ResolvedReference::File(_) if page.is_some() => Some("file_from_web"),
+            ResolvedReference::File(_) => None,
+            ResolvedReference::Url(url) => match Url::parse(url) {
+                Ok(url) if !self.allows_url(page, &url) => Some("origin_not_allowed"),
+                _ => None,
+            },
+        }
+    }
+}
+
+static RESOURCE_POLICY: RwLock<Option<ResourcePolicy>> = RwLock::new(None);
+
+/// Installs `policy` for every page loaded from now on, replacing any earlier one.
+pub fn set_resource_policy(policy: ResourcePolicy) {
+    if let Ok(mut installed) = RESOURCE_POLICY.write() {
+        *installed = Some(policy);
+    }
+}
+
+pub fn resource_policy() -> ResourcePolicy {
+    RESOURCE_POLICY
+        .read()
+        .ok()
+        .and_then(|installed| installed.clone())
+        .unwrap_or_default()
+}
+
 pub struct ResourceManager {
     base: ResourceBase,
+    /// The address of the page itself, which `<base href>` doesn't move; `None` for local
+    /// pages.
+    page: Option<Url>,
+    policy: ResourcePolicy,
     state: RefCell<ResourceState>,
 }
 
@@ -39,12 +97,29 @@ impl ResourceManager {
     }
 
     fn new(base: ResourceBase) -> Self {
+        let page = match &base {
+            ResourceBase::Url(url) => Some(url.clone()),
+            ResourceBase::FileDir(_) => None,
+        };
         Self {
             base,
+            page,
+            policy: resource_policy(),
             state: RefCell::new(ResourceState::new()),
         }
     }
 
+    /// Checks references against `policy` instead of the process-wide one from now on.
+    pub fn set_policy(&mut self, policy: ResourcePolicy) {
+        self.policy = policy;
+    }
+
+    /// Resolves references against `base` from now on, as when the page's `<base href>` moves
+    /// it away from the page's own address.
+    pub fn set_base(&mut self, base: ResourceBase) {
+        self.base = base;
+    }
+
     pub fn tick(&self) -> ResourceTickResult {
         self.state.borrow_mut().drain_events()
     }
@@ -53,6 +128,52 @@ impl ResourceManager {
         self.state.borrow().pending.len()
     }
 
+    /// Whether `reference` is still being fetched, as opposed to having arrived or failed.
+    pub fn is_pending(&self, reference: &str) -> bool {
+        self.resolve_reference(reference)
+            .is_some_and(|resolved| self.state.borrow().pending.contains_key(&resolved))
+    }
+
+    pub fn take_waterfall_entries(&self) -> Vec<net::WaterfallEntry> {
+        std::mem::take(&mut self.state.borrow_mut().waterfall)
+    }
+
+    /// When the image fetched from `url` was first decoded.
+    pub fn decoded_at(&self, url: &str) -> Option<Instant> {
+        self.state.borrow().decoded_at.get(url).copied()
+    }
+
+    /// Whether an `http://` image of an `https://` page was fetched as written, which the
+    /// mixed content policy only does when it allows it.
+    pub fn loaded_insecure_content(&self) -> bool {
+        self.state.borrow().insecure_content
+    }
+
+    /// Local files read so far, including ones that could not be loaded.
+    pub fn local_files(&self) -> Vec<PathBuf> {
+        let state = self.state.borrow();
+        state
+            .cache_ok
+            .keys()
+            .chain(state.cache_fail.iter())
+            .filter_map(|key| match key {
+                ResolvedReference::File(path) => Some(path.clone()),
+                ResolvedReference::Url(_) => None,
+            })
+            .collect()
+    }
+
+    /// Total size of the local image files loaded so far.
+    pub fn local_bytes(&self) -> u64 {
+        self.state
+            .borrow()
+            .cache_ok
+            .iter()
+            .filter(|(key, _)| matches!(key, ResolvedReference::File(_)))
+            .map(|(_, bytes)| bytes.len() as u64)
+            .sum()
+    }
+
     fn resolve_reference(&self, reference: &str) -> Option<ResolvedReference> {
         resolve_reference(&self.base, reference)
     }
@@ -102,7 +223,9 @@ impl ResourceManager {
         }
 
         let bytes = Arc::new(bytes);
-        state.cache_ok.insert(key, Arc::clone(&bytes));
+        if !state.admit(key, Arc::clone(&bytes)) {
+            return None;
+        }
         if debug::enabled(debug::Target::Res, debug::Level::Debug) {
             let path_display = path.display().to_string();
             let path_display = debug::shorten(&path_display, 64);
@@ -115,6 +238,38 @@ impl ResourceManager {
         Some(bytes)
     }
 
+    /// `data:` URLs carry their own bytes, so they are decoded in place instead of fetched.
+    fn cache_data_url(&self, reference: &str) -> Option<Arc<Vec<u8>>> {
+        let mut state = self.state.borrow_mut();
+        let key = ResolvedReference::Url(reference.to_owned());
+
+        if let Some(bytes) = state.cache_ok.get(&key) {
+            return Some(Arc::clone(bytes));
+        }
+        if state.cache_fail.contains(&key) {
+            return None;
+        }
+
+        match DataUrl::parse(reference) {
+            Some(data) if crate::image::looks_like_supported_image(&data.body) => {
+                let bytes = Arc::new(data.body);
+                state.admit(key, Arc::clone(&bytes)).then_some(bytes)
+            }
+            _ => {
+                if debug::enabled(debug::Target::Res, debug::Level::Warn) {
+                    let url = debug::shorten(reference, 64);
+                    debug::log(
+                        debug::Target::Res,
+                        debug::Level::Warn,
+                        format_args!("data! url={url} err=unsupported_image"),
+                    );
+                }
+                state.cache_fail.insert(key);
+                None
+            }
+        }
+    }
+
     fn cache_url(&self, url: String) -> Result<Option<Arc<Vec<u8>>>, String> {
         let mut state = self.state.borrow_mut();
         let key = ResolvedReference::Url(url.clone());
@@ -142,13 +297,51 @@ impl ResourceManager {
 
 impl ResourceLoader for ResourceManager {
     fn load_bytes(&self, reference: &str) -> Result<Option<Arc<Vec<u8>>>, String> {
+        if DataUrl::is_data_url(reference) {
+            return Ok(self.cache_data_url(reference.trim()));
+        }
+
         let Some(resolved) = self.resolve_reference(reference) else {
             return Ok(None);
         };
+        if let Some(reason) = self.policy.blocks(self.page.as_ref(), &resolved) {
+            let mut state = self.state.borrow_mut();
+            if state.cache_fail.insert(resolved.clone())
+                && debug::enabled(debug::Target::Res, debug::Level::Warn)
+            {
+                let reference = debug::shorten(reference, 64);
+                debug::log(
+                    debug::Target::Res,
+                    debug::Level::Warn,
+                    format_args!("block! ref={reference} err={reason}"),
+                );
+            }
+            return Ok(None);
+        }
 
         match resolved {
             ResolvedReference::File(path) => Ok(self.cache_file(path)),
-            ResolvedReference::Url(url) => self.cache_url(url),
+            ResolvedReference::Url(url) => {
+                if let ResourceBase::Url(page) = &self.base
+                    && Url::parse(&url).is_ok_and(|url| net::is_mixed_content(page, &url))
+                {
+                    self.state.borrow_mut().insecure_content = true;
+                }
+                self.cache_url(url)
+            }
+        }
+    }
+
+    fn image_decoded(&self, reference: &str) {
+        if DataUrl::is_data_url(reference) {
+            return;
+        }
+        if let Some(ResolvedReference::Url(url)) = self.resolve_reference(reference) {
+            self.state
+                .borrow_mut()
+                .decoded_at
+                .entry(url)
+                .or_insert_with(Instant::now);
         }
     }
 }
@@ -163,6 +356,13 @@ struct ResourceState {
     pending: HashMap<ResolvedReference, net::RequestId>,
     cache_ok: HashMap<ResolvedReference, Arc<Vec<u8>>>,
     cache_fail: HashSet<ResolvedReference>,
+    waterfall: Vec<net::WaterfallEntry>,
+    /// When each fetched image was first decoded, by URL.
+    decoded_at: HashMap<String, Instant>,
+    insecure_content: bool,
+    /// What the cached images take, encoded and decoded, against
+    /// [`net::ResourceLimits::max_page_bytes`].
+    held_bytes: u64,
 }
 
 impl ResourceState {
@@ -172,13 +372,52 @@ impl ResourceState {
             pending: HashMap::new(),
             cache_ok: HashMap::new(),
             cache_fail: HashSet::new(),
+            waterfall: Vec::new(),
+            decoded_at: HashMap::new(),
+            insecure_content: false,
+            held_bytes: 0,
+        }
+    }
+
+    /// Caches `bytes` for `key`, or fails `key` when the page's images would then hold more than
+    /// the page may.
+    fn admit(&mut self, key: ResolvedReference, bytes: Arc<Vec<u8>>) -> bool {
+        let size = (bytes.len() as u64)
+            .saturating_add(crate::image::decoded_size_bytes(&bytes).unwrap_or(0));
+        let held_bytes = self.held_bytes.saturating_add(size);
+        if held_bytes > net::resource_limits().max_page_bytes {
+            if debug::enabled(debug::Target::Res, debug::Level::Warn) {
+                let reference = match &key {
+                    ResolvedReference::Url(url) => url.clone(),
+                    ResolvedReference::File(path) => path.display().to_string(),
+                };
+                let reference = debug::shorten(&reference, 64);
+                debug::log(
+                    debug::Target::Res,
+                    debug::Level::Warn,
+                    format_args!("res! ref={reference} err=page_memory bytes={size}"),
+                );
+            }
+            self.cache_fail.insert(key);
+            return false;
         }
+        self.held_bytes = held_bytes;
+        self.cache_ok.insert(key, bytes);
+        true
     }
 
     fn drain_events(&mut self) -> ResourceTickResult {
         let mut new_successes = 0usize;
 
         while let Some(event) = self.pool.try_recv() {
+            let mut entry = net::WaterfallEntry {
+                kind: "img",
+                url: event.url.clone(),
+                ok: false,
+                bytes: event.result.as_ref().map_or(0, Vec::len),
+                timing: event.timing,
+                decoded_at: None,
+            };
             let key = ResolvedReference::Url(event.url);
             let Some(_) = self.pending.remove(&key) else {
                 continue;
@@ -187,8 +426,10 @@ impl ResourceState {
             match event.result {
                 Ok(bytes) => {
                     if crate::image::looks_like_supported_image(&bytes) {
-                        self.cache_ok.insert(key, Arc::new(bytes));
-                        new_successes = new_successes.saturating_add(1);
+                        if self.admit(key, Arc::new(bytes)) {
+                            new_successes = new_successes.saturating_add(1);
+                            entry.ok = true;
+                        }
                     } else {
                         if debug::enabled(debug::Target::Res, debug::Level::Warn) {
                             let url = match &key {
@@ -223,6 +464,7 @@ impl ResourceState {
                     self.cache_fail.insert(key);
                 }
             }
+            self.waterfall.push(entry);
         }
 
         ResourceTickResult { new_successes }
@@ -241,13 +483,15 @@ fn resolve_reference(base: &ResourceBase, reference: &str) -> Option<ResolvedRef
         return None;
     }
 
-    if reference.starts_with("http://") || reference.starts_with("https://") {
-        return Some(ResolvedReference::Url(reference.to_owned()));
-    }
-
     match base {
         ResourceBase::Url(base) => {
-            let url = base.resolve(reference)?.as_str().to_owned();
+            let url = net::mixed_content_policy().apply(base, base.resolve(reference)?)?;
+            Some(ResolvedReference::Url(url.without_fragment().to_owned()))
+        }
+        ResourceBase::FileDir(_)
+            if reference.starts_with("http://") || reference.starts_with("https://") =>
+        {
+            let url = Url::parse(reference).ok()?.without_fragment().to_owned();
             Some(ResolvedReference::Url(url))
         }
         ResourceBase::FileDir(dir) => Some(ResolvedReference::File(resolve_file_reference(
@@ -272,3 +516,35 @@ fn resolve_file_reference(base_dir: &Path, reference: &str) -> PathBuf {
 
     base_dir.join(reference)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ResolvedReference, ResourcePolicy};
+    use crate::url::Url;
+    use std::path::PathBuf;
+
+    #[test]
+    fn web_pages_read_no_files_and_fetch_only_allowed_origins() {
+        let page = Url::parse("https://example.com/index.html").unwrap();
+        let file = ResolvedReference::File(PathBuf::from("/etc/passwd"));
+        let own = ResolvedReference::Url("https://example.com:443/logo.png".to_owned());
+        let cdn = ResolvedReference::Url("https://cdn.example.net/logo.png".to_owned());
+        let other = ResolvedReference::Url("http://cdn.example.net/logo.png".to_owned());
+
+        let open = ResourcePolicy::default();
+        assert_eq!(open.blocks(Some(&page), &file), Some("file_from_web"));
+        assert_eq!(open.blocks(None, &file), None);
+        assert_eq!(open.blocks(Some(&page), &other), None);
+
+        let allowlist = ResourcePolicy {
+            allowed_origins: vec!["https://cdn.example.net".to_owned()],
+        };
+        assert_eq!(allowlist.blocks(Some(&page), &own), None);
+        assert_eq!(allowlist.blocks(Some(&page), &cdn), None);
+        assert_eq!(
+            allowlist.blocks(Some(&page), &other),
+            Some("origin_not_allowed")
+        );
+        assert_eq!(allowlist.blocks(None, &own), Some("origin_not_allowed"));
+    }
+}
@@ -0,0 +1,962 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::background::split_top_level_commas;
+use super::declarations::parse_css_opacity_u8;
+use super::parse::parse_css_color;
+use super::transform::parse_transform;
+use super::{ComputedStyle, TransformFunction};
+use crate::css::Keyframes;
+use crate::dom::{Element, Node};
+use crate::geom::Color;
+
+/// How far along its change a property is at each point of a transition's duration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimingFunction {
+    Linear,
+    /// `cubic-bezier(x1, y1, x2, y2)`, which `ease` and its variants are shorthands for.
+    CubicBezier(f32, f32, f32, f32),
+    /// `steps(n)`: jumps at the end of each of `n` equal steps.
+    Steps(u32),
+}
+
+impl TimingFunction {
+    pub const EASE: TimingFunction = TimingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0);
+
+    /// The share of the change made once `time` of the duration has passed, both from 0 to 1.
+    pub fn progress(self, time: f32) -> f32 {
+        let time = time.clamp(0.0, 1.0);
+        match self {
+            TimingFunction::Linear => time,
+            TimingFunction::Steps(steps) => {
+                let steps = steps.max(1) as f32;
+                (time * steps).floor().min(steps) / steps
+            }
+            TimingFunction::CubicBezier(x1, y1, x2, y2) => {
+                let bezier = |a: f32, b: f32, t: f32| {
+                    3.0 * a * t * (1.0 - t) * (1.0 - t) + 3.0 * b * t * t * (1.0 - t) + t * t * t
+                };
+                // x(t) only grows with t, so halving the interval finds where it reaches `time`.
+                let (mut low, mut high) = (0.0f32, 1.0f32);
+                for _ in 0..24 {
+                    let middle = (low + high) / 2.0;
+                    if bezier(x1, x2, middle) < time {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                bezier(y1, y2, (low + high) / 2.0)
+            }
+        }
+    }
+}
+
+pub(super) fn parse_timing_function(value: &str) -> Option<TimingFunction> {
+    let value = value.trim().to_ascii_lowercase();
+    let function = match value.as_str() {
+        "linear" => TimingFunction::Linear,
+        "ease" => TimingFunction::EASE,
+        "ease-in" => TimingFunction::CubicBezier(0.42, 0.0, 1.0, 1.0),
+        "ease-out" => TimingFunction::CubicBezier(0.0, 0.0, 0.58, 1.0),
+        "ease-in-out" => TimingFunction::CubicBezier(0.42, 0.0, 0.58, 1.0),
+        "step-end" => TimingFunction::Steps(1),
+        _ => {
+            let (name, args) = value.strip_suffix(')')?.split_once('(')?;
+            let args: Vec<&str> = args.split(',').map(str::trim).collect();
+            match (name.trim(), args.as_slice()) {
+                ("cubic-bezier", [x1, y1, x2, y2]) => {
+                    let [x1, y1, x2, y2] = [x1, y1, x2, y2].map(|arg| arg.parse::<f32>().ok());
+                    let (x1, x2) = (x1?, x2?);
+                    if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2) {
+                        return None;
+                    }
+                    TimingFunction::CubicBezier(x1, y1?, x2, y2?)
+                }
+                ("steps", [steps, ..]) => TimingFunction::Steps(steps.parse().ok()?),
+                _ => return None,
+            }
+        }
+    };
+    Some(function)
+}
+
+/// A `1.5s` or `300ms` time; negative times aren't supported.
+pub(super) fn parse_time(value: &str) -> Option<Duration> {
+    let value = value.trim().to_ascii_lowercase();
+    let seconds = match value.strip_suffix("ms") {
+        Some(millis) => millis.parse::<f64>().ok()? / 1000.0,
+        None => value.strip_suffix('s')?.parse::<f64>().ok()?,
+    };
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+/// The properties transitions animate; any other property named in `transition-property` keeps
+/// its place in the list but changes at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitionProperty {
+    All,
+    Opacity,
+    Color,
+    BackgroundColor,
+    Transform,
+    Other,
+}
+
+impl TransitionProperty {
+    fn parse(value: &str) -> TransitionProperty {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "all" => TransitionProperty::All,
+            "opacity" => TransitionProperty::Opacity,
+            "color" => TransitionProperty::Color,
+            "background-color" | "background" => TransitionProperty::BackgroundColor,
+            "transform" | "-webkit-transform" => TransitionProperty::Transform,
+            _ => TransitionProperty::Other,
+        }
+    }
+}
+
+/// How one property changes when a new value is computed for it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub duration: Duration,
+    pub delay: Duration,
+    pub timing: TimingFunction,
+}
+
+/// The `transition-*` lists as declared. Shorter lists repeat to match `properties`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Transitions {
+    pub properties: Vec<TransitionProperty>,
+    pub durations: Vec<Duration>,
+    pub delays: Vec<Duration>,
+    pub timing_functions: Vec<TimingFunction>,
+}
+
+impl Transitions {
+    /// The transition `property` changes with, if any: the last entry naming it or `all`.
+    pub fn for_property(&self, property: TransitionProperty) -> Option<Transition> {
+        let index = self
+            .properties
+            .iter()
+            .rposition(|&named| named == property || named == TransitionProperty::All)?;
+        let pick = |list: &[Duration]| {
+            list.get(index % list.len().max(1))
+                .copied()
+                .unwrap_or_default()
+        };
+        let transition = Transition {
+            duration: pick(&self.durations),
+            delay: pick(&self.delays),
+            timing: self
+                .timing_functions
+                .get(index % self.timing_functions.len().max(1))
+                .copied()
+                .unwrap_or(TimingFunction::EASE),
+        };
+        (!transition.duration.is_zero()).then_some(transition)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.properties.is_empty()
+    }
+}
+
+/// The `transition` shorthand: a comma-separated list of a property, a duration, a timing
+/// function and a delay, each optional, with the first time the duration and the second the
+/// delay.
+pub(super) fn parse_transition_shorthand(value: &str) -> Option<Transitions> {
+    let mut transitions = Transitions::default();
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Some(transitions);
+    }
+    for item in split_top_level_commas(value) {
+        let mut property = None;
+        let mut times = Vec::new();
+        let mut timing = None;
+        for token in split_top_level_whitespace(item) {
+            if let Some(time) = parse_time(token) {
+                times.push(time);
+            } else if let Some(function) = parse_timing_function(token) {
+                timing = Some(function);
+            } else if property.is_none() {
+                property = Some(TransitionProperty::parse(token));
+            } else {
+                return None;
+            }
+        }
+        if times.len() > 2 {
+            return None;
+        }
+        transitions
+            .properties
+            .push(property.unwrap_or(TransitionProperty::All));
+        transitions
+            .durations
+            .push(times.first().copied().unwrap_or_default());
+        transitions
+            .delays
+            .push(times.get(1).copied().unwrap_or_default());
+        transitions
+            .timing_functions
+            .push(timing.unwrap_or(TimingFunction::EASE));
+    }
+    Some(transitions)
+}
+
+pub(super) fn parse_transition_properties(value: &str) -> Vec<TransitionProperty> {
+    if value.trim().eq_ignore_ascii_case("none") {
+        return Vec::new();
+    }
+    value.split(',').map(TransitionProperty::parse).collect()
+}
+
+pub(super) fn parse_times(value: &str) -> Option<Vec<Duration>> {
+    value.split(',').map(parse_time).collect()
+}
+
+pub(super) fn parse_timing_functions(value: &str) -> Option<Vec<TimingFunction>> {
+    split_top_level_commas(value)
+        .into_iter()
+        .map(parse_timing_function)
+        .collect()
+}
+
+/// One entry of the `animation-*` lists.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Animation {
+    pub name: String,
+    pub duration: Duration,
+    pub delay: Duration,
+    pub timing: TimingFunction,
+    /// How many times the keyframes play; `f32::INFINITY` for `infinite`.
+    pub iterations: f32,
+}
+
+/// The `animation-*` lists as declared. Shorter lists repeat to match `names`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Animations {
+    pub names: Vec<String>,
+    pub durations: Vec<Duration>,
+    pub delays: Vec<Duration>,
+    pub timing_functions: Vec<TimingFunction>,
+    pub iteration_counts: Vec<f32>,
+}
+
+impl Animations {
+    /// The animations that play, leaving out those named `none` and those that take no time.
+    pub fn list(&self) -> Vec<Animation> {
+        let pick = |list: &[Duration], index: usize| {
+            list.get(index % list.len().max(1))
+                .copied()
+                .unwrap_or_default()
+        };
+        self.names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| !name.eq_ignore_ascii_case("none"))
+            .map(|(index, name)| Animation {
+                name: name.clone(),
+                duration: pick(&self.durations, index),
+                delay: pick(&self.delays, index),
+                timing: self
+                    .timing_functions
+                    .get(index % self.timing_functions.len().max(1))
+                    .copied()
+                    .unwrap_or(TimingFunction::EASE),
+                iterations: self
+                    .iteration_counts
+                    .get(index % self.iteration_counts.len().max(1))
+                    .copied()
+                    .unwrap_or(1.0),
+            })
+            .filter(|animation| !animation.duration.is_zero() && animation.iterations > 0.0)
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+const UNSUPPORTED_ANIMATION_KEYWORDS: [&str; 9] = [
+    "normal",
+    "reverse",
+    "alternate",
+    "alternate-reverse",
+    "forwards",
+    "backwards",
+    "both",
+    "running",
+    "paused",
+];
+
+/// The `animation` shorthand: a comma-separated list of a keyframes name, a duration, a timing
+/// function, a delay and an iteration count, each optional. Directions, fill modes and play
+/// states are accepted but have no effect.
+pub(super) fn parse_animation_shorthand(value: &str) -> Option<Animations> {
+    let mut animations = Animations::default();
+    for item in split_top_level_commas(value) {
+        let mut name = None;
+        let mut times = Vec::new();
+        let mut timing = None;
+        let mut iterations = None;
+        for token in split_top_level_whitespace(item) {
+            if let Some(time) = parse_time(token) {
+                times.push(time);
+            } else if let Some(function) = parse_timing_function(token) {
+                timing = Some(function);
+            } else if let Some(count) = parse_iteration_count(token) {
+                iterations = Some(count);
+            } else if UNSUPPORTED_ANIMATION_KEYWORDS
+                .iter()
+                .any(|keyword| token.eq_ignore_ascii_case(keyword))
+            {
+                continue;
+            } else if name.is_none() {
+                name = Some(unquote(token));
+            } else {
+                return None;
+            }
+        }
+        if times.len() > 2 {
+            return None;
+        }
+        animations
+            .names
+            .push(name.unwrap_or_else(|| "none".to_owned()));
+        animations
+            .durations
+            .push(times.first().copied().unwrap_or_default());
+        animations
+            .delays
+            .push(times.get(1).copied().unwrap_or_default());
+        animations
+            .timing_functions
+            .push(timing.unwrap_or(TimingFunction::EASE));
+        animations.iteration_counts.push(iterations.unwrap_or(1.0));
+    }
+    Some(animations)
+}
+
+pub(super) fn parse_animation_names(value: &str) -> Vec<String> {
+    value.split(',').map(unquote).collect()
+}
+
+pub(super) fn parse_iteration_counts(value: &str) -> Option<Vec<f32>> {
+    value.split(',').map(parse_iteration_count).collect()
+}
+
+fn parse_iteration_count(value: &str) -> Option<f32> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("infinite") {
+        return Some(f32::INFINITY);
+    }
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|count| count.is_finite() && *count >= 0.0)
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches(['"', '\'']).to_owned()
+}
+
+/// Splits at whitespace outside parentheses, so `cubic-bezier(0, 0, 1, 1)` stays one token.
+fn split_top_level_whitespace(input: &str) -> Vec<&str> {
+    let mut depth = 0usize;
+    let mut start = None;
+    let mut parts = Vec::new();
+    for (index, ch) in input.char_indices() {
+        match ch {
+            '(' => depth = depth.saturating_add(1),
+            ')' => depth = depth.saturating_sub(1),
+            _ if ch.is_whitespace() && depth == 0 => {
+                if let Some(from) = start.take() {
+                    parts.push(&input[from..index]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+    if let Some(from) = start {
+        parts.push(&input[from..]);
+    }
+    parts
+}
+
+/// A value a transition can run between.
+trait Animatable: Clone + PartialEq {
+    fn interpolate(from: Self, to: Self, progress: f32) -> Self;
+}
+
+impl Animatable for u8 {
+    fn interpolate(from: u8, to: u8, progress: f32) -> u8 {
+        let value = f32::from(from) + (f32::from(to) - f32::from(from)) * progress;
+        value.round().clamp(0.0, 255.0) as u8
+    }
+}
+
+impl Animatable for Color {
+    fn interpolate(from: Color, to: Color, progress: f32) -> Color {
+        Color {
+            r: u8::interpolate(from.r, to.r, progress),
+            g: u8::interpolate(from.g, to.g, progress),
+            b: u8::interpolate(from.b, to.b, progress),
+            a: u8::interpolate(from.a, to.a, progress),
+        }
+    }
+}
+
+impl Animatable for Option<Color> {
+    /// No background fades as the other color made transparent.
+    fn interpolate(from: Option<Color>, to: Option<Color>, progress: f32) -> Option<Color> {
+        let clear = |color: Color| Color { a: 0, ..color };
+        match (from, to) {
+            (Some(from), Some(to)) => Some(Color::interpolate(from, to, progress)),
+            (Some(from), None) => Some(Color::interpolate(from, clear(from), progress)),
+            (None, Some(to)) => Some(Color::interpolate(clear(to), to, progress)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Animatable for Vec<TransformFunction> {
+    /// Lists of the same functions in the same order change function by function, with `none`
+    /// standing for the other list's functions changing nothing; other lists switch halfway.
+    fn interpolate(
+        from: Vec<TransformFunction>,
+        to: Vec<TransformFunction>,
+        progress: f32,
+    ) -> Vec<TransformFunction> {
+        let identity = |list: &[TransformFunction]| {
+            list.iter()
+                .map(|function| function.identity())
+                .collect::<Vec<_>>()
+        };
+        let from = if from.is_empty() { identity(&to) } else { from };
+        let to = if to.is_empty() { identity(&from) } else { to };
+        let same_functions = from.len() == to.len()
+            && from
+                .iter()
+                .zip(&to)
+                .all(|(from, to)| std::mem::discriminant(from) == std::mem::discriminant(to));
+        if !same_functions {
+            return if progress < 0.5 { from } else { to };
+        }
+        let mix = |from: f32, to: f32| from + (to - from) * progress;
+        from.iter()
+            .zip(&to)
+            .map(|(from, to)| match (*from, *to) {
+                (TransformFunction::Translate(x0, y0), TransformFunction::Translate(x1, y1)) => {
+                    TransformFunction::Translate(mix(x0, x1), mix(y0, y1))
+                }
+                (TransformFunction::Rotate(from), TransformFunction::Rotate(to)) => {
+                    TransformFunction::Rotate(mix(from, to))
+                }
+                (TransformFunction::Scale(x0, y0), TransformFunction::Scale(x1, y1)) => {
+                    TransformFunction::Scale(mix(x0, x1), mix(y0, y1))
+                }
+                (_, to) => to,
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Running<T> {
+    from: T,
+    start: Instant,
+    transition: Transition,
+}
+
+impl<T> Running<T> {
+    fn end(&self) -> Instant {
+        self.start + self.transition.duration
+    }
+}
+
+/// One property of one element: the value its rules last gave it, and the transition towards
+/// that value still running, if any.
+#[derive(Clone, Debug)]
+struct Track<T> {
+    target: T,
+    running: Option<Running<T>>,
+}
+
+impl<T: Animatable> Track<T> {
+    fn value_at(&self, now: Instant) -> T {
+        let Some(running) = &self.running else {
+            return self.target.clone();
+        };
+        let elapsed = now.saturating_duration_since(running.start).as_secs_f32();
+        let time = elapsed / running.transition.duration.as_secs_f32();
+        T::interpolate(
+            running.from.clone(),
+            self.target.clone(),
+            running.transition.timing.progress(time),
+        )
+    }
+
+    /// Moves towards `target`, starting a transition from the value shown now when it changed,
+    /// and returns the value to show.
+    fn update(&mut self, target: T, transition: Option<Transition>, now: Instant) -> T {
+        if target != self.target {
+            let from = self.value_at(now);
+            self.running = transition.map(|transition| Running {
+                from,
+                start: now + transition.delay,
+                transition,
+            });
+            self.target = target;
+        }
+        let value = self.value_at(now);
+        if self
+            .running
+            .as_ref()
+            .is_some_and(|running| now >= running.end())
+        {
+            self.running = None;
+        }
+        value
+    }
+}
+
+/// An animation an element plays, from when its style first named it.
+#[derive(Clone, Debug)]
+struct Playing {
+    name: String,
+    start: Instant,
+    /// `None` for an animation that repeats forever.
+    end: Option<Instant>,
+}
+
+#[derive(Clone, Debug)]
+struct ElementTracks {
+    opacity: Track<u8>,
+    color: Track<Color>,
+    background_color: Track<Option<Color>>,
+    transform: Track<Vec<TransformFunction>>,
+    animations: Vec<Playing>,
+    /// When the element was last styled; animations of elements no longer laid out don't
+    /// keep asking for frames.
+    seen_at: Instant,
+}
+
+/// Sets the properties `keyframes` animate to their values `progress` of the way through, easing
+/// each stretch between two frames with `timing`.
+fn sample_keyframes(
+    keyframes: &Keyframes,
+    progress: f32,
+    timing: TimingFunction,
+    style: &mut ComputedStyle,
+) {
+    style.opacity = sample_property(
+        keyframes,
+        "opacity",
+        progress,
+        timing,
+        style.opacity,
+        parse_css_opacity_u8,
+    );
+    style.color = sample_property(
+        keyframes,
+        "color",
+        progress,
+        timing,
+        style.color,
+        parse_css_color,
+    );
+    style.background_color = sample_property(
+        keyframes,
+        "background-color",
+        progress,
+        timing,
+        style.background_color,
+        |value| {
+            if value.trim().eq_ignore_ascii_case("transparent") {
+                Some(None)
+            } else {
+                parse_css_color(value).map(Some)
+            }
+        },
+    );
+    style.transform = sample_property(
+        keyframes,
+        "transform",
+        progress,
+        timing,
+        std::mem::take(&mut style.transform),
+        |value| parse_transform(value, None),
+    );
+}
+
+/// Frames that don't set `property` are skipped; without a `from` or `to` frame setting it, the
+/// animation starts or ends at `underlying`, the value the element's rules give it.
+fn sample_property<T: Animatable>(
+    keyframes: &Keyframes,
+    property: &str,
+    progress: f32,
+    timing: TimingFunction,
+    underlying: T,
+    parse: impl Fn(&str) -> Option<T>,
+) -> T {
+    let mut values = keyframes.frames.iter().filter_map(|frame| {
+        let declaration = frame
+            .declarations
+            .iter()
+            .rfind(|declaration| declaration.name == property)?;
+        Some((frame.offset, parse(&declaration.value)?))
+    });
+    let Some(first) = values.next() else {
+        return underlying;
+    };
+    let (mut before, mut after) = ((0.0, underlying.clone()), (1.0, underlying));
+    for (offset, value) in std::iter::once(first).chain(values) {
+        if offset <= progress {
+            before = (offset, value);
+        } else {
+            after = (offset, value);
+            break;
+        }
+    }
+    let span = after.0 - before.0;
+    let time = if span > 0.0 {
+        (progress - before.0) / span
+    } else {
+        1.0
+    };
+    T::interpolate(before.1, after.1, timing.progress(time))
+}
+
+/// The transitions and animations running in a page. Styles are only tracked once a time has been set, so
+/// layouts that aren't shown frame after frame get styles exactly as their rules say.
+#[derive(Debug, Default)]
+pub(super) struct Timeline {
+    now: Option<Instant>,
+    /// Keyed by each element's position among its parent's children, all the way down, so an
+    /// element is recognised after its classes or attributes change.
+    elements: HashMap<Vec<usize>, ElementTracks>,
+    /// When each element whose content moves by itself was first laid out, and last.
+    shown: HashMap<Vec<usize>, (Instant, Instant)>,
+}
+
+impl Timeline {
+    pub(super) fn set_time(&mut self, now: Instant) {
+        self.now = Some(now);
+    }
+
+    /// Whether a transition or animation still has frames to show after those of the time last
+    /// set.
+    pub(super) fn is_running(&self) -> bool {
+        let Some(now) = self.now else {
+            return false;
+        };
+        self.elements.values().any(|tracks| {
+            let ends = [
+                tracks.opacity.running.as_ref().map(Running::end),
+                tracks.color.running.as_ref().map(Running::end),
+                tracks.background_color.running.as_ref().map(Running::end),
+                tracks.transform.running.as_ref().map(Running::end),
+            ];
+            let playing = tracks.seen_at == now
+                && tracks
+                    .animations
+                    .iter()
+                    .any(|playing| playing.end.is_none_or(|end| end > now));
+            playing || ends.into_iter().flatten().any(|end| end > now)
+        }) || self.shown.values().any(|&(_, last)| last == now)
+    }
+
+    pub(super) fn time_shown(
+        &mut self,
+        element: &Element,
+        ancestors: &[&Element],
+    ) -> Option<Duration> {
+        let now = self.now?;
+        let key = element_key(element, ancestors)?;
+        let (first, last) = self.shown.entry(key).or_insert((now, now));
+        *last = now;
+        Some(now.saturating_duration_since(*first))
+    }
+
+    /// Replaces the animated properties of `style` with the values shown now.
+    pub(super) fn apply(
+        &mut self,
+        element: &Element,
+        ancestors: &[&Element],
+        keyframes: &HashMap<String, Keyframes>,
+        style: &mut ComputedStyle,
+    ) {
+        let Some(now) = self.now else {
+            return;
+        };
+        let Some(key) = element_key(element, ancestors) else {
+            return;
+        };
+        let transitions = &style.transitions;
+        let tracks = self.elements.entry(key).or_insert_with(|| ElementTracks {
+            opacity: Track {
+                target: style.opacity,
+                running: None,
+            },
+            color: Track {
+                target: style.color,
+                running: None,
+            },
+            background_color: Track {
+                target: style.background_color,
+                running: None,
+            },
+            transform: Track {
+                target: style.transform.clone(),
+                running: None,
+            },
+            animations: Vec::new(),
+            seen_at: now,
+        });
+        let opacity = tracks.opacity.update(
+            style.opacity,
+            transitions.for_property(TransitionProperty::Opacity),
+            now,
+        );
+        let color = tracks.color.update(
+            style.color,
+            transitions.for_property(TransitionProperty::Color),
+            now,
+        );
+        let background_color = tracks.background_color.update(
+            style.background_color,
+            transitions.for_property(TransitionProperty::BackgroundColor),
+            now,
+        );
+        let transform = tracks.transform.update(
+            std::mem::take(&mut style.transform),
+            transitions.for_property(TransitionProperty::Transform),
+            now,
+        );
+        style.opacity = opacity;
+        style.color = color;
+        style.background_color = background_color;
+        style.transform = transform;
+
+        tracks.seen_at = now;
+        let previous = std::mem::take(&mut tracks.animations);
+        for animation in style.animations.list() {
+            let start = previous
+                .iter()
+                .find(|playing| playing.name == animation.name)
+                .map_or(now, |playing| playing.start);
+            let active = animation.duration.as_secs_f32() * animation.iterations;
+            let end = Duration::try_from_secs_f32(active)
+                .ok()
+                .and_then(|active| animation.delay.checked_add(active))
+                .and_then(|length| start.checked_add(length));
+            let elapsed = now.saturating_duration_since(start);
+            if let Some(keyframes) = keyframes.get(&animation.name)
+                && let Some(playing) = elapsed.checked_sub(animation.delay)
+            {
+                let iteration = playing.as_secs_f32() / animation.duration.as_secs_f32();
+                if iteration < animation.iterations {
+                    sample_keyframes(keyframes, iteration.fract(), animation.timing, style);
+                }
+            }
+            tracks.animations.push(Playing {
+                name: animation.name,
+                start,
+                end,
+            });
+        }
+    }
+}
+
+fn element_key(element: &Element, ancestors: &[&Element]) -> Option<Vec<usize>> {
+    let mut key = Vec::with_capacity(ancestors.len());
+    let mut children = ancestors
+        .iter()
+        .skip(1)
+        .copied()
+        .chain(std::iter::once(element));
+    for parent in ancestors {
+        let child = children.next()?;
+        let index = parent
+            .children
+            .iter()
+            .position(|node| matches!(node, Node::Element(el) if std::ptr::eq(el, child)))?;
+        key.push(index);
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transitions_parse_and_ease_between_values() {
+        let transitions =
+            parse_transition_shorthand("opacity 200ms linear, color 1s ease-in 0.5s").unwrap();
+        let opacity = transitions
+            .for_property(TransitionProperty::Opacity)
+            .unwrap();
+        assert_eq!(opacity.duration, Duration::from_millis(200));
+        assert_eq!(opacity.timing, TimingFunction::Linear);
+        let color = transitions.for_property(TransitionProperty::Color).unwrap();
+        assert_eq!(color.delay, Duration::from_millis(500));
+        assert!(
+            transitions
+                .for_property(TransitionProperty::BackgroundColor)
+                .is_none()
+        );
+        assert!(
+            parse_transition_shorthand("all 1s cubic-bezier(0.1, 0.7, 1.0, 0.1)")
+                .unwrap()
+                .for_property(TransitionProperty::BackgroundColor)
+                .is_some()
+        );
+
+        assert_eq!(TimingFunction::Linear.progress(0.25), 0.25);
+        assert_eq!(TimingFunction::Steps(4).progress(0.3), 0.25);
+        let ease = TimingFunction::EASE.progress(0.5);
+        assert!((ease - 0.8024).abs() < 0.01, "{ease}");
+
+        let start = Instant::now();
+        let mut track = Track {
+            target: 0u8,
+            running: None,
+        };
+        assert_eq!(track.update(200, Some(opacity), start), 0);
+        assert_eq!(
+            track.update(200, Some(opacity), start + Duration::from_millis(100)),
+            100
+        );
+        assert_eq!(
+            track.update(200, Some(opacity), start + Duration::from_millis(300)),
+            200
+        );
+        assert!(track.running.is_none());
+    }
+
+    #[test]
+    fn keyframes_play_from_when_an_element_first_names_them() {
+        let doc = crate::html::parse_document("<div></div>");
+        let div = doc.find_first_element_by_name("div").unwrap();
+        let styles = super::super::StyleComputer::from_css(
+            "@keyframes fade { from { opacity: 0 } 50% { opacity: 0.5 } }\
+             div { opacity: 1; animation: fade 1s linear 2 forwards }",
+        );
+        let root = ComputedStyle::root_defaults();
+        assert_eq!(styles.compute_style(div, &root, &[]).opacity, 255);
+
+        let start = Instant::now();
+        let opacity_at = |millis| {
+            styles.set_animation_time(start + Duration::from_millis(millis));
+            styles.compute_style(div, &root, &[]).opacity
+        };
+        let half = parse_css_opacity_u8("0.5").unwrap();
+        assert_eq!(opacity_at(0), 0);
+        assert_eq!(opacity_at(500), half);
+        let quarter = opacity_at(250);
+        assert!(quarter > 0 && quarter < half, "{quarter}");
+        assert_eq!(opacity_at(1250), quarter);
+        assert!(styles.is_animating());
+        assert_eq!(opacity_at(2000), 255);
+        assert!(!styles.is_animating());
+
+        let spinner = parse_animation_shorthand("'spin' 800ms infinite").unwrap();
+        let spin = &spinner.list()[0];
+        assert_eq!(spin.name, "spin");
+        assert_eq!(spin.iterations, f32::INFINITY);
+        assert!(parse_animation_shorthand("none").unwrap().list().is_empty());
+    }
+
+    #[test]
+    fn transforms_transition_function_by_function() {
+        let transition = parse_transition_shorthand("transform 1s linear")
+            .unwrap()
+            .for_property(TransitionProperty::Transform)
+            .unwrap();
+        let start = Instant::now();
+        let mut track = Track {
+            target: Vec::new(),
+            running: None,
+        };
+        let moved = vec![TransformFunction::Translate(40.0, 0.0)];
+        assert_eq!(
+            track.update(moved.clone(), Some(transition), start),
+            vec![TransformFunction::Translate(0.0, 0.0)]
+        );
+        assert_eq!(
+            track.update(
+                moved.clone(),
+                Some(transition),
+                start + Duration::from_millis(250)
+            ),
+            vec![TransformFunction::Translate(10.0, 0.0)]
+        );
+        assert_eq!(
+            track.update(
+                Vec::new(),
+                Some(transition),
+                start + Duration::from_millis(500)
+            ),
+            vec![TransformFunction::Translate(20.0, 0.0)]
+        );
+        assert_eq!(
+            track.update(
+                Vec::new(),
+                Some(transition),
+                start + Duration::from_millis(1500)
+            ),
+            vec![TransformFunction::Translate(0.0, 0.0)]
+        );
+        assert!(track.running.is_none());
+        assert_eq!(
+            track.update(
+                Vec::new(),
+                Some(transition),
+                start + Duration::from_millis(1600)
+            ),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn keyframes_turn_and_move_transformed_elements() {
+        let doc = crate::html::parse_document("<div></div>");
+        let div = doc.find_first_element_by_name("div").unwrap();
+        let styles = super::super::StyleComputer::from_css(
+            "@keyframes spin { to { transform: rotate(360deg) } }\
+             div { animation: spin 1s linear infinite }",
+        );
+        let root = ComputedStyle::root_defaults();
+        let start = Instant::now();
+        let transform_at = |millis| {
+            styles.set_animation_time(start + Duration::from_millis(millis));
+            styles.compute_style(div, &root, &[]).transform
+        };
+        assert_eq!(transform_at(0), vec![TransformFunction::Rotate(0.0)]);
+        assert_eq!(transform_at(1250), vec![TransformFunction::Rotate(90.0)]);
+        assert!(styles.is_animating());
+
+        let from = vec![
+            TransformFunction::Translate(10.0, 0.0),
+            TransformFunction::Scale(2.0, 2.0),
+        ];
+        let to = vec![
+            TransformFunction::Translate(30.0, 0.0),
+            TransformFunction::Rotate(90.0),
+        ];
+        assert_eq!(Vec::interpolate(from.clone(), to.clone(), 0.25), from);
+        assert_eq!(Vec::interpolate(from.clone(), to.clone(), 0.75), to);
+        assert_eq!(
+            Vec::interpolate(Vec::new(), from, 0.5),
+            vec![
+                TransformFunction::Translate(5.0, 0.0),
+                TransformFunction::Scale(1.5, 1.5)
+            ]
+        );
+    }
+}
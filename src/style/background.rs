@@ -91,7 +91,7 @@ fn first_component_outside_parens(input: &str) -> &str {
     input[..idx].trim()
 }
 
-fn split_top_level_commas(input: &str) -> Vec<&str> {
+pub(super) fn split_top_level_commas(input: &str) -> Vec<&str> {
     let bytes = input.as_bytes();
     let mut depth = 0usize;
     let mut start = 0usize;
@@ -1,10 +1,11 @@
 use super::CustomProperties;
 use super::parse::{parse_css_color, parse_css_length_px_with_viewport, parse_html_length_px};
 use super::{
-    AutoEdges, BorderStyle, ComputedStyle, CssEdges, CssLength, Display, FlexAlignItems,
-    FlexDirection, FlexJustifyContent, FlexWrap, Float, FontFamily, LineHeight, LinearGradient,
-    Position, TextAlign, TextTransform, Visibility, WhiteSpace, custom_properties, declarations,
-    length,
+    Animations, AutoEdges, BorderStyle, Clear, ComputedStyle, ContentSize, CssEdges, CssLength,
+    Direction, Display, FlexAlignItems, FlexDirection, FlexJustifyContent, FlexWrap, Float,
+    FontFamily, FontVariantCaps, Hyphens, LineHeight, LinearGradient, OutlineStyle, Position,
+    TextAlign, TextTransform, TransformFunction, Transitions, VerticalAlign, Visibility,
+    WhiteSpace, custom_properties, declarations, length, transform,
 };
 use crate::css::{Rule, Specificity};
 use crate::dom::Element;
@@ -74,6 +75,7 @@ pub(super) struct StyleBuilder {
     visibility: Option<Cascaded<Visibility>>,
     position: Option<Cascaded<Position>>,
     float: Option<Cascaded<Float>>,
+    clear: Option<Cascaded<Clear>>,
     top_px: Option<Cascaded<Option<CssLength>>>,
     right_px: Option<Cascaded<Option<CssLength>>>,
     bottom_px: Option<Cascaded<Option<CssLength>>>,
@@ -85,11 +87,17 @@ pub(super) struct StyleBuilder {
     font_family: Option<Cascaded<FontFamily>>,
     font_size_px: Option<Cascaded<i32>>,
     letter_spacing: Option<Cascaded<LetterSpacing>>,
+    word_spacing: Option<Cascaded<LetterSpacing>>,
+    font_variant_caps: Option<Cascaded<FontVariantCaps>>,
     bold: Option<Cascaded<bool>>,
     underline: Option<Cascaded<bool>>,
     text_align: Option<Cascaded<TextAlign>>,
+    direction: Option<Cascaded<Direction>>,
     text_transform: Option<Cascaded<TextTransform>>,
+    hyphens: Option<Cascaded<Hyphens>>,
+    text_indent: Option<Cascaded<CssLength>>,
     white_space: Option<Cascaded<WhiteSpace>>,
+    vertical_align: Option<Cascaded<VerticalAlign>>,
     line_height: Option<Cascaded<LineHeight>>,
     margin: Option<Cascaded<Edges>>,
     margin_auto: Option<Cascaded<AutoEdges>>,
@@ -97,23 +105,33 @@ pub(super) struct StyleBuilder {
     border_style: Option<Cascaded<BorderStyle>>,
     border_color: Option<Cascaded<Color>>,
     border_radius_px: Option<Cascaded<i32>>,
+    outline_width_px: Option<Cascaded<i32>>,
+    outline_style: Option<Cascaded<OutlineStyle>>,
+    outline_color: Option<Cascaded<Option<Color>>>,
+    outline_offset_px: Option<Cascaded<i32>>,
     padding: Option<Cascaded<CssEdges>>,
     width_px: Option<Cascaded<Option<CssLength>>>,
     min_width_px: Option<Cascaded<Option<CssLength>>>,
     max_width_px: Option<Cascaded<Option<CssLength>>>,
-    height_px: Option<Cascaded<Option<i32>>>,
-    min_height_px: Option<Cascaded<Option<i32>>>,
+    height_px: Option<Cascaded<Option<CssLength>>>,
+    min_height_px: Option<Cascaded<Option<CssLength>>>,
+    max_height_px: Option<Cascaded<Option<CssLength>>>,
     flex_justify_content: Option<Cascaded<FlexJustifyContent>>,
     flex_align_items: Option<Cascaded<FlexAlignItems>>,
     flex_direction: Option<Cascaded<FlexDirection>>,
     flex_wrap: Option<Cascaded<FlexWrap>>,
     flex_grow: Option<Cascaded<i32>>,
     flex_shrink: Option<Cascaded<i32>>,
-    flex_basis_px: Option<Cascaded<Option<i32>>>,
+    flex_basis_px: Option<Cascaded<Option<CssLength>>>,
+    flex_align_self: Option<Cascaded<Option<FlexAlignItems>>>,
+    flex_order: Option<Cascaded<i32>>,
     flex_gap_px: Option<Cascaded<i32>>,
     grid_area: Option<Cascaded<Option<String>>>,
     grid_template_columns: Option<Cascaded<Option<String>>>,
     grid_template_areas: Option<Cascaded<Option<String>>>,
+    transform: Option<Cascaded<Vec<TransformFunction>>>,
+    transitions: Option<Cascaded<Transitions>>,
+    animations: Option<Cascaded<Animations>>,
 }
 
 impl StyleBuilder {
@@ -128,6 +146,7 @@ impl StyleBuilder {
             visibility: None,
             position: None,
             float: None,
+            clear: None,
             top_px: None,
             right_px: None,
             bottom_px: None,
@@ -139,11 +158,17 @@ impl StyleBuilder {
             font_family: None,
             font_size_px: None,
             letter_spacing: None,
+            word_spacing: None,
+            font_variant_caps: None,
             bold: None,
             underline: None,
             text_align: None,
+            direction: None,
             text_transform: None,
+            hyphens: None,
+            text_indent: None,
             white_space: None,
+            vertical_align: None,
             line_height: None,
             margin: None,
             margin_auto: None,
@@ -151,12 +176,17 @@ impl StyleBuilder {
             border_style: None,
             border_color: None,
             border_radius_px: None,
+            outline_width_px: None,
+            outline_style: None,
+            outline_color: None,
+            outline_offset_px: None,
             padding: None,
             width_px: None,
             min_width_px: None,
             max_width_px: None,
             height_px: None,
             min_height_px: None,
+            max_height_px: None,
             flex_justify_content: None,
             flex_align_items: None,
             flex_direction: None,
@@ -164,10 +194,15 @@ impl StyleBuilder {
             flex_grow: None,
             flex_shrink: None,
             flex_basis_px: None,
+            flex_align_self: None,
+            flex_order: None,
             flex_gap_px: None,
             grid_area: None,
             grid_template_columns: None,
             grid_template_areas: None,
+            transform: None,
+            transitions: None,
+            animations: None,
         }
     }
 
@@ -187,6 +222,10 @@ impl StyleBuilder {
         length::parse_css_length(value, viewport_width_px, viewport_height_px)
     }
 
+    pub(super) fn parse_transform(&self, value: &str) -> Option<Vec<TransformFunction>> {
+        transform::parse_transform(value, self.viewport)
+    }
+
     pub(super) fn finish(self) -> ComputedStyle {
         let font_size_px = self
             .font_size_px
@@ -197,6 +236,11 @@ impl StyleBuilder {
             .map(|v| v.value)
             .unwrap_or(LetterSpacing::Px(self.base.letter_spacing_px))
             .resolve_px(font_size_px);
+        let word_spacing_px = self
+            .word_spacing
+            .map(|v| v.value)
+            .unwrap_or(LetterSpacing::Px(self.base.word_spacing_px))
+            .resolve_px(font_size_px);
 
         ComputedStyle {
             display: self.display.map(|v| v.value).unwrap_or(self.base.display),
@@ -206,6 +250,7 @@ impl StyleBuilder {
                 .unwrap_or(self.base.visibility),
             position: self.position.map(|v| v.value).unwrap_or(self.base.position),
             float: self.float.map(|v| v.value).unwrap_or(self.base.float),
+            clear: self.clear.map(|v| v.value).unwrap_or(self.base.clear),
             custom_properties: self.custom_properties,
             top_px: self.top_px.map(|v| v.value).unwrap_or(self.base.top_px),
             right_px: self.right_px.map(|v| v.value).unwrap_or(self.base.right_px),
@@ -230,6 +275,11 @@ impl StyleBuilder {
                 .unwrap_or(self.base.font_family),
             font_size_px,
             letter_spacing_px,
+            word_spacing_px,
+            font_variant_caps: self
+                .font_variant_caps
+                .map(|v| v.value)
+                .unwrap_or(self.base.font_variant_caps),
             bold: self.bold.map(|v| v.value).unwrap_or(self.base.bold),
             underline: self
                 .underline
@@ -239,14 +289,27 @@ impl StyleBuilder {
                 .text_align
                 .map(|v| v.value)
                 .unwrap_or(self.base.text_align),
+            direction: self
+                .direction
+                .map(|v| v.value)
+                .unwrap_or(self.base.direction),
             text_transform: self
                 .text_transform
                 .map(|v| v.value)
                 .unwrap_or(self.base.text_transform),
+            hyphens: self.hyphens.map(|v| v.value).unwrap_or(self.base.hyphens),
+            text_indent: self
+                .text_indent
+                .map(|v| v.value)
+                .unwrap_or(self.base.text_indent),
             white_space: self
                 .white_space
                 .map(|v| v.value)
                 .unwrap_or(self.base.white_space),
+            vertical_align: self
+                .vertical_align
+                .map(|v| v.value)
+                .unwrap_or(self.base.vertical_align),
             line_height: self
                 .line_height
                 .map(|v| v.value)
@@ -272,6 +335,22 @@ impl StyleBuilder {
                 .border_radius_px
                 .map(|v| v.value)
                 .unwrap_or(self.base.border_radius_px),
+            outline_width_px: self
+                .outline_width_px
+                .map(|v| v.value)
+                .unwrap_or(self.base.outline_width_px),
+            outline_style: self
+                .outline_style
+                .map(|v| v.value)
+                .unwrap_or(self.base.outline_style),
+            outline_color: self
+                .outline_color
+                .map(|v| v.value)
+                .unwrap_or(self.base.outline_color),
+            outline_offset_px: self
+                .outline_offset_px
+                .map(|v| v.value)
+                .unwrap_or(self.base.outline_offset_px),
             padding: self.padding.map(|v| v.value).unwrap_or(self.base.padding),
             width_px: self.width_px.map(|v| v.value).unwrap_or(self.base.width_px),
             min_width_px: self
@@ -290,6 +369,10 @@ impl StyleBuilder {
                 .min_height_px
                 .map(|v| v.value)
                 .unwrap_or(self.base.min_height_px),
+            max_height_px: self
+                .max_height_px
+                .map(|v| v.value)
+                .unwrap_or(self.base.max_height_px),
             flex_justify_content: self
                 .flex_justify_content
                 .map(|v| v.value)
@@ -318,6 +401,14 @@ impl StyleBuilder {
                 .flex_basis_px
                 .map(|v| v.value)
                 .unwrap_or(self.base.flex_basis_px),
+            flex_align_self: self
+                .flex_align_self
+                .map(|v| v.value)
+                .unwrap_or(self.base.flex_align_self),
+            flex_order: self
+                .flex_order
+                .map(|v| v.value)
+                .unwrap_or(self.base.flex_order),
             flex_gap_px: self
                 .flex_gap_px
                 .map(|v| v.value)
@@ -334,6 +425,18 @@ impl StyleBuilder {
                 .grid_template_areas
                 .map(|v| v.value)
                 .unwrap_or_else(|| self.base.grid_template_areas.clone()),
+            transform: self
+                .transform
+                .map(|v| v.value)
+                .unwrap_or_else(|| self.base.transform.clone()),
+            transitions: self
+                .transitions
+                .map(|v| v.value)
+                .unwrap_or_else(|| self.base.transitions.clone()),
+            animations: self
+                .animations
+                .map(|v| v.value)
+                .unwrap_or_else(|| self.base.animations.clone()),
         }
     }
 
@@ -411,8 +514,52 @@ impl StyleBuilder {
             self.apply_text_align(TextAlign::Center, priority);
         }
 
+        if element.name == "marquee" {
+            self.apply_white_space(WhiteSpace::NoWrap, priority);
+        }
+
+        // An open dialog is shown as a modal one: fixed, as wide as its content and centred, which
+        // the layout finishes by centring it vertically over a backdrop.
+        if element.name == "dialog" && element.attributes.get("open").is_some() {
+            self.apply_position(Position::Fixed, priority);
+            self.apply_left(Some(CssLength::Px(0)), priority);
+            self.apply_right(Some(CssLength::Px(0)), priority);
+            self.apply_width(Some(CssLength::Content(ContentSize::FitContent)), priority);
+            self.apply_margin_auto(
+                AutoEdges {
+                    top: true,
+                    right: true,
+                    bottom: true,
+                    left: true,
+                },
+                priority,
+            );
+            self.apply_border_width(
+                Edges {
+                    top: 3,
+                    right: 3,
+                    bottom: 3,
+                    left: 3,
+                },
+                priority,
+            );
+            self.apply_border_style(BorderStyle::Solid, priority);
+            self.apply_border_color(Color::BLACK, priority);
+            self.apply_padding(
+                CssEdges {
+                    top: CssLength::Px(16),
+                    right: CssLength::Px(16),
+                    bottom: CssLength::Px(16),
+                    left: CssLength::Px(16),
+                },
+                priority,
+            );
+            self.apply_background_color(Some(Color::WHITE), priority);
+            self.apply_color(Color::BLACK, priority);
+        }
+
         if element.name == "td" && element.attributes.get("align").is_none() {
-            self.apply_text_align(TextAlign::Left, priority);
+            self.apply_text_align(TextAlign::Start, priority);
         }
 
         if element.name == "font" {
@@ -442,7 +589,7 @@ impl StyleBuilder {
             .get("height")
             .and_then(parse_html_length_px)
         {
-            self.apply_height(Some(height), priority);
+            self.apply_height(Some(CssLength::Px(height)), priority);
         }
 
         if let Some(align) = element.attributes.get("align") {
@@ -456,6 +603,14 @@ impl StyleBuilder {
                 self.apply_text_align(align, priority);
             }
         }
+
+        if let Some(dir) = element.attributes.get("dir") {
+            match dir.trim().to_ascii_lowercase().as_str() {
+                "ltr" => self.apply_direction(Direction::Ltr, priority),
+                "rtl" => self.apply_direction(Direction::Rtl, priority),
+                _ => {}
+            }
+        }
     }
 
     pub(super) fn apply_matched_custom_properties(&mut self, matched: &[MatchedRule<'_>]) {
@@ -571,6 +726,10 @@ impl StyleBuilder {
         apply_cascade(&mut self.float, value, priority);
     }
 
+    pub(super) fn apply_clear(&mut self, value: Clear, priority: CascadePriority) {
+        apply_cascade(&mut self.clear, value, priority);
+    }
+
     pub(super) fn apply_top(&mut self, value: Option<CssLength>, priority: CascadePriority) {
         apply_cascade(&mut self.top_px, value, priority);
     }
@@ -623,6 +782,18 @@ impl StyleBuilder {
         apply_cascade(&mut self.letter_spacing, value, priority);
     }
 
+    pub(super) fn apply_word_spacing(&mut self, value: LetterSpacing, priority: CascadePriority) {
+        apply_cascade(&mut self.word_spacing, value, priority);
+    }
+
+    pub(super) fn apply_font_variant_caps(
+        &mut self,
+        value: FontVariantCaps,
+        priority: CascadePriority,
+    ) {
+        apply_cascade(&mut self.font_variant_caps, value, priority);
+    }
+
     pub(super) fn apply_bold(&mut self, value: bool, priority: CascadePriority) {
         apply_cascade(&mut self.bold, value, priority);
     }
@@ -639,10 +810,26 @@ impl StyleBuilder {
         apply_cascade(&mut self.text_transform, value, priority);
     }
 
+    pub(super) fn apply_hyphens(&mut self, value: Hyphens, priority: CascadePriority) {
+        apply_cascade(&mut self.hyphens, value, priority);
+    }
+
+    pub(super) fn apply_text_indent(&mut self, value: CssLength, priority: CascadePriority) {
+        apply_cascade(&mut self.text_indent, value, priority);
+    }
+
+    pub(super) fn apply_direction(&mut self, value: Direction, priority: CascadePriority) {
+        apply_cascade(&mut self.direction, value, priority);
+    }
+
     pub(super) fn apply_white_space(&mut self, value: WhiteSpace, priority: CascadePriority) {
         apply_cascade(&mut self.white_space, value, priority);
     }
 
+    pub(super) fn apply_vertical_align(&mut self, value: VerticalAlign, priority: CascadePriority) {
+        apply_cascade(&mut self.vertical_align, value, priority);
+    }
+
     pub(super) fn apply_line_height(&mut self, value: LineHeight, priority: CascadePriority) {
         apply_cascade(&mut self.line_height, value, priority);
     }
@@ -671,6 +858,22 @@ impl StyleBuilder {
         apply_cascade(&mut self.border_radius_px, value, priority);
     }
 
+    pub(super) fn apply_outline_width_px(&mut self, value: i32, priority: CascadePriority) {
+        apply_cascade(&mut self.outline_width_px, value, priority);
+    }
+
+    pub(super) fn apply_outline_style(&mut self, value: OutlineStyle, priority: CascadePriority) {
+        apply_cascade(&mut self.outline_style, value, priority);
+    }
+
+    pub(super) fn apply_outline_color(&mut self, value: Option<Color>, priority: CascadePriority) {
+        apply_cascade(&mut self.outline_color, value, priority);
+    }
+
+    pub(super) fn apply_outline_offset_px(&mut self, value: i32, priority: CascadePriority) {
+        apply_cascade(&mut self.outline_offset_px, value, priority);
+    }
+
     pub(super) fn apply_padding(&mut self, value: CssEdges, priority: CascadePriority) {
         apply_cascade(&mut self.padding, value, priority);
     }
@@ -687,14 +890,18 @@ impl StyleBuilder {
         apply_cascade(&mut self.max_width_px, value, priority);
     }
 
-    pub(super) fn apply_height(&mut self, value: Option<i32>, priority: CascadePriority) {
+    pub(super) fn apply_height(&mut self, value: Option<CssLength>, priority: CascadePriority) {
         apply_cascade(&mut self.height_px, value, priority);
     }
 
-    pub(super) fn apply_min_height(&mut self, value: Option<i32>, priority: CascadePriority) {
+    pub(super) fn apply_min_height(&mut self, value: Option<CssLength>, priority: CascadePriority) {
         apply_cascade(&mut self.min_height_px, value, priority);
     }
 
+    pub(super) fn apply_max_height(&mut self, value: Option<CssLength>, priority: CascadePriority) {
+        apply_cascade(&mut self.max_height_px, value, priority);
+    }
+
     pub(super) fn apply_flex_justify_content(
         &mut self,
         value: FlexJustifyContent,
@@ -727,10 +934,22 @@ impl StyleBuilder {
         apply_cascade(&mut self.flex_shrink, value, priority);
     }
 
-    pub(super) fn apply_flex_basis(&mut self, value: Option<i32>, priority: CascadePriority) {
+    pub(super) fn apply_flex_basis(&mut self, value: Option<CssLength>, priority: CascadePriority) {
         apply_cascade(&mut self.flex_basis_px, value, priority);
     }
 
+    pub(super) fn apply_flex_align_self(
+        &mut self,
+        value: Option<FlexAlignItems>,
+        priority: CascadePriority,
+    ) {
+        apply_cascade(&mut self.flex_align_self, value, priority);
+    }
+
+    pub(super) fn apply_flex_order(&mut self, value: i32, priority: CascadePriority) {
+        apply_cascade(&mut self.flex_order, value, priority);
+    }
+
     pub(super) fn apply_flex_gap_px(&mut self, value: i32, priority: CascadePriority) {
         apply_cascade(&mut self.flex_gap_px, value, priority);
     }
@@ -755,6 +974,52 @@ impl StyleBuilder {
         apply_cascade(&mut self.grid_template_areas, value, priority);
     }
 
+    pub(super) fn apply_transform(
+        &mut self,
+        value: Vec<TransformFunction>,
+        priority: CascadePriority,
+    ) {
+        apply_cascade(&mut self.transform, value, priority);
+    }
+
+    pub(super) fn apply_transitions(&mut self, value: Transitions, priority: CascadePriority) {
+        apply_cascade(&mut self.transitions, value, priority);
+    }
+
+    /// Sets one of the `transition-*` lists, keeping the others as cascaded so far.
+    pub(super) fn apply_transitions_component(
+        &mut self,
+        update: impl FnOnce(&mut Transitions),
+        priority: CascadePriority,
+    ) {
+        let mut transitions = self
+            .transitions
+            .as_ref()
+            .map(|v| v.value.clone())
+            .unwrap_or_else(|| self.base.transitions.clone());
+        update(&mut transitions);
+        self.apply_transitions(transitions, priority);
+    }
+
+    pub(super) fn apply_animations(&mut self, value: Animations, priority: CascadePriority) {
+        apply_cascade(&mut self.animations, value, priority);
+    }
+
+    /// Sets one of the `animation-*` lists, keeping the others as cascaded so far.
+    pub(super) fn apply_animations_component(
+        &mut self,
+        update: impl FnOnce(&mut Animations),
+        priority: CascadePriority,
+    ) {
+        let mut animations = self
+            .animations
+            .as_ref()
+            .map(|v| v.value.clone())
+            .unwrap_or_else(|| self.base.animations.clone());
+        update(&mut animations);
+        self.apply_animations(animations, priority);
+    }
+
     pub(super) fn apply_padding_component(
         &mut self,
         update: impl FnOnce(CssEdges) -> CssEdges,
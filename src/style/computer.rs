@@ -1,15 +1,35 @@
+use super::animation::Timeline;
 use super::builder::{MatchedRule, StyleBuilder};
+use super::parallel::RuleMatches;
 use super::{ComputedStyle, Display};
-use crate::css::{CompoundSelector, Stylesheet};
+use crate::css::{CompoundSelector, Keyframes, PseudoElement, Rule, Specificity, Stylesheet};
 use crate::dom::{Document, Element, Node};
+use crate::geom::Color;
 use crate::render::Viewport;
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 pub struct StyleComputer {
     stylesheets: Vec<Arc<Stylesheet>>,
     rules: Vec<RuleRef>,
     index: SelectorIndex,
+    /// Whether colors are inverted for `--dark`, which is only done when the page has no dark
+    /// styles of its own.
+    inverts_colors: bool,
+    /// Whether `<noscript>` content is displayed; see [`crate::css_media::set_render_noscript`].
+    renders_noscript: bool,
+    /// Whether any selector styles a pseudo-element, without which none need matching.
+    has_pseudo_element_rules: bool,
+    /// Elements styled so far and the time spent on them, for [`StyleComputer::styling_stats`].
+    styled_elements: AtomicU64,
+    styling_nanos: AtomicU64,
+    /// `@keyframes` rules by name, the last of each name winning.
+    keyframes: HashMap<String, Keyframes>,
+    /// The transitions and animations of the page's elements, for styles computed frame after
+    /// frame.
+    timeline: Mutex<Timeline>,
 }
 
 impl StyleComputer {
@@ -18,6 +38,13 @@ impl StyleComputer {
             stylesheets: Vec::new(),
             rules: Vec::new(),
             index: SelectorIndex::default(),
+            inverts_colors: crate::css_media::force_dark(),
+            renders_noscript: crate::css_media::render_noscript(),
+            has_pseudo_element_rules: false,
+            styled_elements: AtomicU64::new(0),
+            styling_nanos: AtomicU64::new(0),
+            keyframes: HashMap::new(),
+            timeline: Mutex::new(Timeline::default()),
         }
     }
 
@@ -28,10 +55,24 @@ impl StyleComputer {
 
     pub fn from_stylesheets(stylesheets: Vec<Arc<Stylesheet>>) -> StyleComputer {
         let (rules, index) = build_rule_index(&stylesheets);
+        let inverts_colors = crate::css_media::force_dark() && !has_dark_styles(&stylesheets);
+        let has_pseudo_element_rules = has_pseudo_element_rules(&stylesheets);
+        let keyframes = stylesheets
+            .iter()
+            .flat_map(|sheet| &sheet.keyframes)
+            .map(|keyframes| (keyframes.name.clone(), keyframes.clone()))
+            .collect();
         StyleComputer {
             stylesheets,
             rules,
             index,
+            inverts_colors,
+            renders_noscript: crate::css_media::render_noscript(),
+            has_pseudo_element_rules,
+            styled_elements: AtomicU64::new(0),
+            styling_nanos: AtomicU64::new(0),
+            keyframes,
+            timeline: Mutex::new(Timeline::default()),
         }
     }
 
@@ -47,7 +88,7 @@ impl StyleComputer {
         parent: &ComputedStyle,
         ancestors: &[&Element],
     ) -> ComputedStyle {
-        self.compute_style_impl(element, parent, ancestors, None)
+        self.compute_style_impl(element, parent, ancestors, None, None)
     }
 
     pub fn compute_style_in_viewport(
@@ -63,6 +104,109 @@ impl StyleComputer {
             parent,
             ancestors,
             Some((viewport_width_px.max(0), viewport_height_px.max(0))),
+            None,
+        )
+    }
+
+    /// Like [`StyleComputer::compute_style_in_viewport`], but takes the element's rules from
+    /// `matches` when they were found there for the same ancestors.
+    pub fn compute_style_with_matches(
+        &self,
+        element: &Element,
+        parent: &ComputedStyle,
+        ancestors: &[&Element],
+        viewport_width_px: i32,
+        viewport_height_px: i32,
+        matches: &RuleMatches<'_>,
+    ) -> ComputedStyle {
+        self.compute_style_impl(
+            element,
+            parent,
+            ancestors,
+            Some((viewport_width_px.max(0), viewport_height_px.max(0))),
+            Some(matches),
+        )
+    }
+
+    /// The style of `element`'s `pseudo`-element, which inherits from `element_style`, or `None`
+    /// when no rule styles it.
+    pub fn compute_pseudo_style(
+        &self,
+        element: &Element,
+        pseudo: PseudoElement,
+        element_style: &ComputedStyle,
+        ancestors: &[&Element],
+        viewport_width_px: i32,
+        viewport_height_px: i32,
+    ) -> Option<ComputedStyle> {
+        if !self.has_pseudo_element_rules {
+            return None;
+        }
+        let viewport = Some((viewport_width_px.max(0), viewport_height_px.max(0)));
+        let rule_ids = self.match_rule_ids_for(element, ancestors, viewport, Some(pseudo));
+        if rule_ids.is_empty() {
+            return None;
+        }
+        let matched = self.matched_rules(&rule_ids);
+
+        let mut style = ComputedStyle::inherit_from(element_style, Display::Inline);
+        if self.inverts_colors {
+            style.color = invert_lightness(element_style.color);
+            style.border_color = style.color;
+        }
+        let mut builder = StyleBuilder::new(style, viewport);
+        builder.apply_matched_custom_properties(&matched);
+        builder.finalize_custom_properties();
+        builder.apply_matched_styles(&matched);
+
+        let mut style = builder.finish();
+        if self.inverts_colors {
+            invert_colors(&mut style);
+        }
+        Some(style)
+    }
+
+    /// Whether this computer inverts the page's colors; see [`crate::css_media::set_force_dark`].
+    pub fn inverts_colors(&self) -> bool {
+        self.inverts_colors
+    }
+
+    /// Sets the time styles are computed for from now on. Until it is set, styles are computed
+    /// as their rules say; from then on, an element whose transitioned properties change
+    /// eases towards their new values, and `@keyframes` animations play.
+    pub fn set_animation_time(&self, now: Instant) {
+        if let Ok(mut timeline) = self.timeline.lock() {
+            timeline.set_time(now);
+        }
+    }
+
+    /// Whether a transition or animation has frames left to show after the time last set.
+    pub fn is_animating(&self) -> bool {
+        self.timeline
+            .lock()
+            .is_ok_and(|timeline| timeline.is_running())
+    }
+
+    /// How long `element` has been laid out for, once a time has been set. Content that moves by
+    /// itself, like a `<marquee>`'s, keeps frames coming for as long as it is laid out.
+    pub fn time_shown(&self, element: &Element, ancestors: &[&Element]) -> Option<Duration> {
+        self.timeline.lock().ok()?.time_shown(element, ancestors)
+    }
+
+    /// Carries on the transitions and animations `previous` was running, for a page whose stylesheets changed.
+    pub fn continue_animations_from(&self, previous: &StyleComputer) {
+        if let (Ok(mut timeline), Ok(mut previous)) =
+            (self.timeline.lock(), previous.timeline.lock())
+        {
+            *timeline = std::mem::take(&mut *previous);
+        }
+    }
+
+    /// How many elements this computer has styled, and the time that took.
+    pub fn styling_stats(&self) -> (u64, Duration) {
+        (
+            self.styled_elements.load(Ordering::Relaxed),
+            Duration::from_nanos(self.styling_nanos.load(Ordering::Relaxed)),
         )
     }
 
@@ -72,31 +216,100 @@ impl StyleComputer {
         parent: &ComputedStyle,
         ancestors: &[&Element],
         viewport: Option<(i32, i32)>,
+        prematched: Option<&RuleMatches<'_>>,
     ) -> ComputedStyle {
-        let display = default_display_for_element(element);
-        let style = ComputedStyle::inherit_from(parent, display);
+        let started = Instant::now();
+        let display = default_display_for_element(element, self.renders_noscript);
+        let mut style = ComputedStyle::inherit_from(parent, display);
+        if self.inverts_colors && !ancestors.is_empty() {
+            // The parent's color was inverted; declarations are resolved against the page's own.
+            style.color = invert_lightness(parent.color);
+            style.border_color = style.color;
+        }
         let mut builder = StyleBuilder::new(style, viewport);
 
         builder.apply_presentational_hints(element);
 
-        let matched = self.match_rules(element, ancestors, viewport);
+        let matched = match prematched.and_then(|matches| matches.get(element, ancestors, viewport))
+        {
+            Some(rule_ids) => self.matched_rules(rule_ids),
+            None => self.match_rules(element, ancestors, viewport),
+        };
         builder.apply_matched_custom_properties(&matched);
         builder.apply_inline_style_custom_properties(element);
         builder.finalize_custom_properties();
         builder.apply_matched_styles(&matched);
         builder.apply_inline_style(element);
 
-        builder.finish()
+        let mut style = builder.finish();
+        if self.inverts_colors {
+            invert_colors(&mut style);
+        }
+        if !(style.transitions.is_empty() && style.animations.is_empty())
+            && let Ok(mut timeline) = self.timeline.lock()
+        {
+            timeline.apply(element, ancestors, &self.keyframes, &mut style);
+        }
+        self.styled_elements.fetch_add(1, Ordering::Relaxed);
+        self.add_styling_time(started.elapsed());
+        style
+    }
+
+    pub(super) fn add_styling_time(&self, elapsed: Duration) {
+        let nanos = elapsed.as_nanos().try_into().unwrap_or(u64::MAX);
+        self.styling_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    fn match_rules(
+        &self,
+        element: &Element,
+        ancestors: &[&Element],
+        viewport: Option<(i32, i32)>,
+    ) -> Vec<MatchedRule<'_>> {
+        self.matched_rules(&self.match_rule_ids(element, ancestors, viewport))
+    }
+
+    /// The rules behind ids found by [`StyleComputer::match_rule_ids`].
+    pub(super) fn matched_rules(&self, rule_ids: &[(usize, Specificity)]) -> Vec<MatchedRule<'_>> {
+        rule_ids
+            .iter()
+            .filter_map(|&(rule_id, specificity)| {
+                let (rule_ref, rule) = self.rule(rule_id)?;
+                Some(MatchedRule {
+                    rule,
+                    specificity,
+                    order: rule_ref.order,
+                })
+            })
+            .collect()
+    }
+
+    fn rule(&self, rule_id: usize) -> Option<(&RuleRef, &Rule)> {
+        let rule_ref = self.rules.get(rule_id)?;
+        let sheet = self.stylesheets.get(rule_ref.sheet_index)?;
+        let rule = sheet.rules.get(rule_ref.rule_index)?;
+        Some((rule_ref, rule))
+    }
+
+    /// Ids and specificities of the rules matching `element`, in source order.
+    pub(super) fn match_rule_ids(
+        &self,
+        element: &Element,
+        ancestors: &[&Element],
+        viewport: Option<(i32, i32)>,
+    ) -> Vec<(usize, Specificity)> {
+        self.match_rule_ids_for(element, ancestors, viewport, None)
     }
 
-    fn match_rules<'a>(
-        &'a self,
+    fn match_rule_ids_for(
+        &self,
         element: &Element,
         ancestors: &[&Element],
         viewport: Option<(i32, i32)>,
-    ) -> Vec<MatchedRule<'a>> {
+        pseudo: Option<PseudoElement>,
+    ) -> Vec<(usize, Specificity)> {
         let mut seen = HashSet::<usize>::new();
-        let mut matched = Vec::<MatchedRule<'a>>::new();
+        let mut matched = Vec::<(usize, Specificity, u32)>::new();
         let viewport = viewport.map(|(width_px, height_px)| Viewport {
             width_px,
             height_px,
@@ -106,13 +319,7 @@ impl StyleComputer {
             if !seen.insert(rule_id) {
                 return;
             }
-            let Some(rule_ref) = self.rules.get(rule_id) else {
-                return;
-            };
-            let Some(sheet) = self.stylesheets.get(rule_ref.sheet_index) else {
-                return;
-            };
-            let Some(rule) = sheet.rules.get(rule_ref.rule_index) else {
+            let Some((rule_ref, rule)) = self.rule(rule_id) else {
                 return;
             };
             if let Some(media) = rule.media.as_deref() {
@@ -123,15 +330,12 @@ impl StyleComputer {
                     return;
                 }
             }
-            let Some((specificity, _)) = super::selectors::match_rule(rule, element, ancestors)
+            let Some((specificity, _)) =
+                super::selectors::match_rule(rule, element, ancestors, pseudo)
             else {
                 return;
             };
-            matched.push(MatchedRule {
-                rule,
-                specificity,
-                order: rule_ref.order,
-            });
+            matched.push((rule_id, specificity, rule_ref.order));
         };
 
         for &rule_id in &self.index.universal {
@@ -160,8 +364,11 @@ impl StyleComputer {
             }
         }
 
-        matched.sort_by_key(|rule| rule.order);
+        matched.sort_by_key(|&(_, _, order)| order);
         matched
+            .into_iter()
+            .map(|(rule_id, specificity, _)| (rule_id, specificity))
+            .collect()
     }
 }
 
@@ -182,7 +389,7 @@ fn collect_style_text(element: &Element, out: &mut String) {
     }
 }
 
-fn default_display_for_element(element: &Element) -> Display {
+fn default_display_for_element(element: &Element, renders_noscript: bool) -> Display {
     if element.name == "#document" {
         return Display::Block;
     }
@@ -194,6 +401,27 @@ fn default_display_for_element(element: &Element) -> Display {
         return Display::None;
     }
 
+    if element.attributes.get("hidden").is_some() {
+        return Display::None;
+    }
+
+    if element.name == "noscript" && !renders_noscript {
+        return Display::None;
+    }
+
+    // Without `controls` there is nothing of an `<audio>` to show.
+    if element.name == "audio" && element.attributes.get("controls").is_none() {
+        return Display::None;
+    }
+
+    if element.name == "dialog" {
+        return if element.attributes.get("open").is_some() {
+            Display::Block
+        } else {
+            Display::None
+        };
+    }
+
     if element.name == "table" {
         return Display::Table;
     }
@@ -206,8 +434,8 @@ fn default_display_for_element(element: &Element) -> Display {
 
     match element.name.as_str() {
         "html" | "body" | "div" | "p" | "center" | "header" | "main" | "footer" | "nav" | "ul"
-        | "ol" | "li" | "h1" | "h2" | "h3" | "blockquote" | "pre" => Display::Block,
-        "img" | "svg" | "button" | "input" => Display::InlineBlock,
+        | "ol" | "li" | "h1" | "h2" | "h3" | "blockquote" | "pre" | "marquee" => Display::Block,
+        "img" | "svg" | "button" | "input" | "video" | "audio" => Display::InlineBlock,
         "br" => Display::Inline,
         _ => Display::Inline,
     }
@@ -275,6 +503,65 @@ fn selector_bucket_key(last: &CompoundSelector) -> SelectorBucketKey<'_> {
     SelectorBucketKey::Universal
 }
 
+/// Whether the page styles itself for dark mode, with a `prefers-color-scheme: dark` query or a
+/// `color-scheme` that allows dark.
+fn has_dark_styles(stylesheets: &[Arc<Stylesheet>]) -> bool {
+    stylesheets
+        .iter()
+        .flat_map(|sheet| &sheet.rules)
+        .any(|rule| {
+            let dark_query = rule.media.as_deref().is_some_and(|media| {
+                let media = media.to_ascii_lowercase();
+                media.contains("prefers-color-scheme") && media.contains("dark")
+            });
+            dark_query
+                || rule.declarations.iter().any(|declaration| {
+                    declaration.name.eq_ignore_ascii_case("color-scheme")
+                        && declaration
+                            .value
+                            .split_ascii_whitespace()
+                            .any(|scheme| scheme.eq_ignore_ascii_case("dark"))
+                })
+        })
+}
+
+fn has_pseudo_element_rules(stylesheets: &[Arc<Stylesheet>]) -> bool {
+    stylesheets
+        .iter()
+        .flat_map(|sheet| &sheet.rules)
+        .flat_map(|rule| &rule.selectors)
+        .any(|selector| {
+            selector
+                .parts
+                .last()
+                .is_some_and(|subject| subject.pseudo_element.is_some())
+        })
+}
+
+fn invert_colors(style: &mut ComputedStyle) {
+    style.color = invert_lightness(style.color);
+    style.background_color = style.background_color.map(invert_lightness);
+    style.border_color = invert_lightness(style.border_color);
+    if let Some(gradient) = &mut style.background_gradient {
+        gradient.start = invert_lightness(gradient.start);
+        gradient.end = invert_lightness(gradient.end);
+    }
+}
+
+/// Flips a color's HSL lightness and keeps its hue and saturation, so white becomes black and a
+/// dark blue a light one. Applying it twice gives back the original color.
+pub(crate) fn invert_lightness(color: Color) -> Color {
+    let max = color.r.max(color.g).max(color.b);
+    let min = color.r.min(color.g).min(color.b);
+    let flip = |channel: u8| (255 + u16::from(channel) - u16::from(max) - u16::from(min)) as u8;
+    Color {
+        r: flip(color.r),
+        g: flip(color.g),
+        b: flip(color.b),
+        a: color.a,
+    }
+}
+
 fn build_rule_index(stylesheets: &[Arc<Stylesheet>]) -> (Vec<RuleRef>, SelectorIndex) {
     let mut rules = Vec::new();
     let mut index = SelectorIndex::default();
@@ -300,7 +587,7 @@ fn build_rule_index(stylesheets: &[Arc<Stylesheet>]) -> (Vec<RuleRef>, SelectorI
 mod tests {
     use super::*;
     use crate::geom::Color;
-    use crate::style::WhiteSpace;
+    use crate::style::{CssLength, FlexAlignItems, FontVariantCaps, WhiteSpace};
 
     #[test]
     fn selector_matches_descendant() {
@@ -332,6 +619,72 @@ mod tests {
         assert_eq!(style.display, Display::Grid);
     }
 
+    #[test]
+    fn parses_word_spacing_and_small_caps() {
+        let doc = crate::html::parse_document("<div class='text'><b></b></div>");
+        let computer = StyleComputer::from_css(
+            ".text { font-size: 20px; word-spacing: 0.5em; font-variant: small-caps; } \
+             b { font-variant-caps: all-small-caps; }",
+        );
+        let root_style = ComputedStyle::root_defaults();
+        let div = doc
+            .find_first_element_by_name("div")
+            .expect("div element exists");
+        let b = div.find_first_element_by_name("b").expect("b exists");
+
+        let style = computer.compute_style(div, &root_style, &[]);
+        assert_eq!(style.word_spacing_px, 10);
+        assert_eq!(style.font_variant_caps, FontVariantCaps::SmallCaps);
+        let inner = computer.compute_style(b, &style, &[div]);
+        assert_eq!(inner.word_spacing_px, 10);
+        assert_eq!(inner.font_variant_caps, FontVariantCaps::AllSmallCaps);
+    }
+
+    #[test]
+    fn parses_flex_shorthand_order_and_align_self() {
+        let doc = crate::html::parse_document("<div class='item'></div>");
+        let root_style = ComputedStyle::root_defaults();
+        let div = doc
+            .find_first_element_by_name("div")
+            .expect("div element exists");
+        let flex = |css: &str| {
+            let computer = StyleComputer::from_css(&format!(".item {{ {css} }}"));
+            let style = computer.compute_style(div, &root_style, &[]);
+            (style.flex_grow, style.flex_shrink, style.flex_basis_px)
+        };
+
+        assert!(matches!(flex("flex: 2"), (2, 1, Some(CssLength::Px(0)))));
+        assert!(matches!(
+            flex("flex: 1 1 0%"),
+            (1, 1, Some(CssLength::Percent(0.0)))
+        ));
+        assert!(matches!(flex("flex: 0 0 auto"), (0, 0, None)));
+        assert!(matches!(
+            flex("flex: 3 40px"),
+            (3, 1, Some(CssLength::Px(40)))
+        ));
+        assert!(matches!(
+            flex("flex: 50% 2 0"),
+            (2, 0, Some(CssLength::Percent(50.0)))
+        ));
+        assert!(matches!(
+            flex("flex: 120px"),
+            (1, 1, Some(CssLength::Px(120)))
+        ));
+        assert!(matches!(flex("flex: initial"), (0, 1, None)));
+        assert!(matches!(flex("flex: 1 2 3 4"), (0, 1, None)));
+
+        let computer =
+            StyleComputer::from_css(".item { order: -2; align-self: flex-end; flex-basis: 25%; }");
+        let style = computer.compute_style(div, &root_style, &[]);
+        assert_eq!(style.flex_order, -2);
+        assert_eq!(style.flex_align_self, Some(FlexAlignItems::End));
+        assert!(matches!(
+            style.flex_basis_px,
+            Some(CssLength::Percent(25.0))
+        ));
+    }
+
     #[test]
     fn parses_column_gap_into_flex_gap() {
         let doc = crate::html::parse_document("<div class='layout'></div>");
@@ -416,4 +769,64 @@ mod tests {
         let style = computer.compute_style(menu, &root_style, &ancestors);
         assert_eq!(style.color, crate::geom::Color::WHITE);
     }
+
+    #[test]
+    fn forced_dark_inverts_declared_colors_once() {
+        let doc = crate::html::parse_document(
+            "<html><body><p><em>t</em></p><img src=a.png></body></html>",
+        );
+        let mut computer =
+            StyleComputer::from_css("body { background: #ffffff; } p { color: #000080; }");
+        computer.inverts_colors = true;
+        let html = doc.find_first_element_by_name("html").unwrap();
+        let body = doc.find_first_element_by_name("body").unwrap();
+        let p = doc.find_first_element_by_name("p").unwrap();
+        let em = doc.find_first_element_by_name("em").unwrap();
+
+        let html_style = computer.compute_style(html, &ComputedStyle::root_defaults(), &[]);
+        assert_eq!(html_style.color, Color::WHITE);
+        let body_style = computer.compute_style(body, &html_style, &[html]);
+        assert_eq!(body_style.background_color, Some(Color::BLACK));
+        assert_eq!(body_style.color, Color::WHITE);
+        let p_style = computer.compute_style(p, &body_style, &[html, body]);
+        let light_blue = Color {
+            r: 127,
+            g: 127,
+            b: 255,
+            a: 255,
+        };
+        assert_eq!(p_style.color, light_blue);
+        let em_style = computer.compute_style(em, &p_style, &[html, body, p]);
+        assert_eq!(em_style.color, light_blue);
+        assert_eq!(invert_lightness(light_blue).b, 0x80);
+
+        assert!(!has_dark_styles(&computer.stylesheets));
+        for css in [
+            "@media (prefers-color-scheme: dark) { body { color: white; } }",
+            ":root { color-scheme: light dark; }",
+        ] {
+            assert!(
+                has_dark_styles(&[Arc::new(Stylesheet::parse(css))]),
+                "{css}"
+            );
+        }
+    }
+
+    #[test]
+    fn noscript_content_is_displayed_unless_turned_off() {
+        let doc = crate::html::parse_document(
+            "<html><body><noscript><p>Enable JavaScript</p></noscript></body></html>",
+        );
+        let mut computer = StyleComputer::from_css("");
+        let html = doc.find_first_element_by_name("html").unwrap();
+        let body = doc.find_first_element_by_name("body").unwrap();
+        let noscript = doc.find_first_element_by_name("noscript").unwrap();
+        let root = ComputedStyle::root_defaults();
+
+        let shown = computer.compute_style(noscript, &root, &[html, body]);
+        assert_eq!(shown.display, Display::Inline);
+        computer.renders_noscript = false;
+        let hidden = computer.compute_style(noscript, &root, &[html, body]);
+        assert_eq!(hidden.display, Display::None);
+    }
 }
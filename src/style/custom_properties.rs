@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::sync::Arc;
 
 use super::{CascadePriority, Cascaded};
 
@@ -8,13 +8,13 @@ const MAX_VAR_RECURSION_DEPTH: usize = 32;
 
 #[derive(Clone, Debug)]
 pub struct CustomProperties {
-    values: Rc<HashMap<String, String>>,
+    values: Arc<HashMap<String, String>>,
 }
 
 impl Default for CustomProperties {
     fn default() -> Self {
         Self {
-            values: Rc::new(HashMap::new()),
+            values: Arc::new(HashMap::new()),
         }
     }
 }
@@ -25,6 +25,17 @@ impl CustomProperties {
         self.values.get(&name).map(String::as_str)
     }
 
+    /// Names and values sorted by name.
+    pub(super) fn sorted(&self) -> Vec<(&str, &str)> {
+        let mut values: Vec<_> = self
+            .values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        values.sort_unstable();
+        values
+    }
+
     pub(super) fn merge(
         inherited: &CustomProperties,
         declared: &HashMap<String, Cascaded<String>>,
@@ -44,7 +55,7 @@ impl CustomProperties {
         }
 
         CustomProperties {
-            values: Rc::new(merged),
+            values: Arc::new(merged),
         }
     }
 
@@ -1,13 +1,16 @@
 use crate::geom::Edges;
 
+use super::animation;
+use super::length::parse_content_size;
 use super::parse::{
     parse_css_box_edges, parse_css_box_edges_with_auto, parse_css_color, parse_css_flex,
     parse_css_font_family, parse_css_length_px,
 };
 use super::{
-    AutoEdges, BorderStyle, CascadePriority, CssEdges, CssLength, Display, FlexAlignItems,
-    FlexDirection, FlexJustifyContent, FlexWrap, Float, LetterSpacing, Position, StyleBuilder,
-    TextAlign, TextTransform, Visibility, WhiteSpace,
+    AutoEdges, BorderStyle, CascadePriority, Clear, CssEdges, CssLength, Direction, Display,
+    FlexAlignItems, FlexDirection, FlexJustifyContent, FlexWrap, Float, FontVariantCaps, Hyphens,
+    LetterSpacing, OutlineStyle, Position, StyleBuilder, TextAlign, TextTransform, VerticalAlign,
+    Visibility, WhiteSpace,
 };
 
 pub(super) fn apply_declaration(
@@ -71,6 +74,18 @@ pub(super) fn apply_declaration(
                 builder.apply_float(float, priority);
             }
         }
+        "clear" => {
+            let clear = match value.trim().to_ascii_lowercase().as_str() {
+                "none" => Some(Clear::None),
+                "left" => Some(Clear::Left),
+                "right" => Some(Clear::Right),
+                "both" => Some(Clear::Both),
+                _ => None,
+            };
+            if let Some(clear) = clear {
+                builder.apply_clear(clear, priority);
+            }
+        }
         "top" => {
             let value = value.trim();
             if value.eq_ignore_ascii_case("auto")
@@ -154,13 +169,26 @@ pub(super) fn apply_declaration(
             }
         }
         "letter-spacing" => {
-            let value = value.trim();
-            if value.eq_ignore_ascii_case("normal") {
-                builder.apply_letter_spacing(LetterSpacing::Normal, priority);
-            } else if let Some(factor) = parse_em_factor(value) {
-                builder.apply_letter_spacing(LetterSpacing::Em(factor), priority);
-            } else if let Some(px) = builder.parse_css_length_px(value) {
-                builder.apply_letter_spacing(LetterSpacing::Px(px), priority);
+            if let Some(spacing) = parse_spacing(builder, value) {
+                builder.apply_letter_spacing(spacing, priority);
+            }
+        }
+        "word-spacing" => {
+            if let Some(spacing) = parse_spacing(builder, value) {
+                builder.apply_word_spacing(spacing, priority);
+            }
+        }
+        "font-variant" | "font-variant-caps" => {
+            let caps = value.split_ascii_whitespace().find_map(|keyword| {
+                match keyword.to_ascii_lowercase().as_str() {
+                    "normal" | "none" => Some(FontVariantCaps::Normal),
+                    "small-caps" => Some(FontVariantCaps::SmallCaps),
+                    "all-small-caps" => Some(FontVariantCaps::AllSmallCaps),
+                    _ => None,
+                }
+            });
+            if let Some(caps) = caps {
+                builder.apply_font_variant_caps(caps, priority);
             }
         }
         "font-weight" => {
@@ -184,12 +212,24 @@ pub(super) fn apply_declaration(
                 "left" => Some(TextAlign::Left),
                 "center" => Some(TextAlign::Center),
                 "right" => Some(TextAlign::Right),
+                "start" => Some(TextAlign::Start),
+                "end" => Some(TextAlign::End),
                 _ => None,
             };
             if let Some(align) = align {
                 builder.apply_text_align(align, priority);
             }
         }
+        "direction" => {
+            let direction = match value.trim().to_ascii_lowercase().as_str() {
+                "ltr" => Some(Direction::Ltr),
+                "rtl" => Some(Direction::Rtl),
+                _ => None,
+            };
+            if let Some(direction) = direction {
+                builder.apply_direction(direction, priority);
+            }
+        }
         "text-transform" => {
             let transform = match value.trim().to_ascii_lowercase().as_str() {
                 "uppercase" => Some(TextTransform::Uppercase),
@@ -201,6 +241,36 @@ pub(super) fn apply_declaration(
                 builder.apply_text_transform(transform, priority);
             }
         }
+        "hyphens" | "-webkit-hyphens" => {
+            let hyphens = match value.trim().to_ascii_lowercase().as_str() {
+                "none" => Some(Hyphens::None),
+                "manual" => Some(Hyphens::Manual),
+                "auto" => Some(Hyphens::Auto),
+                _ => None,
+            };
+            if let Some(hyphens) = hyphens {
+                builder.apply_hyphens(hyphens, priority);
+            }
+        }
+        "text-indent" => {
+            if let Some(indent) = builder.parse_css_length(value) {
+                builder.apply_text_indent(indent, priority);
+            }
+        }
+        "vertical-align" => {
+            let align = match value.trim().to_ascii_lowercase().as_str() {
+                "baseline" => Some(VerticalAlign::Baseline),
+                "middle" => Some(VerticalAlign::Middle),
+                "top" => Some(VerticalAlign::Top),
+                "bottom" => Some(VerticalAlign::Bottom),
+                "text-top" => Some(VerticalAlign::TextTop),
+                "text-bottom" => Some(VerticalAlign::TextBottom),
+                _ => builder.parse_css_length(value).map(VerticalAlign::Length),
+            };
+            if let Some(align) = align {
+                builder.apply_vertical_align(align, priority);
+            }
+        }
         "white-space" => {
             let white_space = match value.trim().to_ascii_lowercase().as_str() {
                 "normal" => Some(WhiteSpace::Normal),
@@ -299,6 +369,37 @@ pub(super) fn apply_declaration(
                 builder.apply_border_radius_px(px.max(0), priority);
             }
         }
+        "outline" => {
+            let outline = parse_outline_shorthand(builder, value);
+            if let Some(outline) = outline {
+                builder.apply_outline_width_px(outline.width_px.unwrap_or(3), priority);
+                builder.apply_outline_style(outline.style.unwrap_or(OutlineStyle::None), priority);
+                builder.apply_outline_color(outline.color, priority);
+            }
+        }
+        "outline-width" => {
+            if let Some(width_px) = parse_outline_width_px(builder, value) {
+                builder.apply_outline_width_px(width_px, priority);
+            }
+        }
+        "outline-style" => {
+            if let Some(style) = parse_outline_style(value) {
+                builder.apply_outline_style(style, priority);
+            }
+        }
+        "outline-color" => match value.trim().to_ascii_lowercase().as_str() {
+            "currentcolor" | "invert" => builder.apply_outline_color(None, priority),
+            _ => {
+                if let Some(color) = parse_css_color(value) {
+                    builder.apply_outline_color(Some(color), priority);
+                }
+            }
+        },
+        "outline-offset" => {
+            if let Some(offset_px) = builder.parse_css_length_px(value) {
+                builder.apply_outline_offset_px(offset_px, priority);
+            }
+        }
         "margin" => {
             if let Some((edges, auto)) = parse_css_box_edges_with_auto(value) {
                 builder.apply_margin(edges, priority);
@@ -338,6 +439,8 @@ pub(super) fn apply_declaration(
                 || value.eq_ignore_ascii_case("initial")
             {
                 builder.apply_width(None, priority);
+            } else if let Some(size) = parse_content_size(value) {
+                builder.apply_width(Some(CssLength::Content(size)), priority);
             } else if let Some(length) = builder.parse_css_length(value) {
                 builder.apply_width(Some(length), priority);
             }
@@ -346,6 +449,8 @@ pub(super) fn apply_declaration(
             let value = value.trim();
             if value.eq_ignore_ascii_case("unset") || value.eq_ignore_ascii_case("initial") {
                 builder.apply_min_width(None, priority);
+            } else if let Some(size) = parse_content_size(value) {
+                builder.apply_min_width(Some(CssLength::Content(size)), priority);
             } else if let Some(length) = builder.parse_css_length(value) {
                 builder.apply_min_width(Some(length), priority);
             }
@@ -354,6 +459,8 @@ pub(super) fn apply_declaration(
             let value = value.trim();
             if value.eq_ignore_ascii_case("unset") || value.eq_ignore_ascii_case("initial") {
                 builder.apply_max_width(None, priority);
+            } else if let Some(size) = parse_content_size(value) {
+                builder.apply_max_width(Some(CssLength::Content(size)), priority);
             } else if let Some(length) = builder.parse_css_length(value) {
                 builder.apply_max_width(Some(length), priority);
             }
@@ -365,22 +472,35 @@ pub(super) fn apply_declaration(
                 || value.eq_ignore_ascii_case("initial")
             {
                 builder.apply_height(None, priority);
-            } else if let Some(px) = builder.parse_css_length_px(value) {
-                builder.apply_height(Some(px), priority);
+            } else if let Some(length) = builder.parse_css_length(value) {
+                builder.apply_height(Some(length), priority);
             }
         }
         "min-height" => {
             let value = value.trim();
             if value.eq_ignore_ascii_case("unset") || value.eq_ignore_ascii_case("initial") {
                 builder.apply_min_height(None, priority);
-            } else if let Some(px) = builder.parse_css_length_px(value) {
-                builder.apply_min_height(Some(px), priority);
+            } else if let Some(length) = builder.parse_css_length(value) {
+                builder.apply_min_height(Some(length), priority);
+            }
+        }
+        "max-height" => {
+            let value = value.trim();
+            if value.eq_ignore_ascii_case("none")
+                || value.eq_ignore_ascii_case("unset")
+                || value.eq_ignore_ascii_case("initial")
+            {
+                builder.apply_max_height(None, priority);
+            } else if let Some(length) = builder.parse_css_length(value) {
+                builder.apply_max_height(Some(length), priority);
             }
         }
         "flex-direction" => {
             let direction = match value.trim().to_ascii_lowercase().as_str() {
                 "row" => Some(FlexDirection::Row),
+                "row-reverse" => Some(FlexDirection::RowReverse),
                 "column" => Some(FlexDirection::Column),
+                "column-reverse" => Some(FlexDirection::ColumnReverse),
                 _ => None,
             };
             if let Some(direction) = direction {
@@ -414,20 +534,27 @@ pub(super) fn apply_declaration(
                 || value.eq_ignore_ascii_case("initial")
             {
                 builder.apply_flex_basis(None, priority);
-            } else if let Some(px) = builder.parse_css_length_px(value) {
-                builder.apply_flex_basis(Some(px.max(0)), priority);
+            } else if let Some(length) = builder.parse_css_length(value) {
+                builder.apply_flex_basis(Some(length), priority);
             }
         }
         "flex" => {
-            if let Some(flex) = parse_css_flex(value) {
+            if let Some(flex) = parse_css_flex(value, |basis| builder.parse_css_length(basis)) {
                 builder.apply_flex_grow(flex.grow, priority);
                 builder.apply_flex_shrink(flex.shrink, priority);
                 builder.apply_flex_basis(flex.basis_px, priority);
             }
         }
+        "order" => {
+            if let Ok(order) = value.trim().parse::<i32>() {
+                builder.apply_flex_order(order, priority);
+            }
+        }
         "justify-content" => {
             let justify = match value.trim().to_ascii_lowercase().as_str() {
                 "space-between" => Some(FlexJustifyContent::SpaceBetween),
+                "space-around" => Some(FlexJustifyContent::SpaceAround),
+                "space-evenly" => Some(FlexJustifyContent::SpaceEvenly),
                 "flex-start" | "start" => Some(FlexJustifyContent::Start),
                 "center" => Some(FlexJustifyContent::Center),
                 "flex-end" | "end" => Some(FlexJustifyContent::End),
@@ -439,6 +566,7 @@ pub(super) fn apply_declaration(
         }
         "align-items" => {
             let align = match value.trim().to_ascii_lowercase().as_str() {
+                "stretch" | "normal" => Some(FlexAlignItems::Stretch),
                 "center" => Some(FlexAlignItems::Center),
                 "flex-start" | "start" => Some(FlexAlignItems::Start),
                 "flex-end" | "end" => Some(FlexAlignItems::End),
@@ -448,6 +576,19 @@ pub(super) fn apply_declaration(
                 builder.apply_flex_align_items(align, priority);
             }
         }
+        "align-self" => {
+            let align = match value.trim().to_ascii_lowercase().as_str() {
+                "auto" | "unset" | "initial" => Some(None),
+                "stretch" | "normal" => Some(Some(FlexAlignItems::Stretch)),
+                "center" => Some(Some(FlexAlignItems::Center)),
+                "flex-start" | "start" | "self-start" => Some(Some(FlexAlignItems::Start)),
+                "flex-end" | "end" | "self-end" => Some(Some(FlexAlignItems::End)),
+                _ => None,
+            };
+            if let Some(align) = align {
+                builder.apply_flex_align_self(align, priority);
+            }
+        }
         "gap" => {
             let first = value.split_whitespace().next().unwrap_or("");
             if let Some(px) = builder.parse_css_length_px(first) {
@@ -490,6 +631,86 @@ pub(super) fn apply_declaration(
                 builder.apply_grid_template_columns(Some(value.to_owned()), priority);
             }
         }
+        "transform" | "-webkit-transform" => {
+            if let Some(transform) = builder.parse_transform(value) {
+                builder.apply_transform(transform, priority);
+            }
+        }
+        "transition" => {
+            if let Some(transitions) = animation::parse_transition_shorthand(value) {
+                builder.apply_transitions(transitions, priority);
+            }
+        }
+        "transition-property" => {
+            let properties = animation::parse_transition_properties(value);
+            builder.apply_transitions_component(
+                |transitions| transitions.properties = properties,
+                priority,
+            );
+        }
+        "transition-duration" => {
+            if let Some(durations) = animation::parse_times(value) {
+                builder.apply_transitions_component(
+                    |transitions| transitions.durations = durations,
+                    priority,
+                );
+            }
+        }
+        "transition-delay" => {
+            if let Some(delays) = animation::parse_times(value) {
+                builder.apply_transitions_component(
+                    |transitions| transitions.delays = delays,
+                    priority,
+                );
+            }
+        }
+        "transition-timing-function" => {
+            if let Some(timing_functions) = animation::parse_timing_functions(value) {
+                builder.apply_transitions_component(
+                    |transitions| transitions.timing_functions = timing_functions,
+                    priority,
+                );
+            }
+        }
+        "animation" | "-webkit-animation" => {
+            if let Some(animations) = animation::parse_animation_shorthand(value) {
+                builder.apply_animations(animations, priority);
+            }
+        }
+        "animation-name" | "-webkit-animation-name" => {
+            let names = animation::parse_animation_names(value);
+            builder.apply_animations_component(|animations| animations.names = names, priority);
+        }
+        "animation-duration" | "-webkit-animation-duration" => {
+            if let Some(durations) = animation::parse_times(value) {
+                builder.apply_animations_component(
+                    |animations| animations.durations = durations,
+                    priority,
+                );
+            }
+        }
+        "animation-delay" | "-webkit-animation-delay" => {
+            if let Some(delays) = animation::parse_times(value) {
+                builder
+                    .apply_animations_component(|animations| animations.delays = delays, priority);
+            }
+        }
+        "animation-timing-function" | "-webkit-animation-timing-function" => {
+            if let Some(timing_functions) = animation::parse_timing_functions(value) {
+                builder.apply_animations_component(
+                    |animations| animations.timing_functions = timing_functions,
+                    priority,
+                );
+            }
+        }
+        "animation-iteration-count" | "-webkit-animation-iteration-count" => {
+            if let Some(iteration_counts) = animation::parse_iteration_counts(value) {
+                builder.apply_animations_component(
+                    |animations| animations.iteration_counts = iteration_counts,
+                    priority,
+                );
+            }
+        }
         "grid-template-areas" => {
             let value = value.trim();
             if value.eq_ignore_ascii_case("unset")
@@ -570,6 +791,63 @@ fn parse_border_shorthand(value: &str) -> Option<ParsedBorder> {
     })
 }
 
+#[derive(Clone, Copy, Debug)]
+struct ParsedOutline {
+    width_px: Option<i32>,
+    style: Option<OutlineStyle>,
+    color: Option<crate::geom::Color>,
+}
+
+/// The `outline` shorthand: a width, a style and a color in any order, each optional. What it
+/// leaves out goes back to its initial value.
+fn parse_outline_shorthand(builder: &StyleBuilder, value: &str) -> Option<ParsedOutline> {
+    let mut outline = ParsedOutline {
+        width_px: None,
+        style: None,
+        color: None,
+    };
+    for token in value.split_whitespace() {
+        if outline.width_px.is_none()
+            && let Some(width_px) = parse_outline_width_px(builder, token)
+        {
+            outline.width_px = Some(width_px);
+        } else if outline.style.is_none()
+            && let Some(style) = parse_outline_style(token)
+        {
+            outline.style = Some(style);
+        } else if outline.color.is_none()
+            && let Some(color) = parse_css_color(token)
+        {
+            outline.color = Some(color);
+        } else if !token.eq_ignore_ascii_case("currentcolor")
+            && !token.eq_ignore_ascii_case("invert")
+        {
+            return None;
+        }
+    }
+    Some(outline)
+}
+
+fn parse_outline_width_px(builder: &StyleBuilder, value: &str) -> Option<i32> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "thin" => Some(1),
+        "medium" => Some(3),
+        "thick" => Some(5),
+        _ => builder.parse_css_length_px(value).map(|px| px.max(0)),
+    }
+}
+
+fn parse_outline_style(value: &str) -> Option<OutlineStyle> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "none" | "hidden" => Some(OutlineStyle::None),
+        "auto" => Some(OutlineStyle::Auto),
+        "solid" | "dotted" | "dashed" | "double" | "groove" | "ridge" | "inset" | "outset" => {
+            Some(OutlineStyle::Solid)
+        }
+        _ => None,
+    }
+}
+
 fn parse_css_box_edges_length(builder: &StyleBuilder, value: &str) -> Option<CssEdges> {
     let lengths: Vec<CssLength> = value
         .split_whitespace()
@@ -627,7 +905,7 @@ fn parse_css_border_radius_px(value: &str) -> Option<i32> {
     parse_css_length_px(first)
 }
 
-fn parse_css_opacity_u8(value: &str) -> Option<u8> {
+pub(super) fn parse_css_opacity_u8(value: &str) -> Option<u8> {
     let value = value.trim();
     if value.is_empty() {
         return None;
@@ -636,6 +914,19 @@ fn parse_css_opacity_u8(value: &str) -> Option<u8> {
     Some((number.clamp(0.0, 1.0) * 255.0).round() as u8)
 }
 
+/// `letter-spacing` and `word-spacing`: `normal`, or a length that scales with the font when it
+/// is in `em`s.
+fn parse_spacing(builder: &StyleBuilder, value: &str) -> Option<LetterSpacing> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("normal") {
+        Some(LetterSpacing::Normal)
+    } else if let Some(factor) = parse_em_factor(value) {
+        Some(LetterSpacing::Em(factor))
+    } else {
+        builder.parse_css_length_px(value).map(LetterSpacing::Px)
+    }
+}
+
 fn parse_em_factor(value: &str) -> Option<f32> {
     let value = value.trim();
     let number = value.strip_suffix("em")?;
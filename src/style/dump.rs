@@ -0,0 +1,166 @@
+use super::{ComputedStyle, StyleComputer};
+use crate::dom::{Document, Element, Node, element_path};
+use std::fmt::{Debug, Write as _};
+
+impl StyleComputer {
+    /// Each element's path, followed by the computed properties that differ from their initial
+    /// values and its custom properties, one per line, for `--dump-styles`.
+    pub fn dump_styles(
+        &self,
+        document: &Document,
+        viewport_width_px: i32,
+        viewport_height_px: i32,
+    ) -> String {
+        let initial = ComputedStyle::root_defaults();
+        let mut dump = StyleDump {
+            styles: self,
+            viewport: (viewport_width_px, viewport_height_px),
+            initial: properties(&initial),
+            out: String::new(),
+        };
+        let mut ancestors = Vec::new();
+        dump.element(document.render_root(), &initial, &mut ancestors);
+        dump.out
+    }
+}
+
+struct StyleDump<'a> {
+    styles: &'a StyleComputer,
+    viewport: (i32, i32),
+    initial: Vec<(&'static str, String)>,
+    out: String,
+}
+
+impl<'doc> StyleDump<'_> {
+    fn element(
+        &mut self,
+        element: &'doc Element,
+        parent: &ComputedStyle,
+        ancestors: &mut Vec<&'doc Element>,
+    ) {
+        let style = self.styles.compute_style_in_viewport(
+            element,
+            parent,
+            ancestors,
+            self.viewport.0,
+            self.viewport.1,
+        );
+        self.out.push_str(&element_path(ancestors, element));
+        self.out.push('\n');
+        for ((name, value), (_, initial)) in properties(&style).iter().zip(&self.initial) {
+            if value != initial {
+                let _ = writeln!(self.out, "  {name}: {value}");
+            }
+        }
+        for (name, value) in style.custom_properties.sorted() {
+            let _ = writeln!(self.out, "  {name}: {value}");
+        }
+
+        ancestors.push(element);
+        for child in &element.children {
+            if let Node::Element(child) = child {
+                self.element(child, &style, ancestors);
+            }
+        }
+        ancestors.pop();
+    }
+}
+
+fn properties(style: &ComputedStyle) -> Vec<(&'static str, String)> {
+    vec![
+        ("display", value(&style.display)),
+        ("visibility", value(&style.visibility)),
+        ("position", value(&style.position)),
+        ("float", value(&style.float)),
+        ("clear", value(&style.clear)),
+        ("top", optional(&style.top_px)),
+        ("right", optional(&style.right_px)),
+        ("bottom", optional(&style.bottom_px)),
+        ("left", optional(&style.left_px)),
+        ("opacity", value(&style.opacity)),
+        ("color", value(&style.color)),
+        ("background-color", optional(&style.background_color)),
+        ("background-image", optional(&style.background_gradient)),
+        ("font-family", value(&style.font_family)),
+        ("font-size", value(&style.font_size_px)),
+        ("letter-spacing", value(&style.letter_spacing_px)),
+        ("word-spacing", value(&style.word_spacing_px)),
+        ("font-variant-caps", value(&style.font_variant_caps)),
+        ("bold", value(&style.bold)),
+        ("underline", value(&style.underline)),
+        ("text-align", value(&style.text_align)),
+        ("direction", value(&style.direction)),
+        ("text-transform", value(&style.text_transform)),
+        ("hyphens", value(&style.hyphens)),
+        ("text-indent", value(&style.text_indent)),
+        ("white-space", value(&style.white_space)),
+        ("vertical-align", value(&style.vertical_align)),
+        ("line-height", value(&style.line_height)),
+        ("margin", value(&style.margin)),
+        ("margin-auto", value(&style.margin_auto)),
+        ("border-width", value(&style.border_width)),
+        ("border-style", value(&style.border_style)),
+        ("border-color", value(&style.border_color)),
+        ("border-radius", value(&style.border_radius_px)),
+        ("outline-width", value(&style.outline_width_px)),
+        ("outline-style", value(&style.outline_style)),
+        ("outline-color", optional(&style.outline_color)),
+        ("outline-offset", value(&style.outline_offset_px)),
+        ("padding", value(&style.padding)),
+        ("width", optional(&style.width_px)),
+        ("min-width", optional(&style.min_width_px)),
+        ("max-width", optional(&style.max_width_px)),
+        ("height", optional(&style.height_px)),
+        ("min-height", optional(&style.min_height_px)),
+        ("max-height", optional(&style.max_height_px)),
+        ("justify-content", value(&style.flex_justify_content)),
+        ("align-items", value(&style.flex_align_items)),
+        ("flex-direction", value(&style.flex_direction)),
+        ("flex-wrap", value(&style.flex_wrap)),
+        ("flex-grow", value(&style.flex_grow)),
+        ("flex-shrink", value(&style.flex_shrink)),
+        ("flex-basis", optional(&style.flex_basis_px)),
+        ("align-self", optional(&style.flex_align_self)),
+        ("order", value(&style.flex_order)),
+        ("gap", value(&style.flex_gap_px)),
+        ("grid-area", optional(&style.grid_area)),
+        (
+            "grid-template-columns",
+            optional(&style.grid_template_columns),
+        ),
+        ("grid-template-areas", optional(&style.grid_template_areas)),
+        ("transform", value(&style.transform)),
+        ("transition", value(&style.transitions)),
+        ("animation", value(&style.animations)),
+    ]
+}
+
+fn value(value: &impl Debug) -> String {
+    format!("{value:?}")
+}
+
+fn optional(value: &Option<impl Debug>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "none".to_owned(), |value| format!("{value:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn lists_properties_that_differ_from_their_initial_values() {
+        let document = crate::html::parse_document(
+            "<html><style>:root { --gap: 4px } p.note { color: #f00; margin: 0 }</style><body><p class=note>Hi</p></body></html>",
+        );
+        let styles = super::StyleComputer::from_document(&document);
+        let dump = styles.dump_styles(&document, 800, 600);
+        let note = dump
+            .split_once("html > body > p.note\n")
+            .map(|(_, rest)| rest)
+            .unwrap();
+        assert!(note.starts_with("  color: Color { r: 255, g: 0, b: 0, a: 255 }\n"));
+        assert!(note.contains("  --gap: 4px\n"));
+        assert!(!note.contains("margin:"));
+        assert!(dump.starts_with("html\n"));
+    }
+}
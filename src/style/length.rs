@@ -4,7 +4,26 @@ use super::parse::{parse_css_length_px_f32_with_viewport, parse_css_length_px_wi
 pub enum CssLength {
     Px(i32),
     Percent(f32),
-    Calc { percent: f32, px: f32 },
+    Calc {
+        percent: f32,
+        px: f32,
+    },
+    /// `min-content`, `max-content` or `fit-content`, until layout measures the content.
+    Content(ContentSize),
+    /// `fit-content` once measured: the reference width, kept between the content's narrowest
+    /// and widest.
+    FitContent {
+        min_px: i32,
+        max_px: i32,
+    },
+}
+
+/// The intrinsic sizing keywords, which size a box by its content rather than its container.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentSize {
+    MinContent,
+    MaxContent,
+    FitContent,
 }
 
 impl CssLength {
@@ -18,6 +37,8 @@ impl CssLength {
             CssLength::Calc { percent, px } => {
                 ((reference_px as f32) * (percent / 100.0) + px).round() as i32
             }
+            CssLength::Content(_) => reference_px,
+            CssLength::FitContent { min_px, max_px } => reference_px.min(max_px).max(min_px),
         }
     }
 }
@@ -49,6 +70,22 @@ pub(super) fn parse_css_length(
         .map(CssLength::Px)
 }
 
+pub(super) fn parse_content_size(value: &str) -> Option<ContentSize> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("min-content") {
+        Some(ContentSize::MinContent)
+    } else if value.eq_ignore_ascii_case("max-content") {
+        Some(ContentSize::MaxContent)
+    } else if value.eq_ignore_ascii_case("fit-content")
+        || value.eq_ignore_ascii_case("-webkit-fit-content")
+        || value.eq_ignore_ascii_case("-moz-fit-content")
+    {
+        Some(ContentSize::FitContent)
+    } else {
+        None
+    }
+}
+
 fn parse_css_calc_length(
     value: &str,
     viewport_width_px: Option<i32>,
@@ -1,19 +1,31 @@
+mod animation;
 mod background;
 mod builder;
 mod computer;
 mod custom_properties;
 mod declarations;
+mod dump;
 mod length;
+mod parallel;
 mod parse;
 mod selectors;
+mod transform;
 
 use crate::geom::{Color, Edges};
 use std::borrow::Cow;
 
+pub use animation::{
+    Animation, Animations, TimingFunction, Transition, TransitionProperty, Transitions,
+};
 pub use background::{GradientDirection, LinearGradient};
 pub use computer::StyleComputer;
+pub(crate) use computer::invert_lightness;
 pub use custom_properties::CustomProperties;
-pub use length::CssLength;
+pub use length::{ContentSize, CssLength};
+pub use parallel::RuleMatches;
+pub(crate) use parse::parse_css_color;
+pub(crate) use selectors::selector_matches;
+pub use transform::TransformFunction;
 
 use builder::{CascadePriority, Cascaded, LetterSpacing, StyleBuilder};
 
@@ -51,6 +63,15 @@ pub enum Float {
     Right,
 }
 
+/// Which earlier floats a block or float moves down past.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Clear {
+    None,
+    Left,
+    Right,
+    Both,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum FontFamily {
     SansSerif,
@@ -58,11 +79,47 @@ pub enum FontFamily {
     Monospace,
 }
 
+/// `font-variant-caps`, for which small capitals are made from capitals set smaller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FontVariantCaps {
+    Normal,
+    /// Lowercase letters as small capitals.
+    SmallCaps,
+    /// Every letter as a small capital.
+    AllSmallCaps,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TextAlign {
     Left,
     Center,
     Right,
+    /// Left in left-to-right text, right in right-to-left text.
+    Start,
+    /// Right in left-to-right text, left in right-to-left text.
+    End,
+}
+
+impl TextAlign {
+    /// `Left`, `Center` or `Right`, with `Start` and `End` taken in `direction`.
+    pub fn resolve(self, direction: Direction) -> TextAlign {
+        match (self, direction) {
+            (TextAlign::Start, Direction::Ltr) | (TextAlign::End, Direction::Rtl) => {
+                TextAlign::Left
+            }
+            (TextAlign::Start, Direction::Rtl) | (TextAlign::End, Direction::Ltr) => {
+                TextAlign::Right
+            }
+            (align, _) => align,
+        }
+    }
+}
+
+/// Which way text and inline content run, and which side a line starts from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Ltr,
+    Rtl,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -86,6 +143,33 @@ impl TextTransform {
     }
 }
 
+/// Where words may break with a hyphen: nowhere, only at soft hyphens, or also wherever the
+/// hyphenation patterns allow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hyphens {
+    None,
+    Manual,
+    Auto,
+}
+
+/// Where an inline box sits on its line.
+#[derive(Clone, Copy, Debug)]
+pub enum VerticalAlign {
+    Baseline,
+    /// Centred on the parent's baseline raised by half its x-height.
+    Middle,
+    /// Against the top of the line.
+    Top,
+    /// Against the bottom of the line.
+    Bottom,
+    /// Against the top of the parent's font.
+    TextTop,
+    /// Against the bottom of the parent's font.
+    TextBottom,
+    /// On the baseline, raised by this much; percentages are of the box's line height.
+    Length(CssLength),
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum WhiteSpace {
     Normal,
@@ -119,10 +203,21 @@ pub enum BorderStyle {
     Solid,
 }
 
+/// How an outline is drawn. Every line style but `none` paints a solid ring; `auto` paints the
+/// ring focused elements get by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutlineStyle {
+    None,
+    Solid,
+    Auto,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FlexDirection {
     Row,
+    RowReverse,
     Column,
+    ColumnReverse,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -137,10 +232,13 @@ pub enum FlexJustifyContent {
     Center,
     End,
     SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FlexAlignItems {
+    Stretch,
     Start,
     Center,
     End,
@@ -152,6 +250,7 @@ pub struct ComputedStyle {
     pub visibility: Visibility,
     pub position: Position,
     pub float: Float,
+    pub clear: Clear,
     pub custom_properties: CustomProperties,
     pub top_px: Option<CssLength>,
     pub right_px: Option<CssLength>,
@@ -164,11 +263,19 @@ pub struct ComputedStyle {
     pub font_family: FontFamily,
     pub font_size_px: i32,
     pub letter_spacing_px: i32,
+    /// Added to each space between words.
+    pub word_spacing_px: i32,
+    pub font_variant_caps: FontVariantCaps,
     pub bold: bool,
     pub underline: bool,
     pub text_align: TextAlign,
+    pub direction: Direction,
     pub text_transform: TextTransform,
+    pub hyphens: Hyphens,
+    /// How far the first line of a block's text is pushed in, or out when negative.
+    pub text_indent: CssLength,
     pub white_space: WhiteSpace,
+    pub vertical_align: VerticalAlign,
     pub line_height: LineHeight,
     pub margin: Edges,
     pub margin_auto: AutoEdges,
@@ -176,23 +283,37 @@ pub struct ComputedStyle {
     pub border_style: BorderStyle,
     pub border_color: Color,
     pub border_radius_px: i32,
+    /// Painted around the border box, `outline_offset_px` out from it, without taking up room.
+    pub outline_width_px: i32,
+    pub outline_style: OutlineStyle,
+    /// `None` for the element's own text color.
+    pub outline_color: Option<Color>,
+    pub outline_offset_px: i32,
     pub padding: CssEdges,
     pub width_px: Option<CssLength>,
     pub min_width_px: Option<CssLength>,
     pub max_width_px: Option<CssLength>,
-    pub height_px: Option<i32>,
-    pub min_height_px: Option<i32>,
+    pub height_px: Option<CssLength>,
+    pub min_height_px: Option<CssLength>,
+    pub max_height_px: Option<CssLength>,
     pub flex_justify_content: FlexJustifyContent,
     pub flex_align_items: FlexAlignItems,
     pub flex_direction: FlexDirection,
     pub flex_wrap: FlexWrap,
     pub flex_grow: i32,
     pub flex_shrink: i32,
-    pub flex_basis_px: Option<i32>,
+    pub flex_basis_px: Option<CssLength>,
+    /// `None` for `auto`, which lines the item up the way its container's `align-items` says.
+    pub flex_align_self: Option<FlexAlignItems>,
+    pub flex_order: i32,
     pub flex_gap_px: i32,
     pub grid_area: Option<String>,
     pub grid_template_columns: Option<String>,
     pub grid_template_areas: Option<String>,
+    /// Applied about the middle of the border box; empty for `none`.
+    pub transform: Vec<TransformFunction>,
+    pub transitions: Transitions,
+    pub animations: Animations,
 }
 
 impl ComputedStyle {
@@ -202,6 +323,7 @@ impl ComputedStyle {
             visibility: Visibility::Visible,
             position: Position::Static,
             float: Float::None,
+            clear: Clear::None,
             custom_properties: CustomProperties::default(),
             top_px: None,
             right_px: None,
@@ -214,11 +336,17 @@ impl ComputedStyle {
             font_family: FontFamily::SansSerif,
             font_size_px: 16,
             letter_spacing_px: 0,
+            word_spacing_px: 0,
+            font_variant_caps: FontVariantCaps::Normal,
             bold: false,
             underline: false,
-            text_align: TextAlign::Left,
+            text_align: TextAlign::Start,
+            direction: Direction::Ltr,
             text_transform: TextTransform::None,
+            hyphens: Hyphens::Manual,
+            text_indent: CssLength::Px(0),
             white_space: WhiteSpace::Normal,
+            vertical_align: VerticalAlign::Baseline,
             line_height: LineHeight::Normal,
             margin: Edges::ZERO,
             margin_auto: AutoEdges::NONE,
@@ -226,23 +354,33 @@ impl ComputedStyle {
             border_style: BorderStyle::None,
             border_color: Color::BLACK,
             border_radius_px: 0,
+            outline_width_px: 3,
+            outline_style: OutlineStyle::None,
+            outline_color: None,
+            outline_offset_px: 0,
             padding: CssEdges::ZERO,
             width_px: None,
             min_width_px: None,
             max_width_px: None,
             height_px: None,
             min_height_px: None,
+            max_height_px: None,
             flex_justify_content: FlexJustifyContent::Start,
-            flex_align_items: FlexAlignItems::Start,
+            flex_align_items: FlexAlignItems::Stretch,
             flex_direction: FlexDirection::Row,
             flex_wrap: FlexWrap::NoWrap,
             flex_grow: 0,
             flex_shrink: 1,
             flex_basis_px: None,
+            flex_align_self: None,
+            flex_order: 0,
             flex_gap_px: 0,
             grid_area: None,
             grid_template_columns: None,
             grid_template_areas: None,
+            transform: Vec::new(),
+            transitions: Transitions::default(),
+            animations: Animations::default(),
         }
     }
 
@@ -252,6 +390,7 @@ impl ComputedStyle {
             visibility: Visibility::Visible,
             position: Position::Static,
             float: Float::None,
+            clear: Clear::None,
             custom_properties: parent.custom_properties.clone(),
             top_px: None,
             right_px: None,
@@ -264,11 +403,17 @@ impl ComputedStyle {
             font_family: parent.font_family,
             font_size_px: parent.font_size_px,
             letter_spacing_px: parent.letter_spacing_px,
+            word_spacing_px: parent.word_spacing_px,
+            font_variant_caps: parent.font_variant_caps,
             bold: parent.bold,
             underline: parent.underline,
             text_align: parent.text_align,
+            direction: parent.direction,
             text_transform: parent.text_transform,
+            hyphens: parent.hyphens,
+            text_indent: parent.text_indent,
             white_space: parent.white_space,
+            vertical_align: VerticalAlign::Baseline,
             line_height: parent.line_height,
             margin: Edges::ZERO,
             margin_auto: AutoEdges::NONE,
@@ -276,23 +421,33 @@ impl ComputedStyle {
             border_style: BorderStyle::None,
             border_color: parent.color,
             border_radius_px: 0,
+            outline_width_px: 3,
+            outline_style: OutlineStyle::None,
+            outline_color: None,
+            outline_offset_px: 0,
             padding: CssEdges::ZERO,
             width_px: None,
             min_width_px: None,
             max_width_px: None,
             height_px: None,
             min_height_px: None,
+            max_height_px: None,
             flex_justify_content: FlexJustifyContent::Start,
-            flex_align_items: FlexAlignItems::Start,
+            flex_align_items: FlexAlignItems::Stretch,
             flex_direction: FlexDirection::Row,
             flex_wrap: FlexWrap::NoWrap,
             flex_grow: 0,
             flex_shrink: 1,
             flex_basis_px: None,
+            flex_align_self: None,
+            flex_order: 0,
             flex_gap_px: 0,
             grid_area: None,
             grid_template_columns: None,
             grid_template_areas: None,
+            transform: Vec::new(),
+            transitions: Transitions::default(),
+            animations: Animations::default(),
         }
     }
 }
@@ -0,0 +1,258 @@
+use super::StyleComputer;
+use crate::css::Specificity;
+use crate::dom::{Element, Node};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::num::NonZeroUsize;
+use std::thread;
+use std::time::Instant;
+
+/// Documents with fewer elements are matched on demand; starting workers would cost more than
+/// it saves.
+const MIN_PARALLEL_ELEMENTS: usize = 512;
+/// Subtrees handed to each worker, so one large subtree doesn't leave the others idle.
+const SUBTREES_PER_WORKER: usize = 4;
+
+/// The element's address, its parent's address and its depth.
+type MatchKey = (usize, usize, usize);
+
+/// Selector matches for a whole document, found on worker threads ahead of layout.
+///
+/// Matches are keyed by the element and the ancestor chain they were found with, so a lookup
+/// with a different chain (table rows styled past their row group, say) misses and the caller
+/// matches on demand.
+#[derive(Debug, Default)]
+pub struct RuleMatches<'doc> {
+    viewport: Option<(i32, i32)>,
+    by_element: HashMap<MatchKey, Vec<(usize, Specificity)>>,
+    document: PhantomData<&'doc Element>,
+}
+
+impl RuleMatches<'_> {
+    pub fn len(&self) -> usize {
+        self.by_element.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_element.is_empty()
+    }
+
+    pub(super) fn get(
+        &self,
+        element: &Element,
+        ancestors: &[&Element],
+        viewport: Option<(i32, i32)>,
+    ) -> Option<&[(usize, Specificity)]> {
+        if viewport != self.viewport {
+            return None;
+        }
+        self.by_element
+            .get(&match_key(element, ancestors))
+            .map(Vec::as_slice)
+    }
+}
+
+impl StyleComputer {
+    /// Matches every element under `root` against the stylesheets, fanning subtrees out across
+    /// the available cores. Small documents, and machines with one core, get empty matches.
+    pub fn match_document<'doc>(
+        &self,
+        root: &'doc Element,
+        viewport_width_px: i32,
+        viewport_height_px: i32,
+    ) -> RuleMatches<'doc> {
+        let viewport = (viewport_width_px.max(0), viewport_height_px.max(0));
+        let workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        if workers < 2 || !has_at_least_elements(root, MIN_PARALLEL_ELEMENTS) {
+            return RuleMatches {
+                viewport: Some(viewport),
+                ..RuleMatches::default()
+            };
+        }
+        self.match_in_parallel(root, viewport, workers)
+    }
+
+    fn match_in_parallel<'doc>(
+        &self,
+        root: &'doc Element,
+        viewport: (i32, i32),
+        workers: usize,
+    ) -> RuleMatches<'doc> {
+        let started = Instant::now();
+        let viewport = Some(viewport);
+        let mut by_element = HashMap::new();
+
+        // Split breadth-first until there are enough subtrees; the elements split on are matched
+        // here.
+        let mut subtrees: Vec<(&Element, Vec<&Element>)> = vec![(root, Vec::new())];
+        while subtrees.len() < workers * SUBTREES_PER_WORKER
+            && subtrees
+                .iter()
+                .any(|(element, _)| child_elements(element).next().is_some())
+        {
+            let mut next = Vec::new();
+            for (element, mut ancestors) in subtrees {
+                by_element.insert(
+                    match_key(element, &ancestors),
+                    self.match_rule_ids(element, &ancestors, viewport),
+                );
+                ancestors.push(element);
+                next.extend(child_elements(element).map(|child| (child, ancestors.clone())));
+            }
+            subtrees = next;
+        }
+
+        let chunk_len = subtrees.len().div_ceil(workers).max(1);
+        let found = thread::scope(|scope| {
+            let handles = subtrees
+                .chunks(chunk_len)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut found = HashMap::new();
+                        for (element, ancestors) in chunk {
+                            self.match_subtree(element, ancestors, viewport, &mut found);
+                        }
+                        found
+                    })
+                })
+                .collect::<Vec<_>>();
+            // A worker that panicked leaves its subtrees to be matched on demand.
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().ok())
+                .collect::<Vec<_>>()
+        });
+        for matches in found {
+            by_element.extend(matches);
+        }
+
+        self.add_styling_time(started.elapsed());
+        RuleMatches {
+            viewport,
+            by_element,
+            document: PhantomData,
+        }
+    }
+
+    fn match_subtree<'doc>(
+        &self,
+        root: &'doc Element,
+        ancestors: &[&'doc Element],
+        viewport: Option<(i32, i32)>,
+        out: &mut HashMap<MatchKey, Vec<(usize, Specificity)>>,
+    ) {
+        let mut ancestors = ancestors.to_vec();
+        let base_depth = ancestors.len();
+        let mut stack = vec![(root, base_depth)];
+        while let Some((element, depth)) = stack.pop() {
+            ancestors.truncate(depth);
+            out.insert(
+                match_key(element, &ancestors),
+                self.match_rule_ids(element, &ancestors, viewport),
+            );
+            ancestors.push(element);
+            stack.extend(child_elements(element).map(|child| (child, depth + 1)));
+        }
+    }
+}
+
+fn match_key(element: &Element, ancestors: &[&Element]) -> MatchKey {
+    let parent = ancestors
+        .last()
+        .map_or(0, |parent| std::ptr::from_ref::<Element>(parent) as usize);
+    (
+        std::ptr::from_ref(element) as usize,
+        parent,
+        ancestors.len(),
+    )
+}
+
+fn child_elements(element: &Element) -> impl Iterator<Item = &Element> {
+    element.children.iter().filter_map(|child| match child {
+        Node::Element(child) => Some(child),
+        Node::Text(_) => None,
+    })
+}
+
+fn has_at_least_elements(root: &Element, count: usize) -> bool {
+    let mut seen = 0usize;
+    let mut stack = vec![root];
+    while let Some(element) = stack.pop() {
+        seen += 1;
+        if seen >= count {
+            return true;
+        }
+        stack.extend(child_elements(element));
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::ComputedStyle;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn parallel_matches_style_like_on_demand_matching() {
+        assert_send_sync::<StyleComputer>();
+        assert_send_sync::<Element>();
+
+        let mut html = String::from(
+            "<style>\
+             section > p { color: #123456 }\
+             p + p { margin-top: 3px }\
+             .wide { width: 50% }\
+             @media (max-width: 500px) { p { font-size: 20px } }\
+             </style><body>",
+        );
+        for index in 0..40 {
+            html.push_str(&format!(
+                "<section><p class='wide'>{index}</p><p>b</p><div><p>c</p></div></section>"
+            ));
+        }
+        html.push_str("</body>");
+        let document = crate::html::parse_document(&html);
+        let styles = StyleComputer::from_document(&document);
+        let root = document.render_root();
+        let matches = styles.match_in_parallel(root, (400, 300), 4);
+        assert!(matches.len() > 200);
+
+        fn compare<'doc>(
+            styles: &StyleComputer,
+            matches: &RuleMatches<'_>,
+            element: &'doc Element,
+            parent: &ComputedStyle,
+            ancestors: &mut Vec<&'doc Element>,
+        ) {
+            assert!(matches.get(element, ancestors, Some((400, 300))).is_some());
+            let style =
+                styles.compute_style_with_matches(element, parent, ancestors, 400, 300, matches);
+            let expected = styles.compute_style_in_viewport(element, parent, ancestors, 400, 300);
+            assert_eq!(format!("{style:?}"), format!("{expected:?}"));
+            ancestors.push(element);
+            for child in child_elements(element) {
+                compare(styles, matches, child, &style, ancestors);
+            }
+            ancestors.pop();
+        }
+        compare(
+            &styles,
+            &matches,
+            root,
+            &ComputedStyle::root_defaults(),
+            &mut Vec::new(),
+        );
+
+        // A different ancestor chain, or another viewport, is matched on demand.
+        let section = child_elements(root).next().unwrap();
+        let paragraph = child_elements(section).next().unwrap();
+        assert!(matches.get(paragraph, &[root], Some((400, 300))).is_none());
+        assert!(
+            matches
+                .get(paragraph, &[root, section], Some((800, 300)))
+                .is_none()
+        );
+    }
+}
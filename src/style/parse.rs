@@ -1,7 +1,7 @@
 use crate::geom::{Color, Edges};
-use crate::style::FontFamily;
+use crate::style::{CssLength, FontFamily};
 
-pub(super) fn parse_css_color(value: &str) -> Option<Color> {
+pub(crate) fn parse_css_color(value: &str) -> Option<Color> {
     let value = value.trim();
     if let Some(color) = Color::from_css_hex(value) {
         return Some(color);
@@ -155,95 +155,70 @@ pub(super) fn parse_css_length_px_with_viewport(
 pub(super) struct ParsedFlex {
     pub(super) grow: i32,
     pub(super) shrink: i32,
-    pub(super) basis_px: Option<i32>,
+    pub(super) basis_px: Option<CssLength>,
 }
 
-pub(super) fn parse_css_flex(value: &str) -> Option<ParsedFlex> {
+/// Parses the `flex` shorthand: `none`, `auto`, `initial`, or a grow factor optionally followed
+/// by a shrink factor, with a basis before or after them. `parse_basis` reads the basis.
+pub(super) fn parse_css_flex(
+    value: &str,
+    parse_basis: impl Fn(&str) -> Option<CssLength>,
+) -> Option<ParsedFlex> {
     let value = value.trim();
-    if value.is_empty() {
-        return None;
-    }
-
-    if value.eq_ignore_ascii_case("none") {
-        return Some(ParsedFlex {
-            grow: 0,
-            shrink: 0,
+    let keyword = |grow, shrink| {
+        Some(ParsedFlex {
+            grow,
+            shrink,
             basis_px: None,
-        });
+        })
+    };
+    if value.eq_ignore_ascii_case("none") {
+        return keyword(0, 0);
     }
-
     if value.eq_ignore_ascii_case("auto") {
-        return Some(ParsedFlex {
-            grow: 1,
-            shrink: 1,
-            basis_px: None,
-        });
+        return keyword(1, 1);
+    }
+    if value.eq_ignore_ascii_case("initial") {
+        return keyword(0, 1);
     }
 
     let parts: Vec<&str> = value.split_whitespace().collect();
-    match parts.as_slice() {
-        [grow] => {
-            if let Ok(grow) = grow.parse::<f32>() {
-                return Some(ParsedFlex {
-                    grow: grow.round().max(0.0) as i32,
-                    shrink: 1,
-                    basis_px: Some(0),
-                });
-            }
-            if grow.eq_ignore_ascii_case("auto") {
-                return Some(ParsedFlex {
-                    grow: 1,
-                    shrink: 1,
-                    basis_px: None,
-                });
-            }
-            if grow.eq_ignore_ascii_case("none") {
-                return Some(ParsedFlex {
-                    grow: 0,
-                    shrink: 0,
-                    basis_px: None,
-                });
-            }
-            None
-        }
-        [grow, second] => {
-            let grow = grow.parse::<f32>().ok()?.round().max(0.0) as i32;
-            if let Ok(shrink) = second.parse::<f32>() {
-                return Some(ParsedFlex {
-                    grow,
-                    shrink: shrink.round().max(0.0) as i32,
-                    basis_px: None,
-                });
+    if parts.is_empty() || parts.len() > 3 {
+        return None;
+    }
+    let mut grow = None;
+    let mut shrink = None;
+    let mut basis = None;
+    let mut previous_was_grow = false;
+    for part in parts {
+        let factor = part
+            .parse::<f32>()
+            .ok()
+            .map(|factor| factor.round().max(0.0) as i32);
+        match factor {
+            Some(factor) if grow.is_none() => {
+                grow = Some(factor);
+                previous_was_grow = true;
+                continue;
             }
-            if second.eq_ignore_ascii_case("auto") {
-                return Some(ParsedFlex {
-                    grow,
-                    shrink: 1,
-                    basis_px: None,
+            Some(factor) if previous_was_grow && shrink.is_none() => shrink = Some(factor),
+            _ if basis.is_none() => {
+                basis = Some(if part.eq_ignore_ascii_case("auto") {
+                    None
+                } else {
+                    Some(parse_basis(part)?)
                 });
             }
-            parse_css_length_px(second).map(|px| ParsedFlex {
-                grow,
-                shrink: 1,
-                basis_px: Some(px.max(0)),
-            })
+            _ => return None,
         }
-        [grow, shrink, basis] => {
-            let grow = grow.parse::<f32>().ok()?.round().max(0.0) as i32;
-            let shrink = shrink.parse::<f32>().ok()?.round().max(0.0) as i32;
-            let basis_px = if basis.eq_ignore_ascii_case("auto") {
-                None
-            } else {
-                Some(parse_css_length_px(basis)?.max(0))
-            };
-            Some(ParsedFlex {
-                grow,
-                shrink,
-                basis_px,
-            })
-        }
-        _ => None,
+        previous_was_grow = false;
     }
+
+    Some(ParsedFlex {
+        grow: grow.unwrap_or(1),
+        shrink: shrink.unwrap_or(1),
+        basis_px: basis.unwrap_or(Some(CssLength::Px(0))),
+    })
 }
 
 pub(super) fn parse_css_box_edges(value: &str) -> Option<Edges> {
@@ -1,14 +1,16 @@
-use crate::css::{Combinator, PseudoClass, Rule, Selector, Specificity};
+use crate::css::{Combinator, PseudoClass, PseudoElement, Rule, Selector, Specificity};
 use crate::dom::{Element, Node};
 
+/// Matches `rule` against `element`, or with `pseudo` against that pseudo-element of it.
 pub(super) fn match_rule(
     rule: &Rule,
     element: &Element,
     ancestors: &[&Element],
+    pseudo: Option<PseudoElement>,
 ) -> Option<(Specificity, u32)> {
     let mut best: Option<Specificity> = None;
     for selector in &rule.selectors {
-        if selector_matches(selector, element, ancestors) {
+        if subject_matches(selector, element, ancestors, pseudo) {
             let spec = selector.specificity();
             best = Some(best.map_or(spec, |b| b.max(spec)));
         }
@@ -16,19 +18,28 @@ pub(super) fn match_rule(
     best.map(|spec| (spec, rule.order))
 }
 
-fn selector_matches(selector: &Selector, element: &Element, ancestors: &[&Element]) -> bool {
-    if selector.parts.is_empty() {
+pub(crate) fn selector_matches(
+    selector: &Selector,
+    element: &Element,
+    ancestors: &[&Element],
+) -> bool {
+    subject_matches(selector, element, ancestors, None)
+}
+
+fn subject_matches(
+    selector: &Selector,
+    element: &Element,
+    ancestors: &[&Element],
+    pseudo: Option<PseudoElement>,
+) -> bool {
+    let Some(subject) = selector.parts.last() else {
         return false;
-    }
+    };
     if selector.combinators.len() != selector.parts.len().saturating_sub(1) {
         return false;
     }
 
-    if !compound_matches(
-        &selector.parts[selector.parts.len() - 1],
-        element,
-        ancestors,
-    ) {
+    if subject.pseudo_element != pseudo || !compound_fields_match(subject, element, ancestors) {
         return false;
     }
 
@@ -119,6 +130,15 @@ fn compound_matches(
     selector: &crate::css::CompoundSelector,
     element: &Element,
     ancestors: &[&Element],
+) -> bool {
+    selector.pseudo_element.is_none() && compound_fields_match(selector, element, ancestors)
+}
+
+/// Whether `element` matches everything in `selector` but its pseudo-element.
+fn compound_fields_match(
+    selector: &crate::css::CompoundSelector,
+    element: &Element,
+    ancestors: &[&Element],
 ) -> bool {
     if selector.unsupported {
         return false;
@@ -166,7 +186,7 @@ fn pseudo_matches(pseudo: &PseudoClass, element: &Element, ancestors: &[&Element
     match pseudo {
         PseudoClass::Link => element.name == "a" && element.attributes.get("href").is_some(),
         PseudoClass::Visited => false,
-        PseudoClass::Hover => false,
+        PseudoClass::Hover | PseudoClass::Focus => false,
         PseudoClass::Root => element.name == "html",
         PseudoClass::Checked => element.attributes.get("checked").is_some(),
         PseudoClass::NthChild(pattern) => nth_child_matches(element, ancestors, *pattern),
@@ -0,0 +1,108 @@
+use super::parse::parse_css_length_px_f32_with_viewport;
+
+/// One function of a `transform` list, with lengths in CSS pixels and angles in degrees,
+/// clockwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransformFunction {
+    Translate(f32, f32),
+    Rotate(f32),
+    Scale(f32, f32),
+}
+
+impl TransformFunction {
+    /// The function of the same kind that changes nothing, which `none` stands for when a list
+    /// is animated to or from it.
+    pub fn identity(self) -> TransformFunction {
+        match self {
+            TransformFunction::Translate(..) => TransformFunction::Translate(0.0, 0.0),
+            TransformFunction::Rotate(_) => TransformFunction::Rotate(0.0),
+            TransformFunction::Scale(..) => TransformFunction::Scale(1.0, 1.0),
+        }
+    }
+}
+
+/// Parses `none` as an empty list, or a list of `translate`, `rotate` and `scale` functions and
+/// their one-axis forms. Percentages and 3D functions aren't supported, so a list using them is
+/// rejected as a whole.
+pub(super) fn parse_transform(
+    value: &str,
+    viewport: Option<(i32, i32)>,
+) -> Option<Vec<TransformFunction>> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("none") {
+        return Some(Vec::new());
+    }
+    let length = |arg: &str| {
+        parse_css_length_px_f32_with_viewport(
+            arg,
+            viewport.map(|(width, _)| width),
+            viewport.map(|(_, height)| height),
+        )
+        .filter(|px| px.is_finite())
+    };
+    let number = |arg: &str| arg.parse::<f32>().ok().filter(|number| number.is_finite());
+
+    let mut functions = Vec::new();
+    let mut rest = value;
+    while !rest.is_empty() {
+        let (name, after) = rest.split_once('(')?;
+        let (args, after) = after.split_once(')')?;
+        let args: Vec<&str> = args.split(',').map(str::trim).collect();
+        let function = match (name.trim().to_ascii_lowercase().as_str(), args.as_slice()) {
+            ("translate", [x]) => TransformFunction::Translate(length(x)?, 0.0),
+            ("translate", [x, y]) => TransformFunction::Translate(length(x)?, length(y)?),
+            ("translatex", [x]) => TransformFunction::Translate(length(x)?, 0.0),
+            ("translatey", [y]) => TransformFunction::Translate(0.0, length(y)?),
+            ("rotate", [angle]) => TransformFunction::Rotate(parse_angle_deg(angle)?),
+            ("scale", [s]) => {
+                let s = number(s)?;
+                TransformFunction::Scale(s, s)
+            }
+            ("scale", [x, y]) => TransformFunction::Scale(number(x)?, number(y)?),
+            ("scalex", [x]) => TransformFunction::Scale(number(x)?, 1.0),
+            ("scaley", [y]) => TransformFunction::Scale(1.0, number(y)?),
+            _ => return None,
+        };
+        functions.push(function);
+        rest = after.trim_start();
+    }
+    (!functions.is_empty()).then_some(functions)
+}
+
+fn parse_angle_deg(value: &str) -> Option<f32> {
+    let value = value.trim().to_ascii_lowercase();
+    if value == "0" {
+        return Some(0.0);
+    }
+    let (number, degrees_per_unit) = [
+        ("deg", 1.0),
+        ("grad", 0.9),
+        ("rad", 180.0 / std::f32::consts::PI),
+        ("turn", 360.0),
+    ]
+    .into_iter()
+    .find_map(|(unit, factor)| Some((value.strip_suffix(unit)?, factor)))?;
+    let degrees = number.trim().parse::<f32>().ok()? * degrees_per_unit;
+    degrees.is_finite().then_some(degrees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_transform_lists_in_order() {
+        assert_eq!(
+            parse_transform("translate(10px, -4px) rotate(0.25turn) scaleY(2)", None),
+            Some(vec![
+                TransformFunction::Translate(10.0, -4.0),
+                TransformFunction::Rotate(90.0),
+                TransformFunction::Scale(1.0, 2.0),
+            ])
+        );
+        assert_eq!(parse_transform("none", None), Some(Vec::new()));
+        assert_eq!(parse_transform("translateX(50%)", None), None);
+        assert_eq!(parse_transform("rotate(45deg) skew(10deg)", None), None);
+        assert_eq!(parse_transform("rotate(45deg", None), None);
+    }
+}
@@ -1,10 +1,22 @@
+mod data;
+mod percent;
+
+pub use data::DataUrl;
+use percent::EncodeSet;
+pub use percent::{percent_decode, percent_decode_str};
+
+/// An absolute `http`/`https` URL, parsed and serialized roughly as the WHATWG URL standard
+/// does for special schemes. Hosts are lowercased but not IDNA-mapped, so punycode labels pass
+/// through unchanged. Credentials in the authority are dropped.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Url {
     full: String,
     scheme: Scheme,
     host: String,
     port: Option<u16>,
-    path_and_query: String,
+    path_start: usize,
+    query_start: Option<usize>,
+    fragment_start: Option<usize>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -15,43 +27,28 @@ pub enum Scheme {
 
 impl Url {
     pub fn parse(input: &str) -> Result<Self, String> {
-        let input = input.trim();
+        let input = clean_input(input);
         if input.is_empty() {
             return Err("URL is empty".to_owned());
         }
 
-        let (input, _) = split_once(input, '#');
-        let (scheme, rest) = input
-            .split_once("://")
-            .ok_or_else(|| format!("Invalid URL (missing scheme): {input}"))?;
-
-        let scheme = match scheme.to_ascii_lowercase().as_str() {
-            "http" => Scheme::Http,
-            "https" => Scheme::Https,
-            _ => return Err(format!("Unsupported URL scheme: {scheme}")),
-        };
-
-        let authority_end = rest
-            .find(|ch: char| matches!(ch, '/' | '?' | '#'))
-            .unwrap_or(rest.len());
-        let authority = &rest[..authority_end];
-        let mut path_and_query = rest[authority_end..].to_owned();
-        if path_and_query.is_empty() {
-            path_and_query.push('/');
-        } else if path_and_query.starts_with('?') {
-            path_and_query.insert(0, '/');
-        }
-
-        let (host, port) = parse_authority(authority)?;
-        let path_and_query = strip_fragment(&path_and_query);
-
-        Ok(Self::new(scheme, host, port, path_and_query))
+        let (scheme, rest) =
+            split_scheme(&input).ok_or_else(|| format!("Invalid URL (missing scheme): {input}"))?;
+        let scheme =
+            Scheme::from_name(scheme).ok_or_else(|| format!("Unsupported URL scheme: {scheme}"))?;
+        Self::parse_after_scheme(scheme, rest)
     }
 
+    /// The serialized URL, including any fragment.
     pub fn as_str(&self) -> &str {
         &self.full
     }
 
+    /// The serialized URL up to the fragment; this is what gets requested from the network.
+    pub fn without_fragment(&self) -> &str {
+        &self.full[..self.fragment_start.unwrap_or(self.full.len())]
+    }
+
     pub fn scheme(&self) -> Scheme {
         self.scheme
     }
@@ -60,58 +57,136 @@ impl Url {
         &self.host
     }
 
+    /// `None` when the URL uses the scheme's default port.
     pub fn port(&self) -> Option<u16> {
         self.port
     }
 
+    pub fn path(&self) -> &str {
+        let end = self
+            .query_start
+            .or(self.fragment_start)
+            .unwrap_or(self.full.len());
+        &self.full[self.path_start..end]
+    }
+
+    /// The percent-encoded query without its leading `?`.
+    pub fn query(&self) -> Option<&str> {
+        let start = self.query_start?;
+        let end = self.fragment_start.unwrap_or(self.full.len());
+        Some(&self.full[start + 1..end])
+    }
+
+    /// The percent-encoded fragment without its leading `#`.
+    pub fn fragment(&self) -> Option<&str> {
+        self.fragment_start.map(|start| &self.full[start + 1..])
+    }
+
     pub fn path_and_query(&self) -> &str {
-        &self.path_and_query
+        &self.without_fragment()[self.path_start..]
     }
 
+    /// `scheme://host`, with the port when it isn't the scheme's default. Two URLs are
+    /// same-origin when these match.
+    pub fn origin(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}://{}:{port}", self.scheme.as_str(), self.host),
+            None => format!("{}://{}", self.scheme.as_str(), self.host),
+        }
+    }
+
+    /// Resolves an absolute or relative reference against this URL. Returns `None` for empty
+    /// references and for references with a scheme other than `http`/`https`.
     pub fn resolve(&self, reference: &str) -> Option<Url> {
-        let reference = reference.trim();
+        let reference = clean_input(reference);
         if reference.is_empty() {
             return None;
         }
 
-        if reference.starts_with("http://") || reference.starts_with("https://") {
-            return Url::parse(reference).ok();
+        if let Some((scheme, rest)) = split_scheme(&reference) {
+            let scheme = Scheme::from_name(scheme)?;
+            // `http:page.html` is relative when the base is also `http`.
+            if scheme != self.scheme || rest.starts_with(['/', '\\']) {
+                return Self::parse_after_scheme(scheme, rest).ok();
+            }
+            return Some(self.resolve_relative(rest));
         }
 
-        if let Some(rest) = reference.strip_prefix("//") {
-            let scheme = self.scheme.as_str();
-            return Url::parse(&format!("{scheme}://{rest}")).ok();
+        if reference.starts_with(['/', '\\']) && reference[1..].starts_with(['/', '\\']) {
+            return Self::parse_after_scheme(self.scheme, &reference).ok();
         }
+        Some(self.resolve_relative(&reference))
+    }
 
-        let reference = strip_fragment(reference);
-        if reference.starts_with('/') {
-            return Some(Self::new(
-                self.scheme,
-                self.host.clone(),
-                self.port,
-                reference,
-            ));
+    /// The same URL over `https`, keeping any explicit port.
+    pub fn upgraded_to_https(&self) -> Url {
+        if self.scheme == Scheme::Https {
+            return self.clone();
         }
-
-        let base_path = self.path_without_query();
-        let base_dir = base_path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
-        let mut joined = String::new();
-        joined.push_str(base_dir);
-        joined.push('/');
-        joined.push_str(reference.trim_start_matches("./"));
-        if !joined.starts_with('/') {
-            joined.insert(0, '/');
+        // `http` and `https` differ by one trailing letter, so every index moves by one.
+        Url {
+            full: format!("https{}", &self.full[Scheme::Http.as_str().len()..]),
+            scheme: Scheme::Https,
+            host: self.host.clone(),
+            port: self.port,
+            path_start: self.path_start + 1,
+            query_start: self.query_start.map(|start| start + 1),
+            fragment_start: self.fragment_start.map(|start| start + 1),
         }
+    }
 
-        Some(Self::new(
+    fn resolve_relative(&self, reference: &str) -> Url {
+        let parts = Parts::split(reference);
+        let merged;
+        let path = if parts.path.starts_with(['/', '\\']) {
+            parts.path
+        } else if parts.path.is_empty() {
+            self.path()
+        } else {
+            let base = self.path();
+            let dir_end = base.rfind('/').map_or(0, |slash| slash + 1);
+            merged = format!("{}{}", &base[..dir_end], parts.path);
+            &merged
+        };
+        let query = if parts.path.is_empty() && parts.query.is_none() {
+            self.query()
+        } else {
+            parts.query
+        };
+        Self::build(
             self.scheme,
             self.host.clone(),
             self.port,
-            &joined,
+            path,
+            query,
+            parts.fragment,
+        )
+    }
+
+    /// Parses what follows `scheme:`. Special schemes ignore any run of slashes before the host.
+    fn parse_after_scheme(scheme: Scheme, rest: &str) -> Result<Url, String> {
+        let rest = rest.trim_start_matches(['/', '\\']);
+        let authority_end = rest.find(['/', '\\', '?', '#']).unwrap_or(rest.len());
+        let (host, port) = parse_authority(scheme, &rest[..authority_end])?;
+        let parts = Parts::split(&rest[authority_end..]);
+        Ok(Self::build(
+            scheme,
+            host,
+            port,
+            parts.path,
+            parts.query,
+            parts.fragment,
         ))
     }
 
-    fn new(scheme: Scheme, host: String, port: Option<u16>, path_and_query: &str) -> Url {
+    fn build(
+        scheme: Scheme,
+        host: String,
+        port: Option<u16>,
+        path: &str,
+        query: Option<&str>,
+        fragment: Option<&str>,
+    ) -> Url {
         let mut full = String::new();
         full.push_str(scheme.as_str());
         full.push_str("://");
@@ -120,63 +195,201 @@ impl Url {
             full.push(':');
             full.push_str(&port.to_string());
         }
-        if !path_and_query.starts_with('/') {
-            full.push('/');
-        }
-        full.push_str(path_and_query);
+        let path_start = full.len();
+        push_normalized_path(&mut full, path);
+        let query_start = query.map(|query| {
+            let start = full.len();
+            full.push('?');
+            percent::encode_into(&mut full, query, EncodeSet::Query);
+            start
+        });
+        let fragment_start = fragment.map(|fragment| {
+            let start = full.len();
+            full.push('#');
+            percent::encode_into(&mut full, fragment, EncodeSet::Fragment);
+            start
+        });
 
         Url {
+            full,
             scheme,
             host,
             port,
-            path_and_query: path_and_query.to_owned(),
-            full,
+            path_start,
+            query_start,
+            fragment_start,
         }
     }
-
-    fn path_without_query(&self) -> &str {
-        let (path, _) = split_once(&self.path_and_query, '?');
-        path
-    }
 }
 
 impl Scheme {
+    fn from_name(name: &str) -> Option<Scheme> {
+        if name.eq_ignore_ascii_case("http") {
+            Some(Scheme::Http)
+        } else if name.eq_ignore_ascii_case("https") {
+            Some(Scheme::Https)
+        } else {
+            None
+        }
+    }
+
     fn as_str(self) -> &'static str {
         match self {
             Scheme::Http => "http",
             Scheme::Https => "https",
         }
     }
+
+    pub fn default_port(self) -> u16 {
+        match self {
+            Scheme::Http => 80,
+            Scheme::Https => 443,
+        }
+    }
+}
+
+/// The path, query and fragment of a reference, each still unencoded.
+struct Parts<'a> {
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
 }
 
-fn strip_fragment(input: &str) -> &str {
-    let (head, _) = split_once(input, '#');
-    head
+impl<'a> Parts<'a> {
+    fn split(input: &'a str) -> Parts<'a> {
+        let (rest, fragment) = split_once(input, '#');
+        let (path, query) = split_once(rest, '?');
+        Parts {
+            path,
+            query,
+            fragment,
+        }
+    }
 }
 
-fn split_once<'a>(input: &'a str, delimiter: char) -> (&'a str, Option<&'a str>) {
+/// Strips leading and trailing C0 controls and spaces and removes tabs and newlines anywhere.
+fn clean_input(input: &str) -> String {
+    input
+        .trim_matches(|ch: char| ch <= ' ')
+        .chars()
+        .filter(|ch| !matches!(ch, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+/// Splits `scheme:rest` when the input starts with a syntactically valid scheme.
+fn split_scheme(input: &str) -> Option<(&str, &str)> {
+    let colon = input.find(':')?;
+    let scheme = &input[..colon];
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.'));
+    valid.then(|| (scheme, &input[colon + 1..]))
+}
+
+fn split_once(input: &str, delimiter: char) -> (&str, Option<&str>) {
     match input.find(delimiter) {
         Some(idx) => (&input[..idx], Some(&input[idx + delimiter.len_utf8()..])),
         None => (input, None),
     }
 }
 
-fn parse_authority(authority: &str) -> Result<(String, Option<u16>), String> {
-    if authority.is_empty() {
-        return Err("Invalid URL (missing host)".to_owned());
+/// Appends `path` with backslashes read as slashes, `.`/`..` segments removed and unsafe
+/// characters percent-encoded. An empty path becomes `/`.
+fn push_normalized_path(out: &mut String, path: &str) {
+    let path = path.replace('\\', "/");
+    let mut segments: Vec<&str> = Vec::new();
+    let mut raw = path.split('/').peekable();
+    if path.starts_with('/') {
+        raw.next();
+    }
+    while let Some(segment) = raw.next() {
+        let is_last = raw.peek().is_none();
+        if is_double_dot_segment(segment) {
+            segments.pop();
+            if is_last {
+                segments.push("");
+            }
+        } else if is_single_dot_segment(segment) {
+            if is_last {
+                segments.push("");
+            }
+        } else {
+            segments.push(segment);
+        }
     }
 
-    if authority.starts_with('[') {
-        return Err("IPv6 URL hosts are not supported yet".to_owned());
+    if segments.is_empty() {
+        out.push('/');
+    }
+    for segment in segments {
+        out.push('/');
+        percent::encode_into(out, segment, EncodeSet::Path);
     }
+}
 
-    if let Some((host, port_str)) = authority.rsplit_once(':') {
-        if let Ok(port) = port_str.parse::<u16>() {
-            return Ok((host.to_owned(), Some(port)));
-        }
+fn is_single_dot_segment(segment: &str) -> bool {
+    segment == "." || segment.eq_ignore_ascii_case("%2e")
+}
+
+fn is_double_dot_segment(segment: &str) -> bool {
+    matches!(
+        segment.to_ascii_lowercase().as_str(),
+        ".." | ".%2e" | "%2e." | "%2e%2e"
+    )
+}
+
+fn parse_authority(scheme: Scheme, authority: &str) -> Result<(String, Option<u16>), String> {
+    let host_and_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host_and_port)| host_and_port);
+    if host_and_port.is_empty() {
+        return Err("Invalid URL (missing host)".to_owned());
     }
 
-    Ok((authority.to_owned(), None))
+    let (host, port) = if host_and_port.starts_with('[') {
+        let close = host_and_port
+            .find(']')
+            .ok_or_else(|| format!("Invalid URL host: {host_and_port}"))?;
+        let after = &host_and_port[close + 1..];
+        let port = match after.strip_prefix(':') {
+            Some(port) => Some(port),
+            None if after.is_empty() => None,
+            None => return Err(format!("Invalid URL host: {host_and_port}")),
+        };
+        (host_and_port[..=close].to_ascii_lowercase(), port)
+    } else {
+        let (host, port) = split_once(host_and_port, ':');
+        let host = percent_decode_str(host).to_ascii_lowercase();
+        if host.is_empty() {
+            return Err("Invalid URL (missing host)".to_owned());
+        }
+        if host.chars().any(is_forbidden_host_char) {
+            return Err(format!("Invalid URL host: {host}"));
+        }
+        (host, port)
+    };
+
+    let port = match port.filter(|port| !port.is_empty()) {
+        None => None,
+        Some(port) => {
+            let port = port
+                .parse::<u16>()
+                .ok()
+                .filter(|_| port.bytes().all(|b| b.is_ascii_digit()))
+                .ok_or_else(|| format!("Invalid URL port: {port}"))?;
+            (port != scheme.default_port()).then_some(port)
+        }
+    };
+    Ok((host, port))
+}
+
+fn is_forbidden_host_char(ch: char) -> bool {
+    ch <= ' '
+        || ch == '\u{7f}'
+        || matches!(
+            ch,
+            '#' | '%' | '/' | ':' | '<' | '>' | '?' | '@' | '[' | '\\' | ']' | '^' | '|'
+        )
 }
 
 #[cfg(test)]
@@ -205,4 +418,69 @@ mod tests {
         let resolved = base.resolve("/style.css").unwrap();
         assert_eq!(resolved.as_str(), "https://example.com/style.css");
     }
+
+    #[test]
+    fn splits_components_and_normalizes_authority() {
+        let url = Url::parse("HTTP://user:pw@Example.COM:80/a/./b/../c?x=1#top").unwrap();
+        assert_eq!(url.as_str(), "http://example.com/a/c?x=1#top");
+        assert_eq!(url.host(), "example.com");
+        assert_eq!(url.port(), None);
+        assert_eq!(url.path(), "/a/c");
+        assert_eq!(url.query(), Some("x=1"));
+        assert_eq!(url.fragment(), Some("top"));
+        assert_eq!(url.path_and_query(), "/a/c?x=1");
+        assert_eq!(url.without_fragment(), "http://example.com/a/c?x=1");
+
+        let url = Url::parse("https://[::1]:8443").unwrap();
+        assert_eq!(url.as_str(), "https://[::1]:8443/");
+        assert!(Url::parse("https://example.com:99999/").is_err());
+        assert!(Url::parse("https://exa mple.com/").is_err());
+    }
+
+    #[test]
+    fn resolves_references_like_a_browser() {
+        let base = Url::parse("https://example.com/a/b/page.html?q=1#f").unwrap();
+        let resolve = |reference: &str| base.resolve(reference).map(|url| url.as_str().to_owned());
+
+        assert_eq!(
+            resolve("../img/x.png").unwrap(),
+            "https://example.com/a/img/x.png"
+        );
+        assert_eq!(resolve("../../../x").unwrap(), "https://example.com/x");
+        assert_eq!(
+            resolve("?page=2").unwrap(),
+            "https://example.com/a/b/page.html?page=2"
+        );
+        assert_eq!(
+            resolve("#s2").unwrap(),
+            "https://example.com/a/b/page.html?q=1#s2"
+        );
+        assert_eq!(
+            resolve("//cdn.example.net/x.js").unwrap(),
+            "https://cdn.example.net/x.js"
+        );
+        assert_eq!(
+            resolve("https:other.html").unwrap(),
+            "https://example.com/a/b/other.html"
+        );
+        assert_eq!(
+            resolve("  my pic\t.png ").unwrap(),
+            "https://example.com/a/b/my%20pic.png"
+        );
+        assert_eq!(resolve("sub/").unwrap(), "https://example.com/a/b/sub/");
+        assert_eq!(resolve("mailto:someone@example.com"), None);
+        assert_eq!(resolve("javascript:void(0)"), None);
+    }
+
+    #[test]
+    fn keeps_punycode_and_encodes_unicode_paths() {
+        let url = Url::parse("https://xn--bcher-kva.example/b\u{fc}cher").unwrap();
+        assert_eq!(url.host(), "xn--bcher-kva.example");
+        assert_eq!(url.path(), "/b%C3%BCcher");
+        assert_eq!(
+            Url::parse(url.as_str()).unwrap(),
+            url,
+            "serialization should round-trip"
+        );
+    }
 }
@@ -0,0 +1,131 @@
+use super::percent::percent_decode;
+
+/// A decoded `data:` URL, following the WHATWG fetch "data: URL processor".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataUrl {
+    pub mime_type: String,
+    pub body: Vec<u8>,
+}
+
+impl DataUrl {
+    pub fn is_data_url(input: &str) -> bool {
+        input
+            .trim_start()
+            .get(..5)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+    }
+
+    /// Returns `None` for non-`data:` URLs, URLs without a `,`, and invalid base64.
+    pub fn parse(input: &str) -> Option<DataUrl> {
+        let input = input.trim();
+        if !Self::is_data_url(input) {
+            return None;
+        }
+        let rest = &input[5..];
+        let rest = rest.split('#').next().unwrap_or(rest);
+        let (header, payload) = rest.split_once(',')?;
+
+        let mut mime_type = header.trim();
+        let mut base64 = false;
+        if let Some((before, last)) = mime_type.rsplit_once(';')
+            && last.trim().eq_ignore_ascii_case("base64")
+        {
+            mime_type = before.trim();
+            base64 = true;
+        }
+
+        let body = percent_decode(payload);
+        let body = if base64 { decode_base64(&body)? } else { body };
+
+        let mime_type = if mime_type.is_empty() {
+            "text/plain;charset=US-ASCII".to_owned()
+        } else if mime_type.starts_with(';') {
+            format!("text/plain{mime_type}")
+        } else {
+            mime_type.to_owned()
+        };
+
+        Some(DataUrl { mime_type, body })
+    }
+
+    /// The MIME type without parameters.
+    pub fn essence(&self) -> &str {
+        self.mime_type
+            .split(';')
+            .next()
+            .unwrap_or(&self.mime_type)
+            .trim()
+    }
+}
+
+/// Forgiving base64: ASCII whitespace is ignored and padding is optional.
+fn decode_base64(input: &[u8]) -> Option<Vec<u8>> {
+    let mut digits: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    if digits.len().is_multiple_of(4) {
+        for _ in 0..2 {
+            if digits.last() == Some(&b'=') {
+                digits.pop();
+            }
+        }
+    }
+    if digits.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(digits.len() / 4 * 3 + 2);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for digit in digits {
+        let value = match digit {
+            b'A'..=b'Z' => digit - b'A',
+            b'a'..=b'z' => digit - b'a' + 26,
+            b'0'..=b'9' => digit - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        acc = acc << 6 | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_base64_and_percent_encoded_payloads() {
+        let png = DataUrl::parse("data:image/png;base64,iVBORw0K GgoA").unwrap();
+        assert_eq!(png.essence(), "image/png");
+        assert_eq!(png.body, b"\x89PNG\r\n\x1a\n\0");
+
+        let css = DataUrl::parse("DATA:text/css;charset=utf-8,p%20%7Bcolor:red%7D").unwrap();
+        assert_eq!(css.essence(), "text/css");
+        assert_eq!(css.body, b"p {color:red}");
+
+        let plain = DataUrl::parse("data:,hi#frag").unwrap();
+        assert_eq!(plain.mime_type, "text/plain;charset=US-ASCII");
+        assert_eq!(plain.body, b"hi");
+
+        assert_eq!(DataUrl::parse("data:;base64,aGk=").unwrap().body, b"hi");
+        assert_eq!(DataUrl::parse("data:;base64,aGk").unwrap().body, b"hi");
+    }
+
+    #[test]
+    fn rejects_malformed_data_urls() {
+        assert!(DataUrl::parse("data:text/plain").is_none());
+        assert!(DataUrl::parse("data:;base64,a").is_none());
+        assert!(DataUrl::parse("data:;base64,a*==").is_none());
+        assert!(DataUrl::parse("http://example.com/,x").is_none());
+    }
+}
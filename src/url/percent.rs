@@ -0,0 +1,95 @@
+/// Which bytes besides C0 controls, space and non-ASCII get percent-encoded, following the
+/// WHATWG percent-encode sets for special schemes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum EncodeSet {
+    Path,
+    Query,
+    Fragment,
+}
+
+impl EncodeSet {
+    fn contains(self, byte: u8) -> bool {
+        if byte <= 0x20 || byte >= 0x7f {
+            return true;
+        }
+        match self {
+            EncodeSet::Fragment => matches!(byte, b'"' | b'<' | b'>' | b'`'),
+            EncodeSet::Query => matches!(byte, b'"' | b'#' | b'<' | b'>' | b'\''),
+            EncodeSet::Path => {
+                matches!(byte, b'"' | b'#' | b'<' | b'>' | b'?' | b'`' | b'{' | b'}')
+            }
+        }
+    }
+}
+
+/// Appends `input` to `out`, escaping the bytes in `set`. Existing `%XX` escapes are kept as-is.
+pub(super) fn encode_into(out: &mut String, input: &str, set: EncodeSet) {
+    for (index, ch) in input.char_indices() {
+        if ch.is_ascii() && !set.contains(ch as u8) {
+            out.push(ch);
+            continue;
+        }
+        for byte in input[index..index + ch.len_utf8()].bytes() {
+            push_escape(out, byte);
+        }
+    }
+}
+
+fn push_escape(out: &mut String, byte: u8) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    out.push('%');
+    out.push(char::from(HEX[usize::from(byte >> 4)]));
+    out.push(char::from(HEX[usize::from(byte & 0x0f)]));
+}
+
+/// Decodes `%XX` escapes. Malformed escapes are left in place.
+pub fn percent_decode(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hi) = bytes.get(index + 1).and_then(|b| hex_value(*b))
+            && let Some(lo) = bytes.get(index + 2).and_then(|b| hex_value(*b))
+        {
+            out.push(hi << 4 | lo);
+            index += 3;
+            continue;
+        }
+        out.push(bytes[index]);
+        index += 1;
+    }
+    out
+}
+
+/// Like [`percent_decode`], with invalid UTF-8 replaced by U+FFFD.
+pub fn percent_decode_str(input: &str) -> String {
+    String::from_utf8_lossy(&percent_decode(input)).into_owned()
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_spaces_and_non_ascii_but_keeps_escapes() {
+        let mut out = String::new();
+        encode_into(&mut out, "/a b/caf\u{e9}/%41", EncodeSet::Path);
+        assert_eq!(out, "/a%20b/caf%C3%A9/%41");
+    }
+
+    #[test]
+    fn decodes_escapes_and_leaves_malformed_ones() {
+        assert_eq!(percent_decode_str("caf%C3%A9%2"), "caf\u{e9}%2");
+        assert_eq!(percent_decode("%zz%41"), b"%zzA");
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(target_os = "linux")]
+mod inotify;
+#[cfg(target_os = "macos")]
+mod kqueue;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(target_os = "linux")]
+use inotify::DirWatcher;
+#[cfg(target_os = "macos")]
+use kqueue::DirWatcher;
+#[cfg(target_os = "windows")]
+use windows::DirWatcher;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Reports changes to a set of files. The OS notification API (inotify, kqueue or directory
+/// change notifications) only says that something happened near a watched file; each file's
+/// modification time and length are then compared with the last poll, so unrelated files in the
+/// same directory (editor swap files, for example) do not count as changes.
+pub struct FileWatcher {
+    dirs: DirWatcher,
+    stamps: HashMap<PathBuf, Option<Stamp>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileWatcher {
+    pub fn new() -> Result<Self, String> {
+        Ok(Self {
+            dirs: DirWatcher::new()?,
+            stamps: HashMap::new(),
+        })
+    }
+
+    /// Watching a path twice is a no-op. The file does not have to exist yet; its creation
+    /// counts as a change.
+    pub fn watch(&mut self, path: &Path) -> Result<(), String> {
+        let path = std::path::absolute(path)
+            .map_err(|err| format!("Failed to resolve {}: {err}", path.display()))?;
+        if self.stamps.contains_key(&path) {
+            return Ok(());
+        }
+        self.dirs.add(&path)?;
+        let stamp = stamp(&path);
+        self.stamps.insert(path, stamp);
+        Ok(())
+    }
+
+    pub fn is_watching(&self, path: &Path) -> bool {
+        std::path::absolute(path).is_ok_and(|path| self.stamps.contains_key(&path))
+    }
+
+    /// Returns whether any watched file was created, written, removed or replaced since the
+    /// previous call. Never blocks.
+    pub fn poll_changed(&mut self) -> bool {
+        if !self.dirs.poll_events() {
+            return false;
+        }
+        let mut changed = false;
+        for (path, last) in &mut self.stamps {
+            let now = stamp(path);
+            if now != *last {
+                *last = now;
+                changed = true;
+            }
+        }
+        if changed {
+            self.dirs.rearm(self.stamps.keys());
+        }
+        changed
+    }
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(Stamp {
+        modified: metadata.modified().ok(),
+        len: metadata.len(),
+    })
+}
+
+/// Without a notification API every poll re-reads the file stamps.
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+struct DirWatcher;
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+impl DirWatcher {
+    fn new() -> Result<Self, String> {
+        Ok(Self)
+    }
+
+    fn add(&mut self, _path: &Path) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn poll_events(&mut self) -> bool {
+        true
+    }
+
+    fn rearm<'a>(&mut self, _paths: impl Iterator<Item = &'a PathBuf>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_writes_to_watched_files_only() {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("one-agent-one-browser-watch-{nanos}"));
+        std::fs::create_dir_all(&dir).unwrap();
+        let page = dir.join("page.html");
+        std::fs::write(&page, "<p>one</p>").unwrap();
+
+        let mut watcher = FileWatcher::new().unwrap();
+        watcher.watch(&page).unwrap();
+        assert!(watcher.is_watching(&page));
+        assert!(!watcher.poll_changed());
+
+        std::fs::write(dir.join("page.html.swp"), "x").unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(200);
+        while std::time::Instant::now() < deadline {
+            assert!(!watcher.poll_changed());
+        }
+
+        std::fs::write(&page, "<p>one two</p>").unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+        let mut changed = false;
+        while !changed && std::time::Instant::now() < deadline {
+            changed = watcher.poll_changed();
+        }
+        assert!(changed);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,94 @@
+use core::ffi::{c_char, c_int, c_void};
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+const IN_NONBLOCK: c_int = 0o4000;
+const IN_CLOEXEC: c_int = 0o2000000;
+
+const IN_MODIFY: u32 = 0x0000_0002;
+const IN_ATTRIB: u32 = 0x0000_0004;
+const IN_CLOSE_WRITE: u32 = 0x0000_0008;
+const IN_MOVED_FROM: u32 = 0x0000_0040;
+const IN_MOVED_TO: u32 = 0x0000_0080;
+const IN_CREATE: u32 = 0x0000_0100;
+const IN_DELETE: u32 = 0x0000_0200;
+
+const DIR_MASK: u32 =
+    IN_MODIFY | IN_ATTRIB | IN_CLOSE_WRITE | IN_MOVED_FROM | IN_MOVED_TO | IN_CREATE | IN_DELETE;
+
+unsafe extern "C" {
+    fn inotify_init1(flags: c_int) -> c_int;
+    fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+    fn read(fd: c_int, buf: *mut c_void, count: usize) -> isize;
+    fn close(fd: c_int) -> c_int;
+}
+
+/// Watches the parent directories of the watched files, which keeps working when an editor
+/// saves by renaming a new file over the old one.
+pub(super) struct DirWatcher {
+    fd: c_int,
+    dirs: HashSet<PathBuf>,
+}
+
+impl DirWatcher {
+    pub(super) fn new() -> Result<Self, String> {
+        let fd = unsafe { inotify_init1(IN_NONBLOCK | IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(format!(
+                "inotify_init1 failed: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(Self {
+            fd,
+            dirs: HashSet::new(),
+        })
+    }
+
+    pub(super) fn add(&mut self, path: &Path) -> Result<(), String> {
+        let Some(dir) = path.parent() else {
+            return Ok(());
+        };
+        if self.dirs.contains(dir) {
+            return Ok(());
+        }
+        let dir_c = CString::new(dir.as_os_str().as_bytes())
+            .map_err(|_| format!("Invalid path: {}", dir.display()))?;
+        let wd = unsafe { inotify_add_watch(self.fd, dir_c.as_ptr(), DIR_MASK) };
+        if wd < 0 {
+            return Err(format!(
+                "Failed to watch {}: {}",
+                dir.display(),
+                std::io::Error::last_os_error()
+            ));
+        }
+        self.dirs.insert(dir.to_owned());
+        Ok(())
+    }
+
+    /// Drains queued events and returns whether there were any.
+    pub(super) fn poll_events(&mut self) -> bool {
+        let mut buf = [0u8; 4096];
+        let mut any = false;
+        loop {
+            let n = unsafe { read(self.fd, buf.as_mut_ptr().cast(), buf.len()) };
+            if n <= 0 {
+                return any;
+            }
+            any = true;
+        }
+    }
+
+    /// Directory watches survive changes to the files inside them.
+    pub(super) fn rearm<'a>(&mut self, _paths: impl Iterator<Item = &'a PathBuf>) {}
+}
+
+impl Drop for DirWatcher {
+    fn drop(&mut self) {
+        unsafe {
+            close(self.fd);
+        }
+    }
+}
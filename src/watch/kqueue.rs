@@ -0,0 +1,190 @@
+use core::ffi::{c_char, c_int, c_long, c_void};
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+const O_EVTONLY: c_int = 0x0000_8000;
+const O_CLOEXEC: c_int = 0x0100_0000;
+
+const EVFILT_VNODE: i16 = -4;
+const EV_ADD: u16 = 0x0001;
+const EV_CLEAR: u16 = 0x0020;
+
+const NOTE_DELETE: u32 = 0x0000_0001;
+const NOTE_WRITE: u32 = 0x0000_0002;
+const NOTE_EXTEND: u32 = 0x0000_0004;
+const NOTE_ATTRIB: u32 = 0x0000_0008;
+const NOTE_RENAME: u32 = 0x0000_0020;
+
+const VNODE_MASK: u32 = NOTE_DELETE | NOTE_WRITE | NOTE_EXTEND | NOTE_ATTRIB | NOTE_RENAME;
+
+#[repr(C)]
+struct KEvent {
+    ident: usize,
+    filter: i16,
+    flags: u16,
+    fflags: u32,
+    data: isize,
+    udata: *mut c_void,
+}
+
+#[repr(C)]
+struct Timespec {
+    tv_sec: i64,
+    tv_nsec: c_long,
+}
+
+unsafe extern "C" {
+    fn kqueue() -> c_int;
+    fn kevent(
+        kq: c_int,
+        changelist: *const KEvent,
+        nchanges: c_int,
+        eventlist: *mut KEvent,
+        nevents: c_int,
+        timeout: *const Timespec,
+    ) -> c_int;
+    fn open(path: *const c_char, oflag: c_int, ...) -> c_int;
+    fn close(fd: c_int) -> c_int;
+}
+
+/// Watches each file's vnode for in-place writes and its parent directory for files being
+/// created or renamed into place. A file's descriptor follows the old vnode after a rename, so
+/// file watches are reopened whenever a change is seen.
+pub(super) struct DirWatcher {
+    kq: c_int,
+    dirs: HashMap<PathBuf, c_int>,
+    files: HashMap<PathBuf, c_int>,
+}
+
+impl DirWatcher {
+    pub(super) fn new() -> Result<Self, String> {
+        let kq = unsafe { kqueue() };
+        if kq < 0 {
+            return Err(format!(
+                "kqueue failed: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(Self {
+            kq,
+            dirs: HashMap::new(),
+            files: HashMap::new(),
+        })
+    }
+
+    pub(super) fn add(&mut self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent()
+            && !self.dirs.contains_key(dir)
+        {
+            let fd = self.register(dir).ok_or_else(|| {
+                format!(
+                    "Failed to watch {}: {}",
+                    dir.display(),
+                    std::io::Error::last_os_error()
+                )
+            })?;
+            self.dirs.insert(dir.to_owned(), fd);
+        }
+        if let Some(fd) = self.register(path) {
+            self.files.insert(path.to_owned(), fd);
+        }
+        Ok(())
+    }
+
+    /// Drains queued events and returns whether there were any.
+    pub(super) fn poll_events(&mut self) -> bool {
+        let timeout = Timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let mut events: [KEvent; 16] = std::array::from_fn(|_| empty_event());
+        let mut any = false;
+        loop {
+            let n = unsafe {
+                kevent(
+                    self.kq,
+                    std::ptr::null(),
+                    0,
+                    events.as_mut_ptr(),
+                    events.len() as c_int,
+                    &timeout,
+                )
+            };
+            if n <= 0 {
+                return any;
+            }
+            any = true;
+        }
+    }
+
+    pub(super) fn rearm<'a>(&mut self, paths: impl Iterator<Item = &'a PathBuf>) {
+        for path in paths {
+            if let Some(fd) = self.files.remove(path) {
+                unsafe {
+                    close(fd);
+                }
+            }
+            if let Some(fd) = self.register(path) {
+                self.files.insert(path.clone(), fd);
+            }
+        }
+    }
+
+    fn register(&self, path: &Path) -> Option<c_int> {
+        let path_c = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let fd = unsafe { open(path_c.as_ptr(), O_EVTONLY | O_CLOEXEC) };
+        if fd < 0 {
+            return None;
+        }
+        let change = KEvent {
+            ident: fd as usize,
+            filter: EVFILT_VNODE,
+            flags: EV_ADD | EV_CLEAR,
+            fflags: VNODE_MASK,
+            ..empty_event()
+        };
+        let status = unsafe {
+            kevent(
+                self.kq,
+                &change,
+                1,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null(),
+            )
+        };
+        if status < 0 {
+            unsafe {
+                close(fd);
+            }
+            return None;
+        }
+        Some(fd)
+    }
+}
+
+fn empty_event() -> KEvent {
+    KEvent {
+        ident: 0,
+        filter: 0,
+        flags: 0,
+        fflags: 0,
+        data: 0,
+        udata: std::ptr::null_mut(),
+    }
+}
+
+impl Drop for DirWatcher {
+    fn drop(&mut self) {
+        for fd in self.files.values().chain(self.dirs.values()) {
+            unsafe {
+                close(*fd);
+            }
+        }
+        unsafe {
+            close(self.kq);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use core::ffi::c_void;
+use std::collections::HashMap;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+type Bool = i32;
+type DWORD = u32;
+type Handle = *mut c_void;
+
+const INVALID_HANDLE_VALUE: Handle = -1isize as Handle;
+const WAIT_OBJECT_0: DWORD = 0;
+
+const FILE_NOTIFY_CHANGE_FILE_NAME: DWORD = 0x0000_0001;
+const FILE_NOTIFY_CHANGE_SIZE: DWORD = 0x0000_0008;
+const FILE_NOTIFY_CHANGE_LAST_WRITE: DWORD = 0x0000_0010;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn FindFirstChangeNotificationW(path: *const u16, watch_subtree: Bool, filter: DWORD)
+    -> Handle;
+    fn FindNextChangeNotification(handle: Handle) -> Bool;
+    fn FindCloseChangeNotification(handle: Handle) -> Bool;
+    fn WaitForSingleObject(handle: Handle, milliseconds: DWORD) -> DWORD;
+}
+
+/// One change notification handle per parent directory of a watched file.
+pub(super) struct DirWatcher {
+    dirs: HashMap<PathBuf, Handle>,
+}
+
+impl DirWatcher {
+    pub(super) fn new() -> Result<Self, String> {
+        Ok(Self {
+            dirs: HashMap::new(),
+        })
+    }
+
+    pub(super) fn add(&mut self, path: &Path) -> Result<(), String> {
+        let Some(dir) = path.parent() else {
+            return Ok(());
+        };
+        if self.dirs.contains_key(dir) {
+            return Ok(());
+        }
+        let dir_w: Vec<u16> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+        let handle = unsafe {
+            FindFirstChangeNotificationW(
+                dir_w.as_ptr(),
+                0,
+                FILE_NOTIFY_CHANGE_FILE_NAME
+                    | FILE_NOTIFY_CHANGE_SIZE
+                    | FILE_NOTIFY_CHANGE_LAST_WRITE,
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(format!(
+                "Failed to watch {}: {}",
+                dir.display(),
+                std::io::Error::last_os_error()
+            ));
+        }
+        self.dirs.insert(dir.to_owned(), handle);
+        Ok(())
+    }
+
+    /// Returns whether any directory was signalled, re-arming the signalled handles.
+    pub(super) fn poll_events(&mut self) -> bool {
+        let mut any = false;
+        for handle in self.dirs.values() {
+            if unsafe { WaitForSingleObject(*handle, 0) } == WAIT_OBJECT_0 {
+                any = true;
+                unsafe {
+                    FindNextChangeNotification(*handle);
+                }
+            }
+        }
+        any
+    }
+
+    /// Directory handles survive changes to the files inside them.
+    pub(super) fn rearm<'a>(&mut self, _paths: impl Iterator<Item = &'a PathBuf>) {}
+}
+
+impl Drop for DirWatcher {
+    fn drop(&mut self) {
+        for handle in self.dirs.values() {
+            unsafe {
+                FindCloseChangeNotification(*handle);
+            }
+        }
+    }
+}
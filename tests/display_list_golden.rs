@@ -0,0 +1,156 @@
+use one_agent_one_browser::browser::BrowserApp;
+use one_agent_one_browser::geom::Color;
+use one_agent_one_browser::image::Argb32Image;
+use one_agent_one_browser::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use std::path::{Path, PathBuf};
+
+/// Set to rewrite each golden file from the current output instead of comparing against it.
+const UPDATE_ENV: &str = "OAB_UPDATE_GOLDEN";
+
+#[test]
+fn display_lists_match_golden_files() {
+    let cases_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("display-lists");
+    let mut cases: Vec<PathBuf> = std::fs::read_dir(&cases_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "html"))
+        .collect();
+    cases.sort();
+    assert!(!cases.is_empty());
+
+    let update = std::env::var_os(UPDATE_ENV).is_some();
+    let mut failures = Vec::new();
+    for html_path in &cases {
+        let actual = render_display_list(html_path);
+        let golden_path = html_path.with_extension("txt");
+        if update {
+            std::fs::write(&golden_path, &actual).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&golden_path).unwrap_or_else(|err| {
+            panic!(
+                "{}: {err} (run with {UPDATE_ENV}=1 to create it)",
+                golden_path.display()
+            )
+        });
+        if actual != expected {
+            failures.push(format!(
+                "{} differs from {}:\n{actual}",
+                html_path.display(),
+                golden_path.display()
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{}\n(run with {UPDATE_ENV}=1 to accept the new output)",
+        failures.join("\n")
+    );
+}
+
+fn render_display_list(html_path: &Path) -> String {
+    let html = std::fs::read_to_string(html_path).unwrap();
+    let mut app = BrowserApp::from_html("golden", &html).unwrap();
+    let viewport = Viewport {
+        width_px: 400,
+        height_px: 300,
+    };
+    app.render(&mut FixedAdvancePainter, viewport).unwrap();
+    app.display_list().unwrap().to_text()
+}
+
+/// Measures text with a fixed advance of half the font size, so the output does not depend on
+/// installed fonts, and draws nothing.
+struct FixedAdvancePainter;
+
+impl TextMeasurer for FixedAdvancePainter {
+    fn font_metrics_px(&self, style: TextStyle) -> FontMetricsPx {
+        FontMetricsPx {
+            ascent_px: style.font_size_px * 4 / 5,
+            descent_px: style.font_size_px / 5,
+        }
+    }
+
+    fn text_width_px(&self, text: &str, style: TextStyle) -> Result<i32, String> {
+        let advance = style.font_size_px / 2 + style.letter_spacing_px;
+        Ok(text.chars().count() as i32 * advance)
+    }
+}
+
+impl Painter for FixedAdvancePainter {
+    fn clear(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn push_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn pop_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fill_rounded_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _radius_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn stroke_rounded_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _radius_px: i32,
+        _border_width_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _text: &str,
+        _style: TextStyle,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_image(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _image: &Argb32Image,
+        _opacity: u8,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
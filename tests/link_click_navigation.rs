@@ -1,4 +1,4 @@
-use one_agent_one_browser::app::App;
+use one_agent_one_browser::app::{App, Key, KeyInput};
 use one_agent_one_browser::browser::BrowserApp;
 use one_agent_one_browser::geom::Color;
 use one_agent_one_browser::image::Argb32Image;
@@ -171,6 +171,56 @@ fn mouse_back_navigates_to_previous_page() {
     let _ = std::fs::remove_dir(&root);
 }
 
+#[test]
+fn target_blank_link_opens_in_new_tab() {
+    let root = std::env::temp_dir().join(format!("one-agent-one-browser-new-tab-{}", unique_id()));
+    std::fs::create_dir_all(&root).unwrap();
+
+    let page1 = root.join("page1.html");
+    let page2 = root.join("page2.html");
+
+    std::fs::write(
+        &page1,
+        r#"<p><a href="page2.html" target="_blank">Go</a></p>"#,
+    )
+    .unwrap();
+    std::fs::write(&page2, "<p>Page 2</p>").unwrap();
+
+    let mut app = BrowserApp::from_file(&page1).unwrap();
+    let viewport = Viewport {
+        width_px: 200,
+        height_px: 200,
+    };
+
+    let mut painter = NoopPainter;
+    app.render(&mut painter, viewport).unwrap();
+
+    let click = app.mouse_down(0, 0, viewport).unwrap();
+    assert!(click.needs_redraw);
+    assert_eq!(app.tab_count(), 2);
+    assert_eq!(app.active_tab(), 1);
+    assert_eq!(app.title(), "page2.html");
+    assert!(!app.can_go_back());
+
+    let ctrl = |key| KeyInput {
+        key,
+        ctrl: true,
+        alt: false,
+    };
+    app.key_down(ctrl(Key::Tab), viewport).unwrap();
+    assert_eq!(app.active_tab(), 0);
+    assert_eq!(app.title(), "page1.html");
+
+    app.key_down(ctrl(Key::Tab), viewport).unwrap();
+    app.key_down(ctrl(Key::Char('w')), viewport).unwrap();
+    assert_eq!(app.tab_count(), 1);
+    assert_eq!(app.title(), "page1.html");
+
+    let _ = std::fs::remove_file(&page1);
+    let _ = std::fs::remove_file(&page2);
+    let _ = std::fs::remove_dir(&root);
+}
+
 #[test]
 fn scrolling_offsets_click_hit_testing() {
     let root = std::env::temp_dir().join(format!(
@@ -219,6 +269,64 @@ fn scrolling_offsets_click_hit_testing() {
     let _ = std::fs::remove_dir(&root);
 }
 
+#[test]
+fn back_restores_scroll_and_forward_returns() {
+    let root = std::env::temp_dir().join(format!(
+        "one-agent-one-browser-back-forward-{}",
+        unique_id()
+    ));
+    std::fs::create_dir_all(&root).unwrap();
+
+    let page1 = root.join("page1.html");
+    let page2 = root.join("page2.html");
+
+    std::fs::write(
+        &page1,
+        r#"
+<style>body { margin: 0; } p { margin: 0; }</style>
+<div style="height: 400px"></div>
+<p><a href="page2.html">Go</a></p>
+<div style="height: 400px"></div>
+"#,
+    )
+    .unwrap();
+    std::fs::write(&page2, "<p>Page 2</p>").unwrap();
+
+    let mut app = BrowserApp::from_file(&page1).unwrap();
+    let viewport = Viewport {
+        width_px: 200,
+        height_px: 100,
+    };
+
+    let mut painter = NoopPainter;
+    app.render(&mut painter, viewport).unwrap();
+    app.mouse_wheel(400, viewport).unwrap();
+    app.mouse_down(0, 0, viewport).unwrap();
+    assert_eq!(app.title(), "page2.html");
+    assert!(!app.can_go_forward());
+
+    let back = app.navigate_back().unwrap();
+    assert!(back.needs_redraw);
+    assert_eq!(app.title(), "page1.html");
+    assert!(app.can_go_forward());
+
+    // The link is only under the pointer if the scroll offset came back with the page.
+    app.render(&mut painter, viewport).unwrap();
+    let click = app.mouse_down(0, 0, viewport).unwrap();
+    assert!(click.needs_redraw);
+    assert_eq!(app.title(), "page2.html");
+    assert!(!app.can_go_forward());
+
+    app.navigate_back().unwrap();
+    let forward = app.navigate_forward().unwrap();
+    assert!(forward.needs_redraw);
+    assert_eq!(app.title(), "page2.html");
+
+    let _ = std::fs::remove_file(&page1);
+    let _ = std::fs::remove_file(&page2);
+    let _ = std::fs::remove_dir(&root);
+}
+
 #[test]
 fn fixed_link_hit_testing_does_not_use_scroll_offset() {
     let root = std::env::temp_dir().join(format!(
@@ -282,6 +390,34 @@ fn fixed_link_hit_testing_does_not_use_scroll_offset() {
     let _ = std::fs::remove_dir(&root);
 }
 
+#[test]
+fn clicks_link_to_directory_shows_listing() {
+    let root =
+        std::env::temp_dir().join(format!("one-agent-one-browser-dir-listing-{}", unique_id()));
+    let docs = root.join("my docs");
+    std::fs::create_dir_all(&docs).unwrap();
+
+    let page1 = root.join("page1.html");
+    std::fs::write(&page1, r#"<p><a href="my%20docs/">Docs</a></p>"#).unwrap();
+    std::fs::write(docs.join("notes.html"), "<p>Notes</p>").unwrap();
+
+    let mut app = BrowserApp::from_file(&page1).unwrap();
+    let viewport = Viewport {
+        width_px: 200,
+        height_px: 200,
+    };
+
+    let mut painter = NoopPainter;
+    app.render(&mut painter, viewport).unwrap();
+
+    let click = app.mouse_down(0, 0, viewport).unwrap();
+    assert!(click.needs_redraw);
+    assert!(app.title().starts_with("Index of "), "{}", app.title());
+    assert!(app.title().ends_with("my docs/"), "{}", app.title());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
 fn unique_id() -> u128 {
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -0,0 +1,143 @@
+use one_agent_one_browser::browser::BrowserApp;
+use one_agent_one_browser::geom::Color;
+use one_agent_one_browser::image::Argb32Image;
+use one_agent_one_browser::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use std::time::{Duration, Instant};
+
+struct NoopPainter;
+
+impl TextMeasurer for NoopPainter {
+    fn font_metrics_px(&self, _style: TextStyle) -> FontMetricsPx {
+        FontMetricsPx {
+            ascent_px: 8,
+            descent_px: 2,
+        }
+    }
+
+    fn text_width_px(&self, text: &str, _style: TextStyle) -> Result<i32, String> {
+        Ok(text.len() as i32)
+    }
+}
+
+impl Painter for NoopPainter {
+    fn clear(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn push_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn pop_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fill_rounded_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _radius_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn stroke_rounded_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _radius_px: i32,
+        _border_width_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _text: &str,
+        _style: TextStyle,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_image(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _image: &Argb32Image,
+        _opacity: u8,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_svg(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _svg_xml: &str,
+        _opacity: u8,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[test]
+fn watched_file_reloads_after_it_changes_on_disk() {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let root = std::env::temp_dir().join(format!("one-agent-one-browser-live-reload-{nanos}"));
+    std::fs::create_dir_all(&root).unwrap();
+    let page = root.join("page.html");
+    std::fs::write(&page, "<h1>One</h1>").unwrap();
+
+    let mut app = BrowserApp::from_file(&page).unwrap();
+    app.set_watch_files(true);
+    let viewport = Viewport {
+        width_px: 200,
+        height_px: 200,
+    };
+    let mut painter = NoopPainter;
+    app.tick().unwrap();
+    app.render(&mut painter, viewport).unwrap();
+    assert_eq!(app.outline()[0].text, "One");
+
+    std::fs::write(&page, "<h1>Two</h1>").unwrap();
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while app.outline()[0].text != "Two" && Instant::now() < deadline {
+        if app.tick().unwrap().needs_redraw {
+            app.render(&mut painter, viewport).unwrap();
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(app.outline()[0].text, "Two");
+
+    let _ = std::fs::remove_dir_all(&root);
+}
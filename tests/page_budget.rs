@@ -0,0 +1,178 @@
+mod support;
+
+use one_agent_one_browser::browser::BrowserApp;
+use one_agent_one_browser::budget::Budget;
+use one_agent_one_browser::geom::Color;
+use one_agent_one_browser::image::Argb32Image;
+use one_agent_one_browser::render::{FontMetricsPx, Painter, TextMeasurer, TextStyle, Viewport};
+use std::time::Duration;
+use support::http::{HttpTestServer, Route};
+
+struct NoopPainter;
+
+impl TextMeasurer for NoopPainter {
+    fn font_metrics_px(&self, _style: TextStyle) -> FontMetricsPx {
+        FontMetricsPx {
+            ascent_px: 8,
+            descent_px: 2,
+        }
+    }
+
+    fn text_width_px(&self, text: &str, _style: TextStyle) -> Result<i32, String> {
+        Ok(text.len() as i32)
+    }
+}
+
+impl Painter for NoopPainter {
+    fn clear(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn push_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn pop_opacity(&mut self, _opacity: u8) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fill_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn fill_rounded_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _radius_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn stroke_rounded_rect(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _radius_px: i32,
+        _border_width_px: i32,
+        _color: Color,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_text(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _text: &str,
+        _style: TextStyle,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_image(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _image: &Argb32Image,
+        _opacity: u8,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn draw_svg(
+        &mut self,
+        _x_px: i32,
+        _y_px: i32,
+        _width_px: i32,
+        _height_px: i32,
+        _svg_xml: &str,
+        _opacity: u8,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[test]
+fn page_metrics_count_fetched_bytes_and_feed_the_budget() {
+    let html = br#"<!doctype html><link rel="stylesheet" href="/a.css"><p>hello</p>"#.to_vec();
+    let css = b"p { color: #ff0000; }".to_vec();
+    let expected_bytes = (html.len() + css.len()) as u64;
+
+    let server = HttpTestServer::new(vec![
+        Route {
+            path: "/index.html".to_owned(),
+            status: 200,
+            content_type: "text/html; charset=utf-8".to_owned(),
+            body: html,
+            delay: Duration::ZERO,
+        },
+        Route {
+            path: "/a.css".to_owned(),
+            status: 200,
+            content_type: "text/css; charset=utf-8".to_owned(),
+            body: css,
+            delay: Duration::from_millis(20),
+        },
+    ]);
+
+    let mut app = BrowserApp::from_url(&server.url("/index.html")).unwrap();
+    let viewport = Viewport {
+        width_px: 240,
+        height_px: 120,
+    };
+
+    wait_until(Duration::from_secs(2), || {
+        let tick = app.tick().unwrap();
+        tick.ready_for_screenshot && tick.pending_resources == 0
+    });
+    app.render(&mut NoopPainter, viewport).unwrap();
+
+    let metrics = app.page_metrics();
+    assert_eq!(metrics.bytes, expected_bytes);
+    assert!(metrics.load_time >= Duration::from_millis(20));
+    std::thread::sleep(Duration::from_millis(20));
+    assert_eq!(
+        app.page_metrics().load_time,
+        metrics.load_time,
+        "load time stops once the page has loaded"
+    );
+
+    let within = Budget::parse(&format!("bytes={expected_bytes}")).unwrap();
+    assert!(!within.check(&metrics).exceeded());
+    let over = Budget::parse("bytes=10").unwrap().check(&metrics);
+    assert!(over.exceeded());
+    assert!(over.to_json().contains("\"metric\":\"bytes\""));
+
+    assert_eq!(server.requests_for_path("/a.css"), 1);
+    server.shutdown();
+}
+
+fn wait_until(timeout: Duration, mut predicate: impl FnMut() -> bool) {
+    let started = std::time::Instant::now();
+    while started.elapsed() < timeout {
+        if predicate() {
+            return;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    panic!("timeout after {timeout:?}");
+}
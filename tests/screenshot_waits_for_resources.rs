@@ -62,6 +62,58 @@ fn screenshot_waits_for_async_images() {
     server.shutdown();
 }
 
+#[test]
+fn screenshot_dash_writes_png_to_stdout() {
+    let html = br#"<!doctype html>
+<style>
+  body { margin: 0; background: #00ff00; }
+</style>
+<p>stdout</p>
+"#
+    .to_vec();
+
+    let server = HttpTestServer::new(vec![Route {
+        path: "/index.html".to_owned(),
+        status: 200,
+        content_type: "text/html; charset=utf-8".to_owned(),
+        body: html,
+        delay: Duration::ZERO,
+    }]);
+
+    let browser_exe = PathBuf::from(env!("CARGO_BIN_EXE_one-agent-one-browser"));
+    let mut cmd = Command::new(&browser_exe);
+    cmd.env("OAB_SCALE", "1")
+        .env("OAB_LOG", "all")
+        .arg("--headless")
+        .arg("--width=32")
+        .arg("--height=32")
+        .arg(server.url("/index.html"))
+        .arg("--screenshot")
+        .arg("-")
+        .stderr(Stdio::null());
+    #[cfg(target_os = "linux")]
+    cmd.env(
+        "OAB_LINUX_BACKEND",
+        std::env::var("OAB_TEST_LINUX_BACKEND").unwrap_or_else(|_| "x11".to_owned()),
+    );
+    let output = cmd
+        .output()
+        .unwrap_or_else(|err| panic!("Failed to start {}: {err}", browser_exe.display()));
+    assert!(output.status.success(), "Browser process failed");
+
+    let screenshot_path = unique_temp_file_path("screenshot_dash_writes_png_to_stdout", "png");
+    std::fs::write(&screenshot_path, &output.stdout).unwrap();
+    let pixel = read_rgb_png_pixel(&screenshot_path, 30, 30).unwrap();
+    assert!(
+        pixel[0] < 80 && pixel[1] > 200 && pixel[2] < 80,
+        "expected the green page background, got rgb={:?}",
+        pixel
+    );
+
+    let _ = std::fs::remove_file(&screenshot_path);
+    server.shutdown();
+}
+
 fn run_browser_screenshot(
     browser_exe: &Path,
     url: &str,